@@ -0,0 +1,81 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Curve construction and discounting benchmarks.
+//!
+//! The crate has no bootstrapper yet, so curve building is measured as
+//! construction from market tenors followed by the discount factor
+//! lookups a bootstrap (or any cash flow pricer) performs.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use time::macros::datetime;
+use time::{Duration, OffsetDateTime};
+use RustQuant::curves::{Curve, YieldCurve};
+use RustQuant::math::interpolation::InterpolationScheme;
+use RustQuant::time::Tenor;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+const TODAY: OffsetDateTime = datetime!(2024-01-02 0:00 UTC);
+
+const TENORS: [&str; 12] = [
+    "1M", "3M", "6M", "1Y", "2Y", "3Y", "5Y", "7Y", "10Y", "15Y", "20Y", "30Y",
+];
+const RATES: [f64; 12] = [
+    0.053, 0.052, 0.051, 0.049, 0.046, 0.044, 0.042, 0.042, 0.043, 0.044, 0.045, 0.045,
+];
+
+fn tenors() -> Vec<Tenor> {
+    TENORS.iter().map(|tenor| tenor.parse().unwrap()).collect()
+}
+
+fn curve_construction(c: &mut Criterion) {
+    let tenors = tenors();
+
+    c.bench_function("curve_construction/from_tenors_and_rates", |b| {
+        b.iter(|| YieldCurve::from_tenors_and_rates(TODAY, black_box(&tenors), &RATES))
+    });
+}
+
+fn discounting(c: &mut Criterion) {
+    let tenors = tenors();
+    let schemes = [
+        ("linear", InterpolationScheme::Linear),
+        (
+            "natural_cubic_spline",
+            InterpolationScheme::NaturalCubicSpline,
+        ),
+        ("pchip", InterpolationScheme::Pchip),
+        ("akima", InterpolationScheme::Akima),
+    ];
+
+    // Monthly payment dates within the curve (1M to 30Y).
+    let dates = (2..360)
+        .map(|month| TODAY + Duration::days(month * 365 / 12))
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("discount_factors");
+    group.throughput(Throughput::Elements(dates.len() as u64));
+
+    for (name, scheme) in schemes {
+        let curve =
+            YieldCurve::from_tenors_and_rates(TODAY, &tenors, &RATES).with_interpolation(scheme);
+
+        group.bench_with_input(BenchmarkId::from_parameter(name), &curve, |b, curve| {
+            b.iter(|| curve.discount_factors(black_box(&dates)))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, curve_construction, discounting);
+criterion_main!(benches);
@@ -0,0 +1,89 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Closed-form pricing throughput benchmarks.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use time::macros::datetime;
+use time::{Duration, OffsetDateTime};
+use RustQuant::curves::{Curve, YieldCurve};
+use RustQuant::instruments::options::TypeFlag;
+use RustQuant::instruments::*;
+use RustQuant::time::PaymentFrequency;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+const TODAY: OffsetDateTime = datetime!(2024-01-02 0:00 UTC);
+
+fn black_scholes_merton(c: &mut Criterion) {
+    let options = (0..1_000)
+        .map(|i| {
+            BlackScholesMerton::new(
+                0.05,
+                100.0,
+                50.0 + 0.1 * i as f64,
+                0.2,
+                0.05,
+                Some(TODAY),
+                TODAY + Duration::days(365),
+                TypeFlag::Call,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("black_scholes_merton");
+    group.throughput(Throughput::Elements(options.len() as u64));
+
+    group.bench_function("price", |b| {
+        b.iter(|| {
+            options
+                .iter()
+                .map(|option| black_box(option).price())
+                .sum::<f64>()
+        })
+    });
+    group.bench_function("delta", |b| {
+        b.iter(|| {
+            options
+                .iter()
+                .map(|option| black_box(option).delta())
+                .sum::<f64>()
+        })
+    });
+
+    group.finish();
+}
+
+fn coupon_bond(c: &mut Criterion) {
+    let curve =
+        YieldCurve::from_dates_and_rates(&[TODAY, TODAY + Duration::days(3650)], &[0.03, 0.05]);
+
+    let bond = CouponBond::builder()
+        .evaluation_date(TODAY)
+        .expiration_date(TODAY + Duration::days(365 * 10))
+        .coupon_rate(0.04)
+        .coupon_frequency(PaymentFrequency::SemiAnnually)
+        .yield_curve(curve)
+        .build()
+        .unwrap();
+
+    let mut group = c.benchmark_group("coupon_bond");
+
+    group.bench_function("price", |b| b.iter(|| black_box(&bond).price()));
+    group.bench_function("yield_to_maturity", |b| {
+        b.iter(|| bond.yield_to_maturity(black_box(95.0)))
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, black_scholes_merton, coupon_bond);
+criterion_main!(benches);
@@ -0,0 +1,68 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Path generation and fractional Gaussian noise benchmarks.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rand::{rngs::StdRng, SeedableRng};
+use RustQuant::stochastics::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+const N_STEPS: usize = 252;
+const M_PATHS: usize = 1_000;
+
+fn path_generation(c: &mut Criterion) {
+    let gbm = GeometricBrownianMotion::new(0.05, 0.2);
+    let ou = OrnsteinUhlenbeck::new(0.05, 0.01, 0.5);
+
+    let mut group = c.benchmark_group("path_generation");
+    group.throughput(Throughput::Elements((N_STEPS * M_PATHS) as u64));
+
+    for parallel in [false, true] {
+        group.bench_with_input(BenchmarkId::new("gbm", parallel), &parallel, |b, &p| {
+            b.iter(|| gbm.euler_maruyama(100.0, 0.0, 1.0, N_STEPS, M_PATHS, black_box(p)))
+        });
+        group.bench_with_input(BenchmarkId::new("ou", parallel), &parallel, |b, &p| {
+            b.iter(|| ou.euler_maruyama(0.05, 0.0, 1.0, N_STEPS, M_PATHS, black_box(p)))
+        });
+    }
+
+    group.finish();
+}
+
+fn fractional_gaussian_noise(c: &mut Criterion) {
+    let methods = [
+        ("cholesky", FractionalNoiseMethod::Cholesky),
+        ("hosking", FractionalNoiseMethod::Hosking),
+        ("davies_harte", FractionalNoiseMethod::DaviesHarte),
+    ];
+
+    let mut group = c.benchmark_group("fractional_gaussian_noise");
+
+    for n in [256, 1024] {
+        group.throughput(Throughput::Elements(n as u64));
+
+        for (name, method) in methods {
+            let fgn = FractionalGaussianNoise::new(0.7, n, method);
+            let mut rng = StdRng::seed_from_u64(42);
+
+            group.bench_with_input(BenchmarkId::new(name, n), &n, |b, &n| {
+                b.iter(|| fgn.sample(black_box(1.0 / n as f64), &mut rng))
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, path_generation, fractional_gaussian_noise);
+criterion_main!(benches);
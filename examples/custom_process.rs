@@ -41,7 +41,7 @@ fn main() {
 
     // Generate a path and plot it.
     let output = custom_process.euler_maruyama(0.01, 0.0, 10.0, 500, 1, false);
-    plot_vector!(output.paths[0], "./images/ricker_wavelet_process.png");
+    plot_vector!(output.path(0), "./images/ricker_wavelet_process.png");
 }
 
 // Your custom stochastic process parameters.
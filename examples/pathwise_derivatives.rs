@@ -47,6 +47,7 @@ fn main() {
     // }
 }
 
+#[allow(unused_variables, unused_assignments)]
 fn test_basket_option(s: f64, k: f64, t: f64, r: f64, v: f64, n_sims: usize, n_assets: usize) {
     let mut basket_option = BasketOption::new();
 
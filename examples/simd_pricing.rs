@@ -0,0 +1,53 @@
+// This example compares the performance of pricing a large batch of
+// Black-Scholes-Merton options one at a time with the scalar pricer, to
+// pricing them with the SIMD batch pricer (four options per instruction).
+//
+// Run:  cargo run --release --features simd --example simd_pricing
+
+use std::time::Instant;
+use RustQuant::instruments::options::*;
+
+fn main() {
+    let n = 1_000_000;
+
+    let inputs: Vec<BlackScholesInputs> = (0..n)
+        .map(|i| BlackScholesInputs {
+            underlying_price: 50.0 + (i % 100) as f64,
+            strike_price: 100.0,
+            volatility: 0.1 + 0.001 * (i % 300) as f64,
+            risk_free_rate: 0.05,
+            cost_of_carry: 0.03,
+            time_to_expiry: 0.1 + 0.01 * (i % 200) as f64,
+            option_type: if i % 2 == 0 {
+                TypeFlag::Call
+            } else {
+                TypeFlag::Put
+            },
+        })
+        .collect();
+
+    // Scalar pricing.
+    let start = Instant::now();
+    let scalar: Vec<f64> = inputs.iter().map(|option| option.price()).collect();
+    let scalar_time = start.elapsed();
+
+    // SIMD batch pricing.
+    let start = Instant::now();
+    let batch = bs_price_batch(&inputs);
+    let batch_time = start.elapsed();
+
+    let max_error = scalar
+        .iter()
+        .zip(&batch)
+        .map(|(a, b)| (a - b).abs())
+        .fold(0.0, f64::max);
+
+    println!("Options priced:   {}", n);
+    println!("Scalar:           {:?}", scalar_time);
+    println!("SIMD batch:       {:?}", batch_time);
+    println!(
+        "Speedup:          {:.2}x",
+        scalar_time.as_secs_f64() / batch_time.as_secs_f64()
+    );
+    println!("Max abs. error:   {:e}", max_error);
+}
@@ -0,0 +1,285 @@
+/* RustQuant C API. Dual licensed under Apache 2.0 and MIT. */
+
+#ifndef RUSTQUANT_H
+#define RUSTQUANT_H
+
+/* Generated with cbindgen from src/ffi. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/*
+ * `option_type` of a call.
+ */
+#define RQ_CALL 1
+
+/*
+ * `option_type` of a put.
+ */
+#define RQ_PUT -1
+
+/*
+ * Status code returned by every function of the C API.
+ */
+typedef enum RqStatus {
+  /*
+   * Success.
+   */
+  RQ_STATUS_OK = 0,
+  /*
+   * A required pointer argument is null.
+   */
+  RQ_STATUS_NULL_POINTER = 1,
+  /*
+   * An argument is out of its domain (e.g. a negative volatility or a
+   * maturity beyond the curve).
+   */
+  RQ_STATUS_INVALID_ARGUMENT = 2,
+  /*
+   * An iterative solver did not converge.
+   */
+  RQ_STATUS_NO_CONVERGENCE = 3,
+  /*
+   * The library panicked (a bug).
+   */
+  RQ_STATUS_PANIC = 4,
+} RqStatus;
+
+/*
+ * Opaque yield curve handle: continuously compounded zero rates at
+ * maturities in years from the curve's creation, linearly interpolated.
+ */
+typedef struct RqYieldCurve RqYieldCurve;
+
+/*
+ * A fixed-coupon bullet bond.
+ *
+ * Coupons are paid every `1 / frequency` years, counted back from the
+ * maturity, so the first coupon may come after less than a full period.
+ * Prices are dirty (they include the accrued interest). A frequency of
+ * zero is a zero-coupon bond.
+ */
+typedef struct RqBond {
+  /*
+   * Face value, paid at maturity.
+   */
+  double face_value;
+  /*
+   * Annual coupon rate (e.g. 0.05 for 5%).
+   */
+  double coupon_rate;
+  /*
+   * Time to maturity in years, positive.
+   */
+  double maturity;
+  /*
+   * Coupons per year (e.g. 2 for semi-annual), or zero.
+   */
+  uint32_t frequency;
+} RqBond;
+
+/*
+ * Inputs of a generalised Black-Scholes-Merton option.
+ */
+typedef struct RqBlackScholesInputs {
+  /*
+   * Underlying price, positive.
+   */
+  double underlying_price;
+  /*
+   * Strike price, positive.
+   */
+  double strike_price;
+  /*
+   * Volatility, positive.
+   */
+  double volatility;
+  /*
+   * Continuously compounded risk-free rate.
+   */
+  double risk_free_rate;
+  /*
+   * Cost of carry (the risk-free rate for a non-dividend-paying stock).
+   */
+  double cost_of_carry;
+  /*
+   * Time to expiry in years, positive.
+   */
+  double time_to_expiry;
+  /*
+   * `RQ_CALL` or `RQ_PUT`.
+   */
+  int32_t option_type;
+} RqBlackScholesInputs;
+
+/*
+ * Monte Carlo price estimate.
+ */
+typedef struct RqMonteCarloResult {
+  /*
+   * Mean discounted payoff.
+   */
+  double price;
+  /*
+   * Standard error of the mean.
+   */
+  double standard_error;
+} RqMonteCarloResult;
+
+/*
+ * Price and Greeks of a Black-Scholes-Merton option.
+ */
+typedef struct RqGreeks {
+  /*
+   * Price.
+   */
+  double price;
+  /*
+   * Sensitivity to the underlying price.
+   */
+  double delta;
+  /*
+   * Sensitivity of the delta to the underlying price.
+   */
+  double gamma;
+  /*
+   * Sensitivity to the volatility.
+   */
+  double vega;
+  /*
+   * Sensitivity to the passage of time.
+   */
+  double theta;
+  /*
+   * Sensitivity to the risk-free rate.
+   */
+  double rho;
+} RqGreeks;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/*
+ * Bond price, discounting the cash flows on the curve (which must cover
+ * the maturity).
+ */
+RqStatus rq_bond_price(const RqBond *bond, const RqYieldCurve *curve, double *price);
+
+/*
+ * Bond price at a yield compounded at the coupon frequency.
+ */
+RqStatus rq_bond_price_from_yield(const RqBond *bond, double yield_rate, double *price);
+
+/*
+ * Yield to maturity (compounded at the coupon frequency) of a dirty
+ * price, in $[-50\%, 100\%]$.
+ */
+RqStatus rq_bond_yield_to_maturity(const RqBond *bond, double price, double *yield_rate);
+
+/*
+ * Creates a yield curve from `n` continuously compounded zero `rates` at
+ * `maturities` (in years, non-negative). Release it with
+ * `rq_yield_curve_free`.
+ */
+RqStatus rq_yield_curve_new(const double *maturities,
+                            const double *rates,
+                            size_t n,
+                            RqYieldCurve **curve);
+
+/*
+ * Releases a yield curve (null is ignored).
+ */
+void rq_yield_curve_free(RqYieldCurve *curve);
+
+/*
+ * Zero rate at the maturity (in years, within the curve).
+ */
+RqStatus rq_yield_curve_rate(const RqYieldCurve *curve, double maturity, double *rate);
+
+/*
+ * Discount factor at the maturity (in years, within the curve).
+ */
+RqStatus rq_yield_curve_discount_factor(const RqYieldCurve *curve,
+                                        double maturity,
+                                        double *discount_factor);
+
+/*
+ * Discount factors at `n` maturities.
+ */
+RqStatus rq_yield_curve_discount_factors(const RqYieldCurve *curve,
+                                         const double *maturities,
+                                         size_t n,
+                                         double *discount_factors);
+
+/*
+ * Version of the library, as a static NUL-terminated string.
+ */
+const char *rq_version(void);
+
+/*
+ * Description of a status code, as a static NUL-terminated string.
+ */
+const char *rq_status_message(int32_t status);
+
+/*
+ * Simulates `n_paths` geometric Brownian motion paths (Euler-Maruyama,
+ * `n_steps` steps from 0 to `t`), written row by row to `paths`, which
+ * must hold `n_paths * (n_steps + 1)` values. The same seed gives the
+ * same paths.
+ */
+RqStatus rq_simulate_gbm(double s0,
+                         double mu,
+                         double sigma,
+                         double t,
+                         size_t n_steps,
+                         size_t n_paths,
+                         uint64_t seed,
+                         double *paths,
+                         size_t len);
+
+/*
+ * Monte Carlo price of a European option under geometric Brownian motion
+ * with drift the cost of carry, discounted at the risk-free rate. Mainly
+ * a check on the analytic price, or a template for exotic payoffs.
+ */
+RqStatus rq_monte_carlo_european(const RqBlackScholesInputs *inputs,
+                                 size_t n_steps,
+                                 size_t n_paths,
+                                 uint64_t seed,
+                                 RqMonteCarloResult *result);
+
+/*
+ * Black-Scholes-Merton price.
+ */
+RqStatus rq_black_scholes_price(const RqBlackScholesInputs *inputs, double *price);
+
+/*
+ * Black-Scholes-Merton prices of `n` options.
+ */
+RqStatus rq_black_scholes_price_batch(const RqBlackScholesInputs *inputs,
+                                      size_t n,
+                                      double *prices);
+
+/*
+ * Black-Scholes-Merton price and Greeks.
+ */
+RqStatus rq_black_scholes_greeks(const RqBlackScholesInputs *inputs, RqGreeks *greeks);
+
+/*
+ * Black-Scholes-Merton implied volatility of `price` (the `volatility` of
+ * the inputs is ignored). Fails with `RQ_STATUS_NO_CONVERGENCE` if no
+ * volatility in $[10^{-6}, 5]$ matches the price.
+ */
+RqStatus rq_black_scholes_implied_volatility(const RqBlackScholesInputs *inputs,
+                                             double price,
+                                             double *volatility);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* RUSTQUANT_H */
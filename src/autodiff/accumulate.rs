@@ -42,11 +42,17 @@ impl Accumulate<Vec<f64>> for Variable<'_> {
 
         // Traverse the graph backwards and update the adjoints for the parent vertices.
         // This is simply the generalised chain rule.
+        let edges = self.graph.edges.borrow();
+
         for (index, vertex) in self.graph.vertices.borrow().iter().enumerate().rev() {
             let deriv = adjoints[index];
 
             adjoints[vertex.parents[0]] += vertex.partials[0] * deriv;
             adjoints[vertex.parents[1]] += vertex.partials[1] * deriv;
+
+            for &(parent, partial) in &edges[vertex.get_edges()] {
+                adjoints[parent] += partial * deriv;
+            }
         }
 
         adjoints
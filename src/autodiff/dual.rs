@@ -0,0 +1,666 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Forward (tangent) mode via dual and hyper-dual numbers.
+//!
+//! A [`Dual`] number $a + b\epsilon$ with $\epsilon^2 = 0$ carries a value
+//! and its derivative along one direction, so evaluating $f(x + \epsilon)$
+//! gives $f(x) + f'(x)\epsilon$. A [`HyperDual`] number
+//! $a + b\epsilon_1 + c\epsilon_2 + d\epsilon_1\epsilon_2$ also carries the
+//! mixed second derivative, so gamma or a cross-gamma comes out of a single
+//! evaluation of a closed-form pricer, with no tape.
+//!
+//! ```rust
+//! use RustQuant::autodiff::*;
+//!
+//! // f(x) = x^2 exp(x) at x = 1.
+//! let (value, first, second) = second_derivative(|x| x.powi(2) * x.exp(), 1.0);
+//!
+//! let e = std::f64::consts::E;
+//! assert!((value - e).abs() < 1e-14);
+//! assert!((first - 3.0 * e).abs() < 1e-14);
+//! assert!((second - 7.0 * e).abs() < 1e-14);
+//! ```
+
+use crate::math::special::{erf, erfc, norm_cdf, norm_pdf};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Dual number $a + b\epsilon$, $\epsilon^2 = 0$: a value and a first
+/// derivative.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Dual {
+    /// Value.
+    pub value: f64,
+    /// First derivative (tangent).
+    pub derivative: f64,
+}
+
+/// Hyper-dual number $a + b\epsilon_1 + c\epsilon_2 + d\epsilon_1\epsilon_2$,
+/// $\epsilon_1^2 = \epsilon_2^2 = 0$: a value, two first derivatives and
+/// the mixed second derivative.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct HyperDual {
+    /// Value.
+    pub value: f64,
+    /// Derivative along the first direction.
+    pub e1: f64,
+    /// Derivative along the second direction.
+    pub e2: f64,
+    /// Mixed second derivative along both directions.
+    pub e1e2: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Dual {
+    /// New dual number.
+    pub fn new(value: f64, derivative: f64) -> Self {
+        Self { value, derivative }
+    }
+
+    /// Independent variable: unit derivative.
+    pub fn variable(value: f64) -> Self {
+        Self::new(value, 1.0)
+    }
+
+    /// Constant: zero derivative.
+    pub fn constant(value: f64) -> Self {
+        Self::new(value, 0.0)
+    }
+
+    /// Apply a scalar function with value `f`, first derivative `df` and
+    /// second derivative `_d2f` at `self.value`.
+    #[inline]
+    fn chain(self, f: f64, df: f64, _d2f: f64) -> Self {
+        Self::new(f, df * self.derivative)
+    }
+}
+
+impl HyperDual {
+    /// New hyper-dual number.
+    pub fn new(value: f64, e1: f64, e2: f64, e1e2: f64) -> Self {
+        Self {
+            value,
+            e1,
+            e2,
+            e1e2,
+        }
+    }
+
+    /// Independent variable along both directions, so that `e1e2` of the
+    /// result is the second derivative.
+    pub fn variable(value: f64) -> Self {
+        Self::new(value, 1.0, 1.0, 0.0)
+    }
+
+    /// Constant: all derivatives zero.
+    pub fn constant(value: f64) -> Self {
+        Self::new(value, 0.0, 0.0, 0.0)
+    }
+
+    /// Apply a scalar function with value `f`, first derivative `df` and
+    /// second derivative `d2f` at `self.value`.
+    #[inline]
+    fn chain(self, f: f64, df: f64, d2f: f64) -> Self {
+        Self::new(
+            f,
+            df * self.e1,
+            df * self.e2,
+            df * self.e1e2 + d2f * self.e1 * self.e2,
+        )
+    }
+}
+
+/// Elementary functions, shared by [`Dual`] and [`HyperDual`] through
+/// their `chain` rule.
+macro_rules! elementary_functions {
+    ($t:ty) => {
+        impl $t {
+            /// Absolute value (derivative zero at zero).
+            #[inline]
+            pub fn abs(self) -> Self {
+                let sign = if self.value == 0.0 {
+                    0.0
+                } else {
+                    self.value.signum()
+                };
+                self.chain(self.value.abs(), sign, 0.0)
+            }
+
+            /// Reciprocal, $1 / x$.
+            #[inline]
+            pub fn recip(self) -> Self {
+                let r = self.value.recip();
+                self.chain(r, -r * r, 2.0 * r * r * r)
+            }
+
+            /// Exponential function.
+            #[inline]
+            pub fn exp(self) -> Self {
+                let e = self.value.exp();
+                self.chain(e, e, e)
+            }
+
+            /// $e^x - 1$, accurate near zero.
+            #[inline]
+            pub fn exp_m1(self) -> Self {
+                let e = self.value.exp();
+                self.chain(self.value.exp_m1(), e, e)
+            }
+
+            /// Natural logarithm.
+            #[inline]
+            pub fn ln(self) -> Self {
+                let r = self.value.recip();
+                self.chain(self.value.ln(), r, -r * r)
+            }
+
+            /// $\ln(1 + x)$, accurate near zero.
+            #[inline]
+            pub fn ln_1p(self) -> Self {
+                let r = (1.0 + self.value).recip();
+                self.chain(self.value.ln_1p(), r, -r * r)
+            }
+
+            /// Square root.
+            #[inline]
+            pub fn sqrt(self) -> Self {
+                let s = self.value.sqrt();
+                self.chain(s, 0.5 / s, -0.25 / (s * self.value))
+            }
+
+            /// Integer power.
+            #[inline]
+            pub fn powi(self, n: i32) -> Self {
+                let x = self.value;
+                let n_f = n as f64;
+                let d2f = match n {
+                    0 | 1 => 0.0,
+                    _ => n_f * (n_f - 1.0) * x.powi(n - 2),
+                };
+                let df = if n == 0 { 0.0 } else { n_f * x.powi(n - 1) };
+                self.chain(x.powi(n), df, d2f)
+            }
+
+            /// Real power with a constant exponent.
+            #[inline]
+            pub fn powf(self, n: f64) -> Self {
+                let x = self.value;
+                self.chain(
+                    x.powf(n),
+                    n * x.powf(n - 1.0),
+                    n * (n - 1.0) * x.powf(n - 2.0),
+                )
+            }
+
+            /// Power with a differentiable exponent, $x^y = e^{y \ln x}$.
+            #[inline]
+            pub fn pow(self, exponent: Self) -> Self {
+                (exponent * self.ln()).exp()
+            }
+
+            /// Sine.
+            #[inline]
+            pub fn sin(self) -> Self {
+                let (s, c) = self.value.sin_cos();
+                self.chain(s, c, -s)
+            }
+
+            /// Cosine.
+            #[inline]
+            pub fn cos(self) -> Self {
+                let (s, c) = self.value.sin_cos();
+                self.chain(c, -s, -c)
+            }
+
+            /// Tangent.
+            #[inline]
+            pub fn tan(self) -> Self {
+                let t = self.value.tan();
+                let sec2 = 1.0 + t * t;
+                self.chain(t, sec2, 2.0 * t * sec2)
+            }
+
+            /// Hyperbolic tangent.
+            #[inline]
+            pub fn tanh(self) -> Self {
+                let t = self.value.tanh();
+                let sech2 = 1.0 - t * t;
+                self.chain(t, sech2, -2.0 * t * sech2)
+            }
+
+            /// Error function.
+            #[inline]
+            pub fn erf(self) -> Self {
+                let x = self.value;
+                let d = std::f64::consts::FRAC_2_SQRT_PI * (-x * x).exp();
+                self.chain(erf(x), d, -2.0 * x * d)
+            }
+
+            /// Complementary error function.
+            #[inline]
+            pub fn erfc(self) -> Self {
+                let x = self.value;
+                let d = std::f64::consts::FRAC_2_SQRT_PI * (-x * x).exp();
+                self.chain(erfc(x), -d, 2.0 * x * d)
+            }
+
+            /// Standard normal distribution function.
+            #[inline]
+            pub fn norm_cdf(self) -> Self {
+                let x = self.value;
+                let pdf = norm_pdf(x);
+                self.chain(norm_cdf(x), pdf, -x * pdf)
+            }
+
+            /// Standard normal density.
+            #[inline]
+            pub fn norm_pdf(self) -> Self {
+                let x = self.value;
+                let pdf = norm_pdf(x);
+                self.chain(pdf, -x * pdf, (x * x - 1.0) * pdf)
+            }
+
+            /// Maximum by value (the derivatives of the larger argument).
+            #[inline]
+            pub fn max(self, other: Self) -> Self {
+                if other.value > self.value {
+                    other
+                } else {
+                    self
+                }
+            }
+
+            /// Minimum by value (the derivatives of the smaller argument).
+            #[inline]
+            pub fn min(self, other: Self) -> Self {
+                if other.value < self.value {
+                    other
+                } else {
+                    self
+                }
+            }
+        }
+
+        impl From<f64> for $t {
+            fn from(value: f64) -> Self {
+                Self::constant(value)
+            }
+        }
+
+        impl Neg for $t {
+            type Output = Self;
+
+            #[inline]
+            fn neg(self) -> Self {
+                self * -1.0
+            }
+        }
+
+        impl Sub for $t {
+            type Output = Self;
+
+            #[inline]
+            fn sub(self, other: Self) -> Self {
+                self + (-other)
+            }
+        }
+
+        impl Div for $t {
+            type Output = Self;
+
+            #[allow(clippy::suspicious_arithmetic_impl)]
+            #[inline]
+            fn div(self, other: Self) -> Self {
+                self * other.recip()
+            }
+        }
+
+        impl Add<f64> for $t {
+            type Output = Self;
+
+            #[inline]
+            fn add(self, other: f64) -> Self {
+                self + Self::constant(other)
+            }
+        }
+
+        impl Sub<f64> for $t {
+            type Output = Self;
+
+            #[inline]
+            fn sub(self, other: f64) -> Self {
+                self + Self::constant(-other)
+            }
+        }
+
+        impl Div<f64> for $t {
+            type Output = Self;
+
+            #[allow(clippy::suspicious_arithmetic_impl)]
+            #[inline]
+            fn div(self, other: f64) -> Self {
+                self * other.recip()
+            }
+        }
+
+        impl Add<$t> for f64 {
+            type Output = $t;
+
+            #[inline]
+            fn add(self, other: $t) -> $t {
+                other + self
+            }
+        }
+
+        impl Sub<$t> for f64 {
+            type Output = $t;
+
+            #[inline]
+            fn sub(self, other: $t) -> $t {
+                -other + self
+            }
+        }
+
+        impl Mul<$t> for f64 {
+            type Output = $t;
+
+            #[inline]
+            fn mul(self, other: $t) -> $t {
+                other * self
+            }
+        }
+
+        impl Div<$t> for f64 {
+            type Output = $t;
+
+            #[allow(clippy::suspicious_arithmetic_impl)]
+            #[inline]
+            fn div(self, other: $t) -> $t {
+                other.recip() * self
+            }
+        }
+
+        impl<T: Into<$t>> AddAssign<T> for $t {
+            fn add_assign(&mut self, other: T) {
+                *self = *self + other.into();
+            }
+        }
+
+        impl<T: Into<$t>> SubAssign<T> for $t {
+            fn sub_assign(&mut self, other: T) {
+                *self = *self - other.into();
+            }
+        }
+
+        impl<T: Into<$t>> MulAssign<T> for $t {
+            fn mul_assign(&mut self, other: T) {
+                *self = *self * other.into();
+            }
+        }
+
+        impl<T: Into<$t>> DivAssign<T> for $t {
+            fn div_assign(&mut self, other: T) {
+                *self = *self / other.into();
+            }
+        }
+
+        impl std::iter::Sum for $t {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold(Self::constant(0.0), |a, b| a + b)
+            }
+        }
+    };
+}
+
+elementary_functions!(Dual);
+elementary_functions!(HyperDual);
+
+impl Add for Dual {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        Self::new(self.value + other.value, self.derivative + other.derivative)
+    }
+}
+
+impl Mul for Dual {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, other: Self) -> Self {
+        Self::new(
+            self.value * other.value,
+            self.value * other.derivative + self.derivative * other.value,
+        )
+    }
+}
+
+impl Mul<f64> for Dual {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, other: f64) -> Self {
+        Self::new(self.value * other, self.derivative * other)
+    }
+}
+
+impl Add for HyperDual {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        Self::new(
+            self.value + other.value,
+            self.e1 + other.e1,
+            self.e2 + other.e2,
+            self.e1e2 + other.e1e2,
+        )
+    }
+}
+
+impl Mul for HyperDual {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, other: Self) -> Self {
+        Self::new(
+            self.value * other.value,
+            self.value * other.e1 + self.e1 * other.value,
+            self.value * other.e2 + self.e2 * other.value,
+            self.value * other.e1e2
+                + self.e1 * other.e2
+                + self.e2 * other.e1
+                + self.e1e2 * other.value,
+        )
+    }
+}
+
+impl Mul<f64> for HyperDual {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, other: f64) -> Self {
+        Self::new(
+            self.value * other,
+            self.e1 * other,
+            self.e2 * other,
+            self.e1e2 * other,
+        )
+    }
+}
+
+/// Value and first derivative of `f` at `x`, in one forward pass.
+pub fn first_derivative<F>(f: F, x: f64) -> (f64, f64)
+where
+    F: Fn(Dual) -> Dual,
+{
+    let y = f(Dual::variable(x));
+    (y.value, y.derivative)
+}
+
+/// Value, first and second derivative of `f` at `x`, in one forward pass.
+pub fn second_derivative<F>(f: F, x: f64) -> (f64, f64, f64)
+where
+    F: Fn(HyperDual) -> HyperDual,
+{
+    let y = f(HyperDual::variable(x));
+    (y.value, y.e1, y.e1e2)
+}
+
+/// Value and gradient of `f` at `x`, with one forward pass per input.
+pub fn forward_gradient<F>(f: F, x: &[f64]) -> (f64, Vec<f64>)
+where
+    F: Fn(&[Dual]) -> Dual,
+{
+    let mut inputs: Vec<Dual> = x.iter().map(|&v| Dual::constant(v)).collect();
+    let mut value = f(&inputs).value;
+    let mut gradient = Vec::with_capacity(x.len());
+
+    for i in 0..x.len() {
+        inputs[i].derivative = 1.0;
+        let y = f(&inputs);
+        inputs[i].derivative = 0.0;
+
+        value = y.value;
+        gradient.push(y.derivative);
+    }
+
+    (value, gradient)
+}
+
+/// Value, gradient and Hessian of `f` at `x`, with one hyper-dual pass per
+/// pair of inputs ($n(n+1)/2$ passes).
+pub fn forward_hessian<F>(f: F, x: &[f64]) -> (f64, Vec<f64>, Vec<Vec<f64>>)
+where
+    F: Fn(&[HyperDual]) -> HyperDual,
+{
+    let n = x.len();
+    let mut inputs: Vec<HyperDual> = x.iter().map(|&v| HyperDual::constant(v)).collect();
+    let mut value = f(&inputs).value;
+    let mut gradient = vec![0.0; n];
+    let mut hessian = vec![vec![0.0; n]; n];
+
+    for i in 0..n {
+        for j in i..n {
+            inputs[i].e1 = 1.0;
+            inputs[j].e2 = 1.0;
+            let y = f(&inputs);
+            inputs[i].e1 = 0.0;
+            inputs[j].e2 = 0.0;
+
+            value = y.value;
+            if i == j {
+                gradient[i] = y.e1;
+            }
+            hessian[i][j] = y.e1e2;
+            hessian[j][i] = y.e1e2;
+        }
+    }
+
+    (value, gradient, hessian)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_dual {
+    use super::*;
+    use crate::math::special::norm_pdf;
+
+    type Elementary = fn(HyperDual) -> HyperDual;
+    type Plain = fn(f64) -> f64;
+
+    /// Black-Scholes call, generic in the spot.
+    fn call(s: HyperDual, k: f64, r: f64, v: f64, t: f64) -> HyperDual {
+        let d1 = ((s / k).ln() + (r + 0.5 * v * v) * t) / (v * t.sqrt());
+        let d2 = d1 - v * t.sqrt();
+        s * d1.norm_cdf() - k * (-r * t).exp() * d2.norm_cdf()
+    }
+
+    #[test]
+    fn test_elementary_derivatives() {
+        let h = 1e-4;
+        let functions: [(Elementary, Plain); 10] = [
+            (|x| x.sqrt(), |x| x.sqrt()),
+            (|x| x.ln_1p(), |x| x.ln_1p()),
+            (|x| x.exp_m1(), |x| x.exp_m1()),
+            (|x| x.powf(2.5), |x| x.powf(2.5)),
+            (|x| x.powi(-3), |x| x.powi(-3)),
+            (|x| x.tan(), |x| x.tan()),
+            (|x| x.tanh(), |x| x.tanh()),
+            (|x| x.erfc(), crate::math::special::erfc),
+            (|x| x.norm_pdf(), norm_pdf),
+            (|x| 2.0 / (1.0 - x).cos(), |x| 2.0 / (1.0 - x).cos()),
+        ];
+
+        for (dual, plain) in functions {
+            let x = 0.7;
+            let (value, first, second) = second_derivative(dual, x);
+            let fd1 = (plain(x + 0.1 * h) - plain(x - 0.1 * h)) / (0.2 * h);
+            let fd2 = (plain(x + h) - 2.0 * plain(x) + plain(x - h)) / (h * h);
+
+            assert_approx_equal!(value, plain(x), 1e-15);
+            assert_approx_equal!(first, fd1, 1e-7);
+            assert_approx_equal!(second, fd2, 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_dual_matches_hyper_dual() {
+        let f_dual = |x: Dual| x.powi(3).sin() / (1.0 + x * x) + x.pow(x);
+        let f_hyper = |x: HyperDual| x.powi(3).sin() / (1.0 + x * x) + x.pow(x);
+
+        let (v1, d1) = first_derivative(f_dual, 1.3);
+        let (v2, d2, _) = second_derivative(f_hyper, 1.3);
+
+        assert_eq!(v1, v2);
+        assert_approx_equal!(d1, d2, 1e-14);
+    }
+
+    #[test]
+    fn test_black_scholes_gamma() {
+        let (s, k, r, v, t) = (100.0, 95.0, 0.03, 0.25, 0.75);
+        let (_, delta, gamma) = second_derivative(|s| call(s, k, r, v, t), s);
+
+        let d1 = ((s / k).ln() + (r + 0.5 * v * v) * t) / (v * t.sqrt());
+        assert_approx_equal!(delta, norm_cdf(d1), 1e-14);
+        assert_approx_equal!(gamma, norm_pdf(d1) / (s * v * t.sqrt()), 1e-14);
+    }
+
+    #[test]
+    fn test_gradient_and_cross_gamma() {
+        // f(x, y) = x^2 y + exp(x y).
+        let (x, y): (f64, f64) = (0.5, 1.5);
+        let exy = (x * y).exp();
+
+        let (value, gradient) =
+            forward_gradient(|v| v[0] * v[0] * v[1] + (v[0] * v[1]).exp(), &[x, y]);
+        assert_approx_equal!(value, x * x * y + exy, 1e-15);
+        assert_approx_equal!(gradient[0], 2.0 * x * y + y * exy, 1e-14);
+        assert_approx_equal!(gradient[1], x * x + x * exy, 1e-14);
+
+        let (_, hessian_gradient, hessian) =
+            forward_hessian(|v| v[0] * v[0] * v[1] + (v[0] * v[1]).exp(), &[x, y]);
+        assert_eq!(hessian_gradient, gradient);
+        assert_approx_equal!(hessian[0][0], 2.0 * y + y * y * exy, 1e-14);
+        assert_approx_equal!(hessian[1][1], x * x * exy, 1e-14);
+        assert_approx_equal!(hessian[0][1], 2.0 * x + exy * (1.0 + x * y), 1e-14);
+        assert_eq!(hessian[0][1], hessian[1][0]);
+    }
+}
@@ -77,7 +77,7 @@ impl<'v, const N: usize> Gradient<&[Variable<'v>; N], Vec<f64>> for Vec<f64> {
 
 #[cfg(test)]
 mod test_gradient {
-    use crate::{assert_approx_equal, autodiff::*};
+    use crate::autodiff::*;
 
     #[test]
     fn test_borrowed_vector() {
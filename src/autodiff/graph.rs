@@ -29,6 +29,8 @@ use std::cell::RefCell;
 pub struct Graph {
     /// Vector containing the vertices in the Wengert List.
     pub vertices: RefCell<Vec<Vertex>>,
+    /// (parent, partial) pairs of the n-ary vertices, see [`Vertex::edges`].
+    pub edges: RefCell<Vec<(usize, f64)>>,
 }
 // pub struct Graph(RefCell<Rc<[Vertex]>>);
 
@@ -46,6 +48,7 @@ impl Graph {
     pub fn new() -> Self {
         Graph {
             vertices: RefCell::new(Vec::new()),
+            edges: RefCell::new(Vec::new()),
             // vertices: RefCell::new(Rc::new([])),
         }
     }
@@ -55,6 +58,7 @@ impl Graph {
     pub fn with_capacity(capacity: usize) -> Self {
         Graph {
             vertices: RefCell::new(Vec::with_capacity(capacity)),
+            edges: RefCell::new(Vec::new()),
             // vertices: RefCell::new(Rc::new([])),
         }
     }
@@ -63,15 +67,28 @@ impl Graph {
     #[inline]
     pub fn join(&self, other: &Self) -> Self {
         let graph = self.clone();
-        let other = other.vertices.borrow_mut().clone();
-        graph.vertices.borrow_mut().extend(other);
+        let offset = graph.edges.borrow().len();
+        let other_vertices = other.vertices.borrow_mut().clone();
+        graph
+            .vertices
+            .borrow_mut()
+            .extend(other_vertices.into_iter().map(|mut vertex| {
+                if !vertex.get_edges().is_empty() {
+                    vertex.edges = [vertex.edges[0] + offset, vertex.edges[1] + offset];
+                }
+                vertex
+            }));
+        graph
+            .edges
+            .borrow_mut()
+            .extend(other.edges.borrow().iter().copied());
         graph
     }
 
     /// Add a new variable to to the graph.
     /// Returns a new `Variable` instance (the contents of a vertex).
     #[inline]
-    pub fn var(&self, value: f64) -> Variable {
+    pub fn var(&self, value: f64) -> Variable<'_> {
         Variable {
             graph: self,
             value,
@@ -102,6 +119,41 @@ impl Graph {
     #[inline]
     pub fn clear(&self) {
         self.vertices.borrow_mut().clear();
+        self.edges.borrow_mut().clear();
+    }
+
+    /// Number of vertices the graph can hold without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.vertices.borrow().capacity()
+    }
+
+    /// Rewinds the graph to its first `len` vertices, keeping the allocation.
+    ///
+    /// Record `graph.len()` after creating the inputs, and truncate back to it
+    /// between repeated pricings to reuse both the inputs and the tape memory.
+    /// Variables created after the mark must not be used afterwards.
+    #[inline]
+    pub fn truncate(&self, len: usize) {
+        let mut vertices = self.vertices.borrow_mut();
+
+        if len >= vertices.len() {
+            return;
+        }
+
+        // Edges are appended in vertex order, so the first removed n-ary
+        // vertex marks where the retained edges end.
+        let edges_len = vertices[len..]
+            .iter()
+            .map(|vertex| vertex.get_edges())
+            .find(|edges| !edges.is_empty())
+            .map(|edges| edges.start);
+
+        vertices.truncate(len);
+
+        if let Some(edges_len) = edges_len {
+            self.edges.borrow_mut().truncate(edges_len);
+        }
     }
 
     /// Zeroes the adjoints in the graph.
@@ -111,6 +163,10 @@ impl Graph {
             .borrow_mut()
             .iter_mut()
             .for_each(|vertex| vertex.partials = [0.0; 2]);
+        self.edges
+            .borrow_mut()
+            .iter_mut()
+            .for_each(|edge| edge.1 = 0.0);
     }
 
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -139,6 +195,7 @@ impl Graph {
                 Vertex {
                     partials: [0.0, 0.0],
                     parents: [len, len],
+                    edges: [0, 0],
                 }
             }
             // Unary operator pushback.
@@ -156,6 +213,7 @@ impl Graph {
                 Vertex {
                     partials: [partials[0], 0.0],
                     parents: [parents[0], len],
+                    edges: [0, 0],
                 }
             }
             // Binary operator pushback.
@@ -173,6 +231,30 @@ impl Graph {
                 Vertex {
                     partials: [partials[0], partials[1]],
                     parents: [parents[0], parents[1]],
+                    edges: [0, 0],
+                }
+            }
+            // N-ary operator pushback.
+            //
+            // The vertex pushed to the graph is the result of an **n-ary** operation.
+            // e.g. `x.dot(y)` for vectors `x` and `y`.
+            // Thus the parents and partials are appended to the edge list,
+            // and the new vertex refers to them by range.
+            //
+            // 1. Appends the (parent, partial) pairs to the edge list,
+            // 2. Pushes the new vertex onto the graph,
+            // 3. Returns the index of the new vertex.
+            Arity::Nary => {
+                assert!(parents.len() == partials.len());
+
+                let mut edges = self.edges.borrow_mut();
+                let start = edges.len();
+                edges.extend(parents.iter().copied().zip(partials.iter().copied()));
+
+                Vertex {
+                    partials: [0.0, 0.0],
+                    parents: [len, len],
+                    edges: [start, edges.len()],
                 }
             }
         };
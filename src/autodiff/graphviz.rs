@@ -41,6 +41,7 @@ pub fn graphviz(graph: &Graph, vars: &[Variable]) -> String {
     );
 
     let vertices = graph.vertices.borrow();
+    let edges = graph.edges.borrow();
 
     // Initialize a HashSet with variable indices for quick lookup
     let var_indices: std::collections::HashSet<_> = vars.iter().map(|var| var.index).collect();
@@ -71,6 +72,13 @@ pub fn graphviz(graph: &Graph, vars: &[Variable]) -> String {
                 ));
             }
         }
+
+        for (i, (parent, label)) in edges[vertex.get_edges()].iter().enumerate() {
+            dot.push_str(&format!(
+                "\t{} -> {} [label=\"\u{2202}_{}: {:.2?}\"];\n",
+                parent, index, i, label
+            ));
+        }
     }
 
     dot.push_str("}\n");
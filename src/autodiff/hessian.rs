@@ -7,11 +7,108 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+//! Hessian of a scalar function, $f:\mathbb{R}^n \rightarrow \mathbb{R}$.
+//!
+//! The tape only carries first derivatives, so the Hessian is obtained as
+//! the central difference of reverse-mode gradients: $2n$ tapes, each giving
+//! a full gradient exactly, with an $O(h^2)$ error from the outer difference
+//! only. For an exact Hessian of a closed-form function, see
+//! [`forward_hessian`](crate::autodiff::forward_hessian).
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::autodiff::*;
+use nalgebra::DMatrix;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Hessian of `f` at `x`, as central differences of the reverse-mode
+/// gradient, symmetrised.
+///
+/// ```
+/// use RustQuant::autodiff::*;
+///
+/// // f(x, y) = x^2 y
+/// let h = hessian(|v| v[0] * v[0] * v[1], &[1.0, 3.0]);
+///
+/// assert!((h[(0, 0)] - 6.0).abs() < 1e-8);
+/// assert!((h[(0, 1)] - 2.0).abs() < 1e-8);
+/// assert!(h[(1, 1)].abs() < 1e-8);
+/// ```
+pub fn hessian<F>(f: F, x: &[f64]) -> DMatrix<f64>
+where
+    F: for<'v> Fn(&[Variable<'v>]) -> Variable<'v>,
+{
+    let n = x.len();
+    let graph = Graph::new();
+
+    let gradient = |point: &[f64]| {
+        let inputs = graph.vars(point);
+        let adjoints = f(&inputs).accumulate();
+        let gradient = adjoints.wrt(&inputs);
+        graph.clear();
+        gradient
+    };
+
+    let mut hessian = DMatrix::zeros(n, n);
+    let mut point = x.to_vec();
+
+    for j in 0..n {
+        // Step scaled to the input, balancing truncation and round-off.
+        let h = f64::EPSILON.cbrt() * x[j].abs().max(1.0);
+
+        point[j] = x[j] + h;
+        let up = gradient(&point);
+        point[j] = x[j] - h;
+        let down = gradient(&point);
+        point[j] = x[j];
+
+        for i in 0..n {
+            hessian[(i, j)] = (up[i] - down[i]) / (2.0 * h);
+        }
+    }
+
+    (&hessian + hessian.transpose()) * 0.5
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
-// HESSIAN TRAIT AND IMPLEMENTATIONS
+// UNIT TESTS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-/// Return the derivative/s *with-respect-to* the chosen variables.
-pub trait Hessian<IN, OUT> {
-    todo!();
+#[cfg(test)]
+mod tests_hessian {
+    use super::*;
+
+    #[test]
+    fn test_matches_hyper_dual_hessian() {
+        // Two-asset exchange-style function with cross terms.
+        let x = [100.0, 95.0, 0.2];
+
+        let h = hessian(
+            |v| {
+                let d = ((v[0] / v[1]).ln() + 0.5 * v[2] * v[2]) / v[2];
+                v[0] * d.norm_cdf() - v[1] * (d - v[2]).norm_cdf()
+            },
+            &x,
+        );
+
+        let (_, _, exact) = forward_hessian(
+            |v| {
+                let d = ((v[0] / v[1]).ln() + 0.5 * v[2] * v[2]) / v[2];
+                v[0] * d.norm_cdf() - v[1] * (d - v[2]).norm_cdf()
+            },
+            &x,
+        );
+
+        for i in 0..3 {
+            for j in 0..3 {
+                assert_approx_equal!(h[(i, j)], exact[i][j], 1e-7 * exact[i][j].abs().max(1.0));
+            }
+        }
+        assert_eq!(h, h.transpose());
+    }
 }
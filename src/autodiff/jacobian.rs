@@ -0,0 +1,109 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Jacobian of a vector-valued function, $f:\mathbb{R}^n \rightarrow \mathbb{R}^m$.
+//!
+//! The function is taped once and the tape is swept backwards once per
+//! output, giving the matrix row by row. The layout (outputs in rows,
+//! inputs in columns) is the one expected by
+//! [`LevenbergMarquardt::minimize_with_jacobian`](crate::math::optimize::LevenbergMarquardt::minimize_with_jacobian).
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::autodiff::*;
+use nalgebra::DMatrix;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Jacobian of `f` at `x`: one tape, one reverse sweep per output.
+///
+/// ```
+/// use RustQuant::autodiff::*;
+///
+/// // f(x, y) = (x y, x + sin(y))
+/// let j = jacobian(|v| vec![v[0] * v[1], v[0] + v[1].sin()], &[2.0, 0.0]);
+///
+/// assert_eq!(j.shape(), (2, 2));
+/// assert_eq!(j[(0, 0)], 0.0);
+/// assert_eq!(j[(0, 1)], 2.0);
+/// assert_eq!(j[(1, 0)], 1.0);
+/// assert_eq!(j[(1, 1)], 1.0);
+/// ```
+pub fn jacobian<F>(f: F, x: &[f64]) -> DMatrix<f64>
+where
+    F: for<'v> Fn(&[Variable<'v>]) -> Vec<Variable<'v>>,
+{
+    let graph = Graph::new();
+    let inputs = graph.vars(x);
+    let outputs = f(&inputs);
+
+    let mut jacobian = DMatrix::zeros(outputs.len(), x.len());
+
+    for (i, output) in outputs.iter().enumerate() {
+        let adjoints = output.accumulate();
+
+        for (j, input) in inputs.iter().enumerate() {
+            jacobian[(i, j)] = adjoints.wrt(input);
+        }
+    }
+
+    jacobian
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_jacobian {
+    use super::*;
+    use crate::math::optimize::LevenbergMarquardt;
+
+    #[test]
+    fn test_levenberg_marquardt_with_autodiff_jacobian() {
+        // Data from y = 2.5 exp(-1.3 t) + 0.5.
+        let data: Vec<(f64, f64)> = (0..20)
+            .map(|i| {
+                let t = 0.25 * i as f64;
+                (t, 2.5 * (-1.3 * t).exp() + 0.5)
+            })
+            .collect();
+
+        fn model<'v>(p: &[Variable<'v>], t: f64) -> Variable<'v> {
+            p[0] * (-p[1] * t).exp() + p[2]
+        }
+
+        let residuals = |p: &[f64]| -> Vec<f64> {
+            data.iter()
+                .map(|(t, y)| p[0] * (-p[1] * t).exp() + p[2] - y)
+                .collect()
+        };
+        let j = |p: &[f64]| jacobian(|v| data.iter().map(|(t, _)| model(v, *t)).collect(), p);
+
+        // Matches the analytic Jacobian.
+        let p = [1.0, 0.5, 0.0];
+        let j_p = j(&p);
+        for (k, (t, _)) in data.iter().enumerate() {
+            assert_approx_equal!(j_p[(k, 0)], (-p[1] * t).exp(), 1e-15);
+            assert_approx_equal!(j_p[(k, 1)], -p[0] * t * (-p[1] * t).exp(), 1e-15);
+            assert_eq!(j_p[(k, 2)], 1.0);
+        }
+
+        let result = LevenbergMarquardt::new(200, 1e-12).minimize_with_jacobian(residuals, j, &p);
+
+        assert!(result.converged);
+        assert_approx_equal!(result.minimizer[0], 2.5, 1e-8);
+        assert_approx_equal!(result.minimizer[1], 1.3, 1e-8);
+        assert_approx_equal!(result.minimizer[2], 0.5, 1e-8);
+    }
+}
@@ -15,8 +15,9 @@
 //!   - Implementation via Operator and Function Overloading.
 //!   - Useful when number of outputs is *smaller* than number of inputs.
 //!     - i.e for functions $f:\mathbb{R}^n \rightarrow \mathbb{R}^m$, where $m \ll n$
-//! - [ ] Forward (Tangent) Mode
-//!   - Implementation via Dual Numbers.
+//! - [x] Forward (Tangent) Mode
+//!   - Implementation via Dual Numbers, and Hyper-Dual Numbers for second
+//!     derivatives ([`second_derivative`], [`forward_hessian`]).
 //!   - Useful when number of outputs is *larger* than number of inputs.
 //!     - i.e. for functions $f:\mathbb{R}^n \rightarrow \mathbb{R}^m$, where $m \gg n$
 //!
@@ -47,7 +48,7 @@
 //!
 //! You can also generate Graphviz (dot) code to visualize the computation graphs:
 //!
-//! ```rust
+//! ```rust,ignore
 //! println!("{}", graphviz(&graph, &variables));
 //! ```  
 //!
@@ -64,6 +65,14 @@
 pub mod accumulate;
 pub use accumulate::*;
 
+/// Checkpointed reverse mode for long time-stepping computations.
+pub mod checkpoint;
+pub use checkpoint::*;
+
+/// Forward mode: [`Dual`] and [`HyperDual`] numbers.
+pub mod dual;
+pub use dual::*;
+
 /// Implements the gradient computation.
 pub mod gradient;
 pub use gradient::*;
@@ -76,6 +85,24 @@ pub use graph::*;
 pub mod graphviz;
 pub use graphviz::*;
 
+/// Hessian helper, via reverse-mode gradients.
+pub mod hessian;
+pub use hessian::*;
+
+/// Differentiation through nested solves (implicit function theorem).
+pub mod implicit;
+
+/// Jacobian helper, via reverse sweeps over a single tape.
+pub mod jacobian;
+pub use jacobian::*;
+
+/// [`Scalar`] trait, for code generic over `f64` and `Variable`.
+pub mod scalar;
+pub use scalar::*;
+
+/// Vector operations (dot products, matrix-vector products, elementwise maps).
+pub mod vector;
+
 /// Implements [`Vertex`] (nodes) for the `Graph`.
 pub mod vertex;
 pub use vertex::*;
@@ -109,12 +136,12 @@ pub mod overloading {
     /// Overload the standard subtraction operator (`-`).
     pub mod sub;
 }
-pub use overloading::{
-    add::*, div::*, f64::*, iter::*, log::*, minmax::*, mul::*, pow::*, statrs::*, sub::*,
-};
+pub use overloading::{log::*, minmax::*, pow::*};
 
 /// `Variable`s for `autodiff`.
 pub mod variables {
+    /// Complex numbers with `Variable` parts.
+    pub mod complex;
     /// Implements `Variable`s for `nalgebra`.
     pub mod nalgebra;
     /// Implements `Variable`s for `ndarray`.
@@ -122,4 +149,4 @@ pub mod variables {
     /// Base trait for all `Variable`s.
     pub mod variable;
 }
-pub use variables::{nalgebra::*, ndarray::*, variable::*};
+pub use variables::{complex::*, ndarray::*, variable::*};
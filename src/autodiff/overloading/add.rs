@@ -11,9 +11,9 @@ use crate::autodiff::{variables::variable::Variable, vertex::Arity};
 use std::ops::{Add, AddAssign};
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
-/// Overload the standard addition operator (`+`).
-/// d/dx x + y = 1
-/// d/dy x + y = 1
+// Overload the standard addition operator (`+`).
+// d/dx x + y = 1
+// d/dy x + y = 1
 
 /// AddAssign: Variable<'v> += Variable<'v>
 impl<'v> AddAssign<Variable<'v>> for Variable<'v> {
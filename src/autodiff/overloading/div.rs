@@ -11,9 +11,9 @@ use crate::autodiff::{variables::variable::Variable, vertex::Arity};
 use std::ops::{Div, DivAssign};
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
-/// Overload the standard division operator (`/`).
-/// d/dx x/y = 1/y
-/// d/dy x/y = -x/y^2
+// Overload the standard division operator (`/`).
+// d/dx x/y = 1/y
+// d/dy x/y = -x/y^2
 
 /// DivAssign: Variable<'v> /= Variable<'v>
 impl<'v> DivAssign<Variable<'v>> for Variable<'v> {
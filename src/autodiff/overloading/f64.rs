@@ -7,7 +7,7 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-use crate::autodiff::{variables::variable::Variable, vertex::Arity};
+use crate::autodiff::{variables::variable::Variable, vertex::Arity, Subgradient};
 use std::ops::Neg;
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -29,7 +29,7 @@ impl<'v> std::ops::Neg for Variable<'v> {
 
 impl<'v> Variable<'v> {
     /// Absolute value function.
-    /// d/dx abs(x) = sign(x)
+    /// d/dx abs(x) = sign(x), and 0 at x = 0 (see [`Variable::abs_with`]).
     ///
     /// ```
     /// use RustQuant::autodiff::*;
@@ -50,13 +50,7 @@ impl<'v> Variable<'v> {
     /// ```
     #[inline]
     pub fn abs(self) -> Self {
-        Variable {
-            graph: self.graph,
-            value: self.value.abs(),
-            index: self
-                .graph
-                .push(Arity::Unary, &[self.index], &[self.value.signum()]),
-        }
+        self.abs_with(Subgradient::Average)
     }
 
     /// Inverse cosine function.
@@ -202,6 +196,42 @@ impl<'v> Variable<'v> {
         }
     }
 
+    /// Four-quadrant inverse tangent of `self / other` (i.e. `self` is y).
+    /// d/dy atan2(y, x) = x / (x^2 + y^2)
+    /// d/dx atan2(y, x) = -y / (x^2 + y^2)
+    ///
+    /// ```
+    /// use RustQuant::assert_approx_equal;
+    /// use RustQuant::autodiff::*;
+    ///
+    /// let g = Graph::new();
+    ///
+    /// let y = g.var(1.0);
+    /// let x = g.var(-1.0);
+    /// let z = y.atan2(x);
+    /// let grad = z.accumulate();
+    ///
+    /// assert_approx_equal!(z.value, 0.75 * std::f64::consts::PI, 1e-15);
+    /// assert_approx_equal!(grad.wrt(&y), -0.5, 1e-15);
+    /// assert_approx_equal!(grad.wrt(&x), -0.5, 1e-15);
+    /// ```
+    #[inline]
+    pub fn atan2(self, other: Self) -> Self {
+        assert!(std::ptr::eq(self.graph, other.graph));
+
+        let r2 = self.value.powi(2) + other.value.powi(2);
+
+        Variable {
+            graph: self.graph,
+            value: self.value.atan2(other.value),
+            index: self.graph.push(
+                Arity::Binary,
+                &[self.index, other.index],
+                &[other.value / r2, -self.value / r2],
+            ),
+        }
+    }
+
     /// Inverse hyperbolic tangent function.
     /// d/dx tanh^-1(x) = 1 / (1 + x^2)
     ///
@@ -692,7 +722,6 @@ impl<'v> Variable<'v> {
 
 #[cfg(test)]
 mod test_overloading_f64 {
-    use crate::assert_approx_equal;
     use crate::autodiff::*;
 
     #[test]
@@ -724,7 +753,7 @@ mod test_overloading_f64 {
         assert_approx_equal!(x.tanh().value, 0.7615941559557649, 1e-10);
         assert_approx_equal!(x.asinh().value, 0.881373587019543, 1e-10);
         assert_approx_equal!(x.acosh().value, 0.0, 1e-10);
-        assert_eq!(x.atanh().value, std::f64::INFINITY);
+        assert_eq!(x.atanh().value, f64::INFINITY);
         assert_approx_equal!(x.abs().value, 1.0, 1e-10);
     }
 
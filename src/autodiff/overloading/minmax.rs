@@ -75,7 +75,7 @@ impl<'v> Min<Variable<'v>> for f64 {
             index: rhs.graph.push(
                 Arity::Binary,
                 &[rhs.index, rhs.index],
-                &[0.0, if self < &rhs.value { 1.0 } else { 0.0 }],
+                &[0.0, if rhs.value < *self { 1.0 } else { 0.0 }],
             ),
         }
     }
@@ -147,12 +147,125 @@ impl<'v> Max<Variable<'v>> for f64 {
             index: rhs.graph.push(
                 Arity::Binary,
                 &[rhs.index, rhs.index],
-                &[0.0, if self > &rhs.value { 1.0 } else { 0.0 }],
+                &[0.0, if rhs.value > *self { 1.0 } else { 0.0 }],
             ),
         }
     }
 }
 
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// OVERLOADING: KINKS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Convention for the derivative at a kink, where the left and right
+/// derivatives differ (e.g. `max(x, 0)` or `abs(x)` at `x = 0`).
+///
+/// Kinks are hit exactly by option payoffs at the strike, so the choice is
+/// made explicit rather than left to floating point comparisons.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Subgradient {
+    /// Left derivative.
+    Left,
+    /// Right derivative.
+    Right,
+    /// Average of the left and right derivatives.
+    #[default]
+    Average,
+}
+
+impl Subgradient {
+    /// Derivative at the kink, given the left and right derivatives.
+    #[inline]
+    pub fn at(self, left: f64, right: f64) -> f64 {
+        match self {
+            Subgradient::Left => left,
+            Subgradient::Right => right,
+            Subgradient::Average => 0.5 * (left + right),
+        }
+    }
+}
+
+impl<'v> Variable<'v> {
+    /// Positive part, max(x, 0).
+    /// d/dx max(x, 0) = 1 if x > 0, 0 if x < 0, and 1/2 at x = 0 (the
+    /// default `Subgradient`, as for `abs`).
+    ///
+    /// ```
+    /// use RustQuant::autodiff::*;
+    ///
+    /// let g = Graph::new();
+    ///
+    /// let x = g.var(105.0);
+    /// let payoff = (x - 100.0).relu();
+    ///
+    /// assert_eq!(payoff.value, 5.0);
+    /// assert_eq!(payoff.accumulate().wrt(&x), 1.0);
+    /// ```
+    #[inline]
+    pub fn relu(self) -> Self {
+        self.relu_with(Subgradient::default())
+    }
+
+    /// Positive part, max(x, 0), with the derivative at x = 0 chosen by `kink`.
+    #[inline]
+    pub fn relu_with(self, kink: Subgradient) -> Self {
+        self.max_with(0.0, kink)
+    }
+
+    /// Absolute value, with the derivative at x = 0 chosen by `kink`.
+    /// d/dx abs(x) = sign(x)
+    #[inline]
+    pub fn abs_with(self, kink: Subgradient) -> Self {
+        let partial = if self.value == 0.0 {
+            kink.at(-1.0, 1.0)
+        } else {
+            self.value.signum()
+        };
+
+        Variable {
+            graph: self.graph,
+            value: self.value.abs(),
+            index: self.graph.push(Arity::Unary, &[self.index], &[partial]),
+        }
+    }
+
+    /// max(x, c) for a constant c, with the derivative at x = c chosen by `kink`.
+    #[inline]
+    pub fn max_with(self, c: f64, kink: Subgradient) -> Self {
+        let partial = if self.value == c {
+            kink.at(0.0, 1.0)
+        } else if self.value > c {
+            1.0
+        } else {
+            0.0
+        };
+
+        Variable {
+            graph: self.graph,
+            value: self.value.max(c),
+            index: self.graph.push(Arity::Unary, &[self.index], &[partial]),
+        }
+    }
+
+    /// min(x, c) for a constant c, with the derivative at x = c chosen by `kink`.
+    #[inline]
+    pub fn min_with(self, c: f64, kink: Subgradient) -> Self {
+        let partial = if self.value == c {
+            kink.at(1.0, 0.0)
+        } else if self.value < c {
+            1.0
+        } else {
+            0.0
+        };
+
+        Variable {
+            graph: self.graph,
+            value: self.value.min(c),
+            index: self.graph.push(Arity::Unary, &[self.index], &[partial]),
+        }
+    }
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // UNIT TESTS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -194,7 +307,53 @@ mod test_overloading_minmax {
         assert!(Min::min(&x, 2_f64).accumulate().wrt(&x) == 1.0);
         assert!(Max::max(&x, 2_f64).accumulate().wrt(&x) == 0.0);
 
-        assert!(Min::min(&2_f64, x).accumulate().wrt(&x) == 0.0);
-        assert!(Max::max(&2_f64, x).accumulate().wrt(&x) == 1.0);
+        assert!(Min::min(&2_f64, x).accumulate().wrt(&x) == 1.0);
+        assert!(Max::max(&2_f64, x).accumulate().wrt(&x) == 0.0);
+    }
+
+    #[test]
+    fn test_relu_at_zero() {
+        let g = Graph::new();
+
+        let x = g.var(0.0);
+
+        // relu and abs share the default convention at the kink.
+        assert_eq!(x.relu().value, 0.0);
+        assert_eq!(x.relu().accumulate().wrt(&x), 0.5);
+        assert_eq!(
+            x.relu().accumulate().wrt(&x),
+            x.relu_with(Subgradient::default()).accumulate().wrt(&x)
+        );
+    }
+
+    #[test]
+    fn test_kinks() {
+        let g = Graph::new();
+
+        let x = g.var(0.0);
+
+        assert_eq!(x.relu_with(Subgradient::Left).accumulate().wrt(&x), 0.0);
+        assert_eq!(x.relu_with(Subgradient::Right).accumulate().wrt(&x), 1.0);
+        assert_eq!(x.relu_with(Subgradient::Average).accumulate().wrt(&x), 0.5);
+
+        assert_eq!(x.abs().accumulate().wrt(&x), 0.0);
+        assert_eq!(x.abs_with(Subgradient::Left).accumulate().wrt(&x), -1.0);
+        assert_eq!(x.abs_with(Subgradient::Right).accumulate().wrt(&x), 1.0);
+
+        assert_eq!(x.min_with(0.0, Subgradient::Left).accumulate().wrt(&x), 1.0);
+        assert_eq!(
+            x.min_with(0.0, Subgradient::Right).accumulate().wrt(&x),
+            0.0
+        );
+
+        // Away from the kink the convention does not matter.
+        let y = g.var(-2.0);
+        for kink in [Subgradient::Left, Subgradient::Right, Subgradient::Average] {
+            assert_eq!(y.relu_with(kink).value, 0.0);
+            assert_eq!(y.relu_with(kink).accumulate().wrt(&y), 0.0);
+            assert_eq!(y.abs_with(kink).accumulate().wrt(&y), -1.0);
+            assert_eq!(y.max_with(-3.0, kink).accumulate().wrt(&y), 1.0);
+            assert_eq!(y.min_with(-3.0, kink).accumulate().wrt(&y), 0.0);
+        }
     }
 }
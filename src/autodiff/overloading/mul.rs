@@ -11,9 +11,9 @@ use crate::autodiff::{variables::variable::Variable, vertex::Arity};
 use std::ops::{Mul, MulAssign};
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
-/// Overload the standard multiplication operator (`*`).
-/// d/dx x * y = y
-/// d/dy x * y = x
+// Overload the standard multiplication operator (`*`).
+// d/dx x * y = y
+// d/dy x * y = x
 
 /// MulAssign: Variable<'v> *= Variable<'v>
 impl<'v> MulAssign<Variable<'v>> for Variable<'v> {
@@ -10,7 +10,7 @@
 //! Overloading functions from `statrs` crate.
 
 use crate::autodiff::{variables::variable::Variable, vertex::Arity};
-use std::f64::consts::PI;
+use std::f64::consts::{PI, SQRT_2};
 use std::ops::Neg;
 
 impl<'v> Variable<'v> {
@@ -75,4 +75,126 @@ impl<'v> Variable<'v> {
             ),
         }
     }
+
+    /// Standard normal distribution function.
+    /// d/dx N(x) = n(x) = e^(-x^2 / 2) / sqrt(2 PI)
+    ///
+    /// ```
+    /// use RustQuant::assert_approx_equal;
+    /// use RustQuant::autodiff::*;
+    ///
+    /// let g = Graph::new();
+    ///
+    /// let x = g.var(1.0);
+    /// let z = x.norm_cdf();
+    /// let grad = z.accumulate();
+    ///
+    /// assert_approx_equal!(z.value,      0.84134474607, 1e-10);
+    /// assert_approx_equal!(grad.wrt(&x), 0.24197072451, 1e-10);
+    /// ```
+    #[inline]
+    pub fn norm_cdf(self) -> Self {
+        use statrs::function::erf::erfc;
+
+        Variable {
+            graph: self.graph,
+            value: 0.5 * erfc(-self.value / SQRT_2),
+            index: self.graph.push(
+                Arity::Unary,
+                &[self.index],
+                &[(-0.5 * self.value * self.value).exp() / (2.0 * PI).sqrt()],
+            ),
+        }
+    }
+
+    /// Standard normal density function.
+    /// d/dx n(x) = -x n(x)
+    ///
+    /// ```
+    /// use RustQuant::assert_approx_equal;
+    /// use RustQuant::autodiff::*;
+    ///
+    /// let g = Graph::new();
+    ///
+    /// let x = g.var(1.0);
+    /// let z = x.norm_pdf();
+    /// let grad = z.accumulate();
+    ///
+    /// assert_approx_equal!(z.value,       0.24197072451, 1e-10);
+    /// assert_approx_equal!(grad.wrt(&x), -0.24197072451, 1e-10);
+    /// ```
+    #[inline]
+    pub fn norm_pdf(self) -> Self {
+        let pdf = (-0.5 * self.value * self.value).exp() / (2.0 * PI).sqrt();
+
+        Variable {
+            graph: self.graph,
+            value: pdf,
+            index: self
+                .graph
+                .push(Arity::Unary, &[self.index], &[-self.value * pdf]),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_overloading_statrs {
+    use crate::autodiff::*;
+
+    /// Black-Scholes call, taped end-to-end.
+    fn call<'v>(s: Variable<'v>, k: f64, r: Variable<'v>, v: Variable<'v>, t: f64) -> Variable<'v> {
+        let d1 = ((s / k).ln() + (r + 0.5 * v * v) * t) / (v * t.sqrt());
+        let d2 = d1 - v * t.sqrt();
+
+        s * d1.norm_cdf() - k * (-r * t).exp() * d2.norm_cdf()
+    }
+
+    #[test]
+    fn test_black_scholes_greeks() {
+        use crate::math::special::{norm_cdf, norm_pdf};
+
+        let g = Graph::new();
+
+        let (k, t) = (95.0, 0.75);
+        let (s, r, v) = (g.var(100.0), g.var(0.03), g.var(0.25));
+        let price = call(s, k, r, v, t);
+        let grad = price.accumulate();
+
+        let d1 =
+            ((s.value / k).ln() + (r.value + 0.5 * v.value * v.value) * t) / (v.value * t.sqrt());
+        let d2 = d1 - v.value * t.sqrt();
+        let df = (-r.value * t).exp();
+
+        assert_approx_equal!(
+            price.value,
+            s.value * norm_cdf(d1) - k * df * norm_cdf(d2),
+            1e-12
+        );
+        assert_approx_equal!(grad.wrt(&s), norm_cdf(d1), 1e-12);
+        assert_approx_equal!(grad.wrt(&v), s.value * norm_pdf(d1) * t.sqrt(), 1e-10);
+        assert_approx_equal!(grad.wrt(&r), k * t * df * norm_cdf(d2), 1e-10);
+    }
+
+    #[test]
+    fn test_digital_and_payoff() {
+        let g = Graph::new();
+
+        // Smoothed digital N(x) and call payoff max(x - 1, 0) at the kink.
+        let x = g.var(1.0);
+        let digital = (x - 1.0).norm_cdf();
+        let payoff = (x - 1.0).relu_with(Subgradient::Average);
+
+        assert_approx_equal!(digital.value, 0.5, 1e-15);
+        assert_approx_equal!(
+            digital.accumulate().wrt(&x),
+            1.0 / (2.0 * std::f64::consts::PI).sqrt(),
+            1e-15
+        );
+        assert_eq!(payoff.value, 0.0);
+        assert_eq!(payoff.accumulate().wrt(&x), 0.5);
+    }
 }
@@ -11,9 +11,9 @@ use crate::autodiff::{variables::variable::Variable, vertex::Arity};
 use std::ops::{Add, Neg, Sub, SubAssign};
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
-/// Overload the standard subtraction operator (`-`).
-/// d/dx x - y = 1
-/// d/dy x - y = -1
+// Overload the standard subtraction operator (`-`).
+// d/dx x - y = 1
+// d/dy x - y = -1
 
 /// SubAssign: Variable<'v> -= Variable<'v>
 impl<'v> SubAssign<Variable<'v>> for Variable<'v> {
@@ -0,0 +1,307 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Complex numbers with `Variable` real and imaginary parts.
+//!
+//! Characteristic-function pricers (e.g. Heston) are written in complex
+//! arithmetic, but their prices are real functions of real parameters. Each
+//! complex operation here is expanded into real operations on the graph, so
+//! such pricers can be taped and differentiated like any other function.
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::autodiff::{variables::variable::Variable, Graph};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCT AND IMPLEMENTATION
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Complex number whose real and imaginary parts are `Variable`s.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ComplexVariable<'v> {
+    /// Real part.
+    pub re: Variable<'v>,
+    /// Imaginary part.
+    pub im: Variable<'v>,
+}
+
+impl<'v> ComplexVariable<'v> {
+    /// Instantiate a new complex variable.
+    #[inline]
+    pub fn new(re: Variable<'v>, im: Variable<'v>) -> Self {
+        assert!(std::ptr::eq(re.graph, im.graph));
+
+        Self { re, im }
+    }
+
+    /// Complex constant `re + i im` on the graph.
+    #[inline]
+    pub fn constant(graph: &'v Graph, re: f64, im: f64) -> Self {
+        Self::new(graph.var(re), graph.var(im))
+    }
+
+    /// Complex variable with a real `Variable` and a constant imaginary part.
+    #[inline]
+    pub fn from_parts(re: Variable<'v>, im: f64) -> Self {
+        Self::new(re, re.graph.var(im))
+    }
+
+    /// Squared modulus, |z|^2.
+    #[inline]
+    pub fn norm_sqr(self) -> Variable<'v> {
+        self.re * self.re + self.im * self.im
+    }
+
+    /// Modulus, |z|.
+    #[inline]
+    pub fn abs(self) -> Variable<'v> {
+        self.norm_sqr().sqrt()
+    }
+
+    /// Argument, in (-PI, PI].
+    #[inline]
+    pub fn arg(self) -> Variable<'v> {
+        self.im.atan2(self.re)
+    }
+
+    /// Complex conjugate.
+    #[inline]
+    pub fn conj(self) -> Self {
+        Self::new(self.re, -self.im)
+    }
+
+    /// Exponential, e^re (cos(im) + i sin(im)).
+    #[inline]
+    pub fn exp(self) -> Self {
+        let modulus = self.re.exp();
+
+        Self::new(modulus * self.im.cos(), modulus * self.im.sin())
+    }
+
+    /// Principal logarithm, ln|z| + i arg(z).
+    #[inline]
+    pub fn ln(self) -> Self {
+        Self::new(0.5 * self.norm_sqr().ln(), self.arg())
+    }
+
+    /// Principal square root.
+    #[inline]
+    pub fn sqrt(self) -> Self {
+        let modulus = self.abs();
+        let re = (0.5 * (modulus + self.re)).sqrt();
+        let im = (0.5 * (modulus - self.re)).sqrt();
+
+        // The branch cut is the negative real axis.
+        match self.im.value < 0.0 {
+            true => Self::new(re, -im),
+            false => Self::new(re, im),
+        }
+    }
+
+    /// Multiplicative inverse, conj(z) / |z|^2.
+    #[inline]
+    pub fn recip(self) -> Self {
+        let norm_sqr = self.norm_sqr();
+
+        Self::new(self.re / norm_sqr, -self.im / norm_sqr)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// OVERLOADING: STANDARD MATH OPERATORS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// -ComplexVariable<'v>
+impl<'v> Neg for ComplexVariable<'v> {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Self::new(-self.re, -self.im)
+    }
+}
+
+/// ComplexVariable<'v> + ComplexVariable<'v>
+impl<'v> Add for ComplexVariable<'v> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, other: Self) -> Self::Output {
+        Self::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+/// ComplexVariable<'v> - ComplexVariable<'v>
+impl<'v> Sub for ComplexVariable<'v> {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, other: Self) -> Self::Output {
+        Self::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+/// ComplexVariable<'v> * ComplexVariable<'v>
+impl<'v> Mul for ComplexVariable<'v> {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, other: Self) -> Self::Output {
+        Self::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+}
+
+/// ComplexVariable<'v> / ComplexVariable<'v>
+impl<'v> Div for ComplexVariable<'v> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    #[inline]
+    fn div(self, other: Self) -> Self::Output {
+        self * other.recip()
+    }
+}
+
+/// ComplexVariable<'v> + Variable<'v>
+impl<'v> Add<Variable<'v>> for ComplexVariable<'v> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, other: Variable<'v>) -> Self::Output {
+        Self::new(self.re + other, self.im)
+    }
+}
+
+/// ComplexVariable<'v> - Variable<'v>
+impl<'v> Sub<Variable<'v>> for ComplexVariable<'v> {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, other: Variable<'v>) -> Self::Output {
+        Self::new(self.re - other, self.im)
+    }
+}
+
+/// ComplexVariable<'v> * Variable<'v>
+impl<'v> Mul<Variable<'v>> for ComplexVariable<'v> {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, other: Variable<'v>) -> Self::Output {
+        Self::new(self.re * other, self.im * other)
+    }
+}
+
+/// ComplexVariable<'v> / Variable<'v>
+impl<'v> Div<Variable<'v>> for ComplexVariable<'v> {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, other: Variable<'v>) -> Self::Output {
+        Self::new(self.re / other, self.im / other)
+    }
+}
+
+/// ComplexVariable<'v> + f64
+impl<'v> Add<f64> for ComplexVariable<'v> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, other: f64) -> Self::Output {
+        Self::new(self.re + other, self.im)
+    }
+}
+
+/// ComplexVariable<'v> - f64
+impl<'v> Sub<f64> for ComplexVariable<'v> {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, other: f64) -> Self::Output {
+        Self::new(self.re - other, self.im)
+    }
+}
+
+/// ComplexVariable<'v> * f64
+impl<'v> Mul<f64> for ComplexVariable<'v> {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, other: f64) -> Self::Output {
+        Self::new(self.re * other, self.im * other)
+    }
+}
+
+/// f64 - ComplexVariable<'v>
+impl<'v> Sub<ComplexVariable<'v>> for f64 {
+    type Output = ComplexVariable<'v>;
+
+    #[inline]
+    fn sub(self, other: ComplexVariable<'v>) -> Self::Output {
+        ComplexVariable::new(self - other.re, -other.im)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_complex_variable {
+    use super::*;
+    use crate::autodiff::{Accumulate, Gradient};
+    use num_complex::Complex;
+
+    #[test]
+    fn test_values_match_num_complex() {
+        let g = Graph::new();
+
+        let (a, b) = (Complex::new(0.7, -1.3), Complex::new(-2.1, 0.4));
+        let x = ComplexVariable::new(g.var(a.re), g.var(a.im));
+        let y = ComplexVariable::new(g.var(b.re), g.var(b.im));
+
+        let cases = [
+            (x * y, a * b),
+            (x / y, a / b),
+            ((x - y).exp(), (a - b).exp()),
+            (y.ln(), b.ln()),
+            (y.sqrt(), b.sqrt()),
+            (x.sqrt(), a.sqrt()),
+            ((1.0 - x * 2.0).recip(), (1.0 - a * 2.0).inv()),
+        ];
+
+        for (z, expected) in cases {
+            assert_approx_equal!(z.re.value, expected.re, 1e-14);
+            assert_approx_equal!(z.im.value, expected.im, 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_holomorphic_derivative() {
+        // For holomorphic f, d Re f / d re = Re f'(z) and d Im f / d re = Im f'(z).
+        let g = Graph::new();
+
+        let z = Complex::new(0.3, 0.8);
+        let x = ComplexVariable::new(g.var(z.re), g.var(z.im));
+        let f = (x * x).exp().ln().sqrt() / (x + 1.0);
+
+        // f(z) = z / (z + 1) away from branch cuts, f'(z) = 1 / (z + 1)^2.
+        let expected = (z + 1.0).powi(-2);
+
+        assert_approx_equal!(f.re.accumulate().wrt(&x.re), expected.re, 1e-14);
+        assert_approx_equal!(f.im.accumulate().wrt(&x.re), expected.im, 1e-14);
+        assert_approx_equal!(f.re.accumulate().wrt(&x.im), -expected.im, 1e-14);
+    }
+}
@@ -0,0 +1,256 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Vector operations on the computation `Graph`.
+//!
+//! Built from scalar overloads, a dot product of length $n$ costs $2n - 1$
+//! vertices and a matrix-vector product of an $m \times n$ matrix costs
+//! $m(2n - 1)$. The operations here push a single n-ary vertex per output
+//! instead, holding every partial derivative of that output, so curve or
+//! surface node sensitivities stay cheap to tape and to accumulate.
+//!
+//! ```rust
+//! use RustQuant::autodiff::*;
+//!
+//! let g = Graph::new();
+//!
+//! let x = g.vars(&[1.0, 2.0, 3.0]);
+//! let y = g.vars(&[4.0, 5.0, 6.0]);
+//!
+//! // One vertex instead of five.
+//! let z = g.dot(&x, &y);
+//! let grad = z.accumulate();
+//!
+//! assert_eq!(z.value, 32.0);
+//! assert_eq!(grad.wrt(&x), vec![4.0, 5.0, 6.0]);
+//! assert_eq!(grad.wrt(&y), vec![1.0, 2.0, 3.0]);
+//! ```
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::autodiff::*;
+use ndarray::Array2;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Graph {
+    /// Pushes a single vertex with the given `value`, depending on each of
+    /// `parents` through the matching entry of `partials`.
+    /// This is the building block for custom vector operations.
+    #[inline]
+    pub fn nary<'v>(
+        &'v self,
+        value: f64,
+        parents: &[Variable<'v>],
+        partials: &[f64],
+    ) -> Variable<'v> {
+        assert_eq!(parents.len(), partials.len());
+        assert!(parents.iter().all(|p| std::ptr::eq(self, p.graph)));
+
+        let indices: Vec<usize> = parents.iter().map(|p| p.index).collect();
+
+        Variable {
+            graph: self,
+            value,
+            index: self.push(Arity::Nary, &indices, partials),
+        }
+    }
+
+    /// Sum of a vector of variables.
+    /// d/dx_i sum(x) = 1
+    #[inline]
+    pub fn sum<'v>(&'v self, x: &[Variable<'v>]) -> Variable<'v> {
+        let value = x.iter().map(|x_i| x_i.value).sum();
+
+        self.nary(value, x, &vec![1.0; x.len()])
+    }
+
+    /// Dot product of two vectors of variables.
+    /// d/dx_i x.y = y_i
+    /// d/dy_i x.y = x_i
+    #[inline]
+    pub fn dot<'v>(&'v self, x: &[Variable<'v>], y: &[Variable<'v>]) -> Variable<'v> {
+        assert_eq!(x.len(), y.len());
+
+        let value = x
+            .iter()
+            .zip(y)
+            .map(|(x_i, y_i)| x_i.value * y_i.value)
+            .sum();
+
+        let parents: Vec<Variable<'v>> = x.iter().chain(y).copied().collect();
+        let partials: Vec<f64> = y.iter().chain(x).map(|v| v.value).collect();
+
+        self.nary(value, &parents, &partials)
+    }
+
+    /// Dot product of a vector of variables with constant weights.
+    /// d/dx_i x.w = w_i
+    #[inline]
+    pub fn dot_f64<'v>(&'v self, x: &[Variable<'v>], w: &[f64]) -> Variable<'v> {
+        assert_eq!(x.len(), w.len());
+
+        let value = x.iter().zip(w).map(|(x_i, w_i)| x_i.value * w_i).sum();
+
+        self.nary(value, x, w)
+    }
+
+    /// Product of a constant matrix and a vector of variables, $Ax$.
+    /// One vertex per row of $A$.
+    /// d/dx_j (Ax)_i = A_ij
+    #[inline]
+    pub fn mat_vec<'v>(&'v self, a: &Array2<f64>, x: &[Variable<'v>]) -> Vec<Variable<'v>> {
+        assert_eq!(a.ncols(), x.len());
+
+        a.rows()
+            .into_iter()
+            .map(|row| {
+                let w: Vec<f64> = row.iter().copied().collect();
+                self.dot_f64(x, &w)
+            })
+            .collect()
+    }
+
+    /// Product of a matrix of variables and a vector of variables, $Ax$.
+    /// One vertex per row of $A$.
+    #[inline]
+    pub fn mat_vec_var<'v>(
+        &'v self,
+        a: &Array2<Variable<'v>>,
+        x: &[Variable<'v>],
+    ) -> Vec<Variable<'v>> {
+        assert_eq!(a.ncols(), x.len());
+
+        a.rows()
+            .into_iter()
+            .map(|row| {
+                let a_i: Vec<Variable<'v>> = row.iter().copied().collect();
+                self.dot(&a_i, x)
+            })
+            .collect()
+    }
+
+    /// Elementwise map of a scalar function over a vector of variables.
+    /// `f` returns the value and the derivative at each point, so that any
+    /// composite function costs one vertex per element.
+    ///
+    /// ```
+    /// use RustQuant::autodiff::*;
+    ///
+    /// let g = Graph::new();
+    /// let x = g.vars(&[0.0, 1.0]);
+    ///
+    /// // Discount factors exp(-x_i).
+    /// let d = g.map(&x, |x| ((-x).exp(), -(-x).exp()));
+    ///
+    /// assert_eq!(d[1].value, (-1.0_f64).exp());
+    /// assert_eq!(d[1].accumulate().wrt(&x[1]), -(-1.0_f64).exp());
+    /// ```
+    #[inline]
+    pub fn map<'v, F>(&'v self, x: &[Variable<'v>], f: F) -> Vec<Variable<'v>>
+    where
+        F: Fn(f64) -> (f64, f64),
+    {
+        x.iter()
+            .map(|x_i| {
+                let (value, derivative) = f(x_i.value);
+                Variable {
+                    graph: self,
+                    value,
+                    index: self.push(Arity::Unary, &[x_i.index], &[derivative]),
+                }
+            })
+            .collect()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_vector {
+    use crate::autodiff::*;
+
+    #[test]
+    fn test_dot_matches_scalar_graph() {
+        let g = Graph::new();
+
+        let x = g.vars(&[1.0, -2.0, 3.5, 0.25]);
+        let y = g.vars(&[0.5, 4.0, -1.0, 2.0]);
+
+        let before = g.len();
+        let z = g.dot(&x, &y);
+        assert_eq!(g.len(), before + 1);
+
+        let scalar = x.iter().zip(&y).map(|(&a, &b)| a * b).sum::<Variable>();
+
+        let grad = z.accumulate();
+        let grad_scalar = scalar.accumulate();
+
+        assert_eq!(z.value, scalar.value);
+        assert_eq!(grad.wrt(&x), grad_scalar.wrt(&x));
+        assert_eq!(grad.wrt(&y), grad_scalar.wrt(&y));
+    }
+
+    #[test]
+    fn test_mat_vec() {
+        let g = Graph::new();
+
+        let a = ndarray::array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let x = g.vars(&[1.0, 0.5, -1.0]);
+
+        let y = g.mat_vec(&a, &x);
+
+        assert_eq!(y[0].value, -1.0);
+        assert_eq!(y[1].value, 0.5);
+
+        // Jacobian of Ax is A.
+        assert_eq!(y[0].accumulate().wrt(&x), vec![1.0, 2.0, 3.0]);
+        assert_eq!(y[1].accumulate().wrt(&x), vec![4.0, 5.0, 6.0]);
+
+        // Variable matrix: d/dA_ij (Ax)_i = x_j.
+        let a_var = a.map(|&a_ij| g.var(a_ij));
+        let y_var = g.mat_vec_var(&a_var, &x);
+        let grad = y_var[1].accumulate();
+
+        assert_eq!(y_var[1].value, y[1].value);
+        assert_eq!(grad.wrt(&x), vec![4.0, 5.0, 6.0]);
+        assert_eq!(grad.wrt(&a_var[[1, 2]]), -1.0);
+        assert_eq!(grad.wrt(&a_var[[0, 2]]), 0.0);
+    }
+
+    #[test]
+    fn test_chain_of_vector_ops() {
+        // f(x) = sum_i w_i exp(x_i) * x.x
+        let g = Graph::new();
+
+        let xs = [0.1, 0.2, 0.3];
+        let w = [1.0, 2.0, 3.0];
+        let x = g.vars(&xs);
+
+        let e = g.map(&x, |x| (x.exp(), x.exp()));
+        let f = g.dot_f64(&e, &w) * g.dot(&x, &x);
+        let grad = f.accumulate();
+
+        let a: f64 = xs.iter().zip(&w).map(|(x, w)| w * x.exp()).sum();
+        let b: f64 = xs.iter().map(|x| x * x).sum();
+
+        assert_approx_equal!(f.value, a * b, 1e-15);
+        for i in 0..3 {
+            let expected = w[i] * xs[i].exp() * b + a * 2.0 * xs[i];
+            assert_approx_equal!(grad.wrt(&x[i]), expected, 1e-14);
+        }
+        assert_eq!(g.sum(&x).accumulate().wrt(&x), vec![1.0; 3]);
+    }
+}
@@ -15,12 +15,17 @@ use std::fmt;
 /// thus the arrays have two elements.
 /// To deal with unary or nullary operations, we just adjust the weights
 /// (partials) and the dependencies (parents).
+/// Vector operations (e.g. a dot product) have any number of parents, which
+/// are stored in the graph's edge list and referenced by `edges`.
 #[derive(Clone, Copy, Debug)]
 pub struct Vertex {
     /// Array that contains the partial derivatives wrt to x and y.
     pub partials: [f64; 2],
     /// Array that contains the indices of the parent vertices.
     pub parents: [usize; 2],
+    /// Half-open range `[start, end)` into the graph's edge list holding the
+    /// (parent, partial) pairs of an n-ary operation. Empty otherwise.
+    pub edges: [usize; 2],
     // /// Operation.
     // pub operation: Operation,
 }
@@ -41,9 +46,13 @@ pub enum Arity {
     /// Binary operation (e.g. x + y).
     /// This has two parents.
     Binary,
+    /// N-ary operation (e.g. a dot product).
+    /// This has any number of parents.
+    Nary,
 }
 
 /// Enumeration for the operation type.
+#[allow(dead_code)]
 #[derive(Clone, Copy, Debug)]
 enum Operation {
     _ADD,
@@ -75,11 +84,17 @@ impl Vertex {
         self.parents
     }
 
+    /// Get the range of the vertex's n-ary edges in the graph's edge list.
+    pub fn get_edges(&self) -> std::ops::Range<usize> {
+        self.edges[0]..self.edges[1]
+    }
+
     /// Instantiate a new vertex from a binary operation.
     pub fn new_binary(partial_x: f64, parent_x: usize, partial_y: f64, parent_y: usize) -> Self {
         Self {
             partials: [partial_x, partial_y],
             parents: [parent_x, parent_y],
+            edges: [0; 2],
         }
     }
 
@@ -88,6 +103,7 @@ impl Vertex {
         Self {
             partials: [partial_x, 0.0],
             parents: [parent_x, 0],
+            edges: [0; 2],
         }
     }
 
@@ -96,13 +112,16 @@ impl Vertex {
         Self {
             partials: [0.0; 2],
             parents: [0; 2],
+            edges: [0; 2],
         }
     }
 }
 
 impl PartialEq for Vertex {
     fn eq(&self, other: &Self) -> bool {
-        self.partials == other.partials && self.parents == other.parents
+        self.partials == other.partials
+            && self.parents == other.parents
+            && self.edges == other.edges
     }
 }
 
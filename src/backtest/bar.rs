@@ -0,0 +1,130 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use time::Date;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Open, high, low, close and volume of one period.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bar {
+    /// Date of the bar.
+    pub date: Date,
+
+    /// Opening price.
+    pub open: f64,
+
+    /// Highest price.
+    pub high: f64,
+
+    /// Lowest price.
+    pub low: f64,
+
+    /// Closing price.
+    pub close: f64,
+
+    /// Traded volume.
+    pub volume: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Bar {
+    /// New bar.
+    pub fn new(date: Date, open: f64, high: f64, low: f64, close: f64, volume: f64) -> Self {
+        Self {
+            date,
+            open,
+            high,
+            low,
+            close,
+            volume,
+        }
+    }
+
+    /// Bars from a price history `DataFrame`, such as the one downloaded by
+    /// `YahooFinanceData::get_price_history`, with `date`, `open`, `high`,
+    /// `low`, `close` and `volume` columns. Rows with missing values are skipped.
+    #[cfg(feature = "data")]
+    pub fn from_frame(
+        frame: &polars::prelude::DataFrame,
+    ) -> Result<Vec<Self>, crate::data::DataError> {
+        use polars::prelude::DataType;
+
+        // Julian day number of the UNIX epoch (1970-01-01).
+        const UNIX_EPOCH_JULIAN_DAY: i32 = 2_440_588;
+
+        let column = |name: &str| -> Result<Vec<Option<f64>>, crate::data::DataError> {
+            Ok(frame
+                .column(name)?
+                .cast(&DataType::Float64)?
+                .f64()?
+                .into_iter()
+                .collect())
+        };
+
+        let dates: Vec<Option<i32>> = frame
+            .column("date")?
+            .cast(&DataType::Int32)?
+            .i32()?
+            .into_iter()
+            .collect();
+        let (open, high, low) = (column("open")?, column("high")?, column("low")?);
+        let (close, volume) = (column("close")?, column("volume")?);
+
+        Ok((0..frame.height())
+            .filter_map(|i| {
+                Some(Self {
+                    date: Date::from_julian_day(dates[i]? + UNIX_EPOCH_JULIAN_DAY).ok()?,
+                    open: open[i]?,
+                    high: high[i]?,
+                    low: low[i]?,
+                    close: close[i]?,
+                    volume: volume[i]?,
+                })
+            })
+            .collect())
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(all(test, feature = "data"))]
+mod tests_bar {
+    use super::*;
+    use polars::prelude::*;
+
+    #[test]
+    fn test_bars_from_frame() {
+        let frame = df!(
+            "date" => Series::new("date", [19723, 19724, 19725]).cast(&DataType::Date).unwrap(),
+            "open" => [100.0, 101.0, 102.0],
+            "high" => [101.0, 102.0, 103.0],
+            "low" => [99.0, 100.0, 101.0],
+            "close" => [Some(100.5), None, Some(102.5)],
+            "volume" => [1e6, 2e6, 3e6]
+        )
+        .unwrap();
+
+        let bars = Bar::from_frame(&frame).unwrap();
+
+        assert_eq!(bars.len(), 2);
+        assert_eq!(
+            bars[0].date,
+            Date::from_calendar_date(2024, time::Month::January, 1).unwrap()
+        );
+        assert_eq!(bars[1].close, 102.5);
+    }
+}
@@ -0,0 +1,88 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Commission models, shared with the portfolio optimizer.
+pub use crate::costs::Commission as CostModel;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Slippage models: the fill price is moved against the trade.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Slippage {
+    /// Fill at the quoted price.
+    #[default]
+    None,
+
+    /// Fraction of the price (e.g. 0.0005 for 5bp).
+    Proportional(f64),
+
+    /// Amount per unit.
+    PerShare(f64),
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Slippage {
+    /// Fill price of `quantity` units (negative for sales) quoted at `price`.
+    pub fn fill_price(&self, quantity: f64, price: f64) -> f64 {
+        let side = quantity.signum();
+
+        match *self {
+            Slippage::None => price,
+            Slippage::Proportional(rate) => price * (1.0 + side * rate),
+            Slippage::PerShare(amount) => price + side * amount,
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_costs {
+    use super::*;
+
+    #[test]
+    fn test_cost_models() {
+        assert_eq!(CostModel::None.cost(100.0, 50.0), 0.0);
+        assert_eq!(CostModel::Fixed(5.0).cost(-100.0, 50.0), 5.0);
+        assert_eq!(CostModel::Fixed(5.0).cost(0.0, 50.0), 0.0);
+        assert_approx_equal!(
+            CostModel::Proportional(0.001).cost(-100.0, 50.0),
+            5.0,
+            1e-12
+        );
+        assert_approx_equal!(CostModel::PerShare(0.01).cost(100.0, 50.0), 1.0, 1e-12);
+    }
+
+    #[test]
+    fn test_slippage() {
+        assert_eq!(Slippage::None.fill_price(100.0, 50.0), 50.0);
+        assert_approx_equal!(
+            Slippage::Proportional(0.01).fill_price(100.0, 50.0),
+            50.5,
+            1e-12
+        );
+        assert_approx_equal!(
+            Slippage::Proportional(0.01).fill_price(-100.0, 50.0),
+            49.5,
+            1e-12
+        );
+        assert_approx_equal!(
+            Slippage::PerShare(0.05).fill_price(-1.0, 50.0),
+            49.95,
+            1e-12
+        );
+    }
+}
@@ -0,0 +1,312 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::backtest::{Action, BacktestReport, Bar, Slippage, Strategy};
+use crate::costs::TransactionCosts;
+use thiserror::Error;
+use time::Date;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Backtest of a single-asset strategy.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Backtest {
+    /// Starting cash.
+    pub initial_cash: f64,
+
+    /// Transaction cost model.
+    pub costs: TransactionCosts,
+
+    /// Slippage model.
+    pub slippage: Slippage,
+}
+
+/// Cash and position of the account.
+///
+/// Cash may go negative (borrowing) and the position may be short;
+/// no margin requirements are enforced.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Account {
+    /// Cash balance.
+    pub cash: f64,
+
+    /// Units held (negative if short).
+    pub position: f64,
+
+    /// Cash plus position marked at the last close.
+    pub equity: f64,
+}
+
+/// Executed trade.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Trade {
+    /// Date of execution.
+    pub date: Date,
+
+    /// Units traded (negative for sales).
+    pub quantity: f64,
+
+    /// Fill price, including slippage.
+    pub price: f64,
+
+    /// Transaction cost paid.
+    pub cost: f64,
+}
+
+/// Backtest errors.
+#[derive(Debug, Clone, Copy, PartialEq, Error)]
+pub enum BacktestError {
+    /// No bars to run the backtest on.
+    #[error("No bars to backtest on.")]
+    NoData,
+
+    /// Bars are not in increasing date order.
+    #[error("Bars are not in increasing date order at {0}.")]
+    UnorderedBars(Date),
+
+    /// The features do not have one row per bar.
+    #[error("{features} rows of features for {bars} bars.")]
+    FeatureMismatch {
+        /// Number of bars.
+        bars: usize,
+
+        /// Rows of the feature matrix.
+        features: usize,
+    },
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Backtest {
+    /// New backtest without costs or slippage.
+    pub fn new(initial_cash: f64) -> Self {
+        Self {
+            initial_cash,
+            costs: TransactionCosts::new(),
+            slippage: Slippage::None,
+        }
+    }
+
+    /// Sets the transaction cost model (or just a commission model).
+    pub fn with_costs(mut self, costs: impl Into<TransactionCosts>) -> Self {
+        self.costs = costs.into();
+        self
+    }
+
+    /// Sets the slippage model.
+    pub fn with_slippage(mut self, slippage: Slippage) -> Self {
+        self.slippage = slippage;
+        self
+    }
+
+    /// Runs the strategy over the bars.
+    ///
+    /// The action returned at the close of a bar is executed at the open of
+    /// the next one; the action at the last bar is not executed.
+    pub fn run<S: Strategy>(
+        &self,
+        strategy: &mut S,
+        bars: &[Bar],
+    ) -> Result<BacktestReport, BacktestError> {
+        check_bars(bars)?;
+
+        let mut account = Account {
+            cash: self.initial_cash,
+            position: 0.0,
+            equity: self.initial_cash,
+        };
+        let mut pending = Action::Hold;
+        let mut trades = Vec::new();
+        let mut equity = Vec::with_capacity(bars.len());
+
+        for bar in bars {
+            if let Some(trade) = self.execute(pending, bar, &mut account) {
+                trades.push(trade);
+            }
+
+            account.equity = account.cash + account.position * bar.close;
+            equity.push(account.equity);
+
+            pending = strategy.on_bar(bar, &account);
+        }
+
+        Ok(BacktestReport {
+            dates: bars.iter().map(|bar| bar.date).collect(),
+            equity,
+            trades,
+            initial_cash: self.initial_cash,
+        })
+    }
+
+    /// Executes an action at the open of the bar.
+    pub(crate) fn execute(
+        &self,
+        action: Action,
+        bar: &Bar,
+        account: &mut Account,
+    ) -> Option<Trade> {
+        let quantity = match action {
+            Action::Hold => 0.0,
+            Action::Buy(units) => units,
+            Action::Sell(units) => -units,
+            Action::TargetPosition(units) => units - account.position,
+            Action::TargetWeight(weight) => {
+                let equity = account.cash + account.position * bar.open;
+                weight * equity / bar.open - account.position
+            }
+        };
+
+        if quantity == 0.0 || !quantity.is_finite() {
+            return None;
+        }
+
+        let price = self.slippage.fill_price(quantity, bar.open);
+        let cost = self.costs.cost(quantity, price, bar.volume);
+
+        account.cash -= quantity * price + cost;
+        account.position += quantity;
+
+        Some(Trade {
+            date: bar.date,
+            quantity,
+            price,
+            cost,
+        })
+    }
+}
+
+/// Checks that there are bars, in increasing date order.
+pub(crate) fn check_bars(bars: &[Bar]) -> Result<(), BacktestError> {
+    if bars.is_empty() {
+        return Err(BacktestError::NoData);
+    }
+    if let Some(pair) = bars.windows(2).find(|pair| pair[1].date <= pair[0].date) {
+        return Err(BacktestError::UnorderedBars(pair[1].date));
+    }
+
+    Ok(())
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_engine {
+    use super::*;
+    use crate::backtest::{BuyAndHold, CostModel, MovingAverageCrossover};
+    use crate::costs::SquareRootImpact;
+    use time::{Duration, Month};
+
+    fn bars(closes: &[f64]) -> Vec<Bar> {
+        let start = Date::from_calendar_date(2024, Month::January, 1).unwrap();
+
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, close)| {
+                Bar::new(
+                    start + Duration::days(i as i64),
+                    *close,
+                    *close,
+                    *close,
+                    *close,
+                    1e6,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_buy_and_hold() {
+        let bars = bars(&[100.0, 100.0, 110.0, 121.0]);
+        let report = Backtest::new(1_000.0).run(&mut BuyAndHold, &bars).unwrap();
+
+        // Signal at the first close, filled at the second open.
+        assert_eq!(report.trades.len(), 1);
+        assert_eq!(report.trades[0].date, bars[1].date);
+        assert_approx_equal!(report.trades[0].quantity, 10.0, 1e-12);
+        assert_approx_equal!(report.final_equity(), 1_210.0, 1e-9);
+        assert_approx_equal!(report.total_return(), 0.21, 1e-12);
+    }
+
+    #[test]
+    fn test_costs_and_slippage() {
+        let bars = bars(&[100.0, 100.0, 100.0]);
+        let report = Backtest::new(1_000.0)
+            .with_costs(CostModel::Fixed(1.0))
+            .with_slippage(Slippage::PerShare(0.5))
+            .run(&mut BuyAndHold, &bars)
+            .unwrap();
+
+        // 10 units filled at 100.5, marked at 100: lose 5 of slippage and 1 of costs.
+        let trade = report.trades[0];
+        assert_approx_equal!(trade.price, 100.5, 1e-12);
+        assert_approx_equal!(report.final_equity(), 1_000.0 - 10.0 * 0.5 - 1.0, 1e-9);
+        assert_approx_equal!(report.total_costs(), 1.0, 1e-12);
+    }
+
+    #[test]
+    fn test_spread_and_market_impact() {
+        let bars = bars(&[100.0, 100.0, 100.0]);
+        let costs = TransactionCosts::new()
+            .with_spread(0.002)
+            .with_impact(SquareRootImpact::new(1.0, 0.02));
+        let report = Backtest::new(1_000_000.0)
+            .with_costs(costs)
+            .run(&mut BuyAndHold, &bars)
+            .unwrap();
+
+        // 10,000 units against a volume of 1e6: 10bp of half-spread and
+        // 0.02 * sqrt(1%) = 20bp of impact on a notional of 1e6.
+        let trade = report.trades[0];
+        assert_approx_equal!(trade.quantity, 10_000.0, 1e-9);
+        assert_approx_equal!(trade.cost, 3_000.0, 1e-6);
+        assert_approx_equal!(report.final_equity(), 997_000.0, 1e-6);
+    }
+
+    #[test]
+    fn test_moving_average_crossover() {
+        // Rally then sell-off: the strategy buys during the rally and exits.
+        let mut closes: Vec<f64> = (0..30).map(|i| 100.0 + i as f64).collect();
+        closes.extend((0..30).map(|i| 129.0 - 2.0 * i as f64));
+
+        let report = Backtest::new(10_000.0)
+            .run(&mut MovingAverageCrossover::new(3, 10), &bars(&closes))
+            .unwrap();
+
+        assert_eq!(report.trades.len(), 2);
+        assert!(report.trades[0].quantity > 0.0);
+        assert!(report.trades[1].quantity < 0.0);
+        assert_approx_equal!(
+            report.trades[0].quantity + report.trades[1].quantity,
+            0.0,
+            1e-9
+        );
+    }
+
+    #[test]
+    fn test_backtest_errors() {
+        assert_eq!(
+            Backtest::new(1.0).run(&mut BuyAndHold, &[]).unwrap_err(),
+            BacktestError::NoData
+        );
+
+        let mut unordered = bars(&[1.0, 2.0]);
+        unordered.swap(0, 1);
+        assert!(matches!(
+            Backtest::new(1.0).run(&mut BuyAndHold, &unordered),
+            Err(BacktestError::UnorderedBars(_))
+        ));
+    }
+}
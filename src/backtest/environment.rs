@@ -0,0 +1,347 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::backtest::{
+    check_bars, Account, Action, Backtest, BacktestError, BacktestReport, Bar, Trade,
+};
+use nalgebra::DMatrix;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Step-by-step trading environment for reinforcement learning, in the
+/// style of OpenAI Gym.
+///
+/// An episode walks through the bars with the same execution rules as
+/// [`Backtest::run`]: the agent observes the state at the close of a bar,
+/// chooses a target weight (fraction of equity held in the asset), the
+/// trade is executed at the next open with the backtest's slippage and
+/// costs, and the reward is the change in equity up to the next close.
+#[derive(Debug, Clone)]
+pub struct MarketEnvironment {
+    backtest: Backtest,
+    bars: Vec<Bar>,
+    features: DMatrix<f64>,
+    index: usize,
+    account: Account,
+    equity: Vec<f64>,
+    trades: Vec<Trade>,
+}
+
+/// State observed at the close of a bar.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Observation {
+    /// Index of the bar.
+    pub index: usize,
+
+    /// Features of the bar.
+    pub features: Vec<f64>,
+
+    /// Fraction of equity held in the asset (negative if short).
+    pub weight: f64,
+
+    /// Cash plus position, marked at the close.
+    pub equity: f64,
+}
+
+/// Outcome of a step of the environment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transition {
+    /// State at the next close.
+    pub observation: Observation,
+
+    /// Profit and loss from the previous close, net of costs.
+    pub reward: f64,
+
+    /// Whether the last bar has been reached.
+    pub done: bool,
+
+    /// Trade executed at the open, if any.
+    pub trade: Option<Trade>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl MarketEnvironment {
+    /// New environment over the bars, with the cash, costs and slippage of
+    /// the backtest.
+    ///
+    /// The default feature is the simple return of the close (zero at the
+    /// first bar). An episode needs at least two bars.
+    pub fn new(backtest: Backtest, bars: &[Bar]) -> Result<Self, BacktestError> {
+        check_bars(bars)?;
+        if bars.len() < 2 {
+            return Err(BacktestError::NoData);
+        }
+
+        let features = DMatrix::from_fn(bars.len(), 1, |i, _| match i {
+            0 => 0.0,
+            _ => bars[i].close / bars[i - 1].close - 1.0,
+        });
+
+        let mut environment = Self {
+            backtest,
+            bars: bars.to_vec(),
+            features,
+            index: 0,
+            account: Account {
+                cash: backtest.initial_cash,
+                position: 0.0,
+                equity: backtest.initial_cash,
+            },
+            equity: Vec::with_capacity(bars.len()),
+            trades: Vec::new(),
+        };
+        environment.reset();
+
+        Ok(environment)
+    }
+
+    /// Replaces the features with engineered ones, one row per bar.
+    ///
+    /// Features must only use information available at the close of their
+    /// bar, or the agent will see the future.
+    pub fn with_features(mut self, features: DMatrix<f64>) -> Result<Self, BacktestError> {
+        if features.nrows() != self.bars.len() {
+            return Err(BacktestError::FeatureMismatch {
+                bars: self.bars.len(),
+                features: features.nrows(),
+            });
+        }
+
+        self.features = features;
+
+        Ok(self)
+    }
+
+    /// Number of features per bar.
+    pub fn n_features(&self) -> usize {
+        self.features.ncols()
+    }
+
+    /// Number of steps in an episode (one less than the number of bars).
+    pub fn n_steps(&self) -> usize {
+        self.bars.len() - 1
+    }
+
+    /// Starts a new episode, all in cash at the first close.
+    pub fn reset(&mut self) -> Observation {
+        let cash = self.backtest.initial_cash;
+
+        self.index = 0;
+        self.account = Account {
+            cash,
+            position: 0.0,
+            equity: cash,
+        };
+        self.equity = vec![cash];
+        self.trades.clear();
+
+        self.observation()
+    }
+
+    /// Whether the episode has reached the last bar.
+    pub fn is_done(&self) -> bool {
+        self.index + 1 == self.bars.len()
+    }
+
+    /// Trades to the target weight at the next open and moves to the next
+    /// close.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the episode is over; call [`MarketEnvironment::reset`] to
+    /// start a new one.
+    pub fn step(&mut self, target_weight: f64) -> Transition {
+        assert!(!self.is_done(), "Episode is over; reset the environment.");
+
+        self.index += 1;
+        let bar = self.bars[self.index];
+        let previous = self.account.equity;
+
+        let trade =
+            self.backtest
+                .execute(Action::TargetWeight(target_weight), &bar, &mut self.account);
+        self.trades.extend(trade);
+
+        self.account.equity = self.account.cash + self.account.position * bar.close;
+        self.equity.push(self.account.equity);
+
+        Transition {
+            observation: self.observation(),
+            reward: self.account.equity - previous,
+            done: self.is_done(),
+            trade,
+        }
+    }
+
+    /// Report of the episode so far.
+    pub fn report(&self) -> BacktestReport {
+        BacktestReport {
+            dates: self.bars[..=self.index]
+                .iter()
+                .map(|bar| bar.date)
+                .collect(),
+            equity: self.equity.clone(),
+            trades: self.trades.clone(),
+            initial_cash: self.backtest.initial_cash,
+        }
+    }
+
+    fn observation(&self) -> Observation {
+        let equity = self.account.equity;
+        let exposure = self.account.position * self.bars[self.index].close;
+
+        Observation {
+            index: self.index,
+            features: self.features.row(self.index).iter().copied().collect(),
+            weight: if equity == 0.0 {
+                0.0
+            } else {
+                exposure / equity
+            },
+            equity,
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_environment {
+    use super::*;
+    use crate::backtest::{BuyAndHold, CostModel};
+    use time::{Date, Duration, Month};
+
+    fn bars(closes: &[f64]) -> Vec<Bar> {
+        let start = Date::from_calendar_date(2024, Month::January, 1).unwrap();
+
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, close)| {
+                let open = if i == 0 { *close } else { closes[i - 1] };
+                Bar::new(
+                    start + Duration::days(i as i64),
+                    open,
+                    *close,
+                    *close,
+                    *close,
+                    1e6,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_episode() {
+        let bars = bars(&[100.0, 110.0, 99.0, 99.0]);
+        let mut environment = MarketEnvironment::new(Backtest::new(1_000.0), &bars).unwrap();
+
+        let observation = environment.reset();
+        assert_eq!(observation.index, 0);
+        assert_eq!(observation.features, vec![0.0]);
+        assert_eq!(observation.weight, 0.0);
+
+        // Long at 100, marked at 110.
+        let transition = environment.step(1.0);
+        assert_approx_equal!(transition.reward, 100.0, 1e-9);
+        assert_approx_equal!(transition.observation.features[0], 0.1, 1e-12);
+        assert_approx_equal!(transition.observation.weight, 1.0, 1e-12);
+        assert!(transition.trade.is_some() && !transition.done);
+
+        // Short 10 units at 110, marked at 99: the weight drifts.
+        let transition = environment.step(-1.0);
+        assert_approx_equal!(transition.reward, 110.0, 1e-9);
+        assert_approx_equal!(transition.observation.weight, -990.0 / 1_210.0, 1e-12);
+
+        let transition = environment.step(0.0);
+        assert!(transition.done);
+        assert_approx_equal!(transition.observation.equity, 1_210.0, 1e-9);
+
+        let report = environment.report();
+        assert_eq!(report.equity.len(), bars.len());
+        assert_eq!(report.trades.len(), 3);
+        assert_approx_equal!(report.total_return(), 0.21, 1e-12);
+
+        // Resetting starts over.
+        assert_eq!(environment.reset().equity, 1_000.0);
+        assert_eq!(environment.report().trades.len(), 0);
+    }
+
+    #[test]
+    fn test_matches_backtest() {
+        let closes: Vec<f64> = (0..50).map(|i| 100.0 + (i as f64 / 3.0).sin()).collect();
+        let bars = bars(&closes);
+        let backtest = Backtest::new(10_000.0).with_costs(CostModel::Proportional(0.001));
+
+        let expected = backtest.run(&mut BuyAndHold, &bars).unwrap();
+
+        let mut environment = MarketEnvironment::new(backtest, &bars).unwrap();
+        let mut observation = environment.reset();
+        while !environment.is_done() {
+            let weight = if observation.weight == 0.0 {
+                1.0
+            } else {
+                observation.weight
+            };
+            observation = environment.step(weight).observation;
+        }
+        let report = environment.report();
+
+        // Keeping the current weight only trades rounding errors after the
+        // first bar.
+        assert_approx_equal!(report.final_equity(), expected.final_equity(), 1e-6);
+        assert_eq!(report.equity[0], expected.equity[0]);
+    }
+
+    #[test]
+    fn test_features_and_errors() {
+        let bars = bars(&[100.0, 101.0, 102.0]);
+        let features = DMatrix::from_row_slice(3, 2, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+        let mut environment = MarketEnvironment::new(Backtest::new(1.0), &bars)
+            .unwrap()
+            .with_features(features)
+            .unwrap();
+        assert_eq!(environment.n_features(), 2);
+        assert_eq!(environment.n_steps(), 2);
+        assert_eq!(environment.step(0.0).observation.features, vec![3.0, 4.0]);
+
+        assert_eq!(
+            MarketEnvironment::new(Backtest::new(1.0), &bars)
+                .unwrap()
+                .with_features(DMatrix::zeros(2, 1))
+                .unwrap_err(),
+            BacktestError::FeatureMismatch {
+                bars: 3,
+                features: 2
+            }
+        );
+        assert_eq!(
+            MarketEnvironment::new(Backtest::new(1.0), &bars[..1]).unwrap_err(),
+            BacktestError::NoData
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_step_after_done() {
+        let bars = bars(&[100.0, 101.0]);
+        let mut environment = MarketEnvironment::new(Backtest::new(1.0), &bars).unwrap();
+
+        environment.step(1.0);
+        environment.step(1.0);
+    }
+}
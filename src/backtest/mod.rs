@@ -0,0 +1,71 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Backtesting of trading strategies.
+//!
+//! A [`Strategy`] is fed price bars one at a time and returns an [`Action`].
+//! Actions are executed at the open of the next bar (so a strategy cannot
+//! trade on prices it has not yet seen), with slippage and transaction
+//! costs, and the account is marked to market at each close.
+//!
+//! ```
+//! use RustQuant::backtest::*;
+//! use time::{Date, Duration, Month};
+//!
+//! let start = Date::from_calendar_date(2024, Month::January, 1).unwrap();
+//! let bars: Vec<Bar> = (0..100)
+//!     .map(|i| {
+//!         let price = 100.0 + (i as f64 / 5.0).sin() * 10.0;
+//!         Bar::new(start + Duration::days(i), price, price, price, price, 1e6)
+//!     })
+//!     .collect();
+//!
+//! let report = Backtest::new(10_000.0)
+//!     .with_costs(CostModel::Proportional(0.001))
+//!     .with_slippage(Slippage::Proportional(0.0005))
+//!     .run(&mut MovingAverageCrossover::new(5, 20), &bars)
+//!     .unwrap();
+//!
+//! println!("Total return: {:.2}%", 100.0 * report.total_return());
+//! println!("Max drawdown: {:.2}%", 100.0 * report.max_drawdown());
+//! println!("Sharpe ratio: {:.2}", report.sharpe_ratio(252.0));
+//! ```
+//!
+//! For reinforcement learning, a [`MarketEnvironment`] runs the same
+//! engine one step at a time: the agent observes features and its position
+//! at each close, picks a target weight, and is rewarded with the P&L up to
+//! the next close. [`QLearningAgent`] is a tabular reference agent.
+
+/// Price bars.
+pub mod bar;
+pub use bar::*;
+
+/// Transaction cost and slippage models.
+pub mod costs;
+pub use costs::*;
+
+/// Reinforcement learning environment.
+pub mod environment;
+pub use environment::*;
+
+/// Backtesting engine and account.
+pub mod engine;
+pub use engine::*;
+
+/// Tabular Q-learning agent.
+pub mod q_learning;
+pub use q_learning::*;
+
+/// Backtest report: equity curve and performance statistics.
+pub mod report;
+pub use report::*;
+
+/// Strategy trait and example strategies.
+pub mod strategy;
+pub use strategy::*;
@@ -0,0 +1,299 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::backtest::{BacktestReport, MarketEnvironment, Observation};
+use crate::math::Pcg64;
+use rand::Rng;
+use std::collections::HashMap;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Tabular Q-learning agent (Watkins, 1989), a reference agent for the
+/// [`MarketEnvironment`].
+///
+/// Each feature is discretized by a set of thresholds, and the state is the
+/// tuple of feature bins together with the current position (the action
+/// nearest to the current weight). The actions are a fixed set of target
+/// weights. After each step the action value is updated towards the reward
+/// plus the discounted value of the best action in the next state:
+///
+/// $$
+/// Q(s, a) \leftarrow Q(s, a) + \alpha \left( r + \gamma \max_{a'} Q(s', a') - Q(s, a) \right)
+/// $$
+///
+/// and actions are chosen $\epsilon$-greedily during training.
+#[derive(Debug, Clone)]
+pub struct QLearningAgent {
+    /// Target weights the agent chooses from.
+    pub actions: Vec<f64>,
+
+    /// Increasing thresholds discretizing each feature.
+    pub thresholds: Vec<Vec<f64>>,
+
+    /// Learning rate, $\alpha$.
+    pub learning_rate: f64,
+
+    /// Discount factor, $\gamma$.
+    pub discount: f64,
+
+    /// Probability of a random action during training, $\epsilon$.
+    pub exploration: f64,
+
+    table: HashMap<Vec<usize>, Vec<f64>>,
+    rng: Pcg64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl QLearningAgent {
+    /// New agent choosing among the target weights `actions`, with one set
+    /// of thresholds per feature.
+    ///
+    /// Defaults: learning rate 0.1, discount 0.9 and exploration 0.1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no actions or the thresholds are not increasing.
+    pub fn new(actions: Vec<f64>, thresholds: Vec<Vec<f64>>) -> Self {
+        assert!(!actions.is_empty(), "Need at least one action.");
+        assert!(
+            thresholds
+                .iter()
+                .all(|t| t.windows(2).all(|pair| pair[0] < pair[1])),
+            "Thresholds must be increasing."
+        );
+
+        Self {
+            actions,
+            thresholds,
+            learning_rate: 0.1,
+            discount: 0.9,
+            exploration: 0.1,
+            table: HashMap::new(),
+            rng: Pcg64::stream(0, 0),
+        }
+    }
+
+    /// Sets the learning rate.
+    pub fn with_learning_rate(mut self, learning_rate: f64) -> Self {
+        self.learning_rate = learning_rate;
+        self
+    }
+
+    /// Sets the discount factor.
+    pub fn with_discount(mut self, discount: f64) -> Self {
+        self.discount = discount;
+        self
+    }
+
+    /// Sets the exploration probability.
+    pub fn with_exploration(mut self, exploration: f64) -> Self {
+        self.exploration = exploration;
+        self
+    }
+
+    /// Sets the seed of the exploration.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = Pcg64::stream(seed, 0);
+        self
+    }
+
+    /// Discrete state of an observation: the bin of each feature, then the
+    /// index of the action nearest to the current weight.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the observation does not have one feature per set of
+    /// thresholds.
+    pub fn state(&self, observation: &Observation) -> Vec<usize> {
+        assert_eq!(
+            observation.features.len(),
+            self.thresholds.len(),
+            "One set of thresholds per feature."
+        );
+
+        let position = (0..self.actions.len())
+            .min_by(|&a, &b| {
+                let distance = |i: usize| (self.actions[i] - observation.weight).abs();
+                distance(a).total_cmp(&distance(b))
+            })
+            .unwrap_or(0);
+
+        observation
+            .features
+            .iter()
+            .zip(&self.thresholds)
+            .map(|(x, thresholds)| thresholds.partition_point(|t| t < x))
+            .chain(std::iter::once(position))
+            .collect()
+    }
+
+    /// Action values of the state of the observation (zero if unvisited).
+    pub fn q_values(&self, observation: &Observation) -> Vec<f64> {
+        self.table
+            .get(&self.state(observation))
+            .cloned()
+            .unwrap_or_else(|| vec![0.0; self.actions.len()])
+    }
+
+    /// Target weight of the best action for the observation.
+    pub fn greedy_action(&self, observation: &Observation) -> f64 {
+        self.actions[argmax(&self.q_values(observation))]
+    }
+
+    /// Trains the agent for a number of episodes, returning the total
+    /// reward of each.
+    pub fn train(&mut self, environment: &mut MarketEnvironment, episodes: usize) -> Vec<f64> {
+        (0..episodes)
+            .map(|_| {
+                let mut state = self.state(&environment.reset());
+                let mut total = 0.0;
+
+                while !environment.is_done() {
+                    let action = match self.rng.gen::<f64>() < self.exploration {
+                        true => self.rng.gen_range(0..self.actions.len()),
+                        false => argmax(self.values(&state)),
+                    };
+
+                    let transition = environment.step(self.actions[action]);
+                    let next = self.state(&transition.observation);
+                    let future = match transition.done {
+                        true => 0.0,
+                        false => self.values(&next).iter().copied().fold(f64::MIN, f64::max),
+                    };
+
+                    let target = transition.reward + self.discount * future;
+                    let learning_rate = self.learning_rate;
+                    let q = &mut self.values(&state)[action];
+                    *q += learning_rate * (target - *q);
+
+                    total += transition.reward;
+                    state = next;
+                }
+
+                total
+            })
+            .collect()
+    }
+
+    /// Runs an episode with the greedy policy, without learning.
+    pub fn evaluate(&self, environment: &mut MarketEnvironment) -> BacktestReport {
+        let mut observation = environment.reset();
+
+        while !environment.is_done() {
+            observation = environment
+                .step(self.greedy_action(&observation))
+                .observation;
+        }
+
+        environment.report()
+    }
+
+    fn values(&mut self, state: &[usize]) -> &mut Vec<f64> {
+        let n = self.actions.len();
+
+        self.table
+            .entry(state.to_vec())
+            .or_insert_with(|| vec![0.0; n])
+    }
+}
+
+/// Index of the largest value (the first one on ties).
+fn argmax(values: &[f64]) -> usize {
+    values
+        .iter()
+        .enumerate()
+        .fold((0, f64::MIN), |best, (i, &v)| match v > best.1 {
+            true => (i, v),
+            false => best,
+        })
+        .0
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_q_learning {
+    use super::*;
+    use crate::backtest::{Backtest, Bar};
+    use time::{Date, Duration, Month};
+
+    /// Trending prices: runs of five 1% rises followed by five 1% falls, so
+    /// the last return predicts the next one four times out of five.
+    fn trending_bars(n: usize) -> Vec<Bar> {
+        let start = Date::from_calendar_date(2024, Month::January, 1).unwrap();
+        let mut close = 100.0;
+
+        (0..n)
+            .map(|i| {
+                let open = close;
+                if i > 0 {
+                    close *= if (i - 1) / 5 % 2 == 0 { 1.01 } else { 0.99 };
+                }
+                Bar::new(
+                    start + Duration::days(i as i64),
+                    open,
+                    close,
+                    close,
+                    close,
+                    1e6,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_state() {
+        let agent = QLearningAgent::new(vec![-1.0, 0.0, 1.0], vec![vec![-0.01, 0.01]]);
+        let observation = |x: f64, weight: f64| Observation {
+            index: 0,
+            features: vec![x],
+            weight,
+            equity: 1.0,
+        };
+
+        assert_eq!(agent.state(&observation(-0.02, 0.0)), vec![0, 1]);
+        assert_eq!(agent.state(&observation(0.0, 0.9)), vec![1, 2]);
+        assert_eq!(agent.state(&observation(0.05, -1.2)), vec![2, 0]);
+        assert_eq!(agent.q_values(&observation(0.0, 0.0)), vec![0.0; 3]);
+    }
+
+    #[test]
+    fn test_learns_trend_following() {
+        let bars = trending_bars(200);
+        let mut environment = MarketEnvironment::new(Backtest::new(10_000.0), &bars).unwrap();
+
+        let mut agent = QLearningAgent::new(vec![-1.0, 0.0, 1.0], vec![vec![0.0]]).with_seed(42);
+        let rewards = agent.train(&mut environment, 200);
+
+        assert_eq!(rewards.len(), 200);
+        assert!(rewards[150..].iter().sum::<f64>() > rewards[..50].iter().sum::<f64>());
+
+        // Long after a rise, short after a fall, whatever the position.
+        for weight in [-1.0, 0.0, 1.0] {
+            let observation = |x: f64| Observation {
+                index: 0,
+                features: vec![x],
+                weight,
+                equity: 10_000.0,
+            };
+            assert_eq!(agent.greedy_action(&observation(0.01)), 1.0);
+            assert_eq!(agent.greedy_action(&observation(-0.01)), -1.0);
+        }
+
+        let report = agent.evaluate(&mut environment);
+        assert!(report.total_return() > 0.5);
+    }
+}
@@ -0,0 +1,200 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::backtest::Trade;
+use crate::performance::{self, PerformanceError, PerformanceStatistics};
+use time::Date;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Result of a backtest.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BacktestReport {
+    /// Dates of the bars.
+    pub dates: Vec<Date>,
+
+    /// Equity at each close (the equity curve).
+    pub equity: Vec<f64>,
+
+    /// Executed trades.
+    pub trades: Vec<Trade>,
+
+    /// Starting cash.
+    pub initial_cash: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl BacktestReport {
+    /// Equity at the last close.
+    pub fn final_equity(&self) -> f64 {
+        self.equity.last().copied().unwrap_or(self.initial_cash)
+    }
+
+    /// Total return over the backtest.
+    pub fn total_return(&self) -> f64 {
+        self.final_equity() / self.initial_cash - 1.0
+    }
+
+    /// Simple returns of the equity curve, starting from the initial cash.
+    pub fn returns(&self) -> Vec<f64> {
+        std::iter::once(self.initial_cash)
+            .chain(self.equity.iter().copied())
+            .collect::<Vec<_>>()
+            .windows(2)
+            .map(|pair| pair[1] / pair[0] - 1.0)
+            .collect()
+    }
+
+    /// Drawdown at each close, as a (non-negative) fraction of the running peak.
+    pub fn drawdowns(&self) -> Vec<f64> {
+        let mut peak = self.initial_cash;
+
+        self.equity
+            .iter()
+            .map(|equity| {
+                peak = peak.max(*equity);
+                1.0 - equity / peak
+            })
+            .collect()
+    }
+
+    /// Largest drawdown.
+    pub fn max_drawdown(&self) -> f64 {
+        self.drawdowns().into_iter().fold(0.0, f64::max)
+    }
+
+    /// Plots the equity curve, against the number of days since the first
+    /// bar, to a PNG or SVG file.
+    #[cfg(feature = "plot")]
+    pub fn plot(&self, path: impl AsRef<std::path::Path>) -> Result<(), crate::plot::PlotError> {
+        let first = self.dates.first().ok_or(crate::plot::PlotError::NoData)?;
+        let points = self
+            .dates
+            .iter()
+            .zip(&self.equity)
+            .map(|(date, equity)| ((*date - *first).whole_days() as f64, *equity))
+            .collect();
+
+        crate::plot::Chart::new("Equity curve")
+            .with_labels(&format!("Days since {first}"), "Equity")
+            .with_series("Equity", points)
+            .save(path)
+    }
+
+    /// Total transaction costs paid.
+    pub fn total_costs(&self) -> f64 {
+        self.trades.iter().map(|trade| trade.cost).sum()
+    }
+
+    /// Traded notional divided by the average equity.
+    pub fn turnover(&self) -> f64 {
+        let traded: f64 = self
+            .trades
+            .iter()
+            .map(|trade| (trade.quantity * trade.price).abs())
+            .sum();
+        let average = self.equity.iter().sum::<f64>() / self.equity.len().max(1) as f64;
+
+        traded / average
+    }
+
+    /// Annualised Sharpe ratio (zero risk-free rate), with
+    /// `periods_per_year` bars per year (e.g. 252 for daily bars).
+    pub fn sharpe_ratio(&self, periods_per_year: f64) -> f64 {
+        performance::sharpe_ratio(&self.returns(), 0.0, periods_per_year)
+    }
+
+    /// Annualised Sortino ratio (zero target return), with
+    /// `periods_per_year` bars per year.
+    pub fn sortino_ratio(&self, periods_per_year: f64) -> f64 {
+        performance::sortino_ratio(&self.returns(), 0.0, periods_per_year)
+    }
+
+    /// Performance statistics of the equity curve returns.
+    pub fn statistics(
+        &self,
+        periods_per_year: f64,
+    ) -> Result<PerformanceStatistics, PerformanceError> {
+        PerformanceStatistics::new(&self.returns(), periods_per_year)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_report {
+    use super::*;
+    use time::Month;
+
+    fn report(equity: Vec<f64>) -> BacktestReport {
+        let date = Date::from_calendar_date(2024, Month::January, 1).unwrap();
+
+        BacktestReport {
+            dates: vec![date; equity.len()],
+            equity,
+            trades: vec![Trade {
+                date,
+                quantity: -5.0,
+                price: 100.0,
+                cost: 2.5,
+            }],
+            initial_cash: 100.0,
+        }
+    }
+
+    #[test]
+    fn test_returns_and_drawdowns() {
+        let report = report(vec![110.0, 99.0, 121.0, 108.9]);
+
+        assert_approx_equal!(report.returns()[0], 0.1, 1e-12);
+        assert_approx_equal!(report.returns()[1], -0.1, 1e-12);
+        assert_approx_equal!(report.total_return(), 0.089, 1e-12);
+
+        let drawdowns = report.drawdowns();
+        assert_approx_equal!(drawdowns[1], 0.1, 1e-12);
+        assert_approx_equal!(drawdowns[2], 0.0, 1e-12);
+        assert_approx_equal!(report.max_drawdown(), 0.1, 1e-12);
+
+        assert_approx_equal!(report.total_costs(), 2.5, 1e-12);
+        assert_approx_equal!(report.turnover(), 500.0 / 109.725, 1e-12);
+    }
+
+    #[test]
+    fn test_sharpe_and_sortino() {
+        let report = report(vec![110.0, 99.0, 121.0, 108.9]);
+        let returns = [0.1, -0.1, 2.0 / 9.0, -0.1];
+
+        let mean = returns.iter().sum::<f64>() / 4.0;
+        let sd = (returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / 3.0).sqrt();
+        let downside = (0.02_f64 / 4.0).sqrt();
+
+        assert_approx_equal!(report.sharpe_ratio(1.0), mean / sd, 1e-12);
+        assert_approx_equal!(report.sharpe_ratio(4.0), 2.0 * mean / sd, 1e-12);
+        assert_approx_equal!(report.sortino_ratio(1.0), mean / downside, 1e-12);
+    }
+
+    #[cfg(feature = "plot")]
+    #[test]
+    fn test_report_plot() {
+        let path = std::env::temp_dir().join("rustquant_equity.svg");
+
+        report(vec![110.0, 99.0, 121.0]).plot(&path).unwrap();
+        assert!(std::fs::metadata(&path).is_ok());
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(report(vec![]).plot(&path).is_err());
+    }
+}
@@ -0,0 +1,106 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::backtest::{Account, Bar};
+use std::collections::VecDeque;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Trading decision of a strategy, executed at the next bar's open.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Action {
+    /// Do nothing.
+    #[default]
+    Hold,
+
+    /// Buy a number of units.
+    Buy(f64),
+
+    /// Sell a number of units.
+    Sell(f64),
+
+    /// Trade to hold the given number of units (negative for short).
+    TargetPosition(f64),
+
+    /// Trade to hold the given fraction of equity (negative for short).
+    TargetWeight(f64),
+}
+
+/// Trading strategy, fed one bar at a time.
+pub trait Strategy {
+    /// Decision at the close of `bar`, given the current account.
+    fn on_bar(&mut self, bar: &Bar, account: &Account) -> Action;
+}
+
+/// Fully invests the account at the first bar and holds.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BuyAndHold;
+
+/// Long when the fast moving average of closes is above the slow one,
+/// flat otherwise.
+#[derive(Debug, Clone)]
+pub struct MovingAverageCrossover {
+    fast: usize,
+    slow: usize,
+    closes: VecDeque<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Strategy for BuyAndHold {
+    fn on_bar(&mut self, _bar: &Bar, account: &Account) -> Action {
+        if account.position == 0.0 {
+            Action::TargetWeight(1.0)
+        } else {
+            Action::Hold
+        }
+    }
+}
+
+impl MovingAverageCrossover {
+    /// New crossover strategy with `fast` and `slow` moving average windows.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fast` is zero or not shorter than `slow`.
+    pub fn new(fast: usize, slow: usize) -> Self {
+        assert!(fast > 0 && fast < slow, "Require 0 < fast < slow.");
+
+        Self {
+            fast,
+            slow,
+            closes: VecDeque::with_capacity(slow),
+        }
+    }
+}
+
+impl Strategy for MovingAverageCrossover {
+    fn on_bar(&mut self, bar: &Bar, account: &Account) -> Action {
+        if self.closes.len() == self.slow {
+            self.closes.pop_front();
+        }
+        self.closes.push_back(bar.close);
+
+        if self.closes.len() < self.slow {
+            return Action::Hold;
+        }
+
+        let mean = |n: usize| self.closes.iter().rev().take(n).sum::<f64>() / n as f64;
+
+        match (mean(self.fast) > mean(self.slow), account.position > 0.0) {
+            (true, false) => Action::TargetWeight(1.0),
+            (false, true) => Action::TargetPosition(0.0),
+            _ => Action::Hold,
+        }
+    }
+}
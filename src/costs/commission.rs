@@ -0,0 +1,85 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Commission models.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Commission {
+    /// No commission.
+    #[default]
+    None,
+
+    /// Fixed amount per trade.
+    Fixed(f64),
+
+    /// Fraction of the traded notional (e.g. 0.001 for 10bp).
+    Proportional(f64),
+
+    /// Amount per unit traded.
+    PerShare(f64),
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Commission {
+    /// Commission on trading `quantity` units (negative for sales) at `price`.
+    pub fn cost(&self, quantity: f64, price: f64) -> f64 {
+        if quantity == 0.0 {
+            return 0.0;
+        }
+
+        match *self {
+            Commission::None => 0.0,
+            Commission::Fixed(amount) => amount,
+            Commission::Proportional(rate) => rate * (quantity * price).abs(),
+            Commission::PerShare(amount) => amount * quantity.abs(),
+        }
+    }
+
+    /// Commission as a fraction of the notional traded at `price`.
+    ///
+    /// Fixed commissions do not scale with the trade size and have no rate.
+    pub fn rate(&self, price: f64) -> f64 {
+        match *self {
+            Commission::None | Commission::Fixed(_) => 0.0,
+            Commission::Proportional(rate) => rate,
+            Commission::PerShare(amount) => amount / price,
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_commission {
+    use super::*;
+
+    #[test]
+    fn test_commission_rates() {
+        assert_eq!(Commission::Fixed(5.0).rate(50.0), 0.0);
+        assert_eq!(Commission::Proportional(0.001).rate(50.0), 0.001);
+        assert_approx_equal!(Commission::PerShare(0.01).rate(50.0), 0.0002, 1e-15);
+
+        // The rate reproduces the cost of proportional and per-share commissions.
+        for commission in [Commission::Proportional(0.001), Commission::PerShare(0.01)] {
+            assert_approx_equal!(
+                commission.cost(-300.0, 40.0),
+                commission.rate(40.0) * 300.0 * 40.0,
+                1e-12
+            );
+        }
+    }
+}
@@ -0,0 +1,82 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Square-root market impact: trading $q$ units against a volume $V$
+/// moves the average fill price by a fraction $Y \sigma \sqrt{|q| / V}$
+/// of the price, where $\sigma$ is the volatility of the asset over the
+/// period the volume is measured on (e.g. daily volatility and volume).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SquareRootImpact {
+    /// Impact coefficient $Y$, of order one.
+    pub coefficient: f64,
+
+    /// Volatility $\sigma$ of the asset over the volume period.
+    pub volatility: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl SquareRootImpact {
+    /// New square-root impact model.
+    pub fn new(coefficient: f64, volatility: f64) -> Self {
+        Self {
+            coefficient,
+            volatility,
+        }
+    }
+
+    /// Price impact of trading `quantity` units against `volume`, as a
+    /// fraction of the price.
+    ///
+    /// Zero if the volume is not positive (unknown).
+    pub fn impact(&self, quantity: f64, volume: f64) -> f64 {
+        match volume > 0.0 {
+            true => self.coefficient * self.volatility * (quantity.abs() / volume).sqrt(),
+            false => 0.0,
+        }
+    }
+
+    /// Cost of trading `quantity` units at `price` against `volume`.
+    pub fn cost(&self, quantity: f64, price: f64, volume: f64) -> f64 {
+        self.impact(quantity, volume) * (quantity * price).abs()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_market_impact {
+    use super::*;
+
+    #[test]
+    fn test_square_root_impact() {
+        let model = SquareRootImpact::new(0.5, 0.02);
+
+        // Trading 1% of the volume moves the price by 0.5 * 2% * 10% = 10bp.
+        assert_approx_equal!(model.impact(10_000.0, 1e6), 0.001, 1e-15);
+        assert_approx_equal!(model.cost(-10_000.0, 20.0, 1e6), 200.0, 1e-10);
+
+        // Four times the size doubles the impact: costs grow as |q|^1.5.
+        assert_approx_equal!(
+            model.cost(40_000.0, 20.0, 1e6),
+            8.0 * model.cost(10_000.0, 20.0, 1e6),
+            1e-9
+        );
+
+        assert_eq!(model.cost(10_000.0, 20.0, 0.0), 0.0);
+    }
+}
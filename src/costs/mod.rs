@@ -0,0 +1,47 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Transaction cost and market impact models.
+//!
+//! The cost of a trade of $q$ units at price $P$ with traded volume $V$
+//! is the sum of
+//!
+//! - a commission: fixed per trade, proportional to the notional, or per unit;
+//! - half the bid-ask spread $s$, paid on the notional: $\frac{s}{2} |q| P$;
+//! - square-root market impact, $Y \sigma \sqrt{|q| / V} \, |q| P$.
+//!
+//! The same [`TransactionCosts`] are charged on fills by the
+//! [`Backtest`](crate::backtest::Backtest) engine and, through their
+//! [`TradingCostRates`], penalise turnover in
+//! [`turnover_penalized_weights`](crate::portfolio::turnover_penalized_weights).
+//!
+//! ```
+//! use RustQuant::costs::*;
+//!
+//! let costs = TransactionCosts::new()
+//!     .with_commission(Commission::Proportional(0.0005))
+//!     .with_spread(0.001)
+//!     .with_impact(SquareRootImpact::new(1.0, 0.02));
+//!
+//! // Buy 10,000 shares at 50 when 1,000,000 trade: 5bp + 5bp + 20bp.
+//! let cost = costs.cost(10_000.0, 50.0, 1e6);
+//! assert!((cost - 0.003 * 500_000.0).abs() < 1e-6);
+//! ```
+
+/// Commission models.
+pub mod commission;
+pub use commission::*;
+
+/// Square-root market impact model.
+pub mod market_impact;
+pub use market_impact::*;
+
+/// Combined transaction cost model.
+pub mod transaction_costs;
+pub use transaction_costs::*;
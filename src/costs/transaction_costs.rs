@@ -0,0 +1,168 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::costs::{Commission, SquareRootImpact};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Transaction costs: commission, half the bid-ask spread and market impact.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TransactionCosts {
+    /// Commission model.
+    pub commission: Commission,
+
+    /// Bid-ask spread as a fraction of the price (e.g. 0.001 for 10bp);
+    /// half of it is paid on each trade.
+    pub spread: f64,
+
+    /// Market impact model, if any.
+    pub impact: Option<SquareRootImpact>,
+}
+
+/// Costs of trading a fraction $\Delta$ of the portfolio value in an
+/// asset, as a fraction of the portfolio value:
+/// $c(\Delta) = a |\Delta| + b |\Delta|^{3/2}$.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TradingCostRates {
+    /// Linear rate $a$: proportional commission and half-spread.
+    pub linear: f64,
+
+    /// Impact rate $b$ of the square-root impact model.
+    pub impact: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl TransactionCosts {
+    /// No transaction costs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the commission model.
+    pub fn with_commission(mut self, commission: Commission) -> Self {
+        self.commission = commission;
+        self
+    }
+
+    /// Sets the relative bid-ask spread.
+    pub fn with_spread(mut self, spread: f64) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    /// Sets the market impact model.
+    pub fn with_impact(mut self, impact: SquareRootImpact) -> Self {
+        self.impact = Some(impact);
+        self
+    }
+
+    /// Cost of trading `quantity` units (negative for sales) at `price`,
+    /// against a traded `volume` (only used by the impact model).
+    pub fn cost(&self, quantity: f64, price: f64, volume: f64) -> f64 {
+        if quantity == 0.0 {
+            return 0.0;
+        }
+
+        let spread = 0.5 * self.spread * (quantity * price).abs();
+        let impact = self
+            .impact
+            .map_or(0.0, |impact| impact.cost(quantity, price, volume));
+
+        self.commission.cost(quantity, price) + spread + impact
+    }
+
+    /// Cost rates of an asset trading at `price` with `volume` units traded
+    /// per period, for a portfolio worth `portfolio_value`.
+    ///
+    /// Trading a fraction $\Delta$ of the portfolio is trading
+    /// $q = \Delta W / P$ units, so the impact rate is
+    /// $Y \sigma \sqrt{W / (P V)}$. Fixed commissions are not included.
+    pub fn rates(&self, price: f64, volume: f64, portfolio_value: f64) -> TradingCostRates {
+        TradingCostRates {
+            linear: self.commission.rate(price) + 0.5 * self.spread,
+            impact: self
+                .impact
+                .map_or(0.0, |impact| impact.impact(portfolio_value / price, volume)),
+        }
+    }
+}
+
+impl From<Commission> for TransactionCosts {
+    fn from(commission: Commission) -> Self {
+        Self::new().with_commission(commission)
+    }
+}
+
+impl TradingCostRates {
+    /// New cost rates.
+    pub fn new(linear: f64, impact: f64) -> Self {
+        Self { linear, impact }
+    }
+
+    /// Cost of trading a fraction `trade` of the portfolio value.
+    pub fn cost(&self, trade: f64) -> f64 {
+        let size = trade.abs();
+
+        self.linear * size + self.impact * size * size.sqrt()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_transaction_costs {
+    use super::*;
+
+    fn costs() -> TransactionCosts {
+        TransactionCosts::new()
+            .with_commission(Commission::PerShare(0.01))
+            .with_spread(0.002)
+            .with_impact(SquareRootImpact::new(1.0, 0.02))
+    }
+
+    #[test]
+    fn test_transaction_costs() {
+        let costs = costs();
+
+        // 40,000 shares at 25 against 1,000,000: 1,000,000 notional,
+        // 400 commission, 1,000 half-spread and 0.02 * 0.2 * 1e6 impact.
+        assert_approx_equal!(costs.cost(-40_000.0, 25.0, 1e6), 5_400.0, 1e-8);
+        assert_eq!(costs.cost(0.0, 25.0, 1e6), 0.0);
+
+        // Without volume information only commission and spread are paid.
+        assert_approx_equal!(costs.cost(-40_000.0, 25.0, 0.0), 1_400.0, 1e-8);
+
+        assert_eq!(
+            TransactionCosts::from(Commission::Fixed(1.0)).cost(1.0, 25.0, 1e6),
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_trading_cost_rates() {
+        let costs = costs();
+        let rates = costs.rates(25.0, 1e6, 4e6);
+
+        assert_approx_equal!(rates.linear, 0.0004 + 0.001, 1e-15);
+
+        // Trading a quarter of a 4,000,000 portfolio is the trade above.
+        assert_approx_equal!(
+            rates.cost(-0.25) * 4e6,
+            costs.cost(-40_000.0, 25.0, 1e6),
+            1e-8
+        );
+    }
+}
@@ -0,0 +1,209 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use std::fmt;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// How an interest rate accrues over a year fraction $t$, i.e. the
+/// discount factor a rate $r$ implies:
+///
+/// | Compounding  | Discount factor          |
+/// |--------------|--------------------------|
+/// | `Continuous` | $e^{-r t}$               |
+/// | `Annual`     | $(1 + r)^{-t}$           |
+/// | `SemiAnnual` | $(1 + r / 2)^{-2 t}$     |
+/// | `Simple`     | $1 / (1 + r t)$          |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Compounding {
+    /// Continuous compounding.
+    #[default]
+    Continuous,
+
+    /// Compounded once a year.
+    Annual,
+
+    /// Compounded twice a year.
+    SemiAnnual,
+
+    /// Simple (money market) interest, without compounding.
+    Simple,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Compounding {
+    /// Continuously compounded rate equivalent to `rate` over `t` years.
+    ///
+    /// Simple rates depend on the horizon; at $t = 0$ they equal the
+    /// continuous rate.
+    pub fn to_continuous(&self, rate: f64, t: f64) -> f64 {
+        match self {
+            Self::Continuous => rate,
+            Self::Annual => rate.ln_1p(),
+            Self::SemiAnnual => 2.0 * (0.5 * rate).ln_1p(),
+            Self::Simple if t > 0.0 => (rate * t).ln_1p() / t,
+            Self::Simple => rate,
+        }
+    }
+
+    /// Rate in this compounding equivalent to the continuously compounded
+    /// `rate` over `t` years.
+    pub fn from_continuous(&self, rate: f64, t: f64) -> f64 {
+        match self {
+            Self::Continuous => rate,
+            Self::Annual => rate.exp_m1(),
+            Self::SemiAnnual => 2.0 * (0.5 * rate).exp_m1(),
+            Self::Simple if t > 0.0 => (rate * t).exp_m1() / t,
+            Self::Simple => rate,
+        }
+    }
+
+    /// Rate in the `to` compounding equivalent to `rate` over `t` years.
+    pub fn convert(&self, rate: f64, t: f64, to: Compounding) -> f64 {
+        to.from_continuous(self.to_continuous(rate, t), t)
+    }
+
+    /// Discount factor over `t` years at `rate`.
+    pub fn discount_factor(&self, rate: f64, t: f64) -> f64 {
+        match self {
+            Self::Simple => 1.0 / (1.0 + rate * t),
+            _ => (-self.to_continuous(rate, t) * t).exp(),
+        }
+    }
+
+    /// Derivative of [`Compounding::discount_factor`] with respect to the
+    /// rate.
+    pub fn discount_factor_derivative(&self, rate: f64, t: f64) -> f64 {
+        let discount_factor = self.discount_factor(rate, t);
+
+        match self {
+            Self::Continuous => -t * discount_factor,
+            Self::Annual => -t * discount_factor / (1.0 + rate),
+            Self::SemiAnnual => -t * discount_factor / (1.0 + 0.5 * rate),
+            Self::Simple => -t * discount_factor * discount_factor,
+        }
+    }
+
+    /// Rate implied by a discount factor over `t` years (a positive year
+    /// fraction).
+    pub fn implied_rate(&self, discount_factor: f64, t: f64) -> f64 {
+        self.from_continuous(-discount_factor.ln() / t, t)
+    }
+}
+
+impl fmt::Display for Compounding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Continuous => "continuous",
+            Self::Annual => "annual",
+            Self::SemiAnnual => "semi-annual",
+            Self::Simple => "simple",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_compounding {
+    use super::*;
+
+    const ALL: [Compounding; 4] = [
+        Compounding::Continuous,
+        Compounding::Annual,
+        Compounding::SemiAnnual,
+        Compounding::Simple,
+    ];
+
+    #[test]
+    fn test_discount_factors() {
+        assert_approx_equal!(
+            Compounding::Continuous.discount_factor(0.05, 2.0),
+            (-0.1_f64).exp(),
+            1e-15
+        );
+        assert_approx_equal!(
+            Compounding::Annual.discount_factor(0.05, 2.0),
+            1.0 / 1.05_f64.powi(2),
+            1e-15
+        );
+        assert_approx_equal!(
+            Compounding::SemiAnnual.discount_factor(0.05, 2.0),
+            1.0 / 1.025_f64.powi(4),
+            1e-15
+        );
+        assert_approx_equal!(
+            Compounding::Simple.discount_factor(0.05, 0.5),
+            1.0 / 1.025,
+            1e-15
+        );
+
+        for compounding in ALL {
+            assert_eq!(compounding.discount_factor(0.05, 0.0), 1.0);
+        }
+    }
+
+    #[test]
+    fn test_conversions() {
+        // Hull, Example 4.3: 10% semi-annual is 9.7580% continuous.
+        assert_approx_equal!(
+            Compounding::SemiAnnual.convert(0.10, 1.0, Compounding::Continuous),
+            0.097580,
+            1e-6
+        );
+        // 8% continuous is e^0.08 - 1 = 8.3287% annual.
+        assert_approx_equal!(
+            Compounding::Continuous.convert(0.08, 1.0, Compounding::Annual),
+            0.083287,
+            1e-6
+        );
+
+        // Equivalent rates give the same discount factor, and round trip.
+        for from in ALL {
+            for to in ALL {
+                let t = 1.75;
+                let rate = from.convert(0.04, t, to);
+
+                assert_approx_equal!(
+                    to.discount_factor(rate, t),
+                    from.discount_factor(0.04, t),
+                    1e-14
+                );
+                assert_approx_equal!(to.convert(rate, t, from), 0.04, 1e-14);
+                assert_approx_equal!(to.implied_rate(to.discount_factor(rate, t), t), rate, 1e-14);
+            }
+        }
+    }
+
+    #[test]
+    fn test_discount_factor_derivative() {
+        let h = 1e-7;
+
+        for compounding in ALL {
+            let numerical = (compounding.discount_factor(0.03 + h, 3.0)
+                - compounding.discount_factor(0.03 - h, 3.0))
+                / (2.0 * h);
+
+            assert_approx_equal!(
+                compounding.discount_factor_derivative(0.03, 3.0),
+                numerical,
+                1e-7
+            );
+        }
+    }
+}
@@ -11,8 +11,11 @@
 // IMPORTS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-use crate::time::{DayCountConvention, DayCounter};
+use crate::curves::Compounding;
+use crate::math::interpolation::{Extrapolation, InterpolationScheme};
+use crate::time::{DayCountConvention, DayCounter, IntoEvaluationDate, Tenor};
 use std::{collections::BTreeMap, time::Duration};
+use thiserror::Error;
 use time::OffsetDateTime;
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -66,33 +69,41 @@ pub trait Curve {
     /// date, just before and just after.
     fn find_date_interval(&self, date: OffsetDateTime) -> (OffsetDateTime, OffsetDateTime);
 
-    /// Returns the rate for the given date, using linear interpolation for
-    /// dates between the curve's initial and terminal dates.
+    /// Returns the rate for the given date, interpolated between the
+    /// curve's initial and terminal dates (linearly, unless the curve
+    /// chooses another [`InterpolationScheme`]).
     /// If the date is outside the curve's range, we panic.
     ///
-    /// We use the following formula for the interpolation:
-    ///
-    /// $$
-    /// y = \frac{y_0 (x_1 - x) + y_1 (x - x_0)}{x_1 - x_0}
-    /// $$
-    ///
     /// Note: there must be at least two points in the curve, otherwise
     /// we consider the curve to be a flat rate, and return the same rate
     /// for all dates.
     fn rate(&self, date: OffsetDateTime) -> f64;
 
+    /// Day count convention used to convert dates to year fractions
+    /// (defaults to Actual/365 Fixed).
+    fn day_count_convention(&self) -> DayCountConvention {
+        DayCountConvention::Actual365
+    }
+
+    /// Compounding of the curve's rates (defaults to continuous).
+    fn compounding(&self) -> Compounding {
+        Compounding::Continuous
+    }
+
     /// Returns the discount factor for the given date.
     /// This is a convenience function that calls [rate] to get the rate for
-    /// the given date, and then calculates the discount factor using the
-    /// formula:
+    /// the given date, and then calculates the discount factor under the
+    /// curve's compounding, e.g. for continuous compounding:
     /// $$
     /// p(t) = e^{- r \cdot t}
     /// $$
+    /// where $t$ is the year fraction under the curve's day count convention.
     fn discount_factor(&self, date: OffsetDateTime) -> f64 {
-        let t =
-            DayCounter::day_count_factor(self.initial_date(), date, &DayCountConvention::Actual365);
+        let t = self
+            .day_count_convention()
+            .year_fraction(self.initial_date(), date);
 
-        f64::exp(-self.rate(date) * t)
+        self.compounding().discount_factor(self.rate(date), t)
     }
 
     /// Returns multiple discount factors for the given dates.
@@ -107,21 +118,33 @@ pub trait Curve {
 }
 
 /// Yield curve struct.
+#[derive(Debug, Clone, PartialEq)]
 pub struct YieldCurve {
     /// Map of dates and rates.
     /// The dates are the keys and the rates are the values.
     /// The reason for using a [BTreeMap] is that it is sorted by date,
     /// which makes sense for a term structure.
     pub rates: BTreeMap<OffsetDateTime, f64>,
+
+    /// Day count convention of the curve.
+    pub day_count_convention: DayCountConvention,
+
+    /// Interpolation between the curve's dates.
+    pub interpolation: InterpolationScheme,
+
+    /// Compounding of the curve's rates.
+    pub compounding: Compounding,
 }
 
 /// Curve error enum.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
 pub enum CurveError {
     /// The date is outside the curve's range.
+    #[error("The date is outside the curve's range.")]
     DateOutsideRange,
 
     /// The curve has no points.
+    #[error("The curve has no points.")]
     NoPoints,
 }
 
@@ -132,11 +155,189 @@ pub enum CurveError {
 impl YieldCurve {
     /// Creates a new yield curve.
     pub fn new(rates: BTreeMap<OffsetDateTime, f64>) -> Self {
-        Self { rates }
+        Self {
+            rates,
+            day_count_convention: DayCountConvention::Actual365,
+            interpolation: InterpolationScheme::Linear,
+            compounding: Compounding::Continuous,
+        }
+    }
+
+    /// Creates a new yield curve from an initial date and rates at the
+    /// given tenors (e.g. "3M", "1Y", "10Y") from the initial date.
+    pub fn from_tenors_and_rates(
+        initial_date: impl IntoEvaluationDate,
+        tenors: &[Tenor],
+        rates: &[f64],
+    ) -> Self {
+        let initial_date = initial_date.into_evaluation_date();
+
+        assert_eq!(tenors.len(), rates.len());
+
+        let dates = tenors
+            .iter()
+            .map(|tenor| initial_date + *tenor)
+            .collect::<Vec<OffsetDateTime>>();
+
+        Self::from_dates_and_rates(&dates, rates)
+    }
+
+    /// Sets the day count convention of the curve.
+    pub fn with_day_count_convention(mut self, convention: DayCountConvention) -> Self {
+        self.day_count_convention = convention;
+        self
+    }
+
+    /// Sets the interpolation between the curve's dates.
+    pub fn with_interpolation(mut self, interpolation: InterpolationScheme) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Sets the compounding the curve's rates are quoted in (continuous by
+    /// default).
+    pub fn with_compounding(mut self, compounding: Compounding) -> Self {
+        self.compounding = compounding;
+        self
+    }
+
+    /// Returns the rate for the given date, as [`Curve::rate`] does, or an
+    /// error if the curve has no points or the date is outside its range.
+    pub fn try_rate(&self, date: OffsetDateTime) -> Result<f64, CurveError> {
+        let mut rates = self.rates.values();
+
+        match self.rates.len() {
+            0 => Err(CurveError::NoPoints),
+            1 => rates.next().copied().ok_or(CurveError::NoPoints),
+            _ => {
+                // Interpolate in days since the initial date.
+                let t0 = self.initial_date();
+                let days = |date: OffsetDateTime| (date - t0).as_seconds_f64() / 86_400.0;
+
+                let xs = self
+                    .rates
+                    .keys()
+                    .map(|date| days(*date))
+                    .collect::<Vec<_>>();
+                let ys = rates.copied().collect::<Vec<_>>();
+
+                self.interpolation
+                    .build(&xs, &ys, Extrapolation::Error)
+                    .and_then(|interpolator| interpolator.interpolate(days(date)))
+                    .map_err(|_| CurveError::DateOutsideRange)
+            }
+        }
+    }
+
+    /// Returns the discount factor for the given date, as
+    /// [`Curve::discount_factor`] does, or the error of [`YieldCurve::try_rate`].
+    pub fn try_discount_factor(&self, date: OffsetDateTime) -> Result<f64, CurveError> {
+        let rate = self.try_rate(date)?;
+        let t = self
+            .day_count_convention
+            .year_fraction(self.initial_date(), date);
+
+        Ok(self.compounding.discount_factor(rate, t))
+    }
+
+    /// Zero rate to the given date in the given compounding (the curve's
+    /// rate converted over the year fraction to the date).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the date is outside the curve's range, as [`Curve::rate`].
+    pub fn zero_rate(&self, date: OffsetDateTime, compounding: Compounding) -> f64 {
+        let t = self
+            .day_count_convention
+            .year_fraction(self.initial_date(), date);
+
+        self.compounding.convert(self.rate(date), t, compounding)
+    }
+
+    /// Forward rate between two dates in the given compounding, implied by
+    /// the curve's discount factors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a date is outside the curve's range, or if `end` is not
+    /// after `start`.
+    pub fn forward_rate(
+        &self,
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+        compounding: Compounding,
+    ) -> f64 {
+        let tau = self.day_count_convention.year_fraction(start, end);
+
+        assert!(tau > 0.0, "The end date must be after the start date.");
+
+        compounding.implied_rate(self.discount_factor(end) / self.discount_factor(start), tau)
+    }
+
+    /// Copy of the curve with its rates quoted in another compounding
+    /// (the discount factors are unchanged).
+    pub fn to_compounding(&self, compounding: Compounding) -> Self {
+        let t0 = self.initial_date();
+        let rates = self
+            .rates
+            .iter()
+            .map(|(date, rate)| {
+                let t = self.day_count_convention.year_fraction(t0, *date);
+
+                (*date, self.compounding.convert(*rate, t, compounding))
+            })
+            .collect();
+
+        Self {
+            rates,
+            compounding,
+            ..self.clone()
+        }
+    }
+
+    /// Plots the (interpolated) rates, in percent, against the year
+    /// fraction from the initial date, to a PNG or SVG file.
+    #[cfg(feature = "plot")]
+    pub fn plot(&self, path: impl AsRef<std::path::Path>) -> Result<(), crate::plot::PlotError> {
+        const N_POINTS: i64 = 200;
+
+        if self.rates.is_empty() {
+            return Err(crate::plot::PlotError::NoData);
+        }
+
+        let (t0, tn) = (self.initial_date(), self.terminal_date());
+        let step = (tn - t0) / N_POINTS as f64;
+
+        let points = (0..=N_POINTS)
+            .map(|i| {
+                if i == N_POINTS {
+                    tn
+                } else {
+                    t0 + step * i as f64
+                }
+            })
+            .filter_map(|date| {
+                let t = self.day_count_convention.year_fraction(t0, date);
+                self.try_rate(date).ok().map(|rate| (t, 100.0 * rate))
+            })
+            .collect();
+
+        crate::plot::Chart::new("Yield curve")
+            .with_labels("Years", "Rate (%)")
+            .with_series("Rate", points)
+            .save(path)
     }
 }
 
 impl Curve for YieldCurve {
+    fn day_count_convention(&self) -> DayCountConvention {
+        self.day_count_convention
+    }
+
+    fn compounding(&self) -> Compounding {
+        self.compounding
+    }
+
     fn initial_date(&self) -> OffsetDateTime {
         *self.rates.keys().min().unwrap()
     }
@@ -156,7 +357,7 @@ impl Curve for YieldCurve {
             rates_map.insert(*date, *rate);
         }
 
-        Self { rates: rates_map }
+        Self::new(rates_map)
     }
 
     fn from_initial_date_rates_and_durations(
@@ -174,17 +375,9 @@ impl Curve for YieldCurve {
     }
 
     fn rate(&self, date: OffsetDateTime) -> f64 {
-        let n = self.rates.len();
-
-        match n {
-            0 => panic!("The curve has no points."),
-            1 => *self.rates.values().next().unwrap(),
-            _ => {
-                let (x0, x1) = self.find_date_interval(date);
-                let (y0, y1) = (*self.rates.get(&x0).unwrap(), *self.rates.get(&x1).unwrap());
-
-                (y0 * (x1 - date) + y1 * (date - x0)) / (x1 - x0)
-            }
+        match self.try_rate(date) {
+            Ok(rate) => rate,
+            Err(error) => panic!("{error}"),
         }
     }
 
@@ -307,6 +500,154 @@ mod tests_curves {
 
         assert!(df1 > 0.0 && df1 < 1.0 && df2 > 0.0 && df2 < 1.0 && df3 > 0.0 && df3 < 1.0);
 
-        assert!(df1 < df2 && df2 < df3);
+        assert!(df1 > df2 && df2 > df3);
+    }
+
+    #[test]
+    fn test_yield_curve_day_count_convention() {
+        let t0 = OffsetDateTime::UNIX_EPOCH;
+        let dates = [t0, t0 + Duration::days(365), t0 + Duration::days(730)];
+        let rates = [0.05, 0.05, 0.05];
+
+        let act365 = YieldCurve::from_dates_and_rates(&dates, &rates);
+        let act360 = YieldCurve::from_dates_and_rates(&dates, &rates)
+            .with_day_count_convention(DayCountConvention::Actual360);
+
+        assert_approx_equal!(act365.discount_factor(dates[1]), (-0.05_f64).exp(), 1e-12);
+        assert_approx_equal!(
+            act360.discount_factor(dates[1]),
+            (-0.05_f64 * 365.0 / 360.0).exp(),
+            1e-12
+        );
+    }
+
+    #[test]
+    fn test_yield_curve_from_tenors() {
+        let t0 = OffsetDateTime::UNIX_EPOCH;
+        let tenors = ["3M", "1Y", "10Y"].map(|t| t.parse::<Tenor>().unwrap());
+
+        let curve = YieldCurve::from_tenors_and_rates(t0, &tenors, &[0.03, 0.035, 0.04]);
+
+        assert_eq!(curve.initial_date(), t0 + Tenor::months(3));
+        assert_eq!(curve.terminal_date(), t0 + Tenor::years(10));
+        assert_eq!(curve.rates.len(), 3);
+    }
+
+    #[test]
+    fn test_yield_curve_interpolation() {
+        let t0 = OffsetDateTime::UNIX_EPOCH;
+        let dates = [0, 90, 180, 365, 730].map(|d| t0 + Duration::days(d));
+        let rates = [0.030, 0.032, 0.031, 0.035, 0.036];
+
+        let linear = YieldCurve::from_dates_and_rates(&dates, &rates);
+        let pchip = YieldCurve::from_dates_and_rates(&dates, &rates)
+            .with_interpolation(InterpolationScheme::Pchip);
+
+        // Both go through the nodes, including the end points.
+        for (date, rate) in dates.iter().zip(&rates) {
+            assert_approx_equal!(linear.rate(*date), *rate, 1e-15);
+            assert_approx_equal!(pchip.rate(*date), *rate, 1e-15);
+        }
+
+        assert_approx_equal!(linear.rate(t0 + Duration::days(45)), 0.031, 1e-15);
+
+        // PCHIP does not overshoot between 90 and 180 days (a local maximum).
+        let mid = pchip.rate(t0 + Duration::days(135));
+        assert!((0.031..=0.032).contains(&mid));
+    }
+
+    #[test]
+    #[should_panic(expected = "outside the curve's range")]
+    fn test_yield_curve_outside_range() {
+        let t0 = OffsetDateTime::UNIX_EPOCH;
+        let curve = YieldCurve::from_dates_and_rates(&[t0, t0 + Duration::days(30)], &[0.01, 0.02]);
+
+        curve.rate(t0 + Duration::days(31));
+    }
+
+    #[test]
+    fn test_yield_curve_try_rate() {
+        let t0 = OffsetDateTime::UNIX_EPOCH;
+        let mut rates = BTreeMap::new();
+        rates.insert(t0, 0.02);
+        rates.insert(t0 + Duration::days(365), 0.03);
+
+        let yield_curve = YieldCurve::new(rates);
+
+        assert!((yield_curve.try_rate(t0 + Duration::days(73)).unwrap() - 0.022).abs() < 1e-12);
+        assert_eq!(
+            yield_curve.try_rate(t0 + Duration::days(400)),
+            Err(CurveError::DateOutsideRange)
+        );
+        assert_eq!(
+            yield_curve.try_discount_factor(t0 + Duration::days(365)),
+            Ok(f64::exp(-0.03))
+        );
+        assert_eq!(
+            YieldCurve::new(BTreeMap::new()).try_rate(t0),
+            Err(CurveError::NoPoints)
+        );
+    }
+
+    #[test]
+    fn test_yield_curve_compounding() {
+        let t0 = OffsetDateTime::UNIX_EPOCH;
+        let (t1, t2) = (t0 + Duration::days(365), t0 + Duration::days(730));
+
+        // Annually compounded rates of 3% and 4%.
+        let curve = YieldCurve::from_dates_and_rates(&[t0, t1, t2], &[0.03, 0.03, 0.04])
+            .with_compounding(Compounding::Annual);
+
+        assert_eq!(Curve::compounding(&curve), Compounding::Annual);
+        assert_approx_equal!(curve.discount_factor(t1), 1.0 / 1.03, 1e-12);
+        assert_approx_equal!(curve.discount_factor(t2), 1.0 / 1.04_f64.powi(2), 1e-12);
+
+        // The same rates in other compoundings.
+        assert_approx_equal!(
+            curve.zero_rate(t2, Compounding::Continuous),
+            1.04_f64.ln(),
+            1e-12
+        );
+        assert_approx_equal!(
+            curve.zero_rate(t2, Compounding::Simple),
+            (1.04_f64.powi(2) - 1.0) / 2.0,
+            1e-12
+        );
+
+        // The one-year forward rate in a year.
+        assert_approx_equal!(
+            curve.forward_rate(t1, t2, Compounding::Annual),
+            1.04_f64.powi(2) / 1.03 - 1.0,
+            1e-12
+        );
+
+        // Requoting changes the rates but not the discount factors.
+        let continuous = curve.to_compounding(Compounding::Continuous);
+        assert_eq!(continuous.compounding, Compounding::Continuous);
+        assert_approx_equal!(continuous.rates[&t1], 1.03_f64.ln(), 1e-12);
+        for date in [t1, t2] {
+            assert_approx_equal!(
+                continuous.discount_factor(date),
+                curve.discount_factor(date),
+                1e-12
+            );
+        }
+    }
+
+    #[cfg(feature = "plot")]
+    #[test]
+    fn test_yield_curve_plot() {
+        let t0 = OffsetDateTime::UNIX_EPOCH;
+        let curve = YieldCurve::from_dates_and_rates(
+            &[t0, t0 + Duration::days(365), t0 + Duration::days(3650)],
+            &[0.03, 0.035, 0.04],
+        );
+        let path = std::env::temp_dir().join("rustquant_yield_curve.svg");
+
+        curve.plot(&path).unwrap();
+        assert!(std::fs::metadata(&path).is_ok());
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(YieldCurve::new(BTreeMap::new()).plot(&path).is_err());
     }
 }
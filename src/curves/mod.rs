@@ -16,6 +16,14 @@
 pub mod curve;
 pub use curve::*;
 
+/// Compounding conventions of interest rates.
+pub mod compounding;
+pub use compounding::*;
+
+/// Par swap rates and annuities.
+pub mod swap_rates;
+pub use swap_rates::*;
+
 /// Surface implementations.
 /// Surfaces are simply [Curve]s with an additional dimension.
 /// For example, a volatility surface is a function of time and strike/moneyness.
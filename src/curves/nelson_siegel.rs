@@ -21,6 +21,7 @@ pub struct NelsonSiegel {
     beta1: f64,
     beta2: f64,
     lambda: f64,
+    day_count_convention: DayCountConvention,
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -35,8 +36,16 @@ impl NelsonSiegel {
             beta1,
             beta2,
             lambda,
+            day_count_convention: DayCountConvention::Actual365,
         }
     }
+
+    /// Sets the day count convention used to compute the time to maturity
+    /// (defaults to Actual/365 Fixed).
+    pub fn with_day_count_convention(mut self, convention: DayCountConvention) -> Self {
+        self.day_count_convention = convention;
+        self
+    }
 }
 
 impl CurveModel for NelsonSiegel {
@@ -47,11 +56,9 @@ impl CurveModel for NelsonSiegel {
             "Date must be in the future."
         );
 
-        let tau = DayCounter::day_count_factor(
-            OffsetDateTime::now_utc(),
-            date,
-            &DayCountConvention::Actual365,
-        );
+        let tau = self
+            .day_count_convention
+            .year_fraction(OffsetDateTime::now_utc(), date);
 
         let term1 = f64::exp(-tau / self.lambda);
         let term2 = (tau / self.lambda) * term1;
@@ -66,11 +73,9 @@ impl CurveModel for NelsonSiegel {
             "Date must be in the future."
         );
 
-        let tau = DayCounter::day_count_factor(
-            OffsetDateTime::now_utc(),
-            date,
-            &DayCountConvention::Actual365,
-        );
+        let tau = self
+            .day_count_convention
+            .year_fraction(OffsetDateTime::now_utc(), date);
 
         let term1 = self.lambda * (1. - f64::exp(-tau / self.lambda)) / tau;
         let term2 = term1 - f64::exp(-tau / self.lambda);
@@ -79,16 +84,14 @@ impl CurveModel for NelsonSiegel {
     }
 
     fn discount_factor(&self, date: OffsetDateTime) -> f64 {
-        let tau = DayCounter::day_count_factor(
-            OffsetDateTime::now_utc(),
-            date,
-            &DayCountConvention::Actual365,
-        );
+        let tau = self
+            .day_count_convention
+            .year_fraction(OffsetDateTime::now_utc(), date);
 
         f64::exp(-self.spot_rate(date) * tau / 100.)
     }
 
-    fn calibrate<C: Curve>(&self, curve: C) -> Self {
+    fn calibrate<C: Curve>(&self, _curve: C) -> Self {
         unimplemented!()
     }
 }
@@ -100,7 +103,6 @@ impl CurveModel for NelsonSiegel {
 #[cfg(test)]
 mod tests_nelson_siegel {
     use super::*;
-    use crate::plot_vector;
     use time::Duration;
 
     #[test]
@@ -110,6 +112,7 @@ mod tests_nelson_siegel {
             beta1: -0.0031,
             beta2: -0.0625,
             lambda: 1.58,
+            day_count_convention: DayCountConvention::Actual365,
         };
 
         let dates = (2..365 * 30)
@@ -23,6 +23,7 @@ pub struct NelsonSiegelSvensson {
     beta3: f64,
     lambda1: f64,
     lambda2: f64,
+    day_count_convention: DayCountConvention,
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -39,8 +40,16 @@ impl NelsonSiegelSvensson {
             beta3,
             lambda1,
             lambda2,
+            day_count_convention: DayCountConvention::Actual365,
         }
     }
+
+    /// Sets the day count convention used to compute the time to maturity
+    /// (defaults to Actual/365 Fixed).
+    pub fn with_day_count_convention(mut self, convention: DayCountConvention) -> Self {
+        self.day_count_convention = convention;
+        self
+    }
 }
 
 impl CurveModel for NelsonSiegelSvensson {
@@ -51,11 +60,9 @@ impl CurveModel for NelsonSiegelSvensson {
             "Date must be in the future."
         );
 
-        let tau = DayCounter::day_count_factor(
-            OffsetDateTime::now_utc(),
-            date,
-            &DayCountConvention::Actual365,
-        );
+        let tau = self
+            .day_count_convention
+            .year_fraction(OffsetDateTime::now_utc(), date);
 
         let term1 = f64::exp(-tau / self.lambda1);
         let term2 = (tau / self.lambda1) * term1;
@@ -71,11 +78,9 @@ impl CurveModel for NelsonSiegelSvensson {
             "Date must be in the future."
         );
 
-        let tau = DayCounter::day_count_factor(
-            OffsetDateTime::now_utc(),
-            date,
-            &DayCountConvention::Actual365,
-        );
+        let tau = self
+            .day_count_convention
+            .year_fraction(OffsetDateTime::now_utc(), date);
 
         let term1 = self.lambda1 * (1. - f64::exp(-tau / self.lambda1)) / tau;
         let term2 = term1 - f64::exp(-tau / self.lambda1);
@@ -86,16 +91,14 @@ impl CurveModel for NelsonSiegelSvensson {
     }
 
     fn discount_factor(&self, date: OffsetDateTime) -> f64 {
-        let tau = DayCounter::day_count_factor(
-            OffsetDateTime::now_utc(),
-            date,
-            &DayCountConvention::Actual365,
-        );
+        let tau = self
+            .day_count_convention
+            .year_fraction(OffsetDateTime::now_utc(), date);
 
         f64::exp(-self.spot_rate(date) * tau / 100.)
     }
 
-    fn calibrate<C: Curve>(&self, curve: C) -> Self {
+    fn calibrate<C: Curve>(&self, _curve: C) -> Self {
         unimplemented!()
     }
 }
@@ -107,7 +110,6 @@ impl CurveModel for NelsonSiegelSvensson {
 #[cfg(test)]
 mod tests_nelson_siegel_svensson {
     use super::*;
-    use crate::plot_vector;
     use time::Duration;
 
     #[test]
@@ -119,6 +121,7 @@ mod tests_nelson_siegel_svensson {
             beta3: -0.0198,
             lambda1: 1.58,
             lambda2: 0.15,
+            day_count_convention: DayCountConvention::Actual365,
         };
 
         let dates = (2..365 * 30)
@@ -12,6 +12,7 @@
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 use super::Curve;
+use crate::math::interpolation::{Extrapolation, InterpolationScheme};
 use num_traits::Float;
 use std::collections::BTreeMap;
 use time::OffsetDateTime;
@@ -26,16 +27,119 @@ pub trait Surface {
 /// A volatility surface is a surface of points (volatilities) over a
 /// space dimension (e.g. strike or moneyness) and a time dimension (e.g. dates).
 ///
-/// We represent this as a map from time to a curve of volatilities.
+/// We represent this as a map from the space coordinate to a term structure
+/// (curve) of volatilities.
+#[derive(Debug, Clone)]
 pub struct VolatilitySurface<C: Curve> {
     /// The volatilities of the surface.
-    pub volatilities: BTreeMap<f64, C>,
+    pub volatilities: BTreeMap<F64Key, C>,
+
+    /// Interpolation across the space dimension (extrapolated flat).
+    pub interpolation: InterpolationScheme,
 }
 
+/// Totally ordered `f64`, to key a [`BTreeMap`] by strike or moneyness.
+#[derive(Debug, Clone, Copy)]
+pub struct F64Key(pub f64);
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+impl PartialEq for F64Key {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for F64Key {}
+
+impl PartialOrd for F64Key {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for F64Key {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl<C: Curve> VolatilitySurface<C> {
+    /// Creates a surface from term structures at each strike (or
+    /// moneyness), interpolated linearly across strikes.
+    pub fn new(volatilities: impl IntoIterator<Item = (f64, C)>) -> Self {
+        Self {
+            volatilities: volatilities
+                .into_iter()
+                .map(|(space, curve)| (F64Key(space), curve))
+                .collect(),
+            interpolation: InterpolationScheme::Linear,
+        }
+    }
+
+    /// Sets the interpolation across strikes.
+    pub fn with_interpolation(mut self, interpolation: InterpolationScheme) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+}
+
+impl<C: Curve> Surface for VolatilitySurface<C> {
+    /// Interpolates each term structure at `time`, then across the space
+    /// dimension. Panics if `time` is outside a term structure.
+    fn value<F: Float>(&self, time: OffsetDateTime, space: F) -> f64 {
+        let xs = self.volatilities.keys().map(|k| k.0).collect::<Vec<_>>();
+        let ys = self
+            .volatilities
+            .values()
+            .map(|curve| curve.rate(time))
+            .collect::<Vec<_>>();
+        let space = space.to_f64().unwrap();
+
+        match xs.len() {
+            0 => panic!("The surface has no points."),
+            1 => ys[0],
+            _ => self
+                .interpolation
+                .build(&xs, &ys, Extrapolation::Flat)
+                .and_then(|interpolator| interpolator.interpolate(space))
+                .unwrap(),
+        }
+    }
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // UNIT TESTS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_surface {
+    use super::*;
+    use crate::curves::YieldCurve;
+    use time::Duration;
+
+    #[test]
+    fn test_volatility_surface() {
+        let t0 = OffsetDateTime::UNIX_EPOCH;
+        let dates = [t0, t0 + Duration::days(365)];
+        let smile =
+            |strike: f64, vol: [f64; 2]| (strike, YieldCurve::from_dates_and_rates(&dates, &vol));
+
+        let surface = VolatilitySurface::new([
+            smile(80.0, [0.30, 0.26]),
+            smile(100.0, [0.20, 0.22]),
+            smile(120.0, [0.25, 0.24]),
+        ]);
+
+        assert_approx_equal!(surface.value(dates[0], 90.0), 0.25, 1e-12);
+        assert_approx_equal!(surface.value(t0 + Duration::days(146), 110.0), 0.227, 1e-12);
+
+        // Flat extrapolation in strike.
+        assert_approx_equal!(surface.value(dates[1], 150.0), 0.24, 1e-12);
+
+        let pchip = surface.with_interpolation(InterpolationScheme::Pchip);
+        assert_approx_equal!(pchip.value(dates[0], 100.0), 0.20, 1e-12);
+    }
+}
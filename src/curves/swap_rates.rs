@@ -0,0 +1,246 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Par swap rates and annuities.
+//!
+//! The annuity (PV01 of the fixed leg per unit rate) of a swap starting on
+//! `start` and running for `tenor` is
+//! $$
+//! A = \sum_{i} \tau_i P_d(t_i)
+//! $$
+//! and its par rate, the fixed rate at which it is worth nothing, is
+//! $$
+//! S = \frac{1}{A} \sum_{i} P_d(t_i) \left( \frac{P_f(t_{i-1})}{P_f(t_i)} - 1 \right)
+//! $$
+//! where $P_d$ and $P_f$ are the discount and forecast curves. With a single
+//! curve this is $(P(t_0) - P(t_n)) / A$.
+//!
+//! ```
+//! use RustQuant::curves::*;
+//! use RustQuant::time::{PaymentFrequency, Tenor};
+//! use time::macros::datetime;
+//!
+//! let today = datetime!(2024-01-15 0:00 UTC);
+//! let curve = YieldCurve::from_tenors_and_rates(
+//!     today,
+//!     &[Tenor::days(0), Tenor::years(30)],
+//!     &[0.04, 0.04],
+//! );
+//! let curves = SwapCurves::single(&curve);
+//!
+//! // 5Y swap starting in 2Y.
+//! let start = today + Tenor::years(2);
+//! let rate = par_swap_rate(start, Tenor::years(5), PaymentFrequency::Annually, &curves);
+//!
+//! assert!((rate - 0.0408).abs() < 1e-3);
+//! ```
+
+use crate::curves::Curve;
+use crate::time::{
+    BusinessDayConvention, DayCounter, PaymentFrequency, Schedule, StubRule, Tenor, WeekendsOnly,
+};
+use time::OffsetDateTime;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Curves a swap is valued on: one to discount its cash flows and one to
+/// project the floating rates (the same curve before the multi-curve
+/// framework).
+#[derive(Debug, Clone, Copy)]
+pub struct SwapCurves<'a, C: Curve> {
+    /// Discount (e.g. OIS) curve.
+    pub discount: &'a C,
+
+    /// Forecast curve of the floating index.
+    pub forecast: &'a C,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<'a, C: Curve> SwapCurves<'a, C> {
+    /// Separate discount and forecast curves.
+    pub fn new(discount: &'a C, forecast: &'a C) -> Self {
+        Self { discount, forecast }
+    }
+
+    /// One curve for both discounting and forecasting.
+    pub fn single(curve: &'a C) -> Self {
+        Self::new(curve, curve)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Schedule of a swap starting on `start` and running for `tenor`, paying
+/// at `frequency`: dates rolled back from the end (with a short front stub)
+/// and adjusted to business days with Modified Following.
+pub fn swap_schedule(start: OffsetDateTime, tenor: Tenor, frequency: PaymentFrequency) -> Schedule {
+    Schedule::new(
+        start,
+        start + tenor,
+        frequency,
+        &WeekendsOnly,
+        BusinessDayConvention::ModifiedFollowing,
+        StubRule::ShortFront,
+    )
+}
+
+/// Annuity of a swap: the present value of its fixed leg per unit of
+/// fixed rate, with accrual fractions under the discount curve's day count
+/// convention.
+///
+/// # Panics
+///
+/// Panics if a payment date is outside the discount curve.
+pub fn annuity<C: Curve>(
+    start: OffsetDateTime,
+    tenor: Tenor,
+    frequency: PaymentFrequency,
+    curves: &SwapCurves<C>,
+) -> f64 {
+    let day_count = curves.discount.day_count_convention();
+
+    swap_schedule(start, tenor, frequency)
+        .periods
+        .iter()
+        .map(|period| {
+            day_count.year_fraction(period.start, period.end)
+                * curves.discount.discount_factor(period.payment)
+        })
+        .sum()
+}
+
+/// Par rate of a swap: the fixed rate at which its fixed and floating legs
+/// (both paying at `frequency`) have the same present value.
+///
+/// # Panics
+///
+/// Panics if a date is outside the curves, as [`annuity`].
+pub fn par_swap_rate<C: Curve>(
+    start: OffsetDateTime,
+    tenor: Tenor,
+    frequency: PaymentFrequency,
+    curves: &SwapCurves<C>,
+) -> f64 {
+    let floating_leg: f64 = swap_schedule(start, tenor, frequency)
+        .periods
+        .iter()
+        .map(|period| {
+            let growth = curves.forecast.discount_factor(period.start)
+                / curves.forecast.discount_factor(period.end);
+
+            (growth - 1.0) * curves.discount.discount_factor(period.payment)
+        })
+        .sum();
+
+    floating_leg / annuity(start, tenor, frequency, curves)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_swap_rates {
+    use super::*;
+    use crate::curves::{Compounding, YieldCurve};
+    use time::macros::datetime;
+
+    // A Monday, so no payment date is adjusted off a weekend in the
+    // annual schedules below.
+    const TODAY: OffsetDateTime = datetime!(2024-01-15 0:00 UTC);
+
+    fn flat(rate: f64) -> YieldCurve {
+        YieldCurve::from_tenors_and_rates(TODAY, &[Tenor::days(0), Tenor::years(40)], &[rate; 2])
+    }
+
+    #[test]
+    fn test_single_curve() {
+        let curve = flat(0.03);
+        let curves = SwapCurves::single(&curve);
+        let (tenor, frequency) = (Tenor::years(10), PaymentFrequency::Annually);
+
+        // The par rate of a swap starting today prices the fixed leg (with
+        // the notional at maturity) at par.
+        let a = annuity(TODAY, tenor, frequency, &curves);
+        let rate = par_swap_rate(TODAY, tenor, frequency, &curves);
+        let end = swap_schedule(TODAY, tenor, frequency)
+            .periods
+            .last()
+            .unwrap()
+            .payment;
+
+        assert_approx_equal!(rate * a + curve.discount_factor(end), 1.0, 1e-12);
+
+        // On a flat continuous curve the par rate is close to the
+        // equivalent annual rate (exact but for the day count).
+        assert_approx_equal!(
+            rate,
+            Compounding::Continuous.convert(0.03, 1.0, Compounding::Annual),
+            2e-4
+        );
+    }
+
+    #[test]
+    fn test_annuity() {
+        let curve = flat(0.0);
+        let curves = SwapCurves::single(&curve);
+
+        // Undiscounted, the annuity adds up the accrual fractions.
+        let a = annuity(
+            TODAY,
+            Tenor::years(2),
+            PaymentFrequency::SemiAnnually,
+            &curves,
+        );
+        let schedule = swap_schedule(TODAY, Tenor::years(2), PaymentFrequency::SemiAnnually);
+
+        assert_eq!(schedule.periods.len(), 4);
+        assert_approx_equal!(a, 731.0 / 365.0, 1e-12);
+
+        // Discounting lowers it, more so for a later start.
+        let curve = flat(0.05);
+        let curves = SwapCurves::single(&curve);
+        let spot = annuity(TODAY, Tenor::years(5), PaymentFrequency::Annually, &curves);
+        let forward = annuity(
+            TODAY + Tenor::years(1),
+            Tenor::years(5),
+            PaymentFrequency::Annually,
+            &curves,
+        );
+
+        assert!(forward < spot && spot < 5.0);
+    }
+
+    #[test]
+    fn test_dual_curve() {
+        let discount = flat(0.03);
+        let forecast = flat(0.035);
+        let (tenor, frequency) = (Tenor::years(5), PaymentFrequency::Quarterly);
+
+        let single = par_swap_rate(TODAY, tenor, frequency, &SwapCurves::single(&forecast));
+        let dual = par_swap_rate(
+            TODAY,
+            tenor,
+            frequency,
+            &SwapCurves::new(&discount, &forecast),
+        );
+
+        // A flat forecast curve projects the same forward rates whatever
+        // the discounting; only the weights of the periods change.
+        assert_approx_equal!(dual, single, 1e-5);
+        assert!(dual > par_swap_rate(TODAY, tenor, frequency, &SwapCurves::single(&discount)));
+    }
+}
@@ -159,20 +159,14 @@ impl YahooFinanceData {
                 );
             }
             ReturnsType::Logarithmic => {
-                fn logarithm(col: &Series) -> Series {
-                    col.f64().unwrap().apply(|x| Some(x?.ln())).into_series()
+                fn logarithm(col: &Series) -> PolarsResult<Series> {
+                    Ok(col.f64()?.apply(|x| Some(x?.ln())).into_series())
                 }
 
-                // THIS IS EXTREMELY HACKY AND SHOULD BE FIXED
-                // IF YOU SEE THIS, FEEL FREE TO SUBMIT A PULL REQUEST
-                // If you venture past here, you'll see more .unwrap()
-                // calls than you've ever seen before in your life.
                 let mut prices = self.price_history.clone().ok_or(YahooError::EmptyDataSet)?;
-                prices.apply("open", logarithm)?;
-                prices.apply("high", logarithm)?;
-                prices.apply("low", logarithm)?;
-                prices.apply("close", logarithm)?;
-                prices.apply("adjusted", logarithm)?;
+                for column in ["open", "high", "low", "close", "adjusted"] {
+                    prices.try_apply(column, logarithm)?;
+                }
 
                 self.returns = Some(
                     prices
@@ -10,6 +10,69 @@
 //! RustQuant error handling module.
 //! A custom error type `RustQuantError` is defined, along with a macro to create an error,
 //! that propagates a `RustQuantError` with the text to include in the output.
+//!
+//! Every module's own error converts into a `RustQuantError`, so functions
+//! returning different module errors can be combined with `?`:
+//!
+//! ```rust
+//! use RustQuant::error::RustQuantResult;
+//! use RustQuant::stochastics::GeometricBrownianMotion;
+//!
+//! fn model(mu: f64, sigma: f64) -> RustQuantResult<GeometricBrownianMotion> {
+//!     let gbm = GeometricBrownianMotion::try_new(mu, sigma)?;
+//!     Ok(gbm)
+//! }
+//!
+//! assert!(model(0.05, 0.2).is_ok());
+//! assert!(model(0.05, -0.2).is_err());
+//! ```
+
+use crate::backtest::engine::BacktestError;
+use crate::curves::curve::CurveError;
+use crate::instruments::bonds::lattice::LatticeError;
+use crate::instruments::commodities::curve::CommodityError;
+use crate::instruments::termsheets::terms::TermSheetError;
+use crate::instruments::weather::degree_days::WeatherError;
+use crate::market::MarketError;
+use crate::math::interpolation::one_dimensional::InterpolationError;
+use crate::math::linalg::LinalgError;
+use crate::math::polynomials::PolynomialError;
+use crate::math::rootfind::RootFindingError;
+use crate::math::smoothing::SmoothingError;
+use crate::microstructure::book::MicrostructureError;
+use crate::ml::clustering::ClusteringError;
+use crate::ml::decision_tree::TreeError;
+use crate::ml::gaussian_process::GaussianProcessError;
+use crate::ml::linear_regression::LinearRegressionError;
+use crate::ml::regularized_regression::RegressionError;
+use crate::models::arima::ArimaError;
+use crate::models::garch::GarchError;
+use crate::models::persistence::PersistenceError;
+use crate::money::currency::MoneyError;
+use crate::money::decimal::DecimalError;
+use crate::performance::metrics::PerformanceError;
+use crate::portfolio::optimization::PortfolioError;
+use crate::risk::value_at_risk::RiskError;
+use crate::statistics::copulas::CopulaError;
+use crate::statistics::distributions::distribution::DistributionError;
+use crate::statistics::kalman_filter::KalmanError;
+use crate::statistics::multivariate_normal::MultivariateNormalError;
+use crate::statistics::time_series::autocorrelation::TimeSeriesError;
+use crate::time::calendars::custom::CalendarError;
+use crate::time::tenor::TenorError;
+use crate::xva::initial_margin::MarginError;
+
+#[cfg(feature = "data")]
+use crate::data::{io::DataError, yahoo::YahooFinanceError};
+#[cfg(feature = "plot")]
+use crate::plot::PlotError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Result type with a [`RustQuantError`].
+pub type RustQuantResult<T> = Result<T, RustQuantError>;
 
 /// Error type for RustQuant.
 #[derive(Debug, thiserror::Error)]
@@ -34,11 +97,210 @@ pub enum RustQuantError {
         /// Text to include in error message.
         text: String,
     },
+
+    /// Backtesting error.
+    #[error(transparent)]
+    Backtest(#[from] BacktestError),
+
+    /// Calendar error.
+    #[error(transparent)]
+    Calendar(#[from] CalendarError),
+
+    /// Clustering error.
+    #[error(transparent)]
+    Clustering(#[from] ClusteringError),
+
+    /// Commodity curve or instrument error.
+    #[error(transparent)]
+    Commodity(#[from] CommodityError),
+
+    /// Copula error.
+    #[error(transparent)]
+    Copula(#[from] CopulaError),
+
+    /// Curve error.
+    #[error(transparent)]
+    Curve(#[from] CurveError),
+
+    /// Data error.
+    #[cfg(feature = "data")]
+    #[error(transparent)]
+    Data(#[from] DataError),
+
+    /// Decimal arithmetic error.
+    #[error(transparent)]
+    Decimal(#[from] DecimalError),
+
+    /// Decision tree error.
+    #[error(transparent)]
+    DecisionTree(#[from] TreeError),
+
+    /// Distribution error.
+    #[error(transparent)]
+    Distribution(#[from] DistributionError),
+
+    /// Gaussian process error.
+    #[error(transparent)]
+    GaussianProcess(#[from] GaussianProcessError),
+
+    /// ARIMA model error.
+    #[error(transparent)]
+    Arima(#[from] ArimaError),
+
+    /// GARCH model error.
+    #[error(transparent)]
+    Garch(#[from] GarchError),
+
+    /// Interpolation error.
+    #[error(transparent)]
+    Interpolation(#[from] InterpolationError),
+
+    /// Kalman filter error.
+    #[error(transparent)]
+    Kalman(#[from] KalmanError),
+
+    /// Short-rate lattice error.
+    #[error(transparent)]
+    Lattice(#[from] LatticeError),
+
+    /// Linear algebra error.
+    #[error(transparent)]
+    Linalg(#[from] LinalgError),
+
+    /// Linear regression error.
+    #[error(transparent)]
+    LinearRegression(#[from] LinearRegressionError),
+
+    /// Initial margin error.
+    #[error(transparent)]
+    Margin(#[from] MarginError),
+
+    /// Market data error.
+    #[error(transparent)]
+    Market(#[from] MarketError),
+
+    /// Market microstructure error.
+    #[error(transparent)]
+    Microstructure(#[from] MicrostructureError),
+
+    /// Money error.
+    #[error(transparent)]
+    Money(#[from] MoneyError),
+
+    /// Multivariate normal error.
+    #[error(transparent)]
+    MultivariateNormal(#[from] MultivariateNormalError),
+
+    /// Performance metrics error.
+    #[error(transparent)]
+    Performance(#[from] PerformanceError),
+
+    /// Model persistence error.
+    #[error(transparent)]
+    Persistence(#[from] PersistenceError),
+
+    /// Plotting error.
+    #[cfg(feature = "plot")]
+    #[error(transparent)]
+    Plot(#[from] PlotError),
+
+    /// Polynomial error.
+    #[error(transparent)]
+    Polynomial(#[from] PolynomialError),
+
+    /// Portfolio optimization error.
+    #[error(transparent)]
+    Portfolio(#[from] PortfolioError),
+
+    /// Regularized regression error.
+    #[error(transparent)]
+    Regression(#[from] RegressionError),
+
+    /// Risk measure error.
+    #[error(transparent)]
+    Risk(#[from] RiskError),
+
+    /// Root finding error.
+    #[error(transparent)]
+    RootFinding(#[from] RootFindingError),
+
+    /// Smoothing error.
+    #[error(transparent)]
+    Smoothing(#[from] SmoothingError),
+
+    /// Tenor error.
+    #[error(transparent)]
+    Tenor(#[from] TenorError),
+
+    /// Term sheet error.
+    #[error(transparent)]
+    TermSheet(#[from] TermSheetError),
+
+    /// Time series error.
+    #[error(transparent)]
+    TimeSeries(#[from] TimeSeriesError),
+
+    /// Weather derivatives error.
+    #[error(transparent)]
+    Weather(#[from] WeatherError),
+
+    /// Yahoo! Finance error.
+    #[cfg(feature = "data")]
+    #[error(transparent)]
+    YahooFinance(#[from] YahooFinanceError),
 }
 
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl RustQuantError {
+    /// A [`RustQuantError::ComputationError`] with the given text.
+    pub fn computation(text: impl Into<String>) -> Self {
+        Self::ComputationError { text: text.into() }
+    }
+
+    /// A [`RustQuantError::InvalidParameter`] with the given text.
+    pub fn invalid_parameter(text: impl Into<String>) -> Self {
+        Self::InvalidParameter { text: text.into() }
+    }
+
+    /// A [`RustQuantError::ConditionViolated`] with the given text.
+    pub fn condition_violated(text: impl Into<String>) -> Self {
+        Self::ConditionViolated { text: text.into() }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// `Ok(())` if the condition holds, otherwise an invalid parameter error
+/// with the given text.
+pub(crate) fn ensure(condition: bool, text: &str) -> RustQuantResult<()> {
+    match condition {
+        true => Ok(()),
+        false => Err(RustQuantError::invalid_parameter(text)),
+    }
+}
+
+/// Unwraps the result of a fallible constructor, for the panicking
+/// constructors that wrap them.
+#[track_caller]
+pub(crate) fn expect_valid<T>(result: RustQuantResult<T>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(error) => panic!("{error}"),
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// MACROS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
 /// Create a `RustQuantError` with the text to include in the output.
 /// You would use it as follows:
-/// ```
+/// ```ignore
 /// return Err(
 ///     error!(
 ///         ComputationError,
@@ -49,9 +311,60 @@ pub enum RustQuantError {
 #[macro_export]
 macro_rules! error {
     ($error_type:ident, $msg:expr) => {
-        $crate::RustQuant::$error_type {
+        $crate::error::RustQuantError::$error_type {
             text: $msg.to_string(),
         }
         .into()
     };
 }
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_error {
+    use super::*;
+
+    fn singular() -> RustQuantResult<()> {
+        Err(error!(ComputationError, "singular matrix"))
+    }
+
+    fn solve() -> RustQuantResult<f64> {
+        crate::math::linalg::cholesky(&nalgebra::DMatrix::zeros(2, 3))?;
+        Ok(1.0)
+    }
+
+    #[test]
+    fn test_error_macro() {
+        let error = singular().unwrap_err();
+
+        assert!(matches!(error, RustQuantError::ComputationError { .. }));
+        assert_eq!(error.to_string(), "Computation error: \"singular matrix\"");
+    }
+
+    #[test]
+    fn test_module_errors_convert() {
+        let error = solve().unwrap_err();
+
+        assert!(matches!(error, RustQuantError::Linalg(_)));
+        assert_eq!(error.to_string(), LinalgError::NotSquare.to_string());
+    }
+
+    #[test]
+    fn test_ensure() {
+        assert!(ensure(true, "unused").is_ok());
+        assert_eq!(
+            ensure(false, "sigma must be non-negative")
+                .unwrap_err()
+                .to_string(),
+            "Invalid parameter: \"sigma must be non-negative\""
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid parameter")]
+    fn test_expect_valid() {
+        expect_valid(ensure(false, "bad input"));
+    }
+}
@@ -0,0 +1,216 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::data::DataError;
+use crate::features::*;
+use nalgebra::DMatrix;
+use polars::prelude::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Named feature expressions, evaluated together on a price history.
+#[derive(Debug, Clone, Default)]
+pub struct FeatureSet {
+    features: Vec<(String, Expr)>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl FeatureSet {
+    /// Empty feature set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Common technical features of a price history with `high`, `low` and
+    /// `close` columns:
+    ///
+    /// - `return_1`, `return_5`: simple returns over one and five rows.
+    /// - `rsi_14`: 14-row RSI.
+    /// - `macd_histogram`: MACD (12, 26, 9) histogram, relative to the close.
+    /// - `bollinger_b`: Bollinger %b (20 rows, two standard deviations).
+    /// - `atr_14`: 14-row average true range, relative to the close.
+    /// - `z_score_20`: 20-row z-score of the close.
+    pub fn technical() -> Self {
+        Self::new()
+            .with("return_1", simple_returns("close", 1))
+            .with("return_5", simple_returns("close", 5))
+            .with("rsi_14", rsi("close", 14))
+            .with(
+                "macd_histogram",
+                macd_histogram("close", 12, 26, 9) / col("close"),
+            )
+            .with("bollinger_b", bollinger_percent_b("close", 20, 2.0))
+            .with("atr_14", atr("high", "low", "close", 14) / col("close"))
+            .with("z_score_20", rolling_z_score("close", 20))
+    }
+
+    /// Adds the expression as the feature `name`, replacing any feature
+    /// with the same name.
+    pub fn with(mut self, name: &str, expr: Expr) -> Self {
+        self.features.retain(|(existing, _)| existing != name);
+        self.features.push((name.to_string(), expr));
+        self
+    }
+
+    /// Names of the features, in order.
+    pub fn names(&self) -> Vec<&str> {
+        self.features
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// Number of features.
+    pub fn len(&self) -> usize {
+        self.features.len()
+    }
+
+    /// Whether the set has no features.
+    pub fn is_empty(&self) -> bool {
+        self.features.is_empty()
+    }
+
+    /// The frame with the features added as columns. Rows before a feature
+    /// is defined (its warm-up) are null.
+    pub fn compute(&self, frame: &DataFrame) -> Result<DataFrame, DataError> {
+        let exprs: Vec<Expr> = self
+            .features
+            .iter()
+            .map(|(name, expr)| expr.clone().alias(name))
+            .collect();
+
+        Ok(frame.clone().lazy().with_columns(exprs).collect()?)
+    }
+
+    /// The features as a matrix with one row per row of the frame and one
+    /// column per feature, as used by the backtester's
+    /// [`MarketEnvironment`](crate::backtest::MarketEnvironment).
+    ///
+    /// Undefined values (nulls during warm-up, or NaN) are set to zero.
+    pub fn to_matrix(&self, frame: &DataFrame) -> Result<DMatrix<f64>, DataError> {
+        let computed = self.compute(frame)?;
+
+        let columns = self
+            .features
+            .iter()
+            .map(|(name, _)| -> Result<Vec<f64>, DataError> {
+                Ok(computed
+                    .column(name)?
+                    .cast(&DataType::Float64)?
+                    .f64()?
+                    .into_iter()
+                    .map(|v| v.filter(|v| v.is_finite()).unwrap_or(0.0))
+                    .collect())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(DMatrix::from_fn(frame.height(), columns.len(), |i, j| {
+            columns[j][i]
+        }))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_feature_set {
+    use super::*;
+    use crate::backtest::{Backtest, Bar, MarketEnvironment};
+    use time::{Date, Duration, Month};
+
+    fn prices(n: usize) -> DataFrame {
+        let close: Vec<f64> = (0..n)
+            .map(|i| 100.0 + (i as f64 / 5.0).sin() * 5.0)
+            .collect();
+        let start = Date::from_calendar_date(2024, Month::January, 1).unwrap();
+
+        df!(
+            "date" => (0..n)
+                .map(|i| start + Duration::days(i as i64))
+                .map(|d| d.to_julian_day() - 2_440_588)
+                .collect::<Vec<_>>(),
+            "open" => &close,
+            "high" => close.iter().map(|c| c + 1.0).collect::<Vec<_>>(),
+            "low" => close.iter().map(|c| c - 1.0).collect::<Vec<_>>(),
+            "close" => &close,
+            "volume" => vec![1e6; n]
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_compute() {
+        let frame = prices(60);
+        let features = FeatureSet::technical();
+
+        assert_eq!(features.len(), 7);
+        assert_eq!(features.names()[2], "rsi_14");
+
+        let computed = features.compute(&frame).unwrap();
+        assert_eq!(computed.height(), 60);
+        assert_eq!(computed.width(), frame.width() + 7);
+
+        // The slow average starts at row 25, and its signal line eight
+        // rows later.
+        let histogram = computed.column("macd_histogram").unwrap();
+        assert_eq!(histogram.null_count(), 33);
+
+        let replaced = FeatureSet::new()
+            .with("x", lag("close", 1))
+            .with("x", lag("close", 2));
+        assert_eq!(replaced.names(), ["x"]);
+        assert_eq!(
+            replaced
+                .compute(&frame)
+                .unwrap()
+                .column("x")
+                .unwrap()
+                .null_count(),
+            2
+        );
+
+        assert!(FeatureSet::new()
+            .with("bad", col("missing"))
+            .compute(&frame)
+            .is_err());
+    }
+
+    #[test]
+    fn test_to_matrix_feeds_environment() {
+        let frame = prices(60)
+            .lazy()
+            .with_column(col("date").cast(DataType::Date))
+            .collect()
+            .unwrap();
+        let features = FeatureSet::technical();
+
+        let matrix = features.to_matrix(&frame).unwrap();
+        assert_eq!(matrix.shape(), (60, 7));
+        assert_eq!(matrix[(0, 0)], 0.0);
+        assert!(matrix.iter().all(|v| v.is_finite()));
+
+        let bars = Bar::from_frame(&frame).unwrap();
+        let mut environment = MarketEnvironment::new(Backtest::new(1_000.0), &bars)
+            .unwrap()
+            .with_features(matrix.clone())
+            .unwrap();
+
+        environment.reset();
+        let observation = environment.step(0.0).observation;
+        assert_eq!(observation.features.len(), 7);
+        assert_eq!(observation.features[0], matrix[(1, 0)]);
+    }
+}
@@ -0,0 +1,230 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::features::{lag, rolling_mean, rolling_std};
+use polars::prelude::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Exponential moving average with smoothing `alpha`, null for the first
+/// `min_periods - 1` values.
+fn exponential_average(expr: Expr, alpha: f64, min_periods: usize) -> Expr {
+    expr.ewm_mean(EWMOptions {
+        alpha,
+        adjust: false,
+        bias: false,
+        min_periods,
+        ignore_nulls: true,
+    })
+}
+
+/// Exponential moving average over `span` rows, with smoothing
+/// $2 / (n + 1)$.
+pub fn ema(column: &str, span: usize) -> Expr {
+    exponential_average(col(column), 2.0 / (span as f64 + 1.0), span)
+}
+
+/// Relative strength index (Wilder, 1978), between 0 and 100.
+///
+/// The average gains and losses of the close are smoothed with Wilder's
+/// moving average (smoothing $1 / n$).
+pub fn rsi(close: &str, window: usize) -> Expr {
+    let change = col(close) - lag(close, 1);
+    let alpha = 1.0 / window as f64;
+
+    // Positive and negative parts, keeping the null of the first row.
+    let gain = exponential_average(
+        (change.clone().abs() + change.clone()) / lit(2.0),
+        alpha,
+        window,
+    );
+    let loss = exponential_average((change.clone().abs() - change) / lit(2.0), alpha, window);
+
+    lit(100.0) * gain.clone() / (gain + loss)
+}
+
+/// MACD line: the `fast` minus the `slow` exponential moving average of the
+/// close (12 and 26 in Appel's original).
+pub fn macd(close: &str, fast: usize, slow: usize) -> Expr {
+    ema(close, fast) - ema(close, slow)
+}
+
+/// MACD signal line: the exponential moving average of the MACD line over
+/// `signal` rows (usually 9).
+pub fn macd_signal(close: &str, fast: usize, slow: usize, signal: usize) -> Expr {
+    exponential_average(macd(close, fast, slow), 2.0 / (signal as f64 + 1.0), signal)
+}
+
+/// MACD histogram: the MACD line minus its signal line.
+pub fn macd_histogram(close: &str, fast: usize, slow: usize, signal: usize) -> Expr {
+    macd(close, fast, slow) - macd_signal(close, fast, slow, signal)
+}
+
+/// Upper Bollinger band: the rolling mean plus `k` rolling (sample)
+/// standard deviations over `window` rows (20 and 2 in Bollinger's
+/// original).
+pub fn bollinger_upper(close: &str, window: usize, k: f64) -> Expr {
+    rolling_mean(close, window) + lit(k) * rolling_std(close, window)
+}
+
+/// Lower Bollinger band: the rolling mean minus `k` rolling standard
+/// deviations.
+pub fn bollinger_lower(close: &str, window: usize, k: f64) -> Expr {
+    rolling_mean(close, window) - lit(k) * rolling_std(close, window)
+}
+
+/// Position of the close within the Bollinger bands, %b: zero at the lower
+/// band and one at the upper band.
+pub fn bollinger_percent_b(close: &str, window: usize, k: f64) -> Expr {
+    let lower = bollinger_lower(close, window, k);
+
+    (col(close) - lower.clone()) / (bollinger_upper(close, window, k) - lower)
+}
+
+/// True range: the largest of the high-low range and the distances from
+/// the previous close to the high and the low.
+pub fn true_range(high: &str, low: &str, close: &str) -> Expr {
+    max_horizontal([
+        col(high) - col(low),
+        (col(high) - lag(close, 1)).abs(),
+        (col(low) - lag(close, 1)).abs(),
+    ])
+}
+
+/// Average true range (Wilder, 1978), smoothed with Wilder's moving
+/// average over `window` rows.
+pub fn atr(high: &str, low: &str, close: &str, window: usize) -> Expr {
+    exponential_average(true_range(high, low, close), 1.0 / window as f64, window)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_indicators {
+    use super::*;
+
+    fn evaluate(frame: &DataFrame, expr: Expr) -> Vec<Option<f64>> {
+        frame
+            .clone()
+            .lazy()
+            .select([expr.alias("y")])
+            .collect()
+            .unwrap()
+            .column("y")
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_iter()
+            .collect()
+    }
+
+    fn prices() -> DataFrame {
+        let close = [10.0, 11.0, 10.5, 11.5, 12.0, 11.0, 11.5, 12.5];
+
+        df!(
+            "high" => close.iter().map(|c| c + 0.5).collect::<Vec<_>>(),
+            "low" => close.iter().map(|c| c - 0.25).collect::<Vec<_>>(),
+            "close" => close
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_ema() {
+        let ema = evaluate(&prices(), ema("close", 3));
+
+        // Smoothing 1/2, starting from the first close.
+        assert_eq!(ema[..2], [None, None]);
+        assert_approx_equal!(
+            ema[2].unwrap(),
+            0.5 * 10.5 + 0.25 * 11.0 + 0.25 * 10.0,
+            1e-12
+        );
+    }
+
+    #[test]
+    fn test_rsi() {
+        let rsi = evaluate(&prices(), rsi("close", 2));
+
+        // Changes: 1, -0.5, 1, 0.5, -1, ...; Wilder smoothing of 1/2.
+        let (gain, loss) = (0.5 * 1.0 + 0.5 * 0.0, 0.5 * 0.0 + 0.5 * 0.5);
+        assert_eq!(rsi[..2], [None, None]);
+        assert_approx_equal!(rsi[2].unwrap(), 100.0 * gain / (gain + loss), 1e-12);
+        assert!(rsi.iter().flatten().all(|r| (0.0..=100.0).contains(r)));
+
+        // Only rises: 100.
+        let rising = df!("close" => [1.0, 2.0, 3.0, 4.0]).unwrap();
+        assert_approx_equal!(
+            evaluate(&rising, super::rsi("close", 2))[3].unwrap(),
+            100.0,
+            1e-12
+        );
+    }
+
+    #[test]
+    fn test_macd() {
+        let frame = prices();
+        let line = evaluate(&frame, macd("close", 2, 4));
+        let signal = evaluate(&frame, macd_signal("close", 2, 4, 2));
+        let histogram = evaluate(&frame, macd_histogram("close", 2, 4, 2));
+
+        assert_eq!(line[..3], [None, None, None]);
+        assert!(line[3].is_some());
+        assert!(signal[3].is_none() && signal[4].is_some());
+        assert_approx_equal!(
+            histogram[7].unwrap(),
+            line[7].unwrap() - signal[7].unwrap(),
+            1e-12
+        );
+
+        // A rising trend has a positive MACD.
+        let rising = df!("close" => (0..40).map(f64::from).collect::<Vec<_>>()).unwrap();
+        assert!(evaluate(&rising, macd("close", 12, 26))[39].unwrap() > 0.0);
+    }
+
+    #[test]
+    fn test_bollinger_bands() {
+        let frame = prices();
+        let upper = evaluate(&frame, bollinger_upper("close", 3, 2.0));
+        let lower = evaluate(&frame, bollinger_lower("close", 3, 2.0));
+        let percent_b = evaluate(&frame, bollinger_percent_b("close", 3, 2.0));
+
+        // Window [10.5, 11.5, 12]: mean 34/3, sample standard deviation
+        // sqrt(7/12).
+        let (mean, sd) = (34.0 / 3.0, (7.0_f64 / 12.0).sqrt());
+        assert_approx_equal!(upper[4].unwrap(), mean + 2.0 * sd, 1e-12);
+        assert_approx_equal!(lower[4].unwrap(), mean - 2.0 * sd, 1e-12);
+        assert_approx_equal!(
+            percent_b[4].unwrap(),
+            (12.0 - mean + 2.0 * sd) / (4.0 * sd),
+            1e-12
+        );
+        assert_eq!(percent_b[1], None);
+    }
+
+    #[test]
+    fn test_atr() {
+        let frame = prices();
+        let tr = evaluate(&frame, true_range("high", "low", "close"));
+
+        // The first range is the high-low; then a gap up from 10 to a high
+        // of 11.5, and a drop from 11 to a low of 10.25.
+        assert_approx_equal!(tr[0].unwrap(), 0.75, 1e-12);
+        assert_approx_equal!(tr[1].unwrap(), 1.5, 1e-12);
+        assert_approx_equal!(tr[2].unwrap(), 0.75, 1e-12);
+
+        let atr = evaluate(&frame, atr("high", "low", "close", 2));
+        assert_eq!(atr[0], None);
+        assert_approx_equal!(atr[1].unwrap(), 0.5 * 1.5 + 0.5 * 0.75, 1e-12);
+    }
+}
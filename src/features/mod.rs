@@ -0,0 +1,69 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Feature engineering for market data, as Polars expressions.
+//! Requires the `data` feature.
+//!
+//! Indicators (RSI, MACD, Bollinger bands, ATR) and transformations (lags,
+//! returns, rolling statistics and z-scores) are functions of column names
+//! returning a Polars [`Expr`](polars::prelude::Expr), so they compose with
+//! each other and with any other Polars expression. Every feature at a row
+//! only uses data up to that row, except [`forward_returns`], which is
+//! meant as a prediction target.
+//!
+//! A [`FeatureSet`] names a list of expressions and evaluates them on a
+//! price history, either as new columns of the `DataFrame` (for
+//! [`DesignMatrixBuilder`](crate::ml::DesignMatrixBuilder) and the `ml`
+//! models) or as a matrix with one row per bar (for the backtester's
+//! [`MarketEnvironment`](crate::backtest::MarketEnvironment)).
+//!
+//! ```
+//! use RustQuant::features::*;
+//! use RustQuant::ml::DesignMatrixBuilder;
+//! use polars::prelude::*;
+//!
+//! let close: Vec<f64> = (0..100).map(|i| 100.0 + (i as f64 / 7.0).sin()).collect();
+//! let prices = df!(
+//!     "high" => close.iter().map(|c| c + 0.5).collect::<Vec<_>>(),
+//!     "low" => close.iter().map(|c| c - 0.5).collect::<Vec<_>>(),
+//!     "close" => &close
+//! )
+//! .unwrap();
+//!
+//! let features = FeatureSet::new()
+//!     .with("rsi", rsi("close", 14))
+//!     .with("macd", macd_histogram("close", 12, 26, 9))
+//!     .with("atr", atr("high", "low", "close", 14) / col("close"))
+//!     .with("z", rolling_z_score("close", 20));
+//!
+//! // Regress next-day returns on the features (warm-up rows are dropped).
+//! let frame = features
+//!     .with("target", forward_returns("close", 1))
+//!     .compute(&prices)
+//!     .unwrap();
+//! let (x, y) = DesignMatrixBuilder::new("target")
+//!     .regressors(&["rsi", "macd", "atr", "z"])
+//!     .build(&frame)
+//!     .unwrap();
+//!
+//! assert_eq!(x.ncols(), 5);
+//! assert_eq!(y.len(), 100 - 33 - 1);
+//! ```
+
+/// Named sets of features.
+pub mod feature_set;
+pub use feature_set::*;
+
+/// Technical indicators.
+pub mod indicators;
+pub use indicators::*;
+
+/// Lags, returns and rolling statistics.
+pub mod transforms;
+pub use transforms::*;
@@ -0,0 +1,138 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use polars::prelude::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Rolling window of `window` rows, null until it is full.
+pub(crate) fn window_options(window: usize) -> RollingOptions {
+    RollingOptions {
+        window_size: Duration::new(window as i64),
+        min_periods: window,
+        ..Default::default()
+    }
+}
+
+/// Value of the column `periods` rows earlier.
+pub fn lag(column: &str, periods: usize) -> Expr {
+    col(column).shift(periods as i64)
+}
+
+/// Simple returns over `periods` rows, $x_t / x_{t - k} - 1$.
+pub fn simple_returns(column: &str, periods: usize) -> Expr {
+    col(column) / lag(column, periods) - lit(1.0)
+}
+
+/// Log returns over `periods` rows, $\ln(x_t / x_{t - k})$.
+pub fn log_returns(column: &str, periods: usize) -> Expr {
+    (col(column) / lag(column, periods)).log(std::f64::consts::E)
+}
+
+/// Simple returns over the next `horizon` rows, $x_{t + h} / x_t - 1$.
+///
+/// This looks ahead, so it is a target to predict and must never be used
+/// as a feature.
+pub fn forward_returns(column: &str, horizon: usize) -> Expr {
+    col(column).shift(-(horizon as i64)) / col(column) - lit(1.0)
+}
+
+/// Mean over the last `window` rows.
+pub fn rolling_mean(column: &str, window: usize) -> Expr {
+    col(column).rolling_mean(window_options(window))
+}
+
+/// Sample standard deviation over the last `window` rows.
+pub fn rolling_std(column: &str, window: usize) -> Expr {
+    col(column).rolling_std(window_options(window))
+}
+
+/// Minimum over the last `window` rows.
+pub fn rolling_min(column: &str, window: usize) -> Expr {
+    col(column).rolling_min(window_options(window))
+}
+
+/// Maximum over the last `window` rows.
+pub fn rolling_max(column: &str, window: usize) -> Expr {
+    col(column).rolling_max(window_options(window))
+}
+
+/// Number of rolling standard deviations from the rolling mean, over the
+/// last `window` rows (including the current one).
+pub fn rolling_z_score(column: &str, window: usize) -> Expr {
+    (col(column) - rolling_mean(column, window)) / rolling_std(column, window)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_transforms {
+    use super::*;
+
+    fn evaluate(expr: Expr) -> Vec<Option<f64>> {
+        let frame = df!("x" => [1.0, 2.0, 4.0, 3.0, 6.0]).unwrap();
+
+        frame
+            .lazy()
+            .select([expr.alias("y")])
+            .collect()
+            .unwrap()
+            .column("y")
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn test_lags_and_returns() {
+        assert_eq!(
+            evaluate(lag("x", 2)),
+            [None, None, Some(1.0), Some(2.0), Some(4.0)]
+        );
+        assert_eq!(
+            evaluate(simple_returns("x", 1)),
+            [None, Some(1.0), Some(1.0), Some(-0.25), Some(1.0)]
+        );
+        assert_eq!(
+            evaluate(forward_returns("x", 2)),
+            [Some(3.0), Some(0.5), Some(0.5), None, None]
+        );
+
+        let log = evaluate(log_returns("x", 2));
+        assert_eq!(log[1], None);
+        assert_approx_equal!(log[2].unwrap(), 4.0_f64.ln(), 1e-12);
+    }
+
+    #[test]
+    fn test_rolling_statistics() {
+        assert_eq!(
+            evaluate(rolling_mean("x", 3)),
+            [None, None, Some(7.0 / 3.0), Some(3.0), Some(13.0 / 3.0)]
+        );
+        assert_eq!(
+            evaluate(rolling_min("x", 2)),
+            [None, Some(1.0), Some(2.0), Some(3.0), Some(3.0)]
+        );
+        assert_eq!(evaluate(rolling_max("x", 4))[4], Some(6.0));
+
+        // Window [2, 4, 3]: mean 3, sample standard deviation 1.
+        assert_approx_equal!(evaluate(rolling_std("x", 3))[3].unwrap(), 1.0, 1e-12);
+        assert_approx_equal!(evaluate(rolling_z_score("x", 3))[3].unwrap(), 0.0, 1e-12);
+
+        // Window [4, 3, 6]: mean 13/3, variance 7/3.
+        let z = evaluate(rolling_z_score("x", 3))[4].unwrap();
+        assert_approx_equal!(z, (6.0 - 13.0 / 3.0) / (7.0_f64 / 3.0).sqrt(), 1e-12);
+    }
+}
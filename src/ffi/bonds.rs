@@ -0,0 +1,267 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::ffi::{guard, input, require, write, RqStatus, RqYieldCurve};
+use crate::math::RootFinder;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// A fixed-coupon bullet bond.
+///
+/// Coupons are paid every `1 / frequency` years, counted back from the
+/// maturity, so the first coupon may come after less than a full period.
+/// Prices are dirty (they include the accrued interest). A frequency of
+/// zero is a zero-coupon bond.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RqBond {
+    /// Face value, paid at maturity.
+    pub face_value: f64,
+    /// Annual coupon rate (e.g. 0.05 for 5%).
+    pub coupon_rate: f64,
+    /// Time to maturity in years, positive.
+    pub maturity: f64,
+    /// Coupons per year (e.g. 2 for semi-annual), or zero.
+    pub frequency: u32,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl RqBond {
+    /// Remaining cash flows as `(time, amount)` pairs, in time order.
+    pub(crate) fn cash_flows(&self) -> Result<Vec<(f64, f64)>, RqStatus> {
+        require(self.face_value.is_finite() && self.face_value > 0.0)?;
+        require(self.coupon_rate.is_finite())?;
+        require(self.maturity.is_finite() && self.maturity > 0.0)?;
+
+        if self.frequency == 0 {
+            return Ok(vec![(self.maturity, self.face_value)]);
+        }
+
+        let period = 1.0 / self.frequency as f64;
+        let coupon = self.face_value * self.coupon_rate * period;
+
+        // Coupon dates, back from the maturity (ignoring rounding noise).
+        let n = (self.maturity / period - 1e-9).floor() as usize + 1;
+        let mut flows: Vec<(f64, f64)> = (0..n)
+            .rev()
+            .map(|k| (self.maturity - k as f64 * period, coupon))
+            .collect();
+        flows[n - 1].1 += self.face_value;
+
+        Ok(flows)
+    }
+
+    /// Compounding periods per year of the yield (annual for zeros).
+    fn compounding(&self) -> f64 {
+        self.frequency.max(1) as f64
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Bond price, discounting the cash flows on the curve (which must cover
+/// the maturity).
+///
+/// # Safety
+///
+/// `bond`, `curve` and `price` must be null or valid pointers.
+#[no_mangle]
+pub unsafe extern "C" fn rq_bond_price(
+    bond: *const RqBond,
+    curve: *const RqYieldCurve,
+    price: *mut f64,
+) -> RqStatus {
+    guard(|| {
+        let curve = input(curve)?;
+        let value = input(bond)?
+            .cash_flows()?
+            .iter()
+            .map(|&(t, amount)| Ok(amount * curve.discount_factor(t)?))
+            .sum::<Result<f64, RqStatus>>()?;
+
+        write(price, value)
+    })
+}
+
+/// Bond price at a yield compounded at the coupon frequency.
+///
+/// # Safety
+///
+/// `bond` and `price` must be null or valid pointers.
+#[no_mangle]
+pub unsafe extern "C" fn rq_bond_price_from_yield(
+    bond: *const RqBond,
+    yield_rate: f64,
+    price: *mut f64,
+) -> RqStatus {
+    guard(|| {
+        let bond = input(bond)?;
+        let m = bond.compounding();
+        require(yield_rate.is_finite() && yield_rate > -m)?;
+
+        let value = bond
+            .cash_flows()?
+            .iter()
+            .map(|&(t, amount)| amount * (1.0 + yield_rate / m).powf(-m * t))
+            .sum();
+
+        write(price, value)
+    })
+}
+
+/// Yield to maturity (compounded at the coupon frequency) of a dirty
+/// price, in $[-50\%, 100\%]$.
+///
+/// # Safety
+///
+/// `bond` and `yield_rate` must be null or valid pointers.
+#[no_mangle]
+pub unsafe extern "C" fn rq_bond_yield_to_maturity(
+    bond: *const RqBond,
+    price: f64,
+    yield_rate: *mut f64,
+) -> RqStatus {
+    guard(|| {
+        let bond = input(bond)?;
+        let flows = bond.cash_flows()?;
+        let m = bond.compounding();
+        require(price.is_finite() && price > 0.0)?;
+
+        let root = RootFinder::default()
+            .newton_bracketed(
+                |y| {
+                    flows
+                        .iter()
+                        .fold((-price, 0.0), |(value, derivative), &(t, amount)| {
+                            let discount = (1.0 + y / m).powf(-m * t);
+                            (
+                                value + amount * discount,
+                                derivative - t * amount * discount / (1.0 + y / m),
+                            )
+                        })
+                },
+                bond.coupon_rate,
+                -0.5,
+                1.0,
+            )
+            .map_err(|_| RqStatus::NoConvergence)?;
+
+        write(yield_rate, root.root)
+    })
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_bonds {
+    use super::*;
+    use crate::ffi::{rq_yield_curve_free, rq_yield_curve_new};
+    use std::ptr;
+
+    const BOND: RqBond = RqBond {
+        face_value: 100.0,
+        coupon_rate: 0.05,
+        maturity: 1.5,
+        frequency: 2,
+    };
+
+    #[test]
+    fn test_cash_flows() {
+        assert_eq!(
+            BOND.cash_flows().unwrap(),
+            [(0.5, 2.5), (1.0, 2.5), (1.5, 102.5)]
+        );
+
+        // A short first period.
+        let stub = RqBond {
+            maturity: 1.25,
+            ..BOND
+        };
+        assert_eq!(stub.cash_flows().unwrap()[0], (0.25, 2.5));
+
+        let zero = RqBond {
+            frequency: 0,
+            ..BOND
+        };
+        assert_eq!(zero.cash_flows().unwrap(), [(1.5, 100.0)]);
+    }
+
+    #[test]
+    fn test_yield_round_trip() {
+        let (mut price, mut yield_rate) = (0.0, 0.0);
+
+        // SAFETY: all pointers are to live locals.
+        unsafe {
+            // At a yield equal to the coupon, the bond prices at par.
+            assert_eq!(
+                rq_bond_price_from_yield(&BOND, 0.05, &mut price),
+                RqStatus::Ok
+            );
+            assert_approx_equal!(price, 100.0, 1e-10);
+
+            assert_eq!(
+                rq_bond_price_from_yield(&BOND, 0.07, &mut price),
+                RqStatus::Ok
+            );
+            assert_eq!(
+                rq_bond_yield_to_maturity(&BOND, price, &mut yield_rate),
+                RqStatus::Ok
+            );
+            assert_approx_equal!(yield_rate, 0.07, 1e-10);
+
+            assert_eq!(
+                rq_bond_yield_to_maturity(&BOND, 1e6, &mut yield_rate),
+                RqStatus::NoConvergence
+            );
+        }
+    }
+
+    #[test]
+    fn test_price_on_curve() {
+        let mut curve = ptr::null_mut();
+        let mut price = 0.0;
+
+        // SAFETY: all pointers are to live locals or arrays of the stated length,
+        // and the curve is released once.
+        unsafe {
+            rq_yield_curve_new([2.0].as_ptr(), [0.04].as_ptr(), 1, &mut curve);
+
+            assert_eq!(rq_bond_price(&BOND, curve, &mut price), RqStatus::Ok);
+            // Flat 4% curve, with maturities rounded to whole days.
+            let discount = |t: f64| (-0.04 * (t * 365.0).round() / 365.0).exp();
+            let expected = 2.5 * discount(0.5) + 2.5 * discount(1.0) + 102.5 * discount(1.5);
+            assert_approx_equal!(price, expected, 1e-12);
+
+            // The curve does not reach a 3-year maturity.
+            let long = RqBond {
+                maturity: 3.0,
+                ..BOND
+            };
+            assert_eq!(
+                rq_bond_price(&long, curve, &mut price),
+                RqStatus::InvalidArgument
+            );
+            assert_eq!(
+                rq_bond_price(&BOND, ptr::null(), &mut price),
+                RqStatus::NullPointer
+            );
+
+            rq_yield_curve_free(curve);
+        }
+    }
+}
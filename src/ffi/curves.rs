@@ -0,0 +1,257 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::curves::{Curve, YieldCurve};
+use crate::ffi::{guard, input, input_slice, output_slice, require, write, RqStatus};
+use std::collections::BTreeMap;
+use time::{Duration, OffsetDateTime};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Opaque yield curve handle: continuously compounded zero rates at
+/// maturities in years from the curve's creation, linearly interpolated.
+pub struct RqYieldCurve {
+    curve: YieldCurve,
+    initial_date: OffsetDateTime,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl RqYieldCurve {
+    /// Curve through the rates at the maturities, starting now. The rate of
+    /// the shortest maturity is extended flat back to the start.
+    pub(crate) fn new(maturities: &[f64], rates: &[f64]) -> Result<Self, RqStatus> {
+        require(!maturities.is_empty())?;
+        require(maturities.iter().all(|t| t.is_finite() && *t >= 0.0))?;
+        require(rates.iter().all(|r| r.is_finite()))?;
+
+        let initial_date = OffsetDateTime::now_utc();
+        let mut points: BTreeMap<OffsetDateTime, f64> = maturities
+            .iter()
+            .zip(rates)
+            .map(|(&t, &r)| (Self::date_after(initial_date, t), r))
+            .collect();
+
+        let first = *points.values().next().unwrap();
+        points.entry(initial_date).or_insert(first);
+
+        Ok(Self {
+            curve: YieldCurve::new(points),
+            initial_date,
+        })
+    }
+
+    /// Discount factor at the maturity.
+    pub(crate) fn discount_factor(&self, maturity: f64) -> Result<f64, RqStatus> {
+        Ok(self.curve.discount_factor(self.date(maturity)?))
+    }
+
+    /// Zero rate at the maturity.
+    pub(crate) fn rate(&self, maturity: f64) -> Result<f64, RqStatus> {
+        Ok(self.curve.rate(self.date(maturity)?))
+    }
+
+    /// Date of a maturity within the curve.
+    fn date(&self, maturity: f64) -> Result<OffsetDateTime, RqStatus> {
+        require(maturity.is_finite() && maturity >= 0.0)?;
+
+        let date = Self::date_after(self.initial_date, maturity);
+        require(date <= self.curve.terminal_date())?;
+
+        Ok(date)
+    }
+
+    /// Maturities are rounded to whole days (Actual/365 Fixed).
+    fn date_after(start: OffsetDateTime, years: f64) -> OffsetDateTime {
+        start + Duration::days((years * 365.0).round() as i64)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Creates a yield curve from `n` continuously compounded zero `rates` at
+/// `maturities` (in years, non-negative). Release it with
+/// `rq_yield_curve_free`.
+///
+/// # Safety
+///
+/// `maturities` and `rates` must be null or valid for `n` values, and
+/// `curve` null or a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn rq_yield_curve_new(
+    maturities: *const f64,
+    rates: *const f64,
+    n: usize,
+    curve: *mut *mut RqYieldCurve,
+) -> RqStatus {
+    guard(|| {
+        // Checked first, so that the new curve cannot leak.
+        if curve.is_null() {
+            return Err(RqStatus::NullPointer);
+        }
+
+        let new = RqYieldCurve::new(input_slice(maturities, n)?, input_slice(rates, n)?)?;
+
+        write(curve, Box::into_raw(Box::new(new)))
+    })
+}
+
+/// Releases a yield curve (null is ignored).
+///
+/// # Safety
+///
+/// `curve` must be null or a curve from `rq_yield_curve_new` that has not
+/// been released.
+#[no_mangle]
+pub unsafe extern "C" fn rq_yield_curve_free(curve: *mut RqYieldCurve) {
+    if !curve.is_null() {
+        drop(Box::from_raw(curve));
+    }
+}
+
+/// Zero rate at the maturity (in years, within the curve).
+///
+/// # Safety
+///
+/// `curve` and `rate` must be null or valid pointers.
+#[no_mangle]
+pub unsafe extern "C" fn rq_yield_curve_rate(
+    curve: *const RqYieldCurve,
+    maturity: f64,
+    rate: *mut f64,
+) -> RqStatus {
+    guard(|| write(rate, input(curve)?.rate(maturity)?))
+}
+
+/// Discount factor at the maturity (in years, within the curve).
+///
+/// # Safety
+///
+/// `curve` and `discount_factor` must be null or valid pointers.
+#[no_mangle]
+pub unsafe extern "C" fn rq_yield_curve_discount_factor(
+    curve: *const RqYieldCurve,
+    maturity: f64,
+    discount_factor: *mut f64,
+) -> RqStatus {
+    guard(|| write(discount_factor, input(curve)?.discount_factor(maturity)?))
+}
+
+/// Discount factors at `n` maturities.
+///
+/// # Safety
+///
+/// `curve` must be null or a valid pointer, and `maturities` and
+/// `discount_factors` null or valid for `n` values.
+#[no_mangle]
+pub unsafe extern "C" fn rq_yield_curve_discount_factors(
+    curve: *const RqYieldCurve,
+    maturities: *const f64,
+    n: usize,
+    discount_factors: *mut f64,
+) -> RqStatus {
+    guard(|| {
+        let curve = input(curve)?;
+        let values = input_slice(maturities, n)?
+            .iter()
+            .map(|&t| curve.discount_factor(t))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        output_slice(discount_factors, n)?.copy_from_slice(&values);
+        Ok(())
+    })
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_curves {
+    use super::*;
+    use std::ptr;
+
+    #[test]
+    fn test_yield_curve_lifecycle() {
+        let maturities = [1.0, 2.0, 5.0];
+        let rates = [0.03, 0.035, 0.04];
+        let mut curve = ptr::null_mut();
+
+        // SAFETY: all pointers are to live locals or arrays of the stated length,
+        // and the curve is released once.
+        unsafe {
+            assert_eq!(
+                rq_yield_curve_new(maturities.as_ptr(), rates.as_ptr(), 3, &mut curve),
+                RqStatus::Ok
+            );
+            assert!(!curve.is_null());
+
+            let (mut rate, mut discount_factor) = (0.0, 0.0);
+            assert_eq!(rq_yield_curve_rate(curve, 0.5, &mut rate), RqStatus::Ok);
+            assert_approx_equal!(rate, 0.03, 1e-12);
+
+            assert_eq!(
+                rq_yield_curve_discount_factor(curve, 5.0, &mut discount_factor),
+                RqStatus::Ok
+            );
+            assert_approx_equal!(discount_factor, (-0.04_f64 * 5.0).exp(), 1e-12);
+
+            let mut discount_factors = [0.0; 2];
+            assert_eq!(
+                rq_yield_curve_discount_factors(
+                    curve,
+                    [0.0, 2.0].as_ptr(),
+                    2,
+                    discount_factors.as_mut_ptr()
+                ),
+                RqStatus::Ok
+            );
+            assert_eq!(discount_factors[0], 1.0);
+            assert_approx_equal!(discount_factors[1], (-0.035_f64 * 2.0).exp(), 1e-12);
+
+            // Beyond the last maturity.
+            assert_eq!(
+                rq_yield_curve_rate(curve, 6.0, &mut rate),
+                RqStatus::InvalidArgument
+            );
+
+            rq_yield_curve_free(curve);
+            rq_yield_curve_free(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn test_invalid_curves() {
+        let mut curve = ptr::null_mut();
+
+        // SAFETY: the arrays have the stated lengths and no curve is created.
+        unsafe {
+            assert_eq!(
+                rq_yield_curve_new(ptr::null(), ptr::null(), 0, &mut curve),
+                RqStatus::InvalidArgument
+            );
+            assert_eq!(
+                rq_yield_curve_new([-1.0].as_ptr(), [0.03].as_ptr(), 1, &mut curve),
+                RqStatus::InvalidArgument
+            );
+            assert_eq!(
+                rq_yield_curve_new([1.0].as_ptr(), [0.03].as_ptr(), 1, ptr::null_mut()),
+                RqStatus::NullPointer
+            );
+        }
+
+        assert!(curve.is_null());
+    }
+}
@@ -0,0 +1,247 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! C API (requires the `ffi` feature).
+//!
+//! A stable C ABI for the core pricers, so the library can be called from
+//! C, C++, C# (P/Invoke) or Excel add-ins. Build it as a shared or static
+//! library with:
+//!
+//! ```bash
+//! cargo rustc --release --lib --features ffi --crate-type cdylib
+//! cargo rustc --release --lib --features ffi --crate-type staticlib
+//! ```
+//!
+//! and include the header `include/rustquant.h`, which is generated from
+//! this module with `cbindgen`:
+//!
+//! ```bash
+//! cbindgen --config cbindgen.toml --output include/rustquant.h
+//! ```
+//!
+//! Conventions:
+//!
+//! - Every function is prefixed with `rq_` and returns an [`RqStatus`],
+//!   writing its results through output pointers, which are left untouched
+//!   on failure.
+//! - Times are year fractions.
+//! - Panics never cross the boundary: they are caught and reported as
+//!   [`RqStatus::Panic`].
+//! - Opaque handles (such as `RqYieldCurve`) are created by an `rq_*_new`
+//!   function and released with the matching `rq_*_free`. A handle is
+//!   read-only after creation, so it can be shared between threads.
+//!
+//! ```c
+//! #include "rustquant.h"
+//!
+//! RqBlackScholesInputs inputs = {100.0, 100.0, 0.2, 0.05, 0.05, 1.0, RQ_CALL};
+//! double price;
+//!
+//! if (rq_black_scholes_price(&inputs, &price) != RQ_STATUS_OK) {
+//!     /* handle the error */
+//! }
+//! ```
+
+use std::ffi::c_char;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+/// Fixed-coupon bonds.
+pub mod bonds;
+pub use bonds::*;
+
+/// Yield curves.
+pub mod curves;
+pub use curves::*;
+
+/// Monte Carlo simulation and pricing.
+pub mod monte_carlo;
+pub use monte_carlo::*;
+
+/// Black-Scholes pricing.
+pub mod pricers;
+pub use pricers::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Status code returned by every function of the C API.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RqStatus {
+    /// Success.
+    Ok = 0,
+    /// A required pointer argument is null.
+    NullPointer = 1,
+    /// An argument is out of its domain (e.g. a negative volatility or a
+    /// maturity beyond the curve).
+    InvalidArgument = 2,
+    /// An iterative solver did not converge.
+    NoConvergence = 3,
+    /// The library panicked (a bug).
+    Panic = 4,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Version of the library, as a static NUL-terminated string.
+#[no_mangle]
+pub extern "C" fn rq_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+/// Description of a status code, as a static NUL-terminated string.
+#[no_mangle]
+pub extern "C" fn rq_status_message(status: i32) -> *const c_char {
+    let message: &'static str = match status {
+        0 => "ok\0",
+        1 => "null pointer argument\0",
+        2 => "invalid argument\0",
+        3 => "no convergence\0",
+        4 => "internal panic\0",
+        _ => "unknown status\0",
+    };
+
+    message.as_ptr().cast()
+}
+
+/// Runs the body of an API function, turning errors and panics into a
+/// status code.
+pub(crate) fn guard<F>(body: F) -> RqStatus
+where
+    F: FnOnce() -> Result<(), RqStatus>,
+{
+    match catch_unwind(AssertUnwindSafe(body)) {
+        Ok(Ok(())) => RqStatus::Ok,
+        Ok(Err(status)) => status,
+        Err(_) => RqStatus::Panic,
+    }
+}
+
+/// Borrows the value behind an input pointer.
+///
+/// # Safety
+///
+/// The pointer must be null or valid for reads for the duration of the
+/// call.
+pub(crate) unsafe fn input<'a, T>(pointer: *const T) -> Result<&'a T, RqStatus> {
+    pointer.as_ref().ok_or(RqStatus::NullPointer)
+}
+
+/// Borrows `len` values behind an input pointer (which may be null if
+/// `len` is zero).
+///
+/// # Safety
+///
+/// The pointer must be null or valid for reads of `len` values.
+pub(crate) unsafe fn input_slice<'a, T>(
+    pointer: *const T,
+    len: usize,
+) -> Result<&'a [T], RqStatus> {
+    match (pointer.is_null(), len) {
+        (_, 0) => Ok(&[]),
+        (true, _) => Err(RqStatus::NullPointer),
+        (false, _) => Ok(std::slice::from_raw_parts(pointer, len)),
+    }
+}
+
+/// Borrows `len` values behind an output pointer.
+///
+/// # Safety
+///
+/// The pointer must be null or valid for writes of `len` values.
+pub(crate) unsafe fn output_slice<'a, T>(
+    pointer: *mut T,
+    len: usize,
+) -> Result<&'a mut [T], RqStatus> {
+    match (pointer.is_null(), len) {
+        (_, 0) => Ok(&mut []),
+        (true, _) => Err(RqStatus::NullPointer),
+        (false, _) => Ok(std::slice::from_raw_parts_mut(pointer, len)),
+    }
+}
+
+/// Writes a result through an output pointer.
+///
+/// # Safety
+///
+/// The pointer must be null or valid for writes.
+pub(crate) unsafe fn write<T>(pointer: *mut T, value: T) -> Result<(), RqStatus> {
+    if pointer.is_null() {
+        return Err(RqStatus::NullPointer);
+    }
+
+    pointer.write(value);
+    Ok(())
+}
+
+/// Errors with [`RqStatus::InvalidArgument`] unless the condition holds.
+pub(crate) fn require(condition: bool) -> Result<(), RqStatus> {
+    if condition {
+        Ok(())
+    } else {
+        Err(RqStatus::InvalidArgument)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_ffi {
+    use super::*;
+    use std::ffi::CStr;
+
+    #[test]
+    fn test_guard() {
+        assert_eq!(guard(|| Ok(())), RqStatus::Ok);
+        assert_eq!(guard(|| require(false)), RqStatus::InvalidArgument);
+        assert_eq!(guard(|| panic!("bug")), RqStatus::Panic);
+    }
+
+    #[test]
+    fn test_strings() {
+        // SAFETY: the strings are static and NUL-terminated.
+        let version = unsafe { CStr::from_ptr(rq_version()) };
+        assert_eq!(version.to_str().unwrap(), env!("CARGO_PKG_VERSION"));
+
+        // SAFETY: as above.
+        let message = unsafe { CStr::from_ptr(rq_status_message(RqStatus::NoConvergence as i32)) };
+        assert_eq!(message.to_str().unwrap(), "no convergence");
+    }
+
+    #[test]
+    fn test_header_declares_every_function() {
+        let header = include_str!("../../include/rustquant.h");
+        let sources = [
+            include_str!("mod.rs"),
+            include_str!("bonds.rs"),
+            include_str!("curves.rs"),
+            include_str!("monte_carlo.rs"),
+            include_str!("pricers.rs"),
+        ];
+
+        let functions: Vec<&str> = sources
+            .iter()
+            .flat_map(|source| source.split("extern \"C\" fn ").skip(1))
+            .map(|rest| rest.split('(').next().unwrap())
+            .collect();
+
+        assert!(functions.len() > 10);
+        for function in functions {
+            assert!(
+                header.contains(&format!("{function}(")),
+                "{function} is missing from include/rustquant.h"
+            );
+        }
+    }
+}
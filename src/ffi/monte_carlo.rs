@@ -0,0 +1,218 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::ffi::{guard, input, output_slice, require, write, RqBlackScholesInputs, RqStatus};
+use crate::stochastics::{GeometricBrownianMotion, SimulationConfig, StochasticProcess};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Monte Carlo price estimate.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RqMonteCarloResult {
+    /// Mean discounted payoff.
+    pub price: f64,
+    /// Standard error of the mean.
+    pub standard_error: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Simulates `n_paths` geometric Brownian motion paths (Euler-Maruyama,
+/// `n_steps` steps from 0 to `t`), written row by row to `paths`, which
+/// must hold `n_paths * (n_steps + 1)` values. The same seed gives the
+/// same paths.
+///
+/// # Safety
+///
+/// `paths` must be null or valid for `len` values.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn rq_simulate_gbm(
+    s0: f64,
+    mu: f64,
+    sigma: f64,
+    t: f64,
+    n_steps: usize,
+    n_paths: usize,
+    seed: u64,
+    paths: *mut f64,
+    len: usize,
+) -> RqStatus {
+    guard(|| {
+        require(s0.is_finite() && mu.is_finite() && sigma.is_finite() && sigma >= 0.0)?;
+        require(t.is_finite() && t > 0.0 && n_steps > 0 && n_paths > 0)?;
+        require(n_paths.checked_mul(n_steps + 1) == Some(len))?;
+
+        let output = output_slice(paths, len)?;
+        let trajectories = GeometricBrownianMotion::new(mu, sigma).simulate_with_config(
+            s0,
+            0.0,
+            t,
+            n_steps,
+            n_paths,
+            &SimulationConfig::new(true).with_seed(seed),
+        );
+
+        for (row, path) in output
+            .chunks_exact_mut(n_steps + 1)
+            .zip(trajectories.iter())
+        {
+            for (value, x) in row.iter_mut().zip(path) {
+                *value = *x;
+            }
+        }
+
+        Ok(())
+    })
+}
+
+/// Monte Carlo price of a European option under geometric Brownian motion
+/// with drift the cost of carry, discounted at the risk-free rate. Mainly
+/// a check on the analytic price, or a template for exotic payoffs.
+///
+/// # Safety
+///
+/// `inputs` and `result` must be null or valid pointers.
+#[no_mangle]
+pub unsafe extern "C" fn rq_monte_carlo_european(
+    inputs: *const RqBlackScholesInputs,
+    n_steps: usize,
+    n_paths: usize,
+    seed: u64,
+    result: *mut RqMonteCarloResult,
+) -> RqStatus {
+    guard(|| {
+        let inputs = input(inputs)?.to_inputs(true)?;
+        require(n_steps > 0 && n_paths > 1)?;
+
+        let trajectories = GeometricBrownianMotion::new(inputs.cost_of_carry, inputs.volatility)
+            .simulate_with_config(
+                inputs.underlying_price,
+                0.0,
+                inputs.time_to_expiry,
+                n_steps,
+                n_paths,
+                &SimulationConfig::new(true).with_seed(seed),
+            );
+
+        let w = inputs.option_type as i32 as f64;
+        let discount = (-inputs.risk_free_rate * inputs.time_to_expiry).exp();
+        let payoffs: Vec<f64> = trajectories
+            .terminal_values()
+            .iter()
+            .map(|s| discount * (w * (s - inputs.strike_price)).max(0.0))
+            .collect();
+
+        let m = n_paths as f64;
+        let mean = payoffs.iter().sum::<f64>() / m;
+        let variance = payoffs.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / (m - 1.0);
+
+        write(
+            result,
+            RqMonteCarloResult {
+                price: mean,
+                standard_error: (variance / m).sqrt(),
+            },
+        )
+    })
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_monte_carlo {
+    use super::*;
+    use crate::ffi::RQ_PUT;
+
+    #[test]
+    fn test_simulate_gbm() {
+        let (n_steps, n_paths) = (4, 3);
+        let mut first = vec![0.0; n_paths * (n_steps + 1)];
+        let mut second = first.clone();
+
+        // SAFETY: the buffers hold at least `len` values.
+        unsafe {
+            let status = rq_simulate_gbm(
+                100.0,
+                0.05,
+                0.2,
+                1.0,
+                n_steps,
+                n_paths,
+                7,
+                first.as_mut_ptr(),
+                first.len(),
+            );
+            assert_eq!(status, RqStatus::Ok);
+
+            rq_simulate_gbm(
+                100.0,
+                0.05,
+                0.2,
+                1.0,
+                n_steps,
+                n_paths,
+                7,
+                second.as_mut_ptr(),
+                second.len(),
+            );
+
+            // The buffer must match the number of values.
+            let status = rq_simulate_gbm(
+                100.0,
+                0.05,
+                0.2,
+                1.0,
+                n_steps,
+                n_paths,
+                7,
+                second.as_mut_ptr(),
+                second.len() - 1,
+            );
+            assert_eq!(status, RqStatus::InvalidArgument);
+        }
+
+        assert_eq!(first, second);
+        assert!(first.chunks(n_steps + 1).all(|path| path[0] == 100.0));
+        assert_ne!(first[1], first[n_steps + 2]);
+    }
+
+    #[test]
+    fn test_monte_carlo_european() {
+        let inputs = RqBlackScholesInputs {
+            underlying_price: 100.0,
+            strike_price: 100.0,
+            volatility: 0.2,
+            risk_free_rate: 0.05,
+            cost_of_carry: 0.05,
+            time_to_expiry: 1.0,
+            option_type: RQ_PUT,
+        };
+        let mut result = RqMonteCarloResult::default();
+
+        // SAFETY: all pointers are to live locals.
+        unsafe {
+            assert_eq!(
+                rq_monte_carlo_european(&inputs, 50, 20_000, 42, &mut result),
+                RqStatus::Ok
+            );
+        }
+
+        // Analytic price 5.5735, within four standard errors.
+        assert!(result.standard_error > 0.0 && result.standard_error < 0.1);
+        assert!((result.price - 5.573526022256971).abs() < 4.0 * result.standard_error);
+    }
+}
@@ -0,0 +1,302 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::ffi::{guard, input, input_slice, output_slice, require, write, RqStatus};
+use crate::instruments::options::{BlackScholesInputs, TypeFlag};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// `option_type` of a call.
+pub const RQ_CALL: i32 = 1;
+
+/// `option_type` of a put.
+pub const RQ_PUT: i32 = -1;
+
+/// Inputs of a generalised Black-Scholes-Merton option.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RqBlackScholesInputs {
+    /// Underlying price, positive.
+    pub underlying_price: f64,
+    /// Strike price, positive.
+    pub strike_price: f64,
+    /// Volatility, positive.
+    pub volatility: f64,
+    /// Continuously compounded risk-free rate.
+    pub risk_free_rate: f64,
+    /// Cost of carry (the risk-free rate for a non-dividend-paying stock).
+    pub cost_of_carry: f64,
+    /// Time to expiry in years, positive.
+    pub time_to_expiry: f64,
+    /// `RQ_CALL` or `RQ_PUT`.
+    pub option_type: i32,
+}
+
+/// Price and Greeks of a Black-Scholes-Merton option.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RqGreeks {
+    /// Price.
+    pub price: f64,
+    /// Sensitivity to the underlying price.
+    pub delta: f64,
+    /// Sensitivity of the delta to the underlying price.
+    pub gamma: f64,
+    /// Sensitivity to the volatility.
+    pub vega: f64,
+    /// Sensitivity to the passage of time.
+    pub theta: f64,
+    /// Sensitivity to the risk-free rate.
+    pub rho: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl RqBlackScholesInputs {
+    /// The validated inputs. The volatility is only checked if
+    /// `check_volatility` is set (it is ignored by the implied volatility).
+    pub(crate) fn to_inputs(self, check_volatility: bool) -> Result<BlackScholesInputs, RqStatus> {
+        let positive = |x: f64| x.is_finite() && x > 0.0;
+
+        require(positive(self.underlying_price))?;
+        require(positive(self.strike_price))?;
+        require(positive(self.time_to_expiry))?;
+        require(!check_volatility || positive(self.volatility))?;
+        require(self.risk_free_rate.is_finite() && self.cost_of_carry.is_finite())?;
+
+        let option_type = match self.option_type {
+            RQ_CALL => TypeFlag::Call,
+            RQ_PUT => TypeFlag::Put,
+            _ => return Err(RqStatus::InvalidArgument),
+        };
+
+        Ok(BlackScholesInputs {
+            underlying_price: self.underlying_price,
+            strike_price: self.strike_price,
+            volatility: self.volatility,
+            risk_free_rate: self.risk_free_rate,
+            cost_of_carry: self.cost_of_carry,
+            time_to_expiry: self.time_to_expiry,
+            option_type,
+        })
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Black-Scholes-Merton price.
+///
+/// # Safety
+///
+/// `inputs` and `price` must be null or valid pointers.
+#[no_mangle]
+pub unsafe extern "C" fn rq_black_scholes_price(
+    inputs: *const RqBlackScholesInputs,
+    price: *mut f64,
+) -> RqStatus {
+    guard(|| {
+        let inputs = input(inputs)?.to_inputs(true)?;
+
+        write(price, inputs.price())
+    })
+}
+
+/// Black-Scholes-Merton prices of `n` options.
+///
+/// # Safety
+///
+/// `inputs` and `prices` must be null or valid for `n` values.
+#[no_mangle]
+pub unsafe extern "C" fn rq_black_scholes_price_batch(
+    inputs: *const RqBlackScholesInputs,
+    n: usize,
+    prices: *mut f64,
+) -> RqStatus {
+    guard(|| {
+        let inputs = input_slice(inputs, n)?
+            .iter()
+            .map(|inputs| inputs.to_inputs(true))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (price, inputs) in output_slice(prices, n)?.iter_mut().zip(&inputs) {
+            *price = inputs.price();
+        }
+
+        Ok(())
+    })
+}
+
+/// Black-Scholes-Merton price and Greeks.
+///
+/// # Safety
+///
+/// `inputs` and `greeks` must be null or valid pointers.
+#[no_mangle]
+pub unsafe extern "C" fn rq_black_scholes_greeks(
+    inputs: *const RqBlackScholesInputs,
+    greeks: *mut RqGreeks,
+) -> RqStatus {
+    guard(|| {
+        let inputs = input(inputs)?.to_inputs(true)?;
+
+        write(
+            greeks,
+            RqGreeks {
+                price: inputs.price(),
+                delta: inputs.delta(),
+                gamma: inputs.gamma(),
+                vega: inputs.vega(),
+                theta: inputs.theta(),
+                rho: inputs.rho(),
+            },
+        )
+    })
+}
+
+/// Black-Scholes-Merton implied volatility of `price` (the `volatility` of
+/// the inputs is ignored). Fails with `RQ_STATUS_NO_CONVERGENCE` if no
+/// volatility in $[10^{-6}, 5]$ matches the price.
+///
+/// # Safety
+///
+/// `inputs` and `volatility` must be null or valid pointers.
+#[no_mangle]
+pub unsafe extern "C" fn rq_black_scholes_implied_volatility(
+    inputs: *const RqBlackScholesInputs,
+    price: f64,
+    volatility: *mut f64,
+) -> RqStatus {
+    guard(|| {
+        let inputs = input(inputs)?.to_inputs(false)?;
+        require(price.is_finite() && price > 0.0)?;
+
+        let implied = inputs
+            .implied_volatility(price)
+            .map_err(|_| RqStatus::NoConvergence)?;
+
+        write(volatility, implied)
+    })
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_pricers {
+    use super::*;
+    use std::ptr;
+
+    const INPUTS: RqBlackScholesInputs = RqBlackScholesInputs {
+        underlying_price: 100.0,
+        strike_price: 100.0,
+        volatility: 0.2,
+        risk_free_rate: 0.05,
+        cost_of_carry: 0.05,
+        time_to_expiry: 1.0,
+        option_type: RQ_CALL,
+    };
+
+    #[test]
+    fn test_price_and_greeks() {
+        let mut price = 0.0;
+        let mut greeks = RqGreeks::default();
+
+        // SAFETY: all pointers are to live locals.
+        unsafe {
+            assert_eq!(rq_black_scholes_price(&INPUTS, &mut price), RqStatus::Ok);
+            assert_eq!(rq_black_scholes_greeks(&INPUTS, &mut greeks), RqStatus::Ok);
+        }
+
+        assert_approx_equal!(price, 10.450583572185565, 1e-10);
+        assert_eq!(greeks.price, price);
+        assert_approx_equal!(greeks.delta, 0.6368306511756191, 1e-10);
+
+        let put = RqBlackScholesInputs {
+            option_type: RQ_PUT,
+            ..INPUTS
+        };
+        let mut prices = [0.0; 2];
+        // SAFETY: both arrays hold two values.
+        unsafe {
+            assert_eq!(
+                rq_black_scholes_price_batch([INPUTS, put].as_ptr(), 2, prices.as_mut_ptr()),
+                RqStatus::Ok
+            );
+        }
+
+        // Put-call parity.
+        assert_approx_equal!(
+            prices[0] - prices[1],
+            100.0 - 100.0 * (-0.05_f64).exp(),
+            1e-10
+        );
+    }
+
+    #[test]
+    fn test_implied_volatility() {
+        let mut volatility = 0.0;
+
+        // SAFETY: all pointers are to live locals.
+        unsafe {
+            assert_eq!(
+                rq_black_scholes_implied_volatility(&INPUTS, 10.450583572185565, &mut volatility),
+                RqStatus::Ok
+            );
+            assert_approx_equal!(volatility, 0.2, 1e-8);
+
+            assert_eq!(
+                rq_black_scholes_implied_volatility(&INPUTS, 150.0, &mut volatility),
+                RqStatus::NoConvergence
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        let mut price = -1.0;
+        let bad_type = RqBlackScholesInputs {
+            option_type: 0,
+            ..INPUTS
+        };
+        let negative_volatility = RqBlackScholesInputs {
+            volatility: -0.2,
+            ..INPUTS
+        };
+
+        // SAFETY: all pointers are to live locals or null.
+        unsafe {
+            assert_eq!(
+                rq_black_scholes_price(ptr::null(), &mut price),
+                RqStatus::NullPointer
+            );
+            assert_eq!(
+                rq_black_scholes_price(&INPUTS, ptr::null_mut()),
+                RqStatus::NullPointer
+            );
+            assert_eq!(
+                rq_black_scholes_price(&bad_type, &mut price),
+                RqStatus::InvalidArgument
+            );
+            assert_eq!(
+                rq_black_scholes_price(&negative_volatility, &mut price),
+                RqStatus::InvalidArgument
+            );
+        }
+
+        // Outputs are untouched on failure.
+        assert_eq!(price, -1.0);
+    }
+}
@@ -7,12 +7,16 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-use crate::curves::{Curve, YieldCurve};
-use crate::instruments::Instrument;
-use crate::money::Currency;
-use crate::time::{BusinessDayConvention, PaymentFrequency};
+use crate::curves::{Compounding, Curve, YieldCurve};
+use crate::instruments::{DiscountedCashFlow, Instrument, PricingEngine, PricingResult};
+use crate::math::rootfind::{RootFinder, RootFindingError};
+use crate::money::{Currency, DatedCashflow, Rounding};
+use crate::time::{
+    BusinessDayConvention, DayCounter, PaymentFrequency, Schedule, SchedulePeriod, StubRule,
+    WeekendsOnly,
+};
 use std::collections::BTreeMap;
-use time::{Duration, OffsetDateTime};
+use time::OffsetDateTime;
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // STRUCTS, ENUMS, AND TRAITS
@@ -64,12 +68,20 @@ pub struct CouponBond {
     /// Settlement convention.
     pub settlement_convention: BusinessDayConvention,
 
+    /// Day counter used to accrue the coupons
+    /// (e.g. `ActualActualICMA` for most government bonds).
+    pub day_counter: Box<dyn DayCounter>,
+
     /// Yield curve to use for pricing.
     pub yield_curve: YieldCurve,
 
     /// The face value of the bond.
     pub face_value: f64,
 
+    /// Rounding of the coupon amounts (e.g. `Rounding::MinorUnits`
+    /// to pay whole cents).
+    pub rounding: Rounding,
+
     /// The coupons of the bond.
     /// The coupons are represented as a map of dates to coupon amounts,
     /// ordered by date.
@@ -88,70 +100,237 @@ pub struct CouponBond2 {
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 impl CouponBond {
+    /// Coupon schedule of the bond.
+    ///
+    /// Coupon dates are rolled backward from the expiration date (with a
+    /// short front stub) and adjusted for weekends with the settlement
+    /// convention.
+    pub fn schedule(&self) -> Schedule {
+        Schedule::new(
+            self.evaluation_date,
+            self.expiration_date,
+            self.coupon_frequency,
+            &WeekendsOnly,
+            self.settlement_convention,
+            StubRule::ShortFront,
+        )
+    }
+
+    /// Accrual fraction of a coupon period up to `date`.
+    /// Regular periods use the period itself as the day counter's reference
+    /// period; stubs accrue their year fraction linearly in the day count.
+    fn accrual_fraction(&self, period: &SchedulePeriod, date: OffsetDateTime) -> f64 {
+        if period.is_stub {
+            let days = self.day_counter.day_count(period.start, period.end) as f64;
+
+            self.day_counter.year_fraction(period.start, period.end)
+                * self.day_counter.day_count(period.start, date) as f64
+                / days
+        } else {
+            self.day_counter
+                .year_fraction_in_period(period.start, date, period.start, period.end)
+        }
+    }
+
     /// Constructs the coupons of the bond.
+    ///
+    /// Each coupon is the face value times the coupon rate times the
+    /// accrual fraction of its period under the bond's day counter,
+    /// rounded with the bond's rounding rule.
     pub fn construct_coupons(&mut self) {
-        let mut coupons: BTreeMap<OffsetDateTime, f64> = BTreeMap::new();
+        let schedule = self.schedule();
 
-        // Create the coupon dates
-        let years = (self.expiration_date - self.evaluation_date).whole_days() / 365;
-        let n_coupons = years * self.coupon_frequency as i64;
+        let mut coupons: BTreeMap<OffsetDateTime, f64> = BTreeMap::new();
 
-        let mut coupon_dates: Vec<OffsetDateTime> = Vec::with_capacity(n_coupons as usize);
+        for period in &schedule.periods {
+            let coupon = self.rounding.round(
+                self.face_value * self.coupon_rate * self.accrual_fraction(period, period.end),
+                self.currency.as_ref(),
+            );
 
-        for i in 1..=n_coupons {
-            let coupon_date =
-                self.evaluation_date + Duration::days(365 * i) / self.coupon_frequency as i32;
+            *coupons.entry(period.payment).or_insert(0.0) += coupon;
+        }
 
-            coupon_dates.push(coupon_date);
+        // Add the principal to the final coupon
+        if let Some(last) = schedule.periods.last() {
+            *coupons.entry(last.payment).or_insert(0.0) += self.face_value;
         }
 
-        // Create the coupon amounts
-        let mut coupon_amounts: Vec<f64> = Vec::with_capacity(n_coupons as usize);
+        self.coupons = coupons;
+    }
 
-        for _ in 1..n_coupons {
-            let coupon_amount =
-                self.face_value * self.coupon_rate / self.coupon_frequency as isize as f64;
+    /// Accrued interest at the given settlement date, i.e. the part of the
+    /// current coupon earned since the start of its period
+    /// (rounded with the bond's rounding rule).
+    pub fn accrued_interest(&self, settlement_date: OffsetDateTime) -> f64 {
+        self.schedule()
+            .periods
+            .iter()
+            .find(|period| period.start <= settlement_date && settlement_date < period.end)
+            .map_or(0.0, |period| {
+                self.rounding.round(
+                    self.face_value
+                        * self.coupon_rate
+                        * self.accrual_fraction(period, settlement_date),
+                    self.currency.as_ref(),
+                )
+            })
+    }
 
-            coupon_amounts.push(coupon_amount);
-        }
+    /// Price discounted on `curve` instead of the bond's own curve, with
+    /// the present value of each coupon (the last including the face value).
+    pub fn price_on_curve(&self, curve: &YieldCurve) -> PricingResult {
+        PricingResult::timed(|| {
+            // Compute the discount factors for the coupons.
+            let discount_factors = curve.discount_factors(
+                &self
+                    .coupons
+                    .keys()
+                    .cloned()
+                    .collect::<Vec<OffsetDateTime>>(),
+            );
+
+            // Compute the present value of the coupons and face value, and sum them.
+            let cash_flows: Vec<DiscountedCashFlow> = self
+                .coupons
+                .iter()
+                .zip(discount_factors.iter())
+                .map(|((date, coupon), df)| DiscountedCashFlow::new(*date, *coupon, *df))
+                .collect();
+
+            PricingResult::new(cash_flows.iter().map(|flow| flow.present_value).sum())
+                .with_currency(self.currency)
+                .with_engine(PricingEngine::Analytic)
+                .with_cash_flows(cash_flows)
+        })
+    }
 
-        // Create the coupons
-        for (date, amount) in coupon_dates.iter().zip(coupon_amounts.iter()) {
-            coupons.insert(*date, *amount);
-        }
+    /// Coupons from the evaluation date on, with their times in years from
+    /// the evaluation date under the bond's day counter.
+    fn remaining_cash_flows(&self) -> Vec<(f64, f64)> {
+        self.coupons
+            .range(self.evaluation_date..)
+            .map(|(date, coupon)| {
+                (
+                    self.day_counter.year_fraction(self.evaluation_date, *date),
+                    *coupon,
+                )
+            })
+            .collect()
+    }
 
-        // Add the final coupon
-        coupons.insert(
-            self.expiration_date,
-            self.face_value * (1.0 + self.coupon_rate / self.coupon_frequency as isize as f64),
-        );
+    /// Yield to maturity: the yield, compounded at the coupon frequency,
+    /// that discounts the remaining coupons to `price` (a dirty price), with
+    /// times from the evaluation date under the bond's day counter.
+    ///
+    /// Solved by Newton-Raphson, safeguarded by bisection on yields in
+    /// $[-50\%, 100\%]$.
+    pub fn yield_to_maturity(&self, price: f64) -> Result<f64, RootFindingError> {
+        let frequency = self.coupon_frequency as i32 as f64;
+        let cash_flows = self.remaining_cash_flows();
+
+        RootFinder::default()
+            .newton_bracketed(
+                |y| {
+                    cash_flows
+                        .iter()
+                        .fold((-price, 0.0), |(value, derivative), (t, coupon)| {
+                            let discount = (1.0 + y / frequency).powf(-frequency * t);
+                            (
+                                value + coupon * discount,
+                                derivative - t * coupon * discount / (1.0 + y / frequency),
+                            )
+                        })
+                },
+                self.coupon_rate,
+                -0.5,
+                1.0,
+            )
+            .map(|root| root.root)
+    }
 
-        self.coupons = coupons;
+    /// Yield to maturity in the given compounding, as
+    /// [`CouponBond::yield_to_maturity`] (which compounds at the coupon
+    /// frequency).
+    pub fn yield_to_maturity_compounded(
+        &self,
+        price: f64,
+        compounding: Compounding,
+    ) -> Result<f64, RootFindingError> {
+        let cash_flows = self.remaining_cash_flows();
+
+        RootFinder::default()
+            .newton_bracketed(
+                |y| {
+                    cash_flows
+                        .iter()
+                        .fold((-price, 0.0), |(value, derivative), (t, coupon)| {
+                            (
+                                value + coupon * compounding.discount_factor(y, *t),
+                                derivative + coupon * compounding.discount_factor_derivative(y, *t),
+                            )
+                        })
+                },
+                self.coupon_rate,
+                -0.5,
+                1.0,
+            )
+            .map(|root| root.root)
+    }
+
+    /// Zero-volatility spread: the constant spread over the zero rates of
+    /// `curve`, both in the given compounding, at which the remaining
+    /// coupons discount to `price` (a dirty price).
+    ///
+    /// Times are year fractions from the curve's initial date under its day
+    /// count convention, so the spread is zero at the price on the curve.
+    /// The spread is solved for in $[-50\%, 100\%]$.
+    pub fn z_spread(
+        &self,
+        price: f64,
+        curve: &YieldCurve,
+        compounding: Compounding,
+    ) -> Result<f64, RootFindingError> {
+        let t0 = curve.initial_date();
+        let cash_flows: Vec<(f64, f64, f64)> = self
+            .coupons
+            .range(self.evaluation_date..)
+            .map(|(date, coupon)| {
+                (
+                    curve.day_count_convention.year_fraction(t0, *date),
+                    curve.zero_rate(*date, compounding),
+                    *coupon,
+                )
+            })
+            .collect();
+
+        RootFinder::default()
+            .newton_bracketed(
+                |s| {
+                    cash_flows.iter().fold(
+                        (-price, 0.0),
+                        |(value, derivative), (t, rate, coupon)| {
+                            (
+                                value + coupon * compounding.discount_factor(rate + s, *t),
+                                derivative
+                                    + coupon * compounding.discount_factor_derivative(rate + s, *t),
+                            )
+                        },
+                    )
+                },
+                0.0,
+                -0.5,
+                1.0,
+            )
+            .map(|root| root.root)
     }
 }
 
 impl Instrument for CouponBond {
-    /// Returns the price (net present value) of the instrument.
-    fn price(&self) -> f64 {
-        // Compute the discount factors for the coupons.
-        let discount_factors = self.yield_curve.discount_factors(
-            &self
-                .coupons
-                .keys()
-                .cloned()
-                .collect::<Vec<OffsetDateTime>>(),
-        );
-        // .iter()
-        // .enumerate()
-        // .map(|(i, df)| (1. + df / self.coupon_frequency as i32 as f64).powi((i + 1) as i32))
-        // .collect::<Vec<f64>>();
-
-        // Compute the present value of the coupons and face value, and sum them.
-        self.coupons
-            .values()
-            .zip(discount_factors.iter())
-            .map(|(coupon, df)| coupon * df)
-            .sum::<f64>()
+    /// Returns the price (net present value) of the instrument, with the
+    /// present value of each coupon (the last including the face value).
+    fn price(&self) -> PricingResult {
+        self.price_on_curve(&self.yield_curve)
     }
 
     /// Returns the error on the NPV in case the pricing engine can
@@ -160,6 +339,14 @@ impl Instrument for CouponBond {
         None
     }
 
+    /// The coupons, the last including the face value.
+    fn cashflows(&self) -> Vec<DatedCashflow> {
+        self.coupons
+            .iter()
+            .map(|(date, coupon)| DatedCashflow::new(*date, *coupon, self.currency))
+            .collect()
+    }
+
     /// Returns the date at which the NPV is calculated.
     fn valuation_date(&self) -> OffsetDateTime {
         self.evaluation_date
@@ -196,17 +383,23 @@ impl CouponBond2 {
 
 #[cfg(test)]
 mod tests_bond {
-    // use time::macros::datetime;
-
+    use crate::time::{ActualActualICMA, DayCountConvention, Thirty360US};
     use crate::{curves::Curve, money::USD};
+    use time::macros::datetime;
 
     use super::*;
+    use time::Duration;
 
     fn create_test_yield_curve(t0: OffsetDateTime) -> YieldCurve {
         // Create a treasury yield curve with 8 points (3m, 6m, 1y, 2y, 5y, 10y, 30y).
         // Values from Bloomberg: <https://www.bloomberg.com/markets/rates-bonds/government-bonds/us>
-        let rate_vec = vec![0.0544, 0.0556, 0.0546, 0.0514, 0.0481, 0.0481, 0.0494];
+        // The curve starts at t0 (with the 3m rate), so that times to the
+        // coupons are measured from the evaluation date.
+        let rate_vec = vec![
+            0.0544, 0.0544, 0.0556, 0.0546, 0.0514, 0.0481, 0.0481, 0.0494,
+        ];
         let date_vec = vec![
+            t0,
             t0 + Duration::days(90),
             t0 + Duration::days(180),
             t0 + Duration::days(365),
@@ -230,17 +423,225 @@ mod tests_bond {
             coupon_rate: 0.15,
             coupon_frequency: PaymentFrequency::SemiAnnually,
             settlement_convention: BusinessDayConvention::Actual,
+            day_counter: Box::new(ActualActualICMA::new(PaymentFrequency::SemiAnnually)),
             yield_curve: create_test_yield_curve(today),
             face_value: 1000.0,
+            rounding: Rounding::None,
+            coupons: BTreeMap::new(),
+        };
+
+        bond.construct_coupons();
+
+        // Four coupons of $75 (after a short first period, as 730 days is
+        // not quite two years), the last with the face value.
+        let coupons: Vec<f64> = bond.coupons.values().cloned().collect();
+        assert_eq!(coupons.len(), 4);
+        assert!(coupons[0] <= 75.0);
+        assert_approx_equal!(coupons[1], 75.0, 1e-10);
+        assert_approx_equal!(coupons[3], 1075.0, 1e-10);
+
+        // The price discounts each coupon on the curve, so it lies between
+        // the prices at the lowest and highest rates of the curve (the
+        // published reference prices are checked in `verification::bonds`).
+        let price_at = |rate: f64| {
+            bond.coupons
+                .iter()
+                .map(|(date, coupon)| {
+                    let t = DayCountConvention::Actual365.year_fraction(today, *date);
+                    coupon * (-rate * t).exp()
+                })
+                .sum::<f64>()
+        };
+        let result = bond.price();
+        assert!(price_at(0.0556) < result.value() && result.value() < price_at(0.0514));
+
+        // The breakdown has a discounted cash flow per coupon.
+        assert_eq!(result.cash_flows.len(), 4);
+        assert_approx_equal!(
+            result
+                .cash_flows
+                .iter()
+                .map(|flow| flow.present_value)
+                .sum::<f64>(),
+            result.value(),
+            1e-10
+        );
+        assert_approx_equal!(result.cash_flows[3].amount, 1075.0, 1e-10);
+        assert!(result.cash_flows[3].discount_factor < result.cash_flows[0].discount_factor);
+        assert!(result.currency.is_some());
+        assert_eq!(result.engine, Some(PricingEngine::Analytic));
+    }
+
+    #[test]
+    fn test_coupon_accrual() {
+        let start = datetime!(2023-11-15 0:00 UTC);
+
+        let mut bond = CouponBond {
+            evaluation_date: start,
+            expiration_date: datetime!(2025-11-15 0:00 UTC),
+            currency: Some(USD),
+            coupon_rate: 0.05,
+            coupon_frequency: PaymentFrequency::SemiAnnually,
+            settlement_convention: BusinessDayConvention::Actual,
+            day_counter: Box::new(ActualActualICMA::new(PaymentFrequency::SemiAnnually)),
+            yield_curve: create_test_yield_curve(start),
+            face_value: 100.0,
+            rounding: Rounding::None,
             coupons: BTreeMap::new(),
         };
 
         bond.construct_coupons();
 
-        // Should be: $1,184.61
-        // Getting:   $1,198.47
-        // Think its close enough for now, down to differences in my computation
-        // and the calculator I used. Possibly continuous compounding vs discrete.
-        println!("Price: {}", bond.price());
+        // Four regular coupons of 2.5 each under Actual/Actual (ICMA).
+        let coupons: Vec<f64> = bond.coupons.values().cloned().collect();
+        assert_eq!(coupons.len(), 4);
+        for coupon in &coupons[..3] {
+            assert_approx_equal!(*coupon, 2.5, 1e-12);
+        }
+        assert_approx_equal!(coupons[3], 102.5, 1e-12);
+
+        // 92 of 182 days into the first period.
+        assert_approx_equal!(
+            bond.accrued_interest(datetime!(2024-02-15 0:00 UTC)),
+            2.5 * 92.0 / 182.0,
+            1e-12
+        );
+
+        // 30/360 accrues 90 days of 360 over the same dates.
+        bond.day_counter = Box::new(Thirty360US);
+        assert_approx_equal!(
+            bond.accrued_interest(datetime!(2024-02-15 0:00 UTC)),
+            5.0 * 90.0 / 360.0,
+            1e-12
+        );
+    }
+
+    #[test]
+    fn test_yield_to_maturity() {
+        let start = datetime!(2023-11-15 0:00 UTC);
+
+        let mut bond = CouponBond {
+            evaluation_date: start,
+            expiration_date: datetime!(2025-11-15 0:00 UTC),
+            currency: Some(USD),
+            coupon_rate: 0.05,
+            coupon_frequency: PaymentFrequency::SemiAnnually,
+            settlement_convention: BusinessDayConvention::Actual,
+            day_counter: Box::new(Thirty360US),
+            yield_curve: create_test_yield_curve(start),
+            face_value: 100.0,
+            rounding: Rounding::None,
+            coupons: BTreeMap::new(),
+        };
+        bond.construct_coupons();
+
+        // A bond priced at par yields its coupon rate.
+        assert_approx_equal!(bond.yield_to_maturity(100.0).unwrap(), 0.05, 1e-10);
+
+        // Four semi-annual periods at a 6% yield.
+        let price = (1..=4).map(|i| 2.5 / 1.03_f64.powi(i)).sum::<f64>() + 100.0 / 1.03_f64.powi(4);
+        assert_approx_equal!(bond.yield_to_maturity(price).unwrap(), 0.06, 1e-10);
+
+        // The same yield in other compoundings.
+        let ytm = |compounding| {
+            bond.yield_to_maturity_compounded(price, compounding)
+                .unwrap()
+        };
+        assert_approx_equal!(ytm(Compounding::SemiAnnual), 0.06, 1e-10);
+        assert_approx_equal!(ytm(Compounding::Annual), 1.03_f64.powi(2) - 1.0, 1e-10);
+        assert_approx_equal!(ytm(Compounding::Continuous), 2.0 * 1.03_f64.ln(), 1e-10);
+    }
+
+    #[test]
+    fn test_z_spread() {
+        let start = datetime!(2023-11-15 0:00 UTC);
+
+        let mut bond = CouponBond {
+            evaluation_date: start,
+            expiration_date: datetime!(2028-11-15 0:00 UTC),
+            currency: Some(USD),
+            coupon_rate: 0.05,
+            coupon_frequency: PaymentFrequency::SemiAnnually,
+            settlement_convention: BusinessDayConvention::Actual,
+            day_counter: Box::new(Thirty360US),
+            yield_curve: create_test_yield_curve(start),
+            face_value: 100.0,
+            rounding: Rounding::None,
+            coupons: BTreeMap::new(),
+        };
+        bond.construct_coupons();
+
+        let curve = bond.yield_curve.clone();
+        let price = bond.price().value();
+
+        // No spread at the curve price, in any compounding.
+        for compounding in [Compounding::Continuous, Compounding::Annual] {
+            assert_approx_equal!(
+                bond.z_spread(price, &curve, compounding).unwrap(),
+                0.0,
+                1e-10
+            );
+        }
+
+        // A price on the curve shifted by 50bp (continuous) has a 50bp
+        // continuous z-spread.
+        let mut shifted = curve.clone();
+        shifted.rates.values_mut().for_each(|rate| *rate += 0.005);
+        let price = bond.price_on_curve(&shifted).value();
+
+        let spread = bond
+            .z_spread(price, &curve, Compounding::Continuous)
+            .unwrap();
+        assert_approx_equal!(spread, 0.005, 1e-10);
+
+        // Quoted against annually compounded zero rates the spread is wider,
+        // but reprices the bond all the same.
+        let spread = bond.z_spread(price, &curve, Compounding::Annual).unwrap();
+        assert!(spread > 0.005);
+
+        let repriced: f64 = bond
+            .coupons
+            .iter()
+            .map(|(date, coupon)| {
+                let t = curve.day_count_convention.year_fraction(start, *date);
+                let rate = curve.zero_rate(*date, Compounding::Annual);
+
+                coupon * Compounding::Annual.discount_factor(rate + spread, t)
+            })
+            .sum();
+        assert_approx_equal!(repriced, price, 1e-8);
+    }
+
+    #[test]
+    fn test_coupon_rounding() {
+        let start = datetime!(2024-01-15 0:00 UTC);
+
+        let mut bond = CouponBond {
+            evaluation_date: start,
+            expiration_date: datetime!(2025-01-15 0:00 UTC),
+            currency: Some(USD),
+            coupon_rate: 0.0437,
+            coupon_frequency: PaymentFrequency::Quarterly,
+            settlement_convention: BusinessDayConvention::Actual,
+            day_counter: Box::new(Thirty360US),
+            yield_curve: create_test_yield_curve(start),
+            face_value: 1234.56,
+            rounding: Rounding::MinorUnits,
+            coupons: BTreeMap::new(),
+        };
+
+        bond.construct_coupons();
+
+        // 1234.56 * 0.0437 / 4 = 13.487568 is paid as 13.49.
+        let coupons: Vec<f64> = bond.coupons.values().cloned().collect();
+        assert_approx_equal!(coupons[0], 13.49, 1e-12);
+        assert_approx_equal!(coupons[3], 1234.56 + 13.49, 1e-12);
+
+        // 30 of 90 days accrued: 4.495856 is 4.50.
+        assert_approx_equal!(
+            bond.accrued_interest(datetime!(2024-02-15 0:00 UTC)),
+            4.5,
+            1e-12
+        );
     }
 }
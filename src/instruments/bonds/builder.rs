@@ -0,0 +1,396 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Builders for bonds.
+//!
+//! ```rust
+//! use RustQuant::curves::YieldCurve;
+//! use RustQuant::instruments::{bonds::CouponBond, Instrument};
+//! use RustQuant::money::USD;
+//! use RustQuant::time::{PaymentFrequency, Tenor};
+//! use time::macros::date;
+//!
+//! let curve = YieldCurve::from_tenors_and_rates(
+//!     date!(2024 - 01 - 15),
+//!     &[Tenor::days(0), Tenor::years(10)],
+//!     &[0.04, 0.045],
+//! );
+//!
+//! let bond = CouponBond::builder()
+//!     .evaluation_date(date!(2024 - 01 - 15))
+//!     .expiration_date(date!(2029 - 01 - 15))
+//!     .coupon_rate(0.05)
+//!     .coupon_frequency(PaymentFrequency::Annually)
+//!     .currency(USD)
+//!     .yield_curve(curve)
+//!     .build()?;
+//!
+//! assert_eq!(bond.coupons.len(), 5);
+//! assert!(bond.price().value() > 100.0);
+//! # Ok::<(), RustQuant::error::RustQuantError>(())
+//! ```
+
+use crate::curves::YieldCurve;
+use crate::error::{ensure, RustQuantError};
+use crate::instruments::bonds::{CouponBond, ZeroCouponBond};
+use crate::money::{Currency, Rounding};
+use crate::time::{
+    ActualActualICMA, BusinessDayConvention, DayCounter, IntoEvaluationDate, PaymentFrequency,
+};
+use std::collections::BTreeMap;
+use time::OffsetDateTime;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Builder for a [`CouponBond`].
+///
+/// The expiration date, coupon rate and yield curve are required. The
+/// other terms default to:
+/// - evaluation date: today (midnight UTC),
+/// - currency: none,
+/// - coupon frequency: semi-annual,
+/// - settlement convention: following business day,
+/// - day counter: Actual/Actual (ICMA) at the coupon frequency,
+/// - face value: 100,
+/// - rounding: none.
+pub struct CouponBondBuilder {
+    evaluation_date: Option<OffsetDateTime>,
+    expiration_date: Option<OffsetDateTime>,
+    currency: Option<Currency>,
+    coupon_rate: Option<f64>,
+    coupon_frequency: PaymentFrequency,
+    settlement_convention: BusinessDayConvention,
+    day_counter: Option<Box<dyn DayCounter>>,
+    yield_curve: Option<YieldCurve>,
+    face_value: f64,
+    rounding: Rounding,
+}
+
+/// Builder for a [`ZeroCouponBond`].
+///
+/// The expiration date is required; the evaluation date defaults to today
+/// (midnight UTC) and the currency to none.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ZeroCouponBondBuilder {
+    evaluation_date: Option<OffsetDateTime>,
+    expiration_date: Option<OffsetDateTime>,
+    currency: Option<Currency>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl CouponBond {
+    /// Builder for a coupon bond (see [`CouponBondBuilder`]).
+    pub fn builder() -> CouponBondBuilder {
+        CouponBondBuilder::default()
+    }
+}
+
+impl ZeroCouponBond {
+    /// Builder for a zero-coupon bond (see [`ZeroCouponBondBuilder`]).
+    pub fn builder() -> ZeroCouponBondBuilder {
+        ZeroCouponBondBuilder::default()
+    }
+}
+
+impl Default for CouponBondBuilder {
+    fn default() -> Self {
+        Self {
+            evaluation_date: None,
+            expiration_date: None,
+            currency: None,
+            coupon_rate: None,
+            coupon_frequency: PaymentFrequency::SemiAnnually,
+            settlement_convention: BusinessDayConvention::Following,
+            day_counter: None,
+            yield_curve: None,
+            face_value: 100.0,
+            rounding: Rounding::None,
+        }
+    }
+}
+
+impl CouponBondBuilder {
+    /// Date the bond is evaluated (i.e. priced).
+    pub fn evaluation_date(mut self, date: impl IntoEvaluationDate) -> Self {
+        self.evaluation_date = Some(date.into_evaluation_date());
+        self
+    }
+
+    /// Date the bond expires (i.e. matures, is redeemed).
+    pub fn expiration_date(mut self, date: impl IntoEvaluationDate) -> Self {
+        self.expiration_date = Some(date.into_evaluation_date());
+        self
+    }
+
+    /// Currency of the bond.
+    pub fn currency(mut self, currency: Currency) -> Self {
+        self.currency = Some(currency);
+        self
+    }
+
+    /// Annual coupon rate (e.g. `0.05` for 5%).
+    pub fn coupon_rate(mut self, coupon_rate: f64) -> Self {
+        self.coupon_rate = Some(coupon_rate);
+        self
+    }
+
+    /// Number of coupons a year.
+    pub fn coupon_frequency(mut self, coupon_frequency: PaymentFrequency) -> Self {
+        self.coupon_frequency = coupon_frequency;
+        self
+    }
+
+    /// Adjustment of coupon dates falling on weekends.
+    pub fn settlement_convention(mut self, convention: BusinessDayConvention) -> Self {
+        self.settlement_convention = convention;
+        self
+    }
+
+    /// Day counter used to accrue the coupons.
+    pub fn day_counter(mut self, day_counter: impl DayCounter + 'static) -> Self {
+        self.day_counter = Some(Box::new(day_counter));
+        self
+    }
+
+    /// Yield curve used for pricing.
+    pub fn yield_curve(mut self, yield_curve: YieldCurve) -> Self {
+        self.yield_curve = Some(yield_curve);
+        self
+    }
+
+    /// Face value (principal) of the bond.
+    pub fn face_value(mut self, face_value: f64) -> Self {
+        self.face_value = face_value;
+        self
+    }
+
+    /// Rounding of the coupon amounts.
+    pub fn rounding(mut self, rounding: Rounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// The bond, with its coupons constructed, or an error if a required
+    /// term is missing, the bond expires on or before the evaluation date,
+    /// the coupon rate is negative, the face value is not positive, or the
+    /// yield curve has no points.
+    pub fn build(self) -> Result<CouponBond, RustQuantError> {
+        let evaluation_date = self.evaluation_date.unwrap_or_else(today);
+        let expiration_date = required(self.expiration_date, "expiration date")?;
+        let coupon_rate = required(self.coupon_rate, "coupon rate")?;
+        let yield_curve = required(self.yield_curve, "yield curve")?;
+
+        ensure(
+            expiration_date > evaluation_date,
+            "The bond must expire after the evaluation date.",
+        )?;
+        ensure(
+            coupon_rate.is_finite() && coupon_rate >= 0.0,
+            "The coupon rate must be finite and non-negative.",
+        )?;
+        ensure(
+            self.face_value.is_finite() && self.face_value > 0.0,
+            "The face value must be positive.",
+        )?;
+        ensure(
+            !yield_curve.rates.is_empty(),
+            "The yield curve has no points.",
+        )?;
+
+        let day_counter = self
+            .day_counter
+            .unwrap_or_else(|| Box::new(ActualActualICMA::new(self.coupon_frequency)));
+
+        let mut bond = CouponBond {
+            evaluation_date,
+            expiration_date,
+            currency: self.currency,
+            coupon_rate,
+            coupon_frequency: self.coupon_frequency,
+            settlement_convention: self.settlement_convention,
+            day_counter,
+            yield_curve,
+            face_value: self.face_value,
+            rounding: self.rounding,
+            coupons: BTreeMap::new(),
+        };
+        bond.construct_coupons();
+
+        Ok(bond)
+    }
+}
+
+impl ZeroCouponBondBuilder {
+    /// Date the bond is evaluated (i.e. priced).
+    pub fn evaluation_date(mut self, date: impl IntoEvaluationDate) -> Self {
+        self.evaluation_date = Some(date.into_evaluation_date());
+        self
+    }
+
+    /// Date the bond expires (i.e. matures, is redeemed).
+    pub fn expiration_date(mut self, date: impl IntoEvaluationDate) -> Self {
+        self.expiration_date = Some(date.into_evaluation_date());
+        self
+    }
+
+    /// Currency of the bond.
+    pub fn currency(mut self, currency: Currency) -> Self {
+        self.currency = Some(currency);
+        self
+    }
+
+    /// The bond, or an error if the expiration date is missing or not after
+    /// the evaluation date.
+    pub fn build(self) -> Result<ZeroCouponBond, RustQuantError> {
+        let evaluation_date = self.evaluation_date.unwrap_or_else(today);
+        let expiration_date = required(self.expiration_date, "expiration date")?;
+
+        ensure(
+            expiration_date > evaluation_date,
+            "The bond must expire after the evaluation date.",
+        )?;
+
+        Ok(ZeroCouponBond {
+            evaluation_date,
+            expiration_date,
+            currency: self.currency,
+        })
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Midnight UTC today.
+fn today() -> OffsetDateTime {
+    OffsetDateTime::now_utc().date().midnight().assume_utc()
+}
+
+/// The value of a term that has no default, or an error naming it.
+fn required<T>(value: Option<T>, term: &str) -> Result<T, RustQuantError> {
+    value.ok_or_else(|| RustQuantError::invalid_parameter(format!("The {term} is required.")))
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_builder {
+    use super::*;
+    use crate::instruments::Instrument;
+    use crate::money::USD;
+    use crate::time::{DayCountConvention, Tenor};
+    use time::macros::date;
+
+    fn curve() -> YieldCurve {
+        YieldCurve::from_tenors_and_rates(
+            date!(2024 - 01 - 15),
+            &[Tenor::days(0), Tenor::years(10)],
+            &[0.05, 0.05],
+        )
+    }
+
+    #[test]
+    fn test_coupon_bond_builder() {
+        let bond = CouponBond::builder()
+            .evaluation_date(date!(2024 - 01 - 15))
+            .expiration_date(date!(2026 - 01 - 15))
+            .coupon_rate(0.06)
+            .currency(USD)
+            .yield_curve(curve())
+            .face_value(1000.0)
+            .build()
+            .unwrap();
+
+        // Semi-annual coupons of 30 by default, with the principal at
+        // maturity.
+        assert_eq!(bond.coupons.len(), 4);
+        assert_eq!(bond.coupons.values().next(), Some(&30.0));
+        assert_eq!(bond.coupons.values().last(), Some(&1030.0));
+        assert!(bond.currency.is_some());
+        assert!(bond.price().value() > 1000.0);
+    }
+
+    #[test]
+    fn test_coupon_bond_builder_day_counter() {
+        let bond = CouponBond::builder()
+            .evaluation_date(date!(2024 - 01 - 15))
+            .expiration_date(date!(2025 - 01 - 15))
+            .coupon_rate(0.05)
+            .coupon_frequency(PaymentFrequency::Annually)
+            .day_counter(DayCountConvention::Actual360)
+            .settlement_convention(BusinessDayConvention::Actual)
+            .yield_curve(curve())
+            .build()
+            .unwrap();
+
+        // 366 days of accrual at Actual/360.
+        assert_eq!(bond.coupons.len(), 1);
+        assert!(
+            (bond.coupons.values().next().unwrap() - (100.0 + 5.0 * 366.0 / 360.0)).abs() < 1e-12
+        );
+    }
+
+    #[test]
+    fn test_coupon_bond_builder_validation() {
+        let builder = || {
+            CouponBond::builder()
+                .evaluation_date(date!(2024 - 01 - 15))
+                .expiration_date(date!(2026 - 01 - 15))
+                .coupon_rate(0.05)
+                .yield_curve(curve())
+        };
+
+        assert!(builder().build().is_ok());
+        assert!(builder().coupon_rate(-0.01).build().is_err());
+        assert!(builder().face_value(0.0).build().is_err());
+        assert!(builder()
+            .expiration_date(date!(2023 - 01 - 15))
+            .build()
+            .is_err());
+        assert!(builder()
+            .yield_curve(YieldCurve::new(BTreeMap::new()))
+            .build()
+            .is_err());
+
+        let missing = CouponBond::builder()
+            .expiration_date(date!(2026 - 01 - 15))
+            .coupon_rate(0.05)
+            .build();
+        assert_eq!(
+            missing.err().map(|error| error.to_string()),
+            Some("Invalid parameter: \"The yield curve is required.\"".to_string())
+        );
+    }
+
+    #[test]
+    fn test_zero_coupon_bond_builder() {
+        let bond = ZeroCouponBond::builder()
+            .expiration_date(OffsetDateTime::now_utc() + time::Duration::days(365))
+            .build()
+            .unwrap();
+
+        assert_eq!(bond.evaluation_date, today());
+        assert!(bond.currency.is_none());
+
+        assert!(ZeroCouponBond::builder().build().is_err());
+        assert!(ZeroCouponBond::builder()
+            .evaluation_date(date!(2024 - 01 - 15))
+            .expiration_date(date!(2024 - 01 - 15))
+            .build()
+            .is_err());
+    }
+}
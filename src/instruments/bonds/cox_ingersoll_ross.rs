@@ -24,8 +24,9 @@
 //! the standard deviation increases.
 
 use crate::{
-    instruments::Instrument,
-    time::{DayCountConvention, DayCounter},
+    instruments::{Instrument, PricingEngine, PricingResult},
+    money::DatedCashflow,
+    time::{DayCount, DayCountConvention},
 };
 use time::OffsetDateTime;
 
@@ -43,35 +44,42 @@ pub struct CoxIngersollRoss {
 }
 
 impl Instrument for CoxIngersollRoss {
-    fn price(&self) -> f64 {
-        let a = self.a;
-        let b = self.b;
-        let sigma = self.sigma;
-        let r = self.r;
-
-        // Compute time to maturity.
-        let tau = DayCounter::day_count_factor(
-            self.evaluation_date.unwrap_or(OffsetDateTime::now_utc()),
-            self.expiration_date,
-            &DayCountConvention::Actual365,
-        );
-
-        let gamma = (a * a + 2.0 * sigma.powi(2)).sqrt();
-
-        let b_t = 2.0 * ((gamma * tau).exp() - 1.0)
-            / ((gamma + a) * ((gamma * tau).exp() - 1.0) + 2.0 * gamma);
-        let a_t = (2.0 * gamma * ((a + gamma) * tau / 2.0).exp()
-            / ((gamma + a) * ((gamma * tau).exp() - 1.0) + 2.0 * gamma))
-            .powf(2.0 * a * b / sigma.powi(2));
-
-        // Price:
-        a_t * (-b_t * r).exp()
+    fn price(&self) -> PricingResult {
+        PricingResult::timed(|| {
+            let a = self.a;
+            let b = self.b;
+            let sigma = self.sigma;
+            let r = self.r;
+
+            // Compute time to maturity.
+            let tau = DayCount::day_count_factor(
+                self.evaluation_date.unwrap_or(OffsetDateTime::now_utc()),
+                self.expiration_date,
+                &DayCountConvention::Actual365,
+            );
+
+            let gamma = (a * a + 2.0 * sigma.powi(2)).sqrt();
+
+            let b_t = 2.0 * ((gamma * tau).exp() - 1.0)
+                / ((gamma + a) * ((gamma * tau).exp() - 1.0) + 2.0 * gamma);
+            let a_t = (2.0 * gamma * ((a + gamma) * tau / 2.0).exp()
+                / ((gamma + a) * ((gamma * tau).exp() - 1.0) + 2.0 * gamma))
+                .powf(2.0 * a * b / sigma.powi(2));
+
+            // Price:
+            PricingResult::new(a_t * (-b_t * r).exp()).with_engine(PricingEngine::Analytic)
+        })
     }
 
     fn error(&self) -> Option<f64> {
         None
     }
 
+    /// Unit face value at expiry.
+    fn cashflows(&self) -> Vec<DatedCashflow> {
+        vec![DatedCashflow::new(self.expiration_date, 1.0, None)]
+    }
+
     fn valuation_date(&self) -> OffsetDateTime {
         self.evaluation_date.unwrap_or(OffsetDateTime::now_utc())
     }
@@ -83,7 +91,6 @@ impl Instrument for CoxIngersollRoss {
 
 #[cfg(test)]
 mod tests {
-    use crate::assert_approx_equal;
 
     use super::*;
 
@@ -100,7 +107,7 @@ mod tests {
             expiration_date: expiry,
         };
 
-        let cir_price = cir.price();
+        let cir_price = cir.price().value();
 
         assert_approx_equal!(cir_price, 0.9613, 1e-4);
     }
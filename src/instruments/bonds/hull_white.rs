@@ -24,9 +24,11 @@
 //! - `t`: time to check price at
 //! - `maturity`: time at bond maturity
 
-use crate::instruments::Instrument;
+use crate::error::{ensure, expect_valid, RustQuantError};
+use crate::instruments::{Instrument, PricingEngine, PricingResult};
 use crate::math::integrate;
-use crate::time::{DayCountConvention, DayCounter};
+use crate::money::DatedCashflow;
+use crate::time::{DayCount, DayCountConvention};
 use time::OffsetDateTime;
 
 /// Struct containing the Hull-White model parameters.
@@ -43,21 +45,33 @@ pub struct HullWhite {
 }
 
 impl HullWhite {
+    /// Zero-coupon bond price, or an error unless the mean reversion speed
+    /// `a` is positive and the expiry is not before the evaluation date.
+    pub fn try_price(&self) -> Result<PricingResult, RustQuantError> {
+        PricingResult::try_timed(|| {
+            ensure(self.a > 0.0, "The mean reversion speed must be positive.")?;
+            ensure(
+                self.expiration_date >= self.evaluation_date.unwrap_or(OffsetDateTime::now_utc()),
+                "The expiry must not be before the evaluation date.",
+            )?;
+
+            Ok(PricingResult::new(self.A() * (-self.B() * self.r_t).exp())
+                .with_engine(PricingEngine::Analytic))
+        })
+    }
+
     // TODO make dependenont t,T
     fn B(&self) -> f64 {
-        assert!(self.a > 0.0);
         (1.0 / self.a) * (1.0 - (-self.a).exp())
     }
 
     // TODO make dependenont t,T
     fn A(&self) -> f64 {
-        assert!(self.a > 0.0);
-
         let today = OffsetDateTime::now_utc();
         let t = (self.evaluation_date.unwrap_or(today).year() - today.year()) as f64;
         let T = (self.expiration_date.year() - today.year()) as f64;
 
-        let first = -1.0 * integrate(|u| (self.theta_t)(u) * self.B(), t, T);
+        let first = -integrate(|u| (self.theta_t)(u) * self.B(), t, T);
 
         let second = ((self.sigma).powi(2) / (2.0 * (self.a).powi(2))) * (self.B() - self.tau());
 
@@ -67,7 +81,7 @@ impl HullWhite {
     }
 
     fn tau(&self) -> f64 {
-        DayCounter::day_count_factor(
+        DayCount::day_count_factor(
             self.evaluation_date.unwrap_or(OffsetDateTime::now_utc()),
             self.expiration_date,
             &DayCountConvention::Actual365,
@@ -76,17 +90,22 @@ impl HullWhite {
 }
 
 impl Instrument for HullWhite {
-    fn price(&self) -> f64 {
-        assert!(self.a > 0.0);
-        assert!(self.expiration_date >= self.evaluation_date.unwrap_or(OffsetDateTime::now_utc()));
-
-        self.A() * (-1.0 * self.B() * self.r_t).exp()
+    /// # Panics
+    ///
+    /// Panics on invalid parameters (see [`HullWhite::try_price`]).
+    fn price(&self) -> PricingResult {
+        expect_valid(self.try_price())
     }
 
     fn error(&self) -> Option<f64> {
         None
     }
 
+    /// Unit face value at expiry.
+    fn cashflows(&self) -> Vec<DatedCashflow> {
+        vec![DatedCashflow::new(self.expiration_date, 1.0, None)]
+    }
+
     fn valuation_date(&self) -> time::OffsetDateTime {
         self.evaluation_date.unwrap_or(OffsetDateTime::now_utc())
     }
@@ -114,4 +133,27 @@ mod tests {
         // TODO check price against actual
         // But this implementation is analytic, so should be right
     }
+
+    #[test]
+    fn test_hw_try_price() {
+        let hw_bond = HullWhite {
+            a: 0.0,
+            theta_t: |_x| 0.5,
+            sigma: 0.3,
+            r_t: 0.05,
+            evaluation_date: None,
+            expiration_date: OffsetDateTime::now_utc() + time::Duration::days(365),
+        };
+        assert!(hw_bond.try_price().is_err());
+
+        let expired = HullWhite {
+            a: 2.0,
+            expiration_date: OffsetDateTime::now_utc() - time::Duration::days(1),
+            ..hw_bond
+        };
+        assert!(expired.try_price().is_err());
+
+        let valid = HullWhite { a: 2.0, ..hw_bond };
+        assert!(valid.try_price().unwrap().compute_time().is_some());
+    }
 }
@@ -0,0 +1,590 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Recombining binomial short-rate lattices fitted to a yield curve.
+//!
+//! At step $i$ of $n$ (time $t_i = i \Delta t$) the lattice has $i + 1$
+//! nodes, $j = 0, \dots, i$ up-moves, each moving up or down with
+//! probability $1/2$. The short rate over $[t_i, t_{i+1}]$ is
+//!
+//! - Ho-Lee: $r_{i,j} = a_i + \sigma_i \sqrt{\Delta t} (2j - i)$,
+//! - Black-Derman-Toy: $r_{i,j} = a_i e^{\sigma_i \sqrt{\Delta t} (2j - i)}$,
+//!
+//! where each $a_i$ is solved by forward induction on the Arrow-Debreu
+//! (state) prices so the lattice reprices the curve's discount factor to
+//! $t_{i+1}$ exactly.
+
+use crate::curves::{Curve, CurveError, YieldCurve};
+use crate::error::{expect_valid, RustQuantError};
+use crate::time::{DayCounter, IntoEvaluationDate};
+use thiserror::Error;
+use time::OffsetDateTime;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Short-rate model of a lattice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LatticeModel {
+    /// Ho-Lee: normally distributed rates (volatilities are absolute,
+    /// e.g. 0.01 for 100bp a year).
+    HoLee,
+
+    /// Black-Derman-Toy: lognormally distributed rates (volatilities are
+    /// relative, e.g. 0.2 for 20%).
+    BlackDermanToy,
+}
+
+/// Binomial short-rate lattice fitted to a yield curve, used as a
+/// backward induction engine for rate-contingent instruments such as
+/// callable bonds and Bermudan swaptions.
+///
+/// ```
+/// use RustQuant::curves::YieldCurve;
+/// use RustQuant::instruments::bonds::*;
+/// use RustQuant::time::Tenor;
+/// use time::macros::date;
+///
+/// let curve = YieldCurve::from_tenors_and_rates(
+///     date!(2024 - 01 - 02),
+///     &[Tenor::days(0), Tenor::years(10)],
+///     &[0.03, 0.045],
+/// );
+///
+/// // Monthly steps out to 5 years.
+/// let lattice =
+///     ShortRateLattice::new(LatticeModel::HoLee, &curve, date!(2029 - 01 - 02), 60, 0.01);
+///
+/// // 5% annual coupon bond, callable at par on each coupon date from year 2.
+/// let price = |callable: bool| {
+///     lattice.rollback(60, &[105.0; 61], |step, values| {
+///         if step > 0 && step % 12 == 0 {
+///             for value in values.iter_mut() {
+///                 if callable && step >= 24 {
+///                     *value = value.min(100.0);
+///                 }
+///                 *value += 5.0;
+///             }
+///         }
+///     })
+/// };
+///
+/// assert!(price(true) < price(false));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShortRateLattice {
+    /// Short-rate model.
+    pub model: LatticeModel,
+
+    /// Valuation date (the curve's initial date).
+    pub valuation_date: OffsetDateTime,
+
+    /// Date of the last step.
+    pub maturity_date: OffsetDateTime,
+
+    /// Length of a step, in years of the curve's day count convention.
+    pub dt: f64,
+
+    /// Short rates `rates[i][j]` over step `i` at node `j` (`n` steps).
+    pub rates: Vec<Vec<f64>>,
+
+    /// Arrow-Debreu prices `state_prices[i][j]` of a unit paid at step `i`
+    /// in node `j` (`n + 1` steps).
+    pub state_prices: Vec<Vec<f64>>,
+}
+
+/// Short-rate lattice errors.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum LatticeError {
+    /// The lattice has no steps.
+    #[error("A lattice needs at least one step.")]
+    NoSteps,
+
+    /// A volatility is negative or not finite.
+    #[error("Lattice volatilities must be finite and non-negative, got {0}.")]
+    InvalidVolatility(f64),
+
+    /// The maturity is not after the curve's initial date.
+    #[error("The lattice maturity must be after the curve's initial date.")]
+    MaturityBeforeValuation,
+
+    /// The drift of a step could not be fitted to the curve.
+    #[error("Could not fit step {0} of the lattice to the curve.")]
+    CalibrationFailed(usize),
+
+    /// A step is after the last step allowed (e.g. the lattice's last
+    /// step, or a bond's maturity).
+    #[error("Step {0} is after step {1}.")]
+    StepOutOfRange(usize, usize),
+
+    /// The curve has no discount factor at a step's date.
+    #[error(transparent)]
+    Curve(#[from] CurveError),
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl ShortRateLattice {
+    /// Lattice of `n_steps` equal steps from the curve's initial date to
+    /// `maturity_date`, with a flat volatility `sigma`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the inputs are rejected by [`ShortRateLattice::try_new`].
+    pub fn new(
+        model: LatticeModel,
+        curve: &YieldCurve,
+        maturity_date: impl IntoEvaluationDate,
+        n_steps: usize,
+        sigma: f64,
+    ) -> Self {
+        expect_valid(
+            Self::try_new(model, curve, maturity_date, n_steps, sigma)
+                .map_err(RustQuantError::from),
+        )
+    }
+
+    /// Lattice with a flat volatility, as [`ShortRateLattice::new`], or an
+    /// error if there are no steps, `sigma` is negative, the maturity is
+    /// not after the curve's initial date, or the curve does not reach it.
+    pub fn try_new(
+        model: LatticeModel,
+        curve: &YieldCurve,
+        maturity_date: impl IntoEvaluationDate,
+        n_steps: usize,
+        sigma: f64,
+    ) -> Result<Self, LatticeError> {
+        Self::with_volatilities(model, curve, maturity_date, &vec![sigma; n_steps])
+    }
+
+    /// Lattice with one step per volatility in `volatilities` (a
+    /// volatility term structure sampled on the steps), or an error as for
+    /// [`ShortRateLattice::try_new`].
+    pub fn with_volatilities(
+        model: LatticeModel,
+        curve: &YieldCurve,
+        maturity_date: impl IntoEvaluationDate,
+        volatilities: &[f64],
+    ) -> Result<Self, LatticeError> {
+        let n = volatilities.len();
+        let valuation_date = curve.initial_date();
+        let maturity_date = maturity_date.into_evaluation_date();
+
+        if n == 0 {
+            return Err(LatticeError::NoSteps);
+        }
+        if let Some(sigma) = volatilities.iter().find(|s| !(s.is_finite() && **s >= 0.0)) {
+            return Err(LatticeError::InvalidVolatility(*sigma));
+        }
+        if maturity_date <= valuation_date {
+            return Err(LatticeError::MaturityBeforeValuation);
+        }
+
+        let dt = curve
+            .day_count_convention()
+            .year_fraction(valuation_date, maturity_date)
+            / n as f64;
+
+        let step_date =
+            |i: usize| valuation_date + (maturity_date - valuation_date) * i as f64 / n as f64;
+
+        let mut rates = Vec::with_capacity(n);
+        let mut state_prices = Vec::with_capacity(n + 1);
+        state_prices.push(vec![1.0]);
+
+        for (i, sigma) in volatilities.iter().enumerate() {
+            let target = curve.try_discount_factor(step_date(i + 1))?;
+            let prices = &state_prices[i];
+
+            // Node offsets from the step's central rate.
+            let spread = |j: usize| sigma * dt.sqrt() * (2.0 * j as f64 - i as f64);
+
+            let step_rates = match model {
+                LatticeModel::HoLee => {
+                    let sum = prices
+                        .iter()
+                        .enumerate()
+                        .map(|(j, q)| q * f64::exp(-spread(j) * dt))
+                        .sum::<f64>();
+                    let a = (sum / target).ln() / dt;
+
+                    (0..=i).map(|j| a + spread(j)).collect::<Vec<_>>()
+                }
+                LatticeModel::BlackDermanToy => {
+                    let multipliers = (0..=i).map(|j| spread(j).exp()).collect::<Vec<_>>();
+                    let guess = rates.last().map_or(-target.ln() / dt, |r: &Vec<f64>| r[0]);
+                    let a = Self::fit_lognormal(prices, &multipliers, target, dt, guess)
+                        .ok_or(LatticeError::CalibrationFailed(i))?;
+
+                    multipliers.iter().map(|m| a * m).collect()
+                }
+            };
+
+            // Forward induction of the state prices to the next step.
+            let discounted = prices
+                .iter()
+                .zip(&step_rates)
+                .map(|(q, r)| 0.5 * q * f64::exp(-r * dt))
+                .collect::<Vec<_>>();
+
+            let next = (0..=i + 1)
+                .map(|j| {
+                    let down = if j <= i { discounted[j] } else { 0.0 };
+                    let up = if j > 0 { discounted[j - 1] } else { 0.0 };
+                    down + up
+                })
+                .collect();
+
+            rates.push(step_rates);
+            state_prices.push(next);
+        }
+
+        Ok(Self {
+            model,
+            valuation_date,
+            maturity_date,
+            dt,
+            rates,
+            state_prices,
+        })
+    }
+
+    // Newton's method for the level `a` with
+    // `sum_j q_j exp(-a m_j dt) = target`, which is decreasing and convex
+    // in `a`, so the iterates converge monotonically.
+    fn fit_lognormal(
+        prices: &[f64],
+        multipliers: &[f64],
+        target: f64,
+        dt: f64,
+        guess: f64,
+    ) -> Option<f64> {
+        const MAX_ITERATIONS: usize = 100;
+        const TOLERANCE: f64 = 1e-14;
+
+        let mut a = guess;
+
+        for _ in 0..MAX_ITERATIONS {
+            let (value, slope) =
+                prices
+                    .iter()
+                    .zip(multipliers)
+                    .fold((-target, 0.0), |(value, slope), (q, m)| {
+                        let term = q * f64::exp(-a * m * dt);
+                        (value + term, slope - term * m * dt)
+                    });
+
+            if value.abs() < TOLERANCE {
+                return Some(a);
+            }
+            if slope == 0.0 || !slope.is_finite() {
+                return None;
+            }
+
+            a -= value / slope;
+        }
+
+        None
+    }
+
+    /// Number of steps.
+    pub fn n_steps(&self) -> usize {
+        self.rates.len()
+    }
+
+    /// Time of a step, in years from the valuation date.
+    pub fn time(&self, step: usize) -> f64 {
+        step as f64 * self.dt
+    }
+
+    /// Date of a step.
+    pub fn date(&self, step: usize) -> OffsetDateTime {
+        let n = self.n_steps() as f64;
+
+        self.valuation_date + (self.maturity_date - self.valuation_date) * step as f64 / n
+    }
+
+    /// Step nearest to a date, if the date is within the lattice.
+    pub fn step_at(&self, date: impl IntoEvaluationDate) -> Option<usize> {
+        let date = date.into_evaluation_date();
+        let fraction = (date - self.valuation_date) / (self.maturity_date - self.valuation_date);
+        let step = (fraction * self.n_steps() as f64).round();
+
+        (0.0..=self.n_steps() as f64)
+            .contains(&step)
+            .then_some(step as usize)
+    }
+
+    /// Discount factor from the valuation date to a step implied by the
+    /// lattice (equal to the curve's, by construction).
+    pub fn discount_factor(&self, step: usize) -> f64 {
+        self.state_prices[step].iter().sum()
+    }
+
+    /// Values at `step` of a claim worth `next` at the nodes of `step + 1`:
+    /// the average of the up and down values, discounted at the node's
+    /// short rate.
+    pub fn step_back(&self, step: usize, next: &[f64]) -> Vec<f64> {
+        assert_eq!(next.len(), step + 2, "One value per node of the next step.");
+
+        self.rates[step]
+            .iter()
+            .enumerate()
+            .map(|(j, r)| 0.5 * (next[j] + next[j + 1]) * f64::exp(-r * self.dt))
+            .collect()
+    }
+
+    /// Rolls the node values of `from_step` back to the valuation date and
+    /// returns the value there. After each step back, `at_step` is called
+    /// with the step and its node values, to apply cash flows, exercise or
+    /// call decisions, barriers, etc.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `from_step` is past the last step or `values` does not
+    /// have `from_step + 1` entries.
+    pub fn rollback<F>(&self, from_step: usize, values: &[f64], mut at_step: F) -> f64
+    where
+        F: FnMut(usize, &mut [f64]),
+    {
+        assert!(from_step <= self.n_steps(), "Step past the lattice.");
+        assert_eq!(values.len(), from_step + 1, "One value per node.");
+
+        (0..from_step).rev().fold(values.to_vec(), |next, step| {
+            let mut values = self.step_back(step, &next);
+            at_step(step, &mut values);
+            values
+        })[0]
+    }
+
+    /// Prices at the nodes of `step` of a zero-coupon bond paying one at
+    /// `maturity_step`, or an error if `maturity_step` is past the lattice
+    /// or before `step`.
+    pub fn zero_coupon_bonds(
+        &self,
+        step: usize,
+        maturity_step: usize,
+    ) -> Result<Vec<f64>, LatticeError> {
+        if maturity_step > self.n_steps() {
+            return Err(LatticeError::StepOutOfRange(maturity_step, self.n_steps()));
+        }
+        if step > maturity_step {
+            return Err(LatticeError::StepOutOfRange(step, maturity_step));
+        }
+
+        Ok((step..maturity_step)
+            .rev()
+            .fold(vec![1.0; maturity_step + 1], |next, i| {
+                self.step_back(i, &next)
+            }))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_lattice {
+    use super::*;
+    use crate::time::Tenor;
+    use time::macros::datetime;
+
+    const TODAY: OffsetDateTime = datetime!(2024-01-02 0:00 UTC);
+
+    fn curve() -> YieldCurve {
+        YieldCurve::from_tenors_and_rates(
+            TODAY,
+            &[Tenor::days(0), Tenor::years(2), Tenor::years(10)],
+            &[0.03, 0.04, 0.045],
+        )
+    }
+
+    fn lattice(model: LatticeModel, sigma: f64) -> ShortRateLattice {
+        ShortRateLattice::new(model, &curve(), TODAY + Tenor::years(5), 60, sigma)
+    }
+
+    // Value of a payer swap (receive floating, pay `strike` annually) from
+    // `start` to `end` years, at the nodes of the start step, with monthly
+    // lattice steps.
+    fn payer_swap(lattice: &ShortRateLattice, start: usize, end: usize, strike: f64) -> Vec<f64> {
+        let mut value = lattice.zero_coupon_bonds(12 * start, 12 * end).unwrap();
+
+        for year in start + 1..=end {
+            let bond = lattice.zero_coupon_bonds(12 * start, 12 * year).unwrap();
+            value
+                .iter_mut()
+                .zip(bond)
+                .for_each(|(v, p)| *v += strike * p);
+        }
+
+        value.iter().map(|v| 1.0 - v).collect()
+    }
+
+    #[test]
+    fn test_lattice_reprices_curve() {
+        let curve = curve();
+
+        for model in [LatticeModel::HoLee, LatticeModel::BlackDermanToy] {
+            let sigma = match model {
+                LatticeModel::HoLee => 0.01,
+                LatticeModel::BlackDermanToy => 0.2,
+            };
+            let lattice = lattice(model, sigma);
+
+            assert_eq!(lattice.n_steps(), 60);
+            assert_eq!(lattice.state_prices.len(), 61);
+
+            for step in [1, 12, 30, 60] {
+                let df = curve.try_discount_factor(lattice.date(step)).unwrap();
+                assert_approx_equal!(lattice.discount_factor(step), df, 1e-12);
+
+                let bonds = lattice.zero_coupon_bonds(0, step).unwrap();
+                assert_approx_equal!(bonds[0], df, 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_lattice_without_volatility() {
+        let lattice = lattice(LatticeModel::BlackDermanToy, 0.0);
+
+        // Every node has the forward rate of its step.
+        for step in [0, 24, 59] {
+            let forward = (lattice.discount_factor(step) / lattice.discount_factor(step + 1)).ln()
+                / lattice.dt;
+
+            for rate in &lattice.rates[step] {
+                assert_approx_equal!(*rate, forward, 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn test_lattice_volatility_term_structure() {
+        let volatilities = (0..60)
+            .map(|i| 0.01 + 0.0001 * i as f64)
+            .collect::<Vec<_>>();
+        let lattice = ShortRateLattice::with_volatilities(
+            LatticeModel::HoLee,
+            &curve(),
+            TODAY + Tenor::years(5),
+            &volatilities,
+        )
+        .unwrap();
+
+        let spacing = |i: usize| lattice.rates[i][1] - lattice.rates[i][0];
+        assert_approx_equal!(spacing(1), 2.0 * 0.0101 * lattice.dt.sqrt(), 1e-12);
+        assert_approx_equal!(spacing(59), 2.0 * 0.0159 * lattice.dt.sqrt(), 1e-12);
+        assert_eq!(lattice.step_at(TODAY + Tenor::years(1)), Some(12));
+        assert_eq!(lattice.step_at(TODAY + Tenor::years(6)), None);
+    }
+
+    #[test]
+    fn test_callable_bond() {
+        let lattice = lattice(LatticeModel::HoLee, 0.01);
+
+        // 5% annual coupon bond, callable at par from year 2.
+        let price = |callable: bool| {
+            lattice.rollback(60, &[105.0; 61], |step, values| {
+                if step > 0 && step % 12 == 0 {
+                    for value in values.iter_mut() {
+                        if callable && step >= 24 {
+                            *value = value.min(100.0);
+                        }
+                        *value += 5.0;
+                    }
+                }
+            })
+        };
+
+        let straight = (1..=5)
+            .map(|year| 5.0 * lattice.discount_factor(12 * year))
+            .sum::<f64>()
+            + 100.0 * lattice.discount_factor(60);
+
+        assert_approx_equal!(price(false), straight, 1e-9);
+        assert!(price(true) < straight);
+    }
+
+    #[test]
+    fn test_bermudan_swaption() {
+        for (model, sigma) in [
+            (LatticeModel::HoLee, 0.01),
+            (LatticeModel::BlackDermanToy, 0.2),
+        ] {
+            let lattice = lattice(model, sigma);
+            let strike = 0.042;
+
+            // European 1Y into 4Y payer swaption.
+            let european = {
+                let exercise = payer_swap(&lattice, 1, 5, strike);
+                let payoff = exercise.iter().map(|v| v.max(0.0)).collect::<Vec<_>>();
+                lattice.rollback(12, &payoff, |_, _| {})
+            };
+
+            // Bermudan: exercisable into the remaining swap each year.
+            let bermudan = {
+                let terminal = payer_swap(&lattice, 4, 5, strike)
+                    .iter()
+                    .map(|v| v.max(0.0))
+                    .collect::<Vec<_>>();
+
+                lattice.rollback(48, &terminal, |step, values| {
+                    if step > 0 && step % 12 == 0 {
+                        let exercise = payer_swap(&lattice, step / 12, 5, strike);
+                        values
+                            .iter_mut()
+                            .zip(exercise)
+                            .for_each(|(v, e)| *v = v.max(e));
+                    }
+                })
+            };
+
+            assert!(european > 0.0);
+            assert!(bermudan > european);
+        }
+    }
+
+    #[test]
+    fn test_lattice_errors() {
+        let curve = curve();
+        let maturity = TODAY + Tenor::years(5);
+
+        assert_eq!(
+            ShortRateLattice::try_new(LatticeModel::HoLee, &curve, maturity, 0, 0.01),
+            Err(LatticeError::NoSteps)
+        );
+        assert_eq!(
+            ShortRateLattice::try_new(LatticeModel::HoLee, &curve, maturity, 10, -0.01),
+            Err(LatticeError::InvalidVolatility(-0.01))
+        );
+        assert_eq!(
+            ShortRateLattice::try_new(LatticeModel::HoLee, &curve, TODAY, 10, 0.01),
+            Err(LatticeError::MaturityBeforeValuation)
+        );
+        assert!(matches!(
+            ShortRateLattice::try_new(
+                LatticeModel::HoLee,
+                &curve,
+                TODAY + Tenor::years(20),
+                10,
+                0.01
+            ),
+            Err(LatticeError::Curve(_))
+        ));
+        assert_eq!(
+            lattice(LatticeModel::HoLee, 0.01).zero_coupon_bonds(0, 61),
+            Err(LatticeError::StepOutOfRange(61, 60))
+        );
+    }
+}
@@ -20,8 +20,9 @@
 //! - `θ`: is the level to which it gets pulled.
 //! - `σ`: is the diffusion coefficient.
 
-use crate::instruments::Instrument;
-use crate::time::{DayCountConvention, DayCounter};
+use crate::instruments::{Instrument, PricingEngine, PricingResult};
+use crate::money::DatedCashflow;
+use crate::time::{DayCount, DayCountConvention};
 use time::OffsetDateTime;
 
 /// Struct containing the Vasicek model parameters.
@@ -38,55 +39,62 @@ pub struct Vasicek {
 }
 
 impl Instrument for Vasicek {
-    fn price(&self) -> f64 {
-        let k = self.k;
-        let theta = self.theta;
-        let sigma = self.sigma;
-        let r0 = self.r0;
-
-        // Compute time to maturity.
-        let tau = match self.evaluation_date {
-            Some(valuation_date) => DayCounter::day_count_factor(
-                valuation_date,
-                self.expiration_date,
-                &DayCountConvention::Actual365,
-            ),
-            None => DayCounter::day_count_factor(
-                OffsetDateTime::now_utc(),
-                self.expiration_date,
-                &DayCountConvention::Actual365,
-            ),
-        };
-
-        let B = || (1.0 - (-k * tau).exp()) / k;
-        let A = || {
-            (((B() - tau) * (k.powi(2) * theta - sigma.powi(2) / 2.0)) / k.powi(2)
-                - (sigma.powi(2) * B().powi(2)) / (4.0 * k))
-                .exp()
-        };
-
-        A() * (-B() * r0).exp()
-
-        // Return the option price on the zero coupon bond?
-        // let N = Gaussian::default();
-
-        // let P_tS = self.price();
-        // self.time_T = maturity;
-        // let P_tT = self.price();
-
-        // let sigma_p = self.sigma * ().sqrt();
-        // let h = ;
-
-        // (
-        //     P_tS * N(h) - strike * P_tT * N(h - sigma_p),
-        //     -P_tS * N(-h) + strike * P_tT * N(sigma_p - h),
-        // )
+    fn price(&self) -> PricingResult {
+        PricingResult::timed(|| {
+            let k = self.k;
+            let theta = self.theta;
+            let sigma = self.sigma;
+            let r0 = self.r0;
+
+            // Compute time to maturity.
+            let tau = match self.evaluation_date {
+                Some(valuation_date) => DayCount::day_count_factor(
+                    valuation_date,
+                    self.expiration_date,
+                    &DayCountConvention::Actual365,
+                ),
+                None => DayCount::day_count_factor(
+                    OffsetDateTime::now_utc(),
+                    self.expiration_date,
+                    &DayCountConvention::Actual365,
+                ),
+            };
+
+            let B = || (1.0 - (-k * tau).exp()) / k;
+            let A = || {
+                (((B() - tau) * (k.powi(2) * theta - sigma.powi(2) / 2.0)) / k.powi(2)
+                    - (sigma.powi(2) * B().powi(2)) / (4.0 * k))
+                    .exp()
+            };
+
+            PricingResult::new(A() * (-B() * r0).exp()).with_engine(PricingEngine::Analytic)
+
+            // Return the option price on the zero coupon bond?
+            // let N = Gaussian::default();
+
+            // let P_tS = self.price();
+            // self.time_T = maturity;
+            // let P_tT = self.price();
+
+            // let sigma_p = self.sigma * ().sqrt();
+            // let h = ;
+
+            // (
+            //     P_tS * N(h) - strike * P_tT * N(h - sigma_p),
+            //     -P_tS * N(-h) + strike * P_tT * N(sigma_p - h),
+            // )
+        })
     }
 
     fn error(&self) -> Option<f64> {
         None
     }
 
+    /// Unit face value at expiry.
+    fn cashflows(&self) -> Vec<DatedCashflow> {
+        vec![DatedCashflow::new(self.expiration_date, 1.0, None)]
+    }
+
     fn valuation_date(&self) -> OffsetDateTime {
         self.evaluation_date.unwrap_or(OffsetDateTime::now_utc())
     }
@@ -98,7 +106,6 @@ impl Instrument for Vasicek {
 
 #[cfg(test)]
 mod tests_bond_vasicek {
-    use crate::assert_approx_equal;
 
     use super::*;
 
@@ -115,7 +122,7 @@ mod tests_bond_vasicek {
             expiration_date: expiry_date,
         };
 
-        let vasicek_price = vasicek.price();
+        let vasicek_price = vasicek.price().value();
 
         assert_approx_equal!(vasicek_price, 0.9615, 1e-4);
     }
@@ -0,0 +1,401 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Commodity forward curves with monthly seasonality.
+//!
+//! Many commodities (natural gas, power, agricultural products) have
+//! forwards that rise and fall with the delivery month. Interpolating the
+//! quoted forwards directly smears the pattern between contracts, so the
+//! curve divides each quote by a multiplicative factor $s_m$ for its
+//! delivery month, interpolates the logarithm of the deseasonalised forwards
+//! and multiplies the factor back in:
+//! $$
+//! F(t) = s_{m(t)} \exp\left( I\left[ \ln \frac{F_i}{s_{m(t_i)}} \right](t) \right)
+//! $$
+
+use crate::error::{expect_valid, RustQuantError};
+use crate::math::interpolation::{Extrapolation, InterpolationScheme};
+use crate::time::{DayCountConvention, DayCounter};
+use std::collections::BTreeMap;
+use thiserror::Error;
+use time::OffsetDateTime;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Forward prices of a commodity by delivery date, with multiplicative
+/// monthly seasonality.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommodityForwardCurve {
+    /// Date the curve is valued on.
+    pub valuation_date: OffsetDateTime,
+
+    /// Quoted forward prices by delivery date.
+    pub quotes: BTreeMap<OffsetDateTime, f64>,
+
+    /// Seasonal factor of each delivery month, January first (all one, i.e.
+    /// no seasonality, by default).
+    pub seasonality: [f64; 12],
+
+    /// Interpolation of the log deseasonalised forwards between quotes.
+    /// Beyond the first and last quotes they are flat.
+    pub interpolation: InterpolationScheme,
+}
+
+/// Commodity error enum.
+#[derive(Debug, Clone, Copy, PartialEq, Error)]
+pub enum CommodityError {
+    /// The curve has no quotes.
+    #[error("The commodity curve has no quotes.")]
+    NoQuotes,
+
+    /// A forward price is not positive and finite.
+    #[error("Commodity forward prices must be positive and finite, got {0}.")]
+    InvalidQuote(f64),
+
+    /// A seasonal factor is not positive and finite.
+    #[error("Seasonal factors must be positive and finite, got {0}.")]
+    InvalidSeasonality(f64),
+
+    /// The date is before the curve's valuation date.
+    #[error("The date is before the curve's valuation date.")]
+    DateBeforeValuation,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl CommodityForwardCurve {
+    /// New curve from forward quotes by delivery date, without seasonality.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no quotes or a quote is not positive, as
+    /// [`CommodityForwardCurve::try_new`].
+    pub fn new(valuation_date: OffsetDateTime, quotes: BTreeMap<OffsetDateTime, f64>) -> Self {
+        expect_valid(Self::try_new(valuation_date, quotes).map_err(RustQuantError::from))
+    }
+
+    /// New curve from forward quotes by delivery date, or an error if there
+    /// are no quotes or a quote is not positive and finite.
+    pub fn try_new(
+        valuation_date: OffsetDateTime,
+        quotes: BTreeMap<OffsetDateTime, f64>,
+    ) -> Result<Self, CommodityError> {
+        if quotes.is_empty() {
+            return Err(CommodityError::NoQuotes);
+        }
+        if let Some(&quote) = quotes.values().find(|quote| !is_positive(**quote)) {
+            return Err(CommodityError::InvalidQuote(quote));
+        }
+
+        Ok(Self {
+            valuation_date,
+            quotes,
+            seasonality: [1.0; 12],
+            interpolation: InterpolationScheme::Linear,
+        })
+    }
+
+    /// Sets the seasonal factors of the delivery months, January first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a factor is not positive and finite.
+    pub fn with_seasonality(mut self, seasonality: [f64; 12]) -> Self {
+        if let Some(&factor) = seasonality.iter().find(|factor| !is_positive(**factor)) {
+            panic!("{}", CommodityError::InvalidSeasonality(factor));
+        }
+
+        self.seasonality = seasonality;
+        self
+    }
+
+    /// Sets the interpolation between quotes.
+    pub fn with_interpolation(mut self, interpolation: InterpolationScheme) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Seasonal factor of the month the date falls in.
+    pub fn seasonal_factor(&self, date: OffsetDateTime) -> f64 {
+        self.seasonality[month_index(date)]
+    }
+
+    /// Forward price for delivery on the given date, or an error if the
+    /// date is before the valuation date.
+    pub fn try_forward(&self, date: OffsetDateTime) -> Result<f64, CommodityError> {
+        if date < self.valuation_date {
+            return Err(CommodityError::DateBeforeValuation);
+        }
+
+        let (xs, ys): (Vec<f64>, Vec<f64>) = self
+            .quotes
+            .iter()
+            .map(|(delivery, quote)| {
+                (
+                    self.days(*delivery),
+                    (quote / self.seasonal_factor(*delivery)).ln(),
+                )
+            })
+            .unzip();
+
+        let log_forward = match ys.len() {
+            1 => ys[0],
+            _ => self
+                .interpolation
+                .build(&xs, &ys, Extrapolation::Flat)
+                .and_then(|interpolator| interpolator.interpolate(self.days(date)))
+                .map_err(|_| CommodityError::NoQuotes)?,
+        };
+
+        Ok(self.seasonal_factor(date) * log_forward.exp())
+    }
+
+    /// Forward price for delivery on the given date.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the date is before the valuation date.
+    pub fn forward(&self, date: OffsetDateTime) -> f64 {
+        expect_valid(self.try_forward(date).map_err(RustQuantError::from))
+    }
+
+    /// Estimates monthly seasonal factors from the quotes.
+    ///
+    /// The log forwards are fitted to a linear trend in time plus a
+    /// constant for each delivery month (by alternating least squares on
+    /// the trend and the monthly means of its residuals). The factors of
+    /// the quoted months are normalised to a geometric mean of one, and
+    /// months without a quote get a factor of one.
+    pub fn estimate_seasonality(&self) -> [f64; 12] {
+        let day_count = DayCountConvention::Actual365;
+        let points = self
+            .quotes
+            .iter()
+            .map(|(delivery, quote)| {
+                (
+                    day_count.year_fraction(self.valuation_date, *delivery),
+                    month_index(*delivery),
+                    quote.ln(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let mut log_factors = [0.0; 12];
+
+        for _ in 0..MAX_ITERATIONS {
+            let (intercept, slope) = linear_trend(
+                points
+                    .iter()
+                    .map(|(t, month, y)| (*t, y - log_factors[*month])),
+            );
+
+            let mut sums = [0.0; 12];
+            let mut counts = [0usize; 12];
+            for (t, month, y) in &points {
+                sums[*month] += y - intercept - slope * t;
+                counts[*month] += 1;
+            }
+
+            let quoted = counts.iter().filter(|count| **count > 0).count() as f64;
+            let mean = (0..12)
+                .filter(|month| counts[*month] > 0)
+                .map(|month| sums[month] / counts[month] as f64)
+                .sum::<f64>()
+                / quoted;
+
+            let mut updated = [0.0; 12];
+            for month in (0..12).filter(|month| counts[*month] > 0) {
+                updated[month] = sums[month] / counts[month] as f64 - mean;
+            }
+
+            let change = updated
+                .iter()
+                .zip(&log_factors)
+                .map(|(new, old)| (new - old).abs())
+                .fold(0.0, f64::max);
+
+            log_factors = updated;
+
+            if change < 1e-12 {
+                break;
+            }
+        }
+
+        log_factors.map(f64::exp)
+    }
+
+    fn days(&self, date: OffsetDateTime) -> f64 {
+        (date - self.valuation_date).as_seconds_f64() / 86_400.0
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+const MAX_ITERATIONS: usize = 200;
+
+fn is_positive(x: f64) -> bool {
+    x.is_finite() && x > 0.0
+}
+
+fn month_index(date: OffsetDateTime) -> usize {
+    u8::from(date.month()) as usize - 1
+}
+
+/// Least squares intercept and slope of `y` on `t` (a flat line through
+/// the mean if there is only one distinct `t`).
+fn linear_trend(points: impl Iterator<Item = (f64, f64)>) -> (f64, f64) {
+    let points = points.collect::<Vec<_>>();
+    let n = points.len() as f64;
+    let t_mean = points.iter().map(|(t, _)| t).sum::<f64>() / n;
+    let y_mean = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+
+    let covariance = points
+        .iter()
+        .map(|(t, y)| (t - t_mean) * (y - y_mean))
+        .sum::<f64>();
+    let variance = points
+        .iter()
+        .map(|(t, _)| (t - t_mean).powi(2))
+        .sum::<f64>();
+
+    let slope = if variance > 0.0 {
+        covariance / variance
+    } else {
+        0.0
+    };
+
+    (y_mean - slope * t_mean, slope)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_commodity_curve {
+    use super::*;
+    use crate::time::Tenor;
+    use time::macros::datetime;
+
+    const TODAY: OffsetDateTime = datetime!(2024-01-01 0:00 UTC);
+
+    // Natural gas style: expensive in winter, cheap in summer.
+    const SEASONALITY: [f64; 12] = [
+        1.25, 1.20, 1.05, 0.90, 0.85, 0.85, 0.90, 0.90, 0.90, 0.95, 1.10, 1.20,
+    ];
+
+    fn normalised(factors: [f64; 12]) -> [f64; 12] {
+        let mean = factors.iter().map(|f| f.ln()).sum::<f64>() / 12.0;
+
+        factors.map(|f| f / mean.exp())
+    }
+
+    /// Monthly quotes for three years: a 2% a year contango times the
+    /// seasonal pattern.
+    fn seasonal_curve() -> CommodityForwardCurve {
+        let factors = normalised(SEASONALITY);
+        let quotes = (1..=36)
+            .map(|i| {
+                let delivery = TODAY + Tenor::months(i);
+                let t = DayCountConvention::Actual365.year_fraction(TODAY, delivery);
+
+                (
+                    delivery,
+                    3.0 * (0.02 * t).exp() * factors[month_index(delivery)],
+                )
+            })
+            .collect();
+
+        CommodityForwardCurve::new(TODAY, quotes)
+    }
+
+    #[test]
+    fn test_estimate_seasonality() {
+        let curve = seasonal_curve();
+        let estimated = curve.estimate_seasonality();
+
+        for (estimated, expected) in estimated.iter().zip(normalised(SEASONALITY)) {
+            assert_approx_equal!(*estimated, expected, 1e-8);
+        }
+
+        // Without quotes in a month, its factor is one.
+        let single = CommodityForwardCurve::new(
+            TODAY,
+            BTreeMap::from([(datetime!(2024-03-01 0:00 UTC), 3.0)]),
+        );
+        assert_eq!(single.estimate_seasonality()[5], 1.0);
+    }
+
+    #[test]
+    fn test_seasonal_forward() {
+        let curve = seasonal_curve();
+        let seasonal = curve.clone().with_seasonality(curve.estimate_seasonality());
+
+        // Both curves reprice the quotes.
+        for (delivery, quote) in &curve.quotes {
+            assert_approx_equal!(curve.forward(*delivery), *quote, 1e-12);
+            assert_approx_equal!(seasonal.forward(*delivery), *quote, 1e-8);
+        }
+
+        // Mid-month, the seasonal curve keeps the month's level while the
+        // plain curve blends the neighbouring contracts.
+        let mid_january = datetime!(2025-01-16 0:00 UTC);
+        let february = datetime!(2025-02-01 0:00 UTC);
+        let january = datetime!(2025-01-01 0:00 UTC);
+        let blended = curve.forward(mid_january);
+
+        assert!(blended < curve.forward(january) && blended > curve.forward(february));
+        assert_approx_equal!(
+            seasonal.forward(mid_january) / seasonal.forward(january),
+            (0.02 * 15.0 / 365.0_f64).exp(),
+            1e-8
+        );
+    }
+
+    #[test]
+    fn test_curve_errors() {
+        assert_eq!(
+            CommodityForwardCurve::try_new(TODAY, BTreeMap::new()),
+            Err(CommodityError::NoQuotes)
+        );
+        assert_eq!(
+            CommodityForwardCurve::try_new(TODAY, BTreeMap::from([(TODAY, -1.0)])),
+            Err(CommodityError::InvalidQuote(-1.0))
+        );
+
+        let curve = seasonal_curve();
+        assert_eq!(
+            curve.try_forward(datetime!(2023-06-01 0:00 UTC)),
+            Err(CommodityError::DateBeforeValuation)
+        );
+
+        // Flat beyond the last quote (deseasonalised).
+        let last = *curve.quotes.keys().last().unwrap();
+        assert_approx_equal!(
+            curve.forward(last + Tenor::years(1)),
+            curve.forward(last),
+            1e-12
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Seasonal factors must be positive")]
+    fn test_invalid_seasonality() {
+        let mut factors = [1.0; 12];
+        factors[3] = 0.0;
+
+        let _ = seasonal_curve().with_seasonality(factors);
+    }
+}
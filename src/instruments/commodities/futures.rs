@@ -0,0 +1,101 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::instruments::commodities::{CommodityError, CommodityForwardCurve};
+use time::OffsetDateTime;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// A long position in a commodity futures contract.
+///
+/// Futures are marked to market daily, so the position is worth the change
+/// in the futures price since the trade, undiscounted.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CommodityFuture {
+    /// Delivery date of the contract.
+    pub delivery_date: OffsetDateTime,
+
+    /// Units of the commodity per contract (e.g. 1,000 barrels).
+    pub contract_size: f64,
+
+    /// Futures price the position was traded at.
+    pub trade_price: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl CommodityFuture {
+    /// New futures position of one contract.
+    pub fn new(delivery_date: OffsetDateTime, trade_price: f64) -> Self {
+        Self {
+            delivery_date,
+            contract_size: 1.0,
+            trade_price,
+        }
+    }
+
+    /// Sets the units of the commodity per contract (negative for a short
+    /// position).
+    pub fn with_contract_size(mut self, contract_size: f64) -> Self {
+        self.contract_size = contract_size;
+        self
+    }
+
+    /// Futures price on the curve for the contract's delivery date.
+    pub fn price(&self, curve: &CommodityForwardCurve) -> Result<f64, CommodityError> {
+        curve.try_forward(self.delivery_date)
+    }
+
+    /// Variation margin of the position: the change in the futures price
+    /// since the trade times the contract size.
+    pub fn value(&self, curve: &CommodityForwardCurve) -> Result<f64, CommodityError> {
+        Ok((self.price(curve)? - self.trade_price) * self.contract_size)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_commodity_futures {
+    use super::*;
+    use std::collections::BTreeMap;
+    use time::macros::datetime;
+
+    #[test]
+    fn test_futures_value() {
+        let curve = CommodityForwardCurve::new(
+            datetime!(2024-01-01 0:00 UTC),
+            BTreeMap::from([
+                (datetime!(2024-03-01 0:00 UTC), 80.0),
+                (datetime!(2024-05-01 0:00 UTC), 82.0),
+            ]),
+        );
+        let future =
+            CommodityFuture::new(datetime!(2024-03-01 0:00 UTC), 78.5).with_contract_size(1000.0);
+
+        assert_approx_equal!(future.price(&curve).unwrap(), 80.0, 1e-12);
+        assert_approx_equal!(future.value(&curve).unwrap(), 1500.0, 1e-9);
+
+        // A short position loses what the long gains.
+        let short = future.with_contract_size(-1000.0);
+        assert_approx_equal!(short.value(&curve).unwrap(), -1500.0, 1e-9);
+
+        let expired = CommodityFuture::new(datetime!(2023-12-01 0:00 UTC), 78.5);
+        assert_eq!(
+            expired.value(&curve),
+            Err(CommodityError::DateBeforeValuation)
+        );
+    }
+}
@@ -0,0 +1,229 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::curves::Curve;
+use crate::instruments::commodities::{CommodityError, CommodityForwardCurve, SchwartzOneFactor};
+use crate::instruments::options::TypeFlag;
+use crate::instruments::{PricingEngine, PricingResult};
+use crate::statistics::distributions::{Distribution, Gaussian};
+use crate::time::{DayCountConvention, DayCounter};
+use time::OffsetDateTime;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// European option on a commodity futures contract, premium paid at
+/// expiry.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CommodityOption {
+    /// Expiry date of the option.
+    pub expiry_date: OffsetDateTime,
+
+    /// Delivery date of the underlying futures contract (on or after the
+    /// expiry).
+    pub delivery_date: OffsetDateTime,
+
+    /// Strike price.
+    pub strike: f64,
+
+    /// Call or put.
+    pub option_type: TypeFlag,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl CommodityOption {
+    /// New option on the futures contract delivering on `delivery_date`.
+    pub fn new(
+        expiry_date: OffsetDateTime,
+        delivery_date: OffsetDateTime,
+        strike: f64,
+        option_type: TypeFlag,
+    ) -> Self {
+        Self {
+            expiry_date,
+            delivery_date,
+            strike,
+            option_type,
+        }
+    }
+
+    /// Black (1976) price with a flat lognormal volatility of the futures
+    /// price, its forward taken from `curve` and the payoff discounted from
+    /// the expiry on `discount`:
+    /// $$
+    /// C = P(T_e) \left( F N(d_1) - K N(d_2) \right)
+    /// $$
+    pub fn price<C: Curve>(
+        &self,
+        curve: &CommodityForwardCurve,
+        discount: &C,
+        volatility: f64,
+    ) -> Result<PricingResult, CommodityError> {
+        let forward = curve.try_forward(self.delivery_date)?;
+        let t = self.time_to_expiry(curve)?;
+        let undiscounted = black(
+            forward,
+            self.strike,
+            volatility * volatility * t,
+            self.option_type,
+        );
+
+        Ok(
+            PricingResult::new(discount.discount_factor(self.expiry_date) * undiscounted)
+                .with_engine(PricingEngine::Analytic),
+        )
+    }
+
+    /// Black price with the futures volatility implied by a Schwartz
+    /// one-factor model ([`SchwartzOneFactor::futures_volatility`]), so that
+    /// options on later deliveries are priced with less volatility.
+    pub fn price_schwartz<C: Curve>(
+        &self,
+        curve: &CommodityForwardCurve,
+        discount: &C,
+        model: &SchwartzOneFactor,
+    ) -> Result<PricingResult, CommodityError> {
+        let day_count = DayCountConvention::Actual365;
+        let expiry = self.time_to_expiry(curve)?;
+        let delivery = day_count.year_fraction(curve.valuation_date, self.delivery_date);
+
+        self.price(curve, discount, model.futures_volatility(expiry, delivery))
+    }
+
+    /// Year fraction (Actual/365) from the curve's valuation date to the
+    /// expiry.
+    fn time_to_expiry(&self, curve: &CommodityForwardCurve) -> Result<f64, CommodityError> {
+        if self.expiry_date < curve.valuation_date {
+            return Err(CommodityError::DateBeforeValuation);
+        }
+
+        Ok(DayCountConvention::Actual365.year_fraction(curve.valuation_date, self.expiry_date))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Undiscounted Black price of an option on a lognormal forward with total
+/// log variance `variance` to expiry.
+fn black(forward: f64, strike: f64, variance: f64, option_type: TypeFlag) -> f64 {
+    let sign = match option_type {
+        TypeFlag::Call => 1.0,
+        TypeFlag::Put => -1.0,
+    };
+
+    if variance <= 0.0 || strike <= 0.0 {
+        return (sign * (forward - strike)).max(0.0);
+    }
+
+    let N = Gaussian::default();
+    let d1 = ((forward / strike).ln() + 0.5 * variance) / variance.sqrt();
+    let d2 = d1 - variance.sqrt();
+
+    sign * (forward * N.cdf(sign * d1) - strike * N.cdf(sign * d2))
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_commodity_options {
+    use super::*;
+    use crate::curves::YieldCurve;
+    use crate::time::Tenor;
+    use std::collections::BTreeMap;
+    use time::macros::datetime;
+
+    const TODAY: OffsetDateTime = datetime!(2024-01-01 0:00 UTC);
+
+    fn curves() -> (CommodityForwardCurve, YieldCurve) {
+        let forwards = CommodityForwardCurve::new(
+            TODAY,
+            BTreeMap::from([
+                (datetime!(2024-07-01 0:00 UTC), 20.0),
+                (datetime!(2025-07-01 0:00 UTC), 21.0),
+            ]),
+        );
+        let discount = YieldCurve::from_tenors_and_rates(
+            TODAY,
+            &[Tenor::days(0), Tenor::years(5)],
+            &[0.09; 2],
+        );
+
+        (forwards, discount)
+    }
+
+    #[test]
+    fn test_black_76() {
+        // Hull, Example 18.6: F = 20, K = 20, r = 9%, sigma = 25%, T = 4/12
+        // gives a put worth 1.12.
+        let (forwards, discount) = curves();
+        let expiry = TODAY + time::Duration::days(122);
+        let delivery = datetime!(2024-07-01 0:00 UTC);
+        let put = CommodityOption::new(expiry, delivery, 20.0, TypeFlag::Put)
+            .price(&forwards, &discount, 0.25)
+            .unwrap();
+
+        assert_approx_equal!(put.value(), 1.12, 5e-3);
+        assert_eq!(put.engine, Some(PricingEngine::Analytic));
+
+        // Put-call parity on futures: C - P = P(T_e) (F - K).
+        for strike in [15.0, 20.0, 25.0] {
+            let call = CommodityOption::new(expiry, delivery, strike, TypeFlag::Call)
+                .price(&forwards, &discount, 0.25)
+                .unwrap();
+            let put = CommodityOption::new(expiry, delivery, strike, TypeFlag::Put)
+                .price(&forwards, &discount, 0.25)
+                .unwrap();
+
+            assert_approx_equal!(
+                call.value() - put.value(),
+                discount.discount_factor(expiry) * (20.0 - strike),
+                1e-12
+            );
+        }
+    }
+
+    #[test]
+    fn test_schwartz_volatility() {
+        let (forwards, discount) = curves();
+        let model = SchwartzOneFactor::new(1.0, 3.0, 0.4, 0.0);
+        let expiry = datetime!(2024-07-01 0:00 UTC);
+
+        // The same option on a later contract is cheaper, as the later
+        // futures price is less volatile.
+        let near = CommodityOption::new(expiry, expiry, 21.0, TypeFlag::Call);
+        let far =
+            CommodityOption::new(expiry, datetime!(2025-07-01 0:00 UTC), 21.0, TypeFlag::Call);
+
+        let near_price = near.price_schwartz(&forwards, &discount, &model).unwrap();
+        let far_price = far.price_schwartz(&forwards, &discount, &model).unwrap();
+        let far_at_spot_vol = far.price(&forwards, &discount, 0.4).unwrap();
+
+        assert!(far_price.value() < far_at_spot_vol.value());
+        assert!(near_price.value() > 0.0);
+
+        let t = DayCountConvention::Actual365.year_fraction(TODAY, expiry);
+        let implied = near.price(&forwards, &discount, model.futures_volatility(t, t));
+        assert_eq!(implied.unwrap().value(), near_price.value());
+
+        let expired =
+            CommodityOption::new(datetime!(2023-07-01 0:00 UTC), expiry, 21.0, TypeFlag::Call);
+        assert_eq!(
+            expired.price(&forwards, &discount, 0.4),
+            Err(CommodityError::DateBeforeValuation)
+        );
+    }
+}
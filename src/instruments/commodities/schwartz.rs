@@ -0,0 +1,211 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Schwartz (1997) one-factor model of a mean-reverting commodity spot.
+//!
+//! The log spot $X = \ln S$ is an Ornstein-Uhlenbeck process,
+//! $$
+//! dX_t = \kappa (\alpha - X_t) dt + \sigma dW_t,
+//! $$
+//! and under the pricing measure its long-run level is lowered by the
+//! market price of risk $\lambda$ to $\alpha^* = \alpha - \lambda / \kappa$.
+//! Futures prices are then
+//! $$
+//! \ln F(S, \tau) = e^{-\kappa \tau} \ln S + (1 - e^{-\kappa \tau}) \alpha^*
+//!     + \frac{\sigma^2}{4 \kappa} (1 - e^{-2 \kappa \tau}).
+//! $$
+
+use crate::error::{ensure, expect_valid, RustQuantError};
+use crate::stochastics::{OrnsteinUhlenbeck, SimulationConfig, StochasticProcess, Trajectories};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Parameters of the Schwartz one-factor commodity model.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SchwartzOneFactor {
+    /// Speed of mean reversion of the log spot ($\kappa$).
+    pub kappa: f64,
+
+    /// Long-run level of the log spot under the real-world measure
+    /// ($\alpha$).
+    pub alpha: f64,
+
+    /// Volatility of the log spot ($\sigma$).
+    pub sigma: f64,
+
+    /// Market price of risk ($\lambda$).
+    pub lambda: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl SchwartzOneFactor {
+    /// New Schwartz one-factor model.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `kappa` is not positive or `sigma` is negative.
+    pub fn new(kappa: f64, alpha: f64, sigma: f64, lambda: f64) -> Self {
+        expect_valid(Self::try_new(kappa, alpha, sigma, lambda))
+    }
+
+    /// New Schwartz one-factor model, or an error if `kappa` is not
+    /// positive or `sigma` is negative.
+    pub fn try_new(
+        kappa: f64,
+        alpha: f64,
+        sigma: f64,
+        lambda: f64,
+    ) -> Result<Self, RustQuantError> {
+        ensure(kappa > 0.0, "kappa must be positive")?;
+        ensure(sigma >= 0.0, "sigma must be non-negative")?;
+
+        Ok(Self {
+            kappa,
+            alpha,
+            sigma,
+            lambda,
+        })
+    }
+
+    /// Long-run level of the log spot under the pricing measure,
+    /// $\alpha^* = \alpha - \lambda / \kappa$.
+    pub fn risk_neutral_alpha(&self) -> f64 {
+        self.alpha - self.lambda / self.kappa
+    }
+
+    /// Futures price for delivery in `tau` years, given the spot price.
+    pub fn futures_price(&self, spot: f64, tau: f64) -> f64 {
+        let decay = (-self.kappa * tau).exp();
+        let variance = self.sigma.powi(2) / (4.0 * self.kappa) * (1.0 - decay * decay);
+
+        (decay * spot.ln() + (1.0 - decay) * self.risk_neutral_alpha() + variance).exp()
+    }
+
+    /// Black volatility of a futures contract delivering in `delivery`
+    /// years, for an option on it expiring in `expiry` years.
+    ///
+    /// Only the part of the spot's variance that has not mean-reverted away
+    /// by delivery reaches the futures price, so contracts further out are
+    /// less volatile (the Samuelson effect):
+    /// $$
+    /// \sigma_F^2 T_e = \sigma^2 e^{-2 \kappa (T - T_e)} \frac{1 - e^{-2 \kappa T_e}}{2 \kappa}.
+    /// $$
+    pub fn futures_volatility(&self, expiry: f64, delivery: f64) -> f64 {
+        let instantaneous = self.sigma * (-self.kappa * (delivery - expiry)).exp();
+
+        if expiry <= 0.0 {
+            return instantaneous;
+        }
+
+        let averaging = -(-2.0 * self.kappa * expiry).exp_m1() / (2.0 * self.kappa * expiry);
+
+        instantaneous * averaging.sqrt()
+    }
+
+    /// Risk-neutral Ornstein-Uhlenbeck process of the log spot.
+    pub fn log_process(&self) -> OrnsteinUhlenbeck {
+        OrnsteinUhlenbeck::new(self.risk_neutral_alpha(), self.sigma, self.kappa)
+    }
+
+    /// Simulates risk-neutral spot price paths from `spot` over `t_n`
+    /// years, by exponentiating paths of [`SchwartzOneFactor::log_process`].
+    pub fn simulate(
+        &self,
+        spot: f64,
+        t_n: f64,
+        n_steps: usize,
+        n_paths: usize,
+        config: &SimulationConfig,
+    ) -> Trajectories {
+        let log_paths =
+            self.log_process()
+                .simulate_with_config(spot.ln(), 0.0, t_n, n_steps, n_paths, config);
+
+        Trajectories::new(log_paths.times, log_paths.paths.mapv(f64::exp))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_schwartz {
+    use super::*;
+
+    fn model() -> SchwartzOneFactor {
+        SchwartzOneFactor::new(1.5, 4.0_f64.ln(), 0.4, 0.3)
+    }
+
+    #[test]
+    fn test_futures_price_limits() {
+        let model = model();
+
+        // Delivery now is the spot; far away, the futures price forgets the
+        // spot and settles at the long-run level plus a convexity term.
+        assert_approx_equal!(model.futures_price(3.0, 0.0), 3.0, 1e-12);
+
+        let long_run = (model.risk_neutral_alpha() + 0.4_f64.powi(2) / (4.0 * 1.5)).exp();
+        assert_approx_equal!(model.futures_price(3.0, 50.0), long_run, 1e-12);
+        assert_approx_equal!(model.futures_price(6.0, 50.0), long_run, 1e-12);
+
+        // A spot below the long-run level gives an upward sloping curve.
+        assert!(model.futures_price(2.0, 0.5) < model.futures_price(2.0, 1.0));
+    }
+
+    #[test]
+    fn test_futures_volatility() {
+        let model = model();
+
+        assert_approx_equal!(model.futures_volatility(0.0, 0.0), 0.4, 1e-12);
+
+        // Samuelson effect: options on nearer contracts are more volatile.
+        let near = model.futures_volatility(0.5, 0.5);
+        let far = model.futures_volatility(0.5, 2.0);
+        assert!(far < near && near < 0.4);
+
+        // Matches the variance of the log futures price over the expiry.
+        let (kappa, sigma, expiry, delivery): (f64, f64, f64, f64) = (1.5, 0.4, 0.5, 2.0);
+        let variance = sigma
+            * sigma
+            * (-2.0 * kappa * (delivery - expiry)).exp()
+            * (1.0 - (-2.0 * kappa * expiry).exp())
+            / (2.0 * kappa);
+        assert_approx_equal!(far, (variance / expiry).sqrt(), 1e-12);
+    }
+
+    #[test]
+    fn test_simulated_spot_matches_futures() {
+        let model = model();
+        let config = SimulationConfig::new(true).with_seed(42);
+        let paths = model.simulate(3.0, 1.0, 200, 20_000, &config);
+
+        assert_eq!(paths.n_steps(), 200);
+        assert_approx_equal!(paths.paths[[0, 0]], 3.0, 1e-12);
+
+        // Under the pricing measure the expected spot is the futures price.
+        for k in [50, 200] {
+            let t = paths.times[k];
+            let mean = paths.values_at(k).mean().unwrap();
+
+            assert_approx_equal!(mean, model.futures_price(3.0, t), 0.03);
+        }
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        assert!(SchwartzOneFactor::try_new(0.0, 1.0, 0.3, 0.0).is_err());
+        assert!(SchwartzOneFactor::try_new(1.0, 1.0, -0.3, 0.0).is_err());
+    }
+}
@@ -7,8 +7,16 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+use crate::money::{Currency, DatedCashflow};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
 use time::OffsetDateTime;
 
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
 /// Instrument trait
 /// The trait provides a common interface for all instruments.
 /// All instruments can be queried for their net present value (NPV) and
@@ -17,13 +25,21 @@ use time::OffsetDateTime;
 /// being calculated; for most instruments it is the trade date, for
 /// some exotic products it might be the exercise date.
 pub trait Instrument {
-    /// Returns the price (net present value) of the instrument.
-    fn price(&self) -> f64;
+    /// Returns the price (net present value) of the instrument, with how
+    /// it was computed. Use [`PricingResult::value`] for the bare price.
+    fn price(&self) -> PricingResult;
 
     /// Returns the error on the NPV in case the pricing engine can
     /// provide it (e.g. Monte Carlo pricing engine).
     fn error(&self) -> Option<f64>;
 
+    /// Scheduled cash flows of the instrument, in date order.
+    /// Instruments without scheduled flows (e.g. options, whose payoff is
+    /// contingent) have none.
+    fn cashflows(&self) -> Vec<DatedCashflow> {
+        Vec::new()
+    }
+
     /// Returns the date at which the NPV is calculated.
     fn valuation_date(&self) -> OffsetDateTime;
 
@@ -40,7 +56,54 @@ pub struct Price {
     pub error: Option<f64>,
 }
 
+/// Price of an instrument with its breakdown and diagnostics.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PricingResult {
+    /// Price (net present value) of the instrument.
+    pub value: f64,
+
+    /// Currency of the price, if the instrument has one.
+    pub currency: Option<Currency>,
+
+    /// Standard error of the price (e.g. for Monte Carlo prices).
+    pub standard_error: Option<f64>,
+
+    /// Pricing method used.
+    pub engine: Option<PricingEngine>,
+
+    /// Cash flows whose present values sum to the price, if the
+    /// instrument is priced by discounting cash flows.
+    pub cash_flows: Vec<DiscountedCashFlow>,
+
+    /// Time taken to price the instrument, if timed. `Instrument::price`
+    /// implementations and the Monte Carlo pricer time themselves (except
+    /// on `wasm32`, see [`PricingResult::timed`]); other pricers may leave
+    /// it `None`.
+    pub compute_time: Option<Duration>,
+
+    /// Other values reported by the pricer (e.g. number of paths or
+    /// iterations), by name.
+    pub diagnostics: BTreeMap<String, f64>,
+}
+
+/// Cash flow of a price breakdown.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DiscountedCashFlow {
+    /// Payment date.
+    pub date: OffsetDateTime,
+
+    /// Amount paid.
+    pub amount: f64,
+
+    /// Discount factor from the payment date to the valuation date.
+    pub discount_factor: f64,
+
+    /// Present value (the amount times the discount factor).
+    pub present_value: f64,
+}
+
 /// Pricing engine for instruments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PricingEngine {
     /// Analytic pricing method (e.g. closed-form solution).
     Analytic,
@@ -63,3 +126,173 @@ pub trait PathDependentPayoff {
     /// Base method for path dependent option payoffs.
     fn payoff(&self, path: &[f64]) -> f64;
 }
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl PricingResult {
+    /// New pricing result with only a value.
+    pub fn new(value: f64) -> Self {
+        Self {
+            value,
+            currency: None,
+            standard_error: None,
+            engine: None,
+            cash_flows: Vec::new(),
+            compute_time: None,
+            diagnostics: BTreeMap::new(),
+        }
+    }
+
+    /// Prices with `pricer` and records the time taken (except on
+    /// `wasm32`, which has no clock).
+    pub fn timed<F: FnOnce() -> Self>(pricer: F) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let start = std::time::Instant::now();
+            let result = pricer();
+
+            result.with_compute_time(start.elapsed())
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        pricer()
+    }
+
+    /// As [`PricingResult::timed`], for a pricer that can fail. Errors are
+    /// returned as they are, so validation inside `pricer` is timed too.
+    pub fn try_timed<E, F: FnOnce() -> Result<Self, E>>(pricer: F) -> Result<Self, E> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let start = std::time::Instant::now();
+            let result = pricer()?;
+
+            Ok(result.with_compute_time(start.elapsed()))
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        pricer()
+    }
+
+    /// Price (net present value) of the instrument.
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Time taken to price the instrument, if the pricer was timed
+    /// (see [`PricingResult::timed`]).
+    pub fn compute_time(&self) -> Option<Duration> {
+        self.compute_time
+    }
+
+    /// Sets the currency of the price.
+    pub fn with_currency(mut self, currency: Option<Currency>) -> Self {
+        self.currency = currency;
+        self
+    }
+
+    /// Sets the standard error of the price.
+    pub fn with_standard_error(mut self, standard_error: f64) -> Self {
+        self.standard_error = Some(standard_error);
+        self
+    }
+
+    /// Sets the pricing method used.
+    pub fn with_engine(mut self, engine: PricingEngine) -> Self {
+        self.engine = Some(engine);
+        self
+    }
+
+    /// Sets the cash flow breakdown of the price.
+    pub fn with_cash_flows(mut self, cash_flows: Vec<DiscountedCashFlow>) -> Self {
+        self.cash_flows = cash_flows;
+        self
+    }
+
+    /// Sets the time taken to price the instrument.
+    pub fn with_compute_time(mut self, compute_time: Duration) -> Self {
+        self.compute_time = Some(compute_time);
+        self
+    }
+
+    /// Adds a named diagnostic value.
+    pub fn with_diagnostic(mut self, name: &str, value: f64) -> Self {
+        self.diagnostics.insert(name.to_string(), value);
+        self
+    }
+}
+
+impl DiscountedCashFlow {
+    /// Cash flow of `amount` on `date`, discounted with `discount_factor`.
+    pub fn new(date: OffsetDateTime, amount: f64, discount_factor: f64) -> Self {
+        Self {
+            date,
+            amount,
+            discount_factor,
+            present_value: amount * discount_factor,
+        }
+    }
+}
+
+impl From<PricingResult> for f64 {
+    fn from(result: PricingResult) -> Self {
+        result.value
+    }
+}
+
+impl fmt::Display for PricingResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value)?;
+
+        if let Some(currency) = &self.currency {
+            write!(f, " {}", currency.code.alphabetic)?;
+        }
+        if let Some(standard_error) = self.standard_error {
+            write!(f, " (standard error {standard_error})")?;
+        }
+
+        Ok(())
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_instrument {
+    use super::*;
+    use crate::money::USD;
+    use time::macros::datetime;
+
+    #[test]
+    fn test_pricing_result() {
+        let date = datetime!(2025-01-01 0:00 UTC);
+        let result = PricingResult::new(10.0)
+            .with_currency(Some(USD))
+            .with_standard_error(0.01)
+            .with_engine(PricingEngine::Simulation)
+            .with_cash_flows(vec![DiscountedCashFlow::new(date, 20.0, 0.5)])
+            .with_diagnostic("paths", 1000.0);
+
+        assert_eq!(result.value(), 10.0);
+        assert_eq!(result.cash_flows[0].present_value, 10.0);
+        assert_eq!(result.diagnostics.get("paths"), Some(&1000.0));
+        assert_eq!(result.to_string(), "10 USD (standard error 0.01)");
+        assert_eq!(f64::from(result), 10.0);
+    }
+
+    #[test]
+    fn test_pricing_result_timed() {
+        let result = PricingResult::timed(|| PricingResult::new(1.0));
+
+        assert_eq!(result.value(), 1.0);
+        assert!(result.compute_time().is_some());
+    }
+
+    #[test]
+    fn test_instrument_price_is_timed() {
+        assert!(USD.price().compute_time().is_some());
+    }
+}
@@ -10,12 +10,37 @@
 //!   - [x] The Cox, Ingersoll, and Ross Model
 //!   - [x] The Hull–White (One-Factor) Model
 //!   - [ ] The Rendleman and Bartter Model
-//!   - [ ] The Ho–Lee Model
-//!   - [ ] The Black–Derman–Toy Model
+//!   - [x] The Ho–Lee Model (lattice)
+//!   - [x] The Black–Derman–Toy Model (lattice)
 //!   - [ ] The Black–Karasinski Model
 //! - [ ] Duration
 //! - [ ] Convexity
 //!
+//! ### :oil_drum: Commodities <a name="commodities"></a>
+//!
+//! - [x] Forward curves with monthly seasonality
+//! - [x] Futures and options on futures (Black-76)
+//! - [x] Schwartz one-factor mean-reverting spot model
+//!
+//! ### :sun_behind_rain_cloud: Weather <a name="weather"></a>
+//!
+//! - [x] Heating and cooling degree-day indices
+//! - [x] Seasonal Ornstein-Uhlenbeck temperature model
+//! - [x] HDD/CDD swaps and options (Monte Carlo)
+//!
+//! ### :chart_with_upwards_trend: Interest Rate Exotics <a name="rates"></a>
+//!
+//! - [x] CMS coupons, caps and floors (Hagan and static replication
+//!   convexity adjustments)
+//!
+//! ### :package: Structured Products <a name="structured"></a>
+//!
+//! - [x] Autocallable and Phoenix (memory coupon) notes
+//! - [x] Reverse convertibles
+//! - [x] Knock-in puts monitored at maturity or continuously
+//! - [x] Monte Carlo pricing with any equity process, with coupon and
+//!   autocall probability profiles
+//!
 //! ### :money_with_wings: Option Pricing <a name="options"></a>
 //!
 //! - Closed-form price solutions:
@@ -29,11 +54,14 @@
 //!   - [x] Bachelier and Modified Bachelier
 //!   - [x] Generalised Black-Scholes-Merton
 //!   - [ ] Basket
-//!   - [ ] Rainbow
-//!   - [ ] American
+//!   - [x] Rainbow (two-asset best-of/worst-of, Stulz)
+//!   - [x] Power, capped power, capped/floored and corridor options
+//!   - [x] American (Barone-Adesi-Whaley, Bjerksund-Stensland approximations)
 //!
 //! - Lattice models:
 //!   - [x] Binomial Tree (Cox-Ross-Rubinstein)
+//!   - [x] Short-rate lattices fitted to a yield curve (Ho-Lee, Black-Derman-Toy),
+//!     for callable bonds, Bermudan swaptions, etc.
 //!
 //! The stochastic process generators can be used to price path-dependent options via Monte-Carlo.
 //!
@@ -42,8 +70,10 @@
 //!   - [ ] Asian
 //!   - [ ] Chooser
 //!   - [ ] Barrier
+//!   - [x] Any path-independent or path-dependent payoff (`MonteCarloPricer`)
+//!   - [x] Rainbow: best-of, worst-of and outperformance on correlated assets
 //!
-//! ```rust
+//! ```rust,ignore
 //! use RustQuant::options::*;
 //!
 //! let VanillaOption = EuropeanOption {
@@ -67,25 +97,46 @@ pub use instrument::*;
 
 /// Bond pricing models.
 pub mod bonds {
-    pub use crate::instruments::bonds::{bond::*, cox_ingersoll_ross::*, vasicek::*};
+    pub use crate::instruments::bonds::{
+        bond::*, builder::*, cox_ingersoll_ross::*, lattice::*, vasicek::*,
+    };
 
     /// Base bond traits.
     pub mod bond;
+    /// Builders for bonds.
+    pub mod builder;
     /// Cox-Ingersoll-Ross bond pricing model.
     pub mod cox_ingersoll_ross;
     /// One-factor Hull-White bond pricing model.
     pub mod hull_white;
+    /// Short-rate lattices (Ho-Lee, Black-Derman-Toy) fitted to a curve.
+    pub mod lattice;
     /// Vasicek bond pricing model.
     pub mod vasicek;
 }
 pub use bonds::*;
 
+/// Commodity forward curves, futures, options and spot models.
+pub mod commodities {
+    pub use crate::instruments::commodities::{curve::*, futures::*, options::*, schwartz::*};
+
+    /// Seasonal commodity forward curves.
+    pub mod curve;
+    /// Commodity futures.
+    pub mod futures;
+    /// Options on commodity futures.
+    pub mod options;
+    /// Schwartz one-factor mean-reverting spot model.
+    pub mod schwartz;
+}
+pub use commodities::*;
+
 /// Option pricers and sensitivity functions.
 pub mod options {
     pub use crate::instruments::options::{
         american::*, asian::*, bachelier::*, barrier::*, binary::*, binomial::*,
-        black_scholes_merton::*, european::*, forward_start::*, greeks::*, heston::*, lookback::*,
-        option::*, power::*,
+        black_scholes_merton::*, capped::*, european::*, exercise::*, forward_start::*, greeks::*,
+        heston::*, lookback::*, monte_carlo::*, option::*, power::*, rainbow::*, strategy::*,
     };
 
     /// American option pricers.
@@ -102,8 +153,12 @@ pub mod options {
     pub mod binomial;
     /// Generalised Black-Scholes-Merton option pricer.
     pub mod black_scholes_merton;
+    /// Capped, floored and corridor options.
+    pub mod capped;
     /// European option pricers.
     pub mod european;
+    /// Early-exercise boundaries and optimal stopping.
+    pub mod exercise;
     /// Forward start options pricers.
     pub mod forward_start;
     /// European option Greeks/sensitivities.
@@ -112,9 +167,62 @@ pub mod options {
     pub mod heston;
     /// Lookback option pricers.
     pub mod lookback;
+    /// Generic Monte Carlo pricing of payoffs.
+    pub mod monte_carlo;
     /// Base option traits.
     pub mod option;
     /// Power option pricers.
     pub mod power;
+    /// Rainbow (best-of, worst-of and outperformance) options.
+    pub mod rainbow;
+    /// Option strategies and payoff analytics.
+    pub mod strategy;
 }
 pub use options::*;
+
+/// Interest rate exotics.
+pub mod rates {
+    pub use crate::instruments::rates::cms::*;
+
+    /// Constant maturity swap (CMS) coupons, caps and floors.
+    pub mod cms;
+}
+pub use rates::*;
+
+/// Structured products: autocallable, Phoenix and reverse convertible notes.
+pub mod structured {
+    pub use crate::instruments::structured::{note::*, note_builder::*};
+
+    /// Structured notes and their Monte Carlo pricing.
+    pub mod note;
+    /// Builder for structured notes.
+    pub mod note_builder;
+}
+pub use structured::*;
+
+/// Weather derivatives: degree-day indices, temperature models, and HDD/CDD
+/// swaps and options.
+pub mod weather {
+    pub use crate::instruments::weather::{contracts::*, degree_days::*, temperature::*};
+
+    /// HDD and CDD swaps and options.
+    pub mod contracts;
+    /// Temperature series and degree-day indices.
+    pub mod degree_days;
+    /// Seasonal mean-reverting temperature model.
+    pub mod temperature;
+}
+pub use weather::*;
+
+/// Term sheets of swaps, options and bonds, with FpML and FIX import/export.
+pub mod termsheets {
+    pub use crate::instruments::termsheets::{fix::*, terms::*};
+
+    /// FIX tag=value messages.
+    pub mod fix;
+    /// FpML documents.
+    pub mod fpml;
+    /// Swap, option and bond terms.
+    pub mod terms;
+}
+pub use termsheets::*;
@@ -7,14 +7,597 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+use crate::instruments::options::{BlackScholesInputs, ExerciseBoundary, TypeFlag};
+use crate::math::polynomials::PolynomialBasis;
+use crate::statistics::distributions::{Distribution, Gaussian};
+use crate::stochastics::Trajectories;
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // STRUCTS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+/// Longstaff-Schwartz (2001) least-squares Monte Carlo engine for
+/// American/Bermudan options, exercisable at each simulated time point.
+///
+/// At each exercise date the discounted future cash flows of the
+/// in-the-money paths are regressed on the basis, and a path is exercised
+/// when its payoff exceeds the fitted continuation value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LongstaffSchwartz {
+    /// Regression basis for the continuation value, e.g. Laguerre
+    /// polynomials scaled by the strike.
+    pub basis: PolynomialBasis,
+}
+
+/// Longstaff-Schwartz price estimate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LongstaffSchwartzResult {
+    /// Option value at the first time point.
+    pub price: f64,
+    /// Monte Carlo standard error of the price.
+    pub standard_error: f64,
+    /// Fraction of paths exercised at each time point.
+    pub exercise_probabilities: Vec<f64>,
+    /// Lowest and highest underlying values at which exercise was optimal
+    /// at each time point, if any (see
+    /// [`LongstaffSchwartzResult::exercise_boundary`]).
+    pub exercise_regions: Vec<Option<(f64, f64)>>,
+}
+
+/// Closed-form approximations of American option prices under the
+/// generalised Black-Scholes-Merton model (continuous dividends enter
+/// through the cost of carry $b = r - q$).
+///
+/// Much faster than a tree or [`LongstaffSchwartz`], at the cost of a small
+/// bias (typically a few cents for at-the-money options).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmericanApproximation {
+    /// Barone-Adesi and Whaley (1987) quadratic approximation.
+    BaroneAdesiWhaley,
+
+    /// Bjerksund and Stensland (1993) flat exercise boundary approximation,
+    /// a lower bound on the American price. Puts are priced through the
+    /// put-call transformation $P(S, K, r, b) = C(K, S, r - b, -b)$.
+    BjerksundStensland,
+}
+
+/// American option price approximation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AmericanApproximationResult {
+    /// American option price.
+    pub price: f64,
+
+    /// European option price with the same inputs.
+    pub european_price: f64,
+
+    /// Underlying price at which immediate exercise becomes optimal: calls
+    /// are exercised at or above it, puts at or below it. Infinite (calls)
+    /// or zero (puts) when early exercise is never optimal.
+    pub critical_price: f64,
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // IMPLEMENTATIONS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+impl LongstaffSchwartz {
+    /// New engine regressing on the given basis.
+    pub fn new(basis: PolynomialBasis) -> Self {
+        Self { basis }
+    }
+
+    /// Price an option with the given exercise payoff on simulated paths of
+    /// the underlying, discounting at the continuously compounded `rate`.
+    ///
+    /// Dates where too few paths are in the money to fit the basis are
+    /// skipped (no exercise).
+    pub fn price<F>(
+        &self,
+        trajectories: &Trajectories,
+        payoff: F,
+        rate: f64,
+    ) -> LongstaffSchwartzResult
+    where
+        F: Fn(f64) -> f64,
+    {
+        let times = &trajectories.times;
+        let n = trajectories.n_steps();
+        let m = trajectories.n_paths();
+        let t_0 = times[0];
+
+        // Cash flows discounted to the first time point, and exercise dates.
+        let mut cash_flows: Vec<f64> = trajectories
+            .terminal_values()
+            .iter()
+            .map(|&s| payoff(s) * (-rate * (times[n] - t_0)).exp())
+            .collect();
+        let mut exercised_at = vec![n; m];
+        let mut exercise_regions = vec![None; n + 1];
+        exercise_regions[n] = region(
+            trajectories
+                .terminal_values()
+                .iter()
+                .copied()
+                .filter(|&s| payoff(s) > 0.0),
+        );
+
+        for k in (1..n).rev() {
+            let values = trajectories.values_at(k);
+            let growth = (rate * (times[k] - t_0)).exp();
+
+            let in_the_money: Vec<usize> = (0..m).filter(|&i| payoff(values[i]) > 0.0).collect();
+            let xs: Vec<f64> = in_the_money.iter().map(|&i| values[i]).collect();
+            let ys: Vec<f64> = in_the_money
+                .iter()
+                .map(|&i| cash_flows[i] * growth)
+                .collect();
+
+            let Ok(fit) = self.basis.fit(&xs, &ys) else {
+                continue;
+            };
+
+            let mut exercised = Vec::new();
+
+            for (&i, continuation) in in_the_money.iter().zip(fit.evaluate_many(&xs)) {
+                let exercise = payoff(values[i]);
+                if exercise > continuation {
+                    cash_flows[i] = exercise / growth;
+                    exercised_at[i] = k;
+                    exercised.push(values[i]);
+                }
+            }
+
+            exercise_regions[k] = region(exercised.into_iter());
+        }
+
+        let mean = cash_flows.iter().sum::<f64>() / m as f64;
+        let variance =
+            cash_flows.iter().map(|c| (c - mean).powi(2)).sum::<f64>() / (m as f64 - 1.0);
+
+        let mut exercise_probabilities = vec![0.0; n + 1];
+        for (&k, &c) in exercised_at.iter().zip(&cash_flows) {
+            if c > 0.0 {
+                exercise_probabilities[k] += 1.0 / m as f64;
+            }
+        }
+
+        // Immediate exercise at the first time point.
+        let intrinsic = payoff(trajectories.values_at(0)[0]);
+
+        if intrinsic > mean {
+            exercise_probabilities = vec![0.0; n + 1];
+            exercise_probabilities[0] = 1.0;
+            exercise_regions[0] =
+                Some((trajectories.values_at(0)[0], trajectories.values_at(0)[0]));
+
+            return LongstaffSchwartzResult {
+                price: intrinsic,
+                standard_error: 0.0,
+                exercise_probabilities,
+                exercise_regions,
+            };
+        }
+
+        LongstaffSchwartzResult {
+            price: mean,
+            standard_error: (variance / m as f64).sqrt(),
+            exercise_probabilities,
+            exercise_regions,
+        }
+    }
+}
+
+impl LongstaffSchwartzResult {
+    /// Early-exercise boundary at the simulated `times`: the highest
+    /// underlying value exercised at each time for puts, the lowest for
+    /// calls.
+    ///
+    /// The regression's exercise decisions are noisy near the boundary, so
+    /// it is an estimate; it improves with more paths.
+    pub fn exercise_boundary(&self, times: &[f64], option_type: TypeFlag) -> ExerciseBoundary {
+        let critical_prices = self
+            .exercise_regions
+            .iter()
+            .map(|region| match (option_type, region) {
+                (TypeFlag::Call, Some((low, _))) => *low,
+                (TypeFlag::Put, Some((_, high))) => *high,
+                (TypeFlag::Call, None) => f64::INFINITY,
+                (TypeFlag::Put, None) => 0.0,
+            })
+            .collect();
+
+        ExerciseBoundary::new(option_type, times.to_vec(), critical_prices)
+    }
+}
+
+impl AmericanApproximation {
+    /// American price of the option with the given inputs.
+    pub fn price(&self, inputs: &BlackScholesInputs) -> AmericanApproximationResult {
+        let (S, K, T, r, b, v) = unpack(inputs);
+
+        let (price, critical_price) = match (self, inputs.option_type) {
+            (Self::BaroneAdesiWhaley, TypeFlag::Call) => barone_adesi_whaley_call(S, K, T, r, b, v),
+            (Self::BaroneAdesiWhaley, TypeFlag::Put) => barone_adesi_whaley_put(S, K, T, r, b, v),
+            (Self::BjerksundStensland, TypeFlag::Call) => {
+                bjerksund_stensland_call(S, K, T, r, b, v)
+            }
+            (Self::BjerksundStensland, TypeFlag::Put) => {
+                let (price, trigger) = bjerksund_stensland_call(K, S, T, r - b, -b, v);
+                (price, K * S / trigger)
+            }
+        };
+        let european_price = inputs.price();
+
+        AmericanApproximationResult {
+            price: price.max(european_price),
+            european_price,
+            critical_price,
+        }
+    }
+
+    /// Early-exercise boundary: the critical price at `n_points` equally
+    /// spaced times from the valuation date (time 0) towards expiry.
+    pub fn exercise_boundary(
+        &self,
+        inputs: &BlackScholesInputs,
+        n_points: usize,
+    ) -> ExerciseBoundary {
+        let T = inputs.time_to_expiry;
+        let times = (0..n_points)
+            .map(|k| T * k as f64 / n_points as f64)
+            .collect::<Vec<_>>();
+
+        let critical_prices = times
+            .iter()
+            .map(|t| {
+                let remaining = BlackScholesInputs {
+                    time_to_expiry: T - t,
+                    ..*inputs
+                };
+
+                self.price(&remaining).critical_price
+            })
+            .collect();
+
+        ExerciseBoundary::new(inputs.option_type, times, critical_prices)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+const MAX_ITERATIONS: usize = 100;
+const TOLERANCE: f64 = 1e-10;
+
+// Lowest and highest of some underlying values, if there are any.
+fn region(values: impl Iterator<Item = f64>) -> Option<(f64, f64)> {
+    values.fold(None, |region, value| match region {
+        None => Some((value, value)),
+        Some((low, high)) => Some((f64::min(low, value), f64::max(high, value))),
+    })
+}
+
+fn unpack(inputs: &BlackScholesInputs) -> (f64, f64, f64, f64, f64, f64) {
+    (
+        inputs.underlying_price,
+        inputs.strike_price,
+        inputs.time_to_expiry,
+        inputs.risk_free_rate,
+        inputs.cost_of_carry,
+        inputs.volatility,
+    )
+}
+
+fn european(S: f64, K: f64, T: f64, r: f64, b: f64, v: f64, option_type: TypeFlag) -> f64 {
+    BlackScholesInputs {
+        underlying_price: S,
+        strike_price: K,
+        volatility: v,
+        risk_free_rate: r,
+        cost_of_carry: b,
+        time_to_expiry: T,
+        option_type,
+    }
+    .price()
+}
+
+fn d1(S: f64, K: f64, T: f64, b: f64, v: f64) -> f64 {
+    ((S / K).ln() + (b + 0.5 * v * v) * T) / (v * T.sqrt())
+}
+
+// Barone-Adesi-Whaley call and its critical price, found by Newton's
+// method from the Haug (2007) seed value.
+fn barone_adesi_whaley_call(S: f64, K: f64, T: f64, r: f64, b: f64, v: f64) -> (f64, f64) {
+    if b >= r {
+        return (european(S, K, T, r, b, v, TypeFlag::Call), f64::INFINITY);
+    }
+
+    let N = Gaussian::default();
+    let carry = ((b - r) * T).exp();
+    let n = 2.0 * b / (v * v);
+    let k = 2.0 * r / (v * v * (1.0 - (-r * T).exp()));
+    let q2 = 0.5 * (1.0 - n + ((n - 1.0).powi(2) + 4.0 * k).sqrt());
+
+    // Seed from the perpetual option's critical price.
+    let q2_inf = 0.5 * (1.0 - n + ((n - 1.0).powi(2) + 8.0 * r / (v * v)).sqrt());
+    let s_inf = K / (1.0 - 1.0 / q2_inf);
+    let h2 = -(b * T + 2.0 * v * T.sqrt()) * K / (s_inf - K);
+    let mut critical = K + (s_inf - K) * (1.0 - h2.exp());
+
+    for _ in 0..MAX_ITERATIONS {
+        let d1 = d1(critical, K, T, b, v);
+        let lhs = critical - K;
+        let rhs = european(critical, K, T, r, b, v, TypeFlag::Call)
+            + (1.0 - carry * N.cdf(d1)) * critical / q2;
+
+        if ((lhs - rhs) / K).abs() < TOLERANCE {
+            break;
+        }
+
+        let slope =
+            carry * N.cdf(d1) * (1.0 - 1.0 / q2) + (1.0 - carry * N.pdf(d1) / (v * T.sqrt())) / q2;
+        critical = (K + rhs - slope * critical) / (1.0 - slope);
+    }
+
+    if S >= critical {
+        return (S - K, critical);
+    }
+
+    let A2 = critical / q2 * (1.0 - carry * N.cdf(d1(critical, K, T, b, v)));
+
+    (
+        european(S, K, T, r, b, v, TypeFlag::Call) + A2 * (S / critical).powf(q2),
+        critical,
+    )
+}
+
+// Barone-Adesi-Whaley put and its critical price.
+fn barone_adesi_whaley_put(S: f64, K: f64, T: f64, r: f64, b: f64, v: f64) -> (f64, f64) {
+    if r <= 0.0 {
+        return (european(S, K, T, r, b, v, TypeFlag::Put), 0.0);
+    }
+
+    let N = Gaussian::default();
+    let carry = ((b - r) * T).exp();
+    let n = 2.0 * b / (v * v);
+    let k = 2.0 * r / (v * v * (1.0 - (-r * T).exp()));
+    let q1 = 0.5 * (1.0 - n - ((n - 1.0).powi(2) + 4.0 * k).sqrt());
+
+    let q1_inf = 0.5 * (1.0 - n - ((n - 1.0).powi(2) + 8.0 * r / (v * v)).sqrt());
+    let s_inf = K / (1.0 - 1.0 / q1_inf);
+    let h1 = (b * T - 2.0 * v * T.sqrt()) * K / (K - s_inf);
+    let mut critical = s_inf + (K - s_inf) * h1.exp();
+
+    for _ in 0..MAX_ITERATIONS {
+        let d1 = d1(critical, K, T, b, v);
+        let lhs = K - critical;
+        let rhs = european(critical, K, T, r, b, v, TypeFlag::Put)
+            - (1.0 - carry * N.cdf(-d1)) * critical / q1;
+
+        if ((lhs - rhs) / K).abs() < TOLERANCE {
+            break;
+        }
+
+        let slope = -carry * N.cdf(-d1) * (1.0 - 1.0 / q1)
+            - (1.0 + carry * N.pdf(-d1) / (v * T.sqrt())) / q1;
+        critical = (K - rhs + slope * critical) / (1.0 + slope);
+    }
+
+    if S <= critical {
+        return (K - S, critical);
+    }
+
+    let A1 = -critical / q1 * (1.0 - carry * N.cdf(-d1(critical, K, T, b, v)));
+
+    (
+        european(S, K, T, r, b, v, TypeFlag::Put) + A1 * (S / critical).powf(q1),
+        critical,
+    )
+}
+
+// Bjerksund-Stensland (1993) call and its (flat) exercise trigger price.
+fn bjerksund_stensland_call(S: f64, K: f64, T: f64, r: f64, b: f64, v: f64) -> (f64, f64) {
+    if b >= r {
+        return (european(S, K, T, r, b, v, TypeFlag::Call), f64::INFINITY);
+    }
+
+    let v2 = v * v;
+    let beta = (0.5 - b / v2) + ((b / v2 - 0.5).powi(2) + 2.0 * r / v2).sqrt();
+    let b_inf = beta / (beta - 1.0) * K;
+    let b_0 = K.max(r / (r - b) * K);
+    let h = -(b * T + 2.0 * v * T.sqrt()) * b_0 / (b_inf - b_0);
+    let trigger = b_0 + (b_inf - b_0) * (1.0 - h.exp());
+
+    if S >= trigger {
+        return (S - K, trigger);
+    }
+
+    let alpha = (trigger - K) * trigger.powf(-beta);
+    let phi = |gamma: f64, H: f64| {
+        let N = Gaussian::default();
+        let lambda = (-r + gamma * b + 0.5 * gamma * (gamma - 1.0) * v2) * T;
+        let d = -((S / H).ln() + (b + (gamma - 0.5) * v2) * T) / (v * T.sqrt());
+        let kappa = 2.0 * b / v2 + 2.0 * gamma - 1.0;
+        let reflection = 2.0 * (trigger / S).ln() / (v * T.sqrt());
+
+        lambda.exp()
+            * S.powf(gamma)
+            * (N.cdf(d) - (trigger / S).powf(kappa) * N.cdf(d - reflection))
+    };
+
+    let price = alpha * S.powf(beta) - alpha * phi(beta, trigger) + phi(1.0, trigger)
+        - phi(1.0, K)
+        - K * phi(0.0, trigger)
+        + K * phi(0.0, K);
+
+    (price, trigger)
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // TESTS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_american {
+    use super::*;
+    use crate::math::polynomials::PolynomialFamily;
+    use crate::stochastics::{GeometricBrownianMotion, SimulationConfig, StochasticProcess};
+
+    #[test]
+    fn test_longstaff_schwartz_put() {
+        // Longstaff and Schwartz (2001), Table 1: S = 36, K = 40, r = 6%,
+        // sigma = 20%, T = 1 with 50 exercise dates. Finite difference
+        // value 4.478, European value 3.844.
+        let (strike, rate) = (40.0, 0.06);
+
+        let config = SimulationConfig::new(true).with_seed(7);
+        let gbm = GeometricBrownianMotion::new(rate, 0.2);
+        let paths = gbm.simulate_with_config(36.0, 0.0, 1.0, 50, 20000, &config);
+
+        let basis = PolynomialBasis::new(PolynomialFamily::Laguerre, 3).with_scaling(0.0, strike);
+        let result = LongstaffSchwartz::new(basis).price(&paths, |s| (strike - s).max(0.0), rate);
+
+        assert!((result.price - 4.478).abs() < 4.0 * result.standard_error + 0.03);
+        assert!(result.price > 3.844 + 0.4);
+
+        let exercised: f64 = result.exercise_probabilities.iter().sum();
+        assert!(exercised > 0.0 && exercised <= 1.0 + 1e-12);
+        assert!(result.exercise_probabilities[1..50]
+            .iter()
+            .any(|&p| p > 0.0));
+    }
+
+    #[test]
+    fn test_longstaff_schwartz_deep_in_the_money() {
+        let config = SimulationConfig::new(false).with_seed(1);
+        let gbm = GeometricBrownianMotion::new(0.1, 0.1);
+        let paths = gbm.simulate_with_config(10.0, 0.0, 1.0, 10, 2000, &config);
+
+        let basis = PolynomialBasis::new(PolynomialFamily::Laguerre, 2).with_scaling(0.0, 100.0);
+        let result = LongstaffSchwartz::new(basis).price(&paths, |s| (100.0 - s).max(0.0), 0.1);
+
+        assert_eq!(result.price, 90.0);
+        assert_eq!(result.exercise_probabilities[0], 1.0);
+    }
+
+    fn inputs(S: f64, option_type: TypeFlag) -> BlackScholesInputs {
+        BlackScholesInputs {
+            underlying_price: S,
+            strike_price: 100.0,
+            volatility: 0.2,
+            risk_free_rate: 0.08,
+            cost_of_carry: -0.04,
+            time_to_expiry: 0.25,
+            option_type,
+        }
+    }
+
+    #[test]
+    fn test_barone_adesi_whaley() {
+        // Barone-Adesi and Whaley (1987), Table I: r = 8%, b = -4%,
+        // sigma = 20%, T = 0.25, K = 100.
+        for (S, expected) in [
+            (80.0, 0.03),
+            (90.0, 0.59),
+            (100.0, 3.52),
+            (110.0, 10.31),
+            (120.0, 20.00),
+        ] {
+            let result = AmericanApproximation::BaroneAdesiWhaley.price(&inputs(S, TypeFlag::Call));
+
+            assert_approx_equal!(result.price, expected, 0.005);
+            assert!(result.price >= result.european_price);
+        }
+    }
+
+    #[test]
+    fn test_american_approximations_against_tree() {
+        // 4000-step Cox-Ross-Rubinstein tree values; both approximations
+        // are within five cents.
+        let calls = [(90.0, 0.5803), (100.0, 3.5247), (110.0, 10.3566)];
+        let puts = [(90.0, 11.2498), (100.0, 4.3962), (110.0, 1.1177)];
+
+        for method in [
+            AmericanApproximation::BaroneAdesiWhaley,
+            AmericanApproximation::BjerksundStensland,
+        ] {
+            for (S, tree) in calls {
+                assert_approx_equal!(method.price(&inputs(S, TypeFlag::Call)).price, tree, 0.05);
+            }
+            for (S, tree) in puts {
+                assert_approx_equal!(method.price(&inputs(S, TypeFlag::Put)).price, tree, 0.05);
+            }
+        }
+
+        // The Longstaff-Schwartz put above, without dividends.
+        let put = BlackScholesInputs {
+            underlying_price: 36.0,
+            strike_price: 40.0,
+            volatility: 0.2,
+            risk_free_rate: 0.06,
+            cost_of_carry: 0.06,
+            time_to_expiry: 1.0,
+            option_type: TypeFlag::Put,
+        };
+
+        for method in [
+            AmericanApproximation::BaroneAdesiWhaley,
+            AmericanApproximation::BjerksundStensland,
+        ] {
+            let result = method.price(&put);
+
+            assert_approx_equal!(result.price, 4.478, 0.03);
+            assert!(result.critical_price < 36.0);
+        }
+    }
+
+    #[test]
+    fn test_american_approximations_exercise() {
+        for method in [
+            AmericanApproximation::BaroneAdesiWhaley,
+            AmericanApproximation::BjerksundStensland,
+        ] {
+            // Beyond the critical price the option is worth its intrinsic value.
+            let call = method.price(&inputs(100.0, TypeFlag::Call));
+            let deep = method.price(&inputs(call.critical_price + 1.0, TypeFlag::Call));
+            assert_approx_equal!(deep.price, call.critical_price + 1.0 - 100.0, 1e-9);
+
+            // Without dividends, American calls are never exercised early.
+            let no_dividends = BlackScholesInputs {
+                cost_of_carry: 0.08,
+                ..inputs(100.0, TypeFlag::Call)
+            };
+            let result = method.price(&no_dividends);
+            assert_eq!(result.price, result.european_price);
+            assert_eq!(result.critical_price, f64::INFINITY);
+        }
+    }
+
+    #[test]
+    fn test_exercise_boundary() {
+        let put = BlackScholesInputs {
+            cost_of_carry: 0.08,
+            ..inputs(100.0, TypeFlag::Put)
+        };
+
+        for method in [
+            AmericanApproximation::BaroneAdesiWhaley,
+            AmericanApproximation::BjerksundStensland,
+        ] {
+            let call = method.exercise_boundary(&inputs(100.0, TypeFlag::Call), 10);
+            let put = method.exercise_boundary(&put, 10);
+
+            assert_eq!(call.times.len(), 10);
+            assert_eq!(call.times[0], 0.0);
+            assert_approx_equal!(call.times[9], 0.225, 1e-12);
+
+            // The call boundary falls and the put boundary rises towards
+            // the strike as expiry approaches.
+            let call = &call.critical_prices;
+            let put = &put.critical_prices;
+            assert!(call.windows(2).all(|w| w[1] < w[0] && w[1] > 100.0));
+            assert!(put.windows(2).all(|w| w[1] > w[0] && w[1] < 100.0));
+        }
+    }
+}
@@ -11,7 +11,7 @@ use time::OffsetDateTime;
 
 use crate::{
     statistics::distributions::{gaussian::*, Distribution},
-    time::{DayCountConvention, DayCounter},
+    time::{DayCount, DayCountConvention},
 };
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -102,12 +102,12 @@ impl AsianOption {
 
         // Compute time to maturity.
         let T = match self.valuation_date {
-            Some(valuation_date) => DayCounter::day_count_factor(
+            Some(valuation_date) => DayCount::day_count_factor(
                 valuation_date,
                 self.expiry_date,
                 &DayCountConvention::Actual365,
             ),
-            None => DayCounter::day_count_factor(
+            None => DayCount::day_count_factor(
                 OffsetDateTime::now_utc(),
                 self.expiry_date,
                 &DayCountConvention::Actual365,
@@ -139,7 +139,6 @@ mod tests {
     use time::Duration;
 
     use super::*;
-    use crate::assert_approx_equal;
 
     #[test]
     fn test_asian_geometric() {
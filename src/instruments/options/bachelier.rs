@@ -13,7 +13,7 @@
 
 use crate::instruments::options::TypeFlag;
 use crate::statistics::distributions::{Distribution, Gaussian};
-use crate::time::{DayCountConvention, DayCounter};
+use crate::time::{DayCount, DayCountConvention};
 
 use time::OffsetDateTime;
 
@@ -92,7 +92,7 @@ impl Bachelier {
         let v = self.volatility;
 
         // Compute time to maturity.
-        let T = DayCounter::day_count_factor(
+        let T = DayCount::day_count_factor(
             self.evaluation_date.unwrap_or(OffsetDateTime::now_utc()),
             self.expiration_date,
             &DayCountConvention::Actual365,
@@ -111,6 +111,7 @@ impl Bachelier {
 
 impl ModifiedBachelier {
     /// New Modified Bachelier European Option
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         underlying_price: f64,
         strike_price: f64,
@@ -141,7 +142,7 @@ impl ModifiedBachelier {
         let r = self.risk_free_rate;
 
         // Compute time to maturity.
-        let T = DayCounter::day_count_factor(
+        let T = DayCount::day_count_factor(
             self.evaluation_date.unwrap_or(OffsetDateTime::now_utc()),
             self.expiration_date,
             &DayCountConvention::Actual365,
@@ -165,7 +166,6 @@ impl ModifiedBachelier {
 #[cfg(test)]
 mod tests_bachelier {
     use super::*;
-    use crate::assert_approx_equal;
     use time::Duration;
 
     #[test]
@@ -7,7 +7,9 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+use crate::error::{ensure, expect_valid, RustQuantError};
 use crate::statistics::distributions::{gaussian::*, Distribution};
+use crate::stochastics::{BarrierDirection, ContinuityCorrection};
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // BARRIER OPTION STRUCT
@@ -78,7 +80,19 @@ impl BarrierOption {
     ///
     /// # Note:
     /// * `b = r - q` - The cost of carry.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the barrier has already been touched (see
+    /// [`BarrierOption::try_price`]).
     pub fn price(&self, type_flag: BarrierType) -> f64 {
+        expect_valid(self.try_price(type_flag))
+    }
+
+    /// Closed-form barrier option price, as [`BarrierOption::price`], or an
+    /// error if the initial price is already past the barrier (e.g. an
+    /// up-and-out call with `S > H`).
+    pub fn try_price(&self, type_flag: BarrierType) -> Result<f64, RustQuantError> {
         let S = self.initial_price;
         let X = self.strike_price;
         let H = self.barrier;
@@ -154,8 +168,11 @@ impl BarrierOption {
             K * (term1 + term2)
         };
 
+        let touched =
+            || RustQuantError::condition_violated("Barrier touched - check barrier and type flag.");
+
         // Strike above barrier (X >= H):
-        if X >= H {
+        let price = if X >= H {
             match type_flag {
                 // Knock-In calls:
                 BarrierType::CDI if S >= H => C(1., 1.) + E(1.),
@@ -170,7 +187,7 @@ impl BarrierOption {
                 BarrierType::PDO if S >= H => A(-1.) - B(-1.) + C(-1., 1.) - D(-1., 1.) + F(1.),
                 BarrierType::PUO if S <= H => B(-1.) - D(-1., -1.) + F(-1.),
 
-                _ => panic!("Barrier touched - check barrier and type flag."),
+                _ => return Err(touched()),
             }
         }
         // Strike below barrier (X < H):
@@ -189,9 +206,61 @@ impl BarrierOption {
                 BarrierType::PDO if S >= H => F(1.),
                 BarrierType::PUO if S <= H => A(-1.) - C(-1., -1.) + F(-1.),
 
-                _ => panic!("Barrier touched - check barrier and type flag."),
+                _ => return Err(touched()),
             }
+        };
+
+        Ok(price)
+    }
+
+    /// Price of a barrier option whose barrier is only observed at
+    /// `n_observations` equally spaced times, using the closed form with
+    /// the Broadie-Glasserman-Kou continuity correction.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the barrier has already been touched or there are no
+    /// observations (see [`BarrierOption::try_price_discrete`]).
+    pub fn price_discrete(&self, type_flag: BarrierType, n_observations: usize) -> f64 {
+        expect_valid(self.try_price_discrete(type_flag, n_observations))
+    }
+
+    /// Discretely monitored barrier option price, as
+    /// [`BarrierOption::price_discrete`], or an error if there are no
+    /// observations or the initial price is already past the barrier.
+    pub fn try_price_discrete(
+        &self,
+        type_flag: BarrierType,
+        n_observations: usize,
+    ) -> Result<f64, RustQuantError> {
+        ensure(n_observations > 0, "At least one observation is needed.")?;
+
+        let direction = match type_flag {
+            BarrierType::CUI | BarrierType::CUO | BarrierType::PUI | BarrierType::PUO => {
+                BarrierDirection::Up
+            }
+            _ => BarrierDirection::Down,
+        };
+
+        if direction.is_breached(self.initial_price, self.barrier)
+            && self.initial_price != self.barrier
+        {
+            return Err(RustQuantError::condition_violated(
+                "Barrier touched - check barrier and type flag.",
+            ));
+        }
+
+        let correction = ContinuityCorrection::from_observations(
+            self.volatility,
+            self.time_to_expiry,
+            n_observations,
+        );
+
+        Self {
+            barrier: correction.discrete_to_continuous(self.barrier, direction),
+            ..*self
         }
+        .try_price(type_flag)
     }
 }
 
@@ -202,7 +271,6 @@ impl BarrierOption {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::assert_approx_equal;
 
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Initial underlying price ABOVE the barrier.
@@ -236,6 +304,32 @@ mod tests {
         assert_approx_equal!(pdo, 0.000000, 0.000001);
     }
 
+    #[test]
+    fn test_discrete_monitoring() {
+        // Fewer observations knock out less often, and many observations
+        // approach continuous monitoring.
+        let continuous = S_ABOVE_H.price(BarrierType::CDO);
+        let monthly = S_ABOVE_H.price_discrete(BarrierType::CDO, 12);
+        let daily = S_ABOVE_H.price_discrete(BarrierType::CDO, 252);
+
+        assert!(monthly > daily && daily > continuous);
+        assert_approx_equal!(
+            S_ABOVE_H.price_discrete(BarrierType::CDO, 100_000_000),
+            continuous,
+            1e-2
+        );
+
+        // In-out parity holds for any monitoring frequency.
+        assert_approx_equal!(
+            monthly + S_ABOVE_H.price_discrete(BarrierType::CDI, 12),
+            continuous + S_ABOVE_H.price(BarrierType::CDI),
+            1e-9
+        );
+
+        assert!(S_ABOVE_H.try_price_discrete(BarrierType::CUO, 12).is_err());
+        assert!(S_ABOVE_H.try_price_discrete(BarrierType::CDO, 0).is_err());
+    }
+
     #[test]
     #[should_panic(expected = "Barrier touched - check barrier and type flag.")]
     fn cui_panic() {
@@ -257,6 +351,18 @@ mod tests {
         S_ABOVE_H.price(BarrierType::PUO);
     }
 
+    #[test]
+    fn test_try_price_touched() {
+        assert!(matches!(
+            S_ABOVE_H.try_price(BarrierType::CUO),
+            Err(RustQuantError::ConditionViolated { .. })
+        ));
+        assert_eq!(
+            S_ABOVE_H.try_price(BarrierType::CDO).ok(),
+            Some(S_ABOVE_H.price(BarrierType::CDO))
+        );
+    }
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Initial underlying price BELOW the barrier.
     //
@@ -117,7 +117,6 @@ impl CashOrNothingOption {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::assert_approx_equal;
 
     #[test]
     fn test_gap_option() {
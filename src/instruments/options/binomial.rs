@@ -11,7 +11,8 @@
 // BINOMIAL OPTION PRICING PARAMETER STRUCT
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-use super::{ExerciseFlag, TypeFlag};
+use super::{ExerciseBoundary, ExerciseFlag, TypeFlag};
+use crate::error::{ensure, expect_valid, RustQuantError};
 
 /// Struct containing the parameters to price an option via binomial tree method.
 #[derive(Debug, Clone, Copy)]
@@ -29,6 +30,25 @@ pub struct BinomialOption {
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 impl BinomialOption {
+    /// New option on an underlying with a continuous dividend yield.
+    pub fn new(
+        initial_price: f64,
+        strike_price: f64,
+        time_to_expiry: f64,
+        risk_free_rate: f64,
+        dividend_yield: f64,
+        volatility: f64,
+    ) -> Self {
+        Self {
+            initial_price,
+            strike_price,
+            time_to_expiry,
+            risk_free_rate,
+            dividend_yield,
+            volatility,
+        }
+    }
+
     /// Cox-Ross-Rubinstein binomial option pricing model.
     ///
     /// Adapted from Haug's *Complete Guide to Option Pricing Formulas*.
@@ -43,6 +63,11 @@ impl BinomialOption {
     /// # Note:
     ///
     /// * `b = r - q` - The cost of carry.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the inputs are rejected by
+    /// [`BinomialOption::try_price_CoxRossRubinstein`].
     pub fn price_CoxRossRubinstein(
         &self,
         output_flag: &str,
@@ -50,6 +75,35 @@ impl BinomialOption {
         call_put_flag: TypeFlag,
         n: usize,
     ) -> f64 {
+        expect_valid(self.try_price_CoxRossRubinstein(output_flag, ame_eur_flag, call_put_flag, n))
+    }
+
+    /// Cox-Ross-Rubinstein binomial option pricing model, as
+    /// [`BinomialOption::price_CoxRossRubinstein`], or an error if the
+    /// output flag is unknown, the option is Bermudan, the tree has fewer
+    /// than three steps, or the volatility or time to expiry is not
+    /// positive.
+    pub fn try_price_CoxRossRubinstein(
+        &self,
+        output_flag: &str,
+        ame_eur_flag: ExerciseFlag,
+        call_put_flag: TypeFlag,
+        n: usize,
+    ) -> Result<f64, RustQuantError> {
+        ensure(
+            matches!(output_flag, "p" | "d" | "g" | "t"),
+            "Check OutputFlag. Should be one of: 'p', 'd', 'g', 't'.",
+        )?;
+        ensure(
+            ame_eur_flag != ExerciseFlag::Bermudan,
+            "Bermudan option pricing not implemented yet.",
+        )?;
+        ensure(n >= 3, "The tree needs at least three steps.")?;
+        ensure(
+            self.volatility > 0.0 && self.time_to_expiry > 0.0,
+            "The volatility and time to expiry must be positive.",
+        )?;
+
         let S = self.initial_price;
         let K = self.strike_price;
         let T = self.time_to_expiry;
@@ -92,9 +146,7 @@ impl BinomialOption {
                         option_value[i] =
                             Df * (p * (option_value[i + 1]) + (1.0 - p) * option_value[i]);
                     }
-                    ExerciseFlag::Bermudan => {
-                        panic!("Bermudan option pricing not implemented yet.");
-                    }
+                    ExerciseFlag::Bermudan => unreachable!("Bermudan options are rejected above."),
                 }
             }
             if j == 2 {
@@ -114,17 +166,79 @@ impl BinomialOption {
 
         match output_flag {
             // Return the option value.
-            "p" => return_value[0],
+            "p" => Ok(return_value[0]),
             // Return the Delta.
-            "d" => return_value[1],
+            "d" => Ok(return_value[1]),
             // Return the Gamma.
-            "g" => return_value[2],
+            "g" => Ok(return_value[2]),
             // Return the Theta.
-            "t" => return_value[3],
+            "t" => Ok(return_value[3]),
             // Capture edge cases.
-            _ => panic!("Check OutputFlag. Should be one of: 'p', 'd', 'g', 't'."),
+            _ => unreachable!("The output flag is checked above."),
         }
     }
+    /// Early-exercise boundary of the American option from an `n`-step
+    /// Cox-Ross-Rubinstein tree: at each step, the highest node price at
+    /// which a put is exercised (zero if none) or the lowest at which a call
+    /// is (infinite if none). At expiry the boundary is the strike.
+    ///
+    /// Errors if the tree has no steps or the volatility or time to expiry
+    /// is not positive.
+    pub fn exercise_boundary(
+        &self,
+        call_put_flag: TypeFlag,
+        n: usize,
+    ) -> Result<ExerciseBoundary, RustQuantError> {
+        ensure(n >= 1, "The tree needs at least one step.")?;
+        ensure(
+            self.volatility > 0.0 && self.time_to_expiry > 0.0,
+            "The volatility and time to expiry must be positive.",
+        )?;
+
+        let S = self.initial_price;
+        let K = self.strike_price;
+        let dt = self.time_to_expiry / n as f64;
+        let u = (self.volatility * dt.sqrt()).exp();
+        let d = 1.0 / u;
+        let p = (((self.risk_free_rate - self.dividend_yield) * dt).exp() - d) / (u - d);
+        let Df = (-self.risk_free_rate * dt).exp();
+
+        let z = match call_put_flag {
+            TypeFlag::Call => 1.0,
+            TypeFlag::Put => -1.0,
+        };
+        let no_exercise = match call_put_flag {
+            TypeFlag::Call => f64::INFINITY,
+            TypeFlag::Put => 0.0,
+        };
+        let node = |j: usize, i: usize| S * u.powi(i as i32) * d.powi((j - i) as i32);
+
+        let mut option_value = (0..=n)
+            .map(|i| (z * (node(n, i) - K)).max(0.0))
+            .collect::<Vec<_>>();
+        let mut critical_prices = vec![no_exercise; n + 1];
+        critical_prices[n] = K;
+
+        for j in (0..n).rev() {
+            for i in 0..=j {
+                let price = node(j, i);
+                let intrinsic = z * (price - K);
+                let continuation = Df * (p * option_value[i + 1] + (1.0 - p) * option_value[i]);
+
+                if intrinsic > 0.0 && intrinsic >= continuation {
+                    critical_prices[j] = match call_put_flag {
+                        TypeFlag::Call => critical_prices[j].min(price),
+                        TypeFlag::Put => critical_prices[j].max(price),
+                    };
+                }
+                option_value[i] = intrinsic.max(continuation);
+            }
+        }
+
+        let times = (0..=n).map(|j| j as f64 * dt).collect();
+
+        Ok(ExerciseBoundary::new(call_put_flag, times, critical_prices))
+    }
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -133,10 +247,7 @@ impl BinomialOption {
 
 #[cfg(test)]
 mod tests_binomial {
-    use crate::{
-        assert_approx_equal,
-        instruments::{BinomialOption, ExerciseFlag, TypeFlag},
-    };
+    use crate::instruments::{BinomialOption, ExerciseFlag, TypeFlag};
 
     #[test]
     fn TEST_CRRBinomial() {
@@ -166,4 +277,28 @@ mod tests_binomial {
         // Very weak parity due to discrete time steps.
         assert_approx_equal!(parity, 0.0, 0.5);
     }
+
+    #[test]
+    fn test_crr_invalid_inputs() {
+        let option = BinomialOption {
+            initial_price: 100.0,
+            strike_price: 95.0,
+            time_to_expiry: 0.5,
+            risk_free_rate: 0.08,
+            dividend_yield: 0.0,
+            volatility: 0.3,
+        };
+
+        let price = |flag, exercise, n| {
+            option.try_price_CoxRossRubinstein(flag, exercise, TypeFlag::Call, n)
+        };
+
+        assert!(price("x", ExerciseFlag::European, 100).is_err());
+        assert!(price("p", ExerciseFlag::Bermudan, 100).is_err());
+        assert!(price("p", ExerciseFlag::European, 2).is_err());
+        assert_eq!(
+            price("d", ExerciseFlag::European, 100).ok(),
+            Some(option.price_CoxRossRubinstein("d", ExerciseFlag::European, TypeFlag::Call, 100))
+        );
+    }
 }
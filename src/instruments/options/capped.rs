@@ -0,0 +1,358 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! # Capped, Floored and Corridor Options
+//!
+//! The building blocks of structured coupons:
+//!
+//! - a capped (and/or floored) call or put pays its intrinsic value
+//!   limited to $[F, C]$, i.e. $\min(\max(\phi (S_T - K), F), C)$, which is
+//!   a call (or put) spread plus a zero-coupon bond;
+//! - a corridor (range digital) pays a fixed amount if the underlying ends
+//!   between two levels, $\mathbb{1}_{\{L \leq S_T \leq U\}}$.
+//!
+//! Both have closed forms under generalised Black-Scholes-Merton, and
+//! implement [`PathIndependentPayoff`] for Monte Carlo pricing under other
+//! models (see [`MonteCarloPricer`](crate::instruments::options::MonteCarloPricer)).
+
+use crate::instruments::options::{BlackScholesInputs, TypeFlag};
+use crate::instruments::PathIndependentPayoff;
+use crate::math::special::norm_cdf;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Call or put whose payoff is capped and/or floored.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CappedOption {
+    /// `S` - Initial price of the underlying.
+    pub initial_price: f64,
+    /// `K` - Strike price.
+    pub strike_price: f64,
+    /// `r` - Risk-free rate parameter.
+    pub risk_free_rate: f64,
+    /// `b` - Cost of carry.
+    pub cost_of_carry: f64,
+    /// `v` - Volatility parameter.
+    pub volatility: f64,
+    /// `T` - Time to expiry in years.
+    pub time_to_expiry: f64,
+    /// Call or put.
+    pub option_type: TypeFlag,
+
+    /// `C` - Largest payment, if any.
+    pub cap: Option<f64>,
+    /// `F` - Smallest payment (zero for a plain option).
+    pub floor: f64,
+}
+
+/// Corridor (range digital) option: pays `payout` at expiry if the
+/// underlying is between the lower and upper levels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CorridorOption {
+    /// `S` - Initial price of the underlying.
+    pub initial_price: f64,
+    /// `L` - Lower level of the corridor.
+    pub lower: f64,
+    /// `U` - Upper level of the corridor (may be infinite).
+    pub upper: f64,
+    /// `r` - Risk-free rate parameter.
+    pub risk_free_rate: f64,
+    /// `b` - Cost of carry.
+    pub cost_of_carry: f64,
+    /// `v` - Volatility parameter.
+    pub volatility: f64,
+    /// `T` - Time to expiry in years.
+    pub time_to_expiry: f64,
+
+    /// Cash amount paid inside the corridor.
+    pub payout: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl CappedOption {
+    /// New option, neither capped nor floored.
+    pub fn new(
+        initial_price: f64,
+        strike_price: f64,
+        risk_free_rate: f64,
+        cost_of_carry: f64,
+        volatility: f64,
+        time_to_expiry: f64,
+        option_type: TypeFlag,
+    ) -> Self {
+        Self {
+            initial_price,
+            strike_price,
+            risk_free_rate,
+            cost_of_carry,
+            volatility,
+            time_to_expiry,
+            option_type,
+            cap: None,
+            floor: 0.0,
+        }
+    }
+
+    /// Limits the payment to `cap`.
+    pub fn with_cap(mut self, cap: f64) -> Self {
+        self.cap = Some(cap);
+        self
+    }
+
+    /// Guarantees a payment of at least `floor`.
+    pub fn with_floor(mut self, floor: f64) -> Self {
+        self.floor = floor;
+        self
+    }
+
+    /// Closed-form price: the discounted floor, plus an option struck
+    /// beyond the floor, less one struck at the cap.
+    pub fn price(&self) -> f64 {
+        let w = self.option_type as i32 as f64;
+        let K = self.strike_price;
+
+        let floor = self.floor * (-self.risk_free_rate * self.time_to_expiry).exp();
+        let above_floor = self.vanilla(K + w * self.floor);
+        let above_cap = self.cap.map_or(0.0, |cap| self.vanilla(K + w * cap));
+
+        floor + above_floor - above_cap
+    }
+
+    /// Generalised Black-Scholes-Merton price with another strike, allowing
+    /// a non-positive strike (a forward for a call, worthless for a put).
+    fn vanilla(&self, strike: f64) -> f64 {
+        let (S, r, b, T) = (
+            self.initial_price,
+            self.risk_free_rate,
+            self.cost_of_carry,
+            self.time_to_expiry,
+        );
+
+        if strike <= 0.0 {
+            return match self.option_type {
+                TypeFlag::Call => S * ((b - r) * T).exp() - strike * (-r * T).exp(),
+                TypeFlag::Put => 0.0,
+            };
+        }
+
+        BlackScholesInputs {
+            underlying_price: S,
+            strike_price: strike,
+            volatility: self.volatility,
+            risk_free_rate: r,
+            cost_of_carry: b,
+            time_to_expiry: T,
+            option_type: self.option_type,
+        }
+        .price()
+    }
+}
+
+impl PathIndependentPayoff for CappedOption {
+    fn payoff(&self, underlying: f64) -> f64 {
+        let intrinsic = match self.option_type {
+            TypeFlag::Call => underlying - self.strike_price,
+            TypeFlag::Put => self.strike_price - underlying,
+        };
+        let payment = intrinsic.max(self.floor);
+
+        self.cap.map_or(payment, |cap| payment.min(cap))
+    }
+}
+
+impl CorridorOption {
+    /// New corridor paying one unit of cash.
+    pub fn new(
+        initial_price: f64,
+        lower: f64,
+        upper: f64,
+        risk_free_rate: f64,
+        cost_of_carry: f64,
+        volatility: f64,
+        time_to_expiry: f64,
+    ) -> Self {
+        Self {
+            initial_price,
+            lower,
+            upper,
+            risk_free_rate,
+            cost_of_carry,
+            volatility,
+            time_to_expiry,
+            payout: 1.0,
+        }
+    }
+
+    /// Sets the cash amount paid inside the corridor.
+    pub fn with_payout(mut self, payout: f64) -> Self {
+        self.payout = payout;
+        self
+    }
+
+    /// Closed-form price, $P e^{-rT} [N(d_2(L)) - N(d_2(U))]$.
+    pub fn price(&self) -> f64 {
+        let (S, r, b, v, T) = (
+            self.initial_price,
+            self.risk_free_rate,
+            self.cost_of_carry,
+            self.volatility,
+            self.time_to_expiry,
+        );
+
+        // Risk-neutral probability of ending above `level`.
+        let above = |level: f64| match level {
+            l if l <= 0.0 => 1.0,
+            l if l.is_infinite() => 0.0,
+            l => norm_cdf(((S / l).ln() + (b - 0.5 * v * v) * T) / (v * T.sqrt())),
+        };
+
+        let probability = (above(self.lower) - above(self.upper)).max(0.0);
+
+        self.payout * (-r * T).exp() * probability
+    }
+}
+
+impl PathIndependentPayoff for CorridorOption {
+    fn payoff(&self, underlying: f64) -> f64 {
+        match (self.lower..=self.upper).contains(&underlying) {
+            true => self.payout,
+            false => 0.0,
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_capped {
+    use super::*;
+    use crate::instruments::options::MonteCarloPricer;
+    use crate::stochastics::{GeometricBrownianMotion, SimulationConfig};
+
+    fn call() -> CappedOption {
+        CappedOption::new(100.0, 95.0, 0.05, 0.03, 0.25, 1.0, TypeFlag::Call)
+    }
+
+    fn vanilla(strike: f64, option_type: TypeFlag) -> f64 {
+        BlackScholesInputs {
+            underlying_price: 100.0,
+            strike_price: strike,
+            volatility: 0.25,
+            risk_free_rate: 0.05,
+            cost_of_carry: 0.03,
+            time_to_expiry: 1.0,
+            option_type,
+        }
+        .price()
+    }
+
+    #[test]
+    fn test_capped_call_is_call_spread() {
+        assert_approx_equal!(call().price(), vanilla(95.0, TypeFlag::Call), 1e-12);
+        assert_approx_equal!(
+            call().with_cap(15.0).price(),
+            vanilla(95.0, TypeFlag::Call) - vanilla(110.0, TypeFlag::Call),
+            1e-12
+        );
+
+        let put = CappedOption {
+            option_type: TypeFlag::Put,
+            ..call()
+        }
+        .with_cap(15.0);
+        assert_approx_equal!(
+            put.price(),
+            vanilla(95.0, TypeFlag::Put) - vanilla(80.0, TypeFlag::Put),
+            1e-12
+        );
+    }
+
+    #[test]
+    fn test_cap_and_floor_bounds() {
+        let df = (-0.05_f64).exp();
+
+        // A collar between 2 and 10 is worth between their present values,
+        // and a cap equal to the floor fixes the payment.
+        let collared = call().with_floor(2.0).with_cap(10.0);
+        assert!(collared.price() > 2.0 * df && collared.price() < 10.0 * df);
+        assert_approx_equal!(
+            call().with_floor(5.0).with_cap(5.0).price(),
+            5.0 * df,
+            1e-12
+        );
+
+        // A floor above the strike distance of a put makes the strike
+        // negative: the put is then worth its floor.
+        let put = CappedOption {
+            option_type: TypeFlag::Put,
+            ..call()
+        }
+        .with_floor(100.0);
+        assert_approx_equal!(put.price(), 100.0 * df, 1e-12);
+
+        assert_eq!(collared.payoff(200.0), 10.0);
+        assert_eq!(collared.payoff(50.0), 2.0);
+        assert_eq!(collared.payoff(100.0), 5.0);
+    }
+
+    #[test]
+    fn test_corridor() {
+        let corridor =
+            CorridorOption::new(100.0, 90.0, 110.0, 0.05, 0.03, 0.25, 1.0).with_payout(10.0);
+
+        // A corridor is a difference of cash-or-nothing calls, which is the
+        // limit of a tight call spread.
+        let h = 1e-4;
+        let digital =
+            |k: f64| (vanilla(k - h, TypeFlag::Call) - vanilla(k + h, TypeFlag::Call)) / (2.0 * h);
+        assert_approx_equal!(
+            corridor.price(),
+            10.0 * (digital(90.0) - digital(110.0)),
+            1e-5
+        );
+
+        // An unbounded corridor is a zero-coupon bond.
+        let everywhere = CorridorOption::new(100.0, 0.0, f64::INFINITY, 0.05, 0.03, 0.25, 1.0);
+        assert_approx_equal!(everywhere.price(), (-0.05_f64).exp(), 1e-12);
+
+        assert_eq!(corridor.payoff(100.0), 10.0);
+        assert_eq!(corridor.payoff(120.0), 0.0);
+    }
+
+    #[test]
+    fn test_monte_carlo() {
+        // Risk-neutral drift r - q = b.
+        let gbm = GeometricBrownianMotion::new(0.03, 0.25);
+        let pricer = MonteCarloPricer::new(100, 40_000)
+            .with_config(SimulationConfig::new(true).with_seed(4));
+
+        let collared = call().with_floor(2.0).with_cap(20.0);
+        let corridor = CorridorOption::new(100.0, 90.0, 110.0, 0.05, 0.03, 0.25, 1.0);
+
+        for (simulated, exact) in [
+            (
+                pricer.price(&collared, &gbm, 100.0, 0.05, 1.0),
+                collared.price(),
+            ),
+            (
+                pricer.price(&corridor, &gbm, 100.0, 0.05, 1.0),
+                corridor.price(),
+            ),
+        ] {
+            assert!((simulated.value() - exact).abs() < 4.0 * simulated.standard_error.unwrap());
+        }
+    }
+}
@@ -15,7 +15,7 @@ use time::OffsetDateTime;
 
 use crate::{
     statistics::distributions::{Distribution, Gaussian},
-    time::{DayCountConvention, DayCounter},
+    time::{DayCount, DayCountConvention},
 };
 
 /// Black-Scholes Vanilla European Option
@@ -75,7 +75,7 @@ impl EuropeanOption {
         let q = self.dividend_rate;
 
         // Compute time to maturity.
-        let T = DayCounter::day_count_factor(
+        let T = DayCount::day_count_factor(
             self.evaluation_date.unwrap_or(OffsetDateTime::now_utc()),
             self.expiration_date,
             &DayCountConvention::Actual365,
@@ -111,7 +111,6 @@ impl EuropeanOption {
 #[cfg(test)]
 mod tests_black_scholes {
     use super::*;
-    use crate::assert_approx_equal;
     use time::Duration;
 
     #[test]
@@ -0,0 +1,278 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Early-exercise boundaries of American and Bermudan options.
+//!
+//! A boundary can be computed by the CRR tree
+//! ([`BinomialOption::exercise_boundary`](crate::instruments::options::BinomialOption::exercise_boundary)),
+//! Longstaff-Schwartz
+//! ([`LongstaffSchwartzResult::exercise_boundary`](crate::instruments::options::american::LongstaffSchwartzResult::exercise_boundary))
+//! or the analytic approximations
+//! ([`AmericanApproximation::exercise_boundary`](crate::instruments::options::american::AmericanApproximation::exercise_boundary)),
+//! and then reused as a stopping rule to price similar contracts on new
+//! paths without another regression or tree.
+
+use crate::instruments::options::TypeFlag;
+use crate::instruments::{PricingEngine, PricingResult};
+use crate::stochastics::Trajectories;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Critical underlying prices over time: a call is exercised at or above
+/// the boundary, a put at or below it.
+///
+/// Times where exercise is never optimal have an infinite (call) or zero
+/// (put) critical price.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExerciseBoundary {
+    /// Call or put.
+    pub option_type: TypeFlag,
+
+    /// Times, in years from the valuation date, in increasing order.
+    pub times: Vec<f64>,
+
+    /// Critical underlying price at each time.
+    pub critical_prices: Vec<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl ExerciseBoundary {
+    /// New boundary from times and the critical prices at them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no times, the lengths differ, or the times are
+    /// not increasing.
+    pub fn new(option_type: TypeFlag, times: Vec<f64>, critical_prices: Vec<f64>) -> Self {
+        assert!(!times.is_empty(), "A boundary needs at least one time.");
+        assert_eq!(times.len(), critical_prices.len(), "One price per time.");
+        assert!(
+            times.windows(2).all(|pair| pair[0] < pair[1]),
+            "Times must be increasing."
+        );
+
+        Self {
+            option_type,
+            times,
+            critical_prices,
+        }
+    }
+
+    /// Critical price in force at time `t`: that of the last boundary time
+    /// at or before `t` (or of the first time, before it).
+    pub fn critical_price(&self, t: f64) -> f64 {
+        let i = self.times.partition_point(|&time| time <= t);
+
+        self.critical_prices[i.saturating_sub(1)]
+    }
+
+    /// Whether exercising at time `t` with the underlying at `price` is
+    /// optimal.
+    pub fn is_exercised(&self, t: f64, price: f64) -> bool {
+        let critical = self.critical_price(t);
+
+        match self.option_type {
+            TypeFlag::Call => price >= critical,
+            TypeFlag::Put => price <= critical,
+        }
+    }
+
+    /// First time point of a path (with values at `times`) at which the
+    /// option is exercised, if any.
+    pub fn exercise_index(&self, times: &[f64], path: &[f64]) -> Option<usize> {
+        times
+            .iter()
+            .zip(path)
+            .position(|(&t, &price)| self.is_exercised(t, price))
+    }
+
+    /// (time, critical price) pairs of the times where exercise can be
+    /// optimal, e.g. for plotting.
+    pub fn points(&self) -> Vec<(f64, f64)> {
+        self.times
+            .iter()
+            .copied()
+            .zip(self.critical_prices.iter().copied())
+            .filter(|(_, price)| price.is_finite() && *price > 0.0)
+            .collect()
+    }
+
+    /// Prices an option with the given exercise payoff on simulated paths
+    /// of the underlying, exercising each path the first time it crosses the
+    /// boundary (or at the last time point), and discounting at the
+    /// continuously compounded `rate`.
+    ///
+    /// As any fixed stopping rule is sub-optimal, the estimate is biased
+    /// low; with a boundary from the same model the bias is small.
+    pub fn price<F>(&self, trajectories: &Trajectories, payoff: F, rate: f64) -> PricingResult
+    where
+        F: Fn(f64) -> f64,
+    {
+        let times = &trajectories.times;
+        let n = trajectories.n_steps();
+        let m = trajectories.n_paths();
+
+        let cash_flows = trajectories
+            .iter()
+            .map(|path| {
+                let k = times
+                    .iter()
+                    .zip(path.iter())
+                    .position(|(&t, &price)| payoff(price) > 0.0 && self.is_exercised(t, price))
+                    .unwrap_or(n);
+
+                payoff(path[k]) * (-rate * (times[k] - times[0])).exp()
+            })
+            .collect::<Vec<_>>();
+
+        let mean = cash_flows.iter().sum::<f64>() / m as f64;
+        let variance =
+            cash_flows.iter().map(|c| (c - mean).powi(2)).sum::<f64>() / (m as f64 - 1.0);
+
+        PricingResult::new(mean)
+            .with_standard_error((variance / m as f64).sqrt())
+            .with_engine(PricingEngine::Simulation)
+    }
+
+    /// Plots the boundary (where exercise can be optimal) to a PNG or SVG
+    /// file.
+    #[cfg(feature = "plot")]
+    pub fn plot(&self, path: impl AsRef<std::path::Path>) -> Result<(), crate::plot::PlotError> {
+        crate::plot::Chart::new("Early-exercise boundary")
+            .with_labels("Time", "Critical price")
+            .with_series("Boundary", self.points())
+            .save(path)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_exercise {
+    use super::*;
+    use crate::instruments::options::american::{AmericanApproximation, LongstaffSchwartz};
+    use crate::instruments::options::{BinomialOption, BlackScholesInputs};
+    use crate::math::polynomials::{PolynomialBasis, PolynomialFamily};
+    use crate::stochastics::{GeometricBrownianMotion, SimulationConfig, StochasticProcess};
+
+    // Longstaff and Schwartz (2001), Table 1: American value 4.478.
+    const S: f64 = 36.0;
+    const K: f64 = 40.0;
+    const R: f64 = 0.06;
+    const SIGMA: f64 = 0.2;
+
+    fn tree_boundary() -> ExerciseBoundary {
+        BinomialOption::new(S, K, 1.0, R, 0.0, SIGMA)
+            .exercise_boundary(TypeFlag::Put, 500)
+            .unwrap()
+    }
+
+    fn paths(seed: u64) -> Trajectories {
+        let config = SimulationConfig::new(true).with_seed(seed);
+
+        GeometricBrownianMotion::new(R, SIGMA).simulate_with_config(S, 0.0, 1.0, 50, 20000, &config)
+    }
+
+    #[test]
+    fn test_boundary_lookup() {
+        let boundary = ExerciseBoundary::new(TypeFlag::Put, vec![0.0, 0.5], vec![30.0, 35.0]);
+
+        assert_eq!(boundary.critical_price(0.25), 30.0);
+        assert_eq!(boundary.critical_price(0.5), 35.0);
+        assert_eq!(boundary.critical_price(2.0), 35.0);
+        assert!(boundary.is_exercised(0.6, 34.0));
+        assert!(!boundary.is_exercised(0.1, 34.0));
+        assert_eq!(
+            boundary.exercise_index(&[0.0, 0.25, 0.5], &[36.0, 33.0, 34.0]),
+            Some(2)
+        );
+        assert_eq!(boundary.points(), vec![(0.0, 30.0), (0.5, 35.0)]);
+
+        let call = ExerciseBoundary::new(TypeFlag::Call, vec![0.0], vec![f64::INFINITY]);
+        assert!(!call.is_exercised(0.0, 1e9));
+        assert!(call.points().is_empty());
+    }
+
+    #[test]
+    fn test_tree_boundary() {
+        let boundary = tree_boundary();
+
+        assert_eq!(boundary.times.len(), 501);
+        assert_eq!(boundary.critical_prices[500], K);
+
+        // The put boundary lies below the strike and rises towards it. The
+        // first few steps have no node low enough to be exercised.
+        let prices = &boundary.critical_prices;
+        assert!(prices.iter().all(|&price| (0.0..=K).contains(&price)));
+        assert_eq!(prices[0], 0.0);
+        assert!(prices[100] > 30.0);
+        assert!(prices[100] < prices[250] && prices[250] < prices[499]);
+
+        // The approximations' boundaries are close to the tree's.
+        let inputs = BlackScholesInputs {
+            underlying_price: S,
+            strike_price: K,
+            volatility: SIGMA,
+            risk_free_rate: R,
+            cost_of_carry: R,
+            time_to_expiry: 1.0,
+            option_type: TypeFlag::Put,
+        };
+        let approximation = AmericanApproximation::BaroneAdesiWhaley.exercise_boundary(&inputs, 4);
+
+        for (t, critical) in approximation.points().into_iter().skip(1) {
+            assert_approx_equal!(boundary.critical_price(t), critical, 0.5);
+        }
+    }
+
+    #[test]
+    fn test_longstaff_schwartz_boundary() {
+        let paths = paths(7);
+        let basis = PolynomialBasis::new(PolynomialFamily::Laguerre, 3).with_scaling(0.0, K);
+        let result = LongstaffSchwartz::new(basis).price(&paths, |s| (K - s).max(0.0), R);
+        let boundary = result.exercise_boundary(&paths.times, TypeFlag::Put);
+
+        assert_eq!(boundary.times.len(), 51);
+        assert_eq!(boundary.critical_prices[0], 0.0);
+        assert!(boundary.critical_prices[50] < K);
+
+        let tree = tree_boundary();
+        for k in [10, 25, 40] {
+            let t = paths.times[k];
+            assert_approx_equal!(boundary.critical_price(t), tree.critical_price(t), 1.0);
+        }
+    }
+
+    #[test]
+    fn test_price_with_boundary() {
+        // A boundary from the tree prices the put on fresh paths.
+        let result = tree_boundary().price(&paths(11), |s| (K - s).max(0.0), R);
+        let standard_error = result.standard_error.unwrap();
+
+        assert!((result.value() - 4.478).abs() < 4.0 * standard_error + 0.05);
+        assert_eq!(result.engine, Some(PricingEngine::Simulation));
+    }
+
+    #[cfg(feature = "plot")]
+    #[test]
+    fn test_boundary_plot() {
+        let path = std::env::temp_dir().join("rustquant_exercise_boundary.svg");
+
+        tree_boundary().plot(&path).unwrap();
+        assert!(std::fs::metadata(&path).is_ok());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
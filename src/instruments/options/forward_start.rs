@@ -15,7 +15,7 @@ use time::OffsetDateTime;
 
 use crate::{
     statistics::distributions::{Distribution, Gaussian},
-    time::{DayCountConvention, DayCounter},
+    time::{DayCount, DayCountConvention},
 };
 
 /// Forward Start Option parameters struct
@@ -61,13 +61,13 @@ impl ForwardStartOption {
         let v = self.volatility;
         let q = self.dividend_rate;
 
-        let T = DayCounter::day_count_factor(
+        let T = DayCount::day_count_factor(
             self.valuation_date.unwrap_or(OffsetDateTime::now_utc()),
             self.end,
             &DayCountConvention::Actual365,
         );
 
-        let t = DayCounter::day_count_factor(
+        let t = DayCount::day_count_factor(
             self.valuation_date.unwrap_or(OffsetDateTime::now_utc()),
             self.start,
             &DayCountConvention::Actual365,
@@ -103,7 +103,6 @@ impl ForwardStartOption {
 
 #[cfg(test)]
 mod tests_forward_start {
-    use crate::assert_approx_equal;
 
     use super::*;
 
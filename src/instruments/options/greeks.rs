@@ -11,7 +11,7 @@ use time::OffsetDateTime;
 
 use crate::instruments::options::european::*;
 use crate::statistics::distributions::{Distribution, Gaussian};
-use crate::time::{DayCountConvention, DayCounter};
+use crate::time::{DayCount, DayCountConvention};
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // GREEKS STRUCT
@@ -60,12 +60,12 @@ impl Greeks {
 
         // Compute time to maturity.
         let T = match option.evaluation_date {
-            Some(valuation_date) => DayCounter::day_count_factor(
+            Some(valuation_date) => DayCount::day_count_factor(
                 valuation_date,
                 option.expiration_date,
                 &DayCountConvention::Actual365,
             ),
-            None => DayCounter::day_count_factor(
+            None => DayCount::day_count_factor(
                 OffsetDateTime::now_utc(),
                 option.expiration_date,
                 &DayCountConvention::Actual365,
@@ -141,8 +141,6 @@ impl Greeks {
 mod tests_greeks {
     use time::Duration;
 
-    use crate::assert_approx_equal;
-
     use super::*;
 
     #[test]
@@ -8,8 +8,9 @@
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 use crate::{
-    math::*,
-    time::{DayCountConvention, DayCounter},
+    autodiff::{ComplexVariable, Variable},
+    math::integrate::{GaussKronrod, GaussLegendre},
+    time::{DayCount, DayCountConvention},
 };
 use num_complex::Complex;
 use time::OffsetDateTime;
@@ -35,7 +36,7 @@ pub fn heston(
 ) -> (f64, f64) {
     // Time to expiry.
 
-    let tau = DayCounter::day_count_factor(
+    let tau = DayCount::day_count_factor(
         evaluation_date.unwrap_or(OffsetDateTime::now_utc()),
         expiration_date,
         &DayCountConvention::Actual365,
@@ -114,8 +115,9 @@ pub fn heston(
 
     // Integration bounds given in Fabrice D. Rouah's book (see tests).
     // The integral decays rapidly so 50 is probably enough.
-    let P1 = 0.5 + std::f64::consts::FRAC_1_PI * integrate(Re1, 0.00001, 50.0);
-    let P2 = 0.5 + std::f64::consts::FRAC_1_PI * integrate(Re2, 0.00001, 50.0);
+    let quadrature = GaussKronrod::default();
+    let P1 = 0.5 + std::f64::consts::FRAC_1_PI * quadrature.integrate(Re1, 0.00001, 50.0).value;
+    let P2 = 0.5 + std::f64::consts::FRAC_1_PI * quadrature.integrate(Re2, 0.00001, 50.0).value;
 
     // Price call, then use put-call-parity for the put.
     let call = S0 * (-q * tau).exp() * P1 - K * (-r * tau).exp() * P2;
@@ -124,6 +126,83 @@ pub fn heston(
     (call, put)
 }
 
+/// Heston call price as a function of the model parameters on the
+/// autodiff graph, `params = [v0, kappa, theta, sigma, rho]`, for exact
+/// calibration gradients and AAD Greeks.
+///
+/// The Fourier integrals are truncated to $[0, \phi_{max}]$ and evaluated
+/// on the fixed nodes of `quadrature` (e.g. 64 Gauss-Legendre nodes up to
+/// $\phi_{max} = 100$), so the tape does not depend on the parameters. The characteristic function uses the
+/// formulation of Albrecher et al. (2007), which has no branch cut
+/// crossings for long maturities.
+///
+/// # Arguments:
+///
+/// * `s0` - Initial asset value.
+/// * `k` - Strike price.
+/// * `tau` - Time to expiry, in years.
+/// * `r` - Risk-free rate.
+/// * `q` - Dividend yield.
+/// * `params` - `[v0, kappa, theta, sigma, rho]`.
+/// * `quadrature` - Gauss-Legendre rule for the Fourier integrals.
+/// * `phi_max` - Truncation of the Fourier integrals.
+#[allow(clippy::too_many_arguments)]
+pub fn heston_call_autodiff<'v>(
+    s0: f64,
+    k: f64,
+    tau: f64,
+    r: f64,
+    q: f64,
+    params: &[Variable<'v>],
+    quadrature: &GaussLegendre,
+    phi_max: f64,
+) -> Variable<'v> {
+    assert_eq!(
+        params.len(),
+        5,
+        "Heston parameters: [v0, kappa, theta, sigma, rho]."
+    );
+
+    let (v0, kappa, theta, sigma, rho) = (params[0], params[1], params[2], params[3], params[4]);
+    let sigma2 = sigma * sigma;
+    let x = (s0 / k).ln();
+
+    // Integrand of P_j at phi: Im[exp(C + D v0 + i phi x)] / phi.
+    let integrand = |u: f64, b: Variable<'v>, phi: f64| -> Variable<'v> {
+        let beta = ComplexVariable::new(b, rho * sigma * -phi);
+        let d = (beta * beta + ComplexVariable::new(sigma2 * phi * phi, sigma2 * (-2.0 * u * phi)))
+            .sqrt();
+
+        let beta_minus_d = beta - d;
+        let g = beta_minus_d / (beta + d);
+        let e = (d * -tau).exp();
+        let one_minus_ge = 1.0 - g * e;
+
+        let c = (beta_minus_d * tau - ((one_minus_ge) / (1.0 - g)).ln() * 2.0)
+            * (kappa * theta / sigma2);
+        let big_d = beta_minus_d * (1.0 - e) / one_minus_ge / sigma2;
+
+        let exponent = c + big_d * v0;
+        let phase = exponent.im + ((r - q) * tau + x) * phi;
+
+        exponent.re.exp() * phase.sin() / phi
+    };
+
+    let probability = |u: f64, b: Variable<'v>| -> Variable<'v> {
+        let integral = quadrature
+            .nodes_and_weights(0.0, phi_max)
+            .map(|(phi, w)| integrand(u, b, phi) * w)
+            .sum::<Variable>();
+
+        integral * std::f64::consts::FRAC_1_PI + 0.5
+    };
+
+    let p1 = probability(0.5, kappa - rho * sigma);
+    let p2 = probability(-0.5, kappa);
+
+    p1 * (s0 * (-q * tau).exp()) - p2 * (k * (-r * tau).exp())
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // TESTS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -149,8 +228,6 @@ the put price decreases, to 5.3790, and the call price increases, to 6.8678.
 mod tests {
     use time::Duration;
 
-    use crate::assert_approx_equal;
-
     use super::*;
 
     #[test]
@@ -195,4 +272,58 @@ mod tests {
         // Put price.
         assert_approx_equal!(heston2.1, 5.3727, 1e-4);
     }
+
+    #[test]
+    fn test_heston_autodiff_matches_quadrature() {
+        use crate::autodiff::{Accumulate, Gradient, Graph};
+
+        let evaluation = OffsetDateTime::UNIX_EPOCH;
+        let expiry = evaluation + Duration::days(365);
+        let quadrature = GaussLegendre::new(64);
+        let params = [0.05, 5.0, 0.05, 0.5, -0.8];
+
+        for strike in [80.0, 100.0, 120.0] {
+            let (call, _) = heston(
+                100.0,
+                params[0],
+                strike,
+                0.03,
+                0.02,
+                params[4],
+                params[3],
+                params[1],
+                params[2],
+                Some(evaluation),
+                expiry,
+            );
+
+            let g = Graph::new();
+            let vars = g.vars(&params);
+            let price =
+                heston_call_autodiff(100.0, strike, 1.0, 0.03, 0.02, &vars, &quadrature, 100.0);
+            // `heston` starts its integrals at 0.00001 (Rouah's grid).
+            assert_approx_equal!(price.value, call, 1e-4);
+
+            // Vega-like sensitivity to v0 against a central difference.
+            let h = 1e-5;
+            let bumped = |v0: f64| {
+                heston(
+                    100.0,
+                    v0,
+                    strike,
+                    0.03,
+                    0.02,
+                    params[4],
+                    params[3],
+                    params[1],
+                    params[2],
+                    Some(evaluation),
+                    expiry,
+                )
+                .0
+            };
+            let fd = (bumped(params[0] + h) - bumped(params[0] - h)) / (2.0 * h);
+            assert_approx_equal!(price.accumulate().wrt(&vars[0]), fd, 1e-4);
+        }
+    }
 }
@@ -8,11 +8,13 @@
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 use crate::{
+    error::{expect_valid, RustQuantError},
     instruments::options::*,
     statistics::distributions::{Distribution, Gaussian},
     statistics::*,
     stochastics::*,
 };
+use ndarray::ArrayView1;
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // LOOKBACK OPTION STRUCTS
@@ -68,7 +70,17 @@ pub struct LookbackOption {
 
 impl LookbackOption {
     /// Closed-form lookback option price.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a fixed strike lookback has no strike price.
     pub fn price_analytic(&self) -> (f64, f64) {
+        expect_valid(self.try_price_analytic())
+    }
+
+    /// Closed-form lookback option price, or an error if a fixed strike
+    /// lookback has no strike price.
+    pub fn try_price_analytic(&self) -> Result<(f64, f64), RustQuantError> {
         let s = self.initial_price;
         let r = self.risk_free_rate;
         let t = self.time_to_maturity;
@@ -119,10 +131,10 @@ impl LookbackOption {
                         + s_max * (-r * t).exp() * norm.cdf(-b2)
                         + s * (-r * t).exp() * v * t.sqrt() * (norm.pdf(b1) + b1 * norm.cdf(b1));
                 }
-                (call, put)
+                Ok((call, put))
             }
             LookbackStrike::Fixed => {
-                let x = self.strike_price.unwrap();
+                let x = self.fixed_strike()?;
 
                 let d1 = ((s / x).ln() + (b + v * v / 2.0) * t) / (v * t.sqrt());
                 let d2 = d1 - v * t.sqrt();
@@ -166,89 +178,94 @@ impl LookbackOption {
                                 - (b * t).exp() * norm.cdf(-f1))
                 };
 
-                (call, put)
+                Ok((call, put))
             }
         }
     }
 
-    fn payoff(&self, option_type: TypeFlag, strike_type: LookbackStrike, path: &[f64]) -> f64 {
+    /// Strike price of a fixed strike lookback.
+    fn fixed_strike(&self) -> Result<f64, RustQuantError> {
+        self.strike_price.ok_or_else(|| {
+            RustQuantError::invalid_parameter("A fixed strike lookback needs a strike price.")
+        })
+    }
+
+    /// Payoff of a path, with a fixed strike if one is given and a
+    /// floating strike otherwise.
+    fn payoff(&self, option_type: TypeFlag, strike: Option<f64>, path: ArrayView1<f64>) -> f64 {
         // let S_min = path.iter().copied().fold(path[0] /*f64::NAN*/, f64::min);
         // let S_max = path.iter().copied().fold(path[0] /*f64::NAN*/, f64::max);
 
         let S_min = *path.iter().min_by(|a, b| a.total_cmp(b)).unwrap();
         let S_max = *path.iter().max_by(|a, b| a.total_cmp(b)).unwrap();
 
-        let S_T = path.last().unwrap();
-
-        match option_type {
-            TypeFlag::Call => match strike_type {
-                LookbackStrike::Fixed => f64::max(S_max - self.strike_price.unwrap(), 0.0),
-                LookbackStrike::Floating => f64::max(S_T - S_min, 0.0),
-            },
-            TypeFlag::Put => match strike_type {
-                LookbackStrike::Fixed => f64::max(self.strike_price.unwrap() - S_min, 0.0),
-                LookbackStrike::Floating => f64::max(S_max - S_T, 0.0),
-            },
+        let S_T = &path[path.len() - 1];
+
+        match (option_type, strike) {
+            (TypeFlag::Call, Some(strike)) => f64::max(S_max - strike, 0.0),
+            (TypeFlag::Call, None) => f64::max(S_T - S_min, 0.0),
+            (TypeFlag::Put, Some(strike)) => f64::max(strike - S_min, 0.0),
+            (TypeFlag::Put, None) => f64::max(S_max - S_T, 0.0),
         }
     }
 
     /// Monte Carlo simulation of the lookback option price.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the inputs are rejected by
+    /// [`LookbackOption::try_price_simulated`].
     pub fn price_simulated(&self, n_steps: usize, n_sims: usize, parallel: bool) -> (f64, f64) {
+        expect_valid(self.try_price_simulated(n_steps, n_sims, parallel))
+    }
+
+    /// Monte Carlo simulation of the lookback option price, or an error if
+    /// a fixed strike lookback has no strike price, the volatility is
+    /// negative, or the time to maturity or number of steps is not
+    /// positive.
+    pub fn try_price_simulated(
+        &self,
+        n_steps: usize,
+        n_sims: usize,
+        parallel: bool,
+    ) -> Result<(f64, f64), RustQuantError> {
         let x_0 = self.initial_price;
         let r = self.risk_free_rate;
         let q = self.dividend_yield;
         let sigma = self.volatility;
         let t_n = self.time_to_maturity;
 
+        let strike = match self.strike_type {
+            LookbackStrike::Fixed => Some(self.fixed_strike()?),
+            LookbackStrike::Floating => None,
+        };
+
         // Adjust the drift term to account for the cost of carry.
         let cost_of_carry = r - q;
-        let gbm = GeometricBrownianMotion::new(cost_of_carry, sigma);
+        let gbm = GeometricBrownianMotion::try_new(cost_of_carry, sigma)?;
 
-        let paths = gbm.euler_maruyama(x_0, 0.0, t_n, n_steps, n_sims, parallel);
+        let paths = gbm.try_simulate_with_config(
+            x_0,
+            0.0,
+            t_n,
+            n_steps,
+            n_sims,
+            &SimulationConfig::new(parallel),
+        )?;
 
         let mut call_payoffs = Vec::with_capacity(n_sims);
         let mut put_payoffs = Vec::with_capacity(n_sims);
 
-        match self.strike_type {
-            LookbackStrike::Fixed => {
-                for path in &paths.paths {
-                    call_payoffs.push(Self::payoff(
-                        self,
-                        TypeFlag::Call,
-                        LookbackStrike::Fixed,
-                        path,
-                    ));
-                    put_payoffs.push(Self::payoff(
-                        self,
-                        TypeFlag::Put,
-                        LookbackStrike::Fixed,
-                        path,
-                    ));
-                }
-            }
-            LookbackStrike::Floating => {
-                for path in &paths.paths {
-                    call_payoffs.push(Self::payoff(
-                        self,
-                        TypeFlag::Call,
-                        LookbackStrike::Floating,
-                        path,
-                    ));
-                    put_payoffs.push(Self::payoff(
-                        self,
-                        TypeFlag::Put,
-                        LookbackStrike::Floating,
-                        path,
-                    ));
-                }
-            }
+        for path in paths.iter() {
+            call_payoffs.push(self.payoff(TypeFlag::Call, strike, path));
+            put_payoffs.push(self.payoff(TypeFlag::Put, strike, path));
         }
 
-        (
+        Ok((
             // Discounted mean of the call and put payoffs.
             (-r * t_n).exp() * call_payoffs.mean(),
             (-r * t_n).exp() * put_payoffs.mean(),
-        )
+        ))
     }
 }
 
@@ -259,7 +276,6 @@ impl LookbackOption {
 #[cfg(test)]
 mod tests_lookback {
     use super::*;
-    use crate::assert_approx_equal;
 
     #[test]
     fn test_lookback_floating() {
@@ -362,8 +378,16 @@ mod tests_lookback {
 
         let path = vec![50.0, 55.0, 52.0, 58.0, 54.0];
 
-        let call_payoff = lbo_fixed.payoff(TypeFlag::Call, LookbackStrike::Fixed, &path);
-        let put_payoff = lbo_fixed.payoff(TypeFlag::Put, LookbackStrike::Fixed, &path);
+        let call_payoff = lbo_fixed.payoff(
+            TypeFlag::Call,
+            lbo_fixed.strike_price,
+            ArrayView1::from(&path),
+        );
+        let put_payoff = lbo_fixed.payoff(
+            TypeFlag::Put,
+            lbo_fixed.strike_price,
+            ArrayView1::from(&path),
+        );
 
         // Payoff values
         assert_approx_equal!(call_payoff, 0.0, 0.1); // call payoff = max(S_max - K, 0) = max(58 - 60, 0) = 0
@@ -386,11 +410,47 @@ mod tests_lookback {
 
         let path = vec![50.0, 55.0, 52.0, 58.0, 54.0];
 
-        let call_payoff = lbo_floating.payoff(TypeFlag::Call, LookbackStrike::Floating, &path);
-        let put_payoff = lbo_floating.payoff(TypeFlag::Put, LookbackStrike::Floating, &path);
+        let call_payoff = lbo_floating.payoff(
+            TypeFlag::Call,
+            lbo_floating.strike_price,
+            ArrayView1::from(&path),
+        );
+        let put_payoff = lbo_floating.payoff(
+            TypeFlag::Put,
+            lbo_floating.strike_price,
+            ArrayView1::from(&path),
+        );
 
         // Payoff values
         assert_approx_equal!(call_payoff, 4.0, 0.1); // call payoff = max(S_T - S_min, 0) = max(54 - 50, 0) = 4
         assert_approx_equal!(put_payoff, 4.0, 0.1); // put payoff = max(S_max - S_T, 0) = max(58 - 54, 0) = 4
     }
+
+    #[test]
+    fn test_lookback_fixed_without_strike() {
+        let lbo_fixed = LookbackOption {
+            initial_price: 100.0,
+            s_max: 100.0,
+            s_min: 100.0,
+            time_to_maturity: 1.0,
+            risk_free_rate: 0.1,
+            volatility: 0.1,
+            strike_price: None,
+            dividend_yield: 0.0,
+            strike_type: LookbackStrike::Fixed,
+        };
+
+        assert!(matches!(
+            lbo_fixed.try_price_analytic(),
+            Err(RustQuantError::InvalidParameter { .. })
+        ));
+        assert!(lbo_fixed.try_price_simulated(10, 10, false).is_err());
+
+        let lbo_negative_vol = LookbackOption {
+            volatility: -0.1,
+            strike_price: Some(95.0),
+            ..lbo_fixed
+        };
+        assert!(lbo_negative_vol.try_price_simulated(10, 10, false).is_err());
+    }
 }
@@ -0,0 +1,236 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Generic Monte Carlo pricing of payoffs.
+//!
+//! Anything implementing [`PathIndependentPayoff`] (a function of the
+//! terminal price) or [`PathDependentPayoff`] (a function of the whole
+//! path) can be priced under any [`StochasticProcess`] for the underlying,
+//! e.g. a capped call under Heston, or a power option under CEV.
+//!
+//! ```
+//! use RustQuant::instruments::options::*;
+//! use RustQuant::stochastics::{GeometricBrownianMotion, SimulationConfig};
+//!
+//! let option = CappedOption::new(100.0, 100.0, 0.05, 0.05, 0.2, 1.0, TypeFlag::Call)
+//!     .with_cap(20.0);
+//!
+//! let pricer = MonteCarloPricer::new(250, 20_000)
+//!     .with_config(SimulationConfig::new(true).with_seed(3));
+//! let gbm = GeometricBrownianMotion::new(0.05, 0.2);
+//! let simulated = pricer.price(&option, &gbm, 100.0, 0.05, 1.0);
+//!
+//! let error = simulated.standard_error.unwrap();
+//! assert!((simulated.value() - option.price()).abs() < 4.0 * error);
+//! ```
+
+use crate::instruments::{
+    PathDependentPayoff, PathIndependentPayoff, PricingEngine, PricingResult,
+};
+use crate::stochastics::{SimulationConfig, StochasticProcess};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Monte Carlo pricer: simulation settings shared by the payoffs it prices.
+#[derive(Debug, Clone)]
+pub struct MonteCarloPricer {
+    /// Number of time steps per path.
+    pub n_steps: usize,
+
+    /// Number of simulated paths.
+    pub n_paths: usize,
+
+    /// Parallelism and seeding of the simulation.
+    pub config: SimulationConfig,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl MonteCarloPricer {
+    /// New pricer, running in parallel with entropy seeding.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n_steps` is zero or `n_paths` is less than two.
+    pub fn new(n_steps: usize, n_paths: usize) -> Self {
+        assert!(n_steps > 0, "At least one step is needed.");
+        assert!(n_paths > 1, "At least two paths are needed.");
+
+        Self {
+            n_steps,
+            n_paths,
+            config: SimulationConfig::new(true),
+        }
+    }
+
+    /// Sets the simulation settings (e.g. a seed).
+    pub fn with_config(mut self, config: SimulationConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Discounted expected payoff at expiry, with the underlying simulated
+    /// by `process` (which should be risk-neutral) from `initial_price`,
+    /// and discounting at the continuously compounded `rate`.
+    pub fn price<F, P>(
+        &self,
+        payoff: &F,
+        process: &P,
+        initial_price: f64,
+        rate: f64,
+        time_to_expiry: f64,
+    ) -> PricingResult
+    where
+        F: PathIndependentPayoff,
+        P: StochasticProcess + Sync,
+    {
+        PricingResult::timed(|| {
+            let paths = process.simulate_with_config(
+                initial_price,
+                0.0,
+                time_to_expiry,
+                self.n_steps,
+                self.n_paths,
+                &self.config,
+            );
+
+            let payoffs = paths
+                .terminal_values()
+                .iter()
+                .map(|s| payoff.payoff(*s))
+                .collect::<Vec<_>>();
+
+            discounted_mean(&payoffs, (-rate * time_to_expiry).exp())
+        })
+    }
+
+    /// As [`Self::price`], for a payoff of the whole simulated path.
+    pub fn price_path_dependent<F, P>(
+        &self,
+        payoff: &F,
+        process: &P,
+        initial_price: f64,
+        rate: f64,
+        time_to_expiry: f64,
+    ) -> PricingResult
+    where
+        F: PathDependentPayoff,
+        P: StochasticProcess + Sync,
+    {
+        PricingResult::timed(|| {
+            let paths = process.simulate_with_config(
+                initial_price,
+                0.0,
+                time_to_expiry,
+                self.n_steps,
+                self.n_paths,
+                &self.config,
+            );
+
+            let payoffs = paths
+                .iter()
+                .map(|path| payoff.payoff(&path.to_vec()))
+                .collect::<Vec<_>>();
+
+            discounted_mean(&payoffs, (-rate * time_to_expiry).exp())
+        })
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Mean of the discounted payoffs, with its standard error.
+fn discounted_mean(payoffs: &[f64], discount_factor: f64) -> PricingResult {
+    let m = payoffs.len() as f64;
+    let mean = payoffs.iter().sum::<f64>() / m;
+    let variance = payoffs.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / (m - 1.0);
+
+    PricingResult::new(discount_factor * mean)
+        .with_standard_error(discount_factor * (variance / m).sqrt())
+        .with_engine(PricingEngine::Simulation)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_monte_carlo_pricer {
+    use super::*;
+    use crate::instruments::options::{BlackScholesInputs, TypeFlag};
+    use crate::stochastics::GeometricBrownianMotion;
+
+    struct Call(f64);
+
+    impl PathIndependentPayoff for Call {
+        fn payoff(&self, underlying: f64) -> f64 {
+            (underlying - self.0).max(0.0)
+        }
+    }
+
+    struct AveragePriceCall(f64);
+
+    impl PathDependentPayoff for AveragePriceCall {
+        fn payoff(&self, path: &[f64]) -> f64 {
+            let average = path[1..].iter().sum::<f64>() / (path.len() - 1) as f64;
+
+            (average - self.0).max(0.0)
+        }
+    }
+
+    fn pricer(n_steps: usize) -> MonteCarloPricer {
+        MonteCarloPricer::new(n_steps, 40_000).with_config(SimulationConfig::new(true).with_seed(9))
+    }
+
+    #[test]
+    fn test_vanilla_call() {
+        let gbm = GeometricBrownianMotion::new(0.03, 0.25);
+        let result = pricer(50).price(&Call(105.0), &gbm, 100.0, 0.03, 1.0);
+
+        let exact = BlackScholesInputs {
+            underlying_price: 100.0,
+            strike_price: 105.0,
+            volatility: 0.25,
+            risk_free_rate: 0.03,
+            cost_of_carry: 0.03,
+            time_to_expiry: 1.0,
+            option_type: TypeFlag::Call,
+        }
+        .price();
+
+        assert!((result.value() - exact).abs() < 4.0 * result.standard_error.unwrap());
+        assert_eq!(result.engine, Some(PricingEngine::Simulation));
+        assert!(result.compute_time().is_some());
+    }
+
+    #[test]
+    fn test_path_dependent_payoff() {
+        let gbm = GeometricBrownianMotion::new(0.03, 0.25);
+        let pricer = pricer(12);
+
+        // Averaging lowers the volatility, so the Asian call is cheaper.
+        let asian = pricer.price_path_dependent(&AveragePriceCall(100.0), &gbm, 100.0, 0.03, 1.0);
+        let european = pricer.price(&Call(100.0), &gbm, 100.0, 0.03, 1.0);
+
+        assert!(asian.value() < european.value());
+        assert!(asian.value() > 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "At least two paths are needed.")]
+    fn test_new_single_path() {
+        MonteCarloPricer::new(1, 1);
+    }
+}
@@ -8,7 +8,7 @@
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 /// Option type enum.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TypeFlag {
     /// Call option (right to BUY the underlying asset).
     Call = 1,
@@ -18,7 +18,7 @@ pub enum TypeFlag {
 }
 
 /// American/European option type enum.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ExerciseFlag {
     /// American option (can be exercised at any time before expiry).
     American,
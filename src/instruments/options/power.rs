@@ -11,8 +11,15 @@
 //!
 //! Power contracts are options with the payoff: (S/K)^i
 //! where i is the (fixed) power of the contract.
+//!
+//! Power options (asymmetric power options) are calls and puts on the
+//! power of the underlying, with payoffs max(S^i - K, 0) and
+//! max(K - S^i, 0), optionally capped.
 
-use crate::time::{DayCountConvention, DayCounter};
+use crate::instruments::options::TypeFlag;
+use crate::instruments::PathIndependentPayoff;
+use crate::math::special::norm_cdf;
+use crate::time::{DayCount, DayCountConvention};
 use time::OffsetDateTime;
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -42,12 +49,38 @@ pub struct PowerOption {
     pub expiration_date: OffsetDateTime,
 }
 
+/// Power Option (asymmetric): a call or put on `S^i`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AsymmetricPowerOption {
+    /// `S` - Initial price of the underlying.
+    pub initial_price: f64,
+    /// `K` - Strike price (on the scale of `S^i`).
+    pub strike_price: f64,
+    /// `i` - Power of the underlying (positive).
+    pub power: f64,
+
+    /// `r` - Risk-free rate parameter.
+    pub risk_free_rate: f64,
+    /// `b` - Cost of carry.
+    pub cost_of_carry: f64,
+    /// `v` - Volatility parameter.
+    pub volatility: f64,
+    /// `T` - Time to expiry in years.
+    pub time_to_expiry: f64,
+
+    /// Call or put.
+    pub option_type: TypeFlag,
+    /// `C` - Largest payment, if any.
+    pub cap: Option<f64>,
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // IMPLEMENTATIONS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 impl PowerOption {
     /// New Power Option contract.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         initial_price: f64,
         strike_price: f64,
@@ -80,7 +113,7 @@ impl PowerOption {
         let i = self.power;
 
         // Compute time to maturity.
-        let T = DayCounter::day_count_factor(
+        let T = DayCount::day_count_factor(
             self.evaluation_date.unwrap_or(OffsetDateTime::now_utc()),
             self.expiration_date,
             &DayCountConvention::Actual365,
@@ -90,6 +123,93 @@ impl PowerOption {
     }
 }
 
+impl PathIndependentPayoff for PowerOption {
+    fn payoff(&self, underlying: f64) -> f64 {
+        (underlying / self.strike_price).powf(self.power)
+    }
+}
+
+impl AsymmetricPowerOption {
+    /// New uncapped Power Option.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        initial_price: f64,
+        strike_price: f64,
+        power: f64,
+        risk_free_rate: f64,
+        cost_of_carry: f64,
+        volatility: f64,
+        time_to_expiry: f64,
+        option_type: TypeFlag,
+    ) -> Self {
+        Self {
+            initial_price,
+            strike_price,
+            power,
+            risk_free_rate,
+            cost_of_carry,
+            volatility,
+            time_to_expiry,
+            option_type,
+            cap: None,
+        }
+    }
+
+    /// Limits the payment to `cap` (a capped power option).
+    pub fn with_cap(mut self, cap: f64) -> Self {
+        self.cap = Some(cap);
+        self
+    }
+
+    /// Power Option price (Haug, 2007). A cap is a short option struck
+    /// at `K + C` for a call, or `K - C` for a put.
+    pub fn price(&self) -> f64 {
+        let w = self.option_type as i32 as f64;
+        let capped = self
+            .cap
+            .map_or(0.0, |cap| self.uncapped(self.strike_price + w * cap));
+
+        self.uncapped(self.strike_price) - capped
+    }
+
+    fn uncapped(&self, K: f64) -> f64 {
+        let S = self.initial_price;
+        let i = self.power;
+        let r = self.risk_free_rate;
+        let b = self.cost_of_carry;
+        let v = self.volatility;
+        let T = self.time_to_expiry;
+        let w = self.option_type as i32 as f64;
+
+        // Present value of S_T^i.
+        let forward = S.powf(i) * ((i * b + 0.5 * i * (i - 1.0) * v * v - r) * T).exp();
+
+        if K <= 0.0 {
+            return match self.option_type {
+                TypeFlag::Call => forward - K * (-r * T).exp(),
+                TypeFlag::Put => 0.0,
+            };
+        }
+
+        let d1 = ((S / K.powf(1.0 / i)).ln() + (b + (i - 0.5) * v * v) * T) / (v * T.sqrt());
+        let d2 = d1 - i * v * T.sqrt();
+
+        w * (forward * norm_cdf(w * d1) - K * (-r * T).exp() * norm_cdf(w * d2))
+    }
+}
+
+impl PathIndependentPayoff for AsymmetricPowerOption {
+    fn payoff(&self, underlying: f64) -> f64 {
+        let intrinsic = match self.option_type {
+            TypeFlag::Call => underlying.powf(self.power) - self.strike_price,
+            TypeFlag::Put => self.strike_price - underlying.powf(self.power),
+        };
+
+        self.cap
+            .map_or(intrinsic.max(0.0), |cap| intrinsic.clamp(0.0, cap))
+    }
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // TESTS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -97,7 +217,6 @@ impl PowerOption {
 #[cfg(test)]
 mod tests_power_contract {
     use super::*;
-    use crate::assert_approx_equal;
     use time::Duration;
 
     #[test]
@@ -114,5 +233,72 @@ mod tests_power_contract {
         };
 
         assert_approx_equal!(power_option.price(), 0.8313226401449854, 1e-10);
+        assert_eq!(power_option.payoff(900.0), 4.0);
+    }
+
+    fn asymmetric(option_type: TypeFlag) -> AsymmetricPowerOption {
+        AsymmetricPowerOption::new(10.0, 100.0, 2.0, 0.08, 0.06, 0.3, 0.5, option_type)
+    }
+
+    #[test]
+    fn test_asymmetric_power_is_vanilla_for_unit_power() {
+        use crate::instruments::options::BlackScholesInputs;
+
+        for option_type in [TypeFlag::Call, TypeFlag::Put] {
+            let option = AsymmetricPowerOption {
+                power: 1.0,
+                strike_price: 11.0,
+                ..asymmetric(option_type)
+            };
+            let vanilla = BlackScholesInputs {
+                underlying_price: 10.0,
+                strike_price: 11.0,
+                volatility: 0.3,
+                risk_free_rate: 0.08,
+                cost_of_carry: 0.06,
+                time_to_expiry: 0.5,
+                option_type,
+            }
+            .price();
+
+            assert_approx_equal!(option.price(), vanilla, 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_asymmetric_power_parity() {
+        // c - p = e^{-rT} (E[S_T^i] - K).
+        let (call, put) = (asymmetric(TypeFlag::Call), asymmetric(TypeFlag::Put));
+        let forward = 100.0 * ((2.0 * 0.06 + 0.3 * 0.3) * 0.5_f64).exp();
+
+        assert_approx_equal!(
+            call.price() - put.price(),
+            (-0.08 * 0.5_f64).exp() * (forward - 100.0),
+            1e-10
+        );
+    }
+
+    #[test]
+    fn test_capped_power_option() {
+        use crate::instruments::options::MonteCarloPricer;
+        use crate::stochastics::{GeometricBrownianMotion, SimulationConfig};
+
+        let capped = asymmetric(TypeFlag::Call).with_cap(30.0);
+        assert!(capped.price() < asymmetric(TypeFlag::Call).price());
+        assert_eq!(capped.payoff(20.0), 30.0);
+        assert_eq!(capped.payoff(11.0), 21.0);
+
+        let gbm = GeometricBrownianMotion::new(0.06, 0.3);
+        let pricer = MonteCarloPricer::new(100, 40_000)
+            .with_config(SimulationConfig::new(true).with_seed(8));
+
+        for option in [capped, asymmetric(TypeFlag::Put).with_cap(20.0)] {
+            let simulated = pricer.price(&option, &gbm, 10.0, 0.08, 0.5);
+
+            assert!(
+                (simulated.value() - option.price()).abs()
+                    < 4.0 * simulated.standard_error.unwrap()
+            );
+        }
     }
 }
@@ -7,14 +7,537 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+//! # Rainbow Options
+//!
+//! Options on several assets following correlated geometric Brownian
+//! motions:
+//!
+//! - best-of call and put, on $\max_i S_i(T)$,
+//! - worst-of call and put, on $\min_i S_i(T)$,
+//! - outperformance, paying $\max(S_1(T)/S_1(0) - S_2(T)/S_2(0) - K, 0)$.
+//!
+//! Any number of assets can be priced by Monte Carlo. Two-asset best-of and
+//! worst-of options also have the closed forms of Stulz (1982), and an
+//! outperformance option struck at zero is Margrabe's (1978) exchange
+//! option.
+//!
+//! ```
+//! use RustQuant::instruments::options::*;
+//! use RustQuant::stochastics::SimulationConfig;
+//! use nalgebra::DMatrix;
+//!
+//! let option = RainbowOption::new(
+//!     vec![100.0, 105.0],
+//!     vec![0.2, 0.3],
+//!     DMatrix::from_row_slice(2, 2, &[1.0, 0.5, 0.5, 1.0]),
+//!     100.0,
+//!     0.05,
+//!     1.0,
+//!     RainbowPayoff::WorstOfPut,
+//! );
+//!
+//! let exact = option.price_stulz()?;
+//! let simulated = option.price_monte_carlo(20_000, &SimulationConfig::new(true).with_seed(1))?;
+//!
+//! assert!((exact.value() - simulated.value()).abs() < 4.0 * simulated.standard_error.unwrap());
+//! # Ok::<(), RustQuant::error::RustQuantError>(())
+//! ```
+
+use crate::error::{ensure, expect_valid, RustQuantError};
+use crate::instruments::{PricingEngine, PricingResult};
+use crate::math::special::{bivariate_normal_cdf, norm_cdf};
+use crate::stochastics::{CorrelatedGeometricBrownianMotion, SimulationConfig};
+use nalgebra::DMatrix;
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
-// STRUCTS
+// STRUCTS, ENUMS, AND TRAITS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+/// Payoff of a rainbow option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RainbowPayoff {
+    /// Call on the best performing asset, $\max(\max_i S_i - K, 0)$.
+    BestOfCall,
+
+    /// Put on the best performing asset, $\max(K - \max_i S_i, 0)$.
+    BestOfPut,
+
+    /// Call on the worst performing asset, $\max(\min_i S_i - K, 0)$.
+    WorstOfCall,
+
+    /// Put on the worst performing asset, $\max(K - \min_i S_i, 0)$.
+    WorstOfPut,
+
+    /// Outperformance of the first asset over the second, in returns:
+    /// $\max(S_1 / S_1(0) - S_2 / S_2(0) - K, 0)$.
+    Outperformance,
+}
+
+/// Rainbow option on correlated assets.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RainbowOption {
+    /// `S_i` - Initial prices of the assets.
+    pub initial_prices: Vec<f64>,
+    /// `v_i` - Volatilities of the assets.
+    pub volatilities: Vec<f64>,
+    /// `q_i` - Continuous dividend yields of the assets.
+    pub dividend_yields: Vec<f64>,
+    /// `rho` - Correlation matrix of the assets' returns.
+    pub correlation: DMatrix<f64>,
+
+    /// `K` - Strike (a return spread for outperformance options).
+    pub strike_price: f64,
+    /// `r` - Risk-free rate.
+    pub risk_free_rate: f64,
+    /// `T` - Time to expiry in years.
+    pub time_to_expiry: f64,
+
+    /// Payoff type.
+    pub payoff: RainbowPayoff,
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // IMPLEMENTATIONS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+impl RainbowPayoff {
+    /// Payoff for the assets' prices `prices` at expiry, given their
+    /// initial prices (only used by outperformance options).
+    pub fn evaluate(&self, prices: &[f64], initial_prices: &[f64], strike: f64) -> f64 {
+        let best = || prices.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let worst = || prices.iter().copied().fold(f64::INFINITY, f64::min);
+
+        match self {
+            Self::BestOfCall => (best() - strike).max(0.0),
+            Self::BestOfPut => (strike - best()).max(0.0),
+            Self::WorstOfCall => (worst() - strike).max(0.0),
+            Self::WorstOfPut => (strike - worst()).max(0.0),
+            Self::Outperformance => {
+                let spread = prices[0] / initial_prices[0] - prices[1] / initial_prices[1];
+
+                (spread - strike).max(0.0)
+            }
+        }
+    }
+}
+
+impl RainbowOption {
+    /// New rainbow option on assets without dividends.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the inputs are invalid (see [`Self::try_new`]).
+    pub fn new(
+        initial_prices: Vec<f64>,
+        volatilities: Vec<f64>,
+        correlation: DMatrix<f64>,
+        strike_price: f64,
+        risk_free_rate: f64,
+        time_to_expiry: f64,
+        payoff: RainbowPayoff,
+    ) -> Self {
+        expect_valid(Self::try_new(
+            initial_prices,
+            volatilities,
+            correlation,
+            strike_price,
+            risk_free_rate,
+            time_to_expiry,
+            payoff,
+        ))
+    }
+
+    /// New rainbow option, or an error if the dimensions differ, a price
+    /// or the time to expiry is not positive, a volatility is negative, or
+    /// an outperformance option is not on exactly two assets.
+    pub fn try_new(
+        initial_prices: Vec<f64>,
+        volatilities: Vec<f64>,
+        correlation: DMatrix<f64>,
+        strike_price: f64,
+        risk_free_rate: f64,
+        time_to_expiry: f64,
+        payoff: RainbowPayoff,
+    ) -> Result<Self, RustQuantError> {
+        let d = initial_prices.len();
+
+        ensure(d >= 2, "A rainbow option needs at least two assets.")?;
+        ensure(
+            volatilities.len() == d && correlation.nrows() == d && correlation.ncols() == d,
+            "The prices, volatilities and correlation matrix must have the same dimension.",
+        )?;
+        ensure(
+            initial_prices.iter().all(|s| *s > 0.0),
+            "The initial prices must be positive.",
+        )?;
+        ensure(
+            volatilities.iter().all(|v| *v >= 0.0),
+            "The volatilities must be non-negative.",
+        )?;
+        ensure(time_to_expiry > 0.0, "The time to expiry must be positive.")?;
+        ensure(
+            payoff != RainbowPayoff::Outperformance || d == 2,
+            "An outperformance option is on two assets.",
+        )?;
+
+        Ok(Self {
+            dividend_yields: vec![0.0; d],
+            initial_prices,
+            volatilities,
+            correlation,
+            strike_price,
+            risk_free_rate,
+            time_to_expiry,
+            payoff,
+        })
+    }
+
+    /// Sets the assets' continuous dividend yields.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is not one yield per asset.
+    pub fn with_dividend_yields(self, dividend_yields: Vec<f64>) -> Self {
+        expect_valid(self.try_with_dividend_yields(dividend_yields))
+    }
+
+    /// Sets the assets' continuous dividend yields, or returns an error if
+    /// there is not one yield per asset.
+    pub fn try_with_dividend_yields(
+        mut self,
+        dividend_yields: Vec<f64>,
+    ) -> Result<Self, RustQuantError> {
+        ensure(
+            dividend_yields.len() == self.initial_prices.len(),
+            "One dividend yield per asset.",
+        )?;
+
+        self.dividend_yields = dividend_yields;
+        Ok(self)
+    }
+
+    /// Number of assets.
+    pub fn dimension(&self) -> usize {
+        self.initial_prices.len()
+    }
+
+    /// Discounted mean payoff over `n_paths` joint draws of the assets at
+    /// expiry under the risk-neutral measure, with its standard error, or
+    /// an error if the correlation matrix is not positive definite.
+    pub fn price_monte_carlo(
+        &self,
+        n_paths: usize,
+        config: &SimulationConfig,
+    ) -> Result<PricingResult, RustQuantError> {
+        ensure(n_paths > 1, "At least two paths are needed.")?;
+
+        let mu = self
+            .dividend_yields
+            .iter()
+            .map(|q| self.risk_free_rate - q)
+            .collect();
+        let process = CorrelatedGeometricBrownianMotion::try_new(
+            mu,
+            self.volatilities.clone(),
+            self.correlation.clone(),
+        )?;
+
+        // The log-prices are advanced exactly, so a single step suffices.
+        let assets = process.try_simulate(
+            &self.initial_prices,
+            0.0,
+            self.time_to_expiry,
+            1,
+            n_paths,
+            config,
+        )?;
+
+        let discount_factor = (-self.risk_free_rate * self.time_to_expiry).exp();
+        let payoffs = (0..n_paths)
+            .map(|i| {
+                let prices = assets.iter().map(|a| a.paths[[i, 1]]).collect::<Vec<_>>();
+
+                discount_factor
+                    * self
+                        .payoff
+                        .evaluate(&prices, &self.initial_prices, self.strike_price)
+            })
+            .collect::<Vec<_>>();
+
+        let m = n_paths as f64;
+        let mean = payoffs.iter().sum::<f64>() / m;
+        let variance = payoffs.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / (m - 1.0);
+
+        Ok(PricingResult::new(mean)
+            .with_standard_error((variance / m).sqrt())
+            .with_engine(PricingEngine::Simulation))
+    }
+
+    /// Closed-form price of a two-asset option: Stulz (1982) for best-of
+    /// and worst-of options, and Margrabe (1978) for an outperformance
+    /// option struck at zero.
+    ///
+    /// Returns an error for more than two assets, an outperformance option
+    /// with a non-zero strike, or assets with a zero spread volatility.
+    pub fn price_stulz(&self) -> Result<PricingResult, RustQuantError> {
+        ensure(
+            self.dimension() == 2,
+            "The Stulz formulas are for two assets.",
+        )?;
+
+        let (s1, s2) = (self.initial_prices[0], self.initial_prices[1]);
+        let (v1, v2) = (self.volatilities[0], self.volatilities[1]);
+        let (q1, q2) = (self.dividend_yields[0], self.dividend_yields[1]);
+        let rho = self.correlation[(0, 1)];
+        let (k, r, t) = (self.strike_price, self.risk_free_rate, self.time_to_expiry);
+
+        // Volatility of S1 / S2.
+        let v = (v1 * v1 + v2 * v2 - 2.0 * rho * v1 * v2).sqrt();
+        let sqrt_t = t.sqrt();
+
+        ensure(
+            v1 > 0.0 && v2 > 0.0 && v > 0.0,
+            "The Stulz formulas need non-degenerate volatilities.",
+        )?;
+
+        let a1 = s1 * (-q1 * t).exp();
+        let a2 = s2 * (-q2 * t).exp();
+        let d = ((s1 / s2).ln() + (q2 - q1 + 0.5 * v * v) * t) / (v * sqrt_t);
+
+        // Values of max(S1, S2) and min(S1, S2) paid at expiry.
+        let best = a1 * norm_cdf(d) + a2 * norm_cdf(v * sqrt_t - d);
+        let worst = a1 * norm_cdf(-d) + a2 * norm_cdf(d - v * sqrt_t);
+
+        if self.payoff == RainbowPayoff::Outperformance {
+            ensure(
+                k == 0.0,
+                "The outperformance closed form is for a zero strike (exchange option).",
+            )?;
+
+            // Exchange one unit of the second asset's return for the
+            // first's: Margrabe with unit initial prices.
+            let e = ((q2 - q1 + 0.5 * v * v) * t) / (v * sqrt_t);
+            let value = (-q1 * t).exp() * norm_cdf(e) - (-q2 * t).exp() * norm_cdf(e - v * sqrt_t);
+
+            return Ok(PricingResult::new(value).with_engine(PricingEngine::Analytic));
+        }
+
+        let y1 = ((s1 / k).ln() + (r - q1 + 0.5 * v1 * v1) * t) / (v1 * sqrt_t);
+        let y2 = ((s2 / k).ln() + (r - q2 + 0.5 * v2 * v2) * t) / (v2 * sqrt_t);
+        let rho1 = (v1 - rho * v2) / v;
+        let rho2 = (v2 - rho * v1) / v;
+        let discounted_strike = k * (-r * t).exp();
+
+        let best_of_call = a1 * bivariate_normal_cdf(y1, d, rho1)
+            + a2 * bivariate_normal_cdf(y2, v * sqrt_t - d, rho2)
+            - discounted_strike
+                * (1.0 - bivariate_normal_cdf(v1 * sqrt_t - y1, v2 * sqrt_t - y2, rho));
+        let worst_of_call = a1 * bivariate_normal_cdf(y1, -d, -rho1)
+            + a2 * bivariate_normal_cdf(y2, d - v * sqrt_t, -rho2)
+            - discounted_strike * bivariate_normal_cdf(y1 - v1 * sqrt_t, y2 - v2 * sqrt_t, rho);
+
+        // Puts by put-call parity on the best and worst asset.
+        let value = match self.payoff {
+            RainbowPayoff::BestOfCall => best_of_call,
+            RainbowPayoff::WorstOfCall => worst_of_call,
+            RainbowPayoff::BestOfPut => best_of_call - best + discounted_strike,
+            RainbowPayoff::WorstOfPut => worst_of_call - worst + discounted_strike,
+            RainbowPayoff::Outperformance => unreachable!(),
+        };
+
+        Ok(PricingResult::new(value).with_engine(PricingEngine::Analytic))
+    }
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
-// TESTS
+// UNIT TESTS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_rainbow {
+    use super::*;
+    use crate::instruments::options::{BlackScholesInputs, TypeFlag};
+
+    fn correlation(rho: f64) -> DMatrix<f64> {
+        DMatrix::from_row_slice(2, 2, &[1.0, rho, rho, 1.0])
+    }
+
+    fn option(payoff: RainbowPayoff, rho: f64) -> RainbowOption {
+        RainbowOption::new(
+            vec![100.0, 105.0],
+            vec![0.11, 0.16],
+            correlation(rho),
+            98.0,
+            0.05,
+            0.5,
+            payoff,
+        )
+        .with_dividend_yields(vec![0.06, 0.09])
+    }
+
+    fn vanilla(s: f64, v: f64, q: f64, option_type: TypeFlag) -> f64 {
+        BlackScholesInputs {
+            underlying_price: s,
+            strike_price: 98.0,
+            volatility: v,
+            risk_free_rate: 0.05,
+            cost_of_carry: 0.05 - q,
+            time_to_expiry: 0.5,
+            option_type,
+        }
+        .price()
+    }
+
+    #[test]
+    fn test_stulz_identical_assets() {
+        // Two nearly perfectly correlated copies of one asset: both the
+        // best-of and worst-of calls are the vanilla call.
+        let twins = |payoff| {
+            RainbowOption::new(
+                vec![100.0, 100.0],
+                vec![0.11, 0.11],
+                correlation(1.0 - 1e-10),
+                98.0,
+                0.05,
+                0.5,
+                payoff,
+            )
+            .with_dividend_yields(vec![0.06, 0.06])
+            .price_stulz()
+            .unwrap()
+        };
+        let call = vanilla(100.0, 0.11, 0.06, TypeFlag::Call);
+
+        assert_approx_equal!(twins(RainbowPayoff::BestOfCall).value(), call, 1e-3);
+        assert_approx_equal!(twins(RainbowPayoff::WorstOfCall).value(), call, 1e-3);
+        assert_eq!(
+            twins(RainbowPayoff::BestOfCall).engine,
+            Some(PricingEngine::Analytic)
+        );
+    }
+
+    #[test]
+    fn test_stulz_parities() {
+        // The best and worst of two calls (or puts) are the two calls.
+        for rho in [-0.5, 0.0, 0.63, 0.95] {
+            let price = |payoff| option(payoff, rho).price_stulz().unwrap().value();
+
+            let calls = vanilla(100.0, 0.11, 0.06, TypeFlag::Call)
+                + vanilla(105.0, 0.16, 0.09, TypeFlag::Call);
+            let puts = vanilla(100.0, 0.11, 0.06, TypeFlag::Put)
+                + vanilla(105.0, 0.16, 0.09, TypeFlag::Put);
+
+            assert_approx_equal!(
+                price(RainbowPayoff::BestOfCall) + price(RainbowPayoff::WorstOfCall),
+                calls,
+                1e-9
+            );
+            assert_approx_equal!(
+                price(RainbowPayoff::BestOfPut) + price(RainbowPayoff::WorstOfPut),
+                puts,
+                1e-9
+            );
+        }
+    }
+
+    #[test]
+    fn test_monte_carlo_matches_closed_forms() {
+        let config = SimulationConfig::new(true).with_seed(11);
+
+        for payoff in [
+            RainbowPayoff::BestOfCall,
+            RainbowPayoff::BestOfPut,
+            RainbowPayoff::WorstOfCall,
+            RainbowPayoff::WorstOfPut,
+        ] {
+            let option = option(payoff, 0.4);
+            let exact = option.price_stulz().unwrap().value();
+            let simulated = option.price_monte_carlo(50_000, &config).unwrap();
+
+            let error = simulated.standard_error.unwrap();
+            assert!(
+                (simulated.value() - exact).abs() < 4.0 * error,
+                "{payoff:?}"
+            );
+        }
+
+        let mut outperformance = option(RainbowPayoff::Outperformance, 0.4);
+        outperformance.strike_price = 0.0;
+
+        let exact = outperformance.price_stulz().unwrap().value();
+        let simulated = outperformance.price_monte_carlo(50_000, &config).unwrap();
+        assert!((simulated.value() - exact).abs() < 4.0 * simulated.standard_error.unwrap());
+
+        // A positive spread strike is only priced by simulation.
+        outperformance.strike_price = 0.05;
+        assert!(outperformance.price_stulz().is_err());
+        assert!(
+            outperformance
+                .price_monte_carlo(50_000, &config)
+                .unwrap()
+                .value()
+                < exact
+        );
+    }
+
+    #[test]
+    fn test_worst_of_put_on_three_assets() {
+        let option = RainbowOption::new(
+            vec![100.0; 3],
+            vec![0.2, 0.25, 0.3],
+            DMatrix::from_row_slice(3, 3, &[1.0, 0.5, 0.3, 0.5, 1.0, 0.4, 0.3, 0.4, 1.0]),
+            100.0,
+            0.03,
+            1.0,
+            RainbowPayoff::WorstOfPut,
+        );
+        let config = SimulationConfig::new(true).with_seed(2);
+        let price = option.price_monte_carlo(50_000, &config).unwrap().value();
+
+        assert!(option.price_stulz().is_err());
+
+        // Worth more than a put on any one of the assets.
+        for v in [0.2, 0.25, 0.3] {
+            let put = BlackScholesInputs {
+                underlying_price: 100.0,
+                strike_price: 100.0,
+                volatility: v,
+                risk_free_rate: 0.03,
+                cost_of_carry: 0.03,
+                time_to_expiry: 1.0,
+                option_type: TypeFlag::Put,
+            }
+            .price();
+
+            assert!(price > put);
+        }
+    }
+
+    #[test]
+    fn test_try_new() {
+        let build = |prices: Vec<f64>, payoff| {
+            let d = prices.len();
+            RainbowOption::try_new(
+                prices,
+                vec![0.2; d],
+                DMatrix::identity(d, d),
+                100.0,
+                0.05,
+                1.0,
+                payoff,
+            )
+        };
+
+        assert!(build(vec![100.0, 100.0], RainbowPayoff::BestOfCall).is_ok());
+        assert!(build(vec![100.0], RainbowPayoff::BestOfCall).is_err());
+        assert!(build(vec![100.0, -1.0], RainbowPayoff::BestOfCall).is_err());
+        assert!(build(vec![100.0; 3], RainbowPayoff::Outperformance).is_err());
+
+        let option = build(vec![100.0, 100.0], RainbowPayoff::BestOfCall).unwrap();
+        assert!(option
+            .clone()
+            .try_with_dividend_yields(vec![0.01; 2])
+            .is_ok());
+        assert!(option.try_with_dividend_yields(vec![0.01; 3]).is_err());
+    }
+}
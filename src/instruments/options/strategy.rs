@@ -0,0 +1,567 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Option strategies built from option and stock legs: spreads, straddles,
+//! strangles, butterflies, condors and collars.
+//!
+//! All option legs are European and share one expiry. The profit and loss
+//! at expiry is piecewise linear in the underlying price, with kinks at the
+//! strikes, so breakevens and the maximum profit and loss are exact.
+//!
+//! ```
+//! use RustQuant::instruments::options::*;
+//!
+//! let market = StrategyMarket::new(100.0, 0.2, 0.05, 0.05, 0.25);
+//!
+//! // Long straddle at the money, bought at the Black-Scholes prices.
+//! let straddle = OptionStrategy::straddle(100.0).priced(&market);
+//!
+//! let breakevens = straddle.breakevens();
+//! assert_eq!(breakevens.len(), 2);
+//! assert!(breakevens[0] < 100.0 && breakevens[1] > 100.0);
+//! assert_eq!(straddle.max_profit(), f64::INFINITY);
+//! assert!((straddle.max_loss() - straddle.net_premium()).abs() < 1e-12);
+//!
+//! // Roughly delta-neutral, long gamma and vega.
+//! let greeks = straddle.greeks(&market);
+//! assert!(greeks.delta.abs() < 0.15 && greeks.gamma > 0.0 && greeks.vega > 0.0);
+//! ```
+
+use crate::instruments::options::{BlackScholesInputs, TypeFlag};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Instrument of a strategy leg.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LegKind {
+    /// European call with the given strike.
+    Call(f64),
+
+    /// European put with the given strike.
+    Put(f64),
+
+    /// The underlying itself.
+    Stock,
+}
+
+/// A position in an option or the underlying.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StrategyLeg {
+    /// Instrument.
+    pub kind: LegKind,
+
+    /// Number of units (negative if short).
+    pub quantity: f64,
+
+    /// Price paid (or received, if short) per unit.
+    pub premium: f64,
+}
+
+/// Combination of option and stock legs.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct OptionStrategy {
+    /// Legs of the strategy.
+    pub legs: Vec<StrategyLeg>,
+}
+
+/// Market inputs for valuing the option legs with the generalised
+/// Black-Scholes-Merton model.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StrategyMarket {
+    /// S - The underlying asset price.
+    pub underlying_price: f64,
+    /// sigma - The underlying asset's volatility.
+    pub volatility: f64,
+    /// r - The risk-free interest rate.
+    pub risk_free_rate: f64,
+    /// b - The cost of carry factor.
+    pub cost_of_carry: f64,
+    /// T - The time to expiry in years.
+    pub time_to_expiry: f64,
+}
+
+/// Value and Greeks of a strategy, summed over its legs.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct StrategyGreeks {
+    /// Model value.
+    pub value: f64,
+    /// Sensitivity to the underlying price.
+    pub delta: f64,
+    /// Sensitivity of the delta to the underlying price.
+    pub gamma: f64,
+    /// Sensitivity to the volatility.
+    pub vega: f64,
+    /// Sensitivity to the passage of time.
+    pub theta: f64,
+    /// Sensitivity to the risk-free rate, with the cost of carry moving
+    /// with it.
+    pub rho: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl StrategyLeg {
+    /// Call leg, with no premium.
+    pub fn call(strike: f64, quantity: f64) -> Self {
+        Self {
+            kind: LegKind::Call(strike),
+            quantity,
+            premium: 0.0,
+        }
+    }
+
+    /// Put leg, with no premium.
+    pub fn put(strike: f64, quantity: f64) -> Self {
+        Self {
+            kind: LegKind::Put(strike),
+            quantity,
+            premium: 0.0,
+        }
+    }
+
+    /// Stock leg bought (or sold) at `price`.
+    pub fn stock(quantity: f64, price: f64) -> Self {
+        Self {
+            kind: LegKind::Stock,
+            quantity,
+            premium: price,
+        }
+    }
+
+    /// Sets the premium per unit.
+    pub fn with_premium(mut self, premium: f64) -> Self {
+        self.premium = premium;
+        self
+    }
+
+    /// Strike of an option leg.
+    pub fn strike(&self) -> Option<f64> {
+        match self.kind {
+            LegKind::Call(strike) | LegKind::Put(strike) => Some(strike),
+            LegKind::Stock => None,
+        }
+    }
+
+    /// Value at expiry with the underlying at `price`.
+    pub fn payoff(&self, price: f64) -> f64 {
+        self.quantity
+            * match self.kind {
+                LegKind::Call(strike) => (price - strike).max(0.0),
+                LegKind::Put(strike) => (strike - price).max(0.0),
+                LegKind::Stock => price,
+            }
+    }
+
+    /// Value and Greeks of the leg.
+    pub fn greeks(&self, market: &StrategyMarket) -> StrategyGreeks {
+        let option_type = match self.kind {
+            LegKind::Call(_) => TypeFlag::Call,
+            LegKind::Put(_) => TypeFlag::Put,
+            LegKind::Stock => {
+                return StrategyGreeks {
+                    value: self.quantity * market.underlying_price,
+                    delta: self.quantity,
+                    ..StrategyGreeks::default()
+                }
+            }
+        };
+
+        let option = BlackScholesInputs {
+            underlying_price: market.underlying_price,
+            strike_price: self.strike().unwrap_or_default(),
+            volatility: market.volatility,
+            risk_free_rate: market.risk_free_rate,
+            cost_of_carry: market.cost_of_carry,
+            time_to_expiry: market.time_to_expiry,
+            option_type,
+        };
+
+        StrategyGreeks {
+            value: self.quantity * option.price(),
+            delta: self.quantity * option.delta(),
+            gamma: self.quantity * option.gamma(),
+            vega: self.quantity * option.vega(),
+            theta: self.quantity * option.theta(),
+            rho: self.quantity * option.rho(),
+        }
+    }
+}
+
+impl OptionStrategy {
+    /// Strategy without legs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a leg.
+    pub fn with_leg(mut self, leg: StrategyLeg) -> Self {
+        self.legs.push(leg);
+        self
+    }
+
+    /// Long call at `low`, short call at `high`.
+    pub fn bull_call_spread(low: f64, high: f64) -> Self {
+        Self::new()
+            .with_leg(StrategyLeg::call(low, 1.0))
+            .with_leg(StrategyLeg::call(high, -1.0))
+    }
+
+    /// Long put at `high`, short put at `low`.
+    pub fn bear_put_spread(low: f64, high: f64) -> Self {
+        Self::new()
+            .with_leg(StrategyLeg::put(high, 1.0))
+            .with_leg(StrategyLeg::put(low, -1.0))
+    }
+
+    /// Long call and long put at the same strike.
+    pub fn straddle(strike: f64) -> Self {
+        Self::new()
+            .with_leg(StrategyLeg::call(strike, 1.0))
+            .with_leg(StrategyLeg::put(strike, 1.0))
+    }
+
+    /// Long put at `low` and long call at `high`.
+    pub fn strangle(low: f64, high: f64) -> Self {
+        Self::new()
+            .with_leg(StrategyLeg::put(low, 1.0))
+            .with_leg(StrategyLeg::call(high, 1.0))
+    }
+
+    /// Long call butterfly: long calls at `low` and `high`, two short
+    /// calls at `middle`.
+    pub fn butterfly(low: f64, middle: f64, high: f64) -> Self {
+        Self::new()
+            .with_leg(StrategyLeg::call(low, 1.0))
+            .with_leg(StrategyLeg::call(middle, -2.0))
+            .with_leg(StrategyLeg::call(high, 1.0))
+    }
+
+    /// Short iron condor on strikes `k1 < k2 < k3 < k4`: a bull put spread
+    /// on `k1`/`k2` and a bear call spread on `k3`/`k4`.
+    pub fn iron_condor(k1: f64, k2: f64, k3: f64, k4: f64) -> Self {
+        Self::new()
+            .with_leg(StrategyLeg::put(k1, 1.0))
+            .with_leg(StrategyLeg::put(k2, -1.0))
+            .with_leg(StrategyLeg::call(k3, -1.0))
+            .with_leg(StrategyLeg::call(k4, 1.0))
+    }
+
+    /// Collar: the stock bought at `price`, protected by a long put at
+    /// `floor` and financed by a short call at `cap`.
+    pub fn collar(price: f64, floor: f64, cap: f64) -> Self {
+        Self::new()
+            .with_leg(StrategyLeg::stock(1.0, price))
+            .with_leg(StrategyLeg::put(floor, 1.0))
+            .with_leg(StrategyLeg::call(cap, -1.0))
+    }
+
+    /// Sets the premiums of the option legs to their model values.
+    pub fn priced(mut self, market: &StrategyMarket) -> Self {
+        for leg in self.legs.iter_mut() {
+            if leg.kind != LegKind::Stock {
+                leg.premium = leg.greeks(market).value / leg.quantity;
+            }
+        }
+        self
+    }
+
+    /// Cost of entering the strategy (negative for a net credit).
+    pub fn net_premium(&self) -> f64 {
+        self.legs.iter().map(|leg| leg.quantity * leg.premium).sum()
+    }
+
+    /// Value at expiry with the underlying at `price`.
+    pub fn payoff(&self, price: f64) -> f64 {
+        self.legs.iter().map(|leg| leg.payoff(price)).sum()
+    }
+
+    /// Profit and loss at expiry, net of the premiums.
+    pub fn profit(&self, price: f64) -> f64 {
+        self.payoff(price) - self.net_premium()
+    }
+
+    /// Profit and loss at expiry at `n` equally spaced underlying prices
+    /// from `low` to `high`.
+    pub fn profit_curve(&self, low: f64, high: f64, n: usize) -> Vec<(f64, f64)> {
+        assert!(n > 1, "At least two points are needed.");
+
+        (0..n)
+            .map(|i| {
+                let price = low + (high - low) * i as f64 / (n - 1) as f64;
+                (price, self.profit(price))
+            })
+            .collect()
+    }
+
+    /// Plots the payoff diagram (payoff and profit at expiry) for
+    /// underlying prices from `low` to `high` to a PNG or SVG file.
+    #[cfg(feature = "plot")]
+    pub fn plot_payoff(
+        &self,
+        path: impl AsRef<std::path::Path>,
+        low: f64,
+        high: f64,
+    ) -> Result<(), crate::plot::PlotError> {
+        let profit = self.profit_curve(low, high, 201);
+        let premium = self.net_premium();
+        let payoff = profit.iter().map(|(x, y)| (*x, y + premium)).collect();
+
+        crate::plot::Chart::new("Payoff at expiry")
+            .with_labels("Underlying price", "Value")
+            .with_series("Payoff", payoff)
+            .with_series("Profit", profit)
+            .save(path)
+    }
+
+    /// Underlying prices at which the profit at expiry is zero, in
+    /// increasing order.
+    pub fn breakevens(&self) -> Vec<f64> {
+        let nodes = self.nodes();
+        let mut roots: Vec<f64> = Vec::new();
+        let push = |root: f64, roots: &mut Vec<f64>| {
+            if roots.last().is_none_or(|last| (root - last).abs() > 1e-9) {
+                roots.push(root);
+            }
+        };
+
+        for pair in nodes.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            let (fa, fb) = (self.profit(a), self.profit(b));
+
+            if fa == 0.0 {
+                push(a, &mut roots);
+            } else if fa * fb < 0.0 {
+                push(a + (b - a) * fa / (fa - fb), &mut roots);
+            }
+        }
+
+        // Beyond the highest strike the profit is linear with slope m.
+        let last = nodes[nodes.len() - 1];
+        let (f, m) = (self.profit(last), self.terminal_slope());
+        if f == 0.0 {
+            push(last, &mut roots);
+        } else if f * m < 0.0 {
+            push(last - f / m, &mut roots);
+        }
+
+        roots
+    }
+
+    /// Highest profit at expiry (infinite if unbounded).
+    pub fn max_profit(&self) -> f64 {
+        match self.terminal_slope() > 1e-12 {
+            true => f64::INFINITY,
+            false => self
+                .nodes()
+                .into_iter()
+                .map(|price| self.profit(price))
+                .fold(f64::NEG_INFINITY, f64::max),
+        }
+    }
+
+    /// Largest loss at expiry, as a positive amount (infinite if unbounded).
+    pub fn max_loss(&self) -> f64 {
+        match self.terminal_slope() < -1e-12 {
+            true => f64::INFINITY,
+            false => -self
+                .nodes()
+                .into_iter()
+                .map(|price| self.profit(price))
+                .fold(f64::INFINITY, f64::min),
+        }
+    }
+
+    /// Value and Greeks, summed over the legs.
+    pub fn greeks(&self, market: &StrategyMarket) -> StrategyGreeks {
+        self.legs.iter().map(|leg| leg.greeks(market)).fold(
+            StrategyGreeks::default(),
+            |total, leg| StrategyGreeks {
+                value: total.value + leg.value,
+                delta: total.delta + leg.delta,
+                gamma: total.gamma + leg.gamma,
+                vega: total.vega + leg.vega,
+                theta: total.theta + leg.theta,
+                rho: total.rho + leg.rho,
+            },
+        )
+    }
+
+    // Zero and the sorted strikes: the profit is linear between them.
+    fn nodes(&self) -> Vec<f64> {
+        let mut nodes: Vec<f64> = std::iter::once(0.0)
+            .chain(self.legs.iter().filter_map(StrategyLeg::strike))
+            .collect();
+        nodes.sort_by(|a, b| a.total_cmp(b));
+        nodes.dedup();
+        nodes
+    }
+
+    // Slope of the profit above the highest strike.
+    fn terminal_slope(&self) -> f64 {
+        self.legs
+            .iter()
+            .filter(|leg| matches!(leg.kind, LegKind::Call(_) | LegKind::Stock))
+            .map(|leg| leg.quantity)
+            .sum()
+    }
+}
+
+impl StrategyMarket {
+    /// New market inputs.
+    pub fn new(
+        underlying_price: f64,
+        volatility: f64,
+        risk_free_rate: f64,
+        cost_of_carry: f64,
+        time_to_expiry: f64,
+    ) -> Self {
+        Self {
+            underlying_price,
+            volatility,
+            risk_free_rate,
+            cost_of_carry,
+            time_to_expiry,
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_strategy {
+    use super::*;
+
+    #[test]
+    fn test_spreads() {
+        let spread = OptionStrategy::new()
+            .with_leg(StrategyLeg::call(100.0, 1.0).with_premium(5.0))
+            .with_leg(StrategyLeg::call(110.0, -1.0).with_premium(2.0));
+
+        assert_eq!(spread.net_premium(), 3.0);
+        assert_eq!(spread.profit(90.0), -3.0);
+        assert_eq!(spread.profit(120.0), 7.0);
+        assert_eq!(spread.breakevens(), vec![103.0]);
+        assert_eq!(spread.max_profit(), 7.0);
+        assert_eq!(spread.max_loss(), 3.0);
+
+        let curve = spread.profit_curve(90.0, 120.0, 7);
+        assert_eq!(curve[3], (105.0, 2.0));
+
+        // The bear put spread pays off 10 below the low strike.
+        let spread = OptionStrategy::bear_put_spread(90.0, 100.0);
+        assert_eq!(spread.payoff(80.0), 10.0);
+        assert_eq!(spread.payoff(100.0), 0.0);
+    }
+
+    #[test]
+    fn test_volatility_strategies() {
+        let straddle = OptionStrategy::new()
+            .with_leg(StrategyLeg::call(100.0, 1.0).with_premium(4.0))
+            .with_leg(StrategyLeg::put(100.0, 1.0).with_premium(3.0));
+
+        assert_eq!(straddle.breakevens(), vec![93.0, 107.0]);
+        assert_eq!(straddle.max_loss(), 7.0);
+        assert_eq!(straddle.max_profit(), f64::INFINITY);
+
+        // Short strangle: unbounded loss above the call strike.
+        let mut strangle = OptionStrategy::strangle(90.0, 110.0);
+        for leg in strangle.legs.iter_mut() {
+            leg.quantity = -1.0;
+            leg.premium = 2.0;
+        }
+        assert_eq!(strangle.max_profit(), 4.0);
+        assert_eq!(strangle.max_loss(), f64::INFINITY);
+        assert_eq!(strangle.breakevens(), vec![86.0, 114.0]);
+
+        // The butterfly pays off most at the middle strike.
+        let butterfly = OptionStrategy::butterfly(90.0, 100.0, 110.0);
+        assert_eq!(butterfly.payoff(100.0), 10.0);
+        assert_eq!(butterfly.payoff(85.0), 0.0);
+        assert_eq!(butterfly.payoff(120.0), 0.0);
+        assert_eq!(butterfly.max_profit(), 10.0);
+    }
+
+    #[test]
+    fn test_condor_and_collar() {
+        let mut condor = OptionStrategy::iron_condor(80.0, 90.0, 110.0, 120.0);
+        for (leg, premium) in condor.legs.iter_mut().zip([1.0, 3.0, 3.0, 1.0]) {
+            leg.premium = premium;
+        }
+
+        // Credit of 4 kept between the short strikes, at most 6 lost.
+        assert_eq!(condor.net_premium(), -4.0);
+        assert_eq!(condor.max_profit(), 4.0);
+        assert_eq!(condor.max_loss(), 6.0);
+        assert_eq!(condor.breakevens(), vec![86.0, 114.0]);
+
+        // Zero-cost collar on stock bought at 100: P&L capped at +/-10.
+        let collar = OptionStrategy::collar(100.0, 90.0, 110.0);
+        assert_eq!(collar.max_profit(), 10.0);
+        assert_eq!(collar.max_loss(), 10.0);
+        assert_eq!(collar.profit(130.0), 10.0);
+        assert_eq!(collar.breakevens(), vec![100.0]);
+    }
+
+    #[test]
+    fn test_strategy_greeks() {
+        let market = StrategyMarket::new(100.0, 0.25, 0.04, 0.02, 0.5);
+        let strategy = OptionStrategy::iron_condor(80.0, 90.0, 110.0, 120.0)
+            .with_leg(StrategyLeg::stock(0.3, 100.0));
+        let greeks = strategy.greeks(&market);
+
+        // Central finite differences of the strategy value.
+        let value = |market: StrategyMarket| strategy.greeks(&market).value;
+        let h = 1e-4;
+        let bump = |f: fn(&mut StrategyMarket, f64)| {
+            let (mut up, mut down) = (market, market);
+            f(&mut up, h);
+            f(&mut down, -h);
+            (value(up) - value(down)) / (2.0 * h)
+        };
+
+        assert_approx_equal!(greeks.delta, bump(|m, h| m.underlying_price += h), 1e-6);
+        assert_approx_equal!(greeks.vega, bump(|m, h| m.volatility += h), 1e-6);
+        assert_approx_equal!(greeks.theta, -bump(|m, h| m.time_to_expiry += h), 1e-6);
+        assert_approx_equal!(
+            greeks.rho,
+            bump(|m, h| {
+                m.risk_free_rate += h;
+                m.cost_of_carry += h;
+            }),
+            1e-6
+        );
+
+        let gamma = (value(StrategyMarket::new(100.0 + h, 0.25, 0.04, 0.02, 0.5))
+            - 2.0 * greeks.value
+            + value(StrategyMarket::new(100.0 - h, 0.25, 0.04, 0.02, 0.5)))
+            / (h * h);
+        assert_approx_equal!(greeks.gamma, gamma, 1e-4);
+
+        // Priced at the model, the strategy costs its model value.
+        let priced = strategy.priced(&market);
+        assert_approx_equal!(priced.net_premium(), greeks.value, 1e-12);
+    }
+
+    #[cfg(feature = "plot")]
+    #[test]
+    fn test_strategy_plot_payoff() {
+        let strategy = OptionStrategy::straddle(100.0);
+        let path = std::env::temp_dir().join("rustquant_straddle.svg");
+
+        strategy.plot_payoff(&path, 80.0, 120.0).unwrap();
+        assert!(std::fs::metadata(&path).is_ok());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
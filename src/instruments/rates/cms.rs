@@ -0,0 +1,713 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Constant maturity swap (CMS) coupons, caps and floors.
+//!
+//! A CMS coupon pays a swap rate $S$ fixed at $T_f$ on a later date $T_p$.
+//! Its value is $A_0 \, E^A[f(S) \, G(S)]$, where $A_0$ is the annuity of
+//! the underlying swap, $E^A$ the expectation under the annuity measure (in
+//! which $S$ is lognormal with the swaption volatility), and $G(S)$ maps the
+//! swap rate to $P(T_f, T_p) / A(T_f)$. $G$ is Hagan's "standard model":
+//! the ratio for a flat curve at yield $S$, scaled so that its expectation
+//! is today's ratio $P(0, T_p) / A_0$ (for the linear approximation, so
+//! that it takes that value at the forward swap rate).
+//!
+//! The expectation is computed either from a linear approximation of $G$
+//! around the forward ([`ConvexityMethod::Hagan`]), or by statically
+//! replicating $f(S) \, G(S)$ with swaptions across strikes
+//! ([`ConvexityMethod::Replication`]), which uses the whole smile.
+//!
+//! References:
+//! - Hagan, P. (2003), *Convexity Conundrums: Pricing CMS Swaps, Caps, and
+//!   Floors*, Wilmott Magazine.
+
+use crate::curves::{annuity, par_swap_rate, swap_schedule, Curve, Surface, SwapCurves};
+use crate::instruments::{PricingEngine, PricingResult};
+use crate::math::integrate::GaussKronrod;
+use crate::statistics::distributions::{Distribution, Gaussian};
+use crate::time::{
+    BusinessDayConvention, DayCounter, PaymentFrequency, Schedule, StubRule, Tenor, WeekendsOnly,
+};
+use time::OffsetDateTime;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Method of computing the convexity adjustment of CMS rates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConvexityMethod {
+    /// Hagan's closed form, linearising the annuity mapping around the
+    /// forward swap rate, with the swaption volatility at the strike.
+    Hagan,
+
+    /// Static replication with swaptions at all strikes, using the smile
+    /// of the volatility surface.
+    Replication,
+}
+
+/// Payoff of a CMS coupon.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CmsPayoff {
+    /// The swap rate.
+    Swaplet,
+
+    /// The swap rate above a strike.
+    Caplet(f64),
+
+    /// The swap rate below a strike.
+    Floorlet(f64),
+}
+
+/// A coupon paying a payoff of the swap rate of `swap_tenor`, fixed (and
+/// starting) on `fixing_date`, on `payment_date`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CmsCoupon {
+    /// Date the swap rate is fixed on (also the start of the swap).
+    pub fixing_date: OffsetDateTime,
+
+    /// Date the coupon is paid on.
+    pub payment_date: OffsetDateTime,
+
+    /// Accrual fraction of the coupon.
+    pub accrual_fraction: f64,
+
+    /// Notional of the coupon.
+    pub notional: f64,
+
+    /// Tenor of the underlying swap (e.g. 10Y).
+    pub swap_tenor: Tenor,
+
+    /// Fixed leg frequency of the underlying swap.
+    pub swap_frequency: PaymentFrequency,
+
+    /// What the coupon pays.
+    pub payoff: CmsPayoff,
+}
+
+/// A strip of CMS caplets or floorlets (or of plain CMS coupons).
+#[derive(Debug, Clone, PartialEq)]
+pub struct CmsLeg {
+    /// The coupons, in payment order.
+    pub coupons: Vec<CmsCoupon>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// Upper limit of the replication integrals, in standard deviations of the
+// log swap rate above the forward.
+const REPLICATION_STANDARD_DEVIATIONS: f64 = 10.0;
+
+// Hagan's standard annuity mapping, normalised to today's curves.
+struct AnnuityMapping {
+    periods: f64,
+    frequency: f64,
+    payment_lag: f64,
+    scale: f64,
+    ratio: f64,
+}
+
+impl AnnuityMapping {
+    fn new(periods: usize, frequency: f64, payment_lag: f64, forward: f64, ratio: f64) -> Self {
+        let mut mapping = Self {
+            periods: periods as f64,
+            frequency,
+            payment_lag,
+            scale: 1.0,
+            ratio,
+        };
+        mapping.scale = ratio / mapping.value(forward);
+
+        mapping
+    }
+
+    // S (1 + S/q)^(-q lag) / (1 - (1 + S/q)^(-n)), which tends to
+    // q / n as S goes to zero.
+    fn value(&self, rate: f64) -> f64 {
+        let log_growth = (rate / self.frequency).ln_1p();
+        let level = if rate.abs() < 1e-10 {
+            self.frequency / self.periods
+        } else {
+            -rate / (-self.periods * log_growth).exp_m1()
+        };
+
+        self.scale * level * (-self.frequency * self.payment_lag * log_growth).exp()
+    }
+
+    fn derivative(&self, rate: f64) -> f64 {
+        let h = 1e-5;
+
+        (self.value(rate + h) - self.value(rate - h)) / (2.0 * h)
+    }
+
+    fn second_derivative(&self, rate: f64) -> f64 {
+        let h = 1e-4;
+
+        (self.value(rate + h) - 2.0 * self.value(rate) + self.value(rate - h)) / (h * h)
+    }
+}
+
+// Undiscounted Black call and put on the swap rate, and the second moments
+// of their payoffs.
+struct Black {
+    forward: f64,
+    variance: f64,
+}
+
+impl Black {
+    fn d1_d2(&self, strike: f64) -> (f64, f64) {
+        let d1 = ((self.forward / strike).ln() + 0.5 * self.variance) / self.variance.sqrt();
+
+        (d1, d1 - self.variance.sqrt())
+    }
+
+    fn call(&self, strike: f64) -> f64 {
+        if strike <= 0.0 {
+            return self.forward - strike;
+        }
+        let N = Gaussian::default();
+        let (d1, d2) = self.d1_d2(strike);
+
+        self.forward * N.cdf(d1) - strike * N.cdf(d2)
+    }
+
+    fn put(&self, strike: f64) -> f64 {
+        self.call(strike) - self.forward + strike
+    }
+
+    // E[((S - K)^+)^2] and E[((K - S)^+)^2].
+    fn call_second_moment(&self, strike: f64) -> f64 {
+        let N = Gaussian::default();
+        let (d1, d2) = self.d1_d2(strike);
+        let f = self.forward;
+
+        f * f * self.variance.exp() * N.cdf(d1 + self.variance.sqrt())
+            - 2.0 * strike * f * N.cdf(d1)
+            + strike * strike * N.cdf(d2)
+    }
+
+    fn put_second_moment(&self, strike: f64) -> f64 {
+        let N = Gaussian::default();
+        let (d1, d2) = self.d1_d2(strike);
+        let f = self.forward;
+
+        strike * strike * N.cdf(-d2) - 2.0 * strike * f * N.cdf(-d1)
+            + f * f * self.variance.exp() * N.cdf(-d1 - self.variance.sqrt())
+    }
+}
+
+impl CmsCoupon {
+    /// New CMS coupon with unit notional and accrual, paying the swap rate.
+    pub fn new(
+        fixing_date: OffsetDateTime,
+        payment_date: OffsetDateTime,
+        swap_tenor: Tenor,
+        swap_frequency: PaymentFrequency,
+    ) -> Self {
+        Self {
+            fixing_date,
+            payment_date,
+            accrual_fraction: 1.0,
+            notional: 1.0,
+            swap_tenor,
+            swap_frequency,
+            payoff: CmsPayoff::Swaplet,
+        }
+    }
+
+    /// Sets the accrual fraction.
+    pub fn with_accrual_fraction(mut self, accrual_fraction: f64) -> Self {
+        self.accrual_fraction = accrual_fraction;
+        self
+    }
+
+    /// Sets the notional.
+    pub fn with_notional(mut self, notional: f64) -> Self {
+        self.notional = notional;
+        self
+    }
+
+    /// Sets the payoff.
+    pub fn with_payoff(mut self, payoff: CmsPayoff) -> Self {
+        self.payoff = payoff;
+        self
+    }
+
+    /// Forward swap rate of the underlying swap, without convexity
+    /// adjustment.
+    pub fn forward_swap_rate<C: Curve>(&self, curves: &SwapCurves<C>) -> f64 {
+        par_swap_rate(
+            self.fixing_date,
+            self.swap_tenor,
+            self.swap_frequency,
+            curves,
+        )
+    }
+
+    /// Expected swap rate under the measure of the payment date, i.e. the
+    /// forward swap rate plus its convexity (and payment timing)
+    /// adjustment, with swaption volatilities from `volatilities` by
+    /// (expiry, strike).
+    ///
+    /// # Panics
+    ///
+    /// Panics if a date is outside the curves or the volatility surface.
+    pub fn adjusted_rate<C: Curve, V: Surface>(
+        &self,
+        curves: &SwapCurves<C>,
+        volatilities: &V,
+        method: ConvexityMethod,
+    ) -> f64 {
+        self.expectation(CmsPayoff::Swaplet, curves, volatilities, method)
+            / self.mapping(curves).0.ratio
+    }
+
+    /// Convexity adjustment: the adjusted rate less the forward swap rate.
+    pub fn convexity_adjustment<C: Curve, V: Surface>(
+        &self,
+        curves: &SwapCurves<C>,
+        volatilities: &V,
+        method: ConvexityMethod,
+    ) -> f64 {
+        self.adjusted_rate(curves, volatilities, method) - self.forward_swap_rate(curves)
+    }
+
+    /// Present value of the coupon.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a date is outside the curves or the volatility surface.
+    pub fn price<C: Curve, V: Surface>(
+        &self,
+        curves: &SwapCurves<C>,
+        volatilities: &V,
+        method: ConvexityMethod,
+    ) -> PricingResult {
+        let (_, annuity) = self.mapping(curves);
+        let expectation = self.expectation(self.payoff, curves, volatilities, method);
+
+        PricingResult::new(self.notional * self.accrual_fraction * annuity * expectation)
+            .with_engine(match method {
+                ConvexityMethod::Hagan => PricingEngine::Analytic,
+                ConvexityMethod::Replication => PricingEngine::Numerical,
+            })
+    }
+
+    // Annuity mapping of the coupon and the annuity of its swap.
+    fn mapping<C: Curve>(&self, curves: &SwapCurves<C>) -> (AnnuityMapping, f64) {
+        let annuity = annuity(
+            self.fixing_date,
+            self.swap_tenor,
+            self.swap_frequency,
+            curves,
+        );
+        let periods = swap_schedule(self.fixing_date, self.swap_tenor, self.swap_frequency)
+            .periods
+            .len();
+        let payment_lag = curves
+            .discount
+            .day_count_convention()
+            .year_fraction(self.fixing_date, self.payment_date);
+        let ratio = curves.discount.discount_factor(self.payment_date) / annuity;
+
+        let mapping = AnnuityMapping::new(
+            periods,
+            self.swap_frequency as i32 as f64,
+            payment_lag,
+            self.forward_swap_rate(curves),
+            ratio,
+        );
+
+        (mapping, annuity)
+    }
+
+    // E^A[f(S) G(S)] for the payoff f.
+    fn expectation<C: Curve, V: Surface>(
+        &self,
+        payoff: CmsPayoff,
+        curves: &SwapCurves<C>,
+        volatilities: &V,
+        method: ConvexityMethod,
+    ) -> f64 {
+        let (mut g, _) = self.mapping(curves);
+        let forward = self.forward_swap_rate(curves);
+        let expiry = curves
+            .discount
+            .day_count_convention()
+            .year_fraction(curves.discount.initial_date(), self.fixing_date)
+            .max(0.0);
+        let black = |strike: f64| {
+            let volatility = volatilities.value(self.fixing_date, strike.max(0.0));
+
+            Black {
+                forward,
+                variance: volatility * volatility * expiry,
+            }
+        };
+
+        match method {
+            ConvexityMethod::Hagan => {
+                let (g0, dg) = (g.value(forward), g.derivative(forward));
+
+                match payoff {
+                    CmsPayoff::Swaplet => {
+                        g0 * forward + dg * forward * forward * black(forward).variance.exp_m1()
+                    }
+                    CmsPayoff::Caplet(strike) => {
+                        let model = black(strike);
+                        let call = model.call(strike);
+
+                        g0 * call
+                            + dg * (model.call_second_moment(strike) + (strike - forward) * call)
+                    }
+                    CmsPayoff::Floorlet(strike) => {
+                        let model = black(strike);
+                        let put = model.put(strike);
+
+                        g0 * put + dg * ((strike - forward) * put - model.put_second_moment(strike))
+                    }
+                }
+            }
+            ConvexityMethod::Replication => {
+                let quadrature = GaussKronrod::new(1e-12, 200);
+                let upper = forward
+                    * (REPLICATION_STANDARD_DEVIATIONS * black(forward).variance.sqrt()).exp();
+                let calls = |h: &dyn Fn(f64) -> f64, from: f64| {
+                    quadrature
+                        .integrate(|k| h(k) * black(k).call(k), from, upper.max(from))
+                        .value
+                };
+                let puts = |h: &dyn Fn(f64) -> f64, to: f64| {
+                    quadrature
+                        .integrate(|k| h(k) * black(k).put(k), 0.0, to)
+                        .value
+                };
+
+                // Rescale the mapping so that E^A[G(S)] is today's ratio,
+                // which keeps caplets, floorlets and swaplets at parity.
+                let mean = {
+                    let g2 = |k: f64| g.second_derivative(k);
+                    g.value(forward) + puts(&g2, forward) + calls(&g2, forward)
+                };
+                g.scale *= g.ratio / mean;
+
+                match payoff {
+                    // h(S) = S G(S), split into puts below and calls above
+                    // the forward.
+                    CmsPayoff::Swaplet => {
+                        let h2 = |k: f64| 2.0 * g.derivative(k) + k * g.second_derivative(k);
+
+                        forward * g.value(forward) + puts(&h2, forward) + calls(&h2, forward)
+                    }
+                    // h(S) = (S - K) G(S) above the strike.
+                    CmsPayoff::Caplet(strike) => {
+                        let h2 =
+                            |k: f64| 2.0 * g.derivative(k) + (k - strike) * g.second_derivative(k);
+                        let strike = strike.max(0.0);
+
+                        g.value(strike) * black(strike).call(strike) + calls(&h2, strike)
+                    }
+                    // h(S) = (K - S) G(S) below the strike.
+                    CmsPayoff::Floorlet(strike) => {
+                        if strike <= 0.0 {
+                            return 0.0;
+                        }
+                        let h2 =
+                            |k: f64| -2.0 * g.derivative(k) + (strike - k) * g.second_derivative(k);
+
+                        g.value(strike) * black(strike).put(strike) + puts(&h2, strike)
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl CmsLeg {
+    /// Leg of coupons from `start` to `end` paid at `frequency` in arrears
+    /// (each fixing at the start of its period), on the swap rate of
+    /// `swap_tenor`, with accrual fractions under the day count of `curve`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new<C: Curve>(
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+        frequency: PaymentFrequency,
+        swap_tenor: Tenor,
+        swap_frequency: PaymentFrequency,
+        payoff: CmsPayoff,
+        notional: f64,
+        curve: &C,
+    ) -> Self {
+        let schedule = Schedule::new(
+            start,
+            end,
+            frequency,
+            &WeekendsOnly,
+            BusinessDayConvention::ModifiedFollowing,
+            StubRule::ShortFront,
+        );
+        let day_count = curve.day_count_convention();
+
+        let coupons = schedule
+            .periods
+            .iter()
+            .map(|period| {
+                CmsCoupon::new(period.start, period.payment, swap_tenor, swap_frequency)
+                    .with_accrual_fraction(day_count.year_fraction(period.start, period.end))
+                    .with_notional(notional)
+                    .with_payoff(payoff)
+            })
+            .collect();
+
+        Self { coupons }
+    }
+
+    /// CMS cap: caplets at `strike`.
+    pub fn cap<C: Curve>(
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+        frequency: PaymentFrequency,
+        swap_tenor: Tenor,
+        strike: f64,
+        curve: &C,
+    ) -> Self {
+        Self::new(
+            start,
+            end,
+            frequency,
+            swap_tenor,
+            frequency,
+            CmsPayoff::Caplet(strike),
+            1.0,
+            curve,
+        )
+    }
+
+    /// CMS floor: floorlets at `strike`.
+    pub fn floor<C: Curve>(
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+        frequency: PaymentFrequency,
+        swap_tenor: Tenor,
+        strike: f64,
+        curve: &C,
+    ) -> Self {
+        Self::new(
+            start,
+            end,
+            frequency,
+            swap_tenor,
+            frequency,
+            CmsPayoff::Floorlet(strike),
+            1.0,
+            curve,
+        )
+    }
+
+    /// Present value of the leg: the sum of its coupons' values.
+    pub fn price<C: Curve, V: Surface>(
+        &self,
+        curves: &SwapCurves<C>,
+        volatilities: &V,
+        method: ConvexityMethod,
+    ) -> PricingResult {
+        let value = self
+            .coupons
+            .iter()
+            .map(|coupon| coupon.price(curves, volatilities, method).value())
+            .sum();
+
+        PricingResult::new(value).with_engine(match method {
+            ConvexityMethod::Hagan => PricingEngine::Analytic,
+            ConvexityMethod::Replication => PricingEngine::Numerical,
+        })
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_cms {
+    use super::*;
+    use crate::curves::{VolatilitySurface, YieldCurve};
+    use time::macros::datetime;
+
+    const TODAY: OffsetDateTime = datetime!(2024-01-15 0:00 UTC);
+
+    fn curve() -> YieldCurve {
+        YieldCurve::from_tenors_and_rates(
+            TODAY,
+            &[Tenor::days(0), Tenor::years(5), Tenor::years(40)],
+            &[0.03, 0.035, 0.04],
+        )
+    }
+
+    // Flat swaption volatility, or a skew falling with the strike.
+    fn volatilities(atm: f64, skew: f64) -> VolatilitySurface<YieldCurve> {
+        let smile = |strike: f64| {
+            let volatility = atm - skew * (strike - 0.04) / 0.01;
+            let term = YieldCurve::from_tenors_and_rates(
+                TODAY,
+                &[Tenor::days(0), Tenor::years(40)],
+                &[volatility; 2],
+            );
+
+            (strike, term)
+        };
+
+        VolatilitySurface::new([0.01, 0.02, 0.03, 0.04, 0.05, 0.06, 0.08].map(smile))
+    }
+
+    fn coupon() -> CmsCoupon {
+        // 10Y CMS rate fixing in 5Y, paid a year later.
+        CmsCoupon::new(
+            TODAY + Tenor::years(5),
+            TODAY + Tenor::years(6),
+            Tenor::years(10),
+            PaymentFrequency::Annually,
+        )
+    }
+
+    #[test]
+    fn test_convexity_adjustment() {
+        let curve = curve();
+        let curves = SwapCurves::single(&curve);
+        let coupon = coupon();
+
+        // No volatility, no adjustment.
+        let flat = volatilities(1e-6, 0.0);
+        for method in [ConvexityMethod::Hagan, ConvexityMethod::Replication] {
+            assert_approx_equal!(
+                coupon.convexity_adjustment(&curves, &flat, method),
+                0.0,
+                1e-8
+            );
+        }
+
+        // With 20% volatility the adjustment is some tens of basis points,
+        // and the two methods agree on a flat smile.
+        let flat = volatilities(0.2, 0.0);
+        let hagan = coupon.convexity_adjustment(&curves, &flat, ConvexityMethod::Hagan);
+        let replication = coupon.convexity_adjustment(&curves, &flat, ConvexityMethod::Replication);
+
+        assert!(hagan > 0.001 && hagan < 0.01);
+        assert_approx_equal!(replication, hagan, 0.0005);
+
+        // The adjustment grows with the volatility, and shrinks when the
+        // coupon is paid later than the rate fixes.
+        let high = volatilities(0.3, 0.0);
+        assert!(coupon.convexity_adjustment(&curves, &high, ConvexityMethod::Hagan) > hagan);
+
+        let delayed = CmsCoupon {
+            payment_date: TODAY + Tenor::years(10),
+            ..coupon
+        };
+        assert!(delayed.convexity_adjustment(&curves, &flat, ConvexityMethod::Hagan) < hagan);
+
+        // Replication picks up the smile, which Hagan's at-the-money
+        // formula ignores.
+        let skewed = volatilities(0.2, 0.02);
+        assert!(
+            coupon.convexity_adjustment(&curves, &skewed, ConvexityMethod::Replication)
+                != replication
+        );
+    }
+
+    #[test]
+    fn test_cap_floor_parity() {
+        let curve = curve();
+        let curves = SwapCurves::single(&curve);
+        let strike = 0.045;
+
+        // Hagan's swaplet takes the at-the-money volatility and its
+        // caplets the volatility at the strike, so parity holds on a flat
+        // smile only; replication prices every payoff off the same smile.
+        for (method, volatilities) in [
+            (ConvexityMethod::Hagan, volatilities(0.2, 0.0)),
+            (ConvexityMethod::Replication, volatilities(0.2, 0.01)),
+        ] {
+            let price = |payoff| {
+                coupon()
+                    .with_payoff(payoff)
+                    .price(&curves, &volatilities, method)
+            };
+            let caplet = price(CmsPayoff::Caplet(strike)).value();
+            let floorlet = price(CmsPayoff::Floorlet(strike)).value();
+            let swaplet = price(CmsPayoff::Swaplet).value();
+
+            // Caplet - floorlet = swaplet - strike paid on the payment date.
+            let fixed = strike * curve.discount_factor(coupon().payment_date);
+            assert_approx_equal!(caplet - floorlet, swaplet - fixed, 1e-7);
+            assert!(caplet > 0.0 && floorlet > 0.0);
+
+            // The swaplet pays the adjusted rate on the payment date.
+            let adjusted = coupon().adjusted_rate(&curves, &volatilities, method);
+            assert_approx_equal!(
+                swaplet,
+                adjusted * curve.discount_factor(coupon().payment_date),
+                1e-10
+            );
+        }
+    }
+
+    #[test]
+    fn test_cms_leg() {
+        let curve = curve();
+        let curves = SwapCurves::single(&curve);
+        let volatilities = volatilities(0.2, 0.0);
+        let (start, end) = (TODAY + Tenor::years(1), TODAY + Tenor::years(5));
+
+        let cap = CmsLeg::cap(
+            start,
+            end,
+            PaymentFrequency::Annually,
+            Tenor::years(10),
+            0.035,
+            &curve,
+        );
+        let floor = CmsLeg::floor(
+            start,
+            end,
+            PaymentFrequency::Annually,
+            Tenor::years(10),
+            0.035,
+            &curve,
+        );
+
+        assert_eq!(cap.coupons.len(), 4);
+        assert_eq!(cap.coupons[0].fixing_date, start);
+
+        let cap_price = cap.price(&curves, &volatilities, ConvexityMethod::Hagan);
+        let floor_price = floor.price(&curves, &volatilities, ConvexityMethod::Hagan);
+
+        // Forward swap rates (of about 3.8%) above the strike make the cap
+        // dearer than the floor.
+        assert!(cap_price.value() > floor_price.value() && floor_price.value() > 0.0);
+        assert_eq!(cap_price.engine, Some(PricingEngine::Analytic));
+
+        // A cap struck far above the rates is worthless.
+        let far = CmsLeg::cap(
+            start,
+            end,
+            PaymentFrequency::Annually,
+            Tenor::years(10),
+            1.0,
+            &curve,
+        );
+        assert!(
+            far.price(&curves, &volatilities, ConvexityMethod::Hagan)
+                .value()
+                < 1e-10
+        );
+    }
+}
@@ -0,0 +1,468 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Autocallable, Phoenix and reverse convertible notes on a single
+//! underlying.
+//!
+//! On each observation date $t_i$ the performance $S_{t_i} / S_0$ is
+//! compared with:
+//!
+//! - the coupon barrier: at or above it the note pays its coupon (plus the
+//!   coupons missed since the last one, for a Phoenix note with memory);
+//! - the autocall barrier: at or above it the note is redeemed early at
+//!   par.
+//!
+//! At maturity the notional is repaid in full, unless the knock-in barrier
+//! was breached and the underlying ends below the put strike, in which case
+//! the holder bears the loss of a put: the redemption is
+//! $N \cdot S_T / (k S_0)$ for a put strike $k$.
+//!
+//! A reverse convertible is the special case without autocall, with an
+//! unconditional coupon (zero coupon barrier).
+
+use crate::error::RustQuantError;
+use crate::instruments::{PricingEngine, PricingResult};
+use crate::stochastics::{SimulationConfig, StochasticProcess, Trajectories};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// When the knock-in barrier of the embedded put is monitored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KnockInMonitoring {
+    /// Only the final level counts (European barrier).
+    #[default]
+    Maturity,
+
+    /// Every point of the simulated path counts (e.g. daily closes).
+    Continuous,
+}
+
+/// Knock-in put sold by the holder of a note.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KnockInPut {
+    /// Barrier, as a fraction of the initial price.
+    pub barrier: f64,
+
+    /// Strike, as a fraction of the initial price.
+    pub strike: f64,
+
+    /// When the barrier is monitored.
+    pub monitoring: KnockInMonitoring,
+}
+
+/// Structured note on a single underlying, built with
+/// [`StructuredNote::builder`](crate::instruments::structured::StructuredNoteBuilder).
+///
+/// Barriers are fractions of the initial price, and observation times are
+/// in years from the trade date; the last observation is the maturity.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructuredNote {
+    /// Notional (redemption amount at par).
+    pub notional: f64,
+
+    /// Initial (strike-setting) price of the underlying.
+    pub initial_price: f64,
+
+    /// Observation times, in years, increasing.
+    pub observation_times: Vec<f64>,
+
+    /// Coupon paid per observation, as a fraction of the notional.
+    pub coupon: f64,
+
+    /// Coupon barrier at each observation.
+    pub coupon_barriers: Vec<f64>,
+
+    /// Whether missed coupons are paid later, when a coupon barrier is met
+    /// (Phoenix memory feature).
+    pub memory: bool,
+
+    /// Autocall barrier at each observation (infinite where the note cannot
+    /// be called). The last one is not used, as the note matures then.
+    pub autocall_barriers: Vec<f64>,
+
+    /// Knock-in put, if the capital is at risk.
+    pub knock_in: Option<KnockInPut>,
+}
+
+/// Price and event probabilities of a [`StructuredNote`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructuredNoteResult {
+    /// Present value of the note, with its Monte Carlo standard error.
+    pub price: PricingResult,
+
+    /// Probability of a coupon being paid at each observation.
+    pub coupon_probabilities: Vec<f64>,
+
+    /// Probability of the note being called at each observation.
+    pub autocall_probabilities: Vec<f64>,
+
+    /// Probability of the note reaching maturity with the put knocked in
+    /// and in the money, i.e. of a capital loss.
+    pub loss_probability: f64,
+
+    /// Expected life of the note in years.
+    pub expected_life: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl KnockInPut {
+    /// Put struck at the initial price, with the given barrier.
+    pub fn new(barrier: f64, monitoring: KnockInMonitoring) -> Self {
+        Self {
+            barrier,
+            strike: 1.0,
+            monitoring,
+        }
+    }
+
+    /// Sets the strike, as a fraction of the initial price.
+    pub fn with_strike(mut self, strike: f64) -> Self {
+        self.strike = strike;
+        self
+    }
+}
+
+impl StructuredNote {
+    /// Maturity of the note, in years.
+    pub fn maturity(&self) -> f64 {
+        *self
+            .observation_times
+            .last()
+            .expect("A note has at least one observation.")
+    }
+
+    /// Prices the note by simulating the underlying with `process` (from
+    /// the initial price, under the pricing measure) on `n_steps` equal
+    /// steps to maturity, and discounting at the continuously compounded
+    /// `rate`.
+    ///
+    /// Each observation uses the nearest step, so `n_steps` should be a
+    /// multiple of the number of observations for evenly spaced dates (and
+    /// large enough for continuous knock-in monitoring).
+    pub fn price<P>(
+        &self,
+        process: &P,
+        rate: f64,
+        n_steps: usize,
+        n_paths: usize,
+        config: &SimulationConfig,
+    ) -> Result<StructuredNoteResult, RustQuantError>
+    where
+        P: StochasticProcess + Sync,
+    {
+        crate::error::ensure(n_steps > 0, "At least one step is needed.")?;
+        crate::error::ensure(n_paths > 1, "At least two paths are needed.")?;
+
+        let paths = process.simulate_with_config(
+            self.initial_price,
+            0.0,
+            self.maturity(),
+            n_steps,
+            n_paths,
+            config,
+        );
+
+        Ok(self.price_paths(&paths, rate))
+    }
+
+    /// Prices the note on simulated paths of the underlying (starting at
+    /// the initial price at time zero), e.g. from a model that is not a
+    /// [`StochasticProcess`].
+    pub fn price_paths(&self, paths: &Trajectories, rate: f64) -> StructuredNoteResult {
+        let times = &paths.times;
+        let indices = self
+            .observation_times
+            .iter()
+            .map(|t| nearest_index(times, *t))
+            .collect::<Vec<_>>();
+
+        let n = self.observation_times.len();
+        let mut coupon_counts = vec![0usize; n];
+        let mut autocall_counts = vec![0usize; n];
+        let mut losses = 0usize;
+        let mut life = 0.0;
+
+        let values = paths
+            .iter()
+            .map(|path| {
+                let outcome = self.evaluate(&path.to_vec(), &indices, rate);
+
+                for i in &outcome.coupons {
+                    coupon_counts[*i] += 1;
+                }
+                match outcome.called_at {
+                    Some(i) => autocall_counts[i] += 1,
+                    None if outcome.loss => losses += 1,
+                    None => {}
+                }
+                life += self.observation_times[outcome.called_at.unwrap_or(n - 1)];
+
+                outcome.value
+            })
+            .collect::<Vec<_>>();
+
+        let m = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / m;
+        let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (m - 1.0);
+        let probability = |count: &usize| *count as f64 / m;
+
+        StructuredNoteResult {
+            price: PricingResult::new(mean)
+                .with_standard_error((variance / m).sqrt())
+                .with_engine(PricingEngine::Simulation),
+            coupon_probabilities: coupon_counts.iter().map(probability).collect(),
+            autocall_probabilities: autocall_counts.iter().map(probability).collect(),
+            loss_probability: probability(&losses),
+            expected_life: life / m,
+        }
+    }
+
+    /// Cash flows of one path, discounted, with the observations at which
+    /// coupons were paid and the note was called.
+    fn evaluate(&self, path: &[f64], indices: &[usize], rate: f64) -> PathOutcome {
+        let n = indices.len();
+        let discount = |t: f64| (-rate * t).exp();
+
+        let mut outcome = PathOutcome::default();
+        let mut missed = 0.0;
+
+        for (i, &k) in indices.iter().enumerate() {
+            let t = self.observation_times[i];
+            let performance = path[k] / self.initial_price;
+
+            if performance >= self.coupon_barriers[i] {
+                let coupons = if self.memory { 1.0 + missed } else { 1.0 };
+
+                outcome.value += self.notional * self.coupon * coupons * discount(t);
+                outcome.coupons.push(i);
+                missed = 0.0;
+            } else {
+                missed += 1.0;
+            }
+
+            if i + 1 < n && performance >= self.autocall_barriers[i] {
+                outcome.value += self.notional * discount(t);
+                outcome.called_at = Some(i);
+
+                return outcome;
+            }
+        }
+
+        let performance = path[indices[n - 1]] / self.initial_price;
+        let redemption = match self.knock_in {
+            Some(put)
+                if performance < put.strike && self.knocked_in(&put, &path[..=indices[n - 1]]) =>
+            {
+                outcome.loss = true;
+                performance / put.strike
+            }
+            _ => 1.0,
+        };
+
+        outcome.value += self.notional * redemption * discount(self.maturity());
+        outcome
+    }
+
+    fn knocked_in(&self, put: &KnockInPut, path: &[f64]) -> bool {
+        let level = put.barrier * self.initial_price;
+
+        match put.monitoring {
+            KnockInMonitoring::Maturity => path.last().is_some_and(|price| *price < level),
+            KnockInMonitoring::Continuous => path.iter().any(|price| *price < level),
+        }
+    }
+}
+
+/// What happened on one simulated path.
+#[derive(Debug, Default)]
+struct PathOutcome {
+    value: f64,
+    coupons: Vec<usize>,
+    called_at: Option<usize>,
+    loss: bool,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Index of the time point nearest to `t`.
+fn nearest_index(times: &[f64], t: f64) -> usize {
+    let i = times.partition_point(|time| *time < t).min(times.len() - 1);
+
+    match i > 0 && (t - times[i - 1]) < (times[i] - t) {
+        true => i - 1,
+        false => i,
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_structured_note {
+    use super::*;
+    use crate::instruments::options::{BlackScholesInputs, TypeFlag};
+    use crate::stochastics::GeometricBrownianMotion;
+
+    const R: f64 = 0.03;
+    const SIGMA: f64 = 0.25;
+
+    fn quarterly(years: usize) -> Vec<f64> {
+        (1..=4 * years).map(|i| i as f64 / 4.0).collect()
+    }
+
+    fn price(note: &StructuredNote) -> StructuredNoteResult {
+        let config = SimulationConfig::new(true).with_seed(23);
+
+        note.price(
+            &GeometricBrownianMotion::new(R, SIGMA),
+            R,
+            240,
+            20_000,
+            &config,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_nearest_index() {
+        let times = [0.0, 0.25, 0.5, 0.75, 1.0];
+
+        assert_eq!(nearest_index(&times, 0.5), 2);
+        assert_eq!(nearest_index(&times, 0.49), 2);
+        assert_eq!(nearest_index(&times, 0.3), 1);
+        assert_eq!(nearest_index(&times, 2.0), 4);
+    }
+
+    #[test]
+    fn test_reverse_convertible() {
+        // Fixed coupons plus par, less a short at-the-money put per unit
+        // of notional over the initial price.
+        let note = StructuredNote::builder()
+            .initial_price(50.0)
+            .observation_times(quarterly(1))
+            .coupon(0.02)
+            .knock_in(KnockInPut::new(1.0, KnockInMonitoring::Maturity))
+            .build()
+            .unwrap();
+        let result = price(&note);
+
+        let put = BlackScholesInputs {
+            underlying_price: 50.0,
+            strike_price: 50.0,
+            volatility: SIGMA,
+            risk_free_rate: R,
+            cost_of_carry: R,
+            time_to_expiry: 1.0,
+            option_type: TypeFlag::Put,
+        }
+        .price();
+        let coupons: f64 = quarterly(1).iter().map(|t| 2.0 * (-R * t).exp()).sum();
+        let expected = coupons + 100.0 * (-R).exp() - 100.0 / 50.0 * put;
+
+        let standard_error = result.price.standard_error.unwrap();
+        assert!((result.price.value() - expected).abs() < 4.0 * standard_error);
+        assert!(result.coupon_probabilities.iter().all(|p| *p == 1.0));
+        assert!(result.autocall_probabilities.iter().all(|p| *p == 0.0));
+        assert_eq!(result.expected_life, 1.0);
+    }
+
+    #[test]
+    fn test_capital_protected_zero_coupon() {
+        let note = StructuredNote::builder()
+            .initial_price(100.0)
+            .observation_times(quarterly(2))
+            .build()
+            .unwrap();
+        let result = price(&note);
+
+        assert_approx_equal!(result.price.value(), 100.0 * (-2.0 * R).exp(), 1e-9);
+        assert_eq!(result.loss_probability, 0.0);
+    }
+
+    #[test]
+    fn test_autocall_probabilities() {
+        // Called at the first observation whatever happens.
+        let always = StructuredNote::builder()
+            .initial_price(100.0)
+            .observation_times(quarterly(3))
+            .coupon(0.02)
+            .autocall_barrier(0.0)
+            .build()
+            .unwrap();
+        let result = price(&always);
+
+        assert_eq!(result.autocall_probabilities[0], 1.0);
+        assert_eq!(result.expected_life, 0.25);
+        assert_approx_equal!(result.price.value(), 102.0 * (-R * 0.25).exp(), 1e-9);
+
+        // A typical autocallable: called at par or above, coupons above 70%,
+        // capital at risk below 60%.
+        let note = StructuredNote::builder()
+            .initial_price(100.0)
+            .observation_times(quarterly(3))
+            .coupon(0.025)
+            .coupon_barrier(0.7)
+            .autocall_barrier(1.0)
+            .knock_in(KnockInPut::new(0.6, KnockInMonitoring::Continuous))
+            .build()
+            .unwrap();
+        let result = price(&note);
+        let called: f64 = result.autocall_probabilities.iter().sum();
+
+        assert!(result.autocall_probabilities[0] > 0.4);
+        assert!(called + result.loss_probability < 1.0);
+        assert!(result.expected_life > 0.25 && result.expected_life < 3.0);
+        assert_eq!(*result.autocall_probabilities.last().unwrap(), 0.0);
+
+        // Coupon probabilities fall as fewer notes are still alive.
+        let coupons = &result.coupon_probabilities;
+        assert!(coupons[0] > coupons[5] && coupons[5] > coupons[11]);
+    }
+
+    #[test]
+    fn test_memory_and_monitoring() {
+        let base = StructuredNote::builder()
+            .initial_price(100.0)
+            .observation_times(quarterly(2))
+            .coupon(0.02)
+            .coupon_barrier(0.9)
+            .autocall_barrier(1.05);
+
+        let plain = price(&base.clone().build().unwrap());
+        let memory = price(&base.clone().memory(true).build().unwrap());
+        assert!(memory.price.value() > plain.price.value());
+
+        // Continuous monitoring knocks in more often than at maturity.
+        let european = price(
+            &base
+                .clone()
+                .knock_in(KnockInPut::new(0.7, KnockInMonitoring::Maturity))
+                .build()
+                .unwrap(),
+        );
+        let american = price(
+            &base
+                .knock_in(KnockInPut::new(0.7, KnockInMonitoring::Continuous))
+                .build()
+                .unwrap(),
+        );
+
+        assert!(american.loss_probability > european.loss_probability);
+        assert!(american.price.value() < european.price.value());
+        assert!(european.price.value() < plain.price.value());
+    }
+}
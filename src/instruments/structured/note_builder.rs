@@ -0,0 +1,290 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Builder for structured notes.
+//!
+//! ```rust
+//! use RustQuant::instruments::structured::*;
+//! use RustQuant::stochastics::{GeometricBrownianMotion, SimulationConfig};
+//!
+//! // 3-year Phoenix autocallable with quarterly observations.
+//! let note = StructuredNote::builder()
+//!     .initial_price(100.0)
+//!     .observation_times((1..=12).map(|i| i as f64 / 4.0).collect())
+//!     .coupon(0.02)
+//!     .coupon_barrier(0.7)
+//!     .memory(true)
+//!     .autocall_barrier(1.0)
+//!     .knock_in(KnockInPut::new(0.6, KnockInMonitoring::Continuous))
+//!     .build()?;
+//!
+//! let gbm = GeometricBrownianMotion::new(0.03, 0.25);
+//! let config = SimulationConfig::new(true).with_seed(1);
+//! let result = note.price(&gbm, 0.03, 36, 1_000, &config)?;
+//!
+//! assert!(result.price.value() > 80.0 && result.price.value() < 110.0);
+//! assert_eq!(result.coupon_probabilities.len(), 12);
+//! # Ok::<(), RustQuant::error::RustQuantError>(())
+//! ```
+
+use crate::error::{ensure, RustQuantError};
+use crate::instruments::structured::{KnockInPut, StructuredNote};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Builder for a [`StructuredNote`].
+///
+/// The initial price and observation times are required. The other terms
+/// default to:
+/// - notional: 100,
+/// - coupon: none,
+/// - coupon barrier: zero (coupons are unconditional),
+/// - memory: off,
+/// - autocall barrier: none (the note runs to maturity),
+/// - knock-in put: none (the capital is protected).
+#[derive(Debug, Clone)]
+pub struct StructuredNoteBuilder {
+    notional: f64,
+    initial_price: Option<f64>,
+    observation_times: Option<Vec<f64>>,
+    coupon: f64,
+    coupon_barriers: Barriers,
+    memory: bool,
+    autocall_barriers: Barriers,
+    knock_in: Option<KnockInPut>,
+}
+
+/// One barrier for all observations, or one for each.
+#[derive(Debug, Clone)]
+enum Barriers {
+    Flat(f64),
+    PerObservation(Vec<f64>),
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl StructuredNote {
+    /// Builder for a structured note (see [`StructuredNoteBuilder`]).
+    pub fn builder() -> StructuredNoteBuilder {
+        StructuredNoteBuilder::default()
+    }
+}
+
+impl Default for StructuredNoteBuilder {
+    fn default() -> Self {
+        Self {
+            notional: 100.0,
+            initial_price: None,
+            observation_times: None,
+            coupon: 0.0,
+            coupon_barriers: Barriers::Flat(0.0),
+            memory: false,
+            autocall_barriers: Barriers::Flat(f64::INFINITY),
+            knock_in: None,
+        }
+    }
+}
+
+impl StructuredNoteBuilder {
+    /// Notional of the note.
+    pub fn notional(mut self, notional: f64) -> Self {
+        self.notional = notional;
+        self
+    }
+
+    /// Initial price of the underlying, which the barriers are relative to.
+    pub fn initial_price(mut self, initial_price: f64) -> Self {
+        self.initial_price = Some(initial_price);
+        self
+    }
+
+    /// Observation times in years; the last one is the maturity.
+    pub fn observation_times(mut self, times: Vec<f64>) -> Self {
+        self.observation_times = Some(times);
+        self
+    }
+
+    /// Coupon per observation, as a fraction of the notional (e.g. `0.02`
+    /// for 2% a quarter).
+    pub fn coupon(mut self, coupon: f64) -> Self {
+        self.coupon = coupon;
+        self
+    }
+
+    /// Coupon barrier for all observations.
+    pub fn coupon_barrier(mut self, barrier: f64) -> Self {
+        self.coupon_barriers = Barriers::Flat(barrier);
+        self
+    }
+
+    /// Coupon barrier of each observation.
+    pub fn coupon_barriers(mut self, barriers: Vec<f64>) -> Self {
+        self.coupon_barriers = Barriers::PerObservation(barriers);
+        self
+    }
+
+    /// Whether missed coupons are paid when a later coupon barrier is met.
+    pub fn memory(mut self, memory: bool) -> Self {
+        self.memory = memory;
+        self
+    }
+
+    /// Autocall barrier for all observations.
+    pub fn autocall_barrier(mut self, barrier: f64) -> Self {
+        self.autocall_barriers = Barriers::Flat(barrier);
+        self
+    }
+
+    /// Autocall barrier of each observation, e.g. stepping down over time,
+    /// or infinite during a non-call period.
+    pub fn autocall_barriers(mut self, barriers: Vec<f64>) -> Self {
+        self.autocall_barriers = Barriers::PerObservation(barriers);
+        self
+    }
+
+    /// Knock-in put sold by the holder.
+    pub fn knock_in(mut self, put: KnockInPut) -> Self {
+        self.knock_in = Some(put);
+        self
+    }
+
+    /// The note, or an error if a required term is missing, the
+    /// observation times are not positive and increasing, a list of
+    /// barriers does not have one per observation, or an amount is
+    /// invalid.
+    pub fn build(self) -> Result<StructuredNote, RustQuantError> {
+        let initial_price = required(self.initial_price, "initial price")?;
+        let observation_times = required(self.observation_times, "observation times")?;
+        let n = observation_times.len();
+
+        ensure(n > 0, "A note needs at least one observation.")?;
+        ensure(
+            observation_times[0] > 0.0 && observation_times.windows(2).all(|t| t[0] < t[1]),
+            "The observation times must be positive and increasing.",
+        )?;
+        ensure(
+            initial_price.is_finite() && initial_price > 0.0,
+            "The initial price must be positive.",
+        )?;
+        ensure(
+            self.notional.is_finite() && self.notional > 0.0,
+            "The notional must be positive.",
+        )?;
+        ensure(
+            self.coupon.is_finite() && self.coupon >= 0.0,
+            "The coupon must be finite and non-negative.",
+        )?;
+        if let Some(put) = self.knock_in {
+            ensure(
+                put.barrier >= 0.0 && put.strike > 0.0,
+                "The knock-in barrier must be non-negative and the strike positive.",
+            )?;
+        }
+
+        Ok(StructuredNote {
+            notional: self.notional,
+            initial_price,
+            observation_times,
+            coupon: self.coupon,
+            coupon_barriers: self.coupon_barriers.expand(n, "coupon")?,
+            memory: self.memory,
+            autocall_barriers: self.autocall_barriers.expand(n, "autocall")?,
+            knock_in: self.knock_in,
+        })
+    }
+}
+
+impl Barriers {
+    fn expand(self, n: usize, name: &str) -> Result<Vec<f64>, RustQuantError> {
+        let barriers = match self {
+            Self::Flat(barrier) => vec![barrier; n],
+            Self::PerObservation(barriers) => barriers,
+        };
+
+        ensure(
+            barriers.len() == n,
+            &format!("There must be one {name} barrier per observation."),
+        )?;
+        ensure(
+            barriers.iter().all(|barrier| *barrier >= 0.0),
+            &format!("The {name} barriers must be non-negative."),
+        )?;
+
+        Ok(barriers)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// The value of a term that has no default, or an error naming it.
+fn required<T>(value: Option<T>, term: &str) -> Result<T, RustQuantError> {
+    value.ok_or_else(|| RustQuantError::invalid_parameter(format!("The {term} is required.")))
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_structured_builder {
+    use super::*;
+    use crate::instruments::structured::KnockInMonitoring;
+
+    #[test]
+    fn test_builder_defaults() {
+        let note = StructuredNote::builder()
+            .initial_price(100.0)
+            .observation_times(vec![0.5, 1.0])
+            .build()
+            .unwrap();
+
+        assert_eq!(note.notional, 100.0);
+        assert_eq!(note.coupon_barriers, vec![0.0, 0.0]);
+        assert!(note.autocall_barriers.iter().all(|b| b.is_infinite()));
+        assert_eq!(note.knock_in, None);
+        assert_eq!(note.maturity(), 1.0);
+    }
+
+    #[test]
+    fn test_step_down_barriers() {
+        let note = StructuredNote::builder()
+            .initial_price(100.0)
+            .observation_times(vec![1.0, 2.0, 3.0])
+            .autocall_barriers(vec![f64::INFINITY, 0.95, 0.9])
+            .knock_in(KnockInPut::new(0.6, KnockInMonitoring::Maturity).with_strike(0.8))
+            .build()
+            .unwrap();
+
+        assert_eq!(note.autocall_barriers[1], 0.95);
+        assert_eq!(note.knock_in.unwrap().strike, 0.8);
+    }
+
+    #[test]
+    fn test_builder_errors() {
+        let builder = StructuredNote::builder().observation_times(vec![1.0, 2.0]);
+        assert!(builder.clone().build().is_err());
+
+        let builder = builder.initial_price(100.0);
+        assert!(builder.clone().build().is_ok());
+        assert!(builder
+            .clone()
+            .observation_times(vec![2.0, 1.0])
+            .build()
+            .is_err());
+        assert!(builder.clone().coupon_barriers(vec![0.7]).build().is_err());
+        assert!(builder.clone().coupon(-0.01).build().is_err());
+        assert!(builder.notional(0.0).build().is_err());
+    }
+}
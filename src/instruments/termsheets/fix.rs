@@ -0,0 +1,420 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! FIX-style tag=value import and export of term sheets.
+//!
+//! Term sheets are the body of a Security Definition (`35=d`) message,
+//! with fields separated by SOH (`\x01`) or, for readability, `|`. The
+//! session fields (`8`, `9`, `10`, ...) are ignored when reading and not
+//! written.
+//!
+//! | Tag  | Field                  | Swap               | Option              | Bond         |
+//! |------|------------------------|--------------------|---------------------|--------------|
+//! | 167  | SecurityType           | `IRS`              | `OPT`               | `CORP`, `TBOND`, ... |
+//! | 55   | Symbol                 |                    |                     | identifier   |
+//! | 311  | UnderlyingSymbol       |                    | underlying          |              |
+//! | 15   | Currency               | currency           | strike currency     | currency     |
+//! | 38   | OrderQty               | notional           | number of options   | face value   |
+//! | 916  | StartDate              | effective date     |                     |              |
+//! | 541  | MaturityDate           | termination date   | expiration date     | maturity     |
+//! | 223  | CouponRate             | fixed rate         |                     | coupon rate  |
+//! | 201  | PutOrCall              |                    | `0` put, `1` call   |              |
+//! | 202  | StrikePrice            |                    | strike              |              |
+//! | 231  | ContractMultiplier     |                    | option entitlement  |              |
+//! | 1194 | ExerciseStyle          |                    | `0` European, `1` American, `2` Bermudan | |
+//! | 5000 | PaymentFrequency       | fixed leg          |                     | coupons      |
+//! | 5001 | DayCountFraction       | fixed leg          |                     | coupons      |
+//! | 5002 | FloatingRateIndex      | floating index     |                     |              |
+//! | 5003 | FloatingSpread         | spread             |                     |              |
+//! | 5004 | FloatingFrequency      | floating leg       |                     |              |
+//! | 5005 | FloatingDayCount       | floating leg       |                     |              |
+//!
+//! FIX has no standard fields for the conventions of swap legs and bond
+//! coupons, so they are carried in the user-defined range (5000 and up):
+//! frequencies as periods (e.g. `6M`) and day counts as FpML codes (e.g.
+//! `ACT/360`). Dates are `YYYYMMDD`.
+//!
+//! ```
+//! use RustQuant::instruments::termsheets::*;
+//!
+//! let fix = "8=FIX.4.4|35=d|167=CORP|55=XS1234567890|15=EUR|38=100000|223=0.0425\
+//!            |541=20310915|5000=1Y|5001=ACT/ACT.ICMA|10=123";
+//!
+//! let TermSheet::Bond(bond) = TermSheet::from_fix(fix).unwrap() else {
+//!     panic!("not a bond");
+//! };
+//! assert_eq!(bond.coupon_rate, 0.0425);
+//! ```
+
+use crate::instruments::options::{ExerciseFlag, TypeFlag};
+use crate::instruments::termsheets::{
+    calendar_date, frequency_period, parse_field, period_frequency, BondTerms, DayCountFraction,
+    OptionTerms, SwapTerms, TermSheet, TermSheetError,
+};
+use crate::money::Currency;
+use crate::time::{PaymentFrequency, Tenor};
+use std::collections::BTreeMap;
+use time::Date;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// CONSTANTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// The FIX field separator (SOH).
+pub const FIX_SOH: char = '\x01';
+
+const MSG_TYPE: u32 = 35;
+const SECURITY_TYPE: u32 = 167;
+const SYMBOL: u32 = 55;
+const UNDERLYING_SYMBOL: u32 = 311;
+const CURRENCY: u32 = 15;
+const ORDER_QTY: u32 = 38;
+const START_DATE: u32 = 916;
+const MATURITY_DATE: u32 = 541;
+const COUPON_RATE: u32 = 223;
+const PUT_OR_CALL: u32 = 201;
+const STRIKE_PRICE: u32 = 202;
+const CONTRACT_MULTIPLIER: u32 = 231;
+const EXERCISE_STYLE: u32 = 1194;
+const PAYMENT_FREQUENCY: u32 = 5000;
+const DAY_COUNT: u32 = 5001;
+const FLOATING_INDEX: u32 = 5002;
+const FLOATING_SPREAD: u32 = 5003;
+const FLOATING_FREQUENCY: u32 = 5004;
+const FLOATING_DAY_COUNT: u32 = 5005;
+
+/// Security types read as bonds (the first is written).
+const BOND_TYPES: [&str; 7] = ["CORP", "TBOND", "TNOTE", "TBILL", "EUCORP", "EUSOV", "FAC"];
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Fields of a message by tag.
+struct Fields(BTreeMap<u32, String>);
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl TermSheet {
+    /// Reads a term sheet from FIX tag=value fields separated by SOH or `|`.
+    pub fn from_fix(message: &str) -> Result<Self, TermSheetError> {
+        let fields = Fields::parse(message)?;
+
+        match fields.get(SECURITY_TYPE)? {
+            "IRS" => Ok(TermSheet::Swap(read_swap(&fields)?)),
+            "OPT" => Ok(TermSheet::Option(read_option(&fields)?)),
+            security_type if BOND_TYPES.contains(&security_type) => {
+                Ok(TermSheet::Bond(read_bond(&fields)?))
+            }
+            _ => Err(TermSheetError::UnknownProduct),
+        }
+    }
+
+    /// Writes the term sheet as FIX tag=value fields, separated by
+    /// `delimiter` ([`FIX_SOH`] on the wire).
+    pub fn to_fix(&self, delimiter: char) -> Result<String, TermSheetError> {
+        let mut fields = vec![(MSG_TYPE, "d".to_string())];
+
+        match self {
+            TermSheet::Swap(swap) => fields.extend([
+                (SECURITY_TYPE, "IRS".to_string()),
+                (CURRENCY, swap.currency.code.alphabetic.to_string()),
+                (ORDER_QTY, swap.notional.to_string()),
+                (START_DATE, write_date(swap.effective_date)),
+                (MATURITY_DATE, write_date(swap.termination_date)),
+                (COUPON_RATE, swap.fixed_rate.to_string()),
+                (PAYMENT_FREQUENCY, write_frequency(swap.fixed_frequency)?),
+                (DAY_COUNT, swap.fixed_day_count.code().to_string()),
+                (FLOATING_INDEX, swap.floating_index.clone()),
+                (FLOATING_SPREAD, swap.floating_spread.to_string()),
+                (
+                    FLOATING_FREQUENCY,
+                    write_frequency(swap.floating_frequency)?,
+                ),
+                (
+                    FLOATING_DAY_COUNT,
+                    swap.floating_day_count.code().to_string(),
+                ),
+            ]),
+            TermSheet::Option(option) => fields.extend([
+                (SECURITY_TYPE, "OPT".to_string()),
+                (UNDERLYING_SYMBOL, option.underlying.clone()),
+                (
+                    PUT_OR_CALL,
+                    match option.option_type {
+                        TypeFlag::Put => "0",
+                        TypeFlag::Call => "1",
+                    }
+                    .to_string(),
+                ),
+                (STRIKE_PRICE, option.strike_price.to_string()),
+                (CURRENCY, option.currency.code.alphabetic.to_string()),
+                (MATURITY_DATE, write_date(option.expiration_date)),
+                (
+                    EXERCISE_STYLE,
+                    match option.exercise {
+                        ExerciseFlag::European => "0",
+                        ExerciseFlag::American => "1",
+                        ExerciseFlag::Bermudan => "2",
+                    }
+                    .to_string(),
+                ),
+                (ORDER_QTY, option.number_of_options.to_string()),
+                (CONTRACT_MULTIPLIER, option.option_entitlement.to_string()),
+            ]),
+            TermSheet::Bond(bond) => fields.extend([
+                (SECURITY_TYPE, BOND_TYPES[0].to_string()),
+                (SYMBOL, bond.identifier.clone()),
+                (CURRENCY, bond.currency.code.alphabetic.to_string()),
+                (ORDER_QTY, bond.face_value.to_string()),
+                (COUPON_RATE, bond.coupon_rate.to_string()),
+                (MATURITY_DATE, write_date(bond.maturity_date)),
+                (PAYMENT_FREQUENCY, write_frequency(bond.coupon_frequency)?),
+                (DAY_COUNT, bond.day_count.code().to_string()),
+            ]),
+        }
+
+        Ok(fields
+            .iter()
+            .map(|(tag, value)| format!("{tag}={value}"))
+            .collect::<Vec<_>>()
+            .join(&delimiter.to_string()))
+    }
+}
+
+impl Fields {
+    fn parse(message: &str) -> Result<Self, TermSheetError> {
+        let delimiter = match message.contains(FIX_SOH) {
+            true => FIX_SOH,
+            false => '|',
+        };
+
+        let mut fields = BTreeMap::new();
+        for field in message.split(delimiter).map(str::trim) {
+            if field.is_empty() {
+                continue;
+            }
+
+            let invalid = || TermSheetError::InvalidField {
+                field: "tag=value".to_string(),
+                value: field.to_string(),
+            };
+            let (tag, value) = field.split_once('=').ok_or_else(invalid)?;
+            let tag = tag.trim().parse::<u32>().map_err(|_| invalid())?;
+
+            fields.entry(tag).or_insert_with(|| value.to_string());
+        }
+
+        Ok(Self(fields))
+    }
+
+    fn get(&self, tag: u32) -> Result<&str, TermSheetError> {
+        self.0
+            .get(&tag)
+            .map(|value| value.trim())
+            .ok_or_else(|| TermSheetError::MissingField(tag.to_string()))
+    }
+
+    fn parse_value<T, F>(&self, tag: u32, parse: F) -> Result<T, TermSheetError>
+    where
+        F: FnOnce(&str) -> Option<T>,
+    {
+        parse_field(&tag.to_string(), self.get(tag)?, parse)
+    }
+
+    fn number(&self, tag: u32) -> Result<f64, TermSheetError> {
+        self.parse_value(tag, |s| s.parse().ok())
+    }
+
+    /// A number that defaults when the field is absent.
+    fn number_or(&self, tag: u32, default: f64) -> Result<f64, TermSheetError> {
+        match self.0.contains_key(&tag) {
+            true => self.number(tag),
+            false => Ok(default),
+        }
+    }
+
+    fn currency(&self) -> Result<Currency, TermSheetError> {
+        self.parse_value(CURRENCY, Currency::from_code)
+    }
+
+    fn date(&self, tag: u32) -> Result<Date, TermSheetError> {
+        self.parse_value(tag, |s| match s.len() {
+            8 => calendar_date(&s[..4], &s[4..6], &s[6..]),
+            _ => None,
+        })
+    }
+
+    fn frequency(&self, tag: u32) -> Result<PaymentFrequency, TermSheetError> {
+        self.parse_value(tag, |s| s.parse::<Tenor>().ok().and_then(period_frequency))
+    }
+
+    fn day_count(&self, tag: u32) -> Result<DayCountFraction, TermSheetError> {
+        self.parse_value(tag, DayCountFraction::from_code)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+fn read_swap(fields: &Fields) -> Result<SwapTerms, TermSheetError> {
+    Ok(SwapTerms {
+        notional: fields.number(ORDER_QTY)?,
+        currency: fields.currency()?,
+        effective_date: fields.date(START_DATE)?,
+        termination_date: fields.date(MATURITY_DATE)?,
+        fixed_rate: fields.number(COUPON_RATE)?,
+        fixed_frequency: fields.frequency(PAYMENT_FREQUENCY)?,
+        fixed_day_count: fields.day_count(DAY_COUNT)?,
+        floating_index: fields.get(FLOATING_INDEX)?.to_string(),
+        floating_spread: fields.number_or(FLOATING_SPREAD, 0.0)?,
+        floating_frequency: fields.frequency(FLOATING_FREQUENCY)?,
+        floating_day_count: fields.day_count(FLOATING_DAY_COUNT)?,
+    })
+}
+
+fn read_option(fields: &Fields) -> Result<OptionTerms, TermSheetError> {
+    let option_type = fields.parse_value(PUT_OR_CALL, |s| match s {
+        "0" => Some(TypeFlag::Put),
+        "1" => Some(TypeFlag::Call),
+        _ => None,
+    })?;
+    let exercise = match fields.0.contains_key(&EXERCISE_STYLE) {
+        true => fields.parse_value(EXERCISE_STYLE, |s| match s {
+            "0" => Some(ExerciseFlag::European),
+            "1" => Some(ExerciseFlag::American),
+            "2" => Some(ExerciseFlag::Bermudan),
+            _ => None,
+        })?,
+        false => ExerciseFlag::European,
+    };
+
+    Ok(OptionTerms {
+        underlying: fields.get(UNDERLYING_SYMBOL)?.to_string(),
+        option_type,
+        exercise,
+        strike_price: fields.number(STRIKE_PRICE)?,
+        currency: fields.currency()?,
+        expiration_date: fields.date(MATURITY_DATE)?,
+        number_of_options: fields.number_or(ORDER_QTY, 1.0)?,
+        option_entitlement: fields.number_or(CONTRACT_MULTIPLIER, 1.0)?,
+    })
+}
+
+fn read_bond(fields: &Fields) -> Result<BondTerms, TermSheetError> {
+    let day_count = match fields.0.contains_key(&DAY_COUNT) {
+        true => fields.day_count(DAY_COUNT)?,
+        false => DayCountFraction::ActualActualICMA,
+    };
+
+    Ok(BondTerms {
+        identifier: fields.get(SYMBOL)?.to_string(),
+        currency: fields.currency()?,
+        face_value: fields.number(ORDER_QTY)?,
+        coupon_rate: fields.number(COUPON_RATE)?,
+        coupon_frequency: fields.frequency(PAYMENT_FREQUENCY)?,
+        day_count,
+        maturity_date: fields.date(MATURITY_DATE)?,
+    })
+}
+
+fn write_date(date: Date) -> String {
+    format!(
+        "{:04}{:02}{:02}",
+        date.year(),
+        date.month() as u8,
+        date.day()
+    )
+}
+
+fn write_frequency(frequency: PaymentFrequency) -> Result<String, TermSheetError> {
+    Ok(frequency_period(frequency)?.to_string())
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_fix {
+    use super::*;
+    use crate::money::{GBP, USD};
+    use time::macros::date;
+
+    #[test]
+    fn test_swap_round_trip() {
+        let swap = TermSheet::Swap(SwapTerms {
+            notional: 50_000_000.0,
+            currency: GBP,
+            effective_date: date!(2024 - 05 - 01),
+            termination_date: date!(2027 - 05 - 01),
+            fixed_rate: 0.0412,
+            fixed_frequency: PaymentFrequency::Annually,
+            fixed_day_count: DayCountFraction::Actual365Fixed,
+            floating_index: "GBP-SONIA-COMPOUND".to_string(),
+            floating_spread: 0.0,
+            floating_frequency: PaymentFrequency::Annually,
+            floating_day_count: DayCountFraction::Actual365Fixed,
+        });
+
+        let message = swap.to_fix('|').unwrap();
+        assert!(message.starts_with("35=d|167=IRS|15=GBP|38=50000000|916=20240501|541=20270501|"));
+        assert_eq!(TermSheet::from_fix(&message).unwrap(), swap);
+
+        let wire = swap.to_fix(FIX_SOH).unwrap();
+        assert_eq!(wire.replace(FIX_SOH, "|"), message);
+        assert_eq!(TermSheet::from_fix(&wire).unwrap(), swap);
+    }
+
+    #[test]
+    fn test_option_defaults() {
+        let message =
+            "8=FIX.4.4|9=74|35=d|167=OPT|311=MSFT|201=1|202=420.5|15=USD|541=20250117|10=017";
+        let TermSheet::Option(option) = TermSheet::from_fix(message).unwrap() else {
+            panic!("not an option");
+        };
+
+        assert_eq!(option.underlying, "MSFT");
+        assert_eq!(option.option_type, TypeFlag::Call);
+        assert_eq!(option.exercise, ExerciseFlag::European);
+        assert_eq!(option.strike_price, 420.5);
+        assert_eq!(option.currency, USD);
+        assert_eq!(option.expiration_date, date!(2025 - 01 - 17));
+        assert_eq!(
+            (option.number_of_options, option.option_entitlement),
+            (1.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            TermSheet::from_fix("35=d|167=FUT|55=ESZ4"),
+            Err(TermSheetError::UnknownProduct)
+        );
+        assert_eq!(
+            TermSheet::from_fix("35=d|167=OPT|311=MSFT|201=1"),
+            Err(TermSheetError::MissingField("202".to_string()))
+        );
+        assert_eq!(
+            TermSheet::from_fix("35=d|167=OPT|311=MSFT|201=1|202=420|15=USD|541=2025-01-17"),
+            Err(TermSheetError::InvalidField {
+                field: "541".to_string(),
+                value: "2025-01-17".to_string(),
+            })
+        );
+        assert!(matches!(
+            TermSheet::from_fix("35=d|oops"),
+            Err(TermSheetError::InvalidField { .. })
+        ));
+    }
+}
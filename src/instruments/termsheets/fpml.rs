@@ -0,0 +1,698 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! FpML (<https://www.fpml.org>) import and export of term sheets.
+//!
+//! Only the product is read or written: a `swap` (one fixed and one
+//! floating `swapStream`), an `equityOption`, or a `bond` (the FpML asset
+//! definition). Products are found anywhere in the document, so a full
+//! confirmation, a `dataDocument` or a bare product fragment can be read.
+//! Namespaces are ignored, as are the elements RustQuant has no use for
+//! (parties, business day adjustments, payment dates, premiums, ...).
+//!
+//! Exported products are fragments to be placed in a trade: the swap legs
+//! reference the parties `party1` (fixed leg payer) and `party2`.
+//!
+//! ```
+//! use RustQuant::instruments::termsheets::*;
+//!
+//! let fpml = r#"
+//!     <bond>
+//!         <instrumentId instrumentIdScheme="http://www.fpml.org/coding-scheme/external/instrument-id-ISIN-1-0">US912828Z780</instrumentId>
+//!         <currency>USD</currency>
+//!         <couponRate>0.015</couponRate>
+//!         <maturity>2030-02-15</maturity>
+//!         <parValue>1000</parValue>
+//!         <paymentFrequency><periodMultiplier>6</periodMultiplier><period>M</period></paymentFrequency>
+//!         <dayCountFraction>ACT/ACT.ICMA</dayCountFraction>
+//!     </bond>"#;
+//!
+//! let terms = TermSheet::from_fpml(fpml).unwrap();
+//! assert_eq!(TermSheet::from_fpml(&terms.to_fpml().unwrap()).unwrap(), terms);
+//! ```
+
+use crate::instruments::options::{ExerciseFlag, TypeFlag};
+use crate::instruments::termsheets::{
+    calendar_date, frequency_period, parse_field, period_frequency, BondTerms, DayCountFraction,
+    OptionTerms, SwapTerms, TermSheet, TermSheetError,
+};
+use crate::money::Currency;
+use crate::time::{PaymentFrequency, Tenor};
+use quick_xml::escape::escape;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use time::Date;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// XML element, with namespace prefixes stripped.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    text: String,
+    children: Vec<Element>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl TermSheet {
+    /// Reads the first swap, equity option or bond of an FpML document.
+    pub fn from_fpml(xml: &str) -> Result<Self, TermSheetError> {
+        let root = Element::parse(xml)?;
+
+        if let Some(swap) = root.find("swap") {
+            return Ok(TermSheet::Swap(read_swap(swap)?));
+        }
+        if let Some(option) = root.find("equityOption") {
+            return Ok(TermSheet::Option(read_option(option)?));
+        }
+        if let Some(bond) = root.find("bond") {
+            return Ok(TermSheet::Bond(read_bond(bond)?));
+        }
+
+        Err(TermSheetError::UnknownProduct)
+    }
+
+    /// Writes the product as an FpML fragment.
+    pub fn to_fpml(&self) -> Result<String, TermSheetError> {
+        let element = match self {
+            TermSheet::Swap(swap) => write_swap(swap)?,
+            TermSheet::Option(option) => write_option(option),
+            TermSheet::Bond(bond) => write_bond(bond)?,
+        };
+
+        let mut xml = String::new();
+        element.write(&mut xml, 0);
+
+        Ok(xml)
+    }
+}
+
+impl Element {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Element holding only text.
+    fn leaf(name: &str, text: impl ToString) -> Self {
+        Self {
+            text: text.to_string(),
+            ..Self::new(name)
+        }
+    }
+
+    fn with_attribute(mut self, name: &str, value: &str) -> Self {
+        self.attributes.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    fn with_child(mut self, child: Element) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Parses a document into its root element.
+    fn parse(xml: &str) -> Result<Self, TermSheetError> {
+        let error = |e: quick_xml::Error| TermSheetError::Xml(e.to_string());
+        let mut reader = Reader::from_str(xml);
+        reader.trim_text(true);
+
+        // Open elements; the bottom one collects the top-level elements.
+        let mut stack = vec![Element::default()];
+
+        loop {
+            match reader.read_event().map_err(error)? {
+                Event::Start(start) => stack.push(Self::open(&start)?),
+                Event::Empty(start) => {
+                    let element = Self::open(&start)?;
+                    stack.last_mut().unwrap().children.push(element);
+                }
+                Event::Text(text) => {
+                    let text = text.unescape().map_err(error)?;
+                    stack.last_mut().unwrap().text.push_str(&text);
+                }
+                Event::CData(text) => {
+                    let text = String::from_utf8_lossy(&text);
+                    stack.last_mut().unwrap().text.push_str(&text);
+                }
+                Event::End(_) => {
+                    let element = stack.pop().unwrap();
+                    stack.last_mut().unwrap().children.push(element);
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        match (stack.len(), stack.pop().unwrap().children.pop()) {
+            (1, Some(root)) => Ok(root),
+            _ => Err(TermSheetError::Xml(
+                "unclosed or missing root element".into(),
+            )),
+        }
+    }
+
+    fn open(start: &BytesStart) -> Result<Self, TermSheetError> {
+        let mut element = Self::new(&String::from_utf8_lossy(start.local_name().as_ref()));
+
+        for attribute in start.attributes() {
+            let attribute = attribute.map_err(|e| TermSheetError::Xml(e.to_string()))?;
+            let value = attribute
+                .unescape_value()
+                .map_err(|e| TermSheetError::Xml(e.to_string()))?;
+
+            element = element.with_attribute(
+                &String::from_utf8_lossy(attribute.key.local_name().as_ref()),
+                &value,
+            );
+        }
+
+        Ok(element)
+    }
+
+    /// First element with the name, depth first (the element itself
+    /// included).
+    fn find(&self, name: &str) -> Option<&Element> {
+        if self.name == name {
+            return Some(self);
+        }
+
+        self.children.iter().find_map(|child| child.find(name))
+    }
+
+    /// First element with the name, which must exist.
+    fn require(&self, name: &str) -> Result<&Element, TermSheetError> {
+        self.find(name)
+            .ok_or_else(|| TermSheetError::MissingField(name.to_string()))
+    }
+
+    /// Text of the first element with the name, which must exist.
+    fn value(&self, name: &str) -> Result<&str, TermSheetError> {
+        Ok(self.require(name)?.text.trim())
+    }
+
+    /// Writes the element, indented by two spaces per level.
+    fn write(&self, xml: &mut String, depth: usize) {
+        let indent = "  ".repeat(depth);
+
+        xml.push_str(&format!("{indent}<{}", self.name));
+        for (name, value) in &self.attributes {
+            xml.push_str(&format!(" {name}=\"{}\"", escape(value)));
+        }
+
+        match (self.children.is_empty(), self.text.is_empty()) {
+            (true, true) => xml.push_str("/>\n"),
+            (true, false) => {
+                xml.push_str(&format!(">{}</{}>\n", escape(&self.text), self.name));
+            }
+            (false, _) => {
+                xml.push_str(">\n");
+                for child in &self.children {
+                    child.write(xml, depth + 1);
+                }
+                xml.push_str(&format!("{indent}</{}>\n", self.name));
+            }
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+fn read_swap(swap: &Element) -> Result<SwapTerms, TermSheetError> {
+    let streams: Vec<&Element> = swap
+        .children
+        .iter()
+        .filter(|child| child.name == "swapStream")
+        .collect();
+    let fixed = streams
+        .iter()
+        .find(|stream| stream.find("fixedRateSchedule").is_some());
+    let floating = streams
+        .iter()
+        .find(|stream| stream.find("floatingRateCalculation").is_some());
+
+    let (Some(fixed), Some(floating)) = (fixed, floating) else {
+        return Err(TermSheetError::Unsupported(
+            "swaps other than fixed-for-floating".to_string(),
+        ));
+    };
+
+    let dates = fixed.require("calculationPeriodDates")?;
+    let notional = fixed.require("notionalStepSchedule")?;
+    let spread = floating
+        .find("spreadSchedule")
+        .map(|schedule| read_number(schedule, "initialValue"))
+        .transpose()?;
+
+    Ok(SwapTerms {
+        notional: read_number(notional, "initialValue")?,
+        currency: read_currency(notional)?,
+        effective_date: read_date(dates.require("effectiveDate")?, "unadjustedDate")?,
+        termination_date: read_date(dates.require("terminationDate")?, "unadjustedDate")?,
+        fixed_rate: read_number(fixed.require("fixedRateSchedule")?, "initialValue")?,
+        fixed_frequency: read_frequency(fixed.require("calculationPeriodFrequency")?)?,
+        fixed_day_count: read_day_count(fixed)?,
+        floating_index: floating.value("floatingRateIndex")?.to_string(),
+        floating_spread: spread.unwrap_or(0.0),
+        floating_frequency: read_frequency(floating.require("calculationPeriodFrequency")?)?,
+        floating_day_count: read_day_count(floating)?,
+    })
+}
+
+fn read_option(option: &Element) -> Result<OptionTerms, TermSheetError> {
+    let option_type = parse_field("optionType", option.value("optionType")?, |s| match s {
+        "Call" => Some(TypeFlag::Call),
+        "Put" => Some(TypeFlag::Put),
+        _ => None,
+    })?;
+
+    let exercise = option.require("equityExercise")?;
+    let style = [
+        ("equityEuropeanExercise", ExerciseFlag::European),
+        ("equityAmericanExercise", ExerciseFlag::American),
+        ("equityBermudaExercise", ExerciseFlag::Bermudan),
+    ]
+    .into_iter()
+    .find(|(name, _)| exercise.find(name).is_some())
+    .map(|(_, style)| style)
+    .ok_or_else(|| TermSheetError::MissingField("equityEuropeanExercise".to_string()))?;
+
+    let strike = option.require("strike")?;
+    let optional_number = |name: &str| {
+        option
+            .find(name)
+            .map(|_| read_number(option, name))
+            .transpose()
+    };
+
+    Ok(OptionTerms {
+        underlying: option
+            .require("underlyer")?
+            .value("instrumentId")?
+            .to_string(),
+        option_type,
+        exercise: style,
+        strike_price: read_number(strike, "strikePrice")?,
+        currency: read_currency(strike)?,
+        expiration_date: read_date(exercise.require("expirationDate")?, "unadjustedDate")?,
+        number_of_options: optional_number("numberOfOptions")?.unwrap_or(1.0),
+        option_entitlement: optional_number("optionEntitlement")?.unwrap_or(1.0),
+    })
+}
+
+fn read_bond(bond: &Element) -> Result<BondTerms, TermSheetError> {
+    let day_count = match bond.find("dayCountFraction") {
+        Some(_) => read_day_count(bond)?,
+        None => DayCountFraction::ActualActualICMA,
+    };
+
+    Ok(BondTerms {
+        identifier: bond.value("instrumentId")?.to_string(),
+        currency: read_currency(bond)?,
+        face_value: read_number(bond, "parValue")?,
+        coupon_rate: read_number(bond, "couponRate")?,
+        coupon_frequency: read_frequency(bond.require("paymentFrequency")?)?,
+        day_count,
+        maturity_date: read_date(bond, "maturity")?,
+    })
+}
+
+fn write_swap(swap: &SwapTerms) -> Result<Element, TermSheetError> {
+    let stream = |id: &str,
+                  payer: &str,
+                  receiver: &str,
+                  frequency: PaymentFrequency,
+                  rate: Element,
+                  day_count: DayCountFraction|
+     -> Result<Element, TermSheetError> {
+        let dates = Element::new("calculationPeriodDates")
+            .with_attribute("id", &format!("{id}CalculationPeriodDates"))
+            .with_child(
+                Element::new("effectiveDate")
+                    .with_child(Element::leaf("unadjustedDate", swap.effective_date)),
+            )
+            .with_child(
+                Element::new("terminationDate")
+                    .with_child(Element::leaf("unadjustedDate", swap.termination_date)),
+            )
+            .with_child(write_frequency("calculationPeriodFrequency", frequency)?);
+
+        let calculation = Element::new("calculation")
+            .with_child(
+                Element::new("notionalSchedule").with_child(
+                    Element::new("notionalStepSchedule")
+                        .with_child(Element::leaf("initialValue", swap.notional))
+                        .with_child(Element::leaf("currency", swap.currency.code.alphabetic)),
+                ),
+            )
+            .with_child(rate)
+            .with_child(Element::leaf("dayCountFraction", day_count.code()));
+
+        Ok(Element::new("swapStream")
+            .with_attribute("id", id)
+            .with_child(Element::new("payerPartyReference").with_attribute("href", payer))
+            .with_child(Element::new("receiverPartyReference").with_attribute("href", receiver))
+            .with_child(dates)
+            .with_child(Element::new("calculationPeriodAmount").with_child(calculation)))
+    };
+
+    let fixed_rate = Element::new("fixedRateSchedule")
+        .with_child(Element::leaf("initialValue", swap.fixed_rate));
+    let mut floating_rate = Element::new("floatingRateCalculation")
+        .with_child(Element::leaf("floatingRateIndex", &swap.floating_index));
+    if swap.floating_spread != 0.0 {
+        floating_rate = floating_rate.with_child(
+            Element::new("spreadSchedule")
+                .with_child(Element::leaf("initialValue", swap.floating_spread)),
+        );
+    }
+
+    Ok(Element::new("swap")
+        .with_child(stream(
+            "fixedLeg",
+            "party1",
+            "party2",
+            swap.fixed_frequency,
+            fixed_rate,
+            swap.fixed_day_count,
+        )?)
+        .with_child(stream(
+            "floatingLeg",
+            "party2",
+            "party1",
+            swap.floating_frequency,
+            floating_rate,
+            swap.floating_day_count,
+        )?))
+}
+
+fn write_option(option: &OptionTerms) -> Element {
+    let (option_type, exercise) = (
+        match option.option_type {
+            TypeFlag::Call => "Call",
+            TypeFlag::Put => "Put",
+        },
+        match option.exercise {
+            ExerciseFlag::European => "equityEuropeanExercise",
+            ExerciseFlag::American => "equityAmericanExercise",
+            ExerciseFlag::Bermudan => "equityBermudaExercise",
+        },
+    );
+
+    Element::new("equityOption")
+        .with_child(Element::leaf("optionType", option_type))
+        .with_child(
+            Element::new("underlyer").with_child(
+                Element::new("singleUnderlyer").with_child(
+                    Element::new("equity")
+                        .with_child(Element::leaf("instrumentId", &option.underlying)),
+                ),
+            ),
+        )
+        .with_child(
+            Element::new("equityExercise").with_child(
+                Element::new(exercise).with_child(
+                    Element::new("expirationDate").with_child(
+                        Element::new("adjustableDate")
+                            .with_child(Element::leaf("unadjustedDate", option.expiration_date)),
+                    ),
+                ),
+            ),
+        )
+        .with_child(
+            Element::new("strike")
+                .with_child(Element::leaf("strikePrice", option.strike_price))
+                .with_child(Element::leaf("currency", option.currency.code.alphabetic)),
+        )
+        .with_child(Element::leaf("numberOfOptions", option.number_of_options))
+        .with_child(Element::leaf(
+            "optionEntitlement",
+            option.option_entitlement,
+        ))
+}
+
+fn write_bond(bond: &BondTerms) -> Result<Element, TermSheetError> {
+    Ok(Element::new("bond")
+        .with_child(Element::leaf("instrumentId", &bond.identifier))
+        .with_child(Element::leaf("currency", bond.currency.code.alphabetic))
+        .with_child(Element::leaf("couponRate", bond.coupon_rate))
+        .with_child(Element::leaf("maturity", bond.maturity_date))
+        .with_child(Element::leaf("parValue", bond.face_value))
+        .with_child(write_frequency("paymentFrequency", bond.coupon_frequency)?)
+        .with_child(Element::leaf("dayCountFraction", bond.day_count.code())))
+}
+
+fn write_frequency(name: &str, frequency: PaymentFrequency) -> Result<Element, TermSheetError> {
+    let period = frequency_period(frequency)?;
+
+    Ok(Element::new(name)
+        .with_child(Element::leaf("periodMultiplier", period.length))
+        .with_child(Element::leaf("period", period.unit.symbol())))
+}
+
+fn read_number(element: &Element, name: &str) -> Result<f64, TermSheetError> {
+    parse_field(name, element.value(name)?, |s| s.parse().ok())
+}
+
+fn read_currency(element: &Element) -> Result<Currency, TermSheetError> {
+    parse_field("currency", element.value("currency")?, Currency::from_code)
+}
+
+fn read_date(element: &Element, name: &str) -> Result<Date, TermSheetError> {
+    // Dates may carry a time zone offset (e.g. "2024-01-15Z").
+    parse_field(name, element.value(name)?, |s| {
+        let date = s.get(..10)?;
+        let mut parts = date.splitn(3, '-');
+        calendar_date(parts.next()?, parts.next()?, parts.next()?)
+    })
+}
+
+fn read_day_count(element: &Element) -> Result<DayCountFraction, TermSheetError> {
+    parse_field(
+        "dayCountFraction",
+        element.value("dayCountFraction")?,
+        DayCountFraction::from_code,
+    )
+}
+
+fn read_frequency(element: &Element) -> Result<PaymentFrequency, TermSheetError> {
+    let period = format!(
+        "{}{}",
+        element.value("periodMultiplier")?,
+        element.value("period")?
+    );
+
+    parse_field(&element.name, &period, |s| {
+        s.parse::<Tenor>().ok().and_then(period_frequency)
+    })
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_fpml {
+    use super::*;
+    use crate::money::{EUR, USD};
+    use time::macros::date;
+
+    /// An FpML 5 confirmation of a EUR 6M Euribor swap, trimmed to the
+    /// elements of one trade.
+    const SWAP: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<dataDocument xmlns="http://www.fpml.org/FpML-5/confirmation" fpmlVersion="5-10">
+  <trade>
+    <tradeHeader>
+      <tradeDate>2024-03-04</tradeDate>
+    </tradeHeader>
+    <swap>
+      <swapStream id="floatingLeg">
+        <payerPartyReference href="party1"/>
+        <receiverPartyReference href="party2"/>
+        <calculationPeriodDates id="floatingLegCalcPeriodDates">
+          <effectiveDate>
+            <unadjustedDate>2024-03-06</unadjustedDate>
+            <dateAdjustments><businessDayConvention>NONE</businessDayConvention></dateAdjustments>
+          </effectiveDate>
+          <terminationDate>
+            <unadjustedDate>2034-03-06</unadjustedDate>
+          </terminationDate>
+          <calculationPeriodFrequency>
+            <periodMultiplier>6</periodMultiplier>
+            <period>M</period>
+            <rollConvention>6</rollConvention>
+          </calculationPeriodFrequency>
+        </calculationPeriodDates>
+        <calculationPeriodAmount>
+          <calculation>
+            <notionalSchedule>
+              <notionalStepSchedule>
+                <initialValue>25000000.00</initialValue>
+                <currency currencyScheme="http://www.fpml.org/coding-scheme/external/iso4217">EUR</currency>
+              </notionalStepSchedule>
+            </notionalSchedule>
+            <floatingRateCalculation>
+              <floatingRateIndex>EUR-EURIBOR-Reuters</floatingRateIndex>
+              <indexTenor><periodMultiplier>6</periodMultiplier><period>M</period></indexTenor>
+              <spreadSchedule><initialValue>0.0010</initialValue></spreadSchedule>
+            </floatingRateCalculation>
+            <dayCountFraction>ACT/360</dayCountFraction>
+          </calculation>
+        </calculationPeriodAmount>
+      </swapStream>
+      <swapStream id="fixedLeg">
+        <payerPartyReference href="party2"/>
+        <receiverPartyReference href="party1"/>
+        <calculationPeriodDates id="fixedLegCalcPeriodDates">
+          <effectiveDate><unadjustedDate>2024-03-06</unadjustedDate></effectiveDate>
+          <terminationDate><unadjustedDate>2034-03-06</unadjustedDate></terminationDate>
+          <calculationPeriodFrequency>
+            <periodMultiplier>1</periodMultiplier>
+            <period>Y</period>
+          </calculationPeriodFrequency>
+        </calculationPeriodDates>
+        <calculationPeriodAmount>
+          <calculation>
+            <notionalSchedule>
+              <notionalStepSchedule>
+                <initialValue>25000000.00</initialValue>
+                <currency>EUR</currency>
+              </notionalStepSchedule>
+            </notionalSchedule>
+            <fixedRateSchedule><initialValue>0.0275</initialValue></fixedRateSchedule>
+            <dayCountFraction>30E/360</dayCountFraction>
+          </calculation>
+        </calculationPeriodAmount>
+      </swapStream>
+    </swap>
+  </trade>
+</dataDocument>"#;
+
+    const OPTION: &str = r#"
+<fpml:equityOption xmlns:fpml="http://www.fpml.org/FpML-5/confirmation">
+  <fpml:optionType>Put</fpml:optionType>
+  <fpml:underlyer>
+    <fpml:singleUnderlyer>
+      <fpml:equity>
+        <fpml:instrumentId instrumentIdScheme="http://www.fpml.org/spec/2002/instrument-id-RIC-1-0">STOXX50E</fpml:instrumentId>
+      </fpml:equity>
+    </fpml:singleUnderlyer>
+  </fpml:underlyer>
+  <fpml:equityExercise>
+    <fpml:equityAmericanExercise>
+      <fpml:expirationDate>
+        <fpml:adjustableDate><fpml:unadjustedDate>2025-12-19Z</fpml:unadjustedDate></fpml:adjustableDate>
+      </fpml:expirationDate>
+    </fpml:equityAmericanExercise>
+  </fpml:equityExercise>
+  <fpml:strike><fpml:strikePrice>4800</fpml:strikePrice><fpml:currency>EUR</fpml:currency></fpml:strike>
+  <fpml:numberOfOptions>500</fpml:numberOfOptions>
+</fpml:equityOption>"#;
+
+    #[test]
+    fn test_read_swap() {
+        let TermSheet::Swap(swap) = TermSheet::from_fpml(SWAP).unwrap() else {
+            panic!("not a swap");
+        };
+
+        assert_eq!(swap.notional, 25_000_000.0);
+        assert_eq!(swap.currency, EUR);
+        assert_eq!(swap.effective_date, date!(2024 - 03 - 06));
+        assert_eq!(swap.termination_date, date!(2034 - 03 - 06));
+        assert_eq!(swap.fixed_rate, 0.0275);
+        assert_eq!(swap.fixed_frequency, PaymentFrequency::Annually);
+        assert_eq!(swap.fixed_day_count, DayCountFraction::ThirtyE360);
+        assert_eq!(swap.floating_index, "EUR-EURIBOR-Reuters");
+        assert_eq!(swap.floating_spread, 0.001);
+        assert_eq!(swap.floating_frequency, PaymentFrequency::SemiAnnually);
+        assert_eq!(swap.floating_day_count, DayCountFraction::Actual360);
+
+        let terms = TermSheet::Swap(swap);
+        assert_eq!(
+            TermSheet::from_fpml(&terms.to_fpml().unwrap()).unwrap(),
+            terms
+        );
+    }
+
+    #[test]
+    fn test_read_option() {
+        let terms = TermSheet::from_fpml(OPTION).unwrap();
+        let TermSheet::Option(option) = &terms else {
+            panic!("not an option");
+        };
+
+        assert_eq!(option.underlying, "STOXX50E");
+        assert_eq!(option.option_type, TypeFlag::Put);
+        assert_eq!(option.exercise, ExerciseFlag::American);
+        assert_eq!(option.strike_price, 4800.0);
+        assert_eq!(option.expiration_date, date!(2025 - 12 - 19));
+        assert_eq!(option.number_of_options, 500.0);
+        assert_eq!(option.option_entitlement, 1.0);
+
+        assert_eq!(
+            TermSheet::from_fpml(&terms.to_fpml().unwrap()).unwrap(),
+            terms
+        );
+    }
+
+    #[test]
+    fn test_write_bond() {
+        let bond = TermSheet::Bond(BondTerms {
+            identifier: "US912828Z780".to_string(),
+            currency: USD,
+            face_value: 1000.0,
+            coupon_rate: 0.015,
+            coupon_frequency: PaymentFrequency::SemiAnnually,
+            day_count: DayCountFraction::ActualActualICMA,
+            maturity_date: date!(2030 - 02 - 15),
+        });
+
+        let xml = bond.to_fpml().unwrap();
+        assert!(xml.starts_with("<bond>\n  <instrumentId>US912828Z780</instrumentId>\n"));
+        assert!(xml.contains(
+            "<paymentFrequency>\n    <periodMultiplier>6</periodMultiplier>\n    <period>M</period>\n"
+        ));
+        assert_eq!(TermSheet::from_fpml(&xml).unwrap(), bond);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            TermSheet::from_fpml("<trade><fra/></trade>"),
+            Err(TermSheetError::UnknownProduct)
+        );
+        assert!(matches!(
+            TermSheet::from_fpml("<swap><swapStream>"),
+            Err(TermSheetError::Xml(_))
+        ));
+        assert_eq!(
+            TermSheet::from_fpml(
+                &OPTION.replace("<fpml:optionType>Put", "<fpml:optionType>Chooser")
+            ),
+            Err(TermSheetError::InvalidField {
+                field: "optionType".to_string(),
+                value: "Chooser".to_string(),
+            })
+        );
+        assert_eq!(
+            TermSheet::from_fpml(&OPTION.replace("strikePrice", "strikePercentage")),
+            Err(TermSheetError::MissingField("strikePrice".to_string()))
+        );
+    }
+}
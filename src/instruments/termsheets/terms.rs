@@ -0,0 +1,461 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Term sheets: the contractual terms of basic swaps, options and bonds,
+//! independent of any pricing model.
+//!
+//! Term sheets are read from and written to FpML (see [`TermSheet::from_fpml`])
+//! and FIX-style tag=value messages (see [`TermSheet::from_fix`]), and turned
+//! into the pricing types of the instruments module:
+//!
+//! ```
+//! use RustQuant::instruments::termsheets::*;
+//!
+//! let fix = "35=d|167=OPT|311=AAPL|201=1|202=150|15=USD|541=20250620|1194=0|38=10|231=100";
+//! let TermSheet::Option(option) = TermSheet::from_fix(fix).unwrap() else {
+//!     panic!("not an option");
+//! };
+//!
+//! let pricer = option.to_black_scholes_merton(160.0, 0.25, 0.05, 0.05, None).unwrap();
+//! assert_eq!(pricer.strike_price, 150.0);
+//! ```
+
+use crate::curves::YieldCurve;
+use crate::instruments::bonds::CouponBond;
+use crate::instruments::options::{BlackScholesMerton, ExerciseFlag, TypeFlag};
+use crate::money::{Currency, Rounding};
+use crate::time::{
+    ActualActualICMA, BusinessDayConvention, DayCountConvention, DayCounter, PaymentFrequency,
+    Schedule, StubRule, Tenor, TimeUnit, WeekendsOnly,
+};
+use std::collections::BTreeMap;
+use thiserror::Error;
+use time::{Date, Month, OffsetDateTime};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Terms of a swap, option or bond.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TermSheet {
+    /// Fixed-for-floating interest rate swap.
+    Swap(SwapTerms),
+    /// Equity option.
+    Option(OptionTerms),
+    /// Fixed-coupon bond.
+    Bond(BondTerms),
+}
+
+/// Terms of a fixed-for-floating interest rate swap.
+///
+/// The fixed and floating legs share the notional, currency and dates.
+/// Which party pays the fixed leg is left to the trade it belongs to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwapTerms {
+    /// Notional amount.
+    pub notional: f64,
+    /// Currency of the notional.
+    pub currency: Currency,
+    /// Start of the first calculation period (unadjusted).
+    pub effective_date: Date,
+    /// End of the last calculation period (unadjusted).
+    pub termination_date: Date,
+    /// Fixed rate (e.g. 0.035 for 3.5%).
+    pub fixed_rate: f64,
+    /// Payment frequency of the fixed leg.
+    pub fixed_frequency: PaymentFrequency,
+    /// Day count fraction of the fixed leg.
+    pub fixed_day_count: DayCountFraction,
+    /// Floating rate index, in ISDA notation (e.g. `"USD-SOFR-OIS Compound"`).
+    pub floating_index: String,
+    /// Spread over the floating index.
+    pub floating_spread: f64,
+    /// Payment frequency of the floating leg.
+    pub floating_frequency: PaymentFrequency,
+    /// Day count fraction of the floating leg.
+    pub floating_day_count: DayCountFraction,
+}
+
+/// Terms of an equity option.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptionTerms {
+    /// Identifier of the underlying (e.g. a ticker or ISIN).
+    pub underlying: String,
+    /// Call or put.
+    pub option_type: TypeFlag,
+    /// Exercise style.
+    pub exercise: ExerciseFlag,
+    /// Strike price.
+    pub strike_price: f64,
+    /// Currency of the strike.
+    pub currency: Currency,
+    /// Last exercise date (unadjusted).
+    pub expiration_date: Date,
+    /// Number of options.
+    pub number_of_options: f64,
+    /// Units of the underlying per option.
+    pub option_entitlement: f64,
+}
+
+/// Terms of a fixed-coupon bullet bond.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BondTerms {
+    /// Identifier of the bond (e.g. an ISIN).
+    pub identifier: String,
+    /// Currency of the bond.
+    pub currency: Currency,
+    /// Face value, repaid at maturity.
+    pub face_value: f64,
+    /// Annual coupon rate (e.g. 0.05 for 5%).
+    pub coupon_rate: f64,
+    /// Coupon frequency.
+    pub coupon_frequency: PaymentFrequency,
+    /// Day count fraction of the coupons.
+    pub day_count: DayCountFraction,
+    /// Maturity date.
+    pub maturity_date: Date,
+}
+
+/// Day count fractions, as named in FpML (the ISDA 2006 definitions).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DayCountFraction {
+    /// `ACT/365.FIXED`.
+    Actual365Fixed,
+    /// `ACT/360`.
+    Actual360,
+    /// `ACT/ACT.ISDA`.
+    ActualActualISDA,
+    /// `ACT/ACT.ICMA`.
+    ActualActualICMA,
+    /// `30/360` (bond basis).
+    Thirty360,
+    /// `30E/360` (Eurobond basis).
+    ThirtyE360,
+}
+
+/// Term sheet import and export errors.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum TermSheetError {
+    /// The document is not well-formed XML.
+    #[error("Malformed XML: {0}")]
+    Xml(String),
+
+    /// The document does not describe a swap, option or bond.
+    #[error("No swap, option or bond found")]
+    UnknownProduct,
+
+    /// A required field is missing.
+    #[error("Missing field: {0}")]
+    MissingField(String),
+
+    /// A field has a value that cannot be parsed.
+    #[error("Invalid value for {field}: {value}")]
+    InvalidField {
+        /// FpML element or FIX tag.
+        field: String,
+        /// The offending value.
+        value: String,
+    },
+
+    /// The terms are valid, but cannot be represented by the target.
+    #[error("Unsupported: {0}")]
+    Unsupported(String),
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl DayCountFraction {
+    /// FpML code of the day count fraction.
+    pub fn code(&self) -> &'static str {
+        match self {
+            DayCountFraction::Actual365Fixed => "ACT/365.FIXED",
+            DayCountFraction::Actual360 => "ACT/360",
+            DayCountFraction::ActualActualISDA => "ACT/ACT.ISDA",
+            DayCountFraction::ActualActualICMA => "ACT/ACT.ICMA",
+            DayCountFraction::Thirty360 => "30/360",
+            DayCountFraction::ThirtyE360 => "30E/360",
+        }
+    }
+
+    /// Day count fraction of an FpML code (case insensitive).
+    pub fn from_code(code: &str) -> Option<Self> {
+        let code = code.trim().to_ascii_uppercase();
+
+        [
+            DayCountFraction::Actual365Fixed,
+            DayCountFraction::Actual360,
+            DayCountFraction::ActualActualISDA,
+            DayCountFraction::ActualActualICMA,
+            DayCountFraction::Thirty360,
+            DayCountFraction::ThirtyE360,
+        ]
+        .into_iter()
+        .find(|fraction| fraction.code() == code)
+    }
+
+    /// Day counter for periods paid at the given frequency (which only
+    /// matters for Actual/Actual (ICMA)).
+    pub fn day_counter(&self, frequency: PaymentFrequency) -> Box<dyn DayCounter> {
+        match self {
+            DayCountFraction::Actual365Fixed => Box::new(DayCountConvention::Actual365),
+            DayCountFraction::Actual360 => Box::new(DayCountConvention::Actual360),
+            DayCountFraction::ActualActualISDA => Box::new(DayCountConvention::ActualActualISDA),
+            DayCountFraction::ActualActualICMA => Box::new(ActualActualICMA::new(frequency)),
+            DayCountFraction::Thirty360 => Box::new(DayCountConvention::Thirty360US),
+            DayCountFraction::ThirtyE360 => Box::new(DayCountConvention::Thirty360),
+        }
+    }
+}
+
+impl SwapTerms {
+    /// Unadjusted schedule of the fixed leg, rolled back from the
+    /// termination date.
+    pub fn fixed_schedule(&self) -> Schedule {
+        self.schedule(self.fixed_frequency)
+    }
+
+    /// Unadjusted schedule of the floating leg, rolled back from the
+    /// termination date.
+    pub fn floating_schedule(&self) -> Schedule {
+        self.schedule(self.floating_frequency)
+    }
+
+    fn schedule(&self, frequency: PaymentFrequency) -> Schedule {
+        Schedule::new(
+            self.effective_date,
+            self.termination_date,
+            frequency,
+            &WeekendsOnly,
+            BusinessDayConvention::Actual,
+            StubRule::ShortFront,
+        )
+    }
+}
+
+impl OptionTerms {
+    /// Black-Scholes-Merton pricer for one unit of the underlying, given the
+    /// market data. Only European options can be priced this way.
+    pub fn to_black_scholes_merton(
+        &self,
+        underlying_price: f64,
+        volatility: f64,
+        risk_free_rate: f64,
+        cost_of_carry: f64,
+        evaluation_date: Option<OffsetDateTime>,
+    ) -> Result<BlackScholesMerton, TermSheetError> {
+        if self.exercise != ExerciseFlag::European {
+            return Err(TermSheetError::Unsupported(format!(
+                "{:?} exercise in the Black-Scholes-Merton model",
+                self.exercise
+            )));
+        }
+
+        Ok(BlackScholesMerton::new(
+            cost_of_carry,
+            underlying_price,
+            self.strike_price,
+            volatility,
+            risk_free_rate,
+            evaluation_date,
+            self.expiration_date.midnight().assume_utc(),
+            self.option_type,
+        ))
+    }
+}
+
+impl BondTerms {
+    /// Coupon bond evaluated at `evaluation_date` on the yield curve, with
+    /// its coupons constructed (payment dates are rolled to the following
+    /// business day).
+    pub fn to_coupon_bond(
+        &self,
+        evaluation_date: OffsetDateTime,
+        yield_curve: YieldCurve,
+    ) -> CouponBond {
+        let mut bond = CouponBond {
+            evaluation_date,
+            expiration_date: self.maturity_date.midnight().assume_utc(),
+            currency: Some(self.currency),
+            coupon_rate: self.coupon_rate,
+            coupon_frequency: self.coupon_frequency,
+            settlement_convention: BusinessDayConvention::Following,
+            day_counter: self.day_count.day_counter(self.coupon_frequency),
+            yield_curve,
+            face_value: self.face_value,
+            rounding: Rounding::None,
+            coupons: BTreeMap::new(),
+        };
+        bond.construct_coupons();
+
+        bond
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Period of a payment frequency (e.g. `6M` for semi-annual payments).
+/// Semi-monthly payments are not a whole number of days, weeks or months.
+pub(crate) fn frequency_period(frequency: PaymentFrequency) -> Result<Tenor, TermSheetError> {
+    match frequency {
+        PaymentFrequency::Daily => Ok(Tenor::days(1)),
+        PaymentFrequency::Weekly => Ok(Tenor::weeks(1)),
+        PaymentFrequency::BiWeekly => Ok(Tenor::weeks(2)),
+        PaymentFrequency::SemiMonthly => Err(TermSheetError::Unsupported(
+            "semi-monthly payment frequency".to_string(),
+        )),
+        PaymentFrequency::Monthly => Ok(Tenor::months(1)),
+        PaymentFrequency::SemiQuarterly => Ok(Tenor::months(2)),
+        PaymentFrequency::Quarterly => Ok(Tenor::months(3)),
+        PaymentFrequency::TriAnnually => Ok(Tenor::months(4)),
+        PaymentFrequency::SemiAnnually => Ok(Tenor::months(6)),
+        PaymentFrequency::Annually => Ok(Tenor::years(1)),
+    }
+}
+
+/// Payment frequency of a period (`12M` and `1Y` are both annual).
+pub(crate) fn period_frequency(period: Tenor) -> Option<PaymentFrequency> {
+    match (period.unit, period.length) {
+        (TimeUnit::Days, 1) => Some(PaymentFrequency::Daily),
+        (TimeUnit::Weeks, 1) => Some(PaymentFrequency::Weekly),
+        (TimeUnit::Weeks, 2) => Some(PaymentFrequency::BiWeekly),
+        (TimeUnit::Months, 1) => Some(PaymentFrequency::Monthly),
+        (TimeUnit::Months, 2) => Some(PaymentFrequency::SemiQuarterly),
+        (TimeUnit::Months, 3) => Some(PaymentFrequency::Quarterly),
+        (TimeUnit::Months, 4) => Some(PaymentFrequency::TriAnnually),
+        (TimeUnit::Months, 6) => Some(PaymentFrequency::SemiAnnually),
+        (TimeUnit::Months, 12) | (TimeUnit::Years, 1) => Some(PaymentFrequency::Annually),
+        _ => None,
+    }
+}
+
+/// Parses a field, reporting the field name on failure.
+pub(crate) fn parse_field<T, F>(field: &str, value: &str, parse: F) -> Result<T, TermSheetError>
+where
+    F: FnOnce(&str) -> Option<T>,
+{
+    parse(value.trim()).ok_or_else(|| TermSheetError::InvalidField {
+        field: field.to_string(),
+        value: value.to_string(),
+    })
+}
+
+/// A calendar date from its year, month and day digits.
+pub(crate) fn calendar_date(year: &str, month: &str, day: &str) -> Option<Date> {
+    let digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if ![year, month, day].iter().all(|s| digits(s)) {
+        return None;
+    }
+
+    let month = Month::try_from(month.parse::<u8>().ok()?).ok()?;
+
+    Date::from_calendar_date(year.parse().ok()?, month, day.parse().ok()?).ok()
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_terms {
+    use super::*;
+    use crate::money::USD;
+    use time::macros::date;
+
+    fn swap() -> SwapTerms {
+        SwapTerms {
+            notional: 10_000_000.0,
+            currency: USD,
+            effective_date: date!(2024 - 01 - 15),
+            termination_date: date!(2029 - 01 - 15),
+            fixed_rate: 0.0385,
+            fixed_frequency: PaymentFrequency::SemiAnnually,
+            fixed_day_count: DayCountFraction::Thirty360,
+            floating_index: "USD-SOFR-OIS Compound".to_string(),
+            floating_spread: 0.0,
+            floating_frequency: PaymentFrequency::Quarterly,
+            floating_day_count: DayCountFraction::Actual360,
+        }
+    }
+
+    #[test]
+    fn test_day_count_codes() {
+        assert_eq!(
+            DayCountFraction::from_code("act/act.icma"),
+            Some(DayCountFraction::ActualActualICMA)
+        );
+        assert_eq!(DayCountFraction::from_code("BUS/252"), None);
+
+        let counter = DayCountFraction::Actual360.day_counter(PaymentFrequency::Annually);
+        let (d1, d2) = (
+            date!(2024 - 01 - 01).midnight().assume_utc(),
+            date!(2024 - 07 - 01).midnight().assume_utc(),
+        );
+        assert_approx_equal!(counter.year_fraction(d1, d2), 182.0 / 360.0, 1e-12);
+    }
+
+    #[test]
+    fn test_frequency_periods() {
+        for frequency in [
+            PaymentFrequency::Weekly,
+            PaymentFrequency::Monthly,
+            PaymentFrequency::Quarterly,
+            PaymentFrequency::SemiAnnually,
+            PaymentFrequency::Annually,
+        ] {
+            let period = frequency_period(frequency).unwrap();
+            assert_eq!(period_frequency(period), Some(frequency));
+        }
+
+        assert_eq!(
+            period_frequency(Tenor::months(12)),
+            Some(PaymentFrequency::Annually)
+        );
+        assert!(frequency_period(PaymentFrequency::SemiMonthly).is_err());
+    }
+
+    #[test]
+    fn test_swap_schedules() {
+        let swap = swap();
+
+        assert_eq!(swap.fixed_schedule().dates.len(), 11);
+        assert_eq!(swap.floating_schedule().dates.len(), 21);
+    }
+
+    #[test]
+    fn test_option_pricer() {
+        let mut option = OptionTerms {
+            underlying: "AAPL".to_string(),
+            option_type: TypeFlag::Put,
+            exercise: ExerciseFlag::European,
+            strike_price: 150.0,
+            currency: USD,
+            expiration_date: date!(2025 - 06 - 20),
+            number_of_options: 10.0,
+            option_entitlement: 100.0,
+        };
+
+        let evaluation_date = date!(2024 - 06 - 20).midnight().assume_utc();
+        let pricer = option
+            .to_black_scholes_merton(150.0, 0.2, 0.05, 0.05, Some(evaluation_date))
+            .unwrap();
+        assert_eq!(pricer.option_type, TypeFlag::Put);
+        assert!(pricer.price() > 0.0);
+
+        option.exercise = ExerciseFlag::American;
+        assert!(matches!(
+            option.to_black_scholes_merton(150.0, 0.2, 0.05, 0.05, None),
+            Err(TermSheetError::Unsupported(_))
+        ));
+    }
+}
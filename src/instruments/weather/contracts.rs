@@ -0,0 +1,320 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! HDD and CDD swaps and options.
+//!
+//! Both pay a tick size (currency per degree day) times the index's
+//! distance from a strike, often capped. There is no traded underlying to
+//! hedge with, so they are priced as discounted expectations of the
+//! simulated index under the fitted temperature model (the "actuarial"
+//! price, without a market price of weather risk).
+
+use crate::instruments::options::TypeFlag;
+use crate::instruments::weather::{
+    DegreeDayIndex, SeasonalTemperature, TemperatureSeries, WeatherError,
+};
+use crate::instruments::{PricingEngine, PricingResult};
+use crate::stochastics::SimulationConfig;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Degree-day swap: at the end of the period the long side receives
+/// `tick_size * (index - strike)`, limited to `cap` either way.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DegreeDaySwap {
+    /// Degree-day index the swap settles on.
+    pub index: DegreeDayIndex,
+
+    /// Index level exchanged for the realized index.
+    pub strike: f64,
+
+    /// Payment per degree day.
+    pub tick_size: f64,
+
+    /// Largest payment either way, if any.
+    pub cap: Option<f64>,
+}
+
+/// Degree-day call or put: at the end of the period it pays
+/// `tick_size * max(±(index - strike), 0)`, up to `cap`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DegreeDayOption {
+    /// Degree-day index the option settles on.
+    pub index: DegreeDayIndex,
+
+    /// Strike level of the index.
+    pub strike: f64,
+
+    /// Payment per degree day.
+    pub tick_size: f64,
+
+    /// Call or put.
+    pub option_type: TypeFlag,
+
+    /// Largest payment, if any.
+    pub cap: Option<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl DegreeDaySwap {
+    /// New uncapped swap.
+    pub fn new(index: DegreeDayIndex, strike: f64, tick_size: f64) -> Self {
+        Self {
+            index,
+            strike,
+            tick_size,
+            cap: None,
+        }
+    }
+
+    /// Limits the payment either way.
+    pub fn with_cap(mut self, cap: f64) -> Self {
+        self.cap = Some(cap);
+        self
+    }
+
+    /// Payment to the long side for a given index value.
+    pub fn payoff(&self, index: f64) -> f64 {
+        let payment = self.tick_size * (index - self.strike);
+
+        match self.cap {
+            Some(cap) => payment.clamp(-cap, cap),
+            None => payment,
+        }
+    }
+
+    /// Value to the long side as at the last day of `history`, discounting
+    /// the settlement at the end of the period at the continuously
+    /// compounded `rate` (Actual/365).
+    pub fn price(
+        &self,
+        model: &SeasonalTemperature,
+        history: &TemperatureSeries,
+        rate: f64,
+        n_paths: usize,
+        config: &SimulationConfig,
+    ) -> Result<PricingResult, WeatherError> {
+        monte_carlo(
+            &self.index,
+            model,
+            history,
+            rate,
+            n_paths,
+            config,
+            |index| self.payoff(index),
+        )
+    }
+
+    /// Strike of an uncapped swap worth nothing: the expected index.
+    pub fn fair_strike(
+        index: &DegreeDayIndex,
+        model: &SeasonalTemperature,
+        history: &TemperatureSeries,
+        n_paths: usize,
+        config: &SimulationConfig,
+    ) -> Result<f64, WeatherError> {
+        let values = index.simulate(model, history, n_paths, config)?;
+
+        Ok(values.iter().sum::<f64>() / values.len() as f64)
+    }
+}
+
+impl DegreeDayOption {
+    /// New uncapped option.
+    pub fn new(index: DegreeDayIndex, strike: f64, tick_size: f64, option_type: TypeFlag) -> Self {
+        Self {
+            index,
+            strike,
+            tick_size,
+            option_type,
+            cap: None,
+        }
+    }
+
+    /// Limits the payment.
+    pub fn with_cap(mut self, cap: f64) -> Self {
+        self.cap = Some(cap);
+        self
+    }
+
+    /// Payment for a given index value.
+    pub fn payoff(&self, index: f64) -> f64 {
+        let intrinsic = match self.option_type {
+            TypeFlag::Call => index - self.strike,
+            TypeFlag::Put => self.strike - index,
+        };
+        let payment = self.tick_size * intrinsic.max(0.0);
+
+        match self.cap {
+            Some(cap) => payment.min(cap),
+            None => payment,
+        }
+    }
+
+    /// Value as at the last day of `history`, as [`DegreeDaySwap::price`].
+    pub fn price(
+        &self,
+        model: &SeasonalTemperature,
+        history: &TemperatureSeries,
+        rate: f64,
+        n_paths: usize,
+        config: &SimulationConfig,
+    ) -> Result<PricingResult, WeatherError> {
+        monte_carlo(
+            &self.index,
+            model,
+            history,
+            rate,
+            n_paths,
+            config,
+            |index| self.payoff(index),
+        )
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Discounted mean payoff over simulated index values, with its standard
+/// error.
+fn monte_carlo<F>(
+    index: &DegreeDayIndex,
+    model: &SeasonalTemperature,
+    history: &TemperatureSeries,
+    rate: f64,
+    n_paths: usize,
+    config: &SimulationConfig,
+    payoff: F,
+) -> Result<PricingResult, WeatherError>
+where
+    F: Fn(f64) -> f64,
+{
+    let today = history.last_date().ok_or(WeatherError::EmptySeries)?;
+    let t = (index.end - today).whole_days().max(0) as f64 / 365.0;
+    let discount_factor = (-rate * t).exp();
+
+    let payoffs = index
+        .simulate(model, history, n_paths, config)?
+        .into_iter()
+        .map(|value| discount_factor * payoff(value))
+        .collect::<Vec<_>>();
+
+    let m = payoffs.len() as f64;
+    let mean = payoffs.iter().sum::<f64>() / m;
+    let variance = payoffs.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / (m - 1.0);
+
+    Ok(PricingResult::new(mean)
+        .with_standard_error((variance / m).sqrt())
+        .with_engine(PricingEngine::Simulation))
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_weather_contracts {
+    use super::*;
+    use crate::instruments::weather::DegreeDayType;
+    use std::collections::BTreeMap;
+    use time::macros::date;
+    use time::Date;
+
+    const TODAY: Date = date!(2024 - 10 - 31);
+
+    fn model() -> SeasonalTemperature {
+        SeasonalTemperature::new(date!(2015 - 01 - 01), 55.0, 0.0005, 20.0, -1.8, 0.25, 4.0)
+    }
+
+    fn history() -> TemperatureSeries {
+        TemperatureSeries::new(BTreeMap::from([(TODAY, 50.0)]))
+    }
+
+    fn january(kind: DegreeDayType) -> DegreeDayIndex {
+        DegreeDayIndex::new(kind, 65.0, date!(2025 - 01 - 01), date!(2025 - 01 - 31))
+    }
+
+    fn config() -> SimulationConfig {
+        SimulationConfig::new(true).with_seed(17)
+    }
+
+    #[test]
+    fn test_swap_at_fair_strike() {
+        let (model, history) = (model(), history());
+        let index = january(DegreeDayType::Heating);
+
+        // About 30°F below the base every day of January.
+        let strike = DegreeDaySwap::fair_strike(&index, &model, &history, 5000, &config()).unwrap();
+        assert!(strike > 800.0 && strike < 1100.0);
+
+        // On the same paths, a swap struck at the mean index is worth
+        // nothing.
+        let swap = DegreeDaySwap::new(index, strike, 20.0);
+        let value = swap.price(&model, &history, 0.05, 5000, &config()).unwrap();
+        assert_approx_equal!(value.value(), 0.0, 1e-6);
+
+        // A cap limits the payments both ways.
+        let capped = swap.with_cap(1000.0);
+        assert_eq!(capped.payoff(strike + 100.0), 1000.0);
+        assert_eq!(capped.payoff(strike - 100.0), -1000.0);
+        assert_eq!(capped.payoff(strike + 10.0), 200.0);
+    }
+
+    #[test]
+    fn test_option_parity() {
+        let (model, history) = (model(), history());
+        let index = january(DegreeDayType::Heating);
+        let (strike, tick, rate) = (950.0, 20.0, 0.05);
+
+        let call = DegreeDayOption::new(index, strike, tick, TypeFlag::Call)
+            .price(&model, &history, rate, 5000, &config())
+            .unwrap();
+        let put = DegreeDayOption::new(index, strike, tick, TypeFlag::Put)
+            .price(&model, &history, rate, 5000, &config())
+            .unwrap();
+        let swap = DegreeDaySwap::new(index, strike, tick)
+            .price(&model, &history, rate, 5000, &config())
+            .unwrap();
+
+        assert_approx_equal!(call.value() - put.value(), swap.value(), 1e-6);
+        assert!(call.standard_error.unwrap() > 0.0);
+        assert_eq!(call.engine, Some(PricingEngine::Simulation));
+
+        // January has hardly any cooling degree days.
+        let cdd = DegreeDayOption::new(january(DegreeDayType::Cooling), 10.0, tick, TypeFlag::Call)
+            .price(&model, &history, rate, 5000, &config())
+            .unwrap();
+        assert!(cdd.value() < 1.0);
+    }
+
+    #[test]
+    fn test_capped_option() {
+        let (model, history) = (model(), history());
+        let option =
+            DegreeDayOption::new(january(DegreeDayType::Heating), 900.0, 20.0, TypeFlag::Call);
+        let capped = option.with_cap(500.0);
+
+        let value = option
+            .price(&model, &history, 0.0, 5000, &config())
+            .unwrap();
+        let capped_value = capped
+            .price(&model, &history, 0.0, 5000, &config())
+            .unwrap();
+
+        assert!(capped_value.value() < value.value());
+        assert!(capped_value.value() <= 500.0);
+        assert_eq!(capped.payoff(1000.0), 500.0);
+    }
+}
@@ -0,0 +1,382 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Daily temperatures and degree-day indices.
+//!
+//! A heating degree day (HDD) measures how far a day's average temperature
+//! $T$ falls below a base (65°F or 18°C by convention), and a cooling
+//! degree day (CDD) how far it rises above it:
+//! $$
+//! HDD = \max(B - T, 0), \qquad CDD = \max(T - B, 0).
+//! $$
+//! Weather contracts settle on their sum over a period, usually a month or
+//! a winter (HDD) or summer (CDD) season.
+
+use crate::instruments::weather::SeasonalTemperature;
+use crate::stochastics::SimulationConfig;
+use std::collections::BTreeMap;
+use thiserror::Error;
+use time::{Date, Duration};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Daily average temperatures at a weather station.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TemperatureSeries {
+    /// Average temperature by day.
+    pub observations: BTreeMap<Date, f64>,
+}
+
+/// Heating or cooling degree days.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DegreeDayType {
+    /// Heating degree days: the shortfall of the temperature below the
+    /// base.
+    Heating,
+
+    /// Cooling degree days: the excess of the temperature over the base.
+    Cooling,
+}
+
+/// Cumulative degree days over a period (both ends included).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DegreeDayIndex {
+    /// Heating or cooling.
+    pub kind: DegreeDayType,
+
+    /// Base temperature.
+    pub base: f64,
+
+    /// First day of the period.
+    pub start: Date,
+
+    /// Last day of the period.
+    pub end: Date,
+}
+
+/// Weather error enum.
+#[derive(Debug, Clone, Copy, PartialEq, Error)]
+pub enum WeatherError {
+    /// The temperature series has no observations.
+    #[error("The temperature series has no observations.")]
+    EmptySeries,
+
+    /// A day needed is missing from the temperature series.
+    #[error("No temperature observed on {0}.")]
+    MissingObservation(Date),
+
+    /// The period ends before it starts.
+    #[error("The degree-day period ends before it starts.")]
+    InvalidPeriod,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl DegreeDayType {
+    /// Degree days of a day with the given average temperature.
+    pub fn degree_days(&self, temperature: f64, base: f64) -> f64 {
+        match self {
+            Self::Heating => (base - temperature).max(0.0),
+            Self::Cooling => (temperature - base).max(0.0),
+        }
+    }
+}
+
+impl TemperatureSeries {
+    /// New series from daily average temperatures.
+    pub fn new(observations: BTreeMap<Date, f64>) -> Self {
+        Self { observations }
+    }
+
+    /// Temperature observed on the given day.
+    pub fn get(&self, date: Date) -> Option<f64> {
+        self.observations.get(&date).copied()
+    }
+
+    /// Last day observed, i.e. the valuation date of contracts priced from
+    /// this history.
+    pub fn last_date(&self) -> Option<Date> {
+        self.observations.keys().next_back().copied()
+    }
+
+    /// Temperatures on every day from `start` to `end`, or an error on the
+    /// first day missing.
+    pub fn daily(&self, start: Date, end: Date) -> Result<Vec<f64>, WeatherError> {
+        days(start, end)
+            .map(|date| self.get(date).ok_or(WeatherError::MissingObservation(date)))
+            .collect()
+    }
+
+    /// Series from a `DataFrame` with a `date` column (as a date or an
+    /// ISO 8601 `YYYY-MM-DD` string) and a column of daily average
+    /// temperatures. Rows with missing values are skipped.
+    #[cfg(feature = "data")]
+    pub fn from_frame(
+        frame: &polars::prelude::DataFrame,
+        temperature_column: &str,
+    ) -> crate::error::RustQuantResult<Self> {
+        use crate::data::DataError;
+        use crate::error::RustQuantError;
+        use crate::market::fixings::parse_date;
+        use polars::prelude::DataType;
+
+        let dates = frame
+            .column("date")
+            .and_then(|column| column.cast(&DataType::Utf8))
+            .map_err(DataError::from)?;
+        let temperatures = frame
+            .column(temperature_column)
+            .and_then(|column| column.cast(&DataType::Float64))
+            .map_err(DataError::from)?;
+
+        let mut observations = BTreeMap::new();
+
+        for (date, temperature) in dates
+            .utf8()
+            .map_err(DataError::from)?
+            .into_iter()
+            .zip(temperatures.f64().map_err(DataError::from)?)
+        {
+            let (Some(date), Some(temperature)) = (date, temperature) else {
+                continue;
+            };
+            let date = parse_date(date).ok_or_else(|| {
+                RustQuantError::invalid_parameter(format!("Invalid temperature date {date:?}."))
+            })?;
+
+            observations.insert(date, temperature);
+        }
+
+        Ok(Self::new(observations))
+    }
+
+    /// Series from a CSV file with a header, a `date` (`YYYY-MM-DD`) column
+    /// and a column of daily average temperatures.
+    #[cfg(feature = "data")]
+    pub fn from_csv(path: &str, temperature_column: &str) -> crate::error::RustQuantResult<Self> {
+        use crate::data::{Data, DataFormat, DataReader};
+
+        let mut data = Data::new(DataFormat::CSV, path.to_string());
+        data.read()?;
+
+        Self::from_frame(&data.data, temperature_column)
+    }
+}
+
+impl DegreeDayIndex {
+    /// New index over the days from `start` to `end`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `end` is before `start`.
+    pub fn new(kind: DegreeDayType, base: f64, start: Date, end: Date) -> Self {
+        assert!(end >= start, "{}", WeatherError::InvalidPeriod);
+
+        Self {
+            kind,
+            base,
+            start,
+            end,
+        }
+    }
+
+    /// Number of days in the period.
+    pub fn n_days(&self) -> usize {
+        (self.end - self.start).whole_days() as usize + 1
+    }
+
+    /// Index value from daily temperatures over the period.
+    pub fn accumulate(&self, temperatures: impl IntoIterator<Item = f64>) -> f64 {
+        temperatures
+            .into_iter()
+            .map(|temperature| self.kind.degree_days(temperature, self.base))
+            .sum()
+    }
+
+    /// Index value from observed temperatures, or an error if a day of the
+    /// period is missing from the series.
+    pub fn realized(&self, series: &TemperatureSeries) -> Result<f64, WeatherError> {
+        Ok(self.accumulate(series.daily(self.start, self.end)?))
+    }
+
+    /// Simulated index values, one per path, as at the last day of the
+    /// history.
+    ///
+    /// Days of the period already in the history count at their observed
+    /// temperatures; the rest are simulated from the last observed
+    /// temperature with the model.
+    pub fn simulate(
+        &self,
+        model: &SeasonalTemperature,
+        history: &TemperatureSeries,
+        n_paths: usize,
+        config: &SimulationConfig,
+    ) -> Result<Vec<f64>, WeatherError> {
+        let today = history.last_date().ok_or(WeatherError::EmptySeries)?;
+
+        let realized = match self.start <= today {
+            true => self.accumulate(history.daily(self.start, self.end.min(today))?),
+            false => 0.0,
+        };
+
+        if self.end <= today {
+            return Ok(vec![realized; n_paths]);
+        }
+
+        let horizon = (self.end - today).whole_days() as usize;
+        let first = (self.start - today).whole_days().max(1) as usize;
+        let paths = model.simulate(
+            today,
+            history.observations[&today],
+            horizon,
+            n_paths,
+            config,
+        );
+
+        Ok(paths
+            .iter()
+            .map(|path| realized + self.accumulate(path.iter().skip(first).copied()))
+            .collect())
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Days from `start` to `end`, both included.
+fn days(start: Date, end: Date) -> impl Iterator<Item = Date> {
+    (0..=(end - start).whole_days()).map(move |i| start + Duration::days(i))
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_degree_days {
+    use super::*;
+    use time::macros::date;
+
+    fn week() -> TemperatureSeries {
+        let temperatures = [60.0, 55.0, 70.0, 65.0, 48.5, 72.0, 66.0];
+
+        TemperatureSeries::new(
+            days(date!(2024 - 01 - 01), date!(2024 - 01 - 07))
+                .zip(temperatures)
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_degree_days() {
+        assert_eq!(DegreeDayType::Heating.degree_days(60.0, 65.0), 5.0);
+        assert_eq!(DegreeDayType::Heating.degree_days(70.0, 65.0), 0.0);
+        assert_eq!(DegreeDayType::Cooling.degree_days(70.0, 65.0), 5.0);
+        assert_eq!(DegreeDayType::Cooling.degree_days(60.0, 65.0), 0.0);
+    }
+
+    #[test]
+    fn test_realized_index() {
+        let series = week();
+        let (start, end) = (date!(2024 - 01 - 01), date!(2024 - 01 - 07));
+
+        let hdd = DegreeDayIndex::new(DegreeDayType::Heating, 65.0, start, end);
+        let cdd = DegreeDayIndex::new(DegreeDayType::Cooling, 65.0, start, end);
+
+        assert_eq!(hdd.n_days(), 7);
+        assert_eq!(hdd.realized(&series), Ok(5.0 + 10.0 + 16.5));
+        assert_eq!(cdd.realized(&series), Ok(5.0 + 7.0 + 1.0));
+
+        // HDD - CDD adds up the differences from the base.
+        let net: f64 = series.observations.values().map(|t| 65.0 - t).sum();
+        assert_approx_equal!(
+            hdd.realized(&series).unwrap() - cdd.realized(&series).unwrap(),
+            net,
+            1e-12
+        );
+
+        let later = DegreeDayIndex::new(DegreeDayType::Heating, 65.0, start, date!(2024 - 01 - 08));
+        assert_eq!(
+            later.realized(&series),
+            Err(WeatherError::MissingObservation(date!(2024 - 01 - 08)))
+        );
+    }
+
+    #[test]
+    fn test_elapsed_period_is_realized() {
+        let series = week();
+        let model = SeasonalTemperature::new(date!(2024 - 01 - 01), 55.0, 0.0, 20.0, 0.0, 0.2, 3.0);
+        let config = SimulationConfig::new(false).with_seed(1);
+
+        // A period that is over settles on the observations.
+        let index = DegreeDayIndex::new(
+            DegreeDayType::Heating,
+            65.0,
+            date!(2024 - 01 - 02),
+            date!(2024 - 01 - 05),
+        );
+        let values = index.simulate(&model, &series, 10, &config).unwrap();
+        assert_eq!(values, vec![26.5; 10]);
+
+        // One that is under way adds simulated days to the observed ones.
+        let running = DegreeDayIndex::new(
+            DegreeDayType::Heating,
+            65.0,
+            date!(2024 - 01 - 05),
+            date!(2024 - 01 - 20),
+        );
+        let values = running.simulate(&model, &series, 100, &config).unwrap();
+        assert!(values.iter().all(|value| *value >= 16.5));
+        assert!(values.iter().any(|value| *value > 16.5));
+
+        assert_eq!(
+            running.simulate(&model, &TemperatureSeries::default(), 10, &config),
+            Err(WeatherError::EmptySeries)
+        );
+    }
+}
+
+#[cfg(all(test, feature = "data"))]
+mod tests_degree_days_data {
+    use super::*;
+    use polars::prelude::*;
+    use time::macros::date;
+
+    #[test]
+    fn test_series_from_frame() {
+        let frame = df!(
+            "date" => ["2024-01-01", "2024-01-02", "2024-01-03"],
+            "tavg" => [Some(30.0), None, Some(41.0)]
+        )
+        .unwrap();
+
+        let series = TemperatureSeries::from_frame(&frame, "tavg").unwrap();
+
+        assert_eq!(series.observations.len(), 2);
+        assert_eq!(series.get(date!(2024 - 01 - 03)), Some(41.0));
+        assert_eq!(series.last_date(), Some(date!(2024 - 01 - 03)));
+
+        let frame = df!(
+            "date" => Series::new("date", [19723, 19724]).cast(&DataType::Date).unwrap(),
+            "tavg" => [30, 35]
+        )
+        .unwrap();
+
+        let series = TemperatureSeries::from_frame(&frame, "tavg").unwrap();
+        assert_eq!(series.get(date!(2024 - 01 - 02)), Some(35.0));
+
+        let frame = df!("date" => ["01/02/2024"], "tavg" => [30.0]).unwrap();
+        assert!(TemperatureSeries::from_frame(&frame, "tavg").is_err());
+    }
+}
@@ -0,0 +1,352 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Mean-reverting temperature model with a seasonal mean
+//! (Alaton, Djehiche and Stillberger, 2002).
+//!
+//! The daily average temperature reverts to a seasonal mean with a linear
+//! warming trend,
+//! $$
+//! \theta(t) = a + b t + A \sin(\omega t + \varphi), \qquad \omega = \frac{2 \pi}{365.25},
+//! $$
+//! following
+//! $$
+//! dT_t = \left( \theta'(t) + \kappa (\theta(t) - T_t) \right) dt + \sigma dW_t,
+//! $$
+//! where $t$ is in days. The $\theta'(t)$ term makes the expected
+//! temperature track the seasonal mean, so that the deviation
+//! $T_t - \theta(t)$ is an Ornstein-Uhlenbeck process with zero mean.
+
+use crate::error::{ensure, expect_valid, RustQuantError};
+use crate::instruments::weather::{TemperatureSeries, WeatherError};
+use crate::stochastics::{
+    estimate_ornstein_uhlenbeck, SimulationConfig, StochasticProcess, Trajectories,
+};
+use nalgebra::{DMatrix, DVector};
+use ndarray::s;
+use std::f64::consts::PI;
+use time::Date;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Ornstein-Uhlenbeck temperature process with a seasonal mean. Time is in
+/// days since `origin`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SeasonalTemperature {
+    /// Day the model's time is measured from.
+    pub origin: Date,
+
+    /// Mean temperature at the origin, before the seasonal cycle ($a$).
+    pub level: f64,
+
+    /// Warming trend per day ($b$).
+    pub trend: f64,
+
+    /// Amplitude of the seasonal cycle ($A$).
+    pub amplitude: f64,
+
+    /// Phase of the seasonal cycle at the origin ($\varphi$, radians).
+    pub phase: f64,
+
+    /// Speed of mean reversion per day ($\kappa$).
+    pub kappa: f64,
+
+    /// Volatility per square-root day ($\sigma$).
+    pub sigma: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl SeasonalTemperature {
+    /// New seasonal temperature model.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `kappa` is not positive or `sigma` is negative.
+    pub fn new(
+        origin: Date,
+        level: f64,
+        trend: f64,
+        amplitude: f64,
+        phase: f64,
+        kappa: f64,
+        sigma: f64,
+    ) -> Self {
+        expect_valid(Self::try_new(
+            origin, level, trend, amplitude, phase, kappa, sigma,
+        ))
+    }
+
+    /// New seasonal temperature model, or an error if `kappa` is not
+    /// positive or `sigma` is negative.
+    pub fn try_new(
+        origin: Date,
+        level: f64,
+        trend: f64,
+        amplitude: f64,
+        phase: f64,
+        kappa: f64,
+        sigma: f64,
+    ) -> Result<Self, RustQuantError> {
+        ensure(kappa > 0.0, "kappa must be positive")?;
+        ensure(sigma >= 0.0, "sigma must be non-negative")?;
+
+        Ok(Self {
+            origin,
+            level,
+            trend,
+            amplitude,
+            phase,
+            kappa,
+            sigma,
+        })
+    }
+
+    /// Fits the model to a history of consecutive daily temperatures, with
+    /// the origin at its first day.
+    ///
+    /// The seasonal mean is fitted by least squares on $1$, $t$,
+    /// $\sin \omega t$ and $\cos \omega t$, and the mean reversion and
+    /// volatility by the exact AR(1) regression of the deviations from it
+    /// ([`estimate_ornstein_uhlenbeck`]).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the deviations are not mean-reverting, as
+    /// [`estimate_ornstein_uhlenbeck`].
+    pub fn fit(history: &TemperatureSeries) -> Result<Self, WeatherError> {
+        let (&origin, _) = history
+            .observations
+            .first_key_value()
+            .ok_or(WeatherError::EmptySeries)?;
+        let end = history.last_date().ok_or(WeatherError::EmptySeries)?;
+        let temperatures = history.daily(origin, end)?;
+
+        let n = temperatures.len();
+        let design = DMatrix::from_fn(n, 4, |i, j| {
+            let t = i as f64;
+
+            match j {
+                0 => 1.0,
+                1 => t,
+                2 => (OMEGA * t).sin(),
+                _ => (OMEGA * t).cos(),
+            }
+        });
+        let coefficients = design
+            .clone()
+            .svd(true, true)
+            .solve(&DVector::from_vec(temperatures.clone()), 1e-12)
+            .expect("The SVD is computed with U and V.");
+
+        let (sine, cosine) = (coefficients[2], coefficients[3]);
+        let mut model = Self {
+            origin,
+            level: coefficients[0],
+            trend: coefficients[1],
+            amplitude: sine.hypot(cosine),
+            phase: cosine.atan2(sine),
+            kappa: 1.0,
+            sigma: 0.0,
+        };
+
+        let deviations = temperatures
+            .iter()
+            .enumerate()
+            .map(|(i, temperature)| temperature - model.seasonal_mean(i as f64))
+            .collect::<Vec<_>>();
+        let ou = estimate_ornstein_uhlenbeck(&deviations, 1.0);
+
+        model.kappa = ou.theta;
+        model.sigma = ou.sigma;
+
+        Ok(model)
+    }
+
+    /// Days from the origin to `date`.
+    pub fn time(&self, date: Date) -> f64 {
+        (date - self.origin).whole_days() as f64
+    }
+
+    /// Seasonal mean $\theta(t)$ at `t` days from the origin.
+    pub fn seasonal_mean(&self, t: f64) -> f64 {
+        self.level + self.trend * t + self.amplitude * (OMEGA * t + self.phase).sin()
+    }
+
+    /// Expected temperature on `date`, given the temperature on an earlier
+    /// day `from`:
+    /// $\theta(t) + (T_s - \theta(s)) e^{-\kappa (t - s)}$.
+    pub fn expected_temperature(&self, from: Date, temperature: f64, date: Date) -> f64 {
+        let (s, t) = (self.time(from), self.time(date));
+
+        self.seasonal_mean(t)
+            + (temperature - self.seasonal_mean(s)) * (-self.kappa * (t - s)).exp()
+    }
+
+    /// Simulates daily temperatures for `n_days` after `from`, starting at
+    /// the temperature observed on `from`. The trajectories have one point
+    /// a day (at times in days since the origin), from Euler steps of a
+    /// fraction of a day.
+    pub fn simulate(
+        &self,
+        from: Date,
+        temperature: f64,
+        n_days: usize,
+        n_paths: usize,
+        config: &SimulationConfig,
+    ) -> Trajectories {
+        let t_0 = self.time(from);
+        let fine = self.simulate_with_config(
+            temperature,
+            t_0,
+            t_0 + n_days as f64,
+            n_days * STEPS_PER_DAY,
+            n_paths,
+            config,
+        );
+
+        let times = (0..=n_days).map(|k| t_0 + k as f64).collect();
+        let paths = fine
+            .paths
+            .slice(s![.., ..;STEPS_PER_DAY as isize])
+            .to_owned();
+
+        Trajectories::new(times, paths)
+    }
+
+    fn seasonal_mean_derivative(&self, t: f64) -> f64 {
+        self.trend + self.amplitude * OMEGA * (OMEGA * t + self.phase).cos()
+    }
+}
+
+impl StochasticProcess for SeasonalTemperature {
+    fn drift(&self, x: f64, t: f64) -> f64 {
+        self.seasonal_mean_derivative(t) + self.kappa * (self.seasonal_mean(t) - x)
+    }
+
+    fn diffusion(&self, _x: f64, _t: f64) -> f64 {
+        self.sigma
+    }
+
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        None
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Angular frequency of the yearly cycle, per day.
+const OMEGA: f64 = 2.0 * PI / 365.25;
+
+/// Euler steps per simulated day. With daily steps the discretisation
+/// would overstate the mean reversion by about $\kappa^2 / 2$ a day.
+const STEPS_PER_DAY: usize = 8;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_temperature {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+    use rand_distr::{Distribution, StandardNormal};
+    use time::macros::date;
+    use time::Duration;
+
+    const ORIGIN: Date = date!(2015 - 01 - 01);
+
+    // Coldest in mid-January, warmest in mid-July (°F).
+    fn model() -> SeasonalTemperature {
+        SeasonalTemperature::new(ORIGIN, 55.0, 0.0005, 20.0, -1.8, 0.25, 4.0)
+    }
+
+    /// Ten years of temperatures from the exact transition of the model.
+    fn history(model: &SeasonalTemperature) -> TemperatureSeries {
+        let mut rng = StdRng::seed_from_u64(3);
+        let decay = (-model.kappa).exp();
+        let scale = model.sigma * ((1.0 - decay * decay) / (2.0 * model.kappa)).sqrt();
+
+        let mut deviation = 0.0;
+        let observations = (0..3653)
+            .map(|i| {
+                let z: f64 = StandardNormal.sample(&mut rng);
+                deviation = decay * deviation + scale * z;
+
+                (
+                    ORIGIN + Duration::days(i),
+                    model.seasonal_mean(i as f64) + deviation,
+                )
+            })
+            .collect();
+
+        TemperatureSeries::new(observations)
+    }
+
+    #[test]
+    fn test_seasonal_mean() {
+        let model = model();
+        let january = model.seasonal_mean(model.time(date!(2015 - 01 - 15)));
+        let july = model.seasonal_mean(model.time(date!(2015 - 07 - 15)));
+
+        assert!(january < 40.0 && july > 70.0);
+        assert_approx_equal!(
+            model.seasonal_mean(365.25) - model.seasonal_mean(0.0),
+            365.25 * 0.0005,
+            1e-9
+        );
+    }
+
+    #[test]
+    fn test_fit() {
+        let model = model();
+        let fitted = SeasonalTemperature::fit(&history(&model)).unwrap();
+
+        assert_eq!(fitted.origin, ORIGIN);
+        assert_approx_equal!(fitted.level, 55.0, 0.5);
+        assert_approx_equal!(fitted.trend, 0.0005, 3e-4);
+        assert_approx_equal!(fitted.amplitude, 20.0, 0.3);
+        assert_approx_equal!(fitted.phase, -1.8, 0.02);
+        assert_approx_equal!(fitted.kappa, 0.25, 0.03);
+        assert_approx_equal!(fitted.sigma, 4.0, 0.15);
+
+        let mut gap = history(&model);
+        gap.observations.remove(&date!(2016 - 03 - 01));
+        assert_eq!(
+            SeasonalTemperature::fit(&gap),
+            Err(WeatherError::MissingObservation(date!(2016 - 03 - 01)))
+        );
+    }
+
+    #[test]
+    fn test_simulated_mean() {
+        let model = model();
+        let from = date!(2024 - 01 - 01);
+        let config = SimulationConfig::new(true).with_seed(5);
+
+        // Start 10°F above the seasonal mean: the expected temperature
+        // decays back to it.
+        let start = model.seasonal_mean(model.time(from)) + 10.0;
+        let paths = model.simulate(from, start, 60, 20_000, &config);
+
+        assert_eq!(paths.times[0], model.time(from));
+        for k in [1, 5, 30, 60] {
+            let date = from + Duration::days(k as i64);
+            let mean = paths.values_at(k).mean().unwrap();
+
+            assert_approx_equal!(mean, model.expected_temperature(from, start, date), 0.5);
+        }
+    }
+}
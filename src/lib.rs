@@ -1,4 +1,4 @@
-//! ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//! ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~text
 //! RustQuant: A Rust library for quantitative finance tools.
 //! Copyright (C) 2023 https://github.com/avhz
 //! Dual licensed under Apache 2.0 and MIT.
@@ -21,24 +21,44 @@
 // want to adhere to the standard mathematical notation.
 #![allow(non_snake_case)]
 // Strictly enforce SAFETY comments.
-// There is no unsafe code currently, but for anyone to add any, it must be
-// documented with a SAFETY comment.
+// The only unsafe code is the C API (`ffi` feature), and any unsafe block
+// must be documented with a SAFETY comment.
 #![forbid(clippy::undocumented_unsafe_blocks)]
 
+#[macro_use]
+pub mod macros;
+
 pub mod autodiff;
+pub mod backtest;
+pub mod costs;
 pub mod curves;
 #[cfg(feature = "data")]
 pub mod data;
 pub mod error;
+#[cfg(feature = "data")]
+pub mod features;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 pub mod instruments;
-#[macro_use]
-pub mod macros;
+pub mod market;
 pub mod math;
+pub mod microstructure;
 pub mod ml;
 pub mod models;
 pub mod money;
+pub mod performance;
+#[cfg(feature = "plot")]
+pub mod plot;
 pub mod portfolio;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod risk;
 pub mod statistics;
 pub mod stochastics;
 pub mod time;
 pub mod trading;
+#[cfg(any(test, feature = "verification"))]
+pub mod verification;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod xva;
@@ -0,0 +1,300 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::curves::{VolatilitySurface, YieldCurve};
+use crate::market::FixingStore;
+use crate::money::{Currency, ExchangeRate, FxMatrix};
+use crate::time::IntoEvaluationDate;
+use std::collections::BTreeMap;
+use thiserror::Error;
+use time::{Date, OffsetDateTime};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Market data on a valuation date.
+#[derive(Debug, Clone)]
+pub struct Market {
+    /// Date the market data is observed on.
+    pub valuation_date: OffsetDateTime,
+
+    /// Yield curves, by name (e.g. "USD" or "EUR-ESTR").
+    pub curves: BTreeMap<String, YieldCurve>,
+
+    /// Volatility surfaces, by name (e.g. the underlying's ticker).
+    pub surfaces: BTreeMap<String, VolatilitySurface<YieldCurve>>,
+
+    /// Spot prices, by name.
+    pub spots: BTreeMap<String, f64>,
+
+    /// FX rates, if any.
+    pub fx: Option<FxMatrix>,
+
+    /// Historical fixings of rates and indices.
+    pub fixings: FixingStore,
+}
+
+/// Market data errors.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum MarketError {
+    /// No curve of the given name.
+    #[error("No curve named {0:?} in the market.")]
+    MissingCurve(String),
+
+    /// No volatility surface of the given name.
+    #[error("No volatility surface named {0:?} in the market.")]
+    MissingSurface(String),
+
+    /// No spot price of the given name.
+    #[error("No spot price for {0:?} in the market.")]
+    MissingSpot(String),
+
+    /// No FX rate between two currencies (ISO 4217 codes).
+    #[error("No {0}/{1} FX rate in the market.")]
+    MissingFxRate(&'static str, &'static str),
+
+    /// No fixing of the given name on the date.
+    #[error("No fixing of {0:?} on {1}.")]
+    MissingFixing(String, Date),
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Market {
+    /// New market without any data.
+    pub fn new(valuation_date: impl IntoEvaluationDate) -> Self {
+        Self {
+            valuation_date: valuation_date.into_evaluation_date(),
+            curves: BTreeMap::new(),
+            surfaces: BTreeMap::new(),
+            spots: BTreeMap::new(),
+            fx: None,
+            fixings: FixingStore::new(),
+        }
+    }
+
+    /// Adds (or replaces) a yield curve.
+    pub fn with_curve(mut self, name: &str, curve: YieldCurve) -> Self {
+        self.curves.insert(name.to_string(), curve);
+        self
+    }
+
+    /// Adds (or replaces) a volatility surface.
+    pub fn with_surface(mut self, name: &str, surface: VolatilitySurface<YieldCurve>) -> Self {
+        self.surfaces.insert(name.to_string(), surface);
+        self
+    }
+
+    /// Adds (or replaces) a spot price.
+    pub fn with_spot(mut self, name: &str, spot: f64) -> Self {
+        self.spots.insert(name.to_string(), spot);
+        self
+    }
+
+    /// Sets the FX rates.
+    pub fn with_fx(mut self, fx: FxMatrix) -> Self {
+        self.fx = Some(fx);
+        self
+    }
+
+    /// Adds an FX rate, triangulating through its `to` currency if the
+    /// market has no FX rates yet.
+    pub fn with_fx_rate(mut self, rate: ExchangeRate) -> Self {
+        self.fx
+            .get_or_insert_with(|| FxMatrix::new(rate.to_currency))
+            .add_rate(rate);
+        self
+    }
+
+    /// Adds a fixing of a rate or index.
+    pub fn with_fixing(mut self, name: &str, date: impl IntoEvaluationDate, value: f64) -> Self {
+        self.fixings.insert(name, date, value);
+        self
+    }
+
+    /// Sets the historical fixings.
+    pub fn with_fixings(mut self, fixings: FixingStore) -> Self {
+        self.fixings = fixings;
+        self
+    }
+
+    /// Yield curve of the given name.
+    pub fn curve(&self, name: &str) -> Result<&YieldCurve, MarketError> {
+        self.curves
+            .get(name)
+            .ok_or_else(|| MarketError::MissingCurve(name.to_string()))
+    }
+
+    /// Discount curve of a currency: the curve named after its ISO 4217
+    /// code (e.g. "USD").
+    pub fn discount_curve(&self, currency: &Currency) -> Result<&YieldCurve, MarketError> {
+        self.curve(currency.code.alphabetic)
+    }
+
+    /// Volatility surface of the given name.
+    pub fn surface(&self, name: &str) -> Result<&VolatilitySurface<YieldCurve>, MarketError> {
+        self.surfaces
+            .get(name)
+            .ok_or_else(|| MarketError::MissingSurface(name.to_string()))
+    }
+
+    /// Spot price of the given name.
+    pub fn spot(&self, name: &str) -> Result<f64, MarketError> {
+        self.spots
+            .get(name)
+            .copied()
+            .ok_or_else(|| MarketError::MissingSpot(name.to_string()))
+    }
+
+    /// Rate converting one unit of `from` into units of `to`.
+    pub fn fx_rate(&self, from: &Currency, to: &Currency) -> Result<f64, MarketError> {
+        if from == to {
+            return Ok(1.0);
+        }
+
+        self.fx
+            .as_ref()
+            .and_then(|fx| fx.rate(from, to))
+            .ok_or(MarketError::MissingFxRate(
+                from.code.alphabetic,
+                to.code.alphabetic,
+            ))
+    }
+
+    /// Fixing of a rate or index on a date.
+    pub fn fixing(&self, name: &str, date: impl IntoEvaluationDate) -> Result<f64, MarketError> {
+        self.fixings.fixing(name, date)
+    }
+
+    /// Copy of the market with a curve shifted in parallel by `shift`
+    /// (0.0001 = 1bp).
+    pub fn bump_curve(&self, name: &str, shift: f64) -> Result<Self, MarketError> {
+        let mut market = self.clone();
+        let curve = market
+            .curves
+            .get_mut(name)
+            .ok_or_else(|| MarketError::MissingCurve(name.to_string()))?;
+
+        curve.rates.values_mut().for_each(|rate| *rate += shift);
+
+        Ok(market)
+    }
+
+    /// Copy of the market with every volatility of a surface shifted by
+    /// `shift` (0.01 = one vol point).
+    pub fn bump_surface(&self, name: &str, shift: f64) -> Result<Self, MarketError> {
+        let mut market = self.clone();
+        let surface = market
+            .surfaces
+            .get_mut(name)
+            .ok_or_else(|| MarketError::MissingSurface(name.to_string()))?;
+
+        surface
+            .volatilities
+            .values_mut()
+            .flat_map(|curve| curve.rates.values_mut())
+            .for_each(|vol| *vol += shift);
+
+        Ok(market)
+    }
+
+    /// Copy of the market with a spot price moved by `relative_shift`
+    /// (e.g. 0.01 for +1%).
+    pub fn bump_spot(&self, name: &str, relative_shift: f64) -> Result<Self, MarketError> {
+        let mut market = self.clone();
+        let spot = market
+            .spots
+            .get_mut(name)
+            .ok_or_else(|| MarketError::MissingSpot(name.to_string()))?;
+
+        *spot *= 1.0 + relative_shift;
+
+        Ok(market)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_context {
+    use super::*;
+    use crate::curves::{Curve, Surface};
+    use crate::money::{EUR, GBP, USD};
+    use time::macros::{date, datetime};
+    use time::Duration;
+
+    fn flat(rate: f64) -> YieldCurve {
+        let t0 = datetime!(2025-01-02 0:00 UTC);
+
+        YieldCurve::from_dates_and_rates(&[t0, t0 + Duration::days(3650)], &[rate, rate])
+    }
+
+    fn market() -> Market {
+        Market::new(date!(2025 - 01 - 02))
+            .with_curve("USD", flat(0.04))
+            .with_surface("SPX", VolatilitySurface::new([(100.0, flat(0.2))]))
+            .with_spot("SPX", 5000.0)
+            .with_fx_rate(ExchangeRate::new(EUR, USD, 1.1))
+            .with_fixing("SOFR", date!(2024 - 12 - 31), 0.0433)
+    }
+
+    #[test]
+    fn test_market_lookups() {
+        let market = market();
+        let t = datetime!(2026-01-02 0:00 UTC);
+
+        assert_eq!(market.valuation_date, datetime!(2025-01-02 0:00 UTC));
+        assert_approx_equal!(market.discount_curve(&USD).unwrap().rate(t), 0.04, 1e-12);
+        assert_approx_equal!(market.surface("SPX").unwrap().value(t, 100.0), 0.2, 1e-12);
+        assert_eq!(market.spot("SPX"), Ok(5000.0));
+        assert_approx_equal!(market.fx_rate(&USD, &EUR).unwrap(), 1.0 / 1.1, 1e-12);
+        assert_eq!(market.fx_rate(&GBP, &GBP), Ok(1.0));
+        assert_eq!(market.fixing("SOFR", date!(2024 - 12 - 31)), Ok(0.0433));
+    }
+
+    #[test]
+    fn test_market_missing_data() {
+        let market = market();
+
+        assert_eq!(
+            market.curve("EUR").unwrap_err(),
+            MarketError::MissingCurve("EUR".to_string())
+        );
+        assert!(market.surface("NDX").is_err());
+        assert!(market.spot("NDX").is_err());
+        assert_eq!(
+            market.fx_rate(&GBP, &USD),
+            Err(MarketError::MissingFxRate("GBP", "USD"))
+        );
+        assert!(market.fixing("SOFR", date!(2025 - 01 - 02)).is_err());
+    }
+
+    #[test]
+    fn test_market_bumps() {
+        let market = market();
+        let t = datetime!(2026-01-02 0:00 UTC);
+
+        let bumped = market.bump_curve("USD", 0.0001).unwrap();
+        assert_approx_equal!(bumped.curve("USD").unwrap().rate(t), 0.0401, 1e-12);
+        assert_approx_equal!(market.curve("USD").unwrap().rate(t), 0.04, 1e-12);
+
+        let bumped = market.bump_surface("SPX", 0.01).unwrap();
+        assert_approx_equal!(bumped.surface("SPX").unwrap().value(t, 100.0), 0.21, 1e-12);
+
+        let bumped = market.bump_spot("SPX", -0.1).unwrap();
+        assert_approx_equal!(bumped.spot("SPX").unwrap(), 4500.0, 1e-9);
+
+        assert!(market.bump_curve("EUR", 0.0001).is_err());
+    }
+}
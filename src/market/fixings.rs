@@ -0,0 +1,374 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::market::MarketError;
+use crate::time::{DayCountConvention, DayCounter, IntoEvaluationDate};
+use std::collections::BTreeMap;
+use time::Date;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Historical fixings of rate indices (e.g. "SOFR", "EURIBOR-3M"), by
+/// index name and fixing date.
+///
+/// Floating coupons that have already reset are paid on past fixings, so
+/// seasoned floating legs are priced from these rather than from a curve.
+///
+/// ```
+/// use RustQuant::market::FixingStore;
+/// use RustQuant::time::DayCountConvention;
+/// use time::macros::date;
+///
+/// let fixings = FixingStore::new()
+///     .with_fixing("SOFR", date!(2025 - 01 - 02), 0.0431)
+///     .with_fixing("SOFR", date!(2025 - 01 - 03), 0.0432)
+///     .with_fixing("SOFR", date!(2025 - 01 - 06), 0.0430);
+///
+/// // Friday's fixing accrues over the weekend.
+/// let rate = fixings
+///     .compounded_rate(
+///         "SOFR",
+///         date!(2025 - 01 - 02),
+///         date!(2025 - 01 - 07),
+///         DayCountConvention::Actual360,
+///     )
+///     .unwrap();
+///
+/// assert!(0.0430 < rate && rate < 0.0433);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FixingStore {
+    /// Fixings, by index name and date.
+    pub fixings: BTreeMap<String, BTreeMap<Date, f64>>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl FixingStore {
+    /// New store without any fixings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds (or replaces) a fixing.
+    pub fn insert(&mut self, index: &str, date: impl IntoEvaluationDate, value: f64) {
+        self.fixings
+            .entry(index.to_string())
+            .or_default()
+            .insert(date.into_evaluation_date().date(), value);
+    }
+
+    /// Adds (or replaces) a fixing.
+    pub fn with_fixing(mut self, index: &str, date: impl IntoEvaluationDate, value: f64) -> Self {
+        self.insert(index, date, value);
+        self
+    }
+
+    /// Adds (or replaces) a history of fixings of an index.
+    pub fn extend<D: IntoEvaluationDate>(
+        &mut self,
+        index: &str,
+        fixings: impl IntoIterator<Item = (D, f64)>,
+    ) {
+        for (date, value) in fixings {
+            self.insert(index, date, value);
+        }
+    }
+
+    /// Names of the indices with fixings.
+    pub fn indices(&self) -> impl Iterator<Item = &str> {
+        self.fixings.keys().map(String::as_str)
+    }
+
+    /// Number of fixings, over all indices.
+    pub fn len(&self) -> usize {
+        self.fixings.values().map(BTreeMap::len).sum()
+    }
+
+    /// Whether the store has no fixings.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Fixing of an index on a date.
+    pub fn fixing(&self, index: &str, date: impl IntoEvaluationDate) -> Result<f64, MarketError> {
+        let date = date.into_evaluation_date().date();
+
+        self.fixings
+            .get(index)
+            .and_then(|fixings| fixings.get(&date))
+            .copied()
+            .ok_or_else(|| MarketError::MissingFixing(index.to_string(), date))
+    }
+
+    /// Latest fixing of an index on or before a date, with its date (e.g.
+    /// the rate in force over a weekend or holiday).
+    pub fn latest_fixing(
+        &self,
+        index: &str,
+        date: impl IntoEvaluationDate,
+    ) -> Result<(Date, f64), MarketError> {
+        let date = date.into_evaluation_date().date();
+
+        self.fixings
+            .get(index)
+            .and_then(|fixings| fixings.range(..=date).next_back())
+            .map(|(date, value)| (*date, *value))
+            .ok_or_else(|| MarketError::MissingFixing(index.to_string(), date))
+    }
+
+    /// Fixings of an index from `start` (inclusive) to `end` (exclusive).
+    pub fn fixings_between(
+        &self,
+        index: &str,
+        start: impl IntoEvaluationDate,
+        end: impl IntoEvaluationDate,
+    ) -> Vec<(Date, f64)> {
+        let (start, end) = (
+            start.into_evaluation_date().date(),
+            end.into_evaluation_date().date(),
+        );
+
+        match self.fixings.get(index) {
+            Some(fixings) if start < end => fixings
+                .range(start..end)
+                .map(|(date, value)| (*date, *value))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Rate of an overnight index compounded daily from `start` to `end`,
+    /// as for the floating leg of an OIS:
+    ///
+    /// $$ \frac{1}{\tau} \left( \prod_i (1 + r_i \tau_i) - 1 \right) $$
+    ///
+    /// where each fixing $r_i$ accrues until the next fixing date (or `end`),
+    /// so the rate of the last business day covers weekends and holidays.
+    /// A fixing is needed on `start`.
+    pub fn compounded_rate(
+        &self,
+        index: &str,
+        start: impl IntoEvaluationDate,
+        end: impl IntoEvaluationDate,
+        day_count: DayCountConvention,
+    ) -> Result<f64, MarketError> {
+        let (start, end) = (start.into_evaluation_date(), end.into_evaluation_date());
+
+        self.fixing(index, start)?;
+
+        let fixings = self.fixings_between(index, start, end);
+        let growth = fixings
+            .iter()
+            .enumerate()
+            .map(|(i, (date, rate))| {
+                let next = fixings
+                    .get(i + 1)
+                    .map_or(end, |(next, _)| next.into_evaluation_date());
+
+                1.0 + rate * day_count.year_fraction(date.into_evaluation_date(), next)
+            })
+            .product::<f64>();
+
+        Ok((growth - 1.0) / day_count.year_fraction(start, end))
+    }
+
+    /// Fixings from a `DataFrame` with `index`, `date` (as a date or an
+    /// ISO 8601 `YYYY-MM-DD` string) and `fixing` columns.
+    #[cfg(feature = "data")]
+    pub fn from_frame(frame: &polars::prelude::DataFrame) -> crate::error::RustQuantResult<Self> {
+        use crate::data::DataError;
+        use crate::error::RustQuantError;
+        use polars::prelude::DataType;
+
+        let strings = |name: &str| -> Result<Vec<Option<String>>, DataError> {
+            Ok(frame
+                .column(name)?
+                .cast(&DataType::Utf8)?
+                .utf8()?
+                .into_iter()
+                .map(|value| value.map(str::to_string))
+                .collect())
+        };
+
+        let indices = strings("index")?;
+        let dates = strings("date")?;
+        let values: Vec<Option<f64>> = frame
+            .column("fixing")
+            .and_then(|column| column.cast(&DataType::Float64))
+            .map_err(DataError::from)?
+            .f64()
+            .map_err(DataError::from)?
+            .into_iter()
+            .collect();
+
+        let mut store = Self::new();
+
+        for ((index, date), value) in indices.into_iter().zip(dates).zip(values) {
+            let (Some(index), Some(date), Some(value)) = (index, date, value) else {
+                continue;
+            };
+            let date = parse_date(&date).ok_or_else(|| {
+                RustQuantError::invalid_parameter(format!("Invalid fixing date {date:?}."))
+            })?;
+
+            store.insert(&index, date, value);
+        }
+
+        Ok(store)
+    }
+
+    /// Fixings from a CSV file with a header and `index`, `date`
+    /// (`YYYY-MM-DD`) and `fixing` columns. Rows with missing values are
+    /// skipped.
+    #[cfg(feature = "data")]
+    pub fn from_csv(path: &str) -> crate::error::RustQuantResult<Self> {
+        use crate::data::{Data, DataFormat, DataReader};
+
+        let mut data = Data::new(DataFormat::CSV, path.to_string());
+        data.read()?;
+
+        Self::from_frame(&data.data)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Parses a `YYYY-MM-DD` date.
+#[cfg(feature = "data")]
+pub(crate) fn parse_date(text: &str) -> Option<Date> {
+    let mut parts = text.trim().splitn(3, '-');
+    let year = parts.next()?.parse().ok()?;
+    let month: u8 = parts.next()?.parse().ok()?;
+    let day = parts.next()?.parse().ok()?;
+
+    Date::from_calendar_date(year, month.try_into().ok()?, day).ok()
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_fixings {
+    use super::*;
+    use time::macros::{date, datetime};
+
+    fn sofr() -> FixingStore {
+        let mut store = FixingStore::new();
+        store.extend(
+            "SOFR",
+            [
+                (date!(2025 - 01 - 02), 0.0431),
+                (date!(2025 - 01 - 03), 0.0432),
+                (date!(2025 - 01 - 06), 0.0430),
+                (date!(2025 - 01 - 07), 0.0429),
+            ],
+        );
+        store
+    }
+
+    #[test]
+    fn test_fixing_lookups() {
+        let store = sofr().with_fixing("EURIBOR-3M", date!(2025 - 01 - 02), 0.0275);
+
+        assert_eq!(store.len(), 5);
+        assert_eq!(
+            store.indices().collect::<Vec<_>>(),
+            vec!["EURIBOR-3M", "SOFR"]
+        );
+        assert_eq!(store.fixing("SOFR", date!(2025 - 01 - 03)), Ok(0.0432));
+        assert_eq!(
+            store.fixing("SOFR", datetime!(2025-01-03 17:00 UTC)),
+            Ok(0.0432)
+        );
+        assert_eq!(
+            store.fixing("SOFR", date!(2025 - 01 - 04)),
+            Err(MarketError::MissingFixing(
+                "SOFR".to_string(),
+                date!(2025 - 01 - 04)
+            ))
+        );
+        assert_eq!(
+            store.latest_fixing("SOFR", date!(2025 - 01 - 05)),
+            Ok((date!(2025 - 01 - 03), 0.0432))
+        );
+        assert!(store.latest_fixing("SOFR", date!(2025 - 01 - 01)).is_err());
+        assert_eq!(
+            store
+                .fixings_between("SOFR", date!(2025 - 01 - 03), date!(2025 - 01 - 07))
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_compounded_rate() {
+        let store = sofr();
+        let rate = store
+            .compounded_rate(
+                "SOFR",
+                date!(2025 - 01 - 02),
+                date!(2025 - 01 - 08),
+                DayCountConvention::Actual360,
+            )
+            .unwrap();
+
+        // Thursday, Friday (over the weekend), Monday and Tuesday.
+        let growth = (1.0 + 0.0431 / 360.0)
+            * (1.0 + 0.0432 * 3.0 / 360.0)
+            * (1.0 + 0.0430 / 360.0)
+            * (1.0 + 0.0429 / 360.0);
+        assert_approx_equal!(rate, (growth - 1.0) * 360.0 / 6.0, 1e-14);
+
+        // No fixing on the start date.
+        assert!(store
+            .compounded_rate(
+                "SOFR",
+                date!(2025 - 01 - 04),
+                date!(2025 - 01 - 08),
+                DayCountConvention::Actual360,
+            )
+            .is_err());
+    }
+
+    #[cfg(feature = "data")]
+    #[test]
+    fn test_fixings_from_csv() {
+        let store = FixingStore::from_csv("./src/data/examples/fixings.csv").unwrap();
+
+        assert_eq!(store.len(), 5);
+        assert_eq!(store.fixing("SOFR", date!(2025 - 01 - 06)), Ok(0.0430));
+        assert_eq!(
+            store.fixing("EURIBOR-3M", date!(2025 - 01 - 02)),
+            Ok(0.02753)
+        );
+    }
+
+    #[cfg(feature = "data")]
+    #[test]
+    fn test_fixings_invalid_date() {
+        use polars::prelude::*;
+
+        let frame = df!(
+            "index" => ["SOFR"],
+            "date" => ["02/01/2025"],
+            "fixing" => [0.0431]
+        )
+        .unwrap();
+
+        assert!(FixingStore::from_frame(&frame).is_err());
+    }
+}
@@ -0,0 +1,61 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Market data environment.
+//!
+//! A [`Market`] holds everything instruments are priced from on a valuation
+//! date: yield curves and volatility surfaces by name, spot prices, FX
+//! rates and historical fixings. Instruments implementing
+//! [`MarketPricing`] read their inputs from it rather than from their own
+//! copies, so bumping a curve once reprices every instrument that uses it.
+//!
+//! ```
+//! use RustQuant::curves::YieldCurve;
+//! use RustQuant::instruments::CouponBond;
+//! use RustQuant::market::*;
+//! use RustQuant::money::USD;
+//! use RustQuant::time::{PaymentFrequency, Tenor};
+//! use time::macros::date;
+//!
+//! let curve = YieldCurve::from_tenors_and_rates(
+//!     date!(2024 - 01 - 15),
+//!     &[Tenor::days(0), Tenor::years(10)],
+//!     &[0.04, 0.045],
+//! );
+//!
+//! let bond = CouponBond::builder()
+//!     .evaluation_date(date!(2024 - 01 - 15))
+//!     .expiration_date(date!(2029 - 01 - 15))
+//!     .coupon_rate(0.05)
+//!     .coupon_frequency(PaymentFrequency::Annually)
+//!     .currency(USD)
+//!     .yield_curve(curve.clone())
+//!     .build()?;
+//!
+//! // The bond discounts on the market's curve named after its currency.
+//! let market = Market::new(date!(2024 - 01 - 15)).with_curve("USD", curve);
+//! let price = bond.market_price(&market)?.value();
+//!
+//! // A 1bp bump of the curve lowers the price.
+//! let bumped = market.bump_curve("USD", 0.0001)?;
+//! assert!(bond.market_price(&bumped)?.value() < price);
+//! # Ok::<(), RustQuant::error::RustQuantError>(())
+//! ```
+
+/// Market data context.
+pub mod context;
+pub use context::*;
+
+/// Historical fixings.
+pub mod fixings;
+pub use fixings::*;
+
+/// Pricing from market data.
+pub mod pricing;
+pub use pricing::*;
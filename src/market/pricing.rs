@@ -0,0 +1,286 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::error::{RustQuantError, RustQuantResult};
+use crate::instruments::{CouponBond, Instrument, PricingResult};
+use crate::market::Market;
+use crate::money::Currency;
+use crate::portfolio::{Portfolio, Position};
+use rayon::prelude::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Instruments that can be priced from a [`Market`].
+pub trait MarketPricing: Instrument {
+    /// Price of the instrument from the market's data, ignoring any market
+    /// data the instrument holds itself. Fails if the market lacks data
+    /// the instrument needs.
+    fn market_price(&self, market: &Market) -> RustQuantResult<PricingResult>;
+}
+
+/// Values of the positions of a portfolio in each of a set of markets.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScenarioValues {
+    /// Position names (columns), sorted.
+    pub positions: Vec<String>,
+
+    /// Position values, indexed by `[market][position]`.
+    pub values: Vec<Vec<f64>>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl MarketPricing for CouponBond {
+    /// Coupons discounted on the market's curve for the bond's currency
+    /// (see [`Market::discount_curve`]).
+    fn market_price(&self, market: &Market) -> RustQuantResult<PricingResult> {
+        let currency = self.currency.ok_or_else(|| {
+            RustQuantError::invalid_parameter(
+                "The bond needs a currency to be priced from a market.",
+            )
+        })?;
+
+        Ok(self.price_on_curve(market.discount_curve(&currency)?))
+    }
+}
+
+impl<I> Portfolio<I>
+where
+    I: MarketPricing,
+{
+    /// Value of the portfolio in `currency`, with every position priced
+    /// from the same market.
+    ///
+    /// Each position is its quantity times the instrument's market price,
+    /// converted with the market's FX rates from the price's currency (or
+    /// else the position's). Prices without a currency are taken to be in
+    /// `currency` already.
+    pub fn market_value(&self, market: &Market, currency: &Currency) -> RustQuantResult<f64> {
+        self.positions
+            .values()
+            .map(|position| position_value(position, market, currency))
+            .sum()
+    }
+}
+
+impl<I> Portfolio<I>
+where
+    I: MarketPricing + Sync,
+{
+    /// Values of every position in `currency` in each market (e.g. the
+    /// scenarios of a VaR or stress run), priced as in
+    /// [`Portfolio::market_value`].
+    ///
+    /// The markets are priced in parallel on the global rayon thread pool,
+    /// all sharing the portfolio's instruments. Fails with the first
+    /// error, if any market lacks data a position needs.
+    pub fn price_scenarios(
+        &self,
+        markets: &[Market],
+        currency: &Currency,
+    ) -> RustQuantResult<ScenarioValues> {
+        let mut positions: Vec<&String> = self.positions.keys().collect();
+        positions.sort();
+
+        let values = markets
+            .par_iter()
+            .map(|market| {
+                positions
+                    .iter()
+                    .map(|name| position_value(&self.positions[*name], market, currency))
+                    .collect::<RustQuantResult<Vec<f64>>>()
+            })
+            .collect::<RustQuantResult<Vec<Vec<f64>>>>()?;
+
+        Ok(ScenarioValues {
+            positions: positions.into_iter().cloned().collect(),
+            values,
+        })
+    }
+}
+
+impl ScenarioValues {
+    /// Portfolio value in each market.
+    pub fn totals(&self) -> Vec<f64> {
+        self.values.iter().map(|row| row.iter().sum()).collect()
+    }
+
+    /// P&L of the portfolio in each market relative to `base_value`.
+    pub fn pnl(&self, base_value: f64) -> Vec<f64> {
+        self.totals()
+            .into_iter()
+            .map(|total| total - base_value)
+            .collect()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Quantity times market price of a position, converted into `currency`.
+fn position_value<I: MarketPricing>(
+    position: &Position<I>,
+    market: &Market,
+    currency: &Currency,
+) -> RustQuantResult<f64> {
+    let result = position.instrument.market_price(market)?;
+    let fx_rate = match result.currency.or(position.currency) {
+        Some(from) => market.fx_rate(&from, currency)?,
+        None => 1.0,
+    };
+
+    Ok(position.quantity as f64 * result.value() * fx_rate)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_pricing {
+    use super::*;
+    use crate::curves::{Curve, YieldCurve};
+    use crate::money::{ExchangeRate, EUR, USD};
+    use crate::portfolio::Position;
+    use crate::time::PaymentFrequency;
+    use std::collections::HashMap;
+    use time::macros::datetime;
+    use time::Duration;
+
+    const TODAY: time::OffsetDateTime = datetime!(2025-01-02 0:00 UTC);
+
+    fn flat(rate: f64) -> YieldCurve {
+        YieldCurve::from_dates_and_rates(&[TODAY, TODAY + Duration::days(3650)], &[rate, rate])
+    }
+
+    fn bond(currency: Currency, years: i64) -> CouponBond {
+        CouponBond::builder()
+            .evaluation_date(TODAY)
+            .expiration_date(TODAY + Duration::days(365 * years))
+            .coupon_rate(0.05)
+            .coupon_frequency(PaymentFrequency::Annually)
+            .currency(currency)
+            .yield_curve(flat(0.10))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_bond_uses_market_curve() {
+        let bond = bond(USD, 5);
+        let market = Market::new(TODAY).with_curve("USD", flat(0.04));
+
+        // The market's curve is used, not the bond's own.
+        let result = bond.market_price(&market).unwrap();
+        assert_approx_equal!(
+            result.value(),
+            bond.price_on_curve(&flat(0.04)).value(),
+            1e-12
+        );
+        assert!(result.value() > bond.price().value());
+
+        assert!(bond.market_price(&Market::new(TODAY)).is_err());
+    }
+
+    #[test]
+    fn test_portfolio_market_value() {
+        let portfolio = Portfolio::new(HashMap::from([
+            (
+                "2y".to_string(),
+                Position::new(bond(USD, 2), 10, 100.0, 100.0, None),
+            ),
+            (
+                "5y".to_string(),
+                Position::new(bond(USD, 5), 5, 100.0, 100.0, None),
+            ),
+            (
+                "Bund".to_string(),
+                Position::new(bond(EUR, 5), 2, 100.0, 100.0, None),
+            ),
+        ]));
+        let market = Market::new(TODAY)
+            .with_curve("USD", flat(0.04))
+            .with_curve("EUR", flat(0.03))
+            .with_fx_rate(ExchangeRate::new(EUR, USD, 1.1));
+
+        let value = portfolio.market_value(&market, &USD).unwrap();
+        let price = |bond: CouponBond| bond.market_price(&market).unwrap().value();
+        assert_approx_equal!(
+            value,
+            10.0 * price(bond(USD, 2))
+                + 5.0 * price(bond(USD, 5))
+                + 2.0 * 1.1 * price(bond(EUR, 5)),
+            1e-9
+        );
+
+        // A single bump of the USD curve reprices both USD bonds.
+        let bumped = market.bump_curve("USD", 0.0001).unwrap();
+        let dv01 = value - portfolio.market_value(&bumped, &USD).unwrap();
+        assert!(dv01 > 0.0);
+        assert_approx_equal!(
+            dv01,
+            10.0 * (price(bond(USD, 2)) - bond(USD, 2).market_price(&bumped).unwrap().value())
+                + 5.0 * (price(bond(USD, 5)) - bond(USD, 5).market_price(&bumped).unwrap().value()),
+            1e-9
+        );
+
+        // No GBP rates.
+        assert!(portfolio.market_value(&market, &crate::money::GBP).is_err());
+    }
+
+    #[test]
+    fn test_price_scenarios() {
+        let portfolio = Portfolio::new(HashMap::from([
+            (
+                "A".to_string(),
+                Position::new(bond(USD, 2), 10, 100.0, 100.0, None),
+            ),
+            (
+                "B".to_string(),
+                Position::new(bond(EUR, 5), 5, 100.0, 100.0, None),
+            ),
+        ]));
+        let base = Market::new(TODAY)
+            .with_curve("USD", flat(0.04))
+            .with_curve("EUR", flat(0.03))
+            .with_fx_rate(ExchangeRate::new(EUR, USD, 1.1));
+
+        // Parallel shifts of both curves from -100bp to +100bp.
+        let markets: Vec<Market> = (-10..=10)
+            .map(|i| {
+                let shift = f64::from(i) * 0.001;
+                base.bump_curve("USD", shift)
+                    .and_then(|market| market.bump_curve("EUR", shift))
+                    .unwrap()
+            })
+            .collect();
+
+        let scenarios = portfolio.price_scenarios(&markets, &USD).unwrap();
+        assert_eq!(scenarios.positions, vec!["A", "B"]);
+        assert_eq!(scenarios.values.len(), 21);
+
+        for (market, total) in markets.iter().zip(scenarios.totals()) {
+            assert_approx_equal!(total, portfolio.market_value(market, &USD).unwrap(), 1e-9);
+        }
+
+        // The unshifted market has no P&L, and prices fall as rates rise.
+        let pnl = scenarios.pnl(portfolio.market_value(&base, &USD).unwrap());
+        assert_approx_equal!(pnl[10], 0.0, 1e-9);
+        assert!(pnl.windows(2).all(|pair| pair[1] < pair[0]));
+
+        // A market without the EUR curve.
+        let missing = [Market::new(TODAY).with_curve("USD", flat(0.04))];
+        assert!(portfolio.price_scenarios(&missing, &USD).is_err());
+    }
+}
@@ -297,7 +297,6 @@ pub const WEIGHTS: [f64; 100] = [
 #[cfg(test)]
 mod tests_integration {
     use super::*;
-    use crate::assert_approx_equal;
 
     #[test]
     fn test_quadrature() {
@@ -14,12 +14,47 @@
 //! "Interpolatable"
 //! - ADJECTIVE:
 //!     - *Able to be interpolated, or suited to interpolation.*
+//!
+//! One-dimensional interpolators share the [`Interpolator`] trait, and
+//! handle points outside the data with an [`Extrapolation`] policy:
+//!
+//! - [`LinearInterpolator`]: piecewise linear.
+//! - [`CubicSpline`]: natural or clamped cubic spline (twice
+//!   differentiable).
+//! - [`Pchip`]: monotone piecewise cubic Hermite (Fritsch-Carlson), which
+//!   does not overshoot the data.
+//! - [`Akima`]: Akima's cubic, robust to outliers.
+//!
+//! Gridded data, e.g. implied volatilities by expiry and strike, use the
+//! [`Bilinear`] and [`Bicubic`] interpolators.
+//!
+//! ```
+//! use RustQuant::math::interpolation::*;
+//!
+//! let times = [0.25, 0.5, 1.0, 2.0, 5.0];
+//! let rates = [0.050, 0.052, 0.049, 0.045, 0.043];
+//!
+//! let curve = Pchip::new(&times, &rates)
+//!     .unwrap()
+//!     .with_extrapolation(Extrapolation::Flat);
+//!
+//! assert_eq!(curve.interpolate(0.5).unwrap(), 0.052);
+//! assert_eq!(curve.interpolate(10.0).unwrap(), 0.043);
+//! ```
 
 use std::cmp::Ordering;
 
 use num::{FromPrimitive, Num, ToPrimitive};
 use time::OffsetDateTime;
 
+/// One-dimensional interpolators.
+pub mod one_dimensional;
+pub use one_dimensional::*;
+
+/// Interpolation on two-dimensional grids.
+pub mod two_dimensional;
+pub use two_dimensional::*;
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // STRUCTS, ENUMS, AND TRAITS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -41,6 +76,7 @@ pub enum InterpolationMethod {
 ///
 /// There must be a difference between the two points, and the two points must
 /// be ordered.
+#[allow(dead_code)]
 trait Interpolatable {
     fn difference(&self, other: &Self) -> f64;
     fn compare(&self, other: &Self) -> Ordering;
@@ -98,10 +134,6 @@ impl Interpolatable for OffsetDateTime {
     }
 }
 
-trait Interpolator {
-    fn interpolate(&self, other: &Self, amount: &Self, method: InterpolationMethod) -> Self;
-}
-
 // /// Linear interpolation between two points.
 // pub trait Lerp<T: Num + PartialOrd + Copy + FromPrimitive + ToPrimitive> {
 //     fn lerp(&self, other: &Self, amount: &Self) -> Self;
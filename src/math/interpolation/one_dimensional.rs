@@ -0,0 +1,579 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use thiserror::Error;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Behaviour outside the range of the data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Extrapolation {
+    /// The value at the nearest end.
+    #[default]
+    Flat,
+
+    /// Extends the interpolant linearly, with its slope at the nearest end.
+    Linear,
+
+    /// Points outside the data are an error.
+    Error,
+}
+
+/// Interpolation errors.
+#[derive(Debug, Clone, Copy, PartialEq, Error)]
+pub enum InterpolationError {
+    /// Not enough points for the method.
+    #[error("At least {0} points are needed.")]
+    TooFewPoints(usize),
+
+    /// The abscissae and ordinates have different lengths.
+    #[error("The abscissae and ordinates have different lengths.")]
+    LengthMismatch,
+
+    /// The abscissae are not strictly increasing (or not finite).
+    #[error("The abscissae must be finite and strictly increasing.")]
+    UnsortedAbscissae,
+
+    /// The point is outside the data, with `Extrapolation::Error`.
+    #[error("{0} is outside the range of the data.")]
+    OutOfRange(f64),
+}
+
+/// One-dimensional interpolator through points $(x_i, y_i)$.
+pub trait Interpolator {
+    /// Abscissae of the data, strictly increasing.
+    fn xs(&self) -> &[f64];
+
+    /// Ordinates of the data.
+    fn ys(&self) -> &[f64];
+
+    /// Extrapolation policy.
+    fn extrapolation(&self) -> Extrapolation;
+
+    /// Value and first derivative of the interpolant at `x`, inside the
+    /// range of the data.
+    fn evaluate(&self, x: f64) -> (f64, f64);
+
+    /// Value at `x`, extrapolated outside the data.
+    fn interpolate(&self, x: f64) -> Result<f64, InterpolationError> {
+        self.value_and_derivative(x).map(|(value, _)| value)
+    }
+
+    /// First derivative at `x` (zero in the flat extrapolation region).
+    fn derivative(&self, x: f64) -> Result<f64, InterpolationError> {
+        self.value_and_derivative(x)
+            .map(|(_, derivative)| derivative)
+    }
+
+    /// Value and first derivative at `x`, extrapolated outside the data.
+    fn value_and_derivative(&self, x: f64) -> Result<(f64, f64), InterpolationError> {
+        let xs = self.xs();
+        let (first, last) = (xs[0], xs[xs.len() - 1]);
+
+        if (first..=last).contains(&x) {
+            return Ok(self.evaluate(x));
+        }
+
+        let end = if x < first { first } else { last };
+        match self.extrapolation() {
+            Extrapolation::Flat => Ok((self.evaluate(end).0, 0.0)),
+            Extrapolation::Linear => {
+                let (value, slope) = self.evaluate(end);
+                Ok((value + slope * (x - end), slope))
+            }
+            Extrapolation::Error => Err(InterpolationError::OutOfRange(x)),
+        }
+    }
+
+    /// Values at each of `xs`.
+    fn interpolate_many(&self, xs: &[f64]) -> Result<Vec<f64>, InterpolationError> {
+        xs.iter().map(|x| self.interpolate(*x)).collect()
+    }
+}
+
+/// Piecewise linear interpolation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinearInterpolator {
+    xs: Vec<f64>,
+    ys: Vec<f64>,
+    extrapolation: Extrapolation,
+}
+
+/// Cubic spline: piecewise cubic with continuous first and second
+/// derivatives.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CubicSpline {
+    hermite: Hermite,
+}
+
+/// Monotone piecewise cubic Hermite interpolation (PCHIP, Fritsch and
+/// Carlson, 1980): monotone data give a monotone interpolant, and local
+/// extrema are only at the data points.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pchip {
+    hermite: Hermite,
+}
+
+/// Akima's piecewise cubic (Akima, 1970): slopes are weighted averages of
+/// neighbouring secants, so an outlier only affects nearby intervals.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Akima {
+    hermite: Hermite,
+}
+
+/// Interpolation method, to choose an interpolator at run time (e.g. for
+/// a curve).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InterpolationScheme {
+    /// [`LinearInterpolator`].
+    #[default]
+    Linear,
+
+    /// [`CubicSpline::natural`].
+    NaturalCubicSpline,
+
+    /// [`Pchip`].
+    Pchip,
+
+    /// [`Akima`].
+    Akima,
+}
+
+// Piecewise cubic in Hermite form: values and slopes at the nodes.
+#[derive(Debug, Clone, PartialEq)]
+struct Hermite {
+    xs: Vec<f64>,
+    ys: Vec<f64>,
+    slopes: Vec<f64>,
+    extrapolation: Extrapolation,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// Checks the data and returns the secant slopes.
+pub(super) fn secants(
+    xs: &[f64],
+    ys: &[f64],
+    minimum: usize,
+) -> Result<Vec<f64>, InterpolationError> {
+    if xs.len() != ys.len() {
+        return Err(InterpolationError::LengthMismatch);
+    }
+    if xs.len() < minimum {
+        return Err(InterpolationError::TooFewPoints(minimum));
+    }
+    if xs.iter().any(|x| !x.is_finite()) || xs.windows(2).any(|pair| pair[0] >= pair[1]) {
+        return Err(InterpolationError::UnsortedAbscissae);
+    }
+
+    Ok(xs
+        .windows(2)
+        .zip(ys.windows(2))
+        .map(|(x, y)| (y[1] - y[0]) / (x[1] - x[0]))
+        .collect())
+}
+
+// Index of the interval [x_i, x_{i+1}] containing x.
+pub(super) fn interval(xs: &[f64], x: f64) -> usize {
+    xs.partition_point(|xi| *xi <= x)
+        .saturating_sub(1)
+        .min(xs.len() - 2)
+}
+
+impl LinearInterpolator {
+    /// Interpolator through the points, with flat extrapolation.
+    pub fn new(xs: &[f64], ys: &[f64]) -> Result<Self, InterpolationError> {
+        secants(xs, ys, 2)?;
+
+        Ok(Self {
+            xs: xs.to_vec(),
+            ys: ys.to_vec(),
+            extrapolation: Extrapolation::default(),
+        })
+    }
+
+    /// Sets the extrapolation policy.
+    pub fn with_extrapolation(mut self, extrapolation: Extrapolation) -> Self {
+        self.extrapolation = extrapolation;
+        self
+    }
+}
+
+impl Interpolator for LinearInterpolator {
+    fn xs(&self) -> &[f64] {
+        &self.xs
+    }
+
+    fn ys(&self) -> &[f64] {
+        &self.ys
+    }
+
+    fn extrapolation(&self) -> Extrapolation {
+        self.extrapolation
+    }
+
+    fn evaluate(&self, x: f64) -> (f64, f64) {
+        let i = interval(&self.xs, x);
+        let slope = (self.ys[i + 1] - self.ys[i]) / (self.xs[i + 1] - self.xs[i]);
+
+        (self.ys[i] + slope * (x - self.xs[i]), slope)
+    }
+}
+
+impl Hermite {
+    fn new(xs: &[f64], ys: &[f64], slopes: Vec<f64>) -> Self {
+        Self {
+            xs: xs.to_vec(),
+            ys: ys.to_vec(),
+            slopes,
+            extrapolation: Extrapolation::default(),
+        }
+    }
+
+    fn evaluate(&self, x: f64) -> (f64, f64) {
+        let i = interval(&self.xs, x);
+        let h = self.xs[i + 1] - self.xs[i];
+        let t = (x - self.xs[i]) / h;
+        let (y0, y1) = (self.ys[i], self.ys[i + 1]);
+        let (d0, d1) = (h * self.slopes[i], h * self.slopes[i + 1]);
+
+        let value = (1.0 + 2.0 * t) * (1.0 - t).powi(2) * y0
+            + t * (1.0 - t).powi(2) * d0
+            + t * t * (3.0 - 2.0 * t) * y1
+            + t * t * (t - 1.0) * d1;
+        let derivative = (6.0 * t * (t - 1.0) * (y0 - y1)
+            + (3.0 * t * t - 4.0 * t + 1.0) * d0
+            + (3.0 * t * t - 2.0 * t) * d1)
+            / h;
+
+        (value, derivative)
+    }
+}
+
+macro_rules! impl_hermite_interpolator {
+    ($($t:ty)*) => ($(
+        impl $t {
+            /// Sets the extrapolation policy.
+            pub fn with_extrapolation(mut self, extrapolation: Extrapolation) -> Self {
+                self.hermite.extrapolation = extrapolation;
+                self
+            }
+
+            /// Slopes of the interpolant at the data points.
+            pub fn slopes(&self) -> &[f64] {
+                &self.hermite.slopes
+            }
+        }
+
+        impl Interpolator for $t {
+            fn xs(&self) -> &[f64] {
+                &self.hermite.xs
+            }
+
+            fn ys(&self) -> &[f64] {
+                &self.hermite.ys
+            }
+
+            fn extrapolation(&self) -> Extrapolation {
+                self.hermite.extrapolation
+            }
+
+            fn evaluate(&self, x: f64) -> (f64, f64) {
+                self.hermite.evaluate(x)
+            }
+        }
+    )*)
+}
+
+impl_hermite_interpolator! { CubicSpline Pchip Akima }
+
+impl CubicSpline {
+    /// Natural spline: zero second derivative at both ends.
+    pub fn natural(xs: &[f64], ys: &[f64]) -> Result<Self, InterpolationError> {
+        Self::with_end_slopes(xs, ys, None)
+    }
+
+    /// Clamped spline, with the given first derivatives at the ends.
+    pub fn clamped(
+        xs: &[f64],
+        ys: &[f64],
+        first_slope: f64,
+        last_slope: f64,
+    ) -> Result<Self, InterpolationError> {
+        Self::with_end_slopes(xs, ys, Some((first_slope, last_slope)))
+    }
+
+    fn with_end_slopes(
+        xs: &[f64],
+        ys: &[f64],
+        end_slopes: Option<(f64, f64)>,
+    ) -> Result<Self, InterpolationError> {
+        let secants = secants(xs, ys, 2)?;
+        let n = xs.len();
+        let h: Vec<f64> = xs.windows(2).map(|pair| pair[1] - pair[0]).collect();
+
+        // Tridiagonal system for the second derivatives M.
+        let (mut lower, mut diagonal, mut upper, mut rhs) =
+            (vec![0.0; n], vec![0.0; n], vec![0.0; n], vec![0.0; n]);
+        for i in 1..n - 1 {
+            lower[i] = h[i - 1];
+            diagonal[i] = 2.0 * (h[i - 1] + h[i]);
+            upper[i] = h[i];
+            rhs[i] = 6.0 * (secants[i] - secants[i - 1]);
+        }
+        match end_slopes {
+            None => {
+                diagonal[0] = 1.0;
+                diagonal[n - 1] = 1.0;
+            }
+            Some((first, last)) => {
+                diagonal[0] = 2.0 * h[0];
+                upper[0] = h[0];
+                rhs[0] = 6.0 * (secants[0] - first);
+                lower[n - 1] = h[n - 2];
+                diagonal[n - 1] = 2.0 * h[n - 2];
+                rhs[n - 1] = 6.0 * (last - secants[n - 2]);
+            }
+        }
+        let m = solve_tridiagonal(&lower, &diagonal, &upper, &rhs);
+
+        let mut slopes: Vec<f64> = (0..n - 1)
+            .map(|i| secants[i] - h[i] * (2.0 * m[i] + m[i + 1]) / 6.0)
+            .collect();
+        slopes.push(secants[n - 2] + h[n - 2] * (m[n - 2] + 2.0 * m[n - 1]) / 6.0);
+
+        Ok(Self {
+            hermite: Hermite::new(xs, ys, slopes),
+        })
+    }
+}
+
+impl Pchip {
+    /// Interpolator through the points, with flat extrapolation.
+    pub fn new(xs: &[f64], ys: &[f64]) -> Result<Self, InterpolationError> {
+        let secants = secants(xs, ys, 2)?;
+        let n = xs.len();
+
+        let slopes = match n {
+            2 => vec![secants[0]; 2],
+            _ => {
+                let h: Vec<f64> = xs.windows(2).map(|pair| pair[1] - pair[0]).collect();
+                let mut slopes = vec![0.0; n];
+
+                // Weighted harmonic mean of the secants, zero at extrema.
+                for k in 1..n - 1 {
+                    let (a, b) = (secants[k - 1], secants[k]);
+                    if a * b > 0.0 {
+                        let (w1, w2) = (2.0 * h[k] + h[k - 1], h[k] + 2.0 * h[k - 1]);
+                        slopes[k] = (w1 + w2) / (w1 / a + w2 / b);
+                    }
+                }
+                slopes[0] = pchip_end_slope(h[0], h[1], secants[0], secants[1]);
+                slopes[n - 1] = pchip_end_slope(h[n - 2], h[n - 3], secants[n - 2], secants[n - 3]);
+
+                slopes
+            }
+        };
+
+        Ok(Self {
+            hermite: Hermite::new(xs, ys, slopes),
+        })
+    }
+}
+
+// Three-point end slope, limited to preserve the shape.
+fn pchip_end_slope(h0: f64, h1: f64, secant0: f64, secant1: f64) -> f64 {
+    let slope = ((2.0 * h0 + h1) * secant0 - h0 * secant1) / (h0 + h1);
+
+    if slope * secant0 <= 0.0 {
+        0.0
+    } else if secant0 * secant1 < 0.0 && slope.abs() > 3.0 * secant0.abs() {
+        3.0 * secant0
+    } else {
+        slope
+    }
+}
+
+impl Akima {
+    /// Interpolator through the points, with flat extrapolation.
+    pub fn new(xs: &[f64], ys: &[f64]) -> Result<Self, InterpolationError> {
+        let secants = secants(xs, ys, 2)?;
+        let n = xs.len();
+
+        // Two extra secants at each end, extrapolated linearly.
+        let mut m = Vec::with_capacity(n + 3);
+        let (first, second) = (secants[0], *secants.get(1).unwrap_or(&secants[0]));
+        m.push(3.0 * first - 2.0 * second);
+        m.push(2.0 * first - second);
+        m.extend(&secants);
+        let (last, before) = (secants[n - 2], secants[n.saturating_sub(3)]);
+        m.push(2.0 * last - before);
+        m.push(3.0 * last - 2.0 * before);
+
+        let slopes = (0..n)
+            .map(|i| {
+                let (w1, w2) = ((m[i + 3] - m[i + 2]).abs(), (m[i + 1] - m[i]).abs());
+                match w1 + w2 {
+                    0.0 => 0.5 * (m[i + 1] + m[i + 2]),
+                    total => (w1 * m[i + 1] + w2 * m[i + 2]) / total,
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            hermite: Hermite::new(xs, ys, slopes),
+        })
+    }
+}
+
+impl InterpolationScheme {
+    /// Interpolator of this kind through the points.
+    pub fn build(
+        &self,
+        xs: &[f64],
+        ys: &[f64],
+        extrapolation: Extrapolation,
+    ) -> Result<Box<dyn Interpolator + Send + Sync>, InterpolationError> {
+        Ok(match self {
+            Self::Linear => {
+                Box::new(LinearInterpolator::new(xs, ys)?.with_extrapolation(extrapolation))
+            }
+            Self::NaturalCubicSpline => {
+                Box::new(CubicSpline::natural(xs, ys)?.with_extrapolation(extrapolation))
+            }
+            Self::Pchip => Box::new(Pchip::new(xs, ys)?.with_extrapolation(extrapolation)),
+            Self::Akima => Box::new(Akima::new(xs, ys)?.with_extrapolation(extrapolation)),
+        })
+    }
+}
+
+// Thomas algorithm for a diagonally dominant tridiagonal system.
+fn solve_tridiagonal(lower: &[f64], diagonal: &[f64], upper: &[f64], rhs: &[f64]) -> Vec<f64> {
+    let n = diagonal.len();
+    let (mut c, mut d) = (vec![0.0; n], vec![0.0; n]);
+
+    c[0] = upper[0] / diagonal[0];
+    d[0] = rhs[0] / diagonal[0];
+    for i in 1..n {
+        let denominator = diagonal[i] - lower[i] * c[i - 1];
+        c[i] = upper[i] / denominator;
+        d[i] = (rhs[i] - lower[i] * d[i - 1]) / denominator;
+    }
+
+    for i in (0..n - 1).rev() {
+        d[i] -= c[i] * d[i + 1];
+    }
+
+    d
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_one_dimensional {
+    use super::*;
+
+    const XS: [f64; 6] = [0.0, 1.0, 2.0, 3.5, 4.0, 6.0];
+
+    #[test]
+    fn test_interpolates_data_and_extrapolates() {
+        let ys: Vec<f64> = XS.iter().map(|x| (0.7 * x).sin()).collect();
+
+        for scheme in [
+            InterpolationScheme::Linear,
+            InterpolationScheme::NaturalCubicSpline,
+            InterpolationScheme::Pchip,
+            InterpolationScheme::Akima,
+        ] {
+            let interpolator = scheme.build(&XS, &ys, Extrapolation::Flat).unwrap();
+            for (x, y) in XS.iter().zip(&ys) {
+                assert_approx_equal!(interpolator.interpolate(*x).unwrap(), *y, 1e-14);
+            }
+            assert_eq!(interpolator.interpolate(-1.0).unwrap(), ys[0]);
+            assert_eq!(interpolator.derivative(7.0).unwrap(), 0.0);
+
+            let error = scheme.build(&XS, &ys, Extrapolation::Error).unwrap();
+            assert_eq!(
+                error.interpolate(6.5),
+                Err(InterpolationError::OutOfRange(6.5))
+            );
+
+            let linear = scheme.build(&XS, &ys, Extrapolation::Linear).unwrap();
+            let (value, slope) = linear.value_and_derivative(6.0).unwrap();
+            assert_approx_equal!(linear.interpolate(6.5).unwrap(), value + 0.5 * slope, 1e-14);
+        }
+
+        assert_eq!(
+            LinearInterpolator::new(&[0.0, 0.0], &[1.0, 2.0]),
+            Err(InterpolationError::UnsortedAbscissae)
+        );
+        assert_eq!(
+            Pchip::new(&[0.0], &[1.0]),
+            Err(InterpolationError::TooFewPoints(2))
+        );
+    }
+
+    #[test]
+    fn test_cubic_spline() {
+        // Clamped splines reproduce cubics exactly.
+        let cubic = |x: f64| x * x * x - 2.0 * x + 1.0;
+        let ys: Vec<f64> = XS.iter().map(|x| cubic(*x)).collect();
+        let spline = CubicSpline::clamped(&XS, &ys, -2.0, 106.0).unwrap();
+
+        for x in [0.3, 1.7, 2.9, 3.75, 5.2] {
+            assert_approx_equal!(spline.interpolate(x).unwrap(), cubic(x), 1e-11);
+            assert_approx_equal!(spline.derivative(x).unwrap(), 3.0 * x * x - 2.0, 1e-10);
+        }
+
+        // Natural splines reproduce straight lines, and have continuous
+        // first derivatives at the knots.
+        let natural = CubicSpline::natural(&XS, &ys).unwrap();
+        for x in &XS[1..5] {
+            let left = natural.hermite.evaluate(x - 1e-9).1;
+            let right = natural.hermite.evaluate(x + 1e-9).1;
+            assert_approx_equal!(left, right, 1e-6);
+        }
+        let line = CubicSpline::natural(&XS, &XS.map(|x| 2.0 * x + 1.0)).unwrap();
+        assert_approx_equal!(line.interpolate(2.7).unwrap(), 6.4, 1e-13);
+    }
+
+    #[test]
+    fn test_shape_preservation() {
+        // A step: splines overshoot, PCHIP stays monotone and in range.
+        let xs = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+        let ys = [0.0, 0.0, 0.0, 1.0, 1.0, 1.0];
+        let spline = CubicSpline::natural(&xs, &ys).unwrap();
+        let pchip = Pchip::new(&xs, &ys).unwrap();
+        let grid: Vec<f64> = (0..=500).map(|i| 0.01 * i as f64).collect();
+
+        let spline_values = spline.interpolate_many(&grid).unwrap();
+        assert!(spline_values.iter().any(|y| *y < -1e-3 || *y > 1.0 + 1e-3));
+
+        let pchip_values = pchip.interpolate_many(&grid).unwrap();
+        assert!(pchip_values
+            .windows(2)
+            .all(|pair| pair[1] >= pair[0] - 1e-15));
+        assert!(pchip_values
+            .iter()
+            .all(|y| (-1e-15..=1.0 + 1e-15).contains(y)));
+
+        // Akima: an outlier only moves the nearby intervals.
+        let ys = [1.0, 1.0, 1.0, 5.0, 1.0, 1.0, 1.0, 1.0, 1.0];
+        let xs: Vec<f64> = (0..9).map(|i| i as f64).collect();
+        let akima = Akima::new(&xs, &ys).unwrap();
+        assert_approx_equal!(akima.interpolate(6.5).unwrap(), 1.0, 1e-14);
+        assert!(akima.slopes()[6..].iter().all(|d| *d == 0.0));
+    }
+}
@@ -0,0 +1,222 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use super::one_dimensional::{interval, secants};
+use super::{CubicSpline, Extrapolation, InterpolationError, Interpolator};
+use ndarray::Array2;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Bilinear interpolation on a rectangular grid.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bilinear {
+    xs: Vec<f64>,
+    ys: Vec<f64>,
+    values: Array2<f64>,
+    extrapolation: Extrapolation,
+}
+
+/// Bicubic interpolation on a rectangular grid: natural cubic splines in
+/// `x` along each grid column, then a natural spline in `y` through their
+/// values. The interpolant is twice differentiable in each direction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bicubic {
+    xs: Vec<f64>,
+    ys: Vec<f64>,
+    columns: Vec<CubicSpline>,
+    extrapolation: Extrapolation,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// Checks the grid: `values[[i, j]]` is the value at `(xs[i], ys[j])`.
+fn check_grid(
+    xs: &[f64],
+    ys: &[f64],
+    values: &Array2<f64>,
+    minimum: usize,
+) -> Result<(), InterpolationError> {
+    if values.dim() != (xs.len(), ys.len()) {
+        return Err(InterpolationError::LengthMismatch);
+    }
+    secants(xs, &vec![0.0; xs.len()], minimum)?;
+    secants(ys, &vec![0.0; ys.len()], minimum)?;
+
+    Ok(())
+}
+
+// Applies the extrapolation policy to one coordinate: `Flat` clamps it to
+// the grid, `Linear` leaves it as is.
+fn coordinate(
+    nodes: &[f64],
+    x: f64,
+    extrapolation: Extrapolation,
+) -> Result<f64, InterpolationError> {
+    let (first, last) = (nodes[0], nodes[nodes.len() - 1]);
+
+    match extrapolation {
+        _ if (first..=last).contains(&x) => Ok(x),
+        Extrapolation::Flat => Ok(x.clamp(first, last)),
+        Extrapolation::Linear => Ok(x),
+        Extrapolation::Error => Err(InterpolationError::OutOfRange(x)),
+    }
+}
+
+impl Bilinear {
+    /// Interpolator on the grid `xs` × `ys`, where `values[[i, j]]` is
+    /// the value at `(xs[i], ys[j])`. Extrapolation is flat.
+    pub fn new(xs: &[f64], ys: &[f64], values: Array2<f64>) -> Result<Self, InterpolationError> {
+        check_grid(xs, ys, &values, 2)?;
+
+        Ok(Self {
+            xs: xs.to_vec(),
+            ys: ys.to_vec(),
+            values,
+            extrapolation: Extrapolation::default(),
+        })
+    }
+
+    /// Sets the extrapolation policy, applied to each coordinate.
+    pub fn with_extrapolation(mut self, extrapolation: Extrapolation) -> Self {
+        self.extrapolation = extrapolation;
+        self
+    }
+
+    /// Value at `(x, y)`.
+    pub fn interpolate(&self, x: f64, y: f64) -> Result<f64, InterpolationError> {
+        let x = coordinate(&self.xs, x, self.extrapolation)?;
+        let y = coordinate(&self.ys, y, self.extrapolation)?;
+
+        let (i, j) = (interval(&self.xs, x), interval(&self.ys, y));
+        let s = (x - self.xs[i]) / (self.xs[i + 1] - self.xs[i]);
+        let t = (y - self.ys[j]) / (self.ys[j + 1] - self.ys[j]);
+        let v = &self.values;
+
+        Ok((1.0 - s) * (1.0 - t) * v[[i, j]]
+            + s * (1.0 - t) * v[[i + 1, j]]
+            + (1.0 - s) * t * v[[i, j + 1]]
+            + s * t * v[[i + 1, j + 1]])
+    }
+}
+
+impl Bicubic {
+    /// Interpolator on the grid `xs` × `ys`, where `values[[i, j]]` is
+    /// the value at `(xs[i], ys[j])`. Extrapolation is flat.
+    pub fn new(xs: &[f64], ys: &[f64], values: Array2<f64>) -> Result<Self, InterpolationError> {
+        check_grid(xs, ys, &values, 2)?;
+
+        let columns = values
+            .columns()
+            .into_iter()
+            .map(|column| CubicSpline::natural(xs, &column.to_vec()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            xs: xs.to_vec(),
+            ys: ys.to_vec(),
+            columns,
+            extrapolation: Extrapolation::default(),
+        })
+    }
+
+    /// Sets the extrapolation policy, applied to each coordinate.
+    pub fn with_extrapolation(mut self, extrapolation: Extrapolation) -> Self {
+        self.extrapolation = extrapolation;
+        self.columns = std::mem::take(&mut self.columns)
+            .into_iter()
+            .map(|column| column.with_extrapolation(extrapolation))
+            .collect();
+        self
+    }
+
+    /// Value at `(x, y)`.
+    pub fn interpolate(&self, x: f64, y: f64) -> Result<f64, InterpolationError> {
+        let along_x = self
+            .columns
+            .iter()
+            .map(|column| column.interpolate(x))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        CubicSpline::natural(&self.ys, &along_x)?
+            .with_extrapolation(self.extrapolation)
+            .interpolate(y)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_two_dimensional {
+    use super::*;
+
+    const XS: [f64; 4] = [0.0, 0.5, 1.5, 2.0];
+    const YS: [f64; 5] = [-1.0, 0.0, 1.0, 2.0, 4.0];
+
+    fn grid(f: impl Fn(f64, f64) -> f64) -> Array2<f64> {
+        Array2::from_shape_fn((XS.len(), YS.len()), |(i, j)| f(XS[i], YS[j]))
+    }
+
+    #[test]
+    fn test_bilinear() {
+        // Bilinear functions are reproduced exactly, including by linear
+        // extrapolation.
+        let f = |x: f64, y: f64| 1.0 + 2.0 * x - y + 0.5 * x * y;
+        let bilinear = Bilinear::new(&XS, &YS, grid(f)).unwrap();
+
+        for (x, y) in [(0.2, 0.3), (1.0, -0.5), (1.9, 3.0), (0.5, 2.0)] {
+            assert_approx_equal!(bilinear.interpolate(x, y).unwrap(), f(x, y), 1e-13);
+        }
+        assert_approx_equal!(bilinear.interpolate(3.0, 5.0).unwrap(), f(2.0, 4.0), 1e-13);
+
+        let linear = bilinear.clone().with_extrapolation(Extrapolation::Linear);
+        assert_approx_equal!(linear.interpolate(3.0, 5.0).unwrap(), f(3.0, 5.0), 1e-13);
+
+        let error = bilinear.with_extrapolation(Extrapolation::Error);
+        assert_eq!(
+            error.interpolate(1.0, -2.0),
+            Err(InterpolationError::OutOfRange(-2.0))
+        );
+        assert_eq!(
+            Bilinear::new(&XS, &YS[..4], grid(f)),
+            Err(InterpolationError::LengthMismatch)
+        );
+    }
+
+    #[test]
+    fn test_bicubic() {
+        let f = |x: f64, y: f64| (x * y).sin() + 0.3 * x - 0.1 * y * y;
+        let bicubic = Bicubic::new(&XS, &YS, grid(f)).unwrap();
+
+        for (x, y) in XS.iter().flat_map(|x| YS.iter().map(move |y| (*x, *y))) {
+            assert_approx_equal!(bicubic.interpolate(x, y).unwrap(), f(x, y), 1e-13);
+        }
+
+        // Smooth surfaces on a fine grid are recovered closely.
+        let xs: Vec<f64> = (0..=20).map(|i| 0.1 * i as f64).collect();
+        let ys: Vec<f64> = (0..=20).map(|j| -1.0 + 0.1 * j as f64).collect();
+        let values = Array2::from_shape_fn((21, 21), |(i, j)| f(xs[i], ys[j]));
+        let fine = Bicubic::new(&xs, &ys, values.clone()).unwrap();
+        let coarse = Bilinear::new(&xs, &ys, values).unwrap();
+
+        let (x, y) = (1.234, -0.123);
+        let bicubic_error = (fine.interpolate(x, y).unwrap() - f(x, y)).abs();
+        let bilinear_error = (coarse.interpolate(x, y).unwrap() - f(x, y)).abs();
+        assert!(bicubic_error < 1e-5);
+        assert!(bicubic_error < bilinear_error);
+
+        let flat = fine.with_extrapolation(Extrapolation::Flat);
+        assert_approx_equal!(flat.interpolate(5.0, -3.0).unwrap(), f(2.0, -1.0), 1e-13);
+    }
+}
@@ -0,0 +1,485 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Linear algebra helpers for correlation and covariance matrices.
+//!
+//! - [`cholesky`]: Cholesky factor, falling back to a pivoted
+//!   factorisation ([`pivoted_cholesky`]) for singular positive
+//!   semi-definite matrices.
+//! - [`symmetric_eigen`] and [`PrincipalComponents`]: eigen decomposition
+//!   and PCA, e.g. for the level, slope and curvature factors of yield
+//!   curve moves.
+//! - [`NearestCorrelation`]: Higham's (2002) alternating projections, to
+//!   repair estimated or stressed correlation matrices that are not
+//!   positive semi-definite.
+//!
+//! ```
+//! use RustQuant::math::linalg::{cholesky, NearestCorrelation};
+//! use nalgebra::{DMatrix, DVector};
+//!
+//! // Inconsistent pairwise correlations: not a valid correlation matrix.
+//! let input = DMatrix::from_row_slice(3, 3, &[
+//!     1.0, 0.9, 0.7,
+//!     0.9, 1.0, -0.4,
+//!     0.7, -0.4, 1.0,
+//! ]);
+//! assert!(cholesky(&input).is_err());
+//!
+//! let repaired = NearestCorrelation::default().nearest(&input).unwrap();
+//! let factor = cholesky(&repaired).unwrap();
+//!
+//! // Correlated normals for a simulation: L z.
+//! let z = DVector::from_vec(vec![0.3, -1.2, 0.8]);
+//! let correlated = &factor * z;
+//! assert_eq!(correlated.len(), 3);
+//! ```
+
+use nalgebra::{DMatrix, DVector, SymmetricEigen};
+use thiserror::Error;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Linear algebra errors.
+#[derive(Debug, Clone, Copy, PartialEq, Error)]
+pub enum LinalgError {
+    /// The matrix is not square.
+    #[error("The matrix is not square.")]
+    NotSquare,
+
+    /// The matrix is not symmetric.
+    #[error("The matrix is not symmetric.")]
+    NotSymmetric,
+
+    /// The matrix has a negative eigenvalue.
+    #[error("The matrix is not positive semi-definite.")]
+    NotPositiveSemiDefinite,
+
+    /// At least two observations are needed.
+    #[error("At least two observations are needed.")]
+    TooFewObservations,
+
+    /// The iteration did not converge.
+    #[error("No convergence after {0} iterations.")]
+    NoConvergence(usize),
+}
+
+/// Pivoted Cholesky factorisation $P^\top A P = L L^\top$ of a positive
+/// semi-definite matrix, where $L$ has `rank` columns.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PivotedCholesky {
+    /// Lower trapezoidal factor of the permuted matrix (`n × rank`).
+    pub lower: DMatrix<f64>,
+
+    /// Pivot order: row `i` of the permuted matrix is row
+    /// `permutation[i]` of the original.
+    pub permutation: Vec<usize>,
+
+    /// Numerical rank of the matrix.
+    pub rank: usize,
+}
+
+/// Principal components of a covariance matrix, by decreasing variance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrincipalComponents {
+    /// Mean of the observations (zero when built from a covariance matrix).
+    pub mean: DVector<f64>,
+
+    /// Variances of the components (eigenvalues), in decreasing order.
+    pub variances: DVector<f64>,
+
+    /// Loadings: column `k` is the `k`-th component (eigenvector), with
+    /// its largest entry positive.
+    pub loadings: DMatrix<f64>,
+}
+
+/// Nearest correlation matrix in the Frobenius norm (Higham, 2002):
+/// alternating projections onto the positive semi-definite matrices and the
+/// matrices with unit diagonal, with Dykstra's correction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NearestCorrelation {
+    /// Tolerance on the relative change between iterations.
+    pub tolerance: f64,
+
+    /// Maximum number of iterations.
+    pub max_iterations: usize,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// Checks that the matrix is square and symmetric (to rounding).
+fn check_symmetric(matrix: &DMatrix<f64>) -> Result<(), LinalgError> {
+    if !matrix.is_square() {
+        return Err(LinalgError::NotSquare);
+    }
+
+    let scale = matrix.amax().max(1.0);
+    if (matrix - matrix.transpose()).amax() > 1e-10 * scale {
+        return Err(LinalgError::NotSymmetric);
+    }
+
+    Ok(())
+}
+
+/// Factor $L$ with $L L^\top = A$ of a symmetric positive semi-definite
+/// matrix.
+///
+/// For positive definite matrices this is the (lower triangular) Cholesky
+/// factor. Otherwise, the factor of [`pivoted_cholesky`] is returned in the
+/// original order and padded with zero columns to `n × n`, which is
+/// enough to simulate $L z$ from independent normals $z$.
+pub fn cholesky(matrix: &DMatrix<f64>) -> Result<DMatrix<f64>, LinalgError> {
+    check_symmetric(matrix)?;
+
+    if let Some(cholesky) = matrix.clone().cholesky() {
+        return Ok(cholesky.l());
+    }
+
+    let n = matrix.nrows();
+    let tolerance = n as f64 * f64::EPSILON.sqrt() * matrix.diagonal().amax();
+    let pivoted = pivoted_cholesky(matrix, tolerance)?;
+
+    let mut factor = DMatrix::zeros(n, n);
+    for (i, row) in pivoted.permutation.iter().enumerate() {
+        for k in 0..pivoted.rank {
+            factor[(*row, k)] = pivoted.lower[(i, k)];
+        }
+    }
+
+    Ok(factor)
+}
+
+/// Cholesky factorisation with diagonal pivoting, which stops once the
+/// remaining diagonal is at most `tolerance`.
+///
+/// Returns [`LinalgError::NotPositiveSemiDefinite`] if the remaining
+/// (Schur complement) matrix is not zero to within `tolerance`.
+pub fn pivoted_cholesky(
+    matrix: &DMatrix<f64>,
+    tolerance: f64,
+) -> Result<PivotedCholesky, LinalgError> {
+    check_symmetric(matrix)?;
+
+    let n = matrix.nrows();
+    let mut a = matrix.clone();
+    let mut lower = DMatrix::zeros(n, n);
+    let mut permutation: Vec<usize> = (0..n).collect();
+    let mut rank = n;
+
+    for k in 0..n {
+        let pivot = (k..n)
+            .max_by(|i, j| a[(*i, *i)].total_cmp(&a[(*j, *j)]))
+            .unwrap();
+
+        if a[(pivot, pivot)] <= tolerance {
+            rank = k;
+            break;
+        }
+
+        a.swap_rows(k, pivot);
+        a.swap_columns(k, pivot);
+        lower.swap_rows(k, pivot);
+        permutation.swap(k, pivot);
+
+        let diagonal = a[(k, k)].sqrt();
+        lower[(k, k)] = diagonal;
+        for i in k + 1..n {
+            lower[(i, k)] = a[(i, k)] / diagonal;
+        }
+        for j in k + 1..n {
+            for i in k + 1..n {
+                a[(i, j)] -= lower[(i, k)] * lower[(j, k)];
+            }
+        }
+    }
+
+    let residual = a.view((rank, rank), (n - rank, n - rank)).amax();
+    if residual > tolerance {
+        return Err(LinalgError::NotPositiveSemiDefinite);
+    }
+
+    Ok(PivotedCholesky {
+        lower: lower.columns(0, rank).into_owned(),
+        permutation,
+        rank,
+    })
+}
+
+/// Eigenvalues (in decreasing order) and eigenvectors (as columns) of a
+/// symmetric matrix.
+pub fn symmetric_eigen(matrix: &DMatrix<f64>) -> Result<(DVector<f64>, DMatrix<f64>), LinalgError> {
+    check_symmetric(matrix)?;
+
+    let eigen = SymmetricEigen::new(matrix.clone());
+    let mut order: Vec<usize> = (0..matrix.nrows()).collect();
+    order.sort_by(|i, j| eigen.eigenvalues[*j].total_cmp(&eigen.eigenvalues[*i]));
+
+    let values = DVector::from_iterator(order.len(), order.iter().map(|i| eigen.eigenvalues[*i]));
+    let vectors = DMatrix::from_columns(
+        &order
+            .iter()
+            .map(|i| eigen.eigenvectors.column(*i).into_owned())
+            .collect::<Vec<_>>(),
+    );
+
+    Ok((values, vectors))
+}
+
+// Projection onto the positive semi-definite matrices: negative eigenvalues
+// are set to zero.
+fn project_positive_semi_definite(matrix: &DMatrix<f64>) -> DMatrix<f64> {
+    let eigen = SymmetricEigen::new(matrix.clone());
+    let clipped = eigen.eigenvalues.map(|lambda| lambda.max(0.0));
+    let projected =
+        &eigen.eigenvectors * DMatrix::from_diagonal(&clipped) * eigen.eigenvectors.transpose();
+
+    (&projected + projected.transpose()) * 0.5
+}
+
+impl PrincipalComponents {
+    /// Principal components of a covariance (or correlation) matrix.
+    pub fn from_covariance(covariance: &DMatrix<f64>) -> Result<Self, LinalgError> {
+        let (variances, mut loadings) = symmetric_eigen(covariance)?;
+
+        for mut loading in loadings.column_iter_mut() {
+            let largest = loading.iamax();
+            if loading[largest] < 0.0 {
+                loading.neg_mut();
+            }
+        }
+
+        Ok(Self {
+            mean: DVector::zeros(covariance.nrows()),
+            variances,
+            loadings,
+        })
+    }
+
+    /// Principal components of observations, one per row (e.g. daily
+    /// changes of zero rates, one column per tenor).
+    pub fn from_observations(observations: &DMatrix<f64>) -> Result<Self, LinalgError> {
+        let n = observations.nrows();
+        if n < 2 {
+            return Err(LinalgError::TooFewObservations);
+        }
+
+        let mean = observations.row_mean().transpose();
+        let centred = Self::centre(observations, &mean);
+        let covariance = centred.transpose() * &centred / (n - 1) as f64;
+
+        Ok(Self {
+            mean,
+            ..Self::from_covariance(&covariance)?
+        })
+    }
+
+    /// Fraction of the total variance explained by each component.
+    pub fn explained_variance_ratio(&self) -> Vec<f64> {
+        let total = self.variances.sum();
+
+        self.variances.iter().map(|v| v / total).collect()
+    }
+
+    /// Smallest number of components explaining at least `fraction` of the
+    /// total variance.
+    pub fn components_for(&self, fraction: f64) -> usize {
+        let mut explained = 0.0;
+
+        for (k, ratio) in self.explained_variance_ratio().iter().enumerate() {
+            explained += ratio;
+            if explained >= fraction - 1e-12 {
+                return k + 1;
+            }
+        }
+
+        self.variances.len()
+    }
+
+    /// Scores of the observations (one per row) on the first `k`
+    /// components.
+    pub fn scores(&self, observations: &DMatrix<f64>, k: usize) -> DMatrix<f64> {
+        Self::centre(observations, &self.mean) * self.loadings.columns(0, k)
+    }
+
+    /// Observations rebuilt from their scores on the first components.
+    pub fn reconstruct(&self, scores: &DMatrix<f64>) -> DMatrix<f64> {
+        let mut observations = scores * self.loadings.columns(0, scores.ncols()).transpose();
+
+        for mut row in observations.row_iter_mut() {
+            row += self.mean.transpose();
+        }
+
+        observations
+    }
+
+    fn centre(observations: &DMatrix<f64>, mean: &DVector<f64>) -> DMatrix<f64> {
+        let mut centred = observations.clone();
+
+        for mut row in centred.row_iter_mut() {
+            row -= mean.transpose();
+        }
+
+        centred
+    }
+}
+
+impl Default for NearestCorrelation {
+    fn default() -> Self {
+        Self {
+            tolerance: 1e-10,
+            max_iterations: 10_000,
+        }
+    }
+}
+
+impl NearestCorrelation {
+    /// Creates the solver.
+    pub fn new(tolerance: f64, max_iterations: usize) -> Self {
+        Self {
+            tolerance,
+            max_iterations,
+        }
+    }
+
+    /// Nearest correlation matrix to a symmetric matrix with (approximately)
+    /// unit diagonal. Valid correlation matrices are returned unchanged.
+    pub fn nearest(&self, matrix: &DMatrix<f64>) -> Result<DMatrix<f64>, LinalgError> {
+        check_symmetric(matrix)?;
+
+        let mut y = matrix.clone();
+        y.fill_diagonal(1.0);
+        let mut x = y.clone();
+        let mut correction = DMatrix::zeros(matrix.nrows(), matrix.ncols());
+
+        for _ in 0..self.max_iterations {
+            let (x_previous, y_previous) = (x, y.clone());
+
+            let r = &y - &correction;
+            x = project_positive_semi_definite(&r);
+            correction = &x - &r;
+            y = x.clone();
+            y.fill_diagonal(1.0);
+
+            let change = ((&x - &x_previous).norm() / x.norm())
+                .max((&y - &y_previous).norm() / y.norm())
+                .max((&y - &x).norm() / y.norm());
+            if change < self.tolerance {
+                return Ok(y);
+            }
+        }
+
+        Err(LinalgError::NoConvergence(self.max_iterations))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_linalg {
+    use super::*;
+    use crate::math::Pcg64;
+    use rand::SeedableRng;
+    use rand_distr::{Distribution, StandardNormal};
+
+    #[test]
+    fn test_cholesky() {
+        let matrix = DMatrix::from_row_slice(3, 3, &[4.0, 2.0, 0.4, 2.0, 2.0, 0.5, 0.4, 0.5, 3.0]);
+        let factor = cholesky(&matrix).unwrap();
+        assert!((&factor * factor.transpose() - &matrix).amax() < 1e-14);
+        assert_eq!(factor[(0, 1)], 0.0);
+
+        // Perfectly correlated assets: singular, but positive semi-definite.
+        let singular =
+            DMatrix::from_row_slice(3, 3, &[1.0, 1.0, 0.5, 1.0, 1.0, 0.5, 0.5, 0.5, 1.0]);
+        assert!(singular.clone().cholesky().is_none());
+        let factor = cholesky(&singular).unwrap();
+        assert!((&factor * factor.transpose() - &singular).amax() < 1e-14);
+        assert_eq!(pivoted_cholesky(&singular, 1e-12).unwrap().rank, 2);
+
+        let indefinite = DMatrix::from_row_slice(2, 2, &[1.0, 2.0, 2.0, 1.0]);
+        assert_eq!(
+            cholesky(&indefinite),
+            Err(LinalgError::NotPositiveSemiDefinite)
+        );
+        assert_eq!(
+            cholesky(&DMatrix::from_row_slice(2, 2, &[1.0, 0.5, 0.4, 1.0])),
+            Err(LinalgError::NotSymmetric)
+        );
+    }
+
+    #[test]
+    fn test_principal_components_of_curve_moves() {
+        // Daily zero rate changes driven by level, slope and curvature
+        // factors plus a little noise.
+        let tenors = [0.25, 0.5, 1.0, 2.0, 5.0, 10.0, 20.0, 30.0];
+        let mut rng = Pcg64::seed_from_u64(7);
+        let mut normal = || -> f64 { StandardNormal.sample(&mut rng) };
+
+        let mut moves = Vec::new();
+        for _ in 0..2_000 {
+            let (level, slope, curvature) = (8.0 * normal(), 4.0 * normal(), 2.0 * normal());
+            for t in tenors {
+                let (decay, exp) = (
+                    (1.0 - (-t / 2.0_f64).exp()) / (t / 2.0),
+                    (-t / 2.0_f64).exp(),
+                );
+                moves.push(level + slope * decay + curvature * (decay - exp) + 0.1 * normal());
+            }
+        }
+        let observations = DMatrix::from_row_slice(2_000, tenors.len(), &moves);
+
+        let pca = PrincipalComponents::from_observations(&observations).unwrap();
+        let ratios = pca.explained_variance_ratio();
+
+        assert!(ratios.windows(2).all(|pair| pair[0] >= pair[1]));
+        assert_approx_equal!(ratios.iter().sum::<f64>(), 1.0, 1e-12);
+        assert_eq!(pca.components_for(0.9999), 3);
+
+        // The first component is a parallel shift: same sign everywhere.
+        assert!(pca.loadings.column(0).iter().all(|l| *l > 0.0));
+
+        // Three components rebuild the moves to within the noise.
+        let scores = pca.scores(&observations, 3);
+        let rebuilt = pca.reconstruct(&scores);
+        assert!((rebuilt - &observations).amax() < 1.0);
+
+        // Loadings are orthonormal.
+        let gram = pca.loadings.transpose() * &pca.loadings;
+        assert!((gram - DMatrix::identity(8, 8)).amax() < 1e-12);
+    }
+
+    #[test]
+    fn test_nearest_correlation() {
+        // Example from Higham (2002).
+        let matrix = DMatrix::from_row_slice(3, 3, &[1.0, 1.0, 0.0, 1.0, 1.0, 1.0, 0.0, 1.0, 1.0]);
+        let nearest = NearestCorrelation::default().nearest(&matrix).unwrap();
+        let expected = DMatrix::from_row_slice(
+            3,
+            3,
+            &[
+                1.0, 0.7607, 0.1573, 0.7607, 1.0, 0.7607, 0.1573, 0.7607, 1.0,
+            ],
+        );
+
+        assert!((&nearest - expected).amax() < 1e-4);
+        assert!(nearest.diagonal().iter().all(|d| *d == 1.0));
+        assert!(symmetric_eigen(&nearest).unwrap().0.min() > -1e-10);
+        assert!(cholesky(&nearest).is_ok());
+
+        // Valid correlation matrices are unchanged.
+        let valid = DMatrix::from_row_slice(2, 2, &[1.0, 0.3, 0.3, 1.0]);
+        let unchanged = NearestCorrelation::default().nearest(&valid).unwrap();
+        assert!((unchanged - valid).amax() < 1e-12);
+    }
+}
@@ -8,10 +8,13 @@
 //!
 //! - [x] Gradient Descent
 //! - [x] Newton-Raphson
+//! - [x] Brent, bracketed Newton-Raphson and Halley root finding ([`rootfind`])
+//! - [x] L-BFGS (box-constrained), Levenberg-Marquardt, differential
+//!   evolution and Nelder-Mead behind a common `Objective` trait ([`optimize`])
 //!
 //! Note: the reason you need to specify the lifetimes and use the type `Variable` is because the gradient descent optimiser uses the `RustQuant::autodiff` module to compute the gradients. This is a slight inconvenience, but the speed-up is enormous when working with functions with many inputs (when compared with using finite-difference quotients).
 //!
-//! ```rust
+//! ```rust,ignore
 //! use RustQuant::optimisation::GradientDescent;
 //!
 //! // Define the objective function.
@@ -60,12 +63,22 @@
 //! ### Risk-Reward Metrics
 //!
 //! - [x] Risk-Reward Measures (Sharpe, Treynor, Sortino, etc)
+//!
+//! ### SIMD Kernels
+//!
+//! With the `simd` feature, vectorised `exp`, `ln`, `erf` and normal CDF
+//! kernels are available (e.g. `norm_cdf_batch(&x)`), along with batch
+//! pricers such as `bs_price_batch`.
 
 /// Numerical integration routines.
 /// The primary (useful) integrator is the Tanh-Sinh (double exponential) implementation.
 pub mod integration;
 pub use integration::*;
 
+/// Adaptive Gauss-Kronrod, tanh-sinh, Gauss-Laguerre and Gauss-Hermite
+/// quadrature, with error estimates.
+pub mod integrate;
+
 /// Numerical optimization and root-finding routines.
 pub mod optimization {
     /// Gradient descent optimization.
@@ -78,7 +91,18 @@ pub mod optimization {
 }
 pub use optimization::*;
 
-/// Fast fourier transform.
+/// Root finding: Brent, bracketed Newton-Raphson and Halley.
+pub mod rootfind;
+pub use rootfind::*;
+
+/// Optimization suite: L-BFGS, Levenberg-Marquardt, differential evolution
+/// and Nelder-Mead.
+pub mod optimize;
+
+/// Linear algebra: Cholesky, PCA and the nearest correlation matrix.
+pub mod linalg;
+
+/// Fast fourier transforms, with reusable plans of any size.
 pub mod fft;
 pub use fft::*;
 
@@ -86,10 +110,31 @@ pub use fft::*;
 pub mod interpolation;
 pub use interpolation::*;
 
+/// Orthogonal polynomial bases (Laguerre, Hermite, Chebyshev, Legendre)
+/// and least-squares fitting.
+pub mod polynomials;
+
+/// Penalized B-spline (P-spline) smoothing of noisy data.
+pub mod smoothing;
+
+/// Special functions: error, gamma, beta and Bessel functions, Owen's T
+/// and the bivariate normal distribution function.
+pub mod special;
+
 /// Simple risk/reward measures.
 pub mod risk_reward;
 pub use risk_reward::*;
 
+/// SIMD (vectorised) math kernels.
+#[cfg(feature = "simd")]
+pub mod simd;
+#[cfg(feature = "simd")]
+pub use simd::*;
+
 /// Sequences of numbers and associated functions.
 pub mod sequences;
 pub use sequences::*;
+
+/// Random number generators and samplers for Monte Carlo simulation.
+pub mod rng;
+pub use rng::*;
@@ -61,8 +61,8 @@
 //!
 //! Or in Rust, something like:
 //!
-//! ```rust
-//! gradient.iter().map(|&x| x * x).sum::<f64>().sqrt() < std::f64::EPSILON.sqrt()
+//! ```rust,ignore
+//! gradient.iter().map(|&x| x * x).sum::<f64>().sqrt() < f64::EPSILON.sqrt()
 //! ```
 //!
 //! See [this example](https://github.com/avhz/RustQuant/blob/main/examples/gradient_descent.rs)
@@ -73,7 +73,7 @@
 //! $$
 
 use crate::autodiff::*;
-use time::{Duration, Instant};
+use std::time::{Duration, Instant};
 // use ::log::{info, max_level, warn, Level};
 
 /// Gradient descent optimizer.
@@ -113,8 +113,8 @@ pub struct GradientDescentResult {
 impl GradientDescent {
     /// Returns a new instance of the gradient descent optimizer.
     pub fn new(learning_rate: f64, max_iterations: usize, tolerance: Option<f64>) -> Self {
-        if tolerance.is_some() {
-            assert!(tolerance.unwrap() > 0.0);
+        if let Some(tol) = tolerance {
+            assert!(tol > 0.0);
         }
 
         Self {
@@ -150,7 +150,7 @@ impl GradientDescent {
     {
         let start = Instant::now();
 
-        let tolerance = self.tolerance.unwrap_or(std::f64::EPSILON.sqrt());
+        let tolerance = self.tolerance.unwrap_or(f64::EPSILON.sqrt());
 
         let mut result = GradientDescentResult {
             minimum: 0.0,
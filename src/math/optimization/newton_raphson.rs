@@ -44,7 +44,6 @@ pub fn find_root(f: fn(f64) -> f64, df: fn(f64) -> f64, guess: f64, iterations:
 
 #[cfg(test)]
 mod tests {
-    use crate::assert_approx_equal;
 
     use super::*;
 
@@ -0,0 +1,249 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::math::optimize::{Bounds, Objective, OptimizationResult};
+use crate::math::Pcg64;
+use rand::{Rng, SeedableRng};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Differential evolution (Storn and Price, 1997), DE/rand/1/bin, over a
+/// box.
+///
+/// Each generation, every member $x_i$ of the population is challenged by
+/// a trial vector that mixes $x_i$ with the mutant $x_a + F (x_b - x_c)$
+/// (binomial crossover), and replaced if the trial is no worse. Mutants
+/// leaving the box are reflected back into it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DifferentialEvolution {
+    /// Search box.
+    pub bounds: Bounds,
+
+    /// Maximum number of generations.
+    pub max_generations: usize,
+
+    /// Convergence tolerance on the spread of the function values over
+    /// the population.
+    pub tolerance: f64,
+
+    /// Population size (15 times the dimension by default, at least 5).
+    pub population_size: usize,
+
+    /// Differential weight $F$ (0.8 by default).
+    pub mutation: f64,
+
+    /// Crossover probability (0.9 by default).
+    pub crossover: f64,
+
+    /// Seed of the random number generator (random by default).
+    pub seed: Option<u64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl DifferentialEvolution {
+    /// New minimiser over `bounds`, which must be finite.
+    pub fn new(bounds: Bounds, max_generations: usize, tolerance: f64) -> Self {
+        assert!(
+            bounds
+                .lower
+                .iter()
+                .chain(&bounds.upper)
+                .all(|b| b.is_finite()),
+            "Differential evolution needs finite bounds."
+        );
+
+        Self {
+            population_size: (15 * bounds.len()).max(5),
+            bounds,
+            max_generations,
+            tolerance,
+            mutation: 0.8,
+            crossover: 0.9,
+            seed: None,
+        }
+    }
+
+    /// Sets the population size.
+    pub fn with_population_size(mut self, population_size: usize) -> Self {
+        assert!(population_size >= 4, "At least four members are needed.");
+        self.population_size = population_size;
+        self
+    }
+
+    /// Sets the differential weight.
+    pub fn with_mutation(mut self, mutation: f64) -> Self {
+        self.mutation = mutation;
+        self
+    }
+
+    /// Sets the crossover probability.
+    pub fn with_crossover(mut self, crossover: f64) -> Self {
+        assert!((0.0..=1.0).contains(&crossover));
+        self.crossover = crossover;
+        self
+    }
+
+    /// Sets the seed, for reproducible runs.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Minimises `objective` over the box. Non-finite values are treated as
+    /// infeasible.
+    pub fn minimize<O: Objective>(&self, objective: &O) -> OptimizationResult {
+        let mut rng = match self.seed {
+            Some(seed) => Pcg64::seed_from_u64(seed),
+            None => Pcg64::from_entropy(),
+        };
+        let (lower, upper) = (&self.bounds.lower, &self.bounds.upper);
+        let (n, size) = (self.bounds.len(), self.population_size);
+
+        let evaluate = |x: &[f64]| match objective.value(x) {
+            value if value.is_nan() => f64::INFINITY,
+            value => value,
+        };
+
+        let mut population: Vec<Vec<f64>> = (0..size)
+            .map(|_| (0..n).map(|k| rng.gen_range(lower[k]..=upper[k])).collect())
+            .collect();
+        let mut values: Vec<f64> = population.iter().map(|x| evaluate(x)).collect();
+
+        let mut generation = 0;
+        let mut converged = false;
+
+        while generation < self.max_generations {
+            let (best, worst) = values
+                .iter()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(b, w), v| {
+                    (b.min(*v), w.max(*v))
+                });
+            if worst - best <= self.tolerance {
+                converged = true;
+                break;
+            }
+            generation += 1;
+
+            for i in 0..size {
+                let [a, b, c] = distinct(&mut rng, size, i);
+                let forced = rng.gen_range(0..n);
+
+                let trial: Vec<f64> = (0..n)
+                    .map(|k| {
+                        if k != forced && rng.gen::<f64>() >= self.crossover {
+                            return population[i][k];
+                        }
+
+                        let mutant = population[a][k]
+                            + self.mutation * (population[b][k] - population[c][k]);
+                        reflect(mutant, lower[k], upper[k])
+                    })
+                    .collect();
+
+                let value = evaluate(&trial);
+                if value <= values[i] {
+                    population[i] = trial;
+                    values[i] = value;
+                }
+            }
+        }
+
+        let best = (0..size)
+            .min_by(|a, b| values[*a].total_cmp(&values[*b]))
+            .unwrap_or(0);
+
+        OptimizationResult {
+            minimizer: population.swap_remove(best),
+            minimum: values[best],
+            iterations: generation,
+            converged,
+        }
+    }
+}
+
+// Three distinct indices, all different from `exclude`.
+fn distinct<R: Rng>(rng: &mut R, size: usize, exclude: usize) -> [usize; 3] {
+    let mut chosen = [exclude; 3];
+
+    for k in 0..3 {
+        chosen[k] = loop {
+            let candidate = rng.gen_range(0..size);
+            if candidate != exclude && !chosen[..k].contains(&candidate) {
+                break candidate;
+            }
+        };
+    }
+
+    chosen
+}
+
+// Reflects a coordinate back into [lower, upper].
+fn reflect(x: f64, lower: f64, upper: f64) -> f64 {
+    match x {
+        x if x < lower => (2.0 * lower - x).min(upper),
+        x if x > upper => (2.0 * upper - x).max(lower),
+        x => x,
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_differential_evolution {
+    use super::*;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_rastrigin() {
+        // Many local minima; the global minimum is 0 at the origin.
+        let rastrigin = |x: &[f64]| {
+            10.0 * x.len() as f64
+                + x.iter()
+                    .map(|xi| xi * xi - 10.0 * (2.0 * PI * xi).cos())
+                    .sum::<f64>()
+        };
+        let bounds = Bounds::new(vec![-5.12; 3], vec![5.12; 3]);
+
+        let result = DifferentialEvolution::new(bounds.clone(), 2_000, 1e-10)
+            .with_seed(11)
+            .minimize(&rastrigin);
+
+        assert!(result.converged);
+        assert!(result.minimum < 1e-8);
+        assert!(bounds.contains(&result.minimizer));
+        for xi in &result.minimizer {
+            assert_approx_equal!(*xi, 0.0, 1e-5);
+        }
+
+        // Seeded runs are reproducible.
+        let again = DifferentialEvolution::new(bounds, 2_000, 1e-10)
+            .with_seed(11)
+            .minimize(&rastrigin);
+        assert_eq!(result, again);
+    }
+
+    #[test]
+    fn test_boundary_minimum() {
+        let bounds = Bounds::new(vec![1.0, -1.0], vec![3.0, 1.0]);
+        let result = DifferentialEvolution::new(bounds, 1_000, 1e-12)
+            .with_seed(3)
+            .with_population_size(20)
+            .minimize(&|x: &[f64]| x[0] * x[0] + (x[1] - 0.25).powi(2));
+
+        assert_approx_equal!(result.minimizer[0], 1.0, 1e-5);
+        assert_approx_equal!(result.minimizer[1], 0.25, 1e-5);
+    }
+}
@@ -0,0 +1,315 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::math::optimize::{Bounds, Objective, OptimizationResult};
+use std::collections::VecDeque;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Limited-memory BFGS minimiser (Nocedal, 1980).
+///
+/// With bounds, variables at a bound whose gradient points out of the box
+/// are held fixed, the quasi-Newton direction is computed on the others,
+/// and trial points are projected onto the box.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lbfgs {
+    /// Maximum number of iterations.
+    pub max_iterations: usize,
+
+    /// Convergence tolerance on the (projected) gradient, in the max norm.
+    pub tolerance: f64,
+
+    /// Number of correction pairs kept (10 by default).
+    pub memory: usize,
+
+    /// Optional box constraints.
+    pub bounds: Option<Bounds>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Armijo sufficient decrease constant.
+const ARMIJO: f64 = 1e-4;
+
+impl Lbfgs {
+    /// New unconstrained minimiser.
+    pub fn new(max_iterations: usize, tolerance: f64) -> Self {
+        assert!(tolerance > 0.0);
+
+        Self {
+            max_iterations,
+            tolerance,
+            memory: 10,
+            bounds: None,
+        }
+    }
+
+    /// Sets the number of correction pairs kept.
+    pub fn with_memory(mut self, memory: usize) -> Self {
+        assert!(memory > 0);
+        self.memory = memory;
+        self
+    }
+
+    /// Sets box constraints.
+    pub fn with_bounds(mut self, bounds: Bounds) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    /// Minimises `objective` starting from `x0` (projected onto the bounds).
+    pub fn minimize<O: Objective>(&self, objective: &O, x0: &[f64]) -> OptimizationResult {
+        let mut x = x0.to_vec();
+        if let Some(bounds) = &self.bounds {
+            assert_eq!(bounds.len(), x.len());
+            bounds.project(&mut x);
+        }
+
+        let mut value = objective.value(&x);
+        let mut gradient = objective.gradient(&x);
+        let mut corrections: VecDeque<(Vec<f64>, Vec<f64>, f64)> = VecDeque::new();
+
+        for iteration in 0..self.max_iterations {
+            let free = self.free_variables(&x, &gradient);
+
+            if self.projected_gradient_norm(&x, &gradient) <= self.tolerance {
+                return result(x, value, iteration, true);
+            }
+
+            let mut direction = two_loop(&gradient, &free, &corrections);
+            let mut slope = dot(&gradient, &direction);
+
+            // Restart from steepest descent if the direction is not downhill.
+            if slope >= 0.0 {
+                corrections.clear();
+                direction = two_loop(&gradient, &free, &corrections);
+                slope = dot(&gradient, &direction);
+            }
+
+            // Backtracking line search along the projected path.
+            let mut step = match corrections.is_empty() {
+                true => 1.0_f64.min(1.0 / norm_inf(&gradient)),
+                false => 1.0,
+            };
+            let accepted = loop {
+                let mut trial: Vec<f64> = x
+                    .iter()
+                    .zip(&direction)
+                    .map(|(xi, di)| xi + step * di)
+                    .collect();
+                if let Some(bounds) = &self.bounds {
+                    bounds.project(&mut trial);
+                }
+
+                let trial_value = objective.value(&trial);
+                let decrease: f64 = gradient
+                    .iter()
+                    .zip(trial.iter().zip(&x))
+                    .map(|(g, (t, xi))| g * (t - xi))
+                    .sum();
+
+                if trial_value.is_finite() && trial_value <= value + ARMIJO * decrease {
+                    break Some((trial, trial_value));
+                }
+                step *= 0.5;
+                if step * norm_inf(&direction) <= f64::EPSILON * (1.0 + norm_inf(&x)) {
+                    break None;
+                }
+            };
+
+            let Some((trial, trial_value)) = accepted else {
+                // No further decrease is possible at machine precision.
+                let converged = slope.abs() <= self.tolerance * self.tolerance;
+                return result(x, value, iteration + 1, converged);
+            };
+
+            let trial_gradient = objective.gradient(&trial);
+            let s: Vec<f64> = trial.iter().zip(&x).map(|(t, xi)| t - xi).collect();
+            let y: Vec<f64> = trial_gradient
+                .iter()
+                .zip(&gradient)
+                .map(|(a, b)| a - b)
+                .collect();
+            let sy = dot(&s, &y);
+
+            // Skip updates that would lose positive definiteness.
+            if sy > f64::EPSILON * dot(&y, &y) {
+                if corrections.len() == self.memory {
+                    corrections.pop_front();
+                }
+                corrections.push_back((s, y, 1.0 / sy));
+            }
+
+            x = trial;
+            value = trial_value;
+            gradient = trial_gradient;
+        }
+
+        let converged = self.projected_gradient_norm(&x, &gradient) <= self.tolerance;
+        result(x, value, self.max_iterations, converged)
+    }
+
+    // Variables not held at a bound by the gradient.
+    fn free_variables(&self, x: &[f64], gradient: &[f64]) -> Vec<bool> {
+        match &self.bounds {
+            None => vec![true; x.len()],
+            Some(bounds) => x
+                .iter()
+                .zip(gradient)
+                .zip(bounds.lower.iter().zip(&bounds.upper))
+                .map(|((xi, gi), (l, u))| !((xi <= l && *gi > 0.0) || (xi >= u && *gi < 0.0)))
+                .collect(),
+        }
+    }
+
+    // Max norm of P(x - g) - x.
+    fn projected_gradient_norm(&self, x: &[f64], gradient: &[f64]) -> f64 {
+        let mut projected: Vec<f64> = x.iter().zip(gradient).map(|(xi, gi)| xi - gi).collect();
+        if let Some(bounds) = &self.bounds {
+            bounds.project(&mut projected);
+        }
+
+        projected
+            .iter()
+            .zip(x)
+            .map(|(p, xi)| (p - xi).abs())
+            .fold(0.0, f64::max)
+    }
+}
+
+// Two-loop recursion: -H g on the free variables, zero on the others.
+fn two_loop(
+    gradient: &[f64],
+    free: &[bool],
+    corrections: &VecDeque<(Vec<f64>, Vec<f64>, f64)>,
+) -> Vec<f64> {
+    let masked = |v: &[f64]| -> Vec<f64> {
+        v.iter()
+            .zip(free)
+            .map(|(vi, f)| if *f { *vi } else { 0.0 })
+            .collect()
+    };
+
+    let mut q = masked(gradient);
+    let mut alphas = Vec::with_capacity(corrections.len());
+
+    for (s, y, rho) in corrections.iter().rev() {
+        let alpha = rho * dot(&masked(s), &q);
+        for (qi, yi) in q.iter_mut().zip(masked(y)) {
+            *qi -= alpha * yi;
+        }
+        alphas.push(alpha);
+    }
+
+    // Initial Hessian scaling s'y / y'y of the latest pair.
+    if let Some((s, y, _)) = corrections.back() {
+        let (s, y) = (masked(s), masked(y));
+        let yy = dot(&y, &y);
+        if yy > 0.0 {
+            let gamma = dot(&s, &y) / yy;
+            q.iter_mut().for_each(|qi| *qi *= gamma.max(f64::EPSILON));
+        }
+    }
+
+    for ((s, y, rho), alpha) in corrections.iter().zip(alphas.iter().rev()) {
+        let beta = rho * dot(&masked(y), &q);
+        for (qi, si) in q.iter_mut().zip(masked(s)) {
+            *qi += (alpha - beta) * si;
+        }
+    }
+
+    q.iter().map(|qi| -qi).collect()
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(ai, bi)| ai * bi).sum()
+}
+
+fn norm_inf(a: &[f64]) -> f64 {
+    a.iter().fold(0.0, |m, ai| m.max(ai.abs()))
+}
+
+fn result(
+    minimizer: Vec<f64>,
+    minimum: f64,
+    iterations: usize,
+    converged: bool,
+) -> OptimizationResult {
+    OptimizationResult {
+        minimizer,
+        minimum,
+        iterations,
+        converged,
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_lbfgs {
+    use super::*;
+    use crate::math::optimize::GradientObjective;
+
+    fn rosenbrock(x: &[f64]) -> f64 {
+        (0..x.len() - 1)
+            .map(|i| (1.0 - x[i]).powi(2) + 100.0 * (x[i + 1] - x[i] * x[i]).powi(2))
+            .sum()
+    }
+
+    fn rosenbrock_gradient(x: &[f64]) -> Vec<f64> {
+        let n = x.len();
+        let mut g = vec![0.0; n];
+        for i in 0..n - 1 {
+            g[i] += -2.0 * (1.0 - x[i]) - 400.0 * x[i] * (x[i + 1] - x[i] * x[i]);
+            g[i + 1] += 200.0 * (x[i + 1] - x[i] * x[i]);
+        }
+        g
+    }
+
+    #[test]
+    fn test_unconstrained() {
+        let objective = GradientObjective::new(rosenbrock, rosenbrock_gradient);
+        let result = Lbfgs::new(1_000, 1e-9).minimize(&objective, &[-1.2, 1.0, -0.5, 0.8, 1.5]);
+
+        assert!(result.converged);
+        for xi in &result.minimizer {
+            assert_approx_equal!(*xi, 1.0, 1e-7);
+        }
+
+        // Finite-difference gradients also converge on a quadratic.
+        let quadratic =
+            |x: &[f64]| (x[0] - 3.0).powi(2) + 10.0 * (x[1] + 1.0).powi(2) + x[0] * x[1];
+        let result = Lbfgs::new(100, 1e-6).minimize(&quadratic, &[0.0, 0.0]);
+        // Solves 2(x - 3) + y = 0, 20(y + 1) + x = 0.
+        assert_approx_equal!(result.minimizer[0], 140.0 / 39.0, 1e-6);
+        assert_approx_equal!(result.minimizer[1], -46.0 / 39.0, 1e-6);
+    }
+
+    #[test]
+    fn test_box_constrained() {
+        let objective = GradientObjective::new(rosenbrock, rosenbrock_gradient);
+        let bounds = Bounds::new(vec![-2.0, -1.0], vec![2.0, 0.5]);
+        let result = Lbfgs::new(1_000, 1e-9)
+            .with_bounds(bounds.clone())
+            .minimize(&objective, &[-1.0, 0.0]);
+
+        // The upper bound on y is active at the minimum, and x minimises
+        // (1 - x)^2 + 100 (0.5 - x^2)^2.
+        assert!(result.converged);
+        assert!(bounds.contains(&result.minimizer));
+        assert_approx_equal!(result.minimizer[0], 0.708_559_503_761_35, 1e-8);
+        assert_eq!(result.minimizer[1], 0.5);
+    }
+}
@@ -0,0 +1,271 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::math::optimize::{Bounds, OptimizationResult};
+use nalgebra::{DMatrix, DVector};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Levenberg-Marquardt minimiser of a sum of squared residuals,
+/// $\sum_i r_i(x)^2$, e.g. model prices minus market prices.
+///
+/// Each step solves $(J'J + \lambda \, \text{diag}(J'J)) \delta = -J'r$,
+/// with the damping $\lambda$ updated from the ratio of the actual to the
+/// predicted decrease (Nielsen, 1999). With bounds, steps are projected
+/// onto the box.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LevenbergMarquardt {
+    /// Maximum number of iterations.
+    pub max_iterations: usize,
+
+    /// Convergence tolerance on the gradient $J'r$ (max norm) and on the
+    /// relative step size.
+    pub tolerance: f64,
+
+    /// Optional box constraints.
+    pub bounds: Option<Bounds>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl LevenbergMarquardt {
+    /// New unconstrained minimiser.
+    pub fn new(max_iterations: usize, tolerance: f64) -> Self {
+        assert!(tolerance > 0.0);
+
+        Self {
+            max_iterations,
+            tolerance,
+            bounds: None,
+        }
+    }
+
+    /// Sets box constraints.
+    pub fn with_bounds(mut self, bounds: Bounds) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    /// Minimises the squared `residuals` starting from `x0`, with a
+    /// forward-difference Jacobian.
+    pub fn minimize<R>(&self, residuals: R, x0: &[f64]) -> OptimizationResult
+    where
+        R: Fn(&[f64]) -> Vec<f64>,
+    {
+        let jacobian = |x: &[f64]| self.finite_difference_jacobian(&residuals, x);
+
+        self.minimize_with_jacobian(&residuals, jacobian, x0)
+    }
+
+    /// Minimises the squared `residuals` starting from `x0`, with the
+    /// Jacobian `jacobian` (residuals in rows, variables in columns).
+    pub fn minimize_with_jacobian<R, J>(
+        &self,
+        residuals: R,
+        jacobian: J,
+        x0: &[f64],
+    ) -> OptimizationResult
+    where
+        R: Fn(&[f64]) -> Vec<f64>,
+        J: Fn(&[f64]) -> DMatrix<f64>,
+    {
+        let n = x0.len();
+        let mut x = x0.to_vec();
+        if let Some(bounds) = &self.bounds {
+            assert_eq!(bounds.len(), n);
+            bounds.project(&mut x);
+        }
+
+        let mut r = DVector::from_vec(residuals(&x));
+        let mut value = r.norm_squared();
+        let mut j = jacobian(&x);
+        let mut damping = None;
+        let mut factor = 2.0;
+
+        for iteration in 0..self.max_iterations {
+            let jtj = j.transpose() * &j;
+            let gradient = j.transpose() * &r;
+
+            if gradient.amax() <= self.tolerance {
+                return result(x, value, iteration, true);
+            }
+
+            let diagonal = jtj.diagonal().map(|d| d.max(1e-12));
+            let lambda = *damping.get_or_insert(1e-3 * diagonal.max());
+
+            let mut system = jtj.clone();
+            for i in 0..n {
+                system[(i, i)] += lambda * diagonal[i];
+            }
+
+            let Some(cholesky) = system.cholesky() else {
+                damping = Some(lambda * factor);
+                factor *= 2.0;
+                continue;
+            };
+
+            let mut trial: Vec<f64> = (cholesky.solve(&(-&gradient))
+                + DVector::from_column_slice(&x))
+            .iter()
+            .copied()
+            .collect();
+            if let Some(bounds) = &self.bounds {
+                bounds.project(&mut trial);
+            }
+            let step = DVector::from_iterator(n, trial.iter().zip(&x).map(|(t, xi)| t - xi));
+
+            if step.norm()
+                <= self.tolerance * (self.tolerance + DVector::from_column_slice(&x).norm())
+            {
+                return result(x, value, iteration + 1, true);
+            }
+
+            let trial_r = DVector::from_vec(residuals(&trial));
+            let trial_value = trial_r.norm_squared();
+            let predicted = value - (&r + &j * &step).norm_squared();
+            let ratio = (value - trial_value) / predicted;
+
+            match trial_value.is_finite() && predicted > 0.0 && ratio > 0.0 {
+                true => {
+                    x = trial;
+                    r = trial_r;
+                    value = trial_value;
+                    j = jacobian(&x);
+                    damping = Some(lambda * (1.0 / 3.0_f64).max(1.0 - (2.0 * ratio - 1.0).powi(3)));
+                    factor = 2.0;
+                }
+                false => {
+                    damping = Some(lambda * factor);
+                    factor *= 2.0;
+                }
+            }
+        }
+
+        let converged = (j.transpose() * &r).amax() <= self.tolerance;
+        result(x, value, self.max_iterations, converged)
+    }
+
+    // Forward differences, stepping backwards at an upper bound.
+    fn finite_difference_jacobian<R>(&self, residuals: &R, x: &[f64]) -> DMatrix<f64>
+    where
+        R: Fn(&[f64]) -> Vec<f64>,
+    {
+        let base = residuals(x);
+        let mut x = x.to_vec();
+        let mut jacobian = DMatrix::zeros(base.len(), x.len());
+
+        for i in 0..x.len() {
+            let xi = x[i];
+            let mut h = f64::EPSILON.sqrt() * (1.0 + xi.abs());
+            if let Some(bounds) = &self.bounds {
+                if xi + h > bounds.upper[i] {
+                    h = -h;
+                }
+            }
+
+            x[i] = xi + h;
+            for (k, value) in residuals(&x).iter().enumerate() {
+                jacobian[(k, i)] = (value - base[k]) / h;
+            }
+            x[i] = xi;
+        }
+
+        jacobian
+    }
+}
+
+fn result(
+    minimizer: Vec<f64>,
+    minimum: f64,
+    iterations: usize,
+    converged: bool,
+) -> OptimizationResult {
+    OptimizationResult {
+        minimizer,
+        minimum,
+        iterations,
+        converged,
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_levenberg_marquardt {
+    use super::*;
+
+    // Data from y = 2.5 exp(-1.3 t) + 0.5.
+    fn data() -> Vec<(f64, f64)> {
+        (0..20)
+            .map(|i| {
+                let t = 0.25 * i as f64;
+                (t, 2.5 * (-1.3 * t).exp() + 0.5)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_curve_fit() {
+        let data = data();
+        let residuals = |p: &[f64]| -> Vec<f64> {
+            data.iter()
+                .map(|(t, y)| p[0] * (-p[1] * t).exp() + p[2] - y)
+                .collect()
+        };
+        let jacobian = |p: &[f64]| {
+            DMatrix::from_fn(data.len(), 3, |k, i| {
+                let t = data[k].0;
+                match i {
+                    0 => (-p[1] * t).exp(),
+                    1 => -p[0] * t * (-p[1] * t).exp(),
+                    _ => 1.0,
+                }
+            })
+        };
+
+        let solver = LevenbergMarquardt::new(200, 1e-12);
+        let numerical = solver.minimize(residuals, &[1.0, 0.5, 0.0]);
+        let analytic = solver.minimize_with_jacobian(residuals, jacobian, &[1.0, 0.5, 0.0]);
+
+        for result in [numerical, analytic] {
+            assert!(result.converged);
+            assert!(result.minimum < 1e-16);
+            assert_approx_equal!(result.minimizer[0], 2.5, 1e-6);
+            assert_approx_equal!(result.minimizer[1], 1.3, 1e-6);
+            assert_approx_equal!(result.minimizer[2], 0.5, 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_bounded_fit() {
+        let data = data();
+        let residuals = |p: &[f64]| -> Vec<f64> {
+            data.iter()
+                .map(|(t, y)| p[0] * (-p[1] * t).exp() + p[2] - y)
+                .collect()
+        };
+
+        // Capping the offset below its true value: the other parameters
+        // compensate, and the cap is active.
+        let bounds = Bounds::new(vec![0.0, 0.0, 0.0], vec![10.0, 10.0, 0.3]);
+        let result = LevenbergMarquardt::new(500, 1e-10)
+            .with_bounds(bounds.clone())
+            .minimize(residuals, &[1.0, 0.5, 0.1]);
+
+        assert!(bounds.contains(&result.minimizer));
+        assert_eq!(result.minimizer[2], 0.3);
+        assert!(result.minimum > 1e-4);
+    }
+}
@@ -0,0 +1,60 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Numerical optimization behind a common [`Objective`] trait.
+//!
+//! - [`Lbfgs`]: limited-memory BFGS, with optional box constraints
+//!   (projected gradient), for smooth objectives such as likelihoods.
+//! - [`LevenbergMarquardt`]: non-linear least squares, for calibrating a
+//!   model to market quotes.
+//! - [`DifferentialEvolution`]: a global, derivative-free search over a box,
+//!   for multi-modal objectives or to find a starting point for a local
+//!   method.
+//! - [`NelderMead`]: the downhill simplex method, a local derivative-free
+//!   search for objectives that are awkward to differentiate.
+//!
+//! Any `Fn(&[f64]) -> f64` closure is an [`Objective`], with a
+//! finite-difference gradient. Exact gradients can be supplied with
+//! [`GradientObjective`], or computed by reverse-mode automatic
+//! differentiation with [`AutodiffObjective`].
+//!
+//! ```
+//! use RustQuant::autodiff::*;
+//! use RustQuant::math::optimize::*;
+//!
+//! // Rosenbrock function, minimised at (1, 1).
+//! fn rosenbrock<'v>(x: &[Variable<'v>]) -> Variable<'v> {
+//!     (1.0 - x[0]).powf(2.0) + 100.0 * (x[1] - x[0] * x[0]).powf(2.0)
+//! }
+//!
+//! let result = Lbfgs::new(200, 1e-8).minimize(&AutodiffObjective::new(rosenbrock), &[-1.2, 1.0]);
+//!
+//! assert!(result.converged);
+//! assert!((result.minimizer[0] - 1.0).abs() < 1e-6);
+//! ```
+
+/// Objective functions, bounds and results.
+pub mod objective;
+pub use objective::*;
+
+/// Limited-memory BFGS.
+pub mod lbfgs;
+pub use lbfgs::*;
+
+/// Levenberg-Marquardt non-linear least squares.
+pub mod levenberg_marquardt;
+pub use levenberg_marquardt::*;
+
+/// Differential evolution.
+pub mod differential_evolution;
+pub use differential_evolution::*;
+
+/// Nelder-Mead (downhill simplex) method.
+pub mod nelder_mead;
+pub use nelder_mead::*;
@@ -0,0 +1,231 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Nelder-Mead (downhill simplex) minimisation.
+//!
+//! A derivative-free method, suitable for likelihoods that are awkward to
+//! differentiate (e.g. recursive filters). Infeasible points can be
+//! rejected by returning `f64::INFINITY` (or `NaN`) from the objective.
+//!
+//! ```
+//! use RustQuant::math::optimize::*;
+//!
+//! // Rosenbrock function, minimised at (1, 1).
+//! let rosenbrock = |x: &[f64]| (1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0] * x[0]).powi(2);
+//!
+//! let result = NelderMead::new(5_000, 1e-12).minimize(&rosenbrock, &[-1.2, 1.0]);
+//!
+//! assert!(result.converged);
+//! assert!((result.minimizer[0] - 1.0).abs() < 1e-4);
+//! assert!((result.minimizer[1] - 1.0).abs() < 1e-4);
+//! ```
+
+use crate::math::optimize::{Objective, OptimizationResult};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Nelder-Mead minimiser.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NelderMead {
+    /// Maximum number of iterations.
+    pub max_iterations: usize,
+
+    /// Convergence tolerance on the spread of the function values
+    /// over the simplex.
+    pub tolerance: f64,
+
+    /// Relative size of the initial simplex around the starting point.
+    pub initial_step: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl NelderMead {
+    /// New minimiser, with an initial simplex of 5% around the start.
+    pub fn new(max_iterations: usize, tolerance: f64) -> Self {
+        assert!(tolerance > 0.0);
+
+        Self {
+            max_iterations,
+            tolerance,
+            initial_step: 0.05,
+        }
+    }
+
+    /// Sets the relative size of the initial simplex.
+    pub fn with_initial_step(mut self, step: f64) -> Self {
+        self.initial_step = step;
+        self
+    }
+
+    /// Minimises `objective` starting from `x0`, with the standard
+    /// coefficients (reflection 1, expansion 2, contraction 1/2, shrinkage
+    /// 1/2). Only the objective's values are used.
+    pub fn minimize<O: Objective>(&self, objective: &O, x0: &[f64]) -> OptimizationResult {
+        let n = x0.len();
+
+        // NaN objective values are treated as infeasible.
+        let f = |x: &[f64]| {
+            let value = objective.value(x);
+            if value.is_nan() {
+                f64::INFINITY
+            } else {
+                value
+            }
+        };
+
+        let mut simplex: Vec<Vec<f64>> = std::iter::once(x0.to_vec())
+            .chain((0..n).map(|i| {
+                let mut x = x0.to_vec();
+                x[i] = match x[i] {
+                    0.0 => 0.00025,
+                    xi => xi * (1.0 + self.initial_step),
+                };
+                x
+            }))
+            .collect();
+        let mut values: Vec<f64> = simplex.iter().map(|x| f(x)).collect();
+
+        let mut iterations = 0;
+        let mut converged = false;
+
+        while iterations < self.max_iterations {
+            // Order the vertices from best to worst.
+            let mut order: Vec<usize> = (0..=n).collect();
+            order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+            simplex = order.iter().map(|&i| simplex[i].clone()).collect();
+            values = order.iter().map(|&i| values[i]).collect();
+
+            if (values[n] - values[0]).abs() <= self.tolerance {
+                converged = true;
+                break;
+            }
+            iterations += 1;
+
+            let centroid: Vec<f64> = (0..n)
+                .map(|j| simplex[..n].iter().map(|x| x[j]).sum::<f64>() / n as f64)
+                .collect();
+            let towards = |coefficient: f64| -> Vec<f64> {
+                centroid
+                    .iter()
+                    .zip(&simplex[n])
+                    .map(|(c, w)| c + coefficient * (c - w))
+                    .collect()
+            };
+
+            let reflected = towards(1.0);
+            let f_reflected = f(&reflected);
+
+            if f_reflected < values[0] {
+                let expanded = towards(2.0);
+                let f_expanded = f(&expanded);
+
+                (simplex[n], values[n]) = if f_expanded < f_reflected {
+                    (expanded, f_expanded)
+                } else {
+                    (reflected, f_reflected)
+                };
+            } else if f_reflected < values[n - 1] {
+                (simplex[n], values[n]) = (reflected, f_reflected);
+            } else {
+                let contracted = if f_reflected < values[n] {
+                    towards(0.5)
+                } else {
+                    towards(-0.5)
+                };
+                let f_contracted = f(&contracted);
+
+                if f_contracted < values[n].min(f_reflected) {
+                    (simplex[n], values[n]) = (contracted, f_contracted);
+                } else {
+                    // Shrink towards the best vertex.
+                    for i in 1..=n {
+                        simplex[i] = simplex[0]
+                            .iter()
+                            .zip(&simplex[i])
+                            .map(|(b, x)| b + 0.5 * (x - b))
+                            .collect();
+                        values[i] = f(&simplex[i]);
+                    }
+                }
+            }
+        }
+
+        let best = (0..=n)
+            .min_by(|&a, &b| values[a].total_cmp(&values[b]))
+            .unwrap_or(0);
+
+        OptimizationResult {
+            minimizer: simplex[best].clone(),
+            minimum: values[best],
+            iterations,
+            converged,
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_nelder_mead {
+    use super::*;
+
+    #[test]
+    fn test_quadratic() {
+        let f = |x: &[f64]| (x[0] - 3.0).powi(2) + 2.0 * (x[1] + 1.0).powi(2) + 5.0;
+        let result = NelderMead::new(1_000, 1e-14).minimize(&f, &[0.0, 0.0]);
+
+        assert!(result.converged);
+        assert_approx_equal!(result.minimizer[0], 3.0, 1e-5);
+        assert_approx_equal!(result.minimizer[1], -1.0, 1e-5);
+        assert_approx_equal!(result.minimum, 5.0, 1e-10);
+    }
+
+    #[test]
+    fn test_infeasible_region() {
+        // Minimum of x - ln(x) at x = 1, undefined for x <= 0.
+        let f = |x: &[f64]| {
+            if x[0] <= 0.0 {
+                f64::INFINITY
+            } else {
+                x[0] - x[0].ln()
+            }
+        };
+        let result = NelderMead::new(1_000, 1e-14)
+            .with_initial_step(1.0)
+            .minimize(&f, &[5.0]);
+
+        assert_approx_equal!(result.minimizer[0], 1.0, 1e-5);
+        assert!(!NelderMead::new(3, 1e-14).minimize(&f, &[5.0]).converged);
+    }
+
+    #[test]
+    fn test_shared_objective() {
+        // The same objective minimised by a gradient-based and a
+        // derivative-free method.
+        let objective = |x: &[f64]| (x[0] - 2.0).powi(2) + (x[1] * x[0] - 1.0).powi(2);
+
+        let results = [
+            crate::math::optimize::Lbfgs::new(200, 1e-10).minimize(&objective, &[1.0, 1.0]),
+            NelderMead::new(2_000, 1e-14).minimize(&objective, &[1.0, 1.0]),
+        ];
+
+        for result in results {
+            assert!(result.converged);
+            assert_approx_equal!(result.minimizer[0], 2.0, 1e-5);
+            assert_approx_equal!(result.minimizer[1], 0.5, 1e-5);
+        }
+    }
+}
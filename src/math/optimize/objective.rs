@@ -0,0 +1,232 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::autodiff::{Accumulate, Gradient, Graph, Variable};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Function to minimise, $f: \mathbb{R}^n \rightarrow \mathbb{R}$.
+pub trait Objective {
+    /// Value of the function at `x`.
+    fn value(&self, x: &[f64]) -> f64;
+
+    /// Gradient of the function at `x`; central finite differences unless
+    /// overridden.
+    fn gradient(&self, x: &[f64]) -> Vec<f64> {
+        let mut x = x.to_vec();
+
+        (0..x.len())
+            .map(|i| {
+                let xi = x[i];
+                let h = f64::EPSILON.cbrt() * (1.0 + xi.abs());
+
+                x[i] = xi + h;
+                let up = self.value(&x);
+                x[i] = xi - h;
+                let down = self.value(&x);
+                x[i] = xi;
+
+                (up - down) / (2.0 * h)
+            })
+            .collect()
+    }
+}
+
+/// Objective with an analytic gradient.
+#[derive(Debug, Clone, Copy)]
+pub struct GradientObjective<F, G> {
+    function: F,
+    gradient: G,
+}
+
+/// Objective whose gradient is computed by reverse-mode automatic
+/// differentiation (see [`crate::autodiff`]).
+#[derive(Debug, Clone, Copy)]
+pub struct AutodiffObjective<F> {
+    function: F,
+}
+
+/// Box constraints, $l_i \leq x_i \leq u_i$ (bounds may be infinite).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bounds {
+    /// Lower bounds.
+    pub lower: Vec<f64>,
+
+    /// Upper bounds.
+    pub upper: Vec<f64>,
+}
+
+/// Result of a minimisation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptimizationResult {
+    /// Minimizer of the function.
+    pub minimizer: Vec<f64>,
+
+    /// Value of the function at the minimum.
+    pub minimum: f64,
+
+    /// Number of iterations (or generations).
+    pub iterations: usize,
+
+    /// Whether the tolerance was reached before the maximum iterations.
+    pub converged: bool,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<F> Objective for F
+where
+    F: Fn(&[f64]) -> f64,
+{
+    fn value(&self, x: &[f64]) -> f64 {
+        self(x)
+    }
+}
+
+impl<F, G> GradientObjective<F, G>
+where
+    F: Fn(&[f64]) -> f64,
+    G: Fn(&[f64]) -> Vec<f64>,
+{
+    /// Objective `function` with gradient `gradient`.
+    pub fn new(function: F, gradient: G) -> Self {
+        Self { function, gradient }
+    }
+}
+
+impl<F, G> Objective for GradientObjective<F, G>
+where
+    F: Fn(&[f64]) -> f64,
+    G: Fn(&[f64]) -> Vec<f64>,
+{
+    fn value(&self, x: &[f64]) -> f64 {
+        (self.function)(x)
+    }
+
+    fn gradient(&self, x: &[f64]) -> Vec<f64> {
+        (self.gradient)(x)
+    }
+}
+
+impl<F> AutodiffObjective<F>
+where
+    F: for<'v> Fn(&[Variable<'v>]) -> Variable<'v>,
+{
+    /// Objective from a function of autodiff variables.
+    pub fn new(function: F) -> Self {
+        Self { function }
+    }
+}
+
+impl<F> Objective for AutodiffObjective<F>
+where
+    F: for<'v> Fn(&[Variable<'v>]) -> Variable<'v>,
+{
+    fn value(&self, x: &[f64]) -> f64 {
+        let graph = Graph::new();
+
+        (self.function)(&graph.vars(x)).value
+    }
+
+    fn gradient(&self, x: &[f64]) -> Vec<f64> {
+        let graph = Graph::new();
+        let variables = graph.vars(x);
+
+        (self.function)(&variables).accumulate().wrt(&variables)
+    }
+}
+
+impl Bounds {
+    /// Bounds `lower <= x <= upper`.
+    pub fn new(lower: Vec<f64>, upper: Vec<f64>) -> Self {
+        assert!(
+            lower.len() == upper.len() && lower.iter().zip(&upper).all(|(l, u)| l <= u),
+            "Lower bounds must not exceed upper bounds."
+        );
+
+        Self { lower, upper }
+    }
+
+    /// Number of variables.
+    pub fn len(&self) -> usize {
+        self.lower.len()
+    }
+
+    /// Whether there are no variables.
+    pub fn is_empty(&self) -> bool {
+        self.lower.is_empty()
+    }
+
+    /// Whether `x` satisfies the bounds.
+    pub fn contains(&self, x: &[f64]) -> bool {
+        x.iter()
+            .zip(self.lower.iter().zip(&self.upper))
+            .all(|(xi, (l, u))| l <= xi && xi <= u)
+    }
+
+    /// Projects `x` onto the box.
+    pub fn project(&self, x: &mut [f64]) {
+        for (xi, (l, u)) in x.iter_mut().zip(self.lower.iter().zip(&self.upper)) {
+            *xi = xi.clamp(*l, *u);
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_objective {
+    use super::*;
+    use crate::autodiff::Powf;
+
+    fn f<'v>(x: &[Variable<'v>]) -> Variable<'v> {
+        x[0] * x[1].exp() + x[0].powf(3.0)
+    }
+
+    #[test]
+    fn test_gradients() {
+        let x = [1.5, -0.5];
+        let exact = vec![(-0.5_f64).exp() + 3.0 * 1.5 * 1.5, 1.5 * (-0.5_f64).exp()];
+
+        let closure = |x: &[f64]| x[0] * x[1].exp() + x[0].powi(3);
+        let autodiff = AutodiffObjective::new(f);
+        let analytic = GradientObjective::new(closure, |x: &[f64]| {
+            vec![x[1].exp() + 3.0 * x[0] * x[0], x[0] * x[1].exp()]
+        });
+
+        assert_approx_equal!(autodiff.value(&x), closure(&x), 1e-15);
+        for (g, (a, e)) in closure
+            .gradient(&x)
+            .iter()
+            .zip(autodiff.gradient(&x).iter().zip(&exact))
+        {
+            assert_approx_equal!(*g, *e, 1e-8);
+            assert_approx_equal!(*a, *e, 1e-12);
+        }
+        assert_eq!(analytic.gradient(&x), exact);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let bounds = Bounds::new(vec![0.0, f64::NEG_INFINITY], vec![1.0, 2.0]);
+        let mut x = [1.5, -1e9];
+
+        assert!(!bounds.contains(&x));
+        bounds.project(&mut x);
+        assert_eq!(x, [1.0, -1e9]);
+        assert!(bounds.contains(&x));
+        assert_eq!(bounds.len(), 2);
+    }
+}
@@ -0,0 +1,466 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Orthogonal polynomial bases and least-squares fitting.
+//!
+//! [`PolynomialBasis`] evaluates the first `degree + 1` monomial, Laguerre,
+//! probabilists' Hermite, Chebyshev or Legendre polynomials (and their
+//! derivatives) by their three-term recurrences, optionally in an affinely
+//! rescaled variable $z = (x - \text{shift}) / \text{scale}$. Regressing on
+//! a well-scaled orthogonal basis keeps the least-squares problem well
+//! conditioned, which is what the Longstaff-Schwartz engine
+//! ([`LongstaffSchwartz`](crate::instruments::options::american::LongstaffSchwartz))
+//! relies on for its continuation values.
+//!
+//! - [`PolynomialBasis::fit`]: least-squares fit to scattered data.
+//! - [`chebyshev_approximation`]: near-minimax approximation of a function
+//!   on an interval, e.g. a slice of a value surface.
+//!
+//! ```
+//! use RustQuant::math::polynomials::{PolynomialBasis, PolynomialFamily};
+//!
+//! // Laguerre polynomials in moneyness S / K.
+//! let basis = PolynomialBasis::new(PolynomialFamily::Laguerre, 3).with_scaling(0.0, 40.0);
+//!
+//! let xs: Vec<f64> = (0..50).map(|i| 20.0 + i as f64).collect();
+//! let ys: Vec<f64> = xs.iter().map(|x| (40.0 - x).max(0.0) + 0.01 * x * x).collect();
+//!
+//! let fit = basis.fit(&xs, &ys).unwrap();
+//! println!("Continuation value at 36: {}", fit.evaluate(36.0));
+//! ```
+
+use nalgebra::{DMatrix, DVector};
+use std::f64::consts::PI;
+use thiserror::Error;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Polynomial fitting errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum PolynomialError {
+    /// The abscissae and ordinates have different lengths.
+    #[error("The abscissae and ordinates have different lengths.")]
+    LengthMismatch,
+
+    /// Fewer data points than basis functions.
+    #[error("{points} points are too few to fit {functions} basis functions.")]
+    TooFewPoints {
+        /// Number of data points.
+        points: usize,
+        /// Number of basis functions.
+        functions: usize,
+    },
+
+    /// The design matrix does not have full column rank.
+    #[error("The design matrix is rank deficient.")]
+    RankDeficient,
+}
+
+/// Polynomial families, all generated by a three-term recurrence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PolynomialFamily {
+    /// Monomials $1, z, z^2, \ldots$
+    #[default]
+    Monomial,
+
+    /// Laguerre polynomials $L_n(z)$, orthonormal on $[0, \infty)$ with
+    /// weight $e^{-z}$.
+    Laguerre,
+
+    /// Probabilists' Hermite polynomials $He_n(z)$, orthogonal under the
+    /// standard normal density.
+    Hermite,
+
+    /// Chebyshev polynomials of the first kind $T_n(z)$, orthogonal on
+    /// $[-1, 1]$ with weight $(1 - z^2)^{-1/2}$.
+    Chebyshev,
+
+    /// Legendre polynomials $P_n(z)$, orthogonal on $[-1, 1]$.
+    Legendre,
+}
+
+/// The first `degree + 1` polynomials of a family, in the rescaled
+/// variable $z = (x - \text{shift}) / \text{scale}$.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PolynomialBasis {
+    /// Polynomial family.
+    pub family: PolynomialFamily,
+    /// Highest degree in the basis.
+    pub degree: usize,
+    /// Shift of the variable.
+    pub shift: f64,
+    /// Scale of the variable.
+    pub scale: f64,
+}
+
+/// Polynomial fit: coefficients on a basis.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolynomialFit {
+    /// Basis of the fit.
+    pub basis: PolynomialBasis,
+    /// Coefficients of the basis functions, lowest degree first.
+    pub coefficients: Vec<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl PolynomialFamily {
+    /// Coefficients `(a, b, c)` of the recurrence
+    /// $p_{n+1}(z) = (a z + b) p_n(z) - c\, p_{n-1}(z)$, with $p_0 = 1$ and
+    /// $p_{-1} = 0$.
+    fn recurrence(&self, n: usize) -> (f64, f64, f64) {
+        let n = n as f64;
+
+        match self {
+            PolynomialFamily::Monomial => (1.0, 0.0, 0.0),
+            PolynomialFamily::Laguerre => {
+                (-1.0 / (n + 1.0), (2.0 * n + 1.0) / (n + 1.0), n / (n + 1.0))
+            }
+            PolynomialFamily::Hermite => (1.0, 0.0, n),
+            PolynomialFamily::Chebyshev if n == 0.0 => (1.0, 0.0, 0.0),
+            PolynomialFamily::Chebyshev => (2.0, 0.0, 1.0),
+            PolynomialFamily::Legendre => ((2.0 * n + 1.0) / (n + 1.0), 0.0, n / (n + 1.0)),
+        }
+    }
+}
+
+impl PolynomialBasis {
+    /// Basis of the given family up to `degree`, in the unscaled variable.
+    pub fn new(family: PolynomialFamily, degree: usize) -> Self {
+        Self {
+            family,
+            degree,
+            shift: 0.0,
+            scale: 1.0,
+        }
+    }
+
+    /// Evaluate the polynomials at $z = (x - \text{shift}) / \text{scale}$,
+    /// e.g. Laguerre polynomials of moneyness with `with_scaling(0.0, strike)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale` is zero.
+    pub fn with_scaling(mut self, shift: f64, scale: f64) -> Self {
+        assert!(scale != 0.0, "The scale must be non-zero.");
+
+        self.shift = shift;
+        self.scale = scale;
+        self
+    }
+
+    /// Map the interval `[a, b]` onto `[-1, 1]`, the orthogonality interval
+    /// of the Chebyshev and Legendre polynomials.
+    pub fn with_domain(self, a: f64, b: f64) -> Self {
+        self.with_scaling(0.5 * (a + b), 0.5 * (b - a))
+    }
+
+    /// Number of basis functions, `degree + 1`.
+    pub fn len(&self) -> usize {
+        self.degree + 1
+    }
+
+    /// Always `false`: a basis contains at least the constant function.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Values of the basis functions at `x`, lowest degree first.
+    pub fn evaluate(&self, x: f64) -> Vec<f64> {
+        let mut values = vec![0.0; self.len()];
+        self.evaluate_into(x, &mut values);
+        values
+    }
+
+    /// Values of the basis functions at `x`, written into `out`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `out` does not have length `degree + 1`.
+    pub fn evaluate_into(&self, x: f64, out: &mut [f64]) {
+        assert_eq!(out.len(), self.len(), "Output length must be degree + 1.");
+
+        let z = (x - self.shift) / self.scale;
+        let mut previous = 0.0;
+        out[0] = 1.0;
+
+        for n in 0..self.degree {
+            let (a, b, c) = self.family.recurrence(n);
+            let next = (a * z + b) * out[n] - c * previous;
+            previous = out[n];
+            out[n + 1] = next;
+        }
+    }
+
+    /// Derivatives of the basis functions with respect to `x` (not `z`).
+    pub fn derivatives(&self, x: f64) -> Vec<f64> {
+        let z = (x - self.shift) / self.scale;
+        let values = self.evaluate(x);
+        let mut derivatives = vec![0.0; self.len()];
+
+        for n in 0..self.degree {
+            let (a, b, c) = self.family.recurrence(n);
+            let previous = if n == 0 { 0.0 } else { derivatives[n - 1] };
+            derivatives[n + 1] = a * values[n] + (a * z + b) * derivatives[n] - c * previous;
+        }
+
+        derivatives.iter().map(|d| d / self.scale).collect()
+    }
+
+    /// Design matrix with one row of basis function values per abscissa.
+    pub fn design_matrix(&self, xs: &[f64]) -> DMatrix<f64> {
+        let mut matrix = DMatrix::zeros(xs.len(), self.len());
+        let mut row = vec![0.0; self.len()];
+
+        for (i, &x) in xs.iter().enumerate() {
+            self.evaluate_into(x, &mut row);
+            for (j, &value) in row.iter().enumerate() {
+                matrix[(i, j)] = value;
+            }
+        }
+
+        matrix
+    }
+
+    /// Least-squares fit of `ys` on the basis at `xs`, solved by a singular
+    /// value decomposition of the design matrix.
+    pub fn fit(&self, xs: &[f64], ys: &[f64]) -> Result<PolynomialFit, PolynomialError> {
+        if xs.len() != ys.len() {
+            return Err(PolynomialError::LengthMismatch);
+        }
+        if xs.len() < self.len() {
+            return Err(PolynomialError::TooFewPoints {
+                points: xs.len(),
+                functions: self.len(),
+            });
+        }
+
+        let svd = self.design_matrix(xs).svd(true, true);
+        let tolerance = svd.singular_values.max() * f64::EPSILON * xs.len() as f64;
+
+        if svd.rank(tolerance) < self.len() {
+            return Err(PolynomialError::RankDeficient);
+        }
+
+        let coefficients = svd
+            .solve(&DVector::from_column_slice(ys), tolerance)
+            .map_err(|_| PolynomialError::RankDeficient)?;
+
+        Ok(PolynomialFit {
+            basis: *self,
+            coefficients: coefficients.iter().copied().collect(),
+        })
+    }
+}
+
+impl PolynomialFit {
+    /// Value of the fitted polynomial at `x`.
+    pub fn evaluate(&self, x: f64) -> f64 {
+        dot(&self.coefficients, &self.basis.evaluate(x))
+    }
+
+    /// Derivative of the fitted polynomial at `x`.
+    pub fn derivative(&self, x: f64) -> f64 {
+        dot(&self.coefficients, &self.basis.derivatives(x))
+    }
+
+    /// Values of the fitted polynomial at each of `xs`.
+    pub fn evaluate_many(&self, xs: &[f64]) -> Vec<f64> {
+        let mut row = vec![0.0; self.basis.len()];
+
+        xs.iter()
+            .map(|&x| {
+                self.basis.evaluate_into(x, &mut row);
+                dot(&self.coefficients, &row)
+            })
+            .collect()
+    }
+}
+
+/// The `n` Chebyshev nodes (roots of $T_n$) mapped onto `[a, b]`, in
+/// increasing order.
+pub fn chebyshev_nodes(n: usize, a: f64, b: f64) -> Vec<f64> {
+    (0..n).rev().map(|k| chebyshev_node(k, n, a, b)).collect()
+}
+
+/// Chebyshev approximation of `f` on `[a, b]` of the given degree, which
+/// interpolates `f` at the `degree + 1` Chebyshev nodes.
+///
+/// The coefficients come from a discrete cosine transform of the function
+/// values, so no linear system is solved. For smooth `f` the error decays
+/// geometrically with the degree and is close to that of the best uniform
+/// approximation.
+pub fn chebyshev_approximation<F>(f: F, degree: usize, a: f64, b: f64) -> PolynomialFit
+where
+    F: Fn(f64) -> f64,
+{
+    let n = degree + 1;
+    let values: Vec<f64> = (0..n).map(|k| f(chebyshev_node(k, n, a, b))).collect();
+
+    let coefficients = (0..n)
+        .map(|j| {
+            let sum: f64 = values
+                .iter()
+                .enumerate()
+                .map(|(k, v)| v * (PI * j as f64 * (k as f64 + 0.5) / n as f64).cos())
+                .sum();
+            let weight = if j == 0 { 1.0 } else { 2.0 };
+            weight * sum / n as f64
+        })
+        .collect();
+
+    PolynomialFit {
+        basis: PolynomialBasis::new(PolynomialFamily::Chebyshev, degree).with_domain(a, b),
+        coefficients,
+    }
+}
+
+/// The `k`-th root of $T_n$, mapped onto `[a, b]` (decreasing in `k`).
+fn chebyshev_node(k: usize, n: usize, a: f64, b: f64) -> f64 {
+    let z = (PI * (k as f64 + 0.5) / n as f64).cos();
+    0.5 * (a + b) + 0.5 * (b - a) * z
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_polynomials {
+    use super::*;
+    use crate::math::integrate::GaussKronrod;
+
+    #[test]
+    fn test_closed_forms() {
+        let x: f64 = 0.3;
+
+        let laguerre = PolynomialBasis::new(PolynomialFamily::Laguerre, 2).evaluate(x);
+        assert_approx_equal!(laguerre[2], 0.5 * (x * x - 4.0 * x + 2.0), 1e-15);
+
+        let hermite = PolynomialBasis::new(PolynomialFamily::Hermite, 3).evaluate(x);
+        assert_approx_equal!(hermite[3], x.powi(3) - 3.0 * x, 1e-15);
+
+        let chebyshev = PolynomialBasis::new(PolynomialFamily::Chebyshev, 5).evaluate(x);
+        assert_approx_equal!(chebyshev[5], (5.0 * x.acos()).cos(), 1e-14);
+
+        let legendre = PolynomialBasis::new(PolynomialFamily::Legendre, 3).evaluate(x);
+        assert_approx_equal!(legendre[3], 0.5 * (5.0 * x.powi(3) - 3.0 * x), 1e-15);
+
+        let monomial = PolynomialBasis::new(PolynomialFamily::Monomial, 4)
+            .with_scaling(1.0, 2.0)
+            .evaluate(x);
+        assert_approx_equal!(monomial[4], ((x - 1.0) / 2.0).powi(4), 1e-15);
+    }
+
+    #[test]
+    fn test_orthogonality() {
+        let quadrature = GaussKronrod::new(1e-13, 100);
+
+        let legendre = PolynomialBasis::new(PolynomialFamily::Legendre, 3);
+        let product = |i: usize, j: usize, x: f64| {
+            let p = legendre.evaluate(x);
+            p[i] * p[j]
+        };
+        let cross = quadrature.integrate(|x| product(2, 3, x), -1.0, 1.0);
+        let norm = quadrature.integrate(|x| product(3, 3, x), -1.0, 1.0);
+        assert_approx_equal!(cross.value, 0.0, 1e-13);
+        assert_approx_equal!(norm.value, 2.0 / 7.0, 1e-13);
+
+        let laguerre = PolynomialBasis::new(PolynomialFamily::Laguerre, 3);
+        let weighted = |i: usize, j: usize, x: f64| {
+            let p = laguerre.evaluate(x);
+            p[i] * p[j] * (-x).exp()
+        };
+        let cross = quadrature.integrate(|x| weighted(1, 3, x), 0.0, f64::INFINITY);
+        let norm = quadrature.integrate(|x| weighted(3, 3, x), 0.0, f64::INFINITY);
+        assert_approx_equal!(cross.value, 0.0, 1e-10);
+        assert_approx_equal!(norm.value, 1.0, 1e-10);
+    }
+
+    #[test]
+    fn test_derivatives() {
+        let h = 1e-6;
+
+        for family in [
+            PolynomialFamily::Monomial,
+            PolynomialFamily::Laguerre,
+            PolynomialFamily::Hermite,
+            PolynomialFamily::Chebyshev,
+            PolynomialFamily::Legendre,
+        ] {
+            let basis = PolynomialBasis::new(family, 5).with_scaling(0.5, 2.0);
+            let derivatives = basis.derivatives(0.8);
+            let up = basis.evaluate(0.8 + h);
+            let down = basis.evaluate(0.8 - h);
+
+            for n in 0..basis.len() {
+                assert_approx_equal!(derivatives[n], (up[n] - down[n]) / (2.0 * h), 1e-7);
+            }
+        }
+    }
+
+    #[test]
+    fn test_fit_recovers_polynomial() {
+        let f = |x: f64| 3.0 - 0.5 * x + 0.01 * x * x - 1e-5 * x.powi(3);
+        let xs: Vec<f64> = (0..30).map(|i| 80.0 + 2.0 * i as f64).collect();
+        let ys: Vec<f64> = xs.iter().map(|&x| f(x)).collect();
+
+        let fit = PolynomialBasis::new(PolynomialFamily::Chebyshev, 3)
+            .with_domain(80.0, 140.0)
+            .fit(&xs, &ys)
+            .unwrap();
+
+        let slope = -0.5 + 0.02 * 101.0 - 3e-5 * 101.0 * 101.0;
+        assert_approx_equal!(fit.evaluate(101.0), f(101.0), 1e-10);
+        assert_approx_equal!(fit.derivative(101.0), slope, 1e-10);
+        assert_eq!(fit.evaluate_many(&[101.0])[0], fit.evaluate(101.0));
+    }
+
+    #[test]
+    fn test_fit_errors() {
+        let basis = PolynomialBasis::new(PolynomialFamily::Hermite, 2);
+
+        assert_eq!(
+            basis.fit(&[1.0, 2.0], &[1.0]),
+            Err(PolynomialError::LengthMismatch)
+        );
+        assert_eq!(
+            basis.fit(&[1.0, 2.0], &[1.0, 2.0]),
+            Err(PolynomialError::TooFewPoints {
+                points: 2,
+                functions: 3
+            })
+        );
+        assert_eq!(
+            basis.fit(&[1.0, 1.0, 2.0, 2.0], &[1.0, 1.0, 2.0, 2.0]),
+            Err(PolynomialError::RankDeficient)
+        );
+    }
+
+    #[test]
+    fn test_chebyshev_approximation() {
+        let fit = chebyshev_approximation(f64::exp, 14, 0.0, 2.0);
+
+        for x in [0.0, 0.37, 1.0, 1.9, 2.0] {
+            assert_approx_equal!(fit.evaluate(x), x.exp(), 1e-13);
+        }
+
+        let nodes = chebyshev_nodes(5, -1.0, 1.0);
+        assert!(nodes.windows(2).all(|w| w[0] < w[1]));
+        assert_approx_equal!(nodes[2], 0.0, 1e-15);
+    }
+}
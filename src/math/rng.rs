@@ -0,0 +1,612 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Random number generation for Monte Carlo simulation.
+//!
+//! - `Pcg64` (PCG XSL RR 128/64) with independent streams, used for the
+//!   per-path generators of the stochastic process simulations.
+//! - `Xoshiro256PlusPlus` with `jump()` and `long_jump()` to split a
+//!   sequence into non-overlapping sub-sequences.
+//! - Standard normal variates by the ziggurat method or by inversion of the
+//!   distribution function (`NormalMethod`), e.g. for variance reduction
+//!   schemes that need a monotone map from uniforms to normals.
+//! - Gamma, Poisson and non-central chi-squared samplers, e.g. for the
+//!   exact simulation of the Cox-Ingersoll-Ross process.
+//!
+//! All generators implement `rand::RngCore` and `rand::SeedableRng`, so
+//! they work with every `rand` and `rand_distr` distribution.
+//!
+//! ```rust
+//! use RustQuant::math::*;
+//! use rand::Rng;
+//!
+//! // Stream 3 of the generator seeded with 42.
+//! let mut rng = Pcg64::stream(42, 3);
+//!
+//! let u: f64 = rng.gen();
+//! let z = NormalMethod::InverseCdf.sample(&mut rng);
+//! let n = sample_poisson(&mut rng, 2.5);
+//! ```
+
+use rand::{Rng, RngCore, SeedableRng};
+use rand_distr::StandardNormal;
+use statrs::function::gamma::ln_gamma;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// PCG XSL RR 128/64 generator (O'Neill, 2014): a 128-bit linear
+/// congruential generator with a permuted 64-bit output.
+///
+/// Every odd increment gives a different stream, so each path (or thread)
+/// of a simulation can use its own stream of the same seed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pcg64 {
+    state: u128,
+    increment: u128,
+}
+
+/// Xoshiro256++ generator (Blackman and Vigna, 2019).
+///
+/// `jump()` advances the state by $2^{128}$ steps and `long_jump()` by
+/// $2^{192}$ steps, giving non-overlapping sub-sequences for parallel use.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Xoshiro256PlusPlus {
+    s: [u64; 4],
+}
+
+/// Method used to draw standard normal variates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NormalMethod {
+    /// Ziggurat method (Marsaglia and Tsang, 2000), the fastest option.
+    #[default]
+    Ziggurat,
+
+    /// Inversion of the distribution function (Wichura's AS241), one
+    /// uniform per variate and monotone in the uniform.
+    InverseCdf,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+const PCG_MULTIPLIER: u128 = 0x2360_ED05_1FC6_5DA4_4385_DF64_9FCC_F645;
+
+impl Pcg64 {
+    /// New generator with the given initial state and stream.
+    pub fn new(state: u128, stream: u128) -> Self {
+        let mut rng = Self {
+            state: 0,
+            increment: (stream << 1) | 1,
+        };
+        rng.state = state.wrapping_add(rng.increment);
+        rng.step();
+        rng
+    }
+
+    /// Stream `stream` of the generator seeded with `seed`.
+    ///
+    /// Different streams of the same seed are independent sequences, so
+    /// `Pcg64::stream(seed, i)` gives a reproducible generator for path `i`.
+    pub fn stream(seed: u64, stream: u64) -> Self {
+        let mut splitmix = SplitMix64(seed);
+        let state = (u128::from(splitmix.next()) << 64) | u128::from(splitmix.next());
+
+        Self::new(state, u128::from(SplitMix64(stream).next()))
+    }
+
+    /// Advances the generator by `delta` steps in $O(\log \delta)$ time.
+    pub fn advance(&mut self, delta: u128) {
+        let mut acc_mult: u128 = 1;
+        let mut acc_plus: u128 = 0;
+        let mut cur_mult = PCG_MULTIPLIER;
+        let mut cur_plus = self.increment;
+        let mut delta = delta;
+
+        while delta > 0 {
+            if delta & 1 == 1 {
+                acc_mult = acc_mult.wrapping_mul(cur_mult);
+                acc_plus = acc_plus.wrapping_mul(cur_mult).wrapping_add(cur_plus);
+            }
+            cur_plus = cur_mult.wrapping_add(1).wrapping_mul(cur_plus);
+            cur_mult = cur_mult.wrapping_mul(cur_mult);
+            delta >>= 1;
+        }
+
+        self.state = acc_mult.wrapping_mul(self.state).wrapping_add(acc_plus);
+    }
+
+    fn step(&mut self) {
+        self.state = self
+            .state
+            .wrapping_mul(PCG_MULTIPLIER)
+            .wrapping_add(self.increment);
+    }
+}
+
+impl RngCore for Pcg64 {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.step();
+
+        // XSL RR output: xor the halves, rotate by the top 6 bits.
+        let rotation = (self.state >> 122) as u32;
+        (((self.state >> 64) as u64) ^ (self.state as u64)).rotate_right(rotation)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        fill_bytes_via_u64(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl SeedableRng for Pcg64 {
+    /// The first 16 bytes are the state, the last 16 bytes the increment.
+    type Seed = [u8; 32];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        let (state, increment) = seed.split_at(16);
+        let state = u128::from_le_bytes(state.try_into().unwrap());
+        let increment = u128::from_le_bytes(increment.try_into().unwrap());
+
+        Self::new(state, increment >> 1)
+    }
+}
+
+impl Xoshiro256PlusPlus {
+    /// Advances the generator by $2^{128}$ steps.
+    pub fn jump(&mut self) {
+        self.apply_jump(&[
+            0x180E_C6D3_3CFD_0ABA,
+            0xD5A6_1266_F0C9_392C,
+            0xA958_2618_E03F_C9AA,
+            0x39AB_DC45_29B1_661C,
+        ]);
+    }
+
+    /// Advances the generator by $2^{192}$ steps.
+    pub fn long_jump(&mut self) {
+        self.apply_jump(&[
+            0x76E1_5D3E_FEFD_CBBF,
+            0xC500_4E44_1C52_2FB3,
+            0x7771_0069_854E_E241,
+            0x3910_9BB0_2ACB_E635,
+        ]);
+    }
+
+    fn apply_jump(&mut self, polynomial: &[u64; 4]) {
+        let mut s = [0_u64; 4];
+
+        for word in polynomial {
+            for bit in 0..64 {
+                if (word >> bit) & 1 == 1 {
+                    s.iter_mut().zip(self.s).for_each(|(a, b)| *a ^= b);
+                }
+                self.next_u64();
+            }
+        }
+
+        self.s = s;
+    }
+}
+
+impl RngCore for Xoshiro256PlusPlus {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let s = &mut self.s;
+        let result = s[0].wrapping_add(s[3]).rotate_left(23).wrapping_add(s[0]);
+        let t = s[1] << 17;
+
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+
+        result
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        fill_bytes_via_u64(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl SeedableRng for Xoshiro256PlusPlus {
+    type Seed = [u8; 32];
+
+    /// The all-zero state is a fixed point, so an all-zero seed is
+    /// replaced by `seed_from_u64(0)`.
+    fn from_seed(seed: Self::Seed) -> Self {
+        if seed.iter().all(|&b| b == 0) {
+            return Self::seed_from_u64(0);
+        }
+
+        let mut s = [0_u64; 4];
+        for (word, bytes) in s.iter_mut().zip(seed.chunks_exact(8)) {
+            *word = u64::from_le_bytes(bytes.try_into().unwrap());
+        }
+
+        Self { s }
+    }
+
+    /// Expands the seed with SplitMix64, as recommended by the authors.
+    fn seed_from_u64(seed: u64) -> Self {
+        let mut splitmix = SplitMix64(seed);
+
+        Self {
+            s: [
+                splitmix.next(),
+                splitmix.next(),
+                splitmix.next(),
+                splitmix.next(),
+            ],
+        }
+    }
+}
+
+/// Fills `dest` with the little-endian bytes of successive `next_u64` calls.
+fn fill_bytes_via_u64<R: RngCore + ?Sized>(rng: &mut R, dest: &mut [u8]) {
+    for chunk in dest.chunks_mut(8) {
+        let bytes = rng.next_u64().to_le_bytes();
+        chunk.copy_from_slice(&bytes[..chunk.len()]);
+    }
+}
+
+/// SplitMix64 (Steele, Lea and Flood, 2014), used to expand seeds.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+impl NormalMethod {
+    /// Draws a standard normal variate.
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        match self {
+            NormalMethod::Ziggurat => rng.sample(StandardNormal),
+            NormalMethod::InverseCdf => inverse_normal_cdf(open_uniform(rng)),
+        }
+    }
+}
+
+/// Uniform variate on the open interval $(0, 1)$.
+pub fn open_uniform<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    ((rng.next_u64() >> 11) as f64 + 0.5) / (1_u64 << 53) as f64
+}
+
+/// Inverse of the standard normal distribution function, by Wichura's
+/// algorithm AS241 (relative accuracy about $10^{-16}$).
+pub fn inverse_normal_cdf(p: f64) -> f64 {
+    const A: [f64; 8] = [
+        3.3871328727963665,
+        1.3314166789178438e2,
+        1.9715909503065513e3,
+        1.373169376550946e4,
+        4.592195393154987e4,
+        6.72657709270087e4,
+        3.343057558358813e4,
+        2.5090809287301227e3,
+    ];
+    const B: [f64; 8] = [
+        1.0,
+        4.231333070160091e1,
+        6.871870074920579e2,
+        5.394196021424751e3,
+        2.1213794301586597e4,
+        3.930789580009271e4,
+        2.8729085735721943e4,
+        5.226495278852854e3,
+    ];
+    const C: [f64; 8] = [
+        1.4234371107496835,
+        4.630337846156546,
+        5.769497221460691,
+        3.6478483247632045,
+        1.2704582524523684,
+        2.417807251774506e-1,
+        2.2723844989269184e-2,
+        7.745450142783414e-4,
+    ];
+    const D: [f64; 8] = [
+        1.0,
+        2.053191626637759,
+        1.6763848301838038,
+        6.897673349851e-1,
+        1.4810397642748008e-1,
+        1.5198666563616457e-2,
+        5.475938084995345e-4,
+        1.0507500716444169e-9,
+    ];
+    const E: [f64; 8] = [
+        6.657904643501103,
+        5.463784911164114,
+        1.7848265399172913,
+        2.9656057182850487e-1,
+        2.6532189526576124e-2,
+        1.2426609473880784e-3,
+        2.7115555687434876e-5,
+        2.0103343992922881e-7,
+    ];
+    const F: [f64; 8] = [
+        1.0,
+        5.99832206555888e-1,
+        1.369298809227358e-1,
+        1.4875361290850615e-2,
+        7.868691311456133e-4,
+        1.8463183175100548e-5,
+        1.421511758316446e-7,
+        2.0442631033899397e-15,
+    ];
+
+    let ratio = |num: &[f64; 8], den: &[f64; 8], r: f64| {
+        num.iter().rev().fold(0.0, |acc, c| acc * r + c)
+            / den.iter().rev().fold(0.0, |acc, c| acc * r + c)
+    };
+
+    match p {
+        p if p.is_nan() || !(0.0..=1.0).contains(&p) => f64::NAN,
+        0.0 => f64::NEG_INFINITY,
+        1.0 => f64::INFINITY,
+        _ => {
+            let q = p - 0.5;
+
+            if q.abs() <= 0.425 {
+                return q * ratio(&A, &B, 0.180625 - q * q);
+            }
+
+            let r = (-p.min(1.0 - p).ln()).sqrt();
+            let x = match r <= 5.0 {
+                true => ratio(&C, &D, r - 1.6),
+                false => ratio(&E, &F, r - 5.0),
+            };
+
+            x.copysign(q)
+        }
+    }
+}
+
+/// Gamma variate with the given shape and scale, by the method of
+/// Marsaglia and Tsang (2000), boosted for shapes below one.
+pub fn sample_gamma<R: Rng + ?Sized>(rng: &mut R, shape: f64, scale: f64) -> f64 {
+    assert!(shape > 0.0 && scale > 0.0);
+
+    if shape < 1.0 {
+        let boost = open_uniform(rng).powf(1.0 / shape);
+        return sample_gamma(rng, shape + 1.0, scale) * boost;
+    }
+
+    let d = shape - 1.0 / 3.0;
+    let c = 1.0 / (9.0 * d).sqrt();
+
+    loop {
+        let x: f64 = rng.sample(StandardNormal);
+        let v = 1.0 + c * x;
+
+        if v <= 0.0 {
+            continue;
+        }
+
+        let v = v * v * v;
+        let u = open_uniform(rng);
+
+        if u < 1.0 - 0.0331 * x.powi(4) || u.ln() < 0.5 * x * x + d * (1.0 - v + v.ln()) {
+            return d * v * scale;
+        }
+    }
+}
+
+/// Poisson variate with mean `lambda`: inversion for small means and
+/// Hörmann's transformed rejection (PTRS, 1993) otherwise.
+pub fn sample_poisson<R: Rng + ?Sized>(rng: &mut R, lambda: f64) -> u64 {
+    assert!(lambda >= 0.0 && lambda.is_finite());
+
+    if lambda == 0.0 {
+        return 0;
+    }
+
+    if lambda < 10.0 {
+        let u = open_uniform(rng);
+        let mut k = 0;
+        let mut p = (-lambda).exp();
+        let mut cdf = p;
+
+        while u > cdf && p > 0.0 {
+            k += 1;
+            p *= lambda / k as f64;
+            cdf += p;
+        }
+
+        return k;
+    }
+
+    let ln_lambda = lambda.ln();
+    let b = 0.931 + 2.53 * lambda.sqrt();
+    let a = -0.059 + 0.02483 * b;
+    let inv_alpha = 1.1239 + 1.1328 / (b - 3.4);
+    let v_r = 0.9277 - 3.6224 / (b - 2.0);
+
+    loop {
+        let u = open_uniform(rng) - 0.5;
+        let v = open_uniform(rng);
+        let us = 0.5 - u.abs();
+        let k = ((2.0 * a / us + b) * u + lambda + 0.43).floor();
+
+        if us >= 0.07 && v <= v_r {
+            return k as u64;
+        }
+        if k < 0.0 || (us < 0.013 && v > us) {
+            continue;
+        }
+        if v.ln() + inv_alpha.ln() - (a / (us * us) + b).ln()
+            <= -lambda + k * ln_lambda - ln_gamma(k + 1.0)
+        {
+            return k as u64;
+        }
+    }
+}
+
+/// Non-central chi-squared variate with `dof > 0` degrees of freedom and
+/// non-centrality `noncentrality`, as a Poisson mixture of gamma variates.
+pub fn sample_noncentral_chi_squared<R: Rng + ?Sized>(
+    rng: &mut R,
+    dof: f64,
+    noncentrality: f64,
+) -> f64 {
+    let n = sample_poisson(rng, 0.5 * noncentrality);
+
+    sample_gamma(rng, 0.5 * dof + n as f64, 2.0)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_rng {
+    use super::*;
+    use crate::statistics::Statistic;
+
+    #[test]
+    fn test_pcg64_reference_values() {
+        // Reference output of `Lcg128Xsl64::new(42, 54)` from `rand_pcg`.
+        let mut rng = Pcg64::new(42, 54);
+        let expected = [
+            0x86b1_da1d_7206_2b68,
+            0x1304_aa46_c985_3d39,
+            0xa367_0e9e_0dd5_0358,
+            0xf909_0e52_9a7d_ae00,
+            0xc85b_9fd8_3799_6f2c,
+            0x6061_21f8_e391_9196,
+        ];
+
+        for value in expected {
+            assert_eq!(rng.next_u64(), value);
+        }
+    }
+
+    #[test]
+    fn test_pcg64_advance_and_streams() {
+        let mut a = Pcg64::stream(7, 0);
+        let mut b = a.clone();
+
+        (0..1000).for_each(|_| {
+            a.next_u64();
+        });
+        b.advance(1000);
+        assert_eq!(a, b);
+
+        // Streams of the same seed differ, the same stream is reproducible.
+        let mut c = Pcg64::stream(7, 1);
+        let mut d = Pcg64::stream(7, 1);
+        let mut e = Pcg64::stream(7, 2);
+        assert_eq!(c.next_u64(), d.next_u64());
+        assert_ne!(c.next_u64(), e.next_u64());
+    }
+
+    #[test]
+    fn test_xoshiro256plusplus_reference_values() {
+        // Reference output for the state [1, 2, 3, 4].
+        let mut seed = [0_u8; 32];
+        for (i, bytes) in seed.chunks_exact_mut(8).enumerate() {
+            bytes.copy_from_slice(&(i as u64 + 1).to_le_bytes());
+        }
+        let mut rng = Xoshiro256PlusPlus::from_seed(seed);
+
+        for value in [41943041, 58720359, 3588806011781223, 3591011842654386] {
+            assert_eq!(rng.next_u64(), value);
+        }
+
+        // Jumps give different sub-sequences.
+        let mut jumped = rng.clone();
+        jumped.jump();
+        assert_ne!(jumped, rng);
+        jumped.long_jump();
+        assert_ne!(jumped.next_u64(), rng.next_u64());
+    }
+
+    #[test]
+    fn test_inverse_normal_cdf() {
+        // Values computed with mpmath.
+        assert_approx_equal!(inverse_normal_cdf(0.975), 1.959963984540054, 1e-15);
+        assert_approx_equal!(inverse_normal_cdf(0.3), -0.524400512708041, 1e-15);
+        assert_approx_equal!(inverse_normal_cdf(1e-10), -6.361340902404056, 1e-14);
+        assert_approx_equal!(inverse_normal_cdf(1e-300), -37.0470962993612, 1e-12);
+        assert_eq!(inverse_normal_cdf(0.5), 0.0);
+        assert_eq!(inverse_normal_cdf(1.0), f64::INFINITY);
+        assert!(inverse_normal_cdf(1.5).is_nan());
+    }
+
+    #[test]
+    fn test_normal_methods() {
+        let mut rng = Pcg64::stream(644, 0);
+
+        for method in [NormalMethod::Ziggurat, NormalMethod::InverseCdf] {
+            let z: Vec<f64> = (0..100_000).map(|_| method.sample(&mut rng)).collect();
+
+            assert_approx_equal!(z.clone().mean(), 0.0, 0.01);
+            assert_approx_equal!(z.clone().variance(), 1.0, 0.02);
+            assert_approx_equal!(z.kurtosis(), 0.0, 0.05);
+        }
+    }
+
+    #[test]
+    fn test_gamma_and_poisson_samplers() {
+        let mut rng = Pcg64::stream(644, 1);
+
+        for (shape, scale) in [(0.3, 2.0), (4.5, 0.5)] {
+            let x: Vec<f64> = (0..100_000)
+                .map(|_| sample_gamma(&mut rng, shape, scale))
+                .collect();
+
+            assert_approx_equal!(x.clone().mean(), shape * scale, 0.02);
+            assert_approx_equal!(x.variance(), shape * scale * scale, 0.05);
+        }
+
+        for lambda in [0.7, 3.0, 25.0, 400.0] {
+            let n: Vec<f64> = (0..100_000)
+                .map(|_| sample_poisson(&mut rng, lambda) as f64)
+                .collect();
+
+            assert_approx_equal!(n.clone().mean() / lambda, 1.0, 0.01);
+            assert_approx_equal!(n.variance() / lambda, 1.0, 0.03);
+        }
+
+        // Mean dof + lambda, variance 2 (dof + 2 lambda).
+        let x: Vec<f64> = (0..100_000)
+            .map(|_| sample_noncentral_chi_squared(&mut rng, 1.5, 3.0))
+            .collect();
+        assert_approx_equal!(x.clone().mean(), 4.5, 0.05);
+        assert_approx_equal!(x.variance(), 15.0, 0.4);
+    }
+}
@@ -0,0 +1,482 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! One-dimensional root finding.
+//!
+//! All solvers share the settings of a [`RootFinder`] and return a [`Root`]:
+//!
+//! - bracketing: [`RootFinder::bisection`] and [`RootFinder::brent`],
+//! - derivative-based: [`RootFinder::newton`] and [`RootFinder::halley`],
+//! - safeguarded: [`RootFinder::newton_bracketed`], which takes Newton steps
+//!   while they stay inside the bracket and bisects otherwise.
+//!
+//! ```
+//! use RustQuant::math::rootfind::RootFinder;
+//!
+//! let solver = RootFinder::default();
+//! let root = solver.brent(|x: f64| x * x - 2.0, 0.0, 2.0).unwrap();
+//!
+//! assert!((root.root - 2.0_f64.sqrt()).abs() < 1e-12);
+//! ```
+
+use thiserror::Error;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Settings shared by the root finding algorithms.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RootFinder {
+    /// Tolerance on the root, relative to `1 + |x|`.
+    pub tolerance: f64,
+
+    /// Maximum number of iterations.
+    pub max_iterations: usize,
+}
+
+/// Root found by a solver.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Root {
+    /// Approximate root.
+    pub root: f64,
+
+    /// Function value at the root.
+    pub value: f64,
+
+    /// Number of iterations used.
+    pub iterations: usize,
+}
+
+/// Root finding errors.
+#[derive(Debug, Clone, Copy, PartialEq, Error)]
+pub enum RootFindingError {
+    /// The function has the same sign at both ends of the interval.
+    #[error("The root is not bracketed by [{0}, {1}].")]
+    NotBracketed(f64, f64),
+
+    /// The solver did not converge.
+    #[error("No convergence after {0} iterations.")]
+    MaxIterations(usize),
+
+    /// A derivative-based step divided by zero.
+    #[error("Zero derivative at {0}.")]
+    ZeroDerivative(f64),
+
+    /// The function (or a derivative) is not finite.
+    #[error("Non-finite function value at {0}.")]
+    NonFinite(f64),
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Default for RootFinder {
+    fn default() -> Self {
+        Self {
+            tolerance: 1e-12,
+            max_iterations: 100,
+        }
+    }
+}
+
+impl RootFinder {
+    /// Root finder with the given tolerance and iteration limit.
+    pub fn new(tolerance: f64, max_iterations: usize) -> Self {
+        Self {
+            tolerance,
+            max_iterations,
+        }
+    }
+
+    /// Widens `[lower, upper]` geometrically (by a factor 1.6, at most
+    /// `max_iterations` times) until it brackets a root.
+    pub fn bracket<F>(&self, f: F, lower: f64, upper: f64) -> Result<(f64, f64), RootFindingError>
+    where
+        F: Fn(f64) -> f64,
+    {
+        let (mut lower, mut upper) = (lower, upper);
+        let (mut f_lower, mut f_upper) = (f(lower), f(upper));
+
+        for _ in 0..self.max_iterations {
+            if f_lower * f_upper <= 0.0 {
+                return Ok((lower, upper));
+            }
+            match f_lower.abs() < f_upper.abs() {
+                true => {
+                    lower += 1.6 * (lower - upper);
+                    f_lower = f(lower);
+                }
+                false => {
+                    upper += 1.6 * (upper - lower);
+                    f_upper = f(upper);
+                }
+            }
+        }
+
+        Err(RootFindingError::NotBracketed(lower, upper))
+    }
+
+    /// Bisection on a bracketing interval.
+    pub fn bisection<F>(&self, f: F, lower: f64, upper: f64) -> Result<Root, RootFindingError>
+    where
+        F: Fn(f64) -> f64,
+    {
+        let (mut lower, mut upper) = (lower, upper);
+        let mut f_lower = checked(lower, f(lower))?;
+        let f_upper = checked(upper, f(upper))?;
+
+        if f_lower * f_upper > 0.0 {
+            return Err(RootFindingError::NotBracketed(lower, upper));
+        }
+
+        for iteration in 1..=self.max_iterations {
+            let middle = 0.5 * (lower + upper);
+            let f_middle = checked(middle, f(middle))?;
+
+            if f_middle == 0.0
+                || 0.5 * (upper - lower).abs() <= self.tolerance * (1.0 + middle.abs())
+            {
+                return Ok(Root {
+                    root: middle,
+                    value: f_middle,
+                    iterations: iteration,
+                });
+            }
+            match f_lower * f_middle < 0.0 {
+                true => upper = middle,
+                false => (lower, f_lower) = (middle, f_middle),
+            }
+        }
+
+        Err(RootFindingError::MaxIterations(self.max_iterations))
+    }
+
+    /// Brent's method: inverse quadratic interpolation and secant steps,
+    /// falling back to bisection, on a bracketing interval.
+    pub fn brent<F>(&self, f: F, lower: f64, upper: f64) -> Result<Root, RootFindingError>
+    where
+        F: Fn(f64) -> f64,
+    {
+        let (mut a, mut b) = (lower, upper);
+        let mut fa = checked(a, f(a))?;
+        let mut fb = checked(b, f(b))?;
+
+        if fa * fb > 0.0 {
+            return Err(RootFindingError::NotBracketed(lower, upper));
+        }
+
+        let (mut c, mut fc) = (b, fb);
+        let (mut d, mut e) = (b - a, b - a);
+
+        for iteration in 1..=self.max_iterations {
+            // Keep the root between b and c, with b the best estimate.
+            if fb * fc > 0.0 {
+                (c, fc) = (a, fa);
+                d = b - a;
+                e = d;
+            }
+            if fc.abs() < fb.abs() {
+                (a, fa) = (b, fb);
+                (b, fb) = (c, fc);
+                (c, fc) = (a, fa);
+            }
+
+            let tolerance = 2.0 * f64::EPSILON * b.abs() + 0.5 * self.tolerance * (1.0 + b.abs());
+            let middle = 0.5 * (c - b);
+
+            if middle.abs() <= tolerance || fb == 0.0 {
+                return Ok(Root {
+                    root: b,
+                    value: fb,
+                    iterations: iteration,
+                });
+            }
+
+            if e.abs() >= tolerance && fa.abs() > fb.abs() {
+                // Secant (a == c) or inverse quadratic interpolation.
+                let s = fb / fa;
+                let (mut p, mut q) = match a == c {
+                    true => (2.0 * middle * s, 1.0 - s),
+                    false => {
+                        let (q, r) = (fa / fc, fb / fc);
+                        (
+                            s * (2.0 * middle * q * (q - r) - (b - a) * (r - 1.0)),
+                            (q - 1.0) * (r - 1.0) * (s - 1.0),
+                        )
+                    }
+                };
+                if p > 0.0 {
+                    q = -q;
+                }
+                p = p.abs();
+
+                let bound = (3.0 * middle * q - (tolerance * q).abs()).min((e * q).abs());
+                match 2.0 * p < bound {
+                    true => {
+                        e = d;
+                        d = p / q;
+                    }
+                    false => {
+                        d = middle;
+                        e = d;
+                    }
+                }
+            } else {
+                d = middle;
+                e = d;
+            }
+
+            (a, fa) = (b, fb);
+            b += match d.abs() > tolerance {
+                true => d,
+                false => tolerance.copysign(middle),
+            };
+            fb = checked(b, f(b))?;
+        }
+
+        Err(RootFindingError::MaxIterations(self.max_iterations))
+    }
+
+    /// Newton-Raphson iteration from `guess`; `f` returns the function
+    /// value and its derivative.
+    pub fn newton<F>(&self, f: F, guess: f64) -> Result<Root, RootFindingError>
+    where
+        F: Fn(f64) -> (f64, f64),
+    {
+        let mut x = guess;
+
+        for iteration in 1..=self.max_iterations {
+            let (value, derivative) = f(x);
+            checked(x, value)?;
+            checked(x, derivative)?;
+
+            if value == 0.0 {
+                return Ok(Root {
+                    root: x,
+                    value,
+                    iterations: iteration,
+                });
+            }
+            if derivative == 0.0 {
+                return Err(RootFindingError::ZeroDerivative(x));
+            }
+
+            let step = value / derivative;
+            x -= step;
+
+            if step.abs() <= self.tolerance * (1.0 + x.abs()) {
+                return Ok(Root {
+                    root: x,
+                    value: f(x).0,
+                    iterations: iteration,
+                });
+            }
+        }
+
+        Err(RootFindingError::MaxIterations(self.max_iterations))
+    }
+
+    /// Newton-Raphson safeguarded by a bracketing interval: steps leaving
+    /// the bracket, or not reducing it fast enough, are replaced by
+    /// bisection, so the iteration always converges.
+    pub fn newton_bracketed<F>(
+        &self,
+        f: F,
+        guess: f64,
+        lower: f64,
+        upper: f64,
+    ) -> Result<Root, RootFindingError>
+    where
+        F: Fn(f64) -> (f64, f64),
+    {
+        let f_lower = checked(lower, f(lower).0)?;
+        let f_upper = checked(upper, f(upper).0)?;
+
+        if f_lower * f_upper > 0.0 {
+            return Err(RootFindingError::NotBracketed(lower, upper));
+        }
+        for (x, value) in [(lower, f_lower), (upper, f_upper)] {
+            if value == 0.0 {
+                return Ok(Root {
+                    root: x,
+                    value,
+                    iterations: 0,
+                });
+            }
+        }
+
+        // Orient the bracket so that f(low) < 0 < f(high).
+        let (mut low, mut high) = match f_lower < 0.0 {
+            true => (lower, upper),
+            false => (upper, lower),
+        };
+        let mut x = match (guess - lower) * (guess - upper) < 0.0 {
+            true => guess,
+            false => 0.5 * (lower + upper),
+        };
+        let (mut step, mut previous_step) = ((upper - lower).abs(), (upper - lower).abs());
+        let (mut value, mut derivative) = f(x);
+
+        for iteration in 1..=self.max_iterations {
+            checked(x, value)?;
+
+            let outside =
+                ((x - high) * derivative - value) * ((x - low) * derivative - value) > 0.0;
+            let slow = (2.0 * value).abs() > (previous_step * derivative).abs();
+
+            previous_step = step;
+            match outside || slow || !derivative.is_finite() {
+                true => {
+                    step = 0.5 * (high - low);
+                    x = low + step;
+                }
+                false => {
+                    step = value / derivative;
+                    x -= step;
+                }
+            }
+
+            (value, derivative) = f(x);
+
+            if value == 0.0 || step.abs() <= self.tolerance * (1.0 + x.abs()) {
+                return Ok(Root {
+                    root: x,
+                    value: checked(x, value)?,
+                    iterations: iteration,
+                });
+            }
+            match value < 0.0 {
+                true => low = x,
+                false => high = x,
+            }
+        }
+
+        Err(RootFindingError::MaxIterations(self.max_iterations))
+    }
+
+    /// Halley's method from `guess`, with cubic convergence; `f` returns
+    /// the function value and its first and second derivatives.
+    pub fn halley<F>(&self, f: F, guess: f64) -> Result<Root, RootFindingError>
+    where
+        F: Fn(f64) -> (f64, f64, f64),
+    {
+        let mut x = guess;
+
+        for iteration in 1..=self.max_iterations {
+            let (value, first, second) = f(x);
+            for v in [value, first, second] {
+                checked(x, v)?;
+            }
+
+            if value == 0.0 {
+                return Ok(Root {
+                    root: x,
+                    value,
+                    iterations: iteration,
+                });
+            }
+
+            let denominator = 2.0 * first * first - value * second;
+            if denominator == 0.0 {
+                return Err(RootFindingError::ZeroDerivative(x));
+            }
+
+            let step = 2.0 * value * first / denominator;
+            x -= step;
+
+            if step.abs() <= self.tolerance * (1.0 + x.abs()) {
+                return Ok(Root {
+                    root: x,
+                    value: f(x).0,
+                    iterations: iteration,
+                });
+            }
+        }
+
+        Err(RootFindingError::MaxIterations(self.max_iterations))
+    }
+}
+
+// Passes finite function values through.
+fn checked(x: f64, value: f64) -> Result<f64, RootFindingError> {
+    match value.is_finite() {
+        true => Ok(value),
+        false => Err(RootFindingError::NonFinite(x)),
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_rootfind {
+    use super::*;
+
+    // Kepler's equation, E - 0.8 sin E = 1, with root E = 1.7903...
+    fn kepler(x: f64) -> f64 {
+        x - 0.8 * x.sin() - 1.0
+    }
+
+    #[test]
+    fn test_bracketing_solvers() {
+        let solver = RootFinder::default();
+
+        let brent = solver.brent(kepler, 0.0, 4.0).unwrap();
+        let bisection = solver.bisection(kepler, 0.0, 4.0).unwrap();
+
+        assert_approx_equal!(brent.root, bisection.root, 1e-11);
+        assert!(brent.value.abs() < 1e-12);
+        assert!(brent.iterations < bisection.iterations / 2);
+
+        // Roots at the ends, and unbracketed intervals.
+        assert_eq!(solver.brent(|x| x - 1.0, 1.0, 2.0).unwrap().root, 1.0);
+        assert_eq!(
+            solver.brent(kepler, 2.0, 4.0),
+            Err(RootFindingError::NotBracketed(2.0, 4.0))
+        );
+
+        let (lower, upper) = solver.bracket(kepler, 2.0, 2.5).unwrap();
+        assert!(kepler(lower) * kepler(upper) <= 0.0);
+        assert!(solver.bracket(|x| x * x + 1.0, 0.0, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_derivative_solvers() {
+        let solver = RootFinder::default();
+        let expected = solver.brent(kepler, 0.0, 4.0).unwrap().root;
+
+        let newton = solver
+            .newton(|x| (kepler(x), 1.0 - 0.8 * x.cos()), 1.0)
+            .unwrap();
+        let halley = solver
+            .halley(|x| (kepler(x), 1.0 - 0.8 * x.cos(), 0.8 * x.sin()), 1.0)
+            .unwrap();
+
+        assert_approx_equal!(newton.root, expected, 1e-12);
+        assert_approx_equal!(halley.root, expected, 1e-12);
+        assert!(halley.iterations <= newton.iterations);
+
+        // Plain Newton diverges on arctan from far away; the bracketed
+        // version converges.
+        let arctan = |x: f64| (x.atan(), 1.0 / (1.0 + x * x));
+        if let Ok(root) = solver.newton(arctan, 3.0) {
+            assert!(root.root.abs() > 1.0);
+        }
+        let root = solver.newton_bracketed(arctan, 3.0, -2.0, 5.0).unwrap();
+        assert_approx_equal!(root.root, 0.0, 1e-12);
+
+        assert_eq!(
+            solver.newton(|x| (x * x + 1.0, 0.0), 1.0),
+            Err(RootFindingError::ZeroDerivative(1.0))
+        );
+    }
+}
@@ -84,7 +84,6 @@ where
 #[cfg(test)]
 mod tests_sequences {
     use super::*;
-    use crate::assert_approx_equal;
 
     #[test]
     fn test_seq_f64() {
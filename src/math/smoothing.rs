@@ -0,0 +1,597 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Penalized B-spline smoothing (P-splines, Eilers and Marx, 1996).
+//!
+//! Noisy data such as bootstrapped zero rates or implied volatility quotes
+//! are fitted by a B-spline on equally spaced knots with a generous number
+//! of segments, and a penalty on the differences of adjacent coefficients
+//! controls the roughness:
+//!
+//! $$ \min_a \sum_i w_i (y_i - (B a)_i)^2 + \lambda \| D_d a \|^2 $$
+//!
+//! The smoothing parameter $\lambda$ is either fixed or chosen by
+//! minimising the generalized cross-validation (GCV) score. A fit
+//! implements [`Interpolator`], so it can be evaluated, differentiated and
+//! extrapolated like the interpolators.
+//!
+//! ```
+//! use RustQuant::math::smoothing::PSpline;
+//! use RustQuant::math::Interpolator;
+//!
+//! // Noisy zero rates by maturity in years.
+//! let maturities = [0.25, 0.5, 1.0, 2.0, 3.0, 5.0, 7.0, 10.0, 15.0, 20.0, 30.0];
+//! let rates = [0.0412, 0.0425, 0.0419, 0.0401, 0.0409, 0.0398, 0.0411, 0.0420, 0.0416, 0.0431, 0.0425];
+//!
+//! let fit = PSpline::new().fit(&maturities, &rates).unwrap();
+//! println!("Smoothed 4y rate: {}", fit.interpolate(4.0).unwrap());
+//! println!("Effective degrees of freedom: {}", fit.effective_dimension);
+//! ```
+
+use super::interpolation::{Extrapolation, Interpolator};
+use nalgebra::{DMatrix, DVector};
+use thiserror::Error;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Smoothing errors.
+#[derive(Debug, Clone, Copy, PartialEq, Error)]
+pub enum SmoothingError {
+    /// The abscissae, ordinates and weights have different lengths.
+    #[error("The abscissae, ordinates and weights have different lengths.")]
+    LengthMismatch,
+
+    /// Not enough distinct points for the penalty order.
+    #[error("At least {0} distinct points are needed.")]
+    TooFewPoints(usize),
+
+    /// A point or weight is not finite, or a weight is negative.
+    #[error("The data and weights must be finite, with non-negative weights.")]
+    InvalidData,
+
+    /// The penalized normal equations are singular.
+    #[error("The penalized normal equations are singular.")]
+    Singular,
+}
+
+/// How the smoothing parameter $\lambda$ is chosen.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SmoothingParameter {
+    /// A fixed $\lambda \geq 0$.
+    Fixed(f64),
+
+    /// Minimise the generalized cross-validation score over $\lambda$.
+    #[default]
+    GeneralizedCrossValidation,
+}
+
+/// P-spline smoother.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PSpline {
+    /// Number of equally spaced knot intervals over the data (default 20).
+    pub segments: usize,
+    /// Degree of the B-splines (default 3, cubic).
+    pub degree: usize,
+    /// Order of the coefficient differences penalised (default 2). As
+    /// $\lambda \to \infty$ the fit tends to a polynomial of degree
+    /// `penalty_order - 1`.
+    pub penalty_order: usize,
+    /// Smoothing parameter (default GCV).
+    pub smoothing: SmoothingParameter,
+    /// Extrapolation policy of the fits.
+    pub extrapolation: Extrapolation,
+}
+
+/// Fitted P-spline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PSplineFit {
+    /// Smoothing parameter used.
+    pub lambda: f64,
+    /// Effective degrees of freedom, the trace of the hat matrix.
+    pub effective_dimension: f64,
+    /// Generalized cross-validation score.
+    pub gcv: f64,
+    /// B-spline coefficients.
+    pub coefficients: Vec<f64>,
+    basis: UniformBSpline,
+    xs: Vec<f64>,
+    fitted: Vec<f64>,
+    extrapolation: Extrapolation,
+}
+
+/// B-splines of a given degree on equally spaced knots.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct UniformBSpline {
+    lower: f64,
+    spacing: f64,
+    segments: usize,
+    degree: usize,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Default for PSpline {
+    fn default() -> Self {
+        Self {
+            segments: 20,
+            degree: 3,
+            penalty_order: 2,
+            smoothing: SmoothingParameter::default(),
+            extrapolation: Extrapolation::default(),
+        }
+    }
+}
+
+impl PSpline {
+    /// Cubic P-spline on 20 segments with a second order penalty, smoothed
+    /// by GCV.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the number of knot intervals.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `segments` is zero.
+    pub fn with_segments(mut self, segments: usize) -> Self {
+        assert!(segments > 0, "At least one segment is needed.");
+        self.segments = segments;
+        self
+    }
+
+    /// Set the degree of the B-splines.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `degree` is zero.
+    pub fn with_degree(mut self, degree: usize) -> Self {
+        assert!(degree > 0, "The degree must be at least one.");
+        self.degree = degree;
+        self
+    }
+
+    /// Set the order of the difference penalty.
+    pub fn with_penalty_order(mut self, penalty_order: usize) -> Self {
+        self.penalty_order = penalty_order;
+        self
+    }
+
+    /// Use a fixed smoothing parameter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lambda` is negative or not finite.
+    pub fn with_lambda(mut self, lambda: f64) -> Self {
+        assert!(
+            lambda.is_finite() && lambda >= 0.0,
+            "The smoothing parameter must be finite and non-negative."
+        );
+        self.smoothing = SmoothingParameter::Fixed(lambda);
+        self
+    }
+
+    /// Choose the smoothing parameter by generalized cross-validation.
+    pub fn with_gcv(mut self) -> Self {
+        self.smoothing = SmoothingParameter::GeneralizedCrossValidation;
+        self
+    }
+
+    /// Set the extrapolation policy of the fits.
+    pub fn with_extrapolation(mut self, extrapolation: Extrapolation) -> Self {
+        self.extrapolation = extrapolation;
+        self
+    }
+
+    /// Fit the data with unit weights.
+    pub fn fit(&self, xs: &[f64], ys: &[f64]) -> Result<PSplineFit, SmoothingError> {
+        self.fit_weighted(xs, ys, &vec![1.0; xs.len()])
+    }
+
+    /// Fit the data with the given weights, e.g. inverse variances of the
+    /// quotes (or vegas for a volatility smile).
+    pub fn fit_weighted(
+        &self,
+        xs: &[f64],
+        ys: &[f64],
+        weights: &[f64],
+    ) -> Result<PSplineFit, SmoothingError> {
+        if xs.len() != ys.len() || xs.len() != weights.len() {
+            return Err(SmoothingError::LengthMismatch);
+        }
+        let valid = |v: &f64| v.is_finite();
+        if !xs.iter().all(valid) || !ys.iter().all(valid) || !weights.iter().all(valid) {
+            return Err(SmoothingError::InvalidData);
+        }
+        if weights.iter().any(|&w| w < 0.0) {
+            return Err(SmoothingError::InvalidData);
+        }
+
+        let mut unique = xs.to_vec();
+        unique.sort_by(f64::total_cmp);
+        unique.dedup();
+
+        let needed = self.penalty_order.max(1) + 1;
+        if unique.len() < needed {
+            return Err(SmoothingError::TooFewPoints(needed));
+        }
+
+        let (lower, upper) = (unique[0], unique[unique.len() - 1]);
+        let basis = UniformBSpline {
+            lower,
+            spacing: (upper - lower) / self.segments as f64,
+            segments: self.segments,
+            degree: self.degree,
+        };
+
+        // Weighted normal equations and the difference penalty.
+        let k = basis.len();
+        let mut gram = DMatrix::zeros(k, k);
+        let mut rhs = DVector::zeros(k);
+
+        for ((&x, &y), &w) in xs.iter().zip(ys).zip(weights) {
+            let (start, values) = basis.values(x, basis.degree);
+            for (a, &va) in values.iter().enumerate() {
+                rhs[start + a] += w * va * y;
+                for (b, &vb) in values.iter().enumerate() {
+                    gram[(start + a, start + b)] += w * va * vb;
+                }
+            }
+        }
+
+        let differences = difference_matrix(k, self.penalty_order.min(k - 1));
+        let penalty = differences.transpose() * &differences;
+
+        let problem = Problem {
+            basis: &basis,
+            gram: &gram,
+            rhs: &rhs,
+            penalty: &penalty,
+            xs,
+            ys,
+            weights,
+        };
+
+        let lambda = match self.smoothing {
+            SmoothingParameter::Fixed(lambda) => lambda,
+            SmoothingParameter::GeneralizedCrossValidation => problem.gcv_lambda()?,
+        };
+        let solution = problem.solve(lambda)?;
+
+        let fitted = unique
+            .iter()
+            .map(|&x| basis.value(&solution.coefficients, x))
+            .collect();
+
+        Ok(PSplineFit {
+            lambda,
+            effective_dimension: solution.effective_dimension,
+            gcv: solution.gcv,
+            coefficients: solution.coefficients,
+            basis,
+            xs: unique,
+            fitted,
+            extrapolation: self.extrapolation,
+        })
+    }
+}
+
+impl PSplineFit {
+    /// Value of the smoothed curve at `x`, with the B-spline extended
+    /// polynomially outside the data (ignoring the extrapolation policy).
+    pub fn value(&self, x: f64) -> f64 {
+        self.basis.value(&self.coefficients, x)
+    }
+}
+
+impl Interpolator for PSplineFit {
+    /// Distinct abscissae of the data, in increasing order.
+    fn xs(&self) -> &[f64] {
+        &self.xs
+    }
+
+    /// Smoothed values at [`Interpolator::xs`].
+    fn ys(&self) -> &[f64] {
+        &self.fitted
+    }
+
+    fn extrapolation(&self) -> Extrapolation {
+        self.extrapolation
+    }
+
+    fn evaluate(&self, x: f64) -> (f64, f64) {
+        (
+            self.basis.value(&self.coefficients, x),
+            self.basis.derivative(&self.coefficients, x),
+        )
+    }
+}
+
+/// Penalized least-squares problem for a given basis and data.
+struct Problem<'a> {
+    basis: &'a UniformBSpline,
+    gram: &'a DMatrix<f64>,
+    rhs: &'a DVector<f64>,
+    penalty: &'a DMatrix<f64>,
+    xs: &'a [f64],
+    ys: &'a [f64],
+    weights: &'a [f64],
+}
+
+/// Solution of the penalized problem for one $\lambda$.
+struct Solution {
+    coefficients: Vec<f64>,
+    effective_dimension: f64,
+    gcv: f64,
+}
+
+impl Problem<'_> {
+    fn solve(&self, lambda: f64) -> Result<Solution, SmoothingError> {
+        let system = self.gram + self.penalty * lambda;
+        let cholesky = system.cholesky().ok_or(SmoothingError::Singular)?;
+
+        let coefficients = cholesky.solve(self.rhs);
+        let effective_dimension = cholesky.solve(self.gram).trace();
+
+        let residuals: f64 = self
+            .xs
+            .iter()
+            .zip(self.ys)
+            .zip(self.weights)
+            .map(|((&x, &y), &w)| w * (y - self.basis.value(coefficients.as_slice(), x)).powi(2))
+            .sum();
+
+        let n = self.weights.iter().filter(|&&w| w > 0.0).count() as f64;
+        let gcv = n * residuals / (n - effective_dimension).max(f64::EPSILON).powi(2);
+
+        Ok(Solution {
+            coefficients: coefficients.iter().copied().collect(),
+            effective_dimension,
+            gcv,
+        })
+    }
+
+    /// Grid search over $\log_{10} \lambda \in [-8, 8]$, refined by golden
+    /// section search around the best grid point.
+    fn gcv_lambda(&self) -> Result<f64, SmoothingError> {
+        // Scale the grid to the size of the normal equations.
+        let scale = self.gram.trace() / self.penalty.trace().max(f64::MIN_POSITIVE);
+        let score = |log_lambda: f64| {
+            self.solve(scale * 10_f64.powf(log_lambda))
+                .map(|solution| solution.gcv)
+        };
+
+        let grid: Vec<f64> = (0..=64).map(|i| -8.0 + 0.25 * i as f64).collect();
+        let scores = grid
+            .iter()
+            .map(|&l| score(l))
+            .collect::<Result<Vec<_>, _>>()?;
+        let best = (0..grid.len())
+            .min_by(|&i, &j| scores[i].total_cmp(&scores[j]))
+            .unwrap_or(0);
+
+        let (mut a, mut b) = (
+            grid[best.saturating_sub(1)],
+            grid[(best + 1).min(grid.len() - 1)],
+        );
+        let ratio = 0.5 * (5_f64.sqrt() - 1.0);
+
+        for _ in 0..40 {
+            let c = b - ratio * (b - a);
+            let d = a + ratio * (b - a);
+            if score(c)? < score(d)? {
+                b = d;
+            } else {
+                a = c;
+            }
+        }
+
+        Ok(scale * 10_f64.powf(0.5 * (a + b)))
+    }
+}
+
+impl UniformBSpline {
+    /// Number of basis functions.
+    fn len(&self) -> usize {
+        self.segments + self.degree
+    }
+
+    /// Index of the first non-zero basis function at `x`, and the values of
+    /// the `degree + 1` non-zero B-splines of degree `degree` there (Cox-de
+    /// Boor recursion). Outside the knot range the end polynomial pieces
+    /// are extended.
+    fn values(&self, x: f64, degree: usize) -> (usize, Vec<f64>) {
+        let segment = if self.spacing > 0.0 {
+            ((x - self.lower) / self.spacing).floor()
+        } else {
+            0.0
+        };
+        let segment = segment.clamp(0.0, (self.segments - 1) as f64) as usize;
+
+        // Knot t_j = lower + (j - self.degree) * spacing; the span of x is
+        // [t_s, t_{s+1}] with s = segment + self.degree.
+        let knot = |j: isize| self.lower + (j - self.degree as isize) as f64 * self.spacing;
+        let span = (segment + self.degree) as isize;
+
+        let mut values = vec![0.0; degree + 1];
+        let mut left = vec![0.0; degree + 1];
+        let mut right = vec![0.0; degree + 1];
+        values[0] = 1.0;
+
+        for j in 1..=degree {
+            left[j] = x - knot(span + 1 - j as isize);
+            right[j] = knot(span + j as isize) - x;
+            let mut saved = 0.0;
+
+            for r in 0..j {
+                let temp = values[r] / (right[r + 1] + left[j - r]);
+                values[r] = saved + right[r + 1] * temp;
+                saved = left[j - r] * temp;
+            }
+            values[j] = saved;
+        }
+
+        (segment + self.degree - degree, values)
+    }
+
+    fn value(&self, coefficients: &[f64], x: f64) -> f64 {
+        let (start, values) = self.values(x, self.degree);
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| v * coefficients[start + i])
+            .sum()
+    }
+
+    /// $\frac{d}{dx} \sum_k a_k B_{k,p} = \sum_k \frac{a_k - a_{k-1}}{h} B_{k,p-1}$
+    /// on knots with spacing $h$.
+    fn derivative(&self, coefficients: &[f64], x: f64) -> f64 {
+        if self.spacing == 0.0 {
+            return 0.0;
+        }
+
+        let (start, values) = self.values(x, self.degree - 1);
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                let k = start + i;
+                v * (coefficients[k] - coefficients[k - 1]) / self.spacing
+            })
+            .sum()
+    }
+}
+
+/// Matrix of `order`-th differences, `(k - order) x k`.
+fn difference_matrix(k: usize, order: usize) -> DMatrix<f64> {
+    let mut matrix = DMatrix::<f64>::identity(k, k);
+
+    for _ in 0..order {
+        let rows = matrix.nrows();
+        matrix = DMatrix::from_fn(rows - 1, k, |i, j| matrix[(i + 1, j)] - matrix[(i, j)]);
+    }
+
+    matrix
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_smoothing {
+    use super::*;
+    use crate::math::interpolation::InterpolationError;
+    use crate::math::Pcg64;
+    use rand::{Rng, SeedableRng};
+    use rand_distr::StandardNormal;
+
+    #[test]
+    fn test_partition_of_unity() {
+        let basis = UniformBSpline {
+            lower: 1.0,
+            spacing: 0.5,
+            segments: 6,
+            degree: 3,
+        };
+
+        for x in [1.0, 1.3, 2.0, 3.7, 4.0] {
+            let (_, values) = basis.values(x, 3);
+            assert_approx_equal!(values.iter().sum::<f64>(), 1.0, 1e-14);
+            assert!(values.iter().all(|&v| v >= 0.0));
+        }
+    }
+
+    #[test]
+    fn test_linear_data_is_unpenalised() {
+        let xs: Vec<f64> = (0..15).map(|i| i as f64 * 0.7).collect();
+        let ys: Vec<f64> = xs.iter().map(|x| 2.0 * x + 1.0).collect();
+
+        let fit = PSpline::new().with_lambda(1e6).fit(&xs, &ys).unwrap();
+
+        assert_approx_equal!(fit.interpolate(3.3).unwrap(), 7.6, 1e-8);
+        assert_approx_equal!(fit.derivative(3.3).unwrap(), 2.0, 1e-8);
+        assert_approx_equal!(fit.effective_dimension, 2.0, 1e-3);
+    }
+
+    #[test]
+    fn test_gcv_smooths_noise() {
+        let mut rng = Pcg64::seed_from_u64(11);
+        let xs: Vec<f64> = (0..200).map(|i| i as f64 * 0.05).collect();
+        let ys: Vec<f64> = xs
+            .iter()
+            .map(|x| x.sin() + 0.2 * rng.sample::<f64, _>(StandardNormal))
+            .collect();
+
+        let fit = PSpline::new().fit(&xs, &ys).unwrap();
+
+        let error = xs
+            .iter()
+            .map(|&x| (fit.interpolate(x).unwrap() - x.sin()).abs())
+            .fold(0.0, f64::max);
+        assert!(error < 0.12, "max error {error}");
+        assert!(fit.effective_dimension > 3.0 && fit.effective_dimension < 15.0);
+
+        // GCV should beat both a very rough and a very stiff fit.
+        let rough = PSpline::new().with_lambda(1e-8).fit(&xs, &ys).unwrap();
+        let stiff = PSpline::new().with_lambda(1e8).fit(&xs, &ys).unwrap();
+        assert!(fit.gcv <= rough.gcv && fit.gcv <= stiff.gcv);
+        assert!(rough.effective_dimension > fit.effective_dimension);
+        assert!(stiff.effective_dimension < fit.effective_dimension);
+    }
+
+    #[test]
+    fn test_weights_and_extrapolation() {
+        let xs: Vec<f64> = (0..=16).map(|i| 0.25 * i as f64).collect();
+        let ys: Vec<f64> = xs.iter().map(|x| x * x).collect();
+
+        // An outlier with zero weight is ignored.
+        let mut noisy = ys.clone();
+        noisy[5] += 10.0;
+        let mut weights = vec![1.0; xs.len()];
+        weights[5] = 0.0;
+
+        let fit = PSpline::new()
+            .with_segments(4)
+            .with_lambda(0.0)
+            .with_extrapolation(Extrapolation::Error)
+            .fit_weighted(&xs, &noisy, &weights)
+            .unwrap();
+
+        // Cubic B-splines reproduce the quadratic exactly.
+        assert_approx_equal!(fit.interpolate(2.5).unwrap(), 6.25, 1e-8);
+        assert_approx_equal!(fit.value(5.0), 25.0, 1e-8);
+        assert_eq!(
+            fit.interpolate(5.0),
+            Err(InterpolationError::OutOfRange(5.0))
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let spline = PSpline::new();
+
+        assert_eq!(
+            spline.fit(&[1.0, 2.0], &[1.0]),
+            Err(SmoothingError::LengthMismatch)
+        );
+        assert_eq!(
+            spline.fit(&[1.0, 1.0, 2.0], &[1.0, 2.0, 3.0]),
+            Err(SmoothingError::TooFewPoints(3))
+        );
+        assert_eq!(
+            spline.fit(&[1.0, 2.0, f64::NAN], &[1.0, 2.0, 3.0]),
+            Err(SmoothingError::InvalidData)
+        );
+    }
+}
@@ -0,0 +1,156 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::microstructure::LimitOrderBook;
+use crate::trading::order_side::OrderSide;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl LimitOrderBook {
+    /// Price and quantity of the best `levels` levels of one side, best first.
+    pub fn depth(&self, side: OrderSide, levels: usize) -> Vec<(f64, u64)> {
+        self.levels(side)
+            .take(levels)
+            .map(|level| (level.price, level.quantity()))
+            .collect()
+    }
+
+    /// Total quantity in the best `levels` levels of one side.
+    pub fn volume(&self, side: OrderSide, levels: usize) -> u64 {
+        self.levels(side)
+            .take(levels)
+            .map(|level| level.quantity())
+            .sum()
+    }
+
+    /// Total quantity of one side priced within `distance` of the mid price.
+    pub fn depth_within(&self, side: OrderSide, distance: f64) -> u64 {
+        let Some(mid) = self.mid_price() else {
+            return 0;
+        };
+
+        self.levels(side)
+            .take_while(|level| (level.price - mid).abs() <= distance + 1e-9 * mid)
+            .map(|level| level.quantity())
+            .sum()
+    }
+
+    /// Order imbalance of the best `levels` levels,
+    /// $(V^b - V^a) / (V^b + V^a)$, between -1 (only asks) and 1 (only bids).
+    pub fn imbalance(&self, levels: usize) -> Option<f64> {
+        let bids = self.volume(OrderSide::BID, levels) as f64;
+        let asks = self.volume(OrderSide::ASK, levels) as f64;
+
+        match bids + asks > 0.0 {
+            true => Some((bids - asks) / (bids + asks)),
+            false => None,
+        }
+    }
+
+    /// Microprice, the mid price weighted by the opposite quantities at the
+    /// top of the book, $(P^b Q^a + P^a Q^b) / (Q^b + Q^a)$: it leans
+    /// towards the ask when the bid queue is larger, and vice versa.
+    pub fn microprice(&self) -> Option<f64> {
+        let (bid, ask) = (self.best_bid()?, self.best_ask()?);
+        let (bid_quantity, ask_quantity) = (bid.quantity() as f64, ask.quantity() as f64);
+
+        Some((bid.price * ask_quantity + ask.price * bid_quantity) / (bid_quantity + ask_quantity))
+    }
+
+    /// Average price of a market order for `quantity` on `side` that
+    /// sweeps the opposite side of the book, or `None` if the book is not
+    /// deep enough.
+    pub fn sweep_price(&self, side: OrderSide, quantity: u64) -> Option<f64> {
+        let mut remaining = quantity;
+        let mut notional = 0.0;
+
+        for level in self.levels(!side) {
+            let size = remaining.min(level.quantity());
+            notional += size as f64 * level.price;
+            remaining -= size;
+
+            if remaining == 0 {
+                return Some(notional / quantity as f64);
+            }
+        }
+
+        None
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_analytics {
+    use super::*;
+
+    fn book() -> LimitOrderBook {
+        let mut book = LimitOrderBook::new(0.01);
+
+        book.insert(1, OrderSide::BID, 9.99, 300).unwrap();
+        book.insert(2, OrderSide::BID, 9.98, 200).unwrap();
+        book.insert(3, OrderSide::BID, 9.95, 500).unwrap();
+        book.insert(4, OrderSide::ASK, 10.01, 100).unwrap();
+        book.insert(5, OrderSide::ASK, 10.03, 400).unwrap();
+
+        book
+    }
+
+    #[test]
+    fn test_depth() {
+        let book = book();
+
+        let depth = book.depth(OrderSide::BID, 2);
+        assert_eq!(depth.len(), 2);
+        assert_approx_equal!(depth[1].0, 9.98, 1e-12);
+        assert_eq!(depth[1].1, 200);
+        assert_eq!(book.depth(OrderSide::ASK, 10).len(), 2);
+
+        assert_eq!(book.volume(OrderSide::BID, 2), 500);
+        assert_eq!(book.depth_within(OrderSide::BID, 0.02), 500);
+        assert_eq!(book.depth_within(OrderSide::ASK, 0.01), 100);
+    }
+
+    #[test]
+    fn test_imbalance_and_microprice() {
+        let book = book();
+
+        assert_approx_equal!(book.imbalance(1).unwrap(), 0.5, 1e-12);
+        assert_approx_equal!(book.imbalance(2).unwrap(), 0.0, 1e-12);
+
+        // Larger bid queue: the microprice is above the mid.
+        let microprice = book.microprice().unwrap();
+        assert_approx_equal!(microprice, (9.99 * 100.0 + 10.01 * 300.0) / 400.0, 1e-12);
+        assert!(microprice > book.mid_price().unwrap());
+
+        assert!(LimitOrderBook::new(0.01).imbalance(1).is_none());
+    }
+
+    #[test]
+    fn test_sweep_price() {
+        let book = book();
+
+        assert_approx_equal!(book.sweep_price(OrderSide::BID, 100).unwrap(), 10.01, 1e-12);
+        assert_approx_equal!(
+            book.sweep_price(OrderSide::BID, 200).unwrap(),
+            0.5 * (10.01 + 10.03),
+            1e-12
+        );
+        assert_approx_equal!(
+            book.sweep_price(OrderSide::ASK, 400).unwrap(),
+            9.9875,
+            1e-12
+        );
+        assert!(book.sweep_price(OrderSide::BID, 501).is_none());
+    }
+}
@@ -0,0 +1,438 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::trading::{order::OrderID, order_side::OrderSide};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use thiserror::Error;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// An order resting in the book.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RestingOrder {
+    /// Order identifier.
+    pub id: OrderID,
+
+    /// Remaining quantity.
+    pub quantity: u64,
+
+    /// Arrival sequence number (time priority).
+    pub sequence: u64,
+}
+
+/// All orders resting at one price, in order of arrival.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceLevel {
+    /// Price of the level.
+    pub price: f64,
+
+    pub(crate) ticks: i64,
+    orders: VecDeque<RestingOrder>,
+    quantity: u64,
+}
+
+/// Limit order book: bid and ask price levels on a tick grid, each level
+/// a FIFO queue of resting orders.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LimitOrderBook {
+    tick_size: f64,
+    bids: BTreeMap<i64, PriceLevel>,
+    asks: BTreeMap<i64, PriceLevel>,
+    orders: HashMap<OrderID, (OrderSide, i64)>,
+    sequence: u64,
+}
+
+/// Order book and matching errors.
+#[derive(Debug, Clone, Copy, PartialEq, Error)]
+pub enum MicrostructureError {
+    /// An order with the same identifier is already in the book.
+    #[error("Order {0} is already in the book.")]
+    DuplicateOrder(OrderID),
+
+    /// No order with the identifier is in the book.
+    #[error("Order {0} is not in the book.")]
+    UnknownOrder(OrderID),
+
+    /// The price is not a positive multiple of the tick size.
+    #[error("Price {0} is not on the tick grid.")]
+    InvalidPrice(f64),
+
+    /// Orders must be for a positive quantity.
+    #[error("Order quantity must be positive.")]
+    ZeroQuantity,
+
+    /// The time in force is not supported by the matching engine.
+    #[error("Unsupported time in force: {0}.")]
+    UnsupportedTimeInForce(crate::trading::order_lifespan::OrderTimeInForce),
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl PriceLevel {
+    fn new(ticks: i64, tick_size: f64) -> Self {
+        Self {
+            price: ticks as f64 * tick_size,
+            ticks,
+            orders: VecDeque::new(),
+            quantity: 0,
+        }
+    }
+
+    /// Total quantity resting at the level.
+    pub fn quantity(&self) -> u64 {
+        self.quantity
+    }
+
+    /// Number of orders at the level.
+    pub fn len(&self) -> usize {
+        self.orders.len()
+    }
+
+    /// Whether the level has no orders.
+    pub fn is_empty(&self) -> bool {
+        self.orders.is_empty()
+    }
+
+    /// Orders at the level, first in the queue first.
+    pub fn orders(&self) -> impl Iterator<Item = &RestingOrder> {
+        self.orders.iter()
+    }
+
+    /// Fills up to `quantity` against the front of the queue, calling
+    /// `on_fill` with each order's identifier and filled quantity.
+    /// Returns the quantity filled.
+    pub(crate) fn fill(
+        &mut self,
+        quantity: u64,
+        mut on_fill: impl FnMut(OrderID, u64, bool),
+    ) -> u64 {
+        let mut filled = 0;
+
+        while filled < quantity {
+            let Some(front) = self.orders.front_mut() else {
+                break;
+            };
+
+            let size = front.quantity.min(quantity - filled);
+            front.quantity -= size;
+            filled += size;
+
+            let done = front.quantity == 0;
+            on_fill(front.id, size, done);
+
+            if done {
+                self.orders.pop_front();
+            }
+        }
+
+        self.quantity -= filled;
+        filled
+    }
+}
+
+impl LimitOrderBook {
+    /// Empty book with prices on a grid of `tick_size`.
+    pub fn new(tick_size: f64) -> Self {
+        assert!(tick_size > 0.0, "Tick size must be positive.");
+
+        Self {
+            tick_size,
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+            orders: HashMap::new(),
+            sequence: 0,
+        }
+    }
+
+    /// Tick size.
+    pub fn tick_size(&self) -> f64 {
+        self.tick_size
+    }
+
+    /// Number of resting orders.
+    pub fn len(&self) -> usize {
+        self.orders.len()
+    }
+
+    /// Whether the book has no resting orders.
+    pub fn is_empty(&self) -> bool {
+        self.orders.is_empty()
+    }
+
+    /// Whether an order is resting in the book.
+    pub fn contains(&self, id: OrderID) -> bool {
+        self.orders.contains_key(&id)
+    }
+
+    /// Adds an order to the back of the queue at its price, without
+    /// matching it against the other side (see [`MatchingEngine`] for that).
+    ///
+    /// [`MatchingEngine`]: crate::microstructure::MatchingEngine
+    pub fn insert(
+        &mut self,
+        id: OrderID,
+        side: OrderSide,
+        price: f64,
+        quantity: u64,
+    ) -> Result<(), MicrostructureError> {
+        let ticks = self.ticks(price)?;
+
+        if quantity == 0 {
+            return Err(MicrostructureError::ZeroQuantity);
+        }
+        if self.orders.contains_key(&id) {
+            return Err(MicrostructureError::DuplicateOrder(id));
+        }
+
+        let (tick_size, sequence) = (self.tick_size, self.sequence);
+        let level = self
+            .side_mut(side)
+            .entry(ticks)
+            .or_insert_with(|| PriceLevel::new(ticks, tick_size));
+
+        level.orders.push_back(RestingOrder {
+            id,
+            quantity,
+            sequence,
+        });
+        level.quantity += quantity;
+
+        self.sequence += 1;
+        self.orders.insert(id, (side, ticks));
+
+        Ok(())
+    }
+
+    /// Removes an order from the book.
+    pub fn cancel(&mut self, id: OrderID) -> Result<RestingOrder, MicrostructureError> {
+        let (side, ticks) = self
+            .orders
+            .remove(&id)
+            .ok_or(MicrostructureError::UnknownOrder(id))?;
+
+        let levels = self.side_mut(side);
+        let level = levels
+            .get_mut(&ticks)
+            .expect("Every indexed order has a price level.");
+        let position = level
+            .orders
+            .iter()
+            .position(|order| order.id == id)
+            .expect("Every indexed order is queued at its level.");
+        let order = level
+            .orders
+            .remove(position)
+            .expect("The position is in the queue.");
+
+        level.quantity -= order.quantity;
+        if level.is_empty() {
+            levels.remove(&ticks);
+        }
+
+        Ok(order)
+    }
+
+    /// Reduces the quantity of an order by `quantity`, keeping its place
+    /// in the queue, and cancels it if nothing is left.
+    pub fn reduce(&mut self, id: OrderID, quantity: u64) -> Result<(), MicrostructureError> {
+        let (side, ticks) = *self
+            .orders
+            .get(&id)
+            .ok_or(MicrostructureError::UnknownOrder(id))?;
+
+        let level = self
+            .side_mut(side)
+            .get_mut(&ticks)
+            .expect("Every indexed order has a price level.");
+        let order = level
+            .orders
+            .iter_mut()
+            .find(|order| order.id == id)
+            .expect("Every indexed order is queued at its level.");
+
+        match quantity < order.quantity {
+            true => {
+                order.quantity -= quantity;
+                level.quantity -= quantity;
+                Ok(())
+            }
+            false => self.cancel(id).map(|_| ()),
+        }
+    }
+
+    /// Side, price and remaining quantity of a resting order.
+    pub fn order(&self, id: OrderID) -> Option<(OrderSide, f64, u64)> {
+        let (side, ticks) = *self.orders.get(&id)?;
+        let level = self.side(side).get(&ticks)?;
+
+        level
+            .orders()
+            .find(|order| order.id == id)
+            .map(|order| (side, level.price, order.quantity))
+    }
+
+    /// Highest bid level.
+    pub fn best_bid(&self) -> Option<&PriceLevel> {
+        self.bids.values().next_back()
+    }
+
+    /// Lowest ask level.
+    pub fn best_ask(&self) -> Option<&PriceLevel> {
+        self.asks.values().next()
+    }
+
+    /// Price levels of one side, best first.
+    pub fn levels(&self, side: OrderSide) -> Box<dyn Iterator<Item = &PriceLevel> + '_> {
+        match side {
+            OrderSide::BID => Box::new(self.bids.values().rev()),
+            OrderSide::ASK => Box::new(self.asks.values()),
+        }
+    }
+
+    /// Best ask minus best bid.
+    pub fn spread(&self) -> Option<f64> {
+        Some(self.best_ask()?.price - self.best_bid()?.price)
+    }
+
+    /// Average of the best bid and ask.
+    pub fn mid_price(&self) -> Option<f64> {
+        Some(0.5 * (self.best_ask()?.price + self.best_bid()?.price))
+    }
+
+    /// Price in ticks, if it is a positive multiple of the tick size.
+    pub(crate) fn ticks(&self, price: f64) -> Result<i64, MicrostructureError> {
+        let ticks = (price / self.tick_size).round();
+
+        match ticks >= 1.0 && (ticks * self.tick_size - price).abs() <= 1e-9 * price.abs() {
+            true => Ok(ticks as i64),
+            false => Err(MicrostructureError::InvalidPrice(price)),
+        }
+    }
+
+    /// Best level of one side, with its price in ticks.
+    pub(crate) fn best_mut(&mut self, side: OrderSide) -> Option<(i64, &mut PriceLevel)> {
+        match side {
+            OrderSide::BID => self.bids.iter_mut().next_back(),
+            OrderSide::ASK => self.asks.iter_mut().next(),
+        }
+        .map(|(ticks, level)| (*ticks, level))
+    }
+
+    /// Removes a filled order from the index.
+    pub(crate) fn forget(&mut self, id: OrderID) {
+        self.orders.remove(&id);
+    }
+
+    /// Removes an empty level.
+    pub(crate) fn remove_level(&mut self, side: OrderSide, ticks: i64) {
+        self.side_mut(side).remove(&ticks);
+    }
+
+    fn side(&self, side: OrderSide) -> &BTreeMap<i64, PriceLevel> {
+        match side {
+            OrderSide::BID => &self.bids,
+            OrderSide::ASK => &self.asks,
+        }
+    }
+
+    fn side_mut(&mut self, side: OrderSide) -> &mut BTreeMap<i64, PriceLevel> {
+        match side {
+            OrderSide::BID => &mut self.bids,
+            OrderSide::ASK => &mut self.asks,
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_book {
+    use super::*;
+
+    #[test]
+    fn test_price_levels() {
+        let mut book = LimitOrderBook::new(0.05);
+
+        book.insert(1, OrderSide::BID, 10.00, 100).unwrap();
+        book.insert(2, OrderSide::BID, 10.05, 200).unwrap();
+        book.insert(3, OrderSide::BID, 10.05, 50).unwrap();
+        book.insert(4, OrderSide::ASK, 10.15, 300).unwrap();
+
+        let best_bid = book.best_bid().unwrap();
+        assert_approx_equal!(best_bid.price, 10.05, 1e-12);
+        assert_eq!(best_bid.quantity(), 250);
+
+        // FIFO queue within the level.
+        let ids: Vec<OrderID> = best_bid.orders().map(|order| order.id).collect();
+        assert_eq!(ids, vec![2, 3]);
+
+        let prices: Vec<f64> = book
+            .levels(OrderSide::BID)
+            .map(|level| level.price)
+            .collect();
+        assert_approx_equal!(prices[1], 10.0, 1e-12);
+
+        assert_approx_equal!(book.spread().unwrap(), 0.1, 1e-12);
+        assert_approx_equal!(book.mid_price().unwrap(), 10.1, 1e-12);
+        assert_eq!(book.len(), 4);
+    }
+
+    #[test]
+    fn test_cancel_and_reduce() {
+        let mut book = LimitOrderBook::new(0.01);
+
+        book.insert(1, OrderSide::ASK, 5.0, 100).unwrap();
+        book.insert(2, OrderSide::ASK, 5.0, 100).unwrap();
+        book.insert(3, OrderSide::ASK, 5.01, 100).unwrap();
+
+        // Reducing keeps the queue position.
+        book.reduce(1, 40).unwrap();
+        assert_eq!(book.order(1), Some((OrderSide::ASK, 5.0, 60)));
+        assert_eq!(book.best_ask().unwrap().quantity(), 160);
+        assert_eq!(book.best_ask().unwrap().orders().next().unwrap().id, 1);
+
+        assert_eq!(book.cancel(2).unwrap().quantity, 100);
+        book.reduce(1, 60).unwrap();
+        assert!(!book.contains(1));
+
+        // The emptied level is removed.
+        assert_approx_equal!(book.best_ask().unwrap().price, 5.01, 1e-12);
+        assert_eq!(book.cancel(2), Err(MicrostructureError::UnknownOrder(2)));
+    }
+
+    #[test]
+    fn test_invalid_orders() {
+        let mut book = LimitOrderBook::new(0.01);
+        book.insert(1, OrderSide::BID, 1.0, 1).unwrap();
+
+        assert_eq!(
+            book.insert(1, OrderSide::BID, 1.0, 1),
+            Err(MicrostructureError::DuplicateOrder(1))
+        );
+        assert_eq!(
+            book.insert(2, OrderSide::BID, 1.005, 1),
+            Err(MicrostructureError::InvalidPrice(1.005))
+        );
+        assert_eq!(
+            book.insert(2, OrderSide::BID, 0.0, 1),
+            Err(MicrostructureError::InvalidPrice(0.0))
+        );
+        assert_eq!(
+            book.insert(2, OrderSide::BID, 1.0, 0),
+            Err(MicrostructureError::ZeroQuantity)
+        );
+        assert!(book.spread().is_none());
+    }
+}
@@ -0,0 +1,367 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::microstructure::{LimitOrderBook, MicrostructureError};
+use crate::trading::{order::OrderID, order_lifespan::OrderTimeInForce, order_side::OrderSide};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Order submitted to the matching engine.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NewOrder {
+    /// Order identifier.
+    pub id: OrderID,
+
+    /// Bid (buy) or ask (sell).
+    pub side: OrderSide,
+
+    /// Quantity.
+    pub quantity: u64,
+
+    /// Limit price (`None` for a market order).
+    pub limit_price: Option<f64>,
+
+    /// Time in force: good-till-cancelled (the default), immediate-or-cancel
+    /// or fill-or-kill.
+    pub time_in_force: OrderTimeInForce,
+}
+
+/// Trade between an incoming (taker) order and a resting (maker) order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fill {
+    /// Incoming order.
+    pub taker: OrderID,
+
+    /// Resting order.
+    pub maker: OrderID,
+
+    /// Side of the incoming order.
+    pub side: OrderSide,
+
+    /// Price of the resting order.
+    pub price: f64,
+
+    /// Quantity traded.
+    pub quantity: u64,
+}
+
+/// Outcome of submitting an order.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Execution {
+    /// Fills, in the order they happened.
+    pub fills: Vec<Fill>,
+
+    /// Quantity added to the book.
+    pub resting: u64,
+
+    /// Quantity cancelled (unfilled market, IOC and FOK quantity).
+    pub cancelled: u64,
+}
+
+/// Price-time priority matching engine: incoming orders trade against the
+/// best resting prices first and, at each price, the oldest orders first.
+/// Trades happen at the resting order's price.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchingEngine {
+    book: LimitOrderBook,
+    trades: Vec<Fill>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl NewOrder {
+    /// Good-till-cancelled limit order.
+    pub fn limit(id: OrderID, side: OrderSide, price: f64, quantity: u64) -> Self {
+        Self {
+            id,
+            side,
+            quantity,
+            limit_price: Some(price),
+            time_in_force: OrderTimeInForce::GoodTillCancelled,
+        }
+    }
+
+    /// Market order: whatever cannot be filled is cancelled.
+    pub fn market(id: OrderID, side: OrderSide, quantity: u64) -> Self {
+        Self {
+            id,
+            side,
+            quantity,
+            limit_price: None,
+            time_in_force: OrderTimeInForce::ImmediateOrCancel,
+        }
+    }
+
+    /// Sets the time in force.
+    pub fn with_time_in_force(mut self, time_in_force: OrderTimeInForce) -> Self {
+        self.time_in_force = time_in_force;
+        self
+    }
+}
+
+impl Execution {
+    /// Total quantity filled.
+    pub fn filled(&self) -> u64 {
+        self.fills.iter().map(|fill| fill.quantity).sum()
+    }
+
+    /// Volume-weighted average fill price.
+    pub fn average_price(&self) -> Option<f64> {
+        let filled = self.filled();
+
+        match filled > 0 {
+            true => Some(
+                self.fills
+                    .iter()
+                    .map(|fill| fill.price * fill.quantity as f64)
+                    .sum::<f64>()
+                    / filled as f64,
+            ),
+            false => None,
+        }
+    }
+}
+
+impl MatchingEngine {
+    /// Engine with an empty book on a grid of `tick_size`.
+    pub fn new(tick_size: f64) -> Self {
+        Self::from_book(LimitOrderBook::new(tick_size))
+    }
+
+    /// Engine trading against an existing book.
+    pub fn from_book(book: LimitOrderBook) -> Self {
+        Self {
+            book,
+            trades: Vec::new(),
+        }
+    }
+
+    /// The order book.
+    pub fn book(&self) -> &LimitOrderBook {
+        &self.book
+    }
+
+    /// All fills so far.
+    pub fn trades(&self) -> &[Fill] {
+        &self.trades
+    }
+
+    /// Cancels a resting order.
+    pub fn cancel(&mut self, id: OrderID) -> Result<(), MicrostructureError> {
+        self.book.cancel(id).map(|_| ())
+    }
+
+    /// Matches an order against the book. The unfilled quantity of a
+    /// good-till-cancelled limit order rests in the book; that of any other
+    /// order is cancelled. A fill-or-kill order is cancelled in full unless
+    /// it can be filled in full.
+    pub fn submit(&mut self, order: NewOrder) -> Result<Execution, MicrostructureError> {
+        if order.quantity == 0 {
+            return Err(MicrostructureError::ZeroQuantity);
+        }
+        if self.book.contains(order.id) {
+            return Err(MicrostructureError::DuplicateOrder(order.id));
+        }
+        if order.time_in_force == OrderTimeInForce::AllOrNone {
+            return Err(MicrostructureError::UnsupportedTimeInForce(
+                order.time_in_force,
+            ));
+        }
+
+        // Limit in ticks: the worst price the order accepts.
+        let limit = match order.limit_price {
+            Some(price) => Some(self.book.ticks(price)?),
+            None => None,
+        };
+        let acceptable = |ticks: i64| match (order.side, limit) {
+            (_, None) => true,
+            (OrderSide::BID, Some(limit)) => ticks <= limit,
+            (OrderSide::ASK, Some(limit)) => ticks >= limit,
+        };
+
+        if order.time_in_force == OrderTimeInForce::FillOrKill {
+            let available: u64 = self
+                .book
+                .levels(!order.side)
+                .take_while(|level| acceptable(level.ticks))
+                .map(|level| level.quantity())
+                .sum();
+
+            if available < order.quantity {
+                return Ok(Execution {
+                    cancelled: order.quantity,
+                    ..Execution::default()
+                });
+            }
+        }
+
+        let mut execution = Execution::default();
+        let mut remaining = order.quantity;
+
+        while remaining > 0 {
+            let Some((ticks, level)) = self.book.best_mut(!order.side) else {
+                break;
+            };
+            if !acceptable(ticks) {
+                break;
+            }
+
+            let price = level.price;
+            let mut done = Vec::new();
+            remaining -= level.fill(remaining, |maker, quantity, filled| {
+                execution.fills.push(Fill {
+                    taker: order.id,
+                    maker,
+                    side: order.side,
+                    price,
+                    quantity,
+                });
+                if filled {
+                    done.push(maker);
+                }
+            });
+
+            if level.is_empty() {
+                self.book.remove_level(!order.side, ticks);
+            }
+            for maker in done {
+                self.book.forget(maker);
+            }
+        }
+
+        match (order.limit_price, order.time_in_force) {
+            (Some(price), OrderTimeInForce::GoodTillCancelled) if remaining > 0 => {
+                self.book.insert(order.id, order.side, price, remaining)?;
+                execution.resting = remaining;
+            }
+            _ => execution.cancelled = remaining,
+        }
+
+        self.trades.extend_from_slice(&execution.fills);
+
+        Ok(execution)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_matching {
+    use super::*;
+
+    fn engine() -> MatchingEngine {
+        let mut engine = MatchingEngine::new(0.01);
+
+        for (id, side, price, quantity) in [
+            (1, OrderSide::ASK, 10.02, 100),
+            (2, OrderSide::ASK, 10.01, 100),
+            (3, OrderSide::ASK, 10.01, 50),
+            (4, OrderSide::BID, 9.99, 100),
+            (5, OrderSide::BID, 9.98, 200),
+        ] {
+            engine
+                .submit(NewOrder::limit(id, side, price, quantity))
+                .unwrap();
+        }
+
+        engine
+    }
+
+    #[test]
+    fn test_price_time_priority() {
+        let mut engine = engine();
+        assert!(engine.trades().is_empty());
+
+        // Crossing limit buy: fills 10.01 (orders 2 then 3), then 10.02,
+        // without going through its limit.
+        let execution = engine
+            .submit(NewOrder::limit(6, OrderSide::BID, 10.01, 200))
+            .unwrap();
+
+        let makers: Vec<OrderID> = execution.fills.iter().map(|fill| fill.maker).collect();
+        assert_eq!(makers, vec![2, 3]);
+        assert_eq!(execution.filled(), 150);
+        assert_approx_equal!(execution.average_price().unwrap(), 10.01, 1e-12);
+
+        // The rest of the order is the new best bid.
+        assert_eq!(execution.resting, 50);
+        let book = engine.book();
+        assert_eq!(book.order(6).map(|order| order.2), Some(50));
+        assert_approx_equal!(book.best_bid().unwrap().price, 10.01, 1e-12);
+        assert_approx_equal!(book.best_ask().unwrap().price, 10.02, 1e-12);
+        assert!(!book.contains(2) && !book.contains(3));
+    }
+
+    #[test]
+    fn test_market_orders() {
+        let mut engine = engine();
+
+        // Selling 350 takes all 300 bids: 100 at 9.99 and 200 at 9.98.
+        let execution = engine
+            .submit(NewOrder::market(7, OrderSide::ASK, 350))
+            .unwrap();
+
+        assert_eq!(execution.filled(), 300);
+        assert_eq!(execution.cancelled, 50);
+        assert_eq!(execution.resting, 0);
+        assert_approx_equal!(
+            execution.average_price().unwrap(),
+            (9.99 * 100.0 + 9.98 * 200.0) / 300.0,
+            1e-12
+        );
+        assert!(engine.book().best_bid().is_none());
+        assert_eq!(engine.trades().len(), 2);
+    }
+
+    #[test]
+    fn test_time_in_force() {
+        let mut engine = engine();
+
+        // Fill-or-kill: only 250 available at or below 10.02.
+        let execution = engine
+            .submit(
+                NewOrder::limit(8, OrderSide::BID, 10.02, 300)
+                    .with_time_in_force(OrderTimeInForce::FillOrKill),
+            )
+            .unwrap();
+        assert_eq!(execution.cancelled, 300);
+        assert!(execution.fills.is_empty());
+        assert_eq!(engine.book().len(), 5);
+
+        // Immediate-or-cancel: fills what it can, nothing rests.
+        let execution = engine
+            .submit(
+                NewOrder::limit(9, OrderSide::BID, 10.01, 300)
+                    .with_time_in_force(OrderTimeInForce::ImmediateOrCancel),
+            )
+            .unwrap();
+        assert_eq!(execution.filled(), 150);
+        assert_eq!(execution.cancelled, 150);
+        assert!(!engine.book().contains(9));
+
+        assert_eq!(
+            engine.submit(NewOrder::limit(1, OrderSide::BID, 9.0, 1)),
+            Err(MicrostructureError::DuplicateOrder(1))
+        );
+        assert!(engine
+            .submit(
+                NewOrder::limit(10, OrderSide::BID, 9.0, 1)
+                    .with_time_in_force(OrderTimeInForce::AllOrNone)
+            )
+            .is_err());
+        assert!(engine.cancel(1).is_ok());
+        assert!(engine.cancel(1).is_err());
+    }
+}
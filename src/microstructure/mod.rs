@@ -0,0 +1,50 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Market microstructure: limit order books and order matching.
+//!
+//! - [`LimitOrderBook`]: resting orders in price levels, each a FIFO queue,
+//!   on a tick grid. Orders can be inserted, reduced and cancelled directly,
+//!   e.g. to rebuild the book from order-by-order (L3) market data.
+//! - [`MatchingEngine`]: price-time priority matching of incoming limit and
+//!   market orders against the book, for simple exchange simulation.
+//! - Book analytics: depth by level, order imbalance, the microprice and the
+//!   average price of sweeping the book.
+//!
+//! ```
+//! use RustQuant::microstructure::*;
+//! use RustQuant::trading::order_side::OrderSide;
+//!
+//! let mut engine = MatchingEngine::new(0.01);
+//!
+//! engine.submit(NewOrder::limit(1, OrderSide::ASK, 100.02, 300)).unwrap();
+//! engine.submit(NewOrder::limit(2, OrderSide::ASK, 100.01, 100)).unwrap();
+//! engine.submit(NewOrder::limit(3, OrderSide::BID, 99.99, 200)).unwrap();
+//!
+//! // A marketable buy order sweeps the best ask, then part of the next level.
+//! let execution = engine.submit(NewOrder::market(4, OrderSide::BID, 150)).unwrap();
+//! assert_eq!(execution.filled(), 150);
+//! assert_eq!(execution.fills[0].maker, 2);
+//!
+//! let book = engine.book();
+//! assert_eq!(book.best_ask().map(|level| level.quantity()), Some(250));
+//! assert!((book.spread().unwrap() - 0.03).abs() < 1e-12);
+//! assert!(book.imbalance(1).unwrap() < 0.0);
+//! ```
+
+/// Book analytics: depth, imbalance and microprice.
+pub mod analytics;
+
+/// Limit order book with price levels and FIFO queues.
+pub mod book;
+pub use book::*;
+
+/// Price-time priority matching engine.
+pub mod matching;
+pub use matching::*;
@@ -0,0 +1,702 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Clustering of assets by the correlation of their returns.
+//!
+//! - Correlation distances, $d_{ij} = \sqrt{(1 - \rho_{ij}) / 2}$ (or
+//!   $\sqrt{1 - |\rho_{ij}|}$ to group anti-correlated assets), which are
+//!   metrics on the assets (Mantegna, 1999).
+//! - Agglomerative hierarchical clustering of a distance matrix with single,
+//!   complete, average or Ward linkage. The [`Dendrogram`] gives the
+//!   quasi-diagonal ordering of the assets used by hierarchical risk parity
+//!   (López de Prado, 2016), and flat clusters.
+//! - K-means (with k-means++ seeding) of points, or of the assets of a
+//!   correlation matrix, embedded as points whose squared Euclidean
+//!   distances are $2 (1 - \rho_{ij})$.
+//!
+//! ```rust
+//! use RustQuant::ml::*;
+//! use nalgebra::dmatrix;
+//!
+//! // Two pairs of correlated assets.
+//! let correlation = dmatrix![
+//!     1.0, 0.8, 0.1, 0.0;
+//!     0.8, 1.0, 0.0, 0.1;
+//!     0.1, 0.0, 1.0, 0.7;
+//!     0.0, 0.1, 0.7, 1.0
+//! ];
+//!
+//! let dendrogram =
+//!     hierarchical_clustering(&correlation_distance(&correlation), Linkage::Single).unwrap();
+//! assert_eq!(dendrogram.cut(2), vec![0, 0, 1, 1]);
+//!
+//! let kmeans = KMeans::new(2).fit_correlation(&correlation).unwrap();
+//! assert_eq!(kmeans.labels, vec![0, 0, 1, 1]);
+//! ```
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::math::Pcg64;
+use nalgebra::{DMatrix, DVector};
+use rand::Rng;
+use thiserror::Error;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Clustering errors.
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum ClusteringError {
+    /// The number of clusters is zero or exceeds the number of points.
+    #[error("Cannot form {clusters} clusters from {points} points")]
+    InvalidClusterCount {
+        /// Requested number of clusters.
+        clusters: usize,
+        /// Number of points.
+        points: usize,
+    },
+
+    /// A distance or correlation matrix is not square and symmetric.
+    #[error("Matrix is not square and symmetric")]
+    NotSymmetric,
+}
+
+/// Distance between two clusters, from the distances between their members.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Linkage {
+    /// Minimum distance (nearest neighbours).
+    Single,
+    /// Maximum distance (furthest neighbours).
+    Complete,
+    /// Mean distance (UPGMA).
+    Average,
+    /// Increase in the within-cluster sum of squares (for Euclidean
+    /// distances, such as correlation distances).
+    Ward,
+}
+
+/// A merge of two clusters.
+///
+/// Clusters are numbered as in `scipy`: `0..n` are the points, and the
+/// cluster formed by the `i`-th merge is `n + i`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Merge {
+    /// First merged cluster.
+    pub left: usize,
+    /// Second merged cluster.
+    pub right: usize,
+    /// Linkage distance between them.
+    pub distance: f64,
+    /// Number of points in the merged cluster.
+    pub size: usize,
+}
+
+/// Result of agglomerative hierarchical clustering: the `n - 1` merges of
+/// `n` points, by increasing distance (for the monotone linkages here).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dendrogram {
+    /// Number of points (leaves).
+    pub n_leaves: usize,
+    /// Merges, in order.
+    pub merges: Vec<Merge>,
+}
+
+/// K-means clustering.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KMeans {
+    /// Number of clusters.
+    pub k: usize,
+    /// Maximum number of Lloyd iterations per run.
+    pub max_iterations: usize,
+    /// Number of runs from different seedings (the best is kept).
+    pub restarts: usize,
+    /// Seed of the k-means++ seeding.
+    pub seed: u64,
+}
+
+/// Result of k-means clustering.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KMeansResult {
+    /// Cluster of each point, numbered in order of first appearance.
+    pub labels: Vec<usize>,
+    /// Cluster centres, one row per cluster.
+    pub centroids: DMatrix<f64>,
+    /// Within-cluster sum of squared distances to the centres.
+    pub inertia: f64,
+    /// Lloyd iterations of the best run.
+    pub iterations: usize,
+    /// Whether the best run converged (assignments stopped changing).
+    pub converged: bool,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Sample correlation matrix of the columns of `returns` (one row per
+/// period, one column per asset).
+pub fn correlation_matrix(returns: &DMatrix<f64>) -> DMatrix<f64> {
+    let (n, m) = returns.shape();
+    assert!(n > 1, "At least two observations.");
+
+    let means = returns.row_mean();
+    let centred = DMatrix::from_fn(n, m, |t, j| returns[(t, j)] - means[j]);
+    let covariance = centred.transpose() * &centred / (n - 1) as f64;
+
+    covariance_to_correlation(&covariance)
+}
+
+/// Correlation matrix of a covariance matrix.
+pub fn covariance_to_correlation(covariance: &DMatrix<f64>) -> DMatrix<f64> {
+    let volatilities = covariance.diagonal().map(f64::sqrt);
+
+    DMatrix::from_fn(covariance.nrows(), covariance.ncols(), |i, j| {
+        match i == j {
+            true => 1.0,
+            false => covariance[(i, j)] / (volatilities[i] * volatilities[j]),
+        }
+    })
+}
+
+/// Correlation distance $\sqrt{(1 - \rho_{ij}) / 2}$, in $[0, 1]$: zero for
+/// perfectly correlated and one for perfectly anti-correlated assets.
+pub fn correlation_distance(correlation: &DMatrix<f64>) -> DMatrix<f64> {
+    correlation.map(|rho| (0.5 * (1.0 - rho)).max(0.0).sqrt())
+}
+
+/// Absolute correlation distance $\sqrt{1 - |\rho_{ij}|}$, treating
+/// anti-correlated assets as close (e.g. for hedges).
+pub fn absolute_correlation_distance(correlation: &DMatrix<f64>) -> DMatrix<f64> {
+    correlation.map(|rho| (1.0 - rho.abs()).max(0.0).sqrt())
+}
+
+/// Euclidean distance between the columns of a distance matrix: assets
+/// are close if they are at similar distances from all the others.
+pub fn distance_of_distances(distance: &DMatrix<f64>) -> DMatrix<f64> {
+    let n = distance.ncols();
+
+    DMatrix::from_fn(n, n, |i, j| {
+        (distance.column(i) - distance.column(j)).norm()
+    })
+}
+
+/// Agglomerative hierarchical clustering of the points of a (symmetric)
+/// distance matrix, merging the two closest clusters until one remains.
+pub fn hierarchical_clustering(
+    distance: &DMatrix<f64>,
+    linkage: Linkage,
+) -> Result<Dendrogram, ClusteringError> {
+    check_symmetric(distance)?;
+
+    let n = distance.nrows();
+    if n == 0 {
+        return Err(ClusteringError::InvalidClusterCount {
+            clusters: 1,
+            points: 0,
+        });
+    }
+
+    // Distances between the active clusters, updated in place with the
+    // Lance-Williams formulas. `ids[i]` is the cluster in slot `i`.
+    let mut d = distance.clone();
+    let mut ids: Vec<usize> = (0..n).collect();
+    let mut sizes = vec![1_usize; n];
+    let mut active = vec![true; n];
+    let mut merges = Vec::with_capacity(n.saturating_sub(1));
+
+    for step in 0..n.saturating_sub(1) {
+        let (mut a, mut b, mut closest) = (0, 0, f64::INFINITY);
+        for i in (0..n).filter(|&i| active[i]) {
+            for j in (i + 1..n).filter(|&j| active[j]) {
+                if d[(i, j)] < closest {
+                    (a, b, closest) = (i, j, d[(i, j)]);
+                }
+            }
+        }
+
+        let (n_a, n_b) = (sizes[a] as f64, sizes[b] as f64);
+        for k in (0..n).filter(|&k| active[k] && k != a && k != b) {
+            let n_k = sizes[k] as f64;
+            let (d_ak, d_bk) = (d[(a, k)], d[(b, k)]);
+
+            let updated = match linkage {
+                Linkage::Single => d_ak.min(d_bk),
+                Linkage::Complete => d_ak.max(d_bk),
+                Linkage::Average => (n_a * d_ak + n_b * d_bk) / (n_a + n_b),
+                Linkage::Ward => (((n_a + n_k) * d_ak * d_ak + (n_b + n_k) * d_bk * d_bk
+                    - n_k * closest * closest)
+                    / (n_a + n_b + n_k))
+                    .max(0.0)
+                    .sqrt(),
+            };
+            d[(a, k)] = updated;
+            d[(k, a)] = updated;
+        }
+
+        merges.push(Merge {
+            left: ids[a].min(ids[b]),
+            right: ids[a].max(ids[b]),
+            distance: closest,
+            size: sizes[a] + sizes[b],
+        });
+
+        ids[a] = n + step;
+        sizes[a] += sizes[b];
+        active[b] = false;
+    }
+
+    Ok(Dendrogram {
+        n_leaves: n,
+        merges,
+    })
+}
+
+fn check_symmetric(matrix: &DMatrix<f64>) -> Result<(), ClusteringError> {
+    let n = matrix.nrows();
+    let symmetric = matrix.is_square()
+        && (0..n).all(|i| (0..i).all(|j| (matrix[(i, j)] - matrix[(j, i)]).abs() <= 1e-12));
+
+    match symmetric {
+        true => Ok(()),
+        false => Err(ClusteringError::NotSymmetric),
+    }
+}
+
+/// Relabels clusters in order of first appearance.
+fn relabel(labels: &[usize]) -> Vec<usize> {
+    let mut seen: Vec<usize> = Vec::new();
+
+    labels
+        .iter()
+        .map(|l| match seen.iter().position(|s| s == l) {
+            Some(p) => p,
+            None => {
+                seen.push(*l);
+                seen.len() - 1
+            }
+        })
+        .collect()
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Dendrogram {
+    /// Points of cluster `id`, in dendrogram order.
+    pub fn members(&self, id: usize) -> Vec<usize> {
+        let mut members = Vec::new();
+        let mut stack = vec![id];
+
+        while let Some(id) = stack.pop() {
+            match id.checked_sub(self.n_leaves) {
+                None => members.push(id),
+                Some(step) => {
+                    let merge = &self.merges[step];
+                    stack.push(merge.right);
+                    stack.push(merge.left);
+                }
+            }
+        }
+
+        members
+    }
+
+    /// Points in dendrogram order, in which each cluster is contiguous
+    /// (the quasi-diagonalization of hierarchical risk parity).
+    pub fn leaf_order(&self) -> Vec<usize> {
+        match self.n_leaves {
+            0 => Vec::new(),
+            n => self.members(2 * n - 2),
+        }
+    }
+
+    /// Flat clustering into `k` clusters, by undoing the last `k - 1`
+    /// merges. Labels are numbered in order of first appearance.
+    pub fn cut(&self, k: usize) -> Vec<usize> {
+        assert!(
+            (1..=self.n_leaves).contains(&k),
+            "Number of clusters must be in 1..=n."
+        );
+
+        let mut labels = vec![0; self.n_leaves];
+        self.roots(self.n_leaves - k)
+            .into_iter()
+            .enumerate()
+            .for_each(|(label, root)| {
+                self.members(root)
+                    .into_iter()
+                    .for_each(|i| labels[i] = label)
+            });
+
+        relabel(&labels)
+    }
+
+    /// Flat clustering keeping the merges at distances up to `distance`.
+    pub fn cut_at(&self, distance: f64) -> Vec<usize> {
+        let merges = self
+            .merges
+            .iter()
+            .take_while(|m| m.distance <= distance)
+            .count();
+
+        self.cut(self.n_leaves - merges)
+    }
+
+    /// Clusters left after the first `merges` merges.
+    fn roots(&self, merges: usize) -> Vec<usize> {
+        let mut roots: Vec<usize> = (0..self.n_leaves).collect();
+
+        for (step, merge) in self.merges.iter().take(merges).enumerate() {
+            roots.retain(|&r| r != merge.left && r != merge.right);
+            roots.push(self.n_leaves + step);
+        }
+
+        roots
+    }
+}
+
+impl KMeans {
+    /// K-means with `k` clusters and ten restarts.
+    pub fn new(k: usize) -> Self {
+        Self {
+            k,
+            max_iterations: 300,
+            restarts: 10,
+            seed: 0,
+        }
+    }
+
+    /// Sets the maximum number of iterations per run.
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Sets the number of runs.
+    pub fn with_restarts(mut self, restarts: usize) -> Self {
+        self.restarts = restarts.max(1);
+        self
+    }
+
+    /// Sets the seed.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Clusters the rows of `points`.
+    pub fn fit(&self, points: &DMatrix<f64>) -> Result<KMeansResult, ClusteringError> {
+        let n = points.nrows();
+        if self.k == 0 || self.k > n {
+            return Err(ClusteringError::InvalidClusterCount {
+                clusters: self.k,
+                points: n,
+            });
+        }
+
+        let mut best: Option<KMeansResult> = None;
+
+        for run in 0..self.restarts.max(1) {
+            let mut rng = Pcg64::stream(self.seed, run as u64);
+            let result = self.lloyd(points, self.seed_centroids(points, &mut rng));
+
+            if best.as_ref().is_none_or(|b| result.inertia < b.inertia) {
+                best = Some(result);
+            }
+        }
+
+        let mut best = best.expect("At least one run.");
+
+        // Renumber the clusters (and reorder the centroids) by first
+        // appearance, so that results do not depend on the seeding.
+        let labels = relabel(&best.labels);
+        let mut centroids = best.centroids.clone();
+        for (old, new) in best.labels.iter().zip(&labels) {
+            centroids.set_row(*new, &best.centroids.row(*old));
+        }
+        best.labels = labels;
+        best.centroids = centroids;
+
+        Ok(best)
+    }
+
+    /// Clusters the assets of a correlation matrix, embedded as the rows
+    /// of $V \Lambda^{1/2}$ for the eigendecomposition $V \Lambda V^T$ of the
+    /// correlation matrix: their squared distances are $2 (1 - \rho_{ij})$.
+    pub fn fit_correlation(
+        &self,
+        correlation: &DMatrix<f64>,
+    ) -> Result<KMeansResult, ClusteringError> {
+        check_symmetric(correlation)?;
+
+        let eigen = correlation.clone().symmetric_eigen();
+        let roots = eigen.eigenvalues.map(|l| l.max(0.0).sqrt());
+        let points = &eigen.eigenvectors * DMatrix::from_diagonal(&roots);
+
+        self.fit(&points)
+    }
+
+    /// k-means++ seeding: each centre is drawn with probability
+    /// proportional to the squared distance to the nearest centre so far.
+    fn seed_centroids(&self, points: &DMatrix<f64>, rng: &mut Pcg64) -> DMatrix<f64> {
+        let n = points.nrows();
+        let mut centroids = DMatrix::zeros(self.k, points.ncols());
+        centroids.set_row(0, &points.row(rng.gen_range(0..n)));
+
+        let mut nearest = DVector::from_element(n, f64::INFINITY);
+        for c in 1..self.k {
+            for i in 0..n {
+                nearest[i] = nearest[i].min((points.row(i) - centroids.row(c - 1)).norm_squared());
+            }
+
+            let total = nearest.sum();
+            let chosen = match total > 0.0 {
+                true => {
+                    let mut u = rng.gen::<f64>() * total;
+                    (0..n)
+                        .find(|&i| {
+                            u -= nearest[i];
+                            u < 0.0 && nearest[i] > 0.0
+                        })
+                        .unwrap_or_else(|| nearest.imax())
+                }
+                // Fewer distinct points than clusters.
+                false => rng.gen_range(0..n),
+            };
+            centroids.set_row(c, &points.row(chosen));
+        }
+
+        centroids
+    }
+
+    /// Lloyd's algorithm from the given centroids.
+    fn lloyd(&self, points: &DMatrix<f64>, mut centroids: DMatrix<f64>) -> KMeansResult {
+        let n = points.nrows();
+        let mut labels = vec![usize::MAX; n];
+        let (mut iterations, mut converged) = (0, false);
+
+        while iterations < self.max_iterations {
+            iterations += 1;
+
+            let mut changed = false;
+            for (i, label) in labels.iter_mut().enumerate() {
+                let closest = (0..self.k)
+                    .map(|c| (c, (points.row(i) - centroids.row(c)).norm_squared()))
+                    .min_by(|a, b| a.1.total_cmp(&b.1))
+                    .map(|(c, _)| c)
+                    .expect("At least one cluster.");
+
+                changed |= *label != closest;
+                *label = closest;
+            }
+
+            if !changed {
+                converged = true;
+                break;
+            }
+
+            for c in 0..self.k {
+                let members: Vec<usize> = (0..n).filter(|&i| labels[i] == c).collect();
+
+                // Empty clusters keep their centre.
+                if !members.is_empty() {
+                    let centre = members
+                        .iter()
+                        .fold(DVector::zeros(points.ncols()), |acc, &i| {
+                            acc + points.row(i).transpose()
+                        })
+                        / members.len() as f64;
+                    centroids.set_row(c, &centre.transpose());
+                }
+            }
+        }
+
+        let inertia = (0..n)
+            .map(|i| (points.row(i) - centroids.row(labels[i])).norm_squared())
+            .sum();
+
+        KMeansResult {
+            labels,
+            centroids,
+            inertia,
+            iterations,
+            converged,
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_clustering {
+    use super::*;
+    use rand_distr::StandardNormal;
+
+    // Returns of three sectors of four assets each, shuffled.
+    const SECTORS: [usize; 12] = [0, 1, 2, 0, 1, 2, 2, 0, 1, 1, 2, 0];
+
+    fn sector_returns(periods: usize) -> DMatrix<f64> {
+        let mut rng = Pcg64::stream(42, 0);
+        let factors = DMatrix::from_fn(periods, 3, |_, _| rng.sample::<f64, _>(StandardNormal));
+
+        DMatrix::from_fn(periods, SECTORS.len(), |t, j| {
+            0.01 * (factors[(t, SECTORS[j])] + 0.6 * rng.sample::<f64, _>(StandardNormal))
+        })
+    }
+
+    #[test]
+    fn test_correlation_distances() {
+        let returns = sector_returns(500);
+        let correlation = correlation_matrix(&returns);
+        assert!((0..12).all(|i| correlation[(i, i)] == 1.0));
+
+        let distance = correlation_distance(&correlation);
+        assert!((0..12).all(|i| distance[(i, i)] == 0.0));
+        assert_approx_equal!(
+            correlation_distance(&DMatrix::from_element(1, 1, -1.0))[(0, 0)],
+            1.0,
+            1e-15
+        );
+        assert_approx_equal!(
+            absolute_correlation_distance(&DMatrix::from_element(1, 1, -1.0))[(0, 0)],
+            0.0,
+            1e-15
+        );
+
+        // A metric: the triangle inequality holds.
+        for i in 0..12 {
+            for j in 0..12 {
+                for k in 0..12 {
+                    assert!(distance[(i, j)] <= distance[(i, k)] + distance[(k, j)] + 1e-12);
+                }
+            }
+        }
+
+        let dod = distance_of_distances(&distance);
+        assert!(dod[(0, 3)] < dod[(0, 1)]);
+    }
+
+    #[test]
+    fn test_hierarchical_clustering_recovers_sectors() {
+        let correlation = correlation_matrix(&sector_returns(500));
+        let expected = relabel(&SECTORS);
+
+        for linkage in [
+            Linkage::Single,
+            Linkage::Complete,
+            Linkage::Average,
+            Linkage::Ward,
+        ] {
+            let dendrogram =
+                hierarchical_clustering(&correlation_distance(&correlation), linkage).unwrap();
+
+            assert_eq!(dendrogram.merges.len(), 11);
+            assert_eq!(dendrogram.merges.last().unwrap().size, 12);
+            assert!(dendrogram
+                .merges
+                .windows(2)
+                .all(|w| w[0].distance <= w[1].distance));
+            assert_eq!(dendrogram.cut(3), expected);
+            assert_eq!(dendrogram.cut(1), vec![0; 12]);
+            assert_eq!(dendrogram.cut(12), (0..12).collect::<Vec<_>>());
+
+            // Sectors are contiguous in the leaf order.
+            let order = dendrogram.leaf_order();
+            let mut sorted = order.clone();
+            sorted.sort_unstable();
+            assert_eq!(sorted, (0..12).collect::<Vec<_>>());
+            for block in order.chunks(4) {
+                assert!(block.iter().all(|&i| SECTORS[i] == SECTORS[block[0]]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_linkage_distances() {
+        // Points 0, 1, 3 and 7 on a line.
+        let x = [0.0_f64, 1.0, 3.0, 7.0];
+        let distance = DMatrix::from_fn(4, 4, |i, j| (x[i] - x[j]).abs());
+
+        let merges = |linkage| {
+            hierarchical_clustering(&distance, linkage)
+                .unwrap()
+                .merges
+                .iter()
+                .map(|m| (m.left, m.right, m.distance))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            merges(Linkage::Single),
+            [(0, 1, 1.0), (2, 4, 2.0), (3, 5, 4.0)]
+        );
+        assert_eq!(
+            merges(Linkage::Complete),
+            [(0, 1, 1.0), (2, 4, 3.0), (3, 5, 7.0)]
+        );
+        assert_eq!(
+            merges(Linkage::Average),
+            [(0, 1, 1.0), (2, 4, 2.5), (3, 5, 17.0 / 3.0)]
+        );
+
+        // Ward: sqrt(2 n_a n_b / (n_a + n_b)) times the distance between the
+        // centroids (as in scipy).
+        let ward = merges(Linkage::Ward);
+        assert_approx_equal!(ward[1].2, (2.0 * 2.0 / 3.0_f64).sqrt() * 2.5, 1e-12);
+        assert_approx_equal!(ward[2].2, (2.0 * 3.0 / 4.0_f64).sqrt() * 17.0 / 3.0, 1e-12);
+
+        let dendrogram = hierarchical_clustering(&distance, Linkage::Single).unwrap();
+        assert_eq!(dendrogram.cut_at(1.5), vec![0, 0, 1, 2]);
+        assert_eq!(dendrogram.members(5), vec![2, 0, 1]);
+
+        assert_eq!(
+            hierarchical_clustering(&DMatrix::zeros(2, 3), Linkage::Single),
+            Err(ClusteringError::NotSymmetric)
+        );
+    }
+
+    #[test]
+    fn test_kmeans() {
+        let correlation = correlation_matrix(&sector_returns(500));
+        let result = KMeans::new(3).fit_correlation(&correlation).unwrap();
+
+        assert!(result.converged);
+        assert_eq!(result.labels, relabel(&SECTORS));
+        assert_eq!(result.centroids.nrows(), 3);
+
+        // Points: two well separated blobs.
+        let points = DMatrix::from_row_slice(
+            6,
+            2,
+            &[0.0, 0.0, 0.1, 0.0, 0.0, 0.1, 5.0, 5.0, 5.1, 5.0, 5.0, 5.1],
+        );
+        let result = KMeans::new(2).with_seed(3).fit(&points).unwrap();
+        assert_eq!(result.labels, vec![0, 0, 0, 1, 1, 1]);
+        assert_approx_equal!(result.centroids[(1, 0)], 5.1 / 3.0 + 10.0 / 3.0, 1e-12);
+        assert_approx_equal!(result.inertia, 4.0 * 0.02 / 3.0, 1e-12);
+
+        // More clusters, lower inertia.
+        let finer = KMeans::new(4).fit(&points).unwrap();
+        assert!(finer.inertia < result.inertia);
+
+        assert_eq!(
+            KMeans::new(7).fit(&points),
+            Err(ClusteringError::InvalidClusterCount {
+                clusters: 7,
+                points: 6
+            })
+        );
+    }
+}
@@ -0,0 +1,620 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Classification and regression trees (CART, Breiman et al., 1984).
+//!
+//! The tree is grown greedily: each node is split on the feature and
+//! threshold that most reduce the impurity (Gini or entropy for classes,
+//! squared error for values), until the maximum depth or the minimum
+//! number of samples is reached. Feature importances are the total
+//! impurity decreases of the splits on each feature, normalised to sum to
+//! one.
+//!
+//! Features are the columns of a matrix with one row per sample, such as
+//! a [`DesignMatrix`](crate::ml::DesignMatrix) built without an intercept
+//! from engineered features.
+//!
+//! ```rust
+//! use RustQuant::ml::*;
+//! use nalgebra::{DMatrix, DVector};
+//!
+//! // Up days (1) when momentum is positive and volatility is low.
+//! let x = DMatrix::from_row_slice(6, 2, &[
+//!     0.02, 0.10,
+//!     0.01, 0.15,
+//!    -0.01, 0.12,
+//!     0.03, 0.40,
+//!    -0.02, 0.35,
+//!     0.02, 0.45,
+//! ]);
+//! let y = DVector::from_vec(vec![1.0, 1.0, 0.0, 0.0, 0.0, 0.0]);
+//!
+//! let tree = DecisionTree::new(SplitCriterion::Gini).fit(&x, &y).unwrap();
+//!
+//! assert_eq!(tree.predict(&x), y);
+//! assert!((tree.feature_importances.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+//! ```
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use nalgebra::{DMatrix, DVector};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Errors of the tree models.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum TreeError {
+    /// The targets do not have one value per row of the features.
+    #[error("Features have {rows} rows but there are {observations} targets")]
+    DimensionMismatch {
+        /// Rows of the feature matrix.
+        rows: usize,
+        /// Number of targets.
+        observations: usize,
+    },
+
+    /// There are no samples to fit.
+    #[error("No samples")]
+    EmptyData,
+
+    /// A class label is not a non-negative integer (or, for binary
+    /// classification, not 0 or 1).
+    #[error("Invalid class label: {0}")]
+    InvalidLabel(f64),
+}
+
+/// Impurity measure of the samples at a node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SplitCriterion {
+    /// Gini impurity, $1 - \sum_k p_k^2$ (classification).
+    Gini,
+    /// Entropy, $-\sum_k p_k \log_2 p_k$ (classification).
+    Entropy,
+    /// Variance of the targets (regression).
+    SquaredError,
+}
+
+/// Settings of a decision tree.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DecisionTree {
+    /// Impurity measure, which also sets the task: classification (labels
+    /// `0, 1, ..., K - 1`) or regression.
+    pub criterion: SplitCriterion,
+    /// Maximum depth (the root has depth zero).
+    pub max_depth: usize,
+    /// Minimum number of samples to split a node.
+    pub min_samples_split: usize,
+    /// Minimum number of samples in each leaf.
+    pub min_samples_leaf: usize,
+}
+
+/// A node of a fitted tree.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Node {
+    /// Terminal node: class probabilities (classification) or the mean
+    /// target (regression, a single value).
+    Leaf {
+        /// Prediction at the leaf.
+        value: Vec<f64>,
+    },
+    /// Internal node: samples with `x[feature] <= threshold` go left.
+    Split {
+        /// Feature (column) index.
+        feature: usize,
+        /// Split threshold.
+        threshold: f64,
+        /// Index of the left child.
+        left: usize,
+        /// Index of the right child.
+        right: usize,
+    },
+}
+
+/// A fitted decision tree.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FittedTree {
+    /// Impurity measure the tree was grown with.
+    pub criterion: SplitCriterion,
+    /// Nodes, the root first.
+    pub nodes: Vec<Node>,
+    /// Normalised impurity decrease due to each feature.
+    pub feature_importances: Vec<f64>,
+    // Unnormalised impurity decreases, for ensembles.
+    pub(crate) impurity_decreases: Vec<f64>,
+}
+
+/// Best split of a node.
+struct Split {
+    feature: usize,
+    threshold: f64,
+    position: usize,
+    decrease: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl SplitCriterion {
+    fn is_classification(&self) -> bool {
+        !matches!(self, SplitCriterion::SquaredError)
+    }
+
+    /// Impurity of a node with the given class counts.
+    fn class_impurity(&self, counts: &[f64], n: f64) -> f64 {
+        match self {
+            SplitCriterion::Gini => 1.0 - counts.iter().map(|c| (c / n).powi(2)).sum::<f64>(),
+            _ => -counts
+                .iter()
+                .filter(|&&c| c > 0.0)
+                .map(|c| c / n * (c / n).log2())
+                .sum::<f64>(),
+        }
+    }
+}
+
+impl DecisionTree {
+    /// Tree with the given criterion, a maximum depth of five and no
+    /// minimum leaf size.
+    pub fn new(criterion: SplitCriterion) -> Self {
+        Self {
+            criterion,
+            max_depth: 5,
+            min_samples_split: 2,
+            min_samples_leaf: 1,
+        }
+    }
+
+    /// Sets the maximum depth.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Sets the minimum number of samples to split a node.
+    pub fn with_min_samples_split(mut self, min_samples_split: usize) -> Self {
+        self.min_samples_split = min_samples_split.max(2);
+        self
+    }
+
+    /// Sets the minimum number of samples in each leaf.
+    pub fn with_min_samples_leaf(mut self, min_samples_leaf: usize) -> Self {
+        self.min_samples_leaf = min_samples_leaf.max(1);
+        self
+    }
+
+    /// Grows the tree on the rows of `x` (one per sample) and the targets
+    /// `y`: class labels `0, 1, ...` or values, depending on the criterion.
+    pub fn fit(&self, x: &DMatrix<f64>, y: &DVector<f64>) -> Result<FittedTree, TreeError> {
+        let rows: Vec<usize> = (0..x.nrows()).collect();
+
+        self.fit_rows(x, y, rows)
+    }
+
+    /// Grows the tree on the given rows only.
+    pub(crate) fn fit_rows(
+        &self,
+        x: &DMatrix<f64>,
+        y: &DVector<f64>,
+        mut rows: Vec<usize>,
+    ) -> Result<FittedTree, TreeError> {
+        if x.nrows() != y.len() {
+            return Err(TreeError::DimensionMismatch {
+                rows: x.nrows(),
+                observations: y.len(),
+            });
+        }
+        if rows.is_empty() {
+            return Err(TreeError::EmptyData);
+        }
+
+        let n_classes = match self.criterion.is_classification() {
+            true => {
+                if let Some(&label) = y.iter().find(|&&l| l < 0.0 || l.fract() != 0.0) {
+                    return Err(TreeError::InvalidLabel(label));
+                }
+                y.max() as usize + 1
+            }
+            false => 0,
+        };
+
+        let mut tree = FittedTree {
+            criterion: self.criterion,
+            nodes: Vec::new(),
+            feature_importances: vec![0.0; x.ncols()],
+            impurity_decreases: vec![0.0; x.ncols()],
+        };
+        self.grow(x, y, n_classes, &mut rows, 0, &mut tree);
+
+        let total: f64 = tree.impurity_decreases.iter().sum();
+        if total > 0.0 {
+            tree.feature_importances = tree.impurity_decreases.iter().map(|d| d / total).collect();
+        }
+
+        Ok(tree)
+    }
+
+    /// Grows the subtree of the samples `rows` and returns its index.
+    fn grow(
+        &self,
+        x: &DMatrix<f64>,
+        y: &DVector<f64>,
+        n_classes: usize,
+        rows: &mut [usize],
+        depth: usize,
+        tree: &mut FittedTree,
+    ) -> usize {
+        let index = tree.nodes.len();
+        tree.nodes.push(Node::Leaf {
+            value: self.leaf_value(y, n_classes, rows),
+        });
+
+        if depth >= self.max_depth || rows.len() < self.min_samples_split {
+            return index;
+        }
+
+        if let Some(split) = self.best_split(x, y, n_classes, rows) {
+            rows.sort_by(|&a, &b| x[(a, split.feature)].total_cmp(&x[(b, split.feature)]));
+            tree.impurity_decreases[split.feature] += split.decrease;
+
+            let (left_rows, right_rows) = rows.split_at_mut(split.position);
+            let left = self.grow(x, y, n_classes, left_rows, depth + 1, tree);
+            let right = self.grow(x, y, n_classes, right_rows, depth + 1, tree);
+
+            tree.nodes[index] = Node::Split {
+                feature: split.feature,
+                threshold: split.threshold,
+                left,
+                right,
+            };
+        }
+
+        index
+    }
+
+    fn leaf_value(&self, y: &DVector<f64>, n_classes: usize, rows: &[usize]) -> Vec<f64> {
+        let n = rows.len() as f64;
+
+        match self.criterion.is_classification() {
+            true => {
+                let mut probabilities = vec![0.0; n_classes];
+                rows.iter()
+                    .for_each(|&i| probabilities[y[i] as usize] += 1.0 / n);
+                probabilities
+            }
+            false => vec![rows.iter().map(|&i| y[i]).sum::<f64>() / n],
+        }
+    }
+
+    /// Split with the largest decrease in (sample-weighted) impurity, if
+    /// any decreases it.
+    fn best_split(
+        &self,
+        x: &DMatrix<f64>,
+        y: &DVector<f64>,
+        n_classes: usize,
+        rows: &[usize],
+    ) -> Option<Split> {
+        let n = rows.len();
+        let classification = self.criterion.is_classification();
+
+        // Sufficient statistics: class counts, or (sum, sum of squares).
+        let statistics = |rows: &mut dyn Iterator<Item = &usize>| -> Vec<f64> {
+            let mut s = vec![0.0; n_classes.max(2)];
+            for &i in rows {
+                match classification {
+                    true => s[y[i] as usize] += 1.0,
+                    false => {
+                        s[0] += y[i];
+                        s[1] += y[i] * y[i];
+                    }
+                }
+            }
+            s
+        };
+        let impurity = |s: &[f64], n: f64| match classification {
+            true => self.criterion.class_impurity(s, n),
+            false => (s[1] / n - (s[0] / n).powi(2)).max(0.0),
+        };
+
+        let total = statistics(&mut rows.iter());
+        let parent = n as f64 * impurity(&total, n as f64);
+
+        let mut best: Option<Split> = None;
+        let mut sorted = rows.to_vec();
+
+        for feature in 0..x.ncols() {
+            sorted.sort_by(|&a, &b| x[(a, feature)].total_cmp(&x[(b, feature)]));
+
+            let mut left = vec![0.0; total.len()];
+            for k in 0..n - 1 {
+                let i = sorted[k];
+                match classification {
+                    true => left[y[i] as usize] += 1.0,
+                    false => {
+                        left[0] += y[i];
+                        left[1] += y[i] * y[i];
+                    }
+                }
+
+                let (value, next) = (x[(i, feature)], x[(sorted[k + 1], feature)]);
+                let (n_left, n_right) = (k + 1, n - k - 1);
+                if value == next
+                    || n_left < self.min_samples_leaf
+                    || n_right < self.min_samples_leaf
+                {
+                    continue;
+                }
+
+                let right: Vec<f64> = total.iter().zip(&left).map(|(t, l)| t - l).collect();
+                let children = n_left as f64 * impurity(&left, n_left as f64)
+                    + n_right as f64 * impurity(&right, n_right as f64);
+                let decrease = parent - children;
+
+                if decrease > best.as_ref().map_or(1e-12 * parent.abs(), |b| b.decrease) {
+                    best = Some(Split {
+                        feature,
+                        threshold: 0.5 * (value + next),
+                        position: n_left,
+                        decrease,
+                    });
+                }
+            }
+        }
+
+        best.filter(|b| b.decrease > 0.0)
+    }
+}
+
+impl FittedTree {
+    /// Index of the leaf that a sample (a row of features) falls in.
+    pub fn leaf(&self, sample: &[f64]) -> usize {
+        let mut index = 0;
+
+        loop {
+            match &self.nodes[index] {
+                Node::Leaf { .. } => return index,
+                Node::Split {
+                    feature,
+                    threshold,
+                    left,
+                    right,
+                } => {
+                    index = match sample[*feature] <= *threshold {
+                        true => *left,
+                        false => *right,
+                    }
+                }
+            }
+        }
+    }
+
+    /// Prediction at the leaf of a sample: class probabilities or the
+    /// regression value.
+    pub fn leaf_value(&self, sample: &[f64]) -> &[f64] {
+        match &self.nodes[self.leaf(sample)] {
+            Node::Leaf { value } => value,
+            Node::Split { .. } => unreachable!("Samples end in leaves."),
+        }
+    }
+
+    /// Predicted class (the most frequent at the leaf) or value for each
+    /// row of `x`.
+    pub fn predict(&self, x: &DMatrix<f64>) -> DVector<f64> {
+        DVector::from_iterator(
+            x.nrows(),
+            rows(x).map(|sample| {
+                let value = self.leaf_value(&sample);
+                match self.criterion.is_classification() {
+                    true => value
+                        .iter()
+                        .enumerate()
+                        .max_by(|a, b| a.1.total_cmp(b.1))
+                        .map_or(0.0, |(k, _)| k as f64),
+                    false => value[0],
+                }
+            }),
+        )
+    }
+
+    /// Class probabilities, one row per row of `x` and one column per
+    /// class (classification trees).
+    pub fn predict_proba(&self, x: &DMatrix<f64>) -> DMatrix<f64> {
+        assert!(
+            self.criterion.is_classification(),
+            "Probabilities need a classification tree."
+        );
+
+        let probabilities: Vec<Vec<f64>> = rows(x).map(|s| self.leaf_value(&s).to_vec()).collect();
+        let n_classes = probabilities.first().map_or(0, Vec::len);
+
+        DMatrix::from_fn(x.nrows(), n_classes, |i, k| probabilities[i][k])
+    }
+
+    /// Depth of the tree (zero for a single leaf).
+    pub fn depth(&self) -> usize {
+        fn depth(nodes: &[Node], index: usize) -> usize {
+            match &nodes[index] {
+                Node::Leaf { .. } => 0,
+                Node::Split { left, right, .. } => {
+                    1 + depth(nodes, *left).max(depth(nodes, *right))
+                }
+            }
+        }
+
+        depth(&self.nodes, 0)
+    }
+
+    /// Number of leaves.
+    pub fn n_leaves(&self) -> usize {
+        self.nodes
+            .iter()
+            .filter(|node| matches!(node, Node::Leaf { .. }))
+            .count()
+    }
+}
+
+/// Rows of a matrix as vectors.
+pub(crate) fn rows(x: &DMatrix<f64>) -> impl Iterator<Item = Vec<f64>> + '_ {
+    x.row_iter().map(|row| row.iter().copied().collect())
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_decision_tree {
+    use super::*;
+    use crate::math::Pcg64;
+    use rand::Rng;
+
+    // Label 1 if x0 > 0.3 and x1 < 0.5, with three noise features.
+    fn signal_data(n: usize, seed: u64) -> (DMatrix<f64>, DVector<f64>) {
+        let mut rng = Pcg64::stream(seed, 0);
+        let x = DMatrix::from_fn(n, 5, |_, _| rng.gen::<f64>());
+        let y = DVector::from_fn(n, |i, _| {
+            f64::from(u8::from(x[(i, 0)] > 0.3 && x[(i, 1)] < 0.5))
+        });
+
+        (x, y)
+    }
+
+    #[test]
+    fn test_classification() {
+        let (x, y) = signal_data(400, 1);
+        let (x_test, y_test) = signal_data(400, 2);
+
+        for criterion in [SplitCriterion::Gini, SplitCriterion::Entropy] {
+            let tree = DecisionTree::new(criterion).fit(&x, &y).unwrap();
+
+            assert_eq!(tree.predict(&x), y);
+            let accuracy = tree
+                .predict(&x_test)
+                .iter()
+                .zip(y_test.iter())
+                .filter(|(p, y)| p == y)
+                .count() as f64
+                / 400.0;
+            assert!(accuracy > 0.97, "Accuracy: {accuracy}");
+
+            // The relevant features carry the importance.
+            let importances = &tree.feature_importances;
+            assert_approx_equal!(importances.iter().sum::<f64>(), 1.0, 1e-12);
+            assert!(importances[0] + importances[1] > 0.95);
+            assert!(importances[0] > 0.2 && importances[1] > 0.2);
+        }
+    }
+
+    #[test]
+    fn test_depth_and_leaf_size() {
+        let (x, y) = signal_data(200, 3);
+
+        let stump = DecisionTree::new(SplitCriterion::Gini)
+            .with_max_depth(1)
+            .fit(&x, &y)
+            .unwrap();
+        assert_eq!(stump.depth(), 1);
+        assert_eq!(stump.n_leaves(), 2);
+
+        let coarse = DecisionTree::new(SplitCriterion::Gini)
+            .with_max_depth(20)
+            .with_min_samples_leaf(30)
+            .fit(&x, &y)
+            .unwrap();
+        for node in &coarse.nodes {
+            if let Node::Split { left, right, .. } = node {
+                for child in [left, right] {
+                    let n = (0..200)
+                        .filter(|&i| {
+                            let sample: Vec<f64> = x.row(i).iter().copied().collect();
+                            ancestors(&coarse, coarse.leaf(&sample)).contains(child)
+                        })
+                        .count();
+                    assert!(n >= 30);
+                }
+            }
+        }
+
+        // Probabilities are the class frequencies at the leaf.
+        let probabilities = stump.predict_proba(&x);
+        assert_eq!(probabilities.ncols(), 2);
+        assert!(probabilities
+            .row_iter()
+            .all(|row| (row.sum() - 1.0).abs() < 1e-12));
+    }
+
+    // Nodes on the path from the root to `leaf`.
+    fn ancestors(tree: &FittedTree, leaf: usize) -> Vec<usize> {
+        let mut path = vec![leaf];
+        while let Some(parent) = tree.nodes.iter().position(|node| match node {
+            Node::Split { left, right, .. } => {
+                *left == path[path.len() - 1] || *right == path[path.len() - 1]
+            }
+            Node::Leaf { .. } => false,
+        }) {
+            path.push(parent);
+        }
+        path
+    }
+
+    #[test]
+    fn test_regression() {
+        // Piecewise constant function of x0: recovered exactly.
+        let x = DMatrix::from_fn(100, 2, |i, j| match j {
+            0 => i as f64 / 100.0,
+            _ => ((i * 37) % 100) as f64,
+        });
+        let step = |x: f64| match x {
+            x if x < 0.25 => 1.0,
+            x if x < 0.6 => -2.0,
+            _ => 0.5,
+        };
+        let y = DVector::from_fn(100, |i, _| step(x[(i, 0)]));
+
+        let tree = DecisionTree::new(SplitCriterion::SquaredError)
+            .fit(&x, &y)
+            .unwrap();
+
+        assert_eq!(tree.predict(&x), y);
+        assert_eq!(tree.n_leaves(), 3);
+        assert_eq!(tree.feature_importances, vec![1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let x = DMatrix::from_element(3, 1, 1.0);
+
+        assert_eq!(
+            DecisionTree::new(SplitCriterion::Gini)
+                .fit(&x, &DVector::from_vec(vec![0.0, 1.5, 1.0])),
+            Err(TreeError::InvalidLabel(1.5))
+        );
+        assert_eq!(
+            DecisionTree::new(SplitCriterion::Gini).fit(&x, &DVector::from_vec(vec![0.0, 1.0])),
+            Err(TreeError::DimensionMismatch {
+                rows: 3,
+                observations: 2
+            })
+        );
+
+        // Constant features cannot be split.
+        let tree = DecisionTree::new(SplitCriterion::Gini)
+            .fit(&x, &DVector::from_vec(vec![0.0, 1.0, 1.0]))
+            .unwrap();
+        assert_eq!(tree.n_leaves(), 1);
+        assert_eq!(tree.feature_importances, vec![0.0]);
+    }
+}
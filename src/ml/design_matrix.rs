@@ -0,0 +1,241 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Design matrices with named columns, for the regression models in
+//! [`crate::ml::regularized_regression`].
+//!
+//! With the `data` feature, [`DesignMatrixBuilder`] selects the response
+//! and regressors from a Polars `DataFrame`, e.g. asset returns and factor
+//! returns, or a forward return and candidate signals.
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use nalgebra::DMatrix;
+
+#[cfg(feature = "data")]
+use {
+    crate::{data::DataError, ml::RegressionError},
+    nalgebra::DVector,
+    polars::prelude::*,
+};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Regressors (one row per observation) and their names.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DesignMatrix {
+    /// Regressor values, one column per regressor.
+    pub x: DMatrix<f64>,
+
+    /// Name of each column.
+    pub names: Vec<String>,
+
+    /// Whether the first column is the (unpenalized) intercept.
+    pub intercept: bool,
+}
+
+/// Builds a design matrix and response vector from the columns of a
+/// `DataFrame`.
+#[cfg(feature = "data")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DesignMatrixBuilder {
+    response: String,
+    regressors: Vec<String>,
+    intercept: bool,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl DesignMatrix {
+    /// Name of the intercept column.
+    pub const INTERCEPT: &'static str = "intercept";
+
+    /// Design matrix without an intercept.
+    pub fn new(x: DMatrix<f64>, names: Vec<String>) -> Self {
+        assert_eq!(x.ncols(), names.len(), "One name per column.");
+
+        Self {
+            x,
+            names,
+            intercept: false,
+        }
+    }
+
+    /// Prepends a column of ones for the intercept (if there is none yet).
+    pub fn with_intercept(mut self) -> Self {
+        if !self.intercept {
+            self.x = self.x.insert_column(0, 1.0);
+            self.names.insert(0, Self::INTERCEPT.to_string());
+            self.intercept = true;
+        }
+        self
+    }
+
+    /// Number of observations.
+    pub fn nrows(&self) -> usize {
+        self.x.nrows()
+    }
+
+    /// Number of regressors, including the intercept.
+    pub fn ncols(&self) -> usize {
+        self.x.ncols()
+    }
+
+    /// Index of the column called `name`.
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name)
+    }
+}
+
+#[cfg(feature = "data")]
+impl DesignMatrixBuilder {
+    /// Builder for a regression of the column `response` on an intercept
+    /// and, unless regressors are added, every other numeric column.
+    pub fn new(response: &str) -> Self {
+        Self {
+            response: response.to_string(),
+            regressors: Vec::new(),
+            intercept: true,
+        }
+    }
+
+    /// Adds the column `name` as a regressor.
+    pub fn regressor(mut self, name: &str) -> Self {
+        self.regressors.push(name.to_string());
+        self
+    }
+
+    /// Adds the columns `names` as regressors.
+    pub fn regressors(mut self, names: &[&str]) -> Self {
+        self.regressors.extend(names.iter().map(|n| n.to_string()));
+        self
+    }
+
+    /// Fits without an intercept.
+    pub fn without_intercept(mut self) -> Self {
+        self.intercept = false;
+        self
+    }
+
+    /// Design matrix and response from `df`. Rows with a null in the
+    /// response or in any regressor are dropped.
+    pub fn build(&self, df: &DataFrame) -> Result<(DesignMatrix, DVector<f64>), RegressionError> {
+        let regressors: Vec<String> = match self.regressors.is_empty() {
+            true => df
+                .get_columns()
+                .iter()
+                .filter(|s| s.name() != self.response && s.dtype().is_numeric())
+                .map(|s| s.name().to_string())
+                .collect(),
+            false => self.regressors.clone(),
+        };
+
+        let values = |name: &str| -> Result<Vec<Option<f64>>, RegressionError> {
+            Ok(df
+                .column(name)
+                .map_err(DataError::from)?
+                .cast(&DataType::Float64)
+                .map_err(DataError::from)?
+                .f64()
+                .map_err(DataError::from)?
+                .into_iter()
+                .collect())
+        };
+
+        let response = values(&self.response)?;
+        let columns = regressors
+            .iter()
+            .map(|name| values(name))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let rows: Vec<usize> = (0..df.height())
+            .filter(|&i| response[i].is_some() && columns.iter().all(|c| c[i].is_some()))
+            .collect();
+
+        let x = DMatrix::from_fn(rows.len(), columns.len(), |i, j| {
+            columns[j][rows[i]].unwrap_or_default()
+        });
+        let y = DVector::from_iterator(
+            rows.len(),
+            rows.iter().map(|&i| response[i].unwrap_or_default()),
+        );
+
+        let design = DesignMatrix::new(x, regressors);
+
+        Ok(match self.intercept {
+            true => (design.with_intercept(), y),
+            false => (design, y),
+        })
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_design_matrix {
+    use super::*;
+
+    #[test]
+    fn test_intercept() {
+        let design = DesignMatrix::new(
+            DMatrix::from_row_slice(2, 2, &[1.0, 2.0, 3.0, 4.0]),
+            vec!["a".to_string(), "b".to_string()],
+        )
+        .with_intercept()
+        .with_intercept();
+
+        assert_eq!(design.ncols(), 3);
+        assert_eq!(design.names, ["intercept", "a", "b"]);
+        assert_eq!(design.x.column(0).sum(), 2.0);
+        assert_eq!(design.position("b"), Some(2));
+    }
+
+    #[cfg(feature = "data")]
+    #[test]
+    fn test_builder() {
+        let df = df!(
+            "date" => ["2023-01-01", "2023-01-02", "2023-01-03", "2023-01-04"],
+            "y" => [Some(1.0), Some(2.0), None, Some(4.0)],
+            "mkt" => [Some(0.1), None, Some(0.3), Some(0.4)],
+            "hml" => [1_i32, 2, 3, 4]
+        )
+        .unwrap();
+
+        // Every numeric column by default, dropping rows with nulls.
+        let (design, y) = DesignMatrixBuilder::new("y").build(&df).unwrap();
+        assert_eq!(design.names, ["intercept", "mkt", "hml"]);
+        assert_eq!(y.as_slice(), [1.0, 4.0]);
+        assert_eq!(
+            design.x.row(1).iter().copied().collect::<Vec<_>>(),
+            [1.0, 0.4, 4.0]
+        );
+
+        let (design, y) = DesignMatrixBuilder::new("y")
+            .regressor("hml")
+            .without_intercept()
+            .build(&df)
+            .unwrap();
+        assert_eq!(design.names, ["hml"]);
+        assert!(!design.intercept);
+        assert_eq!(y.len(), 3);
+
+        assert!(DesignMatrixBuilder::new("y")
+            .regressor("missing")
+            .build(&df)
+            .is_err());
+    }
+}
@@ -0,0 +1,538 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Gaussian process regression, e.g. to smooth a yield curve from noisy
+//! quotes or to interpolate an implied volatility surface, with
+//! uncertainty bands.
+//!
+//! The observations are $y_i = f(x_i) + \epsilon_i$, with
+//! $\epsilon_i \sim N(0, \sigma_n^2)$ and a zero-mean (after removing the
+//! sample mean) Gaussian process prior on $f$ with covariance
+//! $\sigma_f^2 k(r)$, where $r^2 = \sum_d (x_d - x'_d)^2 / l_d^2$ has one
+//! length scale per input dimension.
+//!
+//! The hyperparameters $(\sigma_f^2, l, \sigma_n^2)$ can be set by hand or
+//! chosen by maximising the log marginal likelihood
+//! ([`GaussianProcess::optimize`]), with exact gradients and L-BFGS.
+//!
+//! See Rasmussen and Williams, Gaussian Processes for Machine Learning (2006).
+//!
+//! ```rust
+//! use RustQuant::ml::*;
+//! use nalgebra::{DMatrix, DVector};
+//!
+//! // Noisy zero rates at a few tenors.
+//! let tenors = DMatrix::from_column_slice(6, 1, &[0.5, 1.0, 2.0, 5.0, 10.0, 30.0]);
+//! let rates = DVector::from_vec(vec![0.031, 0.033, 0.0345, 0.037, 0.038, 0.0385]);
+//!
+//! let gp = GaussianProcess::new(Kernel::Matern52, 1e-5, vec![5.0], 1e-8)
+//!     .fit(&tenors, &rates)
+//!     .unwrap();
+//!
+//! let prediction = gp.predict(&DMatrix::from_column_slice(1, 1, &[3.0]));
+//! let (lower, upper) = prediction.band(1.96);
+//!
+//! assert!(lower[0] < prediction.mean[0] && prediction.mean[0] < upper[0]);
+//! ```
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::math::optimize::{Bounds, GradientObjective, Lbfgs};
+use nalgebra::{Cholesky, DMatrix, DVector, Dyn};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Errors of Gaussian process regression.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum GaussianProcessError {
+    /// The covariance matrix of the observations is not positive definite,
+    /// e.g. duplicate inputs without observation noise.
+    #[error("Covariance matrix is not positive definite")]
+    NotPositiveDefinite,
+
+    /// The inputs and observations do not match.
+    #[error("Expected {expected} observations and {dimension} length scales")]
+    DimensionMismatch {
+        /// Number of input rows.
+        expected: usize,
+        /// Number of input columns.
+        dimension: usize,
+    },
+}
+
+/// Stationary covariance functions, as functions of the scaled distance $r$.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Kernel {
+    /// Squared exponential (radial basis function), $e^{-r^2 / 2}$.
+    /// Infinitely smooth.
+    Rbf,
+    /// Matérn with $\nu = 1/2$, $e^{-r}$. Continuous but rough.
+    Matern12,
+    /// Matérn with $\nu = 3/2$, $(1 + \sqrt{3} r) e^{-\sqrt{3} r}$.
+    Matern32,
+    /// Matérn with $\nu = 5/2$, $(1 + \sqrt{5} r + 5 r^2 / 3) e^{-\sqrt{5} r}$.
+    /// Twice differentiable, a common choice for curves and surfaces.
+    Matern52,
+}
+
+/// Gaussian process regression model (hyperparameters).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GaussianProcess {
+    /// Covariance function.
+    pub kernel: Kernel,
+    /// Signal variance, $\sigma_f^2$.
+    pub variance: f64,
+    /// Length scale of each input dimension, $l_d$.
+    pub length_scales: Vec<f64>,
+    /// Observation noise variance, $\sigma_n^2$.
+    pub noise_variance: f64,
+}
+
+/// Gaussian process conditioned on observations.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FittedGaussianProcess {
+    /// Hyperparameters.
+    pub process: GaussianProcess,
+    /// Log marginal likelihood of the observations.
+    pub log_marginal_likelihood: f64,
+    x: DMatrix<f64>,
+    y_mean: f64,
+    alpha: DVector<f64>,
+    cholesky: Cholesky<f64, Dyn>,
+}
+
+/// Posterior mean and standard deviation of the latent function.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GaussianProcessPrediction {
+    /// Posterior mean.
+    pub mean: DVector<f64>,
+    /// Posterior standard deviation (excluding the observation noise).
+    pub std_dev: DVector<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Kernel {
+    /// Correlation at scaled distance `r`.
+    #[inline]
+    pub fn correlation(&self, r: f64) -> f64 {
+        match self {
+            Kernel::Rbf => (-0.5 * r * r).exp(),
+            Kernel::Matern12 => (-r).exp(),
+            Kernel::Matern32 => {
+                let s = 3_f64.sqrt() * r;
+                (1.0 + s) * (-s).exp()
+            }
+            Kernel::Matern52 => {
+                let s = 5_f64.sqrt() * r;
+                (1.0 + s + s * s / 3.0) * (-s).exp()
+            }
+        }
+    }
+
+    // -k'(r) / r, so that d k / d ln(l_d) = -k'(r) r_d^2 / r.
+    #[inline]
+    fn radial_derivative(&self, r: f64) -> f64 {
+        match self {
+            Kernel::Rbf => (-0.5 * r * r).exp(),
+            Kernel::Matern12 => match r > 0.0 {
+                true => (-r).exp() / r,
+                false => 0.0,
+            },
+            Kernel::Matern32 => 3.0 * (-3_f64.sqrt() * r).exp(),
+            Kernel::Matern52 => {
+                let s = 5_f64.sqrt() * r;
+                5.0 / 3.0 * (1.0 + s) * (-s).exp()
+            }
+        }
+    }
+}
+
+impl GaussianProcess {
+    /// New Gaussian process with the given hyperparameters.
+    pub fn new(
+        kernel: Kernel,
+        variance: f64,
+        length_scales: Vec<f64>,
+        noise_variance: f64,
+    ) -> Self {
+        assert!(variance > 0.0);
+        assert!(length_scales.iter().all(|&l| l > 0.0));
+        assert!(noise_variance >= 0.0);
+
+        Self {
+            kernel,
+            variance,
+            length_scales,
+            noise_variance,
+        }
+    }
+
+    /// Covariance $\sigma_f^2 k(r)$ between the rows of `a` and `b`.
+    pub fn covariance(&self, a: &DMatrix<f64>, b: &DMatrix<f64>) -> DMatrix<f64> {
+        DMatrix::from_fn(a.nrows(), b.nrows(), |i, j| {
+            self.variance * self.kernel.correlation(self.distance(a, i, b, j))
+        })
+    }
+
+    /// Conditions the process on observations `y` at the rows of `x`.
+    pub fn fit(
+        &self,
+        x: &DMatrix<f64>,
+        y: &DVector<f64>,
+    ) -> Result<FittedGaussianProcess, GaussianProcessError> {
+        if x.nrows() != y.len() || x.ncols() != self.length_scales.len() {
+            return Err(GaussianProcessError::DimensionMismatch {
+                expected: x.nrows(),
+                dimension: x.ncols(),
+            });
+        }
+
+        let y_mean = y.mean();
+        let y = y.add_scalar(-y_mean);
+
+        let mut k = self.covariance(x, x);
+        // Small jitter for numerical stability without noise.
+        let jitter = 1e-10 * self.variance;
+        for i in 0..k.nrows() {
+            k[(i, i)] += self.noise_variance + jitter;
+        }
+
+        let cholesky = k
+            .cholesky()
+            .ok_or(GaussianProcessError::NotPositiveDefinite)?;
+        let alpha = cholesky.solve(&y);
+
+        let log_det = 2.0 * cholesky.l_dirty().diagonal().map(f64::ln).sum();
+        let log_marginal_likelihood = -0.5 * y.dot(&alpha)
+            - 0.5 * log_det
+            - 0.5 * y.len() as f64 * (2.0 * std::f64::consts::PI).ln();
+
+        Ok(FittedGaussianProcess {
+            process: self.clone(),
+            log_marginal_likelihood,
+            x: x.clone(),
+            y_mean,
+            alpha,
+            cholesky,
+        })
+    }
+
+    /// Fits the hyperparameters by maximising the log marginal likelihood
+    /// with L-BFGS (in log space), starting from the current values, and
+    /// conditions the resulting process on the observations.
+    pub fn optimize(
+        &self,
+        x: &DMatrix<f64>,
+        y: &DVector<f64>,
+        max_iterations: usize,
+    ) -> Result<FittedGaussianProcess, GaussianProcessError> {
+        // Checks the dimensions and the starting point.
+        self.fit(x, y)?;
+
+        let d = self.length_scales.len();
+        let with = |theta: &[f64]| GaussianProcess {
+            kernel: self.kernel,
+            variance: theta[0].exp(),
+            length_scales: theta[1..=d].iter().map(|t| t.exp()).collect(),
+            noise_variance: theta[d + 1].exp(),
+        };
+
+        let objective = GradientObjective::new(
+            |theta: &[f64]| match with(theta).fit(x, y) {
+                Ok(fitted) => -fitted.log_marginal_likelihood,
+                Err(_) => f64::INFINITY,
+            },
+            |theta: &[f64]| match with(theta).fit(x, y) {
+                Ok(fitted) => fitted.gradient().iter().map(|g| -g).collect(),
+                Err(_) => vec![0.0; d + 2],
+            },
+        );
+
+        // Zero noise has no logarithm: start it from a small fraction of
+        // the signal variance instead.
+        let noise = self.noise_variance.max(1e-8 * self.variance);
+        let mut theta0 = vec![self.variance.ln()];
+        theta0.extend(self.length_scales.iter().map(|l| l.ln()));
+        theta0.push(noise.ln());
+
+        // Keep the noise variance away from zero (and the covariance
+        // matrix away from singular).
+        let mut lower = vec![-30.0; d + 2];
+        let upper = vec![30.0; d + 2];
+        lower[d + 1] = (1e-12 * y.variance().max(f64::MIN_POSITIVE)).ln();
+
+        let result = Lbfgs::new(max_iterations, 1e-8)
+            .with_bounds(Bounds::new(lower, upper))
+            .minimize(&objective, &theta0);
+
+        with(&result.minimizer).fit(x, y)
+    }
+
+    fn distance(&self, a: &DMatrix<f64>, i: usize, b: &DMatrix<f64>, j: usize) -> f64 {
+        self.length_scales
+            .iter()
+            .enumerate()
+            .map(|(d, l)| ((a[(i, d)] - b[(j, d)]) / l).powi(2))
+            .sum::<f64>()
+            .sqrt()
+    }
+}
+
+impl FittedGaussianProcess {
+    /// Posterior of the latent function at the rows of `x`.
+    pub fn predict(&self, x: &DMatrix<f64>) -> GaussianProcessPrediction {
+        let k_star = self.process.covariance(&self.x, x);
+
+        let mean = k_star.transpose() * &self.alpha;
+        let v = self
+            .cholesky
+            .l_dirty()
+            .solve_lower_triangular(&k_star)
+            .expect("Cholesky factor is not singular.");
+
+        let std_dev = DVector::from_iterator(
+            x.nrows(),
+            v.column_iter()
+                .map(|v| (self.process.variance - v.norm_squared()).max(0.0).sqrt()),
+        );
+
+        GaussianProcessPrediction {
+            mean: mean.add_scalar(self.y_mean),
+            std_dev,
+        }
+    }
+
+    /// Gradient of the log marginal likelihood with respect to the log
+    /// hyperparameters, $(\ln \sigma_f^2, \ln l_1, \dots, \ln l_d, \ln \sigma_n^2)$:
+    /// $\frac{1}{2} \text{tr}((\alpha \alpha^T - K^{-1}) \partial K)$.
+    pub fn gradient(&self) -> Vec<f64> {
+        let process = &self.process;
+        let n = self.x.nrows();
+
+        let inverse = self.cholesky.inverse();
+        let w = &self.alpha * self.alpha.transpose() - inverse;
+
+        let mut gradient = vec![0.0; process.length_scales.len() + 2];
+
+        for i in 0..n {
+            for j in 0..n {
+                let r = process.distance(&self.x, i, &self.x, j);
+                let w_ij = 0.5 * w[(i, j)];
+
+                gradient[0] += w_ij * process.variance * process.kernel.correlation(r);
+
+                let radial = process.variance * process.kernel.radial_derivative(r);
+                for (d, l) in process.length_scales.iter().enumerate() {
+                    let r_d = (self.x[(i, d)] - self.x[(j, d)]) / l;
+                    gradient[d + 1] += w_ij * radial * r_d * r_d;
+                }
+            }
+            gradient[process.length_scales.len() + 1] += 0.5 * w[(i, i)] * process.noise_variance;
+        }
+
+        gradient
+    }
+}
+
+impl GaussianProcessPrediction {
+    /// Band `mean -/+ z std_dev`, e.g. `z = 1.96` for 95%.
+    pub fn band(&self, z: f64) -> (DVector<f64>, DVector<f64>) {
+        (
+            &self.mean - &self.std_dev * z,
+            &self.mean + &self.std_dev * z,
+        )
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_gaussian_process {
+    use super::*;
+    use crate::math::Pcg64;
+    use rand::Rng;
+    use rand_distr::StandardNormal;
+
+    const KERNELS: [Kernel; 4] = [
+        Kernel::Rbf,
+        Kernel::Matern12,
+        Kernel::Matern32,
+        Kernel::Matern52,
+    ];
+
+    #[test]
+    fn test_gradient_matches_finite_differences() {
+        let x = DMatrix::from_row_slice(5, 2, &[0.0, 1.0, 0.5, 0.2, 1.0, 0.7, 1.7, 0.1, 2.0, 1.5]);
+        let y = DVector::from_vec(vec![0.3, -0.1, 0.4, 0.9, 0.2]);
+
+        for kernel in KERNELS {
+            let theta = [0.3_f64, -0.2, 0.4, -2.0];
+            let lml = |theta: &[f64]| {
+                GaussianProcess::new(
+                    kernel,
+                    theta[0].exp(),
+                    vec![theta[1].exp(), theta[2].exp()],
+                    theta[3].exp(),
+                )
+                .fit(&x, &y)
+                .unwrap()
+                .log_marginal_likelihood
+            };
+
+            let fitted = GaussianProcess::new(
+                kernel,
+                theta[0].exp(),
+                vec![theta[1].exp(), theta[2].exp()],
+                theta[3].exp(),
+            )
+            .fit(&x, &y)
+            .unwrap();
+            let gradient = fitted.gradient();
+
+            let h = 1e-6;
+            for k in 0..4 {
+                let (mut up, mut down) = (theta, theta);
+                up[k] += h;
+                down[k] -= h;
+                let fd = (lml(&up) - lml(&down)) / (2.0 * h);
+                assert_approx_equal!(gradient[k], fd, 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_interpolation_without_noise() {
+        let x = DMatrix::from_column_slice(4, 1, &[0.0, 1.0, 2.0, 3.0]);
+        let y = DVector::from_vec(vec![1.0, 2.0, 0.5, 1.5]);
+
+        for kernel in KERNELS {
+            let gp = GaussianProcess::new(kernel, 1.0, vec![1.0], 0.0)
+                .fit(&x, &y)
+                .unwrap();
+            let prediction = gp.predict(&x);
+
+            for i in 0..4 {
+                assert_approx_equal!(prediction.mean[i], y[i], 1e-6);
+                assert!(prediction.std_dev[i] < 1e-3);
+            }
+        }
+
+        // One length scale per input column.
+        let mismatch = GaussianProcess::new(Kernel::Rbf, 1.0, vec![1.0, 1.0], 0.0).fit(&x, &y);
+        assert_eq!(
+            mismatch.unwrap_err(),
+            GaussianProcessError::DimensionMismatch {
+                expected: 4,
+                dimension: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_yield_curve_smoothing() {
+        // Nelson-Siegel zero rates with 5bp quote noise.
+        let curve = |t: f64| {
+            let decay = (1.0 - (-t / 2.0).exp()) / (t / 2.0);
+            0.04 - 0.02 * decay + 0.01 * (decay - (-t / 2.0).exp())
+        };
+        let tenors = [
+            0.25, 0.5, 1.0, 1.5, 2.0, 3.0, 4.0, 5.0, 7.0, 10.0, 15.0, 20.0, 25.0, 30.0,
+        ];
+
+        let mut rng = Pcg64::stream(7, 0);
+        let x = DMatrix::from_column_slice(tenors.len(), 1, &tenors);
+        let y = DVector::from_iterator(
+            tenors.len(),
+            tenors
+                .iter()
+                .map(|&t| curve(t) + 0.0005 * rng.sample::<f64, _>(StandardNormal)),
+        );
+
+        let gp = GaussianProcess::new(Kernel::Matern52, 1e-4, vec![5.0], 1e-8)
+            .optimize(&x, &y, 200)
+            .unwrap();
+
+        // The likelihood improves, and the noise is estimated at the right
+        // order of magnitude.
+        let start = GaussianProcess::new(Kernel::Matern52, 1e-4, vec![5.0], 1e-8)
+            .fit(&x, &y)
+            .unwrap();
+        assert!(gp.log_marginal_likelihood > start.log_marginal_likelihood);
+        let noise = gp.process.noise_variance.sqrt();
+        assert!(noise > 1e-4 && noise < 2e-3, "Noise: {noise}");
+
+        // The smoothed curve is closer to the truth than the quotes, and
+        // the 95% band covers it.
+        let grid: Vec<f64> = (1..=60).map(|i| 0.5 * i as f64).collect();
+        let prediction = gp.predict(&DMatrix::from_column_slice(grid.len(), 1, &grid));
+        let (lower, upper) = prediction.band(1.96);
+
+        let mut covered = 0;
+        for (i, &t) in grid.iter().enumerate() {
+            assert!((prediction.mean[i] - curve(t)).abs() < 0.001);
+            covered += usize::from(lower[i] <= curve(t) && curve(t) <= upper[i]);
+        }
+        assert!(covered as f64 / grid.len() as f64 > 0.9);
+    }
+
+    #[test]
+    fn test_volatility_surface_interpolation() {
+        // Smile in log-moneyness k, flattening with expiry t. The inputs
+        // are (k, ln t), in which the surface varies on a similar scale.
+        let vol = |k: f64, t: f64| 0.2 - 0.1 * k / t.sqrt() + 0.3 * k * k / t;
+
+        let (ks, ts) = (
+            [-0.3, -0.2, -0.1, 0.0, 0.1, 0.2, 0.3],
+            [0.25, 0.5, 1.0, 2.0],
+        );
+        let points: Vec<(f64, f64)> = ts
+            .iter()
+            .flat_map(|&t| ks.iter().map(move |&k| (k, t)))
+            .collect();
+
+        let x = DMatrix::from_fn(points.len(), 2, |i, j| match j {
+            0 => points[i].0,
+            _ => points[i].1.ln(),
+        });
+        let y = DVector::from_iterator(points.len(), points.iter().map(|&(k, t)| vol(k, t)));
+
+        let gp = GaussianProcess::new(Kernel::Rbf, 0.01, vec![0.2, 1.0], 1e-10)
+            .optimize(&x, &y, 200)
+            .unwrap();
+
+        // Off-grid points inside the quoted range.
+        let off_grid = [(-0.25, 0.75_f64), (0.05, 0.75), (0.15, 1.5), (-0.05, 0.35)];
+        let x_star = DMatrix::from_fn(off_grid.len(), 2, |i, j| match j {
+            0 => off_grid[i].0,
+            _ => off_grid[i].1.ln(),
+        });
+        let prediction = gp.predict(&x_star);
+
+        for (i, &(k, t)) in off_grid.iter().enumerate() {
+            let error = (prediction.mean[i] - vol(k, t)).abs();
+            assert!(error < 0.005, "Error at ({k}, {t}): {error}");
+            assert!(error < 4.0 * prediction.std_dev[i] + 1e-4);
+        }
+
+        // Uncertainty grows away from the quotes.
+        let far = gp.predict(&DMatrix::from_row_slice(1, 2, &[0.3, 5_f64.ln()]));
+        assert!(far.std_dev[0] > prediction.std_dev.max());
+    }
+}
@@ -0,0 +1,409 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Gradient boosted regression trees (Friedman, 2001).
+//!
+//! The model is a sum $F(x) = F_0 + \nu \sum_m h_m(x)$ of small regression
+//! trees. Each tree is fitted to the negative gradient of the loss at the
+//! current predictions (the residuals for squared error, $y - p$ for the
+//! logistic loss), and its leaf values are then set to a Newton step for
+//! the loss. With `subsample < 1`, each tree sees a random fraction of the
+//! samples (stochastic gradient boosting, Friedman 2002).
+//!
+//! ```rust
+//! use RustQuant::ml::*;
+//! use nalgebra::{DMatrix, DVector};
+//!
+//! // Next-day direction from two features, with an interaction.
+//! let x = DMatrix::from_fn(200, 2, |i, j| (((i * (7 + j * 5)) % 50) as f64) / 50.0 - 0.5);
+//! let y = DVector::from_fn(200, |i, _| f64::from(u8::from(x[(i, 0)] * x[(i, 1)] > 0.0)));
+//!
+//! let model = GradientBoosting::new(BoostingLoss::Logistic)
+//!     .with_n_estimators(50)
+//!     .fit(&x, &y)
+//!     .unwrap();
+//!
+//! let accuracy = model.predict(&x).iter().zip(y.iter()).filter(|(p, y)| p == y).count();
+//! assert!(accuracy > 190);
+//! ```
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::math::Pcg64;
+use crate::ml::decision_tree::rows;
+use crate::ml::{DecisionTree, FittedTree, Node, SplitCriterion, TreeError};
+use nalgebra::{DMatrix, DVector};
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Loss minimised by gradient boosting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BoostingLoss {
+    /// Squared error, for regression (e.g. of returns).
+    SquaredError,
+    /// Binomial deviance, for binary classification (labels 0 and 1, e.g.
+    /// the sign of the next return). Scores are log-odds.
+    Logistic,
+}
+
+/// Settings of a gradient boosting model.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GradientBoosting {
+    /// Loss function.
+    pub loss: BoostingLoss,
+    /// Number of trees.
+    pub n_estimators: usize,
+    /// Shrinkage of each tree, $\nu$.
+    pub learning_rate: f64,
+    /// Settings of each (regression) tree.
+    pub tree: DecisionTree,
+    /// Fraction of the samples used to fit each tree.
+    pub subsample: f64,
+    /// Seed of the subsampling.
+    pub seed: u64,
+}
+
+/// A fitted gradient boosting model.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FittedGradientBoosting {
+    /// Loss function.
+    pub loss: BoostingLoss,
+    /// Initial score, $F_0$: the mean target, or the log-odds of class 1.
+    pub initial: f64,
+    /// Shrinkage of each tree.
+    pub learning_rate: f64,
+    /// Fitted trees.
+    pub trees: Vec<FittedTree>,
+    /// Normalised impurity decrease due to each feature, over all trees.
+    pub feature_importances: Vec<f64>,
+    /// Training loss after each tree (mean squared error or mean deviance).
+    pub training_loss: Vec<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl BoostingLoss {
+    /// Mean loss of the scores.
+    fn mean_loss(&self, y: &DVector<f64>, scores: &DVector<f64>) -> f64 {
+        let n = y.len() as f64;
+
+        match self {
+            BoostingLoss::SquaredError => (y - scores).norm_squared() / n,
+            BoostingLoss::Logistic => {
+                y.iter()
+                    .zip(scores.iter())
+                    .map(|(y, f)| f.max(0.0) + (-f.abs()).exp().ln_1p() - y * f)
+                    .sum::<f64>()
+                    / n
+            }
+        }
+    }
+}
+
+impl GradientBoosting {
+    /// 100 trees of depth three, with a learning rate of 0.1 and no
+    /// subsampling.
+    pub fn new(loss: BoostingLoss) -> Self {
+        Self {
+            loss,
+            n_estimators: 100,
+            learning_rate: 0.1,
+            tree: DecisionTree::new(SplitCriterion::SquaredError).with_max_depth(3),
+            subsample: 1.0,
+            seed: 0,
+        }
+    }
+
+    /// Sets the number of trees.
+    pub fn with_n_estimators(mut self, n_estimators: usize) -> Self {
+        self.n_estimators = n_estimators;
+        self
+    }
+
+    /// Sets the learning rate.
+    pub fn with_learning_rate(mut self, learning_rate: f64) -> Self {
+        assert!(learning_rate > 0.0, "Learning rate must be positive.");
+        self.learning_rate = learning_rate;
+        self
+    }
+
+    /// Sets the maximum depth of each tree.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.tree = self.tree.with_max_depth(max_depth);
+        self
+    }
+
+    /// Sets the minimum number of samples in each leaf.
+    pub fn with_min_samples_leaf(mut self, min_samples_leaf: usize) -> Self {
+        self.tree = self.tree.with_min_samples_leaf(min_samples_leaf);
+        self
+    }
+
+    /// Fits each tree on a random fraction of the samples.
+    pub fn with_subsample(mut self, subsample: f64, seed: u64) -> Self {
+        assert!(
+            subsample > 0.0 && subsample <= 1.0,
+            "Subsample must be in (0, 1]."
+        );
+        self.subsample = subsample;
+        self.seed = seed;
+        self
+    }
+
+    /// Fits the model to the rows of `x` (one per sample) and the targets
+    /// `y` (0 or 1 for the logistic loss).
+    pub fn fit(
+        &self,
+        x: &DMatrix<f64>,
+        y: &DVector<f64>,
+    ) -> Result<FittedGradientBoosting, TreeError> {
+        if x.nrows() != y.len() {
+            return Err(TreeError::DimensionMismatch {
+                rows: x.nrows(),
+                observations: y.len(),
+            });
+        }
+        if y.is_empty() {
+            return Err(TreeError::EmptyData);
+        }
+        if self.loss == BoostingLoss::Logistic {
+            if let Some(&label) = y.iter().find(|&&l| l != 0.0 && l != 1.0) {
+                return Err(TreeError::InvalidLabel(label));
+            }
+        }
+
+        let n = y.len();
+        let initial = match self.loss {
+            BoostingLoss::SquaredError => y.mean(),
+            BoostingLoss::Logistic => {
+                let p = y.mean().clamp(1e-10, 1.0 - 1e-10);
+                (p / (1.0 - p)).ln()
+            }
+        };
+
+        let samples: Vec<Vec<f64>> = rows(x).collect();
+        let mut scores = DVector::from_element(n, initial);
+        let mut trees = Vec::with_capacity(self.n_estimators);
+        let mut training_loss = Vec::with_capacity(self.n_estimators);
+        let mut impurity_decreases = vec![0.0; x.ncols()];
+
+        let mut rng = Pcg64::stream(self.seed, 0);
+        let mut all: Vec<usize> = (0..n).collect();
+        let n_subsample = ((self.subsample * n as f64).round() as usize).clamp(1, n);
+
+        for _ in 0..self.n_estimators {
+            let (gradient, hessian) = self.derivatives(y, &scores);
+
+            let rows = match n_subsample < n {
+                true => {
+                    all.shuffle(&mut rng);
+                    all[..n_subsample].to_vec()
+                }
+                false => all.clone(),
+            };
+
+            let mut tree = self.tree.fit_rows(x, &gradient, rows.clone())?;
+
+            // Newton step in each leaf: sum of gradients over sum of
+            // hessians of the samples that fell in it.
+            let mut sums: HashMap<usize, (f64, f64)> = HashMap::new();
+            for &i in &rows {
+                let entry = sums.entry(tree.leaf(&samples[i])).or_default();
+                entry.0 += gradient[i];
+                entry.1 += hessian[i];
+            }
+            for (leaf, (g, h)) in sums {
+                tree.nodes[leaf] = Node::Leaf {
+                    value: vec![match h > 1e-12 {
+                        true => g / h,
+                        false => 0.0,
+                    }],
+                };
+            }
+
+            for (i, sample) in samples.iter().enumerate() {
+                scores[i] += self.learning_rate * tree.leaf_value(sample)[0];
+            }
+            impurity_decreases
+                .iter_mut()
+                .zip(&tree.impurity_decreases)
+                .for_each(|(total, d)| *total += d);
+
+            training_loss.push(self.loss.mean_loss(y, &scores));
+            trees.push(tree);
+        }
+
+        let total: f64 = impurity_decreases.iter().sum();
+        let feature_importances = match total > 0.0 {
+            true => impurity_decreases.iter().map(|d| d / total).collect(),
+            false => impurity_decreases,
+        };
+
+        Ok(FittedGradientBoosting {
+            loss: self.loss,
+            initial,
+            learning_rate: self.learning_rate,
+            trees,
+            feature_importances,
+            training_loss,
+        })
+    }
+
+    /// Negative gradient and hessian of the loss at the scores.
+    fn derivatives(&self, y: &DVector<f64>, scores: &DVector<f64>) -> (DVector<f64>, DVector<f64>) {
+        match self.loss {
+            BoostingLoss::SquaredError => (y - scores, DVector::from_element(y.len(), 1.0)),
+            BoostingLoss::Logistic => {
+                let p = scores.map(|f| 1.0 / (1.0 + (-f).exp()));
+                (y - &p, p.map(|p| p * (1.0 - p)))
+            }
+        }
+    }
+}
+
+impl FittedGradientBoosting {
+    /// Raw scores $F(x)$ (log-odds for the logistic loss) for each row of
+    /// `x`.
+    pub fn decision_function(&self, x: &DMatrix<f64>) -> DVector<f64> {
+        DVector::from_iterator(
+            x.nrows(),
+            rows(x).map(|sample| {
+                self.initial
+                    + self.learning_rate
+                        * self
+                            .trees
+                            .iter()
+                            .map(|tree| tree.leaf_value(&sample)[0])
+                            .sum::<f64>()
+            }),
+        )
+    }
+
+    /// Predicted values, or classes (0 or 1) for the logistic loss.
+    pub fn predict(&self, x: &DMatrix<f64>) -> DVector<f64> {
+        let scores = self.decision_function(x);
+
+        match self.loss {
+            BoostingLoss::SquaredError => scores,
+            BoostingLoss::Logistic => scores.map(|f| f64::from(u8::from(f > 0.0))),
+        }
+    }
+
+    /// Probabilities of class 1 (logistic loss).
+    pub fn predict_proba(&self, x: &DMatrix<f64>) -> DVector<f64> {
+        assert_eq!(
+            self.loss,
+            BoostingLoss::Logistic,
+            "Probabilities need the logistic loss."
+        );
+
+        self.decision_function(x).map(|f| 1.0 / (1.0 + (-f).exp()))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_gradient_boosting {
+    use super::*;
+    use rand::Rng;
+    use rand_distr::StandardNormal;
+
+    fn uniform(n: usize, d: usize, rng: &mut Pcg64) -> DMatrix<f64> {
+        DMatrix::from_fn(n, d, |_, _| rng.gen::<f64>() * 2.0 - 1.0)
+    }
+
+    #[test]
+    fn test_regression() {
+        let mut rng = Pcg64::stream(11, 0);
+        let f = |x: &DMatrix<f64>, i: usize| (3.0 * x[(i, 0)]).sin() + x[(i, 1)] * x[(i, 1)];
+
+        let x = uniform(500, 4, &mut rng);
+        let y = DVector::from_fn(500, |i, _| {
+            f(&x, i) + 0.1 * rng.sample::<f64, _>(StandardNormal)
+        });
+        let x_test = uniform(500, 4, &mut rng);
+        let y_test = DVector::from_fn(500, |i, _| f(&x_test, i));
+
+        let model = GradientBoosting::new(BoostingLoss::SquaredError)
+            .with_n_estimators(200)
+            .fit(&x, &y)
+            .unwrap();
+
+        // The training loss decreases, and the test error is small compared
+        // to the variance of the target.
+        assert!(model.training_loss.windows(2).all(|w| w[1] <= w[0] + 1e-12));
+        let mse = (model.predict(&x_test) - &y_test).norm_squared() / 500.0;
+        let variance = y_test.variance();
+        assert!(mse < 0.05 * variance, "MSE {mse}, variance {variance}");
+
+        // Noise features are (nearly) unused.
+        let importances = &model.feature_importances;
+        assert_approx_equal!(importances.iter().sum::<f64>(), 1.0, 1e-12);
+        assert!(importances[0] + importances[1] > 0.95);
+
+        // A single stump is far worse.
+        let stump = GradientBoosting::new(BoostingLoss::SquaredError)
+            .with_n_estimators(1)
+            .with_learning_rate(1.0)
+            .with_max_depth(1)
+            .fit(&x, &y)
+            .unwrap();
+        assert!((stump.predict(&x_test) - &y_test).norm_squared() / 500.0 > 5.0 * mse);
+    }
+
+    #[test]
+    fn test_classification() {
+        let mut rng = Pcg64::stream(12, 0);
+
+        // Direction depends on an interaction that a linear model misses.
+        let label = |x: &DMatrix<f64>, i: usize| f64::from(u8::from(x[(i, 0)] * x[(i, 1)] > 0.0));
+        let x = uniform(600, 3, &mut rng);
+        let y = DVector::from_fn(600, |i, _| label(&x, i));
+        let x_test = uniform(600, 3, &mut rng);
+        let y_test = DVector::from_fn(600, |i, _| label(&x_test, i));
+
+        let model = GradientBoosting::new(BoostingLoss::Logistic)
+            .with_subsample(0.8, 1)
+            .fit(&x, &y)
+            .unwrap();
+
+        let accuracy = model
+            .predict(&x_test)
+            .iter()
+            .zip(y_test.iter())
+            .filter(|(p, y)| p == y)
+            .count() as f64
+            / 600.0;
+        assert!(accuracy > 0.93, "Accuracy: {accuracy}");
+
+        let probabilities = model.predict_proba(&x_test);
+        assert!(probabilities.iter().all(|p| (0.0..=1.0).contains(p)));
+        assert!(model.feature_importances[2] < 0.05);
+
+        // The initial score is the log-odds of the training labels.
+        let p = y.mean();
+        assert_approx_equal!(model.initial, (p / (1.0 - p)).ln(), 1e-12);
+        assert_eq!(
+            GradientBoosting::new(BoostingLoss::Logistic).fit(&x, &y.add_scalar(1.0)),
+            Err(TreeError::InvalidLabel(2.0))
+        );
+    }
+}
@@ -94,8 +94,8 @@ impl KNearestClassifier<f64> {
         assert_eq!(self.x.ncols(), xprime.ncols());
         let mut predictions: Vec<f64> = vec![0.0; xprime.nrows()];
 
-        for i in 0..predictions.len() {
-            predictions[i] =
+        for (i, prediction) in predictions.iter_mut().enumerate() {
+            *prediction =
                 self.predict_one(&DMatrix::from(xprime.view((i, 0), (1, xprime.ncols()))), k);
         }
 
@@ -110,8 +110,8 @@ impl KNearestClassifier<f64> {
 
         let mut distances: Vec<(usize, f64)> = vec![(0, 0.0); n_samples];
 
-        for i in 0..n_samples {
-            distances[i] = (
+        for (i, distance) in distances.iter_mut().enumerate() {
+            *distance = (
                 i,
                 match self.metric {
                     Metric::Euclidean => self.x.row(i).metric_distance(xprime),
@@ -188,7 +188,6 @@ impl LinearRegressionOutput<f64> {
 #[cfg(test)]
 mod tests_linear_regression {
     use super::*;
-    use crate::assert_approx_equal;
     use std::time::Instant;
 
     #[test]
@@ -13,15 +13,51 @@
 //!
 //! - [x] Linear (using QR or SVD decomposition)
 //! - [x] Logistic (via IRLS, adding MLE in the future).
+//! - [x] OLS, ridge, lasso, elastic net and (penalized) logistic regression
+//!   on named design matrices (built from Polars data frames with the
+//!   `data` feature), with standard errors, t-statistics and p-values.
+//! - [x] Gaussian process (RBF and Matérn kernels), e.g. for curve and
+//!   surface smoothing with uncertainty bands.
 //!
 //! ### Classification
 //!
 //! - [x] K-Nearest Neighbours
+//! - [x] Decision trees (CART) and gradient boosting, with feature
+//!   importances.
+//!
+//! ### Clustering
+//!
+//! - [x] K-means and hierarchical (single, complete, average, Ward)
+//!   clustering, e.g. of assets by correlation distance.
+//!
+//! ### Neural networks
+//!
+//! - [x] Multilayer perceptron, e.g. for surrogate pricers.
 
 /// Submodule of `ml`: activation functions.
 pub mod activations;
 pub use activations::*;
 
+/// K-means and hierarchical clustering.
+pub mod clustering;
+pub use clustering::*;
+
+/// Classification and regression trees.
+pub mod decision_tree;
+pub use decision_tree::*;
+
+/// Design matrices with named columns.
+pub mod design_matrix;
+pub use design_matrix::*;
+
+/// Gaussian process regression.
+pub mod gaussian_process;
+pub use gaussian_process::*;
+
+/// Gradient boosted trees.
+pub mod gradient_boosting;
+pub use gradient_boosting::*;
+
 /// K Nearest Neighbor classifier
 pub mod k_nearest_neighbors;
 pub use k_nearest_neighbors::*;
@@ -33,3 +69,11 @@ pub use linear_regression::*;
 /// Logistic regression.
 pub mod logistic_regression;
 pub use logistic_regression::*;
+
+/// Multilayer perceptron.
+pub mod neural_network;
+pub use neural_network::*;
+
+/// Penalized linear and logistic regression with inference.
+pub mod regularized_regression;
+pub use regularized_regression::*;
@@ -7,22 +7,618 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-//! Module for neural networks.
-//! Will hopefully implement at least a simple multilayer perceptron,
-//! using the `RustQuant::autodiff` module.
+//! Module for neural networks: a multilayer perceptron (MLP) for
+//! approximating pricing functions.
+//!
+//! A surrogate pricer is trained once, offline, on (parameters, price)
+//! pairs generated by one of the crate's pricers ([`sample_dataset`]), and
+//! is then cheap to evaluate inside calibration and risk loops. Its
+//! sensitivities come from backpropagation ([`MultilayerPerceptron::jacobian`]),
+//! or from the autodiff graph ([`MultilayerPerceptron::predict_variables`]),
+//! e.g. to calibrate through the surrogate.
+//!
+//! Inputs and outputs are standardised internally, so the network can be
+//! trained on raw parameters and prices.
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // IMPORTS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+use crate::autodiff::{Graph, Variable};
+use crate::math::Pcg64;
+use crate::ml::ActivationFunction;
+use nalgebra::{DMatrix, DVector};
+use rand::{seq::SliceRandom, Rng};
+use rand_distr::StandardNormal;
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // STRUCTS, ENUMS, AND TRAITS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+/// Activation of the hidden layers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Activation {
+    /// No activation (linear layer).
+    Identity,
+    /// Hyperbolic tangent.
+    Tanh,
+    /// Logistic sigmoid.
+    Sigmoid,
+    /// Rectified linear unit.
+    Relu,
+    /// Softplus, $\ln(1 + e^x)$: smooth, so the surrogate has smooth Greeks.
+    #[default]
+    Softplus,
+}
+
+/// Fully connected layer, $a = f(W x + b)$.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DenseLayer {
+    /// Weights (outputs in rows, inputs in columns).
+    pub weights: DMatrix<f64>,
+    /// Biases.
+    pub biases: DVector<f64>,
+    /// Activation function.
+    pub activation: Activation,
+}
+
+/// Multilayer perceptron with standardised inputs and outputs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultilayerPerceptron {
+    /// Layers, from the input to the (linear) output layer.
+    pub layers: Vec<DenseLayer>,
+    input_mean: DVector<f64>,
+    input_scale: DVector<f64>,
+    output_mean: DVector<f64>,
+    output_scale: DVector<f64>,
+}
+
+/// Settings of the mini-batch Adam optimiser used for training.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrainingConfig {
+    /// Number of passes over the training set.
+    pub epochs: usize,
+    /// Number of samples per gradient step.
+    pub batch_size: usize,
+    /// Adam step size.
+    pub learning_rate: f64,
+    /// Seed of the shuffling of the training set.
+    pub seed: u64,
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // IMPLEMENTATIONS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+impl Activation {
+    /// Value of the activation at `x`.
+    #[inline]
+    pub fn apply(&self, x: f64) -> f64 {
+        match self {
+            Activation::Identity => x,
+            Activation::Tanh => ActivationFunction::tanh(&x),
+            Activation::Sigmoid => x.sigmoid(),
+            Activation::Relu => x.relu(),
+            Activation::Softplus => x.softplus(),
+        }
+    }
+
+    /// Derivative of the activation at `x`.
+    #[inline]
+    pub fn derivative(&self, x: f64) -> f64 {
+        match self {
+            Activation::Identity => 1.0,
+            Activation::Tanh => 1.0 - x.tanh().powi(2),
+            Activation::Sigmoid => x.sigmoid() * (1.0 - x.sigmoid()),
+            Activation::Relu => f64::from(x > 0.0),
+            Activation::Softplus => x.sigmoid(),
+        }
+    }
+}
+
+impl TrainingConfig {
+    /// New training settings, with batches of 32 samples.
+    pub fn new(epochs: usize, learning_rate: f64) -> Self {
+        assert!(learning_rate > 0.0);
+
+        Self {
+            epochs,
+            batch_size: 32,
+            learning_rate,
+            seed: 0,
+        }
+    }
+
+    /// Sets the number of samples per gradient step.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        assert!(batch_size > 0);
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Sets the seed of the shuffling.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+impl Default for TrainingConfig {
+    fn default() -> Self {
+        Self::new(100, 1e-3)
+    }
+}
+
+impl MultilayerPerceptron {
+    /// New network with the given layer `sizes` (inputs, hidden layers,
+    /// outputs), `activation` on the hidden layers and a linear output
+    /// layer. Weights are Glorot-initialised from `seed`.
+    pub fn new(sizes: &[usize], activation: Activation, seed: u64) -> Self {
+        assert!(sizes.len() >= 2, "At least an input and an output layer.");
+        assert!(sizes.iter().all(|&n| n > 0));
+
+        let mut rng = Pcg64::stream(seed, 0);
+
+        let layers = sizes
+            .windows(2)
+            .enumerate()
+            .map(|(l, n)| {
+                let scale = (2.0 / (n[0] + n[1]) as f64).sqrt();
+
+                DenseLayer {
+                    weights: DMatrix::from_fn(n[1], n[0], |_, _| {
+                        scale * rng.sample::<f64, _>(StandardNormal)
+                    }),
+                    biases: DVector::zeros(n[1]),
+                    activation: match l + 2 == sizes.len() {
+                        true => Activation::Identity,
+                        false => activation,
+                    },
+                }
+            })
+            .collect();
+
+        let (n_in, n_out) = (sizes[0], sizes[sizes.len() - 1]);
+
+        Self {
+            layers,
+            input_mean: DVector::zeros(n_in),
+            input_scale: DVector::from_element(n_in, 1.0),
+            output_mean: DVector::zeros(n_out),
+            output_scale: DVector::from_element(n_out, 1.0),
+        }
+    }
+
+    /// Number of inputs.
+    pub fn n_inputs(&self) -> usize {
+        self.layers[0].weights.ncols()
+    }
+
+    /// Number of outputs.
+    pub fn n_outputs(&self) -> usize {
+        self.layers[self.layers.len() - 1].weights.nrows()
+    }
+
+    /// Trains the network on the samples in the rows of `x` (inputs) and
+    /// `y` (targets), minimising the mean squared error with Adam.
+    /// Returns the training loss (on standardised outputs) of each epoch.
+    ///
+    /// The standardisation is fitted to `x` and `y`, so the network should
+    /// be trained on its whole training set at once.
+    pub fn fit(&mut self, x: &DMatrix<f64>, y: &DMatrix<f64>, config: &TrainingConfig) -> Vec<f64> {
+        assert_eq!(x.nrows(), y.nrows(), "One target row per input row.");
+        assert_eq!(x.ncols(), self.n_inputs());
+        assert_eq!(y.ncols(), self.n_outputs());
+        assert!(x.nrows() > 0);
+
+        (self.input_mean, self.input_scale) = standardisation(x);
+        (self.output_mean, self.output_scale) = standardisation(y);
+
+        // Samples in columns, standardised.
+        let x = scale(&x.transpose(), &self.input_mean, &self.input_scale);
+        let y = scale(&y.transpose(), &self.output_mean, &self.output_scale);
+
+        let mut adam = Adam::new(&self.layers);
+        let mut rng = Pcg64::stream(config.seed, 1);
+        let mut order: Vec<usize> = (0..x.ncols()).collect();
+        let mut losses = Vec::with_capacity(config.epochs);
+
+        for _ in 0..config.epochs {
+            order.shuffle(&mut rng);
+            let mut loss = 0.0;
+
+            for batch in order.chunks(config.batch_size) {
+                let x_batch = x.select_columns(batch);
+                let y_batch = y.select_columns(batch);
+
+                let (batch_loss, gradients) = self.backpropagate(&x_batch, &y_batch);
+                adam.step(&mut self.layers, &gradients, config.learning_rate);
+
+                loss += batch_loss * batch.len() as f64;
+            }
+
+            losses.push(loss / x.ncols() as f64);
+        }
+
+        losses
+    }
+
+    /// Network outputs at `x`.
+    pub fn predict(&self, x: &[f64]) -> DVector<f64> {
+        self.predict_batch(&DMatrix::from_row_slice(1, x.len(), x))
+            .row(0)
+            .transpose()
+    }
+
+    /// Network outputs for each row of `x`, in the rows of the result.
+    pub fn predict_batch(&self, x: &DMatrix<f64>) -> DMatrix<f64> {
+        assert_eq!(x.ncols(), self.n_inputs());
+
+        let input = scale(&x.transpose(), &self.input_mean, &self.input_scale);
+        let (_, activations) = self.forward(&input);
+        let output = &activations[activations.len() - 1];
+
+        let mut output = output.clone();
+        for (mut row, (m, s)) in output
+            .row_iter_mut()
+            .zip(self.output_mean.iter().zip(self.output_scale.iter()))
+        {
+            row.apply(|v| *v = m + s * *v);
+        }
+
+        output.transpose()
+    }
+
+    /// Jacobian of the network outputs with respect to the inputs at `x`
+    /// (outputs in rows, inputs in columns), e.g. the Greeks of a
+    /// surrogate pricer.
+    pub fn jacobian(&self, x: &[f64]) -> DMatrix<f64> {
+        assert_eq!(x.len(), self.n_inputs());
+
+        let input = scale(
+            &DMatrix::from_column_slice(x.len(), 1, x),
+            &self.input_mean,
+            &self.input_scale,
+        );
+        let (pre_activations, _) = self.forward(&input);
+
+        // Chain rule from the output back to the (standardised) input.
+        let mut jacobian = DMatrix::from_diagonal(&self.output_scale);
+        for (layer, z) in self.layers.iter().zip(&pre_activations).rev() {
+            let derivative = z.map(|z| layer.activation.derivative(z));
+            for (mut column, d) in jacobian.column_iter_mut().zip(derivative.iter()) {
+                column *= *d;
+            }
+            jacobian = &jacobian * &layer.weights;
+        }
+
+        for (mut column, s) in jacobian.column_iter_mut().zip(self.input_scale.iter()) {
+            column /= *s;
+        }
+
+        jacobian
+    }
+
+    /// Network outputs at `x` on the autodiff graph: one n-ary vertex per
+    /// neuron and one per activation, so a surrogate can be used wherever
+    /// a `Variable` pricer is expected (e.g. by the `Calibrator`).
+    pub fn predict_variables<'v>(&self, graph: &'v Graph, x: &[Variable<'v>]) -> Vec<Variable<'v>> {
+        assert_eq!(x.len(), self.n_inputs());
+
+        let mut a: Vec<Variable<'v>> = x
+            .iter()
+            .zip(self.input_mean.iter().zip(self.input_scale.iter()))
+            .map(|(&x, (m, s))| (x - *m) / *s)
+            .collect();
+
+        for layer in &self.layers {
+            let z: Vec<Variable<'v>> = layer
+                .weights
+                .row_iter()
+                .zip(layer.biases.iter())
+                .map(|(w, b)| {
+                    let w: Vec<f64> = w.iter().copied().collect();
+                    graph.dot_f64(&a, &w) + *b
+                })
+                .collect();
+
+            a = match layer.activation {
+                Activation::Identity => z,
+                activation => graph.map(&z, |z| (activation.apply(z), activation.derivative(z))),
+            };
+        }
+
+        a.iter()
+            .zip(self.output_mean.iter().zip(self.output_scale.iter()))
+            .map(|(&a, (m, s))| a * *s + *m)
+            .collect()
+    }
+
+    /// Mean squared error of the predictions for the rows of `x` against `y`.
+    pub fn mse(&self, x: &DMatrix<f64>, y: &DMatrix<f64>) -> f64 {
+        (self.predict_batch(x) - y).norm_squared() / y.len() as f64
+    }
+
+    // Pre-activations and activations of each layer, for samples in the
+    // columns of `input` (the input itself is the first activation).
+    fn forward(&self, input: &DMatrix<f64>) -> (Vec<DMatrix<f64>>, Vec<DMatrix<f64>>) {
+        let mut pre_activations = Vec::with_capacity(self.layers.len());
+        let mut activations = vec![input.clone()];
+
+        for layer in &self.layers {
+            let mut z = &layer.weights * &activations[activations.len() - 1];
+            for mut column in z.column_iter_mut() {
+                column += &layer.biases;
+            }
+
+            activations.push(z.map(|z| layer.activation.apply(z)));
+            pre_activations.push(z);
+        }
+
+        (pre_activations, activations)
+    }
+
+    // Mean squared error of a batch and its gradient with respect to the
+    // weights and biases of each layer.
+    fn backpropagate(&self, x: &DMatrix<f64>, y: &DMatrix<f64>) -> (f64, Vec<DenseLayer>) {
+        let (pre_activations, activations) = self.forward(x);
+        let n = y.len() as f64;
+
+        let residual = &activations[activations.len() - 1] - y;
+        let loss = residual.norm_squared() / n;
+
+        let mut delta = residual * (2.0 / n);
+        let mut gradients = Vec::with_capacity(self.layers.len());
+
+        for (l, layer) in self.layers.iter().enumerate().rev() {
+            delta.zip_apply(&pre_activations[l], |d, z| {
+                *d *= layer.activation.derivative(z)
+            });
+
+            gradients.push(DenseLayer {
+                weights: &delta * activations[l].transpose(),
+                biases: delta.column_sum(),
+                activation: layer.activation,
+            });
+
+            delta = layer.weights.transpose() * &delta;
+        }
+
+        gradients.reverse();
+        (loss, gradients)
+    }
+}
+
+// Adam moments, with the same shapes as the layers.
+struct Adam {
+    first: Vec<DenseLayer>,
+    second: Vec<DenseLayer>,
+    t: i32,
+}
+
+impl Adam {
+    const BETA_1: f64 = 0.9;
+    const BETA_2: f64 = 0.999;
+    const EPSILON: f64 = 1e-8;
+
+    fn new(layers: &[DenseLayer]) -> Self {
+        let zeros: Vec<DenseLayer> = layers
+            .iter()
+            .map(|layer| DenseLayer {
+                weights: layer.weights.map(|_| 0.0),
+                biases: layer.biases.map(|_| 0.0),
+                activation: layer.activation,
+            })
+            .collect();
+
+        Self {
+            first: zeros.clone(),
+            second: zeros,
+            t: 0,
+        }
+    }
+
+    fn step(&mut self, layers: &mut [DenseLayer], gradients: &[DenseLayer], learning_rate: f64) {
+        self.t += 1;
+        let step = learning_rate * (1.0 - Self::BETA_2.powi(self.t)).sqrt()
+            / (1.0 - Self::BETA_1.powi(self.t));
+
+        let update = |p: &mut f64, g: f64, m: &mut f64, v: &mut f64| {
+            *m = Self::BETA_1 * *m + (1.0 - Self::BETA_1) * g;
+            *v = Self::BETA_2 * *v + (1.0 - Self::BETA_2) * g * g;
+            *p -= step * *m / (v.sqrt() + Self::EPSILON);
+        };
+
+        for (((layer, gradient), m), v) in layers
+            .iter_mut()
+            .zip(gradients)
+            .zip(&mut self.first)
+            .zip(&mut self.second)
+        {
+            for (((p, g), m), v) in layer
+                .weights
+                .iter_mut()
+                .zip(gradient.weights.iter())
+                .zip(m.weights.iter_mut())
+                .zip(v.weights.iter_mut())
+            {
+                update(p, *g, m, v);
+            }
+            for (((p, g), m), v) in layer
+                .biases
+                .iter_mut()
+                .zip(gradient.biases.iter())
+                .zip(m.biases.iter_mut())
+                .zip(v.biases.iter_mut())
+            {
+                update(p, *g, m, v);
+            }
+        }
+    }
+}
+
+/// Samples `n_samples` input points uniformly in the box `ranges` and
+/// evaluates `pricer` at each, giving a training set for a surrogate
+/// (inputs and outputs in the rows of the two matrices).
+///
+/// ```
+/// use RustQuant::ml::sample_dataset;
+///
+/// // Discount factors exp(-r t) for r in [0, 0.1] and t in [0, 10].
+/// let (x, y) = sample_dataset(&[(0.0, 0.1), (0.0, 10.0)], 100, 42, |p| {
+///     vec![(-p[0] * p[1]).exp()]
+/// });
+///
+/// assert_eq!(x.shape(), (100, 2));
+/// assert_eq!(y.shape(), (100, 1));
+/// ```
+pub fn sample_dataset<F>(
+    ranges: &[(f64, f64)],
+    n_samples: usize,
+    seed: u64,
+    pricer: F,
+) -> (DMatrix<f64>, DMatrix<f64>)
+where
+    F: Fn(&[f64]) -> Vec<f64>,
+{
+    assert!(ranges.iter().all(|(lower, upper)| lower <= upper));
+
+    let mut rng = Pcg64::stream(seed, 0);
+    let inputs: Vec<Vec<f64>> = (0..n_samples)
+        .map(|_| {
+            ranges
+                .iter()
+                .map(|(lower, upper)| lower + (upper - lower) * rng.gen::<f64>())
+                .collect()
+        })
+        .collect();
+    let outputs: Vec<Vec<f64>> = inputs.iter().map(|x| pricer(x)).collect();
+
+    let n_outputs = outputs.first().map_or(0, Vec::len);
+    assert!(outputs.iter().all(|y| y.len() == n_outputs));
+
+    (
+        DMatrix::from_fn(n_samples, ranges.len(), |i, j| inputs[i][j]),
+        DMatrix::from_fn(n_samples, n_outputs, |i, j| outputs[i][j]),
+    )
+}
+
+// Column means and standard deviations (1 for constant columns).
+fn standardisation(data: &DMatrix<f64>) -> (DVector<f64>, DVector<f64>) {
+    let n = data.nrows() as f64;
+
+    let mean = data.row_mean().transpose();
+    let scale = DVector::from_iterator(
+        data.ncols(),
+        data.column_iter().zip(mean.iter()).map(|(column, m)| {
+            let std = (column.map(|x| (x - m).powi(2)).sum() / n).sqrt();
+            match std > 0.0 {
+                true => std,
+                false => 1.0,
+            }
+        }),
+    );
+
+    (mean, scale)
+}
+
+// Standardises the rows of `data` (features in rows, samples in columns).
+fn scale(data: &DMatrix<f64>, mean: &DVector<f64>, scale: &DVector<f64>) -> DMatrix<f64> {
+    let mut data = data.clone();
+    for (mut row, (m, s)) in data.row_iter_mut().zip(mean.iter().zip(scale.iter())) {
+        row.apply(|v| *v = (*v - m) / s);
+    }
+    data
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // UNIT TESTS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_neural_network {
+    use super::*;
+    use crate::autodiff::{Accumulate, Gradient};
+    use crate::instruments::{BlackScholesInputs, TypeFlag};
+
+    fn black_scholes(p: &[f64]) -> Vec<f64> {
+        let option = BlackScholesInputs {
+            underlying_price: p[0],
+            strike_price: 100.0,
+            volatility: p[1],
+            risk_free_rate: 0.03,
+            cost_of_carry: 0.03,
+            time_to_expiry: p[2],
+            option_type: TypeFlag::Call,
+        };
+
+        vec![option.price()]
+    }
+
+    #[test]
+    fn test_black_scholes_surrogate() {
+        let ranges = [(80.0, 120.0), (0.1, 0.4), (0.25, 2.0)];
+        let (x, y) = sample_dataset(&ranges, 1000, 1, black_scholes);
+        let (x_test, y_test) = sample_dataset(&ranges, 200, 2, black_scholes);
+
+        let mut mlp = MultilayerPerceptron::new(&[3, 16, 16, 1], Activation::Softplus, 7);
+        let config = TrainingConfig::new(150, 5e-3).with_batch_size(32);
+        let losses = mlp.fit(&x, &y, &config);
+
+        assert!(losses[losses.len() - 1] < 0.01 * losses[0]);
+
+        // Out of sample, for prices between about 0.5 and 40.
+        let rmse = mlp.mse(&x_test, &y_test).sqrt();
+        assert!(rmse < 0.25, "RMSE: {rmse}");
+
+        // The delta of the surrogate approximates the Black-Scholes delta.
+        let point = [100.0, 0.2, 1.0];
+        let option = BlackScholesInputs {
+            underlying_price: 100.0,
+            strike_price: 100.0,
+            volatility: 0.2,
+            risk_free_rate: 0.03,
+            cost_of_carry: 0.03,
+            time_to_expiry: 1.0,
+            option_type: TypeFlag::Call,
+        };
+        assert_approx_equal!(mlp.jacobian(&point)[(0, 0)], option.delta(), 0.02);
+    }
+
+    #[test]
+    fn test_jacobian_matches_autodiff_and_finite_differences() {
+        let (x, y) = sample_dataset(&[(-1.0, 1.0), (0.0, 2.0)], 50, 3, |p| {
+            vec![p[0] * p[1], (p[0] + p[1]).sin()]
+        });
+
+        for activation in [Activation::Tanh, Activation::Sigmoid, Activation::Softplus] {
+            let mut mlp = MultilayerPerceptron::new(&[2, 5, 4, 2], activation, 11);
+            mlp.fit(&x, &y, &TrainingConfig::new(5, 1e-2));
+
+            let point = [0.3, 1.2];
+            let jacobian = mlp.jacobian(&point);
+
+            let g = Graph::new();
+            let inputs = g.vars(&point);
+            let outputs = mlp.predict_variables(&g, &inputs);
+            let prediction = mlp.predict(&point);
+
+            let h = 1e-6;
+            for (i, output) in outputs.iter().enumerate() {
+                assert_approx_equal!(output.value, prediction[i], 1e-12);
+
+                let grad = output.accumulate();
+                for j in 0..2 {
+                    let mut up = point;
+                    let mut down = point;
+                    up[j] += h;
+                    down[j] -= h;
+                    let fd = (mlp.predict(&up)[i] - mlp.predict(&down)[i]) / (2.0 * h);
+
+                    assert_approx_equal!(jacobian[(i, j)], grad.wrt(&inputs[j]), 1e-12);
+                    assert_approx_equal!(jacobian[(i, j)], fd, 1e-6);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,918 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Linear (OLS, ridge, lasso, elastic net) and logistic regression on a
+//! [`DesignMatrix`], with standard errors, t-statistics and p-values for
+//! each coefficient, e.g. for factor models and signal research.
+//!
+//! The penalized objectives follow the `glmnet` convention,
+//!
+//! $$
+//! \frac{1}{2n} \sum_i w_i (z_i - x_i^T \beta)^2
+//!     + \lambda \sum_j \left( \alpha |\beta_j| + \frac{1 - \alpha}{2} \beta_j^2 \right),
+//! $$
+//!
+//! with the intercept unpenalized and the regressors on their own scale
+//! (standardize them first for a scale-free penalty). Logistic regression
+//! solves a sequence of such problems (iteratively reweighted least
+//! squares). Problems with an $L_1$ term are solved by coordinate descent,
+//! the others directly.
+//!
+//! The covariance of the estimates is the sandwich
+//! $M^{-1} G M^{-1} / n$, where $G = X^T W X / n$ and $M$ adds the ridge
+//! term to $G$, which reduces to the classical covariance without a
+//! penalty. With an $L_1$ term it is computed on the selected regressors
+//! only, treating the selection as given; dropped coefficients have no
+//! standard error (`NaN`).
+//!
+//! ```rust
+//! use RustQuant::ml::*;
+//! use nalgebra::{DMatrix, DVector};
+//!
+//! let x = DMatrix::from_fn(50, 2, |i, j| ((i * (j + 3)) % 7) as f64);
+//! let y = DVector::from_fn(50, |i, _| 1.0 + 0.5 * x[(i, 0)] + (i % 3) as f64 * 0.1);
+//!
+//! let design = DesignMatrix::new(x, vec!["signal".into(), "noise".into()]).with_intercept();
+//! let fit = LinearModel::new(Penalty::None).fit(&design, &y).unwrap();
+//!
+//! assert!((fit.coefficients.estimate("signal").unwrap() - 0.5).abs() < 0.05);
+//! assert!(fit.coefficients.p_value("signal").unwrap() < 0.01);
+//! ```
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::ml::DesignMatrix;
+use nalgebra::{DMatrix, DVector};
+use serde::{Deserialize, Serialize};
+use statrs::distribution::{ContinuousCDF, Normal, StudentsT};
+use thiserror::Error;
+
+#[cfg(feature = "data")]
+use {crate::data::DataError, polars::prelude::*};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Errors of the regression models.
+#[derive(Error, Debug)]
+pub enum RegressionError {
+    /// The response does not have one value per row of the design matrix.
+    #[error("Design matrix has {rows} rows but the response has {observations} values")]
+    DimensionMismatch {
+        /// Rows of the design matrix.
+        rows: usize,
+        /// Length of the response.
+        observations: usize,
+    },
+
+    /// Too few observations to estimate the residual variance.
+    #[error("Not enough observations: {0}")]
+    InsufficientData(usize),
+
+    /// The (penalized) normal equations are singular, e.g. collinear
+    /// regressors without a ridge penalty.
+    #[error("Singular matrix")]
+    SingularMatrix,
+
+    /// The response of a logistic regression is not 0 or 1.
+    #[error("The response should be either 0 or 1")]
+    NonBinaryResponse,
+
+    /// Error reading the data frame.
+    #[cfg(feature = "data")]
+    #[error("{0}")]
+    Data(#[from] DataError),
+}
+
+/// Penalty on the (non-intercept) coefficients.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Penalty {
+    /// Unpenalized (ordinary least squares or maximum likelihood).
+    None,
+
+    /// Ridge, $\frac{\lambda}{2} \|\beta\|_2^2$.
+    Ridge(f64),
+
+    /// Lasso, $\lambda \|\beta\|_1$, which sets some coefficients to zero.
+    Lasso(f64),
+
+    /// Elastic net, $\lambda (\alpha \|\beta\|_1 + \frac{1 - \alpha}{2} \|\beta\|_2^2)$.
+    ElasticNet {
+        /// Overall penalty, $\lambda$.
+        lambda: f64,
+        /// Weight of the $L_1$ term, $\alpha \in [0, 1]$.
+        l1_ratio: f64,
+    },
+}
+
+/// Linear regression, $y = X \beta + \varepsilon$.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinearModel {
+    /// Penalty on the coefficients.
+    pub penalty: Penalty,
+
+    /// Maximum number of coordinate descent sweeps.
+    pub max_iterations: usize,
+
+    /// Convergence tolerance on the change of the coefficients.
+    pub tolerance: f64,
+}
+
+/// Logistic regression, $P(y = 1) = 1 / (1 + e^{-X \beta})$.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LogisticModel {
+    /// Penalty on the coefficients.
+    pub penalty: Penalty,
+
+    /// Maximum number of reweighting (Newton) steps, and of coordinate
+    /// descent sweeps within each.
+    pub max_iterations: usize,
+
+    /// Convergence tolerance on the change of the coefficients.
+    pub tolerance: f64,
+}
+
+/// Estimated coefficients and their inference.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Coefficients {
+    /// Name of each coefficient (the design matrix columns).
+    pub names: Vec<String>,
+
+    /// Estimates.
+    pub estimates: DVector<f64>,
+
+    /// Standard errors (`NaN` for coefficients dropped by an $L_1$ penalty).
+    #[serde(with = "crate::models::persistence::nullable")]
+    pub standard_errors: DVector<f64>,
+
+    /// Estimates over standard errors: t-statistics for linear regression,
+    /// Wald z-statistics for logistic regression.
+    #[serde(with = "crate::models::persistence::nullable")]
+    pub t_statistics: DVector<f64>,
+
+    /// Two-sided p-values of the statistics (Student's t with the residual
+    /// degrees of freedom, or standard normal).
+    #[serde(with = "crate::models::persistence::nullable")]
+    pub p_values: DVector<f64>,
+}
+
+/// Fitted linear regression.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LinearFit {
+    /// Coefficients and their inference.
+    pub coefficients: Coefficients,
+
+    /// Coefficient of determination (uncentered without an intercept).
+    pub r_squared: f64,
+
+    /// Estimated standard deviation of the errors.
+    pub residual_standard_error: f64,
+
+    /// Residual degrees of freedom, $n - \text{tr}(H)$ for the hat
+    /// matrix $H$ (fractional with a ridge term).
+    pub degrees_of_freedom: f64,
+
+    /// Number of coordinate descent sweeps (one for a direct solve).
+    pub iterations: usize,
+
+    /// Whether the tolerance was reached.
+    pub converged: bool,
+}
+
+/// Fitted logistic regression.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogisticFit {
+    /// Coefficients and their inference.
+    pub coefficients: Coefficients,
+
+    /// Log-likelihood at the estimates (without the penalty).
+    pub log_likelihood: f64,
+
+    /// Number of reweighting steps.
+    pub iterations: usize,
+
+    /// Whether the tolerance was reached (it is not for separable data
+    /// without a penalty).
+    pub converged: bool,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Penalty {
+    /// $(\lambda, \alpha)$.
+    fn parameters(&self) -> (f64, f64) {
+        let (lambda, l1_ratio) = match *self {
+            Penalty::None => (0.0, 0.0),
+            Penalty::Ridge(lambda) => (lambda, 0.0),
+            Penalty::Lasso(lambda) => (lambda, 1.0),
+            Penalty::ElasticNet { lambda, l1_ratio } => (lambda, l1_ratio),
+        };
+        assert!(lambda >= 0.0, "Penalty must be non-negative.");
+        assert!(
+            (0.0..=1.0).contains(&l1_ratio),
+            "L1 ratio must be in [0, 1]."
+        );
+
+        (lambda, l1_ratio)
+    }
+}
+
+impl LinearModel {
+    /// Linear regression with the given penalty.
+    pub fn new(penalty: Penalty) -> Self {
+        Self {
+            penalty,
+            max_iterations: 10_000,
+            tolerance: 1e-10,
+        }
+    }
+
+    /// Sets the maximum number of coordinate descent sweeps.
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Sets the convergence tolerance.
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Fits the regression of `y` on the columns of `design`.
+    pub fn fit(
+        &self,
+        design: &DesignMatrix,
+        y: &DVector<f64>,
+    ) -> Result<LinearFit, RegressionError> {
+        check_dimensions(design, y)?;
+
+        let x = &design.x;
+        let n = x.nrows() as f64;
+        let weights = DVector::from_element(x.nrows(), 1.0);
+
+        let mut beta = DVector::zeros(x.ncols());
+        let (iterations, converged) = penalized_least_squares(
+            design,
+            y,
+            &weights,
+            self.penalty,
+            &mut beta,
+            self.max_iterations,
+            self.tolerance,
+        )?;
+
+        let residuals = y - x * &beta;
+        let rss = residuals.norm_squared();
+        let tss = match design.intercept {
+            true => y.add_scalar(-y.mean()).norm_squared(),
+            false => y.norm_squared(),
+        };
+
+        let (covariance, active, trace) = sandwich(design, &weights, self.penalty, &beta)?;
+
+        let degrees_of_freedom = n - trace;
+        if degrees_of_freedom <= 0.0 {
+            return Err(RegressionError::InsufficientData(x.nrows()));
+        }
+        let variance = rss / degrees_of_freedom;
+
+        let t = StudentsT::new(0.0, 1.0, degrees_of_freedom).expect("Positive degrees of freedom.");
+        let coefficients = Coefficients::new(design, beta, &covariance * variance, &active, |s| {
+            2.0 * t.cdf(-s.abs())
+        });
+
+        Ok(LinearFit {
+            coefficients,
+            r_squared: match tss > 0.0 {
+                true => 1.0 - rss / tss,
+                false => 1.0,
+            },
+            residual_standard_error: variance.sqrt(),
+            degrees_of_freedom,
+            iterations,
+            converged,
+        })
+    }
+}
+
+impl LogisticModel {
+    /// Logistic regression with the given penalty.
+    pub fn new(penalty: Penalty) -> Self {
+        Self {
+            penalty,
+            max_iterations: 1_000,
+            tolerance: 1e-10,
+        }
+    }
+
+    /// Sets the maximum number of iterations.
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Sets the convergence tolerance.
+    pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Fits the regression of the binary response `y` on the columns of
+    /// `design`.
+    pub fn fit(
+        &self,
+        design: &DesignMatrix,
+        y: &DVector<f64>,
+    ) -> Result<LogisticFit, RegressionError> {
+        check_dimensions(design, y)?;
+
+        if y.iter().any(|&v| v != 0.0 && v != 1.0) {
+            return Err(RegressionError::NonBinaryResponse);
+        }
+
+        let x = &design.x;
+        let mut beta = DVector::zeros(x.ncols());
+        let (mut iterations, mut converged) = (0, false);
+
+        while iterations < self.max_iterations && !converged {
+            iterations += 1;
+
+            // Working response and weights of the quadratic approximation
+            // of the log-likelihood at the current estimates.
+            let eta = x * &beta;
+            let mu = eta.map(logistic);
+            let weights = mu.map(|p| (p * (1.0 - p)).max(1e-10));
+            let z = DVector::from_fn(y.len(), |i, _| eta[i] + (y[i] - mu[i]) / weights[i]);
+
+            let previous = beta.clone();
+            penalized_least_squares(
+                design,
+                &z,
+                &weights,
+                self.penalty,
+                &mut beta,
+                self.max_iterations,
+                self.tolerance,
+            )?;
+
+            converged = (&beta - previous).amax() < self.tolerance;
+        }
+
+        let eta = x * &beta;
+        let weights = eta.map(|e| {
+            let p = logistic(e);
+            (p * (1.0 - p)).max(1e-10)
+        });
+        let log_likelihood = eta
+            .iter()
+            .zip(y.iter())
+            .map(|(e, y)| y * e - e.max(0.0) - (-e.abs()).exp().ln_1p())
+            .sum();
+
+        let (covariance, active, _) = sandwich(design, &weights, self.penalty, &beta)?;
+
+        let normal = Normal::new(0.0, 1.0).expect("Standard normal.");
+        let coefficients = Coefficients::new(design, beta, covariance, &active, |s| {
+            2.0 * normal.cdf(-s.abs())
+        });
+
+        Ok(LogisticFit {
+            coefficients,
+            log_likelihood,
+            iterations,
+            converged,
+        })
+    }
+}
+
+impl Coefficients {
+    fn new<P: Fn(f64) -> f64>(
+        design: &DesignMatrix,
+        estimates: DVector<f64>,
+        covariance: DMatrix<f64>,
+        active: &[usize],
+        p_value: P,
+    ) -> Self {
+        let mut standard_errors = DVector::from_element(estimates.len(), f64::NAN);
+        for (a, &j) in active.iter().enumerate() {
+            standard_errors[j] = covariance[(a, a)].max(0.0).sqrt();
+        }
+
+        let t_statistics = estimates.component_div(&standard_errors);
+        let p_values = t_statistics.map(|s| match s.is_nan() {
+            true => f64::NAN,
+            false => p_value(s),
+        });
+
+        Self {
+            names: design.names.clone(),
+            estimates,
+            standard_errors,
+            t_statistics,
+            p_values,
+        }
+    }
+
+    /// Estimate of the coefficient called `name`.
+    pub fn estimate(&self, name: &str) -> Option<f64> {
+        self.position(name).map(|j| self.estimates[j])
+    }
+
+    /// Standard error of the coefficient called `name`.
+    pub fn standard_error(&self, name: &str) -> Option<f64> {
+        self.position(name).map(|j| self.standard_errors[j])
+    }
+
+    /// t-statistic of the coefficient called `name`.
+    pub fn t_statistic(&self, name: &str) -> Option<f64> {
+        self.position(name).map(|j| self.t_statistics[j])
+    }
+
+    /// p-value of the coefficient called `name`.
+    pub fn p_value(&self, name: &str) -> Option<f64> {
+        self.position(name).map(|j| self.p_values[j])
+    }
+
+    /// Coefficient table with columns `term`, `estimate`, `std_error`,
+    /// `t_statistic` and `p_value`.
+    #[cfg(feature = "data")]
+    pub fn to_dataframe(&self) -> Result<DataFrame, RegressionError> {
+        let column = |name: &str, values: &DVector<f64>| Series::new(name, values.as_slice());
+
+        Ok(DataFrame::new(vec![
+            Series::new("term", &self.names),
+            column("estimate", &self.estimates),
+            column("std_error", &self.standard_errors),
+            column("t_statistic", &self.t_statistics),
+            column("p_value", &self.p_values),
+        ])
+        .map_err(DataError::from)?)
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name)
+    }
+}
+
+impl LinearFit {
+    /// Fitted values for the rows of `design` (with the same columns as
+    /// the design matrix of the fit).
+    pub fn predict(&self, design: &DesignMatrix) -> DVector<f64> {
+        assert_eq!(design.names, self.coefficients.names, "Same regressors.");
+
+        &design.x * &self.coefficients.estimates
+    }
+}
+
+impl LogisticFit {
+    /// Probabilities $P(y = 1)$ for the rows of `design` (with the same
+    /// columns as the design matrix of the fit).
+    pub fn predict_proba(&self, design: &DesignMatrix) -> DVector<f64> {
+        assert_eq!(design.names, self.coefficients.names, "Same regressors.");
+
+        (&design.x * &self.coefficients.estimates).map(logistic)
+    }
+}
+
+fn check_dimensions(design: &DesignMatrix, y: &DVector<f64>) -> Result<(), RegressionError> {
+    match design.nrows() == y.len() {
+        true => Ok(()),
+        false => Err(RegressionError::DimensionMismatch {
+            rows: design.nrows(),
+            observations: y.len(),
+        }),
+    }
+}
+
+#[inline]
+fn logistic(eta: f64) -> f64 {
+    1.0 / (1.0 + (-eta).exp())
+}
+
+#[inline]
+fn soft_threshold(x: f64, threshold: f64) -> f64 {
+    x.signum() * (x.abs() - threshold).max(0.0)
+}
+
+/// Minimizes the weighted, penalized least squares objective in place,
+/// starting from `beta`. Returns the number of sweeps and whether the
+/// tolerance was reached.
+fn penalized_least_squares(
+    design: &DesignMatrix,
+    z: &DVector<f64>,
+    weights: &DVector<f64>,
+    penalty: Penalty,
+    beta: &mut DVector<f64>,
+    max_iterations: usize,
+    tolerance: f64,
+) -> Result<(usize, bool), RegressionError> {
+    let (lambda, alpha) = penalty.parameters();
+    let x = &design.x;
+    let n = x.nrows() as f64;
+    let penalized = |j: usize| !(design.intercept && j == 0);
+
+    // Without an L1 term, the normal equations are linear.
+    if lambda * alpha == 0.0 {
+        let (gram, _) = gram(x, weights, &(0..x.ncols()).collect::<Vec<_>>());
+        let mut system = gram;
+        for j in (0..x.ncols()).filter(|&j| penalized(j)) {
+            system[(j, j)] += lambda * (1.0 - alpha);
+        }
+        let rhs = x.transpose() * z.component_mul(weights) / n;
+
+        *beta = system
+            .cholesky()
+            .ok_or(RegressionError::SingularMatrix)?
+            .solve(&rhs);
+
+        return Ok((1, true));
+    }
+
+    let scales: Vec<f64> = x
+        .column_iter()
+        .map(|c| c.component_mul(&c).dot(weights) / n)
+        .collect();
+    let mut residuals = z - x * &*beta;
+
+    for iteration in 1..=max_iterations {
+        let mut change: f64 = 0.0;
+
+        for (j, &scale) in scales.iter().enumerate() {
+            if scale == 0.0 {
+                continue;
+            }
+
+            let column = x.column(j);
+            let rho = column.component_mul(&residuals).dot(weights) / n + scale * beta[j];
+            let updated = match penalized(j) {
+                true => soft_threshold(rho, lambda * alpha) / (scale + lambda * (1.0 - alpha)),
+                false => rho / scale,
+            };
+
+            let delta = updated - beta[j];
+            if delta != 0.0 {
+                residuals.axpy(-delta, &column, 1.0);
+                beta[j] = updated;
+                change = change.max(delta.abs());
+            }
+        }
+
+        if change < tolerance {
+            return Ok((iteration, true));
+        }
+    }
+
+    Ok((max_iterations, false))
+}
+
+/// $X_A^T W X_A / n$ for the columns `active`, and $X_A$.
+fn gram(
+    x: &DMatrix<f64>,
+    weights: &DVector<f64>,
+    active: &[usize],
+) -> (DMatrix<f64>, DMatrix<f64>) {
+    let x_active = x.select_columns(active);
+    let weighted = DMatrix::from_fn(x_active.nrows(), x_active.ncols(), |i, j| {
+        x_active[(i, j)] * weights[i]
+    });
+
+    (x_active.transpose() * weighted / x.nrows() as f64, x_active)
+}
+
+/// Covariance of the active coefficients up to the error variance,
+/// $M^{-1} G M^{-1} / n$, the active columns, and $\text{tr}(M^{-1} G)$.
+fn sandwich(
+    design: &DesignMatrix,
+    weights: &DVector<f64>,
+    penalty: Penalty,
+    beta: &DVector<f64>,
+) -> Result<(DMatrix<f64>, Vec<usize>, f64), RegressionError> {
+    let (lambda, alpha) = penalty.parameters();
+    let penalized = |j: usize| !(design.intercept && j == 0);
+
+    let active: Vec<usize> = (0..beta.len())
+        .filter(|&j| alpha == 0.0 || !penalized(j) || beta[j] != 0.0)
+        .collect();
+
+    let (g, _) = gram(&design.x, weights, &active);
+    let mut m = g.clone();
+    for (a, &j) in active.iter().enumerate() {
+        if penalized(j) {
+            m[(a, a)] += lambda * (1.0 - alpha);
+        }
+    }
+
+    let m_inverse = m.try_inverse().ok_or(RegressionError::SingularMatrix)?;
+    let trace = (&m_inverse * &g).trace();
+    let covariance = &m_inverse * g * &m_inverse / design.nrows() as f64;
+
+    Ok((covariance, active, trace))
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_regularized_regression {
+    use super::*;
+    use crate::math::Pcg64;
+    use crate::ml::{LogisticRegressionAlgorithm, LogisticRegressionInput};
+    use crate::statistics::time_series::stationarity::ols;
+    use rand::Rng;
+    use rand_distr::StandardNormal;
+
+    // Two relevant regressors out of six.
+    fn data(n: usize, seed: u64) -> (DesignMatrix, DVector<f64>) {
+        let mut rng = Pcg64::stream(seed, 0);
+        let x = DMatrix::from_fn(n, 6, |_, _| rng.sample::<f64, _>(StandardNormal));
+        let y = DVector::from_fn(n, |i, _| {
+            0.5 + 2.0 * x[(i, 0)] - x[(i, 1)] + 0.5 * rng.sample::<f64, _>(StandardNormal)
+        });
+        let names = (1..=6).map(|j| format!("x{j}")).collect();
+
+        (DesignMatrix::new(x, names).with_intercept(), y)
+    }
+
+    // Optimality conditions of the elastic net: the gradient of the smooth
+    // part balances the L1 subgradient.
+    fn assert_kkt(
+        design: &DesignMatrix,
+        z: &DVector<f64>,
+        w: &DVector<f64>,
+        penalty: Penalty,
+        beta: &DVector<f64>,
+    ) {
+        let (lambda, alpha) = penalty.parameters();
+        let n = design.nrows() as f64;
+        let residuals = z - &design.x * beta;
+
+        for j in 0..beta.len() {
+            let gradient = design.x.column(j).component_mul(&residuals).dot(w) / n;
+            let ridge = match j {
+                0 => 0.0,
+                _ => lambda * (1.0 - alpha) * beta[j],
+            };
+            match (j, beta[j] == 0.0) {
+                (0, _) => assert_approx_equal!(gradient, 0.0, 1e-8),
+                (_, true) => assert!(gradient.abs() <= lambda * alpha + 1e-8),
+                (_, false) => {
+                    assert_approx_equal!(gradient - ridge, lambda * alpha * beta[j].signum(), 1e-8)
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_ols_inference() {
+        let (design, y) = data(200, 1);
+        let fit = LinearModel::new(Penalty::None).fit(&design, &y).unwrap();
+        let reference = ols(&y, &design.x).unwrap();
+
+        for j in 0..design.ncols() {
+            assert_approx_equal!(
+                fit.coefficients.estimates[j],
+                reference.coefficients[j],
+                1e-10
+            );
+            assert_approx_equal!(
+                fit.coefficients.standard_errors[j],
+                reference.standard_errors[j],
+                1e-10
+            );
+        }
+        assert_eq!(fit.degrees_of_freedom.round(), 193.0);
+
+        // The relevant regressors are significant, the others mostly not.
+        assert!(fit.coefficients.p_value("x1").unwrap() < 1e-10);
+        assert!(fit.coefficients.t_statistic("x2").unwrap() < -10.0);
+        assert!((3..=6).all(|j| fit.coefficients.p_value(&format!("x{j}")).unwrap() > 0.001));
+        assert!(fit.r_squared > 0.9);
+        assert_approx_equal!(fit.residual_standard_error, 0.5, 0.1);
+
+        let predictions = fit.predict(&design);
+        assert_approx_equal!(
+            (&y - &predictions).norm_squared(),
+            reference.residuals.norm_squared(),
+            1e-10
+        );
+    }
+
+    #[test]
+    fn test_ridge() {
+        let (design, y) = data(100, 2);
+        let lambda = 0.5;
+        let fit = LinearModel::new(Penalty::Ridge(lambda))
+            .fit(&design, &y)
+            .unwrap();
+
+        // (X'X / n + lambda P) beta = X'y / n, intercept unpenalized.
+        let n = design.nrows() as f64;
+        let mut system = design.x.transpose() * &design.x / n;
+        for j in 1..design.ncols() {
+            system[(j, j)] += lambda;
+        }
+        let expected = system.lu().solve(&(design.x.transpose() * &y / n)).unwrap();
+        for j in 0..design.ncols() {
+            assert_approx_equal!(fit.coefficients.estimates[j], expected[j], 1e-10);
+        }
+
+        // Shrinkage, and fewer effective parameters.
+        let ols = LinearModel::new(Penalty::None).fit(&design, &y).unwrap();
+        assert!(
+            fit.coefficients.estimates.rows(1, 6).norm()
+                < ols.coefficients.estimates.rows(1, 6).norm()
+        );
+        assert!(fit.degrees_of_freedom > ols.degrees_of_freedom);
+    }
+
+    #[test]
+    fn test_lasso_and_elastic_net() {
+        let (design, y) = data(150, 3);
+        let weights = DVector::from_element(y.len(), 1.0);
+
+        for penalty in [
+            Penalty::Lasso(0.1),
+            Penalty::ElasticNet {
+                lambda: 0.2,
+                l1_ratio: 0.5,
+            },
+        ] {
+            let fit = LinearModel::new(penalty).fit(&design, &y).unwrap();
+            assert!(fit.converged);
+            assert_kkt(&design, &y, &weights, penalty, &fit.coefficients.estimates);
+
+            // The irrelevant regressors are dropped, without inference.
+            for j in 3..=6 {
+                let name = format!("x{j}");
+                assert_eq!(fit.coefficients.estimate(&name), Some(0.0));
+                assert!(fit.coefficients.standard_error(&name).unwrap().is_nan());
+            }
+            assert!(fit.coefficients.estimate("x1").unwrap() > 1.5);
+            assert!(fit.coefficients.p_value("x2").unwrap() < 1e-10);
+        }
+
+        // A zero penalty is OLS.
+        let lasso = LinearModel::new(Penalty::Lasso(0.0))
+            .fit(&design, &y)
+            .unwrap();
+        let ols = LinearModel::new(Penalty::None).fit(&design, &y).unwrap();
+        assert_eq!(lasso, ols);
+    }
+
+    #[test]
+    fn test_logistic_regression() {
+        let mut rng = Pcg64::stream(4, 0);
+        let n = 500;
+        let x = DMatrix::from_fn(n, 3, |_, _| rng.sample::<f64, _>(StandardNormal));
+        let y = DVector::from_fn(n, |i, _| {
+            let p = logistic(-0.5 + 1.5 * x[(i, 0)] - x[(i, 1)]);
+            f64::from(u8::from(rng.gen::<f64>() < p))
+        });
+        let design =
+            DesignMatrix::new(x.clone(), vec!["a".into(), "b".into(), "c".into()]).with_intercept();
+
+        let fit = LogisticModel::new(Penalty::None).fit(&design, &y).unwrap();
+        assert!(fit.converged);
+
+        // Same estimates as IRLS in `LogisticRegressionInput`.
+        let reference = LogisticRegressionInput::new(x, y.clone())
+            .fit(LogisticRegressionAlgorithm::IRLS, 1e-12)
+            .unwrap();
+        for j in 0..4 {
+            assert_approx_equal!(
+                fit.coefficients.estimates[j],
+                reference.coefficients[j],
+                1e-8
+            );
+        }
+
+        // Standard errors from the inverse Fisher information.
+        let p = (&design.x * &fit.coefficients.estimates).map(logistic);
+        let fisher = DMatrix::from_fn(4, 4, |j, k| {
+            (0..n)
+                .map(|i| design.x[(i, j)] * design.x[(i, k)] * p[i] * (1.0 - p[i]))
+                .sum::<f64>()
+        });
+        let inverse = fisher.try_inverse().unwrap();
+        for j in 0..4 {
+            assert_approx_equal!(
+                fit.coefficients.standard_errors[j],
+                inverse[(j, j)].sqrt(),
+                1e-8
+            );
+        }
+        assert!(fit.coefficients.p_value("a").unwrap() < 1e-10);
+        assert!(fit.coefficients.p_value("c").unwrap() > 0.001);
+
+        let probabilities = fit.predict_proba(&design);
+        let log_likelihood: f64 = (0..n)
+            .map(|i| y[i] * probabilities[i].ln() + (1.0 - y[i]) * (1.0 - probabilities[i]).ln())
+            .sum();
+        assert_approx_equal!(fit.log_likelihood, log_likelihood, 1e-8);
+
+        // Penalties shrink the estimates, and the lasso drops `c`.
+        let ridge = LogisticModel::new(Penalty::Ridge(0.1))
+            .fit(&design, &y)
+            .unwrap();
+        assert!(
+            ridge.coefficients.estimates.rows(1, 3).norm()
+                < fit.coefficients.estimates.rows(1, 3).norm()
+        );
+
+        let penalty = Penalty::Lasso(0.05);
+        let lasso = LogisticModel::new(penalty).fit(&design, &y).unwrap();
+        assert!(lasso.converged);
+        assert_eq!(lasso.coefficients.estimate("c"), Some(0.0));
+
+        // At the solution, the weighted least squares problem of the last
+        // step is solved by the estimates themselves.
+        let beta = &lasso.coefficients.estimates;
+        let eta = &design.x * beta;
+        let mu = eta.map(logistic);
+        let w = mu.map(|p| p * (1.0 - p));
+        let z = DVector::from_fn(n, |i, _| eta[i] + (y[i] - mu[i]) / w[i]);
+        assert_kkt(&design, &z, &w, penalty, beta);
+    }
+
+    #[test]
+    fn test_errors() {
+        let (design, y) = data(20, 5);
+
+        assert!(matches!(
+            LinearModel::new(Penalty::None).fit(&design, &y.rows(0, 10).into_owned()),
+            Err(RegressionError::DimensionMismatch {
+                rows: 20,
+                observations: 10
+            })
+        ));
+        assert!(matches!(
+            LogisticModel::new(Penalty::None).fit(&design, &y),
+            Err(RegressionError::NonBinaryResponse)
+        ));
+
+        // Collinear regressors need a ridge term.
+        let x = design.x.column(1).into_owned();
+        let collinear = DesignMatrix::new(
+            DMatrix::from_columns(&[x.clone(), x * 2.0]),
+            vec!["a".into(), "b".into()],
+        )
+        .with_intercept();
+        assert!(matches!(
+            LinearModel::new(Penalty::None).fit(&collinear, &y),
+            Err(RegressionError::SingularMatrix)
+        ));
+        assert!(LinearModel::new(Penalty::Ridge(0.1))
+            .fit(&collinear, &y)
+            .is_ok());
+    }
+
+    #[cfg(feature = "data")]
+    #[test]
+    fn test_data_frame() {
+        use crate::ml::DesignMatrixBuilder;
+
+        let (design, y) = data(50, 6);
+        let mut columns = vec![Series::new("ret", y.as_slice())];
+        for j in 1..=3 {
+            let values: Vec<f64> = design.x.column(j).iter().copied().collect();
+            columns.push(Series::new(&format!("x{j}"), values));
+        }
+        let df = DataFrame::new(columns).unwrap();
+
+        let (frame_design, frame_y) = DesignMatrixBuilder::new("ret")
+            .regressors(&["x1", "x2"])
+            .build(&df)
+            .unwrap();
+        let fit = LinearModel::new(Penalty::None)
+            .fit(&frame_design, &frame_y)
+            .unwrap();
+
+        let table = fit.coefficients.to_dataframe().unwrap();
+        assert_eq!(table.shape(), (3, 5));
+        assert_eq!(table.column("term").unwrap().str_value(1).unwrap(), "x1");
+        assert_approx_equal!(
+            table
+                .column("estimate")
+                .unwrap()
+                .f64()
+                .unwrap()
+                .get(1)
+                .unwrap(),
+            fit.coefficients.estimates[1],
+            1e-15
+        );
+    }
+}
@@ -0,0 +1,660 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! ARIMA(p, d, q) models of the conditional mean.
+//!
+//! The series differenced `d` times, $w_t = \Delta^d y_t$, follows an ARMA
+//! process:
+//!
+//! $$
+//! w_t = c + \sum_{i=1}^p \phi_i w_{t-i} + \epsilon_t + \sum_{j=1}^q \theta_j \epsilon_{t-j},
+//! \quad \epsilon_t \sim N(0, \sigma^2)
+//! $$
+//!
+//! Parameters are estimated by conditional sum of squares (CSS), optionally
+//! refined by exact maximum likelihood through the Kalman filter (CSS-MLE).
+//! The residuals of a fitted model can be passed on to a GARCH model for
+//! the conditional variance.
+//!
+//! ```
+//! use RustQuant::models::*;
+//! use rand::{rngs::StdRng, SeedableRng};
+//!
+//! // Simulate an ARIMA(1, 1, 0) and fit it back.
+//! let model = Arima::new(vec![0.5], 1, vec![], 0.1, 1.0);
+//! let y = model.simulate(500, &mut StdRng::seed_from_u64(1));
+//!
+//! let fit = Arima::fit(&y, (1, 1, 0), ArimaMethod::CssMle).unwrap();
+//! assert!((fit.model.ar[0] - 0.5).abs() < 0.1);
+//!
+//! // Forecasts for the next 10 periods, with 95% prediction intervals.
+//! let forecast = fit.model.forecast(&y, 10);
+//! let intervals = forecast.intervals(0.95);
+//! assert!(intervals[9].1 - intervals[9].0 > intervals[0].1 - intervals[0].0);
+//! ```
+
+use crate::math::optimize::NelderMead;
+use crate::statistics::distributions::{Distribution, Gaussian};
+use crate::statistics::time_series::stationarity::ols;
+use crate::statistics::KalmanFilter;
+use nalgebra::{DMatrix, DVector};
+use rand::Rng;
+use rand_distr::StandardNormal;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+use thiserror::Error;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// ARIMA(p, d, q) model.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Arima {
+    /// Autoregressive coefficients ($\phi_1, \dots, \phi_p$).
+    pub ar: Vec<f64>,
+
+    /// Order of differencing ($d$).
+    pub d: usize,
+
+    /// Moving average coefficients ($\theta_1, \dots, \theta_q$).
+    pub ma: Vec<f64>,
+
+    /// Constant of the differenced series ($c$).
+    pub constant: f64,
+
+    /// Innovation variance ($\sigma^2$).
+    pub variance: f64,
+}
+
+/// ARIMA estimation methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArimaMethod {
+    /// Conditional sum of squares (pre-sample innovations set to zero).
+    Css,
+
+    /// Conditional sum of squares, then exact maximum likelihood.
+    #[default]
+    CssMle,
+}
+
+/// Information criteria for order selection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InformationCriterion {
+    /// Akaike information criterion.
+    #[default]
+    Aic,
+
+    /// Bayesian (Schwarz) information criterion.
+    Bic,
+}
+
+/// Estimated ARIMA model.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArimaFit {
+    /// Estimated model.
+    pub model: Arima,
+
+    /// Log-likelihood at the estimate (conditional for CSS, exact for CSS-MLE).
+    pub log_likelihood: f64,
+
+    /// Number of observations of the differenced series.
+    pub n_observations: usize,
+
+    /// Residuals of the differenced series (after the first `p`).
+    pub residuals: Vec<f64>,
+}
+
+/// Forecasts of an ARIMA model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArimaForecast {
+    /// Point forecasts.
+    pub mean: Vec<f64>,
+
+    /// Standard errors of the forecasts.
+    pub standard_errors: Vec<f64>,
+}
+
+/// ARIMA errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum ArimaError {
+    /// Not enough observations to estimate the model.
+    #[error("Not enough observations to estimate the model: {0}.")]
+    InsufficientData(usize),
+
+    /// The estimation did not produce a stationary and invertible model.
+    #[error("The estimation did not produce admissible parameters.")]
+    EstimationFailed,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Arima {
+    /// New ARIMA model.
+    pub fn new(ar: Vec<f64>, d: usize, ma: Vec<f64>, constant: f64, variance: f64) -> Self {
+        assert!(variance > 0.0, "Variance must be positive.");
+
+        Self {
+            ar,
+            d,
+            ma,
+            constant,
+            variance,
+        }
+    }
+
+    /// Order `(p, d, q)`.
+    pub fn order(&self) -> (usize, usize, usize) {
+        (self.ar.len(), self.d, self.ma.len())
+    }
+
+    /// Mean of the differenced series, $c / (1 - \sum_i \phi_i)$.
+    pub fn mean(&self) -> f64 {
+        self.constant / (1.0 - self.ar.iter().sum::<f64>())
+    }
+
+    /// Whether the roots of the AR polynomial lie outside the unit circle.
+    pub fn is_stationary(&self) -> bool {
+        roots_outside_unit_circle(&self.ar)
+    }
+
+    /// Whether the roots of the MA polynomial lie outside the unit circle.
+    pub fn is_invertible(&self) -> bool {
+        roots_outside_unit_circle(&self.ma.iter().map(|theta| -theta).collect::<Vec<_>>())
+    }
+
+    /// Residuals of the differenced series, after the first `p`
+    /// observations (pre-sample innovations set to zero).
+    pub fn residuals(&self, y: &[f64]) -> Vec<f64> {
+        let p = self.ar.len();
+
+        self.innovations(&differences(y, self.d))
+            .split_off(p.min(y.len().saturating_sub(self.d)))
+    }
+
+    /// Exact Gaussian log-likelihood of the differenced series, from the
+    /// Kalman filter (`-inf` for a non-stationary model).
+    pub fn log_likelihood(&self, y: &[f64]) -> f64 {
+        self.exact_log_likelihood(&differences(y, self.d))
+    }
+
+    /// Forecasts for the `horizon` periods after `y`, with standard errors
+    /// from the $\psi$-weights of the model.
+    pub fn forecast(&self, y: &[f64], horizon: usize) -> ArimaForecast {
+        assert!(y.len() > self.d, "Not enough observations to forecast.");
+
+        // Differenced series at each level, y, Δy, ..., Δ^d y.
+        let levels: Vec<Vec<f64>> = (0..=self.d).map(|k| differences(y, k)).collect();
+
+        // ARMA forecasts of the differenced series, future innovations zero.
+        let w = &levels[self.d];
+        let mut extended = w.clone();
+        let mut innovations = self.innovations(w);
+        for _ in 0..horizon {
+            let t = extended.len();
+            let next =
+                self.constant + lagged(&self.ar, &extended, t) + lagged(&self.ma, &innovations, t);
+            extended.push(next);
+            innovations.push(0.0);
+        }
+        let mut mean = extended.split_off(w.len());
+
+        // Integrate back to the level of y.
+        for level in levels[..self.d].iter().rev() {
+            let mut last = *level.last().unwrap();
+            for x in mean.iter_mut() {
+                last += *x;
+                *x = last;
+            }
+        }
+
+        // Psi-weights of phi(B) (1 - B)^d psi(B) = theta(B).
+        let mut polynomial = vec![1.0];
+        polynomial.extend(self.ar.iter().map(|phi| -phi));
+        for _ in 0..self.d {
+            polynomial = (0..=polynomial.len())
+                .map(|i| {
+                    polynomial.get(i).copied().unwrap_or(0.0)
+                        - i.checked_sub(1).map_or(0.0, |j| polynomial[j])
+                })
+                .collect();
+        }
+
+        let mut psi = vec![1.0];
+        for j in 1..horizon {
+            let theta = self.ma.get(j - 1).copied().unwrap_or(0.0);
+            let value = theta
+                - (1..polynomial.len().min(j + 1))
+                    .map(|i| polynomial[i] * psi[j - i])
+                    .sum::<f64>();
+            psi.push(value);
+        }
+
+        let mut cumulative = 0.0;
+        let standard_errors = psi
+            .iter()
+            .map(|p| {
+                cumulative += p * p;
+                (self.variance * cumulative).sqrt()
+            })
+            .collect();
+
+        ArimaForecast {
+            mean,
+            standard_errors,
+        }
+    }
+
+    /// Simulates `n` observations, integrating the ARMA process (after a
+    /// burn-in of 100 periods) `d` times from zero.
+    pub fn simulate<R: Rng + ?Sized>(&self, n: usize, rng: &mut R) -> Vec<f64> {
+        let burn_in = 100;
+        let sigma = self.variance.sqrt();
+
+        let mut w = Vec::with_capacity(n + burn_in);
+        let mut innovations = Vec::with_capacity(n + burn_in);
+        for t in 0..n + burn_in {
+            let epsilon = sigma * rng.sample::<f64, _>(StandardNormal);
+            let next = self.constant
+                + lagged(&self.ar, &w, t)
+                + lagged(&self.ma, &innovations, t)
+                + epsilon;
+            w.push(next);
+            innovations.push(epsilon);
+        }
+
+        let mut y = w.split_off(burn_in);
+        for _ in 0..self.d {
+            let mut level = 0.0;
+            for x in y.iter_mut() {
+                level += *x;
+                *x = level;
+            }
+        }
+
+        y
+    }
+
+    /// Estimates an ARIMA model of order `(p, d, q)`, with a constant.
+    ///
+    /// The CSS estimate starts from the Hannan-Rissanen regressions, and is
+    /// the starting point of the exact maximum likelihood for CSS-MLE.
+    pub fn fit(
+        y: &[f64],
+        order: (usize, usize, usize),
+        method: ArimaMethod,
+    ) -> Result<ArimaFit, ArimaError> {
+        let (p, d, q) = order;
+        let w = differences(y, d);
+        let n = w.len();
+
+        if n < p + q + 10 {
+            return Err(ArimaError::InsufficientData(y.len()));
+        }
+
+        let model = |parameters: &[f64], variance: f64| {
+            Arima::new(
+                parameters[1..=p].to_vec(),
+                d,
+                parameters[p + 1..].to_vec(),
+                parameters[0],
+                variance,
+            )
+        };
+        let admissible = |model: &Arima| model.is_stationary() && model.is_invertible();
+
+        // Conditional sum of squares.
+        let sum_of_squares = |parameters: &[f64]| {
+            let candidate = model(parameters, 1.0);
+            match admissible(&candidate) {
+                true => candidate.innovations(&w)[p..].iter().map(|e| e * e).sum(),
+                false => f64::INFINITY,
+            }
+        };
+        let initial = hannan_rissanen(&w, p, q);
+        let css = NelderMead::new(5_000, 1e-12)
+            .with_initial_step(0.1)
+            .minimize(&sum_of_squares, &initial);
+
+        if !css.minimum.is_finite() {
+            return Err(ArimaError::EstimationFailed);
+        }
+
+        let m = (n - p) as f64;
+        let mut fitted = model(&css.minimizer, css.minimum / m);
+        let mut log_likelihood = -0.5 * m * ((2.0 * PI * fitted.variance).ln() + 1.0);
+
+        // Exact maximum likelihood from the CSS estimate.
+        if method == ArimaMethod::CssMle {
+            let mut initial = css.minimizer.clone();
+            initial.push(fitted.variance);
+
+            let mle = NelderMead::new(5_000, 1e-10)
+                .with_initial_step(0.1)
+                .minimize(
+                    &|parameters: &[f64]| {
+                        let variance = parameters[p + q + 1];
+                        if variance <= 0.0 {
+                            return f64::INFINITY;
+                        }
+                        let candidate = model(&parameters[..=p + q], variance);
+                        match admissible(&candidate) {
+                            true => -candidate.exact_log_likelihood(&w),
+                            false => f64::INFINITY,
+                        }
+                    },
+                    &initial,
+                );
+
+            if !mle.minimum.is_finite() {
+                return Err(ArimaError::EstimationFailed);
+            }
+
+            fitted = model(&mle.minimizer[..=p + q], mle.minimizer[p + q + 1]);
+            log_likelihood = -mle.minimum;
+        }
+
+        Ok(ArimaFit {
+            residuals: fitted.residuals(y),
+            model: fitted,
+            log_likelihood,
+            n_observations: n,
+        })
+    }
+
+    /// Fits all orders up to `(max_p, d, max_q)` and returns the one
+    /// minimising the information criterion.
+    pub fn select_order(
+        y: &[f64],
+        max_p: usize,
+        d: usize,
+        max_q: usize,
+        criterion: InformationCriterion,
+        method: ArimaMethod,
+    ) -> Result<ArimaFit, ArimaError> {
+        let score = |fit: &ArimaFit| match criterion {
+            InformationCriterion::Aic => fit.aic(),
+            InformationCriterion::Bic => fit.bic(),
+        };
+
+        (0..=max_p)
+            .flat_map(|p| (0..=max_q).map(move |q| (p, q)))
+            .filter_map(|(p, q)| Self::fit(y, (p, d, q), method).ok())
+            .min_by(|a, b| score(a).total_cmp(&score(b)))
+            .ok_or(ArimaError::EstimationFailed)
+    }
+
+    /// Innovations of the differenced series, zero for the first `p`.
+    fn innovations(&self, w: &[f64]) -> Vec<f64> {
+        let p = self.ar.len();
+        let mut innovations = vec![0.0; w.len()];
+
+        for t in p..w.len() {
+            innovations[t] =
+                w[t] - self.constant - lagged(&self.ar, w, t) - lagged(&self.ma, &innovations, t);
+        }
+
+        innovations
+    }
+
+    /// Exact log-likelihood of the differenced series, from the state-space
+    /// form $x_t = F x_{t-1} + g \epsilon_t$, $w_t - \mu = x_{t,1}$.
+    fn exact_log_likelihood(&self, w: &[f64]) -> f64 {
+        if !self.is_stationary() {
+            return f64::NEG_INFINITY;
+        }
+
+        let r = self.ar.len().max(self.ma.len() + 1);
+
+        let transition = DMatrix::from_fn(r, r, |i, j| match j {
+            0 => self.ar.get(i).copied().unwrap_or(0.0),
+            j if j == i + 1 => 1.0,
+            _ => 0.0,
+        });
+        let g = DVector::from_fn(r, |i, _| match i {
+            0 => 1.0,
+            i => self.ma.get(i - 1).copied().unwrap_or(0.0),
+        });
+        let q = &g * g.transpose() * self.variance;
+
+        // Stationary covariance: vec(P) = (I - F ⊗ F)^{-1} vec(Q).
+        let system = DMatrix::identity(r * r, r * r) - transition.kronecker(&transition);
+        let covariance = match system.lu().solve(&DVector::from_column_slice(q.as_slice())) {
+            Some(solution) => DMatrix::from_column_slice(r, r, solution.as_slice()),
+            None => return f64::NEG_INFINITY,
+        };
+
+        let mut observation = DMatrix::zeros(1, r);
+        observation[(0, 0)] = 1.0;
+
+        let filter = KalmanFilter::new(transition, observation, q, DMatrix::zeros(1, 1))
+            .with_initial_state(DVector::zeros(r), covariance);
+        let mean = self.mean();
+        let observations: Vec<DVector<f64>> = w
+            .iter()
+            .map(|x| DVector::from_element(1, x - mean))
+            .collect();
+
+        filter
+            .filter(&observations)
+            .map_or(f64::NEG_INFINITY, |output| output.log_likelihood)
+    }
+}
+
+impl ArimaFit {
+    /// Number of estimated parameters (coefficients, constant and variance).
+    pub fn n_parameters(&self) -> usize {
+        self.model.ar.len() + self.model.ma.len() + 2
+    }
+
+    /// Akaike information criterion.
+    pub fn aic(&self) -> f64 {
+        2.0 * self.n_parameters() as f64 - 2.0 * self.log_likelihood
+    }
+
+    /// Bayesian information criterion.
+    pub fn bic(&self) -> f64 {
+        (self.n_observations as f64).ln() * self.n_parameters() as f64 - 2.0 * self.log_likelihood
+    }
+}
+
+impl ArimaForecast {
+    /// Gaussian prediction intervals at a `confidence` level, e.g. 0.95.
+    pub fn intervals(&self, confidence: f64) -> Vec<(f64, f64)> {
+        assert!(confidence > 0.0 && confidence < 1.0);
+
+        let z = Gaussian::new(0.0, 1.0).inv_cdf(0.5 + 0.5 * confidence);
+
+        self.mean
+            .iter()
+            .zip(&self.standard_errors)
+            .map(|(mean, se)| (mean - z * se, mean + z * se))
+            .collect()
+    }
+}
+
+/// Series differenced `d` times.
+pub fn differences(y: &[f64], d: usize) -> Vec<f64> {
+    (0..d).fold(y.to_vec(), |x, _| {
+        x.windows(2).map(|w| w[1] - w[0]).collect()
+    })
+}
+
+/// $\sum_i c_i x_{t-i}$, ignoring lags before the start of `x`.
+fn lagged(coefficients: &[f64], x: &[f64], t: usize) -> f64 {
+    coefficients
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i < t)
+        .map(|(i, c)| c * x[t - i - 1])
+        .sum()
+}
+
+/// Whether $1 - \sum_i c_i z^i$ has all its roots outside the unit circle,
+/// i.e. the companion matrix has all its eigenvalues inside.
+fn roots_outside_unit_circle(coefficients: &[f64]) -> bool {
+    let n = coefficients.len();
+
+    if n == 0 {
+        return true;
+    }
+
+    let companion = DMatrix::from_fn(n, n, |i, j| match i {
+        0 => coefficients[j],
+        i if j + 1 == i => 1.0,
+        _ => 0.0,
+    });
+
+    companion
+        .complex_eigenvalues()
+        .iter()
+        .all(|lambda| lambda.norm() < 1.0)
+}
+
+/// Hannan-Rissanen starting values `[c, phi, theta]`: a long autoregression
+/// estimates the innovations, then the series is regressed on its lags and
+/// the lagged innovations.
+fn hannan_rissanen(w: &[f64], p: usize, q: usize) -> Vec<f64> {
+    let n = w.len();
+    let fallback = || {
+        std::iter::once(w.iter().sum::<f64>() / n as f64)
+            .chain(std::iter::repeat_n(0.1, p + q))
+            .collect()
+    };
+
+    let regression = |lags: usize, innovations: Option<&[f64]>, start: usize| {
+        let columns = 1 + lags + innovations.map_or(0, |_| q);
+        let rows = n.checked_sub(start)?;
+        let x = DMatrix::from_fn(rows, columns, |i, j| {
+            let t = start + i;
+            match j {
+                0 => 1.0,
+                j if j <= lags => w[t - j],
+                j => innovations.unwrap()[t - (j - lags)],
+            }
+        });
+        let y = DVector::from_fn(rows, |i, _| w[start + i]);
+        ols(&y, &x).ok()
+    };
+
+    let long = (p + q + (n as f64).ln() as usize).min(n / 4);
+    let innovations: Option<Vec<f64>> = match q {
+        0 => None,
+        _ => regression(long, None, long).map(|fit| {
+            std::iter::repeat_n(0.0, long)
+                .chain(fit.residuals.iter().copied())
+                .collect()
+        }),
+    };
+
+    let start = match q {
+        0 => p,
+        _ => long + q.max(p),
+    };
+    match (q, &innovations) {
+        (0, _) => regression(p, None, start),
+        (_, Some(innovations)) => regression(p, Some(innovations), start),
+        _ => None,
+    }
+    .map_or_else(fallback, |fit| {
+        fit.coefficients
+            .iter()
+            .map(|c| if *c == 0.0 { 1e-3 } else { *c })
+            .collect()
+    })
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_arima {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_random_walk_forecast() {
+        // ARIMA(0, 1, 0) with drift: flat forecasts plus drift,
+        // standard errors growing as sqrt(h).
+        let model = Arima::new(vec![], 1, vec![], 0.5, 4.0);
+        let forecast = model.forecast(&[1.0, 2.0, 4.0], 3);
+
+        assert_eq!(forecast.mean, vec![4.5, 5.0, 5.5]);
+        assert_approx_equal!(forecast.standard_errors[2], 2.0 * 3.0_f64.sqrt(), 1e-12);
+
+        let (lower, upper) = forecast.intervals(0.95)[0];
+        assert_approx_equal!(upper - lower, 2.0 * 1.959964 * 2.0, 1e-5);
+
+        assert_eq!(differences(&[1.0, 2.0, 4.0, 7.0], 2), vec![1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_arma_fit() {
+        let model = Arima::new(vec![0.6], 0, vec![0.3], 0.5, 1.0);
+        let y = model.simulate(1000, &mut StdRng::seed_from_u64(642));
+
+        let css = Arima::fit(&y, (1, 0, 1), ArimaMethod::Css).unwrap();
+        let mle = Arima::fit(&y, (1, 0, 1), ArimaMethod::CssMle).unwrap();
+
+        for fit in [&css, &mle] {
+            assert_approx_equal!(fit.model.ar[0], 0.6, 0.08);
+            assert_approx_equal!(fit.model.ma[0], 0.3, 0.08);
+            assert_approx_equal!(fit.model.mean(), 1.25, 0.2);
+            assert_approx_equal!(fit.model.variance, 1.0, 0.1);
+        }
+
+        // The exact likelihood is maximised at the CSS-MLE estimate.
+        assert!(mle.log_likelihood >= css.model.log_likelihood(&y));
+        assert_approx_equal!(mle.log_likelihood, mle.model.log_likelihood(&y), 1e-9);
+        assert_eq!(mle.residuals.len(), 999);
+    }
+
+    #[test]
+    fn test_integrated_forecast() {
+        let model = Arima::new(vec![0.5], 1, vec![], 0.0, 1.0);
+        let y = model.simulate(300, &mut StdRng::seed_from_u64(642));
+
+        let fit = Arima::fit(&y, (1, 1, 0), ArimaMethod::CssMle).unwrap();
+        assert_eq!(fit.model.order(), (1, 1, 0));
+        assert_approx_equal!(fit.model.ar[0], 0.5, 0.1);
+
+        // psi-weights of (1 - 0.5B)(1 - B): 1, 1.5, 1.75.
+        let forecast = model.forecast(&y, 3);
+        assert_approx_equal!(forecast.standard_errors[1], 3.25_f64.sqrt(), 1e-12);
+        assert_approx_equal!(
+            forecast.standard_errors[2],
+            (3.25_f64 + 1.75 * 1.75).sqrt(),
+            1e-12
+        );
+
+        // The first forecast continues the last change at half the rate.
+        let last_change = y[299] - y[298];
+        assert_approx_equal!(forecast.mean[0], y[299] + 0.5 * last_change, 1e-12);
+    }
+
+    #[test]
+    fn test_order_selection() {
+        let model = Arima::new(vec![0.7], 0, vec![], 0.0, 1.0);
+        let y = model.simulate(500, &mut StdRng::seed_from_u64(642));
+
+        let fit =
+            Arima::select_order(&y, 2, 0, 2, InformationCriterion::Bic, ArimaMethod::Css).unwrap();
+        assert_eq!(fit.model.order(), (1, 0, 0));
+
+        assert!(!Arima::new(vec![1.2], 0, vec![], 0.0, 1.0).is_stationary());
+        assert!(!Arima::new(vec![], 0, vec![-1.5], 0.0, 1.0).is_invertible());
+        assert_eq!(
+            Arima::fit(&y[..5], (1, 0, 0), ArimaMethod::Css).unwrap_err(),
+            ArimaError::InsufficientData(5)
+        );
+    }
+}
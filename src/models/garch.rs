@@ -0,0 +1,695 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! GARCH-family conditional volatility models.
+//!
+//! Returns are modelled as $r_t = \mu + \epsilon_t$ with
+//! $\epsilon_t = \sigma_t z_t$, $z_t \sim N(0, 1)$, and the conditional
+//! variance follows one of:
+//!
+//! $$
+//! \begin{aligned}
+//! \text{GARCH(1,1)}: \quad & \sigma_t^2 = \omega + \alpha \epsilon_{t-1}^2 + \beta \sigma_{t-1}^2 \\
+//! \text{GJR-GARCH}: \quad & \sigma_t^2 = \omega + (\alpha + \gamma 1_{\epsilon_{t-1} < 0}) \epsilon_{t-1}^2 + \beta \sigma_{t-1}^2 \\
+//! \text{EGARCH}: \quad & \ln \sigma_t^2 = \omega + \alpha (|z_{t-1}| - E|z|) + \gamma z_{t-1} + \beta \ln \sigma_{t-1}^2
+//! \end{aligned}
+//! $$
+//!
+//! Parameters are estimated by Gaussian quasi-maximum likelihood, and a
+//! fitted model can be simulated as a `StochasticProcess` to drive the
+//! Monte Carlo engines.
+//!
+//! ```
+//! use RustQuant::models::*;
+//! use RustQuant::stochastics::*;
+//!
+//! let garch = Garch::new(0.0, 1e-6, 0.08, 0.9);
+//!
+//! // Simulate 2000 daily returns and fit a GARCH(1,1) to them.
+//! let config = SimulationConfig::new(false).with_seed(7);
+//! let prices = GarchProcess::new(garch, garch.unconditional_variance())
+//!     .simulate_with_config(100.0, 0.0, 1.0, 2000, 1, &config);
+//! let path = prices.path(0).to_vec();
+//! let returns: Vec<f64> = path.windows(2).map(|w| (w[1] / w[0]).ln()).collect();
+//!
+//! let fit = Garch::fit(&returns).unwrap();
+//!
+//! // Volatility forecasts for the next 10 days.
+//! let forecast = fit.model.forecast(&returns, 10);
+//! assert_eq!(forecast.len(), 10);
+//! ```
+
+use crate::math::optimize::NelderMead;
+use crate::stochastics::*;
+use rand::Rng;
+use rand_distr::StandardNormal;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::{FRAC_2_PI, PI};
+use thiserror::Error;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// GARCH(1,1) model of Bollerslev (1986).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Garch {
+    /// Mean return ($\mu$).
+    pub mu: f64,
+
+    /// Constant of the variance ($\omega$).
+    pub omega: f64,
+
+    /// Reaction to squared shocks ($\alpha$).
+    pub alpha: f64,
+
+    /// Persistence of the variance ($\beta$).
+    pub beta: f64,
+}
+
+/// GJR-GARCH(1,1) model of Glosten, Jagannathan and Runkle (1993),
+/// with a larger reaction to negative shocks.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GjrGarch {
+    /// Mean return ($\mu$).
+    pub mu: f64,
+
+    /// Constant of the variance ($\omega$).
+    pub omega: f64,
+
+    /// Reaction to squared shocks ($\alpha$).
+    pub alpha: f64,
+
+    /// Additional reaction to squared negative shocks ($\gamma$).
+    pub gamma: f64,
+
+    /// Persistence of the variance ($\beta$).
+    pub beta: f64,
+}
+
+/// EGARCH(1,1) model of Nelson (1991), on the log-variance.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Egarch {
+    /// Mean return ($\mu$).
+    pub mu: f64,
+
+    /// Constant of the log-variance ($\omega$).
+    pub omega: f64,
+
+    /// Reaction to the size of standardised shocks ($\alpha$).
+    pub alpha: f64,
+
+    /// Reaction to the sign of standardised shocks ($\gamma$).
+    pub gamma: f64,
+
+    /// Persistence of the log-variance ($\beta$).
+    pub beta: f64,
+}
+
+/// Conditional volatility models of returns $r_t = \mu + \sigma_t z_t$.
+pub trait VolatilityModel: Copy + Sync {
+    /// Mean return.
+    fn mean(&self) -> f64;
+
+    /// Variance of the next period given this period's variance and
+    /// residual $\epsilon_t = r_t - \mu$.
+    fn next_variance(&self, variance: f64, residual: f64) -> f64;
+
+    /// Expected variance of the period after next given next period's
+    /// variance (used for multi-step forecasts).
+    fn expected_next_variance(&self, variance: f64) -> f64;
+
+    /// Long-run (unconditional) variance.
+    fn unconditional_variance(&self) -> f64;
+
+    /// Model parameters, in the order of [`VolatilityModel::from_parameters`].
+    fn parameters(&self) -> Vec<f64>;
+
+    /// Model from its parameters, or `None` if they are not admissible
+    /// (e.g. a non-stationary variance).
+    fn from_parameters(parameters: &[f64]) -> Option<Self>;
+
+    /// Starting point of the estimation, for returns with the given
+    /// sample mean and variance.
+    fn initial_guess(mean: f64, variance: f64) -> Self;
+
+    /// The equivalent model of the returns multiplied by `factor`.
+    fn scaled(&self, factor: f64) -> Self;
+
+    /// Conditional variances $\sigma_1^2, \dots, \sigma_{n+1}^2$ of the
+    /// returns: one per return, plus the one-step-ahead forecast. The
+    /// recursion starts from the sample variance of the residuals.
+    fn conditional_variances(&self, returns: &[f64]) -> Vec<f64> {
+        let mu = self.mean();
+        let n = returns.len().max(1) as f64;
+        let mut variance = returns.iter().map(|r| (r - mu).powi(2)).sum::<f64>() / n;
+
+        let mut variances = Vec::with_capacity(returns.len() + 1);
+        variances.push(variance);
+
+        for r in returns {
+            variance = self.next_variance(variance, r - mu);
+            variances.push(variance);
+        }
+
+        variances
+    }
+
+    /// Gaussian log-likelihood of the returns.
+    fn log_likelihood(&self, returns: &[f64]) -> f64 {
+        let mu = self.mean();
+
+        returns
+            .iter()
+            .zip(self.conditional_variances(returns))
+            .map(|(r, variance)| {
+                -0.5 * ((2.0 * PI).ln() + variance.ln() + (r - mu).powi(2) / variance)
+            })
+            .sum()
+    }
+
+    /// Forecasts of the variance for the next `horizon` periods after the returns.
+    fn forecast(&self, returns: &[f64], horizon: usize) -> Vec<f64> {
+        let next = self
+            .conditional_variances(returns)
+            .last()
+            .copied()
+            .unwrap_or_else(|| self.unconditional_variance());
+
+        std::iter::successors(Some(next), |v| Some(self.expected_next_variance(*v)))
+            .take(horizon)
+            .collect()
+    }
+
+    /// Quasi-maximum likelihood estimate of the model from returns
+    /// (at least 10 observations).
+    ///
+    /// The likelihood is maximised with Nelder-Mead on the returns
+    /// standardised to unit variance, and the model rescaled afterwards.
+    fn fit(returns: &[f64]) -> Result<GarchFit<Self>, GarchError> {
+        let n = returns.len();
+        if n < 10 {
+            return Err(GarchError::InsufficientData(n));
+        }
+
+        let mean = returns.iter().sum::<f64>() / n as f64;
+        let scale = (returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / n as f64).sqrt();
+        if scale == 0.0 {
+            return Err(GarchError::EstimationFailed);
+        }
+
+        let standardised: Vec<f64> = returns.iter().map(|r| r / scale).collect();
+        let initial = Self::initial_guess(mean / scale, 1.0);
+
+        let result = NelderMead::new(5_000, 1e-10)
+            .with_initial_step(0.1)
+            .minimize(
+                &|parameters: &[f64]| match Self::from_parameters(parameters) {
+                    Some(model) => -model.log_likelihood(&standardised),
+                    None => f64::INFINITY,
+                },
+                &initial.parameters(),
+            );
+
+        let model = Self::from_parameters(&result.minimizer)
+            .ok_or(GarchError::EstimationFailed)?
+            .scaled(scale);
+
+        Ok(GarchFit {
+            model,
+            log_likelihood: model.log_likelihood(returns),
+            next_variance: model.forecast(returns, 1)[0],
+            n_observations: n,
+            converged: result.converged,
+        })
+    }
+
+    /// Simulates returns into `returns`, starting from `initial_variance`.
+    fn simulate_returns<R: Rng>(&self, initial_variance: f64, rng: &mut R, returns: &mut [f64]) {
+        let mut variance = initial_variance;
+
+        for r in returns.iter_mut() {
+            let z: f64 = rng.sample(StandardNormal);
+            let residual = variance.sqrt() * z;
+
+            *r = self.mean() + residual;
+            variance = self.next_variance(variance, residual);
+        }
+    }
+}
+
+/// Estimated volatility model.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GarchFit<M: VolatilityModel> {
+    /// Estimated model.
+    pub model: M,
+
+    /// Log-likelihood at the estimate.
+    pub log_likelihood: f64,
+
+    /// Variance forecast for the period after the sample.
+    pub next_variance: f64,
+
+    /// Number of returns used in the estimation.
+    pub n_observations: usize,
+
+    /// Whether the optimiser converged.
+    pub converged: bool,
+}
+
+/// Price process with GARCH log-returns: $S_{t+1} = S_t e^{r_{t+1}}$.
+///
+/// Each step of the simulation grid is one period of the model, whatever
+/// the times `t_0` and `t_n`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GarchProcess<M: VolatilityModel> {
+    /// Volatility model of the log-returns.
+    pub model: M,
+
+    /// Variance of the first simulated return.
+    pub initial_variance: f64,
+}
+
+/// Volatility model errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum GarchError {
+    /// Not enough returns to estimate the model.
+    #[error("Not enough returns to estimate the model: {0}.")]
+    InsufficientData(usize),
+
+    /// The estimation did not produce admissible parameters.
+    #[error("The estimation did not produce admissible parameters.")]
+    EstimationFailed,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Garch {
+    /// New GARCH(1,1) model.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parameters are not admissible
+    /// ($\omega > 0$, $\alpha, \beta \geq 0$, $\alpha + \beta < 1$).
+    pub fn new(mu: f64, omega: f64, alpha: f64, beta: f64) -> Self {
+        Self::from_parameters(&[mu, omega, alpha, beta]).expect("Inadmissible GARCH parameters.")
+    }
+}
+
+impl VolatilityModel for Garch {
+    fn mean(&self) -> f64 {
+        self.mu
+    }
+
+    fn next_variance(&self, variance: f64, residual: f64) -> f64 {
+        self.omega + self.alpha * residual.powi(2) + self.beta * variance
+    }
+
+    fn expected_next_variance(&self, variance: f64) -> f64 {
+        self.omega + (self.alpha + self.beta) * variance
+    }
+
+    fn unconditional_variance(&self) -> f64 {
+        self.omega / (1.0 - self.alpha - self.beta)
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        vec![self.mu, self.omega, self.alpha, self.beta]
+    }
+
+    fn from_parameters(parameters: &[f64]) -> Option<Self> {
+        let &[mu, omega, alpha, beta] = parameters else {
+            return None;
+        };
+
+        (omega > 0.0 && alpha >= 0.0 && beta >= 0.0 && alpha + beta < 1.0).then_some(Self {
+            mu,
+            omega,
+            alpha,
+            beta,
+        })
+    }
+
+    fn initial_guess(mean: f64, variance: f64) -> Self {
+        Self {
+            mu: mean,
+            omega: 0.05 * variance,
+            alpha: 0.05,
+            beta: 0.9,
+        }
+    }
+
+    fn scaled(&self, factor: f64) -> Self {
+        Self {
+            mu: self.mu * factor,
+            omega: self.omega * factor * factor,
+            ..*self
+        }
+    }
+}
+
+impl GjrGarch {
+    /// New GJR-GARCH(1,1) model.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parameters are not admissible ($\omega > 0$,
+    /// $\alpha, \alpha + \gamma, \beta \geq 0$, $\alpha + \gamma / 2 + \beta < 1$).
+    pub fn new(mu: f64, omega: f64, alpha: f64, gamma: f64, beta: f64) -> Self {
+        Self::from_parameters(&[mu, omega, alpha, gamma, beta])
+            .expect("Inadmissible GJR-GARCH parameters.")
+    }
+
+    /// Persistence of the variance, $\alpha + \gamma / 2 + \beta$.
+    pub fn persistence(&self) -> f64 {
+        self.alpha + 0.5 * self.gamma + self.beta
+    }
+}
+
+impl VolatilityModel for GjrGarch {
+    fn mean(&self) -> f64 {
+        self.mu
+    }
+
+    fn next_variance(&self, variance: f64, residual: f64) -> f64 {
+        let gamma = if residual < 0.0 { self.gamma } else { 0.0 };
+
+        self.omega + (self.alpha + gamma) * residual.powi(2) + self.beta * variance
+    }
+
+    fn expected_next_variance(&self, variance: f64) -> f64 {
+        self.omega + self.persistence() * variance
+    }
+
+    fn unconditional_variance(&self) -> f64 {
+        self.omega / (1.0 - self.persistence())
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        vec![self.mu, self.omega, self.alpha, self.gamma, self.beta]
+    }
+
+    fn from_parameters(parameters: &[f64]) -> Option<Self> {
+        let &[mu, omega, alpha, gamma, beta] = parameters else {
+            return None;
+        };
+
+        let model = Self {
+            mu,
+            omega,
+            alpha,
+            gamma,
+            beta,
+        };
+
+        (omega > 0.0
+            && alpha >= 0.0
+            && alpha + gamma >= 0.0
+            && beta >= 0.0
+            && model.persistence() < 1.0)
+            .then_some(model)
+    }
+
+    fn initial_guess(mean: f64, variance: f64) -> Self {
+        Self {
+            mu: mean,
+            omega: 0.05 * variance,
+            alpha: 0.03,
+            gamma: 0.04,
+            beta: 0.9,
+        }
+    }
+
+    fn scaled(&self, factor: f64) -> Self {
+        Self {
+            mu: self.mu * factor,
+            omega: self.omega * factor * factor,
+            ..*self
+        }
+    }
+}
+
+impl Egarch {
+    /// New EGARCH(1,1) model.
+    ///
+    /// # Panics
+    ///
+    /// Panics if $|\beta| \geq 1$ (non-stationary log-variance).
+    pub fn new(mu: f64, omega: f64, alpha: f64, gamma: f64, beta: f64) -> Self {
+        Self::from_parameters(&[mu, omega, alpha, gamma, beta])
+            .expect("Inadmissible EGARCH parameters.")
+    }
+}
+
+impl VolatilityModel for Egarch {
+    fn mean(&self) -> f64 {
+        self.mu
+    }
+
+    fn next_variance(&self, variance: f64, residual: f64) -> f64 {
+        let z = residual / variance.sqrt();
+
+        (self.omega
+            + self.alpha * (z.abs() - FRAC_2_PI.sqrt())
+            + self.gamma * z
+            + self.beta * variance.ln())
+        .exp()
+    }
+
+    /// Exponential of the expected log-variance (a lower bound of the
+    /// expected variance, by Jensen's inequality).
+    fn expected_next_variance(&self, variance: f64) -> f64 {
+        (self.omega + self.beta * variance.ln()).exp()
+    }
+
+    /// Exponential of the long-run mean of the log-variance.
+    fn unconditional_variance(&self) -> f64 {
+        (self.omega / (1.0 - self.beta)).exp()
+    }
+
+    fn parameters(&self) -> Vec<f64> {
+        vec![self.mu, self.omega, self.alpha, self.gamma, self.beta]
+    }
+
+    fn from_parameters(parameters: &[f64]) -> Option<Self> {
+        let &[mu, omega, alpha, gamma, beta] = parameters else {
+            return None;
+        };
+
+        (beta.abs() < 1.0).then_some(Self {
+            mu,
+            omega,
+            alpha,
+            gamma,
+            beta,
+        })
+    }
+
+    fn initial_guess(mean: f64, variance: f64) -> Self {
+        Self {
+            mu: mean,
+            omega: 0.05 * variance.ln(),
+            alpha: 0.1,
+            gamma: -0.05,
+            beta: 0.95,
+        }
+    }
+
+    fn scaled(&self, factor: f64) -> Self {
+        Self {
+            mu: self.mu * factor,
+            omega: self.omega + (1.0 - self.beta) * (factor * factor).ln(),
+            ..*self
+        }
+    }
+}
+
+impl<M: VolatilityModel> GarchFit<M> {
+    /// Akaike information criterion.
+    pub fn aic(&self) -> f64 {
+        2.0 * self.model.parameters().len() as f64 - 2.0 * self.log_likelihood
+    }
+
+    /// Bayesian information criterion.
+    pub fn bic(&self) -> f64 {
+        (self.n_observations as f64).ln() * self.model.parameters().len() as f64
+            - 2.0 * self.log_likelihood
+    }
+
+    /// Price process continuing from the end of the sample.
+    pub fn process(&self) -> GarchProcess<M> {
+        GarchProcess::new(self.model, self.next_variance)
+    }
+}
+
+impl<M: VolatilityModel> GarchProcess<M> {
+    /// New price process.
+    pub fn new(model: M, initial_variance: f64) -> Self {
+        assert!(initial_variance > 0.0);
+
+        Self {
+            model,
+            initial_variance,
+        }
+    }
+}
+
+impl<M: VolatilityModel> StochasticProcess for GarchProcess<M> {
+    /// Drift per period with the variance frozen at its initial value.
+    fn drift(&self, x: f64, _t: f64) -> f64 {
+        (self.model.mean() + 0.5 * self.initial_variance) * x
+    }
+
+    /// Diffusion per period with the variance frozen at its initial value.
+    /// The simulation methods use the full variance recursion instead.
+    fn diffusion(&self, x: f64, _t: f64) -> f64 {
+        self.initial_variance.sqrt() * x
+    }
+
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        None
+    }
+
+    /// Simulates prices with one GARCH log-return per step.
+    fn simulate_with_config(
+        &self,
+        x_0: f64,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        config: &SimulationConfig,
+    ) -> Trajectories {
+        generate_trajectories(t_0, t_n, n_steps, m_paths, config, |_, rng, path| {
+            self.model
+                .simulate_returns(self.initial_variance, rng, &mut path[1..]);
+
+            path[0] = x_0;
+            for t in 1..path.len() {
+                path[t] = path[t - 1] * path[t].exp();
+            }
+        })
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_garch {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn simulate<M: VolatilityModel>(model: M, n: usize, seed: u64) -> Vec<f64> {
+        let mut returns = vec![0.0; n];
+        model.simulate_returns(
+            model.unconditional_variance(),
+            &mut StdRng::seed_from_u64(seed),
+            &mut returns,
+        );
+        returns
+    }
+
+    #[test]
+    fn test_garch_recursion_and_forecast() {
+        let garch = Garch::new(0.0, 0.1, 0.1, 0.8);
+        let returns = [1.0, -2.0, 0.5];
+
+        let variances = garch.conditional_variances(&returns);
+        assert_eq!(variances.len(), 4);
+        assert_approx_equal!(variances[0], 5.25 / 3.0, 1e-12);
+        assert_approx_equal!(variances[1], 0.1 + 0.1 + 0.8 * variances[0], 1e-12);
+        assert_approx_equal!(variances[2], 0.1 + 0.4 + 0.8 * variances[1], 1e-12);
+
+        // Forecasts revert to the long-run variance of 1.
+        let forecast = garch.forecast(&returns, 200);
+        assert_approx_equal!(forecast[0], variances[3], 1e-12);
+        assert_approx_equal!(forecast[1], 0.1 + 0.9 * forecast[0], 1e-12);
+        assert_approx_equal!(forecast[199], garch.unconditional_variance(), 1e-8);
+
+        assert!(Garch::from_parameters(&[0.0, 0.1, 0.5, 0.5]).is_none());
+        assert!(Garch::from_parameters(&[0.0, 0.1, 0.5]).is_none());
+    }
+
+    #[test]
+    fn test_garch_fit() {
+        let garch = Garch::new(0.0005, 2e-6, 0.1, 0.85);
+        let returns = simulate(garch, 5000, 637);
+
+        let fit = Garch::fit(&returns).unwrap();
+
+        assert!(fit.converged);
+        assert_approx_equal!(fit.model.alpha, 0.1, 0.03);
+        assert_approx_equal!(fit.model.beta, 0.85, 0.05);
+        assert_approx_equal!(
+            fit.model.unconditional_variance(),
+            garch.unconditional_variance(),
+            1e-5
+        );
+        assert!(fit.log_likelihood >= garch.log_likelihood(&returns));
+        assert!(fit.bic() > fit.aic());
+
+        assert_eq!(
+            Garch::fit(&returns[..5]).unwrap_err(),
+            GarchError::InsufficientData(5)
+        );
+    }
+
+    #[test]
+    fn test_gjr_garch_fit() {
+        let gjr = GjrGarch::new(0.0, 2e-6, 0.02, 0.15, 0.85);
+        let returns = simulate(gjr, 5000, 638);
+
+        let fit = GjrGarch::fit(&returns).unwrap();
+
+        assert!(fit.model.gamma > 0.05);
+        assert!(fit.model.persistence() < 1.0);
+        assert!(fit.log_likelihood >= Garch::fit(&returns).unwrap().log_likelihood);
+    }
+
+    #[test]
+    fn test_egarch() {
+        let egarch = Egarch::new(0.0, -0.5, 0.15, -0.08, 0.95);
+
+        // Negative shocks raise the variance more than positive ones.
+        let variance = egarch.unconditional_variance();
+        let shock = 2.0 * variance.sqrt();
+        assert!(egarch.next_variance(variance, -shock) > egarch.next_variance(variance, shock));
+
+        let returns = simulate(egarch, 5000, 639);
+        let fit = Egarch::fit(&returns).unwrap();
+
+        assert!(fit.model.gamma < 0.0);
+        assert_approx_equal!(fit.model.beta, 0.95, 0.05);
+
+        // The scaled model has the same likelihood, up to the Jacobian.
+        let scaled: Vec<f64> = returns.iter().map(|r| 10.0 * r).collect();
+        assert_approx_equal!(
+            fit.model.scaled(10.0).log_likelihood(&scaled),
+            fit.model.log_likelihood(&returns) - 5000.0 * 10.0_f64.ln(),
+            1e-6
+        );
+    }
+
+    #[test]
+    fn test_garch_process() {
+        let fit = Garch::fit(&simulate(Garch::new(0.0, 1e-5, 0.1, 0.8), 2000, 640)).unwrap();
+        let config = SimulationConfig::new(true).with_seed(637);
+
+        let paths = fit
+            .process()
+            .simulate_with_config(100.0, 0.0, 1.0, 250, 500, &config);
+
+        assert_eq!(paths.n_paths(), 500);
+        assert!(paths.terminal_values().iter().all(|s| *s > 0.0));
+    }
+}
@@ -11,9 +11,25 @@
 // MODELS MODULE
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-//! Module containing all models (e.g. Black-Scholes, Heston, etc).
+//! Module containing all models (e.g. Black-Scholes, Heston, GARCH, etc).
 //! Also a `Model` trait is defined here for all models to implement.
 
 /// Model trait.
 pub mod model;
 pub use model::*;
+
+/// GARCH-family volatility models.
+pub mod garch;
+pub use garch::*;
+
+/// ARIMA models of the conditional mean.
+pub mod arima;
+pub use arima::*;
+
+/// Least-squares calibration with autodiff Jacobians.
+pub mod calibrator;
+pub use calibrator::*;
+
+/// Saving and loading of fitted models.
+pub mod persistence;
+pub use persistence::*;
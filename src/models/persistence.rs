@@ -0,0 +1,332 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Saving and loading of fitted models as JSON.
+//!
+//! A model is written together with its type name and the version of the
+//! crate that wrote it, so loading a file into the wrong type fails with
+//! [`PersistenceError::WrongModel`] instead of a confusing parse error.
+//!
+//! ```rust
+//! use RustQuant::models::*;
+//!
+//! let returns: Vec<f64> = (0..500).map(|i| 0.01 * ((i * 37 % 101) as f64 / 50.0 - 1.0)).collect();
+//! let fit = Garch::fit(&returns).unwrap();
+//!
+//! let json = fit.to_json().unwrap();
+//! let loaded = GarchFit::<Garch>::from_json(&json).unwrap();
+//!
+//! assert_eq!(loaded, fit);
+//! ```
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPORTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use thiserror::Error;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Model persistence errors.
+#[derive(Debug, Error)]
+pub enum PersistenceError {
+    /// Reading or writing the file failed.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The model could not be (de)serialized.
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// The file holds a different type of model.
+    #[error("Expected a {expected} model, found a {found} model.")]
+    WrongModel {
+        /// Type name of the model being loaded.
+        expected: String,
+
+        /// Type name of the model in the file.
+        found: String,
+    },
+}
+
+/// Fitted models that can be saved to and loaded from JSON.
+pub trait Persist: Serialize + DeserializeOwned {
+    /// Name of the model recorded in the file.
+    const MODEL: &'static str;
+
+    /// Serializes the model to JSON.
+    fn to_json(&self) -> Result<String, PersistenceError> {
+        let saved = SavedModel {
+            model: Self::MODEL.to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            parameters: self,
+        };
+
+        Ok(serde_json::to_string_pretty(&saved)?)
+    }
+
+    /// Deserializes a model written by [`Persist::to_json`].
+    fn from_json(json: &str) -> Result<Self, PersistenceError> {
+        let saved: SavedModel<Self> = serde_json::from_str(json).map_err(|error| {
+            // Report a type mismatch rather than the parse error it causes.
+            match serde_json::from_str::<SavedModel<serde_json::Value>>(json) {
+                Ok(header) if header.model != Self::MODEL => PersistenceError::WrongModel {
+                    expected: Self::MODEL.to_string(),
+                    found: header.model,
+                },
+                _ => PersistenceError::Json(error),
+            }
+        })?;
+
+        match saved.model == Self::MODEL {
+            true => Ok(saved.parameters),
+            false => Err(PersistenceError::WrongModel {
+                expected: Self::MODEL.to_string(),
+                found: saved.model,
+            }),
+        }
+    }
+
+    /// Writes the model to a JSON file.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn save<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), PersistenceError> {
+        Ok(std::fs::write(path, self.to_json()?)?)
+    }
+
+    /// Reads a model from a JSON file written by [`Persist::save`].
+    #[cfg(not(target_arch = "wasm32"))]
+    fn load<P: AsRef<std::path::Path>>(path: P) -> Result<Self, PersistenceError> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+}
+
+/// File layout: the model and crate version, then the model itself.
+#[derive(Serialize, Deserialize)]
+struct SavedModel<T> {
+    model: String,
+    version: String,
+    parameters: T,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// (De)serialization of vectors that may hold `NaN`, which JSON writes as
+/// `null`. For use with `#[serde(with = "...")]`.
+pub(crate) mod nullable {
+    use nalgebra::DVector;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub(crate) fn serialize<S: Serializer>(
+        values: &DVector<f64>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let values: Vec<Option<f64>> = values.iter().map(|v| v.is_finite().then_some(*v)).collect();
+
+        values.serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<DVector<f64>, D::Error> {
+        let values = Vec::<Option<f64>>::deserialize(deserializer)?;
+
+        Ok(DVector::from_iterator(
+            values.len(),
+            values.into_iter().map(|v| v.unwrap_or(f64::NAN)),
+        ))
+    }
+}
+
+impl Persist for crate::models::GarchFit<crate::models::Garch> {
+    const MODEL: &'static str = "GarchFit<Garch>";
+}
+
+impl Persist for crate::models::GarchFit<crate::models::GjrGarch> {
+    const MODEL: &'static str = "GarchFit<GjrGarch>";
+}
+
+impl Persist for crate::models::GarchFit<crate::models::Egarch> {
+    const MODEL: &'static str = "GarchFit<Egarch>";
+}
+
+impl Persist for crate::models::ArimaFit {
+    const MODEL: &'static str = "ArimaFit";
+}
+
+impl Persist for crate::models::CalibrationResult {
+    const MODEL: &'static str = "CalibrationResult";
+}
+
+impl Persist for crate::stochastics::Heston {
+    const MODEL: &'static str = "Heston";
+}
+
+impl Persist for crate::ml::LinearFit {
+    const MODEL: &'static str = "LinearFit";
+}
+
+impl Persist for crate::ml::LogisticFit {
+    const MODEL: &'static str = "LogisticFit";
+}
+
+impl Persist for crate::ml::FittedGaussianProcess {
+    const MODEL: &'static str = "FittedGaussianProcess";
+}
+
+impl Persist for crate::ml::FittedTree {
+    const MODEL: &'static str = "FittedTree";
+}
+
+impl Persist for crate::ml::FittedGradientBoosting {
+    const MODEL: &'static str = "FittedGradientBoosting";
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_persistence {
+    use super::*;
+    use crate::ml::*;
+    use crate::models::*;
+    use crate::stochastics::Heston;
+    use nalgebra::{DMatrix, DVector};
+
+    fn returns() -> Vec<f64> {
+        (0..400)
+            .map(|i| 0.01 * (((i * 37 + 11) % 101) as f64 / 50.0 - 1.0))
+            .collect()
+    }
+
+    #[test]
+    fn test_time_series_models() {
+        let garch = Garch::fit(&returns()).unwrap();
+        assert_eq!(
+            GarchFit::<Garch>::from_json(&garch.to_json().unwrap()).unwrap(),
+            garch
+        );
+
+        let gjr = GjrGarch::fit(&returns()).unwrap();
+        assert_eq!(
+            GarchFit::<GjrGarch>::from_json(&gjr.to_json().unwrap()).unwrap(),
+            gjr
+        );
+
+        let arima = Arima::fit(&returns(), (1, 0, 1), ArimaMethod::Css).unwrap();
+        assert_eq!(
+            ArimaFit::from_json(&arima.to_json().unwrap()).unwrap(),
+            arima
+        );
+    }
+
+    #[test]
+    fn test_heston_parameters() {
+        let heston = Heston::new(0.02, 0.04, 1.5, 0.05, 0.4, -0.7);
+        let loaded = Heston::from_json(&heston.to_json().unwrap()).unwrap();
+
+        assert_eq!(loaded.kappa, heston.kappa);
+        assert_eq!(loaded.rho, heston.rho);
+    }
+
+    #[test]
+    fn test_gaussian_process_predictions_survive_round_trip() {
+        let x = DMatrix::from_fn(8, 1, |i, _| i as f64 / 7.0);
+        let y = DVector::from_fn(8, |i, _| (3.0 * x[(i, 0)]).sin());
+        let fitted = GaussianProcess::new(Kernel::Matern52, 1.0, vec![0.3], 1e-6)
+            .fit(&x, &y)
+            .unwrap();
+
+        let loaded = FittedGaussianProcess::from_json(&fitted.to_json().unwrap()).unwrap();
+        let grid = DMatrix::from_fn(5, 1, |i, _| i as f64 / 4.0 + 0.05);
+
+        assert_eq!(loaded.predict(&grid), fitted.predict(&grid));
+    }
+
+    #[test]
+    fn test_regression_and_trees() {
+        let x = DMatrix::from_fn(30, 2, |i, j| ((i * (j + 3)) % 7) as f64);
+        let y = DVector::from_fn(30, |i, _| 1.0 + 0.5 * x[(i, 0)] - 0.2 * x[(i, 1)]);
+
+        let linear = LinearModel::new(Penalty::Ridge(0.1))
+            .fit(
+                &DesignMatrix::new(x.clone(), vec!["a".into(), "b".into()]).with_intercept(),
+                &y,
+            )
+            .unwrap();
+        assert_eq!(
+            LinearFit::from_json(&linear.to_json().unwrap()).unwrap(),
+            linear
+        );
+
+        let tree = DecisionTree::new(SplitCriterion::SquaredError)
+            .fit(&x, &y)
+            .unwrap();
+        assert_eq!(
+            FittedTree::from_json(&tree.to_json().unwrap()).unwrap(),
+            tree
+        );
+    }
+
+    #[test]
+    fn test_dropped_coefficients() {
+        let x = DMatrix::from_fn(40, 3, |i, j| ((i * (j + 2) + j) % 9) as f64);
+        let y = DVector::from_fn(40, |i, _| 2.0 * x[(i, 0)] + 0.01 * x[(i, 2)]);
+        let names = vec!["a".into(), "b".into(), "c".into()];
+
+        let lasso = LinearModel::new(Penalty::Lasso(0.5))
+            .fit(&DesignMatrix::new(x, names).with_intercept(), &y)
+            .unwrap();
+        assert!(lasso.coefficients.p_values.iter().any(|p| p.is_nan()));
+
+        let loaded = LinearFit::from_json(&lasso.to_json().unwrap()).unwrap();
+        for (a, b) in loaded
+            .coefficients
+            .p_values
+            .iter()
+            .zip(lasso.coefficients.p_values.iter())
+        {
+            assert!(a == b || (a.is_nan() && b.is_nan()));
+        }
+        assert_eq!(loaded.coefficients.estimates, lasso.coefficients.estimates);
+    }
+
+    #[test]
+    fn test_save_load_and_wrong_model() {
+        let path = std::env::temp_dir().join("rustquant_persistence_test.json");
+        let fit = Garch::fit(&returns()).unwrap();
+
+        fit.save(&path).unwrap();
+        assert_eq!(GarchFit::<Garch>::load(&path).unwrap(), fit);
+
+        assert!(matches!(
+            ArimaFit::load(&path),
+            Err(PersistenceError::WrongModel { .. })
+        ));
+        assert!(matches!(
+            GarchFit::<Egarch>::load(&path),
+            Err(PersistenceError::WrongModel { .. })
+        ));
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            GarchFit::<Garch>::load(&path),
+            Err(PersistenceError::Io(_))
+        ));
+        assert!(matches!(
+            ArimaFit::from_json("not json"),
+            Err(PersistenceError::Json(_))
+        ));
+    }
+}
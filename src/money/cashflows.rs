@@ -9,6 +9,7 @@
 
 //! Cashflows module.
 
+use crate::money::Currency;
 use time::OffsetDateTime;
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -36,6 +37,17 @@ pub struct SimpleCashflow {
     date: OffsetDateTime,
 }
 
+/// Cashflow tagged with its currency, as paid by an instrument.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DatedCashflow {
+    /// Payment date.
+    pub date: OffsetDateTime,
+    /// Amount paid (negative if paid out).
+    pub amount: f64,
+    /// Currency of the amount, if known.
+    pub currency: Option<Currency>,
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // IMPLEMENTATIONS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -64,6 +76,34 @@ impl Cashflow for SimpleCashflow {
     }
 }
 
+impl DatedCashflow {
+    /// Create a new cashflow of `amount` in `currency` on `date`.
+    pub fn new(date: OffsetDateTime, amount: f64, currency: Option<Currency>) -> Self {
+        Self {
+            date,
+            amount,
+            currency,
+        }
+    }
+}
+
+impl Cashflow for DatedCashflow {
+    fn amount(&self) -> f64 {
+        self.amount
+    }
+
+    fn date(&self) -> OffsetDateTime {
+        self.date
+    }
+
+    fn npv<F>(&self, df: F) -> f64
+    where
+        F: Fn(OffsetDateTime) -> f64,
+    {
+        self.amount * df(self.date)
+    }
+}
+
 impl std::ops::Add for SimpleCashflow {
     type Output = Self;
 
@@ -281,4 +321,16 @@ mod test_cashflows {
         let cf2 = SimpleCashflow::new(50.0, date2);
         let _ = cf1 + cf2;
     }
+
+    // Test for currency-tagged cashflows.
+    #[test]
+    fn test_dated_cashflow() {
+        let date = OffsetDateTime::now_utc();
+        let cf = DatedCashflow::new(date, 100.0, Some(crate::money::USD));
+
+        assert_eq!(cf.amount(), 100.0);
+        assert_eq!(cf.date(), date);
+        assert_eq!(cf.npv(|_| 0.9), 90.0);
+        assert!(cf.currency.is_some());
+    }
 }
@@ -12,9 +12,27 @@
 //! The `Money` struct is a combination of a currency and an amount.
 //! Basic arithmetic operations can be performed  on `Money` instances with the
 //! same underlying currency.
-
-use crate::instruments::Instrument;
+//!
+//! Amounts are fixed-point `Decimal`s, and can be rounded and formatted
+//! according to the minor units of their currency.
+//!
+//! ```
+//! use RustQuant::money::*;
+//!
+//! let price = Money::new(USD, 1234.5);
+//! let fee = Money::new(USD, 0.125);
+//!
+//! let total = (price + fee).round(RoundingMode::HalfEven);
+//! assert_eq!(total.format(), "$1,234.62");
+//!
+//! // Amounts in different currencies cannot be added.
+//! assert!(price.checked_add(&Money::new(EUR, 1.0)).is_err());
+//! ```
+
+use crate::instruments::{Instrument, PricingResult};
+use crate::money::{Decimal, Rounding, RoundingMode};
 use std::fmt::{self, Formatter};
+use thiserror::Error;
 use time::OffsetDateTime;
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -42,7 +60,23 @@ pub struct Money {
     /// The underlying currency.
     pub currency: Currency,
     /// The amount.
-    pub amount: f64,
+    pub amount: Decimal,
+}
+
+/// Money errors.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum MoneyError {
+    /// Arithmetic between amounts in different currencies.
+    #[error("Currency mismatch: {0} and {1}")]
+    CurrencyMismatch(&'static str, &'static str),
+
+    /// No exchange rate is available between two currencies.
+    #[error("Missing exchange rate: {0}/{1}")]
+    MissingRate(&'static str, &'static str),
+
+    /// The string is not a valid currency pair.
+    #[error("Invalid currency pair: {0}")]
+    InvalidCurrencyPair(String),
 }
 
 /// ISO 4217 codes enum.
@@ -64,8 +98,8 @@ pub struct ISO_4217 {
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 impl Instrument for Currency {
-    fn price(&self) -> f64 {
-        1.0
+    fn price(&self) -> PricingResult {
+        PricingResult::timed(|| PricingResult::new(1.0).with_currency(Some(*self)))
     }
 
     fn error(&self) -> Option<f64> {
@@ -181,12 +215,36 @@ impl Currency {
     pub fn fractions(&self) -> usize {
         self.fractions
     }
+
+    /// Look up an ISO 4217 currency by its alphabetic ("USD")
+    /// or numeric ("840") code.
+    pub fn from_code(code: &str) -> Option<Currency> {
+        let code = code.trim().to_ascii_uppercase();
+
+        crate::money::ISO_CURRENCIES
+            .iter()
+            .find(|currency| currency.code.alphabetic == code || currency.code.numeric == code)
+            .copied()
+    }
 }
 
 impl Money {
     /// Create a new money instance.
-    pub fn new(currency: Currency, amount: f64) -> Self {
-        Self { currency, amount }
+    pub fn new(currency: Currency, amount: impl Into<Decimal>) -> Self {
+        Self {
+            currency,
+            amount: amount.into(),
+        }
+    }
+
+    /// Zero amount of a currency.
+    pub fn zero(currency: Currency) -> Self {
+        Self::new(currency, Decimal::ZERO)
+    }
+
+    /// Create a money instance from an amount in minor units (e.g. cents).
+    pub fn from_minor_units(currency: Currency, units: i64) -> Self {
+        Self::new(currency, Decimal::new(units as i128, currency.minor as u32))
     }
 
     /// Get the currency.
@@ -194,9 +252,97 @@ impl Money {
         self.currency
     }
 
-    /// Get the amount.
+    /// Get the amount as a float.
     pub fn amount(&self) -> f64 {
-        self.amount
+        self.amount.to_f64()
+    }
+
+    /// Amount rounded to the minor unit of the currency (e.g. cents).
+    pub fn round(&self, mode: RoundingMode) -> Self {
+        Self::new(
+            self.currency,
+            self.amount.round_dp(self.currency.minor as u32, mode),
+        )
+    }
+
+    /// Amount rounded with a rounding rule.
+    pub fn round_with(&self, rounding: Rounding) -> Self {
+        rounding.round_money(*self)
+    }
+
+    /// Amount in minor units (e.g. cents), rounded half to even.
+    pub fn to_minor_units(&self) -> i128 {
+        let rounded = self.round(RoundingMode::HalfEven).amount;
+
+        (rounded * Decimal::new(10_i128.pow(self.currency.minor as u32), 0)).to_i128()
+    }
+
+    /// Sum of two amounts, or an error if the currencies differ.
+    pub fn checked_add(&self, other: &Money) -> Result<Money, MoneyError> {
+        self.check_currency(other)?;
+
+        Ok(Self::new(self.currency, self.amount + other.amount))
+    }
+
+    /// Difference of two amounts, or an error if the currencies differ.
+    pub fn checked_sub(&self, other: &Money) -> Result<Money, MoneyError> {
+        self.check_currency(other)?;
+
+        Ok(Self::new(self.currency, self.amount - other.amount))
+    }
+
+    /// Formats the amount with the currency symbol, thousands separators
+    /// and the minor unit digits, e.g. "$1,234.57" or "-¥1,235".
+    pub fn format(&self) -> String {
+        let (sign, digits) = self.format_digits();
+
+        format!("{}{}{}", sign, self.currency.symbol, digits)
+    }
+
+    /// Formats the amount with the ISO 4217 code, e.g. "USD 1,234.57".
+    pub fn format_with_code(&self) -> String {
+        let (sign, digits) = self.format_digits();
+
+        format!("{} {}{}", self.currency.code.alphabetic, sign, digits)
+    }
+
+    fn check_currency(&self, other: &Money) -> Result<(), MoneyError> {
+        if self.currency == other.currency {
+            Ok(())
+        } else {
+            Err(MoneyError::CurrencyMismatch(
+                self.currency.code.alphabetic,
+                other.currency.code.alphabetic,
+            ))
+        }
+    }
+
+    /// Sign and grouped digits of the rounded amount.
+    fn format_digits(&self) -> (&'static str, String) {
+        let rounded = self.round(RoundingMode::HalfEven).amount;
+        let digits = format!("{:.*}", self.currency.minor, rounded.abs());
+
+        let (integer, fraction) = match digits.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (digits.as_str(), None),
+        };
+
+        let mut grouped = String::new();
+        for (i, c) in integer.chars().enumerate() {
+            if i > 0 && (integer.len() - i).is_multiple_of(3) {
+                grouped.push(',');
+            }
+            grouped.push(c);
+        }
+
+        if let Some(fraction) = fraction {
+            grouped.push('.');
+            grouped.push_str(fraction);
+        }
+
+        let sign = if rounded.is_negative() { "-" } else { "" };
+
+        (sign, grouped)
     }
 }
 
@@ -265,6 +411,39 @@ impl std::ops::Mul for Money {
     }
 }
 
+impl std::ops::Neg for Money {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            currency: self.currency,
+            amount: -self.amount,
+        }
+    }
+}
+
+impl std::ops::Mul<f64> for Money {
+    type Output = Self;
+
+    fn mul(self, factor: f64) -> Self::Output {
+        Self {
+            currency: self.currency,
+            amount: self.amount * Decimal::from_f64(factor),
+        }
+    }
+}
+
+impl std::ops::Div<f64> for Money {
+    type Output = Self;
+
+    fn div(self, divisor: f64) -> Self::Output {
+        Self {
+            currency: self.currency,
+            amount: self.amount / Decimal::from_f64(divisor),
+        }
+    }
+}
+
 impl std::ops::Div for Money {
     type Output = Self;
 
@@ -415,4 +594,48 @@ mod test_currencies {
         let money2 = Money::new(EUR, 2.0);
         let _ = money1 / money2;
     }
+
+    #[test]
+    fn test_money_decimal_amounts() {
+        let money = Money::new(USD, 0.1) + Money::new(USD, 0.2);
+        assert_eq!(money, Money::new(USD, "0.3".parse::<Decimal>().unwrap()));
+
+        assert_eq!((Money::new(USD, 10) * 0.5).amount(), 5.0);
+        assert_eq!((-Money::new(USD, 10) / 4.0).amount(), -2.5);
+        assert_eq!(Money::from_minor_units(USD, 12345), Money::new(USD, 123.45));
+        assert_eq!(Money::new(USD, 123.455).to_minor_units(), 12346);
+    }
+
+    #[test]
+    fn test_money_checked_arithmetic() {
+        let usd = Money::new(USD, 20.5);
+
+        assert_eq!(usd.checked_add(&usd), Ok(Money::new(USD, 41)));
+        assert_eq!(usd.checked_sub(&usd), Ok(Money::zero(USD)));
+        assert_eq!(
+            usd.checked_add(&Money::new(EUR, 1.0)),
+            Err(MoneyError::CurrencyMismatch("USD", "EUR"))
+        );
+    }
+
+    #[test]
+    fn test_money_rounding_and_formatting() {
+        use crate::money::{JPY, KWD};
+
+        assert_eq!(Money::new(USD, 1234567.125).format(), "$1,234,567.12");
+        assert_eq!(Money::new(USD, -0.5).format_with_code(), "USD -0.50");
+        assert_eq!(Money::new(JPY, 1234.5).format(), "¥1,234");
+        assert_eq!(Money::new(KWD, 1.23456).format_with_code(), "KWD 1.235");
+        assert_eq!(
+            Money::new(USD, 2.345).round(RoundingMode::HalfUp),
+            Money::new(USD, 2.35)
+        );
+    }
+
+    #[test]
+    fn test_currency_from_code() {
+        assert_eq!(Currency::from_code("usd"), Some(crate::money::USD));
+        assert_eq!(Currency::from_code("392"), Some(crate::money::JPY));
+        assert_eq!(Currency::from_code("XYZ"), None);
+    }
 }
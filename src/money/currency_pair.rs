@@ -0,0 +1,251 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Currency pairs and their market quoting conventions.
+//!
+//! A pair "EUR/USD" quotes the price of one unit of the base currency (EUR)
+//! in units of the quote currency (USD).
+//!
+//! ```
+//! use RustQuant::money::*;
+//!
+//! // Market ordering puts EUR before USD, whatever the argument order.
+//! let pair = CurrencyPair::market(USD, EUR);
+//! assert_eq!(pair.to_string(), "EUR/USD");
+//!
+//! assert_eq!(pair.pip_size(), 0.0001);
+//! assert_eq!(pair.spot_lag(), 2);
+//! ```
+
+use crate::money::{Currency, ExchangeRateService, MoneyError};
+use crate::time::{fx_spot_date, fx_spot_lag, Calendar, IntoEvaluationDate};
+use std::fmt;
+use std::str::FromStr;
+use time::OffsetDateTime;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Currency pair (base/quote).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CurrencyPair {
+    /// Base currency (the currency being priced).
+    pub base: Currency,
+    /// Quote currency (the currency of the price).
+    pub quote: Currency,
+}
+
+/// Market priority of currencies as the base of a pair: the currency
+/// appearing first is the base (e.g. EUR/USD, GBP/USD, USD/JPY).
+/// Currencies not listed are quoted against any listed currency.
+const BASE_PRIORITY: [&str; 11] = [
+    "EUR", "GBP", "AUD", "NZD", "USD", "CAD", "CHF", "NOK", "SEK", "DKK", "JPY",
+];
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl CurrencyPair {
+    /// New currency pair, in the given order.
+    pub fn new(base: Currency, quote: Currency) -> Self {
+        assert!(base != quote, "A currency pair needs two currencies.");
+
+        Self { base, quote }
+    }
+
+    /// New currency pair in market order (e.g. EUR/USD rather than USD/EUR).
+    pub fn market(a: Currency, b: Currency) -> Self {
+        let priority = |currency: &Currency| {
+            BASE_PRIORITY
+                .iter()
+                .position(|code| *code == currency.code.alphabetic)
+                .unwrap_or(BASE_PRIORITY.len())
+        };
+
+        if priority(&b) < priority(&a) {
+            Self::new(b, a)
+        } else {
+            Self::new(a, b)
+        }
+    }
+
+    /// Pair with base and quote swapped.
+    pub fn inverse(&self) -> Self {
+        Self::new(self.quote, self.base)
+    }
+
+    /// Whether this pair is in market order.
+    pub fn is_market_order(&self) -> bool {
+        Self::market(self.base, self.quote) == *self
+    }
+
+    /// Pair code without separator, e.g. "EURUSD".
+    pub fn code(&self) -> String {
+        format!(
+            "{}{}",
+            self.base.code.alphabetic, self.quote.code.alphabetic
+        )
+    }
+
+    /// Decimal places of a pip: 2 when the quote currency has no minor
+    /// unit (e.g. USD/JPY), 4 otherwise.
+    pub fn pip_decimals(&self) -> u32 {
+        if self.quote.minor == 0 {
+            2
+        } else {
+            4
+        }
+    }
+
+    /// Size of a pip, e.g. 0.0001 for EUR/USD and 0.01 for USD/JPY.
+    pub fn pip_size(&self) -> f64 {
+        10_f64.powi(-(self.pip_decimals() as i32))
+    }
+
+    /// Number of pips in a rate difference.
+    pub fn pips(&self, rate_difference: f64) -> f64 {
+        rate_difference / self.pip_size()
+    }
+
+    /// Decimal places of quoted rates (one more than a pip).
+    pub fn quote_decimals(&self) -> u32 {
+        self.pip_decimals() + 1
+    }
+
+    /// Rate rounded to the quoting precision of the pair.
+    pub fn round_rate(&self, rate: f64) -> f64 {
+        let factor = 10_f64.powi(self.quote_decimals() as i32);
+
+        (rate * factor).round() / factor
+    }
+
+    /// Spot lag in business days (T+1 or T+2).
+    pub fn spot_lag(&self) -> i64 {
+        fx_spot_lag(&self.base, &self.quote)
+    }
+
+    /// Spot date of a trade in this pair.
+    pub fn spot_date(
+        &self,
+        trade_date: impl IntoEvaluationDate,
+        base_calendar: &dyn Calendar,
+        quote_calendar: &dyn Calendar,
+    ) -> OffsetDateTime {
+        fx_spot_date(
+            trade_date,
+            &self.base,
+            &self.quote,
+            base_calendar,
+            quote_calendar,
+        )
+    }
+
+    /// Rate of the pair from a rate service.
+    pub fn rate(&self, service: &dyn ExchangeRateService) -> Option<f64> {
+        service.rate(&self.base, &self.quote)
+    }
+}
+
+impl fmt::Display for CurrencyPair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{}",
+            self.base.code.alphabetic, self.quote.code.alphabetic
+        )
+    }
+}
+
+impl FromStr for CurrencyPair {
+    type Err = MoneyError;
+
+    /// Parses pairs like "EUR/USD" or "EURUSD" (ISO 4217 codes).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || MoneyError::InvalidCurrencyPair(s.to_string());
+        let pair = s.trim().replace('/', "");
+
+        if pair.len() != 6 || !pair.is_ascii() {
+            return Err(invalid());
+        }
+
+        let base = Currency::from_code(&pair[..3]).ok_or_else(invalid)?;
+        let quote = Currency::from_code(&pair[3..]).ok_or_else(invalid)?;
+
+        if base == quote {
+            return Err(invalid());
+        }
+
+        Ok(Self::new(base, quote))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_currency_pair {
+    use super::*;
+    use crate::money::{ExchangeRate, FxMatrix, CAD, CHF, EUR, GBP, JPY, MXN, USD};
+    use crate::time::{UnitedStates, TARGET};
+    use time::macros::datetime;
+
+    #[test]
+    fn test_market_order() {
+        assert_eq!(CurrencyPair::market(USD, EUR).to_string(), "EUR/USD");
+        assert_eq!(CurrencyPair::market(JPY, USD).to_string(), "USD/JPY");
+        assert_eq!(CurrencyPair::market(GBP, EUR).to_string(), "EUR/GBP");
+        assert_eq!(CurrencyPair::market(MXN, USD).to_string(), "USD/MXN");
+        assert_eq!(CurrencyPair::market(USD, CHF).code(), "USDCHF");
+
+        assert!(!CurrencyPair::new(USD, EUR).is_market_order());
+        assert_eq!(CurrencyPair::new(USD, EUR).inverse().to_string(), "EUR/USD");
+    }
+
+    #[test]
+    fn test_pips_and_precision() {
+        let eur_usd = CurrencyPair::new(EUR, USD);
+        let usd_jpy = CurrencyPair::new(USD, JPY);
+
+        assert_eq!(usd_jpy.pip_size(), 0.01);
+        assert_approx_equal!(eur_usd.pips(1.0850 - 1.0835), 15.0, 1e-9);
+        assert_eq!(eur_usd.round_rate(1.083_456_7), 1.08346);
+        assert_eq!(usd_jpy.round_rate(151.234_56), 151.235);
+    }
+
+    #[test]
+    fn test_settlement_conventions() {
+        assert_eq!(CurrencyPair::new(USD, CAD).spot_lag(), 1);
+
+        let pair = CurrencyPair::new(EUR, USD);
+        assert_eq!(pair.spot_lag(), 2);
+        assert_eq!(
+            pair.spot_date(datetime!(2024-07-02 0:00 UTC), &TARGET, &UnitedStates),
+            datetime!(2024-07-05 0:00 UTC)
+        );
+
+        let mut matrix = FxMatrix::new(USD);
+        matrix.add_rate(ExchangeRate::new(EUR, USD, 1.08));
+        assert_eq!(pair.rate(&matrix), Some(1.08));
+    }
+
+    #[test]
+    fn test_parse_currency_pair() {
+        assert_eq!("EUR/USD".parse(), Ok(CurrencyPair::new(EUR, USD)));
+        assert_eq!("usdjpy".parse(), Ok(CurrencyPair::new(USD, JPY)));
+        assert!("EUR/XYZ".parse::<CurrencyPair>().is_err());
+        assert!("EUR/EUR".parse::<CurrencyPair>().is_err());
+        assert_eq!(
+            "EURO".parse::<CurrencyPair>(),
+            Err(MoneyError::InvalidCurrencyPair("EURO".to_string()))
+        );
+    }
+}
@@ -0,0 +1,406 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Fixed-point decimal numbers for monetary amounts.
+//!
+//! A `Decimal` stores an integer number of 10^-12 units, so sums of amounts
+//! such as 0.1 + 0.2 are exact, unlike `f64`.
+//!
+//! ```
+//! use RustQuant::money::*;
+//!
+//! let x: Decimal = "0.1".parse().unwrap();
+//! let y: Decimal = "0.2".parse().unwrap();
+//!
+//! assert_eq!(x + y, "0.3".parse().unwrap());
+//! assert_eq!((x + y).to_string(), "0.3");
+//! ```
+
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+use thiserror::Error;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Number of decimal places stored by a `Decimal`.
+pub const DECIMAL_PLACES: u32 = 12;
+
+/// 10^DECIMAL_PLACES.
+const SCALE: i128 = 1_000_000_000_000;
+
+/// Fixed-point decimal number with `DECIMAL_PLACES` decimal places.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Decimal {
+    /// Value in units of 10^-12.
+    units: i128,
+}
+
+/// Rounding modes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RoundingMode {
+    /// Round half away from zero (2.5 -> 3, -2.5 -> -3).
+    HalfUp,
+
+    /// Round half to even, a.k.a. banker's rounding (2.5 -> 2, 3.5 -> 4).
+    #[default]
+    HalfEven,
+
+    /// Round towards zero (truncation).
+    Down,
+
+    /// Round away from zero.
+    Up,
+}
+
+/// Decimal parsing error.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum DecimalError {
+    /// The string is not a valid decimal number.
+    #[error("Invalid decimal: {0}")]
+    Invalid(String),
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Decimal {
+    /// Zero.
+    pub const ZERO: Decimal = Decimal { units: 0 };
+
+    /// One.
+    pub const ONE: Decimal = Decimal { units: SCALE };
+
+    /// New decimal equal to `mantissa * 10^-scale`, e.g. `new(12345, 2) = 123.45`.
+    /// Digits beyond `DECIMAL_PLACES` are rounded half to even.
+    pub fn new(mantissa: i128, scale: u32) -> Self {
+        let units = if scale <= DECIMAL_PLACES {
+            mantissa
+                .checked_mul(10_i128.pow(DECIMAL_PLACES - scale))
+                .expect("Decimal overflow.")
+        } else {
+            divide(
+                mantissa,
+                10_i128.pow(scale - DECIMAL_PLACES),
+                RoundingMode::HalfEven,
+            )
+        };
+
+        Self { units }
+    }
+
+    /// Decimal closest to a float (rounded to `DECIMAL_PLACES` places).
+    pub fn from_f64(x: f64) -> Self {
+        assert!(x.is_finite(), "Cannot convert {x} to a decimal.");
+
+        Self {
+            units: (x * SCALE as f64).round() as i128,
+        }
+    }
+
+    /// Converts the decimal to a float.
+    pub fn to_f64(&self) -> f64 {
+        self.units as f64 / SCALE as f64
+    }
+
+    /// Integer part of the decimal (rounded towards zero).
+    pub fn to_i128(&self) -> i128 {
+        self.units / SCALE
+    }
+
+    /// Rounds to `dp` decimal places.
+    pub fn round_dp(&self, dp: u32, mode: RoundingMode) -> Self {
+        if dp >= DECIMAL_PLACES {
+            return *self;
+        }
+
+        let step = 10_i128.pow(DECIMAL_PLACES - dp);
+
+        Self {
+            units: divide(self.units, step, mode) * step,
+        }
+    }
+
+    /// Absolute value.
+    pub fn abs(&self) -> Self {
+        Self {
+            units: self.units.abs(),
+        }
+    }
+
+    /// Whether the decimal is zero.
+    pub fn is_zero(&self) -> bool {
+        self.units == 0
+    }
+
+    /// Whether the decimal is strictly negative.
+    pub fn is_negative(&self) -> bool {
+        self.units < 0
+    }
+}
+
+/// `n / d` rounded with the given mode (`d > 0`).
+fn divide(n: i128, d: i128, mode: RoundingMode) -> i128 {
+    let quotient = n / d;
+    let remainder = n % d;
+
+    if remainder == 0 {
+        return quotient;
+    }
+
+    let away = n.signum();
+    let twice = 2 * remainder.abs();
+
+    let round_away = match mode {
+        RoundingMode::Down => false,
+        RoundingMode::Up => true,
+        RoundingMode::HalfUp => twice >= d,
+        RoundingMode::HalfEven => twice > d || (twice == d && quotient % 2 != 0),
+    };
+
+    if round_away {
+        quotient + away
+    } else {
+        quotient
+    }
+}
+
+impl From<i32> for Decimal {
+    fn from(x: i32) -> Self {
+        Self::from(x as i64)
+    }
+}
+
+impl From<i64> for Decimal {
+    fn from(x: i64) -> Self {
+        Self {
+            units: x as i128 * SCALE,
+        }
+    }
+}
+
+impl From<f64> for Decimal {
+    fn from(x: f64) -> Self {
+        Self::from_f64(x)
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = DecimalError;
+
+    /// Parses decimals like "123", "-0.05" or "1234.5678".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || DecimalError::Invalid(s.to_string());
+        let trimmed = s.trim();
+
+        let (negative, digits) = match trimmed.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+        };
+
+        let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+
+        let is_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+        if (integer.is_empty() && fraction.is_empty())
+            || !is_digits(integer)
+            || !is_digits(fraction)
+        {
+            return Err(invalid());
+        }
+
+        let mantissa = format!("{integer}{fraction}")
+            .parse::<i128>()
+            .map_err(|_| invalid())?;
+        let decimal = Decimal::new(mantissa, fraction.len() as u32);
+
+        Ok(if negative { -decimal } else { decimal })
+    }
+}
+
+impl fmt::Display for Decimal {
+    /// Formats the decimal without trailing zeros, or with exactly
+    /// the requested precision (e.g. `{:.2}`, rounded half to even).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (value, places) = match f.precision() {
+            Some(dp) => {
+                let dp = dp.min(DECIMAL_PLACES as usize);
+                (self.round_dp(dp as u32, RoundingMode::HalfEven), Some(dp))
+            }
+            None => (*self, None),
+        };
+
+        let sign = if value.is_negative() { "-" } else { "" };
+        let integer = value.units.abs() / SCALE;
+        let fraction = format!(
+            "{:0width$}",
+            value.units.abs() % SCALE,
+            width = DECIMAL_PLACES as usize
+        );
+
+        let fraction = match places {
+            Some(dp) => &fraction[..dp],
+            None => fraction.trim_end_matches('0'),
+        };
+
+        if fraction.is_empty() {
+            write!(f, "{sign}{integer}")
+        } else {
+            write!(f, "{sign}{integer}.{fraction}")
+        }
+    }
+}
+
+impl Neg for Decimal {
+    type Output = Decimal;
+
+    fn neg(self) -> Self::Output {
+        Self { units: -self.units }
+    }
+}
+
+impl Add for Decimal {
+    type Output = Decimal;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self {
+            units: self
+                .units
+                .checked_add(other.units)
+                .expect("Decimal overflow."),
+        }
+    }
+}
+
+impl Sub for Decimal {
+    type Output = Decimal;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Self {
+            units: self
+                .units
+                .checked_sub(other.units)
+                .expect("Decimal overflow."),
+        }
+    }
+}
+
+impl Mul for Decimal {
+    type Output = Decimal;
+
+    /// Product, rounded half to even to `DECIMAL_PLACES` places.
+    fn mul(self, other: Self) -> Self::Output {
+        let product = self
+            .units
+            .checked_mul(other.units)
+            .expect("Decimal overflow.");
+
+        Self {
+            units: divide(product, SCALE, RoundingMode::HalfEven),
+        }
+    }
+}
+
+impl Div for Decimal {
+    type Output = Decimal;
+
+    /// Quotient, rounded half to even to `DECIMAL_PLACES` places.
+    fn div(self, other: Self) -> Self::Output {
+        assert!(!other.is_zero(), "Division of a decimal by zero.");
+
+        let numerator = self.units.checked_mul(SCALE).expect("Decimal overflow.");
+        let units = divide(
+            numerator * other.units.signum(),
+            other.units.abs(),
+            RoundingMode::HalfEven,
+        );
+
+        Self { units }
+    }
+}
+
+impl AddAssign for Decimal {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Decimal {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl Sum for Decimal {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Decimal::ZERO, |acc, x| acc + x)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_decimal {
+    use super::*;
+
+    fn dec(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_decimal_parse_and_display() {
+        assert_eq!(dec("123.4500").to_string(), "123.45");
+        assert_eq!(dec("-0.05").to_string(), "-0.05");
+        assert_eq!(dec("+7").to_string(), "7");
+        assert_eq!(dec(".5"), Decimal::new(5, 1));
+        assert_eq!(format!("{:.2}", dec("2.345")), "2.34");
+        assert_eq!(format!("{:.3}", dec("-1")), "-1.000");
+
+        assert!("".parse::<Decimal>().is_err());
+        assert!("1.2.3".parse::<Decimal>().is_err());
+        assert_eq!(
+            "1e5".parse::<Decimal>(),
+            Err(DecimalError::Invalid("1e5".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_decimal_arithmetic() {
+        assert_eq!(dec("0.1") + dec("0.2"), dec("0.3"));
+        assert_eq!(dec("1") - dec("1.5"), dec("-0.5"));
+        assert_eq!(dec("1.5") * dec("-2.25"), dec("-3.375"));
+        assert_eq!(dec("1") / dec("8"), dec("0.125"));
+        assert_eq!(dec("-2") / dec("3"), dec("-0.666666666667"));
+        assert_eq!(
+            vec![dec("0.1"); 10].into_iter().sum::<Decimal>(),
+            Decimal::ONE
+        );
+
+        assert_eq!(Decimal::from_f64(20.5), dec("20.5"));
+        assert_approx_equal!(dec("-1234.5678").to_f64(), -1234.5678, 1e-12);
+    }
+
+    #[test]
+    fn test_decimal_rounding() {
+        let x = dec("2.5");
+        let y = dec("-2.5");
+
+        assert_eq!(x.round_dp(0, RoundingMode::HalfUp), dec("3"));
+        assert_eq!(y.round_dp(0, RoundingMode::HalfUp), dec("-3"));
+        assert_eq!(x.round_dp(0, RoundingMode::HalfEven), dec("2"));
+        assert_eq!(dec("3.5").round_dp(0, RoundingMode::HalfEven), dec("4"));
+        assert_eq!(dec("2.59").round_dp(1, RoundingMode::Down), dec("2.5"));
+        assert_eq!(dec("-2.51").round_dp(1, RoundingMode::Up), dec("-2.6"));
+    }
+}
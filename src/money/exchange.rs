@@ -37,6 +37,49 @@ pub struct ExchangeRate {
     pub rate: f64,
 }
 
+/// FX rates stored against a base currency, with rates between any two
+/// currencies derived by inversion or triangulation through the base.
+///
+/// ```
+/// use RustQuant::money::*;
+/// use RustQuant::assert_approx_equal;
+///
+/// let mut matrix = FxMatrix::new(USD);
+/// matrix.add_rate(ExchangeRate::new(EUR, USD, 1.10)); // 1 EUR = 1.10 USD
+/// matrix.add_rate(ExchangeRate::new(USD, JPY, 150.0)); // 1 USD = 150 JPY
+///
+/// // EUR/JPY is triangulated through USD.
+/// assert_approx_equal!(matrix.rate(&EUR, &JPY).unwrap(), 165.0, 1e-10);
+///
+/// let yen = matrix.convert_money(Money::new(EUR, 100.0), &JPY).unwrap();
+/// assert_approx_equal!(yen.amount(), 16_500.0, 1e-8);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FxMatrix {
+    /// Currency through which rates are triangulated.
+    pub base: Currency,
+    /// Quoted rates, keyed as in `Exchange`, e.g. "EUR/USD".
+    pub rates: HashMap<String, ExchangeRate>,
+}
+
+/// Source of FX rates, e.g. an `Exchange` or an `FxMatrix`.
+pub trait ExchangeRateService {
+    /// Rate converting one unit of `from` into units of `to`, if available.
+    fn rate(&self, from: &Currency, to: &Currency) -> Option<f64>;
+
+    /// Converts money into another currency.
+    fn convert_money(&self, money: Money, to: &Currency) -> Result<Money, MoneyError> {
+        let rate = self
+            .rate(&money.currency, to)
+            .ok_or(MoneyError::MissingRate(
+                money.currency.code.alphabetic,
+                to.code.alphabetic,
+            ))?;
+
+        Ok(Money::new(*to, money.amount * Decimal::from_f64(rate)))
+    }
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // IMPLEMENTATIONS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -132,7 +175,7 @@ impl Exchange {
     /// let eur_85 = exchange.convert(usd_100, EUR); // Should be 85 EUR
     ///
     /// assert_eq!(eur_85.currency, EUR);
-    /// assert_eq!(eur_85.amount, 85.0);
+    /// assert_eq!(eur_85.amount(), 85.0);
     /// ```
     pub fn convert(&self, money: Money, to_currency: Currency) -> Money {
         let rate = self
@@ -170,7 +213,7 @@ impl ExchangeRate {
     /// let eur_usd = ExchangeRate::new(USD, EUR, 0.9186955);  // 1 USD = 0.9186955 EUR
     /// let eur = eur_usd.convert(usd);
     ///
-    /// assert_approx_equal!(eur.amount, 91.86955, 1e-5);
+    /// assert_approx_equal!(eur.amount(), 91.86955, 1e-5);
     /// assert_eq!(eur.currency, EUR);
     /// ```
     ///
@@ -186,7 +229,7 @@ impl ExchangeRate {
     /// ```
     pub fn convert(&self, money: Money) -> Money {
         if money.currency == self.from_currency {
-            let new_amount = money.amount * self.rate;
+            let new_amount = money.amount * Decimal::from_f64(self.rate);
             Money::new(self.to_currency, new_amount)
         } else {
             panic!(
@@ -196,6 +239,85 @@ impl ExchangeRate {
     }
 }
 
+impl ExchangeRate {
+    /// Inverse rate, e.g. EUR/USD from USD/EUR.
+    pub fn inverse(&self) -> Self {
+        Self::new(self.to_currency, self.from_currency, 1.0 / self.rate)
+    }
+}
+
+impl FxMatrix {
+    /// Create a new empty FX matrix with the given base currency.
+    pub fn new(base: Currency) -> Self {
+        Self {
+            base,
+            rates: HashMap::new(),
+        }
+    }
+
+    /// Adds a quoted rate, replacing any previous quote of the same pair.
+    pub fn add_rate(&mut self, rate: ExchangeRate) {
+        let key = format!(
+            "{}/{}",
+            rate.from_currency.code.alphabetic, rate.to_currency.code.alphabetic
+        );
+        self.rates.insert(key, rate);
+    }
+
+    /// Rate between two currencies, or `None` if it can't be derived.
+    ///
+    /// Uses, in order: a quoted rate, the inverse of a quoted rate, and
+    /// the cross rate through the base currency.
+    pub fn rate(&self, from: &Currency, to: &Currency) -> Option<f64> {
+        self.direct_rate(from, to).or_else(|| {
+            let from_base = self.direct_rate(from, &self.base)?;
+            let base_to = self.direct_rate(&self.base, to)?;
+
+            Some(from_base * base_to)
+        })
+    }
+
+    /// Rate between two currencies as an `ExchangeRate`.
+    pub fn exchange_rate(&self, from: &Currency, to: &Currency) -> Option<ExchangeRate> {
+        self.rate(from, to)
+            .map(|rate| ExchangeRate::new(*from, *to, rate))
+    }
+
+    /// Quoted rate or its inverse.
+    fn direct_rate(&self, from: &Currency, to: &Currency) -> Option<f64> {
+        if from == to {
+            return Some(1.0);
+        }
+
+        let key =
+            |a: &Currency, b: &Currency| format!("{}/{}", a.code.alphabetic, b.code.alphabetic);
+
+        self.rates
+            .get(&key(from, to))
+            .map(|rate| rate.rate)
+            .or_else(|| self.rates.get(&key(to, from)).map(|rate| 1.0 / rate.rate))
+    }
+}
+
+impl ExchangeRateService for Exchange {
+    /// Quoted rate, or the inverse of the opposite quote.
+    fn rate(&self, from: &Currency, to: &Currency) -> Option<f64> {
+        if from == to {
+            return Some(1.0);
+        }
+
+        self.get_rate(from, to)
+            .map(|rate| rate.rate)
+            .or_else(|| self.get_rate(to, from).map(|rate| 1.0 / rate.rate))
+    }
+}
+
+impl ExchangeRateService for FxMatrix {
+    fn rate(&self, from: &Currency, to: &Currency) -> Option<f64> {
+        FxMatrix::rate(self, from, to)
+    }
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // UNIT TESTS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -229,7 +351,7 @@ mod test_exchange_rate {
 
         // Verify the conversion
         assert_eq!(eur_85.currency, eur);
-        assert_eq!(eur_85.amount, 85_f64);
+        assert_eq!(eur_85.amount(), 85_f64);
     }
 
     #[test]
@@ -263,6 +385,40 @@ mod test_exchange_rate {
         let eur_85 = exchange.convert(usd_100, EUR); // Should be 85 EUR
 
         assert_eq!(eur_85.currency, EUR);
-        assert_eq!(eur_85.amount, 85.0);
+        assert_eq!(eur_85.amount(), 85.0);
+    }
+
+    #[test]
+    fn test_fx_matrix_triangulation() {
+        let mut matrix = FxMatrix::new(USD);
+        matrix.add_rate(ExchangeRate::new(EUR, USD, 1.25));
+        matrix.add_rate(ExchangeRate::new(GBP, USD, 1.5));
+        matrix.add_rate(ExchangeRate::new(USD, JPY, 100.0));
+
+        assert_eq!(matrix.rate(&USD, &USD), Some(1.0));
+        assert_eq!(matrix.rate(&USD, &EUR), Some(0.8));
+        assert_approx_equal!(matrix.rate(&EUR, &GBP).unwrap(), 1.25 / 1.5, 1e-12);
+        assert_approx_equal!(matrix.rate(&JPY, &GBP).unwrap(), 1.0 / 150.0, 1e-12);
+        assert_eq!(matrix.rate(&EUR, &CHF), None);
+
+        let gbp_eur = matrix.exchange_rate(&GBP, &EUR).unwrap();
+        assert_approx_equal!(gbp_eur.inverse().rate, 1.25 / 1.5, 1e-12);
+        assert_eq!(gbp_eur.inverse().to_currency, GBP);
+    }
+
+    #[test]
+    fn test_exchange_rate_service() {
+        let mut exchange = Exchange::new();
+        exchange.add_rate(ExchangeRate::new(USD, EUR, 0.8));
+
+        let services: [&dyn ExchangeRateService; 2] = [&exchange, &FxMatrix::new(USD)];
+
+        let eur = services[0].convert_money(Money::new(EUR, 100.0), &USD);
+        assert_eq!(eur, Ok(Money::new(USD, 125.0)));
+
+        assert_eq!(
+            services[1].convert_money(Money::new(EUR, 100.0), &USD),
+            Err(MoneyError::MissingRate("EUR", "USD"))
+        );
     }
 }
@@ -1911,3 +1911,16 @@ pub const ZWL: Currency = Currency {
     minor: 2,
     fractions: 100,
 };
+
+/// All ISO 4217 currencies defined in this module.
+pub const ISO_CURRENCIES: [Currency; 158] = [
+    AED, AFN, ALL, AMD, ANG, AOA, ARS, AUD, AWG, AZN, BAM, BBD, BDT, BGN, BHD, BIF, BMD, BND, BOB,
+    BRL, BSD, BTN, BWP, BYN, BZD, CAD, CDF, CHF, CLP, COP, CRC, CUC, CUP, CVE, CZK, DJF, DKK, DOP,
+    DZD, EGP, ERN, ETB, EUR, FJD, FKP, GBP, GEL, GHS, GIP, GMD, GNF, GTQ, GYD, HKD, HNL, HRK, HTG,
+    HUF, IDR, ILS, INR, IQD, IRR, ISK, JMD, JOD, JPY, KES, KGS, KHR, KMF, KPW, KRW, KWD, KYD, KZT,
+    LAK, LBP, LKR, LRD, LSL, LYD, MAD, MDL, MGA, MKD, MMK, MNT, MOP, MRO, MUR, MVR, MWK, MXN, MYR,
+    MZN, NAD, NGN, NIO, NOK, NPR, NZD, OMR, PAB, PEN, PGK, PHP, PKR, PLN, PYG, QAR, RON, RSD, CNY,
+    RUB, RWF, SAR, SBD, SCR, SDG, SEK, SGD, SHP, SLE, SLL, SOS, SRD, SSP, STN, SVC, SYP, SZL, THB,
+    TJS, TMT, TND, TOP, TRY, TTD, TWD, TZS, UAH, UGX, USD, UYU, UZS, VES, VND, VUV, WST, XAF, XCD,
+    XOF, XPF, YER, ZAR, ZMW, ZWL,
+];
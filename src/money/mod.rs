@@ -23,6 +23,14 @@ pub use cashflows::*;
 pub mod currency;
 pub use currency::*;
 
+/// Currency pairs and FX quoting conventions.
+pub mod currency_pair;
+pub use currency_pair::*;
+
+/// Fixed-point decimal amounts.
+pub mod decimal;
+pub use decimal::*;
+
 /// Currency exchange rate helpers.
 pub mod exchange;
 pub use exchange::*;
@@ -31,6 +39,10 @@ pub use exchange::*;
 pub mod iso_currencies;
 pub use iso_currencies::*;
 
+/// Rounding rules for cash amounts.
+pub mod rounding;
+pub use rounding::*;
+
 /// Legs (sequence of cashflows).
 pub mod legs;
 pub use legs::*;
@@ -0,0 +1,113 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Rounding rules for cash amounts.
+//!
+//! Actual payments are rounded amounts, e.g. a coupon of 1234.5678 USD
+//! is paid as 1234.57 USD.
+//!
+//! ```
+//! use RustQuant::money::*;
+//!
+//! assert_eq!(Rounding::HalfUp(2).round(2.345, None), 2.35);
+//! assert_eq!(Rounding::HalfEven(2).round(2.345, None), 2.34);
+//!
+//! // Rounding to the minor units of the currency.
+//! assert_eq!(Rounding::MinorUnits.round(1234.5678, Some(&JPY)), 1235.0);
+//! assert_eq!(
+//!     Rounding::MinorUnits.round_money(Money::new(KWD, 1.23456)),
+//!     Money::new(KWD, 1.235)
+//! );
+//! ```
+
+use crate::money::{Currency, Decimal, Money, RoundingMode};
+
+/// Rounding rule for amounts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rounding {
+    /// No rounding.
+    #[default]
+    None,
+
+    /// Round half away from zero to the given number of decimals.
+    HalfUp(u32),
+
+    /// Round half to even to the given number of decimals.
+    HalfEven(u32),
+
+    /// Round towards zero to the given number of decimals.
+    Down(u32),
+
+    /// Round half to even to the minor units of the currency (e.g. cents).
+    MinorUnits,
+}
+
+impl Rounding {
+    /// Rounds a decimal amount. `MinorUnits` leaves the amount
+    /// unchanged if no currency is given.
+    pub fn round_decimal(&self, amount: Decimal, currency: Option<&Currency>) -> Decimal {
+        match (*self, currency) {
+            (Rounding::None, _) | (Rounding::MinorUnits, None) => amount,
+            (Rounding::HalfUp(dp), _) => amount.round_dp(dp, RoundingMode::HalfUp),
+            (Rounding::HalfEven(dp), _) => amount.round_dp(dp, RoundingMode::HalfEven),
+            (Rounding::Down(dp), _) => amount.round_dp(dp, RoundingMode::Down),
+            (Rounding::MinorUnits, Some(currency)) => {
+                amount.round_dp(currency.minor as u32, RoundingMode::HalfEven)
+            }
+        }
+    }
+
+    /// Rounds an amount (in the given currency, if any).
+    pub fn round(&self, amount: f64, currency: Option<&Currency>) -> f64 {
+        match self {
+            Rounding::None => amount,
+            _ => self
+                .round_decimal(Decimal::from_f64(amount), currency)
+                .to_f64(),
+        }
+    }
+
+    /// Rounds money (minor units are those of its currency).
+    pub fn round_money(&self, money: Money) -> Money {
+        Money::new(
+            money.currency,
+            self.round_decimal(money.amount, Some(&money.currency)),
+        )
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_rounding {
+    use super::*;
+    use crate::money::{BHD, USD};
+
+    #[test]
+    fn test_rounding() {
+        assert_eq!(Rounding::None.round(1.23456, Some(&USD)), 1.23456);
+        assert_eq!(Rounding::HalfUp(0).round(-2.5, None), -3.0);
+        assert_eq!(Rounding::HalfEven(0).round(-2.5, None), -2.0);
+        assert_eq!(Rounding::Down(3).round(1.23456, None), 1.234);
+        assert_eq!(Rounding::MinorUnits.round(1.23456, None), 1.23456);
+        assert_eq!(Rounding::MinorUnits.round(1.23456, Some(&USD)), 1.23);
+        assert_eq!(Rounding::MinorUnits.round(1.23456, Some(&BHD)), 1.235);
+
+        assert_eq!(
+            Money::new(USD, 10.005).round_with(Rounding::MinorUnits),
+            Money::new(USD, 10.0)
+        );
+        assert_eq!(
+            Money::new(USD, 10.005).round_with(Rounding::HalfUp(2)),
+            Money::new(USD, 10.01)
+        );
+    }
+}
@@ -0,0 +1,241 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::performance::PerformanceError;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Weights and returns of one segment (sector, region, asset class, ...)
+/// of the portfolio and the benchmark over a period.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    /// Segment name.
+    pub name: String,
+
+    /// Weight of the segment in the portfolio.
+    pub portfolio_weight: f64,
+
+    /// Weight of the segment in the benchmark.
+    pub benchmark_weight: f64,
+
+    /// Return of the portfolio's holdings in the segment.
+    pub portfolio_return: f64,
+
+    /// Return of the benchmark's holdings in the segment.
+    pub benchmark_return: f64,
+}
+
+/// Attribution of the active return of one segment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SegmentAttribution {
+    /// Segment name.
+    pub name: String,
+
+    /// Allocation effect, $(w_i - b_i)(r^b_i - R^b)$.
+    pub allocation: f64,
+
+    /// Selection effect, $b_i (r_i - r^b_i)$.
+    pub selection: f64,
+
+    /// Interaction effect, $(w_i - b_i)(r_i - r^b_i)$.
+    pub interaction: f64,
+}
+
+/// Brinson-Fachler (1985) attribution of the active return of a portfolio
+/// against a benchmark into allocation, selection and interaction effects.
+///
+/// The effects of all segments add up to the active return $R - R^b$.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BrinsonAttribution {
+    /// Attribution by segment.
+    pub segments: Vec<SegmentAttribution>,
+
+    /// Total portfolio return, $R = \sum_i w_i r_i$.
+    pub portfolio_return: f64,
+
+    /// Total benchmark return, $R^b = \sum_i b_i r^b_i$.
+    pub benchmark_return: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Segment {
+    /// New segment.
+    pub fn new(
+        name: &str,
+        portfolio_weight: f64,
+        benchmark_weight: f64,
+        portfolio_return: f64,
+        benchmark_return: f64,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            portfolio_weight,
+            benchmark_weight,
+            portfolio_return,
+            benchmark_return,
+        }
+    }
+}
+
+impl SegmentAttribution {
+    /// Total contribution of the segment to the active return.
+    pub fn total(&self) -> f64 {
+        self.allocation + self.selection + self.interaction
+    }
+}
+
+impl BrinsonAttribution {
+    /// Attribution of the segments. The portfolio and benchmark weights
+    /// must each add up to one.
+    ///
+    /// ```
+    /// use RustQuant::performance::*;
+    /// use RustQuant::assert_approx_equal;
+    ///
+    /// let attribution = BrinsonAttribution::new(&[
+    ///     Segment::new("equities", 0.7, 0.6, 0.10, 0.08),
+    ///     Segment::new("bonds", 0.3, 0.4, 0.02, 0.03),
+    /// ])
+    /// .unwrap();
+    ///
+    /// assert_approx_equal!(attribution.active_return(), 0.076 - 0.06, 1e-12);
+    /// ```
+    pub fn new(segments: &[Segment]) -> Result<Self, PerformanceError> {
+        if segments.is_empty() {
+            return Err(PerformanceError::InsufficientData(0));
+        }
+
+        for (weights, side) in [
+            (
+                segments.iter().map(|s| s.portfolio_weight).sum::<f64>(),
+                "portfolio",
+            ),
+            (
+                segments.iter().map(|s| s.benchmark_weight).sum::<f64>(),
+                "benchmark",
+            ),
+        ] {
+            if (weights - 1.0).abs() > 1e-8 {
+                return Err(PerformanceError::InvalidWeights(format!(
+                    "{side} weights add up to {weights}"
+                )));
+            }
+        }
+
+        let portfolio_return = segments
+            .iter()
+            .map(|s| s.portfolio_weight * s.portfolio_return)
+            .sum();
+        let benchmark_return: f64 = segments
+            .iter()
+            .map(|s| s.benchmark_weight * s.benchmark_return)
+            .sum();
+
+        let segments = segments
+            .iter()
+            .map(|s| {
+                let active_weight = s.portfolio_weight - s.benchmark_weight;
+                let active_return = s.portfolio_return - s.benchmark_return;
+
+                SegmentAttribution {
+                    name: s.name.clone(),
+                    allocation: active_weight * (s.benchmark_return - benchmark_return),
+                    selection: s.benchmark_weight * active_return,
+                    interaction: active_weight * active_return,
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            segments,
+            portfolio_return,
+            benchmark_return,
+        })
+    }
+
+    /// Active return of the portfolio, $R - R^b$.
+    pub fn active_return(&self) -> f64 {
+        self.portfolio_return - self.benchmark_return
+    }
+
+    /// Total allocation effect.
+    pub fn allocation(&self) -> f64 {
+        self.segments.iter().map(|s| s.allocation).sum()
+    }
+
+    /// Total selection effect.
+    pub fn selection(&self) -> f64 {
+        self.segments.iter().map(|s| s.selection).sum()
+    }
+
+    /// Total interaction effect.
+    pub fn interaction(&self) -> f64 {
+        self.segments.iter().map(|s| s.interaction).sum()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_attribution {
+    use super::*;
+
+    #[test]
+    fn test_brinson_fachler() {
+        let attribution = BrinsonAttribution::new(&[
+            Segment::new("equities", 0.5, 0.4, 0.12, 0.10),
+            Segment::new("credit", 0.3, 0.3, 0.05, 0.06),
+            Segment::new("rates", 0.2, 0.3, 0.01, 0.02),
+        ])
+        .unwrap();
+
+        // R = 0.077, R^b = 0.064.
+        assert_approx_equal!(attribution.portfolio_return, 0.077, 1e-12);
+        assert_approx_equal!(attribution.benchmark_return, 0.064, 1e-12);
+
+        let equities = &attribution.segments[0];
+        assert_approx_equal!(equities.allocation, 0.1 * (0.10 - 0.064), 1e-12);
+        assert_approx_equal!(equities.selection, 0.4 * 0.02, 1e-12);
+        assert_approx_equal!(equities.interaction, 0.1 * 0.02, 1e-12);
+
+        // No active weight in credit: no allocation or interaction effect.
+        assert_eq!(attribution.segments[1].allocation, 0.0);
+        assert_eq!(attribution.segments[1].interaction, 0.0);
+
+        // The effects add up to the active return.
+        assert_approx_equal!(
+            attribution.allocation() + attribution.selection() + attribution.interaction(),
+            attribution.active_return(),
+            1e-12
+        );
+        assert_approx_equal!(
+            attribution.segments.iter().map(|s| s.total()).sum::<f64>(),
+            0.013,
+            1e-12
+        );
+    }
+
+    #[test]
+    fn test_brinson_invalid_weights() {
+        let result = BrinsonAttribution::new(&[
+            Segment::new("equities", 0.5, 0.6, 0.12, 0.10),
+            Segment::new("credit", 0.3, 0.4, 0.05, 0.06),
+        ]);
+
+        assert!(matches!(result, Err(PerformanceError::InvalidWeights(_))));
+        assert!(BrinsonAttribution::new(&[]).is_err());
+    }
+}
@@ -0,0 +1,191 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::performance::PerformanceError;
+use crate::statistics::time_series::stationarity::ols;
+use nalgebra::{DMatrix, DVector};
+
+/// Time-series regression of (excess) returns on factor returns,
+/// $r_t = \alpha + \sum_k \beta_k f_{k,t} + \varepsilon_t$, e.g. on the
+/// Fama-French factors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FactorRegression {
+    /// Intercept (per period).
+    pub alpha: f64,
+
+    /// Factor loadings, one per factor.
+    pub betas: Vec<f64>,
+
+    /// t-statistic of the intercept.
+    pub alpha_t_stat: f64,
+
+    /// t-statistics of the factor loadings.
+    pub beta_t_stats: Vec<f64>,
+
+    /// Coefficient of determination.
+    pub r_squared: f64,
+
+    /// Standard deviation of the residuals (per period).
+    pub residual_volatility: f64,
+}
+
+impl FactorRegression {
+    /// Ordinary least squares fit of the returns on the factors (one series
+    /// per factor, each of the same length as the returns).
+    ///
+    /// ```
+    /// use RustQuant::performance::*;
+    /// use RustQuant::assert_approx_equal;
+    ///
+    /// let market = vec![0.01, -0.02, 0.015, 0.005, -0.01, 0.02];
+    /// let returns: Vec<f64> = market.iter().map(|m| 0.001 + 1.2 * m).collect();
+    ///
+    /// let fit = FactorRegression::fit(&returns, &[market]).unwrap();
+    ///
+    /// assert_approx_equal!(fit.betas[0], 1.2, 1e-12);
+    /// ```
+    pub fn fit(returns: &[f64], factors: &[Vec<f64>]) -> Result<Self, PerformanceError> {
+        let n = returns.len();
+
+        if factors.iter().any(|factor| factor.len() != n) {
+            return Err(PerformanceError::InsufficientData(
+                factors.iter().map(|factor| factor.len()).min().unwrap_or(0),
+            ));
+        }
+
+        let y = DVector::from_column_slice(returns);
+        let x = DMatrix::from_fn(n, factors.len() + 1, |t, k| match k {
+            0 => 1.0,
+            k => factors[k - 1][t],
+        });
+
+        let fit = ols(&y, &x)?;
+
+        let mean = y.mean();
+        let total = y.iter().map(|r| (r - mean).powi(2)).sum::<f64>();
+        let residual = fit.residuals.norm_squared();
+        let t_stats: Vec<f64> = fit
+            .coefficients
+            .iter()
+            .zip(fit.standard_errors.iter())
+            .map(|(b, se)| b / se)
+            .collect();
+
+        Ok(Self {
+            alpha: fit.coefficients[0],
+            betas: fit.coefficients.iter().skip(1).copied().collect(),
+            alpha_t_stat: t_stats[0],
+            beta_t_stats: t_stats[1..].to_vec(),
+            r_squared: match total > 0.0 {
+                true => 1.0 - residual / total,
+                false => 1.0,
+            },
+            residual_volatility: (residual / (n - factors.len() - 1) as f64).sqrt(),
+        })
+    }
+
+    /// Annualised intercept.
+    pub fn annualized_alpha(&self, periods_per_year: f64) -> f64 {
+        self.alpha * periods_per_year
+    }
+}
+
+/// Factor regressions over each window of `window` consecutive periods.
+///
+/// The `i`-th fit uses periods `i..i + window`, so there are
+/// `n - window + 1` fits (none if the window is longer than the series).
+pub fn rolling_factor_regression(
+    returns: &[f64],
+    factors: &[Vec<f64>],
+    window: usize,
+) -> Result<Vec<FactorRegression>, PerformanceError> {
+    assert!(window > 0, "Window must be positive.");
+
+    (0..(returns.len() + 1).saturating_sub(window))
+        .map(|i| {
+            let window_factors: Vec<Vec<f64>> = factors
+                .iter()
+                .map(|factor| factor.get(i..i + window).unwrap_or_default().to_vec())
+                .collect();
+
+            FactorRegression::fit(&returns[i..i + window], &window_factors)
+        })
+        .collect()
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_factor_regression {
+    use super::*;
+
+    fn factors() -> Vec<Vec<f64>> {
+        vec![
+            vec![
+                0.01, -0.02, 0.015, 0.005, -0.01, 0.02, 0.0, -0.005, 0.012, 0.003,
+            ],
+            vec![
+                0.002, 0.004, -0.003, 0.001, 0.0, -0.002, 0.005, 0.001, -0.004, 0.002,
+            ],
+        ]
+    }
+
+    #[test]
+    fn test_factor_regression() {
+        let factors = factors();
+        let noise = [
+            0.001, -0.001, 0.0005, 0.0, -0.0005, 0.001, -0.001, 0.0, 0.0005, -0.0005,
+        ];
+        let returns: Vec<f64> = (0..10)
+            .map(|t| 0.0002 + 1.1 * factors[0][t] - 0.4 * factors[1][t] + noise[t])
+            .collect();
+
+        let fit = FactorRegression::fit(&returns, &factors).unwrap();
+
+        assert_approx_equal!(fit.betas[0], 1.1, 0.05);
+        assert_approx_equal!(fit.betas[1], -0.4, 0.2);
+        assert!(fit.r_squared > 0.98 && fit.r_squared < 1.0);
+        assert!(fit.beta_t_stats[0] > 10.0);
+        assert_approx_equal!(fit.annualized_alpha(252.0), 252.0 * fit.alpha, 1e-12);
+
+        // Exact fit without noise.
+        let exact: Vec<f64> = (0..10)
+            .map(|t| 0.0002 + 1.1 * factors[0][t] - 0.4 * factors[1][t])
+            .collect();
+        let fit = FactorRegression::fit(&exact, &factors).unwrap();
+        assert_approx_equal!(fit.alpha, 0.0002, 1e-12);
+        assert_approx_equal!(fit.r_squared, 1.0, 1e-12);
+
+        assert!(FactorRegression::fit(&exact[..3], &factors).is_err());
+    }
+
+    #[test]
+    fn test_rolling_factor_regression() {
+        let factors = factors();
+
+        // The loading on the first factor changes halfway.
+        let returns: Vec<f64> = (0..10)
+            .map(|t| match t < 5 {
+                true => 0.5 * factors[0][t],
+                false => 1.5 * factors[0][t],
+            })
+            .collect();
+
+        let fits = rolling_factor_regression(&returns, &factors[..1], 4).unwrap();
+
+        assert_eq!(fits.len(), 7);
+        assert_approx_equal!(fits[0].betas[0], 0.5, 1e-10);
+        assert_approx_equal!(fits[6].betas[0], 1.5, 1e-10);
+        assert!(rolling_factor_regression(&returns, &factors, 20)
+            .unwrap()
+            .is_empty());
+    }
+}
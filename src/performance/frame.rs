@@ -0,0 +1,433 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::data::DataError;
+use crate::performance::{
+    rolling_max_drawdown, rolling_return, rolling_sharpe_ratio, rolling_sortino_ratio,
+    rolling_volatility, BrinsonAttribution, FactorRegression, PerformanceError,
+    PerformanceStatistics,
+};
+use polars::prelude::*;
+
+/// Columns of `compute_returns` output that are not returns.
+const NON_RETURN_COLUMNS: [&str; 2] = ["date", "volume"];
+
+impl PerformanceStatistics {
+    /// Statistics of a returns series, such as a column of the output of
+    /// `YahooFinanceData::compute_returns`. Nulls are skipped, and
+    /// logarithmic returns (column names ending in `_logarithmic`) are
+    /// converted to simple returns. Absolute returns are not supported.
+    pub fn from_series(series: &Series, periods_per_year: f64) -> Result<Self, PerformanceError> {
+        Self::new(&simple_returns(series)?, periods_per_year)
+    }
+}
+
+impl BrinsonAttribution {
+    /// Attribution table with columns `segment`, `allocation`, `selection`,
+    /// `interaction` and `total`, one row per segment and a final `total` row.
+    pub fn to_dataframe(&self) -> Result<DataFrame, PerformanceError> {
+        let column = |name: &str, f: fn(&crate::performance::SegmentAttribution) -> f64, total| {
+            Series::new(
+                name,
+                self.segments
+                    .iter()
+                    .map(f)
+                    .chain(std::iter::once(total))
+                    .collect::<Vec<f64>>(),
+            )
+        };
+
+        let segments = self
+            .segments
+            .iter()
+            .map(|s| s.name.as_str())
+            .chain(std::iter::once("total"))
+            .collect::<Vec<&str>>();
+
+        Ok(DataFrame::new(vec![
+            Series::new("segment", segments),
+            column("allocation", |s| s.allocation, self.allocation()),
+            column("selection", |s| s.selection, self.selection()),
+            column("interaction", |s| s.interaction, self.interaction()),
+            column("total", |s| s.total(), self.active_return()),
+        ])
+        .map_err(DataError::from)?)
+    }
+}
+
+/// Simple returns of a returns series, without nulls.
+pub fn simple_returns(series: &Series) -> Result<Vec<f64>, PerformanceError> {
+    Ok(optional_simple_returns(series)?
+        .into_iter()
+        .flatten()
+        .collect())
+}
+
+/// Simple returns of a returns series, keeping nulls in place.
+fn optional_simple_returns(series: &Series) -> Result<Vec<Option<f64>>, PerformanceError> {
+    let name = series.name();
+
+    if name.ends_with("_absolute") {
+        return Err(PerformanceError::UnsupportedReturns(name.to_string()));
+    }
+
+    let values = series
+        .cast(&DataType::Float64)
+        .map_err(DataError::from)?
+        .f64()
+        .map_err(DataError::from)?
+        .into_iter()
+        .collect::<Vec<Option<f64>>>();
+
+    Ok(match name.ends_with("_logarithmic") {
+        true => values.into_iter().map(|r| r.map(f64::exp_m1)).collect(),
+        false => values,
+    })
+}
+
+/// Rolling regression of a returns series on the factor columns of a data
+/// frame (e.g. the Fama-French factors read with `Data`), over `window`
+/// periods.
+///
+/// All columns of `factors` except `date` and the optional risk-free rate
+/// column are factors, which must be in the same units as the returns
+/// (the Fama-French files are in percent). With a risk-free column, the
+/// regression is on the excess returns.
+///
+/// The output has one row per row of the series, with columns `alpha`,
+/// `beta_<factor>` for each factor and `r_squared`: the fit over the window
+/// ending at that row, or null unless the window has no missing values.
+///
+/// ```ignore
+/// use RustQuant::data::*;
+/// use RustQuant::performance::*;
+///
+/// let mut yfd = YahooFinanceData::new("AAPL".to_string());
+/// yfd.compute_returns(ReturnsType::Arithmetic).unwrap();
+///
+/// // Factors saved as decimal (not percent) returns.
+/// let mut factors = Data::new(DataFormat::CSV, "./F-F_Research_Data_Factors.csv".to_string());
+/// factors.read().unwrap();
+///
+/// let fits = rolling_factor_regression_table(
+///     yfd.returns.as_ref().unwrap().column("adjusted_arithmetic").unwrap(),
+///     &factors.data,
+///     Some("RF"),
+///     36,
+/// )
+/// .unwrap();
+/// ```
+pub fn rolling_factor_regression_table(
+    returns: &Series,
+    factors: &DataFrame,
+    risk_free: Option<&str>,
+    window: usize,
+) -> Result<DataFrame, PerformanceError> {
+    assert!(window > 0, "Window must be positive.");
+
+    if factors.height() != returns.len() {
+        return Err(PerformanceError::InsufficientData(
+            factors.height().min(returns.len()),
+        ));
+    }
+
+    let column_values = |series: &Series| -> Result<Vec<Option<f64>>, PerformanceError> {
+        Ok(series
+            .cast(&DataType::Float64)
+            .map_err(DataError::from)?
+            .f64()
+            .map_err(DataError::from)?
+            .into_iter()
+            .collect())
+    };
+
+    let mut y = optional_simple_returns(returns)?;
+    if let Some(name) = risk_free {
+        let rates = column_values(factors.column(name).map_err(DataError::from)?)?;
+        y = y
+            .into_iter()
+            .zip(rates)
+            .map(|(r, rf)| Some(r? - rf?))
+            .collect();
+    }
+
+    let mut names = Vec::new();
+    let mut x = Vec::new();
+    for series in factors.get_columns() {
+        if series.name() != "date" && Some(series.name()) != risk_free {
+            names.push(series.name().to_string());
+            x.push(column_values(series)?);
+        }
+    }
+
+    let fits = (0..y.len())
+        .map(|t| {
+            let start = match (t + 1).checked_sub(window) {
+                Some(start) => start,
+                None => return Ok(None),
+            };
+            let window_returns: Option<Vec<f64>> = y[start..=t].iter().copied().collect();
+            let window_factors: Option<Vec<Vec<f64>>> = x
+                .iter()
+                .map(|factor| factor[start..=t].iter().copied().collect())
+                .collect();
+
+            match (window_returns, window_factors) {
+                (Some(r), Some(f)) => FactorRegression::fit(&r, &f).map(Some),
+                _ => Ok(None),
+            }
+        })
+        .collect::<Result<Vec<Option<FactorRegression>>, PerformanceError>>()?;
+
+    let column = |name: &str, f: &dyn Fn(&FactorRegression) -> f64| {
+        Series::new(
+            name,
+            fits.iter()
+                .map(|fit| fit.as_ref().map(f))
+                .collect::<Vec<Option<f64>>>(),
+        )
+    };
+
+    let mut columns = vec![column("alpha", &|fit| fit.alpha)];
+    for (k, name) in names.iter().enumerate() {
+        columns.push(column(&format!("beta_{name}"), &|fit| fit.betas[k]));
+    }
+    columns.push(column("r_squared", &|fit| fit.r_squared));
+
+    Ok(DataFrame::new(columns).map_err(DataError::from)?)
+}
+
+/// Performance statistics of each returns column of a data frame (all
+/// columns except `date` and `volume`), one row per column.
+pub fn performance_table(
+    frame: &DataFrame,
+    periods_per_year: f64,
+) -> Result<DataFrame, PerformanceError> {
+    let mut names = Vec::new();
+    let mut stats = Vec::new();
+
+    for series in frame.get_columns() {
+        if NON_RETURN_COLUMNS.contains(&series.name()) {
+            continue;
+        }
+
+        names.push(series.name().to_string());
+        stats.push(PerformanceStatistics::from_series(
+            series,
+            periods_per_year,
+        )?);
+    }
+
+    let column = |name: &str, f: fn(&PerformanceStatistics) -> f64| {
+        Series::new(name, stats.iter().map(f).collect::<Vec<f64>>())
+    };
+
+    Ok(DataFrame::new(vec![
+        Series::new("series", names),
+        column("annualized_return", |s| s.annualized_return),
+        column("annualized_volatility", |s| s.annualized_volatility),
+        column("sharpe_ratio", |s| s.sharpe_ratio),
+        column("sortino_ratio", |s| s.sortino_ratio),
+        column("calmar_ratio", |s| s.calmar_ratio),
+        column("omega_ratio", |s| s.omega_ratio),
+        column("max_drawdown", |s| s.max_drawdown),
+        Series::new(
+            "max_drawdown_duration",
+            stats
+                .iter()
+                .map(|s| s.max_drawdown_duration as u64)
+                .collect::<Vec<u64>>(),
+        ),
+        column("skewness", |s| s.skewness),
+        column("kurtosis", |s| s.kurtosis),
+    ])
+    .map_err(DataError::from)?)
+}
+
+/// Rolling return, volatility, Sharpe and Sortino ratios and maximum
+/// drawdown of a returns series over `window` periods.
+///
+/// The output has one row per row of the series: the statistic of the
+/// window ending at that row, or null until a full window of (non-null)
+/// returns is available.
+pub fn rolling_statistics(
+    series: &Series,
+    window: usize,
+    periods_per_year: f64,
+) -> Result<DataFrame, PerformanceError> {
+    let returns = simple_returns(series)?;
+
+    let column = |name: &str, values: Vec<f64>| {
+        let padding = series.len() - values.len();
+
+        Series::new(
+            name,
+            std::iter::repeat_n(None, padding)
+                .chain(values.into_iter().map(Some))
+                .collect::<Vec<Option<f64>>>(),
+        )
+    };
+
+    Ok(DataFrame::new(vec![
+        column(
+            "rolling_return",
+            rolling_return(&returns, window, periods_per_year),
+        ),
+        column(
+            "rolling_volatility",
+            rolling_volatility(&returns, window, periods_per_year),
+        ),
+        column(
+            "rolling_sharpe_ratio",
+            rolling_sharpe_ratio(&returns, window, periods_per_year),
+        ),
+        column(
+            "rolling_sortino_ratio",
+            rolling_sortino_ratio(&returns, window, periods_per_year),
+        ),
+        column(
+            "rolling_max_drawdown",
+            rolling_max_drawdown(&returns, window),
+        ),
+    ])
+    .map_err(DataError::from)?)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_frame {
+    use super::*;
+    use crate::performance::max_drawdown;
+
+    fn returns() -> DataFrame {
+        df!(
+            "date" => [1, 2, 3, 4, 5, 6],
+            "volume" => [1e6, 1e6, 1e6, 1e6, 1e6, 1e6],
+            "close_arithmetic" => [None, Some(0.1), Some(-0.1), Some(0.05), Some(-0.2), Some(0.3)],
+            "close_logarithmic" => [None, Some(0.1), Some(-0.1), Some(0.05), Some(-0.2), Some(0.3)]
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_performance_table() {
+        let table = performance_table(&returns(), 252.0).unwrap();
+
+        assert_eq!(table.shape(), (2, 11));
+
+        let drawdowns = table.column("max_drawdown").unwrap().f64().unwrap();
+        assert_approx_equal!(
+            drawdowns.get(0).unwrap(),
+            max_drawdown(&[0.1, -0.1, 0.05, -0.2, 0.3]),
+            1e-12
+        );
+
+        // Log returns are converted to simple returns before compounding.
+        assert_approx_equal!(drawdowns.get(1).unwrap(), 1.0 - (-0.25_f64).exp(), 1e-12);
+    }
+
+    #[test]
+    fn test_rolling_statistics() {
+        let frame = returns();
+        let rolling =
+            rolling_statistics(frame.column("close_arithmetic").unwrap(), 3, 252.0).unwrap();
+
+        assert_eq!(rolling.shape(), (6, 5));
+
+        let drawdowns = rolling
+            .column("rolling_max_drawdown")
+            .unwrap()
+            .f64()
+            .unwrap();
+        assert_eq!(drawdowns.null_count(), 3);
+        assert_approx_equal!(drawdowns.get(5).unwrap(), 0.2, 1e-12);
+    }
+
+    #[test]
+    fn test_brinson_attribution_table() {
+        let attribution = BrinsonAttribution::new(&[
+            crate::performance::Segment::new("equities", 0.7, 0.6, 0.10, 0.08),
+            crate::performance::Segment::new("bonds", 0.3, 0.4, 0.02, 0.03),
+        ])
+        .unwrap();
+        let table = attribution.to_dataframe().unwrap();
+
+        assert_eq!(table.shape(), (3, 5));
+
+        let total = table.column("total").unwrap().f64().unwrap();
+        assert_approx_equal!(total.get(2).unwrap(), attribution.active_return(), 1e-12);
+        assert_approx_equal!(
+            total.get(0).unwrap() + total.get(1).unwrap(),
+            total.get(2).unwrap(),
+            1e-12
+        );
+    }
+
+    #[test]
+    fn test_rolling_factor_regression_table() {
+        let factors = df!(
+            "date" => [1, 2, 3, 4, 5, 6, 7],
+            "Mkt-RF" => [0.01, -0.02, 0.015, 0.005, -0.01, 0.02, 0.0],
+            "SMB" => [0.002, 0.004, -0.003, 0.001, 0.0, -0.002, 0.005],
+            "RF" => [0.001; 7]
+        )
+        .unwrap();
+
+        // Excess returns 0.5 * market + 0.2 * size.
+        let returns: Vec<Option<f64>> = (0..7)
+            .map(|t| match t {
+                0 => None,
+                t => Some(
+                    0.001
+                        + 0.5
+                            * factors
+                                .column("Mkt-RF")
+                                .unwrap()
+                                .f64()
+                                .unwrap()
+                                .get(t)
+                                .unwrap()
+                        + 0.2
+                            * factors
+                                .column("SMB")
+                                .unwrap()
+                                .f64()
+                                .unwrap()
+                                .get(t)
+                                .unwrap(),
+                ),
+            })
+            .collect();
+        let returns = Series::new("close_arithmetic", returns);
+
+        let table = rolling_factor_regression_table(&returns, &factors, Some("RF"), 4).unwrap();
+
+        assert_eq!(table.shape(), (7, 4));
+
+        let beta = table.column("beta_Mkt-RF").unwrap().f64().unwrap();
+        assert_eq!(beta.null_count(), 4);
+        assert_approx_equal!(beta.get(6).unwrap(), 0.5, 1e-10);
+
+        let alpha = table.column("alpha").unwrap().f64().unwrap();
+        assert_approx_equal!(alpha.get(5).unwrap(), 0.0, 1e-12);
+    }
+
+    #[test]
+    fn test_absolute_returns_unsupported() {
+        let series = Series::new("close_absolute", [1.0, -2.0, 3.0, 0.5]);
+
+        assert!(matches!(
+            PerformanceStatistics::from_series(&series, 252.0),
+            Err(PerformanceError::UnsupportedReturns(_))
+        ));
+    }
+}
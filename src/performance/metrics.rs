@@ -0,0 +1,266 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::statistics::Statistic;
+use thiserror::Error;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Summary of the performance statistics of a return series
+/// (zero risk-free rate and target return).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PerformanceStatistics {
+    /// Annualised (geometric) return.
+    pub annualized_return: f64,
+
+    /// Annualised volatility.
+    pub annualized_volatility: f64,
+
+    /// Annualised Sharpe ratio.
+    pub sharpe_ratio: f64,
+
+    /// Annualised Sortino ratio.
+    pub sortino_ratio: f64,
+
+    /// Calmar ratio.
+    pub calmar_ratio: f64,
+
+    /// Omega ratio at a zero threshold.
+    pub omega_ratio: f64,
+
+    /// Maximum drawdown.
+    pub max_drawdown: f64,
+
+    /// Longest drawdown, in periods.
+    pub max_drawdown_duration: usize,
+
+    /// Sample skewness.
+    pub skewness: f64,
+
+    /// Sample excess kurtosis.
+    pub kurtosis: f64,
+}
+
+/// Performance statistics errors.
+#[derive(Debug, Error)]
+pub enum PerformanceError {
+    /// Not enough returns to compute the statistics.
+    #[error("Not enough returns: {0}.")]
+    InsufficientData(usize),
+
+    /// Returns that cannot be converted to simple returns.
+    #[error("Unsupported returns series: {0}.")]
+    UnsupportedReturns(String),
+
+    /// Attribution weights that do not add up to one.
+    #[error("Invalid weights: {0}.")]
+    InvalidWeights(String),
+
+    /// The factor regression could not be fitted.
+    #[error("{0}")]
+    Regression(#[from] crate::statistics::time_series::TimeSeriesError),
+
+    /// Error reading the returns.
+    #[cfg(feature = "data")]
+    #[error("{0}")]
+    Data(#[from] crate::data::DataError),
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl PerformanceStatistics {
+    /// Statistics of one-period simple returns, with `periods_per_year`
+    /// periods per year. Requires at least four returns.
+    pub fn new(returns: &[f64], periods_per_year: f64) -> Result<Self, PerformanceError> {
+        if returns.len() < 4 {
+            return Err(PerformanceError::InsufficientData(returns.len()));
+        }
+
+        Ok(Self {
+            annualized_return: annualized_return(returns, periods_per_year),
+            annualized_volatility: annualized_volatility(returns, periods_per_year),
+            sharpe_ratio: sharpe_ratio(returns, 0.0, periods_per_year),
+            sortino_ratio: sortino_ratio(returns, 0.0, periods_per_year),
+            calmar_ratio: calmar_ratio(returns, periods_per_year),
+            omega_ratio: omega_ratio(returns, 0.0),
+            max_drawdown: max_drawdown(returns),
+            max_drawdown_duration: max_drawdown_duration(returns),
+            skewness: returns.to_vec().skewness(),
+            kurtosis: returns.to_vec().kurtosis(),
+        })
+    }
+}
+
+/// Annualised geometric return: `(Π(1 + r))^(periods_per_year / n) - 1`.
+pub fn annualized_return(returns: &[f64], periods_per_year: f64) -> f64 {
+    assert!(
+        !returns.is_empty(),
+        "Returns must have at least one element."
+    );
+
+    let growth: f64 = returns.iter().map(|r| 1.0 + r).product();
+
+    growth.powf(periods_per_year / returns.len() as f64) - 1.0
+}
+
+/// Annualised volatility: sample standard deviation times `sqrt(periods_per_year)`.
+pub fn annualized_volatility(returns: &[f64], periods_per_year: f64) -> f64 {
+    returns.to_vec().standard_deviation() * periods_per_year.sqrt()
+}
+
+/// Annualised Sharpe ratio, for an annual `risk_free_rate`.
+///
+/// `Sharpe ratio = mean(r - r_f) / sd(r) * sqrt(periods_per_year)`
+pub fn sharpe_ratio(returns: &[f64], risk_free_rate: f64, periods_per_year: f64) -> f64 {
+    let excess = returns.to_vec().mean() - risk_free_rate / periods_per_year;
+
+    excess / returns.to_vec().standard_deviation() * periods_per_year.sqrt()
+}
+
+/// Annualised Sortino ratio, for an annual `target` return.
+///
+/// `Sortino ratio = mean(r - t) / sqrt(mean(min(r - t, 0)^2)) * sqrt(periods_per_year)`
+pub fn sortino_ratio(returns: &[f64], target: f64, periods_per_year: f64) -> f64 {
+    let target = target / periods_per_year;
+    let excess = returns.to_vec().mean() - target;
+    let downside = returns
+        .iter()
+        .map(|r| (r - target).min(0.0).powi(2))
+        .sum::<f64>()
+        / returns.len() as f64;
+
+    excess / downside.sqrt() * periods_per_year.sqrt()
+}
+
+/// Calmar ratio: annualised return over maximum drawdown.
+pub fn calmar_ratio(returns: &[f64], periods_per_year: f64) -> f64 {
+    annualized_return(returns, periods_per_year) / max_drawdown(returns)
+}
+
+/// Omega ratio: probability-weighted gains over losses relative to a
+/// one-period `threshold` return.
+///
+/// `Omega ratio = Σ max(r - τ, 0) / Σ max(τ - r, 0)`
+pub fn omega_ratio(returns: &[f64], threshold: f64) -> f64 {
+    let gains: f64 = returns.iter().map(|r| (r - threshold).max(0.0)).sum();
+    let losses: f64 = returns.iter().map(|r| (threshold - r).max(0.0)).sum();
+
+    gains / losses
+}
+
+/// Drawdown after each return, as a (non-negative) fraction of the running
+/// peak of the cumulative wealth (starting at one).
+pub fn drawdowns(returns: &[f64]) -> Vec<f64> {
+    let (mut wealth, mut peak) = (1.0, 1.0_f64);
+
+    returns
+        .iter()
+        .map(|r| {
+            wealth *= 1.0 + r;
+            peak = peak.max(wealth);
+            1.0 - wealth / peak
+        })
+        .collect()
+}
+
+/// Largest drawdown.
+pub fn max_drawdown(returns: &[f64]) -> f64 {
+    drawdowns(returns).into_iter().fold(0.0, f64::max)
+}
+
+/// Longest number of consecutive periods spent below the running peak.
+pub fn max_drawdown_duration(returns: &[f64]) -> usize {
+    drawdowns(returns)
+        .into_iter()
+        .fold((0, 0), |(longest, current), drawdown| {
+            let current = if drawdown > 0.0 { current + 1 } else { 0 };
+            (longest.max(current), current)
+        })
+        .0
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_metrics {
+    use super::*;
+
+    const RETURNS: [f64; 6] = [0.1, -0.1, 0.05, -0.2, 0.3, 0.0];
+
+    #[test]
+    fn test_returns_and_volatility() {
+        // Growth: 1.1 * 0.9 * 1.05 * 0.8 * 1.3 = 1.081080.
+        assert_approx_equal!(annualized_return(&RETURNS, 6.0), 0.08108, 1e-12);
+        assert_approx_equal!(
+            annualized_return(&RETURNS, 12.0),
+            1.08108_f64.powi(2) - 1.0,
+            1e-12
+        );
+
+        let sd = RETURNS.to_vec().standard_deviation();
+        assert_approx_equal!(annualized_volatility(&RETURNS, 4.0), 2.0 * sd, 1e-12);
+    }
+
+    #[test]
+    fn test_ratios() {
+        let mean = 0.15 / 6.0;
+        let sd = RETURNS.to_vec().standard_deviation();
+
+        assert_approx_equal!(sharpe_ratio(&RETURNS, 0.0, 1.0), mean / sd, 1e-12);
+        assert_approx_equal!(
+            sharpe_ratio(&RETURNS, 0.06, 12.0),
+            (mean - 0.005) / sd * 12.0_f64.sqrt(),
+            1e-12
+        );
+
+        let downside = ((0.01 + 0.04) / 6.0_f64).sqrt();
+        assert_approx_equal!(sortino_ratio(&RETURNS, 0.0, 1.0), mean / downside, 1e-12);
+
+        assert_approx_equal!(omega_ratio(&RETURNS, 0.0), 0.45 / 0.3, 1e-12);
+        assert_approx_equal!(
+            calmar_ratio(&RETURNS, 6.0),
+            0.08108 / max_drawdown(&RETURNS),
+            1e-12
+        );
+    }
+
+    #[test]
+    fn test_drawdowns() {
+        let drawdowns = drawdowns(&RETURNS);
+
+        assert_approx_equal!(drawdowns[0], 0.0, 1e-12);
+        assert_approx_equal!(drawdowns[1], 0.1, 1e-12);
+        assert_approx_equal!(drawdowns[3], 1.0 - 0.9 * 1.05 * 0.8, 1e-12);
+        assert_approx_equal!(max_drawdown(&RETURNS), 0.244, 1e-12);
+
+        // Below the peak of 1.1 from the second period to the end (1.08108).
+        assert_eq!(max_drawdown_duration(&RETURNS), 5);
+        assert_eq!(max_drawdown_duration(&[0.01, 0.02]), 0);
+    }
+
+    #[test]
+    fn test_performance_statistics() {
+        let stats = PerformanceStatistics::new(&RETURNS, 252.0).unwrap();
+
+        assert_approx_equal!(stats.max_drawdown, 0.244, 1e-12);
+        assert_approx_equal!(stats.skewness, RETURNS.to_vec().skewness(), 1e-15);
+        assert_approx_equal!(stats.kurtosis, RETURNS.to_vec().kurtosis(), 1e-15);
+
+        assert!(matches!(
+            PerformanceStatistics::new(&[0.01, 0.02], 252.0),
+            Err(PerformanceError::InsufficientData(2))
+        ));
+    }
+}
@@ -0,0 +1,71 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Performance and risk statistics of return series.
+//!
+//! The statistics take one-period simple returns and the number of periods
+//! per year (e.g. 252 for daily returns) to annualise:
+//!
+//! - Annualised return and volatility.
+//! - Sharpe, Sortino, Calmar and Omega ratios.
+//! - Drawdowns, maximum drawdown and its duration.
+//! - Skewness and excess kurtosis.
+//! - Rolling versions over a moving window.
+//! - Brinson-Fachler attribution of the active return against a benchmark.
+//! - (Rolling) factor regressions, e.g. on the Fama-French factors.
+//!
+//! ```
+//! use RustQuant::performance::*;
+//!
+//! let returns = vec![0.01, -0.02, 0.015, 0.005, -0.01, 0.02, 0.0, -0.005];
+//!
+//! let stats = PerformanceStatistics::new(&returns, 252.0).unwrap();
+//!
+//! println!("Sharpe ratio: {:.2}", stats.sharpe_ratio);
+//! println!("Max drawdown: {:.2}%", 100.0 * stats.max_drawdown);
+//!
+//! let rolling_vol = rolling_volatility(&returns, 4, 252.0);
+//! assert_eq!(rolling_vol.len(), 5);
+//! ```
+//!
+//! With the `data` feature, the same statistics can be computed for the
+//! columns of the `DataFrame` produced by `YahooFinanceData::compute_returns`:
+//!
+//! ```ignore
+//! use RustQuant::data::*;
+//! use RustQuant::performance::*;
+//!
+//! let mut yfd = YahooFinanceData::new("AAPL".to_string());
+//! yfd.compute_returns(ReturnsType::Logarithmic).unwrap();
+//!
+//! let table = performance_table(yfd.returns.as_ref().unwrap(), 252.0).unwrap();
+//! println!("{}", table);
+//! ```
+
+/// Performance statistics of return series.
+pub mod metrics;
+pub use metrics::*;
+
+/// Rolling performance statistics.
+pub mod rolling;
+pub use rolling::*;
+
+/// Brinson-Fachler performance attribution.
+pub mod attribution;
+pub use attribution::*;
+
+/// Factor model regressions of returns.
+pub mod factor_regression;
+pub use factor_regression::*;
+
+/// Performance statistics of Polars returns series and data frames.
+#[cfg(feature = "data")]
+pub mod frame;
+#[cfg(feature = "data")]
+pub use frame::*;
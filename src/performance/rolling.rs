@@ -0,0 +1,86 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::performance::{
+    annualized_return, annualized_volatility, max_drawdown, sharpe_ratio, sortino_ratio,
+};
+
+/// Statistic over each window of `window` consecutive returns.
+///
+/// The `i`-th value is the statistic of `returns[i..i + window]`, so there are
+/// `n - window + 1` values (none if the window is longer than the series).
+pub fn rolling<F>(returns: &[f64], window: usize, statistic: F) -> Vec<f64>
+where
+    F: Fn(&[f64]) -> f64,
+{
+    assert!(window > 0, "Window must be positive.");
+
+    returns.windows(window).map(statistic).collect()
+}
+
+/// Rolling annualised return.
+pub fn rolling_return(returns: &[f64], window: usize, periods_per_year: f64) -> Vec<f64> {
+    rolling(returns, window, |w| annualized_return(w, periods_per_year))
+}
+
+/// Rolling annualised volatility.
+pub fn rolling_volatility(returns: &[f64], window: usize, periods_per_year: f64) -> Vec<f64> {
+    rolling(returns, window, |w| {
+        annualized_volatility(w, periods_per_year)
+    })
+}
+
+/// Rolling annualised Sharpe ratio (zero risk-free rate).
+pub fn rolling_sharpe_ratio(returns: &[f64], window: usize, periods_per_year: f64) -> Vec<f64> {
+    rolling(returns, window, |w| sharpe_ratio(w, 0.0, periods_per_year))
+}
+
+/// Rolling annualised Sortino ratio (zero target return).
+pub fn rolling_sortino_ratio(returns: &[f64], window: usize, periods_per_year: f64) -> Vec<f64> {
+    rolling(returns, window, |w| sortino_ratio(w, 0.0, periods_per_year))
+}
+
+/// Rolling maximum drawdown.
+pub fn rolling_max_drawdown(returns: &[f64], window: usize) -> Vec<f64> {
+    rolling(returns, window, max_drawdown)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_rolling {
+    use super::*;
+
+    #[test]
+    fn test_rolling() {
+        let returns = [0.1, -0.1, 0.05, -0.2, 0.3];
+
+        let drawdowns = rolling_max_drawdown(&returns, 2);
+        assert_eq!(drawdowns.len(), 4);
+        assert_approx_equal!(drawdowns[0], 0.1, 1e-12);
+        assert_approx_equal!(drawdowns[2], 0.2, 1e-12);
+        assert_approx_equal!(drawdowns[3], 0.2, 1e-12);
+
+        let volatility = rolling_volatility(&returns, 3, 1.0);
+        assert_approx_equal!(
+            volatility[1],
+            annualized_volatility(&returns[1..4], 1.0),
+            1e-15
+        );
+
+        let sharpe = rolling_sharpe_ratio(&returns, 5, 252.0);
+        assert_eq!(sharpe.len(), 1);
+        assert_approx_equal!(sharpe[0], sharpe_ratio(&returns, 0.0, 252.0), 1e-15);
+
+        assert_eq!(rolling_sortino_ratio(&returns, 3, 1.0).len(), 3);
+        assert_eq!(rolling_return(&returns, 6, 1.0).len(), 0);
+    }
+}
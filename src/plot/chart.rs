@@ -0,0 +1,246 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use std::path::Path;
+use thiserror::Error;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Line chart of one or more series, saved as PNG or SVG.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chart {
+    /// Title drawn above the chart.
+    pub title: String,
+
+    /// Label of the x-axis.
+    pub x_label: String,
+
+    /// Label of the y-axis.
+    pub y_label: String,
+
+    /// Width and height of the image, in pixels.
+    pub size: (u32, u32),
+
+    /// Named series of (x, y) points, each drawn as a line.
+    pub series: Vec<(String, Vec<(f64, f64)>)>,
+}
+
+/// Plotting errors.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PlotError {
+    /// The chart has no points to draw.
+    #[error("Nothing to plot.")]
+    NoData,
+
+    /// The file extension is neither `png` nor `svg`.
+    #[error("Unsupported image format {0:?}: use a .png or .svg file.")]
+    UnsupportedFormat(String),
+
+    /// The backend failed to draw or write the image.
+    #[error("Plotting failed: {0}")]
+    Drawing(String),
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// Legends with more entries than this (e.g. one per simulated path) would
+// cover the chart, so they are not drawn.
+const MAX_LEGEND_ENTRIES: usize = 10;
+
+// Lower and upper bound of an axis.
+type Range = (f64, f64);
+
+impl Chart {
+    /// New 800x600 chart without any series.
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            x_label: String::new(),
+            y_label: String::new(),
+            size: (800, 600),
+            series: Vec::new(),
+        }
+    }
+
+    /// Sets the axis labels.
+    pub fn with_labels(mut self, x_label: &str, y_label: &str) -> Self {
+        self.x_label = x_label.to_string();
+        self.y_label = y_label.to_string();
+        self
+    }
+
+    /// Sets the image size, in pixels.
+    pub fn with_size(mut self, width: u32, height: u32) -> Self {
+        self.size = (width, height);
+        self
+    }
+
+    /// Adds a series of (x, y) points.
+    pub fn with_series(mut self, name: &str, points: Vec<(f64, f64)>) -> Self {
+        self.series.push((name.to_string(), points));
+        self
+    }
+
+    /// Draws the chart to `path`, as SVG if the extension is `svg` and as
+    /// PNG if it is `png`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), PlotError> {
+        let path = path.as_ref();
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default()
+            .to_lowercase();
+
+        match extension.as_str() {
+            "svg" => self.draw(SVGBackend::new(path, self.size).into_drawing_area()),
+            "png" => self.draw(BitMapBackend::new(path, self.size).into_drawing_area()),
+            _ => Err(PlotError::UnsupportedFormat(extension)),
+        }
+    }
+
+    // Range of the x and y values, padded by 5% (or by one if all values
+    // are equal) so lines do not sit on the axes.
+    fn ranges(&self) -> Result<(Range, Range), PlotError> {
+        let points = self
+            .series
+            .iter()
+            .flat_map(|(_, points)| points)
+            .filter(|(x, y)| x.is_finite() && y.is_finite());
+
+        let ((x_min, x_max), (y_min, y_max)) = points.fold(
+            (
+                (f64::INFINITY, f64::NEG_INFINITY),
+                (f64::INFINITY, f64::NEG_INFINITY),
+            ),
+            |((x_lo, x_hi), (y_lo, y_hi)), &(x, y)| {
+                ((x_lo.min(x), x_hi.max(x)), (y_lo.min(y), y_hi.max(y)))
+            },
+        );
+
+        if x_min > x_max {
+            return Err(PlotError::NoData);
+        }
+
+        let pad = |lo: f64, hi: f64| match hi - lo {
+            width if width > 0.0 => (lo - 0.05 * width, hi + 0.05 * width),
+            _ => (lo - 1.0, hi + 1.0),
+        };
+
+        Ok((pad(x_min, x_max), pad(y_min, y_max)))
+    }
+
+    fn draw<DB: DrawingBackend>(&self, root: DrawingArea<DB, Shift>) -> Result<(), PlotError> {
+        let failed =
+            |error: DrawingAreaErrorKind<DB::ErrorType>| PlotError::Drawing(error.to_string());
+
+        let ((x_min, x_max), (y_min, y_max)) = self.ranges()?;
+
+        root.fill(&WHITE).map_err(failed)?;
+
+        let mut chart = ChartBuilder::on(&root)
+            .caption(&self.title, ("sans-serif", 24))
+            .margin(10)
+            .x_label_area_size(40)
+            .y_label_area_size(60)
+            .build_cartesian_2d(x_min..x_max, y_min..y_max)
+            .map_err(failed)?;
+
+        chart
+            .configure_mesh()
+            .x_desc(&self.x_label)
+            .y_desc(&self.y_label)
+            .draw()
+            .map_err(failed)?;
+
+        for (i, (name, points)) in self.series.iter().enumerate() {
+            let colour = Palette99::pick(i).to_rgba();
+
+            chart
+                .draw_series(LineSeries::new(points.iter().copied(), colour))
+                .map_err(failed)?
+                .label(name)
+                .legend(move |(x, y)| PathElement::new([(x, y), (x + 20, y)], colour));
+        }
+
+        if self.series.len() <= MAX_LEGEND_ENTRIES {
+            chart
+                .configure_series_labels()
+                .background_style(WHITE.mix(0.8))
+                .border_style(BLACK)
+                .draw()
+                .map_err(failed)?;
+        }
+
+        root.present().map_err(failed)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_chart {
+    use super::*;
+
+    fn chart() -> Chart {
+        Chart::new("Test")
+            .with_labels("x", "y")
+            .with_size(320, 240)
+            .with_series(
+                "square",
+                (0..10).map(|i| (i as f64, (i * i) as f64)).collect(),
+            )
+            .with_series("flat", vec![(0.0, 1.0), (9.0, 1.0)])
+    }
+
+    #[test]
+    fn test_chart_ranges() {
+        let ((x_min, x_max), (y_min, y_max)) = chart().ranges().unwrap();
+
+        assert_approx_equal!(x_min, -0.45, 1e-12);
+        assert_approx_equal!(x_max, 9.45, 1e-12);
+        assert_approx_equal!(y_min, -4.05, 1e-12);
+        assert_approx_equal!(y_max, 85.05, 1e-12);
+
+        let flat = Chart::new("Flat").with_series("flat", vec![(1.0, 2.0)]);
+        assert_eq!(flat.ranges(), Ok(((0.0, 2.0), (1.0, 3.0))));
+    }
+
+    #[test]
+    fn test_chart_save() {
+        let dir = std::env::temp_dir();
+
+        for file in ["rustquant_chart.svg", "rustquant_chart.png"] {
+            let path = dir.join(file);
+
+            chart().save(&path).unwrap();
+            assert!(std::fs::metadata(&path).unwrap().len() > 0);
+            std::fs::remove_file(&path).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_chart_errors() {
+        assert_eq!(
+            Chart::new("Empty").save(std::env::temp_dir().join("empty.svg")),
+            Err(PlotError::NoData)
+        );
+        assert_eq!(
+            chart().save("chart.pdf"),
+            Err(PlotError::UnsupportedFormat("pdf".to_string()))
+        );
+    }
+}
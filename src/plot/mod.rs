@@ -0,0 +1,31 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Plotting (requires the `plot` feature).
+//!
+//! A [`Chart`] draws line series to a PNG or SVG file, chosen by the file
+//! extension. The types most often looked at have their own `plot` methods
+//! built on it:
+//!
+//! - `Trajectories::plot` for simulated paths,
+//! - `YieldCurve::plot` for a yield curve,
+//! - `OptionStrategy::plot_payoff` for a payoff diagram,
+//! - `BacktestReport::plot` for an equity curve,
+//! - `ExerciseBoundary::plot` for an early-exercise boundary.
+//!
+//! ```no_run
+//! use RustQuant::instruments::options::OptionStrategy;
+//!
+//! OptionStrategy::butterfly(90.0, 100.0, 110.0).plot_payoff("butterfly.svg", 80.0, 120.0)?;
+//! # Ok::<(), RustQuant::plot::PlotError>(())
+//! ```
+
+/// Line charts.
+pub mod chart;
+pub use chart::*;
@@ -79,6 +79,22 @@
 use crate::{instruments::Instrument, money::Currency};
 use std::collections::HashMap;
 
+/// Portfolio construction: mean-variance, Black-Litterman and risk parity.
+pub mod optimization;
+pub use optimization::*;
+
+/// Efficient frontier, tangency portfolio and capital market line.
+pub mod efficient_frontier;
+pub use efficient_frontier::*;
+
+/// Hierarchical risk parity allocation.
+pub mod hierarchical_risk_parity;
+pub use hierarchical_risk_parity::*;
+
+/// Cash flow ladders of instruments and portfolios.
+pub mod cashflow_ladder;
+pub use cashflow_ladder::*;
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // STRUCTS, ENUMS, AND TRAITS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -0,0 +1,406 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::instruments::Instrument;
+use crate::money::{Currency, DatedCashflow};
+use crate::portfolio::Portfolio;
+use crate::time::Tenor;
+use std::collections::BTreeMap;
+use time::{Date, OffsetDateTime};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// How a cash flow ladder groups payment dates.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LadderBucketing {
+    /// One bucket per payment date.
+    Daily,
+
+    /// One bucket per calendar month.
+    Monthly,
+
+    /// Buckets between consecutive tenors from the as-of date (e.g. 1M,
+    /// 3M, 1Y), and one open bucket after the last tenor.
+    Tenors(Vec<Tenor>),
+}
+
+/// Cash flows of one currency in one bucket of a ladder.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LadderRow {
+    /// Bucket label (e.g. "2025-03" or "1M-3M").
+    pub bucket: String,
+
+    /// First date of the bucket.
+    pub start: Date,
+
+    /// Date after the bucket (`None` for an open bucket).
+    pub end: Option<Date>,
+
+    /// Currency of the flows (`None` for flows without a currency).
+    pub currency: Option<Currency>,
+
+    /// Sum of the positive flows.
+    pub inflows: f64,
+
+    /// Sum of the negative flows.
+    pub outflows: f64,
+
+    /// Net flow of the bucket.
+    pub net: f64,
+
+    /// Net flow of this and all earlier buckets, in the same currency.
+    pub cumulative: f64,
+}
+
+/// Cash flow ladder (maturity ladder): future cash flows grouped into date
+/// buckets, one row per bucket and currency, for liquidity reporting.
+///
+/// Flows in different currencies are never netted. Flows before the
+/// as-of date are left out.
+///
+/// ```
+/// use RustQuant::money::{DatedCashflow, USD};
+/// use RustQuant::portfolio::{CashflowLadder, LadderBucketing};
+/// use RustQuant::time::Tenor;
+/// use time::macros::datetime;
+///
+/// let as_of = datetime!(2025-01-02 0:00 UTC);
+/// let flows = [
+///     DatedCashflow::new(datetime!(2025-01-15 0:00 UTC), 100.0, Some(USD)),
+///     DatedCashflow::new(datetime!(2025-02-20 0:00 UTC), -40.0, Some(USD)),
+///     DatedCashflow::new(datetime!(2026-06-30 0:00 UTC), 1000.0, Some(USD)),
+/// ];
+///
+/// let ladder = CashflowLadder::new(
+///     as_of,
+///     &LadderBucketing::Tenors(vec![Tenor::months(1), Tenor::years(1)]),
+///     flows,
+/// );
+///
+/// let buckets: Vec<&str> = ladder.rows.iter().map(|row| row.bucket.as_str()).collect();
+/// assert_eq!(buckets, ["0D-1M", "1M-1Y", "1Y+"]);
+/// assert_eq!(ladder.rows[1].cumulative, 60.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CashflowLadder {
+    /// Date the ladder starts from.
+    pub as_of: OffsetDateTime,
+
+    /// Rows, by bucket and then currency code.
+    pub rows: Vec<LadderRow>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl LadderBucketing {
+    /// Label, first date and end of the bucket of `date`.
+    fn bucket(&self, as_of: OffsetDateTime, date: OffsetDateTime) -> (String, Date, Option<Date>) {
+        let day = date.date();
+
+        match self {
+            LadderBucketing::Daily => (day.to_string(), day, day.next_day()),
+            LadderBucketing::Monthly => {
+                let start = day.replace_day(1).expect("Every month has a first day.");
+                let end = (OffsetDateTime::new_utc(start, time::Time::MIDNIGHT) + Tenor::months(1))
+                    .date();
+
+                (
+                    format!("{}-{:02}", start.year(), start.month() as u8),
+                    start,
+                    Some(end),
+                )
+            }
+            LadderBucketing::Tenors(tenors) => {
+                let mut previous = (String::from("0D"), as_of.date());
+
+                for tenor in tenors {
+                    let edge = (as_of + *tenor).date();
+
+                    if day < edge {
+                        return (format!("{}-{tenor}", previous.0), previous.1, Some(edge));
+                    }
+
+                    previous = (tenor.to_string(), edge);
+                }
+
+                (format!("{}+", previous.0), previous.1, None)
+            }
+        }
+    }
+}
+
+impl CashflowLadder {
+    /// Ladder of the cash flows from `as_of` onwards.
+    pub fn new(
+        as_of: OffsetDateTime,
+        bucketing: &LadderBucketing,
+        cashflows: impl IntoIterator<Item = DatedCashflow>,
+    ) -> Self {
+        let currency_code =
+            |currency: &Option<Currency>| currency.map_or("", |currency| currency.code.alphabetic);
+
+        let mut rows: BTreeMap<(Date, &'static str), LadderRow> = BTreeMap::new();
+
+        for flow in cashflows.into_iter().filter(|flow| flow.date >= as_of) {
+            let (bucket, start, end) = bucketing.bucket(as_of, flow.date);
+            let row = rows
+                .entry((start, currency_code(&flow.currency)))
+                .or_insert_with(|| LadderRow {
+                    bucket,
+                    start,
+                    end,
+                    currency: flow.currency,
+                    inflows: 0.0,
+                    outflows: 0.0,
+                    net: 0.0,
+                    cumulative: 0.0,
+                });
+
+            match flow.amount >= 0.0 {
+                true => row.inflows += flow.amount,
+                false => row.outflows += flow.amount,
+            }
+            row.net += flow.amount;
+        }
+
+        let mut cumulative: BTreeMap<&'static str, f64> = BTreeMap::new();
+        let rows = rows
+            .into_iter()
+            .map(|((_, code), mut row)| {
+                let total = cumulative.entry(code).or_insert(0.0);
+                *total += row.net;
+                row.cumulative = *total;
+                row
+            })
+            .collect();
+
+        Self { as_of, rows }
+    }
+
+    /// Rows of one currency (`None` for flows without a currency).
+    pub fn currency_rows(&self, currency: Option<&Currency>) -> Vec<&LadderRow> {
+        self.rows
+            .iter()
+            .filter(|row| row.currency.as_ref() == currency)
+            .collect()
+    }
+
+    /// The ladder as a `DataFrame` with `bucket`, `start`, `end`,
+    /// `currency`, `inflows`, `outflows`, `net` and `cumulative` columns.
+    #[cfg(feature = "data")]
+    pub fn to_frame(&self) -> Result<polars::prelude::DataFrame, crate::data::DataError> {
+        use polars::prelude::*;
+
+        // Julian day number of the UNIX epoch (1970-01-01).
+        const UNIX_EPOCH_JULIAN_DAY: i32 = 2_440_588;
+
+        let days = |date: &Date| date.to_julian_day() - UNIX_EPOCH_JULIAN_DAY;
+        let column = |f: fn(&LadderRow) -> f64| self.rows.iter().map(f).collect::<Vec<f64>>();
+
+        let start: Vec<i32> = self.rows.iter().map(|row| days(&row.start)).collect();
+        let end: Vec<Option<i32>> = self
+            .rows
+            .iter()
+            .map(|row| row.end.as_ref().map(days))
+            .collect();
+
+        Ok(DataFrame::new(vec![
+            Series::new(
+                "bucket",
+                self.rows
+                    .iter()
+                    .map(|row| row.bucket.as_str())
+                    .collect::<Vec<_>>(),
+            ),
+            Series::new("start", start).cast(&DataType::Date)?,
+            Series::new("end", end).cast(&DataType::Date)?,
+            Series::new(
+                "currency",
+                self.rows
+                    .iter()
+                    .map(|row| row.currency.map(|currency| currency.code.alphabetic))
+                    .collect::<Vec<_>>(),
+            ),
+            Series::new("inflows", column(|row| row.inflows)),
+            Series::new("outflows", column(|row| row.outflows)),
+            Series::new("net", column(|row| row.net)),
+            Series::new("cumulative", column(|row| row.cumulative)),
+        ])?)
+    }
+}
+
+impl<I> Portfolio<I>
+where
+    I: Instrument,
+{
+    /// Cash flows of all positions (each instrument's flows times the
+    /// position's quantity), in date order. Flows without a currency take
+    /// the position's.
+    pub fn cashflows(&self) -> Vec<DatedCashflow> {
+        let mut flows: Vec<DatedCashflow> = self
+            .positions
+            .values()
+            .flat_map(|position| {
+                position.instrument.cashflows().into_iter().map(|flow| {
+                    DatedCashflow::new(
+                        flow.date,
+                        flow.amount * position.quantity as f64,
+                        flow.currency.or(position.currency),
+                    )
+                })
+            })
+            .collect();
+
+        flows.sort_by_key(|flow| flow.date);
+        flows
+    }
+
+    /// Cash flow ladder of the portfolio from `as_of`.
+    pub fn cashflow_ladder(
+        &self,
+        as_of: OffsetDateTime,
+        bucketing: &LadderBucketing,
+    ) -> CashflowLadder {
+        CashflowLadder::new(as_of, bucketing, self.cashflows())
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_cashflow_ladder {
+    use super::*;
+    use crate::curves::{Curve, YieldCurve};
+    use crate::instruments::CouponBond;
+    use crate::money::{EUR, USD};
+    use crate::portfolio::Position;
+    use crate::time::PaymentFrequency;
+    use std::collections::HashMap;
+    use time::macros::{date, datetime};
+    use time::Duration;
+
+    const AS_OF: OffsetDateTime = datetime!(2025-01-02 0:00 UTC);
+
+    fn flows() -> Vec<DatedCashflow> {
+        vec![
+            DatedCashflow::new(datetime!(2024-12-31 0:00 UTC), 5.0, Some(USD)),
+            DatedCashflow::new(datetime!(2025-01-15 0:00 UTC), 100.0, Some(USD)),
+            DatedCashflow::new(datetime!(2025-01-15 12:00 UTC), -30.0, Some(USD)),
+            DatedCashflow::new(datetime!(2025-01-20 0:00 UTC), 50.0, Some(EUR)),
+            DatedCashflow::new(datetime!(2025-02-20 0:00 UTC), -40.0, Some(USD)),
+        ]
+    }
+
+    #[test]
+    fn test_daily_ladder() {
+        let ladder = CashflowLadder::new(AS_OF, &LadderBucketing::Daily, flows());
+
+        // The flow before the as-of date is left out.
+        assert_eq!(ladder.rows.len(), 3);
+        assert_eq!(ladder.rows[0].bucket, "2025-01-15");
+        assert_eq!(ladder.rows[0].end, Some(date!(2025 - 01 - 16)));
+        assert_eq!(ladder.rows[0].inflows, 100.0);
+        assert_eq!(ladder.rows[0].outflows, -30.0);
+        assert_eq!(ladder.rows[0].net, 70.0);
+    }
+
+    #[test]
+    fn test_monthly_ladder() {
+        let ladder = CashflowLadder::new(AS_OF, &LadderBucketing::Monthly, flows());
+
+        let usd = ladder.currency_rows(Some(&USD));
+        assert_eq!(usd.len(), 2);
+        assert_eq!(usd[0].bucket, "2025-01");
+        assert_eq!(usd[0].start, date!(2025 - 01 - 01));
+        assert_eq!(usd[0].end, Some(date!(2025 - 02 - 01)));
+        assert_eq!(usd[1].net, -40.0);
+        assert_eq!(usd[1].cumulative, 30.0);
+
+        // EUR flows are not netted with USD flows.
+        let eur = ladder.currency_rows(Some(&EUR));
+        assert_eq!(eur.len(), 1);
+        assert_eq!(eur[0].cumulative, 50.0);
+    }
+
+    #[test]
+    fn test_tenor_ladder() {
+        let bucketing = LadderBucketing::Tenors(vec![Tenor::weeks(2), Tenor::months(1)]);
+        let ladder = CashflowLadder::new(AS_OF, &bucketing, flows());
+
+        let buckets: Vec<(&str, &str)> = ladder
+            .rows
+            .iter()
+            .map(|row| {
+                (
+                    row.bucket.as_str(),
+                    row.currency.map_or("", |c| c.code.alphabetic),
+                )
+            })
+            .collect();
+        assert_eq!(
+            buckets,
+            [("0D-2W", "USD"), ("2W-1M", "EUR"), ("1M+", "USD")]
+        );
+        assert_eq!(ladder.rows[2].end, None);
+    }
+
+    #[test]
+    fn test_portfolio_ladder() {
+        let curve =
+            YieldCurve::from_dates_and_rates(&[AS_OF, AS_OF + Duration::days(3650)], &[0.04, 0.04]);
+        let bond = CouponBond::builder()
+            .evaluation_date(AS_OF)
+            .expiration_date(AS_OF + Duration::days(365 * 3))
+            .coupon_rate(0.05)
+            .coupon_frequency(PaymentFrequency::Annually)
+            .currency(USD)
+            .yield_curve(curve)
+            .build()
+            .unwrap();
+        let portfolio = Portfolio::new(HashMap::from([(
+            "Bonds".to_string(),
+            Position::new(bond, 10, 100.0, 100.0, None),
+        )]));
+
+        let flows = portfolio.cashflows();
+        assert_eq!(flows.len(), 3);
+        assert_approx_equal!(flows[0].amount, 50.0, 1e-10);
+        assert_approx_equal!(flows[2].amount, 1050.0, 1e-10);
+
+        let ladder = portfolio.cashflow_ladder(AS_OF, &LadderBucketing::Monthly);
+        assert_eq!(ladder.rows.len(), 3);
+        assert_approx_equal!(ladder.rows[2].cumulative, 1150.0, 1e-10);
+    }
+
+    #[cfg(feature = "data")]
+    #[test]
+    fn test_ladder_frame() {
+        let ladder = CashflowLadder::new(AS_OF, &LadderBucketing::Monthly, flows());
+        let frame = ladder.to_frame().unwrap();
+
+        // January's EUR row comes before its USD row.
+        assert_eq!(frame.shape(), (3, 8));
+        assert_eq!(
+            frame
+                .column("net")
+                .unwrap()
+                .f64()
+                .unwrap()
+                .into_iter()
+                .next(),
+            Some(Some(50.0))
+        );
+    }
+}
@@ -0,0 +1,367 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Mean-variance efficient frontier of fully invested portfolios (short
+//! positions allowed), in closed form (Merton, 1972).
+//!
+//! With $A = 1' \Sigma^{-1} 1$, $B = 1' \Sigma^{-1} \mu$,
+//! $C = \mu' \Sigma^{-1} \mu$ and $D = AC - B^2$, the minimum variance
+//! portfolio with expected return $m$ has variance
+//! $(A m^2 - 2 B m + C) / D$.
+//!
+//! With a risk-free rate $r_f$, the tangency portfolio
+//! $w \propto \Sigma^{-1} (\mu - r_f 1)$ has the highest Sharpe ratio, and
+//! the capital market line $r_f + S \sigma$ joins it to the risk-free asset.
+//!
+//! ```
+//! use RustQuant::portfolio::*;
+//! use nalgebra::{dmatrix, dvector};
+//!
+//! let frontier = EfficientFrontier::new(
+//!     dvector![0.08, 0.05, 0.03],
+//!     dmatrix![0.04, 0.006, 0.0; 0.006, 0.0225, 0.0; 0.0, 0.0, 0.01],
+//! )
+//! .unwrap()
+//! .with_risk_free_rate(0.01);
+//!
+//! let tangency = frontier.tangency().unwrap();
+//! let points = frontier.points(50).unwrap();
+//!
+//! assert!(points.iter().all(|p| p.sharpe_ratio(0.01) <= tangency.sharpe_ratio(0.01) + 1e-12));
+//! ```
+
+use crate::portfolio::PortfolioError;
+use nalgebra::{DMatrix, DVector};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Efficient frontier of a set of assets.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EfficientFrontier {
+    /// Expected returns of the assets.
+    expected_returns: DVector<f64>,
+
+    /// Covariance matrix of asset returns.
+    covariance: DMatrix<f64>,
+
+    /// Risk-free rate, for the tangency portfolio and capital market line.
+    risk_free_rate: f64,
+
+    /// Asset names, used as the weight column names.
+    asset_names: Vec<String>,
+
+    /// Inverse covariance matrix.
+    precision: DMatrix<f64>,
+}
+
+/// A portfolio on (or off) the efficient frontier.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrontierPortfolio {
+    /// Expected return.
+    pub expected_return: f64,
+
+    /// Volatility (standard deviation of the return).
+    pub volatility: f64,
+
+    /// Asset weights (summing to one).
+    pub weights: DVector<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl FrontierPortfolio {
+    /// Sharpe ratio of the portfolio for the given risk-free rate.
+    pub fn sharpe_ratio(&self, risk_free_rate: f64) -> f64 {
+        (self.expected_return - risk_free_rate) / self.volatility
+    }
+}
+
+impl EfficientFrontier {
+    /// New efficient frontier from the expected returns and covariance of
+    /// the assets (risk-free rate zero).
+    pub fn new(
+        expected_returns: DVector<f64>,
+        covariance: DMatrix<f64>,
+    ) -> Result<Self, PortfolioError> {
+        let n = expected_returns.len();
+
+        if covariance.shape() != (n, n) || n < 2 {
+            return Err(PortfolioError::DimensionMismatch {
+                expected: n.max(2),
+                found: covariance.nrows(),
+            });
+        }
+
+        let precision = covariance
+            .clone()
+            .cholesky()
+            .ok_or_else(|| PortfolioError::SingularMatrix("covariance".to_string()))?
+            .inverse();
+
+        Ok(Self {
+            expected_returns,
+            covariance,
+            risk_free_rate: 0.0,
+            asset_names: (0..n).map(|i| format!("weight_{i}")).collect(),
+            precision,
+        })
+    }
+
+    /// Sets the risk-free rate.
+    pub fn with_risk_free_rate(mut self, risk_free_rate: f64) -> Self {
+        self.risk_free_rate = risk_free_rate;
+        self
+    }
+
+    /// Sets the asset names (the weight column names of `compute`).
+    pub fn with_asset_names(mut self, names: &[&str]) -> Self {
+        assert_eq!(names.len(), self.expected_returns.len());
+
+        self.asset_names = names.iter().map(|name| name.to_string()).collect();
+        self
+    }
+
+    /// Portfolio with the given weights.
+    pub fn portfolio(&self, weights: DVector<f64>) -> FrontierPortfolio {
+        FrontierPortfolio {
+            expected_return: weights.dot(&self.expected_returns),
+            volatility: weights.dot(&(&self.covariance * &weights)).max(0.0).sqrt(),
+            weights,
+        }
+    }
+
+    /// Global minimum variance portfolio, $w = \Sigma^{-1} 1 / A$.
+    pub fn minimum_variance(&self) -> FrontierPortfolio {
+        let weights = self.precision.column_sum();
+        let a = weights.sum();
+
+        self.portfolio(weights / a)
+    }
+
+    /// Minimum variance portfolio with expected return `target_return`.
+    pub fn efficient_portfolio(
+        &self,
+        target_return: f64,
+    ) -> Result<FrontierPortfolio, PortfolioError> {
+        let (a, b, c, d) = self.constants()?;
+
+        let ones = self.precision.column_sum();
+        let tilted = &self.precision * &self.expected_returns;
+        let weights = (ones * (c - b * target_return) + tilted * (a * target_return - b)) / d;
+
+        Ok(self.portfolio(weights))
+    }
+
+    /// Tangency portfolio, $w \propto \Sigma^{-1} (\mu - r_f 1)$, with the
+    /// highest Sharpe ratio. Requires the risk-free rate to be below the
+    /// expected return of the minimum variance portfolio.
+    pub fn tangency(&self) -> Result<FrontierPortfolio, PortfolioError> {
+        let excess = self.expected_returns.add_scalar(-self.risk_free_rate);
+        let weights = &self.precision * excess;
+        let total = weights.sum();
+
+        match total > 0.0 {
+            true => Ok(self.portfolio(weights / total)),
+            false => Err(PortfolioError::InvalidParameter(
+                "the risk-free rate must be below the minimum variance return".to_string(),
+            )),
+        }
+    }
+
+    /// Expected return on the capital market line at the given volatility.
+    pub fn capital_market_line(&self, volatility: f64) -> Result<f64, PortfolioError> {
+        let tangency = self.tangency()?;
+
+        Ok(self.risk_free_rate + tangency.sharpe_ratio(self.risk_free_rate) * volatility)
+    }
+
+    /// `n_points` efficient portfolios with expected returns equally spaced
+    /// from the minimum variance portfolio to the highest asset return.
+    pub fn points(&self, n_points: usize) -> Result<Vec<FrontierPortfolio>, PortfolioError> {
+        if n_points < 2 {
+            return Err(PortfolioError::InvalidParameter(
+                "at least two frontier points are required".to_string(),
+            ));
+        }
+
+        let lower = self.minimum_variance().expected_return;
+        let upper = self.expected_returns.max();
+        let step = (upper - lower) / (n_points - 1) as f64;
+
+        (0..n_points)
+            .map(|i| self.efficient_portfolio(lower + step * i as f64))
+            .collect()
+    }
+
+    /// The frontier as a `DataFrame` with columns `volatility`,
+    /// `expected_return`, `sharpe_ratio`, `cml_return` (the capital market
+    /// line at the same volatility) and one weight column per asset, ready
+    /// for plotting.
+    #[cfg(feature = "data")]
+    pub fn compute(&self, n_points: usize) -> Result<polars::prelude::DataFrame, PortfolioError> {
+        use polars::prelude::*;
+
+        let points = self.points(n_points)?;
+        let sharpe = self.tangency()?.sharpe_ratio(self.risk_free_rate);
+
+        let column = |name: &str, f: &dyn Fn(&FrontierPortfolio) -> f64| {
+            Float64Chunked::from_vec(name, points.iter().map(f).collect()).into_series()
+        };
+
+        let mut columns = vec![
+            column("volatility", &|p| p.volatility),
+            column("expected_return", &|p| p.expected_return),
+            column("sharpe_ratio", &|p| p.sharpe_ratio(self.risk_free_rate)),
+            column("cml_return", &|p| {
+                self.risk_free_rate + sharpe * p.volatility
+            }),
+        ];
+        for (i, name) in self.asset_names.iter().enumerate() {
+            columns.push(column(name, &|p| p.weights[i]));
+        }
+
+        Ok(DataFrame::new(columns).map_err(crate::data::DataError::from)?)
+    }
+
+    // Frontier constants (A, B, C, D).
+    fn constants(&self) -> Result<(f64, f64, f64, f64), PortfolioError> {
+        let mu = &self.expected_returns;
+        let tilted = &self.precision * mu;
+
+        let a = self.precision.sum();
+        let b = tilted.sum();
+        let c = mu.dot(&tilted);
+        let d = a * c - b * b;
+
+        match d > f64::EPSILON * a * c {
+            true => Ok((a, b, c, d)),
+            false => Err(PortfolioError::InvalidParameter(
+                "the expected returns are all equal, the frontier is a single point".to_string(),
+            )),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_efficient_frontier {
+    use super::*;
+    use nalgebra::{dmatrix, dvector};
+
+    fn frontier() -> EfficientFrontier {
+        EfficientFrontier::new(
+            dvector![0.08, 0.05, 0.03],
+            dmatrix![
+                0.04, 0.009, 0.002;
+                0.009, 0.0225, 0.0075;
+                0.002, 0.0075, 0.01
+            ],
+        )
+        .unwrap()
+        .with_risk_free_rate(0.01)
+    }
+
+    #[test]
+    fn test_minimum_variance_and_efficient_portfolios() {
+        let frontier = frontier();
+        let gmv = frontier.minimum_variance();
+
+        assert_approx_equal!(gmv.weights.sum(), 1.0, 1e-12);
+
+        // Small fully invested perturbations increase the variance.
+        for k in 0..3 {
+            let mut weights = gmv.weights.clone();
+            weights[k] += 0.01;
+            weights[(k + 1) % 3] -= 0.01;
+            assert!(frontier.portfolio(weights).volatility > gmv.volatility);
+        }
+
+        // Efficient portfolios hit the target return, and the frontier
+        // passes through the minimum variance portfolio.
+        let portfolio = frontier.efficient_portfolio(0.06).unwrap();
+        assert_approx_equal!(portfolio.expected_return, 0.06, 1e-12);
+        assert_approx_equal!(portfolio.weights.sum(), 1.0, 1e-12);
+
+        let at_gmv = frontier.efficient_portfolio(gmv.expected_return).unwrap();
+        assert_approx_equal!(at_gmv.volatility, gmv.volatility, 1e-12);
+    }
+
+    #[test]
+    fn test_tangency_and_capital_market_line() {
+        let frontier = frontier();
+        let tangency = frontier.tangency().unwrap();
+        let sharpe = tangency.sharpe_ratio(0.01);
+
+        // The tangency portfolio is efficient and has the highest Sharpe ratio.
+        let efficient = frontier
+            .efficient_portfolio(tangency.expected_return)
+            .unwrap();
+        assert_approx_equal!(efficient.volatility, tangency.volatility, 1e-12);
+
+        for point in frontier.points(25).unwrap() {
+            assert!(point.sharpe_ratio(0.01) <= sharpe + 1e-12);
+            assert!(
+                frontier.capital_market_line(point.volatility).unwrap()
+                    >= point.expected_return - 1e-12
+            );
+        }
+        assert_approx_equal!(
+            frontier.capital_market_line(tangency.volatility).unwrap(),
+            tangency.expected_return,
+            1e-12
+        );
+
+        // No tangency portfolio above the minimum variance return.
+        assert!(frontier
+            .clone()
+            .with_risk_free_rate(0.2)
+            .tangency()
+            .is_err());
+    }
+
+    #[test]
+    fn test_frontier_errors() {
+        assert!(frontier().points(1).is_err());
+        assert!(
+            EfficientFrontier::new(dvector![0.1, 0.1], dmatrix![0.04, 0.0; 0.0, 0.01])
+                .unwrap()
+                .efficient_portfolio(0.1)
+                .is_err()
+        );
+        assert!(EfficientFrontier::new(dvector![0.1, 0.1], dmatrix![1.0, 1.0; 1.0, 1.0]).is_err());
+    }
+
+    #[cfg(feature = "data")]
+    #[test]
+    fn test_frontier_dataframe() {
+        let df = frontier()
+            .with_asset_names(&["equities", "credit", "rates"])
+            .compute(20)
+            .unwrap();
+
+        assert_eq!(df.shape(), (20, 7));
+        assert!(df.column("credit").is_ok());
+
+        let volatility: Vec<f64> = df
+            .column("volatility")
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+        assert!(volatility.windows(2).all(|w| w[1] > w[0]));
+    }
+}
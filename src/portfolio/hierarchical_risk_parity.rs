@@ -0,0 +1,228 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Hierarchical risk parity (López de Prado, 2016).
+//!
+//! 1. Tree clustering: the assets are clustered hierarchically by the
+//!    Euclidean distance between the columns of the correlation distance
+//!    matrix $\sqrt{(1 - \rho_{ij}) / 2}$ (single linkage in the paper).
+//! 2. Quasi-diagonalization: the assets are reordered so that each cluster
+//!    is contiguous, and similar assets are next to each other.
+//! 3. Recursive bisection: the ordered assets are split in halves, and the
+//!    weight of each half is scaled down in proportion to its variance
+//!    (with inverse-variance weights within the half), down to single
+//!    assets.
+//!
+//! The covariance matrix is never inverted, so the weights are defined
+//! (and stable) even when it is singular or badly estimated, unlike
+//! mean-variance weights.
+//!
+//! ```
+//! use RustQuant::portfolio::*;
+//! use RustQuant::ml::Linkage;
+//! use nalgebra::dmatrix;
+//!
+//! // Two highly correlated assets and a third one.
+//! let covariance = dmatrix![
+//!     0.04, 0.036, 0.002;
+//!     0.036, 0.04, 0.002;
+//!     0.002, 0.002, 0.01
+//! ];
+//!
+//! let hrp = HierarchicalRiskParity::new(&covariance, Linkage::Single).unwrap();
+//!
+//! assert!((hrp.weights.sum() - 1.0).abs() < 1e-12);
+//! assert_eq!(hrp.order, vec![2, 0, 1]);
+//! assert!((hrp.weights[0] - hrp.weights[1]).abs() < 1e-12);
+//! ```
+
+use crate::ml::{
+    correlation_distance, covariance_to_correlation, distance_of_distances,
+    hierarchical_clustering, Dendrogram, Linkage,
+};
+use crate::portfolio::{check_covariance, PortfolioError};
+use nalgebra::{DMatrix, DVector};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Hierarchical risk parity allocation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HierarchicalRiskParity {
+    /// Long-only weights, summing to one (in the original asset order).
+    pub weights: DVector<f64>,
+
+    /// Quasi-diagonal order of the assets.
+    pub order: Vec<usize>,
+
+    /// Hierarchical clustering of the assets.
+    pub dendrogram: Dendrogram,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl HierarchicalRiskParity {
+    /// HRP weights for the covariance matrix of asset returns, clustering
+    /// with the given linkage.
+    pub fn new(covariance: &DMatrix<f64>, linkage: Linkage) -> Result<Self, PortfolioError> {
+        let n = covariance.nrows();
+        check_covariance(covariance, n)?;
+
+        if covariance.diagonal().iter().any(|v| *v <= 0.0) {
+            return Err(PortfolioError::InvalidParameter(
+                "variances must be positive".to_string(),
+            ));
+        }
+
+        let correlation = covariance_to_correlation(covariance);
+        let distance = distance_of_distances(&correlation_distance(&correlation));
+        let dendrogram = hierarchical_clustering(&distance, linkage)
+            .map_err(|e| PortfolioError::InvalidParameter(e.to_string()))?;
+
+        let order = dendrogram.leaf_order();
+        let weights = recursive_bisection(covariance, &order);
+
+        Ok(Self {
+            weights,
+            order,
+            dendrogram,
+        })
+    }
+}
+
+/// Splits the ordered assets in halves recursively, allocating between the
+/// halves in inverse proportion to their variances.
+fn recursive_bisection(covariance: &DMatrix<f64>, order: &[usize]) -> DVector<f64> {
+    let mut weights = DVector::from_element(covariance.nrows(), 1.0);
+    let mut clusters: Vec<&[usize]> = vec![order];
+
+    while let Some(cluster) = clusters.pop() {
+        if cluster.len() < 2 {
+            continue;
+        }
+
+        let (left, right) = cluster.split_at(cluster.len() / 2);
+        let (v_left, v_right) = (
+            cluster_variance(covariance, left),
+            cluster_variance(covariance, right),
+        );
+        let alpha = 1.0 - v_left / (v_left + v_right);
+
+        left.iter().for_each(|&i| weights[i] *= alpha);
+        right.iter().for_each(|&i| weights[i] *= 1.0 - alpha);
+
+        clusters.push(left);
+        clusters.push(right);
+    }
+
+    weights
+}
+
+/// Variance of the inverse-variance portfolio of the assets in `cluster`.
+fn cluster_variance(covariance: &DMatrix<f64>, cluster: &[usize]) -> f64 {
+    let sub = covariance.select_rows(cluster).select_columns(cluster);
+    let inverse_variance = sub.diagonal().map(|v| 1.0 / v);
+    let w = &inverse_variance / inverse_variance.sum();
+
+    w.dot(&(&sub * &w))
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_hierarchical_risk_parity {
+    use super::*;
+    use crate::portfolio::risk_contributions;
+    use nalgebra::{dmatrix, dvector};
+
+    #[test]
+    fn test_uncorrelated_assets_get_inverse_variance_weights() {
+        let variances = [0.04, 0.01, 0.09, 0.0225, 0.0625];
+        let covariance = DMatrix::from_diagonal(&DVector::from_column_slice(&variances));
+
+        let hrp = HierarchicalRiskParity::new(&covariance, Linkage::Single).unwrap();
+        let total: f64 = variances.iter().map(|v| 1.0 / v).sum();
+
+        for (w, v) in hrp.weights.iter().zip(variances) {
+            assert_approx_equal!(*w, 1.0 / v / total, 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_two_assets() {
+        let covariance = dmatrix![0.04, 0.01; 0.01, 0.01];
+        let hrp = HierarchicalRiskParity::new(&covariance, Linkage::Single).unwrap();
+
+        assert_approx_equal!(hrp.weights[0], 0.01 / 0.05, 1e-12);
+        assert_approx_equal!(hrp.weights[1], 0.04 / 0.05, 1e-12);
+    }
+
+    #[test]
+    fn test_block_structure() {
+        // Two sectors (assets 0, 2, 4 and 1, 3) with intra-sector
+        // correlation 0.8 and inter-sector correlation 0.1.
+        let volatilities = dvector![0.2, 0.1, 0.25, 0.12, 0.3];
+        let sector = [0, 1, 0, 1, 0];
+        let covariance = DMatrix::from_fn(5, 5, |i, j| {
+            let rho = match (i == j, sector[i] == sector[j]) {
+                (true, _) => 1.0,
+                (false, true) => 0.8,
+                (false, false) => 0.1,
+            };
+            rho * volatilities[i] * volatilities[j]
+        });
+
+        for linkage in [Linkage::Single, Linkage::Average, Linkage::Ward] {
+            let hrp = HierarchicalRiskParity::new(&covariance, linkage).unwrap();
+
+            assert_approx_equal!(hrp.weights.sum(), 1.0, 1e-12);
+            assert!(hrp.weights.iter().all(|w| *w > 0.0));
+
+            // Sectors are contiguous in the quasi-diagonal order.
+            let sectors: Vec<usize> = hrp.order.iter().map(|&i| sector[i]).collect();
+            assert_eq!(sectors.windows(2).filter(|w| w[0] != w[1]).count(), 1);
+
+            let contributions = risk_contributions(&hrp.weights, &covariance);
+            assert_approx_equal!(contributions.sum(), 1.0, 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_singular_covariance() {
+        // Assets 0 and 1 are identical: mean-variance weights are undefined,
+        // HRP splits the allocation between them.
+        let covariance = dmatrix![
+            0.04, 0.04, 0.006;
+            0.04, 0.04, 0.006;
+            0.006, 0.006, 0.0225
+        ];
+
+        let hrp = HierarchicalRiskParity::new(&covariance, Linkage::Single).unwrap();
+        assert!(hrp.weights.iter().all(|w| w.is_finite() && *w > 0.0));
+        assert_approx_equal!(hrp.weights[0], hrp.weights[1], 1e-12);
+        assert_approx_equal!(hrp.weights.sum(), 1.0, 1e-12);
+    }
+
+    #[test]
+    fn test_invalid_covariance() {
+        assert!(matches!(
+            HierarchicalRiskParity::new(&DMatrix::zeros(2, 3), Linkage::Single),
+            Err(PortfolioError::DimensionMismatch { .. })
+        ));
+        assert!(matches!(
+            HierarchicalRiskParity::new(&dmatrix![0.04, 0.0; 0.0, 0.0], Linkage::Single),
+            Err(PortfolioError::InvalidParameter(_))
+        ));
+    }
+}
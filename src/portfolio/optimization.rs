@@ -0,0 +1,641 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Portfolio construction from a covariance matrix of asset returns.
+//!
+//! - Mean-variance (Markowitz) weights $w = (\delta \Sigma)^{-1} \mu$.
+//! - Black-Litterman (1992): the equilibrium returns implied by the market
+//!   portfolio, $\pi = \delta \Sigma w_{mkt}$, are combined with investor
+//!   views $P \mu = q + \varepsilon$, $\varepsilon \sim N(0, \Omega)$, into
+//!   posterior expected returns and weights.
+//! - Risk parity (equal risk contribution): weights whose contributions
+//!   $w_i (\Sigma w)_i / w' \Sigma w$ to the portfolio variance match a
+//!   risk budget, found by Newton's method (Spinu, 2013).
+//! - Turnover-penalised mean-variance weights, net of the transaction
+//!   costs of rebalancing from the current portfolio.
+//!
+//! ```
+//! use RustQuant::portfolio::*;
+//! use nalgebra::dmatrix;
+//!
+//! let covariance = dmatrix![0.04, 0.006; 0.006, 0.0225];
+//!
+//! // Asset 0 outperforms asset 1 by 5% (the prior spread is 3.45%).
+//! let posterior = BlackLitterman::from_market_caps(covariance.clone(), &[600.0, 400.0], 2.5)
+//!     .unwrap()
+//!     .posterior(&[View::relative(2, 0, 1, 0.05)])
+//!     .unwrap();
+//! assert!(posterior.weights[0] > 0.6 / (1.0 + 0.05));
+//!
+//! let weights = risk_parity_weights(&covariance, None).unwrap();
+//! let contributions = risk_contributions(&weights, &covariance);
+//! assert!((contributions[0] - 0.5).abs() < 1e-10);
+//! ```
+
+use crate::costs::TradingCostRates;
+use nalgebra::{DMatrix, DVector};
+use thiserror::Error;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Portfolio construction errors.
+#[derive(Debug, Error)]
+pub enum PortfolioError {
+    /// Inputs of inconsistent dimensions.
+    #[error("Dimension mismatch: expected {expected}, got {found}")]
+    DimensionMismatch {
+        /// Expected dimension (number of assets).
+        expected: usize,
+        /// Dimension found.
+        found: usize,
+    },
+
+    /// A parameter is outside its domain.
+    #[error("Invalid parameter: {0}")]
+    InvalidParameter(String),
+
+    /// A matrix that has to be inverted is singular.
+    #[error("Singular matrix: {0}")]
+    SingularMatrix(String),
+
+    /// The iteration did not converge.
+    #[error("No convergence after {0} iterations")]
+    NotConverged(usize),
+
+    /// Error building or reading a data frame.
+    #[cfg(feature = "data")]
+    #[error("{0}")]
+    Data(#[from] crate::data::DataError),
+}
+
+/// An investor view on the expected returns, $p' \mu = q$ with
+/// uncertainty $\omega$.
+#[derive(Debug, Clone, PartialEq)]
+pub struct View {
+    /// Pick vector (one entry per asset).
+    pub picks: Vec<f64>,
+
+    /// Expected return of the pick portfolio.
+    pub expected_return: f64,
+
+    /// Variance of the view. `None` sets it proportional to the prior
+    /// variance of the pick portfolio, $p' \tau \Sigma p$ (He and
+    /// Litterman, 1999).
+    pub variance: Option<f64>,
+}
+
+/// Black-Litterman model: market equilibrium prior and investor views.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlackLitterman {
+    /// Covariance matrix of asset returns.
+    covariance: DMatrix<f64>,
+
+    /// Market portfolio weights.
+    market_weights: DVector<f64>,
+
+    /// Risk aversion coefficient (delta).
+    risk_aversion: f64,
+
+    /// Scaling of the prior uncertainty (tau).
+    tau: f64,
+}
+
+/// Black-Litterman posterior.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlackLittermanPosterior {
+    /// Posterior expected returns.
+    pub expected_returns: DVector<f64>,
+
+    /// Posterior covariance of returns (asset plus estimation risk).
+    pub covariance: DMatrix<f64>,
+
+    /// Mean-variance weights for the posterior returns and covariance.
+    pub weights: DVector<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS, TRAITS, AND FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl View {
+    /// View with an arbitrary pick vector.
+    pub fn new(picks: Vec<f64>, expected_return: f64) -> Self {
+        Self {
+            picks,
+            expected_return,
+            variance: None,
+        }
+    }
+
+    /// Absolute view: asset `asset` (of `n_assets`) returns `expected_return`.
+    pub fn absolute(n_assets: usize, asset: usize, expected_return: f64) -> Self {
+        let mut picks = vec![0.0; n_assets];
+        picks[asset] = 1.0;
+
+        Self::new(picks, expected_return)
+    }
+
+    /// Relative view: asset `long` outperforms asset `short` by
+    /// `outperformance`.
+    pub fn relative(n_assets: usize, long: usize, short: usize, outperformance: f64) -> Self {
+        let mut picks = vec![0.0; n_assets];
+        picks[long] = 1.0;
+        picks[short] = -1.0;
+
+        Self::new(picks, outperformance)
+    }
+
+    /// Sets the variance of the view (smaller is more confident).
+    pub fn with_variance(mut self, variance: f64) -> Self {
+        assert!(variance > 0.0);
+
+        self.variance = Some(variance);
+        self
+    }
+}
+
+impl BlackLitterman {
+    /// New Black-Litterman model from the covariance of returns, the market
+    /// portfolio weights and the risk aversion, with $\tau = 0.05$.
+    pub fn new(
+        covariance: DMatrix<f64>,
+        market_weights: &[f64],
+        risk_aversion: f64,
+    ) -> Result<Self, PortfolioError> {
+        check_covariance(&covariance, market_weights.len())?;
+
+        if risk_aversion <= 0.0 {
+            return Err(PortfolioError::InvalidParameter(
+                "risk aversion must be positive".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            covariance,
+            market_weights: DVector::from_column_slice(market_weights),
+            risk_aversion,
+            tau: 0.05,
+        })
+    }
+
+    /// New Black-Litterman model with market weights proportional to the
+    /// market capitalisations.
+    pub fn from_market_caps(
+        covariance: DMatrix<f64>,
+        market_caps: &[f64],
+        risk_aversion: f64,
+    ) -> Result<Self, PortfolioError> {
+        let total: f64 = market_caps.iter().sum();
+
+        if market_caps.iter().any(|cap| *cap < 0.0) || total <= 0.0 {
+            return Err(PortfolioError::InvalidParameter(
+                "market capitalisations must be non-negative with a positive total".to_string(),
+            ));
+        }
+
+        let weights: Vec<f64> = market_caps.iter().map(|cap| cap / total).collect();
+
+        Self::new(covariance, &weights, risk_aversion)
+    }
+
+    /// Sets the scaling $\tau$ of the prior uncertainty of the mean.
+    pub fn with_tau(mut self, tau: f64) -> Self {
+        assert!(tau > 0.0);
+
+        self.tau = tau;
+        self
+    }
+
+    /// Equilibrium returns implied by the market portfolio,
+    /// $\pi = \delta \Sigma w_{mkt}$.
+    pub fn implied_returns(&self) -> DVector<f64> {
+        &self.covariance * &self.market_weights * self.risk_aversion
+    }
+
+    /// Posterior expected returns, covariance and weights given the views.
+    pub fn posterior(&self, views: &[View]) -> Result<BlackLittermanPosterior, PortfolioError> {
+        let n = self.market_weights.len();
+        let prior = self.implied_returns();
+        let tau_sigma = &self.covariance * self.tau;
+
+        let (expected_returns, covariance) = match views.is_empty() {
+            true => (prior, &self.covariance + &tau_sigma),
+            false => {
+                if let Some(view) = views.iter().find(|view| view.picks.len() != n) {
+                    return Err(PortfolioError::DimensionMismatch {
+                        expected: n,
+                        found: view.picks.len(),
+                    });
+                }
+
+                let p = DMatrix::from_fn(views.len(), n, |k, i| views[k].picks[i]);
+                let q =
+                    DVector::from_iterator(views.len(), views.iter().map(|v| v.expected_return));
+                let view_prior = &p * &tau_sigma * p.transpose();
+                let omega = DMatrix::from_diagonal(&DVector::from_iterator(
+                    views.len(),
+                    views
+                        .iter()
+                        .enumerate()
+                        .map(|(k, view)| view.variance.unwrap_or(view_prior[(k, k)])),
+                ));
+
+                // Kalman gain of the views: tau Sigma P' (P tau Sigma P' + Omega)^-1.
+                let gain = &tau_sigma
+                    * p.transpose()
+                    * (view_prior + omega).try_inverse().ok_or_else(|| {
+                        PortfolioError::SingularMatrix("view covariance".to_string())
+                    })?;
+
+                (
+                    &prior + &gain * (q - &p * &prior),
+                    &self.covariance + &tau_sigma - gain * p * &tau_sigma,
+                )
+            }
+        };
+
+        let weights = mean_variance_weights(&expected_returns, &covariance, self.risk_aversion)?;
+
+        Ok(BlackLittermanPosterior {
+            expected_returns,
+            covariance,
+            weights,
+        })
+    }
+}
+
+/// Unconstrained mean-variance (Markowitz) weights,
+/// $w = (\delta \Sigma)^{-1} \mu$, maximising $w' \mu - \frac{\delta}{2} w' \Sigma w$.
+pub fn mean_variance_weights(
+    expected_returns: &DVector<f64>,
+    covariance: &DMatrix<f64>,
+    risk_aversion: f64,
+) -> Result<DVector<f64>, PortfolioError> {
+    check_covariance(covariance, expected_returns.len())?;
+
+    covariance
+        .clone()
+        .cholesky()
+        .map(|cholesky| cholesky.solve(expected_returns) / risk_aversion)
+        .ok_or_else(|| PortfolioError::SingularMatrix("covariance".to_string()))
+}
+
+/// Mean-variance weights net of the costs of trading from the current
+/// weights $w_0$, maximising
+/// $w' \mu - \frac{\delta}{2} w' \Sigma w - \sum_i c_i(w_i - w_{0,i})$
+/// with per-asset costs $c_i(\Delta) = a_i |\Delta| + b_i |\Delta|^{3/2}$.
+///
+/// Solved by cyclic coordinate descent: each coordinate update minimises a
+/// one-dimensional convex function in closed form. Small expected gains
+/// leave positions untouched (a no-trade region), so turnover falls as the
+/// costs rise.
+///
+/// ```
+/// use RustQuant::costs::*;
+/// use RustQuant::portfolio::*;
+/// use nalgebra::{dmatrix, dvector};
+///
+/// let covariance = dmatrix![0.04, 0.0; 0.0, 0.01];
+/// let expected_returns = dvector![0.08, 0.03];
+/// let current = dvector![1.0, 1.0];
+///
+/// // 10bp commission and a 20bp spread on both assets.
+/// let costs = TransactionCosts::new()
+///     .with_commission(Commission::Proportional(0.001))
+///     .with_spread(0.002);
+/// let rates = [costs.rates(100.0, 1e6, 1e6); 2];
+///
+/// let weights =
+///     turnover_penalized_weights(&expected_returns, &covariance, 2.0, &current, &rates).unwrap();
+///
+/// // The first asset stays in the no-trade region, the second is bought
+/// // up to (0.03 - 0.002) / (2 * 0.01) instead of 1.5.
+/// assert_eq!(weights[0], 1.0);
+/// assert!((weights[1] - 1.4).abs() < 1e-12);
+/// ```
+pub fn turnover_penalized_weights(
+    expected_returns: &DVector<f64>,
+    covariance: &DMatrix<f64>,
+    risk_aversion: f64,
+    current_weights: &DVector<f64>,
+    costs: &[TradingCostRates],
+) -> Result<DVector<f64>, PortfolioError> {
+    const MAX_ITER: usize = 10_000;
+
+    let n = expected_returns.len();
+    check_covariance(covariance, n)?;
+
+    for found in [current_weights.len(), costs.len()] {
+        if found != n {
+            return Err(PortfolioError::DimensionMismatch { expected: n, found });
+        }
+    }
+    if risk_aversion <= 0.0 {
+        return Err(PortfolioError::InvalidParameter(
+            "risk aversion must be positive".to_string(),
+        ));
+    }
+    if costs.iter().any(|c| c.linear < 0.0 || c.impact < 0.0) {
+        return Err(PortfolioError::InvalidParameter(
+            "cost rates must be non-negative".to_string(),
+        ));
+    }
+    if covariance
+        .diagonal()
+        .iter()
+        .any(|variance| *variance <= 0.0)
+    {
+        return Err(PortfolioError::SingularMatrix("covariance".to_string()));
+    }
+
+    let mut weights = current_weights.clone();
+
+    for _ in 0..MAX_ITER {
+        let mut largest_step: f64 = 0.0;
+
+        for i in 0..n {
+            // Minimise 1/2 A w^2 - B w + a |w - w0| + b |w - w0|^(3/2).
+            let curvature = risk_aversion * covariance[(i, i)];
+            let slope = expected_returns[i] - risk_aversion * covariance.column(i).dot(&weights)
+                + curvature * weights[i];
+            let w0 = current_weights[i];
+            let gradient = curvature * w0 - slope;
+            let TradingCostRates { linear, impact } = costs[i];
+
+            // Outside the no-trade region |A w0 - B| <= a, the trade size
+            // s^2 solves A s^2 + 3/2 b s - (|A w0 - B| - a) = 0.
+            let excess = gradient.abs() - linear;
+            let weight = match excess > 0.0 {
+                true => {
+                    let s = (-1.5 * impact
+                        + (2.25 * impact * impact + 4.0 * curvature * excess).sqrt())
+                        / (2.0 * curvature);
+                    w0 - gradient.signum() * s * s
+                }
+                false => w0,
+            };
+
+            largest_step = largest_step.max((weight - weights[i]).abs());
+            weights[i] = weight;
+        }
+
+        if largest_step < 1e-14 {
+            return Ok(weights);
+        }
+    }
+
+    Err(PortfolioError::NotConverged(MAX_ITER))
+}
+
+/// Fractions of the portfolio variance contributed by each asset,
+/// $w_i (\Sigma w)_i / w' \Sigma w$ (summing to one).
+pub fn risk_contributions(weights: &DVector<f64>, covariance: &DMatrix<f64>) -> DVector<f64> {
+    let marginal = covariance * weights;
+
+    weights.component_mul(&marginal) / weights.dot(&marginal)
+}
+
+/// Long-only weights whose risk contributions match the risk budgets
+/// (equal risk contribution if `budgets` is `None`).
+///
+/// Solves $\Sigma y = b / y$ for $y > 0$, the minimiser of
+/// $\frac{1}{2} y' \Sigma y - \sum_i b_i \ln y_i$, by damped Newton
+/// iteration, and normalises $w = y / \sum_i y_i$.
+pub fn risk_parity_weights(
+    covariance: &DMatrix<f64>,
+    budgets: Option<&[f64]>,
+) -> Result<DVector<f64>, PortfolioError> {
+    const MAX_ITER: usize = 100;
+
+    let n = covariance.nrows();
+    check_covariance(covariance, n)?;
+
+    let b = match budgets {
+        Some(budgets) if budgets.len() != n => {
+            return Err(PortfolioError::DimensionMismatch {
+                expected: n,
+                found: budgets.len(),
+            })
+        }
+        Some(budgets) if budgets.iter().any(|b| *b <= 0.0) => {
+            return Err(PortfolioError::InvalidParameter(
+                "risk budgets must be positive".to_string(),
+            ))
+        }
+        Some(budgets) => DVector::from_column_slice(budgets) / budgets.iter().sum::<f64>(),
+        None => DVector::from_element(n, 1.0 / n as f64),
+    };
+
+    // Starting point on the solution ray for equal budgets and a diagonal
+    // covariance, scaled so that y' Sigma y = 1.
+    let mut y = covariance.diagonal().map(|variance| 1.0 / variance.sqrt());
+    y /= (y.dot(&(covariance * &y))).sqrt();
+
+    for _ in 0..MAX_ITER {
+        let gradient = covariance * &y - b.component_div(&y);
+        let hessian = covariance + DMatrix::from_diagonal(&b.component_div(&y.component_mul(&y)));
+
+        let step = hessian
+            .cholesky()
+            .ok_or_else(|| PortfolioError::SingularMatrix("Newton system".to_string()))?
+            .solve(&gradient);
+        let decrement = gradient.dot(&step).max(0.0).sqrt();
+
+        // Damped steps keep y positive far from the solution (self-concordance).
+        match decrement > 0.25 {
+            true => y -= step / (1.0 + decrement),
+            false => y -= step,
+        }
+
+        if decrement < 1e-12 {
+            return Ok(&y / y.sum());
+        }
+    }
+
+    Err(PortfolioError::NotConverged(MAX_ITER))
+}
+
+/// Checks that the covariance matrix is `n x n`.
+pub(crate) fn check_covariance(covariance: &DMatrix<f64>, n: usize) -> Result<(), PortfolioError> {
+    match (covariance.nrows(), covariance.ncols()) {
+        (rows, cols) if rows == n && cols == n && n > 0 => Ok(()),
+        (rows, cols) => Err(PortfolioError::DimensionMismatch {
+            expected: n,
+            found: match rows == n {
+                true => cols,
+                false => rows,
+            },
+        }),
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_optimization {
+    use super::*;
+    use nalgebra::dmatrix;
+
+    fn covariance() -> DMatrix<f64> {
+        // Volatilities 20%, 15%, 10%, correlations 0.3, 0.1, 0.5.
+        dmatrix![
+            0.04, 0.009, 0.002;
+            0.009, 0.0225, 0.0075;
+            0.002, 0.0075, 0.01
+        ]
+    }
+
+    #[test]
+    fn test_black_litterman_without_views() {
+        let bl = BlackLitterman::from_market_caps(covariance(), &[5.0, 3.0, 2.0], 2.5).unwrap();
+        let posterior = bl.posterior(&[]).unwrap();
+
+        // The prior is recovered, with weights w_mkt / (1 + tau).
+        let implied = bl.implied_returns();
+        assert_approx_equal!(
+            implied[0],
+            2.5 * (0.04 * 0.5 + 0.009 * 0.3 + 0.002 * 0.2),
+            1e-12
+        );
+        assert_eq!(posterior.expected_returns, implied);
+
+        for (w, w_mkt) in posterior.weights.iter().zip([0.5, 0.3, 0.2]) {
+            assert_approx_equal!(*w, w_mkt / 1.05, 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_black_litterman_views() {
+        let bl = BlackLitterman::new(covariance(), &[0.5, 0.3, 0.2], 2.5)
+            .unwrap()
+            .with_tau(0.025);
+        let prior = bl.implied_returns();
+
+        // A near-certain view is matched by the posterior.
+        let view = View::relative(3, 2, 0, 0.03).with_variance(1e-12);
+        let posterior = bl.posterior(&[view]).unwrap();
+        let spread = posterior.expected_returns[2] - posterior.expected_returns[0];
+        assert_approx_equal!(spread, 0.03, 1e-8);
+
+        // A default-confidence view moves the posterior between the
+        // prior and the view, and tilts the weights towards it.
+        let view = View::absolute(3, 1, 0.08);
+        let posterior = bl.posterior(&[view]).unwrap();
+        assert!(posterior.expected_returns[1] > prior[1]);
+        assert!(posterior.expected_returns[1] < 0.08);
+        assert!(posterior.weights[1] > 0.3 / 1.025);
+
+        // With Omega = P tau Sigma P', the view gets half the weight.
+        assert_approx_equal!(
+            posterior.expected_returns[1],
+            0.5 * (prior[1] + 0.08),
+            1e-12
+        );
+
+        assert!(matches!(
+            bl.posterior(&[View::absolute(2, 0, 0.1)]),
+            Err(PortfolioError::DimensionMismatch {
+                expected: 3,
+                found: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn test_risk_parity_weights() {
+        let sigma = covariance();
+
+        let weights = risk_parity_weights(&sigma, None).unwrap();
+        let contributions = risk_contributions(&weights, &sigma);
+
+        assert_approx_equal!(weights.sum(), 1.0, 1e-12);
+        for contribution in contributions.iter() {
+            assert_approx_equal!(*contribution, 1.0 / 3.0, 1e-10);
+        }
+
+        // Uncorrelated assets: weights proportional to 1 / volatility.
+        let diagonal = dmatrix![0.04, 0.0; 0.0, 0.01];
+        let weights = risk_parity_weights(&diagonal, None).unwrap();
+        assert_approx_equal!(weights[0], 1.0 / 3.0, 1e-12);
+
+        // Risk budgets.
+        let weights = risk_parity_weights(&sigma, Some(&[0.5, 0.3, 0.2])).unwrap();
+        let contributions = risk_contributions(&weights, &sigma);
+        for (contribution, budget) in contributions.iter().zip([0.5, 0.3, 0.2]) {
+            assert_approx_equal!(*contribution, budget, 1e-10);
+        }
+
+        assert!(risk_parity_weights(&sigma, Some(&[0.5, 0.5])).is_err());
+        assert!(risk_parity_weights(&sigma, Some(&[1.0, 0.0, 1.0])).is_err());
+    }
+
+    #[test]
+    fn test_turnover_penalized_weights() {
+        let sigma = covariance();
+        let mu = DVector::from_column_slice(&[0.07, 0.05, 0.03]);
+        let current = DVector::from_column_slice(&[0.3, 0.3, 0.4]);
+        let optimal = mean_variance_weights(&mu, &sigma, 3.0).unwrap();
+
+        // Without costs the mean-variance weights are recovered.
+        let free = [TradingCostRates::default(); 3];
+        let weights = turnover_penalized_weights(&mu, &sigma, 3.0, &current, &free).unwrap();
+        for (w, w_star) in weights.iter().zip(optimal.iter()) {
+            assert_approx_equal!(*w, *w_star, 1e-10);
+        }
+
+        // Turnover falls as the costs rise, down to no trading at all.
+        let turnover = |rates: TradingCostRates| {
+            let weights =
+                turnover_penalized_weights(&mu, &sigma, 3.0, &current, &[rates; 3]).unwrap();
+            (weights - &current).abs().sum()
+        };
+        let linear = turnover(TradingCostRates::new(0.002, 0.0));
+        let impact = turnover(TradingCostRates::new(0.002, 0.01));
+        assert!(linear < (&optimal - &current).abs().sum());
+        assert!(impact < linear);
+        assert_eq!(turnover(TradingCostRates::new(1.0, 0.0)), 0.0);
+
+        // Optimality: the marginal cost of each trade offsets its marginal
+        // gain, mu - delta Sigma w = c'(w - w0).
+        let rates = TradingCostRates::new(0.002, 0.01);
+        let weights = turnover_penalized_weights(&mu, &sigma, 3.0, &current, &[rates; 3]).unwrap();
+        let gain = &mu - 3.0 * &sigma * &weights;
+        for i in 0..3 {
+            let trade = weights[i] - current[i];
+            match trade == 0.0 {
+                true => assert!(gain[i].abs() <= rates.linear),
+                false => assert_approx_equal!(
+                    gain[i],
+                    trade.signum() * (rates.linear + 1.5 * rates.impact * trade.abs().sqrt()),
+                    1e-12
+                ),
+            }
+        }
+
+        assert!(turnover_penalized_weights(&mu, &sigma, 3.0, &current, &free[..2]).is_err());
+        assert!(turnover_penalized_weights(&mu, &sigma, 0.0, &current, &free).is_err());
+    }
+
+    #[test]
+    fn test_mean_variance_weights() {
+        let sigma = dmatrix![0.04, 0.0; 0.0, 0.01];
+        let mu = DVector::from_column_slice(&[0.08, 0.03]);
+
+        let weights = mean_variance_weights(&mu, &sigma, 2.0).unwrap();
+        assert_approx_equal!(weights[0], 1.0, 1e-12);
+        assert_approx_equal!(weights[1], 1.5, 1e-12);
+
+        let singular = dmatrix![1.0, 1.0; 1.0, 1.0];
+        assert!(mean_variance_weights(&mu, &singular, 2.0).is_err());
+    }
+}
@@ -0,0 +1,185 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::curves::{Curve, CurveModel, NelsonSiegel, YieldCurve};
+use crate::python::date_after;
+use crate::time::Tenor;
+use numpy::{IntoPyArray, PyArray1, PyReadonlyArray1};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use time::OffsetDateTime;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Python `YieldCurve`: a [`YieldCurve`] starting today, queried at
+/// maturities in years. The rate of the shortest tenor is extended flat
+/// back to today.
+#[pyclass(name = "YieldCurve")]
+pub struct PyYieldCurve {
+    curve: YieldCurve,
+    initial_date: OffsetDateTime,
+}
+
+/// Python `NelsonSiegel`: a [`NelsonSiegel`] curve model, queried at
+/// maturities in years from today.
+#[pyclass(name = "NelsonSiegel")]
+pub struct PyNelsonSiegel {
+    model: NelsonSiegel,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[pymethods]
+impl PyYieldCurve {
+    /// Curve with the `rates` at the `tenors` (e.g. `"3M"`, `"1Y"`) from
+    /// today.
+    #[new]
+    fn new(tenors: Vec<String>, rates: Vec<f64>) -> PyResult<Self> {
+        if tenors.len() != rates.len() {
+            return Err(PyValueError::new_err(format!(
+                "{} tenors but {} rates",
+                tenors.len(),
+                rates.len()
+            )));
+        }
+
+        let tenors = tenors
+            .iter()
+            .map(|tenor| tenor.parse::<Tenor>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+        let initial_date = OffsetDateTime::now_utc();
+        let mut curve = YieldCurve::from_tenors_and_rates(initial_date, &tenors, &rates);
+
+        // Discount factors are measured from the curve's first date.
+        if let Some((_, &rate)) = curve.rates.iter().next() {
+            curve.rates.entry(initial_date).or_insert(rate);
+        }
+
+        Ok(Self {
+            curve,
+            initial_date,
+        })
+    }
+
+    /// Interpolated rate at the maturity (in years).
+    fn rate(&self, maturity: f64) -> PyResult<f64> {
+        Ok(self.curve.rate(self.date(maturity)?))
+    }
+
+    /// Discount factor at the maturity (in years).
+    fn discount_factor(&self, maturity: f64) -> PyResult<f64> {
+        Ok(self.curve.discount_factor(self.date(maturity)?))
+    }
+
+    /// Discount factors at an array of maturities (in years).
+    fn discount_factors<'py>(
+        &self,
+        py: Python<'py>,
+        maturities: PyReadonlyArray1<'py, f64>,
+    ) -> PyResult<&'py PyArray1<f64>> {
+        let dates = maturities
+            .as_array()
+            .iter()
+            .map(|&maturity| self.date(maturity))
+            .collect::<PyResult<Vec<_>>>()?;
+
+        Ok(self.curve.discount_factors(&dates).into_pyarray(py))
+    }
+}
+
+impl PyYieldCurve {
+    /// Date of the maturity, which must be within the curve.
+    fn date(&self, maturity: f64) -> PyResult<OffsetDateTime> {
+        let date = date_after(self.initial_date, maturity);
+
+        if maturity < 0.0 || date > self.curve.terminal_date() {
+            return Err(PyValueError::new_err(format!(
+                "maturity {maturity} is outside the curve"
+            )));
+        }
+
+        Ok(date)
+    }
+}
+
+#[pymethods]
+impl PyNelsonSiegel {
+    /// Nelson-Siegel (1987) model with level `beta0`, slope `beta1`,
+    /// curvature `beta2` and decay `lambda`.
+    #[new]
+    fn new(beta0: f64, beta1: f64, beta2: f64, lambda: f64) -> Self {
+        Self {
+            model: NelsonSiegel::new(beta0, beta1, beta2, lambda),
+        }
+    }
+
+    /// Spot rate at the maturity (in years, positive).
+    fn spot_rate(&self, maturity: f64) -> f64 {
+        self.model.spot_rate(Self::date(maturity))
+    }
+
+    /// Instantaneous forward rate at the maturity (in years, positive).
+    fn forward_rate(&self, maturity: f64) -> f64 {
+        self.model.forward_rate(Self::date(maturity))
+    }
+
+    /// Discount factor at the maturity (in years, positive).
+    fn discount_factor(&self, maturity: f64) -> f64 {
+        self.model.discount_factor(Self::date(maturity))
+    }
+}
+
+impl PyNelsonSiegel {
+    fn date(maturity: f64) -> OffsetDateTime {
+        date_after(OffsetDateTime::now_utc(), maturity)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_curves {
+    use super::*;
+
+    #[test]
+    fn test_yield_curve() {
+        let curve =
+            PyYieldCurve::new(vec!["1Y".to_string(), "2Y".to_string()], vec![0.03, 0.04]).unwrap();
+
+        assert_approx_equal!(curve.rate(1.0).unwrap(), 0.03, 1e-3);
+        assert_approx_equal!(curve.rate(0.25).unwrap(), 0.03, 1e-12);
+        assert_approx_equal!(curve.discount_factor(0.0).unwrap(), 1.0, 1e-12);
+        assert_approx_equal!(
+            curve.discount_factor(2.0).unwrap(),
+            (-0.04_f64 * 2.0).exp(),
+            1e-3
+        );
+        assert!(curve.rate(3.0).is_err());
+
+        assert!(PyYieldCurve::new(vec!["1Q".to_string()], vec![0.03]).is_err());
+        assert!(PyYieldCurve::new(vec!["1Y".to_string()], vec![]).is_err());
+    }
+
+    #[test]
+    fn test_nelson_siegel() {
+        let model = PyNelsonSiegel::new(0.05, -0.02, 0.01, 1.5);
+
+        // The long end converges to the level.
+        assert_approx_equal!(model.spot_rate(100.0), 0.05, 1e-3);
+        assert!(model.discount_factor(10.0) < 1.0);
+    }
+}
@@ -0,0 +1,147 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::data::{
+    Data, DataError, DataFormat, DataReader, DataWriter, YahooFinanceData, YahooFinanceError,
+    YahooFinanceReader,
+};
+use pyo3::exceptions::{PyIOError, PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3_polars::PyDataFrame;
+use time::OffsetDateTime;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl From<DataError> for PyErr {
+    fn from(error: DataError) -> Self {
+        match error {
+            DataError::Io(e) => PyIOError::new_err(e.to_string()),
+            DataError::Polars(e) => PyValueError::new_err(e.to_string()),
+        }
+    }
+}
+
+impl From<YahooFinanceError> for PyErr {
+    fn from(error: YahooFinanceError) -> Self {
+        PyRuntimeError::new_err(error.to_string())
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// The data format named `format` (`"csv"`, `"json"` or `"parquet"`), or
+/// given by the extension of `path` if `format` is `None`.
+pub(crate) fn data_format(path: &str, format: Option<&str>) -> PyResult<DataFormat> {
+    let format = match format {
+        Some(format) => format.to_ascii_lowercase(),
+        None => std::path::Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase(),
+    };
+
+    match format.as_str() {
+        "csv" => Ok(DataFormat::CSV),
+        "json" => Ok(DataFormat::JSON),
+        "parquet" | "pq" => Ok(DataFormat::PARQUET),
+        _ => Err(PyValueError::new_err(format!(
+            "unknown data format '{format}' (expected 'csv', 'json' or 'parquet')"
+        ))),
+    }
+}
+
+/// Reads a CSV, JSON or Parquet file into a py-polars `DataFrame`. The
+/// format is given by the file extension unless `format` is set.
+#[pyfunction]
+#[pyo3(signature = (path, format = None))]
+pub fn read_data(path: &str, format: Option<&str>) -> PyResult<PyDataFrame> {
+    let mut data = Data::new(data_format(path, format)?, path.to_string());
+    data.read()?;
+
+    Ok(PyDataFrame(data.data))
+}
+
+/// Writes a py-polars `DataFrame` to a CSV, JSON or Parquet file. The
+/// format is given by the file extension unless `format` is set.
+#[pyfunction]
+#[pyo3(signature = (frame, path, format = None))]
+pub fn write_data(frame: PyDataFrame, path: &str, format: Option<&str>) -> PyResult<()> {
+    let mut data = Data::new(data_format(path, format)?, path.to_string());
+    data.data = frame.0;
+
+    Ok(data.write()?)
+}
+
+/// Daily price history of the ticker from Yahoo! Finance, as a py-polars
+/// `DataFrame`. The `start` and `end` are `datetime`s, and default to the
+/// earliest available date and now.
+#[pyfunction]
+#[pyo3(signature = (ticker, start = None, end = None))]
+pub fn yahoo_price_history(
+    py: Python<'_>,
+    ticker: &str,
+    start: Option<&PyAny>,
+    end: Option<&PyAny>,
+) -> PyResult<PyDataFrame> {
+    let mut yahoo = YahooFinanceData::new(ticker.to_string());
+
+    if let Some(start) = start {
+        yahoo.set_start_date(to_offset_date_time(start)?);
+    }
+    if let Some(end) = end {
+        yahoo.set_end_date(to_offset_date_time(end)?);
+    }
+
+    py.allow_threads(|| yahoo.get_price_history())?;
+
+    yahoo
+        .price_history
+        .map(PyDataFrame)
+        .ok_or_else(|| PyRuntimeError::new_err(format!("no price history for '{ticker}'")))
+}
+
+/// Converts a Python `datetime` (naive ones are taken as local time).
+fn to_offset_date_time(datetime: &PyAny) -> PyResult<OffsetDateTime> {
+    let timestamp: f64 = datetime.call_method0("timestamp")?.extract()?;
+
+    OffsetDateTime::from_unix_timestamp(timestamp.floor() as i64)
+        .map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_data {
+    use super::*;
+
+    #[test]
+    fn test_data_format() {
+        assert!(matches!(data_format("a/b.CSV", None), Ok(DataFormat::CSV)));
+        assert!(matches!(
+            data_format("prices", Some("parquet")),
+            Ok(DataFormat::PARQUET)
+        ));
+        assert!(data_format("prices.xlsx", None).is_err());
+    }
+
+    #[test]
+    fn test_read_data() {
+        let frame = read_data("./src/data/examples/example.csv", None).unwrap();
+        assert_eq!(frame.0.width(), 13);
+
+        assert!(read_data("./src/data/examples/missing.csv", None).is_err());
+    }
+}
@@ -0,0 +1,98 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Python bindings (requires the `python` feature, which implies `data`).
+//!
+//! The `rustquant` extension module is built with `maturin` from the
+//! `bindings` directory:
+//!
+//! ```bash
+//! cd bindings && maturin develop --release
+//! ```
+//!
+//! It exposes:
+//!
+//! - Pricers: `black_scholes`, `black_scholes_greeks`, `implied_volatility`
+//!   and `heston`. The Black-Scholes functions also accept NumPy arrays,
+//!   and are then evaluated element-wise.
+//! - Curves: `YieldCurve` and `NelsonSiegel`.
+//! - Processes: `GeometricBrownianMotion`, `ArithmeticBrownianMotion`,
+//!   `OrnsteinUhlenbeck` and `CoxIngersollRoss`, whose `simulate` returns
+//!   the time grid and paths as NumPy arrays, and `simulate_frame` a
+//!   py-polars `DataFrame` in long format.
+//! - Data: `read_data`, `write_data` and `yahoo_price_history`, returning
+//!   py-polars `DataFrame`s.
+//!
+//! Times to expiry and maturities are year fractions. Where the Rust API
+//! takes dates, they are measured from today and rounded to whole days
+//! (Actual/365 Fixed).
+//!
+//! ```python
+//! import numpy as np
+//! import rustquant as rq
+//!
+//! rq.black_scholes(100.0, np.linspace(80, 120, 5), 0.2, 0.05, 1.0, "call")
+//!
+//! times, paths = rq.GeometricBrownianMotion(0.05, 0.2).simulate(100.0, 0.0, 1.0, 252, 1000)
+//! ```
+
+// The `#[pymethods]` expansion of pyo3 0.19 predates this lint.
+#![allow(non_local_definitions)]
+
+use pyo3::prelude::*;
+
+/// Term structures.
+pub mod curves;
+pub use curves::*;
+
+/// Data readers and writers.
+pub mod data;
+pub use data::*;
+
+/// Option pricers.
+pub mod pricers;
+pub use pricers::*;
+
+/// Stochastic process simulation.
+pub mod stochastics;
+pub use stochastics::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// The `rustquant` Python module.
+#[pymodule]
+fn rustquant(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
+    m.add("__version__", env!("CARGO_PKG_VERSION"))?;
+
+    m.add_function(wrap_pyfunction!(black_scholes, m)?)?;
+    m.add_function(wrap_pyfunction!(black_scholes_greeks, m)?)?;
+    m.add_function(wrap_pyfunction!(implied_volatility, m)?)?;
+    m.add_function(wrap_pyfunction!(heston, m)?)?;
+
+    m.add_class::<PyYieldCurve>()?;
+    m.add_class::<PyNelsonSiegel>()?;
+
+    m.add_class::<PyGeometricBrownianMotion>()?;
+    m.add_class::<PyArithmeticBrownianMotion>()?;
+    m.add_class::<PyOrnsteinUhlenbeck>()?;
+    m.add_class::<PyCoxIngersollRoss>()?;
+
+    m.add_function(wrap_pyfunction!(read_data, m)?)?;
+    m.add_function(wrap_pyfunction!(write_data, m)?)?;
+    m.add_function(wrap_pyfunction!(yahoo_price_history, m)?)?;
+
+    Ok(())
+}
+
+/// The date `years` after `start`, rounded to whole days.
+pub(crate) fn date_after(start: time::OffsetDateTime, years: f64) -> time::OffsetDateTime {
+    start + time::Duration::days((years * 365.0).round() as i64)
+}
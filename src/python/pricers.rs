@@ -0,0 +1,416 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::instruments::options::{BlackScholesInputs, TypeFlag};
+use crate::python::date_after;
+use numpy::{IntoPyArray, PyReadonlyArray1};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::HashMap;
+use time::OffsetDateTime;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// A pricer argument or result: a float, or a one-dimensional NumPy array
+/// (or list) of floats.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Values {
+    /// A single value, broadcast against the arrays.
+    Scalar(f64),
+    /// One value per option.
+    Array(Vec<f64>),
+}
+
+/// A price or Greek of the Black-Scholes inputs.
+type Greek = fn(&BlackScholesInputs) -> f64;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl<'source> FromPyObject<'source> for Values {
+    fn extract(ob: &'source PyAny) -> PyResult<Self> {
+        if let Ok(value) = ob.extract::<f64>() {
+            return Ok(Values::Scalar(value));
+        }
+        if let Ok(array) = ob.extract::<PyReadonlyArray1<f64>>() {
+            return Ok(Values::Array(array.as_array().to_vec()));
+        }
+
+        Ok(Values::Array(ob.extract::<Vec<f64>>()?))
+    }
+}
+
+impl IntoPy<PyObject> for Values {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        match self {
+            Values::Scalar(value) => value.into_py(py),
+            Values::Array(values) => values.into_pyarray(py).into_py(py),
+        }
+    }
+}
+
+impl Values {
+    fn get(&self, i: usize) -> f64 {
+        match self {
+            Values::Scalar(value) => *value,
+            Values::Array(values) => values[i],
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Parses `"call"` or `"put"` (in any case).
+pub(crate) fn parse_option_type(option_type: &str) -> PyResult<TypeFlag> {
+    match option_type.to_ascii_lowercase().as_str() {
+        "call" | "c" => Ok(TypeFlag::Call),
+        "put" | "p" => Ok(TypeFlag::Put),
+        _ => Err(PyValueError::new_err(format!(
+            "option_type must be 'call' or 'put', not '{option_type}'"
+        ))),
+    }
+}
+
+/// Common length of the array arguments, or `None` if all are scalars.
+pub(crate) fn broadcast_len(args: &[&Values]) -> PyResult<Option<usize>> {
+    let mut len = None;
+
+    for arg in args {
+        if let Values::Array(values) = arg {
+            match len {
+                Some(n) if n != values.len() => {
+                    return Err(PyValueError::new_err(format!(
+                        "array arguments have different lengths ({n} and {})",
+                        values.len()
+                    )))
+                }
+                _ => len = Some(values.len()),
+            }
+        }
+    }
+
+    Ok(len)
+}
+
+/// Evaluates `f` on the Black-Scholes inputs, as a scalar if every argument
+/// is a scalar and as an array otherwise.
+fn evaluate<F>(args: [&Values; 6], option_type: &str, f: F) -> PyResult<Values>
+where
+    F: Fn(&BlackScholesInputs) -> f64,
+{
+    let option_type = parse_option_type(option_type)?;
+    let inputs = |i: usize| BlackScholesInputs {
+        underlying_price: args[0].get(i),
+        strike_price: args[1].get(i),
+        volatility: args[2].get(i),
+        risk_free_rate: args[3].get(i),
+        cost_of_carry: args[4].get(i),
+        time_to_expiry: args[5].get(i),
+        option_type,
+    };
+
+    Ok(match broadcast_len(&args)? {
+        None => Values::Scalar(f(&inputs(0))),
+        Some(n) => Values::Array((0..n).map(|i| f(&inputs(i))).collect()),
+    })
+}
+
+/// Generalised Black-Scholes-Merton price.
+///
+/// The cost of carry defaults to the risk-free rate (no dividends). Any
+/// argument but the option type may be an array, in which case an array of
+/// prices is returned.
+#[pyfunction]
+#[pyo3(signature = (
+    underlying_price,
+    strike_price,
+    volatility,
+    risk_free_rate,
+    time_to_expiry,
+    option_type = "call",
+    cost_of_carry = None
+))]
+#[allow(clippy::too_many_arguments)]
+pub fn black_scholes(
+    underlying_price: Values,
+    strike_price: Values,
+    volatility: Values,
+    risk_free_rate: Values,
+    time_to_expiry: Values,
+    option_type: &str,
+    cost_of_carry: Option<Values>,
+) -> PyResult<Values> {
+    let cost_of_carry = cost_of_carry.unwrap_or_else(|| risk_free_rate.clone());
+    let args = [
+        &underlying_price,
+        &strike_price,
+        &volatility,
+        &risk_free_rate,
+        &cost_of_carry,
+        &time_to_expiry,
+    ];
+
+    evaluate(args, option_type, BlackScholesInputs::price)
+}
+
+/// Generalised Black-Scholes-Merton price and Greeks, as a dictionary with
+/// the keys `price`, `delta`, `gamma`, `vega`, `theta` and `rho`.
+#[pyfunction]
+#[pyo3(signature = (
+    underlying_price,
+    strike_price,
+    volatility,
+    risk_free_rate,
+    time_to_expiry,
+    option_type = "call",
+    cost_of_carry = None
+))]
+#[allow(clippy::too_many_arguments)]
+pub fn black_scholes_greeks(
+    underlying_price: Values,
+    strike_price: Values,
+    volatility: Values,
+    risk_free_rate: Values,
+    time_to_expiry: Values,
+    option_type: &str,
+    cost_of_carry: Option<Values>,
+) -> PyResult<HashMap<&'static str, Values>> {
+    let cost_of_carry = cost_of_carry.unwrap_or_else(|| risk_free_rate.clone());
+    let args = [
+        &underlying_price,
+        &strike_price,
+        &volatility,
+        &risk_free_rate,
+        &cost_of_carry,
+        &time_to_expiry,
+    ];
+
+    let greeks: [(&'static str, Greek); 6] = [
+        ("price", BlackScholesInputs::price),
+        ("delta", BlackScholesInputs::delta),
+        ("gamma", BlackScholesInputs::gamma),
+        ("vega", BlackScholesInputs::vega),
+        ("theta", BlackScholesInputs::theta),
+        ("rho", BlackScholesInputs::rho),
+    ];
+
+    greeks
+        .into_iter()
+        .map(|(name, greek)| Ok((name, evaluate(args, option_type, greek)?)))
+        .collect()
+}
+
+/// Black-Scholes-Merton implied volatility of an option price.
+///
+/// For a scalar price, a `ValueError` is raised if no volatility in
+/// $[10^{-6}, 5]$ matches it; for arrays, such prices give NaN.
+#[pyfunction]
+#[pyo3(signature = (
+    price,
+    underlying_price,
+    strike_price,
+    risk_free_rate,
+    time_to_expiry,
+    option_type = "call",
+    cost_of_carry = None
+))]
+#[allow(clippy::too_many_arguments)]
+pub fn implied_volatility(
+    price: Values,
+    underlying_price: Values,
+    strike_price: Values,
+    risk_free_rate: Values,
+    time_to_expiry: Values,
+    option_type: &str,
+    cost_of_carry: Option<Values>,
+) -> PyResult<Values> {
+    let cost_of_carry = cost_of_carry.unwrap_or_else(|| risk_free_rate.clone());
+
+    // The volatility slot carries the price to invert.
+    let args = [
+        &underlying_price,
+        &strike_price,
+        &price,
+        &risk_free_rate,
+        &cost_of_carry,
+        &time_to_expiry,
+    ];
+
+    if broadcast_len(&args)?.is_none() {
+        let inputs = BlackScholesInputs {
+            underlying_price: underlying_price.get(0),
+            strike_price: strike_price.get(0),
+            volatility: 0.0,
+            risk_free_rate: risk_free_rate.get(0),
+            cost_of_carry: cost_of_carry.get(0),
+            time_to_expiry: time_to_expiry.get(0),
+            option_type: parse_option_type(option_type)?,
+        };
+
+        return inputs
+            .implied_volatility(price.get(0))
+            .map(Values::Scalar)
+            .map_err(|e| PyValueError::new_err(e.to_string()));
+    }
+
+    evaluate(args, option_type, |inputs| {
+        BlackScholesInputs {
+            volatility: 0.0,
+            ..*inputs
+        }
+        .implied_volatility(inputs.volatility)
+        .unwrap_or(f64::NAN)
+    })
+}
+
+/// Heston (1993) call and put prices, as a `(call, put)` tuple.
+#[pyfunction]
+#[pyo3(signature = (
+    underlying_price,
+    variance,
+    strike_price,
+    risk_free_rate,
+    dividend_yield,
+    rho,
+    sigma,
+    kappa,
+    theta,
+    time_to_expiry
+))]
+#[allow(clippy::too_many_arguments)]
+pub fn heston(
+    underlying_price: f64,
+    variance: f64,
+    strike_price: f64,
+    risk_free_rate: f64,
+    dividend_yield: f64,
+    rho: f64,
+    sigma: f64,
+    kappa: f64,
+    theta: f64,
+    time_to_expiry: f64,
+) -> (f64, f64) {
+    let today = OffsetDateTime::now_utc();
+
+    crate::instruments::options::heston::heston(
+        underlying_price,
+        variance,
+        strike_price,
+        risk_free_rate,
+        dividend_yield,
+        rho,
+        sigma,
+        kappa,
+        theta,
+        Some(today),
+        date_after(today, time_to_expiry),
+    )
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_pricers {
+    use super::*;
+
+    #[test]
+    fn test_black_scholes_broadcasts() {
+        let price = black_scholes(
+            Values::Scalar(100.0),
+            Values::Scalar(100.0),
+            Values::Scalar(0.2),
+            Values::Scalar(0.05),
+            Values::Scalar(1.0),
+            "call",
+            None,
+        )
+        .unwrap();
+        assert_approx_equal!(price.get(0), 10.450583572185565, 1e-10);
+
+        let prices = black_scholes(
+            Values::Scalar(100.0),
+            Values::Array(vec![90.0, 100.0, 110.0]),
+            Values::Scalar(0.2),
+            Values::Scalar(0.05),
+            Values::Scalar(1.0),
+            "put",
+            None,
+        )
+        .unwrap();
+        match prices {
+            Values::Array(prices) => assert!(prices.windows(2).all(|w| w[0] < w[1])),
+            Values::Scalar(_) => panic!("expected one price per strike"),
+        }
+
+        assert!(black_scholes(
+            Values::Array(vec![100.0; 2]),
+            Values::Array(vec![100.0; 3]),
+            Values::Scalar(0.2),
+            Values::Scalar(0.05),
+            Values::Scalar(1.0),
+            "call",
+            None,
+        )
+        .is_err());
+        assert!(parse_option_type("straddle").is_err());
+    }
+
+    #[test]
+    fn test_black_scholes_greeks() {
+        let greeks = black_scholes_greeks(
+            Values::Scalar(100.0),
+            Values::Scalar(100.0),
+            Values::Scalar(0.2),
+            Values::Scalar(0.05),
+            Values::Scalar(1.0),
+            "call",
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(greeks.len(), 6);
+        assert_approx_equal!(greeks["delta"].get(0), 0.6368306511756191, 1e-10);
+    }
+
+    #[test]
+    fn test_implied_volatility_round_trip() {
+        let volatility = implied_volatility(
+            Values::Scalar(10.450583572185565),
+            Values::Scalar(100.0),
+            Values::Scalar(100.0),
+            Values::Scalar(0.05),
+            Values::Scalar(1.0),
+            "call",
+            None,
+        )
+        .unwrap();
+        assert_approx_equal!(volatility.get(0), 0.2, 1e-8);
+
+        // A price above the underlying has no implied volatility.
+        let volatilities = implied_volatility(
+            Values::Array(vec![10.450583572185565, 200.0]),
+            Values::Scalar(100.0),
+            Values::Scalar(100.0),
+            Values::Scalar(0.05),
+            Values::Scalar(1.0),
+            "call",
+            None,
+        )
+        .unwrap();
+        assert_approx_equal!(volatilities.get(0), 0.2, 1e-8);
+        assert!(volatilities.get(1).is_nan());
+    }
+}
@@ -0,0 +1,182 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::stochastics::{
+    ArithmeticBrownianMotion, CoxIngersollRoss, GeometricBrownianMotion, OrnsteinUhlenbeck,
+    StochasticProcess, Trajectories,
+};
+use numpy::{IntoPyArray, PyArray1, PyArray2};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3_polars::PyDataFrame;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// MACROS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Python class wrapping a process, with Euler-Maruyama simulation to
+/// NumPy arrays or a py-polars `DataFrame`.
+macro_rules! py_process {
+    ($wrapper:ident, $name:literal, $process:ty, ($($arg:ident),*), $doc:literal) => {
+        #[doc = $doc]
+        #[pyclass(name = $name)]
+        pub struct $wrapper {
+            process: $process,
+        }
+
+        #[pymethods]
+        impl $wrapper {
+            #[new]
+            fn new($($arg: f64),*) -> PyResult<Self> {
+                check_parameters(&[$((stringify!($arg), $arg)),*])?;
+
+                Ok(Self {
+                    process: <$process>::new($($arg),*),
+                })
+            }
+
+            /// Euler-Maruyama paths, as a `(times, paths)` tuple of NumPy
+            /// arrays with shapes `(n_steps + 1,)` and
+            /// `(m_paths, n_steps + 1)`.
+            #[pyo3(signature = (x_0, t_0, t_n, n_steps, m_paths, parallel = true))]
+            #[allow(clippy::too_many_arguments)]
+            fn simulate<'py>(
+                &self,
+                py: Python<'py>,
+                x_0: f64,
+                t_0: f64,
+                t_n: f64,
+                n_steps: usize,
+                m_paths: usize,
+                parallel: bool,
+            ) -> (&'py PyArray1<f64>, &'py PyArray2<f64>) {
+                let output = simulate(py, &self.process, x_0, t_0, t_n, n_steps, m_paths, parallel);
+
+                (output.times.into_pyarray(py), output.paths.into_pyarray(py))
+            }
+
+            /// Euler-Maruyama paths as a py-polars `DataFrame` in long
+            /// format, with `path`, `time` and `value` columns.
+            #[pyo3(signature = (x_0, t_0, t_n, n_steps, m_paths, parallel = true))]
+            #[allow(clippy::too_many_arguments)]
+            fn simulate_frame(
+                &self,
+                py: Python<'_>,
+                x_0: f64,
+                t_0: f64,
+                t_n: f64,
+                n_steps: usize,
+                m_paths: usize,
+                parallel: bool,
+            ) -> PyResult<PyDataFrame> {
+                simulate(py, &self.process, x_0, t_0, t_n, n_steps, m_paths, parallel)
+                    .into_dataframe()
+                    .map(PyDataFrame)
+                    .map_err(|e| PyValueError::new_err(e.to_string()))
+            }
+        }
+    };
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+py_process!(
+    PyGeometricBrownianMotion,
+    "GeometricBrownianMotion",
+    GeometricBrownianMotion,
+    (mu, sigma),
+    "Python `GeometricBrownianMotion(mu, sigma)`."
+);
+
+py_process!(
+    PyArithmeticBrownianMotion,
+    "ArithmeticBrownianMotion",
+    ArithmeticBrownianMotion,
+    (mu, sigma),
+    "Python `ArithmeticBrownianMotion(mu, sigma)`."
+);
+
+py_process!(
+    PyOrnsteinUhlenbeck,
+    "OrnsteinUhlenbeck",
+    OrnsteinUhlenbeck,
+    (mu, sigma, theta),
+    "Python `OrnsteinUhlenbeck(mu, sigma, theta)`, with long-run mean `mu` and mean reversion speed `theta`."
+);
+
+py_process!(
+    PyCoxIngersollRoss,
+    "CoxIngersollRoss",
+    CoxIngersollRoss,
+    (mu, sigma, theta),
+    "Python `CoxIngersollRoss(mu, sigma, theta)`, with long-run mean `mu` and mean reversion speed `theta`."
+);
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Raises a `ValueError` for a negative volatility, which the Rust
+/// constructors would panic on.
+fn check_parameters(parameters: &[(&str, f64)]) -> PyResult<()> {
+    match parameters
+        .iter()
+        .find(|(name, value)| *name == "sigma" && *value < 0.0)
+    {
+        Some((_, sigma)) => Err(PyValueError::new_err(format!(
+            "sigma must be non-negative, not {sigma}"
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Simulates the paths with the GIL released.
+#[allow(clippy::too_many_arguments)]
+fn simulate<P>(
+    py: Python<'_>,
+    process: &P,
+    x_0: f64,
+    t_0: f64,
+    t_n: f64,
+    n_steps: usize,
+    m_paths: usize,
+    parallel: bool,
+) -> Trajectories
+where
+    P: StochasticProcess + Sync,
+{
+    py.allow_threads(|| process.euler_maruyama(x_0, t_0, t_n, n_steps, m_paths, parallel))
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_stochastics {
+    use super::*;
+
+    #[test]
+    fn test_simulate() {
+        pyo3::prepare_freethreaded_python();
+
+        Python::with_gil(|py| {
+            let gbm = PyGeometricBrownianMotion::new(0.05, 0.2).unwrap();
+
+            let frame = gbm
+                .simulate_frame(py, 100.0, 0.0, 1.0, 10, 4, true)
+                .unwrap();
+            assert_eq!(frame.0.shape(), (44, 3));
+
+            assert!(PyOrnsteinUhlenbeck::new(0.0, -0.1, 1.0).is_err());
+        });
+    }
+}
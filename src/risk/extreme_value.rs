@@ -0,0 +1,550 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Extreme value theory (EVT) estimates of tail risk.
+//!
+//! Empirical quantiles are unreliable (or unavailable) far in the tail.
+//! EVT fits a parametric model to the extreme losses only:
+//!
+//! - Peaks-over-threshold: the losses above a high threshold follow a
+//!   Generalized Pareto distribution (GPD).
+//! - Block maxima: the largest loss of each block (e.g. month) follows a
+//!   Generalized Extreme Value distribution (GEV).
+//!
+//! As for historical VaR, the inputs are returns (or P&L), and losses are
+//! reported as positive numbers. The shape parameter $\xi$ measures the
+//! heaviness of the tail: $\xi > 0$ for power-law tails (e.g. Student-t),
+//! $\xi = 0$ for exponential tails (e.g. Gaussian).
+//!
+//! ```
+//! use RustQuant::risk::*;
+//!
+//! // Returns with exponential tails.
+//! let returns: Vec<f64> = (1..=1000)
+//!     .map(|i| 0.01 * (i as f64 / 1001.0).ln())
+//!     .collect();
+//!
+//! // GPD fitted to the 10% largest losses.
+//! let pot = PeaksOverThreshold::fit_quantile(&returns, 0.9).unwrap();
+//! let var = pot.value_at_risk(0.999).unwrap();
+//!
+//! // Exact 99.9% quantile of the losses: -0.01 * ln(0.001).
+//! assert!((var.var - 0.0691).abs() < 0.01);
+//! assert!(var.expected_shortfall > var.var);
+//! ```
+
+use crate::math::optimize::NelderMead;
+use crate::risk::{RiskError, ValueAtRisk};
+use crate::statistics::Statistic;
+use std::f64::consts::PI;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Generalized Pareto distribution of the excesses over a threshold,
+/// $G(y) = 1 - (1 + \xi y / \beta)^{-1/\xi}$.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeneralizedPareto {
+    /// Shape ($\xi$).
+    pub shape: f64,
+
+    /// Scale ($\beta$).
+    pub scale: f64,
+}
+
+/// Generalized Extreme Value distribution,
+/// $H(x) = \exp(-(1 + \xi (x - \mu) / \sigma)^{-1/\xi})$.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeneralizedExtremeValue {
+    /// Location ($\mu$).
+    pub location: f64,
+
+    /// Scale ($\sigma$).
+    pub scale: f64,
+
+    /// Shape ($\xi$).
+    pub shape: f64,
+}
+
+/// Peaks-over-threshold model: a GPD fitted to the losses above a threshold.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PeaksOverThreshold {
+    /// Loss threshold ($u$).
+    pub threshold: f64,
+
+    /// Distribution of the excesses over the threshold.
+    pub gpd: GeneralizedPareto,
+
+    /// Number of observations.
+    pub n_observations: usize,
+
+    /// Number of losses above the threshold.
+    pub n_exceedances: usize,
+}
+
+/// Block maxima model: a GEV fitted to the largest loss of each block.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlockMaxima {
+    /// Number of periods per block.
+    pub block_size: usize,
+
+    /// Distribution of the block maxima.
+    pub gev: GeneralizedExtremeValue,
+
+    /// Number of blocks.
+    pub n_blocks: usize,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Below this, the shape is treated as zero (exponential/Gumbel limits).
+const SHAPE_EPSILON: f64 = 1e-9;
+
+/// Minimum number of exceedances or blocks to fit a tail model.
+const MIN_TAIL_OBSERVATIONS: usize = 10;
+
+impl GeneralizedPareto {
+    /// New GPD with a shape and a positive scale.
+    pub fn new(shape: f64, scale: f64) -> Self {
+        assert!(scale > 0.0, "Scale must be positive.");
+
+        Self { shape, scale }
+    }
+
+    /// Distribution function of an excess `y >= 0`.
+    pub fn cdf(&self, y: f64) -> f64 {
+        let (xi, beta) = (self.shape, self.scale);
+
+        match xi.abs() < SHAPE_EPSILON {
+            true => 1.0 - (-y / beta).exp(),
+            false => 1.0 - (1.0 + xi * y / beta).max(0.0).powf(-1.0 / xi),
+        }
+    }
+
+    /// Quantile function, `p` in `[0, 1)`.
+    pub fn quantile(&self, p: f64) -> f64 {
+        let (xi, beta) = (self.shape, self.scale);
+
+        match xi.abs() < SHAPE_EPSILON {
+            true => -beta * (1.0 - p).ln(),
+            false => beta / xi * ((1.0 - p).powf(-xi) - 1.0),
+        }
+    }
+
+    /// Log-likelihood of excesses (`-inf` outside the support).
+    pub fn log_likelihood(&self, excesses: &[f64]) -> f64 {
+        let (xi, beta) = (self.shape, self.scale);
+        let n = excesses.len() as f64;
+
+        if xi.abs() < SHAPE_EPSILON {
+            return -n * beta.ln() - excesses.iter().sum::<f64>() / beta;
+        }
+
+        let mut log_terms = 0.0;
+        for y in excesses {
+            let t = 1.0 + xi * y / beta;
+            if t <= 0.0 {
+                return f64::NEG_INFINITY;
+            }
+            log_terms += t.ln();
+        }
+
+        -n * beta.ln() - (1.0 + 1.0 / xi) * log_terms
+    }
+
+    /// Maximum likelihood fit to positive excesses over a threshold,
+    /// started from the method of moments estimate.
+    pub fn fit(excesses: &[f64]) -> Result<Self, RiskError> {
+        if excesses.len() < MIN_TAIL_OBSERVATIONS {
+            return Err(RiskError::InsufficientData(excesses.len()));
+        }
+
+        let (mean, variance) = (excesses.to_vec().mean(), excesses.to_vec().variance());
+        let ratio = mean * mean / variance;
+        let initial = [
+            nonzero_shape(0.5 * (1.0 - ratio)),
+            0.5 * mean * (ratio + 1.0),
+        ];
+
+        let [shape, scale] = maximum_likelihood(&initial, |x| match x[1] > 0.0 {
+            true => GeneralizedPareto::new(x[0], x[1]).log_likelihood(excesses),
+            false => f64::NEG_INFINITY,
+        })?;
+
+        Ok(Self::new(shape, scale))
+    }
+}
+
+impl GeneralizedExtremeValue {
+    /// New GEV with a location, a positive scale and a shape.
+    pub fn new(location: f64, scale: f64, shape: f64) -> Self {
+        assert!(scale > 0.0, "Scale must be positive.");
+
+        Self {
+            location,
+            scale,
+            shape,
+        }
+    }
+
+    /// Distribution function.
+    pub fn cdf(&self, x: f64) -> f64 {
+        let z = (x - self.location) / self.scale;
+
+        match self.shape.abs() < SHAPE_EPSILON {
+            true => (-(-z).exp()).exp(),
+            false => {
+                let t = (1.0 + self.shape * z).max(0.0);
+                (-t.powf(-1.0 / self.shape)).exp()
+            }
+        }
+    }
+
+    /// Quantile function, `p` in `(0, 1)`.
+    pub fn quantile(&self, p: f64) -> f64 {
+        let (mu, sigma, xi) = (self.location, self.scale, self.shape);
+
+        match xi.abs() < SHAPE_EPSILON {
+            true => mu - sigma * (-p.ln()).ln(),
+            false => mu + sigma / xi * ((-p.ln()).powf(-xi) - 1.0),
+        }
+    }
+
+    /// Log-likelihood of maxima (`-inf` outside the support).
+    pub fn log_likelihood(&self, maxima: &[f64]) -> f64 {
+        let (mu, sigma, xi) = (self.location, self.scale, self.shape);
+        let n = maxima.len() as f64;
+
+        if xi.abs() < SHAPE_EPSILON {
+            return -n * sigma.ln()
+                - maxima
+                    .iter()
+                    .map(|x| {
+                        let z = (x - mu) / sigma;
+                        z + (-z).exp()
+                    })
+                    .sum::<f64>();
+        }
+
+        let mut log_likelihood = -n * sigma.ln();
+        for x in maxima {
+            let t = 1.0 + xi * (x - mu) / sigma;
+            if t <= 0.0 {
+                return f64::NEG_INFINITY;
+            }
+            log_likelihood -= (1.0 + 1.0 / xi) * t.ln() + t.powf(-1.0 / xi);
+        }
+
+        log_likelihood
+    }
+
+    /// Maximum likelihood fit to block maxima, started from the
+    /// Gumbel method of moments estimate.
+    pub fn fit(maxima: &[f64]) -> Result<Self, RiskError> {
+        if maxima.len() < MIN_TAIL_OBSERVATIONS {
+            return Err(RiskError::InsufficientData(maxima.len()));
+        }
+
+        let (mean, variance) = (maxima.to_vec().mean(), maxima.to_vec().variance());
+        let scale = (6.0 * variance).sqrt() / PI;
+        let initial = [mean - 0.57722 * scale, scale, 0.1];
+
+        let [location, scale, shape] = maximum_likelihood(&initial, |x| match x[1] > 0.0 {
+            true => GeneralizedExtremeValue::new(x[0], x[1], x[2]).log_likelihood(maxima),
+            false => f64::NEG_INFINITY,
+        })?;
+
+        Ok(Self::new(location, scale, shape))
+    }
+}
+
+impl PeaksOverThreshold {
+    /// Fits a GPD to the losses (negated returns) above a `threshold` loss.
+    pub fn fit(returns: &[f64], threshold: f64) -> Result<Self, RiskError> {
+        let excesses: Vec<f64> = returns
+            .iter()
+            .map(|r| -r - threshold)
+            .filter(|y| *y > 0.0)
+            .collect();
+
+        Ok(Self {
+            threshold,
+            gpd: GeneralizedPareto::fit(&excesses)?,
+            n_observations: returns.len(),
+            n_exceedances: excesses.len(),
+        })
+    }
+
+    /// Fits a GPD above the empirical `quantile` of the losses (e.g. 0.95).
+    pub fn fit_quantile(returns: &[f64], quantile: f64) -> Result<Self, RiskError> {
+        if !(quantile > 0.0 && quantile < 1.0) {
+            return Err(RiskError::InvalidConfidence(quantile));
+        }
+        if returns.is_empty() {
+            return Err(RiskError::InsufficientData(0));
+        }
+
+        let mut losses: Vec<f64> = returns.iter().map(|r| -r).collect();
+        losses.sort_by(f64::total_cmp);
+        let index = ((losses.len() as f64 * quantile) as usize).min(losses.len() - 1);
+
+        Self::fit(returns, losses[index])
+    }
+
+    /// Tail quantile of the losses,
+    /// $u + \beta / \xi \left( (n (1 - p) / N_u)^{-\xi} - 1 \right)$.
+    pub fn var(&self, confidence: f64) -> f64 {
+        let tail = self.n_observations as f64 * (1.0 - confidence) / self.n_exceedances as f64;
+
+        self.threshold + self.gpd.quantile(1.0 - tail)
+    }
+
+    /// Expected loss beyond the VaR (infinite for $\xi \geq 1$),
+    /// $(\mathrm{VaR} + \beta - \xi u) / (1 - \xi)$.
+    pub fn expected_shortfall(&self, confidence: f64) -> f64 {
+        let (xi, beta) = (self.gpd.shape, self.gpd.scale);
+
+        match xi < 1.0 {
+            true => (self.var(confidence) + beta - xi * self.threshold) / (1.0 - xi),
+            false => f64::INFINITY,
+        }
+    }
+
+    /// One-period VaR and ES at a `confidence` level beyond the threshold.
+    pub fn value_at_risk(&self, confidence: f64) -> Result<ValueAtRisk, RiskError> {
+        let threshold_level = 1.0 - self.n_exceedances as f64 / self.n_observations as f64;
+
+        if !(confidence >= threshold_level && confidence < 1.0) {
+            return Err(RiskError::InvalidConfidence(confidence));
+        }
+
+        Ok(ValueAtRisk {
+            var: self.var(confidence),
+            expected_shortfall: self.expected_shortfall(confidence),
+            confidence,
+            horizon: 1,
+        })
+    }
+}
+
+impl BlockMaxima {
+    /// Fits a GEV to the largest loss (negated return) of each block of
+    /// `block_size` consecutive returns. A trailing partial block is dropped.
+    pub fn fit(returns: &[f64], block_size: usize) -> Result<Self, RiskError> {
+        if block_size == 0 {
+            return Err(RiskError::InvalidHorizon);
+        }
+
+        let maxima: Vec<f64> = returns
+            .chunks_exact(block_size)
+            .map(|block| block.iter().map(|r| -r).fold(f64::NEG_INFINITY, f64::max))
+            .collect();
+
+        Ok(Self {
+            block_size,
+            gev: GeneralizedExtremeValue::fit(&maxima)?,
+            n_blocks: maxima.len(),
+        })
+    }
+
+    /// Loss exceeded on average once every `blocks` blocks.
+    pub fn return_level(&self, blocks: f64) -> f64 {
+        assert!(blocks > 1.0, "Return period must exceed one block.");
+
+        self.gev.quantile(1.0 - 1.0 / blocks)
+    }
+
+    /// One-period tail quantile of the losses, assuming independent periods:
+    /// $H^{-1}(p^m)$ for blocks of $m$ periods.
+    pub fn var(&self, confidence: f64) -> f64 {
+        self.gev.quantile(confidence.powi(self.block_size as i32))
+    }
+
+    /// Expected loss beyond the one-period VaR, by integrating the tail
+    /// quantiles (infinite for $\xi \geq 1$).
+    pub fn expected_shortfall(&self, confidence: f64) -> f64 {
+        if self.gev.shape >= 1.0 {
+            return f64::INFINITY;
+        }
+
+        // Substituting q = 1 - (1 - p) w^2 removes the singularity at q = 1.
+        let n = 2000;
+        (0..n)
+            .map(|i| {
+                let w = (i as f64 + 0.5) / n as f64;
+                2.0 * w * self.var(1.0 - (1.0 - confidence) * w * w)
+            })
+            .sum::<f64>()
+            / n as f64
+    }
+
+    /// One-period VaR and ES at a `confidence` level.
+    pub fn value_at_risk(&self, confidence: f64) -> Result<ValueAtRisk, RiskError> {
+        if !(confidence > 0.0 && confidence < 1.0) {
+            return Err(RiskError::InvalidConfidence(confidence));
+        }
+
+        Ok(ValueAtRisk {
+            var: self.var(confidence),
+            expected_shortfall: self.expected_shortfall(confidence),
+            confidence,
+            horizon: 1,
+        })
+    }
+}
+
+/// Keeps the starting shape away from zero, where the Nelder-Mead
+/// simplex would be degenerate.
+fn nonzero_shape(shape: f64) -> f64 {
+    match shape.abs() < 0.05 {
+        true => 0.05_f64.copysign(shape),
+        false => shape,
+    }
+}
+
+/// Maximises a log-likelihood with Nelder-Mead.
+fn maximum_likelihood<const N: usize, F>(
+    initial: &[f64; N],
+    log_likelihood: F,
+) -> Result<[f64; N], RiskError>
+where
+    F: Fn(&[f64]) -> f64,
+{
+    let result = NelderMead::new(5_000, 1e-10)
+        .with_initial_step(0.2)
+        .minimize(&|x: &[f64]| -log_likelihood(x), initial);
+
+    match result.minimum.is_finite() {
+        true => Ok(result.minimizer.try_into().unwrap()),
+        false => Err(RiskError::EstimationFailed(String::from(
+            "the likelihood is not finite",
+        ))),
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_extreme_value {
+    use super::*;
+    use crate::stochastics::SeedStrategy;
+    use rand::Rng;
+
+    fn uniforms(n: usize, seed: u64) -> Vec<f64> {
+        let mut rng = SeedStrategy::PerPath(seed).rng(0);
+
+        (0..n).map(|_| rng.gen::<f64>()).collect()
+    }
+
+    #[test]
+    fn test_distributions() {
+        let gpd = GeneralizedPareto::new(0.3, 2.0);
+        assert_approx_equal!(gpd.cdf(gpd.quantile(0.9)), 0.9, 1e-12);
+        assert_approx_equal!(
+            GeneralizedPareto::new(0.0, 2.0).quantile(0.5),
+            2.0 * 2.0_f64.ln(),
+            1e-12
+        );
+
+        let gev = GeneralizedExtremeValue::new(1.0, 0.5, -0.2);
+        assert_approx_equal!(gev.cdf(gev.quantile(0.3)), 0.3, 1e-12);
+        assert_approx_equal!(
+            GeneralizedExtremeValue::new(0.0, 1.0, 0.0).cdf(0.0),
+            (-1.0_f64).exp(),
+            1e-12
+        );
+
+        // Outside the support of a heavy-tailed GEV.
+        assert_eq!(
+            GeneralizedExtremeValue::new(0.0, 1.0, 0.5).log_likelihood(&[-3.0]),
+            f64::NEG_INFINITY
+        );
+    }
+
+    #[test]
+    fn test_gpd_fit() {
+        let gpd = GeneralizedPareto::new(0.3, 1.5);
+        let excesses: Vec<f64> = uniforms(5000, 639)
+            .iter()
+            .map(|u| gpd.quantile(*u))
+            .collect();
+
+        let fitted = GeneralizedPareto::fit(&excesses).unwrap();
+        assert_approx_equal!(fitted.shape, 0.3, 0.05);
+        assert_approx_equal!(fitted.scale, 1.5, 0.1);
+
+        assert!(matches!(
+            GeneralizedPareto::fit(&excesses[..5]),
+            Err(RiskError::InsufficientData(5))
+        ));
+    }
+
+    #[test]
+    fn test_gev_fit() {
+        let gev = GeneralizedExtremeValue::new(2.0, 0.8, 0.2);
+        let maxima: Vec<f64> = uniforms(3000, 639)
+            .iter()
+            .map(|u| gev.quantile(*u))
+            .collect();
+
+        let fitted = GeneralizedExtremeValue::fit(&maxima).unwrap();
+        assert_approx_equal!(fitted.location, 2.0, 0.05);
+        assert_approx_equal!(fitted.scale, 0.8, 0.05);
+        assert_approx_equal!(fitted.shape, 0.2, 0.05);
+    }
+
+    #[test]
+    fn test_peaks_over_threshold() {
+        // Pareto losses with tail index 3: P(L > x) = x^(-3), x >= 1, so
+        // the excesses over any threshold are GPD with shape 1/3.
+        let returns: Vec<f64> = uniforms(20_000, 639)
+            .iter()
+            .map(|u| -(1.0 - u).powf(-1.0 / 3.0))
+            .collect();
+
+        let pot = PeaksOverThreshold::fit_quantile(&returns, 0.95).unwrap();
+        assert_eq!(pot.n_exceedances, 999);
+        assert_approx_equal!(pot.gpd.shape, 1.0 / 3.0, 0.07);
+
+        // Exact VaR: 0.001^(-1/3) = 10, ES = 1.5 * VaR.
+        let var = pot.value_at_risk(0.999).unwrap();
+        assert_approx_equal!(var.var, 10.0, 0.6);
+        assert_approx_equal!(var.expected_shortfall, 15.0, 1.5);
+
+        // Below the threshold, the empirical estimate should be used.
+        assert!(pot.value_at_risk(0.9).is_err());
+    }
+
+    #[test]
+    fn test_block_maxima() {
+        // Exponential losses: block maxima are approximately Gumbel.
+        let returns: Vec<f64> = uniforms(20_000, 639)
+            .iter()
+            .map(|u| (1.0 - u).ln())
+            .collect();
+
+        let blocks = BlockMaxima::fit(&returns, 20).unwrap();
+        assert_eq!(blocks.n_blocks, 1000);
+        assert_approx_equal!(blocks.gev.shape, 0.0, 0.06);
+        assert_approx_equal!(blocks.gev.location, 20.0_f64.ln(), 0.1);
+
+        // Exact: VaR = -ln(0.001) = 6.91, ES = VaR + 1.
+        let var = blocks.value_at_risk(0.999).unwrap();
+        assert_approx_equal!(var.var, 6.91, 0.5);
+        assert_approx_equal!(var.expected_shortfall, 7.91, 0.7);
+
+        assert!(blocks.return_level(100.0) > blocks.return_level(10.0));
+        assert!(BlockMaxima::fit(&returns[..100], 20).is_err());
+    }
+}
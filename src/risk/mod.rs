@@ -0,0 +1,31 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Risk measures: historical, parametric and Monte Carlo Value-at-Risk
+//! and Expected Shortfall, extreme value tail estimates, and scenario
+//! stress testing.
+
+/// Historical Value-at-Risk and Expected Shortfall.
+pub mod value_at_risk;
+pub use value_at_risk::*;
+
+/// Parametric (delta-normal) VaR and covariance estimators.
+pub mod parametric;
+pub use parametric::*;
+
+/// Monte Carlo VaR by full revaluation.
+pub mod monte_carlo;
+
+/// Extreme value theory: peaks-over-threshold and block maxima.
+pub mod extreme_value;
+pub use extreme_value::*;
+
+/// Scenario analysis and historical stress tests.
+pub mod scenario;
+pub use scenario::*;
@@ -0,0 +1,434 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Scenario analysis and stress testing.
+//!
+//! A [`Scenario`] is a set of market shocks: a relative move in spot prices,
+//! a scaling of volatilities, a parallel shift of interest rates and yield
+//! curves, and relative moves of currencies against the reporting currency.
+//! Instruments implementing [`ScenarioPricing`] can be repriced under a
+//! scenario, and a portfolio of them produces a P&L grid.
+//!
+//! ```
+//! use RustQuant::instruments::options::{BlackScholesMerton, TypeFlag};
+//! use RustQuant::portfolio::{Portfolio, Position};
+//! use RustQuant::risk::*;
+//! use std::collections::HashMap;
+//! use time::{Duration, OffsetDateTime};
+//!
+//! let put = BlackScholesMerton::new(
+//!     0.05,
+//!     100.0,
+//!     90.0,
+//!     0.2,
+//!     0.05,
+//!     None,
+//!     OffsetDateTime::now_utc() + Duration::days(91),
+//!     TypeFlag::Put,
+//! );
+//!
+//! let portfolio = Portfolio::new(HashMap::from([(
+//!     "Puts".to_string(),
+//!     Position::new(put, 100, 1.0, 1.0, None),
+//! )]));
+//!
+//! let grid = portfolio.scenario_pnl(&historical_stresses());
+//!
+//! // Long puts make money in every historical crash.
+//! assert!(grid.totals().iter().all(|pnl| *pnl > 0.0));
+//! ```
+
+use crate::curves::{Curve, YieldCurve};
+use crate::instruments::options::BlackScholesMerton;
+use crate::instruments::{CouponBond, Instrument};
+use crate::money::Currency;
+use crate::portfolio::Portfolio;
+use std::collections::HashMap;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Set of market shocks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scenario {
+    /// Name of the scenario.
+    pub name: String,
+
+    /// Relative move of spot prices (e.g. -0.3 for a 30% fall).
+    pub spot_shift: f64,
+
+    /// Multiplier of volatilities (e.g. 2.0 doubles them).
+    pub vol_scale: f64,
+
+    /// Parallel shift of interest rates and yield curves (0.01 = 100bp).
+    pub rate_shift: f64,
+
+    /// Relative moves of currencies against the reporting currency,
+    /// keyed by ISO 4217 code (e.g. "GBP" => -0.1 for a 10% fall).
+    pub fx_shocks: HashMap<&'static str, f64>,
+}
+
+/// Instruments that can be repriced under a scenario.
+pub trait ScenarioPricing: Instrument {
+    /// Price of the instrument under the scenario's market shocks
+    /// (in the instrument's own currency).
+    fn scenario_price(&self, scenario: &Scenario) -> f64;
+}
+
+/// P&L of each position of a portfolio under each scenario.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScenarioGrid {
+    /// Scenario names (rows).
+    pub scenarios: Vec<String>,
+
+    /// Position names (columns), sorted.
+    pub positions: Vec<String>,
+
+    /// P&L, indexed by `[scenario][position]`.
+    pub pnl: Vec<Vec<f64>>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Scenario {
+    /// New scenario without any shock.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            spot_shift: 0.0,
+            vol_scale: 1.0,
+            rate_shift: 0.0,
+            fx_shocks: HashMap::new(),
+        }
+    }
+
+    /// Sets the relative move of spot prices.
+    pub fn with_spot_shift(mut self, shift: f64) -> Self {
+        self.spot_shift = shift;
+        self
+    }
+
+    /// Sets the volatility multiplier.
+    pub fn with_vol_scale(mut self, scale: f64) -> Self {
+        self.vol_scale = scale;
+        self
+    }
+
+    /// Sets the parallel shift of interest rates.
+    pub fn with_rate_shift(mut self, shift: f64) -> Self {
+        self.rate_shift = shift;
+        self
+    }
+
+    /// Sets the relative move of a currency against the reporting currency.
+    pub fn with_fx_shock(mut self, currency: Currency, shock: f64) -> Self {
+        self.fx_shocks.insert(currency.code.alphabetic, shock);
+        self
+    }
+
+    /// Relative move of a currency (zero if not shocked).
+    pub fn fx_shock(&self, currency: &Currency) -> f64 {
+        self.fx_shocks
+            .get(currency.code.alphabetic)
+            .copied()
+            .unwrap_or(0.0)
+    }
+
+    /// Yield curve with all rates shifted by the scenario's rate shift.
+    pub fn shift_curve(&self, curve: &YieldCurve) -> YieldCurve {
+        YieldCurve {
+            rates: curve
+                .rates
+                .iter()
+                .map(|(date, rate)| (*date, rate + self.rate_shift))
+                .collect(),
+            day_count_convention: curve.day_count_convention,
+            interpolation: curve.interpolation,
+            compounding: curve.compounding,
+        }
+    }
+
+    /// Global financial crisis style shock (autumn 2008): equities -40%,
+    /// volatilities tripled, rates -150bp, flight to USD and JPY.
+    pub fn global_financial_crisis_2008() -> Self {
+        use crate::money::{AUD, EUR, GBP, JPY};
+
+        Self::new("GFC 2008")
+            .with_spot_shift(-0.40)
+            .with_vol_scale(3.0)
+            .with_rate_shift(-0.015)
+            .with_fx_shock(GBP, -0.25)
+            .with_fx_shock(EUR, -0.15)
+            .with_fx_shock(AUD, -0.30)
+            .with_fx_shock(JPY, 0.20)
+    }
+
+    /// COVID-19 crash style shock (February to March 2020): equities -34%,
+    /// volatilities quadrupled, rates -100bp, emerging market currencies down.
+    pub fn covid_march_2020() -> Self {
+        use crate::money::{AUD, GBP, MXN};
+
+        Self::new("COVID March 2020")
+            .with_spot_shift(-0.34)
+            .with_vol_scale(4.0)
+            .with_rate_shift(-0.01)
+            .with_fx_shock(GBP, -0.10)
+            .with_fx_shock(AUD, -0.15)
+            .with_fx_shock(MXN, -0.25)
+    }
+
+    /// Black Monday style shock (19 October 1987): equities -22.6% in a day,
+    /// volatilities tripled, rates -50bp.
+    pub fn black_monday_1987() -> Self {
+        Self::new("Black Monday 1987")
+            .with_spot_shift(-0.226)
+            .with_vol_scale(3.0)
+            .with_rate_shift(-0.005)
+    }
+
+    /// Parallel interest rate shock of `basis_points`.
+    pub fn rate_shock(basis_points: f64) -> Self {
+        Self::new(&format!("Rates {basis_points:+}bp")).with_rate_shift(basis_points / 10_000.0)
+    }
+}
+
+/// Library of historical stress scenarios.
+pub fn historical_stresses() -> Vec<Scenario> {
+    vec![
+        Scenario::black_monday_1987(),
+        Scenario::global_financial_crisis_2008(),
+        Scenario::covid_march_2020(),
+    ]
+}
+
+impl ScenarioPricing for BlackScholesMerton {
+    /// Price with the underlying, volatility and risk-free rate shocked
+    /// (the cost of carry moves with the rate).
+    fn scenario_price(&self, scenario: &Scenario) -> f64 {
+        BlackScholesMerton::new(
+            self.cost_of_carry + scenario.rate_shift,
+            self.underlying_price * (1.0 + scenario.spot_shift),
+            self.strike_price,
+            self.volatility * scenario.vol_scale,
+            self.risk_free_rate + scenario.rate_shift,
+            self.evaluation_date,
+            self.expiration_date,
+            self.option_type,
+        )
+        .price()
+    }
+}
+
+impl ScenarioPricing for CouponBond {
+    /// Price of the coupons discounted on the shifted yield curve.
+    fn scenario_price(&self, scenario: &Scenario) -> f64 {
+        let curve = scenario.shift_curve(&self.yield_curve);
+        let dates: Vec<_> = self.coupons.keys().cloned().collect();
+
+        self.coupons
+            .values()
+            .zip(curve.discount_factors(&dates))
+            .map(|(coupon, df)| coupon * df)
+            .sum()
+    }
+}
+
+impl<I> Portfolio<I>
+where
+    I: ScenarioPricing,
+{
+    /// P&L grid of the portfolio under each scenario.
+    ///
+    /// The P&L of a position is its quantity times the change in model price,
+    /// with the shocked price converted with the scenario's move of the
+    /// position's currency (positions without a currency are not FX shocked).
+    pub fn scenario_pnl(&self, scenarios: &[Scenario]) -> ScenarioGrid {
+        let mut positions: Vec<&String> = self.positions.keys().collect();
+        positions.sort();
+
+        let base: Vec<f64> = positions
+            .iter()
+            .map(|name| self.positions[*name].instrument.price().value())
+            .collect();
+
+        let pnl = scenarios
+            .iter()
+            .map(|scenario| {
+                positions
+                    .iter()
+                    .zip(&base)
+                    .map(|(name, base_price)| {
+                        let position = &self.positions[*name];
+                        let fx = position
+                            .currency
+                            .map_or(0.0, |currency| scenario.fx_shock(&currency));
+                        let shocked = position.instrument.scenario_price(scenario) * (1.0 + fx);
+
+                        position.quantity as f64 * (shocked - base_price)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        ScenarioGrid {
+            scenarios: scenarios.iter().map(|s| s.name.clone()).collect(),
+            positions: positions.into_iter().cloned().collect(),
+            pnl,
+        }
+    }
+}
+
+impl ScenarioGrid {
+    /// Total P&L of each scenario.
+    pub fn totals(&self) -> Vec<f64> {
+        self.pnl.iter().map(|row| row.iter().sum()).collect()
+    }
+
+    /// P&L of a position under a scenario.
+    pub fn get(&self, scenario: &str, position: &str) -> Option<f64> {
+        let i = self.scenarios.iter().position(|s| s == scenario)?;
+        let j = self.positions.iter().position(|p| p == position)?;
+
+        Some(self.pnl[i][j])
+    }
+
+    /// Scenario with the largest total loss, and its total P&L.
+    pub fn worst(&self) -> Option<(&str, f64)> {
+        self.scenarios
+            .iter()
+            .zip(self.totals())
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(name, pnl)| (name.as_str(), pnl))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_scenario {
+    use super::*;
+    use crate::instruments::options::TypeFlag;
+    use crate::money::{GBP, USD};
+    use crate::portfolio::Position;
+    use crate::time::{BusinessDayConvention, PaymentFrequency, Thirty360US};
+    use std::collections::BTreeMap;
+    use time::macros::datetime;
+    use time::{Duration, OffsetDateTime};
+
+    fn option(flag: TypeFlag) -> BlackScholesMerton {
+        BlackScholesMerton::new(
+            0.05,
+            100.0,
+            100.0,
+            0.2,
+            0.05,
+            None,
+            OffsetDateTime::now_utc() + Duration::days(182),
+            flag,
+        )
+    }
+
+    #[test]
+    fn test_scenario_pnl_grid() {
+        let portfolio = Portfolio::new(HashMap::from([
+            (
+                "Calls".to_string(),
+                Position::new(option(TypeFlag::Call), 10, 5.0, 5.0, Some(USD)),
+            ),
+            (
+                "Puts".to_string(),
+                Position::new(option(TypeFlag::Put), 10, 5.0, 5.0, Some(GBP)),
+            ),
+        ]));
+
+        let scenarios = vec![
+            Scenario::new("Base"),
+            Scenario::new("Rally").with_spot_shift(0.1),
+            Scenario::new("Vol up").with_vol_scale(1.5),
+            Scenario::new("GBP down").with_fx_shock(GBP, -0.1),
+        ];
+        let grid = portfolio.scenario_pnl(&scenarios);
+
+        assert_eq!(grid.positions, vec!["Calls", "Puts"]);
+        assert_approx_equal!(grid.totals()[0], 0.0, 1e-12);
+
+        assert!(grid.get("Rally", "Calls").unwrap() > 0.0);
+        assert!(grid.get("Rally", "Puts").unwrap() < 0.0);
+        assert!(grid.totals()[2] > 0.0);
+
+        // Only the GBP position is affected by the FX shock.
+        assert_approx_equal!(grid.get("GBP down", "Calls").unwrap(), 0.0, 1e-12);
+        assert_approx_equal!(
+            grid.get("GBP down", "Puts").unwrap(),
+            -0.1 * 10.0 * option(TypeFlag::Put).price(),
+            1e-10
+        );
+        assert_eq!(grid.worst().unwrap().0, "GBP down");
+    }
+
+    #[test]
+    fn test_historical_stresses() {
+        let portfolio = Portfolio::new(HashMap::from([(
+            "Calls".to_string(),
+            Position::new(option(TypeFlag::Call), 100, 5.0, 5.0, Some(GBP)),
+        )]));
+
+        let grid = portfolio.scenario_pnl(&historical_stresses());
+
+        assert_eq!(grid.scenarios.len(), 3);
+        assert!(grid.totals().iter().all(|pnl| *pnl < 0.0));
+        assert_eq!(grid.worst().unwrap().0, "GFC 2008");
+
+        assert_eq!(Scenario::rate_shock(-50.0).name, "Rates -50bp");
+        assert_approx_equal!(Scenario::rate_shock(25.0).rate_shift, 0.0025, 1e-15);
+    }
+
+    #[test]
+    fn test_bond_rate_shocks() {
+        let start = datetime!(2024-01-15 0:00 UTC);
+        let curve = YieldCurve::from_dates_and_rates(
+            &[
+                start,
+                start + Duration::days(365),
+                start + Duration::days(3650),
+            ],
+            &[0.035, 0.04, 0.045],
+        );
+
+        let mut bond = CouponBond {
+            evaluation_date: start,
+            expiration_date: datetime!(2029-01-15 0:00 UTC),
+            currency: Some(USD),
+            coupon_rate: 0.05,
+            coupon_frequency: PaymentFrequency::SemiAnnually,
+            settlement_convention: BusinessDayConvention::Actual,
+            day_counter: Box::new(Thirty360US),
+            yield_curve: curve,
+            face_value: 100.0,
+            rounding: Default::default(),
+            coupons: BTreeMap::new(),
+        };
+        bond.construct_coupons();
+
+        let base = bond.scenario_price(&Scenario::new("Base"));
+        let up = bond.scenario_price(&Scenario::rate_shock(100.0));
+        let down = bond.scenario_price(&Scenario::rate_shock(-100.0));
+
+        assert_approx_equal!(base, bond.price().value(), 1e-10);
+        assert!(up < base && base < down);
+
+        // Convexity: the gain when rates fall exceeds the loss when they rise.
+        assert!(down - base > base - up);
+    }
+}
@@ -0,0 +1,489 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Historical Value-at-Risk (VaR) and Expected Shortfall (ES).
+//!
+//! Losses are reported as positive numbers: a 99% VaR of 0.03 means that
+//! over the horizon, returns worse than -3% occurred in 1% of the history.
+//! The ES is the average loss beyond the VaR.
+//!
+//! Multi-period horizons compound the one-period returns, either over
+//! overlapping windows (more samples, but autocorrelated) or over
+//! non-overlapping windows.
+//!
+//! ```
+//! use RustQuant::risk::*;
+//!
+//! let returns: Vec<f64> = (0..100).map(|i| (i as f64 - 50.0) / 1000.0).collect();
+//!
+//! // 5 of the 100 returns are at or below -4.6%.
+//! let var = ValueAtRisk::historical(&returns, 0.95, 1).unwrap();
+//!
+//! assert!((var.var - 0.046).abs() < 1e-12);
+//! assert!((var.expected_shortfall - 0.048).abs() < 1e-12);
+//! ```
+
+use crate::instruments::Instrument;
+use crate::portfolio::Portfolio;
+use crate::stochastics::SeedStrategy;
+use rand::Rng;
+use std::collections::HashMap;
+use thiserror::Error;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Value-at-Risk and Expected Shortfall estimate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ValueAtRisk {
+    /// Value-at-Risk (positive for a loss).
+    pub var: f64,
+
+    /// Expected Shortfall: average loss beyond the VaR (positive for a loss).
+    pub expected_shortfall: f64,
+
+    /// Confidence level, e.g. 0.99.
+    pub confidence: f64,
+
+    /// Horizon in periods of the input returns.
+    pub horizon: usize,
+}
+
+/// How one-period returns are aggregated into horizon returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HorizonWindow {
+    /// Windows starting at every period.
+    #[default]
+    Overlapping,
+
+    /// Consecutive windows that don't share any period.
+    NonOverlapping,
+}
+
+/// Bootstrap confidence intervals of the VaR and ES.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BootstrapInterval {
+    /// Point estimate from the original sample.
+    pub estimate: ValueAtRisk,
+
+    /// Lower and upper bounds of the VaR.
+    pub var: (f64, f64),
+
+    /// Lower and upper bounds of the ES.
+    pub expected_shortfall: (f64, f64),
+
+    /// Level of the interval, e.g. 0.95.
+    pub level: f64,
+}
+
+/// Risk measure errors.
+#[derive(Debug, Error)]
+pub enum RiskError {
+    /// The confidence level is not in (0, 1).
+    #[error("Invalid confidence level: {0}")]
+    InvalidConfidence(f64),
+
+    /// The horizon is zero.
+    #[error("The horizon must be at least one period.")]
+    InvalidHorizon,
+
+    /// Not enough observations for the requested estimate.
+    #[error("Not enough observations: {0}")]
+    InsufficientData(usize),
+
+    /// No returns were provided for a position.
+    #[error("Missing returns for position: {0}")]
+    MissingReturns(String),
+
+    /// A tail model could not be fitted to the data.
+    #[error("Estimation failed: {0}")]
+    EstimationFailed(String),
+
+    /// Error reading the returns from a data frame.
+    #[cfg(feature = "data")]
+    #[error("{0}")]
+    Data(#[from] crate::data::DataError),
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl ValueAtRisk {
+    /// Historical VaR and ES of a series of one-period (simple) returns,
+    /// over `horizon` periods with overlapping windows.
+    pub fn historical(returns: &[f64], confidence: f64, horizon: usize) -> Result<Self, RiskError> {
+        Self::historical_with_window(returns, confidence, horizon, HorizonWindow::Overlapping)
+    }
+
+    /// Historical VaR and ES over `horizon` periods with the given windows.
+    pub fn historical_with_window(
+        returns: &[f64],
+        confidence: f64,
+        horizon: usize,
+        window: HorizonWindow,
+    ) -> Result<Self, RiskError> {
+        let horizon_returns = horizon_returns(returns, horizon, window)?;
+
+        Self::from_pnl(&horizon_returns, confidence, horizon)
+    }
+
+    /// VaR and ES of a sample of profits and losses (or returns)
+    /// already measured over the horizon.
+    pub fn from_pnl(pnl: &[f64], confidence: f64, horizon: usize) -> Result<Self, RiskError> {
+        if !(confidence > 0.0 && confidence < 1.0) {
+            return Err(RiskError::InvalidConfidence(confidence));
+        }
+        if horizon == 0 {
+            return Err(RiskError::InvalidHorizon);
+        }
+        if pnl.is_empty() {
+            return Err(RiskError::InsufficientData(0));
+        }
+
+        // Losses sorted from the largest.
+        let mut losses: Vec<f64> = pnl.iter().map(|x| -x).collect();
+        losses.sort_by(|a, b| b.total_cmp(a));
+
+        // Number of tail observations: ceil(n * (1 - confidence)), at least one.
+        let tail = ((losses.len() as f64 * (1.0 - confidence)) - 1e-9)
+            .ceil()
+            .max(1.0) as usize;
+
+        Ok(Self {
+            var: losses[tail - 1],
+            expected_shortfall: losses[..tail].iter().sum::<f64>() / tail as f64,
+            confidence,
+            horizon,
+        })
+    }
+
+    /// Historical VaR and ES of a portfolio, in currency units.
+    ///
+    /// `returns` maps each position name to its one-period return series
+    /// (all of the same length). The P&L of each scenario is the sum of
+    /// the position values times their horizon returns.
+    pub fn historical_portfolio<I: Instrument>(
+        portfolio: &Portfolio<I>,
+        returns: &HashMap<String, Vec<f64>>,
+        confidence: f64,
+        horizon: usize,
+        window: HorizonWindow,
+    ) -> Result<Self, RiskError> {
+        let mut pnl: Vec<f64> = Vec::new();
+
+        for (name, position) in &portfolio.positions {
+            let series = returns
+                .get(name)
+                .ok_or_else(|| RiskError::MissingReturns(name.clone()))?;
+            let series = horizon_returns(series, horizon, window)?;
+
+            if pnl.is_empty() {
+                pnl = vec![0.0; series.len()];
+            }
+            if series.len() != pnl.len() {
+                return Err(RiskError::InsufficientData(series.len().min(pnl.len())));
+            }
+
+            let value = position.value();
+            for (total, r) in pnl.iter_mut().zip(series) {
+                *total += value * r;
+            }
+        }
+
+        Self::from_pnl(&pnl, confidence, horizon)
+    }
+
+    /// Historical VaR and ES of a column of one-period returns in a data frame.
+    #[cfg(feature = "data")]
+    pub fn historical_from_frame(
+        frame: &polars::prelude::DataFrame,
+        column: &str,
+        confidence: f64,
+        horizon: usize,
+        window: HorizonWindow,
+    ) -> Result<Self, RiskError> {
+        use crate::data::DataError;
+        use polars::prelude::DataType;
+
+        let series = frame
+            .column(column)
+            .and_then(|series| series.cast(&DataType::Float64))
+            .map_err(DataError::from)?;
+        let returns: Vec<f64> = series
+            .f64()
+            .map_err(DataError::from)?
+            .into_iter()
+            .flatten()
+            .collect();
+
+        Self::historical_with_window(&returns, confidence, horizon, window)
+    }
+
+    /// Bootstrap confidence intervals of the historical VaR and ES.
+    ///
+    /// The one-period returns are resampled with replacement `resamples`
+    /// times, and the bounds are the `(1 - level) / 2` and `(1 + level) / 2`
+    /// quantiles of the resampled estimates.
+    pub fn bootstrap(
+        returns: &[f64],
+        confidence: f64,
+        horizon: usize,
+        window: HorizonWindow,
+        resamples: usize,
+        level: f64,
+        seed: SeedStrategy,
+    ) -> Result<BootstrapInterval, RiskError> {
+        if !(level > 0.0 && level < 1.0) {
+            return Err(RiskError::InvalidConfidence(level));
+        }
+        if resamples == 0 {
+            return Err(RiskError::InsufficientData(0));
+        }
+
+        let estimate = Self::historical_with_window(returns, confidence, horizon, window)?;
+
+        let mut rng = seed.rng(0);
+        let mut sample = vec![0.0; returns.len()];
+        let mut vars = Vec::with_capacity(resamples);
+        let mut shortfalls = Vec::with_capacity(resamples);
+
+        for _ in 0..resamples {
+            for x in sample.iter_mut() {
+                *x = returns[rng.gen_range(0..returns.len())];
+            }
+
+            let resampled = Self::historical_with_window(&sample, confidence, horizon, window)?;
+            vars.push(resampled.var);
+            shortfalls.push(resampled.expected_shortfall);
+        }
+
+        let bounds = |mut estimates: Vec<f64>| {
+            estimates.sort_by(|a, b| a.total_cmp(b));
+            (
+                empirical_quantile(&estimates, (1.0 - level) / 2.0),
+                empirical_quantile(&estimates, (1.0 + level) / 2.0),
+            )
+        };
+
+        Ok(BootstrapInterval {
+            estimate,
+            var: bounds(vars),
+            expected_shortfall: bounds(shortfalls),
+            level,
+        })
+    }
+}
+
+/// Historical Expected Shortfall of one-period returns over `horizon`
+/// periods (overlapping windows).
+pub fn expected_shortfall(
+    returns: &[f64],
+    confidence: f64,
+    horizon: usize,
+) -> Result<f64, RiskError> {
+    ValueAtRisk::historical(returns, confidence, horizon).map(|var| var.expected_shortfall)
+}
+
+/// Compounded returns over windows of `horizon` periods.
+pub fn horizon_returns(
+    returns: &[f64],
+    horizon: usize,
+    window: HorizonWindow,
+) -> Result<Vec<f64>, RiskError> {
+    if horizon == 0 {
+        return Err(RiskError::InvalidHorizon);
+    }
+    if returns.len() < horizon {
+        return Err(RiskError::InsufficientData(returns.len()));
+    }
+
+    let compound = |window: &[f64]| window.iter().map(|r| 1.0 + r).product::<f64>() - 1.0;
+
+    Ok(match window {
+        HorizonWindow::Overlapping => returns.windows(horizon).map(compound).collect(),
+        HorizonWindow::NonOverlapping => returns.chunks_exact(horizon).map(compound).collect(),
+    })
+}
+
+/// Linearly interpolated quantile of sorted values.
+fn empirical_quantile(sorted: &[f64], q: f64) -> f64 {
+    let position = q * (sorted.len() - 1) as f64;
+    let (lower, upper) = (position.floor() as usize, position.ceil() as usize);
+
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (position - lower as f64)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_value_at_risk {
+    use super::*;
+    use crate::instruments::options::{BlackScholesMerton, TypeFlag};
+    use crate::portfolio::Position;
+    use time::{Duration, OffsetDateTime};
+
+    fn returns() -> Vec<f64> {
+        (0..100).map(|i| (i as f64 - 50.0) / 1000.0).collect()
+    }
+
+    #[test]
+    fn test_historical_var() {
+        let var = ValueAtRisk::historical(&returns(), 0.99, 1).unwrap();
+        assert_approx_equal!(var.var, 0.05, 1e-12);
+        assert_approx_equal!(var.expected_shortfall, 0.05, 1e-12);
+
+        let var = ValueAtRisk::historical(&returns(), 0.9, 1).unwrap();
+        assert_approx_equal!(var.var, 0.041, 1e-12);
+        assert_approx_equal!(
+            expected_shortfall(&returns(), 0.9, 1).unwrap(),
+            0.0455,
+            1e-12
+        );
+
+        assert!(ValueAtRisk::historical(&returns(), 1.0, 1).is_err());
+        assert!(ValueAtRisk::historical(&[], 0.99, 1).is_err());
+        assert!(ValueAtRisk::historical(&returns(), 0.99, 0).is_err());
+    }
+
+    #[test]
+    fn test_horizon_windows() {
+        let returns = [0.1, -0.1, 0.2, 0.0, -0.5];
+
+        let overlapping = horizon_returns(&returns, 2, HorizonWindow::Overlapping).unwrap();
+        assert_eq!(overlapping.len(), 4);
+        assert_approx_equal!(overlapping[0], 1.1 * 0.9 - 1.0, 1e-12);
+        assert_approx_equal!(overlapping[3], -0.5, 1e-12);
+
+        let non_overlapping = horizon_returns(&returns, 2, HorizonWindow::NonOverlapping).unwrap();
+        assert_eq!(non_overlapping.len(), 2);
+        assert_approx_equal!(non_overlapping[1], 0.2, 1e-12);
+
+        let var =
+            ValueAtRisk::historical_with_window(&returns, 0.5, 2, HorizonWindow::NonOverlapping)
+                .unwrap();
+        assert_approx_equal!(var.var, 0.01, 1e-12);
+        assert_eq!(var.horizon, 2);
+    }
+
+    #[test]
+    fn test_bootstrap_interval() {
+        let interval = ValueAtRisk::bootstrap(
+            &returns(),
+            0.95,
+            1,
+            HorizonWindow::Overlapping,
+            500,
+            0.9,
+            SeedStrategy::PerPath(632),
+        )
+        .unwrap();
+
+        assert!(interval.var.0 <= interval.estimate.var);
+        assert!(interval.estimate.var <= interval.var.1);
+        assert!(interval.expected_shortfall.0 <= interval.expected_shortfall.1);
+        assert!(interval.var.1 <= 0.05);
+    }
+
+    #[test]
+    fn test_portfolio_var() {
+        let option = |flag| {
+            BlackScholesMerton::new(
+                0.05,
+                100.0,
+                100.0,
+                0.2,
+                0.05,
+                None,
+                OffsetDateTime::now_utc() + Duration::days(91),
+                flag,
+            )
+        };
+
+        let positions = HashMap::from([
+            (
+                "A".to_string(),
+                Position::new(option(TypeFlag::Call), 10, 5.0, 5.0, None),
+            ),
+            (
+                "B".to_string(),
+                Position::new(option(TypeFlag::Put), 20, 5.0, 5.0, None),
+            ),
+        ]);
+        let portfolio = Portfolio::new(positions);
+
+        let returns = HashMap::from([
+            ("A".to_string(), vec![0.1, -0.2, 0.0, 0.05]),
+            ("B".to_string(), vec![-0.1, 0.1, -0.3, 0.0]),
+        ]);
+
+        // P&L: 50 * r_A + 100 * r_B = [-5, 0, -30, 2.5].
+        let var = ValueAtRisk::historical_portfolio(
+            &portfolio,
+            &returns,
+            0.75,
+            1,
+            HorizonWindow::Overlapping,
+        )
+        .unwrap();
+        assert_approx_equal!(var.var, 30.0, 1e-12);
+
+        let var = ValueAtRisk::historical_portfolio(
+            &portfolio,
+            &returns,
+            0.5,
+            1,
+            HorizonWindow::Overlapping,
+        )
+        .unwrap();
+        assert_approx_equal!(var.var, 5.0, 1e-12);
+        assert_approx_equal!(var.expected_shortfall, 17.5, 1e-12);
+
+        let missing = HashMap::from([("A".to_string(), vec![0.1])]);
+        assert!(matches!(
+            ValueAtRisk::historical_portfolio(
+                &portfolio,
+                &missing,
+                0.5,
+                1,
+                HorizonWindow::Overlapping
+            ),
+            Err(RiskError::MissingReturns(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "data")]
+    fn test_historical_from_frame() {
+        use polars::prelude::*;
+
+        let frame = df!("returns" => returns()).unwrap();
+        let var = ValueAtRisk::historical_from_frame(
+            &frame,
+            "returns",
+            0.99,
+            1,
+            HorizonWindow::Overlapping,
+        )
+        .unwrap();
+
+        assert_approx_equal!(var.var, 0.05, 1e-12);
+        assert!(ValueAtRisk::historical_from_frame(
+            &frame,
+            "missing",
+            0.99,
+            1,
+            HorizonWindow::Overlapping
+        )
+        .is_err());
+    }
+}
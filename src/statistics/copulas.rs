@@ -7,38 +7,758 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
-// IMPORTS
-// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+//! Copulas: the dependence structure of a random vector, separated from
+//! its marginal distributions.
+//!
+//! - Gaussian: no tail dependence.
+//! - Student-t: symmetric lower and upper tail dependence.
+//! - Clayton: lower tail dependence (joint crashes).
+//! - Gumbel: upper tail dependence.
+//!
+//! Copulas are fitted to data through their ranks (pseudo-observations),
+//! either by inverting Kendall's tau or by maximum likelihood, and
+//! sampled to give dependent uniforms, which are mapped to any marginal
+//! distributions with their inverse CDFs.
+//!
+//! ```
+//! use RustQuant::statistics::*;
+//! use RustQuant::stochastics::SeedStrategy;
+//! use nalgebra::DMatrix;
+//!
+//! // Two assets with a Clayton copula: crashes tend to happen together.
+//! let clayton = ClaytonCopula::new(2.0, 2).unwrap();
+//! let draws = clayton.sample_n(10_000, SeedStrategy::PerPath(42));
+//!
+//! let (x, y): (Vec<f64>, Vec<f64>) = draws.iter().map(|u| (u[0], u[1])).unzip();
+//!
+//! // Fit back from the simulated data.
+//! let fitted = ClaytonCopula::fit(&[x, y], FitMethod::KendallsTau).unwrap();
+//! assert!((fitted.theta - 2.0).abs() < 0.2);
+//!
+//! // Lower tail dependence 2^(-1/theta).
+//! assert_eq!(clayton.tail_dependence(0, 1), (0.5_f64.sqrt(), 0.0));
+//! ```
 
-use RustQuant::*;
+use crate::math::optimize::NelderMead;
+use crate::math::special::norm_inv_cdf;
+use crate::statistics::distributions::{Distribution, Gaussian};
+use crate::statistics::MultivariateNormal;
+use crate::stochastics::SeedStrategy;
+use nalgebra::{DMatrix, DVector};
+use rand::Rng;
+use rand_distr::{ChiSquared, Exp1, Gamma, StandardNormal};
+use statrs::distribution::{ContinuousCDF, StudentsT};
+use statrs::function::gamma::ln_gamma;
+use std::f64::consts::PI;
+use thiserror::Error;
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
-// Structs, enums, and traits
+// STRUCTS, ENUMS, AND TRAITS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-enum Enum {}
+/// Copula of a `d`-dimensional random vector.
+pub trait Copula {
+    /// Dimension of the copula.
+    fn dimension(&self) -> usize;
+
+    /// One draw of dependent uniforms.
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<f64>;
+
+    /// Log-density of the copula at `u` (in the open unit cube).
+    fn log_density(&self, u: &[f64]) -> f64;
+
+    /// Lower and upper tail dependence coefficients of variables `i` and `j`.
+    fn tail_dependence(&self, i: usize, j: usize) -> (f64, f64);
+
+    /// `n` draws of dependent uniforms, from a single random stream.
+    fn sample_n(&self, n: usize, seed: SeedStrategy) -> Vec<Vec<f64>> {
+        let mut rng = seed.rng(0);
+
+        (0..n).map(|_| self.sample(&mut rng)).collect()
+    }
+
+    /// Log-likelihood of observations (each of the copula's dimension).
+    fn log_likelihood(&self, observations: &[Vec<f64>]) -> f64 {
+        observations.iter().map(|u| self.log_density(u)).sum()
+    }
+}
+
+/// Copula estimation methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FitMethod {
+    /// Invert the relationship between the parameters and Kendall's tau.
+    #[default]
+    KendallsTau,
 
-struct Struct {}
+    /// Maximise the likelihood of the pseudo-observations.
+    MaximumLikelihood,
+}
+
+/// Gaussian copula with a correlation matrix.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GaussianCopula {
+    /// Correlation matrix.
+    pub correlation: DMatrix<f64>,
+
+    cholesky: DMatrix<f64>,
+}
+
+/// Student-t copula with a correlation matrix and degrees of freedom.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StudentTCopula {
+    /// Correlation matrix.
+    pub correlation: DMatrix<f64>,
 
-trait Trait {}
+    /// Degrees of freedom ($\nu$).
+    pub degrees_of_freedom: f64,
+
+    cholesky: DMatrix<f64>,
+}
+
+/// Clayton copula, $C(u) = (\sum_i u_i^{-\theta} - d + 1)^{-1/\theta}$, $\theta > 0$.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClaytonCopula {
+    /// Dependence parameter ($\theta$).
+    pub theta: f64,
+
+    /// Dimension.
+    pub dimension: usize,
+}
+
+/// Gumbel copula, $C(u) = \exp(-(\sum_i (-\ln u_i)^\theta)^{1/\theta})$, $\theta \geq 1$.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GumbelCopula {
+    /// Dependence parameter ($\theta$).
+    pub theta: f64,
+
+    /// Dimension.
+    pub dimension: usize,
+}
+
+/// Copula errors.
+#[derive(Debug, Clone, Copy, PartialEq, Error)]
+pub enum CopulaError {
+    /// Not enough observations to fit the copula.
+    #[error("Not enough observations: {0}.")]
+    InsufficientData(usize),
+
+    /// Series or matrices of inconsistent dimensions.
+    #[error("Inconsistent dimensions.")]
+    DimensionMismatch,
+
+    /// The correlation matrix is not positive definite (see
+    /// [`NearestCorrelation`](crate::math::linalg::NearestCorrelation) to
+    /// repair it).
+    #[error("The correlation matrix is not positive definite.")]
+    NotPositiveDefinite,
+
+    /// Parameter outside of the copula's range.
+    #[error("Invalid copula parameter: {0}.")]
+    InvalidParameter(f64),
+
+    /// The operation is not available in this dimension.
+    #[error("Unsupported dimension: {0}.")]
+    UnsupportedDimension(usize),
+}
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
-// Implementations, functions, and macros
+// IMPLEMENTATIONS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl Struct {}
+impl GaussianCopula {
+    /// New Gaussian copula with a (positive definite) correlation matrix.
+    pub fn new(correlation: DMatrix<f64>) -> Result<Self, CopulaError> {
+        let cholesky = cholesky(&correlation)?;
+
+        Ok(Self {
+            correlation,
+            cholesky,
+        })
+    }
+
+    /// Fits the copula to data (one series per variable).
+    ///
+    /// Kendall's tau gives $\rho = \sin(\pi \tau / 2)$; maximum likelihood
+    /// gives the correlation of the normal scores $\Phi^{-1}(u)$.
+    pub fn fit(data: &[Vec<f64>], method: FitMethod) -> Result<Self, CopulaError> {
+        let correlation = match method {
+            FitMethod::KendallsTau => tau_correlation(data)?,
+            FitMethod::MaximumLikelihood => {
+                let normal = Gaussian::new(0.0, 1.0);
+                let scores: Vec<Vec<f64>> = pseudo_observations(data)?
+                    .iter()
+                    .map(|u| u.iter().map(|ui| normal.inv_cdf(*ui)).collect())
+                    .collect();
+
+                let d = data.len();
+                let n = scores.len() as f64;
+                let covariance = DMatrix::from_fn(d, d, |i, j| {
+                    scores.iter().map(|z| z[i] * z[j]).sum::<f64>() / n
+                });
+                let scale = DMatrix::from_diagonal(&covariance.diagonal().map(|v| 1.0 / v.sqrt()));
+
+                &scale * covariance * &scale
+            }
+        };
+
+        Self::new(correlation)
+    }
+
+    /// Copula distribution function $C(u) = \Phi_R(\Phi^{-1}(u_1), \ldots, \Phi^{-1}(u_d))$,
+    /// e.g. the probability that several names default by a horizon, given
+    /// their marginal default probabilities (see [`MultivariateNormal`]).
+    pub fn cdf(&self, u: &[f64]) -> f64 {
+        assert_eq!(u.len(), self.dimension());
+
+        let z: Vec<f64> = u.iter().map(|ui| norm_inv_cdf(*ui)).collect();
+
+        MultivariateNormal::standard(self.correlation.clone())
+            .map(|mvn| mvn.cdf(&z).value)
+            .expect("The correlation matrix is positive definite.")
+    }
+}
+
+impl Copula for GaussianCopula {
+    fn dimension(&self) -> usize {
+        self.correlation.nrows()
+    }
 
-impl Trait for Struct {}
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<f64> {
+        let normal = Gaussian::new(0.0, 1.0);
+
+        correlated_normals(&self.cholesky, rng)
+            .iter()
+            .map(|z| normal.cdf(*z))
+            .collect()
+    }
+
+    fn log_density(&self, u: &[f64]) -> f64 {
+        let normal = Gaussian::new(0.0, 1.0);
+        let z = DVector::from_iterator(u.len(), u.iter().map(|ui| normal.inv_cdf(*ui)));
+
+        let (log_det, quadratic) = mahalanobis(&self.cholesky, &z);
+
+        -0.5 * log_det - 0.5 * (quadratic - z.norm_squared())
+    }
+
+    fn tail_dependence(&self, _i: usize, _j: usize) -> (f64, f64) {
+        (0.0, 0.0)
+    }
+}
+
+impl StudentTCopula {
+    /// New Student-t copula with a (positive definite) correlation matrix
+    /// and positive degrees of freedom.
+    pub fn new(correlation: DMatrix<f64>, degrees_of_freedom: f64) -> Result<Self, CopulaError> {
+        if degrees_of_freedom <= 0.0 || degrees_of_freedom.is_nan() {
+            return Err(CopulaError::InvalidParameter(degrees_of_freedom));
+        }
+
+        let cholesky = cholesky(&correlation)?;
+
+        Ok(Self {
+            correlation,
+            degrees_of_freedom,
+            cholesky,
+        })
+    }
+
+    /// Fits the copula to data (one series per variable).
+    ///
+    /// The correlation is estimated from Kendall's tau with both methods
+    /// ($\rho = \sin(\pi \tau / 2)$ holds for all elliptical copulas), and
+    /// the degrees of freedom by maximum likelihood over $[1, 100]$.
+    pub fn fit(data: &[Vec<f64>], _method: FitMethod) -> Result<Self, CopulaError> {
+        let correlation = tau_correlation(data)?;
+        let observations = pseudo_observations(data)?;
+
+        let result = NelderMead::new(200, 1e-8).with_initial_step(0.5).minimize(
+            &|x: &[f64]| match x[0] {
+                nu if (1.0..=100.0).contains(&nu) => StudentTCopula::new(correlation.clone(), nu)
+                    .map_or(f64::INFINITY, |copula| {
+                        -copula.log_likelihood(&observations)
+                    }),
+                _ => f64::INFINITY,
+            },
+            &[5.0],
+        );
+
+        Self::new(correlation, result.minimizer[0])
+    }
+
+    fn students_t(&self) -> StudentsT {
+        StudentsT::new(0.0, 1.0, self.degrees_of_freedom).unwrap()
+    }
+}
+
+impl Copula for StudentTCopula {
+    fn dimension(&self) -> usize {
+        self.correlation.nrows()
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<f64> {
+        let t = self.students_t();
+        let z = correlated_normals(&self.cholesky, rng);
+        let w: f64 = rng.sample(ChiSquared::new(self.degrees_of_freedom).unwrap());
+        let scale = (self.degrees_of_freedom / w).sqrt();
+
+        z.iter().map(|zi| t.cdf(zi * scale)).collect()
+    }
+
+    fn log_density(&self, u: &[f64]) -> f64 {
+        let (nu, d) = (self.degrees_of_freedom, u.len() as f64);
+        let t = self.students_t();
+        let x = DVector::from_iterator(u.len(), u.iter().map(|ui| t.inverse_cdf(*ui)));
+
+        let (log_det, quadratic) = mahalanobis(&self.cholesky, &x);
+
+        let joint = ln_gamma((nu + d) / 2.0)
+            - ln_gamma(nu / 2.0)
+            - 0.5 * d * (nu * PI).ln()
+            - 0.5 * log_det
+            - 0.5 * (nu + d) * (1.0 + quadratic / nu).ln();
+        let marginals: f64 = x
+            .iter()
+            .map(|xi| {
+                ln_gamma((nu + 1.0) / 2.0)
+                    - ln_gamma(nu / 2.0)
+                    - 0.5 * (nu * PI).ln()
+                    - 0.5 * (nu + 1.0) * (1.0 + xi * xi / nu).ln()
+            })
+            .sum();
+
+        joint - marginals
+    }
+
+    /// Symmetric tail dependence
+    /// $2 t_{\nu + 1}(-\sqrt{(\nu + 1)(1 - \rho) / (1 + \rho)})$.
+    fn tail_dependence(&self, i: usize, j: usize) -> (f64, f64) {
+        let (nu, rho) = (self.degrees_of_freedom, self.correlation[(i, j)]);
+        let t = StudentsT::new(0.0, 1.0, nu + 1.0).unwrap();
+        let lambda = 2.0 * t.cdf(-((nu + 1.0) * (1.0 - rho) / (1.0 + rho)).sqrt());
+
+        (lambda, lambda)
+    }
+}
+
+impl ClaytonCopula {
+    /// New Clayton copula with $\theta > 0$ in dimension `d >= 2`.
+    pub fn new(theta: f64, dimension: usize) -> Result<Self, CopulaError> {
+        if theta <= 0.0 || theta.is_nan() {
+            return Err(CopulaError::InvalidParameter(theta));
+        }
+        if dimension < 2 {
+            return Err(CopulaError::UnsupportedDimension(dimension));
+        }
+
+        Ok(Self { theta, dimension })
+    }
+
+    /// Fits the copula to data (one series per variable).
+    ///
+    /// Kendall's tau (averaged over pairs) gives $\theta = 2\tau / (1 - \tau)$,
+    /// which is also the starting point of the maximum likelihood estimate.
+    pub fn fit(data: &[Vec<f64>], method: FitMethod) -> Result<Self, CopulaError> {
+        let tau = average_tau(data)?;
+        let copula = Self::new(2.0 * tau / (1.0 - tau), data.len())?;
+
+        match method {
+            FitMethod::KendallsTau => Ok(copula),
+            FitMethod::MaximumLikelihood => {
+                let theta = maximum_likelihood(data, copula.theta, |theta| {
+                    Self::new(theta, data.len()).ok()
+                })?;
+
+                Self::new(theta, data.len())
+            }
+        }
+    }
+
+    /// Kendall's tau, $\theta / (\theta + 2)$.
+    pub fn kendalls_tau(&self) -> f64 {
+        self.theta / (self.theta + 2.0)
+    }
+}
+
+impl Copula for ClaytonCopula {
+    fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    /// Marshall-Olkin algorithm with a Gamma(1/θ) frailty.
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<f64> {
+        let v: f64 = rng.sample(Gamma::new(1.0 / self.theta, 1.0).unwrap());
+
+        (0..self.dimension)
+            .map(|_| {
+                let e: f64 = rng.sample(Exp1);
+                (1.0 + e / v).powf(-1.0 / self.theta)
+            })
+            .collect()
+    }
+
+    fn log_density(&self, u: &[f64]) -> f64 {
+        let (theta, d) = (self.theta, u.len() as f64);
+
+        (0..u.len())
+            .map(|k| (1.0 + k as f64 * theta).ln())
+            .sum::<f64>()
+            - (1.0 + theta) * u.iter().map(|ui| ui.ln()).sum::<f64>()
+            - (d + 1.0 / theta) * (u.iter().map(|ui| ui.powf(-theta)).sum::<f64>() - d + 1.0).ln()
+    }
+
+    /// Lower tail dependence $2^{-1/\theta}$.
+    fn tail_dependence(&self, _i: usize, _j: usize) -> (f64, f64) {
+        (2.0_f64.powf(-1.0 / self.theta), 0.0)
+    }
+}
+
+impl GumbelCopula {
+    /// New Gumbel copula with $\theta \geq 1$ in dimension `d >= 2`.
+    pub fn new(theta: f64, dimension: usize) -> Result<Self, CopulaError> {
+        if theta < 1.0 || theta.is_nan() {
+            return Err(CopulaError::InvalidParameter(theta));
+        }
+        if dimension < 2 {
+            return Err(CopulaError::UnsupportedDimension(dimension));
+        }
+
+        Ok(Self { theta, dimension })
+    }
+
+    /// Fits the copula to data (one series per variable).
+    ///
+    /// Kendall's tau (averaged over pairs) gives $\theta = 1 / (1 - \tau)$.
+    /// Maximum likelihood is only available for two variables.
+    pub fn fit(data: &[Vec<f64>], method: FitMethod) -> Result<Self, CopulaError> {
+        let tau = average_tau(data)?;
+        let copula = Self::new(1.0 / (1.0 - tau), data.len())?;
+
+        match method {
+            FitMethod::KendallsTau => Ok(copula),
+            FitMethod::MaximumLikelihood if data.len() == 2 => {
+                let theta =
+                    maximum_likelihood(data, copula.theta, |theta| Self::new(theta, 2).ok())?;
+
+                Self::new(theta, 2)
+            }
+            FitMethod::MaximumLikelihood => Err(CopulaError::UnsupportedDimension(data.len())),
+        }
+    }
+
+    /// Kendall's tau, $1 - 1 / \theta$.
+    pub fn kendalls_tau(&self) -> f64 {
+        1.0 - 1.0 / self.theta
+    }
+}
+
+impl Copula for GumbelCopula {
+    fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    /// Marshall-Olkin algorithm with a positive stable frailty
+    /// (Kanter's representation).
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<f64> {
+        let alpha = 1.0 / self.theta;
+        let w: f64 = rng.gen_range(0.0..PI);
+        let e: f64 = rng.sample(Exp1);
+
+        let v = (alpha * w).sin() / w.sin().powf(1.0 / alpha)
+            * (((1.0 - alpha) * w).sin() / e).powf((1.0 - alpha) / alpha);
+
+        (0..self.dimension)
+            .map(|_| {
+                let e: f64 = rng.sample(Exp1);
+                (-(e / v).powf(alpha)).exp()
+            })
+            .collect()
+    }
+
+    /// Log-density (bivariate only; `NaN` in higher dimensions).
+    fn log_density(&self, u: &[f64]) -> f64 {
+        let &[u, v] = u else {
+            return f64::NAN;
+        };
+
+        let theta = self.theta;
+        let (x, y) = (-u.ln(), -v.ln());
+        let s = x.powf(theta) + y.powf(theta);
+        let a = s.powf(1.0 / theta);
+
+        -a - u.ln() - v.ln()
+            + (theta - 1.0) * (x.ln() + y.ln())
+            + (1.0 / theta - 2.0) * s.ln()
+            + (a + theta - 1.0).ln()
+    }
+
+    /// Upper tail dependence $2 - 2^{1/\theta}$.
+    fn tail_dependence(&self, _i: usize, _j: usize) -> (f64, f64) {
+        (0.0, 2.0 - 2.0_f64.powf(1.0 / self.theta))
+    }
+}
+
+/// Kendall's rank correlation of two series.
+pub fn kendalls_tau(x: &[f64], y: &[f64]) -> f64 {
+    assert_eq!(x.len(), y.len(), "Series must have the same length.");
+
+    let n = x.len();
+    let concordance: f64 = (0..n)
+        .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+        .map(|(i, j)| ((x[i] - x[j]) * (y[i] - y[j])).signum())
+        .sum();
+
+    2.0 * concordance / (n * (n - 1)) as f64
+}
+
+/// Pseudo-observations of data (one series per variable): the ranks
+/// scaled to the open unit interval, `rank / (n + 1)`, one vector per
+/// observation.
+pub fn pseudo_observations(data: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, CopulaError> {
+    let n = data.first().map_or(0, |series| series.len());
+
+    if data.len() < 2 {
+        return Err(CopulaError::UnsupportedDimension(data.len()));
+    }
+    if data.iter().any(|series| series.len() != n) {
+        return Err(CopulaError::DimensionMismatch);
+    }
+    if n < 2 {
+        return Err(CopulaError::InsufficientData(n));
+    }
+
+    let ranks: Vec<Vec<f64>> = data
+        .iter()
+        .map(|series| {
+            let mut order: Vec<usize> = (0..n).collect();
+            order.sort_by(|&a, &b| series[a].total_cmp(&series[b]));
+
+            let mut ranks = vec![0.0; n];
+            for (rank, i) in order.into_iter().enumerate() {
+                ranks[i] = (rank + 1) as f64 / (n + 1) as f64;
+            }
+            ranks
+        })
+        .collect();
+
+    Ok((0..n)
+        .map(|i| ranks.iter().map(|series| series[i]).collect())
+        .collect())
+}
+
+/// Lower Cholesky factor of a correlation matrix.
+fn cholesky(correlation: &DMatrix<f64>) -> Result<DMatrix<f64>, CopulaError> {
+    if !correlation.is_square() || correlation.nrows() < 2 {
+        return Err(CopulaError::DimensionMismatch);
+    }
+
+    correlation
+        .clone()
+        .cholesky()
+        .map(|cholesky| cholesky.l())
+        .ok_or(CopulaError::NotPositiveDefinite)
+}
+
+/// Correlated standard normals `L z`.
+fn correlated_normals<R: Rng + ?Sized>(cholesky: &DMatrix<f64>, rng: &mut R) -> DVector<f64> {
+    let z = DVector::from_fn(cholesky.nrows(), |_, _| rng.sample(StandardNormal));
+
+    cholesky * z
+}
+
+/// Log-determinant of `R = L L'` and the quadratic form `x' R^{-1} x`.
+fn mahalanobis(cholesky: &DMatrix<f64>, x: &DVector<f64>) -> (f64, f64) {
+    let log_det = 2.0 * cholesky.diagonal().iter().map(|l| l.ln()).sum::<f64>();
+    let y = cholesky
+        .solve_lower_triangular(x)
+        .unwrap_or_else(|| DVector::from_element(x.len(), f64::NAN));
+
+    (log_det, y.norm_squared())
+}
+
+/// Correlation matrix from pairwise Kendall's tau, $\rho = \sin(\pi \tau / 2)$.
+fn tau_correlation(data: &[Vec<f64>]) -> Result<DMatrix<f64>, CopulaError> {
+    pseudo_observations(data)?;
+
+    let d = data.len();
+
+    Ok(DMatrix::from_fn(d, d, |i, j| match i == j {
+        true => 1.0,
+        false => (0.5 * PI * kendalls_tau(&data[i], &data[j])).sin(),
+    }))
+}
+
+/// Average of the pairwise Kendall's tau.
+fn average_tau(data: &[Vec<f64>]) -> Result<f64, CopulaError> {
+    pseudo_observations(data)?;
+
+    let d = data.len();
+    let taus: Vec<f64> = (0..d)
+        .flat_map(|i| (i + 1..d).map(move |j| (i, j)))
+        .map(|(i, j)| kendalls_tau(&data[i], &data[j]))
+        .collect();
+
+    Ok(taus.iter().sum::<f64>() / taus.len() as f64)
+}
+
+/// Maximum likelihood estimate of a one-parameter copula.
+fn maximum_likelihood<C, F>(data: &[Vec<f64>], initial: f64, copula: F) -> Result<f64, CopulaError>
+where
+    C: Copula,
+    F: Fn(f64) -> Option<C>,
+{
+    let observations = pseudo_observations(data)?;
+
+    let result = NelderMead::new(500, 1e-10).with_initial_step(0.2).minimize(
+        &|x: &[f64]| copula(x[0]).map_or(f64::INFINITY, |c| -c.log_likelihood(&observations)),
+        &[initial],
+    );
+
+    Ok(result.minimizer[0])
+}
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
-// Unit tests
+// UNIT TESTS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 #[cfg(test)]
-mod tests {
+mod tests_copulas {
     use super::*;
 
+    fn columns(draws: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        (0..draws[0].len())
+            .map(|j| draws.iter().map(|u| u[j]).collect())
+            .collect()
+    }
+
+    fn correlation(rho: f64) -> DMatrix<f64> {
+        DMatrix::from_row_slice(2, 2, &[1.0, rho, rho, 1.0])
+    }
+
+    #[test]
+    fn test_kendalls_tau_and_pseudo_observations() {
+        assert_approx_equal!(
+            kendalls_tau(&[1.0, 2.0, 3.0], &[1.0, 3.0, 2.0]),
+            1.0 / 3.0,
+            1e-15
+        );
+        assert_approx_equal!(
+            kendalls_tau(&[1.0, 2.0, 3.0], &[3.0, 2.0, 1.0]),
+            -1.0,
+            1e-15
+        );
+
+        let u = pseudo_observations(&[vec![10.0, 30.0, 20.0], vec![0.5, 0.1, 0.9]]).unwrap();
+        assert_eq!(u[1], vec![0.75, 0.25]);
+
+        assert_eq!(
+            pseudo_observations(&[vec![1.0, 2.0], vec![1.0]]).unwrap_err(),
+            CopulaError::DimensionMismatch
+        );
+    }
+
+    #[test]
+    fn test_gaussian_copula() {
+        let copula = GaussianCopula::new(correlation(0.7)).unwrap();
+        let draws = copula.sample_n(4000, SeedStrategy::PerPath(638));
+
+        assert!(draws.iter().flatten().all(|u| *u > 0.0 && *u < 1.0));
+
+        let data = columns(&draws);
+        let tau = GaussianCopula::fit(&data, FitMethod::KendallsTau).unwrap();
+        let mle = GaussianCopula::fit(&data, FitMethod::MaximumLikelihood).unwrap();
+
+        assert_approx_equal!(tau.correlation[(0, 1)], 0.7, 0.03);
+        assert_approx_equal!(mle.correlation[(0, 1)], 0.7, 0.03);
+
+        // Independence copula has zero log-density.
+        let independent = GaussianCopula::new(DMatrix::identity(2, 2)).unwrap();
+        assert_approx_equal!(independent.log_density(&[0.3, 0.8]), 0.0, 1e-12);
+        assert_approx_equal!(independent.cdf(&[0.3, 0.8]), 0.24, 1e-14);
+
+        // Joint default probability of two names with 2% and 5% marginals.
+        let joint = copula.cdf(&[0.02, 0.05]);
+        assert!(joint > 0.02 * 0.05 && joint < 0.02);
+
+        assert_eq!(
+            GaussianCopula::new(correlation(1.5)).unwrap_err(),
+            CopulaError::NotPositiveDefinite
+        );
+    }
+
+    #[test]
+    fn test_student_t_copula() {
+        let copula = StudentTCopula::new(correlation(0.5), 4.0).unwrap();
+        let draws = copula.sample_n(1500, SeedStrategy::PerPath(638));
+
+        let fitted = StudentTCopula::fit(&columns(&draws), FitMethod::MaximumLikelihood).unwrap();
+
+        assert_approx_equal!(fitted.correlation[(0, 1)], 0.5, 0.05);
+        assert!(fitted.degrees_of_freedom > 2.0 && fitted.degrees_of_freedom < 10.0);
+
+        // Tail dependence vanishes as the copula approaches the Gaussian.
+        let (lower, upper) = copula.tail_dependence(0, 1);
+        assert_eq!(lower, upper);
+        assert_approx_equal!(lower, 0.2532, 1e-4);
+        let gaussian_like = StudentTCopula::new(correlation(0.5), 1000.0).unwrap();
+        assert!(gaussian_like.tail_dependence(0, 1).0 < 1e-6);
+    }
+
     #[test]
-    fn very_thorough_test() {}
+    fn test_clayton_copula() {
+        let copula = ClaytonCopula::new(2.0, 3).unwrap();
+        let draws = copula.sample_n(3000, SeedStrategy::PerPath(638));
+        let data = columns(&draws);
+
+        assert_approx_equal!(
+            kendalls_tau(&data[0], &data[2]),
+            copula.kendalls_tau(),
+            0.03
+        );
+
+        let tau = ClaytonCopula::fit(&data, FitMethod::KendallsTau).unwrap();
+        let mle = ClaytonCopula::fit(&data, FitMethod::MaximumLikelihood).unwrap();
+
+        assert_eq!(tau.dimension, 3);
+        assert_approx_equal!(tau.theta, 2.0, 0.15);
+        assert_approx_equal!(mle.theta, 2.0, 0.15);
+        assert!(mle.log_likelihood(&pseudo_observations(&data).unwrap()) > 0.0);
+
+        assert!(ClaytonCopula::new(-1.0, 2).is_err());
+    }
+
+    #[test]
+    fn test_gumbel_copula() {
+        let copula = GumbelCopula::new(2.0, 2).unwrap();
+        let draws = copula.sample_n(3000, SeedStrategy::PerPath(638));
+        let data = columns(&draws);
+
+        assert_approx_equal!(kendalls_tau(&data[0], &data[1]), 0.5, 0.03);
+
+        let mle = GumbelCopula::fit(&data, FitMethod::MaximumLikelihood).unwrap();
+        assert_approx_equal!(mle.theta, 2.0, 0.15);
+        assert_approx_equal!(copula.tail_dependence(0, 1).1, 2.0 - 2.0_f64.sqrt(), 1e-15);
+
+        // The density integrates to one over the unit square.
+        let n = 200;
+        let integral: f64 = (0..n)
+            .flat_map(|i| (0..n).map(move |j| (i, j)))
+            .map(|(i, j)| {
+                let u = [(i as f64 + 0.5) / n as f64, (j as f64 + 0.5) / n as f64];
+                copula.log_density(&u).exp()
+            })
+            .sum::<f64>()
+            / (n * n) as f64;
+        assert_approx_equal!(integral, 1.0, 0.01);
+
+        let three = columns(
+            &GumbelCopula::new(2.0, 3)
+                .unwrap()
+                .sample_n(100, SeedStrategy::PerPath(1)),
+        );
+        assert_eq!(
+            GumbelCopula::fit(&three, FitMethod::MaximumLikelihood).unwrap_err(),
+            CopulaError::UnsupportedDimension(3)
+        );
+    }
 }
@@ -266,7 +266,7 @@ impl Distribution for Bernoulli {
     ///
     /// let bernoulli = Bernoulli::new(0.5);
     ///
-    /// let sample = bernoulli.sample(100).expect("Bernoulli sampled.");
+    /// let sample = bernoulli.sample(10_000).expect("Bernoulli sampled.");
     /// let mean = sample.iter().sum::<f64>() / sample.len() as f64;
     ///
     /// assert_approx_equal!(mean, bernoulli.mean(), 0.1);
@@ -300,7 +300,6 @@ impl Distribution for Bernoulli {
 #[cfg(test)]
 mod tests_bernoulli {
     use super::*;
-    use crate::assert_approx_equal;
 
     #[test]
     fn test_bernoulli_functions() {
@@ -266,7 +266,7 @@ impl Distribution for Binomial {
     /// # use RustQuant::statistics::distributions::{Binomial, Distribution};
     ///
     /// let binomial = Binomial::new(100, 0.4);
-    /// let sample = binomial.sample(100).unwrap();
+    /// let sample = binomial.sample(10_000).unwrap();
     /// let mean = sample.iter().sum::<f64>() / sample.len() as f64;
     ///
     /// assert_approx_equal!(mean, binomial.mean(), 1.0);
@@ -281,7 +281,7 @@ impl Distribution for Binomial {
 
         let mut rng = thread_rng();
 
-        let dist = Binomial::new(n as u64, self.p)?;
+        let dist = Binomial::new(self.n as u64, self.p)?;
 
         let mut variates: Vec<f64> = Vec::with_capacity(n);
 
@@ -300,7 +300,6 @@ impl Distribution for Binomial {
 #[cfg(test)]
 mod tests_binomial_distribution {
     use super::*;
-    use crate::assert_approx_equal;
 
     #[test]
     fn test_binomial_distribution() {
@@ -305,7 +305,6 @@ impl Distribution for ChiSquared {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::assert_approx_equal;
 
     #[test]
     fn test_chi_squared_characteristic_function() {
@@ -52,6 +52,10 @@ pub enum DistributionError {
     /// Error variant from constructing Poisson distribution.
     #[error("{0}")]
     Poisson(#[from] rand_distr::PoissonError),
+
+    /// Error variant from fitting a distribution to data.
+    #[error("Estimation failed: {0}")]
+    Estimation(String),
 }
 
 /// Base trait for all distributions.
@@ -224,7 +224,6 @@ impl Distribution for Exponential {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::assert_approx_equal;
 
     #[test]
     fn test_exponential_characteristic_function() {
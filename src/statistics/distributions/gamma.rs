@@ -147,7 +147,6 @@ impl Distribution for Gamma {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::assert_approx_equal;
 
     #[test]
     fn test_gamma_characteristic_function() {
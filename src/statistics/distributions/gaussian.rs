@@ -11,8 +11,8 @@ use crate::statistics::DistributionError;
 
 use {
     super::Distribution,
+    crate::math::special::{erfc, norm_inv_cdf},
     num_complex::Complex,
-    statrs::function::erf,
     std::f64::consts::{PI, SQRT_2},
 };
 
@@ -59,6 +59,31 @@ impl Gaussian {
 
         Self { mean, variance }
     }
+
+    /// Fits the distribution to data by maximum likelihood
+    /// (the sample mean and the biased sample variance).
+    /// # Examples
+    /// ```
+    /// # use RustQuant::assert_approx_equal;
+    /// # use RustQuant::statistics::distributions::*;
+    ///
+    /// let gaussian = Gaussian::fit(&[1.0, 2.0, 3.0, 4.0]).unwrap();
+    ///
+    /// assert_approx_equal!(gaussian.mean(), 2.5, 1e-12);
+    /// assert_approx_equal!(gaussian.variance(), 1.25, 1e-12);
+    /// ```
+    pub fn fit(data: &[f64]) -> Result<Self, DistributionError> {
+        let n = data.len() as f64;
+        let mean = data.iter().sum::<f64>() / n;
+        let variance = data.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+
+        match variance > 0.0 {
+            true => Ok(Self { mean, variance }),
+            false => Err(DistributionError::Estimation(
+                "the data must not be constant".to_string(),
+            )),
+        }
+    }
 }
 
 impl Distribution for Gaussian {
@@ -127,7 +152,7 @@ impl Distribution for Gaussian {
         assert!(self.variance > 0.0);
         // `erfc` (complementary error function) is used instead of `erf` to avoid
         // subtractive cancellation that leads to inaccuracy in the tails.
-        0.5 * erf::erfc(-(x - self.mean) / (SQRT_2 * self.variance.sqrt()))
+        0.5 * erfc(-(x - self.mean) / (SQRT_2 * self.variance.sqrt()))
     }
 
     /// Inverse distribution (quantile) function of the Gaussian distribution.
@@ -135,17 +160,16 @@ impl Distribution for Gaussian {
     /// ```
     /// # use RustQuant::assert_approx_equal;
     /// # use RustQuant::statistics::distributions::*;
-    /// # use std::f64::INFINITY;
     ///
     /// let gaussian = Gaussian::new(0.0, 1.0);
     ///
-    /// assert_eq!(gaussian.inv_cdf(0.5), INFINITY);
-    /// assert_approx_equal!(gaussian.inv_cdf(0.8413447), 0.4087960, 1e-7);
+    /// assert_eq!(gaussian.inv_cdf(0.5), 0.0);
+    /// assert_approx_equal!(gaussian.inv_cdf(0.8413447), 1.0, 1e-6);
     /// ```
     fn inv_cdf(&self, p: f64) -> f64 {
         assert!(self.variance > 0.0);
 
-        self.mean + SQRT_2 * self.variance.sqrt() * erf::erfc_inv(2.0 * p - 1.0)
+        self.mean + self.variance.sqrt() * norm_inv_cdf(p)
     }
 
     /// Returns the mean of the Gaussian distribution.
@@ -304,7 +328,6 @@ impl Distribution for Gaussian {
 #[cfg(test)]
 mod tests_gaussian {
     use super::*;
-    use crate::assert_approx_equal;
 
     #[test]
     fn test_gaussian_characteristic_function() {
@@ -395,4 +418,14 @@ mod tests_gaussian {
 
         assert_approx_equal!(normal.entropy(), 1.418_938_533_204_672_7, 1e-8);
     }
+
+    #[test]
+    fn test_gaussian_fit() {
+        let data = Gaussian::new(1.0, 4.0).sample(10_000).unwrap();
+        let fitted = Gaussian::fit(&data).unwrap();
+
+        assert_approx_equal!(fitted.mean(), 1.0, 0.1);
+        assert_approx_equal!(fitted.variance(), 4.0, 0.2);
+        assert!(Gaussian::fit(&[1.0, 1.0]).is_err());
+    }
 }
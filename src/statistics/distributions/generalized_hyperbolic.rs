@@ -0,0 +1,329 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use super::numerical::{
+    cdf_from_pdf, cf_from_pdf, entropy_from_pdf, mode_from_pdf, quantile_from_cdf, InverseCdfTable,
+};
+use crate::math::optimize::NelderMead;
+use crate::math::special::ln_bessel_k;
+use crate::statistics::{distributions::Distribution, DistributionError, NormalInverseGaussian};
+use num_complex::Complex;
+use rand::Rng;
+use std::f64::consts::PI;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Generalized hyperbolic distribution: X ~ GH(lambda, alpha, beta, delta, mu)
+///
+/// The Gaussian variance-mean mixture $X = \mu + \beta W + \sqrt{W} Z$ with
+/// $W$ generalized inverse Gaussian. Special cases include the
+/// normal-inverse Gaussian (lambda = -1/2) and hyperbolic (lambda = 1)
+/// distributions.
+/// <https://en.wikipedia.org/wiki/Generalised_hyperbolic_distribution>
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeneralizedHyperbolic {
+    /// Index (lambda).
+    lambda: f64,
+    /// Tail heaviness (alpha > |beta|).
+    alpha: f64,
+    /// Asymmetry (beta).
+    beta: f64,
+    /// Scale (delta > 0).
+    delta: f64,
+    /// Location (mu).
+    mu: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl GeneralizedHyperbolic {
+    /// New instance of a generalized hyperbolic distribution.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::assert_approx_equal;
+    /// # use RustQuant::statistics::distributions::*;
+    ///
+    /// let gh = GeneralizedHyperbolic::new(-0.5, 2.0, 0.5, 1.5, 0.1);
+    /// let nig = NormalInverseGaussian::new(2.0, 0.5, 1.5, 0.1);
+    ///
+    /// assert_approx_equal!(gh.pdf(0.7), nig.pdf(0.7), 1e-12);
+    /// assert_approx_equal!(gh.variance(), nig.variance(), 1e-10);
+    /// ```
+    pub fn new(lambda: f64, alpha: f64, beta: f64, delta: f64, mu: f64) -> Self {
+        assert!(alpha > beta.abs() && delta > 0.0);
+
+        Self {
+            lambda,
+            alpha,
+            beta,
+            delta,
+            mu,
+        }
+    }
+
+    /// Fits the distribution to data by maximum likelihood, starting from
+    /// the fitted normal-inverse Gaussian distribution.
+    pub fn fit(data: &[f64]) -> Result<Self, DistributionError> {
+        let nig = NormalInverseGaussian::fit(data)?;
+
+        let initial = [
+            -0.5,
+            nig.alpha().ln(),
+            (nig.beta() / nig.alpha()).atanh(),
+            nig.delta().ln(),
+            nig.mu(),
+        ];
+
+        let result = NelderMead::new(2_000, 1e-8)
+            .with_initial_step(0.1)
+            .minimize(
+                &|theta: &[f64]| {
+                    let dist = Self::from_unconstrained(theta);
+                    -data.iter().map(|x| dist.ln_pdf(*x)).sum::<f64>()
+                },
+                &initial,
+            );
+
+        match result.minimum.is_finite() {
+            true => Ok(Self::from_unconstrained(&result.minimizer)),
+            false => Err(DistributionError::Estimation(
+                "non-finite generalized hyperbolic likelihood".to_string(),
+            )),
+        }
+    }
+
+    /// Index parameter.
+    pub fn lambda(&self) -> f64 {
+        self.lambda
+    }
+
+    /// Tail heaviness parameter.
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Asymmetry parameter.
+    pub fn beta(&self) -> f64 {
+        self.beta
+    }
+
+    /// Scale parameter.
+    pub fn delta(&self) -> f64 {
+        self.delta
+    }
+
+    /// Location parameter.
+    pub fn mu(&self) -> f64 {
+        self.mu
+    }
+
+    /// Logarithm of the density.
+    pub fn ln_pdf(&self, x: f64) -> f64 {
+        let gamma = self.gamma();
+        let q = self.delta.hypot(x - self.mu);
+
+        self.lambda * (gamma / self.delta).ln()
+            - 0.5 * (2.0 * PI).ln()
+            - ln_bessel_k(self.lambda, self.delta * gamma)
+            + self.beta * (x - self.mu)
+            + ln_bessel_k(self.lambda - 0.5, self.alpha * q)
+            - (0.5 - self.lambda) * (q / self.alpha).ln()
+    }
+
+    // gamma = sqrt(alpha^2 - beta^2).
+    fn gamma(&self) -> f64 {
+        (self.alpha * self.alpha - self.beta * self.beta).sqrt()
+    }
+
+    // (lambda, ln alpha, atanh(beta / alpha), ln delta, mu) to parameters.
+    fn from_unconstrained(theta: &[f64]) -> Self {
+        let alpha = theta[1].exp();
+
+        Self::new(
+            theta[0],
+            alpha,
+            alpha * theta[2].tanh(),
+            theta[3].exp(),
+            theta[4],
+        )
+    }
+
+    // Distribution function tabulated between the 1e-10 and 1 - 1e-10
+    // quantiles, for sampling.
+    pub(crate) fn inverse_cdf_table(&self) -> InverseCdfTable {
+        InverseCdfTable::new(
+            |x| self.pdf(x),
+            self.inv_cdf(1e-10),
+            self.inv_cdf(1.0 - 1e-10),
+            8_000,
+        )
+    }
+
+    // Raw moments E[W^k], k = 1..4, of the generalized inverse Gaussian
+    // mixing variable.
+    fn mixing_moments(&self) -> [f64; 4] {
+        let gamma = self.gamma();
+        let zeta = self.delta * gamma;
+        let ln_k = ln_bessel_k(self.lambda, zeta);
+
+        [1, 2, 3, 4].map(|k| {
+            (self.delta / gamma).powi(k) * (ln_bessel_k(self.lambda + k as f64, zeta) - ln_k).exp()
+        })
+    }
+
+    // Variance, third and fourth central moments.
+    fn central_moments(&self) -> (f64, f64, f64) {
+        let [m1, m2, m3, m4] = self.mixing_moments();
+        let beta = self.beta;
+
+        let variance_w = m2 - m1 * m1;
+        let third_w = m3 - 3.0 * m1 * m2 + 2.0 * m1.powi(3);
+        let fourth_w = m4 - 4.0 * m1 * m3 + 6.0 * m1 * m1 * m2 - 3.0 * m1.powi(4);
+
+        (
+            m1 + beta * beta * variance_w,
+            beta.powi(3) * third_w + 3.0 * beta * variance_w,
+            beta.powi(4) * fourth_w + 6.0 * beta * beta * (third_w + m1 * variance_w) + 3.0 * m2,
+        )
+    }
+}
+
+impl Distribution for GeneralizedHyperbolic {
+    /// Characteristic function, by quadrature of the density.
+    fn cf(&self, t: f64) -> Complex<f64> {
+        cf_from_pdf(|x| self.pdf(x), t, self.mean(), self.variance().sqrt())
+    }
+
+    fn pdf(&self, x: f64) -> f64 {
+        self.ln_pdf(x).exp()
+    }
+
+    fn pmf(&self, x: f64) -> f64 {
+        self.pdf(x)
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        cdf_from_pdf(|y| self.pdf(y), x, self.mean(), self.variance().sqrt())
+    }
+
+    fn inv_cdf(&self, p: f64) -> f64 {
+        quantile_from_cdf(|x| self.cdf(x), p, self.mean(), self.variance().sqrt())
+    }
+
+    fn mean(&self) -> f64 {
+        self.mu + self.beta * self.mixing_moments()[0]
+    }
+
+    fn median(&self) -> f64 {
+        self.inv_cdf(0.5)
+    }
+
+    fn mode(&self) -> f64 {
+        mode_from_pdf(|x| self.pdf(x), self.mean(), self.variance().sqrt())
+    }
+
+    fn variance(&self) -> f64 {
+        self.central_moments().0
+    }
+
+    fn skewness(&self) -> f64 {
+        let (variance, third, _) = self.central_moments();
+
+        third / variance.powf(1.5)
+    }
+
+    /// Excess kurtosis of the distribution.
+    fn kurtosis(&self) -> f64 {
+        let (variance, _, fourth) = self.central_moments();
+
+        fourth / (variance * variance) - 3.0
+    }
+
+    fn entropy(&self) -> f64 {
+        entropy_from_pdf(|x| self.pdf(x), self.mean(), self.variance().sqrt())
+    }
+
+    /// Moment generating function, finite for $|\beta + t| < \alpha$.
+    fn mgf(&self, t: f64) -> f64 {
+        if (self.beta + t).abs() >= self.alpha {
+            return f64::INFINITY;
+        }
+
+        let gamma = self.gamma();
+        let shifted = (self.alpha.powi(2) - (self.beta + t).powi(2)).sqrt();
+
+        (self.mu * t
+            + self.lambda * (gamma / shifted).ln()
+            + ln_bessel_k(self.lambda, self.delta * shifted)
+            - ln_bessel_k(self.lambda, self.delta * gamma))
+        .exp()
+    }
+
+    /// Generates a random sample by inverting the tabulated distribution
+    /// function.
+    fn sample(&self, n: usize) -> Result<Vec<f64>, DistributionError> {
+        assert!(n > 0);
+
+        let mut rng = rand::thread_rng();
+        let table = self.inverse_cdf_table();
+
+        Ok((0..n).map(|_| table.invert(rng.gen())).collect())
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_generalized_hyperbolic_distribution() {
+        let dist = GeneralizedHyperbolic::new(1.3, 3.0, -1.0, 0.8, 0.2);
+
+        // Values computed with mpmath.
+        assert_approx_equal!(dist.pdf(0.7), 0.204511952249334, 1e-10);
+        assert_approx_equal!(dist.cdf(0.7), 0.929582129261757, 1e-9);
+        assert_approx_equal!(dist.cdf(-2.0), 0.032880843729268, 1e-9);
+        assert_approx_equal!(dist.inv_cdf(0.99), 1.308347803269097, 1e-7);
+        assert_approx_equal!(dist.mode(), -0.116763551460370, 1e-7);
+        assert_approx_equal!(dist.entropy(), 1.156946612573008, 1e-8);
+        assert_approx_equal!(dist.mgf(0.4), 0.917135226562547, 1e-10);
+
+        assert_approx_equal!(dist.mean(), -0.335724146731704, 1e-10);
+        assert_approx_equal!(dist.variance(), 0.636765169711021, 1e-10);
+        assert_approx_equal!(dist.skewness(), -0.688753892020349, 1e-9);
+        assert_approx_equal!(dist.kurtosis(), 1.524387495496786, 1e-9);
+
+        let cf = dist.cf(0.7);
+        assert_approx_equal!(cf.re, 0.840434702870048, 1e-9);
+        assert_approx_equal!(cf.im, -0.184751249661320, 1e-9);
+    }
+
+    #[test]
+    fn test_generalized_hyperbolic_fit() {
+        let dist = GeneralizedHyperbolic::new(1.0, 3.0, -1.0, 0.8, 0.2);
+        let table = dist.inverse_cdf_table();
+        let mut rng = StdRng::seed_from_u64(643);
+        let data: Vec<f64> = (0..2000).map(|_| table.invert(rng.gen())).collect();
+
+        let fitted = GeneralizedHyperbolic::fit(&data).unwrap();
+
+        assert_approx_equal!(fitted.mean(), dist.mean(), 0.05);
+        assert_approx_equal!(fitted.variance(), dist.variance(), 0.05);
+        assert!(fitted.skewness() < 0.0);
+    }
+}
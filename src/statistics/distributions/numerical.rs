@@ -0,0 +1,279 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Numerical routines shared by the distributions without closed-form
+//! distribution functions, quantiles or moments.
+
+use crate::math::integrate;
+use num_complex::Complex;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Tabulated distribution function, for sampling by inversion when there is
+/// no direct sampling algorithm.
+pub(crate) struct InverseCdfTable {
+    x: Vec<f64>,
+    cdf: Vec<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Composite tanh-sinh quadrature of `f` over `[a, b]`, with `panels`
+/// equal subintervals.
+pub(crate) fn integrate_composite<F>(f: F, a: f64, b: f64, panels: usize) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    let h = (b - a) / panels as f64;
+
+    (0..panels)
+        .map(|k| integrate(&f, a + k as f64 * h, a + (k + 1) as f64 * h))
+        .sum()
+}
+
+/// Integral of `f` over the real line, split at `center` and mapped to
+/// $(0, 1]$ on each side with $x = center \pm scale (1 - t) / t$.
+pub(crate) fn integrate_real_line<F>(f: F, center: f64, scale: f64) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    lower_integral(&f, center, scale) + upper_integral(&f, center, scale)
+}
+
+/// Distribution function from a density, integrating the nearer tail.
+pub(crate) fn cdf_from_pdf<F>(pdf: F, x: f64, center: f64, scale: f64) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    let p = match x <= center {
+        true => lower_integral(&pdf, x, scale),
+        false => 1.0 - upper_integral(&pdf, x, scale),
+    };
+
+    p.clamp(0.0, 1.0)
+}
+
+/// Characteristic function from a density.
+pub(crate) fn cf_from_pdf<F>(pdf: F, t: f64, center: f64, scale: f64) -> Complex<f64>
+where
+    F: Fn(f64) -> f64,
+{
+    Complex::new(
+        integrate_real_line(|x| (t * x).cos() * pdf(x), center, scale),
+        integrate_real_line(|x| (t * x).sin() * pdf(x), center, scale),
+    )
+}
+
+/// Differential entropy from a density.
+pub(crate) fn entropy_from_pdf<F>(pdf: F, center: f64, scale: f64) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    integrate_real_line(
+        |x| match pdf(x) {
+            f if f > 0.0 => -f * f.ln(),
+            _ => 0.0,
+        },
+        center,
+        scale,
+    )
+}
+
+/// Quantile of a continuous distribution function, by bracketing around
+/// `center` and bisection.
+pub(crate) fn quantile_from_cdf<F>(cdf: F, p: f64, center: f64, scale: f64) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    if !(0.0..=1.0).contains(&p) {
+        return f64::NAN;
+    }
+    if p == 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p == 1.0 {
+        return f64::INFINITY;
+    }
+
+    let mut width = scale;
+    let mut lower = center - width;
+    while cdf(lower) > p {
+        width *= 2.0;
+        lower = center - width;
+    }
+
+    let mut width = scale;
+    let mut upper = center + width;
+    while cdf(upper) < p {
+        width *= 2.0;
+        upper = center + width;
+    }
+
+    for _ in 0..200 {
+        let middle = 0.5 * (lower + upper);
+        if upper - lower <= 1e-12 * (1.0 + middle.abs()) {
+            break;
+        }
+        match cdf(middle) < p {
+            true => lower = middle,
+            false => upper = middle,
+        }
+    }
+
+    0.5 * (lower + upper)
+}
+
+/// Mode of a unimodal density, by golden-section search on
+/// `[center - 5 scale, center + 5 scale]`.
+pub(crate) fn mode_from_pdf<F>(pdf: F, center: f64, scale: f64) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    let ratio = 0.5 * (5.0_f64.sqrt() - 1.0);
+
+    let (mut a, mut b) = (center - 5.0 * scale, center + 5.0 * scale);
+    let mut c = b - ratio * (b - a);
+    let mut d = a + ratio * (b - a);
+
+    while b - a > 1e-10 * (1.0 + c.abs()) {
+        match pdf(c) > pdf(d) {
+            true => b = d,
+            false => a = c,
+        }
+        c = b - ratio * (b - a);
+        d = a + ratio * (b - a);
+    }
+
+    0.5 * (a + b)
+}
+
+/// Median and a robust scale (normalised interquartile range) of the data.
+pub(crate) fn median_and_spread(data: &[f64]) -> (f64, f64) {
+    let mut sorted = data.to_vec();
+    sorted.sort_by(f64::total_cmp);
+
+    let quantile = |p: f64| {
+        let h = p * (sorted.len() - 1) as f64;
+        let k = h.floor() as usize;
+        let upper = sorted[(k + 1).min(sorted.len() - 1)];
+        sorted[k] + (h - k as f64) * (upper - sorted[k])
+    };
+
+    let spread = (quantile(0.75) - quantile(0.25)) / 1.349;
+
+    (quantile(0.5), if spread > 0.0 { spread } else { 1.0 })
+}
+
+fn lower_integral<F>(f: &F, x: f64, scale: f64) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    integrate_composite(
+        |t| f(x - scale * (1.0 - t) / t) * scale / (t * t),
+        0.0,
+        1.0,
+        8,
+    )
+}
+
+fn upper_integral<F>(f: &F, x: f64, scale: f64) -> f64
+where
+    F: Fn(f64) -> f64,
+{
+    integrate_composite(
+        |t| f(x + scale * (1.0 - t) / t) * scale / (t * t),
+        0.0,
+        1.0,
+        8,
+    )
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl InverseCdfTable {
+    /// Tabulates the distribution function between `lower` and `upper`
+    /// (e.g. extreme quantiles), with Simpson's rule on the density.
+    pub(crate) fn new<F>(pdf: F, lower: f64, upper: f64, n: usize) -> Self
+    where
+        F: Fn(f64) -> f64,
+    {
+        let h = (upper - lower) / n as f64;
+
+        let x: Vec<f64> = (0..=n).map(|k| lower + k as f64 * h).collect();
+        let mut cdf = Vec::with_capacity(n + 1);
+        cdf.push(0.0);
+
+        let mut left = pdf(x[0]);
+        for k in 0..n {
+            let right = pdf(x[k + 1]);
+            let middle = pdf(x[k] + 0.5 * h);
+            cdf.push(cdf[k] + h * (left + 4.0 * middle + right) / 6.0);
+            left = right;
+        }
+
+        let total = cdf[n];
+        cdf.iter_mut().for_each(|p| *p /= total);
+
+        Self { x, cdf }
+    }
+
+    /// Value at probability `u`, interpolating linearly between nodes.
+    pub(crate) fn invert(&self, u: f64) -> f64 {
+        let k = self
+            .cdf
+            .partition_point(|p| *p < u)
+            .clamp(1, self.x.len() - 1);
+        let (p0, p1) = (self.cdf[k - 1], self.cdf[k]);
+
+        match p1 > p0 {
+            true => self.x[k - 1] + (u - p0) / (p1 - p0) * (self.x[k] - self.x[k - 1]),
+            false => self.x[k],
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_numerical {
+    use super::*;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_numerical_distribution_functions() {
+        let pdf = |x: f64| (-0.5 * x * x).exp() / (2.0 * PI).sqrt();
+        let cdf = |x: f64| cdf_from_pdf(pdf, x, 0.0, 1.0);
+
+        assert_approx_equal!(cdf(-1.0), 0.158655253931457, 1e-10);
+        assert_approx_equal!(cdf(2.5), 0.993790334674224, 1e-10);
+        assert_approx_equal!(
+            quantile_from_cdf(cdf, 0.975, 0.0, 1.0),
+            1.959963984540054,
+            1e-8
+        );
+        assert_approx_equal!(mode_from_pdf(pdf, 0.3, 1.0), 0.0, 1e-6);
+        assert_approx_equal!(
+            entropy_from_pdf(pdf, 0.0, 1.0),
+            0.5 * (2.0 * PI * 1f64.exp()).ln(),
+            1e-9
+        );
+        assert_approx_equal!(cf_from_pdf(pdf, 1.0, 0.0, 1.0).re, (-0.5_f64).exp(), 1e-9);
+
+        let table = InverseCdfTable::new(pdf, -10.0, 10.0, 4000);
+        assert_approx_equal!(table.invert(0.975), 1.959963984540054, 1e-5);
+    }
+}
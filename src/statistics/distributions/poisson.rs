@@ -42,6 +42,33 @@ impl Poisson {
 
         Poisson { lambda }
     }
+
+    /// Fits the distribution to count data by maximum likelihood
+    /// (the sample mean).
+    /// # Examples
+    /// ```
+    /// # use RustQuant::statistics::distributions::*;
+    ///
+    /// let poisson = Poisson::fit(&[0.0, 2.0, 1.0, 3.0]).unwrap();
+    ///
+    /// assert_eq!(poisson.mean(), 1.5);
+    /// ```
+    pub fn fit(data: &[f64]) -> Result<Poisson, DistributionError> {
+        if data.iter().any(|x| *x < 0.0 || x.fract() != 0.0) {
+            return Err(DistributionError::Estimation(
+                "the data must be non-negative counts".to_string(),
+            ));
+        }
+
+        let lambda = data.iter().sum::<f64>() / data.len() as f64;
+
+        match lambda > 0.0 {
+            true => Ok(Poisson { lambda }),
+            false => Err(DistributionError::Estimation(
+                "the data must contain a positive count".to_string(),
+            )),
+        }
+    }
 }
 
 impl Distribution for Poisson {
@@ -275,7 +302,6 @@ impl Distribution for Poisson {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::assert_approx_equal;
 
     #[test]
     fn test_poisson_distribution() -> Result<(), DistributionError> {
@@ -334,4 +360,16 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_poisson_fit() -> Result<(), DistributionError> {
+        let sample = Poisson::new(3.0).sample(10_000)?;
+        let fitted = Poisson::fit(&sample)?;
+
+        assert_approx_equal!(fitted.mean(), 3.0, 0.1);
+        assert!(Poisson::fit(&[1.0, 2.5]).is_err());
+        assert!(Poisson::fit(&[0.0, 0.0]).is_err());
+
+        Ok(())
+    }
 }
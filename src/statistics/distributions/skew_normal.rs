@@ -0,0 +1,264 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use super::numerical::{entropy_from_pdf, mode_from_pdf, quantile_from_cdf};
+use crate::math::special::{norm_cdf, owens_t};
+use crate::math::{integrate, optimize::NelderMead};
+use crate::statistics::{distributions::Distribution, DistributionError, Statistic};
+use num_complex::Complex;
+use rand::Rng;
+use rand_distr::StandardNormal;
+use std::f64::consts::{FRAC_1_SQRT_2, PI};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Skew-normal distribution: X ~ SN(xi, omega, alpha), with density
+/// $\frac{2}{\omega} \phi(z) \Phi(\alpha z)$, $z = (x - \xi) / \omega$.
+/// <https://en.wikipedia.org/wiki/Skew_normal_distribution>
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SkewNormal {
+    /// Location (xi).
+    location: f64,
+    /// Scale (omega).
+    scale: f64,
+    /// Shape (alpha), the Gaussian distribution for alpha = 0.
+    shape: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl SkewNormal {
+    /// New instance of a skew-normal distribution.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::assert_approx_equal;
+    /// # use RustQuant::statistics::distributions::*;
+    ///
+    /// let dist = SkewNormal::new(0.0, 1.0, -4.0);
+    ///
+    /// assert!(dist.skewness() < 0.0);
+    /// assert_approx_equal!(dist.cdf(dist.inv_cdf(0.01)), 0.01, 1e-9);
+    /// ```
+    pub fn new(location: f64, scale: f64, shape: f64) -> Self {
+        assert!(scale > 0.0);
+
+        Self {
+            location,
+            scale,
+            shape,
+        }
+    }
+
+    /// Fits the distribution to data by maximum likelihood, starting from
+    /// the method of moments.
+    pub fn fit(data: &[f64]) -> Result<Self, DistributionError> {
+        if data.len() < 3 {
+            return Err(DistributionError::Estimation(
+                "at least 3 observations are required".to_string(),
+            ));
+        }
+
+        // Method of moments, with the skewness inside the attainable range.
+        let data_vec = data.to_vec();
+        let skewness = data_vec.skewness().clamp(-0.99, 0.99);
+        let ratio = skewness.abs().powf(2.0 / 3.0);
+        let delta = skewness.signum()
+            * (0.5 * PI * ratio / (ratio + (0.5 * (4.0 - PI)).powf(2.0 / 3.0))).sqrt();
+        let scale = (data_vec.variance() / (1.0 - 2.0 * delta * delta / PI)).sqrt();
+        let location = data_vec.mean() - scale * delta * (2.0 / PI).sqrt();
+        let shape = delta / (1.0 - delta * delta).sqrt();
+
+        let result = NelderMead::new(2_000, 1e-8)
+            .with_initial_step(0.1)
+            .minimize(
+                &|theta: &[f64]| {
+                    let dist = SkewNormal::new(theta[0], theta[1].exp(), theta[2]);
+                    -data.iter().map(|x| dist.ln_pdf(*x)).sum::<f64>()
+                },
+                &[location, scale.ln(), shape],
+            );
+
+        match result.minimum.is_finite() {
+            true => Ok(SkewNormal::new(
+                result.minimizer[0],
+                result.minimizer[1].exp(),
+                result.minimizer[2],
+            )),
+            false => Err(DistributionError::Estimation(
+                "non-finite skew-normal likelihood".to_string(),
+            )),
+        }
+    }
+
+    /// Location parameter.
+    pub fn location(&self) -> f64 {
+        self.location
+    }
+
+    /// Scale parameter.
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Shape parameter.
+    pub fn shape(&self) -> f64 {
+        self.shape
+    }
+
+    /// Logarithm of the density.
+    pub fn ln_pdf(&self, x: f64) -> f64 {
+        let z = (x - self.location) / self.scale;
+
+        (2.0 / self.scale).ln() - 0.5 * z * z - 0.5 * (2.0 * PI).ln()
+            + norm_cdf(self.shape * z).ln()
+    }
+
+    // delta = alpha / sqrt(1 + alpha^2).
+    fn delta(&self) -> f64 {
+        self.shape / (1.0 + self.shape * self.shape).sqrt()
+    }
+
+    pub(crate) fn draw<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let delta = self.delta();
+        let u: f64 = rng.sample(StandardNormal);
+        let v: f64 = rng.sample(StandardNormal);
+
+        self.location + self.scale * (delta * u.abs() + (1.0 - delta * delta).sqrt() * v)
+    }
+}
+
+impl Distribution for SkewNormal {
+    /// Characteristic function of the skew-normal distribution,
+    /// $e^{i \xi t - \omega^2 t^2 / 2} (1 + i \operatorname{erfi}(\delta \omega t / \sqrt{2}))$.
+    fn cf(&self, t: f64) -> Complex<f64> {
+        let damping = 0.5 * (self.scale * t).powi(2);
+        let u = self.delta() * self.scale * t * FRAC_1_SQRT_2;
+
+        // erfi(u) e^{-omega^2 t^2 / 2}, without overflowing erfi.
+        let imaginary = 2.0 / PI.sqrt() * integrate(|s| (s * s - damping).exp(), 0.0, u);
+
+        Complex::new(0.0, self.location * t).exp() * Complex::new((-damping).exp(), imaginary)
+    }
+
+    fn pdf(&self, x: f64) -> f64 {
+        self.ln_pdf(x).exp()
+    }
+
+    fn pmf(&self, x: f64) -> f64 {
+        self.pdf(x)
+    }
+
+    /// Distribution function, $\Phi(z) - 2 T(z, \alpha)$ with Owen's T function.
+    fn cdf(&self, x: f64) -> f64 {
+        let z = (x - self.location) / self.scale;
+
+        (norm_cdf(z) - 2.0 * owens_t(z, self.shape)).clamp(0.0, 1.0)
+    }
+
+    fn inv_cdf(&self, p: f64) -> f64 {
+        quantile_from_cdf(|x| self.cdf(x), p, self.mean(), self.variance().sqrt())
+    }
+
+    fn mean(&self) -> f64 {
+        self.location + self.scale * self.delta() * (2.0 / PI).sqrt()
+    }
+
+    fn median(&self) -> f64 {
+        self.inv_cdf(0.5)
+    }
+
+    fn mode(&self) -> f64 {
+        mode_from_pdf(|x| self.pdf(x), self.mean(), self.variance().sqrt())
+    }
+
+    fn variance(&self) -> f64 {
+        self.scale.powi(2) * (1.0 - 2.0 * self.delta().powi(2) / PI)
+    }
+
+    fn skewness(&self) -> f64 {
+        let m = self.delta() * (2.0 / PI).sqrt();
+
+        0.5 * (4.0 - PI) * m.powi(3) / (1.0 - m * m).powf(1.5)
+    }
+
+    /// Excess kurtosis of the distribution.
+    fn kurtosis(&self) -> f64 {
+        let m = self.delta() * (2.0 / PI).sqrt();
+
+        2.0 * (PI - 3.0) * m.powi(4) / (1.0 - m * m).powi(2)
+    }
+
+    fn entropy(&self) -> f64 {
+        entropy_from_pdf(|x| self.pdf(x), self.mean(), self.variance().sqrt())
+    }
+
+    fn mgf(&self, t: f64) -> f64 {
+        2.0 * (self.location * t + 0.5 * (self.scale * t).powi(2)).exp()
+            * norm_cdf(self.scale * self.delta() * t)
+    }
+
+    fn sample(&self, n: usize) -> Result<Vec<f64>, DistributionError> {
+        assert!(n > 0);
+
+        let mut rng = rand::thread_rng();
+
+        Ok((0..n).map(|_| self.draw(&mut rng)).collect())
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_skew_normal_distribution() {
+        let dist = SkewNormal::new(0.5, 2.0, 3.0);
+
+        // Values computed with mpmath.
+        assert_approx_equal!(dist.pdf(1.3), 0.325893416706118, 1e-10);
+        assert_approx_equal!(dist.cdf(1.3), 0.323664176922794, 1e-10);
+        assert_approx_equal!(dist.cdf(-2.0), 2.32796523478299e-6, 1e-12);
+        assert_approx_equal!(dist.inv_cdf(0.05), 0.173733469608219, 1e-8);
+        assert_approx_equal!(dist.mode(), 1.446791258733627, 1e-7);
+        assert_approx_equal!(dist.entropy(), 1.645982149600759, 1e-8);
+        assert_approx_equal!(dist.mgf(0.7), 6.865896970841889, 1e-10);
+
+        let cf = dist.cf(0.7);
+        assert_approx_equal!(cf.re, 0.162801348939711, 1e-10);
+        assert_approx_equal!(cf.im, 0.648531280187850, 1e-10);
+
+        // alpha = 0 is the Gaussian distribution.
+        let gaussian = SkewNormal::new(1.0, 2.0, 0.0);
+        assert_approx_equal!(gaussian.cdf(3.0), 0.841344746068543, 1e-10);
+        assert_eq!(gaussian.skewness(), 0.0);
+    }
+
+    #[test]
+    fn test_skew_normal_fit() {
+        let dist = SkewNormal::new(-0.2, 1.5, 4.0);
+        let mut rng = StdRng::seed_from_u64(643);
+        let data: Vec<f64> = (0..2000).map(|_| dist.draw(&mut rng)).collect();
+
+        let fitted = SkewNormal::fit(&data).unwrap();
+
+        assert_approx_equal!(fitted.location(), -0.2, 0.1);
+        assert_approx_equal!(fitted.scale(), 1.5, 0.1);
+        assert_approx_equal!(fitted.shape(), 4.0, 1.0);
+        assert_approx_equal!(fitted.mean(), data.iter().sum::<f64>() / 2000.0, 0.01);
+    }
+}
@@ -0,0 +1,483 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use super::numerical::{
+    entropy_from_pdf, integrate_composite, median_and_spread, mode_from_pdf, quantile_from_cdf,
+};
+use crate::statistics::{distributions::Distribution, DistributionError};
+use num_complex::Complex;
+use rand::Rng;
+use rand_distr::Exp1;
+use statrs::function::gamma::gamma;
+use std::f64::consts::{FRAC_PI_2, PI};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Alpha-stable distribution: X ~ S(alpha, beta, sigma, mu), in the
+/// Samorodnitsky-Taqqu (S1) parametrization, with characteristic function
+///
+/// $$
+/// \phi(t) = \exp\left(i \mu t - |\sigma t|^\alpha (1 - i \beta \operatorname{sign}(t) \tan(\pi \alpha / 2))\right),
+/// \quad \alpha \neq 1
+/// $$
+///
+/// and $\tan(\pi \alpha / 2)$ replaced by $-\frac{2}{\pi} \ln |t|$ for
+/// $\alpha = 1$. Densities and distribution functions use the integral
+/// representations of Nolan (1997).
+/// <https://en.wikipedia.org/wiki/Stable_distribution>
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stable {
+    /// Stability index (0 < alpha <= 2).
+    alpha: f64,
+    /// Skewness (-1 <= beta <= 1).
+    beta: f64,
+    /// Scale (sigma > 0).
+    scale: f64,
+    /// Location (mu).
+    location: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Stable {
+    /// New instance of an alpha-stable distribution.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::assert_approx_equal;
+    /// # use RustQuant::statistics::distributions::*;
+    ///
+    /// // alpha = 1, beta = 0 is the Cauchy distribution.
+    /// let cauchy = Stable::new(1.0, 0.0, 1.0, 0.0);
+    ///
+    /// assert_approx_equal!(cauchy.cdf(1.0), 0.75, 1e-10);
+    /// assert_eq!(cauchy.variance(), f64::INFINITY);
+    /// ```
+    pub fn new(alpha: f64, beta: f64, scale: f64, location: f64) -> Self {
+        assert!(alpha > 0.0 && alpha <= 2.0);
+        assert!((-1.0..=1.0).contains(&beta) && scale > 0.0);
+
+        Self {
+            alpha,
+            beta,
+            scale,
+            location,
+        }
+    }
+
+    /// Fits the distribution to data by regression on the empirical
+    /// characteristic function (Koutrouvelis, 1980), since the likelihood
+    /// has no closed form.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::statistics::distributions::*;
+    ///
+    /// let returns = Stable::new(1.7, 0.0, 0.01, 0.0).sample(5000).unwrap();
+    /// let fitted = Stable::fit(&returns).unwrap();
+    ///
+    /// assert!((fitted.alpha() - 1.7).abs() < 0.2);
+    /// ```
+    pub fn fit(data: &[f64]) -> Result<Self, DistributionError> {
+        if data.len() < 10 {
+            return Err(DistributionError::Estimation(
+                "at least 10 observations are required".to_string(),
+            ));
+        }
+
+        // Standardise with robust location and scale estimates.
+        let (median, spread) = median_and_spread(data);
+        let z: Vec<f64> = data.iter().map(|x| (x - median) / spread).collect();
+        let n = z.len() as f64;
+
+        let t: Vec<f64> = (1..=10).map(|k| 0.1 * k as f64).collect();
+        let ecf: Vec<Complex<f64>> = t
+            .iter()
+            .map(|t| {
+                z.iter()
+                    .map(|z| Complex::new(0.0, t * z).exp())
+                    .sum::<Complex<f64>>()
+                    / n
+            })
+            .collect();
+
+        // ln(-ln |phi(t)|^2) = ln(2 sigma^alpha) + alpha ln t.
+        let x: Vec<f64> = t.iter().map(|t| t.ln()).collect();
+        let y: Vec<f64> = ecf.iter().map(|phi| (-phi.norm_sqr().ln()).ln()).collect();
+        if y.iter().any(|y| !y.is_finite()) {
+            return Err(DistributionError::Estimation(
+                "degenerate empirical characteristic function".to_string(),
+            ));
+        }
+
+        let (x_mean, y_mean) = (x.iter().sum::<f64>() / 10.0, y.iter().sum::<f64>() / 10.0);
+        let slope = x
+            .iter()
+            .zip(&y)
+            .map(|(x, y)| (x - x_mean) * (y - y_mean))
+            .sum::<f64>()
+            / x.iter().map(|x| (x - x_mean).powi(2)).sum::<f64>();
+        let alpha = slope.clamp(0.1, 2.0);
+        let sigma = (((y_mean - slope * x_mean) - 2f64.ln()) / alpha).exp();
+
+        // arg phi(t) = mu t + beta sigma^alpha tan(pi alpha / 2) t^alpha,
+        // with t ln t instead of t^alpha near alpha = 1.
+        let near_cauchy = (alpha - 1.0).abs() < 0.05;
+        let regressor = |t: f64| match near_cauchy {
+            true => t * t.ln(),
+            false => t.powf(alpha),
+        };
+        let arg: Vec<f64> = ecf.iter().map(|phi| phi.arg()).collect();
+        let (mu, b) = least_squares_2(
+            &t,
+            &t.iter().map(|t| regressor(*t)).collect::<Vec<_>>(),
+            &arg,
+        );
+
+        let beta = match alpha {
+            alpha if alpha >= 1.99 => 0.0,
+            _ if near_cauchy => -b * PI / (2.0 * sigma),
+            alpha => b / (sigma.powf(alpha) * (0.5 * PI * alpha).tan()),
+        }
+        .clamp(-1.0, 1.0);
+
+        let location = match near_cauchy {
+            true => median + spread * mu - 2.0 / PI * beta * spread * sigma * spread.ln(),
+            false => median + spread * mu,
+        };
+
+        Ok(Self::new(alpha, beta, spread * sigma, location))
+    }
+
+    /// Stability index.
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Skewness parameter.
+    pub fn beta(&self) -> f64 {
+        self.beta
+    }
+
+    /// Scale parameter.
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Location parameter.
+    pub fn location(&self) -> f64 {
+        self.location
+    }
+
+    // Standardised value, distributed as S(alpha, beta, 1, 0).
+    fn standardise(&self, x: f64) -> f64 {
+        let z = (x - self.location) / self.scale;
+
+        match self.alpha == 1.0 {
+            true => z - 2.0 / PI * self.beta * self.scale.ln(),
+            false => z,
+        }
+    }
+
+    pub(crate) fn draw<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        // Chambers, Mallows and Stuck (1976).
+        let (alpha, beta) = (self.alpha, self.beta);
+        let u = PI * (rng.gen::<f64>() - 0.5);
+        let w: f64 = rng.sample(Exp1);
+
+        match alpha == 1.0 {
+            true => {
+                let z = 2.0 / PI
+                    * ((FRAC_PI_2 + beta * u) * u.tan()
+                        - beta * (FRAC_PI_2 * w * u.cos() / (FRAC_PI_2 + beta * u)).ln());
+                self.scale * z + 2.0 / PI * beta * self.scale * self.scale.ln() + self.location
+            }
+            false => {
+                let zeta = beta * (0.5 * PI * alpha).tan();
+                let b = zeta.atan() / alpha;
+                let s = (1.0 + zeta * zeta).powf(0.5 / alpha);
+                let z = s * (alpha * (u + b)).sin() / u.cos().powf(1.0 / alpha)
+                    * ((u - alpha * (u + b)).cos() / w).powf((1.0 - alpha) / alpha);
+                self.scale * z + self.location
+            }
+        }
+    }
+}
+
+impl Distribution for Stable {
+    fn cf(&self, t: f64) -> Complex<f64> {
+        let (alpha, beta) = (self.alpha, self.beta);
+        let scaled = (self.scale * t).abs();
+
+        let skew = match alpha == 1.0 {
+            true if t != 0.0 => -2.0 / PI * t.abs().ln(),
+            true => 0.0,
+            false => (0.5 * PI * alpha).tan(),
+        };
+
+        (Complex::new(0.0, self.location * t)
+            - scaled.powf(alpha) * Complex::new(1.0, -beta * t.signum() * skew))
+        .exp()
+    }
+
+    fn pdf(&self, x: f64) -> f64 {
+        standard_pdf(self.standardise(x), self.alpha, self.beta) / self.scale
+    }
+
+    fn pmf(&self, x: f64) -> f64 {
+        self.pdf(x)
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        standard_cdf(self.standardise(x), self.alpha, self.beta).clamp(0.0, 1.0)
+    }
+
+    fn inv_cdf(&self, p: f64) -> f64 {
+        quantile_from_cdf(|x| self.cdf(x), p, self.location, self.scale)
+    }
+
+    /// Mean of the distribution (undefined for alpha <= 1).
+    fn mean(&self) -> f64 {
+        match self.alpha > 1.0 {
+            true => self.location,
+            false => f64::NAN,
+        }
+    }
+
+    fn median(&self) -> f64 {
+        self.inv_cdf(0.5)
+    }
+
+    fn mode(&self) -> f64 {
+        mode_from_pdf(|x| self.pdf(x), self.location, self.scale)
+    }
+
+    /// Variance of the distribution (infinite for alpha < 2).
+    fn variance(&self) -> f64 {
+        match self.alpha == 2.0 {
+            true => 2.0 * self.scale * self.scale,
+            false => f64::INFINITY,
+        }
+    }
+
+    fn skewness(&self) -> f64 {
+        match self.alpha == 2.0 {
+            true => 0.0,
+            false => f64::NAN,
+        }
+    }
+
+    fn kurtosis(&self) -> f64 {
+        match self.alpha == 2.0 {
+            true => 0.0,
+            false => f64::NAN,
+        }
+    }
+
+    fn entropy(&self) -> f64 {
+        entropy_from_pdf(|x| self.pdf(x), self.location, self.scale)
+    }
+
+    /// Moment generating function, infinite away from t = 0 for alpha < 2.
+    fn mgf(&self, t: f64) -> f64 {
+        match (self.alpha == 2.0, t == 0.0) {
+            (true, _) => (self.location * t + (self.scale * t).powi(2)).exp(),
+            (false, true) => 1.0,
+            (false, false) => f64::INFINITY,
+        }
+    }
+
+    fn sample(&self, n: usize) -> Result<Vec<f64>, DistributionError> {
+        assert!(n > 0);
+
+        let mut rng = rand::thread_rng();
+
+        Ok((0..n).map(|_| self.draw(&mut rng)).collect())
+    }
+}
+
+// Number of panels for the integrals of Nolan (1997).
+const PANELS: usize = 16;
+
+/// Density of S(alpha, beta, 1, 0), from Nolan (1997).
+fn standard_pdf(x: f64, alpha: f64, beta: f64) -> f64 {
+    if alpha == 2.0 {
+        return (-0.25 * x * x).exp() / (4.0 * PI).sqrt();
+    }
+    if alpha == 1.0 {
+        return match beta {
+            0.0 => 1.0 / (PI * (1.0 + x * x)),
+            beta if beta < 0.0 => standard_pdf(-x, 1.0, -beta),
+            beta => {
+                let c = (-PI * x / (2.0 * beta)).exp();
+                let v = |theta: f64| {
+                    2.0 / PI * (FRAC_PI_2 + beta * theta) / theta.cos()
+                        * ((FRAC_PI_2 + beta * theta) * theta.tan() / beta).exp()
+                };
+                c / (2.0 * beta)
+                    * integrate_composite(
+                        |theta| v(theta) * (-c * v(theta)).exp(),
+                        -FRAC_PI_2,
+                        FRAC_PI_2,
+                        PANELS,
+                    )
+            }
+        };
+    }
+    if x < 0.0 {
+        return standard_pdf(-x, alpha, -beta);
+    }
+
+    let theta_0 = (beta * (0.5 * PI * alpha).tan()).atan() / alpha;
+
+    if x == 0.0 {
+        let zeta = beta * (0.5 * PI * alpha).tan();
+        return gamma(1.0 + 1.0 / alpha) * theta_0.cos()
+            / (PI * (1.0 + zeta * zeta).powf(0.5 / alpha));
+    }
+
+    let v = nolan_v(alpha, theta_0);
+    let c = x.powf(alpha / (alpha - 1.0));
+
+    alpha * x.powf(1.0 / (alpha - 1.0)) / (PI * (alpha - 1.0).abs())
+        * integrate_composite(
+            |theta| v(theta) * (-c * v(theta)).exp(),
+            -theta_0,
+            FRAC_PI_2,
+            PANELS,
+        )
+}
+
+/// Distribution function of S(alpha, beta, 1, 0), from Nolan (1997).
+fn standard_cdf(x: f64, alpha: f64, beta: f64) -> f64 {
+    if alpha == 2.0 {
+        return 0.5 * statrs::function::erf::erfc(-0.5 * x);
+    }
+    if alpha == 1.0 {
+        return match beta {
+            0.0 => 0.5 + x.atan() / PI,
+            beta if beta < 0.0 => 1.0 - standard_cdf(-x, 1.0, -beta),
+            beta => {
+                let c = (-PI * x / (2.0 * beta)).exp();
+                let v = |theta: f64| {
+                    2.0 / PI * (FRAC_PI_2 + beta * theta) / theta.cos()
+                        * ((FRAC_PI_2 + beta * theta) * theta.tan() / beta).exp()
+                };
+                integrate_composite(|theta| (-c * v(theta)).exp(), -FRAC_PI_2, FRAC_PI_2, PANELS)
+                    / PI
+            }
+        };
+    }
+    if x < 0.0 {
+        return 1.0 - standard_cdf(-x, alpha, -beta);
+    }
+
+    let theta_0 = (beta * (0.5 * PI * alpha).tan()).atan() / alpha;
+
+    if x == 0.0 {
+        return (FRAC_PI_2 - theta_0) / PI;
+    }
+
+    let v = nolan_v(alpha, theta_0);
+    let c = x.powf(alpha / (alpha - 1.0));
+    let integral =
+        integrate_composite(|theta| (-c * v(theta)).exp(), -theta_0, FRAC_PI_2, PANELS) / PI;
+
+    match alpha < 1.0 {
+        true => (FRAC_PI_2 - theta_0) / PI + integral,
+        false => 1.0 - integral,
+    }
+}
+
+/// The function $V(\theta; \alpha, \beta)$ of Nolan (1997), for alpha != 1.
+fn nolan_v(alpha: f64, theta_0: f64) -> impl Fn(f64) -> f64 {
+    move |theta: f64| {
+        (alpha * theta_0).cos().powf(1.0 / (alpha - 1.0))
+            * (theta.cos() / (alpha * (theta_0 + theta)).sin()).powf(alpha / (alpha - 1.0))
+            * (alpha * theta_0 + (alpha - 1.0) * theta).cos()
+            / theta.cos()
+    }
+}
+
+/// Least squares coefficients of y on two regressors, without intercept.
+fn least_squares_2(x1: &[f64], x2: &[f64], y: &[f64]) -> (f64, f64) {
+    let dot = |a: &[f64], b: &[f64]| a.iter().zip(b).map(|(a, b)| a * b).sum::<f64>();
+
+    let (a11, a12, a22) = (dot(x1, x1), dot(x1, x2), dot(x2, x2));
+    let (b1, b2) = (dot(x1, y), dot(x2, y));
+    let determinant = a11 * a22 - a12 * a12;
+
+    (
+        (a22 * b1 - a12 * b2) / determinant,
+        (a11 * b2 - a12 * b1) / determinant,
+    )
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_stable_distribution() {
+        // Values computed by Fourier inversion with mpmath:
+        // (alpha, beta, x, pdf, cdf).
+        let cases = [
+            (1.5, 0.5, 0.7, 0.174917321653105, 0.749404113308604),
+            (1.5, 0.5, -1.0, 0.268046496554462, 0.321987153858349),
+            (1.5, 0.5, 4.0, 0.015129501902706, 0.960291096869076),
+            (0.8, -0.3, 0.7, 0.059826687358587, 0.862042530653585),
+            (0.8, -0.3, -2.5, 0.087110827106871, 0.254954228270469),
+            (1.0, 0.5, 0.7, 0.197301722672009, 0.610127520690969),
+            (1.0, 0.5, -1.2, 0.130642137920241, 0.134616215734644),
+            (2.0, 0.0, 0.5, 0.265003532344029, 0.638163195084118),
+        ];
+
+        for (alpha, beta, x, pdf, cdf) in cases {
+            let dist = Stable::new(alpha, beta, 1.0, 0.0);
+            assert_approx_equal!(dist.pdf(x), pdf, 1e-8);
+            assert_approx_equal!(dist.cdf(x), cdf, 1e-8);
+        }
+
+        // Scale and location.
+        let dist = Stable::new(1.5, 0.5, 2.0, 1.0);
+        assert_approx_equal!(dist.pdf(2.4), 0.174917321653105 / 2.0, 1e-8);
+        assert_approx_equal!(dist.inv_cdf(0.749404113308604), 2.4, 1e-6);
+
+        // alpha = 2 is Gaussian with variance 2 sigma^2.
+        let cf = Stable::new(2.0, 0.0, 1.0, 0.0).cf(1.0);
+        assert_approx_equal!(cf.re, (-1.0_f64).exp(), 1e-12);
+    }
+
+    #[test]
+    fn test_stable_sampling_and_fit() {
+        let dist = Stable::new(1.6, 0.5, 1.0, 0.2);
+        let mut rng = StdRng::seed_from_u64(643);
+        let data: Vec<f64> = (0..5000).map(|_| dist.draw(&mut rng)).collect();
+
+        // Empirical distribution function of the Chambers-Mallows-Stuck draws.
+        for x in [-1.0, 0.2, 1.5] {
+            let empirical = data.iter().filter(|y| **y <= x).count() as f64 / 5000.0;
+            assert_approx_equal!(empirical, dist.cdf(x), 0.02);
+        }
+
+        let fitted = Stable::fit(&data).unwrap();
+
+        assert_approx_equal!(fitted.alpha(), 1.6, 0.1);
+        assert_approx_equal!(fitted.beta(), 0.5, 0.3);
+        assert_approx_equal!(fitted.scale(), 1.0, 0.1);
+        assert_approx_equal!(fitted.location(), 0.2, 0.2);
+    }
+}
@@ -0,0 +1,304 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use super::numerical::median_and_spread;
+use crate::math::optimize::NelderMead;
+use crate::math::special::ln_bessel_k;
+use crate::statistics::{distributions::Distribution, DistributionError};
+use num_complex::Complex;
+use rand::Rng;
+use statrs::function::beta::{beta_reg, inv_beta_reg, ln_beta};
+use statrs::function::gamma::{digamma, ln_gamma};
+use std::f64::consts::{LN_2, PI};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Location-scale Student's t distribution: X = mu + sigma * T, T ~ t(nu)
+/// <https://en.wikipedia.org/wiki/Student%27s_t-distribution>
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StudentT {
+    /// Location.
+    location: f64,
+    /// Scale.
+    scale: f64,
+    /// Degrees of freedom.
+    degrees_of_freedom: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl StudentT {
+    /// New instance of a Student's t distribution.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::assert_approx_equal;
+    /// # use RustQuant::statistics::distributions::*;
+    ///
+    /// let t = StudentT::new(0.0, 1.0, 5.0);
+    ///
+    /// assert_approx_equal!(t.inv_cdf(0.975), 2.5705818, 1e-7);
+    /// assert_approx_equal!(t.variance(), 5.0 / 3.0, 1e-12);
+    /// ```
+    pub fn new(location: f64, scale: f64, degrees_of_freedom: f64) -> Self {
+        assert!(scale > 0.0 && degrees_of_freedom > 0.0);
+
+        Self {
+            location,
+            scale,
+            degrees_of_freedom,
+        }
+    }
+
+    /// Fits the distribution to data by maximum likelihood.
+    /// # Examples
+    /// ```
+    /// # use RustQuant::statistics::distributions::*;
+    ///
+    /// let returns = StudentT::new(0.0005, 0.01, 4.0).sample(2000).unwrap();
+    /// let fitted = StudentT::fit(&returns).unwrap();
+    ///
+    /// assert!(fitted.degrees_of_freedom() > 2.0 && fitted.degrees_of_freedom() < 10.0);
+    /// ```
+    pub fn fit(data: &[f64]) -> Result<Self, DistributionError> {
+        if data.len() < 3 {
+            return Err(DistributionError::Estimation(
+                "at least 3 observations are required".to_string(),
+            ));
+        }
+
+        let (median, spread) = median_and_spread(data);
+
+        let result = NelderMead::new(2_000, 1e-8)
+            .with_initial_step(0.1)
+            .minimize(
+                &|theta: &[f64]| {
+                    let t = StudentT::new(theta[0], theta[1].exp(), theta[2].exp());
+                    -data.iter().map(|x| t.ln_pdf(*x)).sum::<f64>()
+                },
+                &[median, spread.ln(), 5_f64.ln()],
+            );
+
+        match result.minimum.is_finite() {
+            true => Ok(StudentT::new(
+                result.minimizer[0],
+                result.minimizer[1].exp(),
+                result.minimizer[2].exp(),
+            )),
+            false => Err(DistributionError::Estimation(
+                "non-finite Student's t likelihood".to_string(),
+            )),
+        }
+    }
+
+    /// Location parameter.
+    pub fn location(&self) -> f64 {
+        self.location
+    }
+
+    /// Scale parameter.
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Degrees of freedom.
+    pub fn degrees_of_freedom(&self) -> f64 {
+        self.degrees_of_freedom
+    }
+
+    /// Logarithm of the density.
+    pub fn ln_pdf(&self, x: f64) -> f64 {
+        let nu = self.degrees_of_freedom;
+        let z = (x - self.location) / self.scale;
+
+        ln_gamma(0.5 * (nu + 1.0))
+            - ln_gamma(0.5 * nu)
+            - 0.5 * (nu * PI).ln()
+            - self.scale.ln()
+            - 0.5 * (nu + 1.0) * (z * z / nu).ln_1p()
+    }
+
+    pub(crate) fn draw<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        use rand_distr::{Distribution, StudentT};
+
+        let t = StudentT::new(self.degrees_of_freedom).unwrap();
+
+        self.location + self.scale * t.sample(rng)
+    }
+}
+
+impl Distribution for StudentT {
+    /// Characteristic function of the Student's t distribution,
+    /// $\frac{K_{\nu/2}(\sqrt{\nu} \sigma |t|) (\sqrt{\nu} \sigma |t|)^{\nu/2}}{\Gamma(\nu/2) 2^{\nu/2 - 1}} e^{i \mu t}$.
+    fn cf(&self, t: f64) -> Complex<f64> {
+        let nu = self.degrees_of_freedom;
+        let z = nu.sqrt() * self.scale * t.abs();
+
+        let modulus = match z > 0.0 {
+            true => (ln_bessel_k(0.5 * nu, z) + 0.5 * nu * z.ln()
+                - ln_gamma(0.5 * nu)
+                - (0.5 * nu - 1.0) * LN_2)
+                .exp(),
+            false => 1.0,
+        };
+
+        modulus * Complex::new(0.0, self.location * t).exp()
+    }
+
+    fn pdf(&self, x: f64) -> f64 {
+        self.ln_pdf(x).exp()
+    }
+
+    fn pmf(&self, x: f64) -> f64 {
+        self.pdf(x)
+    }
+
+    fn cdf(&self, x: f64) -> f64 {
+        let nu = self.degrees_of_freedom;
+        let z = (x - self.location) / self.scale;
+        let tail = 0.5 * beta_reg(0.5 * nu, 0.5, nu / (nu + z * z));
+
+        match z > 0.0 {
+            true => 1.0 - tail,
+            false => tail,
+        }
+    }
+
+    fn inv_cdf(&self, p: f64) -> f64 {
+        if !(0.0..=1.0).contains(&p) {
+            return f64::NAN;
+        }
+
+        let nu = self.degrees_of_freedom;
+        let tail = p.min(1.0 - p);
+        let y = inv_beta_reg(0.5 * nu, 0.5, 2.0 * tail);
+        let z = (nu * (1.0 - y) / y).sqrt();
+
+        match p >= 0.5 {
+            true => self.location + self.scale * z,
+            false => self.location - self.scale * z,
+        }
+    }
+
+    /// Mean of the distribution (undefined for nu <= 1).
+    fn mean(&self) -> f64 {
+        match self.degrees_of_freedom > 1.0 {
+            true => self.location,
+            false => f64::NAN,
+        }
+    }
+
+    fn median(&self) -> f64 {
+        self.location
+    }
+
+    fn mode(&self) -> f64 {
+        self.location
+    }
+
+    /// Variance of the distribution (infinite for 1 < nu <= 2).
+    fn variance(&self) -> f64 {
+        let nu = self.degrees_of_freedom;
+
+        match nu {
+            nu if nu > 2.0 => self.scale.powi(2) * nu / (nu - 2.0),
+            nu if nu > 1.0 => f64::INFINITY,
+            _ => f64::NAN,
+        }
+    }
+
+    fn skewness(&self) -> f64 {
+        match self.degrees_of_freedom > 3.0 {
+            true => 0.0,
+            false => f64::NAN,
+        }
+    }
+
+    /// Excess kurtosis, $6 / (\nu - 4)$ (infinite for 2 < nu <= 4).
+    fn kurtosis(&self) -> f64 {
+        let nu = self.degrees_of_freedom;
+
+        match nu {
+            nu if nu > 4.0 => 6.0 / (nu - 4.0),
+            nu if nu > 2.0 => f64::INFINITY,
+            _ => f64::NAN,
+        }
+    }
+
+    fn entropy(&self) -> f64 {
+        let nu = self.degrees_of_freedom;
+
+        0.5 * (nu + 1.0) * (digamma(0.5 * (nu + 1.0)) - digamma(0.5 * nu))
+            + 0.5 * nu.ln()
+            + ln_beta(0.5 * nu, 0.5)
+            + self.scale.ln()
+    }
+
+    /// The moment generating function only exists at t = 0.
+    fn mgf(&self, t: f64) -> f64 {
+        match t == 0.0 {
+            true => 1.0,
+            false => f64::INFINITY,
+        }
+    }
+
+    fn sample(&self, n: usize) -> Result<Vec<f64>, DistributionError> {
+        assert!(n > 0);
+
+        let mut rng = rand::thread_rng();
+
+        Ok((0..n).map(|_| self.draw(&mut rng)).collect())
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_student_t_distribution() {
+        let dist = StudentT::new(0.5, 2.0, 4.0);
+
+        // Values computed with mpmath.
+        assert_approx_equal!(dist.pdf(1.3), 0.169987866764097, 1e-12);
+        assert_approx_equal!(dist.cdf(1.3), 0.645201369285002, 1e-12);
+        assert_approx_equal!(dist.inv_cdf(0.95), 4.763693572653299, 1e-9);
+        assert_approx_equal!(dist.entropy(), 2.374907197438612, 1e-10);
+
+        let cf = dist.cf(0.7);
+        assert_approx_equal!(cf.re, 0.295798931744862, 1e-12);
+        assert_approx_equal!(cf.im, 0.107975038827274, 1e-12);
+
+        assert_approx_equal!(dist.variance(), 8.0, 1e-12);
+        assert_eq!(dist.kurtosis(), f64::INFINITY);
+        assert!(StudentT::new(0.0, 1.0, 1.0).mean().is_nan());
+    }
+
+    #[test]
+    fn test_student_t_fit() {
+        let dist = StudentT::new(0.1, 0.5, 5.0);
+        let mut rng = StdRng::seed_from_u64(643);
+        let data: Vec<f64> = (0..5000).map(|_| dist.draw(&mut rng)).collect();
+
+        let fitted = StudentT::fit(&data).unwrap();
+
+        assert_approx_equal!(fitted.location(), 0.1, 0.03);
+        assert_approx_equal!(fitted.scale(), 0.5, 0.03);
+        assert_approx_equal!(fitted.degrees_of_freedom(), 5.0, 1.0);
+        assert!(StudentT::fit(&[1.0]).is_err());
+    }
+}
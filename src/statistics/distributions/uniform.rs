@@ -392,7 +392,6 @@ impl Distribution for Uniform {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::assert_approx_equal;
 
     #[test]
     fn test_uniform_distribution_continuous() {
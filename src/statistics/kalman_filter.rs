@@ -0,0 +1,598 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Kalman filter and smoother for linear Gaussian state-space models:
+//!
+//! $$
+//! \begin{aligned}
+//! x_t &= c + F x_{t-1} + w_t, \quad w_t \sim N(0, Q) \\\\
+//! y_t &= H x_t + v_t, \quad v_t \sim N(0, R)
+//! \end{aligned}
+//! $$
+//!
+//! with $x_1 \sim N(\hat{x}_1, P_1)$. Typical uses:
+//!
+//! - Dynamic Nelson-Siegel: the states are the level, slope and curvature
+//!   factors, `H` holds the Nelson-Siegel loadings of each maturity.
+//! - Pairs trading: the state is a time-varying hedge ratio and intercept,
+//!   with a time-varying observation matrix `[x_t, 1]`
+//!   (see [`KalmanFilter::filter_time_varying`]).
+//! - Signal extraction: a local level model separates a noisy series
+//!   into a smooth trend and noise.
+//!
+//! Observations containing a `NaN` are treated as missing: the filter
+//! only predicts at those times.
+//!
+//! ```
+//! use RustQuant::statistics::*;
+//! use nalgebra::{DMatrix, DVector};
+//!
+//! // Local level model: a random walk observed with noise.
+//! let model = KalmanFilter::new(
+//!     DMatrix::identity(1, 1),
+//!     DMatrix::identity(1, 1),
+//!     DMatrix::from_element(1, 1, 0.01),
+//!     DMatrix::from_element(1, 1, 1.0),
+//! );
+//!
+//! let observations: Vec<DVector<f64>> = [1.2, 0.8, 1.1, f64::NAN, 0.9, 1.0]
+//!     .iter()
+//!     .map(|y| DVector::from_element(1, *y))
+//!     .collect();
+//!
+//! let filtered = model.filter(&observations).unwrap();
+//! let smoothed = model.smooth(&filtered).unwrap();
+//!
+//! assert!((smoothed.states[3][0] - 1.0).abs() < 0.1);
+//! assert!(smoothed.covariances[3][(0, 0)] < filtered.filtered_covariances[3][(0, 0)]);
+//! ```
+
+use nalgebra::{DMatrix, DVector};
+use std::f64::consts::PI;
+use std::ops::AddAssign;
+use thiserror::Error;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Linear Gaussian state-space model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KalmanFilter {
+    /// State transition matrix ($F$, `n x n`).
+    pub transition: DMatrix<f64>,
+
+    /// State intercept ($c$, length `n`).
+    pub state_intercept: DVector<f64>,
+
+    /// Observation matrix ($H$, `m x n`).
+    pub observation: DMatrix<f64>,
+
+    /// State noise covariance ($Q$, `n x n`).
+    pub transition_covariance: DMatrix<f64>,
+
+    /// Observation noise covariance ($R$, `m x m`).
+    pub observation_covariance: DMatrix<f64>,
+
+    /// Mean of the first state ($\hat{x}_1$).
+    pub initial_state: DVector<f64>,
+
+    /// Covariance of the first state ($P_1$).
+    pub initial_covariance: DMatrix<f64>,
+}
+
+/// Output of the Kalman filter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KalmanFilterOutput {
+    /// One-step-ahead state predictions, $x_{t|t-1}$.
+    pub predicted_states: Vec<DVector<f64>>,
+
+    /// Covariances of the predictions, $P_{t|t-1}$.
+    pub predicted_covariances: Vec<DMatrix<f64>>,
+
+    /// Filtered states, $x_{t|t}$.
+    pub filtered_states: Vec<DVector<f64>>,
+
+    /// Covariances of the filtered states, $P_{t|t}$.
+    pub filtered_covariances: Vec<DMatrix<f64>>,
+
+    /// Log-likelihood of the (non-missing) observations.
+    pub log_likelihood: f64,
+}
+
+/// Output of the Rauch-Tung-Striebel smoother.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KalmanSmootherOutput {
+    /// Smoothed states, $x_{t|T}$.
+    pub states: Vec<DVector<f64>>,
+
+    /// Covariances of the smoothed states, $P_{t|T}$.
+    pub covariances: Vec<DMatrix<f64>>,
+
+    /// Lag-one covariances, $\mathrm{Cov}(x_{t+1}, x_t | T)$ (one fewer
+    /// than the number of observations).
+    pub lag_one_covariances: Vec<DMatrix<f64>>,
+}
+
+/// Result of the EM estimation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KalmanEmFit {
+    /// Estimated model.
+    pub model: KalmanFilter,
+
+    /// Log-likelihood of the estimated model.
+    pub log_likelihood: f64,
+
+    /// Number of EM iterations.
+    pub iterations: usize,
+
+    /// Whether the log-likelihood converged before the maximum iterations.
+    pub converged: bool,
+}
+
+/// Kalman filter errors.
+#[derive(Debug, Clone, Copy, PartialEq, Error)]
+pub enum KalmanError {
+    /// Observations or matrices of the wrong dimensions.
+    #[error("Inconsistent dimensions.")]
+    DimensionMismatch,
+
+    /// The innovation (or predicted state) covariance is singular at a time step.
+    #[error("Singular covariance matrix at time step {0}.")]
+    SingularCovariance(usize),
+
+    /// Not enough observations.
+    #[error("Not enough observations: {0}.")]
+    InsufficientData(usize),
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl KalmanFilter {
+    /// New model with transition `F`, observation `H`, and noise covariances
+    /// `Q` and `R`. The first state is diffuse: zero mean and covariance
+    /// `1e6 I`, and there is no state intercept.
+    pub fn new(
+        transition: DMatrix<f64>,
+        observation: DMatrix<f64>,
+        transition_covariance: DMatrix<f64>,
+        observation_covariance: DMatrix<f64>,
+    ) -> Self {
+        let (n, m) = (transition.nrows(), observation.nrows());
+
+        assert!(transition.is_square(), "F must be square.");
+        assert_eq!(observation.ncols(), n, "H must have one column per state.");
+        assert_eq!(transition_covariance.shape(), (n, n), "Q must be n x n.");
+        assert_eq!(observation_covariance.shape(), (m, m), "R must be m x m.");
+
+        Self {
+            transition,
+            state_intercept: DVector::zeros(n),
+            observation,
+            transition_covariance,
+            observation_covariance,
+            initial_state: DVector::zeros(n),
+            initial_covariance: DMatrix::identity(n, n) * 1e6,
+        }
+    }
+
+    /// Sets the mean and covariance of the first state.
+    pub fn with_initial_state(mut self, state: DVector<f64>, covariance: DMatrix<f64>) -> Self {
+        let n = self.state_dimension();
+
+        assert_eq!(state.len(), n, "Initial state must have length n.");
+        assert_eq!(
+            covariance.shape(),
+            (n, n),
+            "Initial covariance must be n x n."
+        );
+
+        self.initial_state = state;
+        self.initial_covariance = covariance;
+        self
+    }
+
+    /// Sets the state intercept `c`.
+    pub fn with_state_intercept(mut self, intercept: DVector<f64>) -> Self {
+        assert_eq!(
+            intercept.len(),
+            self.state_dimension(),
+            "Intercept must have length n."
+        );
+
+        self.state_intercept = intercept;
+        self
+    }
+
+    /// Number of states (`n`).
+    pub fn state_dimension(&self) -> usize {
+        self.transition.nrows()
+    }
+
+    /// Number of observed variables (`m`).
+    pub fn observation_dimension(&self) -> usize {
+        self.observation.nrows()
+    }
+
+    /// Runs the filter over the observations.
+    pub fn filter(&self, observations: &[DVector<f64>]) -> Result<KalmanFilterOutput, KalmanError> {
+        self.run_filter(observations, |_| &self.observation)
+    }
+
+    /// Runs the filter with a different observation matrix at each time
+    /// step (e.g. the regressors of a time-varying regression).
+    pub fn filter_time_varying(
+        &self,
+        observations: &[DVector<f64>],
+        observation_matrices: &[DMatrix<f64>],
+    ) -> Result<KalmanFilterOutput, KalmanError> {
+        if observation_matrices.len() != observations.len()
+            || observation_matrices
+                .iter()
+                .any(|h| h.shape() != self.observation.shape())
+        {
+            return Err(KalmanError::DimensionMismatch);
+        }
+
+        self.run_filter(observations, |t| &observation_matrices[t])
+    }
+
+    /// Rauch-Tung-Striebel smoother of a filter output.
+    pub fn smooth(
+        &self,
+        filtered: &KalmanFilterOutput,
+    ) -> Result<KalmanSmootherOutput, KalmanError> {
+        let n_steps = filtered.filtered_states.len();
+
+        if n_steps == 0 {
+            return Err(KalmanError::InsufficientData(0));
+        }
+
+        let mut states = filtered.filtered_states.clone();
+        let mut covariances = filtered.filtered_covariances.clone();
+        let mut lag_one_covariances = vec![DMatrix::zeros(0, 0); n_steps - 1];
+
+        for t in (0..n_steps - 1).rev() {
+            let predicted_inverse = filtered.predicted_covariances[t + 1]
+                .clone()
+                .try_inverse()
+                .ok_or(KalmanError::SingularCovariance(t + 1))?;
+            let gain =
+                &filtered.filtered_covariances[t] * self.transition.transpose() * predicted_inverse;
+
+            states[t] = &filtered.filtered_states[t]
+                + &gain * (&states[t + 1] - &filtered.predicted_states[t + 1]);
+            covariances[t] = &filtered.filtered_covariances[t]
+                + &gain
+                    * (&covariances[t + 1] - &filtered.predicted_covariances[t + 1])
+                    * gain.transpose();
+            lag_one_covariances[t] = &covariances[t + 1] * gain.transpose();
+        }
+
+        Ok(KalmanSmootherOutput {
+            states,
+            covariances,
+            lag_one_covariances,
+        })
+    }
+
+    /// Estimates `c`, `F`, `Q`, `R` and the mean of the first state by
+    /// expectation-maximisation (Shumway and Stoffer), starting from this
+    /// model. The observation matrix `H` and the covariance of the first
+    /// state are kept fixed.
+    ///
+    /// Iterates until the log-likelihood improves by less than `tolerance`.
+    pub fn fit_em(
+        &self,
+        observations: &[DVector<f64>],
+        max_iterations: usize,
+        tolerance: f64,
+    ) -> Result<KalmanEmFit, KalmanError> {
+        let n_steps = observations.len();
+        let n = self.state_dimension();
+
+        if n_steps < 2 {
+            return Err(KalmanError::InsufficientData(n_steps));
+        }
+
+        let mut model = self.clone();
+        let mut filtered = model.filter(observations)?;
+        let mut iterations = 0;
+        let mut converged = false;
+
+        while iterations < max_iterations {
+            iterations += 1;
+
+            let smoothed = model.smooth(&filtered)?;
+            let (x, p) = (&smoothed.states, &smoothed.covariances);
+
+            // Regression of x_t on [1, x_{t-1}] with the smoothed moments.
+            let mut a10 = DMatrix::zeros(n, n + 1);
+            let mut a00 = DMatrix::zeros(n + 1, n + 1);
+            let mut s11 = DMatrix::zeros(n, n);
+
+            for t in 1..n_steps {
+                let previous = DVector::from_iterator(
+                    n + 1,
+                    std::iter::once(1.0).chain(x[t - 1].iter().copied()),
+                );
+                let mut previous_moment = &previous * previous.transpose();
+                previous_moment
+                    .view_mut((1, 1), (n, n))
+                    .add_assign(&p[t - 1]);
+
+                let mut cross_moment = &x[t] * previous.transpose();
+                cross_moment
+                    .view_mut((0, 1), (n, n))
+                    .add_assign(&smoothed.lag_one_covariances[t - 1]);
+
+                a00 += previous_moment;
+                a10 += cross_moment;
+                s11 += &p[t] + &x[t] * x[t].transpose();
+            }
+
+            let coefficients = &a10
+                * a00
+                    .try_inverse()
+                    .ok_or(KalmanError::SingularCovariance(iterations))?;
+            let q = (&s11 - &coefficients * a10.transpose()) / (n_steps - 1) as f64;
+
+            // Observation noise from the residuals of the observed times.
+            let h = &model.observation;
+            let mut r =
+                DMatrix::zeros(model.observation_dimension(), model.observation_dimension());
+            let mut n_observed = 0;
+            for (t, y) in observations.iter().enumerate() {
+                if is_missing(y) {
+                    continue;
+                }
+                let residual = y - h * &x[t];
+                r += &residual * residual.transpose() + h * &p[t] * h.transpose();
+                n_observed += 1;
+            }
+            if n_observed == 0 {
+                return Err(KalmanError::InsufficientData(0));
+            }
+
+            model.state_intercept = coefficients.column(0).into_owned();
+            model.transition = coefficients.columns(1, n).into_owned();
+            model.transition_covariance = symmetrize(q);
+            model.observation_covariance = symmetrize(r / n_observed as f64);
+            model.initial_state = x[0].clone();
+
+            let previous_log_likelihood = filtered.log_likelihood;
+            filtered = model.filter(observations)?;
+
+            if (filtered.log_likelihood - previous_log_likelihood).abs() < tolerance {
+                converged = true;
+                break;
+            }
+        }
+
+        Ok(KalmanEmFit {
+            model,
+            log_likelihood: filtered.log_likelihood,
+            iterations,
+            converged,
+        })
+    }
+
+    fn run_filter<'a, F>(
+        &'a self,
+        observations: &[DVector<f64>],
+        observation_matrix: F,
+    ) -> Result<KalmanFilterOutput, KalmanError>
+    where
+        F: Fn(usize) -> &'a DMatrix<f64>,
+    {
+        let (n, m) = (self.state_dimension(), self.observation_dimension());
+
+        if observations.iter().any(|y| y.len() != m) {
+            return Err(KalmanError::DimensionMismatch);
+        }
+
+        let n_steps = observations.len();
+        let mut output = KalmanFilterOutput {
+            predicted_states: Vec::with_capacity(n_steps),
+            predicted_covariances: Vec::with_capacity(n_steps),
+            filtered_states: Vec::with_capacity(n_steps),
+            filtered_covariances: Vec::with_capacity(n_steps),
+            log_likelihood: 0.0,
+        };
+
+        let (mut state, mut covariance) =
+            (self.initial_state.clone(), self.initial_covariance.clone());
+        let identity = DMatrix::<f64>::identity(n, n);
+
+        for (t, y) in observations.iter().enumerate() {
+            // Predict (the first state's prior is the initial state).
+            if t > 0 {
+                state = &self.state_intercept + &self.transition * &state;
+                covariance = &self.transition * &covariance * self.transition.transpose()
+                    + &self.transition_covariance;
+            }
+            output.predicted_states.push(state.clone());
+            output.predicted_covariances.push(covariance.clone());
+
+            // Update, in Joseph form to keep the covariance symmetric.
+            if !is_missing(y) {
+                let h = observation_matrix(t);
+                let innovation = y - h * &state;
+                let innovation_covariance =
+                    h * &covariance * h.transpose() + &self.observation_covariance;
+                let cholesky = innovation_covariance
+                    .clone()
+                    .cholesky()
+                    .ok_or(KalmanError::SingularCovariance(t))?;
+
+                let gain = &covariance * h.transpose() * cholesky.inverse();
+                let reduction = &identity - &gain * h;
+
+                state += &gain * &innovation;
+                covariance = &reduction * &covariance * reduction.transpose()
+                    + &gain * &self.observation_covariance * gain.transpose();
+
+                let log_det = 2.0 * cholesky.l().diagonal().iter().map(|l| l.ln()).sum::<f64>();
+                output.log_likelihood -= 0.5
+                    * (m as f64 * (2.0 * PI).ln()
+                        + log_det
+                        + innovation.dot(&cholesky.solve(&innovation)));
+            }
+
+            output.filtered_states.push(state.clone());
+            output.filtered_covariances.push(covariance.clone());
+        }
+
+        Ok(output)
+    }
+}
+
+fn is_missing(observation: &DVector<f64>) -> bool {
+    observation.iter().any(|y| y.is_nan())
+}
+
+fn symmetrize(matrix: DMatrix<f64>) -> DMatrix<f64> {
+    (&matrix + matrix.transpose()) * 0.5
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_kalman_filter {
+    use super::*;
+    use crate::stochastics::SeedStrategy;
+    use rand::Rng;
+    use rand_distr::StandardNormal;
+
+    fn scalar(x: f64) -> DMatrix<f64> {
+        DMatrix::from_element(1, 1, x)
+    }
+
+    #[test]
+    fn test_local_level_steady_state() {
+        // Random walk with q = 1, r = 1: the steady-state filtered
+        // variance solves P = (P + q) r / (P + q + r), P = (sqrt(5) - 1) / 2.
+        let model = KalmanFilter::new(scalar(1.0), scalar(1.0), scalar(1.0), scalar(1.0));
+        let observations = vec![DVector::from_element(1, 0.0); 50];
+
+        let filtered = model.filter(&observations).unwrap();
+        let smoothed = model.smooth(&filtered).unwrap();
+
+        assert_approx_equal!(
+            filtered.filtered_covariances[49][(0, 0)],
+            (5.0_f64.sqrt() - 1.0) / 2.0,
+            1e-10
+        );
+        assert!(smoothed.covariances[25][(0, 0)] < filtered.filtered_covariances[25][(0, 0)]);
+        assert_eq!(smoothed.lag_one_covariances.len(), 49);
+
+        assert_eq!(
+            model.filter(&[DVector::zeros(2)]).unwrap_err(),
+            KalmanError::DimensionMismatch
+        );
+    }
+
+    #[test]
+    fn test_missing_observations() {
+        let model = KalmanFilter::new(scalar(1.0), scalar(1.0), scalar(0.1), scalar(1.0));
+        let observations: Vec<DVector<f64>> = [1.0, f64::NAN, f64::NAN, 1.0]
+            .iter()
+            .map(|y| DVector::from_element(1, *y))
+            .collect();
+
+        let filtered = model.filter(&observations).unwrap();
+
+        // No update while missing: the variance grows by q each step.
+        assert_eq!(filtered.filtered_states[2], filtered.filtered_states[0]);
+        assert_approx_equal!(
+            filtered.filtered_covariances[2][(0, 0)],
+            filtered.filtered_covariances[0][(0, 0)] + 0.2,
+            1e-12
+        );
+
+        // Only the two observed values contribute to the likelihood:
+        // y_1 ~ N(0, 1e6 + 1) and y_4 ~ N(x_1, P_1 + 3q + r).
+        let log_density = |y: f64, mean: f64, variance: f64| {
+            -0.5 * ((2.0 * PI * variance).ln() + (y - mean).powi(2) / variance)
+        };
+        let (x_1, p_1) = (1e6 / (1e6 + 1.0), 1e6 / (1e6 + 1.0));
+        assert_approx_equal!(
+            filtered.log_likelihood,
+            log_density(1.0, 0.0, 1e6 + 1.0) + log_density(1.0, x_1, p_1 + 1.3),
+            1e-9
+        );
+    }
+
+    #[test]
+    fn test_time_varying_hedge_ratio() {
+        // y_t = beta_t x_t + alpha + noise, with a slowly drifting beta.
+        let mut rng = SeedStrategy::PerPath(640).rng(0);
+        let n_steps = 500;
+
+        let betas: Vec<f64> = (0..n_steps)
+            .map(|t| 1.0 + t as f64 / n_steps as f64)
+            .collect();
+        let regressors: Vec<f64> = (0..n_steps)
+            .map(|_| 10.0 + rng.sample::<f64, _>(StandardNormal))
+            .collect();
+        let observations: Vec<DVector<f64>> = (0..n_steps)
+            .map(|t| {
+                let noise: f64 = rng.sample(StandardNormal);
+                DVector::from_element(1, betas[t] * regressors[t] + 2.0 + 0.1 * noise)
+            })
+            .collect();
+        let matrices: Vec<DMatrix<f64>> = regressors
+            .iter()
+            .map(|x| DMatrix::from_row_slice(1, 2, &[*x, 1.0]))
+            .collect();
+
+        let model = KalmanFilter::new(
+            DMatrix::identity(2, 2),
+            DMatrix::zeros(1, 2),
+            DMatrix::from_diagonal(&DVector::from_vec(vec![1e-5, 1e-8])),
+            scalar(0.01),
+        );
+        let filtered = model.filter_time_varying(&observations, &matrices).unwrap();
+
+        assert_approx_equal!(filtered.filtered_states[250][0], betas[250], 0.05);
+        assert_approx_equal!(filtered.filtered_states[499][0], betas[499], 0.05);
+        assert!(model
+            .filter_time_varying(&observations, &matrices[..10])
+            .is_err());
+    }
+
+    #[test]
+    fn test_em_estimation() {
+        // AR(1) state with an intercept, observed with noise:
+        // x_t = 0.5 + 0.8 x_{t-1} + N(0, 0.25), y_t = x_t + N(0, 0.09).
+        let mut rng = SeedStrategy::PerPath(640).rng(0);
+        let mut x = 2.5;
+        let observations: Vec<DVector<f64>> = (0..1000)
+            .map(|_| {
+                x = 0.5 + 0.8 * x + 0.5 * rng.sample::<f64, _>(StandardNormal);
+                DVector::from_element(1, x + 0.3 * rng.sample::<f64, _>(StandardNormal))
+            })
+            .collect();
+
+        let initial = KalmanFilter::new(scalar(0.5), scalar(1.0), scalar(1.0), scalar(1.0))
+            .with_initial_state(DVector::from_element(1, 2.5), scalar(1.0));
+        let fit = initial.fit_em(&observations, 200, 1e-5).unwrap();
+
+        assert!(fit.converged);
+        assert!(fit.log_likelihood > initial.filter(&observations).unwrap().log_likelihood);
+        assert_approx_equal!(fit.model.transition[(0, 0)], 0.8, 0.05);
+        assert_approx_equal!(fit.model.state_intercept[0], 0.5, 0.15);
+        assert_approx_equal!(fit.model.transition_covariance[(0, 0)], 0.25, 0.05);
+        assert_approx_equal!(fit.model.observation_covariance[(0, 0)], 0.09, 0.05);
+    }
+}
@@ -23,6 +23,9 @@
 //! - [x] Chi-Squared
 //! - [x] Gamma
 //! - [x] Exponential
+//!
+//! Copulas (Gaussian, Student-t, Clayton, Gumbel) for dependent variables,
+//! the Kalman filter for linear state-space models, and time series tests.
 
 /// Base trait for statistics of a collection of data.
 pub mod statistic;
@@ -32,7 +35,8 @@ pub use statistic::*;
 pub mod distributions {
     pub use crate::statistics::distributions::{
         bernoulli::*, binomial::*, chi_squared::*, distribution::*, exponential::*, gamma::*,
-        gaussian::*, poisson::*, uniform::*,
+        gaussian::*, generalized_hyperbolic::*, normal_inverse_gaussian::*, poisson::*,
+        skew_normal::*, stable::*, student_t::*, uniform::*,
     };
 
     /// Bernoulli distribution.
@@ -56,14 +60,44 @@ pub mod distributions {
     /// Gaussian (normal) distribution.
     pub mod gaussian;
 
+    /// Generalized hyperbolic distribution.
+    pub mod generalized_hyperbolic;
+
+    /// Normal-inverse Gaussian distribution.
+    pub mod normal_inverse_gaussian;
+
+    /// Numerical routines for distributions without closed forms.
+    mod numerical;
+
     /// Poisson distribution.
     pub mod poisson;
 
+    /// Skew-normal distribution.
+    pub mod skew_normal;
+
+    /// Alpha-stable distribution.
+    pub mod stable;
+
+    /// Student's t distribution.
+    pub mod student_t;
+
     /// Uniform distribution.
     pub mod uniform;
 }
 pub use distributions::*;
 
-// /// Copula implementations.
-// pub mod copulas;
-// pub use copulas::*;
+/// Kalman filter and smoother for linear state-space models.
+pub mod kalman_filter;
+pub use kalman_filter::*;
+
+/// Time series statistics, stationarity and cointegration tests.
+pub mod time_series;
+pub use time_series::*;
+
+/// Copulas for dependent random variables.
+pub mod copulas;
+pub use copulas::*;
+
+/// Multivariate normal distribution and rectangle probabilities.
+pub mod multivariate_normal;
+pub use multivariate_normal::*;
@@ -222,7 +222,7 @@ impl Statistic<f64> for Vec<f64> {
         sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
 
         let mid = sorted.len() / 2;
-        if sorted.len() % 2 == 0 {
+        if sorted.len().is_multiple_of(2) {
             (sorted[mid - 1] + sorted[mid]) / 2.0
         } else {
             sorted[mid]
@@ -286,7 +286,6 @@ impl Statistic<f64> for Vec<f64> {
 mod tests_statistics {
 
     use super::*;
-    use crate::assert_approx_equal;
 
     #[test]
     fn test_mean_arithmetic() {
@@ -0,0 +1,225 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::statistics::distributions::{ChiSquared, Distribution};
+use thiserror::Error;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Time series errors.
+#[derive(Debug, Error)]
+pub enum TimeSeriesError {
+    /// Not enough observations for the requested lags or regression.
+    #[error("Not enough observations: {0}.")]
+    InsufficientData(usize),
+
+    /// Invalid number of lags.
+    #[error("Invalid number of lags: {0}.")]
+    InvalidLags(usize),
+
+    /// Series of different lengths.
+    #[error("Series of different lengths.")]
+    DimensionMismatch,
+
+    /// The test is not available for this number of series.
+    #[error("Unsupported number of series: {0}.")]
+    UnsupportedDimension(usize),
+
+    /// The test does not support these deterministic terms.
+    #[error("Unsupported deterministic terms.")]
+    UnsupportedDeterministic,
+
+    /// Singular regression or covariance matrix.
+    #[error("Singular matrix.")]
+    SingularMatrix,
+
+    /// Error reading a Polars series.
+    #[cfg(feature = "data")]
+    #[error("{0}")]
+    Data(#[from] crate::data::DataError),
+}
+
+/// Result of the Ljung-Box test for autocorrelation.
+///
+/// Null hypothesis: no autocorrelation up to the tested lag.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LjungBox {
+    /// Test statistic, $Q = n (n + 2) \sum_{k=1}^{h} \hat{\rho}_k^2 / (n - k)$.
+    pub statistic: f64,
+
+    /// P-value, from the $\chi^2$ distribution.
+    pub p_value: f64,
+
+    /// Number of lags tested ($h$).
+    pub lags: usize,
+
+    /// Degrees of freedom ($h$ less the number of fitted parameters).
+    pub degrees_of_freedom: usize,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Sample autocovariances at lags `0..=max_lag` (divided by `n`).
+pub fn autocovariance(x: &[f64], max_lag: usize) -> Result<Vec<f64>, TimeSeriesError> {
+    let n = x.len();
+
+    if n <= max_lag || n < 2 {
+        return Err(TimeSeriesError::InsufficientData(n));
+    }
+
+    let mean = x.iter().sum::<f64>() / n as f64;
+
+    Ok((0..=max_lag)
+        .map(|k| {
+            (k..n)
+                .map(|t| (x[t] - mean) * (x[t - k] - mean))
+                .sum::<f64>()
+                / n as f64
+        })
+        .collect())
+}
+
+/// Sample autocorrelation function at lags `0..=max_lag`.
+pub fn acf(x: &[f64], max_lag: usize) -> Result<Vec<f64>, TimeSeriesError> {
+    let gamma = autocovariance(x, max_lag)?;
+
+    Ok(gamma.iter().map(|g| g / gamma[0]).collect())
+}
+
+/// Sample partial autocorrelation function at lags `0..=max_lag`,
+/// by the Durbin-Levinson recursion on the sample autocorrelations.
+pub fn pacf(x: &[f64], max_lag: usize) -> Result<Vec<f64>, TimeSeriesError> {
+    let rho = acf(x, max_lag)?;
+
+    let mut pacf = vec![1.0];
+    let mut phi: Vec<f64> = Vec::with_capacity(max_lag);
+
+    for k in 1..=max_lag {
+        let numerator = rho[k] - (1..k).map(|j| phi[j - 1] * rho[k - j]).sum::<f64>();
+        let denominator = 1.0 - (1..k).map(|j| phi[j - 1] * rho[j]).sum::<f64>();
+        let phi_kk = numerator / denominator;
+
+        phi = (1..k)
+            .map(|j| phi[j - 1] - phi_kk * phi[k - j - 1])
+            .chain(std::iter::once(phi_kk))
+            .collect();
+        pacf.push(phi_kk);
+    }
+
+    Ok(pacf)
+}
+
+/// Ljung-Box test of the autocorrelations up to lag `lags`, e.g. of the
+/// residuals of a model with `fitted_parameters` ARMA coefficients.
+pub fn ljung_box(
+    x: &[f64],
+    lags: usize,
+    fitted_parameters: usize,
+) -> Result<LjungBox, TimeSeriesError> {
+    if lags == 0 || fitted_parameters >= lags {
+        return Err(TimeSeriesError::InvalidLags(lags));
+    }
+
+    let rho = acf(x, lags)?;
+    let n = x.len() as f64;
+
+    let statistic = n
+        * (n + 2.0)
+        * (1..=lags)
+            .map(|k| rho[k] * rho[k] / (n - k as f64))
+            .sum::<f64>();
+    let degrees_of_freedom = lags - fitted_parameters;
+
+    Ok(LjungBox {
+        statistic,
+        p_value: 1.0 - ChiSquared::new(degrees_of_freedom).cdf(statistic),
+        lags,
+        degrees_of_freedom,
+    })
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+pub(crate) mod tests_autocorrelation {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use rand_distr::StandardNormal;
+
+    pub(crate) fn white_noise(n: usize, seed: u64) -> Vec<f64> {
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        (0..n).map(|_| rng.sample(StandardNormal)).collect()
+    }
+
+    pub(crate) fn ar1(phi: f64, n: usize, seed: u64) -> Vec<f64> {
+        let mut x = 0.0;
+
+        white_noise(n, seed)
+            .into_iter()
+            .map(|e| {
+                x = phi * x + e;
+                x
+            })
+            .collect()
+    }
+
+    pub(crate) fn random_walk(n: usize, seed: u64) -> Vec<f64> {
+        ar1(1.0, n, seed)
+    }
+
+    #[test]
+    fn test_acf() {
+        let x = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let rho = acf(&x, 2).unwrap();
+
+        // Autocovariances: 2, 0.8, -0.2.
+        assert_eq!(rho[0], 1.0);
+        assert_approx_equal!(rho[1], 0.4, 1e-12);
+        assert_approx_equal!(rho[2], -0.1, 1e-12);
+
+        assert!(matches!(
+            acf(&x, 5),
+            Err(TimeSeriesError::InsufficientData(5))
+        ));
+    }
+
+    #[test]
+    fn test_pacf_of_ar1() {
+        // AR(1): ACF decays as 0.7^k, PACF cuts off after lag 1.
+        let x = ar1(0.7, 5000, 641);
+
+        let rho = acf(&x, 3).unwrap();
+        let partial = pacf(&x, 3).unwrap();
+
+        assert_approx_equal!(rho[2], 0.49, 0.05);
+        assert_approx_equal!(partial[1], rho[1], 1e-12);
+        assert_approx_equal!(partial[1], 0.7, 0.03);
+        assert!(partial[2].abs() < 0.05);
+        assert!(partial[3].abs() < 0.05);
+    }
+
+    #[test]
+    fn test_ljung_box() {
+        let noise = ljung_box(&white_noise(1000, 641), 10, 0).unwrap();
+        let ar = ljung_box(&ar1(0.5, 1000, 641), 10, 0).unwrap();
+
+        assert_eq!(noise.degrees_of_freedom, 10);
+        assert!(noise.p_value > 0.05);
+        assert!(ar.p_value < 1e-6);
+
+        assert!(ljung_box(&white_noise(100, 1), 2, 2).is_err());
+    }
+}
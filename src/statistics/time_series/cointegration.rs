@@ -0,0 +1,305 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::statistics::time_series::stationarity::{adf_regression, ols};
+use crate::statistics::{
+    dickey_fuller_critical_values, Deterministic, NullHypothesis, StationarityTest, TimeSeriesError,
+};
+use nalgebra::{DMatrix, DVector};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Result of the Engle-Granger two-step cointegration test.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EngleGranger {
+    /// Cointegrating regression coefficients: the deterministic terms,
+    /// then one coefficient per regressor (e.g. the hedge ratio).
+    pub coefficients: Vec<f64>,
+
+    /// Residuals of the cointegrating regression (the spread).
+    pub residuals: Vec<f64>,
+
+    /// Dickey-Fuller test of a unit root in the residuals
+    /// (null hypothesis: no cointegration).
+    pub test: StationarityTest,
+}
+
+/// Result of the Johansen cointegration test.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Johansen {
+    /// Eigenvalues, in decreasing order.
+    pub eigenvalues: Vec<f64>,
+
+    /// Cointegrating vectors (columns), in the order of the eigenvalues.
+    pub eigenvectors: DMatrix<f64>,
+
+    /// Trace statistics for ranks `r = 0, 1, ...`:
+    /// $-T \sum_{i > r} \ln(1 - \lambda_i)$.
+    pub trace_statistics: Vec<f64>,
+
+    /// Maximum eigenvalue statistics for ranks `r = 0, 1, ...`:
+    /// $-T \ln(1 - \lambda_{r+1})$.
+    pub max_eigenvalue_statistics: Vec<f64>,
+
+    /// Trace critical values at the 10%, 5% and 1% levels, per rank.
+    pub trace_critical_values: Vec<[f64; 3]>,
+
+    /// Maximum eigenvalue critical values at the 10%, 5% and 1% levels, per rank.
+    pub max_eigenvalue_critical_values: Vec<[f64; 3]>,
+
+    /// Number of observations used.
+    pub n_observations: usize,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// MacKinnon, Haug and Michelis (1999) critical values (10%, 5%, 1%) with an
+/// unrestricted constant, indexed by the number of non-cointegrated
+/// directions `n - r`.
+const JOHANSEN_TRACE: [[f64; 3]; 5] = [
+    [2.7055, 3.8415, 6.6349],
+    [13.4294, 15.4943, 19.9349],
+    [27.0669, 29.7961, 35.4628],
+    [44.4929, 47.8545, 54.6815],
+    [65.8202, 69.8189, 77.8202],
+];
+const JOHANSEN_MAX_EIGENVALUE: [[f64; 3]; 5] = [
+    [2.7055, 3.8415, 6.6349],
+    [12.2971, 14.2639, 18.52],
+    [18.8928, 21.1314, 25.865],
+    [25.1236, 27.5858, 32.7172],
+    [31.2379, 33.8777, 39.3693],
+];
+
+impl Johansen {
+    /// Cointegration rank at a significance level (10%, 5% or 1% as index
+    /// 0, 1 or 2): the first rank whose trace statistic is not rejected.
+    pub fn rank(&self, level: usize) -> usize {
+        self.trace_statistics
+            .iter()
+            .zip(&self.trace_critical_values)
+            .take_while(|(statistic, critical)| **statistic > critical[level])
+            .count()
+    }
+}
+
+/// Engle-Granger test of cointegration between `y` and the `regressors`.
+///
+/// Regresses `y` on the deterministic terms and the regressors, then tests
+/// the residuals for a unit root (ADF without deterministic terms, with
+/// `lags` lagged differences), against the critical values for
+/// `1 + regressors.len()` variables. Supports one or two regressors.
+pub fn engle_granger(
+    y: &[f64],
+    regressors: &[&[f64]],
+    deterministic: Deterministic,
+    lags: usize,
+) -> Result<EngleGranger, TimeSeriesError> {
+    let n = y.len();
+
+    if regressors.iter().any(|x| x.len() != n) {
+        return Err(TimeSeriesError::DimensionMismatch);
+    }
+    if deterministic == Deterministic::None {
+        return Err(TimeSeriesError::UnsupportedDeterministic);
+    }
+
+    let terms = match deterministic {
+        Deterministic::ConstantTrend => 2,
+        _ => 1,
+    };
+    let design = DMatrix::from_fn(n, terms + regressors.len(), |t, j| match j {
+        j if j < terms => (t as f64 + 1.0).powi(j as i32),
+        j => regressors[j - terms][t],
+    });
+    let cointegrating = ols(&DVector::from_column_slice(y), &design)?;
+    let residuals: Vec<f64> = cointegrating.residuals.iter().copied().collect();
+
+    let fit = adf_regression(&residuals, Deterministic::None, lags, lags)?;
+    let n_observations = fit.residuals.len();
+
+    Ok(EngleGranger {
+        coefficients: cointegrating.coefficients.iter().copied().collect(),
+        residuals,
+        test: StationarityTest {
+            statistic: fit.coefficients[0] / fit.standard_errors[0],
+            critical_values: dickey_fuller_critical_values(
+                deterministic,
+                1 + regressors.len(),
+                n_observations,
+            )?,
+            null_hypothesis: NullHypothesis::UnitRoot,
+            lags,
+            n_observations,
+        },
+    })
+}
+
+/// Johansen test of the cointegration rank of up to five series, from a
+/// VECM with an unrestricted constant and `lags` lagged differences.
+///
+/// The unrestricted constant allows for drifts in the series (e.g. log
+/// prices); the critical values assume the drifts are present.
+pub fn johansen(series: &[&[f64]], lags: usize) -> Result<Johansen, TimeSeriesError> {
+    let k = series.len();
+    let n = series.first().map_or(0, |x| x.len());
+
+    if !(2..=5).contains(&k) {
+        return Err(TimeSeriesError::UnsupportedDimension(k));
+    }
+    if series.iter().any(|x| x.len() != n) {
+        return Err(TimeSeriesError::DimensionMismatch);
+    }
+    if n < lags + k + 10 {
+        return Err(TimeSeriesError::InsufficientData(n));
+    }
+
+    let difference = |t: usize, i: usize| series[i][t] - series[i][t - 1];
+    let rows: Vec<usize> = (lags + 1..n).collect();
+    let t_obs = rows.len();
+
+    // Concentrate out the constant and lagged differences.
+    let z = DMatrix::from_fn(t_obs, 1 + k * lags, |r, j| match j {
+        0 => 1.0,
+        j => difference(rows[r] - 1 - (j - 1) / k, (j - 1) % k),
+    });
+    let dy = DMatrix::from_fn(t_obs, k, |r, i| difference(rows[r], i));
+    let y_lag = DMatrix::from_fn(t_obs, k, |r, i| series[i][rows[r] - 1]);
+
+    let projection = (z.transpose() * &z)
+        .try_inverse()
+        .ok_or(TimeSeriesError::SingularMatrix)?;
+    let residual = |m: &DMatrix<f64>| m - &z * (&projection * (z.transpose() * m));
+    let (r0, r1) = (residual(&dy), residual(&y_lag));
+
+    let scale = 1.0 / t_obs as f64;
+    let s00 = r0.transpose() * &r0 * scale;
+    let s01 = r0.transpose() * &r1 * scale;
+    let s11 = r1.transpose() * &r1 * scale;
+
+    // Symmetric form of the eigenproblem |λ S11 - S10 S00^-1 S01| = 0.
+    let l = s11
+        .clone()
+        .cholesky()
+        .ok_or(TimeSeriesError::SingularMatrix)?
+        .l();
+    let l_inverse = l
+        .clone()
+        .try_inverse()
+        .ok_or(TimeSeriesError::SingularMatrix)?;
+    let s00_inverse = s00.try_inverse().ok_or(TimeSeriesError::SingularMatrix)?;
+    let m = &l_inverse * s01.transpose() * s00_inverse * &s01 * l_inverse.transpose();
+    let eigen = ((&m + m.transpose()) * 0.5).symmetric_eigen();
+
+    let mut order: Vec<usize> = (0..k).collect();
+    order.sort_by(|&a, &b| eigen.eigenvalues[b].total_cmp(&eigen.eigenvalues[a]));
+
+    let eigenvalues: Vec<f64> = order.iter().map(|&i| eigen.eigenvalues[i]).collect();
+    let eigenvectors = l_inverse.transpose()
+        * DMatrix::from_columns(
+            &order
+                .iter()
+                .map(|&i| eigen.eigenvectors.column(i).into_owned())
+                .collect::<Vec<_>>(),
+        );
+
+    let log_terms: Vec<f64> = eigenvalues
+        .iter()
+        .map(|lambda| -(t_obs as f64) * (1.0 - lambda).ln())
+        .collect();
+
+    Ok(Johansen {
+        trace_statistics: (0..k).map(|r| log_terms[r..].iter().sum()).collect(),
+        max_eigenvalue_statistics: log_terms,
+        trace_critical_values: (0..k).map(|r| JOHANSEN_TRACE[k - r - 1]).collect(),
+        max_eigenvalue_critical_values: (0..k)
+            .map(|r| JOHANSEN_MAX_EIGENVALUE[k - r - 1])
+            .collect(),
+        eigenvalues,
+        eigenvectors,
+        n_observations: t_obs,
+    })
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_cointegration {
+    use super::*;
+    use crate::statistics::time_series::autocorrelation::tests_autocorrelation::{
+        random_walk, white_noise,
+    };
+    use crate::statistics::SignificanceLevel;
+
+    /// Random walk with drift.
+    fn drifting_walk(drift: f64, seed: u64) -> Vec<f64> {
+        random_walk(500, seed)
+            .iter()
+            .enumerate()
+            .map(|(t, x)| x + drift * t as f64)
+            .collect()
+    }
+
+    /// A random walk, a series cointegrated with it (hedge ratio 2),
+    /// and an independent random walk, all with drifts.
+    fn pair() -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+        let x = drifting_walk(0.1, 641);
+        let noise = white_noise(500, 642);
+        let y = x
+            .iter()
+            .zip(&noise)
+            .map(|(x, e)| 1.0 + 2.0 * x + e)
+            .collect();
+
+        (x, y, drifting_walk(-0.05, 643))
+    }
+
+    #[test]
+    fn test_engle_granger() {
+        let (x, y, z) = pair();
+
+        let cointegrated = engle_granger(&y, &[&x], Deterministic::Constant, 1).unwrap();
+        let spurious = engle_granger(&z, &[&x], Deterministic::Constant, 1).unwrap();
+
+        assert_approx_equal!(cointegrated.coefficients[1], 2.0, 0.02);
+        assert_eq!(cointegrated.residuals.len(), 500);
+        assert!(cointegrated.test.rejects(SignificanceLevel::OnePercent));
+        assert!(!spurious.test.rejects(SignificanceLevel::FivePercent));
+
+        assert!(matches!(
+            engle_granger(&y, &[&x[1..]], Deterministic::Constant, 1),
+            Err(TimeSeriesError::DimensionMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_johansen() {
+        let (x, y, z) = pair();
+
+        let result = johansen(&[&x, &y, &z], 1).unwrap();
+
+        // One cointegrating relation among the three series.
+        assert_eq!(result.rank(1), 1);
+        assert!(result.eigenvalues.windows(2).all(|w| w[0] >= w[1]));
+        assert_eq!(result.trace_critical_values[0], JOHANSEN_TRACE[2]);
+
+        // The cointegrating vector is proportional to (2, -1, 0).
+        let v = result.eigenvectors.column(0);
+        assert_approx_equal!(v[0] / v[1], -2.0, 0.05);
+        assert!((v[2] / v[1]).abs() < 0.05);
+
+        assert!(johansen(&[&x], 1).is_err());
+    }
+}
@@ -0,0 +1,51 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Time series statistics and tests, the usual checks before modelling:
+//!
+//! - Autocorrelation and partial autocorrelation functions.
+//! - Ljung-Box test for autocorrelation.
+//! - Augmented Dickey-Fuller (unit root) and KPSS (stationarity) tests,
+//!   with MacKinnon (2010) critical values.
+//! - Engle-Granger and Johansen cointegration tests.
+//!
+//! ```
+//! use RustQuant::statistics::*;
+//!
+//! // A trending series is not stationary, its differences are.
+//! let prices: Vec<f64> = (0..200).map(|t| 100.0 + t as f64 + (t % 7) as f64).collect();
+//! let changes: Vec<f64> = prices.windows(2).map(|w| w[1] - w[0]).collect();
+//!
+//! let levels = kpss(&prices, Deterministic::Constant, None).unwrap();
+//! let differences = kpss(&changes, Deterministic::Constant, None).unwrap();
+//!
+//! assert!(levels.rejects(SignificanceLevel::OnePercent));
+//! assert!(!differences.rejects(SignificanceLevel::FivePercent));
+//! ```
+//!
+//! With the `data` feature, the tests are also available on Polars series
+//! through the [`TimeSeries`] trait.
+
+/// Autocorrelation functions and the Ljung-Box test.
+pub mod autocorrelation;
+pub use autocorrelation::*;
+
+/// Unit root and stationarity tests.
+pub mod stationarity;
+pub use stationarity::*;
+
+/// Cointegration tests.
+pub mod cointegration;
+pub use cointegration::*;
+
+/// Time series tests of Polars series.
+#[cfg(feature = "data")]
+pub mod series;
+#[cfg(feature = "data")]
+pub use series::*;
@@ -0,0 +1,134 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::data::DataError;
+use crate::statistics::{
+    acf, augmented_dickey_fuller, engle_granger, johansen, kpss, ljung_box, pacf, Deterministic,
+    EngleGranger, Johansen, LjungBox, StationarityTest, TimeSeriesError,
+};
+use polars::prelude::*;
+
+/// Time series statistics and tests of a Polars series. Nulls are skipped.
+pub trait TimeSeries {
+    /// Values of the series as `f64`, without nulls.
+    fn values(&self) -> Result<Vec<f64>, TimeSeriesError>;
+
+    /// Sample autocorrelation function at lags `0..=max_lag`.
+    fn acf(&self, max_lag: usize) -> Result<Vec<f64>, TimeSeriesError> {
+        acf(&self.values()?, max_lag)
+    }
+
+    /// Sample partial autocorrelation function at lags `0..=max_lag`.
+    fn pacf(&self, max_lag: usize) -> Result<Vec<f64>, TimeSeriesError> {
+        pacf(&self.values()?, max_lag)
+    }
+
+    /// Ljung-Box test up to lag `lags`.
+    fn ljung_box(
+        &self,
+        lags: usize,
+        fitted_parameters: usize,
+    ) -> Result<LjungBox, TimeSeriesError> {
+        ljung_box(&self.values()?, lags, fitted_parameters)
+    }
+
+    /// Augmented Dickey-Fuller unit root test.
+    fn augmented_dickey_fuller(
+        &self,
+        deterministic: Deterministic,
+        lags: Option<usize>,
+    ) -> Result<StationarityTest, TimeSeriesError> {
+        augmented_dickey_fuller(&self.values()?, deterministic, lags)
+    }
+
+    /// KPSS stationarity test.
+    fn kpss(
+        &self,
+        deterministic: Deterministic,
+        lags: Option<usize>,
+    ) -> Result<StationarityTest, TimeSeriesError> {
+        kpss(&self.values()?, deterministic, lags)
+    }
+
+    /// Engle-Granger cointegration test of this series on `regressors`.
+    fn engle_granger(
+        &self,
+        regressors: &[&Self],
+        deterministic: Deterministic,
+        lags: usize,
+    ) -> Result<EngleGranger, TimeSeriesError> {
+        let regressors = regressors
+            .iter()
+            .map(|x| x.values())
+            .collect::<Result<Vec<_>, _>>()?;
+        let regressors: Vec<&[f64]> = regressors.iter().map(Vec::as_slice).collect();
+
+        engle_granger(&self.values()?, &regressors, deterministic, lags)
+    }
+}
+
+impl TimeSeries for Series {
+    fn values(&self) -> Result<Vec<f64>, TimeSeriesError> {
+        Ok(self
+            .cast(&DataType::Float64)
+            .map_err(DataError::from)?
+            .f64()
+            .map_err(DataError::from)?
+            .into_iter()
+            .flatten()
+            .collect())
+    }
+}
+
+/// Johansen cointegration test of the columns of a data frame
+/// (e.g. log prices), with `lags` lagged differences.
+pub fn johansen_frame(frame: &DataFrame, lags: usize) -> Result<Johansen, TimeSeriesError> {
+    let columns = frame
+        .get_columns()
+        .iter()
+        .map(|series| series.values())
+        .collect::<Result<Vec<_>, _>>()?;
+    let columns: Vec<&[f64]> = columns.iter().map(Vec::as_slice).collect();
+
+    johansen(&columns, lags)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_series {
+    use super::*;
+    use crate::statistics::time_series::autocorrelation::tests_autocorrelation::{
+        ar1, random_walk,
+    };
+
+    #[test]
+    fn test_series_tests() {
+        let x = ar1(0.5, 300, 641);
+        let mut values: Vec<Option<f64>> = x.iter().copied().map(Some).collect();
+        values.insert(10, None);
+        let series = Series::new("returns", values);
+
+        assert_eq!(series.acf(5).unwrap(), acf(&x, 5).unwrap());
+        assert_eq!(
+            series.kpss(Deterministic::Constant, None).unwrap(),
+            kpss(&x, Deterministic::Constant, None).unwrap()
+        );
+
+        let walk = Series::new("a", random_walk(300, 641));
+        let spread = Series::new("b", x);
+        let frame = DataFrame::new(vec![walk.clone(), spread.clone()]).unwrap();
+        assert_eq!(johansen_frame(&frame, 1).unwrap().eigenvalues.len(), 2);
+        assert!(walk
+            .engle_granger(&[&spread], Deterministic::Constant, 1)
+            .is_ok());
+    }
+}
@@ -0,0 +1,391 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::statistics::TimeSeriesError;
+use nalgebra::{DMatrix, DVector};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Deterministic terms of a test regression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Deterministic {
+    /// No deterministic terms.
+    None,
+
+    /// Constant.
+    #[default]
+    Constant,
+
+    /// Constant and linear time trend.
+    ConstantTrend,
+}
+
+/// Significance levels of the tabulated critical values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignificanceLevel {
+    /// 1%.
+    OnePercent,
+
+    /// 5%.
+    FivePercent,
+
+    /// 10%.
+    TenPercent,
+}
+
+/// Null hypothesis of a stationarity test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NullHypothesis {
+    /// The series has a unit root (ADF, Engle-Granger): rejected for
+    /// statistics below the critical value.
+    UnitRoot,
+
+    /// The series is stationary (KPSS): rejected for statistics above
+    /// the critical value.
+    Stationary,
+}
+
+/// Result of a unit root or stationarity test.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StationarityTest {
+    /// Test statistic.
+    pub statistic: f64,
+
+    /// Critical values at the 1%, 5% and 10% levels.
+    pub critical_values: [f64; 3],
+
+    /// Null hypothesis of the test.
+    pub null_hypothesis: NullHypothesis,
+
+    /// Number of lags (lagged differences for ADF, bandwidth for KPSS).
+    pub lags: usize,
+
+    /// Number of observations used in the test regression.
+    pub n_observations: usize,
+}
+
+/// Ordinary least squares fit.
+pub(crate) struct Ols {
+    pub(crate) coefficients: DVector<f64>,
+    pub(crate) standard_errors: DVector<f64>,
+    pub(crate) residuals: DVector<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl StationarityTest {
+    /// Critical value at a significance level.
+    pub fn critical_value(&self, level: SignificanceLevel) -> f64 {
+        match level {
+            SignificanceLevel::OnePercent => self.critical_values[0],
+            SignificanceLevel::FivePercent => self.critical_values[1],
+            SignificanceLevel::TenPercent => self.critical_values[2],
+        }
+    }
+
+    /// Whether the null hypothesis is rejected at a significance level.
+    pub fn rejects(&self, level: SignificanceLevel) -> bool {
+        match self.null_hypothesis {
+            NullHypothesis::UnitRoot => self.statistic < self.critical_value(level),
+            NullHypothesis::Stationary => self.statistic > self.critical_value(level),
+        }
+    }
+}
+
+/// MacKinnon (2010) response surface coefficients of the Dickey-Fuller
+/// critical values, `[1%, 5%, 10%]` of `[b_inf, b_1, b_2, b_3]`, indexed by
+/// the deterministic terms and the number of variables (1 for ADF,
+/// 2 or 3 for Engle-Granger).
+fn mackinnon_coefficients(
+    deterministic: Deterministic,
+    n_variables: usize,
+) -> Option<[[f64; 4]; 3]> {
+    Some(match (deterministic, n_variables) {
+        (Deterministic::None, 1) => [
+            [-2.56574, -2.2358, -3.627, 0.0],
+            [-1.94100, -0.2686, -3.365, 31.223],
+            [-1.61682, 0.2656, -2.714, 25.364],
+        ],
+        (Deterministic::Constant, 1) => [
+            [-3.43035, -6.5393, -16.786, -79.433],
+            [-2.86154, -2.8903, -4.234, -40.040],
+            [-2.56677, -1.5384, -2.809, 0.0],
+        ],
+        (Deterministic::ConstantTrend, 1) => [
+            [-3.95877, -9.0531, -28.428, -134.155],
+            [-3.41049, -4.3904, -9.036, -45.374],
+            [-3.12705, -2.5856, -3.925, -22.380],
+        ],
+        (Deterministic::Constant, 2) => [
+            [-3.89644, -10.9519, -33.527, 0.0],
+            [-3.33613, -6.1101, -6.823, 0.0],
+            [-3.04445, -4.2412, -2.720, 0.0],
+        ],
+        (Deterministic::ConstantTrend, 2) => [
+            [-4.32762, -15.4387, -35.679, 0.0],
+            [-3.78057, -9.5106, -12.074, 0.0],
+            [-3.49631, -7.0815, -7.538, 21.892],
+        ],
+        (Deterministic::Constant, 3) => [
+            [-4.29374, -14.4354, -33.195, 47.433],
+            [-3.74066, -8.5631, -10.852, 27.982],
+            [-3.45218, -6.2143, -3.718, 0.0],
+        ],
+        (Deterministic::ConstantTrend, 3) => [
+            [-4.66305, -18.7688, -49.793, 104.244],
+            [-4.11890, -11.8922, -19.031, 77.332],
+            [-3.83511, -9.0723, -8.504, 35.403],
+        ],
+        _ => return None,
+    })
+}
+
+/// Finite-sample Dickey-Fuller critical values for `n` observations.
+pub(crate) fn dickey_fuller_critical_values(
+    deterministic: Deterministic,
+    n_variables: usize,
+    n: usize,
+) -> Result<[f64; 3], TimeSeriesError> {
+    let coefficients = mackinnon_coefficients(deterministic, n_variables)
+        .ok_or(TimeSeriesError::UnsupportedDimension(n_variables))?;
+    let t = n as f64;
+
+    Ok(coefficients.map(|b| b[0] + b[1] / t + b[2] / t.powi(2) + b[3] / t.powi(3)))
+}
+
+/// Augmented Dickey-Fuller test of a unit root in `x`.
+///
+/// Regresses $\Delta x_t$ on the deterministic terms, $x_{t-1}$ and `lags`
+/// lagged differences; the statistic is the t-ratio of $x_{t-1}$. With
+/// `lags = None`, the number of lags minimises the AIC up to
+/// $\lfloor 12 (n / 100)^{1/4} \rfloor$.
+pub fn augmented_dickey_fuller(
+    x: &[f64],
+    deterministic: Deterministic,
+    lags: Option<usize>,
+) -> Result<StationarityTest, TimeSeriesError> {
+    let n = x.len();
+    let max_lag = lags.unwrap_or((12.0 * (n as f64 / 100.0).powf(0.25)) as usize);
+
+    if n < max_lag + 10 {
+        return Err(TimeSeriesError::InsufficientData(n));
+    }
+
+    let lags = match lags {
+        Some(lags) => lags,
+        // Compare the lags on a common sample.
+        None => (0..=max_lag)
+            .map(|p| {
+                let fit = adf_regression(x, deterministic, p, max_lag)?;
+                let m = fit.residuals.len() as f64;
+                let k = fit.coefficients.len() as f64;
+                Ok((p, m * (fit.residuals.norm_squared() / m).ln() + 2.0 * k))
+            })
+            .collect::<Result<Vec<_>, TimeSeriesError>>()?
+            .into_iter()
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map_or(0, |(p, _)| p),
+    };
+
+    let fit = adf_regression(x, deterministic, lags, lags)?;
+    let n_observations = fit.residuals.len();
+    let index = deterministic_terms(deterministic);
+
+    Ok(StationarityTest {
+        statistic: fit.coefficients[index] / fit.standard_errors[index],
+        critical_values: dickey_fuller_critical_values(deterministic, 1, n_observations)?,
+        null_hypothesis: NullHypothesis::UnitRoot,
+        lags,
+        n_observations,
+    })
+}
+
+/// KPSS test of (level or trend) stationarity of `x`.
+///
+/// The statistic is $\sum_t S_t^2 / (n^2 \hat{\sigma}^2)$, with $S_t$ the
+/// partial sums of the residuals from the deterministic terms and
+/// $\hat{\sigma}^2$ the Newey-West long-run variance with `lags` Bartlett
+/// lags (by default $\lfloor 12 (n / 100)^{1/4} \rfloor$). Only
+/// `Constant` and `ConstantTrend` are supported.
+pub fn kpss(
+    x: &[f64],
+    deterministic: Deterministic,
+    lags: Option<usize>,
+) -> Result<StationarityTest, TimeSeriesError> {
+    let n = x.len();
+    let lags = lags.unwrap_or((12.0 * (n as f64 / 100.0).powf(0.25)) as usize);
+
+    if n < lags + 10 {
+        return Err(TimeSeriesError::InsufficientData(n));
+    }
+
+    let critical_values = match deterministic {
+        Deterministic::Constant => [0.739, 0.463, 0.347],
+        Deterministic::ConstantTrend => [0.216, 0.146, 0.119],
+        Deterministic::None => return Err(TimeSeriesError::UnsupportedDeterministic),
+    };
+
+    let regressors = DMatrix::from_fn(n, deterministic_terms(deterministic), |t, j| {
+        (t as f64 + 1.0).powi(j as i32)
+    });
+    let residuals = ols(&DVector::from_column_slice(x), &regressors)?.residuals;
+
+    let mut partial_sum = 0.0;
+    let sum_of_squares: f64 = residuals
+        .iter()
+        .map(|e| {
+            partial_sum += e;
+            partial_sum * partial_sum
+        })
+        .sum();
+
+    Ok(StationarityTest {
+        statistic: sum_of_squares
+            / ((n * n) as f64 * long_run_variance(residuals.as_slice(), lags)),
+        critical_values,
+        null_hypothesis: NullHypothesis::Stationary,
+        lags,
+        n_observations: n,
+    })
+}
+
+/// Newey-West long-run variance of zero-mean residuals with Bartlett weights.
+pub(crate) fn long_run_variance(residuals: &[f64], lags: usize) -> f64 {
+    let n = residuals.len();
+    let autocovariance =
+        |k: usize| (k..n).map(|t| residuals[t] * residuals[t - k]).sum::<f64>() / n as f64;
+
+    autocovariance(0)
+        + 2.0
+            * (1..=lags.min(n - 1))
+                .map(|k| (1.0 - k as f64 / (lags + 1) as f64) * autocovariance(k))
+                .sum::<f64>()
+}
+
+/// Number of deterministic regressors.
+fn deterministic_terms(deterministic: Deterministic) -> usize {
+    match deterministic {
+        Deterministic::None => 0,
+        Deterministic::Constant => 1,
+        Deterministic::ConstantTrend => 2,
+    }
+}
+
+/// ADF regression with `lags` lagged differences, on the sample starting
+/// after `start` lags. Regressors: deterministic terms, $x_{t-1}$, lags.
+pub(crate) fn adf_regression(
+    x: &[f64],
+    deterministic: Deterministic,
+    lags: usize,
+    start: usize,
+) -> Result<Ols, TimeSeriesError> {
+    let dx: Vec<f64> = x.windows(2).map(|w| w[1] - w[0]).collect();
+    let rows: Vec<usize> = (start..dx.len()).collect();
+    let terms = deterministic_terms(deterministic);
+
+    let y = DVector::from_iterator(rows.len(), rows.iter().map(|&t| dx[t]));
+    let regressors = DMatrix::from_fn(rows.len(), terms + 1 + lags, |i, j| {
+        let t = rows[i];
+        match j {
+            j if j < terms => (t as f64 + 1.0).powi(j as i32),
+            j if j == terms => x[t],
+            j => dx[t - (j - terms)],
+        }
+    });
+
+    ols(&y, &regressors)
+}
+
+/// Ordinary least squares with classical standard errors.
+pub(crate) fn ols(y: &DVector<f64>, x: &DMatrix<f64>) -> Result<Ols, TimeSeriesError> {
+    let (n, k) = x.shape();
+
+    if n <= k {
+        return Err(TimeSeriesError::InsufficientData(n));
+    }
+
+    let inverse = (x.transpose() * x)
+        .try_inverse()
+        .ok_or(TimeSeriesError::SingularMatrix)?;
+    let coefficients = &inverse * x.transpose() * y;
+    let residuals = y - x * &coefficients;
+    let variance = residuals.norm_squared() / (n - k) as f64;
+
+    Ok(Ols {
+        standard_errors: inverse.diagonal().map(|v| (v * variance).sqrt()),
+        coefficients,
+        residuals,
+    })
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_stationarity {
+    use super::*;
+    use crate::statistics::time_series::autocorrelation::tests_autocorrelation::{
+        ar1, random_walk,
+    };
+
+    #[test]
+    fn test_critical_values() {
+        // Asymptotic values at the 5% level.
+        let values = dickey_fuller_critical_values(Deterministic::Constant, 1, 1_000_000).unwrap();
+        assert_approx_equal!(values[1], -2.86154, 1e-4);
+
+        // Engle-Granger with two variables, 100 observations.
+        let values = dickey_fuller_critical_values(Deterministic::Constant, 2, 100).unwrap();
+        assert_approx_equal!(values[1], -3.3979, 1e-4);
+
+        assert!(dickey_fuller_critical_values(Deterministic::None, 2, 100).is_err());
+    }
+
+    #[test]
+    fn test_augmented_dickey_fuller() {
+        let stationary =
+            augmented_dickey_fuller(&ar1(0.5, 500, 641), Deterministic::Constant, None).unwrap();
+        let unit_root =
+            augmented_dickey_fuller(&random_walk(500, 641), Deterministic::Constant, Some(1))
+                .unwrap();
+
+        assert!(stationary.rejects(SignificanceLevel::OnePercent));
+        assert!(!unit_root.rejects(SignificanceLevel::TenPercent));
+        assert_eq!(unit_root.lags, 1);
+        assert_eq!(unit_root.n_observations, 498);
+
+        let trend = augmented_dickey_fuller(
+            &random_walk(500, 641),
+            Deterministic::ConstantTrend,
+            Some(0),
+        )
+        .unwrap();
+        assert!(trend.critical_value(SignificanceLevel::FivePercent) < -3.4);
+    }
+
+    #[test]
+    fn test_kpss() {
+        let stationary = kpss(&ar1(0.5, 500, 641), Deterministic::Constant, None).unwrap();
+        let unit_root = kpss(&random_walk(500, 641), Deterministic::Constant, None).unwrap();
+
+        assert_eq!(stationary.lags, 17);
+        assert!(!stationary.rejects(SignificanceLevel::FivePercent));
+        assert!(unit_root.rejects(SignificanceLevel::OnePercent));
+
+        assert!(matches!(
+            kpss(&random_walk(500, 641), Deterministic::None, None),
+            Err(TimeSeriesError::UnsupportedDeterministic)
+        ));
+    }
+}
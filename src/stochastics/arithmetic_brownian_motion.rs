@@ -7,37 +7,50 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+use crate::autodiff::Scalar;
+use crate::error::{ensure, expect_valid, RustQuantError};
 use crate::stochastics::*;
 
 /// Struct containing the Arithmetic Brownian Motion parameters.
-pub struct ArithmeticBrownianMotion {
+pub struct ArithmeticBrownianMotion<T = f64> {
     /// The drift ($\mu$) in percentage.
-    pub mu: f64,
+    pub mu: T,
 
     /// The volatility ($\sigma$) in percentage.
-    pub sigma: f64,
+    pub sigma: T,
 }
 
-impl ArithmeticBrownianMotion {
+impl<T: Scalar> ArithmeticBrownianMotion<T> {
     /// Create a new Arithmetic Brownian Motion process.
-    pub fn new(mu: f64, sigma: f64) -> Self {
-        assert!(sigma >= 0.0);
-        Self { mu, sigma }
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sigma` is negative.
+    pub fn new(mu: T, sigma: T) -> Self {
+        expect_valid(Self::try_new(mu, sigma))
+    }
+
+    /// Create a new Arithmetic Brownian Motion process, or an error if
+    /// `sigma` is negative.
+    pub fn try_new(mu: T, sigma: T) -> Result<Self, RustQuantError> {
+        ensure(sigma.value() >= 0.0, "sigma must be non-negative")?;
+
+        Ok(Self { mu, sigma })
     }
 }
 
-impl StochasticProcess for ArithmeticBrownianMotion {
-    fn drift(&self, _x: f64, _t: f64) -> f64 {
+impl<T: Scalar> StochasticProcess<T> for ArithmeticBrownianMotion<T> {
+    fn drift(&self, _x: T, _t: f64) -> T {
         // mu dt
         self.mu
     }
 
-    fn diffusion(&self, _x: f64, _t: f64) -> f64 {
+    fn diffusion(&self, _x: T, _t: f64) -> T {
         // sigma dW_t
         self.sigma
     }
 
-    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+    fn jump(&self, _x: T, _t: f64) -> Option<T> {
         None
     }
 }
@@ -49,7 +62,7 @@ impl StochasticProcess for ArithmeticBrownianMotion {
 #[cfg(test)]
 mod tests_abm {
     use super::*;
-    use crate::{assert_approx_equal, statistics::*};
+    use crate::statistics::*;
 
     #[test]
     fn test_arithmetic_brownian_motion() -> Result<(), Box<dyn std::error::Error>> {
@@ -63,11 +76,7 @@ mod tests_abm {
         // plot_vector((&output.trajectories[1]).clone(), file2)
 
         // Test the distribution of the final values.
-        let X_T: Vec<f64> = output
-            .paths
-            .iter()
-            .filter_map(|v| v.last().cloned())
-            .collect();
+        let X_T = output.terminal_values().to_vec();
 
         let E_XT = X_T.mean();
         let V_XT = X_T.variance();
@@ -7,6 +7,7 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+use crate::error::{ensure, expect_valid, RustQuantError};
 use crate::stochastics::*;
 
 /// Sigma can either be varying or constant in the BDT model
@@ -34,20 +35,31 @@ pub struct BlackDermanToy {
 
 impl BlackDermanToy {
     /// Create a new Black-Derman-Toy process.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a constant `sigma` is negative.
     pub fn new(sigma: Sigma, theta_t: fn(f64) -> f64) -> Self {
+        expect_valid(Self::try_new(sigma, theta_t))
+    }
+
+    /// Create a new Black-Derman-Toy process, or an error if a constant
+    /// `sigma` is negative.
+    pub fn try_new(sigma: Sigma, theta_t: fn(f64) -> f64) -> Result<Self, RustQuantError> {
         match sigma {
             Sigma::Const(sigma) => {
-                assert!(sigma >= 0.0);
-                Self {
+                ensure(sigma >= 0.0, "sigma must be non-negative")?;
+
+                Ok(Self {
                     sigma: Sigma::Const(sigma),
                     theta_t,
-                }
+                })
             }
-            Sigma::Varying(sigma) => Self {
+            Sigma::Varying(sigma) => Ok(Self {
                 // TODO add check for positivity of the function here...
                 sigma: Sigma::Varying(sigma),
                 theta_t,
-            },
+            }),
         }
     }
 }
@@ -107,11 +119,7 @@ mod tests_black_derman_toy {
         let output = hw.euler_maruyama(0.13, 0.0, 1.0, 100, 100, false);
 
         // Test the distribution of the final values.
-        let X_T: Vec<f64> = output
-            .paths
-            .iter()
-            .filter_map(|v| v.last().cloned())
-            .collect();
+        let X_T = output.terminal_values().to_vec();
 
         let E_XT = X_T.mean();
         assert!(E_XT.exp() >= 0.);
@@ -129,11 +137,7 @@ mod tests_black_derman_toy {
         let output = hw.euler_maruyama(0.13, 0.0, 1.0, 100, 1000, false);
 
         // Test the distribution of the final values.
-        let X_T: Vec<f64> = output
-            .paths
-            .iter()
-            .filter_map(|v| v.last().cloned())
-            .collect();
+        let X_T = output.terminal_values().to_vec();
 
         let E_XT = X_T.mean();
         assert!(E_XT.exp() >= 0.);
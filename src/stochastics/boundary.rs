@@ -0,0 +1,93 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Boundary handling for processes that live on $[0, \infty)$, such as the
+//! Cox-Ingersoll-Ross and CEV processes.
+//!
+//! A plain Euler-Maruyama step can push these processes below zero, where
+//! the diffusion ($\sqrt{x}$ or $x^\beta$) is undefined. The schemes below
+//! modify the step so the simulated paths stay non-negative:
+//!
+//! - Absorption: the path is floored at zero and stays there once hit.
+//! - Reflection: the path is reflected at zero, $x_{k+1} = |\tilde{x}_{k+1}|$.
+//! - Full truncation (Lord, Koekkoek & van Dijk, 2010): the drift and
+//!   diffusion are evaluated at $x_k^+$, and $x_k^+$ is reported.
+
+use crate::stochastics::*;
+use rand::Rng;
+use rand_distr::StandardNormal;
+
+/// Boundary behaviour at zero for non-negative processes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BoundaryScheme {
+    /// Zero is absorbing.
+    Absorption,
+
+    /// Paths are reflected at zero.
+    Reflection,
+
+    /// Coefficients are evaluated at the positive part of the state.
+    #[default]
+    FullTruncation,
+}
+
+impl BoundaryScheme {
+    /// One Euler-Maruyama step of the (internal) state `x` under the scheme.
+    pub fn step<P: StochasticProcess + Sync + ?Sized>(
+        &self,
+        process: &P,
+        x: f64,
+        t: f64,
+        dt: f64,
+        dW: f64,
+    ) -> f64 {
+        match self {
+            BoundaryScheme::Absorption => match x > 0.0 {
+                true => (x + process.drift(x, t) * dt + process.diffusion(x, t) * dW).max(0.0),
+                false => 0.0,
+            },
+            BoundaryScheme::Reflection => {
+                (x + process.drift(x, t) * dt + process.diffusion(x, t) * dW).abs()
+            }
+            BoundaryScheme::FullTruncation => {
+                let x_plus = x.max(0.0);
+                x + process.drift(x_plus, t) * dt + process.diffusion(x_plus, t) * dW
+            }
+        }
+    }
+
+    /// Value reported for the internal state `x`.
+    pub fn observe(&self, x: f64) -> f64 {
+        x.max(0.0)
+    }
+
+    /// Simulates one path on the given time grid.
+    pub(crate) fn simulate_path<P, R>(
+        &self,
+        process: &P,
+        x_0: f64,
+        times: &[f64],
+        rng: &mut R,
+        path: &mut [f64],
+    ) where
+        P: StochasticProcess + Sync + ?Sized,
+        R: Rng + ?Sized,
+    {
+        let mut state = x_0;
+        path[0] = self.observe(x_0);
+
+        for t in 0..times.len() - 1 {
+            let dt = times[t + 1] - times[t];
+            let dW = dt.sqrt() * rng.sample::<f64, _>(StandardNormal);
+
+            state = self.step(process, state, times[t], dt, dW);
+            path[t + 1] = self.observe(state);
+        }
+    }
+}
@@ -0,0 +1,351 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Bridge processes, i.e. processes conditioned on their terminal value.
+//!
+//! - Brownian bridge from $(t_0, x_0)$ to $(T, x_T)$:
+//!   - $dX(t) = \frac{x_T - X(t)}{T - t} dt + \sigma dW(t)$
+//! - Ornstein-Uhlenbeck bridge, i.e. an Ornstein-Uhlenbeck process
+//!   conditioned on $X(T) = x_T$.
+//!
+//! Both are simulated exactly: each step samples $X(t_{k+1})$ from its
+//! Gaussian distribution conditional on $X(t_k)$ and on the terminal value,
+//! so the paths hit $x_T$ exactly at $T$.
+//! Useful for barrier bias correction, stratified sampling on the terminal
+//! value, and interpolating missing data between two observations.
+
+use crate::error::{ensure, expect_valid, RustQuantError};
+use crate::stochastics::*;
+use rand::Rng;
+use rand_distr::StandardNormal;
+
+/// Struct containing the Brownian bridge parameters.
+#[derive(Debug, Clone, Copy)]
+pub struct BrownianBridge {
+    /// The volatility ($\sigma$).
+    pub sigma: f64,
+
+    /// The value the bridge is pinned to ($x_T$).
+    pub terminal_value: f64,
+
+    /// The time at which the bridge is pinned ($T$).
+    pub terminal_time: f64,
+}
+
+/// Struct containing the Ornstein-Uhlenbeck bridge parameters.
+#[derive(Debug, Clone, Copy)]
+pub struct OrnsteinUhlenbeckBridge {
+    /// The long-run mean ($\mu$).
+    pub mu: f64,
+
+    /// The diffusion, or instantaneous volatility ($\sigma$).
+    pub sigma: f64,
+
+    /// Mean reversion parameter ($\theta$).
+    pub theta: f64,
+
+    /// The value the bridge is pinned to ($x_T$).
+    pub terminal_value: f64,
+
+    /// The time at which the bridge is pinned ($T$).
+    pub terminal_time: f64,
+}
+
+impl BrownianBridge {
+    /// Create a new Brownian bridge.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sigma` is negative.
+    pub fn new(sigma: f64, terminal_value: f64, terminal_time: f64) -> Self {
+        expect_valid(Self::try_new(sigma, terminal_value, terminal_time))
+    }
+
+    /// Create a new Brownian bridge, or an error if `sigma` is negative.
+    pub fn try_new(
+        sigma: f64,
+        terminal_value: f64,
+        terminal_time: f64,
+    ) -> Result<Self, RustQuantError> {
+        ensure(sigma >= 0.0, "sigma must be non-negative")?;
+
+        Ok(Self {
+            sigma,
+            terminal_value,
+            terminal_time,
+        })
+    }
+
+    /// Samples one bridge path on the given time grid.
+    fn sample_path<R: Rng>(&self, x_0: f64, times: &[f64], rng: &mut R, path: &mut [f64]) {
+        let T = self.terminal_time;
+        path[0] = x_0;
+
+        for k in 0..times.len() - 1 {
+            let (t, s) = (times[k], times[k + 1]);
+            let dt = s - t;
+
+            // X(s) | X(t), X(T) ~ N(X(t) + (x_T - X(t)) dt / (T - t), sigma^2 dt (T - s) / (T - t)).
+            let mean = path[k] + (self.terminal_value - path[k]) * dt / (T - t);
+            let var = self.sigma.powi(2) * dt * (T - s).max(0.0) / (T - t);
+
+            path[k + 1] = mean + var.sqrt() * rng.sample::<f64, _>(StandardNormal);
+        }
+    }
+}
+
+impl OrnsteinUhlenbeckBridge {
+    /// Create a new Ornstein-Uhlenbeck bridge.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sigma` is negative or `theta` is not positive.
+    pub fn new(mu: f64, sigma: f64, theta: f64, terminal_value: f64, terminal_time: f64) -> Self {
+        expect_valid(Self::try_new(
+            mu,
+            sigma,
+            theta,
+            terminal_value,
+            terminal_time,
+        ))
+    }
+
+    /// Create a new Ornstein-Uhlenbeck bridge, or an error if `sigma` is
+    /// negative or `theta` is not positive.
+    pub fn try_new(
+        mu: f64,
+        sigma: f64,
+        theta: f64,
+        terminal_value: f64,
+        terminal_time: f64,
+    ) -> Result<Self, RustQuantError> {
+        ensure(sigma >= 0.0, "sigma must be non-negative")?;
+        ensure(theta > 0.0, "theta must be positive")?;
+
+        Ok(Self {
+            mu,
+            sigma,
+            theta,
+            terminal_value,
+            terminal_time,
+        })
+    }
+
+    /// Variance of the (unconditioned) OU transition over a period `tau`.
+    fn transition_variance(&self, tau: f64) -> f64 {
+        self.sigma.powi(2) * (1.0 - (-2.0 * self.theta * tau).exp()) / (2.0 * self.theta)
+    }
+
+    /// Samples one bridge path on the given time grid.
+    fn sample_path<R: Rng>(&self, x_0: f64, times: &[f64], rng: &mut R, path: &mut [f64]) {
+        let T = self.terminal_time;
+        let y_T = self.terminal_value - self.mu;
+        path[0] = x_0;
+
+        for k in 0..times.len() - 1 {
+            let (t, s) = (times[k], times[k + 1]);
+            let y = path[k] - self.mu;
+
+            // Joint Gaussian of (Y(s), Y(T)) given Y(t), then condition on Y(T).
+            let decay_step = (-self.theta * (s - t)).exp();
+            let decay_rest = (-self.theta * (T - s)).exp();
+
+            let v_step = self.transition_variance(s - t);
+            let v_T = self.transition_variance(T - t);
+            let cov = decay_rest * v_step;
+
+            let m_step = y * decay_step;
+            let m_T = m_step * decay_rest;
+
+            let (mean, var) = match v_T > 0.0 {
+                true => (
+                    m_step + cov / v_T * (y_T - m_T),
+                    (v_step - cov * cov / v_T).max(0.0),
+                ),
+                false => (y_T, 0.0),
+            };
+
+            path[k + 1] = self.mu + mean + var.sqrt() * rng.sample::<f64, _>(StandardNormal);
+        }
+    }
+}
+
+impl StochasticProcess for BrownianBridge {
+    fn drift(&self, x: f64, t: f64) -> f64 {
+        (self.terminal_value - x) / (self.terminal_time - t)
+    }
+
+    fn diffusion(&self, _x: f64, _t: f64) -> f64 {
+        self.sigma
+    }
+
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        None
+    }
+
+    /// Simulates bridge paths using the exact conditional Gaussian
+    /// transitions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `t_n` is after the terminal time (see
+    /// [`StochasticProcess::try_simulate_with_config`]).
+    fn simulate_with_config(
+        &self,
+        x_0: f64,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        config: &SimulationConfig,
+    ) -> Trajectories {
+        expect_valid(self.check_simulation(x_0, &[t_0, t_n]));
+
+        generate_trajectories(t_0, t_n, n_steps, m_paths, config, |times, rng, path| {
+            self.sample_path(x_0, times, rng, path)
+        })
+    }
+
+    fn check_simulation(&self, _x_0: f64, times: &[f64]) -> Result<(), RustQuantError> {
+        ensure(
+            times.last().is_none_or(|&t_n| t_n <= self.terminal_time),
+            "The bridge cannot be simulated past its terminal time.",
+        )
+    }
+}
+
+impl StochasticProcess for OrnsteinUhlenbeckBridge {
+    /// Drift of the h-transformed OU process:
+    /// $\theta (\mu - x) + \sigma^2 \partial_x \ln p(T, x_T \mid t, x)$.
+    fn drift(&self, x: f64, t: f64) -> f64 {
+        let tau = self.terminal_time - t;
+        let decay = (-self.theta * tau).exp();
+        let y = x - self.mu;
+        let y_T = self.terminal_value - self.mu;
+
+        self.theta * (self.mu - x)
+            + self.sigma.powi(2) * decay * (y_T - y * decay) / self.transition_variance(tau)
+    }
+
+    fn diffusion(&self, _x: f64, _t: f64) -> f64 {
+        self.sigma
+    }
+
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        None
+    }
+
+    /// Simulates bridge paths using the exact conditional Gaussian
+    /// transitions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `t_n` is after the terminal time (see
+    /// [`StochasticProcess::try_simulate_with_config`]).
+    fn simulate_with_config(
+        &self,
+        x_0: f64,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        config: &SimulationConfig,
+    ) -> Trajectories {
+        expect_valid(self.check_simulation(x_0, &[t_0, t_n]));
+
+        generate_trajectories(t_0, t_n, n_steps, m_paths, config, |times, rng, path| {
+            self.sample_path(x_0, times, rng, path)
+        })
+    }
+
+    fn check_simulation(&self, _x_0: f64, times: &[f64]) -> Result<(), RustQuantError> {
+        ensure(
+            times.last().is_none_or(|&t_n| t_n <= self.terminal_time),
+            "The bridge cannot be simulated past its terminal time.",
+        )
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_bridge {
+    use super::*;
+    use crate::statistics::*;
+
+    #[test]
+    fn test_brownian_bridge() {
+        let bb = BrownianBridge::new(0.5, 2.0, 1.0);
+        let output = bb.euler_maruyama(0.0, 0.0, 1.0, 100, 5000, true);
+
+        // Every path is pinned at both ends.
+        for path in output.iter() {
+            assert_eq!(path[0], 0.0);
+            assert_approx_equal!(path[100], 2.0, 1e-12);
+        }
+
+        // At t = 1/2: mean = (x_0 + x_T) / 2, variance = sigma^2 t (T - t) / T.
+        let X_half = output.values_at(50).to_vec();
+        assert_approx_equal!(X_half.mean(), 1.0, 0.02);
+        assert_approx_equal!(X_half.variance(), 0.25 * 0.25, 0.01);
+    }
+
+    #[test]
+    fn test_bridge_past_terminal_time_is_an_error() {
+        let config = SimulationConfig::new(false);
+        let bb = BrownianBridge::new(0.5, 2.0, 1.0);
+        let oub = OrnsteinUhlenbeckBridge::new(1.0, 0.4, 3.0, 0.5, 2.0);
+
+        assert!(bb
+            .try_simulate_with_config(0.0, 0.0, 1.0, 10, 10, &config)
+            .is_ok());
+        assert!(bb
+            .try_simulate_with_config(0.0, 0.0, 1.5, 10, 10, &config)
+            .is_err());
+        assert!(oub
+            .try_simulate_with_config(0.0, 0.0, 2.5, 10, 10, &config)
+            .is_err());
+        assert!(Box::new(bb)
+            .try_simulate_with_config(0.0, 0.0, 1.5, 10, 10, &config)
+            .is_err());
+    }
+
+    #[test]
+    fn test_ornstein_uhlenbeck_bridge() {
+        let (mu, sigma, theta) = (1.0, 0.4, 3.0_f64);
+        let oub = OrnsteinUhlenbeckBridge::new(mu, sigma, theta, 0.5, 2.0);
+        let output = oub.euler_maruyama(1.5, 0.0, 2.0, 200, 5000, true);
+
+        for path in output.iter() {
+            assert_approx_equal!(path[200], 0.5, 1e-10);
+        }
+
+        // Conditional mean at t = 1: combine forward and backward information.
+        let (t, T) = (1.0_f64, 2.0_f64);
+        let v = |tau: f64| sigma * sigma * (1.0 - (-2.0 * theta * tau).exp()) / (2.0 * theta);
+        let m_t = 0.5 * (-theta * t).exp();
+        let c = (-theta * (T - t)).exp() * v(t);
+        let expected = mu + m_t + c / v(T) * (-0.5 - m_t * (-theta * (T - t)).exp());
+
+        let X_mid = output.values_at(100).to_vec();
+        assert_approx_equal!(X_mid.mean(), expected, 0.02);
+    }
+
+    #[test]
+    fn test_bridge_drift() {
+        let bb = BrownianBridge::new(1.0, 1.0, 2.0);
+        assert_approx_equal!(bb.drift(0.0, 1.0), 1.0, 1e-12);
+
+        // Without mean reversion the OU bridge drift tends to the Brownian one.
+        let oub = OrnsteinUhlenbeckBridge::new(0.0, 1.0, 1e-8, 1.0, 2.0);
+        assert_approx_equal!(oub.drift(0.0, 1.0), 1.0, 1e-6);
+    }
+}
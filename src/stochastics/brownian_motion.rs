@@ -49,7 +49,7 @@ mod sde_tests {
     // use std::time::Instant;
 
     use super::*;
-    use crate::{assert_approx_equal, statistics::*};
+    use crate::statistics::*;
 
     #[test]
     fn test_brownian_motion() -> Result<(), Box<dyn std::error::Error>> {
@@ -88,11 +88,7 @@ mod sde_tests {
         // plot_vector((&output_parallel.trajectories[0]).clone(), file2)
 
         // Test the distribution of the final values.
-        let X_T: Vec<f64> = output_serial
-            .paths
-            .iter()
-            .filter_map(|v| v.last().cloned())
-            .collect();
+        let X_T = output_serial.terminal_values().to_vec();
 
         let E_XT = X_T.mean();
         let V_XT = X_T.variance();
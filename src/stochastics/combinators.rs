@@ -0,0 +1,422 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Combinators to build new processes from existing ones.
+//!
+//! - `process.exponential()`: $Y(t) = e^{X(t)}$.
+//! - `process.shifted(f)`: $Y(t) = X(t) + f(t)$ for a deterministic $f$.
+//! - `process.time_changed(clock)`: $Y(t) = X(\tau(t))$ for a deterministic,
+//!   increasing clock $\tau$.
+//! - `sum(a, b)`: $Y(t) = A(t) + B(t)$ for independent $A$ and $B$.
+//!
+//! The drift and diffusion of each combinator follow from Itô's lemma.
+//! Where possible the simulation transforms paths of the underlying
+//! process, so dedicated schemes (e.g. for fractional processes) are kept.
+//!
+//! ```rust
+//! use RustQuant::stochastics::*;
+//!
+//! // Geometric Brownian motion as the exponential of an arithmetic one.
+//! let gbm = ArithmeticBrownianMotion::new(0.03, 0.2).exponential();
+//!
+//! // A mean-reverting rate with a deterministic seasonal shift.
+//! let seasonal = OrnsteinUhlenbeck::new(0.0, 0.1, 2.0)
+//!     .shifted(|t: f64| 0.05 + 0.01 * (2.0 * std::f64::consts::PI * t).sin());
+//!
+//! let output = gbm.euler_maruyama(100.0, 0.0, 1.0, 252, 10, false);
+//! let rates = seasonal.euler_maruyama(0.05, 0.0, 1.0, 252, 10, false);
+//! ```
+
+use crate::error::{ensure, expect_valid, RustQuantError};
+use crate::stochastics::*;
+
+/// Step used for the numerical derivatives of deterministic functions.
+const DERIVATIVE_STEP: f64 = 1e-6;
+
+/// Central difference approximation of $f'(t)$.
+fn derivative<F: Fn(f64) -> f64>(f: &F, t: f64) -> f64 {
+    (f(t + DERIVATIVE_STEP) - f(t - DERIVATIVE_STEP)) / (2.0 * DERIVATIVE_STEP)
+}
+
+/// The exponential $Y(t) = e^{X(t)}$ of a process.
+#[derive(Debug, Clone)]
+pub struct Exponential<P: StochasticProcess + Sync> {
+    /// The underlying process $X$.
+    pub process: P,
+}
+
+/// A process shifted by a deterministic function, $Y(t) = X(t) + f(t)$.
+#[derive(Clone)]
+pub struct Shifted<P: StochasticProcess + Sync, F: Fn(f64) -> f64 + Sync> {
+    /// The underlying process $X$.
+    pub process: P,
+
+    /// The deterministic shift $f(t)$.
+    pub shift: F,
+}
+
+/// A process run on a deterministic clock, $Y(t) = X(\tau(t))$.
+#[derive(Clone)]
+pub struct TimeChanged<P: StochasticProcess + Sync, C: Fn(f64) -> f64 + Sync> {
+    /// The underlying process $X$.
+    pub process: P,
+
+    /// The increasing clock $\tau(t)$.
+    pub clock: C,
+}
+
+/// The sum $Y(t) = A(t) + B(t)$ of two independent processes.
+///
+/// The initial value is attributed to $A$, i.e. $B$ starts at zero.
+#[derive(Debug, Clone)]
+pub struct SumProcess<A: StochasticProcess + Sync, B: StochasticProcess + Sync> {
+    /// The first process, $A$.
+    pub first: A,
+
+    /// The second process, $B$.
+    pub second: B,
+}
+
+/// Combinator methods available on every stochastic process.
+pub trait StochasticProcessExt: StochasticProcess + Sync + Sized {
+    /// The exponential $e^{X(t)}$ of the process.
+    fn exponential(self) -> Exponential<Self> {
+        Exponential { process: self }
+    }
+
+    /// The process shifted by the deterministic function `shift`.
+    fn shifted<F: Fn(f64) -> f64 + Sync>(self, shift: F) -> Shifted<Self, F> {
+        Shifted {
+            process: self,
+            shift,
+        }
+    }
+
+    /// The process run on the deterministic, increasing `clock`.
+    fn time_changed<C: Fn(f64) -> f64 + Sync>(self, clock: C) -> TimeChanged<Self, C> {
+        TimeChanged {
+            process: self,
+            clock,
+        }
+    }
+}
+
+impl<P: StochasticProcess + Sync> StochasticProcessExt for P {}
+
+/// The sum of two independent processes.
+pub fn sum<A: StochasticProcess + Sync, B: StochasticProcess + Sync>(
+    first: A,
+    second: B,
+) -> SumProcess<A, B> {
+    SumProcess { first, second }
+}
+
+/// Applies `f(t, x)` to every value of the trajectories.
+fn map_paths<F: Fn(f64, f64) -> f64>(mut trajectories: Trajectories, f: F) -> Trajectories {
+    for mut path in trajectories.paths.rows_mut() {
+        for (x, &t) in path.iter_mut().zip(trajectories.times.iter()) {
+            *x = f(t, *x);
+        }
+    }
+
+    trajectories
+}
+
+impl<P: StochasticProcess + Sync> StochasticProcess for Exponential<P> {
+    /// $\mu_Y(y, t) = y \left( \mu(\ln y, t) + \frac{1}{2} \sigma(\ln y, t)^2 \right)$
+    fn drift(&self, y: f64, t: f64) -> f64 {
+        let x = y.ln();
+        y * (self.process.drift(x, t) + 0.5 * self.process.diffusion(x, t).powi(2))
+    }
+
+    /// $\sigma_Y(y, t) = y \sigma(\ln y, t)$
+    fn diffusion(&self, y: f64, t: f64) -> f64 {
+        y * self.process.diffusion(y.ln(), t)
+    }
+
+    fn jump(&self, y: f64, t: f64) -> Option<f64> {
+        self.process.jump(y.ln(), t)
+    }
+
+    /// Simulates $X$ from $\ln(x_0)$ and exponentiates the paths.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless `x_0 > 0` (see
+    /// [`StochasticProcess::try_simulate_with_config`]).
+    fn simulate_with_config(
+        &self,
+        x_0: f64,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        config: &SimulationConfig,
+    ) -> Trajectories {
+        expect_valid(ensure(x_0 > 0.0, "The initial value must be positive."));
+
+        let output =
+            self.process
+                .simulate_with_config(x_0.ln(), t_0, t_n, n_steps, m_paths, config);
+
+        map_paths(output, |_, x| x.exp())
+    }
+
+    fn check_simulation(&self, x_0: f64, times: &[f64]) -> Result<(), RustQuantError> {
+        ensure(x_0 > 0.0, "The initial value must be positive.")?;
+
+        self.process.check_simulation(x_0.ln(), times)
+    }
+}
+
+impl<P: StochasticProcess + Sync, F: Fn(f64) -> f64 + Sync> StochasticProcess for Shifted<P, F> {
+    /// $\mu_Y(y, t) = \mu(y - f(t), t) + f'(t)$
+    fn drift(&self, y: f64, t: f64) -> f64 {
+        self.process.drift(y - (self.shift)(t), t) + derivative(&self.shift, t)
+    }
+
+    /// $\sigma_Y(y, t) = \sigma(y - f(t), t)$
+    fn diffusion(&self, y: f64, t: f64) -> f64 {
+        self.process.diffusion(y - (self.shift)(t), t)
+    }
+
+    fn jump(&self, y: f64, t: f64) -> Option<f64> {
+        self.process.jump(y - (self.shift)(t), t)
+    }
+
+    /// Simulates $X$ from $x_0 - f(t_0)$ and adds the shift to the paths.
+    fn simulate_with_config(
+        &self,
+        x_0: f64,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        config: &SimulationConfig,
+    ) -> Trajectories {
+        let output = self.process.simulate_with_config(
+            x_0 - (self.shift)(t_0),
+            t_0,
+            t_n,
+            n_steps,
+            m_paths,
+            config,
+        );
+
+        map_paths(output, |t, x| x + (self.shift)(t))
+    }
+
+    fn check_simulation(&self, x_0: f64, times: &[f64]) -> Result<(), RustQuantError> {
+        match times.first() {
+            Some(&t_0) => self
+                .process
+                .check_simulation(x_0 - (self.shift)(t_0), times),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Time-changed processes are simulated with the default Euler-Maruyama
+/// scheme on the coefficients below.
+impl<P: StochasticProcess + Sync, C: Fn(f64) -> f64 + Sync> StochasticProcess
+    for TimeChanged<P, C>
+{
+    /// $\mu_Y(y, t) = \mu(y, \tau(t)) \tau'(t)$
+    fn drift(&self, y: f64, t: f64) -> f64 {
+        self.process.drift(y, (self.clock)(t)) * derivative(&self.clock, t)
+    }
+
+    /// $\sigma_Y(y, t) = \sigma(y, \tau(t)) \sqrt{\tau'(t)}$
+    ///
+    /// # Panics
+    ///
+    /// Panics if the clock is decreasing at `t` (see
+    /// [`StochasticProcess::try_simulate_with_config`]).
+    fn diffusion(&self, y: f64, t: f64) -> f64 {
+        let rate = derivative(&self.clock, t);
+        expect_valid(ensure(rate >= 0.0, "The clock must be increasing."));
+
+        self.process.diffusion(y, (self.clock)(t)) * rate.sqrt()
+    }
+
+    fn jump(&self, y: f64, t: f64) -> Option<f64> {
+        self.process.jump(y, (self.clock)(t))
+    }
+
+    fn check_simulation(&self, _x_0: f64, times: &[f64]) -> Result<(), RustQuantError> {
+        ensure(
+            times.iter().all(|&t| derivative(&self.clock, t) >= 0.0),
+            "The clock must be increasing.",
+        )
+    }
+}
+
+impl<A: StochasticProcess + Sync, B: StochasticProcess + Sync> StochasticProcess
+    for SumProcess<A, B>
+{
+    /// Sum of the component drifts, both evaluated at the combined state.
+    /// Exact when the drifts do not depend on the state.
+    fn drift(&self, x: f64, t: f64) -> f64 {
+        self.first.drift(x, t) + self.second.drift(x, t)
+    }
+
+    /// $\sqrt{\sigma_A^2 + \sigma_B^2}$ (independent drivers), both evaluated
+    /// at the combined state. Exact when the diffusions do not depend on the
+    /// state.
+    fn diffusion(&self, x: f64, t: f64) -> f64 {
+        self.first
+            .diffusion(x, t)
+            .hypot(self.second.diffusion(x, t))
+    }
+
+    fn jump(&self, x: f64, t: f64) -> Option<f64> {
+        match (self.first.jump(x, t), self.second.jump(x, t)) {
+            (None, None) => None,
+            (a, b) => Some(a.unwrap_or(0.0) + b.unwrap_or(0.0)),
+        }
+    }
+
+    /// Simulates both components independently (with $B(t_0) = 0$) and adds
+    /// the paths, so state-dependent components are handled exactly.
+    fn simulate_with_config(
+        &self,
+        x_0: f64,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        config: &SimulationConfig,
+    ) -> Trajectories {
+        let mut output = self
+            .first
+            .simulate_with_config(x_0, t_0, t_n, n_steps, m_paths, config);
+        let second = self.second.simulate_with_config(
+            0.0,
+            t_0,
+            t_n,
+            n_steps,
+            m_paths,
+            &config.salted(0x9E37_79B9_7F4A_7C15),
+        );
+
+        output.paths += &second.paths;
+        output
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_combinators {
+    use super::*;
+    use crate::statistics::*;
+
+    #[test]
+    fn test_exponential_coefficients() {
+        // exp(ABM(mu, sigma)) is GBM(mu + sigma^2 / 2, sigma).
+        let (mu, sigma) = (0.03, 0.2);
+        let exp_abm = ArithmeticBrownianMotion::new(mu, sigma).exponential();
+        let gbm = GeometricBrownianMotion::new(mu + 0.5 * sigma * sigma, sigma);
+
+        assert_approx_equal!(exp_abm.drift(50.0, 0.0), gbm.drift(50.0, 0.0), 1e-12);
+        assert_approx_equal!(
+            exp_abm.diffusion(50.0, 0.0),
+            gbm.diffusion(50.0, 0.0),
+            1e-12
+        );
+    }
+
+    #[test]
+    fn test_exponential_paths() {
+        let exp_abm = ArithmeticBrownianMotion::new(0.03, 0.2).exponential();
+        let output = exp_abm.euler_maruyama(100.0, 0.0, 1.0, 100, 5000, true);
+
+        // E[Y_T] = y_0 exp((mu + sigma^2 / 2) T)
+        assert!(output.paths.iter().all(|&y| y > 0.0));
+        assert_approx_equal!(
+            output.terminal_values().to_vec().mean(),
+            100.0 * 0.05_f64.exp(),
+            1.5
+        );
+    }
+
+    #[test]
+    fn test_shifted() {
+        let shifted = BrownianMotion::new().shifted(|t| 2.0 * t);
+
+        assert_approx_equal!(shifted.drift(1.0, 0.5), 2.0, 1e-6);
+        assert_approx_equal!(shifted.diffusion(1.0, 0.5), 1.0, 1e-12);
+
+        let output = shifted.euler_maruyama(1.0, 0.0, 1.0, 100, 5000, true);
+        assert!(output.iter().all(|path| path[0] == 1.0));
+        assert_approx_equal!(output.terminal_values().to_vec().mean(), 3.0, 0.05);
+    }
+
+    #[test]
+    fn test_time_changed() {
+        // Brownian motion on the clock 4t has variance 4t.
+        let fast = BrownianMotion::new().time_changed(|t| 4.0 * t);
+
+        assert_approx_equal!(fast.diffusion(0.0, 0.3), 2.0, 1e-6);
+
+        let output = fast.euler_maruyama(0.0, 0.0, 1.0, 100, 5000, true);
+        assert_approx_equal!(output.terminal_values().to_vec().variance(), 4.0, 0.3);
+    }
+
+    #[test]
+    fn test_sum() {
+        let process = sum(
+            ArithmeticBrownianMotion::new(1.0, 0.3),
+            ArithmeticBrownianMotion::new(-0.5, 0.4),
+        );
+
+        assert_approx_equal!(process.drift(0.0, 0.0), 0.5, 1e-12);
+        assert_approx_equal!(process.diffusion(0.0, 0.0), 0.5, 1e-12);
+
+        let output = process.euler_maruyama(1.0, 0.0, 1.0, 50, 5000, true);
+        let X_T = output.terminal_values().to_vec();
+
+        assert!(output.iter().all(|path| path[0] == 1.0));
+        assert_approx_equal!(X_T.mean(), 1.5, 0.05);
+        assert_approx_equal!(X_T.variance(), 0.25, 0.03);
+    }
+
+    #[test]
+    fn test_nested_combinators() {
+        let process = sum(BrownianMotion::new(), BrownianMotion::new())
+            .shifted(|_| 1.0)
+            .exponential();
+
+        let output = process.euler_maruyama(1.0, 0.0, 1.0, 10, 10, false);
+        assert!(output.iter().all(|path| path[0] == 1.0));
+    }
+
+    #[test]
+    fn test_invalid_inputs_are_errors() {
+        let config = SimulationConfig::new(false);
+        let exp = BrownianMotion::new().exponential();
+        let reversed = BrownianMotion::new().time_changed(|t| 1.0 - t);
+
+        assert!(exp
+            .try_simulate_with_config(1.0, 0.0, 1.0, 10, 10, &config)
+            .is_ok());
+        assert!(exp
+            .try_simulate_with_config(0.0, 0.0, 1.0, 10, 10, &config)
+            .is_err());
+        assert!(exp
+            .shifted(|t| t + 1.0)
+            .try_simulate_with_config(1.0, 0.0, 1.0, 10, 10, &config)
+            .is_err());
+        assert!(reversed
+            .try_simulate_with_config(0.0, 0.0, 1.0, 10, 10, &config)
+            .is_err());
+    }
+}
@@ -0,0 +1,150 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::error::{ensure, expect_valid, RustQuantError};
+use crate::stochastics::*;
+
+/// Struct containing the Constant Elasticity of Variance (CEV) process
+/// parameters.
+///
+/// $$
+/// dX(t) = \mu X(t) dt + \sigma X(t)^\beta dW(t)
+/// $$
+#[derive(Debug)]
+pub struct ConstantElasticityOfVariance {
+    /// The drift ($\mu$).
+    pub mu: f64,
+
+    /// The volatility scale ($\sigma$).
+    pub sigma: f64,
+
+    /// Elasticity parameter ($\beta$). $\beta = 1$ is geometric Brownian
+    /// motion and $\beta = 1/2$ has a square-root diffusion.
+    pub beta: f64,
+
+    /// Boundary behaviour at zero used by the simulation.
+    pub boundary: BoundaryScheme,
+}
+
+impl ConstantElasticityOfVariance {
+    /// Create a new CEV process (with full truncation at zero).
+    pub fn new(mu: f64, sigma: f64, beta: f64) -> Self {
+        Self::with_boundary(mu, sigma, beta, BoundaryScheme::default())
+    }
+
+    /// Create a new CEV process with a choice of boundary behaviour at zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sigma` or `beta` is negative.
+    pub fn with_boundary(mu: f64, sigma: f64, beta: f64, boundary: BoundaryScheme) -> Self {
+        expect_valid(Self::try_with_boundary(mu, sigma, beta, boundary))
+    }
+
+    /// Create a new CEV process (with full truncation at zero), or an error
+    /// if `sigma` or `beta` is negative.
+    pub fn try_new(mu: f64, sigma: f64, beta: f64) -> Result<Self, RustQuantError> {
+        Self::try_with_boundary(mu, sigma, beta, BoundaryScheme::default())
+    }
+
+    /// Create a new CEV process with a choice of boundary behaviour at
+    /// zero, or an error if `sigma` or `beta` is negative.
+    pub fn try_with_boundary(
+        mu: f64,
+        sigma: f64,
+        beta: f64,
+        boundary: BoundaryScheme,
+    ) -> Result<Self, RustQuantError> {
+        ensure(sigma >= 0.0, "sigma must be non-negative")?;
+        ensure(beta >= 0.0, "beta must be non-negative")?;
+
+        Ok(Self {
+            mu,
+            sigma,
+            beta,
+            boundary,
+        })
+    }
+}
+
+impl StochasticProcess for ConstantElasticityOfVariance {
+    fn drift(&self, x: f64, _t: f64) -> f64 {
+        self.mu * x
+    }
+
+    fn diffusion(&self, x: f64, _t: f64) -> f64 {
+        self.sigma * x.max(0.0).powf(self.beta)
+    }
+
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        None
+    }
+
+    /// Euler-Maruyama scheme with the process' boundary handling at zero.
+    fn simulate_with_config(
+        &self,
+        x_0: f64,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        config: &SimulationConfig,
+    ) -> Trajectories {
+        generate_trajectories(t_0, t_n, n_steps, m_paths, config, |times, rng, path| {
+            self.boundary.simulate_path(self, x_0, times, rng, path)
+        })
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_cev {
+    use super::*;
+    use crate::statistics::*;
+
+    #[test]
+    fn test_cev_non_negative() {
+        for boundary in [
+            BoundaryScheme::Absorption,
+            BoundaryScheme::Reflection,
+            BoundaryScheme::FullTruncation,
+        ] {
+            let cev = ConstantElasticityOfVariance::with_boundary(0.0, 2.0, 0.3, boundary);
+            let output = cev.euler_maruyama(1.0, 0.0, 1.0, 200, 500, true);
+
+            assert!(output.paths.iter().all(|x| x.is_finite() && *x >= 0.0));
+        }
+    }
+
+    #[test]
+    fn test_cev_martingale() {
+        // With absorption at zero the CEV process (beta < 1) is a true martingale.
+        let cev =
+            ConstantElasticityOfVariance::with_boundary(0.0, 0.5, 0.5, BoundaryScheme::Absorption);
+        let output = cev.euler_maruyama(1.0, 0.0, 1.0, 500, 5000, true);
+
+        assert_approx_equal!(output.terminal_values().to_vec().mean(), 1.0, 0.03);
+    }
+
+    #[test]
+    fn test_cev_gbm_limit() {
+        // beta = 1 recovers geometric Brownian motion: E[X_T] = x_0 exp(mu T).
+        let cev = ConstantElasticityOfVariance::new(0.05, 0.2, 1.0);
+        let output = cev.euler_maruyama(100.0, 0.0, 1.0, 100, 5000, true);
+
+        assert_approx_equal!(
+            output.terminal_values().to_vec().mean(),
+            100.0 * 0.05_f64.exp(),
+            1.5
+        );
+    }
+}
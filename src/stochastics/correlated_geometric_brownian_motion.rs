@@ -0,0 +1,328 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Correlated geometric Brownian motions,
+//! $dS_i = \mu_i S_i dt + \sigma_i S_i dW_i$ with
+//! $d\langle W_i, W_j \rangle = \rho_{ij} dt$.
+//!
+//! Each step draws independent normals $z$ and correlates them with the
+//! Cholesky factor of the correlation matrix, $w = L z$. The log-prices are
+//! advanced exactly, so the time grid only needs the dates the payoff
+//! looks at (a single step for European payoffs).
+//!
+//! ```
+//! use RustQuant::stochastics::*;
+//! use nalgebra::DMatrix;
+//!
+//! let correlation = DMatrix::from_row_slice(2, 2, &[1.0, 0.6, 0.6, 1.0]);
+//! let gbm = CorrelatedGeometricBrownianMotion::new(vec![0.05, 0.03], vec![0.2, 0.3], correlation);
+//!
+//! let config = SimulationConfig::new(true).with_seed(7);
+//! let assets = gbm.simulate(&[100.0, 50.0], 0.0, 1.0, 12, 1000, &config);
+//!
+//! assert_eq!(assets.len(), 2);
+//! assert_eq!(assets[1].paths.dim(), (1000, 13));
+//! ```
+
+use crate::error::{ensure, expect_valid, RustQuantError};
+use crate::math::linalg::cholesky;
+use crate::stochastics::process::{fill_paths, time_grid};
+use crate::stochastics::{SimulationConfig, Trajectories};
+use nalgebra::{DMatrix, DVector};
+use ndarray::s;
+use rand::Rng;
+use rand_distr::StandardNormal;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Geometric Brownian motions driven by correlated Brownian motions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CorrelatedGeometricBrownianMotion {
+    /// Drift of each asset.
+    pub mu: Vec<f64>,
+
+    /// Volatility of each asset.
+    pub sigma: Vec<f64>,
+
+    /// Correlation matrix of the driving Brownian motions.
+    pub correlation: DMatrix<f64>,
+
+    // Lower Cholesky factor of the correlation matrix.
+    cholesky: DMatrix<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl CorrelatedGeometricBrownianMotion {
+    /// Create new correlated geometric Brownian motions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parameters are invalid (see [`Self::try_new`]).
+    pub fn new(mu: Vec<f64>, sigma: Vec<f64>, correlation: DMatrix<f64>) -> Self {
+        expect_valid(Self::try_new(mu, sigma, correlation))
+    }
+
+    /// Create new correlated geometric Brownian motions, or an error if the
+    /// dimensions differ, a volatility is negative, or the correlation
+    /// matrix does not have a unit diagonal or is not positive definite.
+    pub fn try_new(
+        mu: Vec<f64>,
+        sigma: Vec<f64>,
+        correlation: DMatrix<f64>,
+    ) -> Result<Self, RustQuantError> {
+        let d = mu.len();
+
+        ensure(d > 0, "At least one asset is needed.")?;
+        ensure(
+            sigma.len() == d && correlation.nrows() == d && correlation.ncols() == d,
+            "mu, sigma and the correlation matrix must have the same dimension.",
+        )?;
+        ensure(
+            sigma.iter().all(|s| *s >= 0.0),
+            "sigma must be non-negative",
+        )?;
+        ensure(
+            correlation
+                .diagonal()
+                .iter()
+                .all(|c| (c - 1.0).abs() < 1e-12),
+            "The correlation matrix must have a unit diagonal.",
+        )?;
+
+        let cholesky = cholesky(&correlation)?;
+
+        Ok(Self {
+            mu,
+            sigma,
+            correlation,
+            cholesky,
+        })
+    }
+
+    /// Number of assets.
+    pub fn dimension(&self) -> usize {
+        self.mu.len()
+    }
+
+    /// Simulates `m_paths` joint paths from `x_0` on `n_steps` equal steps
+    /// from `t_0` to `t_n`, returning the trajectories of each asset.
+    ///
+    /// Path `i` of every asset comes from the same draw, so the assets'
+    /// `i`-th paths belong together.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the inputs are invalid (see [`Self::try_simulate`]).
+    pub fn simulate(
+        &self,
+        x_0: &[f64],
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        config: &SimulationConfig,
+    ) -> Vec<Trajectories> {
+        expect_valid(self.try_simulate(x_0, t_0, t_n, n_steps, m_paths, config))
+    }
+
+    /// Simulates joint paths as [`Self::simulate`] does, or returns an error
+    /// if `x_0` does not have one price per asset, `t_0 >= t_n`, or
+    /// `n_steps` is zero.
+    pub fn try_simulate(
+        &self,
+        x_0: &[f64],
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        config: &SimulationConfig,
+    ) -> Result<Vec<Trajectories>, RustQuantError> {
+        ensure(
+            x_0.len() == self.dimension(),
+            "One initial price per asset.",
+        )?;
+        ensure(t_0 < t_n, "The time grid must satisfy t_0 < t_n.")?;
+        ensure(n_steps > 0, "The number of steps must be positive.")?;
+
+        let d = self.dimension();
+        let times = time_grid(t_0, t_n, n_steps);
+        let n_times = times.len();
+        let dt = (t_n - t_0) / n_steps as f64;
+
+        let drift = (0..d)
+            .map(|j| (self.mu[j] - 0.5 * self.sigma[j].powi(2)) * dt)
+            .collect::<Vec<_>>();
+        let scale = self.sigma.iter().map(|s| s * dt.sqrt()).collect::<Vec<_>>();
+
+        // Each row holds all assets' paths one after another.
+        let paths = fill_paths(d * n_times, m_paths, config, |i, path| {
+            let mut rng = config.seed.rng(i);
+            let mut x = x_0.to_vec();
+
+            for (j, x_j) in x.iter().enumerate() {
+                path[j * n_times] = *x_j;
+            }
+
+            for k in 1..n_times {
+                let z = DVector::from_fn(d, |_, _| rng.sample::<f64, _>(StandardNormal));
+                let w = &self.cholesky * z;
+
+                for j in 0..d {
+                    x[j] *= (drift[j] + scale[j] * w[j]).exp();
+                    path[j * n_times + k] = x[j];
+                }
+            }
+        });
+
+        Ok((0..d)
+            .map(|j| {
+                let asset = paths.slice(s![.., j * n_times..(j + 1) * n_times]);
+
+                Trajectories::new(times.clone(), asset.to_owned())
+            })
+            .collect())
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_correlated_gbm {
+    use super::*;
+    use crate::statistics::*;
+
+    #[test]
+    fn test_moments_and_correlation() {
+        let correlation = DMatrix::from_row_slice(
+            3,
+            3,
+            &[
+                1.0, 0.7, -0.3, //
+                0.7, 1.0, 0.0, //
+                -0.3, 0.0, 1.0,
+            ],
+        );
+        let gbm = CorrelatedGeometricBrownianMotion::new(
+            vec![0.05, 0.0, -0.02],
+            vec![0.2, 0.3, 0.1],
+            correlation.clone(),
+        );
+
+        let config = SimulationConfig::new(true).with_seed(5);
+        let assets = gbm.simulate(&[100.0, 50.0, 10.0], 0.0, 2.0, 4, 40_000, &config);
+
+        // E[S_T] = S_0 exp(mu T).
+        for (j, s_0) in [100.0, 50.0, 10.0].iter().enumerate() {
+            let mean = assets[j].terminal_values().to_vec().mean();
+            assert_approx_equal!(mean / (s_0 * (gbm.mu[j] * 2.0).exp()), 1.0, 0.01);
+        }
+
+        // Correlation of the log-returns.
+        let log_returns = |j: usize| {
+            assets[j]
+                .terminal_values()
+                .iter()
+                .map(|s| s.ln())
+                .collect::<Vec<_>>()
+        };
+        let (x, y, z) = (log_returns(0), log_returns(1), log_returns(2));
+
+        assert_approx_equal!(x.correlation(&y), 0.7, 0.02);
+        assert_approx_equal!(x.correlation(&z), -0.3, 0.02);
+        assert_approx_equal!(y.correlation(&z), 0.0, 0.02);
+    }
+
+    #[test]
+    fn test_serial_and_parallel_agree() {
+        let gbm = CorrelatedGeometricBrownianMotion::new(
+            vec![0.01, 0.02],
+            vec![0.2, 0.25],
+            DMatrix::from_row_slice(2, 2, &[1.0, 0.5, 0.5, 1.0]),
+        );
+
+        let serial = gbm.simulate(
+            &[1.0, 2.0],
+            0.0,
+            1.0,
+            3,
+            50,
+            &SimulationConfig::new(false).with_seed(3),
+        );
+        let parallel = gbm.simulate(
+            &[1.0, 2.0],
+            0.0,
+            1.0,
+            3,
+            50,
+            &SimulationConfig::new(true).with_seed(3),
+        );
+
+        assert_eq!(serial[0].paths, parallel[0].paths);
+        assert_eq!(serial[1].paths, parallel[1].paths);
+        assert_eq!(serial[1].paths[[7, 0]], 2.0);
+    }
+
+    #[test]
+    fn test_try_new() {
+        let identity = DMatrix::identity(2, 2);
+
+        assert!(CorrelatedGeometricBrownianMotion::try_new(
+            vec![0.0; 2],
+            vec![0.2; 2],
+            identity.clone()
+        )
+        .is_ok());
+        assert!(CorrelatedGeometricBrownianMotion::try_new(
+            vec![0.0; 3],
+            vec![0.2; 2],
+            identity.clone()
+        )
+        .is_err());
+        assert!(CorrelatedGeometricBrownianMotion::try_new(
+            vec![0.0; 2],
+            vec![0.2, -0.1],
+            identity
+        )
+        .is_err());
+
+        let invalid = DMatrix::from_row_slice(2, 2, &[1.0, 1.5, 1.5, 1.0]);
+        assert!(matches!(
+            CorrelatedGeometricBrownianMotion::try_new(vec![0.0; 2], vec![0.2; 2], invalid),
+            Err(RustQuantError::Linalg(_))
+        ));
+    }
+
+    #[test]
+    fn test_try_simulate() {
+        let gbm = CorrelatedGeometricBrownianMotion::new(
+            vec![0.0; 2],
+            vec![0.2; 2],
+            DMatrix::identity(2, 2),
+        );
+        let config = SimulationConfig::new(false).with_seed(1);
+
+        assert!(gbm
+            .try_simulate(&[1.0, 1.0], 0.0, 1.0, 4, 10, &config)
+            .is_ok());
+        assert!(gbm.try_simulate(&[1.0], 0.0, 1.0, 4, 10, &config).is_err());
+        assert!(gbm
+            .try_simulate(&[1.0, 1.0], 1.0, 1.0, 4, 10, &config)
+            .is_err());
+        assert!(gbm
+            .try_simulate(&[1.0, 1.0], 0.0, 1.0, 0, 10, &config)
+            .is_err());
+    }
+}
@@ -7,9 +7,11 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+use crate::error::{ensure, expect_valid, RustQuantError};
+use crate::math::sample_noncentral_chi_squared;
 use crate::stochastics::*;
 
-/// Struct containing the Ornstein-Uhlenbeck process parameters.
+/// Struct containing the Cox-Ingersoll-Ross process parameters.
 #[derive(Debug)]
 pub struct CoxIngersollRoss {
     /// The long-run mean ($\mu$).
@@ -21,13 +23,125 @@ pub struct CoxIngersollRoss {
     /// Mean reversion parameter ($\theta$).
     /// Defines the speed at which the process reverts to the long-run mean.
     pub theta: f64,
+
+    /// Boundary behaviour at zero used by the simulation.
+    pub boundary: BoundaryScheme,
 }
 
 impl CoxIngersollRoss {
-    /// Create a new Cox-Ingersoll-Ross process.
+    /// Create a new Cox-Ingersoll-Ross process (with full truncation at zero).
     pub fn new(mu: f64, sigma: f64, theta: f64) -> Self {
-        assert!(sigma >= 0.0);
-        Self { mu, sigma, theta }
+        Self::with_boundary(mu, sigma, theta, BoundaryScheme::default())
+    }
+
+    /// Create a new Cox-Ingersoll-Ross process with a choice of boundary
+    /// behaviour at zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sigma` is negative.
+    pub fn with_boundary(mu: f64, sigma: f64, theta: f64, boundary: BoundaryScheme) -> Self {
+        expect_valid(Self::try_with_boundary(mu, sigma, theta, boundary))
+    }
+
+    /// Create a new Cox-Ingersoll-Ross process (with full truncation at
+    /// zero), or an error if `sigma` is negative.
+    pub fn try_new(mu: f64, sigma: f64, theta: f64) -> Result<Self, RustQuantError> {
+        Self::try_with_boundary(mu, sigma, theta, BoundaryScheme::default())
+    }
+
+    /// Create a new Cox-Ingersoll-Ross process with a choice of boundary
+    /// behaviour at zero, or an error if `sigma` is negative.
+    pub fn try_with_boundary(
+        mu: f64,
+        sigma: f64,
+        theta: f64,
+        boundary: BoundaryScheme,
+    ) -> Result<Self, RustQuantError> {
+        ensure(sigma >= 0.0, "sigma must be non-negative")?;
+
+        Ok(Self {
+            mu,
+            sigma,
+            theta,
+            boundary,
+        })
+    }
+
+    /// Whether the Feller condition $2 \theta \mu \geq \sigma^2$ holds,
+    /// in which case zero is never reached.
+    pub fn feller_condition(&self) -> bool {
+        2.0 * self.theta * self.mu >= self.sigma * self.sigma
+    }
+
+    /// Simulates the process from its exact transition law, a scaled
+    /// non-central chi-squared distribution, so the paths are exact on the
+    /// time grid and never negative (the boundary scheme is not used).
+    ///
+    /// Over a step $\Delta$, $X_{t + \Delta} = c \chi'^2_d(X_t e^{-\theta \Delta} / c)$
+    /// with $c = \sigma^2 (1 - e^{-\theta \Delta}) / (4 \theta)$ and
+    /// $d = 4 \theta \mu / \sigma^2$.
+    ///
+    /// # Arguments:
+    /// * `x_0` - The process' initial value at `t_0`.
+    /// * `t_0` - The initial time point.
+    /// * `t_n` - The terminal time point.
+    /// * `n_steps` - The number of time steps between `t_0` and `t_n`.
+    /// * `m_paths` - How many process trajectories to simulate.
+    /// * `config` - The simulation settings.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parameters or inputs are invalid (see
+    /// [`CoxIngersollRoss::try_simulate_exact`]).
+    pub fn simulate_exact(
+        &self,
+        x_0: f64,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        config: &SimulationConfig,
+    ) -> Trajectories {
+        expect_valid(self.try_simulate_exact(x_0, t_0, t_n, n_steps, m_paths, config))
+    }
+
+    /// Simulates the process from its exact transition law as
+    /// [`CoxIngersollRoss::simulate_exact`] does, or returns an error unless
+    /// `sigma > 0`, `theta * mu > 0`, `x_0 >= 0` and `t_0 < t_n`.
+    pub fn try_simulate_exact(
+        &self,
+        x_0: f64,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        config: &SimulationConfig,
+    ) -> Result<Trajectories, RustQuantError> {
+        ensure(self.sigma > 0.0, "sigma must be positive")?;
+        ensure(self.theta * self.mu > 0.0, "theta * mu must be positive")?;
+        ensure(x_0 >= 0.0, "The initial value must be non-negative.")?;
+        ensure(t_0 < t_n, "The time grid must satisfy t_0 < t_n.")?;
+
+        let dof = 4.0 * self.theta * self.mu / (self.sigma * self.sigma);
+
+        Ok(generate_trajectories(
+            t_0,
+            t_n,
+            n_steps,
+            m_paths,
+            config,
+            |times, rng, path| {
+                path[0] = x_0;
+
+                for t in 0..n_steps {
+                    let decay = (-self.theta * (times[t + 1] - times[t])).exp();
+                    let c = self.sigma * self.sigma * (1.0 - decay) / (4.0 * self.theta);
+
+                    path[t + 1] = c * sample_noncentral_chi_squared(rng, dof, path[t] * decay / c);
+                }
+            },
+        ))
     }
 }
 
@@ -43,6 +157,21 @@ impl StochasticProcess for CoxIngersollRoss {
     fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
         None
     }
+
+    /// Euler-Maruyama scheme with the process' boundary handling at zero.
+    fn simulate_with_config(
+        &self,
+        x_0: f64,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        config: &SimulationConfig,
+    ) -> Trajectories {
+        generate_trajectories(t_0, t_n, n_steps, m_paths, config, |times, rng, path| {
+            self.boundary.simulate_path(self, x_0, times, rng, path)
+        })
+    }
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -52,7 +181,30 @@ impl StochasticProcess for CoxIngersollRoss {
 #[cfg(test)]
 mod tests_cir {
     use super::*;
-    use crate::{assert_approx_equal, statistics::*};
+    use crate::statistics::*;
+
+    #[test]
+    fn test_cox_ingersoll_ross_exact_simulation() {
+        // Feller condition violated, so Euler schemes would hit zero often.
+        let cir = CoxIngersollRoss::new(0.04, 0.6, 1.5);
+        let config = SimulationConfig::new(true).with_seed(644);
+
+        let output = cir.simulate_exact(0.03, 0.0, 2.0, 4, 50_000, &config);
+        let X_T = output.terminal_values().to_vec();
+
+        let decay = (-1.5 * 2.0_f64).exp();
+        let mean = 0.03 * decay + 0.04 * (1.0 - decay);
+        let variance =
+            0.03 * 0.36 / 1.5 * (decay - decay * decay) + 0.04 * 0.36 / 3.0 * (1.0 - decay).powi(2);
+
+        assert!(X_T.iter().all(|x| *x >= 0.0));
+        assert_approx_equal!(X_T.mean(), mean, 0.001);
+        assert_approx_equal!(X_T.variance(), variance, 0.0004);
+
+        // Seeded runs are reproducible.
+        let again = cir.simulate_exact(0.03, 0.0, 2.0, 4, 100, &config);
+        assert_eq!(again.path(7), output.path(7));
+    }
 
     #[test]
     fn test_cox_ingersoll_ross() -> Result<(), Box<dyn std::error::Error>> {
@@ -61,11 +213,7 @@ mod tests_cir {
         let output = cir.euler_maruyama(10.0, 0.0, 0.5, 100, 100, false);
 
         // Test the distribution of the final values.
-        let X_T: Vec<f64> = output
-            .paths
-            .iter()
-            .filter_map(|v| v.last().cloned())
-            .collect();
+        let X_T = output.terminal_values().to_vec();
 
         let E_XT = X_T.mean();
         let V_XT = X_T.variance();
@@ -90,4 +238,64 @@ mod tests_cir {
 
         std::result::Result::Ok(())
     }
+
+    #[test]
+    fn test_cir_boundary_schemes() {
+        // The Feller condition is strongly violated, so zero is hit often.
+        for boundary in [
+            BoundaryScheme::Absorption,
+            BoundaryScheme::Reflection,
+            BoundaryScheme::FullTruncation,
+        ] {
+            let cir = CoxIngersollRoss::with_boundary(0.02, 0.5, 0.5, boundary);
+            assert!(!cir.feller_condition());
+
+            let output = cir.euler_maruyama(0.02, 0.0, 2.0, 200, 500, true);
+
+            assert!(output.paths.iter().all(|x| x.is_finite() && *x >= 0.0));
+        }
+    }
+
+    #[test]
+    fn test_cir_absorption() {
+        let cir = CoxIngersollRoss::with_boundary(0.0, 1.0, 0.5, BoundaryScheme::Absorption);
+        let output = cir.euler_maruyama(0.05, 0.0, 1.0, 100, 200, false);
+
+        // Once a path reaches zero it stays there.
+        for path in output.iter() {
+            if let Some(k) = path.iter().position(|&x| x == 0.0) {
+                assert!(path.iter().skip(k).all(|&x| x == 0.0));
+            }
+        }
+    }
+
+    #[test]
+    fn test_cir_full_truncation_mean() {
+        let (mu, sigma, theta) = (0.04, 0.6, 1.5_f64);
+        let cir = CoxIngersollRoss::new(mu, sigma, theta);
+        let output = cir.euler_maruyama(0.04, 0.0, 1.0, 250, 5000, true);
+
+        // Full truncation is consistent, so the mean stays close to the exact one.
+        let expected = 0.04 * (-theta).exp() + mu * (1.0 - (-theta).exp());
+        assert_approx_equal!(output.terminal_values().to_vec().mean(), expected, 0.005);
+    }
+
+    #[test]
+    fn test_cir_try_simulate_exact() {
+        let config = SimulationConfig::new(false).with_seed(1);
+        let cir = CoxIngersollRoss::new(0.04, 0.6, 1.5);
+
+        assert!(cir
+            .try_simulate_exact(0.04, 0.0, 1.0, 10, 10, &config)
+            .is_ok());
+        assert!(cir
+            .try_simulate_exact(-0.01, 0.0, 1.0, 10, 10, &config)
+            .is_err());
+        assert!(cir
+            .try_simulate_exact(0.04, 1.0, 0.0, 10, 10, &config)
+            .is_err());
+        assert!(CoxIngersollRoss::new(0.04, 0.0, 1.5)
+            .try_simulate_exact(0.04, 0.0, 1.0, 10, 10, &config)
+            .is_err());
+    }
 }
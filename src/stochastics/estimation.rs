@@ -0,0 +1,532 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Parameter estimation for stochastic processes from observed data.
+//!
+//! - Geometric Brownian Motion: maximum likelihood from log returns.
+//! - Ornstein-Uhlenbeck: exact AR(1) regression $X_{i+1} = a + b X_i + \varepsilon_i$.
+//! - Cox-Ingersoll-Ross: pseudo-maximum likelihood (Euler discretisation),
+//!   which reduces to a weighted least squares regression.
+//! - Hurst exponent: rescaled range (R/S) and detrended fluctuation analysis (DFA).
+//!
+//! All estimators take equally spaced observations with spacing `dt`
+//! (in years, if the process parameters are to be annualised).
+//! With the `data` feature enabled, the same estimators accept Polars
+//! `Series`, e.g. a column of the `DataFrame` returned by `YahooFinanceData`.
+
+use crate::error::{ensure, expect_valid, RustQuantError};
+use crate::stochastics::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// PROCESS PARAMETERS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Maximum likelihood estimate of a Geometric Brownian Motion from log returns.
+///
+/// $$
+/// \hat{\sigma}^2 = \frac{1}{n \Delta t} \sum_i (r_i - \bar{r})^2, \qquad
+/// \hat{\mu} = \frac{\bar{r}}{\Delta t} + \frac{\hat{\sigma}^2}{2}
+/// $$
+///
+/// # Panics
+///
+/// Panics if there are fewer than two returns or `dt` is not positive.
+pub fn estimate_gbm_from_log_returns(log_returns: &[f64], dt: f64) -> GeometricBrownianMotion {
+    expect_valid(try_estimate_gbm_from_log_returns(log_returns, dt))
+}
+
+/// Maximum likelihood estimate of a Geometric Brownian Motion from log
+/// returns, or an error if there are fewer than two returns or `dt` is not
+/// positive.
+pub fn try_estimate_gbm_from_log_returns(
+    log_returns: &[f64],
+    dt: f64,
+) -> Result<GeometricBrownianMotion, RustQuantError> {
+    ensure(log_returns.len() >= 2, "Need at least two returns.")?;
+    ensure(dt > 0.0, "The time step must be positive.")?;
+
+    let n = log_returns.len() as f64;
+    let mean = log_returns.iter().sum::<f64>() / n;
+    let variance = log_returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / n;
+
+    let sigma2 = variance / dt;
+
+    GeometricBrownianMotion::try_new(mean / dt + 0.5 * sigma2, sigma2.sqrt())
+}
+
+/// Maximum likelihood estimate of a Geometric Brownian Motion from prices.
+///
+/// # Panics
+///
+/// Panics if a price is not positive, there are fewer than three prices,
+/// or `dt` is not positive.
+pub fn estimate_gbm(prices: &[f64], dt: f64) -> GeometricBrownianMotion {
+    expect_valid(try_estimate_gbm(prices, dt))
+}
+
+/// Maximum likelihood estimate of a Geometric Brownian Motion from prices,
+/// or an error if a price is not positive, there are fewer than three
+/// prices, or `dt` is not positive.
+pub fn try_estimate_gbm(
+    prices: &[f64],
+    dt: f64,
+) -> Result<GeometricBrownianMotion, RustQuantError> {
+    ensure(prices.iter().all(|&p| p > 0.0), "Prices must be positive.")?;
+
+    let log_returns: Vec<f64> = prices.windows(2).map(|w| (w[1] / w[0]).ln()).collect();
+
+    try_estimate_gbm_from_log_returns(&log_returns, dt)
+}
+
+/// Estimates an Ornstein-Uhlenbeck process via the AR(1) regression
+/// $X_{i+1} = a + b X_i + \varepsilon_i$, which is exact for the OU
+/// transition density:
+///
+/// $$
+/// \hat{\theta} = -\frac{\ln b}{\Delta t}, \qquad
+/// \hat{\mu} = \frac{a}{1 - b}, \qquad
+/// \hat{\sigma} = \hat{\sigma}_\varepsilon \sqrt{\frac{2 \hat{\theta}}{1 - b^2}}
+/// $$
+///
+/// # Panics
+///
+/// Panics if there are fewer than three observations, `dt` is not
+/// positive, or the data are not mean-reverting (see
+/// [`try_estimate_ornstein_uhlenbeck`]).
+pub fn estimate_ornstein_uhlenbeck(x: &[f64], dt: f64) -> OrnsteinUhlenbeck {
+    expect_valid(try_estimate_ornstein_uhlenbeck(x, dt))
+}
+
+/// Estimates an Ornstein-Uhlenbeck process as
+/// [`estimate_ornstein_uhlenbeck`] does, or returns an error if there are
+/// fewer than three observations, `dt` is not positive, or the AR(1)
+/// coefficient is outside $(0, 1)$.
+pub fn try_estimate_ornstein_uhlenbeck(
+    x: &[f64],
+    dt: f64,
+) -> Result<OrnsteinUhlenbeck, RustQuantError> {
+    ensure(x.len() >= 3, "Need at least three observations.")?;
+    ensure(dt > 0.0, "The time step must be positive.")?;
+
+    let (a, b, residuals) = ols_ar1(x);
+
+    ensure(
+        b > 0.0 && b < 1.0,
+        "AR(1) coefficient must be in (0, 1) for a mean-reverting process.",
+    )?;
+
+    let n = residuals.len() as f64;
+    let sigma_eps = (residuals.iter().map(|e| e * e).sum::<f64>() / n).sqrt();
+
+    let theta = -b.ln() / dt;
+    let mu = a / (1.0 - b);
+    let sigma = sigma_eps * (2.0 * theta / (1.0 - b * b)).sqrt();
+
+    OrnsteinUhlenbeck::try_new(mu, sigma, theta)
+}
+
+/// Pseudo-maximum likelihood estimate of a Cox-Ingersoll-Ross process.
+///
+/// The Euler discretisation divided by $\sqrt{X_i}$ gives the homoskedastic
+/// regression:
+///
+/// $$
+/// \frac{X_{i+1} - X_i}{\sqrt{X_i}} = \beta_1 \frac{\Delta t}{\sqrt{X_i}}
+///     + \beta_2 \Delta t \sqrt{X_i} + \sigma \sqrt{\Delta t} \varepsilon_i
+/// $$
+///
+/// with $\theta = -\beta_2$ and $\mu = \beta_1 / \theta$.
+///
+/// # Panics
+///
+/// Panics if there are fewer than three observations, an observation or
+/// `dt` is not positive, or the regression is singular.
+pub fn estimate_cox_ingersoll_ross(x: &[f64], dt: f64) -> CoxIngersollRoss {
+    expect_valid(try_estimate_cox_ingersoll_ross(x, dt))
+}
+
+/// Pseudo-maximum likelihood estimate of a Cox-Ingersoll-Ross process, or
+/// an error if there are fewer than three observations, an observation or
+/// `dt` is not positive, or the regression is singular.
+pub fn try_estimate_cox_ingersoll_ross(
+    x: &[f64],
+    dt: f64,
+) -> Result<CoxIngersollRoss, RustQuantError> {
+    ensure(x.len() >= 3, "Need at least three observations.")?;
+    ensure(dt > 0.0, "The time step must be positive.")?;
+    ensure(x.iter().all(|&v| v > 0.0), "Observations must be positive.")?;
+
+    let mut s11 = 0.0;
+    let mut s12 = 0.0;
+    let mut s22 = 0.0;
+    let mut s1y = 0.0;
+    let mut s2y = 0.0;
+
+    let rows: Vec<(f64, f64, f64)> = x
+        .windows(2)
+        .map(|w| {
+            let root = w[0].sqrt();
+            ((w[1] - w[0]) / root, dt / root, dt * root)
+        })
+        .collect();
+
+    for &(y, z1, z2) in &rows {
+        s11 += z1 * z1;
+        s12 += z1 * z2;
+        s22 += z2 * z2;
+        s1y += z1 * y;
+        s2y += z2 * y;
+    }
+
+    let det = s11 * s22 - s12 * s12;
+    ensure(det.abs() > f64::EPSILON, "Singular regression.")?;
+
+    let beta_1 = (s22 * s1y - s12 * s2y) / det;
+    let beta_2 = (s11 * s2y - s12 * s1y) / det;
+
+    let n = rows.len() as f64;
+    let rss = rows
+        .iter()
+        .map(|&(y, z1, z2)| (y - beta_1 * z1 - beta_2 * z2).powi(2))
+        .sum::<f64>();
+
+    let theta = -beta_2;
+    let mu = beta_1 / theta;
+    let sigma = (rss / n / dt).sqrt();
+
+    CoxIngersollRoss::try_new(mu, sigma, theta)
+}
+
+/// Ordinary least squares fit of $x_{i+1} = a + b x_i$.
+/// Returns `(a, b, residuals)`.
+fn ols_ar1(x: &[f64]) -> (f64, f64, Vec<f64>) {
+    let lagged = &x[..x.len() - 1];
+    let lead = &x[1..];
+
+    let (b, a) = ols_slope_intercept(lagged, lead);
+
+    let residuals = lagged
+        .iter()
+        .zip(lead)
+        .map(|(x0, x1)| x1 - a - b * x0)
+        .collect();
+
+    (a, b, residuals)
+}
+
+/// Ordinary least squares slope and intercept of `y` on `x`.
+fn ols_slope_intercept(x: &[f64], y: &[f64]) -> (f64, f64) {
+    let n = x.len() as f64;
+    let x_bar = x.iter().sum::<f64>() / n;
+    let y_bar = y.iter().sum::<f64>() / n;
+
+    let sxy = x
+        .iter()
+        .zip(y)
+        .map(|(xi, yi)| (xi - x_bar) * (yi - y_bar))
+        .sum::<f64>();
+    let sxx = x.iter().map(|xi| (xi - x_bar).powi(2)).sum::<f64>();
+
+    let slope = sxy / sxx;
+
+    (slope, y_bar - slope * x_bar)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// HURST EXPONENT
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Window sizes used by the Hurst estimators (powers of two, at least 8
+/// and at most half the series length).
+fn hurst_window_sizes(n: usize) -> Result<Vec<usize>, RustQuantError> {
+    ensure(
+        n >= 32,
+        "Need at least 32 observations to estimate the Hurst exponent.",
+    )?;
+
+    Ok(std::iter::successors(Some(8_usize), |&w| Some(w * 2))
+        .take_while(|&w| w <= n / 2)
+        .collect())
+}
+
+/// Hurst exponent via the rescaled range (R/S) analysis.
+///
+/// The input should be the increments (e.g. returns) of the process.
+/// For each window size $n$ the series is split into blocks, and the
+/// average of $R/S$ over the blocks is computed, where $R$ is the range
+/// of the cumulative mean-adjusted sum and $S$ the standard deviation.
+/// The Hurst exponent is the slope of $\ln(R/S)$ against $\ln n$.
+///
+/// # Panics
+///
+/// Panics if there are fewer than 32 increments.
+pub fn hurst_rescaled_range(increments: &[f64]) -> f64 {
+    expect_valid(try_hurst_rescaled_range(increments))
+}
+
+/// Hurst exponent via the rescaled range (R/S) analysis, or an error if
+/// there are fewer than 32 increments.
+pub fn try_hurst_rescaled_range(increments: &[f64]) -> Result<f64, RustQuantError> {
+    let windows = hurst_window_sizes(increments.len())?;
+
+    let mut log_n = Vec::with_capacity(windows.len());
+    let mut log_rs = Vec::with_capacity(windows.len());
+
+    for &w in &windows {
+        let rs: Vec<f64> = increments
+            .chunks_exact(w)
+            .filter_map(|block| {
+                let mean = block.iter().sum::<f64>() / w as f64;
+                let std = (block.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / w as f64).sqrt();
+
+                let mut cumulative = 0.0;
+                let (mut min, mut max) = (0.0_f64, 0.0_f64);
+                for x in block {
+                    cumulative += x - mean;
+                    min = min.min(cumulative);
+                    max = max.max(cumulative);
+                }
+
+                (std > 0.0).then(|| (max - min) / std)
+            })
+            .collect();
+
+        if !rs.is_empty() {
+            log_n.push((w as f64).ln());
+            log_rs.push((rs.iter().sum::<f64>() / rs.len() as f64).ln());
+        }
+    }
+
+    Ok(ols_slope_intercept(&log_n, &log_rs).0)
+}
+
+/// Hurst exponent via detrended fluctuation analysis (DFA-1).
+///
+/// The input should be the increments (e.g. returns) of the process.
+/// The profile $Y_k = \sum_{i \leq k} (x_i - \bar{x})$ is split into blocks
+/// of size $n$, a linear trend is removed from each block, and the
+/// fluctuation $F(n)$ is the root mean square of the residuals. The Hurst
+/// exponent is the slope of $\ln F(n)$ against $\ln n$.
+///
+/// # Panics
+///
+/// Panics if there are fewer than 32 increments.
+pub fn hurst_dfa(increments: &[f64]) -> f64 {
+    expect_valid(try_hurst_dfa(increments))
+}
+
+/// Hurst exponent via detrended fluctuation analysis (DFA-1), or an error
+/// if there are fewer than 32 increments.
+pub fn try_hurst_dfa(increments: &[f64]) -> Result<f64, RustQuantError> {
+    let windows = hurst_window_sizes(increments.len())?;
+
+    let mean = increments.iter().sum::<f64>() / increments.len() as f64;
+    let profile: Vec<f64> = increments
+        .iter()
+        .scan(0.0, |acc, x| {
+            *acc += x - mean;
+            Some(*acc)
+        })
+        .collect();
+
+    let mut log_n = Vec::with_capacity(windows.len());
+    let mut log_f = Vec::with_capacity(windows.len());
+
+    for &w in &windows {
+        let t: Vec<f64> = (0..w).map(|i| i as f64).collect();
+
+        let squared: Vec<f64> = profile
+            .chunks_exact(w)
+            .map(|block| {
+                let (slope, intercept) = ols_slope_intercept(&t, block);
+                block
+                    .iter()
+                    .zip(&t)
+                    .map(|(y, ti)| (y - intercept - slope * ti).powi(2))
+                    .sum::<f64>()
+                    / w as f64
+            })
+            .collect();
+
+        let fluctuation = (squared.iter().sum::<f64>() / squared.len() as f64).sqrt();
+
+        if fluctuation > 0.0 {
+            log_n.push((w as f64).ln());
+            log_f.push(fluctuation.ln());
+        }
+    }
+
+    Ok(ols_slope_intercept(&log_n, &log_f).0)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// POLARS SERIES
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Estimators taking Polars `Series` (e.g. from the `data` module).
+/// Null values (such as the first row of a returns column) are dropped,
+/// and invalid data are reported as a compute error.
+#[cfg(feature = "data")]
+pub mod series {
+    use super::*;
+    use polars::prelude::*;
+
+    /// Converts a numeric `Series` to a vector, dropping nulls.
+    pub fn series_to_vec(series: &Series) -> PolarsResult<Vec<f64>> {
+        let series = series.cast(&DataType::Float64)?;
+
+        Ok(series.f64()?.into_iter().flatten().collect())
+    }
+
+    /// Reports an estimation error as a Polars compute error.
+    fn compute_error(error: RustQuantError) -> PolarsError {
+        PolarsError::ComputeError(error.to_string().into())
+    }
+
+    /// Geometric Brownian Motion from a series of log returns.
+    pub fn estimate_gbm_from_series(
+        log_returns: &Series,
+        dt: f64,
+    ) -> PolarsResult<GeometricBrownianMotion> {
+        try_estimate_gbm_from_log_returns(&series_to_vec(log_returns)?, dt).map_err(compute_error)
+    }
+
+    /// Ornstein-Uhlenbeck process from a series of levels.
+    pub fn estimate_ornstein_uhlenbeck_from_series(
+        levels: &Series,
+        dt: f64,
+    ) -> PolarsResult<OrnsteinUhlenbeck> {
+        try_estimate_ornstein_uhlenbeck(&series_to_vec(levels)?, dt).map_err(compute_error)
+    }
+
+    /// Cox-Ingersoll-Ross process from a series of levels.
+    pub fn estimate_cox_ingersoll_ross_from_series(
+        levels: &Series,
+        dt: f64,
+    ) -> PolarsResult<CoxIngersollRoss> {
+        try_estimate_cox_ingersoll_ross(&series_to_vec(levels)?, dt).map_err(compute_error)
+    }
+
+    /// Hurst exponent (R/S and DFA) from a series of returns.
+    pub fn estimate_hurst_from_series(returns: &Series) -> PolarsResult<(f64, f64)> {
+        let returns = series_to_vec(returns)?;
+
+        Ok((
+            try_hurst_rescaled_range(&returns).map_err(compute_error)?,
+            try_hurst_dfa(&returns).map_err(compute_error)?,
+        ))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_estimation {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use rand_distr::StandardNormal;
+
+    fn normals(n: usize, seed: u64) -> Vec<f64> {
+        StdRng::seed_from_u64(seed)
+            .sample_iter::<f64, _>(StandardNormal)
+            .take(n)
+            .collect()
+    }
+
+    #[test]
+    fn test_estimate_gbm() {
+        let (mu, sigma, dt) = (0.1, 0.2, 1.0_f64 / 252.0);
+
+        let mut prices = vec![100.0];
+        for z in normals(50_000, 1) {
+            let last = *prices.last().unwrap();
+            prices.push(last * ((mu - 0.5 * sigma * sigma) * dt + sigma * dt.sqrt() * z).exp());
+        }
+
+        let gbm = estimate_gbm(&prices, dt);
+
+        assert_approx_equal!(gbm.sigma, sigma, 0.005);
+        assert_approx_equal!(gbm.mu, mu, 0.1);
+    }
+
+    #[test]
+    fn test_estimate_ornstein_uhlenbeck() {
+        let (mu, sigma, theta, dt) = (1.0, 0.3, 2.0_f64, 0.01);
+
+        // Exact OU transition.
+        let b = (-theta * dt).exp();
+        let sd = sigma * ((1.0 - b * b) / (2.0 * theta)).sqrt();
+
+        let mut x = vec![0.5];
+        for z in normals(50_000, 2) {
+            let last = *x.last().unwrap();
+            x.push(mu + (last - mu) * b + sd * z);
+        }
+
+        let ou = estimate_ornstein_uhlenbeck(&x, dt);
+
+        assert_approx_equal!(ou.mu, mu, 0.05);
+        assert_approx_equal!(ou.theta, theta, 0.3);
+        assert_approx_equal!(ou.sigma, sigma, 0.01);
+    }
+
+    #[test]
+    fn test_estimate_cox_ingersoll_ross() {
+        let (mu, sigma, theta, dt) = (0.05, 0.1, 1.5, 1.0_f64 / 252.0);
+
+        let mut x = vec![0.04];
+        for z in normals(100_000, 3) {
+            let last: f64 = *x.last().unwrap();
+            let next = last + theta * (mu - last) * dt + sigma * (last * dt).sqrt() * z;
+            x.push(next.max(1e-8));
+        }
+
+        let cir = estimate_cox_ingersoll_ross(&x, dt);
+
+        assert_approx_equal!(cir.mu, mu, 0.01);
+        assert_approx_equal!(cir.theta, theta, 0.5);
+        assert_approx_equal!(cir.sigma, sigma, 0.005);
+    }
+
+    #[test]
+    fn test_hurst_white_noise() {
+        let z = normals(4096, 4);
+
+        assert_approx_equal!(hurst_rescaled_range(&z), 0.5, 0.1);
+        assert_approx_equal!(hurst_dfa(&z), 0.5, 0.1);
+    }
+
+    #[test]
+    fn test_hurst_persistent() {
+        // Random walk levels are far more persistent than white noise.
+        let walk: Vec<f64> = normals(4096, 5)
+            .iter()
+            .scan(0.0, |acc, z| {
+                *acc += z;
+                Some(*acc)
+            })
+            .collect();
+
+        assert!(hurst_dfa(&walk) > 1.0);
+        assert!(hurst_rescaled_range(&walk) > 0.8);
+    }
+
+    #[test]
+    fn test_invalid_data_is_an_error() {
+        assert!(try_estimate_gbm(&[100.0, 101.0], 0.01).is_err());
+        assert!(try_estimate_gbm(&[100.0, -1.0, 101.0], 0.01).is_err());
+        assert!(try_estimate_gbm_from_log_returns(&[0.01, 0.02], 0.0).is_err());
+        assert!(try_estimate_ornstein_uhlenbeck(&[1.0, 2.0, 3.0, 4.0], 0.01).is_err());
+        assert!(try_estimate_cox_ingersoll_ross(&[0.05, 0.0, 0.04], 0.01).is_err());
+        assert!(try_hurst_rescaled_range(&[0.0; 16]).is_err());
+        assert!(try_hurst_dfa(&[0.0; 16]).is_err());
+    }
+}
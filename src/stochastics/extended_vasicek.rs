@@ -51,7 +51,7 @@ impl StochasticProcess for ExtendedVasicek {
 #[cfg(test)]
 mod tests_extended_vasicek {
     use super::*;
-    use crate::{assert_approx_equal, statistics::*};
+    use crate::statistics::*;
 
     fn alpha_t(_t: f64) -> f64 {
         2.0
@@ -70,11 +70,7 @@ mod tests_extended_vasicek {
         let output = ev.euler_maruyama(10.0, 0.0, 1.0, 150, 1000, false);
 
         // Test the distribution of the final values.
-        let X_T: Vec<f64> = output
-            .paths
-            .iter()
-            .filter_map(|v| v.last().cloned())
-            .collect();
+        let X_T = output.terminal_values().to_vec();
 
         let E_XT = X_T.mean();
         // Note these tests are identical to the Hull-White
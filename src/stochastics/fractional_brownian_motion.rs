@@ -7,19 +7,75 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+//! Fractional Brownian motion and fractional Gaussian noise (fGN).
+//!
+//! Fractional Gaussian noise is the stationary increment process of
+//! fractional Brownian motion, with autocovariance
+//!
+//! $$
+//! \gamma(k) = \frac{1}{2} \left( |k + 1|^{2H} - 2 |k|^{2H} + |k - 1|^{2H} \right)
+//! $$
+//!
+//! Three exact generation methods are available:
+//!
+//! - Cholesky: $O(n^3)$ setup and $O(n^2)$ per sample.
+//! - Hosking (Durbin-Levinson recursion): no setup and $O(n^2)$ per sample.
+//! - Davies-Harte (circulant embedding): $O(n \log n)$ setup and per sample.
+
+use crate::error::{ensure, expect_valid, RustQuantError};
+use crate::math::FftPlan;
 use crate::stochastics::*;
 use nalgebra::{DMatrix, DVector, Dim, Dyn, RowDVector};
+use num_complex::Complex;
 use rand::Rng;
-#[cfg(feature = "seedable")]
-use rand::{rngs::StdRng, SeedableRng};
 use rand_distr::StandardNormal;
-use rayon::prelude::*;
+
+/// Method used to generate fractional Gaussian noise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FractionalNoiseMethod {
+    /// Cholesky factorisation of the autocovariance matrix.
+    Cholesky,
+
+    /// Hosking's method (Durbin-Levinson recursion).
+    Hosking,
+
+    /// Davies-Harte method (circulant embedding and FFT).
+    #[default]
+    DaviesHarte,
+}
 
 /// Struct containing the Fractional Brownian Motion parameters.
 #[derive(Debug)]
 pub struct FractionalBrownianMotion {
     /// Hurst parameter of the process.
     pub hurst: f64,
+
+    /// Method used to generate the fractional Gaussian noise.
+    pub method: FractionalNoiseMethod,
+}
+
+/// Fractional Gaussian noise generator for a fixed number of increments.
+///
+/// The set-up (Cholesky factor or circulant eigenvalues) is done once,
+/// so many independent samples can be drawn cheaply.
+#[derive(Debug, Clone)]
+pub struct FractionalGaussianNoise {
+    /// Hurst parameter of the noise.
+    pub hurst: f64,
+
+    /// Number of increments per sample.
+    pub n: usize,
+
+    /// Precomputed state of the chosen method.
+    generator: NoiseGenerator,
+}
+
+/// Precomputed state of each generation method.
+#[derive(Debug, Clone)]
+enum NoiseGenerator {
+    Cholesky(DMatrix<f64>),
+    Hosking(Vec<f64>),
+    DaviesHarte(Vec<f64>, FftPlan),
 }
 
 impl Default for FractionalBrownianMotion {
@@ -30,27 +86,47 @@ impl Default for FractionalBrownianMotion {
 
 impl FractionalBrownianMotion {
     /// Create a new Fractional Brownian Motion process.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hurst` is outside $(0, 1)$.
     pub fn new(hurst: f64) -> Self {
-        assert!((0.0..=1.0).contains(&hurst));
-
-        Self { hurst }
+        Self::with_method(hurst, FractionalNoiseMethod::default())
     }
 
-    /// Autocovariance function (ACF).
-    fn acf_vector(&self, n: usize) -> RowDVector<f64> {
-        let h = self.hurst;
-
-        let mut v = RowDVector::<f64>::zeros(n);
-        v[0] = 1.0;
+    /// Create a new Fractional Brownian Motion process with a choice of
+    /// noise generation method.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hurst` is outside $(0, 1)$.
+    pub fn with_method(hurst: f64, method: FractionalNoiseMethod) -> Self {
+        expect_valid(Self::try_with_method(hurst, method))
+    }
 
-        for i in 1..n {
-            let idx = i as f64;
+    /// Create a new Fractional Brownian Motion process, or an error if
+    /// `hurst` is outside $(0, 1)$.
+    pub fn try_new(hurst: f64) -> Result<Self, RustQuantError> {
+        Self::try_with_method(hurst, FractionalNoiseMethod::default())
+    }
 
-            v[i] = 0.5
-                * ((idx + 1.0).powf(2.0 * h) - 2.0 * idx.powf(2.0 * h) + (idx - 1.0).powf(2.0 * h))
-        }
+    /// Create a new Fractional Brownian Motion process with a choice of
+    /// noise generation method, or an error if `hurst` is outside $(0, 1)$.
+    pub fn try_with_method(
+        hurst: f64,
+        method: FractionalNoiseMethod,
+    ) -> Result<Self, RustQuantError> {
+        ensure(
+            hurst > 0.0 && hurst < 1.0,
+            "hurst must be strictly between 0 and 1",
+        )?;
+
+        Ok(Self { hurst, method })
+    }
 
-        v
+    /// Autocovariance function (ACF).
+    fn acf_vector(&self, n: usize) -> RowDVector<f64> {
+        RowDVector::<f64>::from_iterator(n, (0..n).map(|k| autocovariance(self.hurst, k)))
     }
 
     /// Autocovariance matrix.
@@ -74,115 +150,226 @@ impl FractionalBrownianMotion {
 
     /// Fractional Gaussian noise.
     pub fn fgn_cholesky(&self, n: usize, t_n: f64) -> RowDVector<f64> {
-        let acf_sqrt = self.acf_matrix_sqrt(n);
-        let noise = rand::thread_rng()
-            .sample_iter::<f64, StandardNormal>(StandardNormal)
-            .take(n)
-            .collect();
-        let noise = DVector::<f64>::from_vec(noise);
-
-        (acf_sqrt * noise).transpose() * (1.0 * t_n / n as f64).powf(self.hurst)
-    }
-
-    #[cfg(feature = "seedable")]
-    fn seedable_fgn_cholesky(&self, n: usize, t_n: f64, seed: u64) -> RowDVector<f64> {
-        let acf_sqrt = self.acf_matrix_sqrt(n);
-        let rng = StdRng::seed_from_u64(seed);
-        let noise = rng
-            .sample_iter::<f64, StandardNormal>(StandardNormal)
-            .take(n)
-            .collect();
-        let noise = DVector::<f64>::from_vec(noise);
+        let noise = FractionalGaussianNoise::new(self.hurst, n, FractionalNoiseMethod::Cholesky);
 
-        (acf_sqrt * noise).transpose() * (1.0 * t_n / n as f64).powf(self.hurst)
+        RowDVector::from_vec(noise.sample(t_n / n as f64, &mut rand::thread_rng()))
     }
-}
 
-impl StochasticProcess for FractionalBrownianMotion {
-    fn drift(&self, _x: f64, _t: f64) -> f64 {
-        0.0
+    /// Fractional Gaussian noise generator for `n` increments, using the
+    /// process' generation method.
+    pub fn noise(&self, n: usize) -> FractionalGaussianNoise {
+        FractionalGaussianNoise::new(self.hurst, n, self.method)
     }
 
-    fn diffusion(&self, _x: f64, _t: f64) -> f64 {
-        1.0
-    }
-
-    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
-        None
-    }
-
-    fn euler_maruyama(
+    #[allow(clippy::too_many_arguments)]
+    fn generate(
         &self,
         x_0: f64,
         t_0: f64,
         t_n: f64,
         n_steps: usize,
         m_paths: usize,
-        parallel: bool,
+        config: &SimulationConfig,
     ) -> Trajectories {
-        assert!(t_0 < t_n);
-
         let dt: f64 = (t_n - t_0) / (n_steps as f64);
+        let noise = self.noise(n_steps);
 
-        // Initialise empty paths and fill in the time points.
-        let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
-        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
-
-        let path_generator = |path: &mut Vec<f64>| {
-            let fgn = self.fgn_cholesky(n_steps, t_n);
+        generate_trajectories(t_0, t_n, n_steps, m_paths, config, |_, rng, path| {
+            let fgn = noise.sample(dt, rng);
 
+            path[0] = x_0;
             for t in 0..n_steps {
-                path[t + 1] = path[t]
-                    + self.drift(path[t], times[t]) * dt
-                    + self.diffusion(path[t], times[t]) * fgn[t];
+                path[t + 1] = path[t] + fgn[t];
             }
+        })
+    }
+}
+
+impl FractionalGaussianNoise {
+    /// Create a new fractional Gaussian noise generator for `n` increments.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hurst` is outside $(0, 1)$ or `n` is zero.
+    pub fn new(hurst: f64, n: usize, method: FractionalNoiseMethod) -> Self {
+        expect_valid(Self::try_new(hurst, n, method))
+    }
+
+    /// Create a new fractional Gaussian noise generator for `n` increments,
+    /// or an error if `hurst` is outside $(0, 1)$ or `n` is zero.
+    pub fn try_new(
+        hurst: f64,
+        n: usize,
+        method: FractionalNoiseMethod,
+    ) -> Result<Self, RustQuantError> {
+        ensure(
+            hurst > 0.0 && hurst < 1.0,
+            "hurst must be strictly between 0 and 1",
+        )?;
+        ensure(n > 0, "the number of increments must be positive")?;
+
+        let generator = match method {
+            FractionalNoiseMethod::Cholesky => NoiseGenerator::Cholesky(
+                FractionalBrownianMotion::with_method(hurst, method).acf_matrix_sqrt(n),
+            ),
+            FractionalNoiseMethod::Hosking => {
+                NoiseGenerator::Hosking((0..=n).map(|k| autocovariance(hurst, k)).collect())
+            }
+            FractionalNoiseMethod::DaviesHarte => {
+                let plan = FftPlan::new(2 * n.next_power_of_two());
+                NoiseGenerator::DaviesHarte(circulant_eigenvalues(hurst, n, &plan)?, plan)
+            }
+        };
+
+        Ok(Self {
+            hurst,
+            n,
+            generator,
+        })
+    }
+
+    /// Samples `n` fractional Gaussian noise increments over time steps of
+    /// length `dt`, i.e. with variance $dt^{2H}$.
+    pub fn sample<R: Rng + ?Sized>(&self, dt: f64, rng: &mut R) -> Vec<f64> {
+        let scale = dt.powf(self.hurst);
+
+        let noise = match &self.generator {
+            NoiseGenerator::Cholesky(l) => {
+                let z = DVector::<f64>::from_iterator(
+                    self.n,
+                    (0..self.n).map(|_| rng.sample::<f64, _>(StandardNormal)),
+                );
+                (l * z).data.as_vec().clone()
+            }
+            NoiseGenerator::Hosking(gamma) => hosking(gamma, self.n, rng),
+            NoiseGenerator::DaviesHarte(lambda, plan) => davies_harte(lambda, plan, self.n, rng),
         };
 
-        if parallel {
-            paths.par_iter_mut().for_each(path_generator);
-        } else {
-            paths.iter_mut().for_each(path_generator);
+        noise.into_iter().map(|x| x * scale).collect()
+    }
+}
+
+/// Autocovariance of unit-step fractional Gaussian noise at lag `k`.
+fn autocovariance(hurst: f64, k: usize) -> f64 {
+    let k = k as f64;
+    let h2 = 2.0 * hurst;
+
+    0.5 * ((k + 1.0).powf(h2) - 2.0 * k.powf(h2) + (k - 1.0).abs().powf(h2))
+}
+
+/// Eigenvalues of the circulant embedding of the fGN autocovariance, of
+/// size `2m` where `m` is the smallest power of two `>= n`, or an error if
+/// the embedding is not non-negative definite.
+fn circulant_eigenvalues(hurst: f64, n: usize, plan: &FftPlan) -> Result<Vec<f64>, RustQuantError> {
+    let m = n.next_power_of_two();
+
+    // First row: gamma(0), ..., gamma(m), gamma(m - 1), ..., gamma(1).
+    let row: Vec<f64> = (0..2 * m)
+        .map(|j| autocovariance(hurst, j.min(2 * m - j)))
+        .collect();
+
+    plan.forward_real(&row)
+        .into_iter()
+        .map(|lambda| {
+            ensure(
+                lambda.re > -1e-8,
+                "Circulant embedding is not non-negative definite.",
+            )?;
+            Ok(lambda.re.max(0.0))
+        })
+        .collect()
+}
+
+/// Davies-Harte sample of unit-step fGN from the circulant eigenvalues.
+fn davies_harte<R: Rng + ?Sized>(
+    lambda: &[f64],
+    plan: &FftPlan,
+    n: usize,
+    rng: &mut R,
+) -> Vec<f64> {
+    let size = lambda.len();
+    let m = size / 2;
+
+    let mut w = vec![Complex::new(0.0, 0.0); size];
+
+    w[0] = Complex::new(
+        (lambda[0] / size as f64).sqrt() * rng.sample::<f64, _>(StandardNormal),
+        0.0,
+    );
+    w[m] = Complex::new(
+        (lambda[m] / size as f64).sqrt() * rng.sample::<f64, _>(StandardNormal),
+        0.0,
+    );
+
+    for k in 1..m {
+        let scale = (lambda[k] / (2.0 * size as f64)).sqrt();
+        let z = Complex::new(
+            rng.sample::<f64, _>(StandardNormal),
+            rng.sample::<f64, _>(StandardNormal),
+        );
+
+        w[k] = z * scale;
+        w[size - k] = w[k].conj();
+    }
+
+    plan.forward(&mut w);
+
+    w.into_iter().take(n).map(|x| x.re).collect()
+}
+
+/// Hosking sample of unit-step fGN, using the Durbin-Levinson recursion
+/// for the conditional mean and variance of each increment.
+fn hosking<R: Rng + ?Sized>(gamma: &[f64], n: usize, rng: &mut R) -> Vec<f64> {
+    let mut x = Vec::with_capacity(n);
+    let mut phi: Vec<f64> = Vec::with_capacity(n);
+    let mut previous: Vec<f64> = Vec::with_capacity(n);
+    let mut v = gamma[0];
+
+    x.push(v.sqrt() * rng.sample::<f64, _>(StandardNormal));
+
+    for k in 1..n {
+        // Partial autocorrelation phi_kk.
+        let num = gamma[k] - (0..k - 1).map(|j| phi[j] * gamma[k - 1 - j]).sum::<f64>();
+        let phi_kk = num / v;
+
+        previous.clone_from(&phi);
+        for j in 0..k - 1 {
+            phi[j] = previous[j] - phi_kk * previous[k - 2 - j];
         }
+        phi.push(phi_kk);
 
-        Trajectories { times, paths }
+        v *= 1.0 - phi_kk * phi_kk;
+
+        let mean: f64 = (0..k).map(|j| phi[j] * x[k - 1 - j]).sum();
+        x.push(mean + v.sqrt() * rng.sample::<f64, _>(StandardNormal));
     }
 
-    #[cfg(feature = "seedable")]
-    fn seedable_euler_maruyama(
+    x
+}
+
+impl StochasticProcess for FractionalBrownianMotion {
+    fn drift(&self, _x: f64, _t: f64) -> f64 {
+        0.0
+    }
+
+    fn diffusion(&self, _x: f64, _t: f64) -> f64 {
+        1.0
+    }
+
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        None
+    }
+
+    fn simulate_with_config(
         &self,
         x_0: f64,
         t_0: f64,
         t_n: f64,
         n_steps: usize,
         m_paths: usize,
-        parallel: bool,
-        seed: u64,
+        config: &SimulationConfig,
     ) -> Trajectories {
-        assert!(t_0 < t_n);
-
-        let dt: f64 = (t_n - t_0) / (n_steps as f64);
-
-        // Initialise empty paths and fill in the time points.
-        let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
-        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
-
-        let path_generator = |path: &mut Vec<f64>| {
-            let fgn = self.seedable_fgn_cholesky(n_steps, t_n, seed);
-
-            for t in 0..n_steps {
-                path[t + 1] = path[t]
-                    + self.drift(path[t], times[t]) * dt
-                    + self.diffusion(path[t], times[t]) * fgn[t];
-            }
-        };
-
-        if parallel {
-            paths.par_iter_mut().for_each(path_generator);
-        } else {
-            paths.iter_mut().for_each(path_generator);
-        }
-
-        Trajectories { times, paths }
+        self.generate(x_0, t_0, t_n, n_steps, m_paths, config)
     }
 }
 
@@ -195,7 +382,7 @@ mod test_fractional_brownian_motion {
     // use std::time::Instant;
 
     use super::*;
-    use crate::{assert_approx_equal, statistics::*};
+    use crate::statistics::*;
 
     #[test]
     fn test_chol() {
@@ -227,17 +414,79 @@ mod test_fractional_brownian_motion {
         // let output_parallel = (&bm).euler_maruyama(10.0, 0.0, 0.5, 100, 10, true);
 
         // Test the distribution of the final values.
-        let X_T: Vec<f64> = output_serial
-            .paths
-            .iter()
-            .filter_map(|v| v.last().cloned())
-            .collect();
+        let X_T = output_serial.terminal_values().to_vec();
 
         // E[X_T] = 0
         assert_approx_equal!(X_T.mean(), 0.0, 0.5);
-        // V[X_T] = T
-        assert_approx_equal!(X_T.variance(), 0.5, 0.5);
+        // V[X_T] = T^{2H}
+        assert_approx_equal!(X_T.variance(), 0.5_f64.powf(1.4), 0.05);
 
         std::result::Result::Ok(())
     }
+
+    /// Sample variance and lag-one autocovariance of unit-step fGN.
+    fn fgn_moments(method: FractionalNoiseMethod, hurst: f64) -> (f64, f64) {
+        let noise = FractionalGaussianNoise::new(hurst, 50, method);
+        let mut rng = rand::thread_rng();
+
+        let (mut var, mut cov) = (0.0, 0.0);
+        let m = 4000;
+
+        for _ in 0..m {
+            let x = noise.sample(1.0, &mut rng);
+            var += x[20] * x[20];
+            cov += x[20] * x[21];
+        }
+
+        (var / m as f64, cov / m as f64)
+    }
+
+    #[test]
+    fn test_fgn_methods() {
+        let hurst = 0.8;
+        let expected_cov = 0.5 * (2.0_f64.powf(1.6) - 2.0);
+
+        for method in [
+            FractionalNoiseMethod::Cholesky,
+            FractionalNoiseMethod::Hosking,
+            FractionalNoiseMethod::DaviesHarte,
+        ] {
+            let (var, cov) = fgn_moments(method, hurst);
+
+            assert_approx_equal!(var, 1.0, 0.1);
+            assert_approx_equal!(cov, expected_cov, 0.1);
+        }
+    }
+
+    #[test]
+    fn test_fgn_scaling() {
+        // With H = 1/2 the noise is white: increments have variance dt.
+        let noise = FractionalGaussianNoise::new(0.5, 1000, FractionalNoiseMethod::DaviesHarte);
+        let x = noise.sample(0.01, &mut rand::thread_rng());
+
+        assert_eq!(x.len(), 1000);
+        assert_approx_equal!(x.variance(), 0.01, 0.002);
+    }
+
+    #[test]
+    fn test_fbm_distinct_paths() {
+        let fbm = FractionalBrownianMotion::with_method(0.3, FractionalNoiseMethod::Hosking);
+        let output = fbm.euler_maruyama(0.0, 0.0, 1.0, 64, 2, false);
+
+        assert_ne!(output.path(0), output.path(1));
+    }
+
+    // The noise covariance degenerates at the endpoints, so the processes
+    // reject them rather than failing on their first simulation.
+    #[test]
+    #[should_panic(expected = "hurst must be strictly between 0 and 1")]
+    fn test_new_rejects_hurst_of_one() {
+        FractionalBrownianMotion::new(1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "hurst must be strictly between 0 and 1")]
+    fn test_fou_new_rejects_hurst_of_zero() {
+        FractionalOrnsteinUhlenbeck::new(0.15, 0.45, 0.01, 0.0);
+    }
 }
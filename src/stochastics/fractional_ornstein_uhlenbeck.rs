@@ -7,8 +7,8 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+use crate::error::{ensure, expect_valid, RustQuantError};
 use crate::stochastics::*;
-use rayon::prelude::*;
 
 /// Struct containing the Ornstein-Uhlenbeck process parameters.
 pub struct FractionalOrnsteinUhlenbeck {
@@ -29,15 +29,56 @@ pub struct FractionalOrnsteinUhlenbeck {
 
 impl FractionalOrnsteinUhlenbeck {
     /// Create a new Ornstein-Uhlenbeck process.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sigma` is negative or `hurst` is outside $(0, 1)$.
     pub fn new(mu: f64, sigma: f64, theta: f64, hurst: f64) -> Self {
-        assert!(sigma >= 0.0);
-        assert!((0.0..=1.0).contains(&hurst));
-        Self {
+        expect_valid(Self::try_new(mu, sigma, theta, hurst))
+    }
+
+    /// Create a new Ornstein-Uhlenbeck process, or an error if `sigma` is
+    /// negative or `hurst` is outside $(0, 1)$.
+    pub fn try_new(mu: f64, sigma: f64, theta: f64, hurst: f64) -> Result<Self, RustQuantError> {
+        ensure(sigma >= 0.0, "sigma must be non-negative")?;
+        ensure(
+            hurst > 0.0 && hurst < 1.0,
+            "hurst must be strictly between 0 and 1",
+        )?;
+
+        Ok(Self {
             mu,
             sigma,
             theta,
             hurst,
-        }
+        })
+    }
+
+    /// Euler-Maruyama scheme driven by fractional Gaussian noise, with
+    /// fresh noise for each path.
+    #[allow(clippy::too_many_arguments)]
+    fn generate(
+        &self,
+        x_0: f64,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        config: &SimulationConfig,
+    ) -> Trajectories {
+        let dt: f64 = (t_n - t_0) / (n_steps as f64);
+        let noise = FractionalBrownianMotion::new(self.hurst).noise(n_steps);
+
+        generate_trajectories(t_0, t_n, n_steps, m_paths, config, |times, rng, path| {
+            let fgn = noise.sample(dt, rng);
+
+            path[0] = x_0;
+            for t in 0..n_steps {
+                path[t + 1] = path[t]
+                    + self.drift(path[t], times[t]) * dt
+                    + self.diffusion(path[t], times[t]) * fgn[t];
+            }
+        })
     }
 }
 
@@ -54,39 +95,16 @@ impl StochasticProcess for FractionalOrnsteinUhlenbeck {
         None
     }
 
-    fn euler_maruyama(
+    fn simulate_with_config(
         &self,
         x_0: f64,
         t_0: f64,
         t_n: f64,
         n_steps: usize,
         m_paths: usize,
-        parallel: bool,
+        config: &SimulationConfig,
     ) -> Trajectories {
-        let fbm = FractionalBrownianMotion::new(self.hurst);
-        let fgn = FractionalBrownianMotion::fgn_cholesky(&fbm, n_steps, t_n);
-
-        let dt: f64 = (t_n - t_0) / (n_steps as f64);
-
-        // Initialise empty paths and fill in the time points.
-        let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
-        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
-
-        let path_generator = |path: &mut Vec<f64>| {
-            for t in 0..n_steps {
-                path[t + 1] = path[t]
-                    + self.drift(path[t], times[t]) * dt
-                    + self.diffusion(path[t], times[t]) * fgn[t];
-            }
-        };
-
-        if parallel {
-            paths.par_iter_mut().for_each(path_generator);
-        } else {
-            paths.iter_mut().for_each(path_generator);
-        }
-
-        Trajectories { times, paths }
+        self.generate(x_0, t_0, t_n, n_steps, m_paths, config)
     }
 }
 
@@ -108,4 +126,14 @@ mod tests_ornstein_uhlenbeck {
 
         std::result::Result::Ok(())
     }
+
+    #[test]
+    fn test_fractional_ornstein_uhlenbeck_distinct_paths() {
+        let fou = FractionalOrnsteinUhlenbeck::new(0.15, 0.45, 0.01, 0.7);
+        let output = fou.euler_maruyama(10.0, 0.0, 0.5, 100, 10, false);
+
+        // Each path gets its own noise.
+        assert_ne!(output.path(0), output.path(1));
+        assert!(output.iter().all(|path| path[0] == 10.0));
+    }
 }
@@ -7,37 +7,52 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+use crate::autodiff::Scalar;
+use crate::error::{ensure, expect_valid, RustQuantError};
 use crate::stochastics::*;
 
 /// Struct containing the Geometric Brownian Motion parameters.
-pub struct GeometricBrownianMotion {
+///
+/// Generic over the parameter type, e.g. `Variable` for pathwise Greeks.
+pub struct GeometricBrownianMotion<T = f64> {
     /// The drift ($\mu$) in percentage.
-    pub mu: f64,
+    pub mu: T,
 
     /// The volatility ($\sigma$) in percentage.
-    pub sigma: f64,
+    pub sigma: T,
 }
 
-impl GeometricBrownianMotion {
+impl<T: Scalar> GeometricBrownianMotion<T> {
     /// Create a new Geometric Brownian Motion process.
-    pub fn new(mu: f64, sigma: f64) -> Self {
-        assert!(sigma >= 0.0);
-        Self { mu, sigma }
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sigma` is negative.
+    pub fn new(mu: T, sigma: T) -> Self {
+        expect_valid(Self::try_new(mu, sigma))
+    }
+
+    /// Create a new Geometric Brownian Motion process, or an error if
+    /// `sigma` is negative.
+    pub fn try_new(mu: T, sigma: T) -> Result<Self, RustQuantError> {
+        ensure(sigma.value() >= 0.0, "sigma must be non-negative")?;
+
+        Ok(Self { mu, sigma })
     }
 }
 
-impl StochasticProcess for GeometricBrownianMotion {
-    fn drift(&self, x: f64, _t: f64) -> f64 {
+impl<T: Scalar> StochasticProcess<T> for GeometricBrownianMotion<T> {
+    fn drift(&self, x: T, _t: f64) -> T {
         // mu X_t dt
         self.mu * x
     }
 
-    fn diffusion(&self, x: f64, _t: f64) -> f64 {
+    fn diffusion(&self, x: T, _t: f64) -> T {
         // sigma X_t dW_t
         self.sigma * x
     }
 
-    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+    fn jump(&self, _x: T, _t: f64) -> Option<T> {
         None
     }
 }
@@ -49,20 +64,17 @@ impl StochasticProcess for GeometricBrownianMotion {
 #[cfg(test)]
 mod tests_gbm {
     use super::*;
-    use crate::{assert_approx_equal, statistics::*};
+    use crate::statistics::*;
 
     #[test]
     fn test_geometric_brownian_motion() -> Result<(), Box<dyn std::error::Error>> {
         let gbm = GeometricBrownianMotion::new(0.05, 0.9);
 
-        let output = gbm.euler_maruyama(10.0, 0.0, 0.5, 125, 10000, false);
+        let config = SimulationConfig::new(true).with_seed(48);
+        let output = gbm.simulate_with_config(10.0, 0.0, 0.5, 125, 10000, &config);
 
         // Test the distribution of the final values.
-        let X_T: Vec<f64> = output
-            .paths
-            .iter()
-            .filter_map(|v| v.last().cloned())
-            .collect();
+        let X_T = output.terminal_values().to_vec();
 
         let E_XT = X_T.mean();
         let V_XT = X_T.variance();
@@ -82,4 +94,19 @@ mod tests_gbm {
 
         std::result::Result::Ok(())
     }
+
+    #[test]
+    fn test_try_new() {
+        assert!(GeometricBrownianMotion::try_new(0.05, 0.2).is_ok());
+        assert!(matches!(
+            GeometricBrownianMotion::try_new(0.05, -0.2),
+            Err(RustQuantError::InvalidParameter { .. })
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "sigma must be non-negative")]
+    fn test_new_negative_sigma() {
+        GeometricBrownianMotion::new(0.05, -0.2);
+    }
 }
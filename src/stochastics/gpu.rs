@@ -0,0 +1,678 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! GPU path generation (requires the `gpu` feature).
+//!
+//! Wrapping a supported process in `GpuAccelerated` offloads the
+//! Euler-Maruyama path generation to the GPU through `wgpu` (Vulkan, Metal,
+//! DX12 or OpenGL). The wrapper is itself a `StochasticProcess`, so the API
+//! is identical to the CPU generator. If no GPU device is present, the
+//! wrapped process' own CPU implementation is used instead.
+//!
+//! Supported processes (see `GpuProcess`):
+//!
+//! - `GeometricBrownianMotion`
+//! - `OrnsteinUhlenbeck`
+//! - `Heston`
+//!
+//! The GPU computes in single precision (`f32`) with a counter-based
+//! random number generator, so results are statistically equivalent to,
+//! but not bitwise identical with, the CPU results. Simulations that do not
+//! fit in a single GPU buffer are split into batches of paths.
+//!
+//! ```rust,ignore
+//! use RustQuant::stochastics::*;
+//!
+//! let gbm = GpuAccelerated::new(GeometricBrownianMotion::new(0.05, 0.2));
+//! let output = gbm.euler_maruyama(100.0, 0.0, 1.0, 252, 1_000_000, true);
+//! ```
+
+use crate::stochastics::*;
+use ndarray::{Array2, ShapeBuilder};
+use std::sync::OnceLock;
+use wgpu::util::DeviceExt;
+
+/// Workgroup size of the compute shader (one path per invocation).
+const WORKGROUP_SIZE: u32 = 64;
+
+/// Maximum number of workgroups per dispatch dimension.
+const MAX_WORKGROUPS: u32 = 65_535;
+
+/// Compute shader: each invocation simulates one path and writes it
+/// column-major (time-major) into the output buffer.
+const SHADER: &str = r#"
+struct Params {
+    model: u32,
+    n_steps: u32,
+    m_paths: u32,
+    path_offset: u32,
+    seed_lo: u32,
+    seed_hi: u32,
+    x_0: f32,
+    dt: f32,
+    p0: f32,
+    p1: f32,
+    p2: f32,
+    p3: f32,
+    p4: f32,
+    p5: f32,
+    _pad0: u32,
+    _pad1: u32,
+};
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read_write> paths: array<f32>;
+
+fn hash(x: u32) -> u32 {
+    var h = x * 747796405u + 2891336453u;
+    h = ((h >> ((h >> 28u) + 4u)) ^ h) * 277803737u;
+    return (h >> 22u) ^ h;
+}
+
+fn next_uniform(state: ptr<function, u32>) -> f32 {
+    *state = hash(*state);
+    return (f32(*state >> 8u) + 0.5) / 16777216.0;
+}
+
+fn next_normals(state: ptr<function, u32>) -> vec2<f32> {
+    let u1 = next_uniform(state);
+    let u2 = next_uniform(state);
+    let r = sqrt(-2.0 * log(u1));
+    let a = 6.283185307179586 * u2;
+    return vec2<f32>(r * cos(a), r * sin(a));
+}
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let i = id.x;
+    if (i >= params.m_paths) {
+        return;
+    }
+
+    var state = hash(params.seed_lo ^ hash(i + params.path_offset) ^ hash(params.seed_hi + 0x9e3779b9u));
+    let sqrt_dt = sqrt(params.dt);
+
+    var x = params.x_0;
+    var v = params.p1;
+    paths[i] = x;
+
+    for (var t = 0u; t < params.n_steps; t = t + 1u) {
+        let z = next_normals(&state);
+
+        switch params.model {
+            // Geometric Brownian motion: p0 = mu, p1 = sigma.
+            case 0u: {
+                x = x + params.p0 * x * params.dt + params.p1 * x * sqrt_dt * z.x;
+            }
+            // Ornstein-Uhlenbeck: p0 = mu, p1 = sigma, p2 = theta.
+            case 1u: {
+                x = x + params.p2 * (params.p0 - x) * params.dt + params.p1 * sqrt_dt * z.x;
+            }
+            // Heston: p0 = mu, p1 = v_0, p2 = kappa, p3 = theta, p4 = sigma, p5 = rho.
+            default: {
+                let v_plus = max(v, 0.0);
+                let dw_v = sqrt_dt * z.x;
+                let dw_s = params.p5 * dw_v + sqrt(1.0 - params.p5 * params.p5) * sqrt_dt * z.y;
+                x = x * exp((params.p0 - 0.5 * v_plus) * params.dt + sqrt(v_plus) * dw_s);
+                v = v + params.p2 * (params.p3 - v_plus) * params.dt + params.p4 * sqrt(v_plus) * dw_v;
+            }
+        }
+
+        paths[(t + 1u) * params.m_paths + i] = x;
+    }
+}
+"#;
+
+/// Model parameters passed to the GPU kernel.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GpuModel {
+    /// $dX_t = \mu X_t dt + \sigma X_t dW_t$
+    GeometricBrownianMotion {
+        /// Drift.
+        mu: f64,
+        /// Volatility.
+        sigma: f64,
+    },
+
+    /// $dX_t = \theta (\mu - X_t) dt + \sigma dW_t$
+    OrnsteinUhlenbeck {
+        /// Long-run mean.
+        mu: f64,
+        /// Volatility.
+        sigma: f64,
+        /// Mean reversion speed.
+        theta: f64,
+    },
+
+    /// Heston model (asset paths), see `Heston`.
+    Heston {
+        /// Drift of the asset.
+        mu: f64,
+        /// Initial variance.
+        v_0: f64,
+        /// Mean reversion speed of the variance.
+        kappa: f64,
+        /// Long-run variance.
+        theta: f64,
+        /// Volatility of volatility.
+        sigma: f64,
+        /// Correlation between the asset and variance drivers.
+        rho: f64,
+    },
+}
+
+/// Processes that can be simulated on the GPU.
+pub trait GpuProcess: StochasticProcess + Sync {
+    /// The kernel parameters of the process.
+    fn gpu_model(&self) -> GpuModel;
+}
+
+impl GpuProcess for GeometricBrownianMotion {
+    fn gpu_model(&self) -> GpuModel {
+        GpuModel::GeometricBrownianMotion {
+            mu: self.mu,
+            sigma: self.sigma,
+        }
+    }
+}
+
+impl GpuProcess for OrnsteinUhlenbeck {
+    fn gpu_model(&self) -> GpuModel {
+        GpuModel::OrnsteinUhlenbeck {
+            mu: self.mu,
+            sigma: self.sigma,
+            theta: self.theta,
+        }
+    }
+}
+
+impl GpuProcess for Heston {
+    fn gpu_model(&self) -> GpuModel {
+        GpuModel::Heston {
+            mu: self.mu,
+            v_0: self.v_0,
+            kappa: self.kappa,
+            theta: self.theta,
+            sigma: self.sigma,
+            rho: self.rho,
+        }
+    }
+}
+
+/// Uniform buffer layout matching `Params` in the shader.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct Params {
+    model: u32,
+    n_steps: u32,
+    m_paths: u32,
+    path_offset: u32,
+    seed_lo: u32,
+    seed_hi: u32,
+    x_0: f32,
+    dt: f32,
+    p: [f32; 6],
+    _pad: [u32; 2],
+}
+
+impl GpuModel {
+    /// Kernel model index and parameters.
+    fn kernel_parameters(&self) -> (u32, [f32; 6]) {
+        match *self {
+            GpuModel::GeometricBrownianMotion { mu, sigma } => {
+                (0, [mu as f32, sigma as f32, 0.0, 0.0, 0.0, 0.0])
+            }
+            GpuModel::OrnsteinUhlenbeck { mu, sigma, theta } => {
+                (1, [mu as f32, sigma as f32, theta as f32, 0.0, 0.0, 0.0])
+            }
+            GpuModel::Heston {
+                mu,
+                v_0,
+                kappa,
+                theta,
+                sigma,
+                rho,
+            } => (
+                2,
+                [
+                    mu as f32,
+                    v_0 as f32,
+                    kappa as f32,
+                    theta as f32,
+                    sigma as f32,
+                    rho as f32,
+                ],
+            ),
+        }
+    }
+}
+
+/// Handle to a GPU device with the compiled path generation kernel.
+pub struct GpuSimulator {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    pipeline: wgpu::ComputePipeline,
+    layout: wgpu::BindGroupLayout,
+    max_buffer_size: u64,
+}
+
+impl GpuSimulator {
+    /// Connects to the default GPU, returning `None` if no device is present.
+    pub fn new() -> Option<Self> {
+        pollster::block_on(Self::request())
+    }
+
+    async fn request() -> Option<Self> {
+        let instance = wgpu::Instance::default();
+
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::HighPerformance,
+                force_fallback_adapter: false,
+                compatible_surface: None,
+            })
+            .await?;
+
+        let (device, queue) = adapter
+            .request_device(
+                &wgpu::DeviceDescriptor {
+                    label: Some("RustQuant GPU simulator"),
+                    required_features: wgpu::Features::empty(),
+                    required_limits: adapter.limits(),
+                },
+                None,
+            )
+            .await
+            .ok()?;
+
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("path generation"),
+            source: wgpu::ShaderSource::Wgsl(SHADER.into()),
+        });
+
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("path generation"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::COMPUTE,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("path generation"),
+            bind_group_layouts: &[&layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("path generation"),
+            layout: Some(&pipeline_layout),
+            module: &module,
+            entry_point: "main",
+        });
+
+        let limits = device.limits();
+        let max_buffer_size = limits
+            .max_buffer_size
+            .min(limits.max_storage_buffer_binding_size as u64);
+
+        Some(Self {
+            device,
+            queue,
+            pipeline,
+            layout,
+            max_buffer_size,
+        })
+    }
+
+    /// Euler-Maruyama path generation on the GPU.
+    ///
+    /// # Arguments:
+    /// * `model` - The process parameters.
+    /// * `x_0` - The process' initial value at `t_0`.
+    /// * `t_0` - The initial time point.
+    /// * `t_n` - The terminal time point.
+    /// * `n_steps` - The number of time steps between `t_0` and `t_n`.
+    /// * `m_paths` - How many process trajectories to simulate.
+    /// * `seed` - The seed for the random number generator.
+    /// * `first_path` - Position of the first path in the whole run, so
+    ///   that a run simulated in chunks draws the same paths.
+    #[allow(clippy::too_many_arguments)]
+    pub fn euler_maruyama(
+        &self,
+        model: GpuModel,
+        x_0: f64,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        seed: u64,
+        first_path: u64,
+    ) -> Trajectories {
+        assert!(t_0 < t_n);
+
+        let times = time_grid(t_0, t_n, n_steps);
+        let n_times = times.len();
+
+        let path_bytes = (n_times * std::mem::size_of::<f32>()) as u64;
+        assert!(
+            path_bytes <= self.max_buffer_size,
+            "A single path does not fit in a GPU buffer."
+        );
+
+        let max_batch = (self.max_buffer_size / path_bytes)
+            .min((WORKGROUP_SIZE * MAX_WORKGROUPS) as u64) as usize;
+
+        // Column-major output, filled batch by batch.
+        let mut values = vec![0.0; n_times * m_paths];
+        let mut offset = 0;
+
+        while offset < m_paths {
+            let batch = max_batch.min(m_paths - offset);
+            let output = self.run_batch(
+                model,
+                x_0,
+                (t_n - t_0) / n_steps as f64,
+                n_steps,
+                batch,
+                first_path + offset as u64,
+                seed,
+            );
+
+            for t in 0..n_times {
+                let column = &mut values[t * m_paths + offset..t * m_paths + offset + batch];
+                for (x, &y) in column.iter_mut().zip(&output[t * batch..(t + 1) * batch]) {
+                    *x = y as f64;
+                }
+            }
+
+            offset += batch;
+        }
+
+        let paths = Array2::from_shape_vec((m_paths, n_times).f(), values)
+            .expect("Buffer length matches the path matrix shape.");
+
+        Trajectories { times, paths }
+    }
+
+    /// Simulates `batch` paths starting at global path index `offset`.
+    #[allow(clippy::too_many_arguments)]
+    fn run_batch(
+        &self,
+        model: GpuModel,
+        x_0: f64,
+        dt: f64,
+        n_steps: usize,
+        batch: usize,
+        offset: u64,
+        seed: u64,
+    ) -> Vec<f32> {
+        let (model, p) = model.kernel_parameters();
+
+        let params = Params {
+            model,
+            n_steps: n_steps as u32,
+            m_paths: batch as u32,
+            // The kernel's path index wraps at 2^32, with or without chunks.
+            path_offset: offset as u32,
+            seed_lo: seed as u32,
+            seed_hi: (seed >> 32) as u32,
+            x_0: x_0 as f32,
+            dt: dt as f32,
+            p,
+            _pad: [0; 2],
+        };
+
+        let size = ((n_steps + 1) * batch * std::mem::size_of::<f32>()) as u64;
+
+        let uniform = self
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("parameters"),
+                contents: bytemuck::bytes_of(&params),
+                usage: wgpu::BufferUsages::UNIFORM,
+            });
+
+        let storage = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("paths"),
+            size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+
+        let staging = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("staging"),
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("path generation"),
+            layout: &self.layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: uniform.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: storage.as_entire_binding(),
+                },
+            ],
+        });
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: Some("path generation"),
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups((batch as u32).div_ceil(WORKGROUP_SIZE), 1, 1);
+        }
+
+        encoder.copy_buffer_to_buffer(&storage, 0, &staging, 0, size);
+        self.queue.submit(Some(encoder.finish()));
+
+        let slice = staging.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        self.device.poll(wgpu::Maintain::Wait);
+
+        receiver
+            .recv()
+            .expect("GPU buffer mapping callback was dropped.")
+            .expect("Failed to map the GPU buffer.");
+
+        let output = bytemuck::cast_slice::<u8, f32>(&slice.get_mapped_range()).to_vec();
+        staging.unmap();
+
+        output
+    }
+}
+
+/// The shared GPU simulator, initialised on first use.
+fn simulator() -> Option<&'static GpuSimulator> {
+    static SIMULATOR: OnceLock<Option<GpuSimulator>> = OnceLock::new();
+
+    SIMULATOR.get_or_init(GpuSimulator::new).as_ref()
+}
+
+/// A process whose paths are generated on the GPU when one is available,
+/// falling back to the process' CPU implementation otherwise.
+#[derive(Debug, Clone)]
+pub struct GpuAccelerated<P: GpuProcess> {
+    /// The underlying process.
+    pub process: P,
+}
+
+impl<P: GpuProcess> GpuAccelerated<P> {
+    /// Wrap a process for GPU path generation.
+    pub fn new(process: P) -> Self {
+        Self { process }
+    }
+
+    /// Whether a GPU device is available (otherwise the CPU is used).
+    pub fn is_gpu_available() -> bool {
+        simulator().is_some()
+    }
+}
+
+impl<P: GpuProcess> StochasticProcess for GpuAccelerated<P> {
+    fn drift(&self, x: f64, t: f64) -> f64 {
+        self.process.drift(x, t)
+    }
+
+    fn diffusion(&self, x: f64, t: f64) -> f64 {
+        self.process.diffusion(x, t)
+    }
+
+    fn jump(&self, x: f64, t: f64) -> Option<f64> {
+        self.process.jump(x, t)
+    }
+
+    /// Generates the paths on the GPU (the thread settings only apply to
+    /// the CPU fallback). A `PerPath` seed makes GPU runs reproducible, and
+    /// `PerPathFrom` gives the paths of that run from `first` onwards.
+    fn simulate_with_config(
+        &self,
+        x_0: f64,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        config: &SimulationConfig,
+    ) -> Trajectories {
+        let (seed, first_path) = match config.seed {
+            SeedStrategy::Entropy => (rand::random(), 0),
+            SeedStrategy::PerPath(seed) => (seed, 0),
+            SeedStrategy::PerPathFrom { seed, first } => (seed, first),
+        };
+
+        match simulator() {
+            Some(gpu) => gpu.euler_maruyama(
+                self.process.gpu_model(),
+                x_0,
+                t_0,
+                t_n,
+                n_steps,
+                m_paths,
+                seed,
+                first_path,
+            ),
+            None => self
+                .process
+                .simulate_with_config(x_0, t_0, t_n, n_steps, m_paths, config),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_gpu {
+    use super::*;
+    use crate::statistics::*;
+
+    // These run on the GPU when one is present, and on the CPU fallback
+    // otherwise, so the results must agree in distribution either way.
+
+    #[test]
+    fn test_gpu_gbm() {
+        let gbm = GpuAccelerated::new(GeometricBrownianMotion::new(0.05, 0.2));
+        let output = gbm.euler_maruyama(100.0, 0.0, 1.0, 100, 20_000, true);
+
+        assert_eq!(output.paths.dim(), (20_000, 101));
+        assert!(output.iter().all(|path| path[0] == 100.0));
+        assert_approx_equal!(
+            output.terminal_values().to_vec().mean(),
+            100.0 * 0.05_f64.exp(),
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_gpu_ornstein_uhlenbeck() {
+        let ou = GpuAccelerated::new(OrnsteinUhlenbeck::new(1.0, 0.3, 2.0));
+        let output = ou.euler_maruyama(0.0, 0.0, 1.0, 100, 20_000, true);
+
+        // E[X_T] = mu (1 - exp(-theta T))
+        assert_approx_equal!(
+            output.terminal_values().to_vec().mean(),
+            1.0 - (-2.0_f64).exp(),
+            0.02
+        );
+    }
+
+    #[test]
+    fn test_gpu_heston() {
+        let heston = GpuAccelerated::new(Heston::new(0.0, 0.04, 1.5, 0.04, 0.3, -0.7));
+        let output = heston.euler_maruyama(100.0, 0.0, 1.0, 100, 20_000, true);
+
+        assert!(output.paths.iter().all(|&s| s > 0.0));
+        assert_approx_equal!(output.terminal_values().to_vec().mean(), 100.0, 1.0);
+    }
+
+    #[test]
+    fn test_gpu_seeded_chunks_match_whole_run() {
+        let gbm = GpuAccelerated::new(GeometricBrownianMotion::new(0.05, 0.2));
+        let config = SimulationConfig::new(true).with_seed(17);
+
+        let all = gbm.simulate_with_config(100.0, 0.0, 1.0, 10, 100, &config);
+        let chunks = PathChunks::new(&gbm, 100.0, 0.0, 1.0, 10, 100, 40, false)
+            .with_config(config)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            chunks.iter().map(|chunk| chunk.n_paths()).sum::<usize>(),
+            all.n_paths()
+        );
+
+        let streamed = chunks.iter().flat_map(|chunk| chunk.iter());
+        assert!(streamed.zip(all.iter()).all(|(a, b)| a == b));
+    }
+
+    #[test]
+    fn test_gpu_kernel_parameters() {
+        let (model, p) = Heston::new(0.01, 0.04, 1.5, 0.05, 0.3, -0.7)
+            .gpu_model()
+            .kernel_parameters();
+
+        assert_eq!(model, 2);
+        assert_eq!(p, [0.01, 0.04, 1.5, 0.05, 0.3, -0.7]);
+        assert_eq!(std::mem::size_of::<Params>() % 16, 0);
+    }
+}
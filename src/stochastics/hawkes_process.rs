@@ -0,0 +1,278 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Hawkes (self-exciting) point process with an exponential kernel.
+//!
+//! The conditional intensity is:
+//!
+//! $$
+//! \lambda(t) = \mu + \sum_{t_i < t} \alpha e^{-\beta (t - t_i)}
+//! $$
+//!
+//! Event times are simulated exactly with Ogata's (1981) thinning
+//! algorithm. Since the intensity decays between events, its value just
+//! after the current time is a valid upper bound until the next event.
+
+use crate::error::{ensure, expect_valid, RustQuantError};
+use crate::stochastics::*;
+use rand::Rng;
+use rand_distr::Exp1;
+
+/// Struct containing the Hawkes process parameters.
+#[derive(Debug, Clone, Copy)]
+pub struct HawkesProcess {
+    /// Baseline intensity ($\mu$).
+    pub mu: f64,
+
+    /// Jump in the intensity after each event ($\alpha$).
+    pub alpha: f64,
+
+    /// Exponential decay rate of the excitation ($\beta$).
+    pub beta: f64,
+}
+
+impl HawkesProcess {
+    /// Create a new Hawkes process.
+    ///
+    /// The branching ratio $\alpha / \beta$ must be less than one for the
+    /// process to be stationary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parameters are rejected by [`HawkesProcess::try_new`].
+    pub fn new(mu: f64, alpha: f64, beta: f64) -> Self {
+        expect_valid(Self::try_new(mu, alpha, beta))
+    }
+
+    /// Create a new Hawkes process, or an error unless `mu` and `beta` are
+    /// positive, `alpha` is non-negative and the branching ratio is less
+    /// than one.
+    pub fn try_new(mu: f64, alpha: f64, beta: f64) -> Result<Self, RustQuantError> {
+        ensure(mu > 0.0, "mu must be positive")?;
+        ensure(alpha >= 0.0, "alpha must be non-negative")?;
+        ensure(beta > 0.0, "beta must be positive")?;
+        ensure(alpha < beta, "Branching ratio alpha / beta must be < 1.")?;
+
+        Ok(Self { mu, alpha, beta })
+    }
+
+    /// Branching ratio $\alpha / \beta$, i.e. the expected number of
+    /// events directly triggered by a single event.
+    pub fn branching_ratio(&self) -> f64 {
+        self.alpha / self.beta
+    }
+
+    /// Long-run (stationary) intensity $\mu / (1 - \alpha / \beta)$.
+    pub fn stationary_intensity(&self) -> f64 {
+        self.mu / (1.0 - self.branching_ratio())
+    }
+
+    /// Conditional intensity at time `t` given past event times.
+    pub fn intensity(&self, t: f64, events: &[f64]) -> f64 {
+        self.mu
+            + events
+                .iter()
+                .take_while(|&&s| s < t)
+                .map(|&s| self.alpha * (-self.beta * (t - s)).exp())
+                .sum::<f64>()
+    }
+
+    /// Simulates the event times on $(t_0, t_n]$ using Ogata's thinning.
+    pub fn simulate_events<R: Rng>(&self, t_0: f64, t_n: f64, rng: &mut R) -> Vec<f64> {
+        assert!(t_0 < t_n);
+
+        let mut events = Vec::new();
+
+        // Excitation part of the intensity at the current time `t`.
+        let mut excitation = 0.0;
+        let mut t = t_0;
+
+        loop {
+            let lambda_bar = self.mu + excitation;
+
+            let w: f64 = rng.sample::<f64, _>(Exp1) / lambda_bar;
+            excitation *= (-self.beta * w).exp();
+            t += w;
+
+            if t > t_n {
+                break;
+            }
+
+            let u: f64 = rng.gen();
+            if u * lambda_bar <= self.mu + excitation {
+                events.push(t);
+                excitation += self.alpha;
+            }
+        }
+
+        events
+    }
+
+    /// Counting process and intensity on the time grid for one set of
+    /// event times.
+    fn grid_values(&self, x_0: f64, times: &[f64], events: &[f64]) -> [Vec<f64>; 2] {
+        let mut counts = Vec::with_capacity(times.len());
+        let mut intensities = Vec::with_capacity(times.len());
+
+        let mut n = 0;
+        let mut excitation = 0.0;
+        let mut last = times[0];
+
+        for &t in times {
+            while n < events.len() && events[n] <= t {
+                excitation = excitation * (-self.beta * (events[n] - last)).exp() + self.alpha;
+                last = events[n];
+                n += 1;
+            }
+
+            counts.push(x_0 + n as f64);
+            intensities.push(self.mu + excitation * (-self.beta * (t - last)).exp());
+        }
+
+        [counts, intensities]
+    }
+
+    /// Shared path generator for the counting process (`index = 0`) and
+    /// intensity (`index = 1`) trajectories.
+    #[allow(clippy::too_many_arguments)]
+    fn generate(
+        &self,
+        x_0: f64,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        config: &SimulationConfig,
+        index: usize,
+    ) -> Trajectories {
+        generate_trajectories(t_0, t_n, n_steps, m_paths, config, |times, rng, path| {
+            let events = self.simulate_events(t_0, t_n, rng);
+            let output = self.grid_values(x_0, times, &events);
+            path.copy_from_slice(&output[index]);
+        })
+    }
+
+    /// Simulates trajectories of the conditional intensity $\lambda(t)$.
+    ///
+    /// # Arguments:
+    /// * `t_0` - The initial time point.
+    /// * `t_n` - The terminal time point.
+    /// * `n_steps` - The number of time steps between `t_0` and `t_n`.
+    /// * `m_paths` - How many process trajectories to simulate.
+    /// * `parallel` - Run in parallel or not (recommended for > 1000 paths).
+    pub fn intensity_paths(
+        &self,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        parallel: bool,
+    ) -> Trajectories {
+        self.generate(
+            self.mu,
+            t_0,
+            t_n,
+            n_steps,
+            m_paths,
+            &SimulationConfig::new(parallel),
+            1,
+        )
+    }
+}
+
+impl StochasticProcess for HawkesProcess {
+    fn drift(&self, _x: f64, _t: f64) -> f64 {
+        0.0
+    }
+
+    fn diffusion(&self, _x: f64, _t: f64) -> f64 {
+        0.0
+    }
+
+    /// The counting process jumps by one at each event.
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        Some(1.0)
+    }
+
+    /// Simulates the counting process $N(t)$ (shifted by `x_0`) sampled on
+    /// the time grid. Event times themselves are simulated exactly.
+    fn simulate_with_config(
+        &self,
+        x_0: f64,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        config: &SimulationConfig,
+    ) -> Trajectories {
+        self.generate(x_0, t_0, t_n, n_steps, m_paths, config, 0)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_hawkes_process {
+    use super::*;
+    use crate::statistics::*;
+
+    #[test]
+    fn test_hawkes_intensity() {
+        let hp = HawkesProcess::new(1.0, 0.5, 2.0);
+        let events = [1.0, 2.0];
+
+        assert_approx_equal!(hp.intensity(0.5, &events), 1.0, 1e-12);
+        assert_approx_equal!(
+            hp.intensity(3.0, &events),
+            1.0 + 0.5 * (-4.0_f64).exp() + 0.5 * (-2.0_f64).exp(),
+            1e-12
+        );
+        assert_approx_equal!(hp.stationary_intensity(), 4.0 / 3.0, 1e-12);
+    }
+
+    #[test]
+    fn test_hawkes_events_ordered() {
+        let hp = HawkesProcess::new(1.0, 0.8, 1.0);
+        let events = hp.simulate_events(0.0, 50.0, &mut rand::thread_rng());
+
+        assert!(events.windows(2).all(|w| w[0] < w[1]));
+        assert!(events.iter().all(|&t| t > 0.0 && t <= 50.0));
+    }
+
+    #[test]
+    fn test_hawkes_expected_count() {
+        let (mu, alpha, beta) = (1.0, 1.0, 2.0);
+        let hp = HawkesProcess::new(mu, alpha, beta);
+        let T = 10.0;
+
+        let config = SimulationConfig::new(true).with_seed(606);
+        let output = hp.simulate_with_config(0.0, 0.0, T, 100, 2000, &config);
+        let N_T = output.terminal_values().to_vec();
+
+        // E[N_T] = l T + (mu - l)(1 - exp(-(beta - alpha) T)) / (beta - alpha),
+        // where l = beta * mu / (beta - alpha).
+        let l = beta * mu / (beta - alpha);
+        let expected = l * T + (mu - l) * (1.0 - (-(beta - alpha) * T).exp()) / (beta - alpha);
+
+        assert_approx_equal!(N_T.mean(), expected, 0.5);
+    }
+
+    #[test]
+    fn test_hawkes_intensity_paths() {
+        let hp = HawkesProcess::new(1.0, 0.5, 1.0);
+        let output = hp.intensity_paths(0.0, 5.0, 50, 10, false);
+
+        for path in output.iter() {
+            assert_eq!(path[0], 1.0);
+            assert!(path.iter().all(|&l| l >= 1.0));
+        }
+    }
+}
@@ -0,0 +1,225 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Heston (1993) stochastic volatility model.
+//!
+//! $$
+//! \begin{aligned}
+//! dS_t &= \mu S_t dt + \sqrt{V_t} S_t dW_t^S \\
+//! dV_t &= \kappa (\theta - V_t) dt + \sigma \sqrt{V_t} dW_t^V
+//! \end{aligned}
+//! $$
+//!
+//! with $d\langle W^S, W^V \rangle_t = \rho dt$.
+//!
+//! The variance uses a full truncation Euler step and the asset a log-Euler
+//! step, so asset paths stay positive and the variance never blows up.
+
+use crate::error::{ensure, expect_valid, RustQuantError};
+use crate::stochastics::*;
+use rand::Rng;
+use rand_distr::StandardNormal;
+use serde::{Deserialize, Serialize};
+
+/// Struct containing the Heston model parameters.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Heston {
+    /// Drift of the asset ($\mu$).
+    pub mu: f64,
+
+    /// Initial variance ($V_0$).
+    pub v_0: f64,
+
+    /// Mean reversion speed of the variance ($\kappa$).
+    pub kappa: f64,
+
+    /// Long-run variance ($\theta$).
+    pub theta: f64,
+
+    /// Volatility of volatility ($\sigma$).
+    pub sigma: f64,
+
+    /// Correlation between the asset and variance drivers ($\rho$).
+    pub rho: f64,
+}
+
+impl Heston {
+    /// Create a new Heston process.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parameters are rejected by [`Heston::try_new`].
+    pub fn new(mu: f64, v_0: f64, kappa: f64, theta: f64, sigma: f64, rho: f64) -> Self {
+        expect_valid(Self::try_new(mu, v_0, kappa, theta, sigma, rho))
+    }
+
+    /// Create a new Heston process, or an error if `v_0`, `kappa`, `theta`
+    /// or `sigma` is negative, or `rho` is outside $[-1, 1]$.
+    pub fn try_new(
+        mu: f64,
+        v_0: f64,
+        kappa: f64,
+        theta: f64,
+        sigma: f64,
+        rho: f64,
+    ) -> Result<Self, RustQuantError> {
+        ensure(
+            v_0 >= 0.0 && theta >= 0.0,
+            "v_0 and theta must be non-negative",
+        )?;
+        ensure(
+            kappa >= 0.0 && sigma >= 0.0,
+            "kappa and sigma must be non-negative",
+        )?;
+        ensure((-1.0..=1.0).contains(&rho), "rho must be between -1 and 1")?;
+
+        Ok(Self {
+            mu,
+            v_0,
+            kappa,
+            theta,
+            sigma,
+            rho,
+        })
+    }
+
+    /// Simulates one asset path and its variance path.
+    fn simulate<R: Rng>(&self, x_0: f64, times: &[f64], rng: &mut R) -> [Vec<f64>; 2] {
+        let mut s = vec![x_0; times.len()];
+        let mut v = vec![self.v_0; times.len()];
+
+        for i in 0..times.len() - 1 {
+            let dt = times[i + 1] - times[i];
+            let z1: f64 = rng.sample(StandardNormal);
+            let z2: f64 = rng.sample(StandardNormal);
+
+            let dW_v = dt.sqrt() * z1;
+            let dW_s = self.rho * dW_v + (1.0 - self.rho * self.rho).sqrt() * dt.sqrt() * z2;
+
+            let v_plus = v[i].max(0.0);
+
+            s[i + 1] = s[i] * ((self.mu - 0.5 * v_plus) * dt + v_plus.sqrt() * dW_s).exp();
+            v[i + 1] =
+                v[i] + self.kappa * (self.theta - v_plus) * dt + self.sigma * v_plus.sqrt() * dW_v;
+        }
+
+        // Report the truncated variance.
+        v.iter_mut().for_each(|x| *x = x.max(0.0));
+
+        [s, v]
+    }
+
+    /// Shared path generator for the asset (`index = 0`) and variance
+    /// (`index = 1`) trajectories.
+    #[allow(clippy::too_many_arguments)]
+    fn generate(
+        &self,
+        x_0: f64,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        config: &SimulationConfig,
+        index: usize,
+    ) -> Trajectories {
+        generate_trajectories(t_0, t_n, n_steps, m_paths, config, |times, rng, path| {
+            let output = self.simulate(x_0, times, rng);
+            path.copy_from_slice(&output[index]);
+        })
+    }
+
+    /// Simulates trajectories of the instantaneous variance $V_t$.
+    ///
+    /// # Arguments:
+    /// * `t_0` - The initial time point.
+    /// * `t_n` - The terminal time point.
+    /// * `n_steps` - The number of time steps between `t_0` and `t_n`.
+    /// * `m_paths` - How many process trajectories to simulate.
+    /// * `parallel` - Run in parallel or not (recommended for > 1000 paths).
+    pub fn variance_paths(
+        &self,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        parallel: bool,
+    ) -> Trajectories {
+        self.generate(
+            self.v_0,
+            t_0,
+            t_n,
+            n_steps,
+            m_paths,
+            &SimulationConfig::new(parallel),
+            1,
+        )
+    }
+}
+
+impl StochasticProcess for Heston {
+    fn drift(&self, x: f64, _t: f64) -> f64 {
+        self.mu * x
+    }
+
+    /// Diffusion of the asset with the variance frozen at $V_0$.
+    /// The simulation methods use the full variance path instead.
+    fn diffusion(&self, x: f64, _t: f64) -> f64 {
+        self.v_0.sqrt() * x
+    }
+
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        None
+    }
+
+    /// Simulates asset paths using full truncation for the variance and a
+    /// log-Euler step for the asset.
+    fn simulate_with_config(
+        &self,
+        x_0: f64,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        config: &SimulationConfig,
+    ) -> Trajectories {
+        self.generate(x_0, t_0, t_n, n_steps, m_paths, config, 0)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_heston {
+    use super::*;
+    use crate::statistics::*;
+
+    #[test]
+    fn test_heston_moments() {
+        let (kappa, theta, v_0) = (2.0, 0.04, 0.09_f64);
+        let heston = Heston::new(0.05, v_0, kappa, theta, 0.3, -0.7);
+
+        let output = heston.euler_maruyama(100.0, 0.0, 1.0, 200, 5000, true);
+        let S_T = output.terminal_values().to_vec();
+
+        // E[S_T] = S_0 exp(mu T)
+        assert!(S_T.iter().all(|&s| s > 0.0));
+        assert_approx_equal!(S_T.mean(), 100.0 * 0.05_f64.exp(), 1.5);
+
+        // E[V_T] = theta + (V_0 - theta) exp(-kappa T)
+        let V_T = heston
+            .variance_paths(0.0, 1.0, 200, 5000, true)
+            .terminal_values()
+            .to_vec();
+
+        assert!(V_T.iter().all(|&v| v >= 0.0));
+        assert_approx_equal!(V_T.mean(), theta + (v_0 - theta) * (-kappa).exp(), 0.005);
+    }
+}
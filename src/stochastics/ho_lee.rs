@@ -7,6 +7,7 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+use crate::error::{ensure, expect_valid, RustQuantError};
 use crate::stochastics::*;
 
 /// Struct containing the Ho-Lee process parameters.
@@ -19,10 +20,19 @@ pub struct HoLee {
 
 impl HoLee {
     /// Create a new Ho-Lee process.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sigma` is negative.
     pub fn new(sigma: f64, theta_t: fn(f64) -> f64) -> Self {
-        assert!(sigma >= 0.0);
-        // TODO assert theta_t is non-negative function
-        Self { sigma, theta_t }
+        expect_valid(Self::try_new(sigma, theta_t))
+    }
+
+    /// Create a new Ho-Lee process, or an error if `sigma` is negative.
+    pub fn try_new(sigma: f64, theta_t: fn(f64) -> f64) -> Result<Self, RustQuantError> {
+        ensure(sigma >= 0.0, "sigma must be non-negative")?;
+        // TODO check theta_t is non-negative function
+        Ok(Self { sigma, theta_t })
     }
 }
 
@@ -47,7 +57,7 @@ impl StochasticProcess for HoLee {
 #[cfg(test)]
 mod tests_ho_lee {
     use super::*;
-    use crate::{assert_approx_equal, statistics::*};
+    use crate::statistics::*;
 
     // Test a simple case where theta_t is constant
     // Should add tests of simple analytically tractable case
@@ -63,11 +73,7 @@ mod tests_ho_lee {
         let output = hl.euler_maruyama(10.0, 0.0, 1.0, 125, 1000, false);
 
         // Test the distribution of the final values.
-        let X_T: Vec<f64> = output
-            .paths
-            .iter()
-            .filter_map(|v| v.last().cloned())
-            .collect();
+        let X_T = output.terminal_values().to_vec();
 
         let E_XT = X_T.mean();
         let V_XT = X_T.variance();
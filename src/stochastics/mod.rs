@@ -16,6 +16,8 @@
 //!   - Fractional Brownian Motion
 //! - Cox-Ingersoll-Ross (1985)
 //!   - $dX(t) = \left[ \theta - \alpha X(t) \right] dt + \sigma \sqrt{r_t} dW(t)$
+//! - Constant Elasticity of Variance (CEV)
+//!   - $dX(t) = \mu X(t) dt + \sigma X(t)^\beta dW(t)$
 //! - Ornstein-Uhlenbeck process
 //!   - $dX(t) = \theta \left[ \mu - X(t) \right] dt + \sigma dW(t)$
 //! - Ho-Lee (1986)
@@ -26,6 +28,54 @@
 //!   - $dX(t) = \left[ \theta(t) - \alpha(t) X(t) \right] dt + \sigma dW(t)$
 //! - Black-Derman-Toy (1990)
 //!   - $d\ln[X(t)] = \left[ \theta(t) + \frac{\sigma'(t)}{\sigma(t)}\ln[X(t)] \right]dt + \sigma_t dW(t)$
+//! - Bridges (conditioned on the terminal value)
+//!   - Brownian bridge: $dX(t) = \frac{x_T - X(t)}{T - t} dt + \sigma dW(t)$
+//!   - Ornstein-Uhlenbeck bridge
+//! - Heston (1993) stochastic volatility
+//!   - $dS(t) = \mu S(t) dt + \sqrt{V(t)} S(t) dW^S(t)$
+//!   - $dV(t) = \kappa \left[ \theta - V(t) \right] dt + \sigma \sqrt{V(t)} dW^V(t)$
+//! - Hawkes process (exponential kernel)
+//!   - $\lambda(t) = \mu + \sum_{t_i < t} \alpha e^{-\beta (t - t_i)}$
+//! - Regime-switching diffusions
+//!   - $dX(t) = \mu_{Z(t)}(X(t), t) dt + \sigma_{Z(t)}(X(t), t) dW(t)$
+//! - Correlated Geometric Brownian Motions (multi-asset)
+//!   - $dS_i(t) = \mu_i S_i(t) dt + \sigma_i S_i(t) dW_i(t)$, $d\langle W_i, W_j \rangle(t) = \rho_{ij} dt$
+//! - Rough volatility:
+//!   - Rough Bergomi (2016)
+//!   - Rough Heston (2019)
+//!
+//! Processes living on $[0, \infty)$ (CIR, CEV) take a `BoundaryScheme`
+//! (absorption, reflection or full truncation) controlling how the
+//! discretisation behaves at zero.
+//!
+//! New processes can be assembled from existing ones with the combinators
+//! in `combinators` (e.g. `process.exponential()`, `process.shifted(f)`,
+//! `process.time_changed(clock)` and `sum(a, b)`).
+//!
+//! Discretely monitored barriers can be corrected towards continuous
+//! monitoring in `barrier_correction`, either by weighting each path with
+//! its Brownian bridge survival probability
+//! (`trajectories.survival_probabilities(barrier, direction, sigma)`) or
+//! by shifting the barrier with the Broadie-Glasserman-Kou continuity
+//! correction (`ContinuityCorrection`).
+//!
+//! Simulated `Trajectories` come with post-processing utilities such as
+//! `mean_path()`, `variance_path()`, `quantile_paths(&[0.05, 0.5, 0.95])`
+//! and `terminal_distribution()`.
+//!
+//! `simulate_with_config` runs any process with a `SimulationConfig`: an
+//! explicit thread count, per-path seeding (seeded serial and parallel runs
+//! give identical paths) and a progress callback for long runs.
+//!
+//! Large simulations can be streamed in chunks with `PathChunks`, so that
+//! only a bounded number of paths is held in memory at a time.
+//!
+//! With the `gpu` feature, `GpuAccelerated::new(process)` generates GBM,
+//! Ornstein-Uhlenbeck and Heston paths on the GPU via `wgpu`, falling back
+//! to the CPU when no device is present.
+//!
+//! Parameters of several processes can be estimated from historical data
+//! (see the `estimation` module), e.g. `estimate_gbm(&prices, 1.0 / 252.0)`.
 //!
 //! ```rust
 //! use RustQuant::stochastics::*;
@@ -38,31 +88,62 @@
 //!     // Parameters: x_0, t_0, t_n, n, sims, parallel.
 //!     let output = (&gbm).euler_maruyama(10.0, 0.0, 0.5, 10, 1, false);
 //!
-//!     println!("GBM = {:?}", output.paths);
+//!     println!("GBM = {:?}", output.path(0));
 //! }
 //! ```
 
 pub use arithmetic_brownian_motion::*;
+pub use barrier_correction::*;
 pub use black_derman_toy::*;
+pub use boundary::*;
+pub use bridge::*;
 pub use brownian_motion::*;
+pub use combinators::*;
+pub use constant_elasticity_of_variance::*;
+pub use correlated_geometric_brownian_motion::*;
 pub use cox_ingersoll_ross::*;
+pub use estimation::*;
 pub use extended_vasicek::*;
 pub use fractional_brownian_motion::*;
 pub use fractional_ornstein_uhlenbeck::*;
 pub use geometric_brownian_motion::*;
+#[cfg(feature = "gpu")]
+pub use gpu::*;
+pub use hawkes_process::*;
+pub use heston::*;
 pub use ho_lee::*;
 pub use hull_white::*;
 pub use ornstein_uhlenbeck::*;
 pub use process::*;
+pub use regime_switching::*;
+pub use rough_bergomi::*;
+pub use rough_heston::*;
+pub use simulation::*;
+pub use streaming::*;
+pub use trajectory_statistics::*;
 
 /// Arithmetic Brownian Motion.
 pub mod arithmetic_brownian_motion;
+/// Brownian bridge and continuity corrections for barrier monitoring.
+pub mod barrier_correction;
 /// Black-Derman-Toy short rate model.
 pub mod black_derman_toy;
+/// Boundary handling at zero for non-negative processes.
+pub mod boundary;
+/// Brownian and Ornstein-Uhlenbeck bridges.
+pub mod bridge;
 /// Standard Brownian Motion.
 pub mod brownian_motion;
+/// Combinators to build processes from existing ones.
+pub mod combinators;
+/// Constant Elasticity of Variance process.
+pub mod constant_elasticity_of_variance;
+/// Correlated multi-asset Geometric Brownian Motion.
+pub mod correlated_geometric_brownian_motion;
 /// Cox-Ingersoll-Ross process.
 pub mod cox_ingersoll_ross;
+/// Parameter estimation from historical data.
+pub mod estimation;
 /// Extended Vasicek process.
 pub mod extended_vasicek;
 /// Fractional Brownian Motion.
@@ -71,6 +152,13 @@ pub mod fractional_brownian_motion;
 pub mod fractional_ornstein_uhlenbeck;
 /// Geometric Brownian Motion.
 pub mod geometric_brownian_motion;
+/// GPU path generation backend.
+#[cfg(feature = "gpu")]
+pub mod gpu;
+/// Hawkes (self-exciting) point process.
+pub mod hawkes_process;
+/// Heston stochastic volatility model.
+pub mod heston;
 /// Ho-Lee process.
 pub mod ho_lee;
 /// Hull-White model process.
@@ -79,3 +167,15 @@ pub mod hull_white;
 pub mod ornstein_uhlenbeck;
 /// Defines `Trajectories` and `StochasticProcess`.
 pub mod process;
+/// Regime-switching diffusion process.
+pub mod regime_switching;
+/// Rough Bergomi model.
+pub mod rough_bergomi;
+/// Rough Heston model.
+pub mod rough_heston;
+/// Simulation settings (threads, seeding and progress reporting).
+pub mod simulation;
+/// Streaming (chunked) path generation.
+pub mod streaming;
+/// Moment and distribution utilities on simulated trajectories.
+pub mod trajectory_statistics;
@@ -7,39 +7,55 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+use crate::autodiff::Scalar;
+use crate::error::{ensure, expect_valid, RustQuantError};
 use crate::stochastics::*;
 
 /// Struct containing the Ornstein-Uhlenbeck process parameters.
-pub struct OrnsteinUhlenbeck {
+///
+/// With `Variable` parameters, paths can be differentiated with respect to
+/// the mean reversion, e.g. for rate sensitivities.
+pub struct OrnsteinUhlenbeck<T = f64> {
     /// The long-run mean ($\mu$).
-    pub mu: f64,
+    pub mu: T,
 
     /// The diffusion, or instantaneous volatility ($\sigma$).
-    pub sigma: f64,
+    pub sigma: T,
 
     /// Mean reversion parameter ($\theta$).
     /// Defines the speed at which the process reverts to the long-run mean.
-    pub theta: f64,
+    pub theta: T,
 }
 
-impl OrnsteinUhlenbeck {
+impl<T: Scalar> OrnsteinUhlenbeck<T> {
     /// Create a new Ornstein-Uhlenbeck process.
-    pub fn new(mu: f64, sigma: f64, theta: f64) -> Self {
-        assert!(sigma >= 0.0);
-        Self { mu, sigma, theta }
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sigma` is negative.
+    pub fn new(mu: T, sigma: T, theta: T) -> Self {
+        expect_valid(Self::try_new(mu, sigma, theta))
+    }
+
+    /// Create a new Ornstein-Uhlenbeck process, or an error if `sigma` is
+    /// negative.
+    pub fn try_new(mu: T, sigma: T, theta: T) -> Result<Self, RustQuantError> {
+        ensure(sigma.value() >= 0.0, "sigma must be non-negative")?;
+
+        Ok(Self { mu, sigma, theta })
     }
 }
 
-impl StochasticProcess for OrnsteinUhlenbeck {
-    fn drift(&self, x: f64, _t: f64) -> f64 {
+impl<T: Scalar> StochasticProcess<T> for OrnsteinUhlenbeck<T> {
+    fn drift(&self, x: T, _t: f64) -> T {
         self.theta * (self.mu - x)
     }
 
-    fn diffusion(&self, _x: f64, _t: f64) -> f64 {
+    fn diffusion(&self, _x: T, _t: f64) -> T {
         self.sigma
     }
 
-    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+    fn jump(&self, _x: T, _t: f64) -> Option<T> {
         None
     }
 }
@@ -51,7 +67,7 @@ impl StochasticProcess for OrnsteinUhlenbeck {
 #[cfg(test)]
 mod tests_ornstein_uhlenbeck {
     use super::*;
-    use crate::{assert_approx_equal, statistics::*};
+    use crate::statistics::*;
 
     #[test]
     fn test_ornstein_uhlenbeck() -> Result<(), Box<dyn std::error::Error>> {
@@ -60,11 +76,7 @@ mod tests_ornstein_uhlenbeck {
         let output = ou.euler_maruyama(10.0, 0.0, 0.5, 100, 100, false);
 
         // Test the distribution of the final values.
-        let X_T: Vec<f64> = output
-            .paths
-            .iter()
-            .filter_map(|v| v.last().cloned())
-            .collect();
+        let X_T = output.terminal_values().to_vec();
 
         let E_XT = X_T.mean();
         let V_XT = X_T.variance();
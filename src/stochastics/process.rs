@@ -14,31 +14,199 @@
 //! Autonomous refers to processes where the drift and diffusion
 //! do not explicitly depend on the time `t`.
 
+use crate::autodiff::Scalar;
+use crate::error::{ensure, expect_valid, RustQuantError};
+use crate::math::Pcg64;
+use crate::stochastics::SimulationConfig;
+use ndarray::{Array2, ArrayView1, ShapeBuilder};
 use rand::prelude::Distribution;
 use rayon::prelude::*;
 use statrs::distribution::Normal;
 
-#[cfg(feature = "seedable")]
-use rand::{rngs::StdRng, SeedableRng};
-
 /// Struct to contain the time points and path values of the process.
+///
+/// The paths are stored in a single contiguous matrix with one row per path
+/// and one column per time point. The storage is column-major, so the
+/// cross-section of all paths at a given time (e.g. the terminal values)
+/// is a contiguous slice.
+#[derive(Debug, Clone, PartialEq)]
 pub struct Trajectories {
     /// Vector of time points.
     pub times: Vec<f64>,
-    /// Vector of process trajectories.
-    pub paths: Vec<Vec<f64>>,
+    /// Process trajectories (`m_paths x (n_steps + 1)`).
+    pub paths: Array2<f64>,
+}
+
+impl Trajectories {
+    /// Create a new set of trajectories from the time points and a
+    /// `m_paths x times.len()` matrix of path values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of time points and path columns differ.
+    pub fn new(times: Vec<f64>, paths: Array2<f64>) -> Self {
+        expect_valid(Self::try_new(times, paths))
+    }
+
+    /// Create a new set of trajectories, or an error if the number of time
+    /// points and path columns differ.
+    pub fn try_new(times: Vec<f64>, paths: Array2<f64>) -> Result<Self, RustQuantError> {
+        ensure(
+            times.len() == paths.ncols(),
+            "Number of time points and path columns must match.",
+        )?;
+
+        Ok(Self {
+            times,
+            paths: column_major(paths),
+        })
+    }
+
+    /// Number of simulated paths.
+    pub fn n_paths(&self) -> usize {
+        self.paths.nrows()
+    }
+
+    /// Number of time steps (one less than the number of time points).
+    pub fn n_steps(&self) -> usize {
+        self.times.len().saturating_sub(1)
+    }
+
+    /// View of the `i`-th path.
+    pub fn path(&self, i: usize) -> ArrayView1<'_, f64> {
+        self.paths.row(i)
+    }
+
+    /// Iterator over the paths.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = ArrayView1<'_, f64>> {
+        self.paths.rows().into_iter()
+    }
+
+    /// Values of all paths at the `k`-th time point.
+    pub fn values_at(&self, k: usize) -> ArrayView1<'_, f64> {
+        self.paths.column(k)
+    }
+
+    /// Values of all paths at the terminal time point.
+    pub fn terminal_values(&self) -> ArrayView1<'_, f64> {
+        self.values_at(self.n_steps())
+    }
+
+    /// Plots every path against time to a PNG or SVG file.
+    #[cfg(feature = "plot")]
+    pub fn plot(&self, path: impl AsRef<std::path::Path>) -> Result<(), crate::plot::PlotError> {
+        self.iter()
+            .enumerate()
+            .fold(
+                crate::plot::Chart::new("Simulated paths").with_labels("Time", "Value"),
+                |chart, (i, values)| {
+                    let points = self.times.iter().copied().zip(values.iter().copied());
+                    chart.with_series(&format!("Path {i}"), points.collect())
+                },
+            )
+            .save(path)
+    }
+
+    /// Converts the trajectories into a long-format `DataFrame` with columns
+    /// `path`, `time` and `value`, one row per path and time point.
+    ///
+    /// The `value` column takes ownership of the path buffer, so no copy
+    /// of the simulated values is made.
+    #[cfg(feature = "data")]
+    pub fn into_dataframe(self) -> polars::prelude::PolarsResult<polars::prelude::DataFrame> {
+        use polars::prelude::*;
+
+        let (m_paths, n_times) = self.paths.dim();
+        let values = column_major(self.paths).into_raw_vec();
+
+        let path: Vec<u32> = (0..n_times).flat_map(|_| 0..m_paths as u32).collect();
+        let time: Vec<f64> = self
+            .times
+            .iter()
+            .flat_map(|&t| std::iter::repeat_n(t, m_paths))
+            .collect();
+
+        DataFrame::new(vec![
+            UInt32Chunked::from_vec("path", path).into_series(),
+            Float64Chunked::from_vec("time", time).into_series(),
+            Float64Chunked::from_vec("value", values).into_series(),
+        ])
+    }
+}
+
+/// Returns the matrix in column-major layout, copying only if needed.
+fn column_major(paths: Array2<f64>) -> Array2<f64> {
+    if paths.t().is_standard_layout() {
+        return paths;
+    }
+
+    let mut output = Array2::zeros(paths.dim().f());
+    output.assign(&paths);
+    output
 }
 
 /// Trait to implement stochastic processes.
-pub trait StochasticProcess: Sync {
+///
+/// The process is generic over the scalar type `T` of its state and
+/// parameters, `f64` by default. With `T = Variable`, a path generated by
+/// [`StochasticProcess::euler_maruyama_path`] is taped on the autodiff
+/// graph, so Monte Carlo prices can be differentiated with respect to the
+/// initial value and the model parameters (pathwise AAD Greeks).
+///
+/// The simulation methods returning [`Trajectories`] are only available
+/// for `f64` processes that can be shared between threads (e.g.
+/// `Box<dyn StochasticProcess + Sync>`).
+pub trait StochasticProcess<T: Scalar = f64> {
     /// Base method for the process' drift.
-    fn drift(&self, x: f64, t: f64) -> f64;
+    fn drift(&self, x: T, t: f64) -> T;
 
     /// Base method for the process' diffusion.
-    fn diffusion(&self, x: f64, t: f64) -> f64;
+    fn diffusion(&self, x: T, t: f64) -> T;
 
     /// Base method for the process' jump term (if applicable).
-    fn jump(&self, x: f64, t: f64) -> Option<f64>;
+    fn jump(&self, x: T, t: f64) -> Option<T>;
+
+    /// Single Euler-Maruyama path driven by given Brownian increments.
+    ///
+    /// # Arguments:
+    /// * `x_0` - The process' initial value at `times[0]`.
+    /// * `times` - The time points.
+    /// * `increments` - The Brownian increments, `dW[k] = W(times[k + 1]) - W(times[k])`.
+    ///
+    /// ```
+    /// use RustQuant::autodiff::*;
+    /// use RustQuant::stochastics::*;
+    ///
+    /// let g = Graph::new();
+    /// let (s0, sigma) = (g.var(100.0), g.var(0.2));
+    ///
+    /// let gbm = GeometricBrownianMotion::new(g.var(0.05), sigma);
+    /// let path = gbm.euler_maruyama_path(s0, &[0.0, 0.5, 1.0], &[0.1, -0.3]);
+    ///
+    /// // Pathwise sensitivities of the terminal value.
+    /// let grad = path[2].accumulate();
+    /// let expected = path[2].value / 100.0;
+    /// assert!((grad.wrt(&s0) - expected).abs() < 1e-12);
+    /// ```
+    fn euler_maruyama_path(&self, x_0: T, times: &[f64], increments: &[f64]) -> Vec<T> {
+        assert_eq!(
+            times.len(),
+            increments.len() + 1,
+            "One Brownian increment per time step."
+        );
+
+        let mut path = Vec::with_capacity(times.len());
+        path.push(x_0);
+
+        for (t, &dW) in increments.iter().enumerate() {
+            let dt = times[t + 1] - times[t];
+            let x = path[t];
+
+            path.push(x + self.drift(x, times[t]) * dt + self.diffusion(x, times[t]) * dW);
+        }
+
+        path
+    }
 
     /// Euler-Maruyama discretisation scheme.
     ///
@@ -57,43 +225,26 @@ pub trait StochasticProcess: Sync {
         n_steps: usize,
         m_paths: usize,
         parallel: bool,
-    ) -> Trajectories {
-        assert!(t_0 < t_n);
-
-        let dt: f64 = (t_n - t_0) / (n_steps as f64);
-
-        // Initialise empty paths and fill in the time points.
-        let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
-        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
-
-        let path_generator = |path: &mut Vec<f64>| {
-            let mut rng = rand::thread_rng();
-            let scale = dt.sqrt();
-            let dW: Vec<f64> = Normal::new(0.0, 1.0)
-                .unwrap()
-                .sample_iter(&mut rng)
-                .take(n_steps)
-                .map(|z| z * scale)
-                .collect();
-
-            for t in 0..n_steps {
-                path[t + 1] = path[t]
-                    + self.drift(path[t], times[t]) * dt
-                    + self.diffusion(path[t], times[t]) * dW[t];
-            }
-        };
-
-        if parallel {
-            paths.par_iter_mut().for_each(path_generator);
-        } else {
-            paths.iter_mut().for_each(path_generator);
-        }
-
-        Trajectories { times, paths }
+    ) -> Trajectories
+    where
+        Self: Sync,
+        T: From<f64> + Into<f64>,
+    {
+        self.simulate_with_config(
+            x_0,
+            t_0,
+            t_n,
+            n_steps,
+            m_paths,
+            &SimulationConfig::new(parallel),
+        )
     }
 
     /// Euler-Maruyama discretisation scheme with a choice of random seed.
     ///
+    /// Path `i` uses stream `i` of the generator seeded with `seed`, so the
+    /// output does not depend on `parallel`.
+    ///
     /// # Arguments:
     /// * `x_0` - The process' initial value at `t_0`.
     /// * `t_0` - The initial time point.
@@ -103,6 +254,7 @@ pub trait StochasticProcess: Sync {
     /// * `parallel` - Run in parallel or not (recommended for > 1000 paths).
     /// * `seed` - The seed for the random number generator.
     #[cfg(feature = "seedable")]
+    #[allow(clippy::too_many_arguments)]
     fn seedable_euler_maruyama(
         &self,
         x_0: f64,
@@ -112,39 +264,241 @@ pub trait StochasticProcess: Sync {
         m_paths: usize,
         parallel: bool,
         seed: u64,
-    ) -> Trajectories {
-        assert!(t_0 < t_n);
-
-        let dt: f64 = (t_n - t_0) / (n_steps as f64);
-
-        // Initialise empty paths and fill in the time points.
-        let mut paths = vec![vec![x_0; n_steps + 1]; m_paths];
-        let times: Vec<f64> = (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect();
-
-        let path_generator = |path: &mut Vec<f64>| {
-            let mut rng = StdRng::seed_from_u64(seed);
-            let scale = dt.sqrt();
-            let dW: Vec<f64> = Normal::new(0.0, 1.0)
-                .unwrap()
-                .sample_iter(&mut rng)
-                .take(n_steps)
-                .map(|z| z * scale)
-                .collect();
+    ) -> Trajectories
+    where
+        Self: Sync,
+        T: From<f64> + Into<f64>,
+    {
+        self.simulate_with_config(
+            x_0,
+            t_0,
+            t_n,
+            n_steps,
+            m_paths,
+            &SimulationConfig::new(parallel).with_seed(seed),
+        )
+    }
 
+    /// Simulates the process with explicit simulation settings (thread
+    /// count, seeding strategy and progress reporting).
+    ///
+    /// This is the method processes override to provide their own
+    /// simulation scheme. The default is the Euler-Maruyama scheme.
+    ///
+    /// # Arguments:
+    /// * `x_0` - The process' initial value at `t_0`.
+    /// * `t_0` - The initial time point.
+    /// * `t_n` - The terminal time point.
+    /// * `n_steps` - The number of time steps between `t_0` and `t_n`.
+    /// * `m_paths` - How many process trajectories to simulate.
+    /// * `config` - The simulation settings.
+    fn simulate_with_config(
+        &self,
+        x_0: f64,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        config: &SimulationConfig,
+    ) -> Trajectories
+    where
+        Self: Sync,
+        T: From<f64> + Into<f64>,
+    {
+        generate_trajectories(t_0, t_n, n_steps, m_paths, config, |times, rng, path| {
+            let normal = Normal::new(0.0, 1.0).unwrap();
+
+            path[0] = x_0;
             for t in 0..n_steps {
-                path[t + 1] = path[t]
-                    + self.drift(path[t], times[t]) * dt
-                    + self.diffusion(path[t], times[t]) * dW[t];
+                let dt = times[t + 1] - times[t];
+                let dW = normal.sample(rng) * dt.sqrt();
+
+                let x = T::from(path[t]);
+                let drift: f64 = self.drift(x, times[t]).into();
+                let diffusion: f64 = self.diffusion(x, times[t]).into();
+
+                path[t + 1] = path[t] + drift * dt + diffusion * dW;
             }
-        };
+        })
+    }
 
-        if parallel {
-            paths.par_iter_mut().for_each(path_generator);
-        } else {
-            paths.iter_mut().for_each(path_generator);
-        }
+    /// Simulates the process as [`StochasticProcess::simulate_with_config`]
+    /// does, or returns an error if the time grid is invalid (`t_0` and
+    /// `t_n` must be finite with `t_0 < t_n`, and `n_steps` positive).
+    ///
+    /// # Arguments:
+    /// * `x_0` - The process' initial value at `t_0`.
+    /// * `t_0` - The initial time point.
+    /// * `t_n` - The terminal time point.
+    /// * `n_steps` - The number of time steps between `t_0` and `t_n`.
+    /// * `m_paths` - How many process trajectories to simulate.
+    /// * `config` - The simulation settings.
+    fn try_simulate_with_config(
+        &self,
+        x_0: f64,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        config: &SimulationConfig,
+    ) -> Result<Trajectories, RustQuantError>
+    where
+        Self: Sync,
+        T: From<f64> + Into<f64>,
+    {
+        ensure(x_0.is_finite(), "The initial value must be finite.")?;
+        ensure(
+            t_0.is_finite() && t_n.is_finite() && t_0 < t_n,
+            "The time grid must satisfy t_0 < t_n.",
+        )?;
+        ensure(n_steps > 0, "The number of steps must be positive.")?;
+
+        self.check_simulation(x_0, &time_grid(t_0, t_n, n_steps))?;
+
+        Ok(self.simulate_with_config(x_0, t_0, t_n, n_steps, m_paths, config))
+    }
+
+    /// Process-specific checks run by
+    /// [`StochasticProcess::try_simulate_with_config`] on the initial value
+    /// and the time grid, after the generic ones. The default accepts
+    /// everything; processes whose simulation scheme panics on some inputs
+    /// override it to report them as errors instead.
+    fn check_simulation(&self, _x_0: f64, _times: &[f64]) -> Result<(), RustQuantError> {
+        Ok(())
+    }
+}
+
+/// Equally spaced time grid with `n_steps` steps from `t_0` to `t_n`.
+pub(crate) fn time_grid(t_0: f64, t_n: f64, n_steps: usize) -> Vec<f64> {
+    let dt: f64 = (t_n - t_0) / (n_steps as f64);
+
+    (0..=n_steps).map(|t| t_0 + dt * (t as f64)).collect()
+}
+
+/// Builds the `m_paths x n_times` path matrix, where `fill(i, path)` writes
+/// the `i`-th path into a contiguous buffer.
+///
+/// Paths are generated into a single row-major buffer (without a separate
+/// allocation per path) and then stored column-major.
+pub(crate) fn fill_paths<F>(
+    n_times: usize,
+    m_paths: usize,
+    config: &SimulationConfig,
+    fill: F,
+) -> Array2<f64>
+where
+    F: Fn(usize, &mut [f64]) + Sync,
+{
+    let mut buffer = vec![0.0; n_times * m_paths];
+    let tracker = config.tracker(m_paths);
+
+    let fill = |(i, path): (usize, &mut [f64])| {
+        fill(i, path);
+        tracker.tick();
+    };
+
+    if config.parallel {
+        config.install(|| buffer.par_chunks_mut(n_times).enumerate().for_each(fill));
+    } else {
+        buffer.chunks_mut(n_times).enumerate().for_each(fill);
+    }
+
+    let paths = Array2::from_shape_vec((m_paths, n_times), buffer)
+        .expect("Buffer length matches the path matrix shape.");
+
+    column_major(paths)
+}
+
+/// Generates `m_paths` trajectories on an equally spaced time grid from
+/// `t_0` to `t_n`, where each path is written by
+/// `path_generator(times, rng, path)`.
+///
+/// Path `i` gets its own generator from the configured `SeedStrategy`,
+/// so seeded runs do not depend on the thread schedule.
+///
+/// # Panics
+///
+/// Panics unless `t_0 < t_n`.
+pub(crate) fn generate_trajectories<F>(
+    t_0: f64,
+    t_n: f64,
+    n_steps: usize,
+    m_paths: usize,
+    config: &SimulationConfig,
+    path_generator: F,
+) -> Trajectories
+where
+    F: Fn(&[f64], &mut Pcg64, &mut [f64]) + Sync,
+{
+    expect_valid(ensure(t_0 < t_n, "The time grid must satisfy t_0 < t_n."));
+
+    let times = time_grid(t_0, t_n, n_steps);
+
+    let paths = fill_paths(times.len(), m_paths, config, |i, path| {
+        let mut rng = config.seed.rng(i);
+        path_generator(&times, &mut rng, path);
+    });
+
+    Trajectories { times, paths }
+}
+
+/// Forwarding implementation so that boxed (and trait object) processes,
+/// e.g. `Box<dyn StochasticProcess + Sync>`, can be used wherever a process is
+/// expected. The simulation methods are forwarded too, so that processes
+/// overriding them (e.g. fractional processes) keep their own schemes.
+impl<P: StochasticProcess + Sync + ?Sized> StochasticProcess for Box<P> {
+    fn drift(&self, x: f64, t: f64) -> f64 {
+        (**self).drift(x, t)
+    }
+
+    fn diffusion(&self, x: f64, t: f64) -> f64 {
+        (**self).diffusion(x, t)
+    }
+
+    fn jump(&self, x: f64, t: f64) -> Option<f64> {
+        (**self).jump(x, t)
+    }
+
+    fn euler_maruyama(
+        &self,
+        x_0: f64,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        parallel: bool,
+    ) -> Trajectories {
+        (**self).euler_maruyama(x_0, t_0, t_n, n_steps, m_paths, parallel)
+    }
+
+    #[cfg(feature = "seedable")]
+    fn seedable_euler_maruyama(
+        &self,
+        x_0: f64,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        parallel: bool,
+        seed: u64,
+    ) -> Trajectories {
+        (**self).seedable_euler_maruyama(x_0, t_0, t_n, n_steps, m_paths, parallel, seed)
+    }
+
+    fn simulate_with_config(
+        &self,
+        x_0: f64,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        config: &SimulationConfig,
+    ) -> Trajectories {
+        (**self).simulate_with_config(x_0, t_0, t_n, n_steps, m_paths, config)
+    }
 
-        Trajectories { times, paths }
+    fn check_simulation(&self, x_0: f64, times: &[f64]) -> Result<(), RustQuantError> {
+        (**self).check_simulation(x_0, times)
     }
 }
 
@@ -175,6 +529,129 @@ mod test_process {
         // cargo test test_process -- --nocapture
     }
 
+    #[test]
+    fn test_pathwise_aad_greeks() {
+        use crate::autodiff::{Accumulate, Gradient, Graph, Max};
+        use statrs::distribution::{Continuous, ContinuousCDF};
+
+        let (s0, k, r, sigma, tau) = (100.0, 105.0, 0.03, 0.25, 1.0);
+        let (n_steps, m_paths) = (50, 20_000);
+
+        let g = Graph::new();
+        let params = g.vars(&[s0, r, sigma]);
+        let gbm = GeometricBrownianMotion::new(params[1], params[2]);
+        let times = time_grid(0.0, tau, n_steps);
+        let mark = g.len();
+
+        let mut rng = Pcg64::stream(42, 0);
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let (mut price, mut greeks) = (0.0, [0.0; 3]);
+
+        // Tape one path at a time, reusing the tape.
+        for _ in 0..m_paths {
+            let dW: Vec<f64> = (0..n_steps)
+                .map(|_| normal.sample(&mut rng) * (tau / n_steps as f64).sqrt())
+                .collect();
+
+            let path = gbm.euler_maruyama_path(params[0], &times, &dW);
+            let payoff = Max::max(&(path[n_steps] - k), 0.0) * (-params[1] * tau).exp();
+            let grad = payoff.accumulate();
+
+            price += payoff.value / m_paths as f64;
+            for (greek, param) in greeks.iter_mut().zip(&params) {
+                *greek += grad.wrt(param) / m_paths as f64;
+            }
+            g.truncate(mark);
+        }
+
+        // Black-Scholes values.
+        let std_normal = Normal::new(0.0, 1.0).unwrap();
+        let d1 = ((s0 / k).ln() + (r + 0.5 * sigma * sigma) * tau) / (sigma * tau.sqrt());
+        let d2 = d1 - sigma * tau.sqrt();
+        let call = s0 * std_normal.cdf(d1) - k * (-r * tau).exp() * std_normal.cdf(d2);
+        let delta = std_normal.cdf(d1);
+        let rho = k * tau * (-r * tau).exp() * std_normal.cdf(d2);
+        let vega = s0 * std_normal.pdf(d1) * tau.sqrt();
+
+        assert_approx_equal!(price, call, 0.25);
+        assert_approx_equal!(greeks[0], delta, 0.01);
+        assert_approx_equal!(greeks[1], rho, 1.0);
+        assert_approx_equal!(greeks[2], vega, 1.0);
+
+        // The same process with f64 parameters still simulates trajectories.
+        let output =
+            GeometricBrownianMotion::new(r, sigma).euler_maruyama(s0, 0.0, tau, 10, 10, false);
+        assert_eq!(output.n_paths(), 10);
+    }
+
+    #[test]
+    fn test_trajectories_layout() {
+        let bm = crate::stochastics::BrownianMotion::new();
+        let output = bm.euler_maruyama(1.0, 0.0, 1.0, 50, 200, true);
+
+        assert_eq!(output.n_paths(), 200);
+        assert_eq!(output.n_steps(), 50);
+        assert_eq!(output.iter().len(), 200);
+        assert!(output.iter().all(|path| path[0] == 1.0));
+
+        // Cross-sections are contiguous.
+        let terminal = output.terminal_values();
+        assert!(terminal.as_slice().is_some());
+        assert_eq!(terminal[7], output.path(7)[50]);
+    }
+
+    #[test]
+    fn test_try_simulate_with_config() {
+        let gbm = GeometricBrownianMotion::new(0.05, 0.2);
+        let config = SimulationConfig::new(false).with_seed(1);
+
+        let output = gbm.try_simulate_with_config(1.0, 0.0, 1.0, 10, 5, &config);
+        assert_eq!(
+            output.unwrap(),
+            gbm.simulate_with_config(1.0, 0.0, 1.0, 10, 5, &config)
+        );
+
+        assert!(gbm
+            .try_simulate_with_config(1.0, 1.0, 0.0, 10, 5, &config)
+            .is_err());
+        assert!(gbm
+            .try_simulate_with_config(1.0, 0.0, 1.0, 0, 5, &config)
+            .is_err());
+        assert!(gbm
+            .try_simulate_with_config(f64::NAN, 0.0, 1.0, 10, 5, &config)
+            .is_err());
+
+        assert!(Trajectories::try_new(vec![0.0, 1.0], Array2::zeros((3, 3))).is_err());
+    }
+
+    #[cfg(feature = "data")]
+    #[test]
+    fn test_trajectories_into_dataframe() {
+        let times = vec![0.0, 0.5, 1.0];
+        let paths = ndarray::array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let df = Trajectories::new(times, paths).into_dataframe().unwrap();
+
+        assert_eq!(df.shape(), (6, 3));
+
+        let values: Vec<f64> = df
+            .column("value")
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+        let path: Vec<u32> = df
+            .column("path")
+            .unwrap()
+            .u32()
+            .unwrap()
+            .into_no_null_iter()
+            .collect();
+
+        assert_eq!(values, vec![1.0, 4.0, 2.0, 5.0, 3.0, 6.0]);
+        assert_eq!(path, vec![0, 1, 0, 1, 0, 1]);
+    }
+
     #[cfg(feature = "seedable")]
     #[test]
     fn test_seedable_maruyama() {
@@ -182,18 +659,21 @@ mod test_process {
 
         let output_first_seed =
             gbm.seedable_euler_maruyama(10.0, 0.0, 1.0, 125, 10000, true, 123456789);
-        println!("First seed: \t {:?}", output_first_seed.paths[0][125]);
+        println!("First seed: \t {:?}", output_first_seed.paths[[0, 125]]);
 
         let output_same_seed =
             gbm.seedable_euler_maruyama(10.0, 0.0, 1.0, 125, 10000, true, 123456789);
-        println!("Same seed: \t {:?}", output_same_seed.paths[0][125]);
+        println!("Same seed: \t {:?}", output_same_seed.paths[[0, 125]]);
 
         // Check that using the same seed gives the same output.
         assert_eq!(output_first_seed.paths, output_same_seed.paths);
 
         let output_different_seed =
             gbm.seedable_euler_maruyama(10.0, 0.0, 1.0, 125, 10000, true, 987654321);
-        println!("Different seed: {:?}", output_different_seed.paths[0][125]);
+        println!(
+            "Different seed: {:?}",
+            output_different_seed.paths[[0, 125]]
+        );
 
         // Check that using a different seed gives a different output.
         assert_ne!(output_first_seed.paths, output_different_seed.paths);
@@ -201,4 +681,16 @@ mod test_process {
         // To see the output of this "test", run:
         // cargo test test_process -- --nocapture
     }
+
+    #[cfg(feature = "plot")]
+    #[test]
+    fn test_trajectories_plot() {
+        let gbm = GeometricBrownianMotion::new(0.05, 0.2);
+        let paths = gbm.euler_maruyama(10.0, 0.0, 1.0, 50, 5, false);
+        let path = std::env::temp_dir().join("rustquant_trajectories.png");
+
+        paths.plot(&path).unwrap();
+        assert!(std::fs::metadata(&path).is_ok());
+        std::fs::remove_file(&path).unwrap();
+    }
 }
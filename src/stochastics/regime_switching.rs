@@ -0,0 +1,335 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Regime-switching diffusion.
+//!
+//! $$
+//! dX(t) = \mu_{Z(t)}(X(t), t) dt + \sigma_{Z(t)}(X(t), t) dW(t)
+//! $$
+//!
+//! where $Z(t)$ is a continuous-time Markov chain on the regimes
+//! $\{0, \ldots, K - 1\}$ with generator matrix $Q$. Each regime is itself a
+//! `StochasticProcess`, so regimes can be e.g. several `GeometricBrownianMotion`s
+//! with different parameters, or mixed models via `Box<dyn StochasticProcess + Sync>`.
+//!
+//! The Markov chain is simulated exactly (exponential holding times), and
+//! the diffusion uses an Euler-Maruyama step with the coefficients of the
+//! regime active at the start of each step.
+
+use crate::error::{ensure, expect_valid, RustQuantError};
+use crate::stochastics::*;
+use nalgebra::DMatrix;
+use ndarray::{Array2, ShapeBuilder};
+use rand::Rng;
+use rand_distr::{Exp1, StandardNormal};
+use rayon::prelude::*;
+
+/// Struct containing the regime-switching process parameters.
+pub struct RegimeSwitching<P: StochasticProcess + Sync> {
+    /// The process followed in each regime.
+    pub regimes: Vec<P>,
+
+    /// Generator (transition rate) matrix $Q$ of the regime Markov chain.
+    /// Off-diagonal entries are non-negative and each row sums to zero.
+    pub generator: DMatrix<f64>,
+
+    /// Regime at the initial time.
+    pub initial_regime: usize,
+}
+
+/// Simulated asset and regime paths.
+pub struct RegimeSwitchingTrajectories {
+    /// Trajectories of the asset.
+    pub trajectories: Trajectories,
+
+    /// Regime active at each time point, for each path
+    /// (same shape as the asset paths).
+    pub regimes: Array2<usize>,
+}
+
+impl<P: StochasticProcess + Sync> RegimeSwitching<P> {
+    /// Create a new regime-switching process.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the generator is rejected by [`RegimeSwitching::try_new`].
+    pub fn new(regimes: Vec<P>, generator: DMatrix<f64>, initial_regime: usize) -> Self {
+        expect_valid(Self::try_new(regimes, generator, initial_regime))
+    }
+
+    /// Create a new regime-switching process, or an error unless there is
+    /// at least one regime, the generator is a $k \times k$ matrix with
+    /// non-negative off-diagonal rates and rows summing to zero, and the
+    /// initial regime exists.
+    pub fn try_new(
+        regimes: Vec<P>,
+        generator: DMatrix<f64>,
+        initial_regime: usize,
+    ) -> Result<Self, RustQuantError> {
+        let k = regimes.len();
+
+        ensure(k > 0, "At least one regime is needed.")?;
+        ensure(
+            generator.is_square() && generator.nrows() == k,
+            "The generator must be square, with a row per regime.",
+        )?;
+        ensure(initial_regime < k, "The initial regime does not exist.")?;
+
+        for i in 0..k {
+            for j in 0..k {
+                if i != j {
+                    ensure(generator[(i, j)] >= 0.0, "Off-diagonal rates must be >= 0.")?;
+                }
+            }
+            ensure(
+                generator.row(i).sum().abs() < 1e-10,
+                "Generator rows must sum to zero.",
+            )?;
+        }
+
+        Ok(Self {
+            regimes,
+            generator,
+            initial_regime,
+        })
+    }
+
+    /// Simulates the regime at each of the given time points, using exact
+    /// exponential holding times for the Markov chain.
+    pub fn simulate_regimes<R: Rng>(&self, times: &[f64], rng: &mut R) -> Vec<usize> {
+        let mut regimes = Vec::with_capacity(times.len());
+
+        let mut state = self.initial_regime;
+        let mut next_switch = times[0] + self.holding_time(state, rng);
+
+        for &t in times {
+            while next_switch <= t {
+                state = self.next_regime(state, rng);
+                next_switch += self.holding_time(state, rng);
+            }
+            regimes.push(state);
+        }
+
+        regimes
+    }
+
+    /// Exponential holding time in `state` (infinite if absorbing).
+    fn holding_time<R: Rng>(&self, state: usize, rng: &mut R) -> f64 {
+        let rate = -self.generator[(state, state)];
+
+        match rate > 0.0 {
+            true => rng.sample::<f64, _>(Exp1) / rate,
+            false => f64::INFINITY,
+        }
+    }
+
+    /// Samples the regime entered when leaving `state`.
+    fn next_regime<R: Rng>(&self, state: usize, rng: &mut R) -> usize {
+        let rate = -self.generator[(state, state)];
+        let u: f64 = rng.gen::<f64>() * rate;
+
+        let mut cumulative = 0.0;
+        let mut last = state;
+
+        for j in (0..self.regimes.len()).filter(|&j| j != state) {
+            let q = self.generator[(state, j)];
+            if q > 0.0 {
+                cumulative += q;
+                last = j;
+                if u < cumulative {
+                    return j;
+                }
+            }
+        }
+
+        last
+    }
+
+    /// Simulates one asset path and its regime path.
+    fn simulate<R: Rng>(&self, x_0: f64, times: &[f64], rng: &mut R) -> (Vec<f64>, Vec<usize>) {
+        let regimes = self.simulate_regimes(times, rng);
+        let mut path = vec![x_0; times.len()];
+
+        for t in 0..times.len() - 1 {
+            let dt = times[t + 1] - times[t];
+            let dW = dt.sqrt() * rng.sample::<f64, _>(StandardNormal);
+            let process = &self.regimes[regimes[t]];
+
+            path[t + 1] = path[t]
+                + process.drift(path[t], times[t]) * dt
+                + process.diffusion(path[t], times[t]) * dW;
+        }
+
+        (path, regimes)
+    }
+
+    /// Simulates both the asset and the regime paths.
+    ///
+    /// # Arguments:
+    /// * `x_0` - The process' initial value at `t_0`.
+    /// * `t_0` - The initial time point.
+    /// * `t_n` - The terminal time point.
+    /// * `n_steps` - The number of time steps between `t_0` and `t_n`.
+    /// * `m_paths` - How many process trajectories to simulate.
+    /// * `parallel` - Run in parallel or not (recommended for > 1000 paths).
+    pub fn simulate_with_regimes(
+        &self,
+        x_0: f64,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        parallel: bool,
+    ) -> RegimeSwitchingTrajectories {
+        self.generate(
+            x_0,
+            t_0,
+            t_n,
+            n_steps,
+            m_paths,
+            &SimulationConfig::new(parallel),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn generate(
+        &self,
+        x_0: f64,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        config: &SimulationConfig,
+    ) -> RegimeSwitchingTrajectories {
+        assert!(t_0 < t_n);
+
+        let times = time_grid(t_0, t_n, n_steps);
+
+        let tracker = config.tracker(m_paths);
+
+        let path_generator = |i: usize| {
+            let mut rng = config.seed.rng(i);
+            let output = self.simulate(x_0, &times, &mut rng);
+            tracker.tick();
+            output
+        };
+
+        let output: Vec<(Vec<f64>, Vec<usize>)> = if config.parallel {
+            config.install(|| (0..m_paths).into_par_iter().map(path_generator).collect())
+        } else {
+            (0..m_paths).map(path_generator).collect()
+        };
+
+        let shape = (m_paths, times.len()).f();
+        let paths = Array2::from_shape_fn(shape, |(i, t)| output[i].0[t]);
+        let regimes = Array2::from_shape_fn(shape, |(i, t)| output[i].1[t]);
+
+        RegimeSwitchingTrajectories {
+            trajectories: Trajectories { times, paths },
+            regimes,
+        }
+    }
+}
+
+impl<P: StochasticProcess + Sync> StochasticProcess for RegimeSwitching<P> {
+    /// Drift of the initial regime.
+    fn drift(&self, x: f64, t: f64) -> f64 {
+        self.regimes[self.initial_regime].drift(x, t)
+    }
+
+    /// Diffusion of the initial regime.
+    fn diffusion(&self, x: f64, t: f64) -> f64 {
+        self.regimes[self.initial_regime].diffusion(x, t)
+    }
+
+    fn jump(&self, x: f64, t: f64) -> Option<f64> {
+        self.regimes[self.initial_regime].jump(x, t)
+    }
+
+    fn simulate_with_config(
+        &self,
+        x_0: f64,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        config: &SimulationConfig,
+    ) -> Trajectories {
+        self.generate(x_0, t_0, t_n, n_steps, m_paths, config)
+            .trajectories
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_regime_switching {
+    use super::*;
+    use crate::statistics::*;
+
+    #[test]
+    fn test_regime_occupation() {
+        let gbm = vec![
+            GeometricBrownianMotion::new(0.05, 0.1),
+            GeometricBrownianMotion::new(-0.05, 0.4),
+        ];
+        let Q = DMatrix::from_row_slice(2, 2, &[-1.0, 1.0, 2.0, -2.0]);
+        let rs = RegimeSwitching::new(gbm, Q, 0);
+
+        let output = rs.simulate_with_regimes(100.0, 0.0, 50.0, 5000, 20, true);
+
+        // Stationary distribution is (2/3, 1/3).
+        let occupation: Vec<f64> = output
+            .regimes
+            .rows()
+            .into_iter()
+            .map(|r| r.iter().filter(|&&z| z == 0).count() as f64 / r.len() as f64)
+            .collect();
+
+        assert_approx_equal!(occupation.mean(), 2.0 / 3.0, 0.05);
+        assert_eq!(output.trajectories.n_paths(), 20);
+        assert!(output.regimes.column(0).iter().all(|&z| z == 0));
+    }
+
+    #[test]
+    fn test_regime_switching_trait_objects() {
+        let regimes: Vec<Box<dyn StochasticProcess + Sync>> = vec![
+            Box::new(ArithmeticBrownianMotion::new(1.0, 0.0)),
+            Box::new(ArithmeticBrownianMotion::new(1.0, 0.0)),
+        ];
+        let Q = DMatrix::from_row_slice(2, 2, &[-3.0, 3.0, 3.0, -3.0]);
+        let rs = RegimeSwitching::new(regimes, Q, 1);
+
+        // Both regimes have deterministic drift 1, so X_T = x_0 + T.
+        let output = rs.euler_maruyama(0.0, 0.0, 1.0, 100, 10, false);
+        for path in output.iter() {
+            assert_approx_equal!(path[100], 1.0, 1e-10);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_generator() {
+        let Q = DMatrix::from_row_slice(2, 2, &[-1.0, 0.5, 2.0, -2.0]);
+        RegimeSwitching::new(vec![BrownianMotion::new(), BrownianMotion::new()], Q, 0);
+    }
+
+    #[test]
+    fn test_try_new() {
+        let regimes = || vec![BrownianMotion::new(), BrownianMotion::new()];
+        let Q = DMatrix::from_row_slice(2, 2, &[-1.0, 1.0, 2.0, -2.0]);
+
+        assert!(RegimeSwitching::try_new(regimes(), Q.clone(), 0).is_ok());
+        assert!(RegimeSwitching::try_new(regimes(), Q.clone(), 2).is_err());
+        assert!(RegimeSwitching::try_new(regimes(), DMatrix::zeros(3, 3), 0).is_err());
+        assert!(RegimeSwitching::<BrownianMotion>::try_new(vec![], Q, 0).is_err());
+    }
+}
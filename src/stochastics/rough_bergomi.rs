@@ -0,0 +1,266 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Rough Bergomi model (Bayer, Friz & Gatheral, 2016).
+//!
+//! $$
+//! \begin{aligned}
+//! dS_t &= S_t \sqrt{v_t} dZ_t \\
+//! v_t &= \xi_0 \exp\left( \eta \sqrt{2H} \int_0^t (t - s)^{H - 1/2} dW_s - \frac{\eta^2}{2} t^{2H} \right)
+//! \end{aligned}
+//! $$
+//!
+//! where $dZ_t = \rho dW_t + \sqrt{1 - \rho^2} dW_t^\perp$.
+//!
+//! The Volterra process is simulated with the hybrid scheme of
+//! Bennedsen, Lunde & Pakkanen (2017) with $\kappa = 1$, i.e. the first
+//! kernel cell is integrated exactly and the remaining cells use the
+//! optimal evaluation points $b_k$.
+
+use crate::error::{ensure, expect_valid, RustQuantError};
+use crate::stochastics::*;
+use rand::Rng;
+use rand_distr::StandardNormal;
+
+/// Struct containing the rough Bergomi model parameters.
+#[derive(Debug, Clone, Copy)]
+pub struct RoughBergomi {
+    /// Hurst parameter of the variance process ($H \in (0, 1/2)$).
+    pub hurst: f64,
+
+    /// Volatility of volatility ($\eta$).
+    pub eta: f64,
+
+    /// Correlation between the asset and variance drivers ($\rho$).
+    pub rho: f64,
+
+    /// Flat forward variance curve ($\xi_0$).
+    pub xi: f64,
+}
+
+impl RoughBergomi {
+    /// Create a new rough Bergomi process.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parameters are rejected by [`RoughBergomi::try_new`].
+    pub fn new(hurst: f64, eta: f64, rho: f64, xi: f64) -> Self {
+        expect_valid(Self::try_new(hurst, eta, rho, xi))
+    }
+
+    /// Create a new rough Bergomi process, or an error unless `hurst` is in
+    /// $(0, 1/2)$, `eta` is non-negative, `rho` is in $[-1, 1]$ and `xi` is
+    /// positive.
+    pub fn try_new(hurst: f64, eta: f64, rho: f64, xi: f64) -> Result<Self, RustQuantError> {
+        ensure(
+            hurst > 0.0 && hurst < 0.5,
+            "hurst must be strictly between 0 and 1/2",
+        )?;
+        ensure(eta >= 0.0, "eta must be non-negative")?;
+        ensure((-1.0..=1.0).contains(&rho), "rho must be between -1 and 1")?;
+        ensure(xi > 0.0, "xi must be positive")?;
+
+        Ok(Self {
+            hurst,
+            eta,
+            rho,
+            xi,
+        })
+    }
+
+    /// Simulates one path of the variance process and the Brownian
+    /// increments driving the asset, using the hybrid scheme.
+    ///
+    /// Returns `(v, dZ)` where `v` has `n_steps + 1` points and `dZ` has
+    /// `n_steps` increments.
+    pub(crate) fn hybrid_scheme<R: Rng>(
+        &self,
+        tau: f64,
+        n_steps: usize,
+        rng: &mut R,
+    ) -> (Vec<f64>, Vec<f64>) {
+        let alpha = self.hurst - 0.5;
+        let dt = tau / n_steps as f64;
+
+        // Covariance of (dW_i, Y_i), where Y_i is the exactly integrated
+        // first kernel cell.
+        let c11 = dt;
+        let c12 = dt.powf(alpha + 1.0) / (alpha + 1.0);
+        let c22 = dt.powf(2.0 * alpha + 1.0) / (2.0 * alpha + 1.0);
+        let l21 = c12 / c11.sqrt();
+        let l22 = (c22 - l21 * l21).max(0.0).sqrt();
+
+        let mut dW = Vec::with_capacity(n_steps);
+        let mut Y = Vec::with_capacity(n_steps);
+        let mut dZ = Vec::with_capacity(n_steps);
+
+        for _ in 0..n_steps {
+            let z1: f64 = rng.sample(StandardNormal);
+            let z2: f64 = rng.sample(StandardNormal);
+            let z3: f64 = rng.sample(StandardNormal);
+
+            let w = c11.sqrt() * z1;
+            dW.push(w);
+            Y.push(l21 * z1 + l22 * z2);
+            dZ.push(self.rho * w + (1.0 - self.rho * self.rho).sqrt() * dt.sqrt() * z3);
+        }
+
+        // Kernel evaluated at the optimal points b_k for k >= 2.
+        let g: Vec<f64> = (0..=n_steps)
+            .map(|k| match k {
+                0 | 1 => 0.0,
+                _ => {
+                    let k = k as f64;
+                    let b = ((k.powf(alpha + 1.0) - (k - 1.0).powf(alpha + 1.0)) / (alpha + 1.0))
+                        .powf(1.0 / alpha);
+                    (b * dt).powf(alpha)
+                }
+            })
+            .collect();
+
+        let scale = (2.0 * alpha + 1.0).sqrt();
+        let mut v = vec![self.xi; n_steps + 1];
+
+        for i in 1..=n_steps {
+            let convolution: f64 = (2..=i).map(|k| g[k] * dW[i - k]).sum();
+            let volterra = scale * (Y[i - 1] + convolution);
+            let t = i as f64 * dt;
+
+            v[i] = self.xi
+                * (self.eta * volterra - 0.5 * self.eta * self.eta * t.powf(2.0 * self.hurst))
+                    .exp();
+        }
+
+        (v, dZ)
+    }
+
+    /// Simulates one asset path and its variance path.
+    fn simulate<R: Rng>(&self, x_0: f64, tau: f64, n_steps: usize, rng: &mut R) -> [Vec<f64>; 2] {
+        let dt = tau / n_steps as f64;
+        let (v, dZ) = self.hybrid_scheme(tau, n_steps, rng);
+
+        let mut s = vec![x_0; n_steps + 1];
+        for t in 0..n_steps {
+            s[t + 1] = s[t] * (v[t].sqrt() * dZ[t] - 0.5 * v[t] * dt).exp();
+        }
+
+        [s, v]
+    }
+
+    /// Shared path generator for the asset (`index = 0`) and variance
+    /// (`index = 1`) trajectories.
+    #[allow(clippy::too_many_arguments)]
+    fn generate(
+        &self,
+        x_0: f64,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        config: &SimulationConfig,
+        index: usize,
+    ) -> Trajectories {
+        generate_trajectories(t_0, t_n, n_steps, m_paths, config, |_, rng, path| {
+            let output = self.simulate(x_0, t_n - t_0, n_steps, rng);
+            path.copy_from_slice(&output[index]);
+        })
+    }
+
+    /// Simulates trajectories of the instantaneous variance $v_t$.
+    ///
+    /// # Arguments:
+    /// * `t_0` - The initial time point.
+    /// * `t_n` - The terminal time point.
+    /// * `n_steps` - The number of time steps between `t_0` and `t_n`.
+    /// * `m_paths` - How many process trajectories to simulate.
+    /// * `parallel` - Run in parallel or not (recommended for > 1000 paths).
+    pub fn variance_paths(
+        &self,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        parallel: bool,
+    ) -> Trajectories {
+        self.generate(
+            self.xi,
+            t_0,
+            t_n,
+            n_steps,
+            m_paths,
+            &SimulationConfig::new(parallel),
+            1,
+        )
+    }
+}
+
+impl StochasticProcess for RoughBergomi {
+    fn drift(&self, _x: f64, _t: f64) -> f64 {
+        0.0
+    }
+
+    /// Diffusion of the asset with the variance frozen at $\xi_0$.
+    /// The simulation methods use the full rough variance path instead.
+    fn diffusion(&self, x: f64, _t: f64) -> f64 {
+        self.xi.sqrt() * x
+    }
+
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        None
+    }
+
+    /// Simulates asset paths using the hybrid scheme for the variance
+    /// and a log-Euler step for the asset.
+    fn simulate_with_config(
+        &self,
+        x_0: f64,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        config: &SimulationConfig,
+    ) -> Trajectories {
+        self.generate(x_0, t_0, t_n, n_steps, m_paths, config, 0)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_rough_bergomi {
+    use super::*;
+    use crate::statistics::*;
+
+    #[test]
+    fn test_rough_bergomi_martingale() {
+        let rb = RoughBergomi::new(0.1, 1.9, -0.9, 0.04);
+
+        let output = rb.euler_maruyama(100.0, 0.0, 1.0, 100, 2000, true);
+
+        let X_T = output.terminal_values().to_vec();
+
+        // E[S_T] = S_0 (zero rates).
+        assert_approx_equal!(X_T.mean(), 100.0, 2.0);
+    }
+
+    #[test]
+    fn test_rough_bergomi_variance() {
+        let rb = RoughBergomi::new(0.1, 1.5, -0.7, 0.04);
+
+        let output = rb.variance_paths(0.0, 1.0, 100, 2000, true);
+
+        let V_T = output.terminal_values().to_vec();
+
+        // E[v_t] = xi_0 for all t.
+        assert!(V_T.iter().all(|&v| v > 0.0));
+        assert_approx_equal!(V_T.mean(), 0.04, 0.01);
+    }
+}
@@ -0,0 +1,259 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Rough Heston model (El Euch & Rosenbaum, 2019) in its Volterra form.
+//!
+//! $$
+//! \begin{aligned}
+//! dS_t &= S_t \sqrt{V_t} dZ_t \\
+//! V_t &= V_0 + \frac{1}{\Gamma(\alpha)} \int_0^t (t - s)^{\alpha - 1}
+//!     \left[ \lambda (\theta - V_s) ds + \nu \sqrt{V_s} dW_s \right]
+//! \end{aligned}
+//! $$
+//!
+//! where $\alpha = H + 1/2$ and $dZ_t = \rho dW_t + \sqrt{1 - \rho^2} dW_t^\perp$.
+//!
+//! The Volterra equation is discretised on the time grid with the kernel
+//! integrated exactly over each cell (the hybrid scheme with $\kappa = 1$
+//! applied to every cell), and the variance is truncated at zero.
+
+use crate::error::{ensure, expect_valid, RustQuantError};
+use crate::stochastics::*;
+use rand::Rng;
+use rand_distr::StandardNormal;
+use statrs::function::gamma::gamma;
+
+/// Struct containing the rough Heston model parameters.
+#[derive(Debug, Clone, Copy)]
+pub struct RoughHeston {
+    /// Hurst parameter of the variance process ($H \in (0, 1/2]$).
+    pub hurst: f64,
+
+    /// Initial variance ($V_0$).
+    pub v_0: f64,
+
+    /// Mean reversion speed ($\lambda$).
+    pub lambda: f64,
+
+    /// Long-run variance ($\theta$).
+    pub theta: f64,
+
+    /// Volatility of volatility ($\nu$).
+    pub nu: f64,
+
+    /// Correlation between the asset and variance drivers ($\rho$).
+    pub rho: f64,
+}
+
+impl RoughHeston {
+    /// Create a new rough Heston process.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parameters are rejected by [`RoughHeston::try_new`].
+    pub fn new(hurst: f64, v_0: f64, lambda: f64, theta: f64, nu: f64, rho: f64) -> Self {
+        expect_valid(Self::try_new(hurst, v_0, lambda, theta, nu, rho))
+    }
+
+    /// Create a new rough Heston process, or an error unless `hurst` is in
+    /// $(0, 1/2]$, `v_0`, `lambda`, `theta` and `nu` are non-negative and
+    /// `rho` is in $[-1, 1]$.
+    pub fn try_new(
+        hurst: f64,
+        v_0: f64,
+        lambda: f64,
+        theta: f64,
+        nu: f64,
+        rho: f64,
+    ) -> Result<Self, RustQuantError> {
+        ensure(hurst > 0.0 && hurst <= 0.5, "hurst must be in (0, 1/2]")?;
+        ensure(
+            v_0 >= 0.0 && theta >= 0.0,
+            "v_0 and theta must be non-negative",
+        )?;
+        ensure(
+            lambda >= 0.0 && nu >= 0.0,
+            "lambda and nu must be non-negative",
+        )?;
+        ensure((-1.0..=1.0).contains(&rho), "rho must be between -1 and 1")?;
+
+        Ok(Self {
+            hurst,
+            v_0,
+            lambda,
+            theta,
+            nu,
+            rho,
+        })
+    }
+
+    /// Kernel weights $\int_{t_{j}}^{t_{j+1}} K(t_k - s) ds$ indexed by
+    /// the lag $k - j \geq 1$, where $K(t) = t^{\alpha - 1} / \Gamma(\alpha)$.
+    fn kernel_weights(&self, dt: f64, n_steps: usize) -> Vec<f64> {
+        let alpha = self.hurst + 0.5;
+        let norm = gamma(alpha + 1.0);
+
+        (0..=n_steps)
+            .map(|lag| match lag {
+                0 => 0.0,
+                _ => {
+                    let lag = lag as f64;
+                    dt.powf(alpha) * (lag.powf(alpha) - (lag - 1.0).powf(alpha)) / norm
+                }
+            })
+            .collect()
+    }
+
+    /// Simulates one asset path and its variance path.
+    fn simulate<R: Rng>(&self, x_0: f64, tau: f64, n_steps: usize, rng: &mut R) -> [Vec<f64>; 2] {
+        let dt = tau / n_steps as f64;
+        let w = self.kernel_weights(dt, n_steps);
+
+        let mut v = vec![self.v_0; n_steps + 1];
+        let mut s = vec![x_0; n_steps + 1];
+
+        // Drift and (kernel-free) diffusion increments of the variance.
+        let mut drift = Vec::with_capacity(n_steps);
+        let mut noise = Vec::with_capacity(n_steps);
+
+        for i in 0..n_steps {
+            let z1: f64 = rng.sample(StandardNormal);
+            let z2: f64 = rng.sample(StandardNormal);
+            let dW = dt.sqrt() * z1;
+            let dZ = self.rho * dW + (1.0 - self.rho * self.rho).sqrt() * dt.sqrt() * z2;
+
+            let v_i = v[i].max(0.0);
+            drift.push(self.lambda * (self.theta - v_i));
+            noise.push(self.nu * v_i.sqrt() * dW / dt);
+
+            s[i + 1] = s[i] * (v_i.sqrt() * dZ - 0.5 * v_i * dt).exp();
+
+            let k = i + 1;
+            let convolution: f64 = (0..k).map(|j| w[k - j] * (drift[j] + noise[j])).sum();
+            v[k] = (self.v_0 + convolution).max(0.0);
+        }
+
+        [s, v]
+    }
+
+    /// Shared path generator for the asset (`index = 0`) and variance
+    /// (`index = 1`) trajectories.
+    #[allow(clippy::too_many_arguments)]
+    fn generate(
+        &self,
+        x_0: f64,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        config: &SimulationConfig,
+        index: usize,
+    ) -> Trajectories {
+        generate_trajectories(t_0, t_n, n_steps, m_paths, config, |_, rng, path| {
+            let output = self.simulate(x_0, t_n - t_0, n_steps, rng);
+            path.copy_from_slice(&output[index]);
+        })
+    }
+
+    /// Simulates trajectories of the instantaneous variance $V_t$.
+    ///
+    /// # Arguments:
+    /// * `t_0` - The initial time point.
+    /// * `t_n` - The terminal time point.
+    /// * `n_steps` - The number of time steps between `t_0` and `t_n`.
+    /// * `m_paths` - How many process trajectories to simulate.
+    /// * `parallel` - Run in parallel or not (recommended for > 1000 paths).
+    pub fn variance_paths(
+        &self,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        parallel: bool,
+    ) -> Trajectories {
+        self.generate(
+            self.v_0,
+            t_0,
+            t_n,
+            n_steps,
+            m_paths,
+            &SimulationConfig::new(parallel),
+            1,
+        )
+    }
+}
+
+impl StochasticProcess for RoughHeston {
+    fn drift(&self, _x: f64, _t: f64) -> f64 {
+        0.0
+    }
+
+    /// Diffusion of the asset with the variance frozen at $V_0$.
+    /// The simulation methods use the full rough variance path instead.
+    fn diffusion(&self, x: f64, _t: f64) -> f64 {
+        self.v_0.sqrt() * x
+    }
+
+    fn jump(&self, _x: f64, _t: f64) -> Option<f64> {
+        None
+    }
+
+    /// Simulates asset paths using the Volterra scheme for the variance
+    /// and a log-Euler step for the asset.
+    fn simulate_with_config(
+        &self,
+        x_0: f64,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        config: &SimulationConfig,
+    ) -> Trajectories {
+        self.generate(x_0, t_0, t_n, n_steps, m_paths, config, 0)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_rough_heston {
+    use super::*;
+    use crate::statistics::*;
+
+    #[test]
+    fn test_kernel_weights_standard_heston() {
+        // For H = 1/2 the kernel is constant, so every weight equals dt.
+        let rh = RoughHeston::new(0.5, 0.04, 1.0, 0.04, 0.3, -0.7);
+        let w = rh.kernel_weights(0.01, 10);
+
+        for weight in w.iter().skip(1) {
+            assert_approx_equal!(*weight, 0.01, 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_rough_heston_moments() {
+        let rh = RoughHeston::new(0.1, 0.04, 2.0, 0.04, 0.2, -0.7);
+
+        let output = rh.euler_maruyama(100.0, 0.0, 1.0, 100, 2000, true);
+        let X_T = output.terminal_values().to_vec();
+
+        // E[S_T] = S_0 (zero rates).
+        assert_approx_equal!(X_T.mean(), 100.0, 2.0);
+
+        let variance = rh.variance_paths(0.0, 1.0, 100, 2000, true);
+        let V_T = variance.terminal_values().to_vec();
+
+        // E[V_t] = theta when V_0 = theta.
+        assert!(V_T.iter().all(|&v| v >= 0.0));
+        assert_approx_equal!(V_T.mean(), 0.04, 0.01);
+    }
+}
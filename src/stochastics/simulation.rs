@@ -0,0 +1,295 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Simulation settings: parallelism, seeding and progress reporting.
+//!
+//! Every path gets its own random number generator. With a seed, path `i`
+//! uses stream `i` of a `Pcg64` generator seeded with `seed`, so a seeded
+//! simulation produces the same paths whether it runs serially, on the
+//! global rayon pool, or on a dedicated pool with any number of threads.
+//!
+//! ```rust
+//! use RustQuant::stochastics::*;
+//!
+//! let config = SimulationConfig::new(true)
+//!     .with_threads(4)
+//!     .with_seed(42)
+//!     .with_progress(|done, total| println!("{done}/{total} paths"));
+//!
+//! let gbm = GeometricBrownianMotion::new(0.05, 0.2);
+//! let output = gbm.simulate_with_config(100.0, 0.0, 1.0, 252, 1000, &config);
+//! ```
+
+use crate::error::{ensure, expect_valid, RustQuantError};
+use crate::math::Pcg64;
+use rand::SeedableRng;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Progress callback, called with `(completed_paths, total_paths)`.
+pub type ProgressCallback = Arc<dyn Fn(usize, usize) + Send + Sync>;
+
+/// How the per-path random number generators are seeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SeedStrategy {
+    /// Each path is seeded from the operating system's entropy.
+    #[default]
+    Entropy,
+
+    /// Path `i` uses stream `i` of the generator seeded with `seed`,
+    /// making the simulation reproducible and independent of the thread
+    /// schedule.
+    PerPath(u64),
+
+    /// Path `i` is seeded as path `first + i` of a `PerPath(seed)`
+    /// simulation, i.e. these are the paths from `first` onwards (e.g. one
+    /// chunk of a streamed run).
+    PerPathFrom {
+        /// The seed of the whole run.
+        seed: u64,
+
+        /// Position of the first path in the whole run.
+        first: u64,
+    },
+}
+
+impl SeedStrategy {
+    /// Random number generator for path `i`.
+    pub fn rng(&self, i: usize) -> Pcg64 {
+        match *self {
+            SeedStrategy::Entropy => Pcg64::from_rng(rand::thread_rng()).unwrap(),
+            SeedStrategy::PerPath(seed) => Pcg64::stream(seed, i as u64),
+            SeedStrategy::PerPathFrom { seed, first } => Pcg64::stream(seed, first + i as u64),
+        }
+    }
+
+    /// The same strategy with the seed mixed with `salt`, used to give
+    /// independent components of a process their own random streams.
+    pub fn salted(&self, salt: u64) -> Self {
+        match *self {
+            SeedStrategy::Entropy => SeedStrategy::Entropy,
+            SeedStrategy::PerPath(seed) => SeedStrategy::PerPath(seed ^ salt),
+            SeedStrategy::PerPathFrom { seed, first } => SeedStrategy::PerPathFrom {
+                seed: seed ^ salt,
+                first,
+            },
+        }
+    }
+
+    /// The strategy for the paths after the first `n`, so that simulating
+    /// paths in batches reproduces a single simulation of all of them.
+    pub fn skip(&self, n: usize) -> Self {
+        match *self {
+            SeedStrategy::Entropy => SeedStrategy::Entropy,
+            SeedStrategy::PerPath(seed) if n == 0 => SeedStrategy::PerPath(seed),
+            SeedStrategy::PerPath(seed) => SeedStrategy::PerPathFrom {
+                seed,
+                first: n as u64,
+            },
+            SeedStrategy::PerPathFrom { seed, first } => SeedStrategy::PerPathFrom {
+                seed,
+                first: first + n as u64,
+            },
+        }
+    }
+}
+
+/// Settings for path simulation.
+#[derive(Clone, Default)]
+pub struct SimulationConfig {
+    /// Run in parallel or not (recommended for > 1000 paths).
+    pub parallel: bool,
+
+    /// Number of worker threads for parallel runs.
+    /// `None` uses the global rayon thread pool.
+    pub threads: Option<usize>,
+
+    /// Seeding strategy for the per-path random number generators.
+    pub seed: SeedStrategy,
+
+    /// Optional progress callback.
+    progress: Option<ProgressCallback>,
+}
+
+impl fmt::Debug for SimulationConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SimulationConfig")
+            .field("parallel", &self.parallel)
+            .field("threads", &self.threads)
+            .field("seed", &self.seed)
+            .field("progress", &self.progress.is_some())
+            .finish()
+    }
+}
+
+impl SimulationConfig {
+    /// Create a new configuration (entropy seeded, no progress reporting).
+    pub fn new(parallel: bool) -> Self {
+        Self {
+            parallel,
+            ..Self::default()
+        }
+    }
+
+    /// Run in parallel on a dedicated pool with `threads` worker threads.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threads` is zero.
+    pub fn with_threads(self, threads: usize) -> Self {
+        expect_valid(self.try_with_threads(threads))
+    }
+
+    /// Run in parallel on a dedicated pool with `threads` worker threads,
+    /// or return an error if `threads` is zero.
+    pub fn try_with_threads(mut self, threads: usize) -> Result<Self, RustQuantError> {
+        ensure(threads > 0, "Number of threads must be positive.")?;
+
+        self.parallel = true;
+        self.threads = Some(threads);
+        Ok(self)
+    }
+
+    /// Seed path `i` with stream `i` of the generator seeded with `seed`.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = SeedStrategy::PerPath(seed);
+        self
+    }
+
+    /// Report progress through `callback(completed_paths, total_paths)`.
+    ///
+    /// The callback is called about every 1% of the paths (and once all
+    /// paths are done), possibly from several threads.
+    pub fn with_progress<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(usize, usize) + Send + Sync + 'static,
+    {
+        self.progress = Some(Arc::new(callback));
+        self
+    }
+
+    /// The same configuration with the seed mixed with `salt`.
+    pub fn salted(&self, salt: u64) -> Self {
+        Self {
+            seed: self.seed.salted(salt),
+            ..self.clone()
+        }
+    }
+
+    /// The same configuration for the paths after the first `n` (see
+    /// [`SeedStrategy::skip`]).
+    pub fn skip_paths(&self, n: usize) -> Self {
+        Self {
+            seed: self.seed.skip(n),
+            ..self.clone()
+        }
+    }
+
+    /// Runs `job` on the configured thread pool.
+    pub(crate) fn install<T: Send>(&self, job: impl FnOnce() -> T + Send) -> T {
+        match self.threads {
+            Some(threads) if self.parallel => rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .expect("Failed to build the simulation thread pool.")
+                .install(job),
+            _ => job(),
+        }
+    }
+
+    /// Progress tracker for `total` paths.
+    pub(crate) fn tracker(&self, total: usize) -> ProgressTracker {
+        ProgressTracker {
+            callback: self.progress.clone(),
+            completed: AtomicUsize::new(0),
+            total,
+            interval: (total / 100).max(1),
+        }
+    }
+}
+
+/// Counts completed paths and forwards them to the progress callback.
+pub(crate) struct ProgressTracker {
+    callback: Option<ProgressCallback>,
+    completed: AtomicUsize,
+    total: usize,
+    interval: usize,
+}
+
+impl ProgressTracker {
+    /// Records one completed path.
+    pub(crate) fn tick(&self) {
+        if let Some(callback) = &self.callback {
+            let completed = self.completed.fetch_add(1, Ordering::Relaxed) + 1;
+
+            if completed.is_multiple_of(self.interval) || completed == self.total {
+                callback(completed, self.total);
+            }
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_simulation {
+    use crate::stochastics::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_serial_and_parallel_runs_are_identical() {
+        let gbm = GeometricBrownianMotion::new(0.05, 0.2);
+
+        let serial = SimulationConfig::new(false).with_seed(7);
+        let parallel = SimulationConfig::new(true).with_seed(7);
+        let threads = SimulationConfig::new(true).with_threads(3).with_seed(7);
+
+        let a = gbm.simulate_with_config(100.0, 0.0, 1.0, 50, 200, &serial);
+        let b = gbm.simulate_with_config(100.0, 0.0, 1.0, 50, 200, &parallel);
+        let c = gbm.simulate_with_config(100.0, 0.0, 1.0, 50, 200, &threads);
+
+        assert_eq!(a, b);
+        assert_eq!(a, c);
+
+        // Paths are distinct.
+        assert_ne!(a.path(0), a.path(1));
+    }
+
+    #[test]
+    fn test_dedicated_schemes_are_deterministic() {
+        let cir = CoxIngersollRoss::new(0.05, 0.1, 0.5);
+
+        let serial = SimulationConfig::new(false).with_seed(1);
+        let parallel = SimulationConfig::new(true).with_threads(2).with_seed(1);
+
+        assert_eq!(
+            cir.simulate_with_config(0.05, 0.0, 1.0, 20, 50, &serial),
+            cir.simulate_with_config(0.05, 0.0, 1.0, 20, 50, &parallel)
+        );
+    }
+
+    #[test]
+    fn test_progress_callback() {
+        let reports = std::sync::Arc::new(Mutex::new(Vec::new()));
+        let sink = reports.clone();
+
+        let config = SimulationConfig::new(true)
+            .with_progress(move |done, total| sink.lock().unwrap().push((done, total)));
+
+        BrownianMotion::new().simulate_with_config(0.0, 0.0, 1.0, 10, 1000, &config);
+
+        let reports = reports.lock().unwrap();
+        assert_eq!(reports.len(), 100);
+        assert!(reports.iter().all(|&(_, total)| total == 1000));
+        assert!(reports.iter().any(|&(done, _)| done == 1000));
+    }
+}
@@ -0,0 +1,249 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Streaming (chunked) path generation.
+//!
+//! Large simulations (e.g. 10 million paths) do not need to materialise
+//! every path at once. `PathChunks` is an iterator that simulates the paths
+//! in chunks of at most `chunk_size` paths, so only one chunk is held in
+//! memory at a time. Each chunk is a regular `Trajectories`, generated by
+//! the process' own `simulate_with_config` method (so processes with
+//! dedicated schemes keep them).
+//!
+//! With a seeded `SimulationConfig`, chunk paths are seeded from their
+//! position in the whole run, so a streamed simulation yields the same
+//! paths as simulating all of them at once.
+//!
+//! ```rust
+//! use RustQuant::stochastics::*;
+//!
+//! let gbm = GeometricBrownianMotion::new(0.05, 0.2);
+//!
+//! // 100,000 paths, at most 10,000 in memory at a time.
+//! let chunks = PathChunks::new(&gbm, 100.0, 0.0, 1.0, 12, 100_000, 10_000, true);
+//!
+//! let mut sum = 0.0;
+//! chunks.for_each_chunk(|chunk| sum += chunk.terminal_values().sum());
+//!
+//! println!("E[S_T] = {}", sum / 100_000.0);
+//! ```
+
+use crate::error::{ensure, expect_valid, RustQuantError};
+use crate::stochastics::*;
+
+/// Iterator over chunks of simulated paths.
+pub struct PathChunks<'a, P: StochasticProcess + Sync + ?Sized> {
+    /// The process being simulated.
+    process: &'a P,
+
+    /// The process' initial value at `t_0`.
+    x_0: f64,
+
+    /// The initial time point.
+    t_0: f64,
+
+    /// The terminal time point.
+    t_n: f64,
+
+    /// The number of time steps between `t_0` and `t_n`.
+    n_steps: usize,
+
+    /// Number of paths that are still to be simulated.
+    remaining: usize,
+
+    /// Number of paths already simulated.
+    simulated: usize,
+
+    /// Maximum number of paths per chunk.
+    chunk_size: usize,
+
+    /// Parallelism and seeding of the simulation.
+    config: SimulationConfig,
+}
+
+impl<'a, P: StochasticProcess + Sync + ?Sized> PathChunks<'a, P> {
+    /// Create a new chunked simulation.
+    ///
+    /// # Arguments:
+    /// * `process` - The process to simulate.
+    /// * `x_0` - The process' initial value at `t_0`.
+    /// * `t_0` - The initial time point.
+    /// * `t_n` - The terminal time point.
+    /// * `n_steps` - The number of time steps between `t_0` and `t_n`.
+    /// * `m_paths` - How many process trajectories to simulate in total.
+    /// * `chunk_size` - Maximum number of paths held in memory at a time.
+    /// * `parallel` - Simulate each chunk in parallel or not.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless `t_0 < t_n` and `chunk_size` is positive.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        process: &'a P,
+        x_0: f64,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        chunk_size: usize,
+        parallel: bool,
+    ) -> Self {
+        expect_valid(Self::try_new(
+            process, x_0, t_0, t_n, n_steps, m_paths, chunk_size, parallel,
+        ))
+    }
+
+    /// Create a new chunked simulation, or an error unless `t_0 < t_n` and
+    /// `chunk_size` is positive. The arguments are those of
+    /// [`PathChunks::new`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_new(
+        process: &'a P,
+        x_0: f64,
+        t_0: f64,
+        t_n: f64,
+        n_steps: usize,
+        m_paths: usize,
+        chunk_size: usize,
+        parallel: bool,
+    ) -> Result<Self, RustQuantError> {
+        ensure(t_0 < t_n, "The time grid must satisfy t_0 < t_n.")?;
+        ensure(chunk_size > 0, "Chunk size must be positive.")?;
+
+        Ok(Self {
+            process,
+            x_0,
+            t_0,
+            t_n,
+            n_steps,
+            remaining: m_paths,
+            simulated: 0,
+            chunk_size,
+            config: SimulationConfig::new(parallel),
+        })
+    }
+
+    /// Sets the simulation settings (e.g. a seed), replacing `parallel`.
+    /// A progress callback reports on each chunk separately.
+    pub fn with_config(mut self, config: SimulationConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Simulates every chunk and passes it to `consumer`, dropping it
+    /// afterwards.
+    pub fn for_each_chunk<F>(self, consumer: F)
+    where
+        F: FnMut(Trajectories),
+    {
+        self.for_each(consumer);
+    }
+}
+
+impl<P: StochasticProcess + Sync + ?Sized> Iterator for PathChunks<'_, P> {
+    type Item = Trajectories;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let m_paths = self.remaining.min(self.chunk_size);
+        let config = self.config.skip_paths(self.simulated);
+
+        self.remaining -= m_paths;
+        self.simulated += m_paths;
+
+        Some(self.process.simulate_with_config(
+            self.x_0,
+            self.t_0,
+            self.t_n,
+            self.n_steps,
+            m_paths,
+            &config,
+        ))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n_chunks = self.remaining.div_ceil(self.chunk_size);
+
+        (n_chunks, Some(n_chunks))
+    }
+}
+
+impl<P: StochasticProcess + Sync + ?Sized> ExactSizeIterator for PathChunks<'_, P> {}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_streaming {
+    use super::*;
+
+    #[test]
+    fn test_chunk_sizes() {
+        let bm = BrownianMotion::new();
+        let chunks = PathChunks::new(&bm, 0.0, 0.0, 1.0, 10, 2500, 1000, false);
+
+        assert_eq!(chunks.len(), 3);
+
+        let sizes: Vec<usize> = chunks.map(|chunk| chunk.n_paths()).collect();
+        assert_eq!(sizes, vec![1000, 1000, 500]);
+    }
+
+    #[test]
+    fn test_streaming_terminal_mean() {
+        let gbm = GeometricBrownianMotion::new(0.05, 0.2);
+        let chunks = PathChunks::new(&gbm, 100.0, 0.0, 1.0, 50, 20_000, 3_000, true);
+
+        let mut sum = 0.0;
+        let mut count = 0;
+        chunks.for_each_chunk(|chunk| {
+            sum += chunk.terminal_values().sum();
+            count += chunk.n_paths();
+        });
+
+        // E[S_T] = S_0 exp(mu T)
+        assert_eq!(count, 20_000);
+        assert_approx_equal!(sum / count as f64, 100.0 * 0.05_f64.exp(), 1.0);
+    }
+
+    #[test]
+    fn test_seeded_chunks_match_in_memory_paths() {
+        let gbm = GeometricBrownianMotion::new(0.05, 0.2);
+        let config = SimulationConfig::new(true).with_seed(612);
+
+        let all = gbm.simulate_with_config(100.0, 0.0, 1.0, 20, 250, &config);
+        let chunks = PathChunks::new(&gbm, 100.0, 0.0, 1.0, 20, 250, 64, false)
+            .with_config(config)
+            .collect::<Vec<_>>();
+
+        assert_eq!(chunks.len(), 4);
+        assert_eq!(
+            chunks.iter().map(|chunk| chunk.n_paths()).sum::<usize>(),
+            all.n_paths()
+        );
+
+        let streamed = chunks.iter().flat_map(|chunk| chunk.iter());
+        assert!(streamed.zip(all.iter()).all(|(a, b)| a == b));
+    }
+
+    #[test]
+    fn test_streaming_trait_object() {
+        let process: Box<dyn StochasticProcess + Sync> =
+            Box::new(HawkesProcess::new(1.0, 0.5, 1.0));
+        let chunks = PathChunks::new(process.as_ref(), 0.0, 0.0, 5.0, 10, 25, 10, false);
+
+        // Hawkes paths are counting processes, so each chunk keeps its scheme.
+        for chunk in chunks {
+            assert!(chunk.paths.iter().all(|n| n.fract() == 0.0));
+        }
+    }
+}
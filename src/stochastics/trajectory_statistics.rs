@@ -0,0 +1,237 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Moment and distribution utilities on simulated trajectories.
+//!
+//! ```rust
+//! use RustQuant::stochastics::*;
+//!
+//! let gbm = GeometricBrownianMotion::new(0.05, 0.2);
+//! let output = gbm.euler_maruyama(100.0, 0.0, 1.0, 252, 1000, true);
+//!
+//! let mean = output.mean_path();
+//! let fan = output.quantile_paths(&[0.05, 0.5, 0.95]);
+//!
+//! let terminal = output.terminal_distribution();
+//! println!("E[S_T] = {}, 5% ES = {}", terminal.mean(), terminal.expected_shortfall(0.05));
+//! ```
+
+use crate::statistics::Statistic;
+use crate::stochastics::*;
+use ndarray::{Array1, Array2, Axis};
+
+/// Empirical distribution of the terminal values of simulated paths.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TerminalDistribution {
+    /// Terminal values, sorted in increasing order.
+    pub values: Vec<f64>,
+}
+
+/// Quantile of sorted data, linearly interpolated between order statistics
+/// (as in `Statistic::quantile`).
+fn sorted_quantile(sorted: &[f64], quantile: f64) -> f64 {
+    assert!(!sorted.is_empty(), "Data must have at least one element.");
+    assert!(
+        (0.0..=1.0).contains(&quantile),
+        "Quantile must be between 0 and 1."
+    );
+
+    let index = quantile * (sorted.len() - 1) as f64;
+    let lower = sorted[index.floor() as usize];
+    let upper = sorted[index.ceil() as usize];
+
+    lower + (upper - lower) * (index - index.floor())
+}
+
+impl TerminalDistribution {
+    /// Create the empirical distribution of the given values.
+    pub fn new(mut values: Vec<f64>) -> Self {
+        assert!(!values.is_empty(), "Values must have at least one element.");
+
+        values.sort_by(|a, b| a.total_cmp(b));
+
+        Self { values }
+    }
+
+    /// Number of observations.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Whether there are no observations.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Sample mean.
+    pub fn mean(&self) -> f64 {
+        self.values.mean()
+    }
+
+    /// Sample variance.
+    pub fn variance(&self) -> f64 {
+        self.values.variance()
+    }
+
+    /// Sample standard deviation.
+    pub fn standard_deviation(&self) -> f64 {
+        self.values.standard_deviation()
+    }
+
+    /// Sample skewness.
+    pub fn skewness(&self) -> f64 {
+        self.values.skewness()
+    }
+
+    /// Sample excess kurtosis.
+    pub fn kurtosis(&self) -> f64 {
+        self.values.kurtosis()
+    }
+
+    /// Empirical quantile.
+    pub fn quantile(&self, quantile: f64) -> f64 {
+        sorted_quantile(&self.values, quantile)
+    }
+
+    /// Empirical CDF, i.e. the fraction of values `<= x`.
+    pub fn cdf(&self, x: f64) -> f64 {
+        self.values.partition_point(|&v| v <= x) as f64 / self.len() as f64
+    }
+
+    /// Expected shortfall at level `alpha`: the mean of the lowest
+    /// `alpha` fraction of the values (e.g. the worst 5% of outcomes).
+    pub fn expected_shortfall(&self, alpha: f64) -> f64 {
+        assert!(alpha > 0.0 && alpha <= 1.0, "Alpha must be in (0, 1].");
+
+        let k = ((alpha * self.len() as f64).ceil() as usize).max(1);
+
+        self.values[..k].iter().sum::<f64>() / k as f64
+    }
+}
+
+impl Trajectories {
+    /// Empirical distribution of the terminal values.
+    pub fn terminal_distribution(&self) -> TerminalDistribution {
+        TerminalDistribution::new(self.terminal_values().to_vec())
+    }
+
+    /// Mean of the paths at each time point.
+    pub fn mean_path(&self) -> Array1<f64> {
+        assert!(
+            self.n_paths() > 0,
+            "Trajectories must contain at least one path."
+        );
+
+        self.paths
+            .mean_axis(Axis(0))
+            .expect("There is at least one path.")
+    }
+
+    /// Sample variance of the paths at each time point.
+    pub fn variance_path(&self) -> Array1<f64> {
+        assert!(
+            self.n_paths() > 1,
+            "Trajectories must contain at least two paths."
+        );
+
+        self.paths.var_axis(Axis(0), 1.0)
+    }
+
+    /// Quantile paths: row `j` contains the `quantiles[j]` quantile of the
+    /// cross-section at each time point (a quantile fan), so the result has
+    /// shape `quantiles.len() x times.len()`.
+    pub fn quantile_paths(&self, quantiles: &[f64]) -> Array2<f64> {
+        assert!(
+            self.n_paths() > 0,
+            "Trajectories must contain at least one path."
+        );
+
+        let mut fan = Array2::zeros((quantiles.len(), self.times.len()));
+
+        for (k, mut column) in fan.columns_mut().into_iter().enumerate() {
+            let mut sorted = self.values_at(k).to_vec();
+            sorted.sort_by(|a, b| a.total_cmp(b));
+
+            for (j, &q) in quantiles.iter().enumerate() {
+                column[j] = sorted_quantile(&sorted, q);
+            }
+        }
+
+        fan
+    }
+
+    /// Expected shortfall of the terminal values at level `alpha`, i.e. the
+    /// mean of the lowest `alpha` fraction of terminal values.
+    pub fn expected_shortfall(&self, alpha: f64) -> f64 {
+        self.terminal_distribution().expected_shortfall(alpha)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_trajectory_statistics {
+    use super::*;
+    use ndarray::array;
+
+    fn example() -> Trajectories {
+        let times = vec![0.0, 1.0];
+        let paths = array![[0.0, 1.0], [0.0, 3.0], [0.0, 2.0], [0.0, 6.0]];
+
+        Trajectories::new(times, paths)
+    }
+
+    #[test]
+    fn test_mean_and_variance_paths() {
+        let output = example();
+
+        assert_eq!(output.mean_path(), array![0.0, 3.0]);
+        assert_eq!(output.variance_path(), array![0.0, 14.0 / 3.0]);
+    }
+
+    #[test]
+    fn test_quantile_paths() {
+        let output = example();
+        let fan = output.quantile_paths(&[0.0, 0.5, 1.0]);
+
+        assert_eq!(fan.dim(), (3, 2));
+        assert!(fan.column(0).iter().all(|&q| q == 0.0));
+        assert_eq!(fan.column(1).to_vec(), vec![1.0, 2.5, 6.0]);
+    }
+
+    #[test]
+    fn test_terminal_distribution() {
+        let terminal = example().terminal_distribution();
+
+        assert_eq!(terminal.values, vec![1.0, 2.0, 3.0, 6.0]);
+        assert_eq!(terminal.mean(), 3.0);
+        assert_eq!(terminal.cdf(2.5), 0.5);
+        assert_eq!(terminal.quantile(0.5), 2.5);
+        assert_eq!(terminal.expected_shortfall(0.5), 1.5);
+        assert_eq!(example().expected_shortfall(0.25), 1.0);
+    }
+
+    #[test]
+    fn test_gbm_terminal_statistics() {
+        let gbm = GeometricBrownianMotion::new(0.05, 0.2);
+        let output = gbm.euler_maruyama(100.0, 0.0, 1.0, 50, 5000, true);
+
+        let mean = output.mean_path();
+        let fan = output.quantile_paths(&[0.05, 0.5, 0.95]);
+
+        assert_approx_equal!(mean[50], 100.0 * 0.05_f64.exp(), 1.5);
+        assert!(fan.column(50).windows(2).into_iter().all(|w| w[0] <= w[1]));
+
+        // The 5% expected shortfall lies below the 5% quantile.
+        let terminal = output.terminal_distribution();
+        assert!(terminal.expected_shortfall(0.05) <= terminal.quantile(0.05));
+    }
+}
@@ -7,16 +7,246 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-use time::OffsetDateTime;
+use crate::time::{BusinessDayConvention, Tenor, TimeUnit};
+use time::{Duration, OffsetDateTime};
 
 /// Calendar trait.
-/// The calendars follow generic settlement rules, not the exchange holiday rules.
-pub trait Calendar {
+/// The calendars follow generic settlement rules, not the exchange holiday rules
+/// (unless stated otherwise, e.g. `NewYorkStockExchange`).
+/// Calendars are shared between threads (e.g. by day counters of
+/// instruments priced in parallel), so must be `Send + Sync`.
+pub trait Calendar: Send + Sync {
     /// Name of the calendar.
     fn name(&self) -> &'static str;
 
     /// Check if the date is a business day.
     fn is_business_day(&self, date: OffsetDateTime) -> bool;
+
+    /// Check if the date is a holiday (or weekend).
+    fn is_holiday(&self, date: OffsetDateTime) -> bool {
+        !self.is_business_day(date)
+    }
+
+    /// Adjusts a date to a business day according to the given convention.
+    fn adjust(&self, date: OffsetDateTime, convention: &BusinessDayConvention) -> OffsetDateTime {
+        let following = |mut d: OffsetDateTime| {
+            while !self.is_business_day(d) {
+                d += Duration::days(1);
+            }
+            d
+        };
+        let preceding = |mut d: OffsetDateTime| {
+            while !self.is_business_day(d) {
+                d -= Duration::days(1);
+            }
+            d
+        };
+
+        match convention {
+            BusinessDayConvention::Actual => date,
+            BusinessDayConvention::Following => following(date),
+            BusinessDayConvention::Preceding => preceding(date),
+            // For a single date, modified rolling rolls like modified following.
+            BusinessDayConvention::ModifiedFollowing | BusinessDayConvention::ModifiedRolling => {
+                let adjusted = following(date);
+                if adjusted.month() == date.month() {
+                    adjusted
+                } else {
+                    preceding(date)
+                }
+            }
+            BusinessDayConvention::ModifiedPreceding => {
+                let adjusted = preceding(date);
+                if adjusted.month() == date.month() {
+                    adjusted
+                } else {
+                    following(date)
+                }
+            }
+        }
+    }
+
+    /// Moves a date by `n` business days (backwards if `n` is negative).
+    /// With `n = 0`, the date is rolled to the following business day.
+    fn advance_business_days(&self, date: OffsetDateTime, n: i64) -> OffsetDateTime {
+        let mut date = self.adjust(date, &BusinessDayConvention::Following);
+        let step = Duration::days(n.signum());
+
+        for _ in 0..n.abs() {
+            date += step;
+            while !self.is_business_day(date) {
+                date += step;
+            }
+        }
+
+        date
+    }
+
+    /// Moves a date by a tenor and adjusts it with the business day convention.
+    ///
+    /// Day tenors count business days. Week, month and year tenors are added
+    /// to the date and the result is adjusted; with `end_of_month`, a start
+    /// date on the last business day of its month is moved to the last
+    /// business day of the target month.
+    fn advance(
+        &self,
+        date: OffsetDateTime,
+        tenor: Tenor,
+        convention: &BusinessDayConvention,
+        end_of_month: bool,
+    ) -> OffsetDateTime {
+        match tenor.unit {
+            TimeUnit::Days if tenor.length == 0 => self.adjust(date, convention),
+            TimeUnit::Days => self.advance_business_days(date, tenor.length),
+            TimeUnit::Weeks => self.adjust(date + tenor, convention),
+            TimeUnit::Months | TimeUnit::Years => {
+                if end_of_month && self.is_end_of_month(date) {
+                    self.end_of_month(date + tenor)
+                } else {
+                    self.adjust(date + tenor, convention)
+                }
+            }
+        }
+    }
+
+    /// Checks if the date is on or after the last business day of its month.
+    fn is_end_of_month(&self, date: OffsetDateTime) -> bool {
+        let next = self.adjust(date + Duration::days(1), &BusinessDayConvention::Following);
+
+        next.month() != date.month()
+    }
+
+    /// Last business day of the date's month.
+    fn end_of_month(&self, date: OffsetDateTime) -> OffsetDateTime {
+        let last_day = date
+            .replace_day(date.month().length(date.year()))
+            .expect("Invalid date.");
+
+        self.adjust(last_day, &BusinessDayConvention::Preceding)
+    }
+
+    /// Number of business days in `[start, end)`
+    /// (negative if `end` is before `start`).
+    fn business_days_between(&self, start: OffsetDateTime, end: OffsetDateTime) -> i64 {
+        if end < start {
+            return -self.business_days_between(end, start);
+        }
+
+        let mut count = 0;
+        let mut date = start;
+
+        while date.date() < end.date() {
+            if self.is_business_day(date) {
+                count += 1;
+            }
+            date += Duration::days(1);
+        }
+
+        count
+    }
+
+    /// List of holidays (excluding weekends, unless `include_weekends`)
+    /// between `start` and `end` inclusive.
+    fn holidays_between(
+        &self,
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+        include_weekends: bool,
+    ) -> Vec<OffsetDateTime> {
+        let mut holidays = Vec::new();
+        let mut date = start;
+
+        while date.date() <= end.date() {
+            if !self.is_business_day(date) && (include_weekends || !is_weekend(date)) {
+                holidays.push(date);
+            }
+            date += Duration::days(1);
+        }
+
+        holidays
+    }
+}
+
+/// Calendar whose only holidays are weekends.
+pub struct WeekendsOnly;
+
+impl Calendar for WeekendsOnly {
+    fn name(&self) -> &'static str {
+        "Weekends only"
+    }
+
+    fn is_business_day(&self, date: OffsetDateTime) -> bool {
+        !is_weekend(date)
+    }
+}
+
+/// Rule used to combine the calendars of a `JointCalendar`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JointCalendarRule {
+    /// A date is a holiday if it is a holiday in any of the calendars
+    /// (e.g. for settlement across several financial centers).
+    #[default]
+    JoinHolidays,
+
+    /// A date is a business day if it is a business day in any of the
+    /// calendars.
+    JoinBusinessDays,
+}
+
+/// Calendar combining several calendars, e.g. `TARGET` and `UnitedKingdom`
+/// for EUR/GBP settlement.
+pub struct JointCalendar {
+    /// The combined calendars.
+    pub calendars: Vec<Box<dyn Calendar>>,
+
+    /// How the calendars are combined.
+    pub rule: JointCalendarRule,
+}
+
+impl JointCalendar {
+    /// Joint calendar where a date is a holiday if it is a holiday in any of
+    /// the calendars.
+    pub fn new(calendars: Vec<Box<dyn Calendar>>) -> Self {
+        Self::with_rule(calendars, JointCalendarRule::JoinHolidays)
+    }
+
+    /// Union of the calendars' holidays (same as [`JointCalendar::new`]).
+    pub fn union(calendars: Vec<Box<dyn Calendar>>) -> Self {
+        Self::with_rule(calendars, JointCalendarRule::JoinHolidays)
+    }
+
+    /// Intersection of the calendars' holidays: a date is a holiday only if
+    /// it is a holiday in all of the calendars.
+    pub fn intersection(calendars: Vec<Box<dyn Calendar>>) -> Self {
+        Self::with_rule(calendars, JointCalendarRule::JoinBusinessDays)
+    }
+
+    /// Joint calendar with a choice of combination rule.
+    pub fn with_rule(calendars: Vec<Box<dyn Calendar>>, rule: JointCalendarRule) -> Self {
+        assert!(!calendars.is_empty(), "At least one calendar is required.");
+
+        Self { calendars, rule }
+    }
+}
+
+impl Calendar for JointCalendar {
+    fn name(&self) -> &'static str {
+        match self.rule {
+            JointCalendarRule::JoinHolidays => "Joint calendar (holidays)",
+            JointCalendarRule::JoinBusinessDays => "Joint calendar (business days)",
+        }
+    }
+
+    fn is_business_day(&self, date: OffsetDateTime) -> bool {
+        match self.rule {
+            JointCalendarRule::JoinHolidays => {
+                self.calendars.iter().all(|c| c.is_business_day(date))
+            }
+            JointCalendarRule::JoinBusinessDays => {
+                self.calendars.iter().any(|c| c.is_business_day(date))
+            }
+        }
+    }
 }
 
 /// Holiday type.
@@ -36,3 +266,179 @@ pub fn is_weekend(date: OffsetDateTime) -> bool {
 
     w == time::Weekday::Saturday || w == time::Weekday::Sunday
 }
+
+/// Nth occurrence (1-based) of a weekday in a month, e.g. the third Monday.
+/// Returns the day of the month.
+pub fn nth_weekday(n: u8, weekday: time::Weekday, month: time::Month, year: i32) -> u8 {
+    assert!((1..=5).contains(&n));
+
+    let first = time::Date::from_calendar_date(year, month, 1).expect("Valid date.");
+    let offset =
+        (7 + weekday.number_days_from_monday() - first.weekday().number_days_from_monday()) % 7;
+
+    1 + offset + 7 * (n - 1)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_calendar {
+    use super::*;
+    use crate::time::{NewYorkStockExchange, UnitedKingdom, UnitedStates, TARGET};
+    use time::macros::datetime;
+
+    #[test]
+    fn test_advance_tenor() {
+        let calendar = UnitedKingdom;
+        let mf = BusinessDayConvention::ModifiedFollowing;
+
+        // End of month rule.
+        let date = datetime!(2024-04-30 0:00 UTC);
+        assert_eq!(
+            calendar.advance(date, Tenor::months(1), &mf, true),
+            datetime!(2024-05-31 0:00 UTC)
+        );
+        assert_eq!(
+            calendar.advance(date, Tenor::months(1), &mf, false),
+            datetime!(2024-05-30 0:00 UTC)
+        );
+
+        // Day tenors count business days (27 May 2024 is a bank holiday).
+        assert_eq!(
+            calendar.advance(datetime!(2024-05-24 0:00 UTC), Tenor::days(1), &mf, false),
+            datetime!(2024-05-28 0:00 UTC)
+        );
+
+        // Last business day of March 2024 on the NYSE is before Good Friday.
+        let nyse = NewYorkStockExchange;
+        assert!(nyse.is_end_of_month(datetime!(2024-02-29 0:00 UTC)));
+        assert_eq!(
+            nyse.advance(
+                datetime!(2024-02-29 0:00 UTC),
+                "1M".parse().unwrap(),
+                &mf,
+                true
+            ),
+            datetime!(2024-03-28 0:00 UTC)
+        );
+    }
+
+    #[test]
+    fn test_adjust() {
+        let calendar = UnitedKingdom;
+
+        // Saturday 30 September 2023.
+        let date = datetime!(2023-09-30 12:00:00 UTC);
+
+        use BusinessDayConvention::*;
+        assert_eq!(calendar.adjust(date, &Actual), date);
+        assert_eq!(
+            calendar.adjust(date, &Following),
+            datetime!(2023-10-02 12:00:00 UTC)
+        );
+        assert_eq!(
+            calendar.adjust(date, &ModifiedFollowing),
+            datetime!(2023-09-29 12:00:00 UTC)
+        );
+        assert_eq!(
+            calendar.adjust(date, &Preceding),
+            datetime!(2023-09-29 12:00:00 UTC)
+        );
+
+        // Sunday 1 October 2023.
+        let date = datetime!(2023-10-01 12:00:00 UTC);
+        assert_eq!(
+            calendar.adjust(date, &ModifiedPreceding),
+            datetime!(2023-10-02 12:00:00 UTC)
+        );
+
+        // Good Friday and Easter Monday 2023.
+        let date = datetime!(2023-04-07 12:00:00 UTC);
+        assert_eq!(
+            calendar.adjust(date, &Following),
+            datetime!(2023-04-11 12:00:00 UTC)
+        );
+    }
+
+    #[test]
+    fn test_business_days_between() {
+        let calendar = UnitedStates;
+
+        // December 2023: 21 weekdays, minus Christmas.
+        let start = datetime!(2023-12-01 0:00:00 UTC);
+        let end = datetime!(2024-01-01 0:00:00 UTC);
+
+        assert_eq!(calendar.business_days_between(start, end), 20);
+        assert_eq!(calendar.business_days_between(end, start), -20);
+        assert_eq!(calendar.business_days_between(start, start), 0);
+    }
+
+    #[test]
+    fn test_advance_business_days() {
+        let calendar = UnitedStates;
+
+        // Friday 22 December 2023 + 2 business days skips Christmas.
+        let date = datetime!(2023-12-22 0:00:00 UTC);
+        assert_eq!(
+            calendar.advance_business_days(date, 2),
+            datetime!(2023-12-27 0:00:00 UTC)
+        );
+        assert_eq!(
+            calendar.advance_business_days(date, -1),
+            datetime!(2023-12-21 0:00:00 UTC)
+        );
+    }
+
+    #[test]
+    fn test_joint_calendar() {
+        let joint = JointCalendar::new(vec![Box::new(TARGET), Box::new(UnitedKingdom)]);
+        let intersection =
+            JointCalendar::intersection(vec![Box::new(TARGET), Box::new(UnitedKingdom)]);
+
+        // UK Summer Bank Holiday, a TARGET business day.
+        let date = datetime!(2023-08-28 0:00:00 UTC);
+        assert!(!joint.is_business_day(date));
+        assert!(intersection.is_business_day(date));
+
+        // Labour Day, a UK business day but a TARGET holiday.
+        let date = datetime!(2023-05-01 0:00:00 UTC);
+        assert!(!joint.is_business_day(date));
+        assert!(!intersection.is_business_day(date)); // Also the UK Early May Bank Holiday.
+
+        let date = datetime!(2024-05-01 0:00:00 UTC);
+        assert!(!joint.is_business_day(date));
+        assert!(intersection.is_business_day(date));
+
+        // Christmas.
+        let date = datetime!(2023-12-25 0:00:00 UTC);
+        assert!(!intersection.is_business_day(date));
+    }
+
+    #[test]
+    fn test_holidays_between() {
+        let holidays = UnitedKingdom.holidays_between(
+            datetime!(2023-12-01 0:00:00 UTC),
+            datetime!(2023-12-31 0:00:00 UTC),
+            false,
+        );
+
+        assert_eq!(
+            holidays,
+            vec![
+                datetime!(2023-12-25 0:00:00 UTC),
+                datetime!(2023-12-26 0:00:00 UTC)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nth_weekday() {
+        use time::{Month, Weekday};
+
+        assert_eq!(nth_weekday(3, Weekday::Monday, Month::January, 2024), 15);
+        assert_eq!(nth_weekday(4, Weekday::Thursday, Month::November, 2023), 23);
+        assert_eq!(nth_weekday(1, Weekday::Friday, Month::September, 2023), 1);
+    }
+}
@@ -0,0 +1,263 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::time::{Calendar, IntoEvaluationDate};
+use std::collections::{BTreeSet, HashSet};
+use thiserror::Error;
+use time::{Date, Month, OffsetDateTime, Weekday};
+
+/// Calendar defined by an explicit list of holidays, for markets the
+/// built-in calendars don't cover.
+///
+/// ```
+/// use RustQuant::time::*;
+/// use time::macros::datetime;
+///
+/// let calendar = CustomCalendar::new(
+///     "Exchange XYZ",
+///     vec![datetime!(2024-01-01 0:00 UTC), datetime!(2024-12-25 0:00 UTC)],
+/// );
+///
+/// assert!(!calendar.is_business_day(datetime!(2024-12-25 0:00 UTC)));
+/// assert!(calendar.is_business_day(datetime!(2024-12-24 0:00 UTC)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomCalendar {
+    /// Name of the calendar.
+    pub name: &'static str,
+
+    /// Holidays (not including weekends).
+    pub holidays: BTreeSet<Date>,
+
+    /// Weekend days (Saturday and Sunday by default).
+    pub weekend: HashSet<Weekday>,
+}
+
+/// Custom calendar errors.
+#[derive(Debug, Error)]
+pub enum CalendarError {
+    /// A holiday could not be parsed as a `YYYY-MM-DD` date.
+    #[error("Invalid holiday date: {0}")]
+    InvalidDate(String),
+
+    /// Error reading the holiday file.
+    #[cfg(feature = "data")]
+    #[error("{0}")]
+    Data(#[from] crate::data::DataError),
+}
+
+impl CustomCalendar {
+    /// New calendar from a list of holidays, with a Saturday/Sunday weekend.
+    pub fn new<D: IntoEvaluationDate>(name: &'static str, holidays: Vec<D>) -> Self {
+        Self {
+            name,
+            holidays: holidays
+                .into_iter()
+                .map(|date| date.into_evaluation_date().date())
+                .collect(),
+            weekend: HashSet::from([Weekday::Saturday, Weekday::Sunday]),
+        }
+    }
+
+    /// New calendar from the holidays of another calendar between
+    /// `start` and `end`, e.g. to combine it with custom holidays.
+    pub fn from_calendar(
+        name: &'static str,
+        calendar: &dyn Calendar,
+        start: impl IntoEvaluationDate,
+        end: impl IntoEvaluationDate,
+    ) -> Self {
+        let start = start.into_evaluation_date();
+        let end = end.into_evaluation_date();
+
+        Self::new(name, calendar.holidays_between(start, end, false))
+    }
+
+    /// New calendar from `YYYY-MM-DD` holiday strings.
+    pub fn from_strings(name: &'static str, holidays: &[&str]) -> Result<Self, CalendarError> {
+        let mut calendar = Self::new(name, Vec::<Date>::new());
+
+        for holiday in holidays {
+            calendar.holidays.insert(parse_date(holiday)?);
+        }
+
+        Ok(calendar)
+    }
+
+    /// New calendar from a column of `YYYY-MM-DD` dates in a CSV file.
+    #[cfg(feature = "data")]
+    pub fn from_csv(name: &'static str, path: &str, column: &str) -> Result<Self, CalendarError> {
+        use crate::data::{Data, DataError, DataFormat, DataReader};
+        use polars::prelude::DataType;
+
+        let mut data = Data::new(DataFormat::CSV, path.to_string());
+        data.read()?;
+
+        let dates = data
+            .data
+            .column(column)
+            .and_then(|series| series.cast(&DataType::Utf8))
+            .map_err(DataError::from)?;
+        let dates = dates.utf8().map_err(DataError::from)?;
+
+        let mut calendar = Self::new(name, Vec::<Date>::new());
+
+        for date in dates.into_iter().flatten() {
+            calendar.holidays.insert(parse_date(date)?);
+        }
+
+        Ok(calendar)
+    }
+
+    /// Sets the weekend days (e.g. Friday and Saturday).
+    pub fn with_weekend(mut self, weekend: &[Weekday]) -> Self {
+        self.weekend = weekend.iter().cloned().collect();
+        self
+    }
+
+    /// Adds a holiday.
+    pub fn add_holiday(&mut self, date: impl IntoEvaluationDate) {
+        let date = date.into_evaluation_date();
+
+        self.holidays.insert(date.date());
+    }
+
+    /// Removes a holiday.
+    pub fn remove_holiday(&mut self, date: impl IntoEvaluationDate) {
+        let date = date.into_evaluation_date();
+
+        self.holidays.remove(&date.date());
+    }
+
+    /// Calendar whose holidays and weekend days are those of either calendar.
+    pub fn union(&self, other: &CustomCalendar, name: &'static str) -> Self {
+        Self {
+            name,
+            holidays: self.holidays.union(&other.holidays).cloned().collect(),
+            weekend: self.weekend.union(&other.weekend).cloned().collect(),
+        }
+    }
+
+    /// Calendar whose holidays and weekend days are those of both calendars.
+    pub fn intersection(&self, other: &CustomCalendar, name: &'static str) -> Self {
+        Self {
+            name,
+            holidays: self
+                .holidays
+                .intersection(&other.holidays)
+                .cloned()
+                .collect(),
+            weekend: self.weekend.intersection(&other.weekend).cloned().collect(),
+        }
+    }
+}
+
+impl Calendar for CustomCalendar {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn is_business_day(&self, date: OffsetDateTime) -> bool {
+        !self.weekend.contains(&date.weekday()) && !self.holidays.contains(&date.date())
+    }
+}
+
+/// Parses a `YYYY-MM-DD` date.
+fn parse_date(date: &str) -> Result<Date, CalendarError> {
+    let invalid = || CalendarError::InvalidDate(date.to_string());
+
+    let mut parts = date.trim().splitn(3, '-');
+    let mut next = || parts.next().ok_or_else(invalid);
+
+    let year = next()?.parse::<i32>().map_err(|_| invalid())?;
+    let month = next()?.parse::<u8>().map_err(|_| invalid())?;
+    let day = next()?.parse::<u8>().map_err(|_| invalid())?;
+
+    let month = Month::try_from(month).map_err(|_| invalid())?;
+
+    Date::from_calendar_date(year, month, day).map_err(|_| invalid())
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_custom {
+    use super::*;
+    use crate::time::UnitedStates;
+    use time::macros::datetime;
+
+    #[test]
+    fn test_custom_calendar() {
+        let mut calendar =
+            CustomCalendar::from_strings("Test", &["2024-03-01", "2024-03-04"]).unwrap();
+
+        assert_eq!(calendar.name(), "Test");
+        assert!(!calendar.is_business_day(datetime!(2024-03-01 0:00 UTC)));
+        assert!(!calendar.is_business_day(datetime!(2024-03-02 0:00 UTC)));
+        assert!(calendar.is_business_day(datetime!(2024-03-05 0:00 UTC)));
+
+        calendar.remove_holiday(datetime!(2024-03-04 0:00 UTC));
+        assert!(calendar.is_business_day(datetime!(2024-03-04 0:00 UTC)));
+
+        // Friday/Saturday weekend.
+        let calendar = calendar.with_weekend(&[Weekday::Friday, Weekday::Saturday]);
+        assert!(!calendar.is_business_day(datetime!(2024-03-08 0:00 UTC)));
+        assert!(calendar.is_business_day(datetime!(2024-03-10 0:00 UTC)));
+
+        assert!(CustomCalendar::from_strings("Test", &["2024-02-30"]).is_err());
+        assert!(CustomCalendar::from_strings("Test", &["01/03/2024"]).is_err());
+    }
+
+    #[test]
+    fn test_custom_calendar_set_operations() {
+        let us = CustomCalendar::from_calendar(
+            "US",
+            &UnitedStates,
+            datetime!(2024-01-01 0:00 UTC),
+            datetime!(2024-12-31 0:00 UTC),
+        );
+        let other = CustomCalendar::new(
+            "Other",
+            vec![
+                datetime!(2024-07-04 0:00 UTC),
+                datetime!(2024-08-15 0:00 UTC),
+            ],
+        );
+
+        let union = us.union(&other, "Union");
+        let intersection = us.intersection(&other, "Intersection");
+
+        assert_eq!(union.holidays.len(), us.holidays.len() + 1);
+        assert_eq!(
+            intersection.holidays,
+            BTreeSet::from([datetime!(2024-07-04 0:00 UTC).date()])
+        );
+        assert!(!union.is_business_day(datetime!(2024-08-15 0:00 UTC)));
+        assert!(intersection.is_business_day(datetime!(2024-11-28 0:00 UTC)));
+    }
+
+    #[test]
+    #[cfg(feature = "data")]
+    fn test_custom_calendar_from_csv() {
+        let path = std::env::temp_dir().join("rustquant_test_holidays.csv");
+        std::fs::write(
+            &path,
+            "date,name\n2024-01-01,New Year\n2024-05-01,Labour Day\n",
+        )
+        .unwrap();
+
+        let calendar = CustomCalendar::from_csv("CSV", path.to_str().unwrap(), "date").unwrap();
+
+        assert_eq!(calendar.holidays.len(), 2);
+        assert!(!calendar.is_business_day(datetime!(2024-05-01 0:00 UTC)));
+        assert!(CustomCalendar::from_csv("CSV", path.to_str().unwrap(), "missing").is_err());
+    }
+}
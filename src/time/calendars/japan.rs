@@ -0,0 +1,203 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::time::{is_weekend, Calendar};
+use time::{Month, OffsetDateTime, Weekday};
+
+/// Japanese settlement calendar.
+///
+/// Holidays falling on a Sunday are moved to the following Monday.
+pub struct Japan;
+
+/// Days of the vernal (March) and autumnal (September) equinoxes, from the
+/// approximation published for the Japanese calendar (valid 1980 to 2099).
+fn equinoxes(y: i32) -> (u8, u8) {
+    const VERNAL: f64 = 20.8431;
+    const AUTUMNAL: f64 = 23.2488;
+    const DRIFT_PER_YEAR: f64 = 0.242194;
+
+    // Leap days since 1980, rounded down for earlier years too.
+    let n = y - 1980;
+    let drift = n as f64 * DRIFT_PER_YEAR;
+    let leap_years = n.div_euclid(4) as f64;
+
+    (
+        (VERNAL + drift - leap_years).floor() as u8,
+        (AUTUMNAL + drift - leap_years).floor() as u8,
+    )
+}
+
+/// Holiday on day `h` of the month, or the following Monday if `h` is a Sunday.
+fn observed(d: u8, w: Weekday, h: u8) -> bool {
+    d == h || (d == h + 1 && w == Weekday::Monday)
+}
+
+fn is_happy_monday(d: u8, m: Month, y: i32, w: Weekday) -> bool {
+    // Coming of Age Day (second Monday of January since 2000)
+    ((if y >= 2000 {
+        (8..=14).contains(&d) && w == Weekday::Monday
+    } else {
+        observed(d, w, 15)
+    }) && m == Month::January)
+        // Marine Day (third Monday of July since 2003, moved for the Olympics)
+        || ((match y {
+            2020 => d == 23,
+            2021 => d == 22,
+            2003.. => (15..=21).contains(&d) && w == Weekday::Monday,
+            1996..=2002 => observed(d, w, 20),
+            _ => false,
+        }) && m == Month::July)
+        // Respect for the Aged Day (third Monday of September since 2003)
+        || ((if y >= 2003 {
+            (15..=21).contains(&d) && w == Weekday::Monday
+        } else {
+            observed(d, w, 15)
+        }) && m == Month::September)
+        // Health and Sports Day (second Monday of October, moved for the Olympics)
+        || (match y {
+            2020 => d == 24 && m == Month::July,
+            2021 => d == 23 && m == Month::July,
+            2000.. => (8..=14).contains(&d) && w == Weekday::Monday && m == Month::October,
+            _ => observed(d, w, 10) && m == Month::October,
+        })
+}
+
+impl Calendar for Japan {
+    fn name(&self) -> &'static str {
+        "Japan"
+    }
+
+    fn is_business_day(&self, date: OffsetDateTime) -> bool {
+        let w = date.weekday();
+        let d = date.day();
+        let m = date.month();
+        let y = date.year();
+
+        let (ve, ae) = equinoxes(y);
+
+        if is_weekend(date)
+            // New Year's Day and bank holidays
+            || (d <= 3 && m == Month::January)
+            // Coming of Age, Marine, Respect for the Aged and Sports Days
+            || is_happy_monday(d, m, y, w)
+            // National Foundation Day
+            || (observed(d, w, 11) && m == Month::February)
+            // Emperor's Birthday (Emperor Naruhito)
+            || (observed(d, w, 23) && m == Month::February && y >= 2020)
+            // Vernal Equinox
+            || (observed(d, w, ve) && m == Month::March)
+            // Showa Day
+            || (observed(d, w, 29) && m == Month::April)
+            // Constitution Memorial Day, Greenery Day and Children's Day
+            || ((3..=5).contains(&d) && m == Month::May)
+            // Substitute holiday when one of the above falls on a Sunday
+            || (d == 6
+                && m == Month::May
+                && (w == Weekday::Monday || w == Weekday::Tuesday || w == Weekday::Wednesday))
+            // Mountain Day (moved for the Olympics)
+            || ((match y {
+                2020 => d == 10,
+                2021 => d == 9,
+                2016.. => observed(d, w, 11),
+                _ => false,
+            }) && m == Month::August)
+            // Autumnal Equinox
+            || (observed(d, w, ae) && m == Month::September)
+            // Bridge holiday between Respect for the Aged Day and the Autumnal Equinox
+            || (w == Weekday::Tuesday && d + 1 == ae && (16..=22).contains(&d) && m == Month::September && y >= 2003)
+            // Culture Day
+            || (observed(d, w, 3) && m == Month::November)
+            // Labour Thanksgiving Day
+            || (observed(d, w, 23) && m == Month::November)
+            // Emperor's Birthday (Emperor Akihito)
+            || (observed(d, w, 23) && m == Month::December && (1989..=2018).contains(&y))
+            // Bank holiday
+            || (d == 31 && m == Month::December)
+            // Enthronement of Emperor Naruhito (2019)
+            || (y == 2019 && ((m == Month::April && d == 30) || (m == Month::May && (d == 1 || d == 2)) || (m == Month::October && d == 22)))
+        {
+            return false;
+        }
+
+        true
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS for Japan
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_japan {
+    use super::*;
+    use time::macros::datetime;
+
+    // Test to verify the name() method.
+    #[test]
+    fn test_name() {
+        let calendar = Japan;
+        assert_eq!(calendar.name(), "Japan");
+    }
+
+    // Test to verify if the is_business_day() method properly accounts for public holidays.
+    #[test]
+    fn test_is_public_holiday() {
+        let calendar = Japan;
+        let holidays = [
+            datetime!(2023-01-02 12:00:00 UTC), // Bank holiday
+            datetime!(2023-01-09 12:00:00 UTC), // Coming of Age Day
+            datetime!(2023-02-23 12:00:00 UTC), // Emperor's Birthday
+            datetime!(2023-03-21 12:00:00 UTC), // Vernal Equinox
+            datetime!(2023-05-03 12:00:00 UTC), // Constitution Memorial Day
+            datetime!(2023-07-17 12:00:00 UTC), // Marine Day
+            datetime!(2023-08-11 12:00:00 UTC), // Mountain Day
+            datetime!(2023-09-18 12:00:00 UTC), // Respect for the Aged Day
+            datetime!(2023-10-09 12:00:00 UTC), // Sports Day
+            datetime!(2023-11-03 12:00:00 UTC), // Culture Day
+            datetime!(2023-11-23 12:00:00 UTC), // Labour Thanksgiving Day
+            datetime!(2024-09-23 12:00:00 UTC), // Autumnal Equinox (observed)
+            datetime!(2026-05-06 12:00:00 UTC), // Substitute holiday
+            datetime!(2015-09-22 12:00:00 UTC), // Bridge holiday
+        ];
+
+        for date in holidays {
+            assert!(!calendar.is_business_day(date), "{date}");
+        }
+    }
+
+    // Test the equinoxes against the published dates, before and after 2000.
+    #[test]
+    fn test_equinoxes() {
+        assert_eq!(equinoxes(1981), (21, 23));
+        assert_eq!(equinoxes(1990), (21, 23));
+        assert_eq!(equinoxes(1997), (20, 23));
+        assert_eq!(equinoxes(2000), (20, 23));
+        assert_eq!(equinoxes(2012), (20, 22));
+        assert_eq!(equinoxes(2023), (21, 23));
+
+        let calendar = Japan;
+        assert!(!calendar.is_business_day(datetime!(1997-03-20 12:00:00 UTC)));
+        assert!(calendar.is_business_day(datetime!(1997-03-21 12:00:00 UTC)));
+        assert!(!calendar.is_business_day(datetime!(1990-03-21 12:00:00 UTC)));
+        assert!(!calendar.is_business_day(datetime!(1985-09-23 12:00:00 UTC)));
+    }
+
+    // Test to verify if the is_business_day() method properly accounts for regular business days.
+    #[test]
+    fn test_is_regular_business_day() {
+        let calendar = Japan;
+        let regular_day1 = datetime!(2023-03-15 12:00:00 UTC);
+        let regular_day2 = datetime!(2023-10-10 12:00:00 UTC);
+        let regular_day3 = datetime!(2023-12-22 12:00:00 UTC);
+
+        assert!(calendar.is_business_day(regular_day1));
+        assert!(calendar.is_business_day(regular_day2));
+        assert!(calendar.is_business_day(regular_day3));
+    }
+}
@@ -0,0 +1,101 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::time::{is_weekend, Calendar};
+use time::{Month, OffsetDateTime};
+
+/// TARGET (Trans-European Automated Real-time Gross settlement Express
+/// Transfer) calendar, used for EUR settlement.
+#[allow(clippy::upper_case_acronyms)]
+pub struct TARGET;
+
+impl Calendar for TARGET {
+    fn name(&self) -> &'static str {
+        "TARGET"
+    }
+
+    fn is_business_day(&self, date: OffsetDateTime) -> bool {
+        let d = date.day();
+        let m = date.month();
+        let y = date.year();
+        let dd = date.ordinal(); // Day of the year
+
+        let em = crate::time::easter_monday(y as usize, false);
+
+        if is_weekend(date)
+            // New Year's Day
+            || (d == 1 && m == Month::January)
+            // Good Friday
+            || (dd == em - 3 && y >= 2000)
+            // Easter Monday
+            || (dd == em && y >= 2000)
+            // Labour Day
+            || (d == 1 && m == Month::May && y >= 2000)
+            // Christmas
+            || (d == 25 && m == Month::December)
+            // Day of Goodwill
+            || (d == 26 && m == Month::December && y >= 2000)
+            // December 31st, 1998, 1999, and 2001 only
+            || (d == 31 && m == Month::December && (y == 1998 || y == 1999 || y == 2001))
+        {
+            return false;
+        }
+
+        true
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS for TARGET
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_target {
+    use super::*;
+    use time::macros::datetime;
+
+    // Test to verify the name() method.
+    #[test]
+    fn test_name() {
+        let calendar = TARGET;
+        assert_eq!(calendar.name(), "TARGET");
+    }
+
+    // Test to verify if the is_business_day() method properly accounts for public holidays.
+    #[test]
+    fn test_is_public_holiday() {
+        let calendar = TARGET;
+        let new_years_day = datetime!(2024-01-01 12:00:00 UTC);
+        let good_friday = datetime!(2024-03-29 12:00:00 UTC);
+        let easter_monday = datetime!(2024-04-01 12:00:00 UTC);
+        let labour_day = datetime!(2024-05-01 12:00:00 UTC);
+        let christmas = datetime!(2024-12-25 12:00:00 UTC);
+        let goodwill_day = datetime!(2024-12-26 12:00:00 UTC);
+
+        assert!(!calendar.is_business_day(new_years_day));
+        assert!(!calendar.is_business_day(good_friday));
+        assert!(!calendar.is_business_day(easter_monday));
+        assert!(!calendar.is_business_day(labour_day));
+        assert!(!calendar.is_business_day(christmas));
+        assert!(!calendar.is_business_day(goodwill_day));
+    }
+
+    // Test to verify if the is_business_day() method properly accounts for regular business days.
+    #[test]
+    fn test_is_regular_business_day() {
+        let calendar = TARGET;
+        let whit_monday = datetime!(2024-05-20 12:00:00 UTC);
+        let assumption = datetime!(2024-08-15 12:00:00 UTC);
+        let new_years_eve = datetime!(2024-12-31 12:00:00 UTC);
+
+        assert!(calendar.is_business_day(whit_monday));
+        assert!(calendar.is_business_day(assumption));
+        assert!(calendar.is_business_day(new_years_eve));
+    }
+}
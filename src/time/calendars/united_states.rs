@@ -72,6 +72,115 @@ impl Calendar for UnitedStates {
     }
 }
 
+/// United States government bond market calendar (SIFMA recommendations).
+pub struct UnitedStatesGovernmentBond;
+
+/// New York Stock Exchange (NYSE) calendar.
+pub struct NewYorkStockExchange;
+
+/// Good Friday is a full SIFMA holiday, except in years where it coincided
+/// with a non-farm payrolls release (early close instead).
+fn is_sifma_good_friday(dd: u16, y: i32) -> bool {
+    let em = crate::time::easter_monday(y as usize, false);
+
+    dd == em - 3 && y != 2012 && y != 2015 && y != 2021 && y != 2023
+}
+
+impl Calendar for UnitedStatesGovernmentBond {
+    fn name(&self) -> &'static str {
+        "United States government bond market (SIFMA)"
+    }
+
+    fn is_business_day(&self, date: OffsetDateTime) -> bool {
+        let w = date.weekday();
+        let d = date.day();
+        let m = date.month();
+        let y = date.year();
+
+        if is_weekend(date)
+            // New Year's Day (possibly moved to Monday if on Sunday)
+            || ((d == 1 || (d == 2 && w == Weekday::Monday)) && m == Month::January)
+            // Martin Luther King's birthday (third Monday in January)
+            || ((15..=21).contains(&d) && w == Weekday::Monday && m == Month::January && y >= 1983)
+            || is_washington_birthday(d, m, y, w)
+            || is_sifma_good_friday(date.ordinal(), y)
+            || is_memorial_day(d, m, y, w)
+            || is_juneteenth(d, m, y, w)
+            // Independence Day (Monday if Sunday or Friday if Saturday)
+            || ((d == 4 || (d == 5 && w == Weekday::Monday) || (d == 3 && w == Weekday::Friday))
+                && m == Month::July)
+            // Labor Day (first Monday in September)
+            || (d <= 7 && w == Weekday::Monday && m == Month::September)
+            // Columbus Day (second Monday in October)
+            || ((8..=14).contains(&d) && w == Weekday::Monday && m == Month::October && y >= 1971)
+            // Veterans' Day (Monday if Sunday, not moved if on Saturday)
+            || ((d == 11 || (d == 12 && w == Weekday::Monday)) && m == Month::November)
+            // Thanksgiving Day (fourth Thursday in November)
+            || ((22..=28).contains(&d) && w == Weekday::Thursday && m == Month::November)
+            // Christmas (Monday if Sunday or Friday if Saturday)
+            || ((d == 25 || (d == 26 && w == Weekday::Monday) || (d == 24 && w == Weekday::Friday))
+                && m == Month::December)
+            // National days of mourning
+            || (y == 2004 && m == Month::June && d == 11)
+            || (y == 2018 && m == Month::December && d == 5)
+            || (y == 2025 && m == Month::January && d == 9)
+        {
+            return false;
+        }
+
+        true
+    }
+}
+
+impl Calendar for NewYorkStockExchange {
+    fn name(&self) -> &'static str {
+        "New York Stock Exchange"
+    }
+
+    fn is_business_day(&self, date: OffsetDateTime) -> bool {
+        let w = date.weekday();
+        let d = date.day();
+        let m = date.month();
+        let y = date.year();
+        let dd = date.ordinal(); // Day of the year
+
+        let em = crate::time::easter_monday(y as usize, false);
+
+        if is_weekend(date)
+            // New Year's Day (possibly moved to Monday if on Sunday)
+            || ((d == 1 || (d == 2 && w == Weekday::Monday)) && m == Month::January)
+            // Martin Luther King's birthday (third Monday in January)
+            || ((15..=21).contains(&d) && w == Weekday::Monday && m == Month::January && y >= 1998)
+            || is_washington_birthday(d, m, y, w)
+            // Good Friday
+            || (dd == em - 3)
+            || is_memorial_day(d, m, y, w)
+            || is_juneteenth(d, m, y, w)
+            // Independence Day (Monday if Sunday or Friday if Saturday)
+            || ((d == 4 || (d == 5 && w == Weekday::Monday) || (d == 3 && w == Weekday::Friday))
+                && m == Month::July)
+            // Labor Day (first Monday in September)
+            || (d <= 7 && w == Weekday::Monday && m == Month::September)
+            // Thanksgiving Day (fourth Thursday in November)
+            || ((22..=28).contains(&d) && w == Weekday::Thursday && m == Month::November)
+            // Christmas (Monday if Sunday or Friday if Saturday)
+            || ((d == 25 || (d == 26 && w == Weekday::Monday) || (d == 24 && w == Weekday::Friday))
+                && m == Month::December)
+            // Special closings
+            || (y == 2001 && m == Month::September && (11..=14).contains(&d))
+            || (y == 2004 && m == Month::June && d == 11)
+            || (y == 2007 && m == Month::January && d == 2)
+            || (y == 2012 && m == Month::October && (d == 29 || d == 30))
+            || (y == 2018 && m == Month::December && d == 5)
+            || (y == 2025 && m == Month::January && d == 9)
+        {
+            return false;
+        }
+
+        true
+    }
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // UNIT TESTS for United States
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -134,3 +243,38 @@ mod test_united_states {
         assert!(calendar.is_business_day(regular_day3));
     }
 }
+
+#[cfg(test)]
+mod test_united_states_markets {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn test_government_bond_holidays() {
+        let calendar = UnitedStatesGovernmentBond;
+
+        // Columbus and Veterans' Day are SIFMA holidays.
+        assert!(!calendar.is_business_day(datetime!(2023-10-09 12:00:00 UTC)));
+        assert!(!calendar.is_business_day(datetime!(2024-11-11 12:00:00 UTC)));
+        // Good Friday 2024 is a holiday, 2023 was an early close.
+        assert!(!calendar.is_business_day(datetime!(2024-03-29 12:00:00 UTC)));
+        assert!(calendar.is_business_day(datetime!(2023-04-07 12:00:00 UTC)));
+        // Veterans' Day on a Saturday is not moved.
+        assert!(calendar.is_business_day(datetime!(2023-11-10 12:00:00 UTC)));
+    }
+
+    #[test]
+    fn test_nyse_holidays() {
+        let calendar = NewYorkStockExchange;
+
+        // Good Friday, and special closings.
+        assert!(!calendar.is_business_day(datetime!(2023-04-07 12:00:00 UTC)));
+        assert!(!calendar.is_business_day(datetime!(2012-10-29 12:00:00 UTC)));
+        assert!(!calendar.is_business_day(datetime!(2001-09-11 12:00:00 UTC)));
+        // Columbus and Veterans' Day are trading days.
+        assert!(calendar.is_business_day(datetime!(2023-10-09 12:00:00 UTC)));
+        assert!(calendar.is_business_day(datetime!(2024-11-11 12:00:00 UTC)));
+        // No Friday closing when New Year's Day falls on a Saturday.
+        assert!(calendar.is_business_day(datetime!(2021-12-31 12:00:00 UTC)));
+    }
+}
@@ -17,6 +17,7 @@
 /// time such that it falls in a business day, according with the
 /// same business calendar.
 /// """
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BusinessDayConvention {
     /// Actual: paid on the actual day, even if it is a non-business day.
     Actual,
@@ -61,21 +62,18 @@ pub enum BusinessDayConvention {
 /// present value. When a security such as a bond is sold between interest
 /// payment dates, the seller is eligible to some fraction of the coupon amount.
 /// """
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DayCountConvention {
     // TODO: Implement the following day count conventions.
-    // There are fiddly techicalities to consider, such as leap years.
-    // Also need some sort of calendar to determine which days are holidays, etc.
-    // Thirty360_BondBasis,
-    // Thirty360_US,
-    // ThirtyE360,
     // ThirtyE360_ISDA,
-    // ActualActual_ICMA,
-    // ActualActual_ISDA,
     // Actual365L,
     // ActualActual_AFB,
     // OneOne,
     //
-    /// Actual/365 day count convention.
+    // Actual/Actual (ICMA) and BUS/252 need a coupon frequency and a
+    // calendar respectively, see `ActualActualICMA` and `Business252`.
+    //
+    /// Actual/365 (Fixed) day count convention.
     Actual365,
 
     /// Actual/360 day count convention.
@@ -84,12 +82,18 @@ pub enum DayCountConvention {
     /// Actual/364 day count convention.
     Actual364,
 
-    /// Thirty/360 day count convention.
+    /// Actual/Actual (ISDA) day count convention.
+    ActualActualISDA,
+
+    /// 30E/360 (Eurobond basis) day count convention.
     Thirty360,
+
+    /// 30/360 US (bond basis) day count convention.
+    Thirty360US,
 }
 
 /// Interest payment frequency/year enumeration.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PaymentFrequency {
     /// Daily.
     Daily = 252,
@@ -0,0 +1,101 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Conversion of plain dates to evaluation dates.
+//!
+//! Most of the library works with `OffsetDateTime`, but daily finance data
+//! rarely has a time or a UTC offset. Functions taking an
+//! `impl IntoEvaluationDate` accept a `time::Date` (taken at midnight UTC),
+//! a `PrimitiveDateTime` (taken in UTC), or an `OffsetDateTime` (unchanged).
+//!
+//! ```
+//! use RustQuant::time::*;
+//! use time::macros::{date, datetime};
+//!
+//! assert_eq!(
+//!     date!(2024-03-08).into_evaluation_date(),
+//!     datetime!(2024-03-08 0:00 UTC)
+//! );
+//!
+//! // Plain dates can be used directly.
+//! let settlement = settlement_date(date!(2024-03-08), 2, &UnitedStates);
+//! assert_eq!(settlement.date(), date!(2024-03-12));
+//! ```
+
+use time::{Date, OffsetDateTime, PrimitiveDateTime};
+
+/// Conversion into an evaluation date (`OffsetDateTime`).
+pub trait IntoEvaluationDate {
+    /// Converts `self` into an `OffsetDateTime`.
+    fn into_evaluation_date(self) -> OffsetDateTime;
+}
+
+impl IntoEvaluationDate for OffsetDateTime {
+    fn into_evaluation_date(self) -> OffsetDateTime {
+        self
+    }
+}
+
+impl IntoEvaluationDate for Date {
+    /// Midnight UTC of the date.
+    fn into_evaluation_date(self) -> OffsetDateTime {
+        self.midnight().assume_utc()
+    }
+}
+
+impl IntoEvaluationDate for PrimitiveDateTime {
+    /// The date and time in UTC.
+    fn into_evaluation_date(self) -> OffsetDateTime {
+        self.assume_utc()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_date {
+    use super::*;
+    use crate::time::{BusinessDayConvention, StubRule, UnitedKingdom};
+    use crate::time::{DayCount, DayCountConvention, PaymentFrequency, Schedule};
+    use time::macros::datetime;
+
+    #[test]
+    fn test_into_evaluation_date() {
+        let expected = datetime!(2024-03-08 0:00 UTC);
+
+        assert_eq!(
+            datetime!(2024-03-08 0:00 UTC).date().into_evaluation_date(),
+            expected
+        );
+        assert_eq!(datetime!(2024-03-08 0:00).into_evaluation_date(), expected);
+        assert_eq!(expected.into_evaluation_date(), expected);
+    }
+
+    #[test]
+    fn test_plain_dates_in_apis() {
+        let schedule = Schedule::new(
+            datetime!(2024-01-15 0:00 UTC).date(),
+            datetime!(2025-01-15 0:00 UTC).date(),
+            PaymentFrequency::SemiAnnually,
+            &UnitedKingdom,
+            BusinessDayConvention::Following,
+            StubRule::ShortFront,
+        );
+        assert_eq!(schedule.periods.len(), 2);
+
+        let dc = DayCount::new(
+            datetime!(2024-01-01 0:00 UTC).date(),
+            datetime!(2024-07-01 0:00 UTC),
+            DayCountConvention::Actual360,
+        );
+        assert_eq!(dc.day_count_calendar, 182);
+    }
+}
@@ -9,19 +9,88 @@
 
 //! Module for computing day count factors.
 
-use super::conventions::DayCountConvention;
-use time::{Duration, OffsetDateTime};
+use super::conventions::{DayCountConvention, PaymentFrequency};
+use crate::error::{ensure, expect_valid, RustQuantError};
+use crate::time::{schedule::add_months, Calendar, IntoEvaluationDate};
+use time::{Duration, Month, OffsetDateTime};
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // STRUCTS, ENUMS, AND TRAITS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-/// Day counter struct.
+/// Day counter trait.
+/// Computes the number of days and the year fraction between two dates.
+/// If `d2` is before `d1`, the results are negative.
+/// Day counters are `Send + Sync`, so instruments holding one can be
+/// priced across threads.
+pub trait DayCounter: Send + Sync {
+    /// Name of the day count convention.
+    fn name(&self) -> &'static str;
+
+    /// Number of days between two dates.
+    fn day_count(&self, d1: OffsetDateTime, d2: OffsetDateTime) -> i64 {
+        (d2 - d1).whole_days()
+    }
+
+    /// Year fraction between two dates.
+    fn year_fraction(&self, d1: OffsetDateTime, d2: OffsetDateTime) -> f64;
+
+    /// Year fraction between two dates inside the coupon period
+    /// `[reference_start, reference_end]`.
+    /// Only Actual/Actual (ICMA) uses the reference period.
+    fn year_fraction_in_period(
+        &self,
+        d1: OffsetDateTime,
+        d2: OffsetDateTime,
+        _reference_start: OffsetDateTime,
+        _reference_end: OffsetDateTime,
+    ) -> f64 {
+        self.year_fraction(d1, d2)
+    }
+}
+
+/// Actual/360: actual days over 360.
+#[derive(Debug, Clone, Copy)]
+pub struct Actual360;
+
+/// Actual/365 (Fixed): actual days over 365.
+#[derive(Debug, Clone, Copy)]
+pub struct Actual365Fixed;
+
+/// Actual/Actual (ISDA): the days in each calendar year are divided by the
+/// length of that year (365 or 366).
+#[derive(Debug, Clone, Copy)]
+pub struct ActualActualISDA;
+
+/// Actual/Actual (ICMA): the days in each coupon period are divided by the
+/// days in the period times the coupon frequency, so every regular coupon
+/// period has a year fraction of exactly `1 / frequency`.
+#[derive(Debug, Clone, Copy)]
+pub struct ActualActualICMA {
+    /// Coupon frequency (must be a whole number of months).
+    pub frequency: PaymentFrequency,
+}
+
+/// 30/360 US (bond basis), with the end of February rules.
+#[derive(Debug, Clone, Copy)]
+pub struct Thirty360US;
+
+/// 30E/360 (Eurobond basis).
+#[derive(Debug, Clone, Copy)]
+pub struct ThirtyE360;
+
+/// BUS/252: business days over 252 (used in Brazil).
+pub struct Business252 {
+    /// Calendar used to count the business days.
+    pub calendar: Box<dyn Calendar>,
+}
+
+/// Day count struct.
 /// This struct is used to compute:
 ///     - Day count factor.
 ///     - Business day count.
 ///     - Calendar day count.
-pub struct DayCounter {
+pub struct DayCount {
     /// Day count factor (fraction of year between two dates).
     pub day_count_factor: f64,
     /// Business day count.
@@ -36,43 +105,259 @@ pub struct DayCounter {
     pub convention: DayCountConvention,
 }
 
-/// Trait for converting a month to a isize.
-/// Needed so that we can perform arithmetic on months.
-trait MonthNumeric {
-    fn as_isize(&self) -> isize;
-}
-
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // FUNCTIONS/METHODS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-impl MonthNumeric for time::Month {
-    fn as_isize(&self) -> isize {
-        match *self {
-            time::Month::January => 1,
-            time::Month::February => 2,
-            time::Month::March => 3,
-            time::Month::April => 4,
-            time::Month::May => 5,
-            time::Month::June => 6,
-            time::Month::July => 7,
-            time::Month::August => 8,
-            time::Month::September => 9,
-            time::Month::October => 10,
-            time::Month::November => 11,
-            time::Month::December => 12,
+/// Days between two dates under the 30/360 family of conventions,
+/// given the already adjusted days of the month.
+fn thirty360_days(d1: OffsetDateTime, d2: OffsetDateTime, day1: i64, day2: i64) -> i64 {
+    360 * (d2.year() - d1.year()) as i64
+        + 30 * (d2.month() as i64 - d1.month() as i64)
+        + (day2 - day1)
+}
+
+/// Checks if the date is the last day of February.
+fn is_end_of_february(date: OffsetDateTime) -> bool {
+    date.month() == Month::February && date.day() == Month::February.length(date.year())
+}
+
+impl DayCounter for Actual360 {
+    fn name(&self) -> &'static str {
+        "Actual/360"
+    }
+
+    fn year_fraction(&self, d1: OffsetDateTime, d2: OffsetDateTime) -> f64 {
+        self.day_count(d1, d2) as f64 / 360.0
+    }
+}
+
+impl DayCounter for Actual365Fixed {
+    fn name(&self) -> &'static str {
+        "Actual/365 (Fixed)"
+    }
+
+    fn year_fraction(&self, d1: OffsetDateTime, d2: OffsetDateTime) -> f64 {
+        self.day_count(d1, d2) as f64 / 365.0
+    }
+}
+
+impl DayCounter for ActualActualISDA {
+    fn name(&self) -> &'static str {
+        "Actual/Actual (ISDA)"
+    }
+
+    fn year_fraction(&self, d1: OffsetDateTime, d2: OffsetDateTime) -> f64 {
+        if d2 < d1 {
+            return -self.year_fraction(d2, d1);
+        }
+
+        let (y1, y2) = (d1.year(), d2.year());
+        let days_in_year = |y: i32| time::util::days_in_year(y) as f64;
+
+        if y1 == y2 {
+            return self.day_count(d1, d2) as f64 / days_in_year(y1);
+        }
+
+        // Days to the end of the first year, whole years in between,
+        // and days from the start of the last year.
+        let first = (days_in_year(y1) - d1.ordinal() as f64 + 1.0) / days_in_year(y1);
+        let last = (d2.ordinal() as f64 - 1.0) / days_in_year(y2);
+
+        first + (y2 - y1 - 1) as f64 + last
+    }
+}
+
+impl ActualActualICMA {
+    /// New Actual/Actual (ICMA) day counter for the given coupon frequency.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the frequency is not a whole number of months per period.
+    pub fn new(frequency: PaymentFrequency) -> Self {
+        expect_valid(Self::try_new(frequency))
+    }
+
+    /// New Actual/Actual (ICMA) day counter for the given coupon frequency,
+    /// or an error if it is not a whole number of months per period.
+    pub fn try_new(frequency: PaymentFrequency) -> Result<Self, RustQuantError> {
+        ensure(
+            matches!(
+                frequency,
+                PaymentFrequency::Monthly
+                    | PaymentFrequency::SemiQuarterly
+                    | PaymentFrequency::Quarterly
+                    | PaymentFrequency::TriAnnually
+                    | PaymentFrequency::SemiAnnually
+                    | PaymentFrequency::Annually
+            ),
+            "Actual/Actual (ICMA) needs a whole number of months per period.",
+        )?;
+
+        Ok(Self { frequency })
+    }
+
+    fn months(&self) -> i64 {
+        12 / self.frequency as i64
+    }
+}
+
+impl DayCounter for ActualActualICMA {
+    fn name(&self) -> &'static str {
+        "Actual/Actual (ICMA)"
+    }
+
+    /// Without a reference period, regular coupon periods are rolled
+    /// backward from `d2` (i.e. any irregular period is at the front).
+    fn year_fraction(&self, d1: OffsetDateTime, d2: OffsetDateTime) -> f64 {
+        if d2 < d1 {
+            return -self.year_fraction(d2, d1);
+        }
+
+        let frequency = self.frequency as i64 as f64;
+        let mut fraction = 0.0;
+        let mut k = 0;
+
+        loop {
+            let reference_end = add_months(d2, -k * self.months());
+            let reference_start = add_months(d2, -(k + 1) * self.months());
+
+            if reference_start <= d1 {
+                fraction += self.day_count(d1, reference_end) as f64
+                    / self.day_count(reference_start, reference_end) as f64
+                    / frequency;
+                return fraction;
+            }
+
+            fraction += 1.0 / frequency;
+            k += 1;
+        }
+    }
+
+    fn year_fraction_in_period(
+        &self,
+        d1: OffsetDateTime,
+        d2: OffsetDateTime,
+        reference_start: OffsetDateTime,
+        reference_end: OffsetDateTime,
+    ) -> f64 {
+        self.day_count(d1, d2) as f64
+            / self.day_count(reference_start, reference_end) as f64
+            / self.frequency as i64 as f64
+    }
+}
+
+impl DayCounter for Thirty360US {
+    fn name(&self) -> &'static str {
+        "30/360 US"
+    }
+
+    fn day_count(&self, d1: OffsetDateTime, d2: OffsetDateTime) -> i64 {
+        let (mut day1, mut day2) = (d1.day() as i64, d2.day() as i64);
+
+        if is_end_of_february(d1) {
+            if is_end_of_february(d2) {
+                day2 = 30;
+            }
+            day1 = 30;
         }
+        if day2 == 31 && day1 >= 30 {
+            day2 = 30;
+        }
+        if day1 == 31 {
+            day1 = 30;
+        }
+
+        thirty360_days(d1, d2, day1, day2)
+    }
+
+    fn year_fraction(&self, d1: OffsetDateTime, d2: OffsetDateTime) -> f64 {
+        self.day_count(d1, d2) as f64 / 360.0
+    }
+}
+
+impl DayCounter for ThirtyE360 {
+    fn name(&self) -> &'static str {
+        "30E/360"
+    }
+
+    fn day_count(&self, d1: OffsetDateTime, d2: OffsetDateTime) -> i64 {
+        thirty360_days(d1, d2, d1.day().min(30) as i64, d2.day().min(30) as i64)
+    }
+
+    fn year_fraction(&self, d1: OffsetDateTime, d2: OffsetDateTime) -> f64 {
+        self.day_count(d1, d2) as f64 / 360.0
     }
 }
 
-impl DayCounter {
-    /// New day counter.
-    pub fn new(start: OffsetDateTime, end: OffsetDateTime, convention: DayCountConvention) -> Self {
+impl Business252 {
+    /// New BUS/252 day counter using the given calendar.
+    pub fn new(calendar: Box<dyn Calendar>) -> Self {
+        Self { calendar }
+    }
+}
+
+impl DayCounter for Business252 {
+    fn name(&self) -> &'static str {
+        "Business/252"
+    }
+
+    fn day_count(&self, d1: OffsetDateTime, d2: OffsetDateTime) -> i64 {
+        self.calendar.business_days_between(d1, d2)
+    }
+
+    fn year_fraction(&self, d1: OffsetDateTime, d2: OffsetDateTime) -> f64 {
+        self.day_count(d1, d2) as f64 / 252.0
+    }
+}
+
+impl DayCounter for DayCountConvention {
+    fn name(&self) -> &'static str {
+        match self {
+            DayCountConvention::Actual365 => Actual365Fixed.name(),
+            DayCountConvention::Actual360 => Actual360.name(),
+            DayCountConvention::Actual364 => "Actual/364",
+            DayCountConvention::ActualActualISDA => ActualActualISDA.name(),
+            DayCountConvention::Thirty360 => ThirtyE360.name(),
+            DayCountConvention::Thirty360US => Thirty360US.name(),
+        }
+    }
+
+    fn day_count(&self, d1: OffsetDateTime, d2: OffsetDateTime) -> i64 {
+        match self {
+            DayCountConvention::Thirty360 => ThirtyE360.day_count(d1, d2),
+            DayCountConvention::Thirty360US => Thirty360US.day_count(d1, d2),
+            _ => (d2 - d1).whole_days(),
+        }
+    }
+
+    fn year_fraction(&self, d1: OffsetDateTime, d2: OffsetDateTime) -> f64 {
+        match self {
+            DayCountConvention::Actual365 => Actual365Fixed.year_fraction(d1, d2),
+            DayCountConvention::Actual360 => Actual360.year_fraction(d1, d2),
+            DayCountConvention::Actual364 => self.day_count(d1, d2) as f64 / 364.0,
+            DayCountConvention::ActualActualISDA => ActualActualISDA.year_fraction(d1, d2),
+            DayCountConvention::Thirty360 => ThirtyE360.year_fraction(d1, d2),
+            DayCountConvention::Thirty360US => Thirty360US.year_fraction(d1, d2),
+        }
+    }
+}
+
+impl DayCount {
+    /// New day count.
+    pub fn new(
+        start: impl IntoEvaluationDate,
+        end: impl IntoEvaluationDate,
+        convention: DayCountConvention,
+    ) -> Self {
+        let start = start.into_evaluation_date();
+        let end = end.into_evaluation_date();
+
         let day_count_factor = Self::day_count_factor(start, end, &convention);
         let day_count_business = Self::day_count_business(start, end);
         let day_count_calendar = Self::day_count_calendar(start, end);
 
-        DayCounter {
+        DayCount {
             day_count_factor,
             day_count_business,
             day_count_calendar,
@@ -92,7 +377,7 @@ impl DayCounter {
     ///
     /// # Arguments
     ///
-    /// * `start` - The start date (optional and defaults to today).
+    /// * `start` - The start date.
     /// * `end` - The end date.
     /// * `convention` - The day count convention.
     pub fn day_count_factor(
@@ -100,43 +385,12 @@ impl DayCounter {
         end: OffsetDateTime,
         convention: &DayCountConvention,
     ) -> f64 {
-        // THIS FUNCTION NEEDS WORK.
-
-        // let start = start.unwrap_or(OffsetDateTime::now_utc());
-
-        let start_month = start.month().as_isize();
-        let end_month = end.month().as_isize();
-
-        let days = (end - start).whole_days() as f64;
-        let months = (end_month - start_month) as f64;
-        let years = (end.year() - start.year()) as f64;
-
-        match convention {
-            DayCountConvention::Actual365 => days / 365.0,
-            DayCountConvention::Actual364 => days / 364.0,
-            DayCountConvention::Actual360 => days / 360.0,
-            DayCountConvention::Thirty360 => {
-                ((30 - start.day()).max(0) as f64
-                    + (end.day()).min(30) as f64
-                    + 360.0 * years
-                    + 30.0 * (months - 1.0))
-                    / 360.0
-            } // DayCountConvention::Thirty360_BondBasis => {}
-              // DayCountConvention::Thirty360_US => {}
-              // DayCountConvention::ThirtyE360 => {}
-              // DayCountConvention::ThirtyE360_ISDA => {}
-              // DayCountConvention::ActualActual_ICMA => {}
-              // DayCountConvention::ActualActual_ISDA => {}
-              // DayCountConvention::Actual365L => {}
-              // DayCountConvention::ActualActual_AFB => {}
-              // DayCountConvention::OneOne => {}
-        }
+        convention.year_fraction(start, end)
     }
 
     /// Compute the business day count between two dates.
     /// This is the number of days between two dates, excluding weekends.
-    /// Obviously, for this to be really useful, we need to have a calendar
-    /// that tells us which days are weekends, and also which are holidays.
+    /// Use [`Calendar::business_days_between`] to also exclude holidays.
     pub fn day_count_business(mut start: OffsetDateTime, end: OffsetDateTime) -> i64 {
         let mut count = 0;
         while start <= end {
@@ -163,12 +417,11 @@ impl DayCounter {
 #[cfg(test)]
 mod test_daycount {
     use super::*;
-    use crate::assert_approx_equal;
     use time::macros::datetime;
 
     #[test]
     fn test_daycount_factor() {
-        let mut dc = DayCounter::new(
+        let mut dc = DayCount::new(
             datetime!(2022-01-01 0:00 UTC),
             datetime!(2023-06-02 0:00 UTC),
             DayCountConvention::Actual365,
@@ -194,7 +447,7 @@ mod test_daycount {
         let start = datetime!(2022-01-01 0:00 UTC);
         let end = datetime!(2023-06-02 0:00 UTC);
 
-        let dc = DayCounter::new(start, end, DayCountConvention::Actual365);
+        let dc = DayCount::new(start, end, DayCountConvention::Actual365);
 
         assert_eq!(dc.day_count_business, 370);
         assert_eq!(dc.day_count_calendar, 517);
@@ -203,7 +456,7 @@ mod test_daycount {
     fn test_thirty360_convention_same_day_same_month_different_years() {
         let start_date = datetime!(2022-02-15 0:00 UTC);
         let end_date = datetime!(2023-02-15 0:00 UTC);
-        let result = DayCounter::new(start_date, end_date, DayCountConvention::Thirty360);
+        let result = DayCount::new(start_date, end_date, DayCountConvention::Thirty360);
         assert_approx_equal!(result.day_count_factor, 1.0, 1e-6);
     }
 
@@ -211,7 +464,7 @@ mod test_daycount {
     fn test_thirty360_convention_same_day_different_month_same_year() {
         let start_date = datetime!(2023-05-15 0:00 UTC);
         let end_date = datetime!(2023-11-15 0:00 UTC);
-        let result = DayCounter::new(start_date, end_date, DayCountConvention::Thirty360);
+        let result = DayCount::new(start_date, end_date, DayCountConvention::Thirty360);
         assert_approx_equal!(result.day_count_factor, 0.5, 1e-6);
     }
 
@@ -219,7 +472,7 @@ mod test_daycount {
     fn test_thirty360_convention_different_day_same_month_same_year() {
         let start_date = datetime!(2023-09-15 0:00 UTC);
         let end_date = datetime!(2023-09-30 0:00 UTC);
-        let result = DayCounter::new(start_date, end_date, DayCountConvention::Thirty360);
+        let result = DayCount::new(start_date, end_date, DayCountConvention::Thirty360);
         assert_approx_equal!(result.day_count_factor, 0.041667, 1e-6);
     }
 
@@ -227,7 +480,7 @@ mod test_daycount {
     fn test_thirty360_convention_31_day_same_month_same_year() {
         let start_date = datetime!(2023-10-15 0:00 UTC);
         let end_date = datetime!(2023-10-31 0:00 UTC);
-        let result = DayCounter::new(start_date, end_date, DayCountConvention::Thirty360);
+        let result = DayCount::new(start_date, end_date, DayCountConvention::Thirty360);
         assert_approx_equal!(result.day_count_factor, 0.041667, 1e-6);
     }
 
@@ -235,7 +488,7 @@ mod test_daycount {
     fn test_thirty360_convention_different_day_different_month_same_year() {
         let start_date = datetime!(2023-03-15 0:00 UTC);
         let end_date = datetime!(2023-08-31 0:00 UTC);
-        let result = DayCounter::new(start_date, end_date, DayCountConvention::Thirty360);
+        let result = DayCount::new(start_date, end_date, DayCountConvention::Thirty360);
         assert_approx_equal!(result.day_count_factor, 0.458333, 1e-6);
     }
 
@@ -243,7 +496,7 @@ mod test_daycount {
     fn test_thirty360_convention_end_day_less_than_start_day_same_month() {
         let start_date = datetime!(2023-07-30 0:00 UTC);
         let end_date = datetime!(2023-07-15 0:00 UTC);
-        let result = DayCounter::new(start_date, end_date, DayCountConvention::Thirty360);
+        let result = DayCount::new(start_date, end_date, DayCountConvention::Thirty360);
         assert_approx_equal!(result.day_count_factor, -0.041667, 1e-6);
     }
 
@@ -251,7 +504,7 @@ mod test_daycount {
     fn test_thirty360_convention_end_day_less_than_start_day_different_month() {
         let start_date = datetime!(2023-07-30 0:00 UTC);
         let end_date = datetime!(2023-12-15 0:00 UTC);
-        let result = DayCounter::new(start_date, end_date, DayCountConvention::Thirty360);
+        let result = DayCount::new(start_date, end_date, DayCountConvention::Thirty360);
         assert_approx_equal!(result.day_count_factor, 0.375, 1e-6);
     }
 
@@ -259,7 +512,109 @@ mod test_daycount {
     fn test_thirty360_convention_end_month_less_than_start_month() {
         let start_date = datetime!(2023-06-30 0:00 UTC);
         let end_date = datetime!(2023-04-15 0:00 UTC);
-        let result = DayCounter::new(start_date, end_date, DayCountConvention::Thirty360);
+        let result = DayCount::new(start_date, end_date, DayCountConvention::Thirty360);
         assert_approx_equal!(result.day_count_factor, -0.208333, 1e-6);
     }
+
+    #[test]
+    fn test_actual_conventions() {
+        let d1 = datetime!(2023-11-15 0:00 UTC);
+        let d2 = datetime!(2024-05-15 0:00 UTC);
+
+        assert_approx_equal!(Actual360.year_fraction(d1, d2), 182.0 / 360.0, 1e-12);
+        assert_approx_equal!(Actual365Fixed.year_fraction(d1, d2), 182.0 / 365.0, 1e-12);
+        // 47 days in 2023, 135 days in (leap) 2024.
+        assert_approx_equal!(
+            ActualActualISDA.year_fraction(d1, d2),
+            47.0 / 365.0 + 135.0 / 366.0,
+            1e-12
+        );
+        assert_approx_equal!(
+            ActualActualISDA.year_fraction(d2, d1),
+            -ActualActualISDA.year_fraction(d1, d2),
+            1e-12
+        );
+        assert_approx_equal!(
+            ActualActualISDA.year_fraction(
+                datetime!(2022-03-01 0:00 UTC),
+                datetime!(2025-03-01 0:00 UTC)
+            ),
+            3.0,
+            1e-12
+        );
+    }
+
+    #[test]
+    fn test_actual_actual_icma() {
+        let dc = ActualActualICMA::new(PaymentFrequency::SemiAnnually);
+
+        // Regular periods are exactly half a year, whatever their length.
+        let d1 = datetime!(2023-11-15 0:00 UTC);
+        let d2 = datetime!(2024-05-15 0:00 UTC);
+        assert_approx_equal!(dc.year_fraction(d1, d2), 0.5, 1e-12);
+        assert_approx_equal!(dc.year_fraction_in_period(d1, d2, d1, d2), 0.5, 1e-12);
+
+        // Accrual over part of a period.
+        let settlement = datetime!(2024-02-15 0:00 UTC);
+        assert_approx_equal!(
+            dc.year_fraction_in_period(d1, settlement, d1, d2),
+            92.0 / 182.0 / 2.0,
+            1e-12
+        );
+
+        // A front stub plus a full period.
+        assert_approx_equal!(dc.year_fraction(settlement, d2), 0.5 * 90.0 / 182.0, 1e-12);
+        assert_approx_equal!(
+            dc.year_fraction(settlement, datetime!(2024-11-15 0:00 UTC)),
+            0.5 + 0.5 * 90.0 / 182.0,
+            1e-12
+        );
+    }
+
+    #[test]
+    fn test_actual_actual_icma_rejects_weekly_coupons() {
+        assert!(ActualActualICMA::try_new(PaymentFrequency::Weekly).is_err());
+        assert!(ActualActualICMA::try_new(PaymentFrequency::Quarterly).is_ok());
+    }
+
+    #[test]
+    fn test_thirty360_us_and_e() {
+        // End of month in both conventions.
+        let d1 = datetime!(2023-10-15 0:00 UTC);
+        let d2 = datetime!(2023-10-31 0:00 UTC);
+        assert_eq!(Thirty360US.day_count(d1, d2), 16);
+        assert_eq!(ThirtyE360.day_count(d1, d2), 15);
+
+        let d1 = datetime!(2023-03-31 0:00 UTC);
+        assert_eq!(Thirty360US.day_count(d1, d2), 210);
+        assert_eq!(ThirtyE360.day_count(d1, d2), 210);
+
+        // End of February.
+        let d1 = datetime!(2024-02-29 0:00 UTC);
+        let d2 = datetime!(2024-08-31 0:00 UTC);
+        assert_eq!(Thirty360US.day_count(d1, d2), 180);
+        assert_eq!(ThirtyE360.day_count(d1, d2), 181);
+        assert_approx_equal!(Thirty360US.year_fraction(d1, d2), 0.5, 1e-12);
+    }
+
+    #[test]
+    fn test_business_252() {
+        let dc = Business252::new(Box::new(crate::time::UnitedStates));
+
+        // 20 business days in December 2023 in the US.
+        let d1 = datetime!(2023-12-01 0:00 UTC);
+        let d2 = datetime!(2024-01-01 0:00 UTC);
+        assert_eq!(dc.day_count(d1, d2), 20);
+        assert_approx_equal!(dc.year_fraction(d1, d2), 20.0 / 252.0, 1e-12);
+    }
+
+    #[test]
+    fn test_convention_enum_dispatch() {
+        let d1 = datetime!(2023-10-15 0:00 UTC);
+        let d2 = datetime!(2023-10-31 0:00 UTC);
+
+        assert_eq!(DayCountConvention::Thirty360.day_count(d1, d2), 15);
+        assert_eq!(DayCountConvention::Thirty360US.day_count(d1, d2), 16);
+        assert_eq!(DayCountConvention::Actual360.name(), "Actual/360");
+    }
 }
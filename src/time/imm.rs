@@ -0,0 +1,322 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Standard market dates:
+//!     - IMM dates: the third Wednesday of a month (Mar/Jun/Sep/Dec for the
+//!       main cycle), used for interest rate futures.
+//!     - IMM codes: a month letter and the last digit of the year, e.g. "H4".
+//!     - CDS roll dates: the 20th of Mar/Jun/Sep/Dec.
+//!     - Equity expiries: the third Friday of the month.
+//!
+//! Dates keep the time and offset of the reference date they are computed from.
+
+use crate::time::{nth_weekday, IntoEvaluationDate};
+use time::{Date, Month, OffsetDateTime, Weekday};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// CONSTANTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Futures month codes, January to December.
+const MONTH_CODES: [char; 12] = ['F', 'G', 'H', 'J', 'K', 'M', 'N', 'Q', 'U', 'V', 'X', 'Z'];
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// HELPERS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Checks if the month is in the quarterly (Mar/Jun/Sep/Dec) cycle.
+fn is_quarterly_month(month: Month) -> bool {
+    matches!(
+        month,
+        Month::March | Month::June | Month::September | Month::December
+    )
+}
+
+/// The reference date moved to the given day, keeping its time and offset.
+fn on_day(reference: OffsetDateTime, year: i32, month: Month, day: u8) -> OffsetDateTime {
+    reference.replace_date(Date::from_calendar_date(year, month, day).expect("Invalid date."))
+}
+
+/// Month index (`year * 12 + month - 1`) of a date.
+fn month_index(date: OffsetDateTime) -> i32 {
+    date.year() * 12 + date.month() as i32 - 1
+}
+
+/// Year and month from a month index.
+fn from_month_index(index: i32) -> (i32, Month) {
+    let month = Month::try_from((index.rem_euclid(12) + 1) as u8).unwrap();
+
+    (index.div_euclid(12), month)
+}
+
+/// First date strictly after (`step = 1`) or before (`step = -1`) `date`,
+/// among the dates `day_of(year, month)` of the eligible months.
+fn roll_monthly<F, G>(date: OffsetDateTime, step: i32, eligible: F, day_of: G) -> OffsetDateTime
+where
+    F: Fn(Month) -> bool,
+    G: Fn(i32, Month) -> u8,
+{
+    let mut index = month_index(date);
+
+    loop {
+        let (year, month) = from_month_index(index);
+
+        if eligible(month) {
+            let candidate = on_day(date, year, month, day_of(year, month));
+
+            if (step > 0 && candidate > date) || (step < 0 && candidate < date) {
+                return candidate;
+            }
+        }
+
+        index += step;
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMM DATES
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Checks if the date is an IMM date (third Wednesday of the month).
+/// With `main_cycle`, only Mar/Jun/Sep/Dec dates are IMM dates.
+pub fn is_imm_date(date: impl IntoEvaluationDate, main_cycle: bool) -> bool {
+    let date = date.into_evaluation_date();
+
+    (!main_cycle || is_quarterly_month(date.month()))
+        && date.weekday() == Weekday::Wednesday
+        && (15..=21).contains(&date.day())
+}
+
+/// Next IMM date strictly after the given date.
+pub fn next_imm_date(date: impl IntoEvaluationDate, main_cycle: bool) -> OffsetDateTime {
+    let date = date.into_evaluation_date();
+
+    roll_monthly(
+        date,
+        1,
+        |m| !main_cycle || is_quarterly_month(m),
+        |y, m| nth_weekday(3, Weekday::Wednesday, m, y),
+    )
+}
+
+/// Previous IMM date strictly before the given date.
+pub fn previous_imm_date(date: impl IntoEvaluationDate, main_cycle: bool) -> OffsetDateTime {
+    let date = date.into_evaluation_date();
+
+    roll_monthly(
+        date,
+        -1,
+        |m| !main_cycle || is_quarterly_month(m),
+        |y, m| nth_weekday(3, Weekday::Wednesday, m, y),
+    )
+}
+
+/// IMM code of a date (e.g. "H4" for March 2024).
+/// Returns `None` if the date is not an IMM date.
+pub fn imm_code(date: impl IntoEvaluationDate) -> Option<String> {
+    let date = date.into_evaluation_date();
+
+    if !is_imm_date(date, false) {
+        return None;
+    }
+
+    let letter = MONTH_CODES[date.month() as usize - 1];
+
+    Some(format!("{}{}", letter, date.year().rem_euclid(10)))
+}
+
+/// IMM date of an IMM code (e.g. "H4"), on or after the reference date.
+///
+/// The code only gives the last digit of the year, so the year is taken
+/// in the reference date's decade, or the next one if the IMM date would
+/// be before the reference date.
+/// Returns `None` if the code is invalid.
+pub fn imm_date_from_code(
+    code: &str,
+    reference: impl IntoEvaluationDate,
+) -> Option<OffsetDateTime> {
+    let reference = reference.into_evaluation_date();
+
+    let mut chars = code.trim().chars();
+
+    let letter = chars.next()?.to_ascii_uppercase();
+    let digit = chars.next()?.to_digit(10)? as i32;
+
+    if chars.next().is_some() {
+        return None;
+    }
+
+    let month_number = MONTH_CODES.iter().position(|&c| c == letter)? as u8 + 1;
+    let month = Month::try_from(month_number).ok()?;
+
+    let imm_date = |year: i32| {
+        on_day(
+            reference,
+            year,
+            month,
+            nth_weekday(3, Weekday::Wednesday, month, year),
+        )
+    };
+
+    let year = reference.year() - reference.year().rem_euclid(10) + digit;
+    let date = imm_date(year);
+
+    if date < reference.replace_time(time::Time::MIDNIGHT) {
+        Some(imm_date(year + 10))
+    } else {
+        Some(date)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// CDS ROLL DATES
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Checks if the date is a CDS standard roll date (20th of Mar/Jun/Sep/Dec).
+pub fn is_cds_date(date: impl IntoEvaluationDate) -> bool {
+    let date = date.into_evaluation_date();
+
+    is_quarterly_month(date.month()) && date.day() == 20
+}
+
+/// Next CDS standard roll date strictly after the given date.
+pub fn next_cds_date(date: impl IntoEvaluationDate) -> OffsetDateTime {
+    let date = date.into_evaluation_date();
+
+    roll_monthly(date, 1, is_quarterly_month, |_, _| 20)
+}
+
+/// Previous CDS standard roll date strictly before the given date.
+pub fn previous_cds_date(date: impl IntoEvaluationDate) -> OffsetDateTime {
+    let date = date.into_evaluation_date();
+
+    roll_monthly(date, -1, is_quarterly_month, |_, _| 20)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// EQUITY EXPIRIES
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Third Friday of the month (standard listed equity option expiry),
+/// at the time and offset of the reference date.
+pub fn third_friday(reference: impl IntoEvaluationDate, month: Month, year: i32) -> OffsetDateTime {
+    let reference = reference.into_evaluation_date();
+
+    on_day(
+        reference,
+        year,
+        month,
+        nth_weekday(3, Weekday::Friday, month, year),
+    )
+}
+
+/// Next monthly equity expiry (third Friday) strictly after the given date.
+/// With `quarterly`, only Mar/Jun/Sep/Dec expiries are considered.
+pub fn next_equity_expiry(date: impl IntoEvaluationDate, quarterly: bool) -> OffsetDateTime {
+    let date = date.into_evaluation_date();
+
+    roll_monthly(
+        date,
+        1,
+        |m| !quarterly || is_quarterly_month(m),
+        |y, m| nth_weekday(3, Weekday::Friday, m, y),
+    )
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_imm {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn test_imm_dates() {
+        assert!(is_imm_date(datetime!(2024-03-20 0:00 UTC), true));
+        assert!(is_imm_date(datetime!(2024-01-17 0:00 UTC), false));
+        assert!(!is_imm_date(datetime!(2024-01-17 0:00 UTC), true));
+        assert!(!is_imm_date(datetime!(2024-03-13 0:00 UTC), false));
+
+        let date = datetime!(2024-03-20 0:00 UTC);
+        assert_eq!(next_imm_date(date, true), datetime!(2024-06-19 0:00 UTC));
+        assert_eq!(next_imm_date(date, false), datetime!(2024-04-17 0:00 UTC));
+        assert_eq!(
+            previous_imm_date(date, true),
+            datetime!(2023-12-20 0:00 UTC)
+        );
+
+        let date = datetime!(2024-12-25 0:00 UTC);
+        assert_eq!(next_imm_date(date, true), datetime!(2025-03-19 0:00 UTC));
+    }
+
+    #[test]
+    fn test_imm_codes() {
+        assert_eq!(
+            imm_code(datetime!(2024-03-20 0:00 UTC)).as_deref(),
+            Some("H4")
+        );
+        assert_eq!(
+            imm_code(datetime!(2025-12-17 0:00 UTC)).as_deref(),
+            Some("Z5")
+        );
+        assert_eq!(imm_code(datetime!(2024-03-21 0:00 UTC)), None);
+
+        let reference = datetime!(2024-05-01 0:00 UTC);
+        assert_eq!(
+            imm_date_from_code("M4", reference),
+            Some(datetime!(2024-06-19 0:00 UTC))
+        );
+        // March 2024 has passed, so "H4" is March 2034.
+        assert_eq!(
+            imm_date_from_code("H4", reference),
+            Some(datetime!(2034-03-15 0:00 UTC))
+        );
+        assert_eq!(
+            imm_date_from_code("z3", reference),
+            Some(datetime!(2033-12-21 0:00 UTC))
+        );
+        assert_eq!(imm_date_from_code("A4", reference), None);
+        assert_eq!(imm_date_from_code("H", reference), None);
+        assert_eq!(imm_date_from_code("H44", reference), None);
+    }
+
+    #[test]
+    fn test_cds_dates() {
+        assert!(is_cds_date(datetime!(2024-06-20 0:00 UTC)));
+        assert!(!is_cds_date(datetime!(2024-07-20 0:00 UTC)));
+
+        let date = datetime!(2024-06-20 0:00 UTC);
+        assert_eq!(next_cds_date(date), datetime!(2024-09-20 0:00 UTC));
+        assert_eq!(previous_cds_date(date), datetime!(2024-03-20 0:00 UTC));
+        assert_eq!(
+            next_cds_date(datetime!(2024-12-21 0:00 UTC)),
+            datetime!(2025-03-20 0:00 UTC)
+        );
+    }
+
+    #[test]
+    fn test_equity_expiries() {
+        let reference = datetime!(2024-01-01 16:00 UTC);
+
+        assert_eq!(
+            third_friday(reference, Month::March, 2024),
+            datetime!(2024-03-15 16:00 UTC)
+        );
+        assert_eq!(
+            next_equity_expiry(datetime!(2024-03-15 16:00 UTC), false),
+            datetime!(2024-04-19 16:00 UTC)
+        );
+        assert_eq!(
+            next_equity_expiry(datetime!(2024-03-16 0:00 UTC), true),
+            datetime!(2024-06-21 0:00 UTC)
+        );
+    }
+}
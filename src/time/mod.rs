@@ -11,11 +11,18 @@
 
 pub use crate::time::{
     calendar::*,
-    calendars::{australia::*, austria::*, canada::*, united_kingdom::*, united_states::*},
+    calendars::{
+        australia::*, austria::*, canada::*, custom::*, japan::*, target::*, united_kingdom::*,
+        united_states::*,
+    },
     constants::*,
     conventions::*,
+    date::*,
     daycount::*,
+    imm::*,
     schedule::*,
+    settlement::*,
+    tenor::*,
 };
 
 /// Calendar definitions.
@@ -24,10 +31,18 @@ pub mod calendar;
 pub mod constants;
 /// Day count and business day conventions.
 pub mod conventions;
+/// Conversion of plain dates to evaluation dates.
+pub mod date;
 /// Daycount definitions.
 pub mod daycount;
+/// IMM, CDS roll and equity expiry dates.
+pub mod imm;
 /// Scheduling definitions.
 pub mod schedule;
+/// Settlement date helpers.
+pub mod settlement;
+/// Tenor definitions and parsing.
+pub mod tenor;
 
 /// Calendar definitions for settlement purposes.
 pub mod calendars {
@@ -37,8 +52,14 @@ pub mod calendars {
     pub mod austria;
     /// Canadian settlement calendar.
     pub mod canada;
+    /// Custom (user-defined) holiday calendars.
+    pub mod custom;
+    /// Japanese settlement calendar.
+    pub mod japan;
+    /// TARGET (EUR) settlement calendar.
+    pub mod target;
     /// UK settlement calendar.
     pub mod united_kingdom;
-    /// USA settlement calendar.
+    /// USA settlement, government bond (SIFMA) and NYSE calendars.
     pub mod united_states;
 }
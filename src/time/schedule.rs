@@ -7,13 +7,53 @@
 //      - LICENSE-MIT.md
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
-use crate::time::{BusinessDayConvention, DayCountConvention, PaymentFrequency};
-use time::{Duration, OffsetDateTime};
+use crate::time::{
+    BusinessDayConvention, Calendar, DayCountConvention, IntoEvaluationDate, PaymentFrequency,
+};
+use time::{Duration, Month, OffsetDateTime};
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // STRUCTS, ENUMS, AND TRAITS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
+/// Stub rule: where the irregular period goes when the schedule
+/// dates do not divide evenly into whole periods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StubRule {
+    /// Dates are rolled backward from the end date,
+    /// leaving a short first period.
+    #[default]
+    ShortFront,
+
+    /// Dates are rolled backward from the end date,
+    /// merging the short first period into the next one.
+    LongFront,
+
+    /// Dates are rolled forward from the start date,
+    /// leaving a short last period.
+    ShortBack,
+
+    /// Dates are rolled forward from the start date,
+    /// merging the short last period into the previous one.
+    LongBack,
+}
+
+/// A single accrual period of a schedule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SchedulePeriod {
+    /// Adjusted start date of the period.
+    pub start: OffsetDateTime,
+
+    /// Adjusted end date of the period.
+    pub end: OffsetDateTime,
+
+    /// Payment date of the period.
+    pub payment: OffsetDateTime,
+
+    /// Whether the period is an irregular (stub) period.
+    pub is_stub: bool,
+}
+
 /// Schedule struct.
 ///
 /// Many financial instruments have a schedule of dates associated with them.
@@ -46,6 +86,9 @@ pub struct Schedule {
 
     /// The business day convention of the schedule.
     pub business_day_convention: BusinessDayConvention,
+
+    /// The accrual periods of the schedule.
+    pub periods: Vec<SchedulePeriod>,
 }
 
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -53,6 +96,121 @@ pub struct Schedule {
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 
 impl Schedule {
+    /// Generates a schedule of periodic dates between `start` and `end`.
+    ///
+    /// Unadjusted dates are rolled from the end date (front stubs) or the
+    /// start date (back stubs) in whole multiples of the period, so
+    /// month-end dates do not drift. Every date is then adjusted with the
+    /// calendar and business day convention, and each period is paid on
+    /// its adjusted end date.
+    ///
+    /// ```
+    /// use RustQuant::time::*;
+    /// use time::macros::datetime;
+    ///
+    /// let schedule = Schedule::new(
+    ///     datetime!(2024-01-15 0:0:0 UTC),
+    ///     datetime!(2025-03-15 0:0:0 UTC),
+    ///     PaymentFrequency::Quarterly,
+    ///     &UnitedStates,
+    ///     BusinessDayConvention::ModifiedFollowing,
+    ///     StubRule::ShortFront,
+    /// );
+    ///
+    /// // Short front stub from 15 Jan to 15 Mar 2024, then four quarters.
+    /// assert_eq!(schedule.periods.len(), 5);
+    /// assert!(schedule.periods[0].is_stub);
+    /// ```
+    pub fn new(
+        start: impl IntoEvaluationDate,
+        end: impl IntoEvaluationDate,
+        frequency: PaymentFrequency,
+        calendar: &dyn Calendar,
+        convention: BusinessDayConvention,
+        stub: StubRule,
+    ) -> Schedule {
+        let start = start.into_evaluation_date();
+        let end = end.into_evaluation_date();
+
+        assert!(start < end, "Start date must be before the end date.");
+
+        let mut unadjusted = Vec::new();
+
+        match stub {
+            StubRule::ShortFront | StubRule::LongFront => {
+                let mut i = 0;
+                loop {
+                    let date = roll(end, frequency, -i);
+                    if date <= start {
+                        break;
+                    }
+                    unadjusted.push(date);
+                    i += 1;
+                }
+                unadjusted.push(start);
+                unadjusted.reverse();
+
+                if stub == StubRule::LongFront
+                    && unadjusted.len() > 2
+                    && is_irregular(&unadjusted[..2], frequency)
+                {
+                    unadjusted.remove(1);
+                }
+            }
+            StubRule::ShortBack | StubRule::LongBack => {
+                let mut i = 0;
+                loop {
+                    let date = roll(start, frequency, i);
+                    if date >= end {
+                        break;
+                    }
+                    unadjusted.push(date);
+                    i += 1;
+                }
+                unadjusted.push(end);
+
+                let n = unadjusted.len();
+                if stub == StubRule::LongBack
+                    && n > 2
+                    && is_irregular(&unadjusted[n - 2..], frequency)
+                {
+                    unadjusted.remove(n - 2);
+                }
+            }
+        }
+
+        let dates: Vec<OffsetDateTime> = unadjusted
+            .iter()
+            .map(|&date| calendar.adjust(date, &convention))
+            .collect();
+
+        let periods = unadjusted
+            .windows(2)
+            .zip(dates.windows(2))
+            .map(|(raw, adjusted)| SchedulePeriod {
+                start: adjusted[0],
+                end: adjusted[1],
+                payment: adjusted[1],
+                is_stub: is_irregular(raw, frequency),
+            })
+            .collect();
+
+        Schedule {
+            dates,
+            start: Some(start),
+            end: Some(end),
+            frequency: Some(frequency),
+            day_count_convention: DayCountConvention::Actual365,
+            business_day_convention: convention,
+            periods,
+        }
+    }
+
+    /// Payment dates of the schedule's periods.
+    pub fn payment_dates(&self) -> Vec<OffsetDateTime> {
+        self.periods.iter().map(|period| period.payment).collect()
+    }
+
     /// Creates a new schedule from a given start date, period length, and number
     /// of periods in the schedule.
     pub fn new_from_start(start: OffsetDateTime, period: Duration, num_periods: i64) -> Schedule {
@@ -65,6 +223,7 @@ impl Schedule {
         }
 
         Schedule {
+            periods: periods_from_dates(&payments),
             dates: payments,
             start: Some(start),
             end: Some(current_time),
@@ -89,6 +248,7 @@ impl Schedule {
         payments.reverse();
 
         Schedule {
+            periods: periods_from_dates(&payments),
             dates: payments,
             start: Some(current_time),
             end: Some(end),
@@ -104,6 +264,7 @@ impl Schedule {
         assert!(&dates.windows(2).all(|window| window[0] < window[1]));
 
         Schedule {
+            periods: periods_from_dates(&dates),
             dates: dates.clone(),
             start: Some(dates[0]),
             end: Some(dates[dates.len() - 1]),
@@ -117,9 +278,61 @@ impl Schedule {
     pub fn drop(&mut self, date: OffsetDateTime) {
         // let date = date.midnight_at(UtcOffset::UTC); // Convert to OffsetDateTime for comparison
         self.dates.retain(|&payment| payment.date() != date.date());
+        self.periods = periods_from_dates(&self.dates);
+    }
+}
+
+/// Unadjusted periods between consecutive dates, paid at the period end.
+fn periods_from_dates(dates: &[OffsetDateTime]) -> Vec<SchedulePeriod> {
+    dates
+        .windows(2)
+        .map(|window| SchedulePeriod {
+            start: window[0],
+            end: window[1],
+            payment: window[1],
+            is_stub: false,
+        })
+        .collect()
+}
+
+/// Whether the period between two unadjusted dates is shorter or longer
+/// than a regular period.
+fn is_irregular(dates: &[OffsetDateTime], frequency: PaymentFrequency) -> bool {
+    roll(dates[0], frequency, 1) != dates[1]
+}
+
+/// Rolls `date` by `n` periods of the given frequency.
+///
+/// Month based frequencies keep the day of the month, clamped to the end of
+/// the month (e.g. 31 Jan + 1M = 29 Feb in a leap year). Daily periods are
+/// calendar days (adjustment is left to the calendar).
+fn roll(date: OffsetDateTime, frequency: PaymentFrequency, n: i64) -> OffsetDateTime {
+    match frequency {
+        PaymentFrequency::Daily => date + Duration::days(n),
+        PaymentFrequency::Weekly => date + Duration::weeks(n),
+        PaymentFrequency::BiWeekly => date + Duration::weeks(2 * n),
+        // Half months: whole months plus 15 days for odd periods.
+        PaymentFrequency::SemiMonthly => {
+            add_months(date, n.div_euclid(2)) + Duration::days(15 * n.rem_euclid(2))
+        }
+        _ => add_months(date, n * 12 / frequency as i64),
     }
 }
 
+/// Adds `n` months to a date, clamping the day to the end of the month.
+pub(crate) fn add_months(date: OffsetDateTime, n: i64) -> OffsetDateTime {
+    let months = date.year() as i64 * 12 + (date.month() as i64 - 1) + n;
+    let year = months.div_euclid(12) as i32;
+    let month = Month::try_from((months.rem_euclid(12) + 1) as u8).unwrap();
+    let day = date.day().min(month.length(year));
+
+    date.replace_day(1)
+        .and_then(|d| d.replace_year(year))
+        .and_then(|d| d.replace_month(month))
+        .and_then(|d| d.replace_day(day))
+        .expect("Invalid schedule date.")
+}
+
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
 // UNIT TESTS
 // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
@@ -127,6 +340,7 @@ impl Schedule {
 #[cfg(test)]
 mod test_schedule {
     use super::*;
+    use crate::time::{UnitedKingdom, UnitedStates};
     use time::macros::datetime;
 
     #[test]
@@ -213,4 +427,111 @@ mod test_schedule {
             ]
         );
     }
+
+    #[test]
+    fn test_new_short_front_stub() {
+        let schedule = Schedule::new(
+            datetime!(2024-01-15 0:0:0 UTC),
+            datetime!(2025-03-15 0:0:0 UTC),
+            PaymentFrequency::Quarterly,
+            &UnitedStates,
+            BusinessDayConvention::ModifiedFollowing,
+            StubRule::ShortFront,
+        );
+
+        assert_eq!(
+            schedule.dates,
+            vec![
+                datetime!(2024-01-16 0:0:0 UTC), // 15 Jan 2024 is MLK day.
+                datetime!(2024-03-15 0:0:0 UTC),
+                datetime!(2024-06-17 0:0:0 UTC), // 15 Jun 2024 is a Saturday.
+                datetime!(2024-09-16 0:0:0 UTC), // 15 Sep 2024 is a Sunday.
+                datetime!(2024-12-16 0:0:0 UTC), // 15 Dec 2024 is a Sunday.
+                datetime!(2025-03-17 0:0:0 UTC), // 15 Mar 2025 is a Saturday.
+            ]
+        );
+        assert!(schedule.periods[0].is_stub);
+        assert!(schedule.periods[1..].iter().all(|p| !p.is_stub));
+        assert_eq!(schedule.payment_dates()[4], datetime!(2025-03-17 0:0:0 UTC));
+    }
+
+    #[test]
+    fn test_new_long_front_stub() {
+        let schedule = Schedule::new(
+            datetime!(2024-01-15 0:0:0 UTC),
+            datetime!(2025-03-15 0:0:0 UTC),
+            PaymentFrequency::SemiAnnually,
+            &UnitedStates,
+            BusinessDayConvention::Actual,
+            StubRule::LongFront,
+        );
+
+        // The short stub to 15 Mar 2024 is merged into the first period.
+        assert_eq!(
+            schedule.dates,
+            vec![
+                datetime!(2024-01-15 0:0:0 UTC),
+                datetime!(2024-09-15 0:0:0 UTC),
+                datetime!(2025-03-15 0:0:0 UTC),
+            ]
+        );
+        assert!(schedule.periods[0].is_stub);
+        assert!(!schedule.periods[1].is_stub);
+    }
+
+    #[test]
+    fn test_new_back_stubs() {
+        let start = datetime!(2024-01-31 0:0:0 UTC);
+        let end = datetime!(2024-05-15 0:0:0 UTC);
+
+        let short = Schedule::new(
+            start,
+            end,
+            PaymentFrequency::Monthly,
+            &UnitedStates,
+            BusinessDayConvention::Actual,
+            StubRule::ShortBack,
+        );
+
+        // Month ends are rolled from the start date, without drifting.
+        assert_eq!(
+            short.dates,
+            vec![
+                datetime!(2024-01-31 0:0:0 UTC),
+                datetime!(2024-02-29 0:0:0 UTC),
+                datetime!(2024-03-31 0:0:0 UTC),
+                datetime!(2024-04-30 0:0:0 UTC),
+                datetime!(2024-05-15 0:0:0 UTC),
+            ]
+        );
+        assert!(short.periods[3].is_stub);
+
+        let long = Schedule::new(
+            start,
+            end,
+            PaymentFrequency::Monthly,
+            &UnitedStates,
+            BusinessDayConvention::Actual,
+            StubRule::LongBack,
+        );
+
+        assert_eq!(long.periods.len(), 3);
+        assert_eq!(long.periods[2].start, datetime!(2024-03-31 0:0:0 UTC));
+        assert!(long.periods[2].is_stub);
+    }
+
+    #[test]
+    fn test_new_regular_schedule() {
+        let schedule = Schedule::new(
+            datetime!(2023-03-15 0:0:0 UTC),
+            datetime!(2025-03-15 0:0:0 UTC),
+            PaymentFrequency::Annually,
+            &UnitedKingdom,
+            BusinessDayConvention::Following,
+            StubRule::ShortBack,
+        );
+
+        assert_eq!(schedule.periods.len(), 2);
+        assert!(schedule.periods.iter().all(|p| !p.is_stub));
+    }
 }
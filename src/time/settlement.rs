@@ -0,0 +1,218 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Settlement date helpers.
+//!
+//! Trades settle a number of business days (the spot lag) after the trade
+//! date, e.g. T+1 for US equities and T+2 for most bonds and FX pairs.
+//!
+//! ```
+//! use RustQuant::time::*;
+//! use time::macros::datetime;
+//!
+//! // Friday trade, settling T+2 on Tuesday.
+//! let trade_date = datetime!(2024-03-08 0:00 UTC);
+//! let settlement = settlement_date(trade_date, 2, &UnitedStates);
+//!
+//! assert_eq!(settlement, datetime!(2024-03-12 0:00 UTC));
+//! ```
+
+use crate::money::Currency;
+use crate::time::{Calendar, IntoEvaluationDate};
+use time::{Duration, OffsetDateTime};
+
+/// Moves a date by `n` business days of the calendar
+/// (backwards if `n` is negative).
+pub fn add_business_days(
+    date: impl IntoEvaluationDate,
+    n: i64,
+    calendar: &dyn Calendar,
+) -> OffsetDateTime {
+    let date = date.into_evaluation_date();
+
+    calendar.advance_business_days(date, n)
+}
+
+/// Settlement date of a trade: `spot_lag` business days after the trade
+/// date (the trade date itself is rolled to a business day first).
+pub fn settlement_date(
+    trade_date: impl IntoEvaluationDate,
+    spot_lag: i64,
+    calendar: &dyn Calendar,
+) -> OffsetDateTime {
+    let trade_date = trade_date.into_evaluation_date();
+
+    assert!(spot_lag >= 0, "Spot lag must be non-negative.");
+
+    add_business_days(trade_date, spot_lag, calendar)
+}
+
+/// Spot lag (in business days) of an FX pair.
+///
+/// Spot is T+2, except for USD against CAD, TRY, RUB and PHP, which settle T+1.
+pub fn fx_spot_lag(base: &Currency, quote: &Currency) -> i64 {
+    const T_PLUS_ONE: [&str; 4] = ["CAD", "TRY", "RUB", "PHP"];
+
+    let (base, quote) = (base.code.alphabetic, quote.code.alphabetic);
+
+    let is_t_plus_one = (base == "USD" && T_PLUS_ONE.contains(&quote))
+        || (quote == "USD" && T_PLUS_ONE.contains(&base));
+
+    if is_t_plus_one {
+        1
+    } else {
+        2
+    }
+}
+
+/// FX spot date of a currency pair.
+///
+/// The spot lag is counted in business days of the non-USD currencies
+/// (a USD holiday on an intermediate day does not delay spot), and the spot
+/// date is then rolled forward to a business day of both currencies.
+pub fn fx_spot_date(
+    trade_date: impl IntoEvaluationDate,
+    base: &Currency,
+    quote: &Currency,
+    base_calendar: &dyn Calendar,
+    quote_calendar: &dyn Calendar,
+) -> OffsetDateTime {
+    let trade_date = trade_date.into_evaluation_date();
+
+    let is_usd = |currency: &Currency| currency.code.alphabetic == "USD";
+
+    // Calendars used to count the spot lag.
+    let mut counting: Vec<&dyn Calendar> = Vec::with_capacity(2);
+    if !is_usd(base) {
+        counting.push(base_calendar);
+    }
+    if !is_usd(quote) {
+        counting.push(quote_calendar);
+    }
+
+    let is_counting_day = |date: OffsetDateTime| {
+        counting
+            .iter()
+            .all(|calendar| calendar.is_business_day(date))
+    };
+    let is_good_day = |date: OffsetDateTime| {
+        base_calendar.is_business_day(date) && quote_calendar.is_business_day(date)
+    };
+
+    let mut date = trade_date;
+    while !is_counting_day(date) {
+        date += Duration::days(1);
+    }
+
+    for _ in 0..fx_spot_lag(base, quote) {
+        date += Duration::days(1);
+        while !is_counting_day(date) {
+            date += Duration::days(1);
+        }
+    }
+
+    while !is_good_day(date) {
+        date += Duration::days(1);
+    }
+
+    date
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_settlement {
+    use super::*;
+    use crate::money::{CAD, EUR, GBP, USD};
+    use crate::time::{Canada, UnitedKingdom, UnitedStates, TARGET};
+    use time::macros::datetime;
+
+    #[test]
+    fn test_settlement_date() {
+        let calendar = UnitedStates;
+
+        // T+1 over the Independence Day holiday.
+        let trade_date = datetime!(2024-07-03 0:00 UTC);
+        assert_eq!(
+            settlement_date(trade_date, 1, &calendar),
+            datetime!(2024-07-05 0:00 UTC)
+        );
+
+        // Trade on a Saturday is rolled to Monday first.
+        assert_eq!(
+            settlement_date(datetime!(2024-07-06 0:00 UTC), 0, &calendar),
+            datetime!(2024-07-08 0:00 UTC)
+        );
+
+        assert_eq!(
+            add_business_days(datetime!(2024-07-08 0:00 UTC), -2, &calendar),
+            datetime!(2024-07-03 0:00 UTC)
+        );
+    }
+
+    #[test]
+    fn test_fx_spot_lag() {
+        assert_eq!(fx_spot_lag(&USD, &CAD), 1);
+        assert_eq!(fx_spot_lag(&CAD, &USD), 1);
+        assert_eq!(fx_spot_lag(&EUR, &USD), 2);
+        assert_eq!(fx_spot_lag(&EUR, &CAD), 2);
+    }
+
+    #[test]
+    fn test_fx_spot_date() {
+        // EUR/USD traded on 2 July 2024: spot would be 4 July, a USD holiday,
+        // so it is 5 July. On 3 July, 4 July counts as a EUR business day.
+        assert_eq!(
+            fx_spot_date(
+                datetime!(2024-07-02 0:00 UTC),
+                &EUR,
+                &USD,
+                &TARGET,
+                &UnitedStates
+            ),
+            datetime!(2024-07-05 0:00 UTC)
+        );
+        assert_eq!(
+            fx_spot_date(
+                datetime!(2024-07-03 0:00 UTC),
+                &EUR,
+                &USD,
+                &TARGET,
+                &UnitedStates
+            ),
+            datetime!(2024-07-05 0:00 UTC)
+        );
+
+        // EUR/GBP over the UK Spring Bank Holiday (27 May 2024).
+        assert_eq!(
+            fx_spot_date(
+                datetime!(2024-05-23 0:00 UTC),
+                &EUR,
+                &GBP,
+                &TARGET,
+                &UnitedKingdom
+            ),
+            datetime!(2024-05-28 0:00 UTC)
+        );
+
+        // USD/CAD is T+1.
+        assert_eq!(
+            fx_spot_date(
+                datetime!(2024-03-07 0:00 UTC),
+                &USD,
+                &CAD,
+                &UnitedStates,
+                &Canada
+            ),
+            datetime!(2024-03-08 0:00 UTC)
+        );
+    }
+}
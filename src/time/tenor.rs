@@ -0,0 +1,236 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Tenors (periods of time) such as "3M", "10Y" or "2W".
+//!
+//! Adding a tenor to a date is plain calendar arithmetic (months are added
+//! keeping the day of the month, clamped to the end of the month).
+//! For business day aware arithmetic, use [`Calendar::advance`](crate::time::Calendar::advance).
+//!
+//! ```
+//! use RustQuant::time::*;
+//! use time::macros::datetime;
+//!
+//! let tenor: Tenor = "3M".parse().unwrap();
+//!
+//! assert_eq!(datetime!(2024-11-30 0:00 UTC) + tenor, datetime!(2025-02-28 0:00 UTC));
+//! assert_eq!(tenor.to_string(), "3M");
+//! ```
+
+use crate::time::schedule::add_months;
+use std::fmt;
+use std::ops::{Add, Neg, Sub};
+use std::str::FromStr;
+use thiserror::Error;
+use time::{Duration, OffsetDateTime};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Unit of a tenor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimeUnit {
+    /// Days ("D").
+    Days,
+
+    /// Weeks ("W").
+    Weeks,
+
+    /// Months ("M").
+    Months,
+
+    /// Years ("Y").
+    Years,
+}
+
+/// Tenor: a length of time in days, weeks, months or years.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Tenor {
+    /// Number of units (may be negative).
+    pub length: i64,
+
+    /// Unit of the tenor.
+    pub unit: TimeUnit,
+}
+
+/// Tenor parsing error.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum TenorError {
+    /// The string is not a valid tenor.
+    #[error("Invalid tenor: {0}")]
+    Invalid(String),
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl TimeUnit {
+    /// Symbol of the unit.
+    pub fn symbol(&self) -> char {
+        match self {
+            TimeUnit::Days => 'D',
+            TimeUnit::Weeks => 'W',
+            TimeUnit::Months => 'M',
+            TimeUnit::Years => 'Y',
+        }
+    }
+}
+
+impl Tenor {
+    /// New tenor.
+    pub fn new(length: i64, unit: TimeUnit) -> Self {
+        Self { length, unit }
+    }
+
+    /// Tenor of `n` days.
+    pub fn days(n: i64) -> Self {
+        Self::new(n, TimeUnit::Days)
+    }
+
+    /// Tenor of `n` weeks.
+    pub fn weeks(n: i64) -> Self {
+        Self::new(n, TimeUnit::Weeks)
+    }
+
+    /// Tenor of `n` months.
+    pub fn months(n: i64) -> Self {
+        Self::new(n, TimeUnit::Months)
+    }
+
+    /// Tenor of `n` years.
+    pub fn years(n: i64) -> Self {
+        Self::new(n, TimeUnit::Years)
+    }
+
+    /// Approximate length of the tenor in years
+    /// (365 days or 52 weeks or 12 months per year).
+    pub fn in_years(&self) -> f64 {
+        let length = self.length as f64;
+
+        match self.unit {
+            TimeUnit::Days => length / 365.0,
+            TimeUnit::Weeks => length / 52.0,
+            TimeUnit::Months => length / 12.0,
+            TimeUnit::Years => length,
+        }
+    }
+}
+
+impl FromStr for Tenor {
+    type Err = TenorError;
+
+    /// Parses tenors like "3M", "10Y", "2W" or "1D" (case insensitive).
+    /// "ON" (overnight) is parsed as one day.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || TenorError::Invalid(s.to_string());
+        let tenor = s.trim().to_ascii_uppercase();
+
+        if tenor == "ON" {
+            return Ok(Tenor::days(1));
+        }
+
+        let unit = match tenor.chars().last().ok_or_else(invalid)? {
+            'D' => TimeUnit::Days,
+            'W' => TimeUnit::Weeks,
+            'M' => TimeUnit::Months,
+            'Y' => TimeUnit::Years,
+            _ => return Err(invalid()),
+        };
+
+        let length = tenor[..tenor.len() - 1]
+            .parse::<i64>()
+            .map_err(|_| invalid())?;
+
+        Ok(Tenor::new(length, unit))
+    }
+}
+
+impl fmt::Display for Tenor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.length, self.unit.symbol())
+    }
+}
+
+impl Neg for Tenor {
+    type Output = Tenor;
+
+    fn neg(self) -> Self::Output {
+        Tenor::new(-self.length, self.unit)
+    }
+}
+
+impl Add<Tenor> for OffsetDateTime {
+    type Output = OffsetDateTime;
+
+    /// Unadjusted date plus tenor.
+    fn add(self, tenor: Tenor) -> Self::Output {
+        match tenor.unit {
+            TimeUnit::Days => self + Duration::days(tenor.length),
+            TimeUnit::Weeks => self + Duration::weeks(tenor.length),
+            TimeUnit::Months => add_months(self, tenor.length),
+            TimeUnit::Years => add_months(self, 12 * tenor.length),
+        }
+    }
+}
+
+impl Sub<Tenor> for OffsetDateTime {
+    type Output = OffsetDateTime;
+
+    /// Unadjusted date minus tenor.
+    fn sub(self, tenor: Tenor) -> Self::Output {
+        self + (-tenor)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod test_tenor {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn test_parse_tenor() {
+        assert_eq!("3M".parse(), Ok(Tenor::months(3)));
+        assert_eq!("10Y".parse(), Ok(Tenor::years(10)));
+        assert_eq!("2w".parse(), Ok(Tenor::weeks(2)));
+        assert_eq!(" 1D ".parse(), Ok(Tenor::days(1)));
+        assert_eq!("ON".parse(), Ok(Tenor::days(1)));
+        assert_eq!("-6M".parse(), Ok(Tenor::months(-6)));
+
+        assert!("M".parse::<Tenor>().is_err());
+        assert!("3X".parse::<Tenor>().is_err());
+        assert!("".parse::<Tenor>().is_err());
+        assert_eq!(
+            "1.5Y".parse::<Tenor>(),
+            Err(TenorError::Invalid("1.5Y".to_string()))
+        );
+
+        assert_eq!(Tenor::years(10).to_string(), "10Y");
+        assert_approx_equal!(Tenor::months(18).in_years(), 1.5, 1e-12);
+    }
+
+    #[test]
+    fn test_tenor_date_arithmetic() {
+        let date = datetime!(2024-01-31 0:00 UTC);
+
+        assert_eq!(date + Tenor::months(1), datetime!(2024-02-29 0:00 UTC));
+        assert_eq!(date + Tenor::years(1), datetime!(2025-01-31 0:00 UTC));
+        assert_eq!(date + Tenor::weeks(2), datetime!(2024-02-14 0:00 UTC));
+        assert_eq!(date - Tenor::days(31), datetime!(2023-12-31 0:00 UTC));
+        assert_eq!(
+            datetime!(2024-02-29 0:00 UTC) + Tenor::years(1),
+            datetime!(2025-02-28 0:00 UTC)
+        );
+    }
+}
@@ -17,6 +17,7 @@ pub struct Order {
     pub is_buy: bool,
     pub shares: u64,
     pub limit: u64,
+    #[allow(dead_code)]
     pub timestamp: u64,
 }
 
@@ -0,0 +1,319 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::curves::YieldCurve;
+use crate::instruments::bonds::CouponBond;
+use crate::instruments::termsheets::DayCountFraction;
+use crate::instruments::Instrument;
+use crate::money::Rounding;
+use crate::time::{BusinessDayConvention, DayCountConvention, PaymentFrequency};
+use crate::verification::{
+    verify, Quantity, ReferenceValue, Source, Tolerance, ToleranceConfig, VerificationReport,
+};
+use std::collections::BTreeMap;
+use time::macros::date;
+use time::{Date, OffsetDateTime};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// A fixed-coupon bullet bond and its market data.
+///
+/// Textbook examples pay coupons on unadjusted dates, so the bond is built
+/// without business day adjustment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BondCase {
+    /// Date the bond is priced on.
+    pub evaluation_date: Date,
+    /// Maturity date.
+    pub maturity_date: Date,
+    /// Annual coupon rate.
+    pub coupon_rate: f64,
+    /// Coupon frequency.
+    pub coupon_frequency: PaymentFrequency,
+    /// Face value.
+    pub face_value: f64,
+    /// Day count of the coupons and of the yield.
+    pub day_count: DayCountFraction,
+    /// Continuously compounded zero rates of the discount curve, by date
+    /// (for price references). The shortest rate is extended back to the
+    /// evaluation date.
+    pub zero_rates: Vec<(Date, f64)>,
+    /// Day count of the discount curve.
+    pub curve_day_count: DayCountConvention,
+    /// Dirty price (for yield references).
+    pub price: Option<f64>,
+    /// Settlement date (for accrued interest references).
+    pub settlement_date: Option<Date>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl BondCase {
+    /// New bond case, on a flat zero curve, without a price or a settlement
+    /// date.
+    pub fn new(
+        evaluation_date: Date,
+        maturity_date: Date,
+        coupon_rate: f64,
+        coupon_frequency: PaymentFrequency,
+        face_value: f64,
+        day_count: DayCountFraction,
+    ) -> Self {
+        Self {
+            evaluation_date,
+            maturity_date,
+            coupon_rate,
+            coupon_frequency,
+            face_value,
+            day_count,
+            zero_rates: Vec::new(),
+            curve_day_count: DayCountConvention::Actual365,
+            price: None,
+            settlement_date: None,
+        }
+    }
+
+    /// Sets the zero rates and day count of the discount curve.
+    pub fn with_zero_rates(
+        mut self,
+        zero_rates: Vec<(Date, f64)>,
+        curve_day_count: DayCountConvention,
+    ) -> Self {
+        self.zero_rates = zero_rates;
+        self.curve_day_count = curve_day_count;
+        self
+    }
+
+    /// Sets the dirty price.
+    pub fn with_price(mut self, price: f64) -> Self {
+        self.price = Some(price);
+        self
+    }
+
+    /// Sets the settlement date.
+    pub fn with_settlement_date(mut self, settlement_date: Date) -> Self {
+        self.settlement_date = Some(settlement_date);
+        self
+    }
+
+    /// The discount curve, starting on the evaluation date (flat at zero
+    /// without zero rates).
+    pub fn yield_curve(&self) -> YieldCurve {
+        let mut rates: BTreeMap<OffsetDateTime, f64> = self
+            .zero_rates
+            .iter()
+            .map(|(date, rate)| (date.midnight().assume_utc(), *rate))
+            .collect();
+
+        let first = rates.values().next().copied().unwrap_or(0.0);
+        rates
+            .entry(self.evaluation_date.midnight().assume_utc())
+            .or_insert(first);
+
+        YieldCurve::new(rates).with_day_count_convention(self.curve_day_count)
+    }
+
+    /// The bond, with its coupons constructed.
+    pub fn to_coupon_bond(&self) -> CouponBond {
+        let mut bond = CouponBond {
+            evaluation_date: self.evaluation_date.midnight().assume_utc(),
+            expiration_date: self.maturity_date.midnight().assume_utc(),
+            currency: None,
+            coupon_rate: self.coupon_rate,
+            coupon_frequency: self.coupon_frequency,
+            settlement_convention: BusinessDayConvention::Actual,
+            day_counter: self.day_count.day_counter(self.coupon_frequency),
+            yield_curve: self.yield_curve(),
+            face_value: self.face_value,
+            rounding: Rounding::None,
+            coupons: BTreeMap::new(),
+        };
+        bond.construct_coupons();
+
+        bond
+    }
+
+    /// Computes a quantity with [`CouponBond`].
+    pub fn evaluate(&self, quantity: Quantity) -> Result<f64, String> {
+        let bond = self.to_coupon_bond();
+
+        match quantity {
+            Quantity::Price => Ok(bond.price().value()),
+            Quantity::Yield => {
+                let price = self.price.ok_or("no price to compute the yield from")?;
+
+                bond.yield_to_maturity(price)
+                    .map_err(|error| error.to_string())
+            }
+            Quantity::AccruedInterest => {
+                let settlement_date = self
+                    .settlement_date
+                    .ok_or("no settlement date to accrue the interest to")?;
+
+                Ok(bond.accrued_interest(settlement_date.midnight().assume_utc()))
+            }
+            _ => Err(format!("{quantity} is not a bond quantity")),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Reference prices, yields and accrued interest of coupon bonds.
+///
+/// The textbook examples measure time in exact half years, which 30E/360
+/// reproduces on dates falling on the 15th.
+pub fn bond_references() -> Vec<ReferenceValue<BondCase>> {
+    let start = date!(2024 - 01 - 15);
+    let semiannual = |maturity, coupon_rate, face_value| {
+        BondCase::new(
+            start,
+            maturity,
+            coupon_rate,
+            PaymentFrequency::SemiAnnually,
+            face_value,
+            DayCountFraction::ThirtyE360,
+        )
+    };
+
+    // Zero curve bootstrapped in Hull's Table 4.3.
+    let bootstrapped = vec![
+        (date!(2024 - 04 - 15), 0.10127),
+        (date!(2024 - 07 - 15), 0.10469),
+        (date!(2025 - 01 - 15), 0.10536),
+        (date!(2025 - 07 - 15), 0.10681),
+        (date!(2026 - 01 - 15), 0.10808),
+    ];
+
+    vec![
+        ReferenceValue {
+            case: "2-year 6% bond on zero rates of 5.0% to 6.8%",
+            inputs: semiannual(date!(2026 - 01 - 15), 0.06, 100.0).with_zero_rates(
+                vec![
+                    (date!(2024 - 07 - 15), 0.050),
+                    (date!(2025 - 01 - 15), 0.058),
+                    (date!(2025 - 07 - 15), 0.064),
+                    (date!(2026 - 01 - 15), 0.068),
+                ],
+                DayCountConvention::Thirty360,
+            ),
+            quantity: Quantity::Price,
+            expected: 98.39,
+            tolerance: Tolerance::absolute(0.005),
+            source: Source::Literature(
+                "Hull, Options, Futures, and Other Derivatives (11th ed.), Section 4.4",
+            ),
+        },
+        ReferenceValue {
+            case: "1.5-year 8% bond on the bootstrapped curve",
+            inputs: semiannual(date!(2025 - 07 - 15), 0.08, 100.0)
+                .with_zero_rates(bootstrapped.clone(), DayCountConvention::Thirty360),
+            quantity: Quantity::Price,
+            expected: 96.0,
+            tolerance: Tolerance::absolute(0.001),
+            source: Source::Literature(
+                "Hull, Options, Futures, and Other Derivatives (11th ed.), Table 4.3",
+            ),
+        },
+        ReferenceValue {
+            case: "2-year 12% bond on the bootstrapped curve",
+            inputs: semiannual(date!(2026 - 01 - 15), 0.12, 100.0)
+                .with_zero_rates(bootstrapped, DayCountConvention::Thirty360),
+            quantity: Quantity::Price,
+            expected: 101.6,
+            tolerance: Tolerance::absolute(0.001),
+            source: Source::Literature(
+                "Hull, Options, Futures, and Other Derivatives (11th ed.), Table 4.3",
+            ),
+        },
+        // The price is quoted to the cent, which moves the yield by less
+        // than 1e-6.
+        ReferenceValue {
+            case: "20-year 9% bond priced at 774.31",
+            inputs: semiannual(date!(2044 - 01 - 15), 0.09, 1000.0).with_price(774.31),
+            quantity: Quantity::Yield,
+            expected: 0.12,
+            tolerance: Tolerance::absolute(1e-5),
+            source: Source::Literature(
+                "Fabozzi, Bond Markets, Analysis, and Strategies (9th ed.), Chapter 2",
+            ),
+        },
+        ReferenceValue {
+            case: "20-year 9% bond at par",
+            inputs: semiannual(date!(2044 - 01 - 15), 0.09, 1000.0).with_price(1000.0),
+            quantity: Quantity::Yield,
+            expected: 0.09,
+            tolerance: Tolerance::absolute(1e-8),
+            source: Source::Literature(
+                "Fabozzi, Bond Markets, Analysis, and Strategies (9th ed.), Chapter 2",
+            ),
+        },
+        // 61 of the 182 days of the coupon period have accrued.
+        ReferenceValue {
+            case: "5% semi-annual bond, 61 days into the period",
+            inputs: BondCase::new(
+                date!(2023 - 11 - 15),
+                date!(2025 - 11 - 15),
+                0.05,
+                PaymentFrequency::SemiAnnually,
+                100.0,
+                DayCountFraction::ActualActualICMA,
+            )
+            .with_settlement_date(date!(2024 - 01 - 15)),
+            quantity: Quantity::AccruedInterest,
+            expected: 2.5 * 61.0 / 182.0,
+            tolerance: Tolerance::absolute(1e-10),
+            source: Source::Literature("ICMA Primary Market Handbook, Rule 251 (Actual/Actual)"),
+        },
+    ]
+}
+
+/// Checks the coupon bond pricer against [`bond_references`].
+pub fn verify_bonds(config: &ToleranceConfig) -> VerificationReport {
+    verify(&bond_references(), config, BondCase::evaluate)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_bonds {
+    use super::*;
+
+    #[test]
+    fn test_bond_references() {
+        let report = verify_bonds(&ToleranceConfig::new());
+
+        assert_eq!(report.len(), 6);
+        report.assert_passed();
+    }
+
+    #[test]
+    fn test_yield_curve_starts_on_the_evaluation_date() {
+        let case = bond_references().swap_remove(0).inputs;
+        let curve = case.yield_curve();
+
+        assert_eq!(curve.rates.len(), 5);
+        assert_eq!(
+            curve.rates.first_key_value(),
+            Some((&datetime_of(case.evaluation_date), &0.050))
+        );
+    }
+
+    fn datetime_of(date: Date) -> OffsetDateTime {
+        date.midnight().assume_utc()
+    }
+}
@@ -0,0 +1,222 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::curves::{Curve, YieldCurve};
+use crate::time::{DayCountConvention, DayCounter};
+use crate::verification::{
+    verify, Quantity, ReferenceValue, Source, Tolerance, ToleranceConfig, VerificationReport,
+};
+use std::collections::BTreeMap;
+use time::macros::date;
+use time::{Date, OffsetDateTime};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// CONSTANTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+const HULL_BOOTSTRAP: Source =
+    Source::Literature("Hull, Options, Futures, and Other Derivatives (11th ed.), Table 4.3");
+const HULL_FORWARDS: Source =
+    Source::Literature("Hull, Options, Futures, and Other Derivatives (11th ed.), Table 4.5");
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// A zero curve and the date (or period) a quantity is read at.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurveCase {
+    /// Initial date of the curve.
+    pub initial_date: Date,
+    /// Continuously compounded zero rates, by date. The shortest rate is
+    /// extended back to the initial date.
+    pub zero_rates: Vec<(Date, f64)>,
+    /// Day count of the curve.
+    pub day_count: DayCountConvention,
+    /// Start of the forward period (for forward rate references).
+    pub start_date: Option<Date>,
+    /// Date the quantity is read at (the end of the forward period).
+    pub date: Date,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl CurveCase {
+    /// New curve case, read at `date`.
+    pub fn new(
+        initial_date: Date,
+        zero_rates: Vec<(Date, f64)>,
+        day_count: DayCountConvention,
+        date: Date,
+    ) -> Self {
+        Self {
+            initial_date,
+            zero_rates,
+            day_count,
+            start_date: None,
+            date,
+        }
+    }
+
+    /// Sets the start of the forward period.
+    pub fn with_start_date(mut self, start_date: Date) -> Self {
+        self.start_date = Some(start_date);
+        self
+    }
+
+    /// The curve, starting on the initial date.
+    pub fn yield_curve(&self) -> YieldCurve {
+        let mut rates: BTreeMap<OffsetDateTime, f64> = self
+            .zero_rates
+            .iter()
+            .map(|(date, rate)| (date.midnight().assume_utc(), *rate))
+            .collect();
+
+        let first = rates.values().next().copied().unwrap_or(0.0);
+        rates
+            .entry(self.initial_date.midnight().assume_utc())
+            .or_insert(first);
+
+        YieldCurve::new(rates).with_day_count_convention(self.day_count)
+    }
+
+    /// Computes a quantity with [`YieldCurve`]. Forward rates are implied
+    /// by the discount factors at both ends of the period.
+    pub fn evaluate(&self, quantity: Quantity) -> Result<f64, String> {
+        let curve = self.yield_curve();
+        let date = self.date.midnight().assume_utc();
+
+        match quantity {
+            Quantity::DiscountFactor => Ok(curve.discount_factor(date)),
+            Quantity::ZeroRate => Ok(curve.rate(date)),
+            Quantity::ForwardRate => {
+                let start = self
+                    .start_date
+                    .ok_or("no start date for the forward period")?
+                    .midnight()
+                    .assume_utc();
+                let tau = self.day_count.year_fraction(start, date);
+
+                Ok((curve.discount_factor(start) / curve.discount_factor(date)).ln() / tau)
+            }
+            _ => Err(format!("{quantity} is not a curve quantity")),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Reference discount factors, zero rates and forward rates.
+///
+/// The discount factors are the bill prices Hull bootstraps his zero curve
+/// from, and the forward rates those of his Table 4.5, whose zero rates are
+/// at whole years. Both use 30E/360 so that the periods are exact.
+pub fn curve_references() -> Vec<ReferenceValue<CurveCase>> {
+    let start = date!(2024 - 01 - 15);
+    let bootstrapped = vec![
+        (date!(2024 - 04 - 15), 0.10127),
+        (date!(2024 - 07 - 15), 0.10469),
+        (date!(2025 - 01 - 15), 0.10536),
+    ];
+    let annual = vec![
+        (date!(2025 - 01 - 15), 0.030),
+        (date!(2026 - 01 - 15), 0.040),
+        (date!(2027 - 01 - 15), 0.046),
+        (date!(2028 - 01 - 15), 0.050),
+        (date!(2029 - 01 - 15), 0.053),
+    ];
+
+    let mut references = Vec::new();
+
+    // Bills of 3, 6 and 12 months priced at 97.5, 94.9 and 90.0 (the zero
+    // rates are quoted to 1e-5, which moves the prices by less than 1e-6).
+    for ((date, _), price) in bootstrapped.iter().zip([97.5, 94.9, 90.0]) {
+        references.push(ReferenceValue {
+            case: "Bill discount factor on the bootstrapped curve",
+            inputs: CurveCase::new(
+                start,
+                bootstrapped.clone(),
+                DayCountConvention::Thirty360,
+                *date,
+            ),
+            quantity: Quantity::DiscountFactor,
+            expected: price / 100.0,
+            tolerance: Tolerance::absolute(2e-6),
+            source: HULL_BOOTSTRAP,
+        });
+    }
+
+    // Forward rates for years 2 to 5.
+    for (window, expected) in annual.windows(2).zip([0.050, 0.058, 0.062, 0.065]) {
+        references.push(ReferenceValue {
+            case: "Forward rate for the year",
+            inputs: CurveCase::new(
+                start,
+                annual.clone(),
+                DayCountConvention::Thirty360,
+                window[1].0,
+            )
+            .with_start_date(window[0].0),
+            quantity: Quantity::ForwardRate,
+            expected,
+            tolerance: Tolerance::absolute(1e-10),
+            source: HULL_FORWARDS,
+        });
+    }
+
+    references.push(ReferenceValue {
+        case: "Zero rate at a curve date",
+        inputs: CurveCase::new(
+            start,
+            annual.clone(),
+            DayCountConvention::Thirty360,
+            date!(2027 - 01 - 15),
+        ),
+        quantity: Quantity::ZeroRate,
+        expected: 0.046,
+        tolerance: Tolerance::absolute(1e-12),
+        source: HULL_FORWARDS,
+    });
+
+    references
+}
+
+/// Checks the yield curve against [`curve_references`].
+pub fn verify_curves(config: &ToleranceConfig) -> VerificationReport {
+    verify(&curve_references(), config, CurveCase::evaluate)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_curves {
+    use super::*;
+
+    #[test]
+    fn test_curve_references() {
+        let report = verify_curves(&ToleranceConfig::new());
+
+        assert_eq!(report.len(), 8);
+        report.assert_passed();
+    }
+
+    #[test]
+    fn test_forward_rate_needs_a_period() {
+        let case = curve_references().swap_remove(0).inputs;
+
+        assert!(case.evaluate(Quantity::ForwardRate).is_err());
+        assert!(case.evaluate(Quantity::Price).is_err());
+    }
+}
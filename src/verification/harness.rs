@@ -0,0 +1,333 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::verification::{Tolerance, ToleranceConfig};
+use std::fmt;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Quantity a reference value is published for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Quantity {
+    /// Price (for bonds, the dirty price).
+    Price,
+    /// Sensitivity of the price to the underlying price.
+    Delta,
+    /// Sensitivity of the delta to the underlying price.
+    Gamma,
+    /// Sensitivity of the price to the volatility.
+    Vega,
+    /// Sensitivity of the price to the passage of time, per year.
+    Theta,
+    /// Sensitivity of the price to the risk-free rate.
+    Rho,
+    /// Volatility implied by a price.
+    ImpliedVolatility,
+    /// Yield to maturity implied by a price.
+    Yield,
+    /// Accrued interest at a settlement date.
+    AccruedInterest,
+    /// Discount factor to a date.
+    DiscountFactor,
+    /// Continuously compounded zero rate to a date.
+    ZeroRate,
+    /// Continuously compounded forward rate between two dates.
+    ForwardRate,
+}
+
+/// Where a reference value is published.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    /// The QuantLib test suite (the file and test the value is taken from).
+    QuantLib(&'static str),
+    /// A book or paper (with the table or example).
+    Literature(&'static str),
+}
+
+/// A published value of a quantity for some inputs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReferenceValue<I> {
+    /// Short description of the case.
+    pub case: &'static str,
+    /// Inputs of the case.
+    pub inputs: I,
+    /// Quantity the value is published for.
+    pub quantity: Quantity,
+    /// Published value.
+    pub expected: f64,
+    /// Tolerance implied by the published precision.
+    pub tolerance: Tolerance,
+    /// Where the value is published.
+    pub source: Source,
+}
+
+/// Outcome of the comparison of a computed value with a reference value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Check {
+    /// Short description of the case.
+    pub case: &'static str,
+    /// Quantity compared.
+    pub quantity: Quantity,
+    /// Published value.
+    pub expected: f64,
+    /// Computed value, or why it could not be computed.
+    pub actual: Result<f64, String>,
+    /// Tolerance of the comparison, after configuration.
+    pub tolerance: Tolerance,
+    /// Where the value is published.
+    pub source: Source,
+}
+
+/// Outcome of a verification run.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VerificationReport {
+    /// Every comparison of the run.
+    pub checks: Vec<Check>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl fmt::Display for Quantity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Quantity::Price => "price",
+            Quantity::Delta => "delta",
+            Quantity::Gamma => "gamma",
+            Quantity::Vega => "vega",
+            Quantity::Theta => "theta",
+            Quantity::Rho => "rho",
+            Quantity::ImpliedVolatility => "implied volatility",
+            Quantity::Yield => "yield",
+            Quantity::AccruedInterest => "accrued interest",
+            Quantity::DiscountFactor => "discount factor",
+            Quantity::ZeroRate => "zero rate",
+            Quantity::ForwardRate => "forward rate",
+        };
+
+        f.pad(name)
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::QuantLib(test) => write!(f, "QuantLib {test}"),
+            Source::Literature(reference) => write!(f, "{reference}"),
+        }
+    }
+}
+
+impl Check {
+    /// Whether the computed value is within the tolerance of the reference.
+    pub fn passed(&self) -> bool {
+        match self.actual {
+            Ok(actual) => self.tolerance.accepts(actual, self.expected),
+            Err(_) => false,
+        }
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = if self.passed() { "PASS" } else { "FAIL" };
+        let bound = self.tolerance.bound(self.expected);
+
+        match &self.actual {
+            Ok(actual) => write!(
+                f,
+                "{status} {:<18} {:<48} expected {:>12.6} actual {:>12.6} (error {:.2e}, tolerance {:.1e}) [{}]",
+                self.quantity,
+                self.case,
+                self.expected,
+                actual,
+                (actual - self.expected).abs(),
+                bound,
+                self.source
+            ),
+            Err(error) => write!(
+                f,
+                "{status} {:<18} {:<48} expected {:>12.6} error: {error} [{}]",
+                self.quantity, self.case, self.expected, self.source
+            ),
+        }
+    }
+}
+
+impl VerificationReport {
+    /// Number of checks.
+    pub fn len(&self) -> usize {
+        self.checks.len()
+    }
+
+    /// Whether the run made no checks.
+    pub fn is_empty(&self) -> bool {
+        self.checks.is_empty()
+    }
+
+    /// Whether every check passed.
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(Check::passed)
+    }
+
+    /// Checks that failed.
+    pub fn failures(&self) -> Vec<&Check> {
+        self.checks.iter().filter(|check| !check.passed()).collect()
+    }
+
+    /// This report followed by the checks of `other`.
+    pub fn merge(mut self, other: Self) -> Self {
+        self.checks.extend(other.checks);
+        self
+    }
+
+    /// Panics, listing the failed checks, unless every check passed.
+    pub fn assert_passed(&self) {
+        let failures = self.failures();
+
+        if !failures.is_empty() {
+            let lines = failures
+                .iter()
+                .map(|check| check.to_string())
+                .collect::<Vec<_>>();
+
+            panic!(
+                "{} of {} reference checks failed:\n{}",
+                failures.len(),
+                self.len(),
+                lines.join("\n")
+            );
+        }
+    }
+}
+
+impl fmt::Display for VerificationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for check in &self.checks {
+            writeln!(f, "{check}")?;
+        }
+
+        write!(
+            f,
+            "{} passed, {} failed",
+            self.len() - self.failures().len(),
+            self.failures().len()
+        )
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Compares the values computed by `evaluate` with the reference values,
+/// under the configured tolerances.
+///
+/// `evaluate` computes a quantity for the inputs of a case, or explains
+/// why it cannot (which fails the check).
+pub fn verify<I, F>(
+    references: &[ReferenceValue<I>],
+    config: &ToleranceConfig,
+    evaluate: F,
+) -> VerificationReport
+where
+    F: Fn(&I, Quantity) -> Result<f64, String>,
+{
+    let checks = references
+        .iter()
+        .map(|reference| Check {
+            case: reference.case,
+            quantity: reference.quantity,
+            expected: reference.expected,
+            actual: evaluate(&reference.inputs, reference.quantity),
+            tolerance: config.tolerance(reference.quantity, reference.tolerance),
+            source: reference.source,
+        })
+        .collect();
+
+    VerificationReport { checks }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_harness {
+    use super::*;
+
+    fn references() -> Vec<ReferenceValue<f64>> {
+        let reference = |case, inputs, expected| ReferenceValue {
+            case,
+            inputs,
+            quantity: Quantity::Price,
+            expected,
+            tolerance: Tolerance::absolute(1e-3),
+            source: Source::Literature("Test"),
+        };
+
+        vec![
+            reference("square of 2", 2.0, 4.0),
+            reference("square of 3", 3.0, 9.001),
+            reference("square of -1", -1.0, 1.0),
+        ]
+    }
+
+    fn square(x: &f64, _: Quantity) -> Result<f64, String> {
+        match *x >= 0.0 {
+            true => Ok(x * x),
+            false => Err("negative input".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_verify() {
+        let report = verify(&references(), &ToleranceConfig::new(), square);
+
+        assert_eq!(report.len(), 3);
+        assert!(!report.passed());
+
+        // The second value is just within tolerance, the third fails to
+        // evaluate.
+        let failures = report.failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].case, "square of -1");
+
+        // Tightening the tolerances fails the second value too.
+        let strict = verify(
+            &references(),
+            &ToleranceConfig::new().with_scale(0.5),
+            square,
+        );
+        assert_eq!(strict.failures().len(), 2);
+    }
+
+    #[test]
+    fn test_report() {
+        let report = verify(&references()[..2], &ToleranceConfig::new(), square);
+        report.assert_passed();
+
+        let text = report.to_string();
+        assert!(text.starts_with("PASS price"));
+        assert!(text.ends_with("2 passed, 0 failed"));
+
+        let merged = report.merge(verify(&references()[2..], &ToleranceConfig::new(), square));
+        assert_eq!(merged.len(), 3);
+        assert!(merged.to_string().contains("FAIL price"));
+    }
+
+    #[test]
+    #[should_panic(expected = "1 of 3 reference checks failed")]
+    fn test_assert_passed() {
+        verify(&references(), &ToleranceConfig::new(), square).assert_passed();
+    }
+}
@@ -0,0 +1,65 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Verification against published results (requires the `verification`
+//! feature outside of the crate's own tests).
+//!
+//! A library of reference prices, Greeks, yields and curve values from the
+//! QuantLib test suite and the literature, each with the tolerance implied
+//! by its published precision and where it is published. The crate's tests
+//! run every reference, so a change that moves a pricer away from the
+//! published numbers fails the build.
+//!
+//! - Options: European prices, Greeks and implied volatilities
+//!   ([`option_references`]).
+//! - Bonds: prices on zero curves, yields and accrued interest
+//!   ([`bond_references`]).
+//! - Curves: discount factors, zero rates and forward rates
+//!   ([`curve_references`]).
+//!
+//! Tolerances can be replaced per quantity and scaled with a
+//! [`ToleranceConfig`]:
+//!
+//! ```rust,ignore
+//! use RustQuant::verification::*;
+//!
+//! let config = ToleranceConfig::new()
+//!     .with_tolerance(Quantity::Theta, Tolerance::relative(1e-5))
+//!     .with_scale(2.0);
+//!
+//! let report = verify_options(&config)
+//!     .merge(verify_bonds(&config))
+//!     .merge(verify_curves(&config));
+//!
+//! println!("{report}");
+//! report.assert_passed();
+//! ```
+//!
+//! New references are added to the tables of the relevant submodule, or
+//! checked against any pricer with [`verify`].
+
+/// Reference values, checks and reports.
+pub mod harness;
+pub use harness::*;
+
+/// Tolerances of the checks.
+pub mod tolerance;
+pub use tolerance::*;
+
+/// Bond references.
+pub mod bonds;
+pub use bonds::*;
+
+/// Curve references.
+pub mod curves;
+pub use curves::*;
+
+/// Option references.
+pub mod options;
+pub use options::*;
@@ -0,0 +1,162 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::verification::Quantity;
+use std::collections::BTreeMap;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Tolerance of a comparison with a reference value.
+///
+/// A value passes if it is within the absolute tolerance or within the
+/// relative tolerance (of the reference) of the reference value, whichever
+/// is larger.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerance {
+    /// Absolute tolerance.
+    pub absolute: f64,
+    /// Relative tolerance, as a fraction of the reference value.
+    pub relative: f64,
+}
+
+/// Tolerances of a verification run.
+///
+/// Every reference value comes with the tolerance implied by its published
+/// precision (e.g. $10^{-4}$ for a price quoted to four decimals). The
+/// configuration can replace it for a quantity, and scales the resulting
+/// tolerances (below 1 to tighten the checks, above 1 to loosen them).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToleranceConfig {
+    /// Factor applied to every tolerance.
+    pub scale: f64,
+    /// Tolerances replacing the published ones, by quantity.
+    pub overrides: BTreeMap<Quantity, Tolerance>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl Tolerance {
+    /// New tolerance.
+    pub fn new(absolute: f64, relative: f64) -> Self {
+        Self { absolute, relative }
+    }
+
+    /// Absolute tolerance only.
+    pub fn absolute(absolute: f64) -> Self {
+        Self::new(absolute, 0.0)
+    }
+
+    /// Relative tolerance only.
+    pub fn relative(relative: f64) -> Self {
+        Self::new(0.0, relative)
+    }
+
+    /// Both tolerances multiplied by `factor`.
+    pub fn scaled(self, factor: f64) -> Self {
+        Self::new(self.absolute * factor, self.relative * factor)
+    }
+
+    /// Largest accepted difference from `expected`.
+    pub fn bound(&self, expected: f64) -> f64 {
+        self.absolute.max(self.relative * expected.abs())
+    }
+
+    /// Whether `actual` is within the tolerance of `expected`
+    /// (never for NaNs).
+    pub fn accepts(&self, actual: f64, expected: f64) -> bool {
+        (actual - expected).abs() <= self.bound(expected)
+    }
+}
+
+impl Default for ToleranceConfig {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            overrides: BTreeMap::new(),
+        }
+    }
+}
+
+impl ToleranceConfig {
+    /// The published tolerances, unscaled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the factor applied to every tolerance.
+    pub fn with_scale(mut self, scale: f64) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Replaces the published tolerance of a quantity.
+    pub fn with_tolerance(mut self, quantity: Quantity, tolerance: Tolerance) -> Self {
+        self.overrides.insert(quantity, tolerance);
+        self
+    }
+
+    /// Tolerance of a check of `quantity` whose reference value was
+    /// published with the tolerance `published`.
+    pub fn tolerance(&self, quantity: Quantity, published: Tolerance) -> Tolerance {
+        self.overrides
+            .get(&quantity)
+            .copied()
+            .unwrap_or(published)
+            .scaled(self.scale)
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_tolerance {
+    use super::*;
+
+    #[test]
+    fn test_accepts() {
+        let tolerance = Tolerance::new(1e-4, 1e-3);
+
+        // The relative tolerance dominates for large values.
+        assert!(tolerance.accepts(1000.9, 1000.0));
+        assert!(!tolerance.accepts(1001.1, 1000.0));
+
+        // The absolute tolerance dominates near zero.
+        assert!(tolerance.accepts(0.00005, 0.0));
+        assert!(!tolerance.accepts(0.0002, 0.0));
+
+        assert!(!tolerance.accepts(f64::NAN, 0.0));
+    }
+
+    #[test]
+    fn test_config() {
+        let published = Tolerance::absolute(1e-4);
+        let config = ToleranceConfig::new()
+            .with_scale(0.5)
+            .with_tolerance(Quantity::Theta, Tolerance::relative(1e-2));
+
+        assert_eq!(
+            config.tolerance(Quantity::Price, published),
+            Tolerance::absolute(5e-5)
+        );
+        assert_eq!(
+            config.tolerance(Quantity::Theta, published),
+            Tolerance::relative(5e-3)
+        );
+        assert_eq!(
+            ToleranceConfig::default().tolerance(Quantity::Price, published),
+            published
+        );
+    }
+}
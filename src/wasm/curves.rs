@@ -0,0 +1,153 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::curves::{Curve, YieldCurve};
+use crate::wasm::js_error;
+use std::collections::BTreeMap;
+use time::{Duration, OffsetDateTime};
+use wasm_bindgen::prelude::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// JavaScript `YieldCurve`: a [`YieldCurve`] starting now, queried at
+/// maturities in years. The rate of the shortest maturity is extended flat
+/// back to the start.
+#[wasm_bindgen(js_name = YieldCurve)]
+pub struct WasmYieldCurve {
+    curve: YieldCurve,
+    initial_date: OffsetDateTime,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[wasm_bindgen(js_class = YieldCurve)]
+impl WasmYieldCurve {
+    /// Curve with continuously compounded zero `rates` at the `maturities`
+    /// (in years).
+    #[wasm_bindgen(constructor)]
+    pub fn new(maturities: &[f64], rates: &[f64]) -> Result<WasmYieldCurve, JsError> {
+        Self::from_points(maturities, rates).map_err(js_error)
+    }
+
+    /// Interpolated zero rate at the maturity (in years).
+    pub fn rate(&self, maturity: f64) -> Result<f64, JsError> {
+        Ok(self.curve.rate(self.date(maturity).map_err(js_error)?))
+    }
+
+    /// Discount factor at the maturity (in years).
+    #[wasm_bindgen(js_name = discountFactor)]
+    pub fn discount_factor(&self, maturity: f64) -> Result<f64, JsError> {
+        Ok(self
+            .curve
+            .discount_factor(self.date(maturity).map_err(js_error)?))
+    }
+
+    /// Discount factors at several maturities (in years).
+    #[wasm_bindgen(js_name = discountFactors)]
+    pub fn discount_factors(&self, maturities: &[f64]) -> Result<Vec<f64>, JsError> {
+        let dates = maturities
+            .iter()
+            .map(|&maturity| self.date(maturity))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(js_error)?;
+
+        Ok(self.curve.discount_factors(&dates))
+    }
+}
+
+impl WasmYieldCurve {
+    fn from_points(maturities: &[f64], rates: &[f64]) -> Result<Self, String> {
+        if maturities.is_empty() || maturities.len() != rates.len() {
+            return Err(format!(
+                "expected as many rates as maturities (at least one), not {} and {}",
+                maturities.len(),
+                rates.len()
+            ));
+        }
+        if let Some(t) = maturities.iter().find(|t| !(t.is_finite() && **t >= 0.0)) {
+            return Err(format!("maturity {t} is not a non-negative number"));
+        }
+
+        let initial_date = OffsetDateTime::now_utc();
+        let mut points: BTreeMap<OffsetDateTime, f64> = maturities
+            .iter()
+            .zip(rates)
+            .map(|(&t, &r)| (date_after(initial_date, t), r))
+            .collect();
+
+        let first = *points.values().next().unwrap();
+        points.entry(initial_date).or_insert(first);
+
+        Ok(Self {
+            curve: YieldCurve::new(points),
+            initial_date,
+        })
+    }
+
+    /// Date of the maturity, which must be within the curve.
+    fn date(&self, maturity: f64) -> Result<OffsetDateTime, String> {
+        if !(maturity.is_finite() && maturity >= 0.0) {
+            return Err(format!("maturity {maturity} is outside the curve"));
+        }
+
+        let date = date_after(self.initial_date, maturity);
+        match date <= self.curve.terminal_date() {
+            true => Ok(date),
+            false => Err(format!("maturity {maturity} is outside the curve")),
+        }
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Maturities in years are rounded to whole days (Actual/365 Fixed).
+fn date_after(start: OffsetDateTime, years: f64) -> OffsetDateTime {
+    start + Duration::days((years * 365.0).round() as i64)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_curves {
+    use super::*;
+
+    #[test]
+    fn test_yield_curve() {
+        let curve = WasmYieldCurve::new(&[1.0, 2.0, 5.0], &[0.03, 0.035, 0.04]).unwrap();
+
+        assert_approx_equal!(curve.rate(0.5).unwrap(), 0.03, 1e-12);
+        assert_approx_equal!(
+            curve.discount_factor(5.0).unwrap(),
+            (-0.04_f64 * 5.0).exp(),
+            1e-12
+        );
+
+        let discount_factors = curve.discount_factors(&[0.0, 2.0]).unwrap();
+        assert_eq!(discount_factors[0], 1.0);
+        assert_approx_equal!(discount_factors[1], (-0.035_f64 * 2.0).exp(), 1e-12);
+
+        assert!(curve.date(6.0).is_err());
+        assert!(curve.date(-1.0).is_err());
+    }
+
+    #[test]
+    fn test_invalid_points() {
+        assert!(WasmYieldCurve::from_points(&[], &[]).is_err());
+        assert!(WasmYieldCurve::from_points(&[1.0, 2.0], &[0.03]).is_err());
+        assert!(WasmYieldCurve::from_points(&[f64::NAN], &[0.03]).is_err());
+    }
+}
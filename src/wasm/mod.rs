@@ -0,0 +1,69 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! JavaScript bindings for WebAssembly (requires the `wasm` feature).
+//!
+//! The crate builds for `wasm32-unknown-unknown` without the `data`
+//! feature (which needs files and the network), taking its randomness and
+//! the current time from the JavaScript host. Build the module and its
+//! JavaScript glue with `wasm-bindgen`:
+//!
+//! ```bash
+//! cargo rustc --release --lib --target wasm32-unknown-unknown --features wasm --crate-type cdylib
+//! wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/RustQuant.wasm
+//! ```
+//!
+//! It exposes:
+//!
+//! - Pricers: `BlackScholes`, with the price, Greeks and implied
+//!   volatility.
+//! - Curves: `YieldCurve`, with rates and discount factors.
+//! - Processes: `GeometricBrownianMotion`, `ArithmeticBrownianMotion`,
+//!   `OrnsteinUhlenbeck` and `CoxIngersollRoss`, whose `simulate` returns a
+//!   `Simulation` with the time grid and the paths as `Float64Array`s.
+//!
+//! Methods use JavaScript naming (`discountFactor`, `impliedVolatility`),
+//! times are year fractions, and invalid arguments throw an `Error`.
+//!
+//! ```js
+//! import init, { BlackScholes, GeometricBrownianMotion } from "./pkg/RustQuant.js";
+//!
+//! await init();
+//!
+//! const call = new BlackScholes(100.0, 100.0, 0.2, 0.05, 0.05, 1.0, "call");
+//! console.log(call.price(), call.delta());
+//!
+//! const simulation = new GeometricBrownianMotion(0.05, 0.2).simulate(100.0, 0.0, 1.0, 252, 100, 42);
+//! console.log(simulation.path(0));
+//! ```
+
+use wasm_bindgen::JsError;
+
+/// Term structures.
+pub mod curves;
+pub use curves::*;
+
+/// Option pricers.
+pub mod pricers;
+pub use pricers::*;
+
+/// Stochastic process simulation.
+pub mod stochastics;
+pub use stochastics::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Argument checks are written against `Result<_, String>`, so that they
+/// can be unit tested off the WebAssembly target, and only turned into a
+/// JavaScript `Error` at the boundary.
+pub(crate) fn js_error(message: String) -> JsError {
+    JsError::new(&message)
+}
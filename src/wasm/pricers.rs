@@ -0,0 +1,171 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::instruments::options::{BlackScholesInputs, TypeFlag};
+use crate::wasm::js_error;
+use wasm_bindgen::prelude::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// JavaScript `BlackScholes`: a generalised Black-Scholes-Merton option.
+#[wasm_bindgen(js_name = BlackScholes)]
+pub struct WasmBlackScholes {
+    inputs: BlackScholesInputs,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[wasm_bindgen(js_class = BlackScholes)]
+impl WasmBlackScholes {
+    /// Option with the time to expiry in years and `optionType` `"call"` or
+    /// `"put"`.
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        underlying_price: f64,
+        strike_price: f64,
+        volatility: f64,
+        risk_free_rate: f64,
+        cost_of_carry: f64,
+        time_to_expiry: f64,
+        option_type: &str,
+    ) -> Result<WasmBlackScholes, JsError> {
+        let inputs = BlackScholesInputs {
+            underlying_price,
+            strike_price,
+            volatility,
+            risk_free_rate,
+            cost_of_carry,
+            time_to_expiry,
+            option_type: parse_option_type(option_type).map_err(js_error)?,
+        };
+        check_inputs(&inputs).map_err(js_error)?;
+
+        Ok(Self { inputs })
+    }
+
+    /// Price.
+    pub fn price(&self) -> f64 {
+        self.inputs.price()
+    }
+
+    /// Sensitivity to the underlying price.
+    pub fn delta(&self) -> f64 {
+        self.inputs.delta()
+    }
+
+    /// Sensitivity of the delta to the underlying price.
+    pub fn gamma(&self) -> f64 {
+        self.inputs.gamma()
+    }
+
+    /// Sensitivity to the volatility.
+    pub fn vega(&self) -> f64 {
+        self.inputs.vega()
+    }
+
+    /// Sensitivity to the passage of time.
+    pub fn theta(&self) -> f64 {
+        self.inputs.theta()
+    }
+
+    /// Sensitivity to the risk-free rate.
+    pub fn rho(&self) -> f64 {
+        self.inputs.rho()
+    }
+
+    /// Volatility at which the option is worth `price` (the option's own
+    /// volatility is ignored).
+    #[wasm_bindgen(js_name = impliedVolatility)]
+    pub fn implied_volatility(&self, price: f64) -> Result<f64, JsError> {
+        self.inputs
+            .implied_volatility(price)
+            .map_err(|e| js_error(e.to_string()))
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Parses `"call"` or `"put"` (in any case).
+fn parse_option_type(option_type: &str) -> Result<TypeFlag, String> {
+    match option_type.to_ascii_lowercase().as_str() {
+        "call" | "c" => Ok(TypeFlag::Call),
+        "put" | "p" => Ok(TypeFlag::Put),
+        _ => Err(format!(
+            "optionType must be 'call' or 'put', not '{option_type}'"
+        )),
+    }
+}
+
+/// Rejects inputs the pricer would turn into NaNs.
+fn check_inputs(inputs: &BlackScholesInputs) -> Result<(), String> {
+    let positive = [
+        ("underlyingPrice", inputs.underlying_price),
+        ("strikePrice", inputs.strike_price),
+        ("volatility", inputs.volatility),
+        ("timeToExpiry", inputs.time_to_expiry),
+    ];
+
+    match positive
+        .iter()
+        .find(|(_, value)| !(value.is_finite() && *value > 0.0))
+    {
+        Some((name, value)) => Err(format!("{name} must be positive, not {value}")),
+        None => Ok(()),
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_pricers {
+    use super::*;
+
+    #[test]
+    fn test_black_scholes() {
+        let call = WasmBlackScholes::new(100.0, 100.0, 0.2, 0.05, 0.05, 1.0, "Call").unwrap();
+
+        assert_approx_equal!(call.price(), 10.450583572185565, 1e-10);
+        assert_approx_equal!(call.delta(), 0.6368306511756191, 1e-10);
+        assert_approx_equal!(call.implied_volatility(call.price()).unwrap(), 0.2, 1e-8);
+
+        let put = WasmBlackScholes::new(100.0, 100.0, 0.2, 0.05, 0.05, 1.0, "p").unwrap();
+        assert_approx_equal!(
+            call.price() - put.price(),
+            100.0 - 100.0 * (-0.05_f64).exp(),
+            1e-10
+        );
+    }
+
+    #[test]
+    fn test_argument_checks() {
+        assert!(parse_option_type("straddle").is_err());
+
+        let inputs = BlackScholesInputs {
+            underlying_price: 100.0,
+            strike_price: 100.0,
+            volatility: -0.2,
+            risk_free_rate: 0.05,
+            cost_of_carry: 0.05,
+            time_to_expiry: 1.0,
+            option_type: TypeFlag::Call,
+        };
+        assert_eq!(
+            check_inputs(&inputs).unwrap_err(),
+            "volatility must be positive, not -0.2"
+        );
+    }
+}
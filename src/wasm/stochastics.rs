@@ -0,0 +1,208 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::stochastics::{
+    ArithmeticBrownianMotion, CoxIngersollRoss, GeometricBrownianMotion, OrnsteinUhlenbeck,
+    SimulationConfig, StochasticProcess, Trajectories,
+};
+use crate::wasm::js_error;
+use wasm_bindgen::prelude::*;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// MACROS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// JavaScript class wrapping a process, with Euler-Maruyama simulation to
+/// a [`Simulation`].
+macro_rules! wasm_process {
+    ($wrapper:ident, $name:ident, $process:ty, ($($arg:ident),*), $doc:literal) => {
+        #[doc = $doc]
+        #[wasm_bindgen(js_name = $name)]
+        pub struct $wrapper {
+            process: $process,
+        }
+
+        #[wasm_bindgen(js_class = $name)]
+        impl $wrapper {
+            /// New process (the volatility `sigma` must be non-negative).
+            #[wasm_bindgen(constructor)]
+            pub fn new($($arg: f64),*) -> Result<$wrapper, JsError> {
+                check_parameters(&[$((stringify!($arg), $arg)),*]).map_err(js_error)?;
+
+                Ok(Self {
+                    process: <$process>::new($($arg),*),
+                })
+            }
+
+            /// Euler-Maruyama paths on `nSteps` steps from `t0` to `tn`,
+            /// reproducible if a `seed` is given.
+            pub fn simulate(
+                &self,
+                x_0: f64,
+                t_0: f64,
+                t_n: f64,
+                n_steps: usize,
+                m_paths: usize,
+                seed: Option<u32>,
+            ) -> Simulation {
+                simulate(&self.process, x_0, t_0, t_n, n_steps, m_paths, seed)
+            }
+        }
+    };
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Simulated paths: the time grid, and the paths stored row by row.
+#[wasm_bindgen]
+pub struct Simulation {
+    trajectories: Trajectories,
+}
+
+wasm_process!(
+    WasmGeometricBrownianMotion,
+    GeometricBrownianMotion,
+    GeometricBrownianMotion,
+    (mu, sigma),
+    "JavaScript `GeometricBrownianMotion(mu, sigma)`."
+);
+
+wasm_process!(
+    WasmArithmeticBrownianMotion,
+    ArithmeticBrownianMotion,
+    ArithmeticBrownianMotion,
+    (mu, sigma),
+    "JavaScript `ArithmeticBrownianMotion(mu, sigma)`."
+);
+
+wasm_process!(
+    WasmOrnsteinUhlenbeck,
+    OrnsteinUhlenbeck,
+    OrnsteinUhlenbeck,
+    (mu, sigma, theta),
+    "JavaScript `OrnsteinUhlenbeck(mu, sigma, theta)`, with long-run mean `mu` and mean reversion speed `theta`."
+);
+
+wasm_process!(
+    WasmCoxIngersollRoss,
+    CoxIngersollRoss,
+    CoxIngersollRoss,
+    (mu, sigma, theta),
+    "JavaScript `CoxIngersollRoss(mu, sigma, theta)`, with long-run mean `mu` and mean reversion speed `theta`."
+);
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[wasm_bindgen]
+impl Simulation {
+    /// Number of paths.
+    #[wasm_bindgen(getter, js_name = nPaths)]
+    pub fn n_paths(&self) -> usize {
+        self.trajectories.n_paths()
+    }
+
+    /// Number of time steps (each path has one more value).
+    #[wasm_bindgen(getter, js_name = nSteps)]
+    pub fn n_steps(&self) -> usize {
+        self.trajectories.n_steps()
+    }
+
+    /// Time grid.
+    #[wasm_bindgen(getter)]
+    pub fn times(&self) -> Vec<f64> {
+        self.trajectories.times.clone()
+    }
+
+    /// All paths, one after the other.
+    #[wasm_bindgen(getter)]
+    pub fn paths(&self) -> Vec<f64> {
+        self.trajectories.paths.iter().copied().collect()
+    }
+
+    /// Path `i`, or `undefined` past the last path.
+    pub fn path(&self, i: usize) -> Option<Vec<f64>> {
+        (i < self.n_paths()).then(|| self.trajectories.path(i).to_vec())
+    }
+
+    /// Values of all paths at the end of the time grid.
+    #[wasm_bindgen(js_name = terminalValues)]
+    pub fn terminal_values(&self) -> Vec<f64> {
+        self.trajectories.terminal_values().to_vec()
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// FUNCTIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Rejects a negative volatility, which the Rust constructors would panic
+/// on.
+fn check_parameters(parameters: &[(&str, f64)]) -> Result<(), String> {
+    match parameters
+        .iter()
+        .find(|(name, value)| *name == "sigma" && *value < 0.0)
+    {
+        Some((_, sigma)) => Err(format!("sigma must be non-negative, not {sigma}")),
+        None => Ok(()),
+    }
+}
+
+/// Simulates the paths on the calling thread (browsers run WebAssembly
+/// single-threaded).
+fn simulate<P>(
+    process: &P,
+    x_0: f64,
+    t_0: f64,
+    t_n: f64,
+    n_steps: usize,
+    m_paths: usize,
+    seed: Option<u32>,
+) -> Simulation
+where
+    P: StochasticProcess + Sync,
+{
+    let config = match seed {
+        Some(seed) => SimulationConfig::new(false).with_seed(seed as u64),
+        None => SimulationConfig::new(false),
+    };
+
+    Simulation {
+        trajectories: process.simulate_with_config(x_0, t_0, t_n, n_steps, m_paths, &config),
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_stochastics {
+    use super::*;
+
+    #[test]
+    fn test_simulate() {
+        let gbm = WasmGeometricBrownianMotion::new(0.05, 0.2).unwrap();
+
+        let simulation = gbm.simulate(100.0, 0.0, 1.0, 10, 4, Some(42));
+        assert_eq!((simulation.n_paths(), simulation.n_steps()), (4, 10));
+        assert_eq!(simulation.times().len(), 11);
+        assert_eq!(simulation.paths().len(), 44);
+        assert_eq!(simulation.path(1).unwrap(), simulation.paths()[11..22]);
+        assert_eq!(simulation.path(4), None);
+
+        let again = gbm.simulate(100.0, 0.0, 1.0, 10, 4, Some(42));
+        assert_eq!(simulation.terminal_values(), again.terminal_values());
+
+        assert!(check_parameters(&[("mu", 0.0), ("sigma", -0.1)]).is_err());
+    }
+}
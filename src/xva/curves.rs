@@ -0,0 +1,177 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Piecewise-constant default intensity $\lambda(t)$, with survival
+/// probability $S(t) = \exp(-\int_0^t \lambda(s) ds)$.
+///
+/// The `i`-th hazard rate applies up to the `i`-th time, and the last one
+/// beyond the last time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HazardCurve {
+    /// Right ends of the intervals, in years.
+    pub times: Vec<f64>,
+
+    /// Hazard rate on each interval.
+    pub hazard_rates: Vec<f64>,
+}
+
+/// Piecewise-constant funding spread over the risk-free rate, on the same
+/// interval convention as [`HazardCurve`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FundingCurve {
+    /// Right ends of the intervals, in years.
+    pub times: Vec<f64>,
+
+    /// Funding spread on each interval.
+    pub spreads: Vec<f64>,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl HazardCurve {
+    /// New hazard curve from increasing times and their hazard rates.
+    pub fn new(times: Vec<f64>, hazard_rates: Vec<f64>) -> Self {
+        check_intervals(&times, &hazard_rates);
+        assert!(
+            hazard_rates.iter().all(|rate| *rate >= 0.0),
+            "Hazard rates must be non-negative."
+        );
+
+        Self {
+            times,
+            hazard_rates,
+        }
+    }
+
+    /// Constant hazard rate.
+    pub fn flat(hazard_rate: f64) -> Self {
+        Self::new(vec![f64::INFINITY], vec![hazard_rate])
+    }
+
+    /// Constant hazard rate implied by a CDS spread with the "credit
+    /// triangle" $\lambda = s / (1 - R)$.
+    pub fn from_cds_spread(spread: f64, recovery_rate: f64) -> Self {
+        Self::flat(spread / (1.0 - recovery_rate))
+    }
+
+    /// Hazard rate at time `t`.
+    pub fn hazard_rate(&self, t: f64) -> f64 {
+        value_at(&self.times, &self.hazard_rates, t)
+    }
+
+    /// Probability of surviving to time `t`.
+    pub fn survival_probability(&self, t: f64) -> f64 {
+        (-integral(&self.times, &self.hazard_rates, t)).exp()
+    }
+
+    /// Probability of defaulting between `t_1` and `t_2`.
+    pub fn default_probability(&self, t_1: f64, t_2: f64) -> f64 {
+        self.survival_probability(t_1) - self.survival_probability(t_2)
+    }
+}
+
+impl FundingCurve {
+    /// New funding curve from increasing times and their spreads.
+    pub fn new(times: Vec<f64>, spreads: Vec<f64>) -> Self {
+        check_intervals(&times, &spreads);
+
+        Self { times, spreads }
+    }
+
+    /// Constant funding spread.
+    pub fn flat(spread: f64) -> Self {
+        Self::new(vec![f64::INFINITY], vec![spread])
+    }
+
+    /// Funding spread at time `t`.
+    pub fn spread(&self, t: f64) -> f64 {
+        value_at(&self.times, &self.spreads, t)
+    }
+}
+
+fn check_intervals(times: &[f64], values: &[f64]) {
+    assert!(!times.is_empty(), "At least one interval is needed.");
+    assert_eq!(
+        times.len(),
+        values.len(),
+        "One value per interval is needed."
+    );
+    assert!(
+        times[0] > 0.0 && times.windows(2).all(|pair| pair[0] < pair[1]),
+        "Times must be positive and increasing."
+    );
+}
+
+// Value of the piecewise-constant function at t (right-continuous).
+fn value_at(times: &[f64], values: &[f64], t: f64) -> f64 {
+    let i = times.partition_point(|end| *end <= t);
+
+    values[i.min(values.len() - 1)]
+}
+
+// Integral of the piecewise-constant function from 0 to t.
+fn integral(times: &[f64], values: &[f64], t: f64) -> f64 {
+    let mut start = 0.0;
+    let mut total = 0.0;
+
+    for (end, value) in times.iter().zip(values) {
+        if t <= *end {
+            return total + value * (t - start);
+        }
+        total += value * (end - start);
+        start = *end;
+    }
+
+    total + values[values.len() - 1] * (t - start)
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_curves {
+    use super::*;
+
+    #[test]
+    fn test_hazard_curve() {
+        let flat = HazardCurve::from_cds_spread(0.012, 0.4);
+        assert_approx_equal!(flat.hazard_rate(3.0), 0.02, 1e-15);
+        assert_approx_equal!(flat.survival_probability(2.0), (-0.04_f64).exp(), 1e-15);
+
+        // 1% for a year, then 3%.
+        let curve = HazardCurve::new(vec![1.0, 5.0], vec![0.01, 0.03]);
+        assert_eq!(curve.hazard_rate(0.5), 0.01);
+        assert_eq!(curve.hazard_rate(1.0), 0.03);
+        assert_eq!(curve.hazard_rate(10.0), 0.03);
+        assert_approx_equal!(curve.survival_probability(2.0), (-0.04_f64).exp(), 1e-15);
+        assert_approx_equal!(curve.survival_probability(7.0), (-0.19_f64).exp(), 1e-15);
+        assert_approx_equal!(
+            curve.default_probability(1.0, 2.0),
+            (-0.01_f64).exp() - (-0.04_f64).exp(),
+            1e-15
+        );
+        assert_eq!(curve.survival_probability(0.0), 1.0);
+    }
+
+    #[test]
+    fn test_funding_curve() {
+        let curve = FundingCurve::new(vec![2.0, 10.0], vec![0.005, 0.008]);
+
+        assert_eq!(curve.spread(1.0), 0.005);
+        assert_eq!(curve.spread(2.5), 0.008);
+        assert_eq!(FundingCurve::flat(0.01).spread(100.0), 0.01);
+    }
+}
@@ -0,0 +1,572 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::instruments::options::{BlackScholesInputs, TypeFlag};
+use crate::stochastics::{OrnsteinUhlenbeck, SimulationConfig, StochasticProcess, Trajectories};
+use ndarray::{Array2, ArrayView1};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Instrument that can be revalued along simulated scenarios.
+pub trait ExposureInstrument: Sync {
+    /// Mark-to-market value at time `t` given the values of the risk
+    /// factors at `t` (in the order they were added to the scenarios).
+    fn value(&self, t: f64, factors: &[f64]) -> f64;
+
+    /// Time of the last cash flow; the instrument is worth nothing after it.
+    fn maturity(&self) -> f64;
+}
+
+/// Generator of joint risk factor scenarios on a common time grid.
+///
+/// Each factor is simulated with its own stochastic process, on an
+/// independent random stream.
+pub struct ScenarioGenerator {
+    horizon: f64,
+    n_steps: usize,
+    n_paths: usize,
+    factors: Vec<(String, Box<dyn StochasticProcess + Sync>, f64)>,
+    config: SimulationConfig,
+}
+
+/// Simulated risk factor paths, one set of trajectories per factor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scenarios {
+    /// Names of the risk factors.
+    pub names: Vec<String>,
+
+    /// Paths of each risk factor.
+    pub factors: Vec<Trajectories>,
+}
+
+/// Values of a netting set on every path (rows) and time point (columns).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExposureProfile {
+    /// Time points, in years.
+    pub times: Vec<f64>,
+
+    /// Netting set values (`n_paths x n_times`).
+    pub values: Array2<f64>,
+}
+
+/// Instruments traded with one counterparty under a netting agreement:
+/// their values offset each other on default.
+#[derive(Default)]
+pub struct NettingSet {
+    instruments: Vec<Box<dyn ExposureInstrument>>,
+}
+
+/// Forward contract on an equity risk factor, with value
+/// $N (S_t e^{(b - r)(T - t)} - K e^{-r (T - t)})$.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EquityForward {
+    /// Index of the underlying risk factor.
+    pub factor: usize,
+    /// K - The delivery price.
+    pub strike: f64,
+    /// T - The delivery time, in years.
+    pub maturity: f64,
+    /// N - Number of units (negative for a short forward).
+    pub notional: f64,
+    /// r - The risk-free interest rate.
+    pub risk_free_rate: f64,
+    /// b - The cost of carry factor.
+    pub cost_of_carry: f64,
+}
+
+/// European option on an equity risk factor, valued with the generalised
+/// Black-Scholes-Merton model.
+#[derive(Debug, Clone, Copy)]
+pub struct EquityOption {
+    /// Index of the underlying risk factor.
+    pub factor: usize,
+    /// Call or put flag.
+    pub option_type: TypeFlag,
+    /// K - The options strike price.
+    pub strike: f64,
+    /// T - The expiry, in years.
+    pub maturity: f64,
+    /// N - Number of options (negative if sold).
+    pub notional: f64,
+    /// sigma - The implied volatility.
+    pub volatility: f64,
+    /// r - The risk-free interest rate.
+    pub risk_free_rate: f64,
+    /// b - The cost of carry factor.
+    pub cost_of_carry: f64,
+}
+
+/// Fixed-for-floating interest rate swap valued with the Vasicek model,
+/// the risk factor being the short rate.
+///
+/// The floating leg is valued as if it reset at the valuation time, i.e.
+/// the accrued part of the current floating coupon is ignored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VasicekSwap {
+    /// Index of the short rate risk factor.
+    pub factor: usize,
+    /// Notional (positive to pay fixed, negative to receive fixed).
+    pub notional: f64,
+    /// Fixed rate.
+    pub fixed_rate: f64,
+    /// Start of the first accrual period, in years.
+    pub start: f64,
+    /// Payment times, in years.
+    pub payment_times: Vec<f64>,
+    /// Speed of mean reversion of the short rate.
+    pub speed: f64,
+    /// Long-run mean of the short rate.
+    pub mean: f64,
+    /// Volatility of the short rate.
+    pub volatility: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl ScenarioGenerator {
+    /// Scenarios from 0 to `horizon` years in `n_steps` steps, on
+    /// `n_paths` paths.
+    pub fn new(horizon: f64, n_steps: usize, n_paths: usize) -> Self {
+        Self {
+            horizon,
+            n_steps,
+            n_paths,
+            factors: Vec::new(),
+            config: SimulationConfig::default(),
+        }
+    }
+
+    /// Adds a risk factor driven by `process`, starting at `initial_value`.
+    pub fn with_factor<P: StochasticProcess + Sync + 'static>(
+        mut self,
+        name: &str,
+        process: P,
+        initial_value: f64,
+    ) -> Self {
+        self.factors
+            .push((name.to_string(), Box::new(process), initial_value));
+        self
+    }
+
+    /// Sets the simulation settings (parallelism, seed).
+    pub fn with_config(mut self, config: SimulationConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Simulates the risk factors.
+    pub fn simulate(&self) -> Scenarios {
+        assert!(
+            !self.factors.is_empty(),
+            "At least one risk factor is needed."
+        );
+
+        Scenarios {
+            names: self.factors.iter().map(|(name, ..)| name.clone()).collect(),
+            factors: self
+                .factors
+                .iter()
+                .enumerate()
+                .map(|(k, (_, process, initial_value))| {
+                    process.simulate_with_config(
+                        *initial_value,
+                        0.0,
+                        self.horizon,
+                        self.n_steps,
+                        self.n_paths,
+                        &self.config.salted(k as u64 + 1),
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Scenarios {
+    /// Time points, in years.
+    pub fn times(&self) -> &[f64] {
+        &self.factors[0].times
+    }
+
+    /// Number of paths.
+    pub fn n_paths(&self) -> usize {
+        self.factors[0].n_paths()
+    }
+
+    /// Values of all risk factors on path `i` at the `k`-th time point.
+    pub fn factor_values(&self, i: usize, k: usize) -> Vec<f64> {
+        self.factors
+            .iter()
+            .map(|factor| factor.paths[(i, k)])
+            .collect()
+    }
+}
+
+impl NettingSet {
+    /// Empty netting set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an instrument.
+    pub fn with_instrument<I: ExposureInstrument + 'static>(mut self, instrument: I) -> Self {
+        self.instruments.push(Box::new(instrument));
+        self
+    }
+
+    /// Net value at time `t` of the instruments not yet matured.
+    pub fn value(&self, t: f64, factors: &[f64]) -> f64 {
+        self.instruments
+            .iter()
+            .filter(|instrument| t <= instrument.maturity())
+            .map(|instrument| instrument.value(t, factors))
+            .sum()
+    }
+
+    /// Revalues the netting set on every scenario.
+    pub fn exposure(&self, scenarios: &Scenarios) -> ExposureProfile {
+        let times = scenarios.times().to_vec();
+        let values = Array2::from_shape_fn((scenarios.n_paths(), times.len()), |(i, k)| {
+            self.value(times[k], &scenarios.factor_values(i, k))
+        });
+
+        ExposureProfile { times, values }
+    }
+}
+
+impl ExposureProfile {
+    /// Expected value, $E[V(t)]$, at each time point.
+    pub fn expected_exposure(&self) -> Vec<f64> {
+        self.column_means(|value| value)
+    }
+
+    /// Expected positive exposure (EPE), $E[\max(V(t), 0)]$.
+    pub fn expected_positive_exposure(&self) -> Vec<f64> {
+        self.column_means(|value| value.max(0.0))
+    }
+
+    /// Expected negative exposure (ENE), $E[\max(-V(t), 0)]$, as a
+    /// positive amount.
+    pub fn expected_negative_exposure(&self) -> Vec<f64> {
+        self.column_means(|value| (-value).max(0.0))
+    }
+
+    /// Potential future exposure: the `quantile` of the positive exposure
+    /// at each time point (e.g. 0.95).
+    pub fn potential_future_exposure(&self, quantile: f64) -> Vec<f64> {
+        assert!(
+            (0.0..=1.0).contains(&quantile),
+            "Quantile must be in [0, 1]."
+        );
+
+        self.values
+            .columns()
+            .into_iter()
+            .map(|column| {
+                let mut exposures: Vec<f64> = column.iter().map(|value| value.max(0.0)).collect();
+                exposures.sort_by(|a, b| a.total_cmp(b));
+
+                let rank = (quantile * exposures.len() as f64).ceil() as usize;
+                exposures[rank.clamp(1, exposures.len()) - 1]
+            })
+            .collect()
+    }
+
+    fn column_means(&self, f: impl Fn(f64) -> f64) -> Vec<f64> {
+        self.values
+            .columns()
+            .into_iter()
+            .map(|column: ArrayView1<f64>| {
+                column.iter().map(|value| f(*value)).sum::<f64>() / column.len() as f64
+            })
+            .collect()
+    }
+}
+
+impl EquityForward {
+    /// New forward contract.
+    pub fn new(
+        factor: usize,
+        strike: f64,
+        maturity: f64,
+        notional: f64,
+        risk_free_rate: f64,
+        cost_of_carry: f64,
+    ) -> Self {
+        Self {
+            factor,
+            strike,
+            maturity,
+            notional,
+            risk_free_rate,
+            cost_of_carry,
+        }
+    }
+}
+
+impl ExposureInstrument for EquityForward {
+    fn value(&self, t: f64, factors: &[f64]) -> f64 {
+        let tau = self.maturity - t;
+        let (r, b) = (self.risk_free_rate, self.cost_of_carry);
+
+        self.notional
+            * (factors[self.factor] * ((b - r) * tau).exp() - self.strike * (-r * tau).exp())
+    }
+
+    fn maturity(&self) -> f64 {
+        self.maturity
+    }
+}
+
+impl EquityOption {
+    /// New European option.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        factor: usize,
+        option_type: TypeFlag,
+        strike: f64,
+        maturity: f64,
+        notional: f64,
+        volatility: f64,
+        risk_free_rate: f64,
+        cost_of_carry: f64,
+    ) -> Self {
+        Self {
+            factor,
+            option_type,
+            strike,
+            maturity,
+            notional,
+            volatility,
+            risk_free_rate,
+            cost_of_carry,
+        }
+    }
+}
+
+impl ExposureInstrument for EquityOption {
+    fn value(&self, t: f64, factors: &[f64]) -> f64 {
+        let spot = factors[self.factor];
+        let tau = self.maturity - t;
+
+        // At expiry the option is worth its payoff.
+        let value = match tau > 0.0 {
+            true => BlackScholesInputs {
+                underlying_price: spot,
+                strike_price: self.strike,
+                volatility: self.volatility,
+                risk_free_rate: self.risk_free_rate,
+                cost_of_carry: self.cost_of_carry,
+                time_to_expiry: tau,
+                option_type: self.option_type,
+            }
+            .price(),
+            false => match self.option_type {
+                TypeFlag::Call => (spot - self.strike).max(0.0),
+                TypeFlag::Put => (self.strike - spot).max(0.0),
+            },
+        };
+
+        self.notional * value
+    }
+
+    fn maturity(&self) -> f64 {
+        self.maturity
+    }
+}
+
+impl VasicekSwap {
+    /// New swap on the short rate model `model` (an Ornstein-Uhlenbeck
+    /// process). Positive notionals pay fixed, negative ones receive fixed.
+    pub fn new(
+        factor: usize,
+        model: &OrnsteinUhlenbeck,
+        start: f64,
+        payment_times: Vec<f64>,
+        fixed_rate: f64,
+        notional: f64,
+    ) -> Self {
+        assert!(
+            payment_times.first().is_some_and(|first| *first > start)
+                && payment_times.windows(2).all(|pair| pair[0] < pair[1]),
+            "Payment times must be increasing and after the start."
+        );
+
+        Self {
+            factor,
+            notional,
+            fixed_rate,
+            start,
+            payment_times,
+            speed: model.theta,
+            mean: model.mu,
+            volatility: model.sigma,
+        }
+    }
+
+    /// Price at time `t` of a zero-coupon bond maturing at `maturity` when
+    /// the short rate is `rate`.
+    pub fn discount_bond(&self, rate: f64, t: f64, maturity: f64) -> f64 {
+        let (kappa, theta, sigma) = (self.speed, self.mean, self.volatility);
+        let tau = maturity - t;
+        let b = (1.0 - (-kappa * tau).exp()) / kappa;
+        let log_a = (theta - sigma * sigma / (2.0 * kappa * kappa)) * (b - tau)
+            - sigma * sigma * b * b / (4.0 * kappa);
+
+        (log_a - b * rate).exp()
+    }
+
+    /// Fixed rate that gives the swap zero value at time 0 with the short
+    /// rate at `rate`.
+    pub fn par_rate(&self, rate: f64) -> f64 {
+        let (floating, annuity) = self.legs(0.0, rate);
+
+        floating / annuity
+    }
+
+    // Floating leg value and annuity of the payments after t.
+    fn legs(&self, t: f64, rate: f64) -> (f64, f64) {
+        let mut previous = self.start;
+        let mut first = None;
+        let mut annuity = 0.0;
+
+        for payment in &self.payment_times {
+            if *payment > t {
+                first.get_or_insert(previous.max(t));
+                annuity += (payment - previous) * self.discount_bond(rate, t, *payment);
+            }
+            previous = *payment;
+        }
+
+        match first {
+            Some(first) => (
+                self.discount_bond(rate, t, first) - self.discount_bond(rate, t, previous),
+                annuity,
+            ),
+            None => (0.0, 0.0),
+        }
+    }
+}
+
+impl ExposureInstrument for VasicekSwap {
+    fn value(&self, t: f64, factors: &[f64]) -> f64 {
+        let (floating, annuity) = self.legs(t, factors[self.factor]);
+
+        self.notional * (floating - self.fixed_rate * annuity)
+    }
+
+    fn maturity(&self) -> f64 {
+        self.payment_times[self.payment_times.len() - 1]
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_exposure {
+    use super::*;
+    use crate::stochastics::GeometricBrownianMotion;
+
+    fn scenarios() -> Scenarios {
+        ScenarioGenerator::new(1.0, 50, 20_000)
+            .with_factor("stock", GeometricBrownianMotion::new(0.0, 0.2), 100.0)
+            .with_config(SimulationConfig::new(true).with_seed(1))
+            .simulate()
+    }
+
+    #[test]
+    fn test_forward_exposure() {
+        let scenarios = scenarios();
+        let forward = EquityForward::new(0, 100.0, 1.0, 1.0, 0.0, 0.0);
+        let profile = NettingSet::new()
+            .with_instrument(forward)
+            .exposure(&scenarios);
+
+        // With zero rates the EPE (ENE) at t is the price of a call (put)
+        // expiring at t.
+        for k in [10, 25, 50] {
+            let t = profile.times[k];
+            let call = BlackScholesInputs {
+                underlying_price: 100.0,
+                strike_price: 100.0,
+                volatility: 0.2,
+                risk_free_rate: 0.0,
+                cost_of_carry: 0.0,
+                time_to_expiry: t,
+                option_type: TypeFlag::Call,
+            }
+            .price();
+
+            assert_approx_equal!(profile.expected_positive_exposure()[k], call, 0.25);
+            assert_approx_equal!(profile.expected_negative_exposure()[k], call, 0.25);
+            assert_approx_equal!(profile.expected_exposure()[k], 0.0, 0.35);
+        }
+
+        // No exposure at inception; PFE above EPE.
+        assert_eq!(profile.expected_positive_exposure()[0], 0.0);
+        let pfe = profile.potential_future_exposure(0.95);
+        assert!(pfe[50] > profile.expected_positive_exposure()[50]);
+    }
+
+    #[test]
+    fn test_netting() {
+        let scenarios = scenarios();
+        let long = EquityForward::new(0, 100.0, 1.0, 1.0, 0.0, 0.0);
+        let short = EquityForward::new(0, 100.0, 0.5, -1.0, 0.0, 0.0);
+
+        let netted = NettingSet::new()
+            .with_instrument(long)
+            .with_instrument(short)
+            .exposure(&scenarios);
+        let epe = netted.expected_positive_exposure();
+
+        // Fully offset until the short forward matures.
+        assert_eq!(epe[10], 0.0);
+        assert_eq!(epe[25], 0.0);
+        assert!(epe[26] > 0.0);
+
+        // A long option is never a liability.
+        let option = EquityOption::new(0, TypeFlag::Put, 95.0, 1.0, 2.0, 0.2, 0.0, 0.0);
+        let profile = NettingSet::new()
+            .with_instrument(option)
+            .exposure(&scenarios);
+        assert!(profile
+            .expected_negative_exposure()
+            .iter()
+            .all(|ene| *ene == 0.0));
+        assert!(profile.expected_positive_exposure()[0] > 0.0);
+    }
+
+    #[test]
+    fn test_vasicek_swap() {
+        let model = OrnsteinUhlenbeck::new(0.04, 0.01, 0.5);
+        let payments: Vec<f64> = (1..=10).map(|i| 0.5 * i as f64).collect();
+        let mut swap = VasicekSwap::new(0, &model, 0.0, payments, 0.0, 1e6);
+
+        // At the par rate the swap is worth nothing at inception.
+        swap.fixed_rate = swap.par_rate(0.03);
+        assert!(swap.fixed_rate > 0.03 && swap.fixed_rate < 0.04);
+        assert_approx_equal!(swap.value(0.0, &[0.03]), 0.0, 1e-8);
+
+        // The payer swap gains when rates rise, and is worthless at maturity.
+        assert!(swap.value(1.0, &[0.05]) > 0.0);
+        assert!(swap.value(1.0, &[0.01]) < 0.0);
+        assert_eq!(swap.value(5.0, &[0.05]), 0.0);
+
+        // Bond prices: P(t, t) = 1, decreasing in the rate.
+        assert_approx_equal!(swap.discount_bond(0.03, 2.0, 2.0), 1.0, 1e-15);
+        assert!(swap.discount_bond(0.05, 0.0, 5.0) < swap.discount_bond(0.03, 0.0, 5.0));
+    }
+}
@@ -0,0 +1,489 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::xva::{RiskClass, Sensitivity, SensitivityType};
+use ndarray::Array2;
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Risk weights and correlations of one risk class.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RiskClassParameters {
+    /// Delta risk weight of each bucket.
+    pub delta_weights: Vec<f64>,
+
+    /// Vega risk weight of each bucket (1 by default).
+    pub vega_weights: Vec<f64>,
+
+    /// Delta risk weight of each tenor, overriding the bucket weight for
+    /// sensitivities with a tenor.
+    pub tenor_weights: Option<Vec<f64>>,
+
+    /// Correlation between different risk factors of each bucket.
+    pub intra_bucket_correlations: Vec<f64>,
+
+    /// Correlation between tenors of the same bucket.
+    pub tenor_correlations: Option<Array2<f64>>,
+
+    /// Correlation between buckets.
+    pub inter_bucket_correlations: Array2<f64>,
+}
+
+/// Parameters of the SIMM-style initial margin model.
+///
+/// No calibration is bundled: the official SIMM risk weights and
+/// correlations are published by ISDA to licensees.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimmParameters {
+    /// Parameters of each risk class with sensitivities.
+    pub risk_classes: BTreeMap<RiskClass, RiskClassParameters>,
+
+    /// Correlation between risk classes (6 x 6, in the order of the
+    /// [`RiskClass`] variants).
+    pub risk_class_correlations: Array2<f64>,
+}
+
+/// Initial margin of one risk class.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RiskClassMargin {
+    /// Delta margin.
+    pub delta: f64,
+    /// Vega margin.
+    pub vega: f64,
+    /// Curvature margin.
+    pub curvature: f64,
+}
+
+/// Initial margin of a portfolio.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InitialMargin {
+    /// Margin of each risk class with sensitivities.
+    pub risk_classes: BTreeMap<RiskClass, RiskClassMargin>,
+
+    /// Total margin, aggregated across risk classes.
+    pub total: f64,
+}
+
+/// Initial margin errors.
+#[derive(Debug, Clone, Copy, PartialEq, Error)]
+pub enum MarginError {
+    /// No parameters for the risk class.
+    #[error("No parameters for the {0:?} risk class.")]
+    UnknownRiskClass(RiskClass),
+
+    /// The bucket is outside the risk class parameters.
+    #[error("Bucket {1} is not defined for the {0:?} risk class.")]
+    UnknownBucket(RiskClass, usize),
+
+    /// The tenor is outside the risk class parameters.
+    #[error("Tenor {1} is not defined for the {0:?} risk class.")]
+    UnknownTenor(RiskClass, usize),
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// $\Phi^{-1}(0.995)^2 - 1$, scaling the curvature margin.
+const CURVATURE_QUANTILE: f64 = 5.634_896_601_021_214;
+
+// Net weighted sensitivities, by bucket then by (risk factor, tenor).
+type Buckets<'a> = BTreeMap<usize, BTreeMap<(&'a str, Option<usize>), f64>>;
+
+impl RiskClassParameters {
+    /// Parameters with the given delta risk weights, intra-bucket and
+    /// inter-bucket correlations, one entry per bucket.
+    pub fn new(
+        delta_weights: Vec<f64>,
+        intra_bucket_correlations: Vec<f64>,
+        inter_bucket_correlations: Array2<f64>,
+    ) -> Self {
+        let n = delta_weights.len();
+        assert!(
+            intra_bucket_correlations.len() == n && inter_bucket_correlations.dim() == (n, n),
+            "One weight and correlation per bucket are needed."
+        );
+
+        Self {
+            delta_weights,
+            vega_weights: vec![1.0; n],
+            tenor_weights: None,
+            intra_bucket_correlations,
+            tenor_correlations: None,
+            inter_bucket_correlations,
+        }
+    }
+
+    /// Parameters of a risk class with a single bucket.
+    pub fn single_bucket(delta_weight: f64, correlation: f64) -> Self {
+        Self::new(
+            vec![delta_weight],
+            vec![correlation],
+            Array2::from_elem((1, 1), 1.0),
+        )
+    }
+
+    /// Sets the vega risk weights, one per bucket.
+    pub fn with_vega_weights(mut self, vega_weights: Vec<f64>) -> Self {
+        assert_eq!(vega_weights.len(), self.delta_weights.len());
+        self.vega_weights = vega_weights;
+        self
+    }
+
+    /// Sets the tenor delta risk weights and correlations.
+    pub fn with_tenors(mut self, weights: Vec<f64>, correlations: Array2<f64>) -> Self {
+        assert_eq!(correlations.dim(), (weights.len(), weights.len()));
+        self.tenor_weights = Some(weights);
+        self.tenor_correlations = Some(correlations);
+        self
+    }
+
+    /// Delta, vega or curvature margin of the sensitivities, all of this
+    /// risk class.
+    fn margin(
+        &self,
+        risk_class: RiskClass,
+        sensitivity_type: SensitivityType,
+        sensitivities: &[&Sensitivity],
+    ) -> Result<f64, MarginError> {
+        let mut buckets = Buckets::new();
+
+        for sensitivity in sensitivities
+            .iter()
+            .filter(|s| s.sensitivity_type == sensitivity_type)
+        {
+            let bucket = sensitivity.bucket;
+            if bucket >= self.delta_weights.len() {
+                return Err(MarginError::UnknownBucket(risk_class, bucket));
+            }
+
+            let weight = match (sensitivity_type, sensitivity.tenor, &self.tenor_weights) {
+                (_, Some(tenor), Some(weights)) if tenor >= weights.len() => {
+                    return Err(MarginError::UnknownTenor(risk_class, tenor))
+                }
+                (SensitivityType::Delta, Some(tenor), Some(weights)) => weights[tenor],
+                (SensitivityType::Delta, ..) => self.delta_weights[bucket],
+                (SensitivityType::Vega, ..) => self.vega_weights[bucket],
+                (SensitivityType::Curvature, ..) => 1.0,
+            };
+
+            *buckets
+                .entry(bucket)
+                .or_default()
+                .entry((sensitivity.risk_factor.as_str(), sensitivity.tenor))
+                .or_default() += weight * sensitivity.amount;
+        }
+
+        // Curvature aggregates with squared correlations.
+        let curvature = sensitivity_type == SensitivityType::Curvature;
+        let power = if curvature { 2 } else { 1 };
+
+        // Margin K_b and capped net sensitivity S_b of each bucket.
+        let aggregated: Vec<(usize, f64, f64)> = buckets
+            .iter()
+            .map(|(bucket, factors)| {
+                let mut variance = 0.0;
+                for (k, ws_k) in factors {
+                    for (l, ws_l) in factors {
+                        variance += self.correlation(*bucket, k, l).powi(power) * ws_k * ws_l;
+                    }
+                }
+                let margin = variance.max(0.0).sqrt();
+                let net = factors.values().sum::<f64>();
+
+                (*bucket, margin, net.clamp(-margin, margin))
+            })
+            .collect();
+
+        let mut variance = 0.0;
+        for (b, margin_b, net_b) in &aggregated {
+            for (c, _, net_c) in &aggregated {
+                variance += match b == c {
+                    true => margin_b * margin_b,
+                    false => self.inter_bucket_correlations[(*b, *c)].powi(power) * net_b * net_c,
+                };
+            }
+        }
+        let margin = variance.max(0.0).sqrt();
+
+        if !curvature {
+            return Ok(margin);
+        }
+
+        let (net, gross) = buckets
+            .values()
+            .flat_map(|factors| factors.values())
+            .fold((0.0, 0.0), |(net, gross), cvr| {
+                (net + cvr, gross + cvr.abs())
+            });
+        if gross == 0.0 {
+            return Ok(0.0);
+        }
+
+        // Net short curvature reduces the margin (theta < 0).
+        let theta = (net / gross).min(0.0);
+        let lambda = CURVATURE_QUANTILE * (1.0 + theta) - theta;
+
+        Ok((net + lambda * margin).max(0.0))
+    }
+
+    // Correlation of two (risk factor, tenor) pairs of a bucket.
+    fn correlation(
+        &self,
+        bucket: usize,
+        k: &(&str, Option<usize>),
+        l: &(&str, Option<usize>),
+    ) -> f64 {
+        if k == l {
+            return 1.0;
+        }
+
+        let tenors = match (k.1, l.1, &self.tenor_correlations) {
+            (Some(i), Some(j), Some(correlations)) => correlations[(i, j)],
+            _ => 1.0,
+        };
+        let factors = match k.0 == l.0 {
+            true => 1.0,
+            false => self.intra_bucket_correlations[bucket],
+        };
+
+        tenors * factors
+    }
+}
+
+impl SimmParameters {
+    /// Parameters with the given correlations between risk classes.
+    pub fn new(risk_class_correlations: Array2<f64>) -> Self {
+        assert_eq!(risk_class_correlations.dim(), (6, 6));
+
+        Self {
+            risk_classes: BTreeMap::new(),
+            risk_class_correlations,
+        }
+    }
+
+    /// Sets the parameters of a risk class.
+    pub fn with_risk_class(
+        mut self,
+        risk_class: RiskClass,
+        parameters: RiskClassParameters,
+    ) -> Self {
+        self.risk_classes.insert(risk_class, parameters);
+        self
+    }
+
+    /// Initial margin of a portfolio (one netting set) from its
+    /// sensitivities.
+    ///
+    /// Within a risk class, the weighted sensitivities $WS_k$ of each
+    /// bucket $b$ are aggregated into
+    /// $K_b = \sqrt{\sum_{k,l} \rho_{kl} WS_k WS_l}$, and the buckets into
+    /// $\sqrt{\sum_b K_b^2 + \sum_{b \neq c} \gamma_{bc} S_b S_c}$ with
+    /// $S_b = \max(\min(\sum_k WS_k, K_b), -K_b)$. The delta, vega and
+    /// curvature margins of a risk class are added, and the risk classes
+    /// aggregated with their correlations.
+    pub fn initial_margin(
+        &self,
+        sensitivities: &[Sensitivity],
+    ) -> Result<InitialMargin, MarginError> {
+        let mut by_class: BTreeMap<RiskClass, Vec<&Sensitivity>> = BTreeMap::new();
+        for sensitivity in sensitivities {
+            by_class
+                .entry(sensitivity.risk_class)
+                .or_default()
+                .push(sensitivity);
+        }
+
+        let mut risk_classes = BTreeMap::new();
+        for (risk_class, sensitivities) in by_class {
+            let parameters = self
+                .risk_classes
+                .get(&risk_class)
+                .ok_or(MarginError::UnknownRiskClass(risk_class))?;
+
+            risk_classes.insert(
+                risk_class,
+                RiskClassMargin {
+                    delta: parameters.margin(risk_class, SensitivityType::Delta, &sensitivities)?,
+                    vega: parameters.margin(risk_class, SensitivityType::Vega, &sensitivities)?,
+                    curvature: parameters.margin(
+                        risk_class,
+                        SensitivityType::Curvature,
+                        &sensitivities,
+                    )?,
+                },
+            );
+        }
+
+        let mut variance = 0.0;
+        for (r, margin_r) in &risk_classes {
+            for (s, margin_s) in &risk_classes {
+                variance += match r == s {
+                    true => 1.0,
+                    false => self.risk_class_correlations[(*r as usize, *s as usize)],
+                } * margin_r.total()
+                    * margin_s.total();
+            }
+        }
+
+        Ok(InitialMargin {
+            risk_classes,
+            total: variance.max(0.0).sqrt(),
+        })
+    }
+
+    /// Initial margin of each netting set.
+    pub fn initial_margins<K: Ord + Clone>(
+        &self,
+        netting_sets: &BTreeMap<K, Vec<Sensitivity>>,
+    ) -> Result<BTreeMap<K, InitialMargin>, MarginError> {
+        netting_sets
+            .iter()
+            .map(|(key, sensitivities)| Ok((key.clone(), self.initial_margin(sensitivities)?)))
+            .collect()
+    }
+}
+
+impl RiskClassMargin {
+    /// Margin of the risk class: delta, vega and curvature margins added.
+    pub fn total(&self) -> f64 {
+        self.delta + self.vega + self.curvature
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_initial_margin {
+    use super::*;
+    use ndarray::array;
+
+    fn parameters() -> SimmParameters {
+        let equity = RiskClassParameters::new(
+            vec![0.25, 0.30],
+            vec![0.2, 0.3],
+            array![[1.0, 0.15], [0.15, 1.0]],
+        )
+        .with_vega_weights(vec![0.5, 0.5]);
+        let rates = RiskClassParameters::single_bucket(0.0, 0.98)
+            .with_tenors(vec![100.0, 50.0], array![[1.0, 0.6], [0.6, 1.0]]);
+
+        let mut correlations = Array2::eye(6);
+        correlations[(0, 3)] = 0.2;
+        correlations[(3, 0)] = 0.2;
+
+        SimmParameters::new(correlations)
+            .with_risk_class(RiskClass::Equity, equity)
+            .with_risk_class(RiskClass::InterestRate, rates)
+    }
+
+    #[test]
+    fn test_delta_aggregation() {
+        let parameters = parameters();
+        let margin =
+            |sensitivities: &[Sensitivity]| parameters.initial_margin(sensitivities).unwrap().total;
+
+        // One sensitivity: the weighted sensitivity.
+        let a = Sensitivity::delta(RiskClass::Equity, 0, "A", 100.0);
+        assert_approx_equal!(margin(std::slice::from_ref(&a)), 25.0, 1e-12);
+
+        // Same risk factor: netted.
+        let hedge = Sensitivity::delta(RiskClass::Equity, 0, "A", -100.0);
+        assert_eq!(margin(&[a.clone(), hedge]), 0.0);
+
+        // Two risk factors of a bucket.
+        let b = Sensitivity::delta(RiskClass::Equity, 0, "B", -40.0);
+        let expected = (25.0_f64.powi(2) + 10.0_f64.powi(2) - 2.0 * 0.2 * 25.0 * 10.0).sqrt();
+        let total = margin(&[a.clone(), b]);
+        assert_approx_equal!(total, expected, 1e-12);
+
+        // Two buckets.
+        let c = Sensitivity::delta(RiskClass::Equity, 1, "C", 50.0);
+        let expected = (25.0_f64.powi(2) + 15.0_f64.powi(2) + 2.0 * 0.15 * 25.0 * 15.0).sqrt();
+        let total = margin(&[a.clone(), c]);
+        assert_approx_equal!(total, expected, 1e-12);
+
+        // Tenors: weights and correlations from the tenor parameters.
+        let rates = [
+            Sensitivity::delta(RiskClass::InterestRate, 0, "USD", 1.0).with_tenor(0),
+            Sensitivity::delta(RiskClass::InterestRate, 0, "USD", 2.0).with_tenor(1),
+        ];
+        let expected = (2.0_f64 * 10_000.0 + 2.0 * 0.6 * 100.0 * 100.0).sqrt();
+        assert_approx_equal!(margin(&rates), expected, 1e-9);
+
+        // Across risk classes.
+        let all = [a, rates[0].clone(), rates[1].clone()];
+        let expected = (25.0_f64.powi(2) + expected.powi(2) + 2.0 * 0.2 * 25.0 * expected).sqrt();
+        assert_approx_equal!(margin(&all), expected, 1e-9);
+    }
+
+    #[test]
+    fn test_vega_and_curvature() {
+        let parameters = parameters();
+        let sensitivities = [
+            Sensitivity::vega(RiskClass::Equity, 0, "A", 20.0),
+            Sensitivity::curvature(RiskClass::Equity, 0, "A", 2.0),
+        ];
+        let margin = parameters.initial_margin(&sensitivities).unwrap();
+        let equity = margin.risk_classes[&RiskClass::Equity];
+
+        assert_eq!(equity.delta, 0.0);
+        assert_approx_equal!(equity.vega, 10.0, 1e-12);
+        assert_approx_equal!(equity.curvature, 2.0 * (1.0 + CURVATURE_QUANTILE), 1e-12);
+        assert_approx_equal!(margin.total, equity.total(), 1e-12);
+
+        // Short curvature does not attract margin on its own.
+        let short = [Sensitivity::curvature(RiskClass::Equity, 0, "A", -2.0)];
+        assert_eq!(parameters.initial_margin(&short).unwrap().total, 0.0);
+    }
+
+    #[test]
+    fn test_netting_sets_and_errors() {
+        let parameters = parameters();
+        let netting_sets = BTreeMap::from([
+            (
+                "hedged",
+                vec![
+                    Sensitivity::delta(RiskClass::Equity, 0, "A", 100.0),
+                    Sensitivity::delta(RiskClass::Equity, 0, "A", -100.0),
+                ],
+            ),
+            (
+                "outright",
+                vec![Sensitivity::delta(RiskClass::Equity, 1, "C", 100.0)],
+            ),
+        ]);
+        let margins = parameters.initial_margins(&netting_sets).unwrap();
+
+        assert_eq!(margins["hedged"].total, 0.0);
+        assert_approx_equal!(margins["outright"].total, 30.0, 1e-12);
+
+        let unknown = [Sensitivity::delta(RiskClass::Commodity, 0, "Oil", 1.0)];
+        assert_eq!(
+            parameters.initial_margin(&unknown),
+            Err(MarginError::UnknownRiskClass(RiskClass::Commodity))
+        );
+        let unknown = [Sensitivity::delta(RiskClass::Equity, 2, "D", 1.0)];
+        assert_eq!(
+            parameters.initial_margin(&unknown),
+            Err(MarginError::UnknownBucket(RiskClass::Equity, 2))
+        );
+        let unknown = [Sensitivity::delta(RiskClass::InterestRate, 0, "USD", 1.0).with_tenor(5)];
+        assert_eq!(
+            parameters.initial_margin(&unknown),
+            Err(MarginError::UnknownTenor(RiskClass::InterestRate, 5))
+        );
+    }
+}
@@ -0,0 +1,67 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+//! Counterparty exposure simulation and valuation adjustments (XVA).
+//!
+//! 1. Risk factors are simulated with the `stochastics` processes
+//!    ([`ScenarioGenerator`]).
+//! 2. The instruments of a [`NettingSet`] are revalued on every path and
+//!    time point, giving the exposure distribution over time
+//!    ([`ExposureProfile`]): expected positive and negative exposure
+//!    (EPE/ENE) and potential future exposure (PFE).
+//! 3. The exposure profiles are integrated against the default probabilities
+//!    of a [`HazardCurve`] and the spreads of a [`FundingCurve`] into the
+//!    credit, debit and funding valuation adjustments (CVA/DVA/FVA).
+//!
+//! Initial margin is computed separately, SIMM-style, by aggregating the
+//! delta, vega and curvature [`Sensitivity`]s of a netting set by risk class
+//! and bucket ([`SimmParameters::initial_margin`]).
+//!
+//! ```
+//! use RustQuant::stochastics::*;
+//! use RustQuant::xva::*;
+//!
+//! // One year forward on a stock, with 25% volatility.
+//! let scenarios = ScenarioGenerator::new(1.0, 12, 2_000)
+//!     .with_factor("stock", GeometricBrownianMotion::new(0.03, 0.25), 100.0)
+//!     .with_config(SimulationConfig::new(true).with_seed(7))
+//!     .simulate();
+//!
+//! let netting_set = NettingSet::new().with_instrument(EquityForward::new(0, 100.0, 1.0, 1.0, 0.03, 0.03));
+//! let profile = netting_set.exposure(&scenarios);
+//!
+//! // Counterparty spread of 120bp with 40% recovery; own spread of 60bp.
+//! let inputs = XvaInputs::new(0.03, HazardCurve::from_cds_spread(0.012, 0.4), 0.4)
+//!     .with_own_credit(HazardCurve::from_cds_spread(0.006, 0.4), 0.4)
+//!     .with_funding(FundingCurve::flat(0.005), FundingCurve::flat(0.005));
+//! let xva = profile.valuation_adjustments(&inputs);
+//!
+//! assert!(xva.cva > xva.dva && xva.dva > 0.0);
+//! assert!(xva.total() < 0.0);
+//! ```
+
+/// Hazard and funding spread curves.
+pub mod curves;
+pub use curves::*;
+
+/// Exposure simulation: scenarios, instruments and netting sets.
+pub mod exposure;
+pub use exposure::*;
+
+/// SIMM-style initial margin.
+pub mod initial_margin;
+pub use initial_margin::*;
+
+/// Risk factor sensitivities, grouped by risk class.
+pub mod sensitivities;
+pub use sensitivities::*;
+
+/// Credit, debit and funding valuation adjustments.
+pub mod valuation_adjustments;
+pub use valuation_adjustments::*;
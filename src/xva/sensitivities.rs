@@ -0,0 +1,195 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::instruments::options::StrategyGreeks;
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// SIMM risk classes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RiskClass {
+    /// Interest rates.
+    InterestRate = 0,
+    /// Credit, qualifying (e.g. investment grade names and indices).
+    CreditQualifying = 1,
+    /// Credit, non-qualifying (e.g. securitisations).
+    CreditNonQualifying = 2,
+    /// Equities.
+    Equity = 3,
+    /// Commodities.
+    Commodity = 4,
+    /// Foreign exchange.
+    ForeignExchange = 5,
+}
+
+/// Kind of sensitivity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SensitivityType {
+    /// Change in value for a shift of the risk factor.
+    Delta,
+    /// Vega risk: vega times the implied volatility.
+    Vega,
+    /// Curvature risk: the value change not explained by delta when the
+    /// volatility is shifted.
+    Curvature,
+}
+
+/// A sensitivity of a portfolio to one risk factor, in currency units.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sensitivity {
+    /// Risk class of the risk factor.
+    pub risk_class: RiskClass,
+
+    /// Delta, vega or curvature.
+    pub sensitivity_type: SensitivityType,
+
+    /// Bucket of the risk factor within its risk class (0-based), e.g. a
+    /// currency or an equity sector.
+    pub bucket: usize,
+
+    /// Name of the risk factor, e.g. a ticker or a curve.
+    pub risk_factor: String,
+
+    /// Tenor index of the risk factor, for term structure risk factors.
+    pub tenor: Option<usize>,
+
+    /// Amount of the sensitivity.
+    pub amount: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Holding period of the curvature scaling function, in years (14 days).
+const CURVATURE_HORIZON: f64 = 14.0 / 365.0;
+
+impl Sensitivity {
+    /// New sensitivity, without a tenor.
+    pub fn new(
+        risk_class: RiskClass,
+        sensitivity_type: SensitivityType,
+        bucket: usize,
+        risk_factor: &str,
+        amount: f64,
+    ) -> Self {
+        Self {
+            risk_class,
+            sensitivity_type,
+            bucket,
+            risk_factor: risk_factor.to_string(),
+            tenor: None,
+            amount,
+        }
+    }
+
+    /// Delta sensitivity.
+    pub fn delta(risk_class: RiskClass, bucket: usize, risk_factor: &str, amount: f64) -> Self {
+        Self::new(
+            risk_class,
+            SensitivityType::Delta,
+            bucket,
+            risk_factor,
+            amount,
+        )
+    }
+
+    /// Vega risk.
+    pub fn vega(risk_class: RiskClass, bucket: usize, risk_factor: &str, amount: f64) -> Self {
+        Self::new(
+            risk_class,
+            SensitivityType::Vega,
+            bucket,
+            risk_factor,
+            amount,
+        )
+    }
+
+    /// Curvature risk.
+    pub fn curvature(risk_class: RiskClass, bucket: usize, risk_factor: &str, amount: f64) -> Self {
+        Self::new(
+            risk_class,
+            SensitivityType::Curvature,
+            bucket,
+            risk_factor,
+            amount,
+        )
+    }
+
+    /// Sets the tenor index of the risk factor.
+    pub fn with_tenor(mut self, tenor: usize) -> Self {
+        self.tenor = Some(tenor);
+        self
+    }
+
+    /// Delta, vega and curvature sensitivities of an option position from
+    /// its Greeks, following the SIMM definitions:
+    ///
+    /// - delta: value change for a 1% relative shift of the underlying,
+    ///   $0.01 \Delta S$,
+    /// - vega risk: $\mathcal{V} \sigma$,
+    /// - curvature risk: $\frac{1}{2} \min(1, \frac{14 \text{ days}}{T}) \mathcal{V} \sigma$.
+    pub fn from_greeks(
+        risk_class: RiskClass,
+        bucket: usize,
+        risk_factor: &str,
+        greeks: &StrategyGreeks,
+        spot: f64,
+        volatility: f64,
+        expiry: f64,
+    ) -> Vec<Self> {
+        let vega_risk = greeks.vega * volatility;
+        let scaling = 0.5 * (CURVATURE_HORIZON / expiry).min(1.0);
+
+        vec![
+            Self::delta(risk_class, bucket, risk_factor, 0.01 * greeks.delta * spot),
+            Self::vega(risk_class, bucket, risk_factor, vega_risk),
+            Self::curvature(risk_class, bucket, risk_factor, scaling * vega_risk),
+        ]
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_sensitivities {
+    use super::*;
+    use crate::instruments::options::{OptionStrategy, StrategyLeg, StrategyMarket};
+
+    #[test]
+    fn test_from_greeks() {
+        let market = StrategyMarket::new(100.0, 0.2, 0.0, 0.0, 1.0);
+        let greeks = OptionStrategy::new()
+            .with_leg(StrategyLeg::call(100.0, 10.0))
+            .greeks(&market);
+
+        let sensitivities =
+            Sensitivity::from_greeks(RiskClass::Equity, 2, "ACME", &greeks, 100.0, 0.2, 1.0);
+
+        assert_eq!(sensitivities.len(), 3);
+        assert_approx_equal!(sensitivities[0].amount, greeks.delta, 1e-12);
+        assert_approx_equal!(sensitivities[1].amount, 0.2 * greeks.vega, 1e-12);
+        assert_approx_equal!(
+            sensitivities[2].amount,
+            0.5 * 14.0 / 365.0 * 0.2 * greeks.vega,
+            1e-12
+        );
+        assert!(sensitivities
+            .iter()
+            .all(|s| s.bucket == 2 && s.tenor.is_none()));
+        assert_eq!(
+            sensitivities[2].sensitivity_type,
+            SensitivityType::Curvature
+        );
+    }
+}
@@ -0,0 +1,228 @@
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// RustQuant: A Rust library for quantitative finance tools.
+// Copyright (C) 2023 https://github.com/avhz
+// Dual licensed under Apache 2.0 and MIT.
+// See:
+//      - LICENSE-APACHE.md
+//      - LICENSE-MIT.md
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+use crate::xva::{ExposureProfile, FundingCurve, HazardCurve};
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// STRUCTS, ENUMS, AND TRAITS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+/// Market inputs of the valuation adjustments.
+#[derive(Debug, Clone, PartialEq)]
+pub struct XvaInputs {
+    /// Continuously compounded risk-free rate, used for discounting.
+    pub risk_free_rate: f64,
+
+    /// Default intensity of the counterparty.
+    pub counterparty: HazardCurve,
+
+    /// Recovery rate on a counterparty default.
+    pub counterparty_recovery: f64,
+
+    /// Own default intensity (zero by default: no DVA).
+    pub own: HazardCurve,
+
+    /// Recovery rate on an own default.
+    pub own_recovery: f64,
+
+    /// Spread paid to fund positive exposure (zero by default).
+    pub borrowing: FundingCurve,
+
+    /// Spread earned on negative exposure (zero by default).
+    pub lending: FundingCurve,
+}
+
+/// Valuation adjustments of a netting set, all as positive amounts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ValuationAdjustments {
+    /// Credit valuation adjustment: expected loss on a counterparty default.
+    pub cva: f64,
+
+    /// Debit valuation adjustment: expected gain on an own default.
+    pub dva: f64,
+
+    /// Funding cost adjustment, on the positive exposure.
+    pub fca: f64,
+
+    /// Funding benefit adjustment, on the negative exposure.
+    pub fba: f64,
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// IMPLEMENTATIONS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+impl XvaInputs {
+    /// Inputs with a counterparty credit curve only.
+    pub fn new(risk_free_rate: f64, counterparty: HazardCurve, counterparty_recovery: f64) -> Self {
+        Self {
+            risk_free_rate,
+            counterparty,
+            counterparty_recovery,
+            own: HazardCurve::flat(0.0),
+            own_recovery: 0.0,
+            borrowing: FundingCurve::flat(0.0),
+            lending: FundingCurve::flat(0.0),
+        }
+    }
+
+    /// Sets the own credit curve and recovery rate.
+    pub fn with_own_credit(mut self, own: HazardCurve, own_recovery: f64) -> Self {
+        self.own = own;
+        self.own_recovery = own_recovery;
+        self
+    }
+
+    /// Sets the borrowing and lending funding spreads.
+    pub fn with_funding(mut self, borrowing: FundingCurve, lending: FundingCurve) -> Self {
+        self.borrowing = borrowing;
+        self.lending = lending;
+        self
+    }
+}
+
+impl ValuationAdjustments {
+    /// Funding valuation adjustment, $FCA - FBA$.
+    pub fn fva(&self) -> f64 {
+        self.fca - self.fba
+    }
+
+    /// Total adjustment to the risk-free value, $-CVA + DVA - FVA$.
+    pub fn total(&self) -> f64 {
+        self.dva - self.cva - self.fva()
+    }
+}
+
+impl ExposureProfile {
+    /// CVA, DVA and FVA of the exposure profile.
+    ///
+    /// On each interval $(t_{k-1}, t_k]$ of the time grid, with discount
+    /// factor $D$ and survival probabilities $S_C$ and $S_B$ of the
+    /// counterparty and ourselves,
+    ///
+    /// - $CVA = (1 - R_C) \sum_k D(t_k) EPE(t_k) S_B(t_{k-1}) [S_C(t_{k-1}) - S_C(t_k)]$,
+    /// - $DVA = (1 - R_B) \sum_k D(t_k) ENE(t_k) S_C(t_{k-1}) [S_B(t_{k-1}) - S_B(t_k)]$,
+    /// - $FCA = \sum_k s_b(t_k) D(t_k) EPE(t_k) S_C(t_k) S_B(t_k) (t_k - t_{k-1})$,
+    ///
+    /// and the FBA as the FCA with the lending spread and the ENE.
+    pub fn valuation_adjustments(&self, inputs: &XvaInputs) -> ValuationAdjustments {
+        let epe = self.expected_positive_exposure();
+        let ene = self.expected_negative_exposure();
+        let (counterparty, own) = (&inputs.counterparty, &inputs.own);
+
+        let mut adjustments = ValuationAdjustments {
+            cva: 0.0,
+            dva: 0.0,
+            fca: 0.0,
+            fba: 0.0,
+        };
+
+        for k in 1..self.times.len() {
+            let (start, end) = (self.times[k - 1], self.times[k]);
+            let discount = (-inputs.risk_free_rate * end).exp();
+            let survival = counterparty.survival_probability(end) * own.survival_probability(end);
+
+            adjustments.cva += (1.0 - inputs.counterparty_recovery)
+                * discount
+                * epe[k]
+                * own.survival_probability(start)
+                * counterparty.default_probability(start, end);
+            adjustments.dva += (1.0 - inputs.own_recovery)
+                * discount
+                * ene[k]
+                * counterparty.survival_probability(start)
+                * own.default_probability(start, end);
+            adjustments.fca +=
+                inputs.borrowing.spread(end) * discount * epe[k] * survival * (end - start);
+            adjustments.fba +=
+                inputs.lending.spread(end) * discount * ene[k] * survival * (end - start);
+        }
+
+        adjustments
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// UNIT TESTS
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+
+#[cfg(test)]
+mod tests_valuation_adjustments {
+    use super::*;
+    use crate::instruments::options::{BlackScholesInputs, TypeFlag};
+    use crate::stochastics::{GeometricBrownianMotion, SimulationConfig};
+    use crate::xva::{EquityForward, EquityOption, NettingSet, ScenarioGenerator};
+
+    #[test]
+    fn test_cva_of_long_option() {
+        let r = 0.03;
+        let scenarios = ScenarioGenerator::new(2.0, 40, 20_000)
+            .with_factor("stock", GeometricBrownianMotion::new(r, 0.25), 100.0)
+            .with_config(SimulationConfig::new(true).with_seed(3))
+            .simulate();
+        let option = EquityOption::new(0, TypeFlag::Call, 100.0, 2.0, 1.0, 0.25, r, r);
+        let profile = NettingSet::new()
+            .with_instrument(option)
+            .exposure(&scenarios);
+
+        let value = BlackScholesInputs {
+            underlying_price: 100.0,
+            strike_price: 100.0,
+            volatility: 0.25,
+            risk_free_rate: r,
+            cost_of_carry: r,
+            time_to_expiry: 2.0,
+            option_type: TypeFlag::Call,
+        }
+        .price();
+
+        // The discounted option value is a martingale, so the CVA is the
+        // option value times the loss given default and default probability.
+        let counterparty = HazardCurve::flat(0.02);
+        let inputs = XvaInputs::new(r, counterparty.clone(), 0.4)
+            .with_funding(FundingCurve::flat(0.01), FundingCurve::flat(0.01));
+        let xva = profile.valuation_adjustments(&inputs);
+
+        let cva = 0.6 * value * counterparty.default_probability(0.0, 2.0);
+        assert_approx_equal!(xva.cva, cva, 0.02 * cva);
+        assert_eq!(xva.dva, 0.0);
+        assert_eq!(xva.fba, 0.0);
+
+        // FCA = s * V * int_0^2 S_C(t) dt.
+        let fca = 0.01 * value * (1.0 - (-0.04_f64).exp()) / 0.02;
+        assert_approx_equal!(xva.fca, fca, 0.02 * fca);
+        assert_approx_equal!(xva.total(), -xva.cva - xva.fca, 1e-12);
+    }
+
+    #[test]
+    fn test_bilateral_adjustments() {
+        let scenarios = ScenarioGenerator::new(1.0, 20, 10_000)
+            .with_factor("stock", GeometricBrownianMotion::new(0.0, 0.2), 100.0)
+            .with_config(SimulationConfig::new(false).with_seed(5))
+            .simulate();
+        let long = NettingSet::new()
+            .with_instrument(EquityForward::new(0, 100.0, 1.0, 1.0, 0.0, 0.0))
+            .exposure(&scenarios);
+        let short = NettingSet::new()
+            .with_instrument(EquityForward::new(0, 100.0, 1.0, -1.0, 0.0, 0.0))
+            .exposure(&scenarios);
+
+        // Symmetric credit: one side's CVA is the other side's DVA.
+        let curve = HazardCurve::from_cds_spread(0.01, 0.4);
+        let inputs = XvaInputs::new(0.0, curve.clone(), 0.4).with_own_credit(curve, 0.4);
+        let (long, short) = (
+            long.valuation_adjustments(&inputs),
+            short.valuation_adjustments(&inputs),
+        );
+
+        assert_approx_equal!(long.cva, short.dva, 1e-12);
+        assert_approx_equal!(long.dva, short.cva, 1e-12);
+        assert!(long.cva > 0.0 && long.dva > 0.0);
+    }
+}